@@ -120,7 +120,7 @@ CREATE TABLE square_memberships (
   last_charged_at INTEGER NOT NULL,
   last_charged_price_fen INTEGER NOT NULL,
   paid_until INTEGER NOT NULL,
-  subscription_status TEXT NOT NULL CHECK(subscription_status IN ('active', 'cancelled', 'terminated', 'suspended', 'issuerPaused')),
+  subscription_status TEXT NOT NULL CHECK(subscription_status IN ('active', 'cancelled', 'terminated', 'suspended', 'issuerPaused', 'pastDue')),
   finalized_block_number INTEGER NOT NULL,
   finalized_block_hash TEXT NOT NULL,
   verified_at INTEGER NOT NULL,
@@ -166,7 +166,7 @@ CREATE TABLE square_creator_subscriptions (
   last_charged_at INTEGER NOT NULL,
   last_charged_price_fen INTEGER NOT NULL,
   paid_until INTEGER NOT NULL,
-  subscription_status TEXT NOT NULL CHECK(subscription_status IN ('active', 'cancelled', 'terminated', 'suspended', 'issuerPaused')),
+  subscription_status TEXT NOT NULL CHECK(subscription_status IN ('active', 'cancelled', 'terminated', 'suspended', 'issuerPaused', 'pastDue')),
   finalized_block_number INTEGER NOT NULL,
   finalized_block_hash TEXT NOT NULL,
   verified_at INTEGER NOT NULL,
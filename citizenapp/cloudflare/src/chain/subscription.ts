@@ -30,7 +30,8 @@ export type SubscriptionStatus =
   | "cancelled"
   | "terminated"
   | "suspended"
-  | "issuerPaused";
+  | "issuerPaused"
+  | "pastDue";
 
 export type SuspendReason =
   | "needReconsent"
@@ -130,6 +131,7 @@ const STATUS_BY_BYTE: Record<number, SubscriptionStatus> = {
   2: "terminated",
   3: "suspended",
   4: "issuerPaused",
+  5: "pastDue",
 };
 
 function encodeIssuerKey(issuer: SubscriptionIssuer): Uint8Array {
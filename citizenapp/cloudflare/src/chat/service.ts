@@ -50,6 +50,11 @@ interface ConsumeKeyPackageRequest {
   key_package_id?: unknown;
 }
 
+interface RevokeDeviceRequest {
+  device_id?: unknown;
+  revoked_device_id?: unknown;
+}
+
 interface SubmitEnvelopeRequest {
   envelope_id?: unknown;
   sender_device_id?: unknown;
@@ -184,6 +189,52 @@ export async function registerChatDevice(request: Request, env: Env): Promise<Re
   });
 }
 
+/** 列出当前身份的 Chat 设备名册(不含推送 Token),供多设备管理与自家设备邀请。 */
+export async function listChatDevices(request: Request, env: Env): Promise<Response> {
+  const session = await requireSession(request, env);
+  const rows = await env.DB.prepare(
+    `SELECT cid_number, binding_revision, account_id, device_id,
+        device_public_key_hex, expires_at
+      FROM chat_devices
+      WHERE cid_number = ? AND binding_revision = ? AND account_id = ? AND expires_at > ?
+      ORDER BY created_at ASC`,
+  ).bind(
+    session.cid_number,
+    session.binding_revision,
+    session.account_id,
+    nowMs(),
+  ).all<ChatDeviceRow>();
+  return jsonResponse({ ok: true, cid_number: session.cid_number, devices: rows.results ?? [] });
+}
+
+/** 由本人另一台在册设备吊销设备：删除设备行与其未领取的 KeyPackage。 */
+export async function revokeChatDevice(request: Request, env: Env): Promise<Response> {
+  const session = await requireSession(request, env);
+  const body = await readJson<RevokeDeviceRequest>(request);
+  const cidNumber = session.cid_number;
+  const deviceId = assertDeviceId(body.device_id);
+  await requireActiveDevice(
+    env,
+    cidNumber,
+    deviceId,
+    session.binding_revision,
+    session.account_id,
+  );
+  const revokedDeviceId = assertDeviceId(body.revoked_device_id);
+  // 本机退出走注销；吊销只能由另一台设备发起，避免误把自己锁在门外。
+  if (revokedDeviceId === deviceId) {
+    throw new HttpError(400, 'cannot_revoke_current_device', '不能吊销当前设备');
+  }
+  const deleted = await env.DB.prepare(`DELETE FROM chat_devices WHERE cid_number = ? AND device_id = ?`)
+    .bind(cidNumber, revokedDeviceId).run();
+  if ((deleted.meta?.changes ?? 0) !== 1) {
+    throw new HttpError(404, 'chat_device_not_found', 'Chat 设备不存在或已吊销');
+  }
+  await env.DB.prepare(`DELETE FROM chat_keypackages WHERE cid_number = ? AND device_id = ?`)
+    .bind(cidNumber, revokedDeviceId).run();
+  return jsonResponse({ ok: true, cid_number: cidNumber, revoked_device_id: revokedDeviceId });
+}
+
 export async function publishChatKeyPackage(request: Request, env: Env): Promise<Response> {
   const session = await requireSession(request, env);
   const body = await readJson<PublishKeyPackageRequest>(request);
@@ -11,9 +11,11 @@ import { deleteContactRoute, listContactsRoute, putContactRoute } from "./contac
 import {
   consumeChatKeyPackage,
   fetchChatKeyPackages,
+  listChatDevices,
   openChatWebSocket,
   publishChatKeyPackage,
   registerChatDevice,
+  revokeChatDevice,
   submitChatEnvelope,
   submitChatSignal,
 } from "./chat/service";
@@ -216,6 +218,12 @@ export async function routeRequest(
   if (request.method === "POST" && path === "/chat/devices/register") {
     return registerChatDevice(request, env);
   }
+  if (request.method === "GET" && path === "/chat/devices") {
+    return listChatDevices(request, env);
+  }
+  if (request.method === "POST" && path === "/chat/devices/revoke") {
+    return revokeChatDevice(request, env);
+  }
   if (request.method === "POST" && path === "/chat/keypackages") {
     return publishChatKeyPackage(request, env);
   }
@@ -13,7 +13,12 @@ vi.mock('../src/chain/identity', () => ({
 }));
 import { buildChatDeviceBindingMessageBase64Url } from '../src/chat/binding';
 import { assertDevicePublicKeyHex, base64UrlToBytes, bytesToBase64Url } from '../src/chat/codec';
-import { openChatWebSocket, submitChatEnvelope, submitChatSignal } from '../src/chat/service';
+import {
+  openChatWebSocket,
+  revokeChatDevice,
+  submitChatEnvelope,
+  submitChatSignal,
+} from '../src/chat/service';
 import {
   CHAT_WS_PONG_TYPE,
   CHAT_WS_READY_TYPE,
@@ -27,6 +32,9 @@ const ACCOUNT_ID = '0x1111111111111111111111111111111111111111111111111111111111
 const SENDER_CID = 'CN220-CTZN2-198805200-2026';
 const RECIPIENT_CID = 'CN220-CTZN2-199001010-2026';
 
+// 已执行的写语句(SQL + 绑定值),供吊销等写路径断言。
+const executedRuns: Array<{ sql: string; values: unknown[] }> = [];
+
 class ChatStmt {
   private values: unknown[] = [];
   constructor(private readonly sql: string) {}
@@ -52,6 +60,7 @@ class ChatStmt {
     return { results: [] };
   }
   async run(): Promise<{ meta: { changes: number } }> {
+    executedRuns.push({ sql: this.sql, values: this.values });
     return { meta: { changes: 1 } };
   }
 }
@@ -232,4 +241,26 @@ describe('device-only Chat transport', () => {
     // 只路由给收件人身份主键 cid_number 命名的 DO,不落库、不广播其他身份。
     expect(routedName).toBe(RECIPIENT_CID);
   });
+
+  it('revokes another own device with its key packages and refuses the current one', async () => {
+    const revoke = (revokedDeviceId: string) =>
+      revokeChatDevice(
+        new Request('https://worker.test/chat/devices/revoke', {
+          method: 'POST',
+          headers: { authorization: 'Bearer test-session', 'content-type': 'application/json' },
+          body: JSON.stringify({ device_id: 'alice-phone', revoked_device_id: revokedDeviceId }),
+        }),
+        fakeEnv(),
+      );
+    executedRuns.length = 0;
+    const response = await revoke('alice-tablet');
+    expect(await response.json()).toMatchObject({ ok: true, revoked_device_id: 'alice-tablet' });
+    expect(executedRuns.map((run) => run.sql.split(' WHERE')[0])).toEqual([
+      'DELETE FROM chat_devices',
+      'DELETE FROM chat_keypackages',
+    ]);
+    expect(executedRuns.every((run) => run.values[0] === SENDER_CID && run.values[1] === 'alice-tablet')).toBe(true);
+
+    await expect(revoke('alice-phone')).rejects.toMatchObject({ code: 'cannot_revoke_current_device' });
+  });
 });
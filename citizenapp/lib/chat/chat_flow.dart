@@ -51,22 +51,30 @@ typedef ChatMediaDeliveredMarker = Future<void> Function(String attachmentId);
 /// 大媒体(>100MB)经 Cloudflare R2 瞬时中转的上传结果。
 ///
 /// 客户端流式加密后上传密文到 R2,把 R2 对象键 + 一次性内容密钥 + 分块参数
-/// 放进 E2E 控制消息;Cloudflare 只经手密文,拿不到内容密钥。
+/// 放进 E2E 控制消息;Cloudflare 只经手密文,拿不到内容密钥。群聊由 Rust 按
+/// MLS exporter 派生附件密钥加密,此时带 [mlsKeyHex] 与两枚哈希而无 [contentKeyB64]。
 class ChatRelayDescriptor {
   const ChatRelayDescriptor({
     required this.relayObjectKey,
-    required this.contentKeyB64,
+    this.contentKeyB64,
     required this.chunkSize,
     required this.encSize,
+    this.mlsKeyHex,
+    this.contentHashHex,
+    this.ciphertextHashHex,
   });
 
   final String relayObjectKey;
-  final String contentKeyB64;
+  final String? contentKeyB64;
   final int chunkSize;
   final int encSize;
+  final String? mlsKeyHex;
+  final String? contentHashHex;
+  final String? ciphertextHashHex;
 }
 
-/// 把源文件流式加密上传到 Cloudflare R2 瞬时中转,返回描述子。仅 >100MB 走此路。
+/// 把源文件流式加密上传到 Cloudflare R2 瞬时中转,返回描述子。单聊仅 >100MB 走此路,
+/// 群附件一律走此路。
 typedef ChatRelayUploader = Future<ChatRelayDescriptor> Function({
   required String conversationId,
   required String attachmentId,
@@ -28,6 +28,9 @@ class ChatContent {
     this.contentKeyB64,
     this.chunkSize,
     this.encSize,
+    this.mlsKeyHex,
+    this.contentHashHex,
+    this.ciphertextHashHex,
     this.packId,
     this.stickerId,
   });
@@ -52,6 +55,9 @@ class ChatContent {
     String? contentKeyB64,
     int? chunkSize,
     int? encSize,
+    String? mlsKeyHex,
+    String? contentHashHex,
+    String? ciphertextHashHex,
   }) {
     assert(
       kind == ChatMessageKind.image ||
@@ -73,6 +79,9 @@ class ChatContent {
       contentKeyB64: contentKeyB64,
       chunkSize: chunkSize,
       encSize: encSize,
+      mlsKeyHex: mlsKeyHex,
+      contentHashHex: contentHashHex,
+      ciphertextHashHex: ciphertextHashHex,
     );
   }
 
@@ -114,6 +123,13 @@ class ChatContent {
   final int? chunkSize;
   final int? encSize;
 
+  /// 群中转媒体改由 Rust 按 MLS exporter secret 派生的附件密钥加密时携带
+  /// (取代 [contentKeyB64]):附件密钥、明文与密文 SHA-256,均只随 E2E 信封传。
+  /// 收端按此核对密文与解密后的内容,不依赖本机当前 epoch。
+  final String? mlsKeyHex;
+  final String? contentHashHex;
+  final String? ciphertextHashHex;
+
   /// kind=sticker:内置贴纸包与贴纸 id。
   final String? packId;
   final String? stickerId;
@@ -124,9 +140,12 @@ class ChatContent {
       kind == ChatMessageKind.video ||
       kind == ChatMessageKind.file;
 
-  /// 是否为经 Cloudflare R2 中转的大媒体(>100MB,携 relayObjectKey)。
+  /// 是否为经 Cloudflare R2 中转的媒体(单聊 >100MB 或任意群附件,携 relayObjectKey)。
   bool get isRelayMedia => isMedia && (relayObjectKey ?? '').isNotEmpty;
 
+  /// 中转密文是否由 MLS 附件密钥加密(Rust `chat_attachment`)。
+  bool get isMlsSealed => isRelayMedia && (mlsKeyHex ?? '').isNotEmpty;
+
   /// 会话列表 / 通知用的简短摘要。
   String get summary => switch (kind) {
         ChatMessageKind.text => text ?? '',
@@ -171,6 +190,11 @@ class ChatPayloadCodec {
           map['content_key'] = content.contentKeyB64;
           map['chunk_size'] = content.chunkSize;
           map['enc_size'] = content.encSize;
+          if (content.mlsKeyHex != null) {
+            map['mls_key'] = content.mlsKeyHex;
+            map['content_hash'] = content.contentHashHex;
+            map['ciphertext_hash'] = content.ciphertextHashHex;
+          }
         }
       case ChatMessageKind.sticker:
         map['pack_id'] = content.packId;
@@ -213,6 +237,9 @@ class ChatPayloadCodec {
           contentKeyB64: _asString(decoded['content_key']),
           chunkSize: _asInt(decoded['chunk_size']),
           encSize: _asInt(decoded['enc_size']),
+          mlsKeyHex: _asString(decoded['mls_key']),
+          contentHashHex: _asString(decoded['content_hash']),
+          ciphertextHashHex: _asString(decoded['ciphertext_hash']),
         ),
       ChatMessageKind.sticker => ChatContent.sticker(
           packId: _asString(decoded['pack_id']) ?? '',
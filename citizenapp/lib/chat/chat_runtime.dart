@@ -9,7 +9,13 @@ import 'package:firebase_messaging/firebase_messaging.dart';
 import 'package:shared_preferences/shared_preferences.dart';
 
 import '../8964/services/square_api_client.dart';
+import '../citizen/institution/institution_chain_state.dart';
+import '../citizen/institution/institution_repository.dart';
+import '../citizen/proposal/admins-change/services/institution_admin_service.dart';
+import '../log/app_log.dart';
 import '../my/myid/identity_account_cache.dart';
+import '../rpc/chain_event_subscription.dart';
+import '../rpc/citizen_identity_rpc.dart';
 import '../wallet/core/device_subkey.dart';
 import '../security/local_data_key.dart';
 import 'media/attachment_vault.dart';
@@ -24,8 +30,10 @@ import 'chat_media_limits.dart';
 import 'chat_models.dart';
 import 'chat_payload.dart';
 import 'chat_push_service.dart';
+import 'devices/device_history_transfer.dart';
 import 'group/group_flow.dart';
 import 'group/group_model.dart';
+import 'group/institution_group.dart';
 import 'media/chat_relay_media.dart';
 import 'media/media_resend.dart';
 import 'proto/chat_envelope.pb.dart';
@@ -46,6 +54,12 @@ typedef ChatDeviceBindingSigner = Future<String> Function({
   required Uint8List bindingMessage,
 });
 
+/// CID 绑定账户(sr25519)对 MLS 设备授权摘要签名,返回 64 字节签名。
+typedef ChatMlsDeviceBindingSigner = Future<List<int>> Function({
+  required String accountId,
+  required Uint8List signingMessage,
+});
+
 typedef ChatCloudTransportFactory = ChatCloudTransport Function({
   required String accountId,
   required String localDeviceId,
@@ -161,6 +175,7 @@ class ChatRuntime {
     SquareApiClient? squareApiClient,
     ChatLoginSigner? loginSigner,
     ChatDeviceBindingSigner? deviceBindingSigner,
+    ChatMlsDeviceBindingSigner? mlsDeviceBindingSigner,
     DeviceSubkey? deviceSubkey,
     MlsStateStoreFactory? stateStoreFactory,
     MlsCrypto Function(
@@ -179,6 +194,7 @@ class ChatRuntime {
         _squareApiClient = squareApiClient ?? SquareApiClient(),
         _loginSigner = loginSigner,
         _deviceBindingSigner = deviceBindingSigner,
+        _mlsDeviceBindingSigner = mlsDeviceBindingSigner,
         _deviceSubkey = deviceSubkey ?? DeviceSubkey(),
         _stateStoreFactory = stateStoreFactory,
         _cryptoFactory = cryptoFactory,
@@ -191,10 +207,14 @@ class ChatRuntime {
   static const _kDeviceId = 'chat.device.id';
   static const _kDevicePublicKeyHex = 'chat.device.public_key_hex';
   static const _kDeviceBindingPrefix = 'chat.cloudflare.device_binding';
+  static const _kMlsAttestationPrefix = 'chat.mls.device_attestation';
   static const _kPushTokenPrefix = 'chat.push.token';
   static const _kKeyPackagePublishedPrefix =
       'chat.cloudflare.key_package_until';
   static const _deviceBindingTtl = Duration(days: 90);
+
+  /// finalized 块触发机构群同步的最小间隔(任职按天到期,不必每块都读名册)。
+  static const _institutionSyncMinInterval = Duration(minutes: 10);
   static const _keyPackageRefreshSkewMillis = 24 * 60 * 60 * 1000;
   static const _sessionRefreshSkewMillis = 60 * 1000;
 
@@ -210,6 +230,7 @@ class ChatRuntime {
   final SquareApiClient _squareApiClient;
   final ChatLoginSigner? _loginSigner;
   final ChatDeviceBindingSigner? _deviceBindingSigner;
+  final ChatMlsDeviceBindingSigner? _mlsDeviceBindingSigner;
   final DeviceSubkey _deviceSubkey;
   final MlsStateStoreFactory? _stateStoreFactory;
   final MlsCrypto Function(
@@ -225,6 +246,14 @@ class ChatRuntime {
   /// peer_ready 触发的补发不得对在途媒体再整块重传。
   final Set<String> _mediaBytesInFlight = {};
 
+  /// 机构群名册同步串行:进行中再被触发只记一次补跑。
+  bool _institutionSyncRunning = false;
+  bool _institutionSyncQueued = false;
+  DateTime? _lastInstitutionSyncAt;
+
+  /// 本人老设备回传的历史迁移包分片重组。
+  final HistoryBundleAssembler _historyAssembler = HistoryBundleAssembler();
+
   /// 同一账户/设备只允许一条初始化链。成功上下文复用到 session 临近过期；
   /// 失败只释放命中的 future，不得误删后来创建的新初始化。
   final Map<String, Future<_ChatAccountContext>> _readyFlights = {};
@@ -758,6 +787,34 @@ class ChatRuntime {
     };
   }
 
+  /// 群聊中转上传 seam:一律由 Rust 按群 exporter secret 派生附件密钥封装后上传;
+  /// OpenMLS native 不可用时拒发,不退回一次性内容密钥或逐成员直传。
+  ChatRelayUploader _groupRelayUploader(_ChatAccountContext context) {
+    final crypto = context.crypto;
+    return ({
+      required conversationId,
+      required attachmentId,
+      required media,
+      int recipientCount = 1,
+    }) async {
+      if (crypto is! NativeMlsCrypto) {
+        throw StateError('群附件须经 OpenMLS 封装,本机 MLS 不可用');
+      }
+      return ChatRelayMedia.uploadMlsSealed(
+        transport: context.transport,
+        byteSize: media.byteSize,
+        recipientCount: recipientCount,
+        tempDirectory: Directory('${(await _attachmentDirectory()).path}/.tmp'),
+        seal: (outputPath) => crypto.sealAttachment(
+          groupId: conversationId,
+          attachmentId: attachmentId,
+          sourcePath: media.sourcePath,
+          outputPath: outputPath,
+        ),
+      );
+    };
+  }
+
   /// 发送内置贴纸:只走控制信封,不经 WebRTC。首次会话缺 KeyPackage 时同样
   /// 领取后重试。
   Future<List<ChatDeliveryResult>> sendSticker({
@@ -813,13 +870,116 @@ class ChatRuntime {
     final context = await _readyContext(await _readAccount());
     final invitees = await _fetchInviteeKeyPackages(context, inviteeCidNumbers);
     final groupId = newGroupId(context.account.cidNumber);
-    return _groupFlow(context).createGroup(
+    final flow = _groupFlow(context);
+    await flow.createGroup(
       groupId: groupId,
       name: name,
       cidNumber: context.account.cidNumber,
       localDeviceId: context.deviceId,
       invitees: invitees,
     );
+    await _addOwnDevicesTo(context, flow, groupId);
+    return (await _store.readGroup(context.account.cidNumber, groupId))!;
+  }
+
+  /// 建机构岗位群(仅机构管理员):名册取链上该岗位有效任职者,逐个领 KeyPackage 入群。
+  Future<ChatGroup> createInstitutionGroup({
+    required String institutionCidNumber,
+    required String scope,
+    required String name,
+  }) async {
+    final context = await _readyContext(await _readAccount());
+    final roster = await _readInstitutionRoster(institutionCidNumber, scope);
+    final selfCidNumber = context.account.cidNumber;
+    if (!roster.isAdmin(selfCidNumber)) {
+      throw StateError('只有机构管理员可以建立机构群');
+    }
+    final invitees = await _fetchInviteeKeyPackages(
+      context,
+      roster.memberCidNumbers
+          .where((cidNumber) => cidNumber != selfCidNumber)
+          .toList(),
+    );
+    return _groupFlow(context).createGroup(
+      groupId: institutionGroupId(institutionCidNumber, scope),
+      name: name,
+      cidNumber: selfCidNumber,
+      localDeviceId: context.deviceId,
+      invitees: invitees,
+    );
+  }
+
+  /// 本机作为机构管理员,把机构群名册对齐链上任职(卸任移除、新任补加)。
+  ///
+  /// [institutionCidNumber] 非空时只同步该机构的群。单群失败不影响其余群,失败逐条
+  /// 记日志并返回给调用方;新任者暂无 KeyPackage 时跳过,下次同步再补。
+  Future<List<GroupSyncFailure>> syncInstitutionGroups({
+    String? institutionCidNumber,
+  }) async {
+    final context = await _readyContext(await _readAccount());
+    final flow = _groupFlow(context);
+    final groups = await _store.readGroups(context.account.cidNumber);
+    final failures = <GroupSyncFailure>[];
+    for (final group in groups) {
+      final institution = parseInstitutionGroupId(group.groupId);
+      if (institution == null || group.leftLocally) {
+        continue;
+      }
+      if (institutionCidNumber != null &&
+          institution.institutionCidNumber != institutionCidNumber) {
+        continue;
+      }
+      if (!group.adminSet.contains(context.account.cidNumber)) {
+        continue;
+      }
+      try {
+        await flow.syncInstitutionGroup(
+          groupId: group.groupId,
+          fetchKeyPackages: (cidNumbers) =>
+              _fetchAvailableKeyPackages(context, cidNumbers),
+        );
+      } catch (error) {
+        AppLog.d('[ChatRuntime] 机构群 ${group.groupId} 名册同步失败: $error');
+        failures.add(
+          GroupSyncFailure(groupId: group.groupId, error: error),
+        );
+      }
+    }
+    return failures;
+  }
+
+  /// 后台触发机构群同步(任职刷新 / finalized 新块),串行执行,失败只记日志。
+  ///
+  /// [force] 为 false 时(链事件触发)与上次同步间隔不足 [_institutionSyncMinInterval]
+  /// 则跳过;任职刷新强制同步。
+  Future<void> _syncInstitutionGroupsInBackground({bool force = false}) async {
+    final last = _lastInstitutionSyncAt;
+    if (!force &&
+        last != null &&
+        DateTime.now().difference(last) < _institutionSyncMinInterval) {
+      return;
+    }
+    if (_institutionSyncRunning) {
+      _institutionSyncQueued = true;
+      return;
+    }
+    _institutionSyncRunning = true;
+    try {
+      do {
+        _institutionSyncQueued = false;
+        _lastInstitutionSyncAt = DateTime.now();
+        try {
+          final failures = await syncInstitutionGroups();
+          if (failures.isNotEmpty) {
+            AppLog.d('[ChatRuntime] 机构群同步有 ${failures.length} 个群失败,下次触发重试');
+          }
+        } catch (error) {
+          AppLog.d('[ChatRuntime] 机构群同步失败: $error');
+        }
+      } while (_institutionSyncQueued);
+    } finally {
+      _institutionSyncRunning = false;
+    }
   }
 
   /// 加人(仅 admin)。
@@ -896,8 +1056,8 @@ class ChatRuntime {
     );
   }
 
-  /// 群发媒体:≤100MB 对每个成员 WebRTC 直传(离线按成员补发);>100MB 走已部署中转
-  /// (一次上传 + K 扇 N,仅薪火可发/可收)。四门按己档强制。
+  /// 群发媒体:不论大小一律 MLS 封装后经已部署中转一次上传 + 控制消息扇 N,
+  /// 不走逐成员 WebRTC 直传。四门按己档强制。
   Future<List<ChatDeliveryResult>> sendGroupMedia({
     required String groupId,
     required ChatMediaDraft media,
@@ -908,29 +1068,203 @@ class ChatRuntime {
       senderCidNumber: context.account.cidNumber,
       senderDeviceId: context.deviceId,
       media: media,
-      sendMemberAttachment: _guardedDeviceSender(context),
-      uploadRelayMedia: _relayUploader(context),
+      uploadRelayMedia: _groupRelayUploader(context),
       saveLocalAttachment: _copySentAttachmentToCache,
-      recordPendingMember: (attachmentId, memberCidNumber) =>
-          _store.recordOutgoingMedia(
-        ownerCidNumber: context.account.cidNumber,
-        attachmentId: attachmentId,
-        recipientCidNumber: memberCidNumber,
-        conversationId: groupId,
-        fileName: media.fileName,
-        contentType: media.contentType,
-        byteSize: media.byteSize,
-      ),
-      markMemberDelivered: (attachmentId, memberCidNumber) =>
-          _store.deleteOutgoingMedia(
-        context.account.cidNumber,
-        attachmentId,
-        memberCidNumber,
+    );
+  }
+
+  // ==== 本人多设备 ====
+
+  /// 本身份在册的 Chat 设备(含本机)与本机设备 ID。
+  Future<({String currentDeviceId, List<String> deviceIds})>
+      listOwnDevices() async {
+    final context = await _readyContext(await _readAccount());
+    return (
+      currentDeviceId: context.deviceId,
+      deviceIds: await context.transport.listDevices(),
+    );
+  }
+
+  /// 把本人其他设备补进所有在群的群(新设备上线后由任一老设备执行)。
+  ///
+  /// 单群失败不影响其余群,逐条记日志并返回;设备暂无 KeyPackage 时跳过,下次同步再补。
+  Future<List<GroupSyncFailure>> syncOwnDevices() async {
+    final context = await _readyContext(await _readAccount());
+    final flow = _groupFlow(context);
+    final groups = await _store.readGroups(context.account.cidNumber);
+    final failures = <GroupSyncFailure>[];
+    for (final group in groups) {
+      if (group.leftLocally) {
+        continue;
+      }
+      try {
+        await _addOwnDevicesTo(context, flow, group.groupId);
+      } catch (error) {
+        AppLog.d('[ChatRuntime] 群 ${group.groupId} 补本人设备失败: $error');
+        failures.add(GroupSyncFailure(groupId: group.groupId, error: error));
+      }
+    }
+    return failures;
+  }
+
+  /// 吊销本人另一台设备:先在 Worker 除名(停止投递),再逐群移除其叶子重钥。
+  ///
+  /// 返回未能移除该设备叶子的群;这些群里被吊销设备仍持有群密钥,调用方须提示
+  /// 用户重试(重复吊销幂等)。
+  Future<List<GroupSyncFailure>> revokeOwnDevice(String deviceId) async {
+    final context = await _readyContext(await _readAccount());
+    if (deviceId == context.deviceId) {
+      throw StateError('不能吊销当前设备');
+    }
+    await context.transport.revokeDevice(deviceId);
+    HistoryRequestInbox.instance.dropDevice(deviceId);
+    final flow = _groupFlow(context);
+    final groups = await _store.readGroups(context.account.cidNumber);
+    final failures = <GroupSyncFailure>[];
+    for (final group in groups) {
+      if (group.leftLocally) {
+        continue;
+      }
+      try {
+        await flow.removeOwnDevice(groupId: group.groupId, deviceId: deviceId);
+      } catch (error) {
+        AppLog.d('[ChatRuntime] 群 ${group.groupId} 移除设备 $deviceId 失败: $error');
+        failures.add(GroupSyncFailure(groupId: group.groupId, error: error));
+      }
+    }
+    return failures;
+  }
+
+  /// 新设备向本人其他设备请求历史迁移;老设备在线时经信令回传迁移包。
+  Future<void> requestHistoryFromOwnDevices() async {
+    final context = await _readyContext(await _readAccount());
+    final crypto = context.crypto;
+    if (crypto is! NativeMlsCrypto) {
+      throw StateError('历史迁移需要 OpenMLS native');
+    }
+    final request = await crypto.requestHistoryTransfer();
+    await context.transport.sendSignal(
+      recipientCidNumber: context.account.cidNumber,
+      signal: {'kind': kHistoryTransferRequestKind, 'request': request},
+    );
+  }
+
+  /// 老设备收到本人新设备的历史迁移请求:只入待确认箱,不自动导出。
+  void _holdHistoryRequest(
+    _ChatAccountContext context,
+    Map<String, dynamic> request,
+  ) {
+    final requesterDeviceId = request['device_id'];
+    final createdAtMillis = request['created_at_ms'];
+    if (context.crypto is! NativeMlsCrypto ||
+        requesterDeviceId is! String ||
+        requesterDeviceId.isEmpty ||
+        requesterDeviceId == context.deviceId ||
+        createdAtMillis is! int) {
+      return;
+    }
+    HistoryRequestInbox.instance.hold(
+      PendingHistoryRequest(
+        deviceId: requesterDeviceId,
+        createdAtMillis: createdAtMillis,
+        request: request,
       ),
     );
   }
 
-  /// 逐个被邀请 CID 领取一枚 KeyPackage（复用 1:1 fetch/consume）。
+  /// 用户在本机同意某条历史迁移请求后导出:Rust 核对请求方是本 CID 链上授权设备
+  /// 并封装 → 分片定向回传。请求已过期或不在待确认箱时抛错。
+  Future<void> approveHistoryRequest(String requestId) async {
+    final pending = HistoryRequestInbox.instance.take(requestId);
+    if (pending == null) {
+      throw StateError('迁移请求已处理或已过期');
+    }
+    final context = await _readyContext(await _readAccount());
+    final crypto = context.crypto;
+    if (crypto is! NativeMlsCrypto) {
+      throw StateError('历史迁移需要 OpenMLS native');
+    }
+    final request = pending.request;
+    final requesterDeviceId = pending.deviceId;
+    final records = await _store.exportHistory(
+      ownerCidNumber: context.account.cidNumber,
+      currentAccountId: context.account.accountId,
+    );
+    final bundle = await crypto.sealHistoryTransfer(
+      request: request,
+      history: ChatHistoryCodec.encode(records),
+    );
+    final chunks = splitHistoryBundle(
+      transferId: '${context.deviceId}-${_newNonce()}',
+      bundle: bundle,
+    );
+    for (final chunk in chunks) {
+      await context.transport.sendSignal(
+        recipientCidNumber: context.account.cidNumber,
+        recipientDeviceId: requesterDeviceId,
+        signal: chunk,
+      );
+    }
+  }
+
+  /// 用户拒绝某条历史迁移请求:直接丢弃,不回任何信令。
+  void rejectHistoryRequest(String requestId) {
+    HistoryRequestInbox.instance.take(requestId);
+  }
+
+  /// 新设备集齐迁移包后解封并导入;返回是否有新记录落库。
+  Future<bool> _acceptHistoryChunk(
+    _ChatAccountContext context,
+    Map<String, dynamic> signal,
+  ) async {
+    final crypto = context.crypto;
+    final bundle = _historyAssembler.add(signal);
+    if (bundle == null || crypto is! NativeMlsCrypto) {
+      return false;
+    }
+    final opened = await crypto.openHistoryTransfer(bundle);
+    final imported = await _store.importHistory(
+      ownerCidNumber: context.account.cidNumber,
+      currentAccountId: context.account.accountId,
+      records: ChatHistoryCodec.decode(opened.history),
+    );
+    return imported > 0;
+  }
+
+  /// 领取本人其他设备各一枚 KeyPackage 并加进群(已在群的设备由 flow 跳过)。
+  Future<void> _addOwnDevicesTo(
+    _ChatAccountContext context,
+    ChatGroupFlow flow,
+    String groupId,
+  ) async {
+    final state = await (context.crypto as MlsGroupCrypto).groupState(groupId);
+    final present = state.memberIdentities.toSet();
+    final available = await context.transport.fetchKeyPackages(
+      targetCidNumber: context.account.cidNumber,
+      limit: 20,
+    );
+    final byDevice = <String, MlsKeyPackage>{};
+    for (final keyPackage in available) {
+      if (keyPackage.deviceId == context.deviceId ||
+          present.contains('${keyPackage.cidNumber}:${keyPackage.deviceId}')) {
+        continue;
+      }
+      byDevice.putIfAbsent(keyPackage.deviceId, () => keyPackage);
+    }
+    final packages = <MlsKeyPackage>[];
+    for (final keyPackage in byDevice.values) {
+      packages.add(await context.transport.consumeKeyPackage(
+        targetCidNumber: context.account.cidNumber,
+        keyPackageId: keyPackage.keyPackageId,
+      ));
+    }
+    if (packages.isNotEmpty) {
+      await flow.addOwnDevices(groupId: groupId, keyPackages: packages);
+    }
+  }
+
+  /// 逐个被邀请 CID 按设备各领取一枚 KeyPackage(复用 1:1 fetch/consume),
+  /// 对方的手机、平板等设备一并入群。
   Future<List<MlsKeyPackage>> _fetchInviteeKeyPackages(
     _ChatAccountContext context,
     List<String> inviteeCidNumbers,
@@ -939,18 +1273,47 @@ class ChatRuntime {
     for (final cidNumber in inviteeCidNumbers) {
       final available = await context.transport.fetchKeyPackages(
         targetCidNumber: cidNumber,
+        limit: 20,
       );
       if (available.isEmpty) {
         throw StateError('对方 $cidNumber 没有可用 Chat KeyPackage');
       }
-      final consumed = await context.transport.consumeKeyPackage(
+      final byDevice = <String, MlsKeyPackage>{};
+      for (final keyPackage in available) {
+        byDevice.putIfAbsent(keyPackage.deviceId, () => keyPackage);
+      }
+      for (final keyPackage in byDevice.values) {
+        final consumed = await context.transport.consumeKeyPackage(
+          targetCidNumber: cidNumber,
+          keyPackageId: keyPackage.keyPackageId,
+        );
+        if (consumed.cidNumber != cidNumber) {
+          throw StateError('Worker 返回的 KeyPackage CID 与请求目标不一致');
+        }
+        packages.add(consumed);
+      }
+    }
+    return packages;
+  }
+
+  /// 同 [_fetchInviteeKeyPackages],但跳过暂无 KeyPackage 的 CID(机构群同步用)。
+  /// 其余错误(Worker 返回不符的 KeyPackage 等)照常抛出。
+  Future<List<MlsKeyPackage>> _fetchAvailableKeyPackages(
+    _ChatAccountContext context,
+    List<String> cidNumbers,
+  ) async {
+    final packages = <MlsKeyPackage>[];
+    for (final cidNumber in cidNumbers) {
+      final available = await context.transport.fetchKeyPackages(
         targetCidNumber: cidNumber,
-        keyPackageId: available.first.keyPackageId,
+        limit: 1,
       );
-      if (consumed.cidNumber != cidNumber) {
-        throw StateError('Worker 返回的 KeyPackage CID 与请求目标不一致');
+      if (available.isEmpty) {
+        // 新任者尚未上线发布 KeyPackage,下次同步再补。
+        AppLog.d('[ChatRuntime] $cidNumber 暂无 Chat KeyPackage,机构群待下次同步补加');
+        continue;
       }
-      packages.add(consumed);
+      packages.addAll(await _fetchInviteeKeyPackages(context, [cidNumber]));
     }
     return packages;
   }
@@ -963,6 +1326,7 @@ class ChatRuntime {
       cidNumber: context.account.cidNumber,
       currentAccountId: context.account.accountId,
       localDeviceId: context.deviceId,
+      institutionRosterReader: _readInstitutionRoster,
       deliverer: (envelope, _, recipientCidNumber) {
         return ChatFlow.deliverWithTransport(
           transport: context.transport,
@@ -1018,7 +1382,7 @@ class ChatRuntime {
     );
   }
 
-  /// >100MB 中转媒体的接收:门②(超本机会员档则拒收,非薪火收 >100MB 一律拒)→
+  /// 中转媒体(单聊 >100MB / 群附件)的接收:门②(超本机会员档则拒收,非薪火收 >100MB 一律拒)→
   /// 命中缓存直接返回 → 否则换 URL 流式下载密文、解密落缓存。
   Future<ChatDownloadedAttachment> _downloadRelayAttachment(
     String conversationId,
@@ -1056,13 +1420,35 @@ class ChatRuntime {
       fileName: fileName,
     );
     await File(destPath).parent.create(recursive: true);
-    await ChatRelayMedia.download(
-      transport: context.transport,
-      relayObjectKey: content.relayObjectKey ?? '',
-      contentKeyB64: content.contentKeyB64 ?? '',
-      destPath: destPath,
-      tempDirectory: Directory('${cacheDirectory.path}/.tmp'),
-    );
+    final crypto = context.crypto;
+    if (content.isMlsSealed) {
+      if (crypto is! NativeMlsCrypto) {
+        throw StateError('MLS 附件解密需要 OpenMLS native');
+      }
+      await ChatRelayMedia.downloadMlsSealed(
+        transport: context.transport,
+        relayObjectKey: content.relayObjectKey ?? '',
+        tempDirectory: Directory('${cacheDirectory.path}/.tmp'),
+        open: (encPath) => crypto.openAttachment(
+          attachmentId: attachmentId,
+          keyHex: content.mlsKeyHex ?? '',
+          chunkSize: content.chunkSize ?? 0,
+          contentHashHex: content.contentHashHex ?? '',
+          ciphertextHashHex: content.ciphertextHashHex,
+          encryptedSize: content.encSize,
+          sourcePath: encPath,
+          outputPath: destPath,
+        ),
+      );
+    } else {
+      await ChatRelayMedia.download(
+        transport: context.transport,
+        relayObjectKey: content.relayObjectKey ?? '',
+        contentKeyB64: content.contentKeyB64 ?? '',
+        destPath: destPath,
+        tempDirectory: Directory('${cacheDirectory.path}/.tmp'),
+      );
+    }
     return ChatDownloadedAttachment(
       attachmentId: attachmentId,
       fileName: fileName,
@@ -1221,7 +1607,7 @@ class ChatRuntime {
           if (encoded is! String || encoded.isEmpty) return;
           final bytes = _base64UrlDecode(encoded);
           final conversationId = _peekConversationId(bytes);
-          if (conversationId != null && conversationId.startsWith('grp:')) {
+          if (conversationId != null && isGroupConversationId(conversationId)) {
             await _groupFlow(context).processIncomingGroupEnvelope(bytes);
           } else {
             await _messageFlow(context).processIncomingEnvelopeBytes(bytes);
@@ -1236,8 +1622,19 @@ class ChatRuntime {
           if (senderCidNumber is! String || signal is! Map<String, dynamic>) {
             return;
           }
+          final ownSignal = senderCidNumber == context.account.cidNumber;
           if (signal['kind'] == 'peer_ready') {
             await retryOutgoing(recipientCidNumber: senderCidNumber);
+          } else if (signal['kind'] == kHistoryTransferRequestKind) {
+            final request = signal['request'];
+            if (ownSignal && request is Map<String, dynamic>) {
+              _holdHistoryRequest(context, request);
+              await onNotice();
+            }
+          } else if (signal['kind'] == kHistoryTransferChunkKind) {
+            if (ownSignal && await _acceptHistoryChunk(context, signal)) {
+              await onNotice();
+            }
           } else {
             await context.webrtc.handleSignal(senderCidNumber, signal);
           }
@@ -1265,10 +1662,30 @@ class ChatRuntime {
     final tokenSubscription = _pushService.tokenChanges.listen(
       (_) => unawaited(_refreshPushRegistration(context)),
     );
+    // 机构群名册跟随链上任职:任何页面刷新到任职即对齐;卸任/到期靠 finalized 块节流兜底。
+    final assignmentSubscription =
+        InstitutionAdminService.assignmentRefreshes.listen(
+      (_) => unawaited(_syncInstitutionGroupsInBackground(force: true)),
+    );
+    final chainEvents = ChainEventSubscription();
+    final chainEventSubscription = chainEvents.events.listen((event) {
+      if (event.type == ChainEventType.newFinalizedBlock) {
+        unawaited(_syncInstitutionGroupsInBackground());
+      }
+    });
+    unawaited(chainEvents.connect().then((connected) {
+      if (!connected) {
+        AppLog.d('[ChatRuntime] 链事件订阅连接失败,机构群仅随任职刷新同步');
+      }
+    }));
+    unawaited(_syncInstitutionGroupsInBackground(force: true));
     await retryOutgoing();
     return () async {
       await pushSubscription.cancel();
       await tokenSubscription.cancel();
+      await assignmentSubscription.cancel();
+      await chainEventSubscription.cancel();
+      chainEvents.disconnect();
       await stopSocket();
     };
   }
@@ -1329,6 +1746,51 @@ class ChatRuntime {
     return created;
   }
 
+  /// Chat MLS 群成员授权校验所需的链上 CID 绑定快照(finalized)。
+  static Future<List<ChatChainBinding>> _readChainBindings(
+    List<String> cidNumbers,
+  ) async {
+    final rows =
+        await CitizenIdentityRpc().fetchFinalizedCidBindings(cidNumbers);
+    return [
+      for (final row in rows)
+        ChatChainBinding(
+          cidNumber: row.cidNumber,
+          accountIdHex: row.accountIdHex,
+          bindingRevision: row.bindingRevision.toInt(),
+        ),
+    ];
+  }
+
+  /// 机构群名册:finalized 状态的管理员集合 + 各自有效岗位任职。
+  static Future<InstitutionRoster> _readInstitutionRoster(
+    String institutionCidNumber,
+    String scope,
+  ) async {
+    final institution =
+        await InstitutionRepository().getByCid(institutionCidNumber);
+    if (institution == null) {
+      throw StateError('未知机构 $institutionCidNumber');
+    }
+    final views = await InstitutionAdminService().fetchAdminViews(
+      adminIdentityOf(institution),
+      institutionCidNumber,
+      announce: false,
+    );
+    return InstitutionRoster.fromAdmins(
+      institutionCidNumber: institutionCidNumber,
+      scope: scope,
+      admins: [
+        for (final view in views)
+          (
+            cidNumber: view.admin.cid_number,
+            roleCodes:
+                view.assignments.map((assignment) => assignment.roleCode),
+          ),
+      ],
+    );
+  }
+
   Future<_ChatAccountContext> _buildAccountContext(_ChatAccount account) async {
     final prefs = await _prefs;
     var deviceId = prefs.getString(_kDeviceId);
@@ -1353,7 +1815,12 @@ class ChatRuntime {
         devicePublicKey: devicePublicKey.isEmpty ? '00' : devicePublicKey,
       );
       final crypto = _cryptoFactory?.call(identity, stateStore) ??
-          NativeMlsCrypto(identity: identity, stateStore: stateStore);
+          NativeMlsCrypto(
+            identity: identity,
+            stateStore: stateStore,
+            chainBindingReader: _readChainBindings,
+            institutionRosterReader: _readInstitutionRoster,
+          );
       MlsKeyPackage? freshKeyPackage;
       if (devicePublicKey.isEmpty) {
         freshKeyPackage = await crypto.createKeyPackage(identity);
@@ -1370,7 +1837,22 @@ class ChatRuntime {
         );
       }
       final finalCrypto = _cryptoFactory?.call(identity, stateStore) ??
-          NativeMlsCrypto(identity: identity, stateStore: stateStore);
+          NativeMlsCrypto(
+            identity: identity,
+            stateStore: stateStore,
+            chainBindingReader: _readChainBindings,
+            institutionRosterReader: _readInstitutionRoster,
+          );
+      if (finalCrypto is MlsDeviceBindingCrypto &&
+          await _ensureMlsDeviceAttested(
+            account: account,
+            identity: identity,
+            crypto: finalCrypto as MlsDeviceBindingCrypto,
+            prefs: prefs,
+          )) {
+        // 授权前生成的 KeyPackage 凭证不带证明，对端入群校验必拒，改发新包。
+        freshKeyPackage = null;
+      }
       final service = await _ensureServiceReady(
         account: account,
         identity: identity,
@@ -1516,6 +1998,59 @@ class ChatRuntime {
     await prefs.setString(pushCacheKey, pushToken.token);
   }
 
+  /// 本机 MLS 签名公钥的 CID 绑定账户授权(写入 native 设备记录)。
+  ///
+  /// 授权未到刷新窗口则跳过;新装授权后作废 KeyPackage 发布缓存，让对端拿到
+  /// 携带证明的新包。返回本次是否新装了授权。
+  Future<bool> _ensureMlsDeviceAttested({
+    required _ChatAccount account,
+    required ChatDevice identity,
+    required MlsDeviceBindingCrypto crypto,
+    required SharedPreferences prefs,
+  }) async {
+    final cacheKey = _mlsAttestationCacheKey(account, identity);
+    final cachedExpiresAt = prefs.getInt(cacheKey) ?? 0;
+    final now = DateTime.now().millisecondsSinceEpoch;
+    if (cachedExpiresAt - _keyPackageRefreshSkewMillis > now) {
+      return false;
+    }
+    final expiresAt = DateTime.now().toUtc().add(_deviceBindingTtl);
+    final attestedPublicKey = await attestMlsDevice(
+      crypto: crypto,
+      accountIdHex: account.accountId,
+      bindingRevision: account.bindingRevision,
+      expiresAt: expiresAt,
+      nonce: _newNonce(),
+      sign: (signingMessage) => _signMlsDeviceBinding(
+        account: account,
+        signingMessage: Uint8List.fromList(signingMessage),
+      ),
+    );
+    if (attestedPublicKey.toLowerCase() !=
+        identity.devicePublicKey.toLowerCase()) {
+      throw StateError('MLS 设备授权公钥与本机 Chat 身份不一致');
+    }
+    await prefs.setInt(cacheKey, expiresAt.millisecondsSinceEpoch);
+    await prefs.remove(_keyPackageCacheKey(account, identity));
+    return true;
+  }
+
+  Future<List<int>> _signMlsDeviceBinding({
+    required _ChatAccount account,
+    required Uint8List signingMessage,
+  }) {
+    final signer = _mlsDeviceBindingSigner;
+    if (signer != null) {
+      return signer(
+        accountId: account.accountId,
+        signingMessage: signingMessage,
+      );
+    }
+    // 授权对象是 MLS 凭证，须由 CID 绑定账户 sr25519 签名(触发一次生物识别);
+    // P-256 设备子钥不被群成员认可。
+    return _walletManager.signForAccountId(account.accountId, signingMessage);
+  }
+
   Future<ChatPushToken> _readPushToken() {
     return _pushTokenProvider?.call() ?? _pushService.initialize();
   }
@@ -1727,6 +2262,13 @@ String _deviceBindingCacheKey(_ChatAccount account, ChatDevice identity) {
       '${_safePath(identity.deviceId)}.${identity.devicePublicKey}';
 }
 
+String _mlsAttestationCacheKey(_ChatAccount account, ChatDevice identity) {
+  return '${ChatRuntime._kMlsAttestationPrefix}.'
+      '${_safePath(account.cidNumber)}.${account.bindingRevision}.'
+      '${_safePath(account.accountId)}.'
+      '${_safePath(identity.deviceId)}.${identity.devicePublicKey}';
+}
+
 String _keyPackageCacheKey(_ChatAccount account, ChatDevice identity) {
   return '${ChatRuntime._kKeyPackagePublishedPrefix}.'
       '${_safePath(account.cidNumber)}.${account.bindingRevision}.'
@@ -1745,7 +2287,7 @@ List<int> _base64UrlDecode(String value) {
   return base64Url.decode(normalized);
 }
 
-/// 只读取 envelope 的 conversation_id 以决定路由(群 `grp:` vs 私聊 `dm:`);
+/// 只读取 envelope 的 conversation_id 以决定路由(群 `grp:` / 机构群 `inst:` vs 私聊 `dm:`);
 /// 解析失败返回 null,交由原私聊路径兜底。
 String? _peekConversationId(List<int> envelopeBytes) {
   try {
@@ -37,6 +37,10 @@ enum GroupProcessStatus {
   applied('applied'),
   outOfOrder('out_of_order'),
   stale('stale'),
+
+  /// Commit 新增叶子未获 CID 链上绑定授权,或机构群握手不合名册(提交方非机构
+  /// 管理员 / 新人不在岗位名册),Rust 未落盘(群状态停在原 epoch)。
+  rejected('rejected'),
   unknown('unknown');
 
   const GroupProcessStatus(this.wireName);
@@ -113,6 +117,7 @@ class GroupInbound {
     required this.selfRemoved,
     this.plaintext,
     this.memberIdentities,
+    this.staleMemberIdentities = const [],
   });
 
   final String groupId;
@@ -130,11 +135,38 @@ class GroupInbound {
   /// 应用 Commit / 入群 Welcome 后的 MLS 权威名册(标识,含设备段)。
   final List<String>? memberIdentities;
 
+  /// 名册中链上授权已失效(CID 换绑/注销/无证明)的叶子标识,待 admin 驱逐。
+  final List<String> staleMemberIdentities;
+
   bool get isApplied => status == GroupProcessStatus.applied;
 
   bool get isOutOfOrder => status == GroupProcessStatus.outOfOrder;
 }
 
+/// 机构群按链上名册同步的结果。
+///
+/// [commit] 为移除卸任成员的 Commit(无需移除时为 null),发给同步前全体成员;
+/// [missingCidNumbers] 为名册内尚未入群的 CID,由上层领 KeyPackage 后加人。
+class InstitutionSyncBundle {
+  const InstitutionSyncBundle({
+    required this.groupId,
+    required this.epoch,
+    this.commit,
+    this.removedCidNumbers = const [],
+    this.missingCidNumbers = const [],
+    this.selfExtraneous = false,
+  });
+
+  final String groupId;
+  final int epoch;
+  final MlsWireMessage? commit;
+  final List<String> removedCidNumbers;
+  final List<String> missingCidNumbers;
+
+  /// 本机 CID 已不在名册内(卸任),应自行退群。
+  final bool selfExtraneous;
+}
+
 /// 只读群状态(名册对账 + 上限守)。
 class GroupState {
   const GroupState({
@@ -150,6 +182,80 @@ class GroupState {
   int get memberCount => memberIdentities.length;
 }
 
+/// 单个 CID 的链上绑定快照(轻节点 finalized `AccountIdByCid` +
+/// `BindingRevisionByCid`)。[accountIdHex] 为空表示该 CID 已无绑定账户。
+class ChatChainBinding {
+  const ChatChainBinding({
+    required this.cidNumber,
+    required this.accountIdHex,
+    required this.bindingRevision,
+  });
+
+  final String cidNumber;
+  final String? accountIdHex;
+  final int bindingRevision;
+
+  Map<String, Object?> toJson() => {
+        'cid_number': cidNumber,
+        'account_id_hex': accountIdHex,
+        'binding_revision': bindingRevision,
+      };
+}
+
+/// 按 CID 批量读链上绑定快照,供 Rust 逐叶校验 MLS 凭证授权。
+typedef ChatChainBindingReader = Future<List<ChatChainBinding>> Function(
+  List<String> cidNumbers,
+);
+
+/// MLS 设备凭证授权的 FFI 边界(可注入,单测用 fake)。
+///
+/// [prepareDeviceBinding] 给出待 CID 绑定账户 sr25519 签名的摘要,
+/// [installDeviceBinding] 回装签名;此后本机新建 KeyPackage 的凭证都携带该证明。
+abstract class MlsDeviceBindingCrypto {
+  Future<({String devicePublicKeyHex, List<int> signingMessage})>
+      prepareDeviceBinding({
+    required String accountIdHex,
+    required int bindingRevision,
+    required DateTime expiresAt,
+    required String nonce,
+  });
+
+  Future<void> installDeviceBinding({
+    required String accountIdHex,
+    required int bindingRevision,
+    required DateTime expiresAt,
+    required String nonce,
+    required List<int> signature,
+  });
+}
+
+/// 设备初始化时为本机 MLS 签名公钥取得 CID 绑定账户授权,返回被授权的公钥 hex。
+///
+/// [sign] 必须由 CID 当前绑定账户(sr25519)签名;签名不成立时 native 拒绝安装。
+Future<String> attestMlsDevice({
+  required MlsDeviceBindingCrypto crypto,
+  required String accountIdHex,
+  required int bindingRevision,
+  required DateTime expiresAt,
+  required String nonce,
+  required Future<List<int>> Function(List<int> signingMessage) sign,
+}) async {
+  final prepared = await crypto.prepareDeviceBinding(
+    accountIdHex: accountIdHex,
+    bindingRevision: bindingRevision,
+    expiresAt: expiresAt,
+    nonce: nonce,
+  );
+  await crypto.installDeviceBinding(
+    accountIdHex: accountIdHex,
+    bindingRevision: bindingRevision,
+    expiresAt: expiresAt,
+    nonce: nonce,
+    signature: await sign(prepared.signingMessage),
+  );
+  return prepared.devicePublicKeyHex;
+}
+
 /// OpenMLS 群 FFI 边界接口(可注入,单测用 fake)。
 ///
 /// 实现必须调用成熟 OpenMLS native,不允许在 Dart 中自研群密码学。
@@ -169,6 +275,12 @@ abstract class MlsGroupCrypto {
     List<String> memberCidNumbers,
   );
 
+  /// 按成员标识(`cid:device`)移除单个设备叶子(本人设备吊销),CID 其余设备保留。
+  Future<GroupCommitBundle> removeDevices(
+    String groupId,
+    List<String> memberIdentities,
+  );
+
   /// 群 application message(单次加密,Dart 侧扇出)。
   Future<MlsWireMessage> groupCreateMessage(
     String groupId,
@@ -180,4 +292,10 @@ abstract class MlsGroupCrypto {
 
   /// 只读群状态(epoch + 名册)。
   Future<GroupState> groupState(String groupId);
+
+  /// 驱逐名册中链上授权已失效的叶子;无可驱逐叶子时返回 null。
+  Future<GroupCommitBundle?> evictStaleMembers(String groupId);
+
+  /// 机构群按链上岗位名册同步:移除卸任成员,回吐待加入 CID。
+  Future<InstitutionSyncBundle> syncInstitutionGroup(String groupId);
 }
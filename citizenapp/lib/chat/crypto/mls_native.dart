@@ -1,10 +1,12 @@
 import 'dart:convert';
 import 'dart:ffi';
 import 'dart:io';
+import 'dart:isolate';
 
 import 'package:ffi/ffi.dart';
 import 'package:path/path.dart' as path;
 
+import '../group/institution_group.dart';
 import 'mls_boundary.dart';
 import 'mls_group_boundary.dart';
 import 'mls_state_store.dart';
@@ -41,21 +43,77 @@ class MlsNativeSmokeResult {
   }
 }
 
+/// Rust `chat_attachment` 加密附件的结果:整体随 MLS 控制消息端到端下发。
+class MlsSealedAttachment {
+  const MlsSealedAttachment({
+    required this.attachmentId,
+    required this.keyHex,
+    required this.chunkSize,
+    required this.byteSize,
+    required this.encryptedSize,
+    required this.contentHashHex,
+    required this.ciphertextHashHex,
+    required this.epoch,
+  });
+
+  final String attachmentId;
+
+  /// 由发送时 epoch 的 exporter secret 派生的附件密钥,只进 E2E 明文。
+  final String keyHex;
+  final int chunkSize;
+  final int byteSize;
+  final int encryptedSize;
+  final String contentHashHex;
+  final String ciphertextHashHex;
+  final int epoch;
+
+  factory MlsSealedAttachment.fromJson(Map<String, dynamic> json) {
+    return MlsSealedAttachment(
+      attachmentId: _requireField(json, 'attachment_id'),
+      keyHex: _requireField(json, 'key_hex'),
+      chunkSize: (json['chunk_size'] as num?)?.toInt() ?? 0,
+      byteSize: (json['byte_size'] as num?)?.toInt() ?? 0,
+      encryptedSize: (json['encrypted_size'] as num?)?.toInt() ?? 0,
+      contentHashHex: _requireField(json, 'content_hash_hex'),
+      ciphertextHashHex: _requireField(json, 'ciphertext_hash_hex'),
+      epoch: (json['epoch'] as num?)?.toInt() ?? 0,
+    );
+  }
+}
+
 /// 通过现有 native 库调用 Rust OpenMLS。
 ///
 /// 该类只负责跨 FFI 边界，密码学实现全部在 Rust OpenMLS 中完成。
-class NativeMlsCrypto implements MlsCrypto, MlsGroupCrypto {
+class NativeMlsCrypto
+    implements MlsCrypto, MlsGroupCrypto, MlsDeviceBindingCrypto {
   NativeMlsCrypto({
     MlsNativeBindings? bindings,
     ChatDevice? identity,
     MlsStateStore? stateStore,
+    ChatChainBindingReader? chainBindingReader,
+    InstitutionRosterReader? institutionRosterReader,
   })  : _bindings = bindings ?? MlsNativeBindings.load(),
         _identity = identity,
-        _stateStore = stateStore;
+        _stateStore = stateStore,
+        _chainBindingReader = chainBindingReader,
+        _institutionRosterReader = institutionRosterReader;
 
   final MlsNativeBindings _bindings;
   final ChatDevice? _identity;
   final MlsStateStore? _stateStore;
+  final ChatChainBindingReader? _chainBindingReader;
+  final InstitutionRosterReader? _institutionRosterReader;
+
+  /// 机构名册短缓存(同 [_chainBindingTtl]):入站握手连续处理时不重复读轻节点。
+  final Map<String, (InstitutionRoster, DateTime)> _institutionRosterCache = {};
+
+  /// 链上绑定快照短缓存:同一批消息连续处理时不重复读轻节点;过期后重读,
+  /// 保证 CID 换绑/注销能在一个缓存周期内被察觉。
+  static const Duration _chainBindingTtl = Duration(minutes: 1);
+  final Map<String, (ChatChainBinding, DateTime)> _chainBindingCache = {};
+
+  /// `needs_bindings` 补读重放上限(每轮只补缺失 CID,正常一轮收敛)。
+  static const int _maxBindingRounds = 3;
 
   /// 当前账户运行上下文退出后立即清零 Dart 侧 MLS 状态信封钥。
   void dispose() {
@@ -206,6 +264,7 @@ class NativeMlsCrypto implements MlsCrypto, MlsGroupCrypto {
       'cid_number': identity.cidNumber,
       'device_id': identity.deviceId,
       'group_id': groupId,
+      ...await _institutionRosterField(groupId, refresh: true),
     });
     return GroupCreated(
       groupId: (response['group_id'] ?? groupId).toString(),
@@ -229,6 +288,10 @@ class NativeMlsCrypto implements MlsCrypto, MlsGroupCrypto {
       'group_id': groupId,
       'key_packages_hex':
           keyPackages.map((keyPackage) => keyPackage.keyPackageHex).toList(),
+      'chain_bindings': await _chainBindingsJson(
+        keyPackages.map((keyPackage) => keyPackage.cidNumber).toList(),
+      ),
+      ...await _institutionRosterField(groupId, refresh: true),
     });
     final treeHex = (response['ratchet_tree_hex'] ?? '').toString();
     final welcomeHex = (response['welcome_wire_hex'] ?? '').toString();
@@ -266,6 +329,7 @@ class NativeMlsCrypto implements MlsCrypto, MlsGroupCrypto {
       'device_id': identity.deviceId,
       'group_id': groupId,
       'member_cid_numbers': memberCidNumbers,
+      ...await _institutionRosterField(groupId),
     });
     final removed = (response['removed_cid_numbers'] as List?)
             ?.map((item) => item.toString())
@@ -283,6 +347,34 @@ class NativeMlsCrypto implements MlsCrypto, MlsGroupCrypto {
     );
   }
 
+  @override
+  Future<GroupCommitBundle> removeDevices(
+    String groupId,
+    List<String> memberIdentities,
+  ) async {
+    final identity = _requireIdentity();
+    final stateStore = _requireStateStore();
+    await stateStore.ensureReady();
+    final response = _bindings.callJson(_bindings.groupRemoveDevices, {
+      'state_store_dir': stateStore.path,
+      'state_key_hex': stateStore.stateKeyHex,
+      'cid_number': identity.cidNumber,
+      'device_id': identity.deviceId,
+      'group_id': groupId,
+      'member_identities': memberIdentities,
+      ...await _institutionRosterField(groupId),
+    });
+    return GroupCommitBundle(
+      groupId: (response['group_id'] ?? groupId).toString(),
+      epoch: (response['epoch'] as num?)?.toInt() ?? 0,
+      commit: _groupWire(
+        groupId,
+        (response['commit_wire_hex'] ?? '').toString(),
+        MlsMessageKind.application,
+      ),
+    );
+  }
+
   @override
   Future<MlsWireMessage> groupCreateMessage(
     String groupId,
@@ -311,7 +403,7 @@ class NativeMlsCrypto implements MlsCrypto, MlsGroupCrypto {
     final identity = _requireIdentity();
     final stateStore = _requireStateStore();
     await stateStore.ensureReady();
-    final response = _bindings.callJson(_bindings.groupProcess, {
+    final request = <String, Object?>{
       'state_store_dir': stateStore.path,
       'state_key_hex': stateStore.stateKeyHex,
       'cid_number': identity.cidNumber,
@@ -319,7 +411,36 @@ class NativeMlsCrypto implements MlsCrypto, MlsGroupCrypto {
       'group_id': wire.conversationId,
       'wire_message_hex': wire.wireHex,
       if (wire.ratchetTreeHex != null) 'ratchet_tree_hex': wire.ratchetTreeHex,
-    });
+    };
+    // Rust 校验名册授权时缺哪个 CID 就回 needs_bindings(未落盘),补读后原样重放;
+    // 机构群握手缺岗位名册回 needs_institution_roster,同样补读后重放。
+    var cidNumbers = _cachedChainBindingCids();
+    var response = <String, dynamic>{};
+    for (var round = 0; round < _maxBindingRounds; round++) {
+      request['chain_bindings'] = await _chainBindingsJson(cidNumbers);
+      response = _bindings.callJson(_bindings.groupProcess, request);
+      final status = response['status'];
+      if (status == 'needs_institution_roster') {
+        request.addAll(
+          await _institutionRosterField(wire.conversationId, refresh: true),
+        );
+        continue;
+      }
+      if (status != 'needs_bindings') {
+        break;
+      }
+      final missing = (response['missing_cid_numbers'] as List? ?? const [])
+          .map((item) => item.toString());
+      cidNumbers = {...cidNumbers, ...missing}.toList();
+    }
+    if (response['status'] == 'needs_bindings' ||
+        response['status'] == 'needs_institution_roster') {
+      throw StateError('群名册链上绑定快照补读未收敛');
+    }
+    final stale = (response['stale_members'] as List?)
+            ?.map((item) => ((item as Map)['identity'] ?? '').toString())
+            .toList() ??
+        const <String>[];
     final plaintextHex = response['plaintext_hex']?.toString();
     final members = (response['member_identities'] as List?)
         ?.map((item) => item.toString())
@@ -339,6 +460,7 @@ class NativeMlsCrypto implements MlsCrypto, MlsGroupCrypto {
           ? null
           : _hexToBytes(plaintextHex),
       memberIdentities: members,
+      staleMemberIdentities: stale,
     );
   }
 
@@ -365,6 +487,320 @@ class NativeMlsCrypto implements MlsCrypto, MlsGroupCrypto {
     );
   }
 
+  @override
+  Future<GroupCommitBundle?> evictStaleMembers(String groupId) async {
+    final identity = _requireIdentity();
+    final stateStore = _requireStateStore();
+    final roster = await groupState(groupId);
+    final response = _bindings.callJson(_bindings.groupEvictStale, {
+      'state_store_dir': stateStore.path,
+      'state_key_hex': stateStore.stateKeyHex,
+      'cid_number': identity.cidNumber,
+      'device_id': identity.deviceId,
+      'group_id': groupId,
+      'chain_bindings': await _chainBindingsJson(
+        cidNumbersFromMemberIdentities(roster.memberIdentities),
+        refresh: true,
+      ),
+    });
+    final commitHex = response['commit_wire_hex']?.toString();
+    if (commitHex == null || commitHex.isEmpty) {
+      return null;
+    }
+    final evicted = (response['evicted_members'] as List? ?? const [])
+        .map((item) => ((item as Map)['identity'] ?? '').toString());
+    return GroupCommitBundle(
+      groupId: (response['group_id'] ?? groupId).toString(),
+      epoch: (response['epoch'] as num?)?.toInt() ?? 0,
+      commit: _groupWire(groupId, commitHex, MlsMessageKind.application),
+      removedCidNumbers: cidNumbersFromMemberIdentities(evicted),
+    );
+  }
+
+  @override
+  Future<InstitutionSyncBundle> syncInstitutionGroup(String groupId) async {
+    final identity = _requireIdentity();
+    final stateStore = _requireStateStore();
+    await stateStore.ensureReady();
+    final response = _bindings.callJson(_bindings.groupInstitutionSync, {
+      'state_store_dir': stateStore.path,
+      'state_key_hex': stateStore.stateKeyHex,
+      'cid_number': identity.cidNumber,
+      'device_id': identity.deviceId,
+      'group_id': groupId,
+      ...await _institutionRosterField(groupId, refresh: true),
+    });
+    final commitHex = response['commit_wire_hex']?.toString();
+    List<String> cids(String field) => (response[field] as List? ?? const [])
+        .map((item) => item.toString())
+        .toList();
+    return InstitutionSyncBundle(
+      groupId: (response['group_id'] ?? groupId).toString(),
+      epoch: (response['epoch'] as num?)?.toInt() ?? 0,
+      commit: commitHex == null || commitHex.isEmpty
+          ? null
+          : _groupWire(groupId, commitHex, MlsMessageKind.application),
+      removedCidNumbers: cids('removed_cid_numbers'),
+      missingCidNumbers: cids('missing_cid_numbers'),
+      selfExtraneous: response['self_extraneous'] == true,
+    );
+  }
+
+  /// 新设备发起历史迁移:生成一次性 HPKE 密钥(私钥只留 Rust 本地状态),返回
+  /// 带本机凭证签名的请求,经信令发给本人其他设备。
+  Future<Map<String, dynamic>> requestHistoryTransfer() async {
+    final identity = _requireIdentity();
+    final stateStore = _requireStateStore();
+    await stateStore.ensureReady();
+    final response = _bindings.callJson(_bindings.historyRequest, {
+      'state_store_dir': stateStore.path,
+      'state_key_hex': stateStore.stateKeyHex,
+      'cid_number': identity.cidNumber,
+      'device_id': identity.deviceId,
+    });
+    return (response['request'] as Map).cast<String, dynamic>();
+  }
+
+  /// 老设备核对请求方是本 CID 链上授权的设备后,把 [history] 封给其一次性公钥。
+  Future<Map<String, dynamic>> sealHistoryTransfer({
+    required Map<String, dynamic> request,
+    required List<int> history,
+  }) async {
+    final identity = _requireIdentity();
+    final stateStore = _requireStateStore();
+    await stateStore.ensureReady();
+    final response = _bindings.callJson(_bindings.historySeal, {
+      'state_store_dir': stateStore.path,
+      'state_key_hex': stateStore.stateKeyHex,
+      'cid_number': identity.cidNumber,
+      'device_id': identity.deviceId,
+      'request': request,
+      'chain_bindings':
+          await _chainBindingsJson([identity.cidNumber], refresh: true),
+      'history_hex': _bytesToHex(history),
+    });
+    return (response['bundle'] as Map).cast<String, dynamic>();
+  }
+
+  /// 新设备解开迁移包;一次性私钥随即销毁,同一请求只接受一个迁移包。
+  Future<({String fromDeviceId, List<int> history})> openHistoryTransfer(
+    Map<String, dynamic> bundle,
+  ) async {
+    final identity = _requireIdentity();
+    final stateStore = _requireStateStore();
+    await stateStore.ensureReady();
+    final response = _bindings.callJson(_bindings.historyOpen, {
+      'state_store_dir': stateStore.path,
+      'state_key_hex': stateStore.stateKeyHex,
+      'cid_number': identity.cidNumber,
+      'device_id': identity.deviceId,
+      'bundle': bundle,
+      'chain_bindings':
+          await _chainBindingsJson([identity.cidNumber], refresh: true),
+    });
+    return (
+      fromDeviceId: _requireField(response, 'from_device_id'),
+      history: _hexToBytes(_requireField(response, 'history_hex')),
+    );
+  }
+
+  /// 以群当前 epoch 的 exporter secret 派生附件密钥,把 [sourcePath] 分块加密到
+  /// [outputPath]。大文件在后台 isolate 里流式处理,不阻塞 UI。
+  Future<MlsSealedAttachment> sealAttachment({
+    required String groupId,
+    required String attachmentId,
+    required String sourcePath,
+    required String outputPath,
+    int? chunkSize,
+  }) async {
+    final identity = _requireIdentity();
+    final stateStore = _requireStateStore();
+    await stateStore.ensureReady();
+    final response = await _runAttachmentJob(_attachmentSealSymbol, {
+      'state_store_dir': stateStore.path,
+      'state_key_hex': stateStore.stateKeyHex,
+      'cid_number': identity.cidNumber,
+      'device_id': identity.deviceId,
+      'group_id': groupId,
+      'attachment_id': attachmentId,
+      'source_path': sourcePath,
+      'output_path': outputPath,
+      if (chunkSize != null) 'chunk_size': chunkSize,
+    });
+    return MlsSealedAttachment.fromJson(response);
+  }
+
+  /// 按 MLS 消息内的附件描述核对密文、解密并核对内容哈希;任一不符即抛错且
+  /// 不留输出文件。无需本机群状态,群已换代或本机已退群也能解开旧附件。
+  Future<void> openAttachment({
+    required String attachmentId,
+    required String keyHex,
+    required int chunkSize,
+    required String contentHashHex,
+    String? ciphertextHashHex,
+    int? encryptedSize,
+    required String sourcePath,
+    required String outputPath,
+  }) async {
+    await _runAttachmentJob(_attachmentOpenSymbol, {
+      'attachment_id': attachmentId,
+      'key_hex': keyHex,
+      'chunk_size': chunkSize,
+      'content_hash_hex': contentHashHex,
+      'ciphertext_hash_hex': ciphertextHashHex,
+      'encrypted_size': encryptedSize,
+      'source_path': sourcePath,
+      'output_path': outputPath,
+    });
+  }
+
+  /// 只核对已下载密文的大小与哈希(断点续传后、解密前)。
+  Future<void> verifyAttachment({
+    required String ciphertextHashHex,
+    int? encryptedSize,
+    required String sourcePath,
+  }) async {
+    await _runAttachmentJob(_attachmentVerifySymbol, {
+      'ciphertext_hash_hex': ciphertextHashHex,
+      'encrypted_size': encryptedSize,
+      'source_path': sourcePath,
+    });
+  }
+
+  /// 生成待 CID 绑定账户签名的设备授权摘要(签名对象 = 本机 MLS 签名公钥)。
+  @override
+  Future<({String devicePublicKeyHex, List<int> signingMessage})>
+      prepareDeviceBinding({
+    required String accountIdHex,
+    required int bindingRevision,
+    required DateTime expiresAt,
+    required String nonce,
+  }) async {
+    await _requireStateStore().ensureReady();
+    final response = _bindings.callJson(
+      _bindings.deviceBinding,
+      _deviceBindingRequest(
+        action: 'prepare',
+        accountIdHex: accountIdHex,
+        bindingRevision: bindingRevision,
+        expiresAt: expiresAt,
+        nonce: nonce,
+      ),
+    );
+    return (
+      devicePublicKeyHex: _requireField(response, 'device_public_key_hex'),
+      signingMessage:
+          _hexToBytes(_requireField(response, 'signing_message_hex')),
+    );
+  }
+
+  /// 安装 CID 绑定账户对 [prepareDeviceBinding] 摘要的 sr25519 签名;此后新
+  /// KeyPackage 的凭证携带该授权证明。
+  @override
+  Future<void> installDeviceBinding({
+    required String accountIdHex,
+    required int bindingRevision,
+    required DateTime expiresAt,
+    required String nonce,
+    required List<int> signature,
+  }) async {
+    _bindings.callJson(
+      _bindings.deviceBinding,
+      _deviceBindingRequest(
+        action: 'install',
+        accountIdHex: accountIdHex,
+        bindingRevision: bindingRevision,
+        expiresAt: expiresAt,
+        nonce: nonce,
+      )..['signature_hex'] = _bytesToHex(signature),
+    );
+  }
+
+  Map<String, Object?> _deviceBindingRequest({
+    required String action,
+    required String accountIdHex,
+    required int bindingRevision,
+    required DateTime expiresAt,
+    required String nonce,
+  }) {
+    final identity = _requireIdentity();
+    final stateStore = _requireStateStore();
+    return {
+      'state_store_dir': stateStore.path,
+      'state_key_hex': stateStore.stateKeyHex,
+      'cid_number': identity.cidNumber,
+      'device_id': identity.deviceId,
+      'action': action,
+      'account_id_hex': accountIdHex,
+      'binding_revision': bindingRevision,
+      'expires_at_ms': expiresAt.toUtc().millisecondsSinceEpoch,
+      'nonce': nonce,
+    };
+  }
+
+  List<String> _cachedChainBindingCids() {
+    final now = DateTime.now();
+    _chainBindingCache.removeWhere(
+      (_, entry) => now.difference(entry.$2) > _chainBindingTtl,
+    );
+    return _chainBindingCache.keys.toList();
+  }
+
+  Future<List<Map<String, Object?>>> _chainBindingsJson(
+    List<String> cidNumbers, {
+    bool refresh = false,
+  }) async {
+    final now = DateTime.now();
+    final wanted = cidNumbers.toSet();
+    final toFetch = wanted.where((cidNumber) {
+      final cached = _chainBindingCache[cidNumber];
+      return refresh ||
+          cached == null ||
+          now.difference(cached.$2) > _chainBindingTtl;
+    }).toList();
+    if (toFetch.isNotEmpty) {
+      final reader = _chainBindingReader;
+      if (reader == null) {
+        throw StateError('NativeMlsCrypto 需要链上绑定读取器才能校验群成员授权');
+      }
+      for (final binding in await reader(toFetch)) {
+        _chainBindingCache[binding.cidNumber] = (binding, now);
+      }
+    }
+    return [
+      for (final cidNumber in wanted)
+        if (_chainBindingCache[cidNumber] case final entry?) entry.$1.toJson(),
+    ];
+  }
+
+  /// 机构群请求附带的名册快照字段;普通群返回空 map。
+  Future<Map<String, Object?>> _institutionRosterField(
+    String groupId, {
+    bool refresh = false,
+  }) async {
+    final institution = parseInstitutionGroupId(groupId);
+    if (institution == null) {
+      return const {};
+    }
+    final now = DateTime.now();
+    final cached = _institutionRosterCache[groupId];
+    var roster = cached?.$1;
+    if (refresh ||
+        cached == null ||
+        now.difference(cached.$2) > _chainBindingTtl) {
+      final reader = _institutionRosterReader;
+      if (reader == null) {
+        throw StateError('NativeMlsCrypto 需要机构名册读取器才能处理机构群');
+      }
+      roster = await reader(
+        institution.institutionCidNumber,
+        institution.scope,
+      );
+      _institutionRosterCache[groupId] = (roster, now);
+    }
+    return {'institution_roster': roster!.toJson()};
+  }
+
   MlsWireMessage _groupWire(
     String groupId,
     String wireHex,
@@ -425,9 +861,19 @@ class MlsNativeBindings {
     required this.groupCreate,
     required this.groupAddMembers,
     required this.groupRemoveMembers,
+    required this.groupRemoveDevices,
     required this.groupCreateMessage,
     required this.groupProcess,
     required this.groupState,
+    required this.groupEvictStale,
+    required this.groupInstitutionSync,
+    required this.deviceBinding,
+    required this.historyRequest,
+    required this.historySeal,
+    required this.historyOpen,
+    required this.attachmentSeal,
+    required this.attachmentOpen,
+    required this.attachmentVerify,
     required MlsFreeStringDart freeString,
   }) : _freeString = freeString;
 
@@ -439,9 +885,19 @@ class MlsNativeBindings {
   final MlsJsonDart groupCreate;
   final MlsJsonDart groupAddMembers;
   final MlsJsonDart groupRemoveMembers;
+  final MlsJsonDart groupRemoveDevices;
   final MlsJsonDart groupCreateMessage;
   final MlsJsonDart groupProcess;
   final MlsJsonDart groupState;
+  final MlsJsonDart groupEvictStale;
+  final MlsJsonDart groupInstitutionSync;
+  final MlsJsonDart deviceBinding;
+  final MlsJsonDart historyRequest;
+  final MlsJsonDart historySeal;
+  final MlsJsonDart historyOpen;
+  final MlsJsonDart attachmentSeal;
+  final MlsJsonDart attachmentOpen;
+  final MlsJsonDart attachmentVerify;
   final MlsFreeStringDart _freeString;
 
   static MlsNativeBindings load() {
@@ -471,6 +927,9 @@ class MlsNativeBindings {
       groupRemoveMembers: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
         'citizen_chat_mls_group_remove_members_json',
       ),
+      groupRemoveDevices: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
+        'citizen_chat_mls_group_remove_devices_json',
+      ),
       groupCreateMessage: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
         'citizen_chat_mls_group_create_message_json',
       ),
@@ -480,6 +939,33 @@ class MlsNativeBindings {
       groupState: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
         'citizen_chat_mls_group_state_json',
       ),
+      groupEvictStale: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
+        'citizen_chat_mls_group_evict_stale_json',
+      ),
+      groupInstitutionSync: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
+        'citizen_chat_mls_group_institution_sync_json',
+      ),
+      deviceBinding: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
+        'citizen_chat_mls_device_binding_json',
+      ),
+      historyRequest: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
+        'citizen_chat_mls_history_request_json',
+      ),
+      historySeal: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
+        'citizen_chat_mls_history_seal_json',
+      ),
+      historyOpen: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
+        'citizen_chat_mls_history_open_json',
+      ),
+      attachmentSeal: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
+        _attachmentSealSymbol,
+      ),
+      attachmentOpen: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
+        _attachmentOpenSymbol,
+      ),
+      attachmentVerify: library.lookupFunction<MlsJsonNative, MlsJsonDart>(
+        _attachmentVerifySymbol,
+      ),
       freeString:
           library.lookupFunction<MlsFreeStringNative, MlsFreeStringDart>(
         'smoldot_free_string',
@@ -534,6 +1020,33 @@ class MlsNativeBindings {
   }
 }
 
+const String _attachmentSealSymbol = 'citizen_chat_mls_attachment_seal_json';
+const String _attachmentOpenSymbol = 'citizen_chat_mls_attachment_open_json';
+const String _attachmentVerifySymbol =
+    'citizen_chat_mls_attachment_verify_json';
+
+/// 附件加解密放到后台 isolate;顶层函数的闭包只捕获 [symbol] 与 [request]。
+Future<Map<String, dynamic>> _runAttachmentJob(
+  String symbol,
+  Map<String, Object?> request,
+) =>
+    Isolate.run(() => _attachmentJob(symbol, request));
+
+/// 在后台 isolate 内重新打开 native 库执行(函数指针不跨 isolate)。
+/// seal 只读群状态、不回写,不与主 isolate 的 OpenMLS 写入冲突。
+Map<String, dynamic> _attachmentJob(
+  String symbol,
+  Map<String, Object?> request,
+) {
+  final bindings = MlsNativeBindings.load();
+  final function = switch (symbol) {
+    _attachmentSealSymbol => bindings.attachmentSeal,
+    _attachmentOpenSymbol => bindings.attachmentOpen,
+    _ => bindings.attachmentVerify,
+  };
+  return bindings.callJson(function, request);
+}
+
 DynamicLibrary _loadSmoldotLibrary() {
   final candidates = <String>[];
   final cwd = Directory.current.path;
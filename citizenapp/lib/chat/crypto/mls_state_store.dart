@@ -11,8 +11,9 @@ import 'mls_session.dart';
 /// OpenMLS provider storage 由 Rust native 写入该目录；Dart 只管理目录位置、
 /// 下传状态信封密钥，以及 application 早于 Welcome 到达时的 pending 队列。
 ///
-/// 该目录下**一律不得出现明文**：`openmls_storage.bin` / `device.bin` 由 Rust
-/// 用 [stateKey] 做 AES-256-GCM 信封；`pending_inbound.bin` 由本类同钥加密。
+/// 该目录下**一律不得出现明文**：`openmls_kv.sqlite`(逐条记录)/ `device.bin`
+/// 由 Rust 用 [stateKey] 做 AES-256-GCM 信封(旧版整文件 `openmls_storage.bin`
+/// 首次打开即迁入 KV 并删除)；`pending_inbound.bin` 由本类同钥加密。
 class MlsStateStore {
   const MlsStateStore(
     this.directory, {
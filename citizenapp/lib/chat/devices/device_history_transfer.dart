@@ -0,0 +1,232 @@
+// 本人设备间历史迁移:新设备发请求,老设备核对同 CID 后把聊天记录封给新设备。
+//
+// 密码学全在 Rust `chat_devices.rs`(HPKE 一次性密钥 + 凭证签名 + 链上绑定核对),
+// 本层只做记录编解码与信令分片:迁移包经 `citizen_chat_signal` 定向投给请求设备,
+// 单条信令受 Worker 64 KiB 上限约束,故按 [kHistoryChunkChars] 切片后重组。
+// 老设备收到请求只进 [HistoryRequestInbox],须用户在本机逐条同意后才导出。
+
+import 'dart:convert';
+import 'dart:io';
+
+import 'package:flutter/foundation.dart';
+
+import '../storage/chat_store.dart';
+
+/// 新设备广播给本人其他设备的历史迁移请求信令。
+const String kHistoryTransferRequestKind = 'history_transfer_request';
+
+/// 老设备回传迁移包分片的信令。
+const String kHistoryTransferChunkKind = 'history_transfer_chunk';
+
+/// 单片字符数:留足信令 JSON 外壳余量,低于 Worker `chat_signal` 64 KiB 上限。
+const int kHistoryChunkChars = 48 * 1024;
+
+/// 单次迁移最多分片数(约 12 MB 迁移包),超出视为异常丢弃。
+const int kMaxHistoryChunks = 256;
+
+/// 迁移请求有效期,与 Rust `HISTORY_REQUEST_TTL_MILLIS` 一致;过期请求不再展示。
+const Duration kHistoryRequestTtl = Duration(minutes: 10);
+
+/// 待本机用户确认的历史迁移请求(来自本人另一台设备)。
+class PendingHistoryRequest {
+  const PendingHistoryRequest({
+    required this.deviceId,
+    required this.createdAtMillis,
+    required this.request,
+  });
+
+  /// 请求设备 ID。
+  final String deviceId;
+
+  /// 请求方签入请求的创建时间(毫秒)。
+  final int createdAtMillis;
+
+  /// 原始请求,同意后原样交 Rust 核对封装。
+  final Map<String, dynamic> request;
+
+  String get requestId => '$deviceId@$createdAtMillis';
+
+  bool isExpired(DateTime now) =>
+      now.millisecondsSinceEpoch - createdAtMillis >=
+      kHistoryRequestTtl.inMilliseconds;
+}
+
+/// 历史迁移请求待确认箱。进程内单例,收信的 ChatRuntime 与设置页共用;
+/// 同一设备只保留最新一条,过期请求在读取时剔除。
+class HistoryRequestInbox {
+  HistoryRequestInbox._();
+
+  static final HistoryRequestInbox instance = HistoryRequestInbox._();
+
+  final ValueNotifier<List<PendingHistoryRequest>> _pending =
+      ValueNotifier(const []);
+
+  /// 待确认请求(变更时通知,用于设置页角标与列表)。
+  ValueListenable<List<PendingHistoryRequest>> get pending => _pending;
+
+  /// 收到请求:替换同设备旧请求。
+  void hold(PendingHistoryRequest request) {
+    final now = DateTime.now();
+    if (request.isExpired(now)) {
+      return;
+    }
+    _pending.value = [
+      for (final item in _pending.value)
+        if (item.deviceId != request.deviceId && !item.isExpired(now)) item,
+      request,
+    ];
+  }
+
+  /// 取出(并移出箱)一条未过期请求;不存在或已过期返回 null。
+  PendingHistoryRequest? take(String requestId) {
+    final now = DateTime.now();
+    PendingHistoryRequest? found;
+    final rest = <PendingHistoryRequest>[];
+    for (final item in _pending.value) {
+      if (item.requestId == requestId) {
+        found = item;
+      } else if (!item.isExpired(now)) {
+        rest.add(item);
+      }
+    }
+    _pending.value = rest;
+    if (found == null || found.isExpired(now)) {
+      return null;
+    }
+    return found;
+  }
+
+  /// 设备被吊销后丢弃其请求。
+  void dropDevice(String deviceId) {
+    _pending.value = [
+      for (final item in _pending.value)
+        if (item.deviceId != deviceId) item,
+    ];
+  }
+}
+
+/// 聊天记录 ↔ 迁移明文(gzip JSON)。明文只进 Rust HPKE 封装,不落盘、不上云。
+class ChatHistoryCodec {
+  const ChatHistoryCodec._();
+
+  static List<int> encode(List<ChatHistoryRecord> records) {
+    final json = [
+      for (final record in records)
+        {
+          'envelope_id': record.envelopeId,
+          'conversation_id': record.conversationId,
+          'conversation_title': record.conversationTitle,
+          'conversation_kind': record.conversationKind,
+          'peer_cid_number': record.peerCidNumber,
+          'direction': record.direction,
+          'sender_cid_number': record.senderCidNumber,
+          'recipient_cid_number': record.recipientCidNumber,
+          'sender_device_id': record.senderDeviceId,
+          'message_kind': record.messageKind,
+          'delivery_state': record.deliveryState,
+          'created_at': record.createdAtMillis,
+          'plaintext': record.plaintext,
+        },
+    ];
+    return gzip.encode(utf8.encode(jsonEncode(json)));
+  }
+
+  static List<ChatHistoryRecord> decode(List<int> bytes) {
+    final decoded = jsonDecode(utf8.decode(gzip.decode(bytes)));
+    if (decoded is! List) {
+      throw const FormatException('历史迁移明文格式无效');
+    }
+    String field(Map<String, dynamic> item, String key) =>
+        (item[key] ?? '').toString();
+    return [
+      for (final item in decoded.whereType<Map<String, dynamic>>())
+        if (field(item, 'envelope_id').isNotEmpty &&
+            field(item, 'conversation_id').isNotEmpty)
+          ChatHistoryRecord(
+            envelopeId: field(item, 'envelope_id'),
+            conversationId: field(item, 'conversation_id'),
+            conversationTitle: field(item, 'conversation_title'),
+            conversationKind: field(item, 'conversation_kind') == 'group'
+                ? 'group'
+                : 'dm',
+            peerCidNumber: field(item, 'peer_cid_number'),
+            direction: field(item, 'direction') == 'outgoing'
+                ? 'outgoing'
+                : 'incoming',
+            senderCidNumber: field(item, 'sender_cid_number'),
+            recipientCidNumber: field(item, 'recipient_cid_number'),
+            senderDeviceId: field(item, 'sender_device_id'),
+            messageKind: field(item, 'message_kind'),
+            deliveryState: field(item, 'delivery_state'),
+            createdAtMillis: (item['created_at'] as num?)?.toInt() ?? 0,
+            plaintext: item['plaintext'] as String?,
+          ),
+    ];
+  }
+}
+
+/// 把迁移包(Rust `HistoryBundle` JSON)切成信令分片。
+List<Map<String, Object?>> splitHistoryBundle({
+  required String transferId,
+  required Map<String, dynamic> bundle,
+}) {
+  final text = jsonEncode(bundle);
+  final count = (text.length + kHistoryChunkChars - 1) ~/ kHistoryChunkChars;
+  if (count > kMaxHistoryChunks) {
+    throw StateError('历史迁移包过大($count 片),请缩小迁移范围');
+  }
+  return [
+    for (var index = 0; index < count; index++)
+      {
+        'kind': kHistoryTransferChunkKind,
+        'transfer_id': transferId,
+        'index': index,
+        'count': count,
+        'data': text.substring(
+          index * kHistoryChunkChars,
+          (index + 1) * kHistoryChunkChars > text.length
+              ? text.length
+              : (index + 1) * kHistoryChunkChars,
+        ),
+      },
+  ];
+}
+
+/// 按 transfer_id 重组迁移包分片;分片可乱序到达,重复分片覆盖。
+class HistoryBundleAssembler {
+  final Map<String, List<String?>> _pending = {};
+
+  /// 收到一片;集齐时返回完整迁移包并清理,否则返回 null。格式异常的分片丢弃。
+  Map<String, dynamic>? add(Map<String, dynamic> signal) {
+    final transferId = signal['transfer_id'];
+    final index = signal['index'];
+    final count = signal['count'];
+    final data = signal['data'];
+    if (transferId is! String ||
+        transferId.isEmpty ||
+        index is! int ||
+        count is! int ||
+        data is! String ||
+        count <= 0 ||
+        count > kMaxHistoryChunks ||
+        index < 0 ||
+        index >= count) {
+      return null;
+    }
+    final parts = _pending.putIfAbsent(
+      transferId,
+      () => List<String?>.filled(count, null),
+    );
+    if (parts.length != count) {
+      _pending.remove(transferId);
+      return null;
+    }
+    parts[index] = data;
+    if (parts.any((part) => part == null)) {
+      return null;
+    }
+    _pending.remove(transferId);
+    final decoded = jsonDecode(parts.join());
+    return decoded is Map<String, dynamic> ? decoded : null;
+  }
+}
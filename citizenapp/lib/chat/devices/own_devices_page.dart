@@ -0,0 +1,239 @@
+import 'package:flutter/material.dart';
+
+import 'package:citizenapp/chat/chat_runtime.dart';
+import 'package:citizenapp/chat/devices/device_history_transfer.dart';
+import 'package:citizenapp/chat/group/group_model.dart';
+import 'package:citizenapp/ui/app_theme.dart';
+
+/// 本人 Chat 设备页:在册设备 + 吊销 + 补进群 + 历史迁移(请求与逐条确认)。
+class OwnDevicesPage extends StatefulWidget {
+  const OwnDevicesPage({super.key, this.runtime});
+
+  final ChatRuntime? runtime;
+
+  @override
+  State<OwnDevicesPage> createState() => _OwnDevicesPageState();
+}
+
+class _OwnDevicesPageState extends State<OwnDevicesPage> {
+  late final ChatRuntime _runtime = widget.runtime ?? ChatRuntime();
+
+  String _currentDeviceId = '';
+  List<String> _deviceIds = const [];
+  bool _loading = true;
+  bool _busy = false;
+  String? _error;
+
+  @override
+  void initState() {
+    super.initState();
+    _load();
+  }
+
+  Future<void> _load() async {
+    try {
+      final devices = await _runtime.listOwnDevices();
+      if (!mounted) return;
+      setState(() {
+        _currentDeviceId = devices.currentDeviceId;
+        _deviceIds = devices.deviceIds;
+        _loading = false;
+      });
+    } catch (error) {
+      if (!mounted) return;
+      setState(() {
+        _error = '$error';
+        _loading = false;
+      });
+    }
+  }
+
+  Future<void> _run(Future<String?> Function() action) async {
+    if (_busy) return;
+    setState(() {
+      _busy = true;
+      _error = null;
+    });
+    try {
+      final notice = await action();
+      await _load();
+      if (notice != null && mounted) {
+        ScaffoldMessenger.of(context).showSnackBar(
+          SnackBar(content: Text(notice)),
+        );
+      }
+    } catch (error) {
+      if (mounted) setState(() => _error = '$error');
+    } finally {
+      if (mounted) setState(() => _busy = false);
+    }
+  }
+
+  String _failureNotice(String action, List<GroupSyncFailure> failures) {
+    if (failures.isEmpty) return '$action完成';
+    return '$action未完成:${failures.length} 个群失败,请稍后重试';
+  }
+
+  Future<void> _revoke(String deviceId) async {
+    final confirmed = await showDialog<bool>(
+      context: context,
+      builder: (context) => AlertDialog(
+        title: const Text('吊销设备'),
+        content: Text('吊销后设备 $deviceId 将收不到新消息,并从所有群移除。确定吊销？'),
+        actions: [
+          TextButton(
+            onPressed: () => Navigator.of(context).pop(false),
+            child: const Text('取消'),
+          ),
+          TextButton(
+            onPressed: () => Navigator.of(context).pop(true),
+            child: const Text('吊销'),
+          ),
+        ],
+      ),
+    );
+    if (confirmed != true) return;
+    await _run(() async =>
+        _failureNotice('吊销', await _runtime.revokeOwnDevice(deviceId)));
+  }
+
+  Future<void> _approve(PendingHistoryRequest request) async {
+    final confirmed = await showDialog<bool>(
+      context: context,
+      builder: (context) => AlertDialog(
+        title: const Text('迁移聊天记录'),
+        content: Text(
+          '设备 ${request.deviceId} 请求迁移本机的全部聊天记录。'
+          '只有确认是您本人正在登录的新设备时才同意。',
+        ),
+        actions: [
+          TextButton(
+            onPressed: () => Navigator.of(context).pop(false),
+            child: const Text('取消'),
+          ),
+          TextButton(
+            onPressed: () => Navigator.of(context).pop(true),
+            child: const Text('同意迁移'),
+          ),
+        ],
+      ),
+    );
+    if (confirmed != true) return;
+    await _run(() async {
+      await _runtime.approveHistoryRequest(request.requestId);
+      return '聊天记录已发送到 ${request.deviceId}';
+    });
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    return Scaffold(
+      appBar: AppBar(
+        title: const Text('聊天设备'),
+        centerTitle: true,
+      ),
+      body: _loading
+          ? const Center(
+              child: CircularProgressIndicator(color: AppTheme.primary))
+          : ListView(
+              padding: const EdgeInsets.all(16),
+              children: [
+                if (_error != null)
+                  Padding(
+                    padding: const EdgeInsets.only(bottom: 12),
+                    child: Text(
+                      _error!,
+                      style: const TextStyle(color: AppTheme.danger),
+                    ),
+                  ),
+                ValueListenableBuilder<List<PendingHistoryRequest>>(
+                  valueListenable: HistoryRequestInbox.instance.pending,
+                  builder: (context, requests, _) {
+                    final now = DateTime.now();
+                    final live = requests
+                        .where((request) => !request.isExpired(now))
+                        .toList(growable: false);
+                    if (live.isEmpty) return const SizedBox.shrink();
+                    return Container(
+                      margin: const EdgeInsets.only(bottom: 16),
+                      decoration:
+                          AppTheme.cardDecoration(radius: AppTheme.radiusLg),
+                      child: Column(
+                        children: [
+                          for (final request in live)
+                            ListTile(
+                              leading: const Icon(Icons.history_rounded),
+                              title: const Text('聊天记录迁移请求'),
+                              subtitle: Text(request.deviceId),
+                              trailing: Row(
+                                mainAxisSize: MainAxisSize.min,
+                                children: [
+                                  TextButton(
+                                    onPressed: _busy
+                                        ? null
+                                        : () => _runtime.rejectHistoryRequest(
+                                              request.requestId,
+                                            ),
+                                    child: const Text('拒绝'),
+                                  ),
+                                  TextButton(
+                                    onPressed:
+                                        _busy ? null : () => _approve(request),
+                                    child: const Text('同意'),
+                                  ),
+                                ],
+                              ),
+                            ),
+                        ],
+                      ),
+                    );
+                  },
+                ),
+                Container(
+                  decoration:
+                      AppTheme.cardDecoration(radius: AppTheme.radiusLg),
+                  child: Column(
+                    children: [
+                      for (final deviceId in _deviceIds)
+                        ListTile(
+                          leading: const Icon(Icons.smartphone_rounded),
+                          title: Text(deviceId),
+                          subtitle: deviceId == _currentDeviceId
+                              ? const Text('本机')
+                              : null,
+                          trailing: deviceId == _currentDeviceId
+                              ? null
+                              : TextButton(
+                                  onPressed:
+                                      _busy ? null : () => _revoke(deviceId),
+                                  child: const Text('吊销'),
+                                ),
+                        ),
+                    ],
+                  ),
+                ),
+                const SizedBox(height: 16),
+                OutlinedButton(
+                  onPressed: _busy
+                      ? null
+                      : () => _run(() async => _failureNotice(
+                            '同步到群',
+                            await _runtime.syncOwnDevices(),
+                          )),
+                  child: const Text('把本人其他设备加入我的群'),
+                ),
+                const SizedBox(height: 8),
+                OutlinedButton(
+                  onPressed: _busy
+                      ? null
+                      : () => _run(() async {
+                            await _runtime.requestHistoryFromOwnDevices();
+                            return '已发出请求,请在旧设备的「聊天设备」页同意';
+                          }),
+                  child: const Text('从旧设备迁移聊天记录'),
+                ),
+              ],
+            ),
+    );
+  }
+}
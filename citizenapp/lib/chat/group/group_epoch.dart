@@ -91,6 +91,7 @@ class GroupEpochOrdering {
           await bufferPut(result.groupId, result.messageEpoch, buffered);
           return;
         case GroupProcessStatus.stale:
+        case GroupProcessStatus.rejected:
         case GroupProcessStatus.unknown:
           // 丢弃,继续取同 epoch 下一条。
           break;
@@ -20,8 +20,9 @@ import 'group_epoch.dart';
 import 'group_fanout.dart';
 import 'group_membership.dart';
 import 'group_model.dart';
+import 'institution_group.dart';
 
-/// 群 ID 形如 `grp:<creator CID>:<nonce>`。
+/// 群 ID 形如 `grp:<creator CID>:<nonce>`;机构群 `inst:<机构 CID>:<范围>` 取机构 CID。
 String creatorCidNumberFromGroupId(String groupId) {
   final parts = groupId.split(':');
   return parts.length >= 2 ? parts[1] : '';
@@ -32,10 +33,9 @@ String newGroupId(String creatorCidNumber) {
   return 'grp:$creatorCidNumber:${_nonce()}';
 }
 
-/// 登记/清除某成员的待投递群媒体(离线补发按成员;键 attachmentId+成员 CID)。
-typedef GroupMemberMediaRecorder = Future<void> Function(
-  String attachmentId,
-  String memberCidNumber,
+/// 按 CID 领取 KeyPackage(机构群同步补加新任者;领不到的 CID 跳过)。
+typedef GroupKeyPackageFetcher = Future<List<MlsKeyPackage>> Function(
+  List<String> cidNumbers,
 );
 
 class ChatGroupFlow {
@@ -47,6 +47,7 @@ class ChatGroupFlow {
     required String cidNumber,
     required String currentAccountId,
     required String localDeviceId,
+    InstitutionRosterReader? institutionRosterReader,
     this.defaultTtlMillis = 30 * 24 * 60 * 60 * 1000,
   })  : _crypto = crypto,
         _store = store,
@@ -54,7 +55,8 @@ class ChatGroupFlow {
         _ownerCidNumber = ownerCidNumber,
         _cidNumber = cidNumber,
         _currentAccountId = currentAccountId,
-        _localDeviceId = localDeviceId;
+        _localDeviceId = localDeviceId,
+        _institutionRosterReader = institutionRosterReader;
 
   final MlsGroupCrypto _crypto;
   final ChatStore _store;
@@ -66,6 +68,9 @@ class ChatGroupFlow {
   final String _cidNumber;
   final String _currentAccountId;
   final String _localDeviceId;
+
+  /// 机构群的群管理员即链上机构管理员;普通群不使用。
+  final InstitutionRosterReader? _institutionRosterReader;
   final int defaultTtlMillis;
 
   /// 建群:创建者为唯一成员(admin),可选带初始邀请。
@@ -138,13 +143,15 @@ class ChatGroupFlow {
     required List<String> existingCidNumbers,
     required List<MlsKeyPackage> invitees,
   }) async {
+    // Commit 收件人按加人前的名册算:新进的本人设备只收 Welcome。
+    final commitRecipients =
+        await _fanoutRecipients(groupId, existingCidNumbers);
     final bundle = await _crypto.addMembers(groupId, invitees);
     final nowMillis = DateTime.now().millisecondsSinceEpoch;
 
-    // Welcome → 全部新人;Commit → 现有成员(减自己)。
+    // Welcome → 全部新人(含本人新设备);Commit → 现有成员(减本机)。
     final inviteeCidNumbers = cidNumbersFromMemberIdentities(
       invitees.map((keyPackage) => keyPackage.cidNumber),
-      excludeCidNumber: actorCidNumber,
     );
     final welcome = bundle.welcome;
     if (welcome != null && inviteeCidNumbers.isNotEmpty) {
@@ -158,9 +165,6 @@ class ChatGroupFlow {
         tag: 'welcome',
       );
     }
-    final commitRecipients = existingCidNumbers
-        .where((cidNumber) => cidNumber != actorCidNumber)
-        .toList();
     if (commitRecipients.isNotEmpty) {
       await _fanoutHandshake(
         wire: bundle.commit,
@@ -189,9 +193,8 @@ class ChatGroupFlow {
     final nowMillis = DateTime.now().millisecondsSinceEpoch;
 
     // Commit → 剩余成员 + 被删者(镜像此刻仍含被删者),都减自己。
-    final recipients = group.memberCidNumbers
-        .where((cidNumber) => cidNumber != actorCidNumber)
-        .toList();
+    final recipients =
+        await _fanoutRecipients(groupId, group.memberCidNumbers);
     if (recipients.isNotEmpty) {
       await _fanoutHandshake(
         wire: bundle.commit,
@@ -206,6 +209,126 @@ class ChatGroupFlow {
     await _reconcileFromChain(groupId, group.creatorCidNumber);
   }
 
+  /// 把本人其他设备加进群(任何在群成员均可,无需 admin):CID 名册不变,只多叶子。
+  /// 已在群内的设备自动跳过;无新设备时不产生握手。
+  Future<void> addOwnDevices({
+    required String groupId,
+    required List<MlsKeyPackage> keyPackages,
+  }) async {
+    final group = await _requireGroup(groupId);
+    if (group.leftLocally) {
+      return;
+    }
+    if (keyPackages.any((keyPackage) => keyPackage.cidNumber != _cidNumber)) {
+      throw ArgumentError('addOwnDevices 只接受本人 CID 的 KeyPackage');
+    }
+    final present =
+        (await _crypto.groupState(groupId)).memberIdentities.toSet();
+    final invitees = keyPackages
+        .where((keyPackage) => !present
+            .contains('${keyPackage.cidNumber}:${keyPackage.deviceId}'))
+        .toList();
+    if (invitees.isEmpty) {
+      return;
+    }
+    await _addMembersInternal(
+      groupId: groupId,
+      actorCidNumber: _cidNumber,
+      actorDeviceId: _localDeviceId,
+      creatorCidNumber: group.creatorCidNumber,
+      existingCidNumbers: group.memberCidNumbers,
+      invitees: invitees,
+    );
+  }
+
+  /// 吊销本人设备:只移除该设备叶子(后向保密),本人其余设备留在群内。
+  /// 该设备不在群内时返回 false。
+  Future<bool> removeOwnDevice({
+    required String groupId,
+    required String deviceId,
+  }) async {
+    final group = await _requireGroup(groupId);
+    if (group.leftLocally || deviceId == _localDeviceId) {
+      return false;
+    }
+    final identity = '$_cidNumber:$deviceId';
+    final state = await _crypto.groupState(groupId);
+    if (!state.memberIdentities.contains(identity)) {
+      return false;
+    }
+    // 收件人在移除前算,被吊销设备也收到 Commit 并得知自己出群。
+    final recipients =
+        await _fanoutRecipients(groupId, group.memberCidNumbers);
+    final bundle = await _crypto.removeDevices(groupId, [identity]);
+    if (recipients.isNotEmpty) {
+      await _fanoutHandshake(
+        wire: bundle.commit,
+        recipients: recipients,
+        senderCidNumber: _cidNumber,
+        senderDeviceId: _localDeviceId,
+        groupId: groupId,
+        nowMillis: DateTime.now().millisecondsSinceEpoch,
+        tag: 'commit',
+      );
+    }
+    await _reconcileFromChain(groupId, group.creatorCidNumber);
+    return true;
+  }
+
+  /// 机构群按链上岗位名册同步(机构管理员设备执行):先移除卸任成员,再为新任者
+  /// 领 KeyPackage 加人;本机已卸任则退群。名册无变化时不产生任何握手。
+  Future<InstitutionSyncBundle?> syncInstitutionGroup({
+    required String groupId,
+    required GroupKeyPackageFetcher fetchKeyPackages,
+  }) async {
+    final group = await _requireGroup(groupId);
+    if (group.leftLocally || parseInstitutionGroupId(groupId) == null) {
+      return null;
+    }
+    final bundle = await _crypto.syncInstitutionGroup(groupId);
+    final commit = bundle.commit;
+    if (commit != null) {
+      // Commit → 同步前全体成员(含被移除者),减自己。
+      final recipients =
+          await _fanoutRecipients(groupId, group.memberCidNumbers);
+      if (recipients.isNotEmpty) {
+        await _fanoutHandshake(
+          wire: commit,
+          recipients: recipients,
+          senderCidNumber: _cidNumber,
+          senderDeviceId: _localDeviceId,
+          groupId: groupId,
+          nowMillis: DateTime.now().millisecondsSinceEpoch,
+          tag: 'commit',
+        );
+      }
+      await _reconcileFromChain(groupId, group.creatorCidNumber);
+    }
+    if (bundle.selfExtraneous) {
+      await leaveGroup(groupId);
+      return bundle;
+    }
+    if (bundle.missingCidNumbers.isNotEmpty) {
+      final invitees = await fetchKeyPackages(bundle.missingCidNumbers);
+      if (invitees.isNotEmpty) {
+        final current = await _requireGroup(groupId);
+        GroupMembership.ensureCanAdd(
+          currentCount: current.roster.length,
+          addingCount: invitees.length,
+        );
+        await _addMembersInternal(
+          groupId: groupId,
+          actorCidNumber: _cidNumber,
+          actorDeviceId: _localDeviceId,
+          creatorCidNumber: current.creatorCidNumber,
+          existingCidNumbers: current.memberCidNumbers,
+          invitees: invitees,
+        );
+      }
+    }
+    return bundle;
+  }
+
   /// 退群:先发退群请求(群 admin 收到后自动 removeMembers 重钥,保证后向保密),
   /// 再本机即刻标记已退、停止参与。发送失败不阻断本机退出。
   Future<void> leaveGroup(String groupId) async {
@@ -232,9 +355,8 @@ class ChatGroupFlow {
   /// 广播群控制消息(改名/退群请求):走 E2E application 扇出,**不落聊天消息行**。
   Future<void> sendGroupControl(String groupId, GroupControl control) async {
     final group = await _requireGroup(groupId);
-    final recipients = group.memberCidNumbers
-        .where((cidNumber) => cidNumber != _cidNumber)
-        .toList();
+    final recipients =
+        await _fanoutRecipients(groupId, group.memberCidNumbers);
     if (recipients.isEmpty) {
       return;
     }
@@ -288,18 +410,15 @@ class ChatGroupFlow {
     );
   }
 
-  /// 群发媒体:控制消息单次加密扇 N;字节 **>100MB 走已部署中转**(一次上传 + K 扇 N),
-  /// **≤100MB 对每个成员逐个 WebRTC 直传**(口径 A,离线按成员补发)。四门按己档强制。
+  /// 群发媒体:字节一律经 MLS 导出密钥封装后**一次上传到已部署中转**,控制消息
+  /// 单次加密扇 N;不论大小都不走逐成员 WebRTC 直传。四门按己档强制。
   Future<List<ChatDeliveryResult>> sendGroupMedia({
     required String groupId,
     required String senderCidNumber,
     required String senderDeviceId,
     required ChatMediaDraft media,
-    required ChatAttachmentDeviceSender sendMemberAttachment,
-    ChatRelayUploader? uploadRelayMedia,
+    required ChatRelayUploader uploadRelayMedia,
     ChatLocalAttachmentSaver? saveLocalAttachment,
-    GroupMemberMediaRecorder? recordPendingMember,
-    GroupMemberMediaRecorder? markMemberDelivered,
   }) async {
     final group = await _requireGroup(groupId);
     if (group.leftLocally) {
@@ -316,22 +435,16 @@ class ChatGroupFlow {
     final nowMillis = DateTime.now().millisecondsSinceEpoch;
     final attachmentId = 'att-$nowMillis-${_nonce()}';
 
-    // 路由:>100MB **必须**经中转(一次上传),绝不走 WebRTC。
-    ChatRelayDescriptor? relay;
-    if (ChatMediaLimits.needsRelay(media.byteSize)) {
-      if (uploadRelayMedia == null) {
-        throw StateError('>100MB 媒体必须经 Cloudflare 中转,但中转未配置');
-      }
-      relay = await uploadRelayMedia(
-        conversationId: groupId,
-        attachmentId: attachmentId,
-        media: media,
-        // 群删时机:全部收件人(减自己)ack 后删,避免首个 ack 即删。
-        recipientCount: group.memberCidNumbers
-            .where((account) => account != senderCidNumber)
-            .length,
-      );
-    }
+    // 群附件只有一条路径:MLS 封装密文一次上传,收方按描述符拉取并校验哈希。
+    final relay = await uploadRelayMedia(
+      conversationId: groupId,
+      attachmentId: attachmentId,
+      media: media,
+      // 群删时机:全部收件人(减自己)ack 后删,避免首个 ack 即删。
+      recipientCount: group.memberCidNumbers
+          .where((account) => account != senderCidNumber)
+          .length,
+    );
 
     final payload = ChatPayloadCodec.encode(
       ChatContent.media(
@@ -344,10 +457,13 @@ class ChatGroupFlow {
         height: media.height,
         durationMs: media.durationMs,
         blurhash: media.blurhash,
-        relayObjectKey: relay?.relayObjectKey,
-        contentKeyB64: relay?.contentKeyB64,
-        chunkSize: relay?.chunkSize,
-        encSize: relay?.encSize,
+        relayObjectKey: relay.relayObjectKey,
+        contentKeyB64: relay.contentKeyB64,
+        chunkSize: relay.chunkSize,
+        encSize: relay.encSize,
+        mlsKeyHex: relay.mlsKeyHex,
+        contentHashHex: relay.contentHashHex,
+        ciphertextHashHex: relay.ciphertextHashHex,
       ),
     );
     // 控制消息单次加密扇 N + 落 1 逻辑媒体消息(复用共用编排)。
@@ -366,31 +482,6 @@ class ChatGroupFlow {
       sourcePath: media.sourcePath,
       byteSize: media.byteSize,
     );
-    // 中转路径:密文已在 R2,收方按需拉;不走 WebRTC。
-    if (relay != null) {
-      return results;
-    }
-    // ≤100MB：对每个成员 CID 逐个 WebRTC 直传（离线按成员留 pending 补发）。
-    final members = group.memberCidNumbers
-        .where((account) => account != senderCidNumber)
-        .toList(growable: false);
-    for (final member in members) {
-      await recordPendingMember?.call(attachmentId, member);
-      try {
-        await sendMemberAttachment(
-          recipientCidNumber: member,
-          conversationId: groupId,
-          attachmentId: attachmentId,
-          fileName: media.fileName,
-          contentType: media.contentType,
-          sourcePath: media.sourcePath,
-          byteSize: media.byteSize,
-        );
-        await markMemberDelivered?.call(attachmentId, member);
-      } on Exception {
-        // 该成员离线/直连失败:留 pending,peer_ready 补发。
-      }
-    }
     return results;
   }
 
@@ -409,9 +500,8 @@ class ChatGroupFlow {
     final nowMillis = DateTime.now().millisecondsSinceEpoch;
     final wire =
         await _crypto.groupCreateMessage(groupId, utf8.encode(payload));
-    final recipients = group.memberCidNumbers
-        .where((account) => account != senderCidNumber)
-        .toList();
+    final recipients =
+        await _fanoutRecipients(groupId, group.memberCidNumbers);
     final messageId = '$groupId-msg-$nowMillis-${_nonce()}';
     final envelopes = GroupFanout.fanOut(
       wire: wire,
@@ -473,7 +563,20 @@ class ChatGroupFlow {
     List<int> envelopeBytes,
   ) async {
     final envelope = ChatEnvelope.fromBuffer(envelopeBytes);
+    // Worker 按 CID 投递到本人全部设备:本机发出的回声直接丢弃。
+    if (envelope.senderCidNumber == _cidNumber &&
+        envelope.senderDeviceId == _localDeviceId) {
+      return null;
+    }
     final wire = imMlsWireMessageFromEnvelope(envelope);
+    // 发给本人新设备的 Welcome 也会到达已在群内的本人设备,忽略即可。
+    if (wire.messageKind == MlsMessageKind.welcome) {
+      final existing =
+          await _store.readGroup(_ownerCidNumber, envelope.conversationId);
+      if (existing != null && !existing.leftLocally) {
+        return null;
+      }
+    }
     try {
       final result = await GroupEpochOrdering.processOrdered(
         wire: wire,
@@ -530,6 +633,7 @@ class ChatGroupFlow {
           epoch: result.groupEpoch,
         );
         await _reconcileRosterFrom(result, creator);
+        await _evictStaleIfAdmin(result);
         // 回放入群前缓冲的同群消息。
         final pending = await _store.takePendingInbound(
           _ownerCidNumber,
@@ -544,6 +648,7 @@ class ChatGroupFlow {
           return;
         }
         await _reconcileRosterFrom(result, creator);
+        await _evictStaleIfAdmin(result);
       case GroupInboundKind.application:
         final plaintext = utf8.decode(result.plaintext ?? const []);
         // 群控制消息先判别:是控制则处理、绝不当聊天消息显示;否则落普通消息。
@@ -559,6 +664,7 @@ class ChatGroupFlow {
           envelopeBytes: envelopeBytes,
           messageKind: ChatPayloadCodec.decode(plaintext).kind,
           plaintext: plaintext,
+          fromOwnDevice: envelope.senderCidNumber == _cidNumber,
         );
       case GroupInboundKind.unknown:
         break;
@@ -594,13 +700,49 @@ class ChatGroupFlow {
     }
   }
 
+  /// 名册里出现链上授权失效(CID 换绑/注销)的叶子时,由 admin 提交驱逐 Commit;
+  /// 其余成员只等 admin 的 Commit 收敛,避免多人并发提交同一 epoch。
+  Future<void> _evictStaleIfAdmin(GroupInbound result) async {
+    if (result.staleMemberIdentities.isEmpty) {
+      return;
+    }
+    final group = await _store.readGroup(_ownerCidNumber, result.groupId);
+    if (group == null ||
+        group.leftLocally ||
+        !group.adminSet.contains(_cidNumber)) {
+      return;
+    }
+    final bundle = await _crypto.evictStaleMembers(result.groupId);
+    if (bundle == null) {
+      return;
+    }
+    final recipients =
+        await _fanoutRecipients(result.groupId, group.memberCidNumbers);
+    if (recipients.isNotEmpty) {
+      await _fanoutHandshake(
+        wire: bundle.commit,
+        recipients: recipients,
+        senderCidNumber: _cidNumber,
+        senderDeviceId: _localDeviceId,
+        groupId: result.groupId,
+        nowMillis: DateTime.now().millisecondsSinceEpoch,
+        tag: 'commit',
+      );
+    }
+    await _reconcileFromChain(result.groupId, group.creatorCidNumber);
+  }
+
   Future<void> _reconcileFromChain(
       String groupId, String creatorCidNumber) async {
     final state = await _crypto.groupState(groupId);
     await _store.reconcileGroupRoster(
       ownerCidNumber: _ownerCidNumber,
       groupId: groupId,
-      members: _rolesFor(state.memberIdentities, creatorCidNumber),
+      members: await _rolesFor(
+        groupId,
+        state.memberIdentities,
+        creatorCidNumber,
+      ),
       epoch: state.epoch,
     );
   }
@@ -613,24 +755,51 @@ class ChatGroupFlow {
     await _store.reconcileGroupRoster(
       ownerCidNumber: _ownerCidNumber,
       groupId: result.groupId,
-      members: _rolesFor(identities, creatorCidNumber),
+      members: await _rolesFor(result.groupId, identities, creatorCidNumber),
       epoch: result.groupEpoch,
     );
   }
 
-  Map<String, GroupMemberRole> _rolesFor(
+  /// 普通群 admin = 创建者;机构群 admin = 在群内的链上机构管理员。
+  Future<Map<String, GroupMemberRole>> _rolesFor(
+    String groupId,
     Iterable<String> identities,
     String creatorCidNumber,
-  ) {
+  ) async {
     final cidNumbers = cidNumbersFromMemberIdentities(identities);
+    final institution = parseInstitutionGroupId(groupId);
+    final reader = _institutionRosterReader;
+    final roster = institution == null || reader == null
+        ? null
+        : await reader(institution.institutionCidNumber, institution.scope);
+    bool isAdmin(String cidNumber) =>
+        roster?.isAdmin(cidNumber) ?? cidNumber == creatorCidNumber;
     return {
       for (final cidNumber in cidNumbers)
-        cidNumber: cidNumber == creatorCidNumber
-            ? GroupMemberRole.admin
-            : GroupMemberRole.member,
+        cidNumber:
+            isAdmin(cidNumber) ? GroupMemberRole.admin : GroupMemberRole.member,
     };
   }
 
+  /// 扇出收件 CID:群成员减本人;本人其他设备也在群内时加回本人 CID
+  /// (Worker 按 CID 投递到本人全部设备,本机回声在入站处丢弃)。
+  Future<List<String>> _fanoutRecipients(
+    String groupId,
+    Iterable<String> memberCidNumbers,
+  ) async {
+    final recipients = memberCidNumbers
+        .where((cidNumber) => cidNumber != _cidNumber)
+        .toList();
+    final ownIdentity = '$_cidNumber:$_localDeviceId';
+    final state = await _crypto.groupState(groupId);
+    if (state.memberIdentities.any((identity) =>
+        identity != ownIdentity &&
+        cidNumberFromMemberIdentity(identity) == _cidNumber)) {
+      recipients.add(_cidNumber);
+    }
+    return recipients;
+  }
+
   Future<void> _fanoutHandshake({
     required MlsWireMessage wire,
     required List<String> recipients,
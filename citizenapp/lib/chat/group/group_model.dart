@@ -67,3 +67,18 @@ class ChatGroup {
   List<String> get memberCidNumbers =>
       roster.map((member) => member.cidNumber).toList(growable: false);
 }
+
+/// 逐群操作(名册同步、补设备、吊销设备)中单个群的失败,交调用方展示或记录;
+/// 其余群照常处理,失败群下次同步重试。
+class GroupSyncFailure {
+  const GroupSyncFailure({
+    required this.groupId,
+    required this.error,
+  });
+
+  final String groupId;
+  final Object error;
+
+  @override
+  String toString() => '$groupId: $error';
+}
@@ -0,0 +1,112 @@
+// 机构岗位群:群名册跟随链上 `InstitutionRoleAssignments` / 机构管理员集合。
+//
+// 群 ID 固定为 `inst:<机构 CID>:<范围>`(范围 = 岗位码或 [kInstitutionAdminsScope]),
+// 进入 MLS GroupContext,由 OpenMLS 对群归属签名;谁能进群、谁能改名册由 Rust
+// `chat_institution.rs` 按本文件的 [InstitutionRoster] 快照逐次核对。
+// 名册由轻节点 finalized 状态拼出,本层只做纯数据,不读链。
+
+/// 全体机构管理员群的范围标记(岗位码不以 `@` 开头)。
+const String kInstitutionAdminsScope = '@admins';
+
+const String _institutionGroupPrefix = 'inst';
+
+/// 机构群 ID。
+String institutionGroupId(String institutionCidNumber, String scope) {
+  if (institutionCidNumber.isEmpty ||
+      institutionCidNumber.contains(':') ||
+      scope.isEmpty) {
+    throw ArgumentError('机构群需要合法的机构 CID 与岗位范围');
+  }
+  return '$_institutionGroupPrefix:$institutionCidNumber:$scope';
+}
+
+/// 解析机构群 ID;普通群 / 私聊返回 null。
+({String institutionCidNumber, String scope})? parseInstitutionGroupId(
+  String groupId,
+) {
+  final first = groupId.indexOf(':');
+  if (first < 0 || groupId.substring(0, first) != _institutionGroupPrefix) {
+    return null;
+  }
+  final second = groupId.indexOf(':', first + 1);
+  if (second < 0) {
+    return null;
+  }
+  final cidNumber = groupId.substring(first + 1, second);
+  final scope = groupId.substring(second + 1);
+  if (cidNumber.isEmpty || scope.isEmpty) {
+    return null;
+  }
+  return (institutionCidNumber: cidNumber, scope: scope);
+}
+
+/// conversation_id 是否走群编排(普通私密群或机构群)。
+bool isGroupConversationId(String conversationId) {
+  return conversationId.startsWith('grp:') ||
+      parseInstitutionGroupId(conversationId) != null;
+}
+
+/// 机构群名册快照。
+class InstitutionRoster {
+  const InstitutionRoster({
+    required this.institutionCidNumber,
+    required this.scope,
+    required this.memberCidNumbers,
+    required this.adminCidNumbers,
+  });
+
+  /// 由管理员人员集合与其有效任职拼出名册:岗位群取任该岗位者,管理员群取全体管理员。
+  ///
+  /// [admins] 每项为管理员 CID 与其当前有效岗位码;无 CID 的行(数据异常)跳过。
+  factory InstitutionRoster.fromAdmins({
+    required String institutionCidNumber,
+    required String scope,
+    required Iterable<({String cidNumber, Iterable<String> roleCodes})> admins,
+  }) {
+    final adminCidNumbers = <String>{};
+    final memberCidNumbers = <String>{};
+    for (final admin in admins) {
+      if (admin.cidNumber.isEmpty) {
+        continue;
+      }
+      adminCidNumbers.add(admin.cidNumber);
+      if (scope == kInstitutionAdminsScope || admin.roleCodes.contains(scope)) {
+        memberCidNumbers.add(admin.cidNumber);
+      }
+    }
+    return InstitutionRoster(
+      institutionCidNumber: institutionCidNumber,
+      scope: scope,
+      memberCidNumbers: memberCidNumbers.toList()..sort(),
+      adminCidNumbers: adminCidNumbers.toList()..sort(),
+    );
+  }
+
+  final String institutionCidNumber;
+  final String scope;
+
+  /// 应在群内的 CID。
+  final List<String> memberCidNumbers;
+
+  /// 有权提交名册变更(加人/删人)的机构管理员 CID。
+  final List<String> adminCidNumbers;
+
+  bool isAdmin(String cidNumber) => adminCidNumbers.contains(cidNumber);
+
+  /// 名册指纹:轮询比对链上任职是否变化。
+  String get fingerprint =>
+      '${memberCidNumbers.join(',')}|${adminCidNumbers.join(',')}';
+
+  Map<String, Object?> toJson() => {
+        'institution_cid_number': institutionCidNumber,
+        'scope': scope,
+        'member_cid_numbers': memberCidNumbers,
+        'admin_cid_numbers': adminCidNumbers,
+      };
+}
+
+/// 按机构 CID + 范围从 finalized 状态读取名册快照。
+typedef InstitutionRosterReader = Future<InstitutionRoster> Function(
+  String institutionCidNumber,
+  String scope,
+);
@@ -1,13 +1,16 @@
 // 大媒体(>100MB)中转编排:把 MediaRelayCrypto(客户端流式加密)与 Cloudflare R2
 // 瞬时中转 transport 串起来。发送=加密→申请槽→流式 PUT 密文;接收=换 URL→流式 GET
 // 密文→流式解密落盘→ack(触发服务端删)。全程流式,5GB 不进内存;Cloudflare 只经手
-// 密文、拿不到内容密钥。详见 memory/05-modules/citizenapp/chat/CHAT_GROUP_TECHNICAL.md §11。
+// 密文、拿不到内容密钥。群聊改由 Rust `chat_attachment` 按 MLS 附件密钥加解密
+// (`uploadMlsSealed`/`downloadMlsSealed`),中转编排不变。
+// 详见 memory/05-modules/citizenapp/chat/CHAT_GROUP_TECHNICAL.md §11。
 
 import 'dart:convert';
 import 'dart:io';
 import 'dart:math';
 
 import '../chat_flow.dart' show ChatRelayDescriptor;
+import '../crypto/mls_native.dart' show MlsSealedAttachment;
 import '../transport/chat_cloud_transport.dart';
 import 'media_relay_crypto.dart';
 
@@ -32,21 +35,13 @@ class ChatRelayMedia {
       key: key,
     );
     try {
-      // 服务端按**明文** byteSize 门控(>100MB,≤5GB);recipientCount 供群删时机。
-      final init = await transport.initRelayUpload(
+      final objectKey = await _putRelayObject(
+        transport: transport,
+        encPath: encPath,
+        encSize: encSize,
         byteSize: byteSize,
         recipientCount: recipientCount,
       );
-      final objectKey = (init['object_key'] ?? '').toString();
-      if (objectKey.isEmpty) {
-        throw StateError('中转上传槽申请失败');
-      }
-      await _streamPut(
-        transport.relayBlobUri(objectKey),
-        transport.sessionBearer,
-        encPath,
-        encSize,
-      );
       return ChatRelayDescriptor(
         relayObjectKey: objectKey,
         contentKeyB64: base64Encode(key),
@@ -58,6 +53,39 @@ class ChatRelayMedia {
     }
   }
 
+  /// 群聊版上传:由 [seal] 调 Rust 按 MLS exporter 派生的附件密钥把源文件加密到
+  /// 给定路径,再照常申请槽、流式 PUT。描述子带附件密钥与两枚哈希,无一次性 K。
+  static Future<ChatRelayDescriptor> uploadMlsSealed({
+    required ChatCloudTransport transport,
+    required int byteSize,
+    required Directory tempDirectory,
+    required Future<MlsSealedAttachment> Function(String outputPath) seal,
+    int recipientCount = 1,
+  }) async {
+    await tempDirectory.create(recursive: true);
+    final encPath = '${tempDirectory.path}/relay-up-${_nonce()}.enc';
+    try {
+      final sealed = await seal(encPath);
+      final objectKey = await _putRelayObject(
+        transport: transport,
+        encPath: encPath,
+        encSize: sealed.encryptedSize,
+        byteSize: byteSize,
+        recipientCount: recipientCount,
+      );
+      return ChatRelayDescriptor(
+        relayObjectKey: objectKey,
+        chunkSize: sealed.chunkSize,
+        encSize: sealed.encryptedSize,
+        mlsKeyHex: sealed.keyHex,
+        contentHashHex: sealed.contentHashHex,
+        ciphertextHashHex: sealed.ciphertextHashHex,
+      );
+    } finally {
+      await _deleteQuietly(encPath);
+    }
+  }
+
   /// 换取下载 URL → 流式 GET 密文 → 流式解密落 [destPath] → ack(触发服务端删)。
   static Future<void> download({
     required ChatCloudTransport transport,
@@ -87,6 +115,55 @@ class ChatRelayMedia {
     }
   }
 
+  /// 群聊版下载:流式 GET 密文后交 [open](Rust 核对密文哈希 → 分块解密 → 核对
+  /// 内容哈希,通过才落 destPath),成功再 ack。
+  static Future<void> downloadMlsSealed({
+    required ChatCloudTransport transport,
+    required String relayObjectKey,
+    required Directory tempDirectory,
+    required Future<void> Function(String encPath) open,
+  }) async {
+    await tempDirectory.create(recursive: true);
+    final encPath = '${tempDirectory.path}/relay-dl-${_nonce()}.enc';
+    try {
+      await _streamGet(
+        transport.relayBlobUri(relayObjectKey),
+        transport.sessionBearer,
+        encPath,
+      );
+      await open(encPath);
+      await transport.relayAck(relayObjectKey);
+    } finally {
+      await _deleteQuietly(encPath);
+    }
+  }
+
+  /// 申请上传槽并流式 PUT 密文,返回 R2 对象键。
+  static Future<String> _putRelayObject({
+    required ChatCloudTransport transport,
+    required String encPath,
+    required int encSize,
+    required int byteSize,
+    required int recipientCount,
+  }) async {
+    // 服务端按**明文** byteSize 门控(>100MB,≤5GB);recipientCount 供群删时机。
+    final init = await transport.initRelayUpload(
+      byteSize: byteSize,
+      recipientCount: recipientCount,
+    );
+    final objectKey = (init['object_key'] ?? '').toString();
+    if (objectKey.isEmpty) {
+      throw StateError('中转上传槽申请失败');
+    }
+    await _streamPut(
+      transport.relayBlobUri(objectKey),
+      transport.sessionBearer,
+      encPath,
+      encSize,
+    );
+    return objectKey;
+  }
+
   static Future<void> _streamPut(
     Uri uri,
     String? bearer,
@@ -35,6 +35,41 @@ class ChatStoredMessage {
   final String? plaintext;
 }
 
+/// 本人设备间历史迁移的一条消息(明文只在迁移包 HPKE 密文内出现)。
+class ChatHistoryRecord {
+  const ChatHistoryRecord({
+    required this.envelopeId,
+    required this.conversationId,
+    required this.conversationTitle,
+    required this.conversationKind,
+    required this.peerCidNumber,
+    required this.direction,
+    required this.senderCidNumber,
+    required this.recipientCidNumber,
+    required this.senderDeviceId,
+    required this.messageKind,
+    required this.deliveryState,
+    required this.createdAtMillis,
+    this.plaintext,
+  });
+
+  final String envelopeId;
+  final String conversationId;
+  final String conversationTitle;
+
+  /// `dm` / `group`。
+  final String conversationKind;
+  final String peerCidNumber;
+  final String direction;
+  final String senderCidNumber;
+  final String recipientCidNumber;
+  final String senderDeviceId;
+  final String messageKind;
+  final String deliveryState;
+  final int createdAtMillis;
+  final String? plaintext;
+}
+
 /// 仅保存在发送设备上的待重试密文。
 class ChatQueuedEnvelope {
   const ChatQueuedEnvelope({
@@ -1451,7 +1486,163 @@ class ChatStore {
     });
   }
 
+  /// 导出本机最近 [limit] 条聊天记录(按时间升序),供本人新设备历史迁移。
+  Future<List<ChatHistoryRecord>> exportHistory({
+    required String ownerCidNumber,
+    required String currentAccountId,
+    int limit = 2000,
+  }) async {
+    final binding = await _crypto.resolveCipherBinding(
+      ownerCidNumber: ownerCidNumber,
+      currentAccountId: currentAccountId,
+    );
+    return _walletIsar.read((isar) async {
+      final conversations = {
+        for (final row in await isar.chatConversationEntitys
+            .filter()
+            .idGreaterThan(0, include: true)
+            .findAll())
+          if (row.ownerCidNumber == ownerCidNumber) row.conversationId: row,
+      };
+      final rows = (await isar.chatMessageEntitys
+              .filter()
+              .idGreaterThan(0, include: true)
+              .findAll())
+          .where((row) =>
+              row.ownerCidNumber == ownerCidNumber &&
+              row.bindingRevision == binding.bindingRevision &&
+              row.accountId == binding.accountId)
+          .toList(growable: false)
+        ..sort((a, b) => b.createdAtMillis.compareTo(a.createdAtMillis));
+      final recent = rows.take(limit).toList().reversed;
+      final out = <ChatHistoryRecord>[];
+      for (final row in recent) {
+        final conversation = conversations[row.conversationId];
+        out.add(ChatHistoryRecord(
+          envelopeId: row.envelopeId,
+          conversationId: row.conversationId,
+          conversationTitle: conversation?.title ?? row.conversationId,
+          conversationKind: conversation?.conversationKind ?? 'dm',
+          peerCidNumber: conversation?.peerCidNumber ?? row.senderCidNumber,
+          direction: row.direction,
+          senderCidNumber: row.senderCidNumber,
+          recipientCidNumber: row.recipientCidNumber,
+          senderDeviceId: row.senderDeviceId,
+          messageKind: row.messageKind,
+          deliveryState: row.deliveryState,
+          createdAtMillis: row.createdAtMillis,
+          plaintext: await _openMessage(row, currentAccountId),
+        ));
+      }
+      return out;
+    });
+  }
+
+  /// 导入本人老设备迁移来的历史:本机已有的消息(同 envelopeId)跳过,正文按本机
+  /// 子钥重新加密落库,不计未读。返回新写入条数。
+  Future<int> importHistory({
+    required String ownerCidNumber,
+    required String currentAccountId,
+    required List<ChatHistoryRecord> records,
+  }) async {
+    final binding = await _crypto.resolveCipherBinding(
+      ownerCidNumber: ownerCidNumber,
+      currentAccountId: currentAccountId,
+    );
+    final existing = await _walletIsar.read((isar) async {
+      final ids = <String>{};
+      for (final record in records) {
+        final row = await isar.chatMessageEntitys
+            .getByOwnerCidNumberEnvelopeId(ownerCidNumber, record.envelopeId);
+        if (row != null) ids.add(record.envelopeId);
+      }
+      return ids;
+    });
+    final fresh = records
+        .where((record) => !existing.contains(record.envelopeId))
+        .toList(growable: false);
+    if (fresh.isEmpty) {
+      return 0;
+    }
+    final sealed = <String, _SealedMessage>{};
+    final summaries = <String, (ChatHistoryRecord, String)>{};
+    for (final record in fresh) {
+      sealed[record.envelopeId] = await _sealMessage(
+        ownerCidNumber: ownerCidNumber,
+        currentAccountId: currentAccountId,
+        envelopeId: record.envelopeId,
+        plaintext: record.plaintext,
+      );
+      final latest = summaries[record.conversationId]?.$1;
+      if (latest == null || record.createdAtMillis >= latest.createdAtMillis) {
+        summaries[record.conversationId] = (
+          record,
+          await _sealSummary(
+            ownerCidNumber: ownerCidNumber,
+            currentAccountId: currentAccountId,
+            conversationId: record.conversationId,
+            plaintext: record.plaintext,
+          ),
+        );
+      }
+    }
+    await _walletIsar.writeTxn((isar) async {
+      for (final (record, summaryCipher) in summaries.values) {
+        final conversation = await isar.chatConversationEntitys
+            .getByOwnerCidNumberConversationId(
+                ownerCidNumber, record.conversationId);
+        // 本机已有更新的会话摘要时保留,只补消息行。
+        if (conversation != null &&
+            conversation.lastUpdatedAtMillis >= record.createdAtMillis) {
+          continue;
+        }
+        final entity = conversation ?? ChatConversationEntity();
+        entity
+          ..ownerCidNumber = ownerCidNumber
+          ..bindingRevision = binding.bindingRevision
+          ..accountId = binding.accountId
+          ..conversationId = record.conversationId
+          ..peerCidNumber = conversation?.peerCidNumber ?? record.peerCidNumber
+          ..title = conversation?.title ?? record.conversationTitle
+          ..conversationKind =
+              conversation?.conversationKind ?? record.conversationKind
+          ..lastMessageCipher = summaryCipher
+          ..lastUpdatedAtMillis = record.createdAtMillis
+          ..unreadCount = conversation?.unreadCount ?? 0
+          ..lastDeliveryState = record.deliveryState;
+        await isar.chatConversationEntitys
+            .putByOwnerCidNumberConversationId(entity);
+      }
+      for (final record in fresh) {
+        final message = sealed[record.envelopeId]!;
+        await isar.chatMessageEntitys.putByOwnerCidNumberEnvelopeId(
+          ChatMessageEntity()
+            ..ownerCidNumber = ownerCidNumber
+            ..bindingRevision = binding.bindingRevision
+            ..accountId = binding.accountId
+            ..envelopeId = record.envelopeId
+            ..conversationId = record.conversationId
+            ..direction = record.direction
+            ..senderCidNumber = record.senderCidNumber
+            ..recipientCidNumber = record.recipientCidNumber
+            ..senderDeviceId = record.senderDeviceId
+            ..messageKind = record.messageKind
+            ..mlsMessageKind =
+                MlsWireMessageKind.MLS_WIRE_MESSAGE_KIND_APPLICATION.name
+            ..deliveryState = record.deliveryState
+            ..plaintextCipher = message.cipher
+            ..searchTokens = message.tokens
+            ..envelopeBytesHex = ''
+            ..createdAtMillis = record.createdAtMillis,
+        );
+      }
+    });
+    return fresh.length;
+  }
+
   /// 群收到:一条入站逻辑消息(该成员就收到一封)。会话保持群名,不被发送方覆盖。
+  ///
+  /// [fromOwnDevice]:本人其他设备发出的群消息,按本机已发落库、不计未读。
   Future<void> saveIncomingGroupMessage({
     required String ownerCidNumber,
     required String currentAccountId,
@@ -1459,7 +1650,11 @@ class ChatStore {
     required List<int> envelopeBytes,
     required ChatMessageKind messageKind,
     required String plaintext,
+    bool fromOwnDevice = false,
   }) async {
+    final deliveryState = fromOwnDevice
+        ? ChatMessageDeliveryState.sent
+        : ChatMessageDeliveryState.receivedByDevice;
     final binding = await _crypto.resolveCipherBinding(
       ownerCidNumber: ownerCidNumber,
       currentAccountId: currentAccountId,
@@ -1485,8 +1680,8 @@ class ChatStore {
         groupId: envelope.conversationId,
         lastMessageCipher: summaryCipher,
         lastUpdatedAtMillis: envelope.createdAtMillis.toInt(),
-        unreadDelta: 1,
-        deliveryState: ChatMessageDeliveryState.receivedByDevice,
+        unreadDelta: fromOwnDevice ? 0 : 1,
+        deliveryState: deliveryState,
       );
       await isar.chatMessageEntitys.putByOwnerCidNumberEnvelopeId(
         _messageEntity(
@@ -1495,9 +1690,9 @@ class ChatStore {
           accountId: binding.accountId,
           envelope: envelope,
           envelopeBytes: envelopeBytes,
-          direction: 'incoming',
+          direction: fromOwnDevice ? 'outgoing' : 'incoming',
           messageKind: messageKind,
-          deliveryState: ChatMessageDeliveryState.receivedByDevice,
+          deliveryState: deliveryState,
           plaintextCipher: sealed.cipher,
           searchTokens: sealed.tokens,
         ),
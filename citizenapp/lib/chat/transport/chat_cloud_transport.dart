@@ -59,6 +59,28 @@ class ChatCloudTransport implements ChatTransport {
     });
   }
 
+  /// 本身份在册的 Chat 设备 ID(含本机),按登记先后排序。
+  Future<List<String>> listDevices() async {
+    final json = await _getJson('/chat/devices');
+    final items = json['devices'];
+    if (items is! List) {
+      throw const FormatException('Cloudflare 设备名册响应格式无效');
+    }
+    return items
+        .whereType<Map<String, dynamic>>()
+        .map((item) => (item['device_id'] ?? '').toString())
+        .where((deviceId) => deviceId.isNotEmpty)
+        .toList(growable: false);
+  }
+
+  /// 由本机吊销本人另一台设备:Worker 删除其设备行与未领取的 KeyPackage。
+  Future<void> revokeDevice(String deviceId) async {
+    await _postJson('/chat/devices/revoke', {
+      'device_id': localDeviceId,
+      'revoked_device_id': deviceId,
+    });
+  }
+
   /// 发布本机 KeyPackage。[cidNumber] 为**本身份**主键 CID 号（Worker 按 CID 归档）。
   Future<void> publishKeyPackage(
     MlsKeyPackage keyPackage, {
@@ -1,3 +1,4 @@
+import 'dart:async';
 import 'dart:convert';
 import 'dart:typed_data';
 
@@ -36,6 +37,13 @@ class InstitutionAdminService {
   final ChainRpc _rpc;
   final AdminAccountService _accountService;
 
+  static final StreamController<String> _assignmentRefreshes =
+      StreamController<String>.broadcast();
+
+  /// 每次从链上读到某机构的岗位任职后发出该机构 CID(机构群据此对齐名册)。
+  /// 机构群自身读名册时传 `announce: false`,避免同步触发同步。
+  static Stream<String> get assignmentRefreshes => _assignmentRefreshes.stream;
+
   Future<List<AdminPerson>> fetchAdmins(AdminAccountIdentity identity) {
     return _accountService.fetchAdmins(identity);
   }
@@ -44,8 +52,9 @@ class InstitutionAdminService {
   /// 管理员可以暂时不担任任何岗位，因此不要求岗位任职覆盖全部管理员。
   Future<List<InstitutionAdminAssignment>> fetchAssignments(
     AdminAccountIdentity identity,
-    String cidNumber,
-  ) async {
+    String cidNumber, {
+    bool announce = true,
+  }) async {
     if (identity.type == AdminAccountIdentityType.personalAccount) {
       throw ArgumentError('个人多签不属于机构岗位模型');
     }
@@ -83,17 +92,20 @@ class InstitutionAdminService {
         }
       }
     }
+    if (announce) _assignmentRefreshes.add(cidNumber);
     return out;
   }
 
   /// 以管理员人员集合为主表左连接岗位任职；管理员无岗位时仍返回人员行。
   Future<List<InstitutionAdminView>> fetchAdminViews(
     AdminAccountIdentity identity,
-    String cidNumber,
-  ) async {
+    String cidNumber, {
+    bool announce = true,
+  }) async {
     final account = await _accountService.fetchByIdentity(identity);
     if (account == null) return const [];
-    final assignments = await fetchAssignments(identity, cidNumber);
+    final assignments =
+        await fetchAssignments(identity, cidNumber, announce: announce);
     return mergeAdminViews(account.admins, assignments);
   }
 
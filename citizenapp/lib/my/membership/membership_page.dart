@@ -1170,6 +1170,7 @@ class _ActiveMembershipBanner extends StatelessWidget {
       'terminated' => '扣款失败 · 订阅已终止',
       'suspended' => '已挂起 · 待重新签名或充值',
       'issuerPaused' => '创作者暂停 · 恢复后自动续',
+      'pastDue' => '扣款失败 · 宽限期内自动重试',
       _ => '链上到期自动续费',
     };
     final window =
@@ -14,6 +14,8 @@ import 'package:citizenapp/8964/profile/services/square_session_provider.dart';
 import 'package:citizenapp/8964/profile/user_profile_page.dart';
 import 'package:citizenapp/8964/profile/widgets/local_identity_avatar.dart';
 import 'package:citizenapp/8964/services/square_api_client.dart';
+import 'package:citizenapp/chat/devices/device_history_transfer.dart';
+import 'package:citizenapp/chat/devices/own_devices_page.dart';
 import 'package:citizenapp/my/myid/identity_account_cache.dart';
 import 'package:citizenapp/my/myid/identity_account_resolver.dart';
 import 'package:citizenapp/my/myid/identity_badge_snapshot_store.dart';
@@ -1040,6 +1042,23 @@ class _SettingsPageState extends State<_SettingsPage> {
                         value: _pinLockEnabled,
                         onChanged: _deviceLockEnabled ? null : _togglePinLock,
                       ),
+                      const Divider(height: 1, indent: 56, endIndent: 16),
+                      ValueListenableBuilder<List<PendingHistoryRequest>>(
+                        valueListenable: HistoryRequestInbox.instance.pending,
+                        builder: (context, requests, _) => _buildNavTile(
+                          icon: Icons.devices_rounded,
+                          title: '聊天设备',
+                          subtitle: requests.isEmpty
+                              ? '管理登录 Chat 的本人设备与聊天记录迁移'
+                              : '有 ${requests.length} 条聊天记录迁移请求待确认',
+                          highlight: requests.isNotEmpty,
+                          onTap: () => Navigator.of(context).push(
+                            MaterialPageRoute(
+                              builder: (_) => const OwnDevicesPage(),
+                            ),
+                          ),
+                        ),
+                      ),
                     ],
                   ),
                 ),
@@ -1176,6 +1195,61 @@ class _SettingsPageState extends State<_SettingsPage> {
     );
   }
 
+  Widget _buildNavTile({
+    required IconData icon,
+    required String title,
+    required String subtitle,
+    required VoidCallback onTap,
+    bool highlight = false,
+  }) {
+    return InkWell(
+      onTap: onTap,
+      child: Padding(
+        padding: const EdgeInsets.symmetric(horizontal: 16, vertical: 14),
+        child: Row(
+          children: [
+            Container(
+              width: 36,
+              height: 36,
+              decoration: BoxDecoration(
+                color: AppTheme.primary.withAlpha(20),
+                borderRadius: BorderRadius.circular(8),
+              ),
+              child: Icon(icon, size: 20, color: AppTheme.primary),
+            ),
+            const SizedBox(width: 14),
+            Expanded(
+              child: Column(
+                crossAxisAlignment: CrossAxisAlignment.start,
+                children: [
+                  Text(
+                    title,
+                    style: const TextStyle(
+                      fontSize: 15,
+                      fontWeight: FontWeight.w500,
+                      color: AppTheme.textPrimary,
+                    ),
+                  ),
+                  const SizedBox(height: 2),
+                  Text(
+                    subtitle,
+                    style: TextStyle(
+                      fontSize: 12,
+                      color:
+                          highlight ? AppTheme.danger : AppTheme.textTertiary,
+                    ),
+                  ),
+                ],
+              ),
+            ),
+            const Icon(Icons.chevron_right_rounded,
+                color: AppTheme.textTertiary),
+          ],
+        ),
+      ),
+    );
+  }
+
   Widget _buildUpdateButton() {
     final state = _updateController.state;
     if (!state.hasUpdate) {
@@ -342,6 +342,49 @@ class CitizenIdentityRpc {
     }
   }
 
+  /// 在最新 finalized 区块批量读取 CID 当前绑定:`AccountIdByCid` +
+  /// `BindingRevisionByCid`。Chat MLS 据此逐叶校验群成员凭证授权;账户缺失
+  /// (注销/解绑)时 `accountIdHex` 为 null、`bindingRevision` 为 0。
+  Future<
+      List<
+          ({
+            String cidNumber,
+            String? accountIdHex,
+            BigInt bindingRevision,
+          })>> fetchFinalizedCidBindings(List<String> cidNumbers) async {
+    final finalized = await _rpc.fetchFinalizedBlock();
+    final blockHashHex =
+        '0x${SignedExtrinsicBuilder.hexEncode(finalized.blockHash)}';
+    return Future.wait(cidNumbers.map((cidNumber) async {
+      final cidScale = _encodedCid(cidNumber);
+      final rows = await Future.wait<Uint8List?>([
+        _rpc.fetchStorageAtBlock(
+          _hex(_storageMapKey('CitizenIdentity', 'AccountIdByCid', cidScale)),
+          blockHashHex,
+        ),
+        _rpc.fetchStorageAtBlock(
+          _hex(_storageMapKey(
+            'CitizenIdentity',
+            'BindingRevisionByCid',
+            cidScale,
+          )),
+          blockHashHex,
+        ),
+      ]);
+      final account = rows[0];
+      if (account != null && account.length != 32) {
+        throw const FormatException('AccountIdByCid 必须为 32 字节 AccountId32');
+      }
+      return (
+        cidNumber: cidNumber,
+        accountIdHex: account == null ? null : _hex(account),
+        bindingRevision: account == null || rows[1] == null
+            ? BigInt.zero
+            : _decodeU64(rows[1], 'BindingRevisionByCid'),
+      );
+    }));
+  }
+
   // ──── 内部：extrinsic 编码 ────
 
   /// `self_occupy_cid` call data:`[10][5][BoundedVec<u8>(cid)]`。
@@ -540,6 +540,7 @@ class SubscriptionRpc {
       2 => 'terminated',
       3 => 'suspended',
       4 => 'issuerPaused',
+      5 => 'pastDue',
       _ => throw const FormatException('subscription_status 枚举不合法'),
     };
     final authorizedPriceFen = reader.u128();
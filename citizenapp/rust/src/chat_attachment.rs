@@ -0,0 +1,411 @@
+//! MLS 聊天附件加密：图片/文件在 Rust 侧按块流式 AES-256-GCM 加密后才交给上传通道。
+//!
+//! 每个附件一把独立密钥，由发送时所在 epoch 的 MLS exporter secret 按
+//! [`ATTACHMENT_EXPORTER_LABEL`] + 附件 id 派生(见 `chat_mls::attachment_seal_json`)；
+//! 密钥与内容哈希随 MLS application message 端到端送达，接收方不依赖自己的 epoch
+//! 重新派生，群在上传期间换代也能解开。密文不离开本地之前已经与 MLS 绑定，
+//! Worker 的 `media`/`uploads` 与中继只经手密文。
+//!
+//! 密文布局：按 `chunk_size` 切明文，每块独立输出 `ciphertext || tag(16)` 顺序拼接。
+//! 块 nonce = 块序号(u64 大端) || 末块标志，AAD 绑定附件 id——
+//! 换块、删块、截断、跨附件拼接都会在解密时失败。空文件也输出一个空末块。
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    AeadCore, Aes256Gcm, Nonce,
+};
+use sha2::{Digest, Sha256};
+
+/// MLS exporter 标签；context 为附件 id 的 UTF-8 字节。
+pub(crate) const ATTACHMENT_EXPORTER_LABEL: &str = "citizenapp.chat/attachment|key";
+/// 每块 AAD 前缀，其后拼附件 id。
+const ATTACHMENT_AAD_PREFIX: &[u8] = b"citizenapp.chat/attachment|chunk";
+/// 默认块大小：64 KiB，兼顾内存占用与 tag 开销。
+pub(crate) const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+const MIN_CHUNK_SIZE: usize = 4 * 1024;
+const MAX_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+/// 附件 id 上限：进 AAD 与 exporter context，只接受短标识。
+const MAX_ATTACHMENT_ID_LEN: usize = 128;
+const TAG_LEN: usize = 16;
+
+/// 加密结果摘要：随 MLS 消息下发的元数据。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SealedAttachment {
+    pub(crate) byte_size: u64,
+    pub(crate) encrypted_size: u64,
+    pub(crate) chunk_count: u64,
+    /// 明文 SHA-256：解密后核对，证明拿到的正是发送方的文件。
+    pub(crate) content_hash: [u8; 32],
+    /// 密文 SHA-256：下载完即可核对，无需密钥，便于断点续传后校验。
+    pub(crate) ciphertext_hash: [u8; 32],
+}
+
+/// 解密结果摘要。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct OpenedAttachment {
+    pub(crate) byte_size: u64,
+    pub(crate) content_hash: [u8; 32],
+}
+
+/// 校验块大小；缺省取 [`DEFAULT_CHUNK_SIZE`]。
+pub(crate) fn resolve_chunk_size(chunk_size: Option<usize>) -> Result<usize, String> {
+    let size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+    if !(MIN_CHUNK_SIZE..=MAX_CHUNK_SIZE).contains(&size) {
+        return Err(format!(
+            "附件块大小须在 {MIN_CHUNK_SIZE}..={MAX_CHUNK_SIZE} 字节之间"
+        ));
+    }
+    Ok(size)
+}
+
+/// 校验附件 id：非空、不超长、仅可见 ASCII(与 Dart 侧 UUID/hex 标识一致)。
+pub(crate) fn validate_attachment_id(attachment_id: &str) -> Result<(), String> {
+    if attachment_id.is_empty() || attachment_id.len() > MAX_ATTACHMENT_ID_LEN {
+        return Err(format!(
+            "attachment_id 长度须在 1..={MAX_ATTACHMENT_ID_LEN} 之间"
+        ));
+    }
+    if !attachment_id.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err("attachment_id 只能包含可见 ASCII 字符".to_string());
+    }
+    Ok(())
+}
+
+/// 流式加密：逐块读明文、逐块写密文，内存只持两块。
+pub(crate) fn encrypt_stream<R: Read, W: Write>(
+    key: &[u8; 32],
+    attachment_id: &str,
+    chunk_size: usize,
+    mut source: R,
+    mut sink: W,
+) -> Result<SealedAttachment, String> {
+    let cipher = cipher(key)?;
+    let aad = chunk_aad(attachment_id);
+    let mut content_hasher = Sha256::new();
+    let mut ciphertext_hasher = Sha256::new();
+    let mut byte_size = 0u64;
+    let mut encrypted_size = 0u64;
+    let mut index = 0u64;
+
+    // 预读下一块才能判断当前块是否为末块。
+    let mut current = read_chunk(&mut source, chunk_size)?;
+    loop {
+        let next = if current.len() == chunk_size {
+            read_chunk(&mut source, chunk_size)?
+        } else {
+            Vec::new()
+        };
+        let last = next.is_empty();
+        content_hasher.update(&current);
+        byte_size += current.len() as u64;
+        let sealed = cipher
+            .encrypt(
+                &chunk_nonce(index, last),
+                Payload {
+                    msg: &current,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| "附件加密失败".to_string())?;
+        ciphertext_hasher.update(&sealed);
+        encrypted_size += sealed.len() as u64;
+        sink.write_all(&sealed)
+            .map_err(|error| format!("写入附件密文失败: {error}"))?;
+        index += 1;
+        if last {
+            break;
+        }
+        current = next;
+    }
+    sink.flush()
+        .map_err(|error| format!("写入附件密文失败: {error}"))?;
+
+    Ok(SealedAttachment {
+        byte_size,
+        encrypted_size,
+        chunk_count: index,
+        content_hash: content_hasher.finalize().into(),
+        ciphertext_hash: ciphertext_hasher.finalize().into(),
+    })
+}
+
+/// 流式解密并核对明文哈希。任何一块认证失败、缺末块或哈希不符都报错；
+/// 调用方须丢弃已写出的部分(文件版 [`decrypt_file`] 已处理)。
+pub(crate) fn decrypt_stream<R: Read, W: Write>(
+    key: &[u8; 32],
+    attachment_id: &str,
+    chunk_size: usize,
+    expected_content_hash: &[u8; 32],
+    mut source: R,
+    mut sink: W,
+) -> Result<OpenedAttachment, String> {
+    let cipher = cipher(key)?;
+    let aad = chunk_aad(attachment_id);
+    let sealed_size = chunk_size + TAG_LEN;
+    let mut content_hasher = Sha256::new();
+    let mut byte_size = 0u64;
+    let mut index = 0u64;
+
+    let mut current = read_chunk(&mut source, sealed_size)?;
+    loop {
+        if current.len() < TAG_LEN {
+            return Err("附件密文被截断".to_string());
+        }
+        let next = if current.len() == sealed_size {
+            read_chunk(&mut source, sealed_size)?
+        } else {
+            Vec::new()
+        };
+        let last = next.is_empty();
+        let clear = cipher
+            .decrypt(
+                &chunk_nonce(index, last),
+                Payload {
+                    msg: &current,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| format!("附件第 {index} 块解密失败:密钥不匹配或密文被篡改/截断"))?;
+        content_hasher.update(&clear);
+        byte_size += clear.len() as u64;
+        sink.write_all(&clear)
+            .map_err(|error| format!("写入附件明文失败: {error}"))?;
+        index += 1;
+        if last {
+            break;
+        }
+        current = next;
+    }
+    sink.flush()
+        .map_err(|error| format!("写入附件明文失败: {error}"))?;
+
+    let content_hash: [u8; 32] = content_hasher.finalize().into();
+    if content_hash != *expected_content_hash {
+        return Err("附件内容哈希与 MLS 消息不符".to_string());
+    }
+    Ok(OpenedAttachment {
+        byte_size,
+        content_hash,
+    })
+}
+
+/// 文件版加密：写临时文件后原子改名，失败不留半截密文。
+pub(crate) fn encrypt_file(
+    key: &[u8; 32],
+    attachment_id: &str,
+    chunk_size: usize,
+    source_path: &Path,
+    output_path: &Path,
+) -> Result<SealedAttachment, String> {
+    let source = File::open(source_path).map_err(|error| format!("打开附件原文件失败: {error}"))?;
+    write_via_temp(output_path, |sink| {
+        encrypt_stream(key, attachment_id, chunk_size, BufReader::new(source), sink)
+    })
+}
+
+/// 文件版解密：明文先写临时文件，哈希核对通过才改名到 `output_path`。
+pub(crate) fn decrypt_file(
+    key: &[u8; 32],
+    attachment_id: &str,
+    chunk_size: usize,
+    expected_content_hash: &[u8; 32],
+    source_path: &Path,
+    output_path: &Path,
+) -> Result<OpenedAttachment, String> {
+    let source = File::open(source_path).map_err(|error| format!("打开附件密文失败: {error}"))?;
+    write_via_temp(output_path, |sink| {
+        decrypt_stream(
+            key,
+            attachment_id,
+            chunk_size,
+            expected_content_hash,
+            BufReader::new(source),
+            sink,
+        )
+    })
+}
+
+/// 核对已下载密文的大小与 SHA-256；不需要密钥。
+pub(crate) fn verify_ciphertext_file(
+    path: &Path,
+    expected_size: Option<u64>,
+    expected_hash: &[u8; 32],
+) -> Result<(), String> {
+    let mut source =
+        BufReader::new(File::open(path).map_err(|error| format!("打开附件密文失败: {error}"))?);
+    let mut hasher = Sha256::new();
+    let mut size = 0u64;
+    let mut buf = vec![0u8; DEFAULT_CHUNK_SIZE];
+    loop {
+        let read = source
+            .read(&mut buf)
+            .map_err(|error| format!("读取附件密文失败: {error}"))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+        size += read as u64;
+    }
+    if expected_size.is_some_and(|expected| expected != size) {
+        return Err(format!("附件密文大小不符:实际 {size} 字节"));
+    }
+    let hash: [u8; 32] = hasher.finalize().into();
+    if hash != *expected_hash {
+        return Err("附件密文哈希不符".to_string());
+    }
+    Ok(())
+}
+
+/// 解析 32 字节 hex 字段(密钥、哈希)。
+pub(crate) fn parse_32(field_name: &str, value: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|error| format!("{field_name} 不是合法 hex: {error}"))?;
+    <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| format!("{field_name} 必须为 32 字节"))
+}
+
+fn cipher(key: &[u8; 32]) -> Result<Aes256Gcm, String> {
+    Aes256Gcm::new_from_slice(key).map_err(|error| format!("构造附件加密器失败: {error}"))
+}
+
+fn chunk_aad(attachment_id: &str) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(ATTACHMENT_AAD_PREFIX.len() + 1 + attachment_id.len());
+    aad.extend_from_slice(ATTACHMENT_AAD_PREFIX);
+    aad.push(b'|');
+    aad.extend_from_slice(attachment_id.as_bytes());
+    aad
+}
+
+/// 12 字节 nonce：3 字节 0 || 块序号(u64 大端) || 末块标志。每附件独立密钥，序号不会复用。
+fn chunk_nonce(index: u64, last: bool) -> Nonce<<Aes256Gcm as AeadCore>::NonceSize> {
+    let mut nonce = [0u8; 12];
+    nonce[3..11].copy_from_slice(&index.to_be_bytes());
+    nonce[11] = u8::from(last);
+    *Nonce::from_slice(&nonce)
+}
+
+/// 读满 `len` 字节或读到 EOF。
+fn read_chunk<R: Read>(source: &mut R, len: usize) -> Result<Vec<u8>, String> {
+    let mut buf = Vec::with_capacity(len);
+    source
+        .take(len as u64)
+        .read_to_end(&mut buf)
+        .map_err(|error| format!("读取附件失败: {error}"))?;
+    Ok(buf)
+}
+
+fn write_via_temp<T>(
+    output_path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> Result<T, String>,
+) -> Result<T, String> {
+    let temp = temp_path(output_path);
+    let file = File::create(&temp).map_err(|error| format!("创建附件输出文件失败: {error}"))?;
+    let mut sink = BufWriter::new(file);
+    let result = write(&mut sink).and_then(|value| {
+        sink.into_inner()
+            .map_err(|error| format!("写入附件输出失败: {error}"))?
+            .sync_all()
+            .map_err(|error| format!("落盘附件输出失败: {error}"))?;
+        Ok(value)
+    });
+    match result {
+        Ok(value) => {
+            fs::rename(&temp, output_path)
+                .map_err(|error| format!("替换附件输出文件失败: {error}"))?;
+            Ok(value)
+        }
+        Err(error) => {
+            let _ = fs::remove_file(&temp);
+            Err(error)
+        }
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".partial");
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decrypt_stream, encrypt_stream, resolve_chunk_size, validate_attachment_id, TAG_LEN,
+    };
+
+    const KEY: [u8; 32] = [7u8; 32];
+    const CHUNK: usize = 4 * 1024;
+
+    fn seal(plaintext: &[u8]) -> (Vec<u8>, super::SealedAttachment) {
+        let mut out = Vec::new();
+        let sealed = encrypt_stream(&KEY, "att-1", CHUNK, plaintext, &mut out).unwrap();
+        (out, sealed)
+    }
+
+    fn open(ciphertext: &[u8], hash: &[u8; 32]) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        decrypt_stream(&KEY, "att-1", CHUNK, hash, ciphertext, &mut out)?;
+        Ok(out)
+    }
+
+    #[test]
+    fn round_trips_across_chunk_boundaries() {
+        for len in [0, 1, CHUNK - 1, CHUNK, CHUNK + 1, 3 * CHUNK, 3 * CHUNK + 17] {
+            let plaintext: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            let (ciphertext, sealed) = seal(&plaintext);
+            let chunks = len.div_ceil(CHUNK).max(1) as u64;
+            // 恰好整块时不额外补空块。
+            assert_eq!(sealed.chunk_count, chunks, "len={len}");
+            assert_eq!(sealed.byte_size, len as u64);
+            assert_eq!(
+                sealed.encrypted_size,
+                (len + chunks as usize * TAG_LEN) as u64
+            );
+            assert_eq!(ciphertext.len() as u64, sealed.encrypted_size);
+            assert_eq!(open(&ciphertext, &sealed.content_hash).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn rejects_tampering_truncation_and_reordering() {
+        let plaintext = vec![42u8; 3 * CHUNK];
+        let (ciphertext, sealed) = seal(&plaintext);
+        let hash = sealed.content_hash;
+
+        let mut flipped = ciphertext.clone();
+        flipped[CHUNK + 5] ^= 1;
+        assert!(open(&flipped, &hash).is_err());
+
+        // 整块截掉末块：前一块不带末块标志，必须失败。
+        let truncated = &ciphertext[..2 * (CHUNK + TAG_LEN)];
+        assert!(open(truncated, &hash).is_err());
+
+        let sealed_len = CHUNK + TAG_LEN;
+        let mut swapped = ciphertext[sealed_len..2 * sealed_len].to_vec();
+        swapped.extend_from_slice(&ciphertext[..sealed_len]);
+        swapped.extend_from_slice(&ciphertext[2 * sealed_len..]);
+        assert!(open(&swapped, &hash).is_err());
+
+        let mut out = Vec::new();
+        assert!(
+            decrypt_stream(&KEY, "att-2", CHUNK, &hash, ciphertext.as_slice(), &mut out).is_err()
+        );
+        assert!(open(&ciphertext, &[0u8; 32]).unwrap_err().contains("哈希"));
+    }
+
+    #[test]
+    fn validates_parameters() {
+        assert!(resolve_chunk_size(None).is_ok());
+        assert!(resolve_chunk_size(Some(1024)).is_err());
+        assert!(resolve_chunk_size(Some(8 * 1024 * 1024)).is_err());
+        assert!(validate_attachment_id("").is_err());
+        assert!(validate_attachment_id("with space").is_err());
+        assert!(validate_attachment_id(&"a".repeat(129)).is_err());
+        assert!(validate_attachment_id("0f3a-uuid").is_ok());
+    }
+}
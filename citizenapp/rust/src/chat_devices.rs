@@ -0,0 +1,451 @@
+//! 同一 CID 多设备：本人设备之间的加密历史迁移握手。
+//!
+//! 多设备本身落在 MLS 名册上：一个 CID 的每台设备各持一片叶子，各带一份由 CID
+//! 绑定账户签发的 `OP_SIGN_CHAT_DEVICE_BIND` 证明(见 `chat_identity`)。新设备入群后
+//! 只能解密之后的消息，之前的本地聊天记录由同 CID 的老设备经本模块转交：
+//! 1. 新设备生成一次性 HPKE 密钥对，用 MLS 设备签名钥签 [`HistoryRequest`]；
+//! 2. 老设备按链上绑定快照核对请求方凭证(同 CID、证明仍有效、签名成立)，
+//!    HPKE 封装历史后用自己的设备签名钥签 [`HistoryBundle`]；
+//! 3. 新设备同样核对发送方属于本 CID 后解封，一次性私钥随即作废。
+//!
+//! 历史明文的格式由 Dart 决定，本层只当不透明字节；HPKE 与签名全部走 OpenMLS 的
+//! RustCrypto 提供者，不另造密码学。
+
+use openmls_traits::{
+    crypto::OpenMlsCrypto,
+    signatures::Signer,
+    types::{HpkeCiphertext, HpkeConfig, SignatureScheme},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::chat_identity::{self, ChainBindings};
+
+/// 请求签名域。
+const HISTORY_REQUEST_LABEL: &[u8] = b"citizenapp.chat/history-transfer|request";
+/// 迁移包签名域。
+const HISTORY_BUNDLE_LABEL: &[u8] = b"citizenapp.chat/history-transfer|bundle";
+/// HPKE info 前缀：把密文钉在 (CID, 发送设备, 接收设备) 上。
+const HISTORY_HPKE_INFO: &[u8] = b"citizenapp.chat/history-transfer|hpke";
+/// 请求有效期：两台设备须同时在手，过期请求一律不受理。
+const HISTORY_REQUEST_TTL_MILLIS: u64 = 10 * 60 * 1000;
+/// 允许的时钟偏差。
+const CLOCK_SKEW_MILLIS: u64 = 60 * 1000;
+
+/// 新设备发出的历史迁移请求。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct HistoryRequest {
+    pub(crate) cid_number: String,
+    pub(crate) device_id: String,
+    /// 请求设备的 MLS 凭证内容(含绑定证明)hex。
+    pub(crate) credential_hex: String,
+    pub(crate) signature_public_key_hex: String,
+    /// 一次性 HPKE 接收公钥。
+    pub(crate) hpke_public_key_hex: String,
+    pub(crate) created_at_ms: u64,
+    pub(crate) signature_hex: String,
+}
+
+/// 老设备回送的加密历史。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct HistoryBundle {
+    pub(crate) cid_number: String,
+    pub(crate) from_device_id: String,
+    pub(crate) to_device_id: String,
+    pub(crate) credential_hex: String,
+    pub(crate) signature_public_key_hex: String,
+    /// 回应的请求公钥；接收端据此拒绝错配或重放的迁移包。
+    pub(crate) hpke_public_key_hex: String,
+    pub(crate) kem_output_hex: String,
+    pub(crate) ciphertext_hex: String,
+    pub(crate) signature_hex: String,
+}
+
+/// 新设备组装并签名请求。
+pub(crate) fn build_request(
+    signer: &impl Signer,
+    cid_number: &str,
+    device_id: &str,
+    credential_content: &[u8],
+    signature_public_key: &[u8],
+    hpke_public_key: &[u8],
+    now_millis: u64,
+) -> Result<HistoryRequest, String> {
+    let mut request = HistoryRequest {
+        cid_number: cid_number.to_string(),
+        device_id: device_id.to_string(),
+        credential_hex: hex::encode(credential_content),
+        signature_public_key_hex: hex::encode(signature_public_key),
+        hpke_public_key_hex: hex::encode(hpke_public_key),
+        created_at_ms: now_millis,
+        signature_hex: String::new(),
+    };
+    let signature = signer
+        .sign(&request_payload(&request))
+        .map_err(|error| format!("签名历史迁移请求失败: {error:?}"))?;
+    request.signature_hex = hex::encode(signature);
+    Ok(request)
+}
+
+/// 老设备核对请求并封装历史。
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn seal_history(
+    crypto: &impl OpenMlsCrypto,
+    config: HpkeConfig,
+    signer: &impl Signer,
+    bindings: &ChainBindings,
+    own_cid_number: &str,
+    own_device_id: &str,
+    own_credential_content: &[u8],
+    own_signature_public_key: &[u8],
+    request: &HistoryRequest,
+    history: &[u8],
+    now_millis: u64,
+) -> Result<HistoryBundle, String> {
+    if request.device_id == own_device_id {
+        return Err("不能向本设备迁移历史".to_string());
+    }
+    if request.created_at_ms + HISTORY_REQUEST_TTL_MILLIS <= now_millis
+        || request.created_at_ms > now_millis + CLOCK_SKEW_MILLIS
+    {
+        return Err("历史迁移请求已过期".to_string());
+    }
+    verify_own_device(
+        crypto,
+        bindings,
+        own_cid_number,
+        &request.cid_number,
+        &request.device_id,
+        &request.credential_hex,
+        &request.signature_public_key_hex,
+        &request_payload(request),
+        &request.signature_hex,
+    )?;
+    let hpke_public_key = decode_hex("hpke_public_key_hex", &request.hpke_public_key_hex)?;
+    let sealed = crypto
+        .hpke_seal(
+            config,
+            &hpke_public_key,
+            &hpke_info(own_cid_number, own_device_id, &request.device_id),
+            request.hpke_public_key_hex.as_bytes(),
+            history,
+        )
+        .map_err(|error| format!("封装历史失败: {error:?}"))?;
+    let mut bundle = HistoryBundle {
+        cid_number: own_cid_number.to_string(),
+        from_device_id: own_device_id.to_string(),
+        to_device_id: request.device_id.clone(),
+        credential_hex: hex::encode(own_credential_content),
+        signature_public_key_hex: hex::encode(own_signature_public_key),
+        hpke_public_key_hex: request.hpke_public_key_hex.clone(),
+        kem_output_hex: hex::encode(sealed.kem_output.as_slice()),
+        ciphertext_hex: hex::encode(sealed.ciphertext.as_slice()),
+        signature_hex: String::new(),
+    };
+    let signature = signer
+        .sign(&bundle_payload(&bundle))
+        .map_err(|error| format!("签名历史迁移包失败: {error:?}"))?;
+    bundle.signature_hex = hex::encode(signature);
+    Ok(bundle)
+}
+
+/// 新设备核对发送方并解封历史。
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn open_history(
+    crypto: &impl OpenMlsCrypto,
+    config: HpkeConfig,
+    bindings: &ChainBindings,
+    own_cid_number: &str,
+    own_device_id: &str,
+    hpke_public_key_hex: &str,
+    hpke_private_key: &[u8],
+    bundle: &HistoryBundle,
+) -> Result<Vec<u8>, String> {
+    if bundle.to_device_id != own_device_id || bundle.hpke_public_key_hex != hpke_public_key_hex {
+        return Err("历史迁移包不是发给本次请求的".to_string());
+    }
+    if bundle.from_device_id == own_device_id {
+        return Err("历史迁移包不能来自本设备".to_string());
+    }
+    verify_own_device(
+        crypto,
+        bindings,
+        own_cid_number,
+        &bundle.cid_number,
+        &bundle.from_device_id,
+        &bundle.credential_hex,
+        &bundle.signature_public_key_hex,
+        &bundle_payload(bundle),
+        &bundle.signature_hex,
+    )?;
+    let ciphertext = HpkeCiphertext {
+        kem_output: decode_hex("kem_output_hex", &bundle.kem_output_hex)?.into(),
+        ciphertext: decode_hex("ciphertext_hex", &bundle.ciphertext_hex)?.into(),
+    };
+    crypto
+        .hpke_open(
+            config,
+            &ciphertext,
+            hpke_private_key,
+            &hpke_info(own_cid_number, &bundle.from_device_id, own_device_id),
+            bundle.hpke_public_key_hex.as_bytes(),
+        )
+        .map_err(|error| format!("解封历史失败: {error:?}"))
+}
+
+/// 对端必须是本 CID 的另一台设备：凭证标识一致、链上绑定仍授权、签名成立。
+#[allow(clippy::too_many_arguments)]
+fn verify_own_device(
+    crypto: &impl OpenMlsCrypto,
+    bindings: &ChainBindings,
+    own_cid_number: &str,
+    cid_number: &str,
+    device_id: &str,
+    credential_hex: &str,
+    signature_public_key_hex: &str,
+    payload: &[u8],
+    signature_hex: &str,
+) -> Result<(), String> {
+    if cid_number != own_cid_number {
+        return Err(format!("对端设备属于其他 CID {cid_number}"));
+    }
+    let credential = decode_hex("credential_hex", credential_hex)?;
+    if chat_identity::member_identity(&credential) != format!("{cid_number}:{device_id}") {
+        return Err("对端凭证与设备标识不一致".to_string());
+    }
+    let public_key = decode_hex("signature_public_key_hex", signature_public_key_hex)?;
+    bindings
+        .verify_leaf(&credential, &public_key, None)
+        .map_err(|reason| format!("对端设备未获 CID 链上绑定授权: {}", reason.as_str()))?;
+    let signature = decode_hex("signature_hex", signature_hex)?;
+    crypto
+        .verify_signature(SignatureScheme::ED25519, payload, &public_key, &signature)
+        .map_err(|_| "对端设备签名无效".to_string())
+}
+
+fn request_payload(request: &HistoryRequest) -> Vec<u8> {
+    let mut out = HISTORY_REQUEST_LABEL.to_vec();
+    for field in [
+        request.cid_number.as_str(),
+        &request.device_id,
+        &request.credential_hex,
+        &request.signature_public_key_hex,
+        &request.hpke_public_key_hex,
+    ] {
+        push_field(&mut out, field.as_bytes());
+    }
+    out.extend_from_slice(&request.created_at_ms.to_be_bytes());
+    out
+}
+
+fn bundle_payload(bundle: &HistoryBundle) -> Vec<u8> {
+    let mut out = HISTORY_BUNDLE_LABEL.to_vec();
+    for field in [
+        bundle.cid_number.as_str(),
+        &bundle.from_device_id,
+        &bundle.to_device_id,
+        &bundle.credential_hex,
+        &bundle.signature_public_key_hex,
+        &bundle.hpke_public_key_hex,
+        &bundle.kem_output_hex,
+        &bundle.ciphertext_hex,
+    ] {
+        push_field(&mut out, field.as_bytes());
+    }
+    out
+}
+
+fn hpke_info(cid_number: &str, from_device_id: &str, to_device_id: &str) -> Vec<u8> {
+    let mut out = HISTORY_HPKE_INFO.to_vec();
+    for field in [cid_number, from_device_id, to_device_id] {
+        push_field(&mut out, field.as_bytes());
+    }
+    out
+}
+
+/// 长度前缀拼接，杜绝字段边界歧义。
+fn push_field(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+fn decode_hex(field_name: &str, value: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value).map_err(|error| format!("{field_name} 不是合法 hex: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat_identity::{
+        tests::{sign_attestation, test_account},
+        ChainBinding,
+    };
+    use openmls::prelude::Ciphersuite;
+    use openmls_basic_credential::SignatureKeyPair;
+    use openmls_rust_crypto::RustCrypto;
+
+    const SUITE: Ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
+
+    struct Device {
+        id: &'static str,
+        signer: SignatureKeyPair,
+        credential: Vec<u8>,
+    }
+
+    fn device(cid_number: &str, id: &'static str, child: &[u8; 32], account: &str) -> Device {
+        let signer = SignatureKeyPair::new(SignatureScheme::ED25519).unwrap();
+        let attestation = sign_attestation(
+            child,
+            account,
+            cid_number,
+            1,
+            id,
+            &hex::encode(signer.to_public_vec()),
+        );
+        let credential = chat_identity::credential_content(cid_number, id, Some(&attestation))
+            .expect("credential content");
+        Device {
+            id,
+            signer,
+            credential,
+        }
+    }
+
+    fn bindings(account: &str) -> ChainBindings {
+        ChainBindings::new(vec![ChainBinding {
+            cid_number: "CTZN-A".to_string(),
+            account_id_hex: Some(account.to_string()),
+            binding_revision: 1,
+        }])
+        .unwrap()
+    }
+
+    #[test]
+    fn history_moves_between_devices_of_the_same_cid() {
+        let crypto = RustCrypto::default();
+        let config = SUITE.hpke_config();
+        let (account, child) = test_account(7);
+        let phone = device("CTZN-A", "phone", &child, &account);
+        let tablet = device("CTZN-A", "tablet", &child, &account);
+        let chain = bindings(&account);
+        let keypair = crypto.derive_hpke_keypair(config, &[9u8; 32]).unwrap();
+        let now = 1_000_000;
+
+        let request = build_request(
+            &tablet.signer,
+            "CTZN-A",
+            tablet.id,
+            &tablet.credential,
+            &tablet.signer.to_public_vec(),
+            &keypair.public,
+            now,
+        )
+        .unwrap();
+        let bundle = seal_history(
+            &crypto,
+            config,
+            &phone.signer,
+            &chain,
+            "CTZN-A",
+            phone.id,
+            &phone.credential,
+            &phone.signer.to_public_vec(),
+            &request,
+            b"history",
+            now + 1,
+        )
+        .unwrap();
+        let opened = open_history(
+            &crypto,
+            config,
+            &chain,
+            "CTZN-A",
+            tablet.id,
+            &request.hpke_public_key_hex,
+            &keypair.private,
+            &bundle,
+        )
+        .unwrap();
+        assert_eq!(opened, b"history");
+
+        // 过期请求、被篡改的请求、发给别的设备的包都不受理。
+        assert!(seal_history(
+            &crypto,
+            config,
+            &phone.signer,
+            &chain,
+            "CTZN-A",
+            phone.id,
+            &phone.credential,
+            &phone.signer.to_public_vec(),
+            &request,
+            b"history",
+            now + HISTORY_REQUEST_TTL_MILLIS,
+        )
+        .is_err());
+        let forged = HistoryRequest {
+            hpke_public_key_hex: hex::encode([1u8; 32]),
+            ..request.clone()
+        };
+        assert!(seal_history(
+            &crypto,
+            config,
+            &phone.signer,
+            &chain,
+            "CTZN-A",
+            phone.id,
+            &phone.credential,
+            &phone.signer.to_public_vec(),
+            &forged,
+            b"history",
+            now,
+        )
+        .is_err());
+        assert!(open_history(
+            &crypto,
+            config,
+            &chain,
+            "CTZN-A",
+            "laptop",
+            &request.hpke_public_key_hex,
+            &keypair.private,
+            &bundle,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn other_cid_cannot_request_history() {
+        let crypto = RustCrypto::default();
+        let config = SUITE.hpke_config();
+        let (account, child) = test_account(7);
+        let (other_account, other_child) = test_account(8);
+        let phone = device("CTZN-A", "phone", &child, &account);
+        let stranger = device("CTZN-B", "laptop", &other_child, &other_account);
+        let keypair = crypto.derive_hpke_keypair(config, &[9u8; 32]).unwrap();
+        let request = build_request(
+            &stranger.signer,
+            "CTZN-B",
+            stranger.id,
+            &stranger.credential,
+            &stranger.signer.to_public_vec(),
+            &keypair.public,
+            1,
+        )
+        .unwrap();
+        let error = seal_history(
+            &crypto,
+            config,
+            &phone.signer,
+            &bindings(&account),
+            "CTZN-A",
+            phone.id,
+            &phone.credential,
+            &phone.signer.to_public_vec(),
+            &request,
+            b"history",
+            2,
+        )
+        .unwrap_err();
+        assert!(error.contains("其他 CID"));
+    }
+}
@@ -0,0 +1,443 @@
+//! Chat MLS 凭证 ↔ 链上 CID 身份绑定。
+//!
+//! 每个 MLS 叶子的 BasicCredential 除 `"cid_number:device_id"` 外，还携带一份由该
+//! CID 当前绑定 sr25519 账户签发的 `OP_SIGN_CHAT_DEVICE_BIND` 证明，证明对象是
+//! 该叶子的 MLS Ed25519 签名公钥。群成员在加人、处理 Welcome、处理 Commit 时都
+//! 按轻节点读出的 `AccountIdByCid` / `BindingRevisionByCid` 快照逐叶校验；CID
+//! 换绑(revision 前进)或注销(账户缺失)后，旧证明即失效，由上层发起驱逐。
+//!
+//! 签名消息布局与 Dart `ChatDeviceBinding.signingMessage` / Worker
+//! `buildChatDeviceBindingMessage` 逐字节一致，只是 `device_public_key` 字段换成
+//! MLS 签名公钥 hex，签名方换成 CID 绑定账户(sr25519，context `substrate`)。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// 对齐 `primitives::sign::OP_SIGN_CHAT_DEVICE_BIND`。
+const OP_SIGN_CHAT_DEVICE_BIND: u8 = 0x1A;
+/// 对齐 `primitives::core_const::GMB`。
+const GMB: &[u8; 3] = b"GMB";
+/// 凭证标识里证明段的分隔符：`"cid:device|att:<hex(json)>"`。
+const ATTESTATION_MARKER: &str = "|att:";
+
+/// CID 绑定账户对某个 MLS 设备签名公钥的授权证明。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct DeviceBindingAttestation {
+    pub(crate) cid_number: String,
+    pub(crate) binding_revision: u64,
+    /// 签名时的 CID 绑定账户，小写 `0x` + 64 hex。
+    pub(crate) account_id_hex: String,
+    pub(crate) device_id: String,
+    /// 被授权的 MLS 签名公钥(小写 hex，无前缀)。
+    pub(crate) device_public_key_hex: String,
+    /// 证明签发期限；仅在 KeyPackage 入群时校验，已入群叶子不因过期被驱逐。
+    pub(crate) expires_at_ms: u64,
+    pub(crate) nonce: String,
+    pub(crate) signature_hex: String,
+}
+
+/// Dart 从轻节点 finalized 状态读出的单个 CID 绑定快照。
+///
+/// `account_id_hex` 为空表示 `AccountIdByCid` 已不存在(CID 注销/解绑)。
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct ChainBinding {
+    pub(crate) cid_number: String,
+    pub(crate) account_id_hex: Option<String>,
+    #[serde(default)]
+    pub(crate) binding_revision: u64,
+}
+
+/// 叶子未通过链上身份校验的原因。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LeafRejection {
+    /// 快照里没有该 CID，需 Dart 补读后重试，不构成驱逐理由。
+    MissingBinding,
+    /// 凭证不带证明(旧版本设备)。
+    Unattested,
+    /// 证明内容与凭证标识或叶子签名公钥不一致。
+    Mismatch,
+    /// sr25519 签名不成立。
+    BadSignature,
+    /// 证明已过签发期限(仅入群时)。
+    Expired,
+    /// CID 已换绑，证明账户或 revision 落后于链上。
+    Rebound,
+    /// CID 已无绑定账户。
+    Revoked,
+}
+
+impl LeafRejection {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::MissingBinding => "missing_binding",
+            Self::Unattested => "unattested",
+            Self::Mismatch => "mismatch",
+            Self::BadSignature => "bad_signature",
+            Self::Expired => "expired",
+            Self::Rebound => "rebound",
+            Self::Revoked => "revoked",
+        }
+    }
+}
+
+/// 按 CID 索引的链上绑定快照。
+pub(crate) struct ChainBindings {
+    by_cid: HashMap<String, ChainBinding>,
+}
+
+impl ChainBindings {
+    pub(crate) fn new(bindings: Vec<ChainBinding>) -> Result<Self, String> {
+        let mut by_cid = HashMap::with_capacity(bindings.len());
+        for binding in bindings {
+            if binding.cid_number.trim().is_empty() {
+                return Err("chain_bindings 含空 cid_number".to_string());
+            }
+            if let Some(account) = binding.account_id_hex.as_deref() {
+                normalize_account_id(account)?;
+                if binding.binding_revision == 0 {
+                    return Err(format!(
+                        "chain_bindings[{}] 有绑定账户时 binding_revision 必须大于 0",
+                        binding.cid_number
+                    ));
+                }
+            }
+            if by_cid.insert(binding.cid_number.clone(), binding).is_some() {
+                return Err("chain_bindings 含重复 cid_number".to_string());
+            }
+        }
+        Ok(Self { by_cid })
+    }
+
+    /// 校验一片叶子：凭证内容 + 叶子签名公钥 → 链上绑定是否仍授权该设备。
+    ///
+    /// `now_millis` 为 `Some` 时额外校验证明期限(用于 KeyPackage 入群)。
+    pub(crate) fn verify_leaf(
+        &self,
+        credential_content: &[u8],
+        signature_key: &[u8],
+        now_millis: Option<u64>,
+    ) -> Result<(), LeafRejection> {
+        let identity = member_identity(credential_content);
+        let cid_number = identity
+            .split_once(':')
+            .map(|(cid_number, _)| cid_number)
+            .unwrap_or(identity.as_str());
+        let binding = self
+            .by_cid
+            .get(cid_number)
+            .ok_or(LeafRejection::MissingBinding)?;
+        let attestation = parse_attestation(credential_content).ok_or(LeafRejection::Unattested)?;
+        if identity != format!("{}:{}", attestation.cid_number, attestation.device_id)
+            || attestation.device_public_key_hex != hex::encode(signature_key)
+        {
+            return Err(LeafRejection::Mismatch);
+        }
+        verify_attestation_signature(&attestation)?;
+        if let Some(now) = now_millis {
+            if attestation.expires_at_ms <= now {
+                return Err(LeafRejection::Expired);
+            }
+        }
+        let chain_account = binding
+            .account_id_hex
+            .as_deref()
+            .ok_or(LeafRejection::Revoked)?;
+        let chain_account =
+            normalize_account_id(chain_account).map_err(|_| LeafRejection::Revoked)?;
+        if chain_account != attestation.account_id_hex
+            || binding.binding_revision != attestation.binding_revision
+        {
+            return Err(LeafRejection::Rebound);
+        }
+        Ok(())
+    }
+}
+
+/// 构造 32 字节签名消息:`BLAKE2-256(GMB || 0x1A || SCALE(fields))`。
+pub(crate) fn binding_message(
+    cid_number: &str,
+    binding_revision: u64,
+    account_id_hex: &str,
+    device_id: &str,
+    device_public_key_hex: &str,
+    expires_at_ms: u64,
+    nonce: &str,
+) -> [u8; 32] {
+    let mut data = Vec::with_capacity(256);
+    data.extend_from_slice(GMB);
+    data.push(OP_SIGN_CHAT_DEVICE_BIND);
+    push_scale_string(&mut data, cid_number);
+    data.extend_from_slice(&binding_revision.to_le_bytes());
+    push_scale_string(&mut data, account_id_hex);
+    push_scale_string(&mut data, device_id);
+    push_scale_string(&mut data, device_public_key_hex);
+    data.extend_from_slice(&expires_at_ms.to_le_bytes());
+    push_scale_string(&mut data, nonce);
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(blake2_rfc::blake2b::blake2b(32, &[], &data).as_bytes());
+    digest
+}
+
+/// 对一份证明的 sr25519 签名做本地校验(不查链)。
+pub(crate) fn verify_attestation_signature(
+    attestation: &DeviceBindingAttestation,
+) -> Result<(), LeafRejection> {
+    let account =
+        normalize_account_id(&attestation.account_id_hex).map_err(|_| LeafRejection::Mismatch)?;
+    if account != attestation.account_id_hex {
+        return Err(LeafRejection::Mismatch);
+    }
+    let public = hex::decode(&account[2..]).map_err(|_| LeafRejection::Mismatch)?;
+    let signature = hex::decode(
+        attestation
+            .signature_hex
+            .strip_prefix("0x")
+            .unwrap_or(&attestation.signature_hex),
+    )
+    .map_err(|_| LeafRejection::BadSignature)?;
+    if signature.len() != 64 {
+        return Err(LeafRejection::BadSignature);
+    }
+    let message = binding_message(
+        &attestation.cid_number,
+        attestation.binding_revision,
+        &attestation.account_id_hex,
+        &attestation.device_id,
+        &attestation.device_public_key_hex,
+        attestation.expires_at_ms,
+        &attestation.nonce,
+    );
+    // SAFETY: public/signature/message 长度已分别校验为 32/64/32 字节。
+    let status = unsafe {
+        citizen_signer::citizen_sr25519_verify(
+            public.as_ptr(),
+            signature.as_ptr(),
+            message.as_ptr(),
+            message.len(),
+        )
+    };
+    if status == citizen_signer::CITIZEN_SIGNER_OK {
+        Ok(())
+    } else {
+        Err(LeafRejection::BadSignature)
+    }
+}
+
+/// 凭证内容:`"cid:device"`，有证明时追加 `"|att:<hex(json)>"`。
+pub(crate) fn credential_content(
+    cid_number: &str,
+    device_id: &str,
+    attestation: Option<&DeviceBindingAttestation>,
+) -> Result<Vec<u8>, String> {
+    let mut content = format!("{cid_number}:{device_id}");
+    if let Some(attestation) = attestation {
+        let encoded = serde_json::to_vec(attestation)
+            .map_err(|error| format!("序列化设备绑定证明失败: {error}"))?;
+        content.push_str(ATTESTATION_MARKER);
+        content.push_str(&hex::encode(encoded));
+    }
+    Ok(content.into_bytes())
+}
+
+/// 凭证内容 → 对外成员标识 `"cid:device"`(去掉证明段)。
+pub(crate) fn member_identity(credential_content: &[u8]) -> String {
+    let content = String::from_utf8_lossy(credential_content);
+    match content.split_once(ATTESTATION_MARKER) {
+        Some((identity, _)) => identity.to_string(),
+        None => content.into_owned(),
+    }
+}
+
+fn parse_attestation(credential_content: &[u8]) -> Option<DeviceBindingAttestation> {
+    let content = std::str::from_utf8(credential_content).ok()?;
+    let (_, encoded) = content.split_once(ATTESTATION_MARKER)?;
+    let bytes = hex::decode(encoded).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// 账户统一为小写 `0x` + 64 hex，保证签名消息与链上快照比对口径一致。
+pub(crate) fn normalize_account_id(value: &str) -> Result<String, String> {
+    let body = value.strip_prefix("0x").unwrap_or(value);
+    if body.len() != 64 || !body.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("account_id_hex 必须是 32 字节 hex".to_string());
+    }
+    Ok(format!("0x{}", body.to_ascii_lowercase()))
+}
+
+fn push_scale_string(out: &mut Vec<u8>, value: &str) {
+    let bytes = value.as_bytes();
+    push_compact_len(out, bytes.len());
+    out.extend_from_slice(bytes);
+}
+
+fn push_compact_len(out: &mut Vec<u8>, len: usize) {
+    let len = len as u64;
+    if len < 1 << 6 {
+        out.push((len as u8) << 2);
+    } else if len < 1 << 14 {
+        out.extend_from_slice(&(((len as u16) << 2) | 0b01).to_le_bytes());
+    } else if len < 1 << 30 {
+        out.extend_from_slice(&(((len as u32) << 2) | 0b10).to_le_bytes());
+    } else {
+        let bytes = len.to_le_bytes();
+        let used = 8 - (len.leading_zeros() / 8) as usize;
+        out.push((((used - 4) as u8) << 2) | 0b11);
+        out.extend_from_slice(&bytes[..used]);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// 测试用 sr25519 子钥 → (account_id_hex, child)。
+    pub(crate) fn test_account(seed_byte: u8) -> (String, [u8; 32]) {
+        let child = [seed_byte; 32];
+        let mut public = [0u8; 32];
+        let status = unsafe {
+            citizen_signer::citizen_sr25519_public_key(child.as_ptr(), public.as_mut_ptr())
+        };
+        assert_eq!(status, citizen_signer::CITIZEN_SIGNER_OK);
+        (format!("0x{}", hex::encode(public)), child)
+    }
+
+    pub(crate) fn sign_attestation(
+        child: &[u8; 32],
+        account_id_hex: &str,
+        cid_number: &str,
+        binding_revision: u64,
+        device_id: &str,
+        device_public_key_hex: &str,
+    ) -> DeviceBindingAttestation {
+        let expires_at_ms = u64::MAX;
+        let nonce = "nonce-1";
+        let message = binding_message(
+            cid_number,
+            binding_revision,
+            account_id_hex,
+            device_id,
+            device_public_key_hex,
+            expires_at_ms,
+            nonce,
+        );
+        let mut signature = [0u8; 64];
+        let status = unsafe {
+            citizen_signer::citizen_sr25519_sign(
+                child.as_ptr(),
+                message.as_ptr(),
+                message.len(),
+                signature.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, citizen_signer::CITIZEN_SIGNER_OK);
+        DeviceBindingAttestation {
+            cid_number: cid_number.to_string(),
+            binding_revision,
+            account_id_hex: account_id_hex.to_string(),
+            device_id: device_id.to_string(),
+            device_public_key_hex: device_public_key_hex.to_string(),
+            expires_at_ms,
+            nonce: nonce.to_string(),
+            signature_hex: hex::encode(signature),
+        }
+    }
+
+    fn bindings(cid_number: &str, account: Option<&str>, revision: u64) -> ChainBindings {
+        ChainBindings::new(vec![ChainBinding {
+            cid_number: cid_number.to_string(),
+            account_id_hex: account.map(str::to_string),
+            binding_revision: revision,
+        }])
+        .unwrap()
+    }
+
+    #[test]
+    fn binding_message_matches_dart_layout() {
+        // 与 Dart `ChatDeviceBinding.signingMessage` 同一 SCALE 拼接顺序。
+        let mut expected = b"GMB".to_vec();
+        expected.push(0x1A);
+        expected.extend_from_slice(&[4 << 2]);
+        expected.extend_from_slice(b"CTZN");
+        expected.extend_from_slice(&7u64.to_le_bytes());
+        expected.extend_from_slice(&[2 << 2]);
+        expected.extend_from_slice(b"0x");
+        expected.extend_from_slice(&[2 << 2]);
+        expected.extend_from_slice(b"d1");
+        expected.extend_from_slice(&[2 << 2]);
+        expected.extend_from_slice(b"ab");
+        expected.extend_from_slice(&9u64.to_le_bytes());
+        expected.extend_from_slice(&[1 << 2]);
+        expected.extend_from_slice(b"n");
+        let digest = blake2_rfc::blake2b::blake2b(32, &[], &expected);
+        assert_eq!(
+            binding_message("CTZN", 7, "0x", "d1", "ab", 9, "n"),
+            digest.as_bytes()
+        );
+    }
+
+    #[test]
+    fn attested_leaf_verifies_until_cid_is_rebound_or_revoked() {
+        let (account, child) = test_account(7);
+        let signature_key = [0x11u8; 32];
+        let attestation = sign_attestation(
+            &child,
+            &account,
+            "CTZN-A",
+            3,
+            "dev-1",
+            &hex::encode(signature_key),
+        );
+        let content =
+            credential_content("CTZN-A", "dev-1", Some(&attestation)).expect("credential content");
+        assert_eq!(member_identity(&content), "CTZN-A:dev-1");
+
+        let current = bindings("CTZN-A", Some(&account), 3);
+        assert_eq!(
+            current.verify_leaf(&content, &signature_key, Some(0)),
+            Ok(())
+        );
+        assert_eq!(
+            current.verify_leaf(&content, &[0x22u8; 32], None),
+            Err(LeafRejection::Mismatch)
+        );
+        assert_eq!(
+            bindings("CTZN-A", Some(&account), 4).verify_leaf(&content, &signature_key, None),
+            Err(LeafRejection::Rebound)
+        );
+        assert_eq!(
+            bindings("CTZN-A", None, 0).verify_leaf(&content, &signature_key, None),
+            Err(LeafRejection::Revoked)
+        );
+        assert_eq!(
+            bindings("CTZN-B", Some(&account), 3).verify_leaf(&content, &signature_key, None),
+            Err(LeafRejection::MissingBinding)
+        );
+        let bare = credential_content("CTZN-A", "dev-1", None).expect("credential content");
+        assert_eq!(
+            current.verify_leaf(&bare, &signature_key, None),
+            Err(LeafRejection::Unattested)
+        );
+    }
+
+    #[test]
+    fn attestation_signed_by_other_account_is_rejected() {
+        let (account, _) = test_account(7);
+        let (_, other_child) = test_account(8);
+        let signature_key = [0x11u8; 32];
+        let forged = sign_attestation(
+            &other_child,
+            &account,
+            "CTZN-A",
+            3,
+            "dev-1",
+            &hex::encode(signature_key),
+        );
+        let content =
+            credential_content("CTZN-A", "dev-1", Some(&forged)).expect("credential content");
+        assert_eq!(
+            bindings("CTZN-A", Some(&account), 3).verify_leaf(&content, &signature_key, None),
+            Err(LeafRejection::BadSignature)
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! 机构岗位群：MLS 群名册跟随链上 `InstitutionRoleAssignments` / 机构管理员集合。
+//!
+//! 机构群的 conversation_id 固定为 `inst:<机构 CID>:<范围>`，范围是岗位码或
+//! [`ADMINS_SCOPE`](全体管理员)。group_id 进入 MLS GroupContext，每个 Commit 与
+//! Welcome 都对其签名，所以"这是哪个机构、哪个岗位的群"由 MLS 本身保证不可篡改；
+//! 谁有资格进群、谁有资格改名册，则由 Dart 从轻节点 finalized 状态读出的
+//! [`InstitutionRoster`] 快照逐次核对：
+//! - 只有机构管理员可以建群、加人、删人(提交 Commit)；
+//! - 新进叶子的 CID 必须在该岗位的有效任职名册内；
+//! - 收到 Welcome 时发起方必须是机构管理员，且本机 CID 在名册内。
+//!
+//! 名册与群成员的差集以 `institution_missing` / `institution_extraneous` 回吐，
+//! 由管理员设备据此补发加人 / 删人 Commit。
+
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+
+/// 机构群 conversation_id 前缀。
+const INSTITUTION_GROUP_PREFIX: &str = "inst";
+/// 全体机构管理员群的范围标记；岗位码不以 `@` 开头，不会与之冲突。
+pub(crate) const ADMINS_SCOPE: &str = "@admins";
+
+/// 从 conversation_id 解出的机构群归属。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct InstitutionGroupRef {
+    pub(crate) cid_number: String,
+    pub(crate) scope: String,
+}
+
+/// 解析 `inst:<cid>:<scope>`；非机构群返回 None。
+pub(crate) fn parse_group_id(conversation_id: &str) -> Option<InstitutionGroupRef> {
+    let mut parts = conversation_id.splitn(3, ':');
+    if parts.next()? != INSTITUTION_GROUP_PREFIX {
+        return None;
+    }
+    let cid_number = parts.next()?;
+    let scope = parts.next()?;
+    if cid_number.is_empty() || scope.is_empty() {
+        return None;
+    }
+    Some(InstitutionGroupRef {
+        cid_number: cid_number.to_string(),
+        scope: scope.to_string(),
+    })
+}
+
+/// Dart 按机构 CID + 范围从 finalized 状态拼出的名册快照。
+///
+/// `member_cid_numbers` 为应在群内的 CID：岗位群取该岗位有效任职者，
+/// 管理员群取全体管理员；`admin_cid_numbers` 为有权提交名册变更的机构管理员。
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct InstitutionRoster {
+    pub(crate) institution_cid_number: String,
+    pub(crate) scope: String,
+    pub(crate) member_cid_numbers: Vec<String>,
+    pub(crate) admin_cid_numbers: Vec<String>,
+}
+
+/// 名册与 MLS 群成员的差集(均按 CID 去重排序)。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RosterDiff {
+    /// 名册内、群里尚无任何叶子的 CID：须领 KeyPackage 加入。
+    pub(crate) missing: Vec<String>,
+    /// 群里有叶子、已不在名册内的 CID：须移除。
+    pub(crate) extraneous: Vec<String>,
+}
+
+impl InstitutionRoster {
+    /// 快照必须与群归属一致，且成员都是机构管理员(任职只授予管理员)。
+    pub(crate) fn check_for(&self, group: &InstitutionGroupRef) -> Result<(), String> {
+        if self.institution_cid_number != group.cid_number || self.scope != group.scope {
+            return Err("机构名册快照与机构群归属不一致".to_string());
+        }
+        if self.admin_cid_numbers.is_empty() {
+            return Err("机构名册快照缺少管理员集合".to_string());
+        }
+        if let Some(outsider) = self
+            .member_cid_numbers
+            .iter()
+            .find(|cid_number| !self.is_admin(cid_number))
+        {
+            return Err(format!("机构名册成员 {outsider} 不是该机构管理员"));
+        }
+        if self.scope == ADMINS_SCOPE
+            && self
+                .admin_cid_numbers
+                .iter()
+                .any(|cid_number| !self.is_member(cid_number))
+        {
+            return Err("管理员群名册必须等于机构管理员集合".to_string());
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_member(&self, cid_number: &str) -> bool {
+        self.member_cid_numbers
+            .iter()
+            .any(|value| value == cid_number)
+    }
+
+    pub(crate) fn is_admin(&self, cid_number: &str) -> bool {
+        self.admin_cid_numbers
+            .iter()
+            .any(|value| value == cid_number)
+    }
+
+    pub(crate) fn diff<I>(&self, present_cid_numbers: I) -> RosterDiff
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let present: BTreeSet<String> = present_cid_numbers.into_iter().collect();
+        let expected: BTreeSet<String> = self.member_cid_numbers.iter().cloned().collect();
+        RosterDiff {
+            missing: expected.difference(&present).cloned().collect(),
+            extraneous: present.difference(&expected).cloned().collect(),
+        }
+    }
+}
+
+/// 机构群操作统一取名册：是机构群则必须带快照且归属一致，否则忽略快照。
+pub(crate) fn roster_for<'a>(
+    conversation_id: &str,
+    roster: Option<&'a InstitutionRoster>,
+) -> Result<Option<&'a InstitutionRoster>, String> {
+    let Some(group) = parse_group_id(conversation_id) else {
+        return Ok(None);
+    };
+    let roster = roster.ok_or_else(|| "机构群操作必须提供机构名册快照".to_string())?;
+    roster.check_for(&group)?;
+    Ok(Some(roster))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roster(members: &[&str], admins: &[&str]) -> InstitutionRoster {
+        InstitutionRoster {
+            institution_cid_number: "NRC001".to_string(),
+            scope: "COMMITTEE".to_string(),
+            member_cid_numbers: members.iter().map(|value| value.to_string()).collect(),
+            admin_cid_numbers: admins.iter().map(|value| value.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn group_id_parses_only_institution_groups() {
+        assert_eq!(
+            parse_group_id("inst:NRC001:@admins"),
+            Some(InstitutionGroupRef {
+                cid_number: "NRC001".to_string(),
+                scope: ADMINS_SCOPE.to_string(),
+            })
+        );
+        assert_eq!(parse_group_id("grp:CID1:abcd"), None);
+        assert_eq!(parse_group_id("inst::COMMITTEE"), None);
+        assert_eq!(parse_group_id("inst:NRC001"), None);
+    }
+
+    #[test]
+    fn roster_must_match_group_and_stay_within_admins() {
+        let group = parse_group_id("inst:NRC001:COMMITTEE").unwrap();
+        assert!(roster(&["A", "B"], &["A", "B", "C"])
+            .check_for(&group)
+            .is_ok());
+        assert!(roster(&["A", "X"], &["A", "B"]).check_for(&group).is_err());
+
+        let other = parse_group_id("inst:NRC001:@admins").unwrap();
+        assert!(roster(&["A"], &["A"]).check_for(&other).is_err());
+        let admins = |members: &[&str]| InstitutionRoster {
+            scope: ADMINS_SCOPE.to_string(),
+            ..roster(members, &["A", "B"])
+        };
+        assert!(admins(&["A", "B"]).check_for(&other).is_ok());
+        assert!(admins(&["A"]).check_for(&other).is_err());
+
+        assert!(roster_for("grp:A:1", None).unwrap().is_none());
+        assert!(roster_for("inst:NRC001:COMMITTEE", None).is_err());
+    }
+
+    #[test]
+    fn diff_reports_missing_and_extraneous_cids() {
+        let diff = roster(&["A", "B", "C"], &["A", "B", "C", "D"])
+            .diff(["A", "D", "A"].iter().map(|value| value.to_string()));
+        assert_eq!(diff.missing, vec!["B".to_string(), "C".to_string()]);
+        assert_eq!(diff.extraneous, vec!["D".to_string()]);
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     ffi::CStr,
     fs,
     os::raw::c_char,
@@ -11,27 +11,36 @@ use openmls::{
     prelude::{
         tls_codec::{Deserialize as TlsDeserialize, Serialize as TlsSerialize},
         BasicCredential, Ciphersuite, Credential, CredentialWithKey, Extensions, GroupId,
-        KeyPackage, KeyPackageBundle, KeyPackageIn, MlsGroup, MlsGroupCreateConfig,
+        KeyPackage, KeyPackageBundle, KeyPackageIn, LeafNodeIndex, MlsGroup, MlsGroupCreateConfig,
         MlsMessageBodyIn, MlsMessageIn, ProcessedMessageContent, ProtocolMessage, ProtocolVersion,
         RatchetTreeIn, StagedWelcome,
     },
     storage::OpenMlsProvider as OpenMlsStorageProvider,
 };
 use openmls_basic_credential::SignatureKeyPair;
-use openmls_memory_storage::MemoryStorage;
 use openmls_rust_crypto::{OpenMlsRustCrypto, RustCrypto};
 use openmls_traits::{
-    signatures::Signer, types::SignatureScheme, OpenMlsProvider as OpenMlsTraitsProvider,
+    crypto::OpenMlsCrypto, random::OpenMlsRand, signatures::Signer, types::SignatureScheme,
+    OpenMlsProvider as OpenMlsTraitsProvider,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use crate::{
+    chat_attachment,
+    chat_devices::{self, HistoryBundle, HistoryRequest},
+    chat_identity::{self, ChainBinding, ChainBindings, DeviceBindingAttestation, LeafRejection},
+    chat_institution::{self, InstitutionRoster},
+    chat_mls_store,
+};
+
 const GMB_MLS_CIPHERSUITE: Ciphersuite = Ciphersuite::MLS_128_DHKEMX25519_AES128GCM_SHA256_Ed25519;
 const DEFAULT_KEYPACKAGE_TTL_MILLIS: u64 = 30 * 24 * 60 * 60 * 1000;
 
 /// MLS 本地状态信封的 AAD 域,把密文钉死在用途上,防止两个文件互换。
-const STATE_AAD_STORAGE: &[u8] = b"citizenapp.local/mls|openmls_storage";
+pub(crate) const STATE_AAD_STORAGE: &[u8] = b"citizenapp.local/mls|openmls_storage";
 const STATE_AAD_DEVICE: &[u8] = b"citizenapp.local/mls|device_record";
+const STATE_AAD_HISTORY: &[u8] = b"citizenapp.local/mls|history_transfer";
 /// GCM nonce 固定 12 字节;密文布局 = nonce || ciphertext || tag(16)。
 const STATE_NONCE_LEN: usize = 12;
 
@@ -46,7 +55,7 @@ fn parse_state_key(state_key_hex: &str) -> Result<[u8; 32], String> {
 }
 
 /// AES-256-GCM 封装:随机 12 字节 nonce,输出 `nonce || ciphertext || tag`。
-fn seal_state(key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+pub(crate) fn seal_state(key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
     use aes_gcm::{
         aead::{Aead, KeyInit, OsRng, Payload},
         AeadCore, Aes256Gcm, Nonce,
@@ -73,7 +82,7 @@ fn seal_state(key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, S
 
 /// AES-256-GCM 解封。密钥不符 / 密文被篡改一律报错,**绝不返回空状态**——
 /// 静默降级会让 App 误以为"没有 MLS 状态"而重建身份,等于丢掉全部会话。
-fn open_state(key: &[u8; 32], blob: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+pub(crate) fn open_state(key: &[u8; 32], blob: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
     use aes_gcm::{
         aead::{Aead, KeyInit, Payload},
         Aes256Gcm, Nonce,
@@ -148,17 +157,20 @@ struct DeviceRecord {
     device_id: String,
     signature_public_key_hex: String,
     signature_scheme: String,
+    /// CID 绑定账户对本设备 MLS 签名公钥的授权证明；安装后新生成的凭证才携带。
+    #[serde(default)]
+    binding_attestation: Option<DeviceBindingAttestation>,
 }
 
 struct MlsProvider {
     crypto: RustCrypto,
-    storage: MemoryStorage,
+    storage: chat_mls_store::MlsKvStorage,
 }
 
 impl OpenMlsTraitsProvider for MlsProvider {
     type CryptoProvider = RustCrypto;
     type RandProvider = RustCrypto;
-    type StorageProvider = MemoryStorage;
+    type StorageProvider = chat_mls_store::MlsKvStorage;
 
     fn storage(&self) -> &Self::StorageProvider {
         &self.storage
@@ -288,12 +300,20 @@ fn rekey_state_json(request_json: *const c_char) -> Result<String, String> {
                     .as_deref()
                     .ok_or_else(|| "stage 缺少 new_state_key_hex".to_string())?,
             )?;
+            // 尚未迁移的旧整文件信封与 KV 各自重封，commit 时一并换上。
             stage_rekey_state_file(
-                &storage_path(state_dir),
+                &chat_mls_store::legacy_envelope_path(state_dir),
                 &current_key,
                 &new_key,
                 STATE_AAD_STORAGE,
             )?;
+            let kv_path = chat_mls_store::kv_path(state_dir);
+            chat_mls_store::stage_rekey(
+                state_dir,
+                &rekey_staged_path(&kv_path),
+                &current_key,
+                &new_key,
+            )?;
             stage_rekey_state_file(
                 &device_record_path(state_dir),
                 &current_key,
@@ -302,11 +322,13 @@ fn rekey_state_json(request_json: *const c_char) -> Result<String, String> {
             )?;
         }
         "commit" => {
-            commit_rekey_state_file(&storage_path(state_dir))?;
+            commit_rekey_state_file(&chat_mls_store::legacy_envelope_path(state_dir))?;
+            commit_rekey_state_file(&chat_mls_store::kv_path(state_dir))?;
             commit_rekey_state_file(&device_record_path(state_dir))?;
         }
         "discard" => {
-            discard_rekey_state_file(&storage_path(state_dir))?;
+            discard_rekey_state_file(&chat_mls_store::legacy_envelope_path(state_dir))?;
+            discard_rekey_state_file(&chat_mls_store::kv_path(state_dir))?;
             discard_rekey_state_file(&device_record_path(state_dir))?;
         }
         _ => return Err("MLS 状态换绑 action 必须为 stage/commit/discard".to_string()),
@@ -344,7 +366,7 @@ fn create_key_package_json(request_json: *const c_char) -> Result<String, String
                     .map_err(|error| format!("序列化 OpenMLS KeyPackage 失败: {error}"))?,
             );
             let device_public_key_hex = hex::encode(signer.to_public_vec());
-            save_provider(state_dir, &provider, &state_key)?;
+            save_provider(state_dir, &provider)?;
             (
                 key_package_hex,
                 format!("{:?}", GMB_MLS_CIPHERSUITE),
@@ -353,7 +375,7 @@ fn create_key_package_json(request_json: *const c_char) -> Result<String, String
         } else {
             let provider = OpenMlsRustCrypto::default();
             let (credential, signer) = generate_credential(
-                format!("{}:{}", request.cid_number, request.device_id).into_bytes(),
+                chat_identity::credential_content(&request.cid_number, &request.device_id, None)?,
                 GMB_MLS_CIPHERSUITE.signature_algorithm(),
                 &provider,
             )?;
@@ -564,7 +586,7 @@ fn encrypt_json(request_json: *const c_char) -> Result<String, String> {
             .tls_serialize_detached()
             .map_err(|error| format!("序列化 MLS application message 失败: {error}"))?,
     );
-    save_provider(state_dir, &provider, &state_key)?;
+    save_provider(state_dir, &provider)?;
 
     let response = json!({
         "conversation_id": request.conversation_id,
@@ -625,7 +647,7 @@ fn decrypt_json(request_json: *const c_char) -> Result<String, String> {
             if group.group_id() != &group_id {
                 return Err("Welcome group_id 与 conversation_id 不一致".to_string());
             }
-            save_provider(state_dir, &provider, &state_key)?;
+            save_provider(state_dir, &provider)?;
             json!({
                 "conversation_id": request.conversation_id,
                 "message_kind": "welcome",
@@ -636,7 +658,6 @@ fn decrypt_json(request_json: *const c_char) -> Result<String, String> {
         MlsMessageBodyIn::PublicMessage(message) => decrypt_protocol_message(
             state_dir,
             &provider,
-            &state_key,
             &request.conversation_id,
             group_id,
             message.into(),
@@ -644,7 +665,6 @@ fn decrypt_json(request_json: *const c_char) -> Result<String, String> {
         MlsMessageBodyIn::PrivateMessage(message) => decrypt_protocol_message(
             state_dir,
             &provider,
-            &state_key,
             &request.conversation_id,
             group_id,
             message.into(),
@@ -657,7 +677,6 @@ fn decrypt_json(request_json: *const c_char) -> Result<String, String> {
 fn decrypt_protocol_message(
     state_dir: &Path,
     provider: &MlsProvider,
-    state_key: &[u8; 32],
     conversation_id: &str,
     group_id: GroupId,
     protocol_message: openmls::prelude::ProtocolMessage,
@@ -672,7 +691,7 @@ fn decrypt_protocol_message(
         ProcessedMessageContent::ApplicationMessage(message) => message.into_bytes(),
         _ => return Err("MLS 处理结果不是 application message".to_string()),
     };
-    save_provider(state_dir, provider, state_key)?;
+    save_provider(state_dir, provider)?;
     Ok(json!({
         "conversation_id": conversation_id,
         "message_kind": "application",
@@ -725,75 +744,29 @@ fn generate_key_package(
         .map_err(|error| format!("生成 OpenMLS KeyPackage 失败: {error:?}"))
 }
 
-/// OpenMLS storage 的可序列化形态。
-///
-/// 不走上游 `save_to_file` / `load_from_file`——那两个 API 硬绑 `&File`，只能把
-/// **明文**直接写盘。这里改为自己序列化 `MemoryStorage.values`(公开字段)到内存
-/// 缓冲，再整体 AEAD 加密落盘，全程无明文触盘。
+/// 旧版整文件信封的可序列化形态，仅供 `chat_mls_store` 迁移读取。
 #[derive(Serialize, Deserialize, Default)]
-struct SerializableMlsStorage {
-    values: std::collections::HashMap<String, String>,
+pub(crate) struct SerializableMlsStorage {
+    pub(crate) values: std::collections::HashMap<String, String>,
 }
 
+/// 打开本次调用的 OpenMLS 存储。
+///
+/// 存储直接逐条读写 `chat_mls_store` 的加密 KV，并在整个调用期间持有状态库写锁；
+/// 不调 [`save_provider`] 即回滚本次全部改动。
 fn load_provider(state_dir: &Path, state_key: &[u8; 32]) -> Result<MlsProvider, String> {
     fs::create_dir_all(state_dir).map_err(|error| format!("创建 MLS 状态目录失败: {error}"))?;
     purge_legacy_plaintext_state(state_dir);
 
-    let storage = MemoryStorage::default();
-    let storage_path = storage_path(state_dir);
-    if storage_path.exists() {
-        use base64::Engine;
-        let blob = fs::read(&storage_path)
-            .map_err(|error| format!("读取 OpenMLS storage 失败: {error}"))?;
-        let clear = open_state(state_key, &blob, STATE_AAD_STORAGE)?;
-        let parsed: SerializableMlsStorage = serde_json::from_slice(&clear)
-            .map_err(|error| format!("解析 OpenMLS storage 失败: {error}"))?;
-        let mut values = storage
-            .values
-            .write()
-            .map_err(|_| "OpenMLS storage 写锁异常".to_string())?;
-        for (key, value) in parsed.values {
-            let key = base64::prelude::BASE64_STANDARD
-                .decode(key)
-                .map_err(|error| format!("OpenMLS storage 键解码失败: {error}"))?;
-            let value = base64::prelude::BASE64_STANDARD
-                .decode(value)
-                .map_err(|error| format!("OpenMLS storage 值解码失败: {error}"))?;
-            values.insert(key, value);
-        }
-    }
     Ok(MlsProvider {
         crypto: RustCrypto::default(),
-        storage,
+        storage: chat_mls_store::MlsKvStorage::open(state_dir, state_key)?,
     })
 }
 
-fn save_provider(
-    state_dir: &Path,
-    provider: &MlsProvider,
-    state_key: &[u8; 32],
-) -> Result<(), String> {
-    use base64::Engine;
-    fs::create_dir_all(state_dir).map_err(|error| format!("创建 MLS 状态目录失败: {error}"))?;
-
-    let mut serializable = SerializableMlsStorage::default();
-    {
-        let values = provider
-            .storage()
-            .values
-            .read()
-            .map_err(|_| "OpenMLS storage 读锁异常".to_string())?;
-        for (key, value) in &*values {
-            serializable.values.insert(
-                base64::prelude::BASE64_STANDARD.encode(key),
-                base64::prelude::BASE64_STANDARD.encode(value),
-            );
-        }
-    }
-    let clear = serde_json::to_vec(&serializable)
-        .map_err(|error| format!("序列化 OpenMLS storage 失败: {error}"))?;
-    let sealed = seal_state(state_key, &clear, STATE_AAD_STORAGE)?;
-    atomic_write(&storage_path(state_dir), &sealed)?;
+/// 提交本次调用的写事务并释放写锁。
+fn save_provider(state_dir: &Path, provider: &MlsProvider) -> Result<(), String> {
+    provider.storage().commit()?;
     purge_legacy_plaintext_state(state_dir);
     Ok(())
 }
@@ -805,27 +778,23 @@ fn ensure_device_signer(
     device_id: &str,
     state_key: &[u8; 32],
 ) -> Result<(CredentialWithKey, SignatureKeyPair), String> {
-    let record_path = device_record_path(state_dir);
     let signature_algorithm = GMB_MLS_CIPHERSUITE.signature_algorithm();
-    if record_path.exists() {
-        let blob =
-            fs::read(&record_path).map_err(|error| format!("读取 MLS 设备记录失败: {error}"))?;
-        let clear = open_state(state_key, &blob, STATE_AAD_DEVICE)?;
-        let record: DeviceRecord = serde_json::from_slice(&clear)
-            .map_err(|error| format!("解析 MLS 设备记录失败: {error}"))?;
-        if record.cid_number != cid_number || record.device_id != device_id {
-            return Err("MLS 状态目录已绑定到其他 CID 或设备".to_string());
-        }
+    if let Some(record) = read_device_record(state_dir, cid_number, device_id, state_key)? {
         let public_key =
             decode_hex_field("signature_public_key_hex", &record.signature_public_key_hex)?;
         let signer = SignatureKeyPair::read(provider.storage(), &public_key, signature_algorithm)
             .ok_or_else(|| "MLS 设备签名密钥不在 OpenMLS storage 中".to_string())?;
-        let credential = credential_with_public_key(cid_number, device_id, public_key);
+        let credential = credential_with_public_key(
+            cid_number,
+            device_id,
+            public_key,
+            record.binding_attestation.as_ref(),
+        )?;
         return Ok((credential, signer));
     }
 
     let (credential, signer) = generate_credential(
-        format!("{cid_number}:{device_id}").into_bytes(),
+        chat_identity::credential_content(cid_number, device_id, None)?,
         signature_algorithm,
         provider,
     )?;
@@ -834,22 +803,57 @@ fn ensure_device_signer(
         device_id: device_id.to_string(),
         signature_public_key_hex: hex::encode(signer.to_public_vec()),
         signature_scheme: format!("{:?}", signature_algorithm),
+        binding_attestation: None,
     };
-    let clear = serde_json::to_vec(&record).map_err(|error| error.to_string())?;
-    let sealed = seal_state(state_key, &clear, STATE_AAD_DEVICE)?;
-    atomic_write(&record_path, &sealed)?;
+    write_device_record(state_dir, &record, state_key)?;
     Ok((credential, signer))
 }
 
+fn read_device_record(
+    state_dir: &Path,
+    cid_number: &str,
+    device_id: &str,
+    state_key: &[u8; 32],
+) -> Result<Option<DeviceRecord>, String> {
+    let record_path = device_record_path(state_dir);
+    if !record_path.exists() {
+        return Ok(None);
+    }
+    let blob = fs::read(&record_path).map_err(|error| format!("读取 MLS 设备记录失败: {error}"))?;
+    let clear = open_state(state_key, &blob, STATE_AAD_DEVICE)?;
+    let record: DeviceRecord = serde_json::from_slice(&clear)
+        .map_err(|error| format!("解析 MLS 设备记录失败: {error}"))?;
+    if record.cid_number != cid_number || record.device_id != device_id {
+        return Err("MLS 状态目录已绑定到其他 CID 或设备".to_string());
+    }
+    Ok(Some(record))
+}
+
+fn write_device_record(
+    state_dir: &Path,
+    record: &DeviceRecord,
+    state_key: &[u8; 32],
+) -> Result<(), String> {
+    let clear = serde_json::to_vec(record).map_err(|error| error.to_string())?;
+    let sealed = seal_state(state_key, &clear, STATE_AAD_DEVICE)?;
+    atomic_write(&device_record_path(state_dir), &sealed)
+}
+
 fn credential_with_public_key(
     cid_number: &str,
     device_id: &str,
     public_key: Vec<u8>,
-) -> CredentialWithKey {
-    CredentialWithKey {
-        credential: BasicCredential::new(format!("{cid_number}:{device_id}").into_bytes()).into(),
+    attestation: Option<&DeviceBindingAttestation>,
+) -> Result<CredentialWithKey, String> {
+    Ok(CredentialWithKey {
+        credential: BasicCredential::new(chat_identity::credential_content(
+            cid_number,
+            device_id,
+            attestation,
+        )?)
+        .into(),
         signature_key: public_key.into(),
-    }
+    })
 }
 
 fn mls_group_config() -> MlsGroupCreateConfig {
@@ -875,14 +879,14 @@ fn decode_hex_field(field_name: &str, value: &str) -> Result<Vec<u8>, String> {
     hex::decode(normalized).map_err(|error| format!("{field_name} 不是合法 hex: {error}"))
 }
 
-fn storage_path(state_dir: &Path) -> PathBuf {
-    state_dir.join("openmls_storage.bin")
-}
-
 fn device_record_path(state_dir: &Path) -> PathBuf {
     state_dir.join("device.bin")
 }
 
+fn history_key_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("history_transfer.bin")
+}
+
 fn rekey_staged_path(path: &Path) -> PathBuf {
     path.with_extension("account_rekey")
 }
@@ -987,6 +991,9 @@ struct GroupCreateRequest {
     cid_number: String,
     device_id: String,
     group_id: String,
+    /// 机构群(`inst:<cid>:<范围>`)必填：建群者须为机构管理员且在名册内。
+    #[serde(default)]
+    institution_roster: Option<InstitutionRoster>,
 }
 
 #[derive(Deserialize)]
@@ -998,6 +1005,11 @@ struct GroupAddMembersRequest {
     device_id: String,
     group_id: String,
     key_packages_hex: Vec<String>,
+    /// 待加 CID 的链上绑定快照(轻节点 finalized 读数)。
+    chain_bindings: Vec<ChainBinding>,
+    /// 机构群必填：待加 CID 须在岗位名册内。
+    #[serde(default)]
+    institution_roster: Option<InstitutionRoster>,
 }
 
 #[derive(Deserialize)]
@@ -1010,6 +1022,9 @@ struct GroupRemoveMembersRequest {
     group_id: String,
     /// 按 CID 移除（移除该 CID 在群内的全部设备叶子）。
     member_cid_numbers: Vec<String>,
+    /// 机构群必填：只有机构管理员可以删人。
+    #[serde(default)]
+    institution_roster: Option<InstitutionRoster>,
 }
 
 #[derive(Deserialize)]
@@ -1033,6 +1048,11 @@ struct GroupProcessRequest {
     group_id: String,
     wire_message_hex: String,
     ratchet_tree_hex: Option<String>,
+    /// 群名册各 CID 的链上绑定快照；缺哪个 CID 由响应 `missing_cid_numbers` 指明。
+    chain_bindings: Vec<ChainBinding>,
+    /// 机构群的 Welcome / Commit 必填；缺失时回 `needs_institution_roster`。
+    #[serde(default)]
+    institution_roster: Option<InstitutionRoster>,
 }
 
 #[derive(Deserialize)]
@@ -1045,6 +1065,139 @@ struct GroupStateRequest {
     group_id: String,
 }
 
+#[derive(Deserialize)]
+struct GroupEvictStaleRequest {
+    state_store_dir: String,
+    /// MLS 本地状态信封密钥(32 字节 hex),由 Dart 侧 LocalKeyPurpose.mls 子钥下传。
+    state_key_hex: String,
+    cid_number: String,
+    device_id: String,
+    group_id: String,
+    /// 群名册全部 CID 的链上绑定快照。
+    chain_bindings: Vec<ChainBinding>,
+}
+
+#[derive(Deserialize)]
+struct GroupInstitutionSyncRequest {
+    state_store_dir: String,
+    /// MLS 本地状态信封密钥(32 字节 hex),由 Dart 侧 LocalKeyPurpose.mls 子钥下传。
+    state_key_hex: String,
+    cid_number: String,
+    device_id: String,
+    group_id: String,
+    /// 轻节点 finalized 读出的机构名册快照。
+    institution_roster: InstitutionRoster,
+}
+
+#[derive(Deserialize)]
+struct GroupRemoveDevicesRequest {
+    state_store_dir: String,
+    /// MLS 本地状态信封密钥(32 字节 hex),由 Dart 侧 LocalKeyPurpose.mls 子钥下传。
+    state_key_hex: String,
+    cid_number: String,
+    device_id: String,
+    group_id: String,
+    /// 按设备移除:`"cid_number:device_id"`,只移除对应叶子。
+    member_identities: Vec<String>,
+    /// 机构群必填：非管理员只能移除本人其他设备。
+    #[serde(default)]
+    institution_roster: Option<InstitutionRoster>,
+}
+
+#[derive(Deserialize)]
+struct HistoryRequestRequest {
+    state_store_dir: String,
+    /// MLS 本地状态信封密钥(32 字节 hex),由 Dart 侧 LocalKeyPurpose.mls 子钥下传。
+    state_key_hex: String,
+    cid_number: String,
+    device_id: String,
+}
+
+#[derive(Deserialize)]
+struct HistorySealRequest {
+    state_store_dir: String,
+    /// MLS 本地状态信封密钥(32 字节 hex),由 Dart 侧 LocalKeyPurpose.mls 子钥下传。
+    state_key_hex: String,
+    cid_number: String,
+    device_id: String,
+    request: HistoryRequest,
+    /// 本 CID 的链上绑定快照。
+    chain_bindings: Vec<ChainBinding>,
+    history_hex: String,
+}
+
+#[derive(Deserialize)]
+struct HistoryOpenRequest {
+    state_store_dir: String,
+    /// MLS 本地状态信封密钥(32 字节 hex),由 Dart 侧 LocalKeyPurpose.mls 子钥下传。
+    state_key_hex: String,
+    cid_number: String,
+    device_id: String,
+    bundle: HistoryBundle,
+    /// 本 CID 的链上绑定快照。
+    chain_bindings: Vec<ChainBinding>,
+}
+
+/// 新设备待用的一次性 HPKE 私钥(与设备记录同样整体信封加密落盘)。
+#[derive(Serialize, Deserialize)]
+struct PendingHistoryKey {
+    hpke_public_key_hex: String,
+    hpke_private_key_hex: String,
+    created_at_ms: u64,
+}
+
+#[derive(Deserialize)]
+struct AttachmentSealRequest {
+    state_store_dir: String,
+    /// MLS 本地状态信封密钥(32 字节 hex),由 Dart 侧 LocalKeyPurpose.mls 子钥下传。
+    state_key_hex: String,
+    cid_number: String,
+    device_id: String,
+    group_id: String,
+    /// 发送方生成的附件标识，进 exporter context 与每块 AAD。
+    attachment_id: String,
+    source_path: String,
+    output_path: String,
+    chunk_size: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct AttachmentOpenRequest {
+    /// 以下字段均取自 MLS 消息内的附件描述，不依赖接收方当前 epoch。
+    key_hex: String,
+    attachment_id: String,
+    chunk_size: usize,
+    content_hash_hex: String,
+    /// 给出时先核对密文再解密。
+    ciphertext_hash_hex: Option<String>,
+    encrypted_size: Option<u64>,
+    source_path: String,
+    output_path: String,
+}
+
+#[derive(Deserialize)]
+struct AttachmentVerifyRequest {
+    ciphertext_hash_hex: String,
+    encrypted_size: Option<u64>,
+    source_path: String,
+}
+
+#[derive(Deserialize)]
+struct DeviceBindingRequest {
+    state_store_dir: String,
+    /// MLS 本地状态信封密钥(32 字节 hex),由 Dart 侧 LocalKeyPurpose.mls 子钥下传。
+    state_key_hex: String,
+    cid_number: String,
+    device_id: String,
+    /// `prepare`:返回待 CID 绑定账户签名的摘要;`install`:验签后写入设备记录。
+    action: String,
+    account_id_hex: String,
+    binding_revision: u64,
+    expires_at_ms: u64,
+    nonce: String,
+    signature_hex: Option<String>,
+}
+
 /// 创建 MLS 群(创建者为唯一成员,epoch 0)。
 ///
 /// # Safety
@@ -1154,76 +1307,276 @@ pub unsafe extern "C" fn citizen_chat_mls_group_state_json(
     }
 }
 
-/// 从 BasicCredential 还原成员标识（"cid_number:device_id"）。
-fn identity_of(credential: &Credential) -> String {
-    String::from_utf8_lossy(credential.serialized_content()).into_owned()
+/// 按链上绑定快照为本设备签发/安装 MLS 凭证授权证明。
+///
+/// # Safety
+/// 见 `citizen_chat_mls_create_key_package_json`。
+#[no_mangle]
+pub unsafe extern "C" fn citizen_chat_mls_device_binding_json(
+    request_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    match device_binding_json(request_json) {
+        Ok(value) => crate::string_into_raw(value, error_out),
+        Err(message) => {
+            crate::set_error(error_out, &message);
+            std::ptr::null_mut()
+        }
+    }
 }
 
-/// 从成员标识取 CID 段（"cid_number:device_id" → "cid_number"）。
-fn cid_of(credential: &Credential) -> String {
-    let identity = identity_of(credential);
-    match identity.split_once(':') {
-        Some((cid_number, _)) => cid_number.to_string(),
-        None => identity,
+/// 驱逐名册中链上授权已失效(CID 换绑/注销/无证明)的叶子:产 Remove Commit。
+///
+/// # Safety
+/// 见 `citizen_chat_mls_create_key_package_json`。
+#[no_mangle]
+pub unsafe extern "C" fn citizen_chat_mls_group_evict_stale_json(
+    request_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    match group_evict_stale_json(request_json) {
+        Ok(value) => crate::string_into_raw(value, error_out),
+        Err(message) => {
+            crate::set_error(error_out, &message);
+            std::ptr::null_mut()
+        }
     }
 }
 
-fn group_create_json(request_json: *const c_char) -> Result<String, String> {
-    let request: GroupCreateRequest = parse_request(request_json)?;
-    require_non_empty("state_store_dir", &request.state_store_dir)?;
-    require_non_empty("cid_number", &request.cid_number)?;
-    require_non_empty("device_id", &request.device_id)?;
-    require_non_empty("group_id", &request.group_id)?;
+/// 按链上机构名册同步机构群:移除已卸任成员(产 Remove Commit),回吐待加入 CID。
+///
+/// # Safety
+/// 见 `citizen_chat_mls_create_key_package_json`。
+#[no_mangle]
+pub unsafe extern "C" fn citizen_chat_mls_group_institution_sync_json(
+    request_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    match group_institution_sync_json(request_json) {
+        Ok(value) => crate::string_into_raw(value, error_out),
+        Err(message) => {
+            crate::set_error(error_out, &message);
+            std::ptr::null_mut()
+        }
+    }
+}
 
-    let state_dir = Path::new(&request.state_store_dir);
-    let state_key = parse_state_key(&request.state_key_hex)?;
-    let provider = load_provider(state_dir, &state_key)?;
-    let (credential, signer) = ensure_device_signer(
-        &provider,
-        state_dir,
-        &request.cid_number,
-        &request.device_id,
-        &state_key,
-    )?;
-    let group_id = group_id_from_conversation(&request.group_id)?;
-    if MlsGroup::load(provider.storage(), &group_id)
-        .map_err(|error| format!("加载 MLS 群失败: {error:?}"))?
-        .is_some()
-    {
-        return Err("MLS 群已存在，请勿重复创建".to_string());
+/// 按设备移除叶子(本人设备吊销 / 管理员移除单台设备),产 Remove Commit。
+///
+/// # Safety
+/// 见 `citizen_chat_mls_create_key_package_json`。
+#[no_mangle]
+pub unsafe extern "C" fn citizen_chat_mls_group_remove_devices_json(
+    request_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    match group_remove_devices_json(request_json) {
+        Ok(value) => crate::string_into_raw(value, error_out),
+        Err(message) => {
+            crate::set_error(error_out, &message);
+            std::ptr::null_mut()
+        }
     }
-    let group = MlsGroup::new_with_group_id(
-        &provider,
-        &signer,
-        &mls_group_config(),
-        group_id,
-        credential,
-    )
-    .map_err(|error| format!("创建 MLS 群失败: {error:?}"))?;
-    let epoch = group.epoch().as_u64();
-    save_provider(state_dir, &provider, &state_key)?;
+}
 
-    let response = json!({
-        "group_id": request.group_id,
-        "epoch": epoch,
-        "cipher_suite": format!("{:?}", GMB_MLS_CIPHERSUITE),
-    });
-    serde_json::to_string(&response).map_err(|error| error.to_string())
+/// 新设备发起历史迁移:生成一次性 HPKE 密钥并签名请求。
+///
+/// # Safety
+/// 见 `citizen_chat_mls_create_key_package_json`。
+#[no_mangle]
+pub unsafe extern "C" fn citizen_chat_mls_history_request_json(
+    request_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    match history_request_json(request_json) {
+        Ok(value) => crate::string_into_raw(value, error_out),
+        Err(message) => {
+            crate::set_error(error_out, &message);
+            std::ptr::null_mut()
+        }
+    }
 }
 
-fn group_add_members_json(request_json: *const c_char) -> Result<String, String> {
-    let request: GroupAddMembersRequest = parse_request(request_json)?;
-    require_non_empty("state_store_dir", &request.state_store_dir)?;
-    require_non_empty("cid_number", &request.cid_number)?;
-    require_non_empty("device_id", &request.device_id)?;
-    require_non_empty("group_id", &request.group_id)?;
-    if request.key_packages_hex.is_empty() {
-        return Err("group_add_members 至少需要一个 KeyPackage".to_string());
+/// 老设备核对同 CID 请求后封装本地历史。
+///
+/// # Safety
+/// 见 `citizen_chat_mls_create_key_package_json`。
+#[no_mangle]
+pub unsafe extern "C" fn citizen_chat_mls_history_seal_json(
+    request_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    match history_seal_json(request_json) {
+        Ok(value) => crate::string_into_raw(value, error_out),
+        Err(message) => {
+            crate::set_error(error_out, &message);
+            std::ptr::null_mut()
+        }
     }
+}
 
-    let state_dir = Path::new(&request.state_store_dir);
-    let state_key = parse_state_key(&request.state_key_hex)?;
-    let provider = load_provider(state_dir, &state_key)?;
+/// 新设备解封历史迁移包(一次性私钥随即删除)。
+///
+/// # Safety
+/// 见 `citizen_chat_mls_create_key_package_json`。
+#[no_mangle]
+pub unsafe extern "C" fn citizen_chat_mls_history_open_json(
+    request_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    match history_open_json(request_json) {
+        Ok(value) => crate::string_into_raw(value, error_out),
+        Err(message) => {
+            crate::set_error(error_out, &message);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// 以当前 epoch 的 exporter secret 派生附件密钥，流式加密本地文件。
+///
+/// # Safety
+/// 见 `citizen_chat_mls_create_key_package_json`。
+#[no_mangle]
+pub unsafe extern "C" fn citizen_chat_mls_attachment_seal_json(
+    request_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    match attachment_seal_json(request_json) {
+        Ok(value) => crate::string_into_raw(value, error_out),
+        Err(message) => {
+            crate::set_error(error_out, &message);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// 按 MLS 消息内的附件描述解密并核对内容哈希。
+///
+/// # Safety
+/// 见 `citizen_chat_mls_create_key_package_json`。
+#[no_mangle]
+pub unsafe extern "C" fn citizen_chat_mls_attachment_open_json(
+    request_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    match attachment_open_json(request_json) {
+        Ok(value) => crate::string_into_raw(value, error_out),
+        Err(message) => {
+            crate::set_error(error_out, &message);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// 只核对已下载附件密文的大小与哈希(无需密钥)。
+///
+/// # Safety
+/// 见 `citizen_chat_mls_create_key_package_json`。
+#[no_mangle]
+pub unsafe extern "C" fn citizen_chat_mls_attachment_verify_json(
+    request_json: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    match attachment_verify_json(request_json) {
+        Ok(value) => crate::string_into_raw(value, error_out),
+        Err(message) => {
+            crate::set_error(error_out, &message);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// 从 BasicCredential 还原成员标识（"cid_number:device_id"，不含授权证明段）。
+fn identity_of(credential: &Credential) -> String {
+    chat_identity::member_identity(credential.serialized_content())
+}
+
+/// 从成员标识取 CID 段（"cid_number:device_id" → "cid_number"）。
+fn cid_of(credential: &Credential) -> String {
+    let identity = identity_of(credential);
+    match identity.split_once(':') {
+        Some((cid_number, _)) => cid_number.to_string(),
+        None => identity,
+    }
+}
+
+fn group_create_json(request_json: *const c_char) -> Result<String, String> {
+    let request: GroupCreateRequest = parse_request(request_json)?;
+    require_non_empty("state_store_dir", &request.state_store_dir)?;
+    require_non_empty("cid_number", &request.cid_number)?;
+    require_non_empty("device_id", &request.device_id)?;
+    require_non_empty("group_id", &request.group_id)?;
+    if let Some(roster) =
+        chat_institution::roster_for(&request.group_id, request.institution_roster.as_ref())?
+    {
+        require_institution_admin(roster, &request.cid_number)?;
+        if !roster.is_member(&request.cid_number) {
+            return Err("建群者不在该机构群名册内".to_string());
+        }
+    }
+
+    let state_dir = Path::new(&request.state_store_dir);
+    let state_key = parse_state_key(&request.state_key_hex)?;
+    let provider = load_provider(state_dir, &state_key)?;
+    let (credential, signer) = ensure_device_signer(
+        &provider,
+        state_dir,
+        &request.cid_number,
+        &request.device_id,
+        &state_key,
+    )?;
+    let group_id = group_id_from_conversation(&request.group_id)?;
+    if MlsGroup::load(provider.storage(), &group_id)
+        .map_err(|error| format!("加载 MLS 群失败: {error:?}"))?
+        .is_some()
+    {
+        return Err("MLS 群已存在，请勿重复创建".to_string());
+    }
+    let group = MlsGroup::new_with_group_id(
+        &provider,
+        &signer,
+        &mls_group_config(),
+        group_id,
+        credential,
+    )
+    .map_err(|error| format!("创建 MLS 群失败: {error:?}"))?;
+    let epoch = group.epoch().as_u64();
+    save_provider(state_dir, &provider)?;
+
+    let response = json!({
+        "group_id": request.group_id,
+        "epoch": epoch,
+        "cipher_suite": format!("{:?}", GMB_MLS_CIPHERSUITE),
+    });
+    serde_json::to_string(&response).map_err(|error| error.to_string())
+}
+
+fn group_add_members_json(request_json: *const c_char) -> Result<String, String> {
+    let request: GroupAddMembersRequest = parse_request(request_json)?;
+    require_non_empty("state_store_dir", &request.state_store_dir)?;
+    require_non_empty("cid_number", &request.cid_number)?;
+    require_non_empty("device_id", &request.device_id)?;
+    require_non_empty("group_id", &request.group_id)?;
+    if request.key_packages_hex.is_empty() {
+        return Err("group_add_members 至少需要一个 KeyPackage".to_string());
+    }
+    let roster =
+        chat_institution::roster_for(&request.group_id, request.institution_roster.as_ref())?;
+    // 非管理员只能把本人其他设备加入(逐个 KeyPackage 核对 CID)。
+    let own_devices_only = match roster {
+        Some(roster) if !roster.is_admin(&request.cid_number) => {
+            if !roster.is_member(&request.cid_number) {
+                return Err("只有机构管理员可以调整机构群名册".to_string());
+            }
+            true
+        }
+        _ => false,
+    };
+
+    let state_dir = Path::new(&request.state_store_dir);
+    let state_key = parse_state_key(&request.state_key_hex)?;
+    let provider = load_provider(state_dir, &state_key)?;
     let (_credential, signer) = ensure_device_signer(
         &provider,
         state_dir,
@@ -1246,6 +1599,8 @@ fn group_add_members_json(request_json: *const c_char) -> Result<String, String>
         ));
     }
 
+    let bindings = ChainBindings::new(request.chain_bindings)?;
+    let now = now_millis();
     let mut key_packages = Vec::with_capacity(adding);
     for (index, kp_hex) in request.key_packages_hex.iter().enumerate() {
         let bytes = decode_hex_field(&format!("key_packages_hex[{index}]"), kp_hex)?;
@@ -1253,6 +1608,32 @@ fn group_add_members_json(request_json: *const c_char) -> Result<String, String>
             .map_err(|error| format!("反序列化 KeyPackage[{index}] 失败: {error}"))?
             .validate(provider.crypto(), ProtocolVersion::default())
             .map_err(|error| format!("验证 KeyPackage[{index}] 失败: {error:?}"))?;
+        let leaf = key_package.leaf_node();
+        bindings
+            .verify_leaf(
+                leaf.credential().serialized_content(),
+                leaf.signature_key().as_slice(),
+                Some(now),
+            )
+            .map_err(|reason| {
+                format!(
+                    "KeyPackage[{index}] 未获 CID 链上绑定授权: {}",
+                    reason.as_str()
+                )
+            })?;
+        if let Some(roster) = roster {
+            let cid_number = cid_of(leaf.credential());
+            if own_devices_only && cid_number != request.cid_number {
+                return Err(format!(
+                    "KeyPackage[{index}] 不是本人设备，非机构管理员只能加入本人设备"
+                ));
+            }
+            if !roster.is_member(&cid_number) {
+                return Err(format!(
+                    "KeyPackage[{index}] 的 CID {cid_number} 不在机构群名册内"
+                ));
+            }
+        }
         key_packages.push(key_package);
     }
 
@@ -1280,7 +1661,7 @@ fn group_add_members_json(request_json: *const c_char) -> Result<String, String>
             .map_err(|error| format!("序列化 ratchet tree 失败: {error}"))?,
     );
     let epoch = group.epoch().as_u64();
-    save_provider(state_dir, &provider, &state_key)?;
+    save_provider(state_dir, &provider)?;
 
     let response = json!({
         "group_id": request.group_id,
@@ -1301,6 +1682,11 @@ fn group_remove_members_json(request_json: *const c_char) -> Result<String, Stri
     if request.member_cid_numbers.is_empty() {
         return Err("group_remove_members 至少需要一个成员 CID".to_string());
     }
+    if let Some(roster) =
+        chat_institution::roster_for(&request.group_id, request.institution_roster.as_ref())?
+    {
+        require_institution_admin(roster, &request.cid_number)?;
+    }
 
     let state_dir = Path::new(&request.state_store_dir);
     let state_key = parse_state_key(&request.state_key_hex)?;
@@ -1350,7 +1736,7 @@ fn group_remove_members_json(request_json: *const c_char) -> Result<String, Stri
             .map_err(|error| format!("序列化 Commit 失败: {error}"))?,
     );
     let epoch = group.epoch().as_u64();
-    save_provider(state_dir, &provider, &state_key)?;
+    save_provider(state_dir, &provider)?;
 
     let response = json!({
         "group_id": request.group_id,
@@ -1394,7 +1780,7 @@ fn group_create_message_json(request_json: *const c_char) -> Result<String, Stri
             .map_err(|error| format!("序列化群 application message 失败: {error}"))?,
     );
     let epoch = group.epoch().as_u64();
-    save_provider(state_dir, &provider, &state_key)?;
+    save_provider(state_dir, &provider)?;
 
     let response = json!({
         "group_id": request.group_id,
@@ -1426,6 +1812,7 @@ fn group_process_json(request_json: *const c_char) -> Result<String, String> {
     let wire_bytes = decode_hex_field("wire_message_hex", &request.wire_message_hex)?;
     let message_in = MlsMessageIn::tls_deserialize_exact(wire_bytes)
         .map_err(|error| format!("反序列化 MLS wire message 失败: {error}"))?;
+    let bindings = ChainBindings::new(request.chain_bindings)?;
 
     let response = match message_in.extract() {
         MlsMessageBodyIn::Welcome(welcome) => {
@@ -1439,25 +1826,83 @@ fn group_process_json(request_json: *const c_char) -> Result<String, String> {
                 }
                 _ => None,
             };
-            let group = StagedWelcome::new_from_welcome(
+            let staged = StagedWelcome::new_from_welcome(
                 &provider,
                 mls_group_config().join_config(),
                 welcome,
                 ratchet_tree,
             )
-            .map_err(|error| format!("处理群 Welcome 失败: {error:?}"))?
-            .into_group(&provider)
-            .map_err(|error| format!("从 Welcome 创建群失败: {error:?}"))?;
+            .map_err(|error| format!("处理群 Welcome 失败: {error:?}"))?;
+            let welcome_sender_cid = cid_of(
+                staged
+                    .welcome_sender()
+                    .map_err(|error| format!("读取 Welcome 发起方失败: {error:?}"))?
+                    .credential(),
+            );
+            let group = staged
+                .into_group(&provider)
+                .map_err(|error| format!("从 Welcome 创建群失败: {error:?}"))?;
             if group.group_id() != &group_id {
                 return Err("Welcome group_id 与 group_id 不一致".to_string());
             }
             let epoch = group.epoch().as_u64();
+            // 机构群:只接受机构管理员拉入,且本机 CID 须在名册内;快照缺失则不落盘待补读。
+            let institution =
+                match institution_roster(&request.group_id, request.institution_roster.as_ref())? {
+                    InstitutionCheck::NotInstitution => None,
+                    InstitutionCheck::NeedsRoster => {
+                        return serde_json::to_string(&needs_institution_roster_response(
+                            &request.group_id,
+                            "welcome",
+                            epoch,
+                            epoch,
+                        ))
+                        .map_err(|error| error.to_string());
+                    }
+                    InstitutionCheck::Roster(roster) => Some(roster),
+                };
+            if let Some(roster) = institution {
+                // 本人其他设备拉入本设备同样合法(多设备),其余须为机构管理员。
+                let detail = if !roster.is_admin(&welcome_sender_cid)
+                    && welcome_sender_cid != request.cid_number
+                {
+                    Some(format!(
+                        "Welcome 发起方 {welcome_sender_cid} 不是机构管理员"
+                    ))
+                } else if !roster.is_member(&request.cid_number) {
+                    Some("本机 CID 不在机构群名册内".to_string())
+                } else {
+                    None
+                };
+                if let Some(detail) = detail {
+                    return serde_json::to_string(&rejected_response(
+                        &request.group_id,
+                        "welcome",
+                        epoch,
+                        epoch,
+                        detail,
+                    ))
+                    .map_err(|error| error.to_string());
+                }
+            }
+            // 入群即逐叶核对链上授权；快照不全则不落盘，待 Dart 补读后重放同一 Welcome。
+            let audit = audit_roster(&group, &bindings, None);
+            if !audit.missing_cid_numbers.is_empty() {
+                return serde_json::to_string(&needs_bindings_response(
+                    &request.group_id,
+                    "welcome",
+                    epoch,
+                    epoch,
+                    &audit.missing_cid_numbers,
+                ))
+                .map_err(|error| error.to_string());
+            }
             let members: Vec<String> = group
                 .members()
                 .map(|m| identity_of(&m.credential))
                 .collect();
-            save_provider(state_dir, &provider, &state_key)?;
-            json!({
+            save_provider(state_dir, &provider)?;
+            let mut response = json!({
                 "group_id": request.group_id,
                 "message_kind": "welcome",
                 "status": "applied",
@@ -1466,23 +1911,30 @@ fn group_process_json(request_json: *const c_char) -> Result<String, String> {
                 "self_removed": false,
                 "plaintext_hex": serde_json::Value::Null,
                 "member_identities": members,
-            })
+                "stale_members": stale_members_json(&audit.stale),
+            });
+            if let Some(roster) = institution {
+                attach_institution_diff(&mut response, roster, &group);
+            }
+            response
         }
         MlsMessageBodyIn::PublicMessage(message) => process_group_protocol(
             state_dir,
             &provider,
-            &state_key,
             &request.group_id,
             group_id,
             message.into(),
+            &bindings,
+            request.institution_roster.as_ref(),
         )?,
         MlsMessageBodyIn::PrivateMessage(message) => process_group_protocol(
             state_dir,
             &provider,
-            &state_key,
             &request.group_id,
             group_id,
             message.into(),
+            &bindings,
+            request.institution_roster.as_ref(),
         )?,
         _ => return Err("不支持的群 MLS wire message 类型".to_string()),
     };
@@ -1491,13 +1943,19 @@ fn group_process_json(request_json: *const c_char) -> Result<String, String> {
 
 /// Commit/Application 的 epoch 有序处理。message_epoch>current→out_of_order(不处理,
 /// Dart 缓冲);<current 或解密失败→stale;==→应用并回吐名册/自我移除标志。
+///
+/// Commit 合并后逐叶核对链上授权:本 Commit 新进的叶子不合格→rejected(不落盘);
+/// 快照不全→needs_bindings(不落盘);既有叶子失效→照常应用并回吐 `stale_members`
+/// 供上层发起驱逐。机构群的 Commit 另须由机构管理员提交、新进叶子须在名册内。
+#[allow(clippy::too_many_arguments)]
 fn process_group_protocol(
     state_dir: &Path,
     provider: &MlsProvider,
-    state_key: &[u8; 32],
     conversation_id: &str,
     group_id: GroupId,
     protocol_message: ProtocolMessage,
+    bindings: &ChainBindings,
+    institution_roster: Option<&InstitutionRoster>,
 ) -> Result<serde_json::Value, String> {
     let message_epoch = protocol_message.epoch().as_u64();
     let mut group = MlsGroup::load(provider.storage(), &group_id)
@@ -1535,11 +1993,12 @@ fn process_group_protocol(
         }
     };
 
+    let sender_cid = cid_of(processed.credential());
     match processed.into_content() {
         ProcessedMessageContent::ApplicationMessage(message) => {
             let plaintext = message.into_bytes();
             let epoch = group.epoch().as_u64();
-            save_provider(state_dir, provider, state_key)?;
+            save_provider(state_dir, provider)?;
             Ok(json!({
                 "group_id": conversation_id,
                 "message_kind": "application",
@@ -1552,12 +2011,88 @@ fn process_group_protocol(
             }))
         }
         ProcessedMessageContent::StagedCommitMessage(staged) => {
+            let institution = match institution_roster(conversation_id, institution_roster)? {
+                InstitutionCheck::NotInstitution => None,
+                InstitutionCheck::NeedsRoster => {
+                    return Ok(needs_institution_roster_response(
+                        conversation_id,
+                        "commit",
+                        message_epoch,
+                        current,
+                    ));
+                }
+                InstitutionCheck::Roster(roster) => Some(roster),
+            };
             let self_removed = staged.self_removed();
+            let previous: HashMap<Vec<u8>, String> = group
+                .members()
+                .map(|m| (m.signature_key, cid_of(&m.credential)))
+                .collect();
             group
                 .merge_staged_commit(provider, *staged)
                 .map_err(|error| format!("合并群 Commit 失败: {error:?}"))?;
             let epoch = group.epoch().as_u64();
+            // 机构群:管理员可任意调整名册;在册成员只能增删本人设备的叶子(多设备)。
+            if let Some(roster) = institution {
+                if !roster.is_admin(&sender_cid)
+                    && (!roster.is_member(&sender_cid)
+                        || touches_other_cids(&group, &previous, &sender_cid))
+                {
+                    return Ok(rejected_response(
+                        conversation_id,
+                        "commit",
+                        message_epoch,
+                        current,
+                        format!("机构群 Commit 提交方 {sender_cid} 不是机构管理员"),
+                    ));
+                }
+            }
+            let mut stale = Vec::new();
             let members: Vec<String> = if group.is_active() {
+                let audit = audit_roster(&group, bindings, Some((&previous, now_millis())));
+                if !audit.missing_cid_numbers.is_empty() {
+                    return Ok(needs_bindings_response(
+                        conversation_id,
+                        "commit",
+                        message_epoch,
+                        current,
+                        &audit.missing_cid_numbers,
+                    ));
+                }
+                if let Some(leaf) = audit
+                    .stale
+                    .iter()
+                    .find(|leaf| !previous.contains_key(&leaf.signature_key))
+                {
+                    return Ok(rejected_response(
+                        conversation_id,
+                        "commit",
+                        message_epoch,
+                        current,
+                        format!(
+                            "Commit 新增成员 {} 未获 CID 链上绑定授权: {}",
+                            leaf.identity,
+                            leaf.reason.as_str()
+                        ),
+                    ));
+                }
+                if let Some(roster) = institution {
+                    if let Some(outsider) = group
+                        .members()
+                        .filter(|m| !previous.contains_key(&m.signature_key))
+                        .map(|m| cid_of(&m.credential))
+                        .find(|cid_number| !roster.is_member(cid_number))
+                    {
+                        return Ok(rejected_response(
+                            conversation_id,
+                            "commit",
+                            message_epoch,
+                            current,
+                            format!("Commit 新增成员 {outsider} 不在机构群名册内"),
+                        ));
+                    }
+                }
+                stale = audit.stale;
                 group
                     .members()
                     .map(|m| identity_of(&m.credential))
@@ -1565,8 +2100,8 @@ fn process_group_protocol(
             } else {
                 Vec::new()
             };
-            save_provider(state_dir, provider, state_key)?;
-            Ok(json!({
+            save_provider(state_dir, provider)?;
+            let mut response = json!({
                 "group_id": conversation_id,
                 "message_kind": "commit",
                 "status": "applied",
@@ -1575,7 +2110,12 @@ fn process_group_protocol(
                 "self_removed": self_removed,
                 "plaintext_hex": serde_json::Value::Null,
                 "member_identities": members,
-            }))
+                "stale_members": stale_members_json(&stale),
+            });
+            if let (Some(roster), true) = (institution, group.is_active()) {
+                attach_institution_diff(&mut response, roster, &group);
+            }
+            Ok(response)
         }
         _ => Err("群暂不支持独立提案消息".to_string()),
     }
@@ -1617,105 +2157,905 @@ fn group_state_json(request_json: *const c_char) -> Result<String, String> {
     serde_json::to_string(&response).map_err(|error| error.to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{
-        atomic_write, create_key_package_json, group_add_members_json, group_create_json,
-        group_create_message_json, group_process_json, group_remove_members_json, group_state_json,
-        open_state, parse_state_key, purge_legacy_plaintext_state, seal_state,
-        two_party_smoke_json, STATE_AAD_DEVICE, STATE_AAD_STORAGE,
-    };
-    use std::ffi::CString;
-    use std::fs;
+/// 名册中未通过链上授权校验的一片叶子。
+struct StaleLeaf {
+    index: LeafNodeIndex,
+    identity: String,
+    signature_key: Vec<u8>,
+    reason: LeafRejection,
+}
 
-    /// MLS 状态信封测试密钥(64 hex = 32 字节)。
-    const TEST_STATE_KEY_HEX: &str =
-        "0101010101010101010101010101010101010101010101010101010101010101";
+/// 名册逐叶核对结果:缺快照的 CID(须补读)与授权已失效的叶子(须驱逐)。
+struct RosterAudit {
+    missing_cid_numbers: Vec<String>,
+    stale: Vec<StaleLeaf>,
+}
 
-    #[test]
-    fn creates_real_openmls_key_package() {
-        let request = CString::new(r#"{"cid_number":"CID-ALICE","device_id":"alice-phone"}"#)
-            .expect("request should be valid");
-        let response =
-            create_key_package_json(request.as_ptr()).expect("key package should be created");
-        let json: serde_json::Value =
-            serde_json::from_str(&response).expect("response should be json");
-        assert_eq!(json["cid_number"], "CID-ALICE");
-        assert!(json["key_package_hex"].as_str().unwrap().len() > 100);
-        // 键名即跨语言契约:Dart 侧 `mls_native.dart` 按这些名字读,漏 `_hex`
-        // 后缀会让设备公钥恒空并卡死 Chat 首启(2026-08-04)。改名必须两侧同改。
-        let device_public_key_hex = json["device_public_key_hex"]
-            .as_str()
-            .expect("响应必须含 device_public_key_hex");
-        assert!(!device_public_key_hex.is_empty());
-        assert!(device_public_key_hex
-            .chars()
-            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c)));
+/// `admitted` 为 `Some((previous, now))` 时，不在 `previous` 中的叶子视为本次新进，
+/// 与 KeyPackage 入群同口径额外校验证明期限；既有叶子不因证明过期被驱逐。
+fn audit_roster(
+    group: &MlsGroup,
+    bindings: &ChainBindings,
+    admitted: Option<(&HashMap<Vec<u8>, String>, u64)>,
+) -> RosterAudit {
+    let mut missing = HashSet::new();
+    let mut stale = Vec::new();
+    for member in group.members() {
+        let now_millis = admitted
+            .filter(|(previous, _)| !previous.contains_key(&member.signature_key))
+            .map(|(_, now)| now);
+        match bindings.verify_leaf(
+            member.credential.serialized_content(),
+            &member.signature_key,
+            now_millis,
+        ) {
+            Ok(()) => {}
+            Err(LeafRejection::MissingBinding) => {
+                missing.insert(cid_of(&member.credential));
+            }
+            Err(reason) => stale.push(StaleLeaf {
+                index: member.index,
+                identity: identity_of(&member.credential),
+                signature_key: member.signature_key,
+                reason,
+            }),
+        }
     }
-
-    #[test]
-    fn openmls_two_party_smoke_round_trips_plaintext() {
-        let request =
-            CString::new(r#"{"plaintext":"hello openmls"}"#).expect("request should be valid");
-        let response = two_party_smoke_json(request.as_ptr()).expect("smoke should pass");
-        let json: serde_json::Value =
-            serde_json::from_str(&response).expect("response should be json");
-        assert_eq!(json["plaintext"], "hello openmls");
-        assert_eq!(json["decrypted_plaintext"], "hello openmls");
-        assert!(json["alice_wire_message_hex"].as_str().unwrap().len() > 100);
+    let mut missing_cid_numbers: Vec<String> = missing.into_iter().collect();
+    missing_cid_numbers.sort();
+    RosterAudit {
+        missing_cid_numbers,
+        stale,
     }
+}
 
-    #[test]
-    fn group_three_party_round_trip() {
-        use serde_json::json;
-        use std::fs;
-        use std::os::raw::c_char;
-        use std::path::Path;
+fn stale_members_json(stale: &[StaleLeaf]) -> serde_json::Value {
+    serde_json::Value::Array(
+        stale
+            .iter()
+            .map(|leaf| json!({"identity": leaf.identity, "reason": leaf.reason.as_str()}))
+            .collect(),
+    )
+}
 
-        let base = std::env::temp_dir().join(format!("citizen_group_rt_{}", std::process::id()));
-        let _ = fs::remove_dir_all(&base);
-        let dir_a = base.join("a");
-        let dir_b = base.join("b");
-        let dir_c = base.join("c");
-        for d in [&dir_a, &dir_b, &dir_c] {
-            fs::create_dir_all(d).expect("临时目录应可创建");
-        }
-        let group_id = "grp:CID-A:testnonce";
-        let path = |p: &Path| p.to_str().unwrap().to_string();
+/// 快照缺 CID 时的统一回包:状态不落盘,Dart 补读 `missing_cid_numbers` 后重放。
+fn needs_bindings_response(
+    conversation_id: &str,
+    message_kind: &str,
+    message_epoch: u64,
+    group_epoch: u64,
+    missing_cid_numbers: &[String],
+) -> serde_json::Value {
+    json!({
+        "group_id": conversation_id,
+        "message_kind": message_kind,
+        "status": "needs_bindings",
+        "message_epoch": message_epoch,
+        "group_epoch": group_epoch,
+        "self_removed": false,
+        "plaintext_hex": serde_json::Value::Null,
+        "member_identities": serde_json::Value::Null,
+        "missing_cid_numbers": missing_cid_numbers,
+    })
+}
 
-        let invoke = |f: fn(*const c_char) -> Result<String, String>, req: serde_json::Value| {
-            let c = CString::new(serde_json::to_string(&req).unwrap()).unwrap();
-            let out = f(c.as_ptr()).expect("FFI 调用应成功");
-            serde_json::from_str::<serde_json::Value>(&out).expect("响应应为 JSON")
-        };
+/// 群握手被拒的统一回包:状态不落盘,`detail` 说明原因。
+fn rejected_response(
+    conversation_id: &str,
+    message_kind: &str,
+    message_epoch: u64,
+    group_epoch: u64,
+    detail: String,
+) -> serde_json::Value {
+    json!({
+        "group_id": conversation_id,
+        "message_kind": message_kind,
+        "status": "rejected",
+        "message_epoch": message_epoch,
+        "group_epoch": group_epoch,
+        "self_removed": false,
+        "plaintext_hex": serde_json::Value::Null,
+        "member_identities": serde_json::Value::Null,
+        "detail": detail,
+    })
+}
 
-        // A 建群(创建者=唯一成员,epoch 0)。
-        let created = invoke(
-            group_create_json,
-            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id}),
-        );
-        assert_eq!(created["epoch"].as_u64(), Some(0));
+/// 机构群握手缺名册快照:状态不落盘,Dart 读取机构名册后重放。
+fn needs_institution_roster_response(
+    conversation_id: &str,
+    message_kind: &str,
+    message_epoch: u64,
+    group_epoch: u64,
+) -> serde_json::Value {
+    json!({
+        "group_id": conversation_id,
+        "message_kind": message_kind,
+        "status": "needs_institution_roster",
+        "message_epoch": message_epoch,
+        "group_epoch": group_epoch,
+        "self_removed": false,
+        "plaintext_hex": serde_json::Value::Null,
+        "member_identities": serde_json::Value::Null,
+    })
+}
 
-        // B / C 生成 KeyPackage。
-        let b_kp = invoke(
-            create_key_package_json,
-            json!({"cid_number": "CID-B", "device_id": "devB", "state_store_dir": path(&dir_b), "state_key_hex": TEST_STATE_KEY_HEX}),
-        )["key_package_hex"]
-            .as_str()
-            .unwrap()
-            .to_string();
-        let c_kp = invoke(
-            create_key_package_json,
-            json!({"cid_number": "CID-C", "device_id": "devC", "state_store_dir": path(&dir_c), "state_key_hex": TEST_STATE_KEY_HEX}),
-        )["key_package_hex"]
-            .as_str()
-            .unwrap()
-            .to_string();
+/// 机构群握手对名册快照的需求。
+enum InstitutionCheck<'a> {
+    NotInstitution,
+    NeedsRoster,
+    Roster(&'a InstitutionRoster),
+}
 
-        // A 批量加 B、C(1 Commit + 1 Welcome)。
+fn institution_roster<'a>(
+    conversation_id: &str,
+    roster: Option<&'a InstitutionRoster>,
+) -> Result<InstitutionCheck<'a>, String> {
+    let Some(group) = chat_institution::parse_group_id(conversation_id) else {
+        return Ok(InstitutionCheck::NotInstitution);
+    };
+    let Some(roster) = roster else {
+        return Ok(InstitutionCheck::NeedsRoster);
+    };
+    roster.check_for(&group)?;
+    Ok(InstitutionCheck::Roster(roster))
+}
+
+fn require_institution_admin(roster: &InstitutionRoster, cid_number: &str) -> Result<(), String> {
+    if roster.is_admin(cid_number) {
+        Ok(())
+    } else {
+        Err("只有机构管理员可以调整机构群名册".to_string())
+    }
+}
+
+/// Commit 是否增删了提交方以外 CID 的叶子(按签名公钥比对合并前后名册)。
+fn touches_other_cids(
+    group: &MlsGroup,
+    previous: &HashMap<Vec<u8>, String>,
+    sender_cid: &str,
+) -> bool {
+    let current: HashMap<Vec<u8>, String> = group
+        .members()
+        .map(|m| (m.signature_key, cid_of(&m.credential)))
+        .collect();
+    let added = current
+        .iter()
+        .filter(|(key, _)| !previous.contains_key(*key));
+    let removed = previous
+        .iter()
+        .filter(|(key, _)| !current.contains_key(*key));
+    added
+        .chain(removed)
+        .any(|(_, cid_number)| cid_number != sender_cid)
+}
+
+/// 机构群应用握手后回吐名册差集,供管理员设备补发加人 / 删人。
+fn attach_institution_diff(
+    response: &mut serde_json::Value,
+    roster: &InstitutionRoster,
+    group: &MlsGroup,
+) {
+    let diff = roster.diff(group.members().map(|m| cid_of(&m.credential)));
+    response["institution_missing"] = json!(diff.missing);
+    response["institution_extraneous"] = json!(diff.extraneous);
+}
+
+fn device_binding_json(request_json: *const c_char) -> Result<String, String> {
+    let request: DeviceBindingRequest = parse_request(request_json)?;
+    require_non_empty("state_store_dir", &request.state_store_dir)?;
+    require_non_empty("cid_number", &request.cid_number)?;
+    require_non_empty("device_id", &request.device_id)?;
+    require_non_empty("nonce", &request.nonce)?;
+    let account_id_hex = chat_identity::normalize_account_id(&request.account_id_hex)?;
+    if request.binding_revision == 0 {
+        return Err("binding_revision 必须大于 0".to_string());
+    }
+    if request.expires_at_ms <= now_millis() {
+        return Err("设备绑定证明已过期".to_string());
+    }
+
+    let state_dir = Path::new(&request.state_store_dir);
+    let state_key = parse_state_key(&request.state_key_hex)?;
+    let provider = load_provider(state_dir, &state_key)?;
+    let (_credential, signer) = ensure_device_signer(
+        &provider,
+        state_dir,
+        &request.cid_number,
+        &request.device_id,
+        &state_key,
+    )?;
+    let device_public_key_hex = hex::encode(signer.to_public_vec());
+
+    let response = match request.action.as_str() {
+        "prepare" => {
+            // 首次调用会生成设备签名钥,须随 storage 一并落盘。
+            save_provider(state_dir, &provider)?;
+            let message = chat_identity::binding_message(
+                &request.cid_number,
+                request.binding_revision,
+                &account_id_hex,
+                &request.device_id,
+                &device_public_key_hex,
+                request.expires_at_ms,
+                &request.nonce,
+            );
+            json!({
+                "device_public_key_hex": device_public_key_hex,
+                "account_id_hex": account_id_hex,
+                "signing_message_hex": hex::encode(message),
+            })
+        }
+        "install" => {
+            let signature_hex = request
+                .signature_hex
+                .ok_or_else(|| "install 必须提供 signature_hex".to_string())?;
+            let attestation = DeviceBindingAttestation {
+                cid_number: request.cid_number.clone(),
+                binding_revision: request.binding_revision,
+                account_id_hex,
+                device_id: request.device_id.clone(),
+                device_public_key_hex: device_public_key_hex.clone(),
+                expires_at_ms: request.expires_at_ms,
+                nonce: request.nonce,
+                signature_hex: signature_hex
+                    .strip_prefix("0x")
+                    .unwrap_or(&signature_hex)
+                    .to_ascii_lowercase(),
+            };
+            chat_identity::verify_attestation_signature(&attestation)
+                .map_err(|reason| format!("设备绑定签名无效: {}", reason.as_str()))?;
+            let mut record = read_device_record(
+                state_dir,
+                &request.cid_number,
+                &request.device_id,
+                &state_key,
+            )?
+            .ok_or_else(|| "MLS 设备记录缺失".to_string())?;
+            record.binding_attestation = Some(attestation);
+            write_device_record(state_dir, &record, &state_key)?;
+            json!({
+                "device_public_key_hex": device_public_key_hex,
+                "binding_revision": request.binding_revision,
+                "attested": true,
+            })
+        }
+        _ => return Err("device_binding action 仅支持 prepare / install".to_string()),
+    };
+    serde_json::to_string(&response).map_err(|error| error.to_string())
+}
+
+fn group_evict_stale_json(request_json: *const c_char) -> Result<String, String> {
+    let request: GroupEvictStaleRequest = parse_request(request_json)?;
+    require_non_empty("state_store_dir", &request.state_store_dir)?;
+    require_non_empty("cid_number", &request.cid_number)?;
+    require_non_empty("device_id", &request.device_id)?;
+    require_non_empty("group_id", &request.group_id)?;
+
+    let state_dir = Path::new(&request.state_store_dir);
+    let state_key = parse_state_key(&request.state_key_hex)?;
+    let provider = load_provider(state_dir, &state_key)?;
+    let (_credential, signer) = ensure_device_signer(
+        &provider,
+        state_dir,
+        &request.cid_number,
+        &request.device_id,
+        &state_key,
+    )?;
+    let group_id = group_id_from_conversation(&request.group_id)?;
+    let mut group = MlsGroup::load(provider.storage(), &group_id)
+        .map_err(|error| format!("加载 MLS 群失败: {error:?}"))?
+        .ok_or_else(|| "MLS 群不存在，无法驱逐".to_string())?;
+
+    let bindings = ChainBindings::new(request.chain_bindings)?;
+    let audit = audit_roster(&group, &bindings, None);
+    if !audit.missing_cid_numbers.is_empty() {
+        return Err(format!(
+            "缺少链上绑定快照: {}",
+            audit.missing_cid_numbers.join(",")
+        ));
+    }
+    // 本设备叶子失效只能由本人重新签发证明后换新 KeyPackage 重新入群,不能自删。
+    let own_index = group.own_leaf_index();
+    let self_stale = audit.stale.iter().any(|leaf| leaf.index == own_index);
+    let targets: Vec<StaleLeaf> = audit
+        .stale
+        .into_iter()
+        .filter(|leaf| leaf.index != own_index)
+        .collect();
+    if targets.is_empty() {
+        let response = json!({
+            "group_id": request.group_id,
+            "epoch": group.epoch().as_u64(),
+            "commit_wire_hex": serde_json::Value::Null,
+            "evicted_members": [],
+            "self_stale": self_stale,
+        });
+        return serde_json::to_string(&response).map_err(|error| error.to_string());
+    }
+
+    let indices: Vec<LeafNodeIndex> = targets.iter().map(|leaf| leaf.index).collect();
+    let (commit, _welcome, _group_info) = group
+        .remove_members(&provider, &signer, &indices)
+        .map_err(|error| format!("MLS 驱逐失效成员失败: {error:?}"))?;
+    group
+        .merge_pending_commit(&provider)
+        .map_err(|error| format!("合并 pending commit 失败: {error:?}"))?;
+
+    let commit_wire_hex = hex::encode(
+        commit
+            .tls_serialize_detached()
+            .map_err(|error| format!("序列化 Commit 失败: {error}"))?,
+    );
+    let epoch = group.epoch().as_u64();
+    save_provider(state_dir, &provider)?;
+
+    let response = json!({
+        "group_id": request.group_id,
+        "epoch": epoch,
+        "commit_wire_hex": commit_wire_hex,
+        "evicted_members": stale_members_json(&targets),
+        "self_stale": self_stale,
+    });
+    serde_json::to_string(&response).map_err(|error| error.to_string())
+}
+
+fn group_institution_sync_json(request_json: *const c_char) -> Result<String, String> {
+    let request: GroupInstitutionSyncRequest = parse_request(request_json)?;
+    require_non_empty("state_store_dir", &request.state_store_dir)?;
+    require_non_empty("cid_number", &request.cid_number)?;
+    require_non_empty("device_id", &request.device_id)?;
+    require_non_empty("group_id", &request.group_id)?;
+    let roster =
+        chat_institution::roster_for(&request.group_id, Some(&request.institution_roster))?
+            .ok_or_else(|| "仅机构群支持按链上名册同步".to_string())?;
+    require_institution_admin(roster, &request.cid_number)?;
+
+    let state_dir = Path::new(&request.state_store_dir);
+    let state_key = parse_state_key(&request.state_key_hex)?;
+    let provider = load_provider(state_dir, &state_key)?;
+    let (_credential, signer) = ensure_device_signer(
+        &provider,
+        state_dir,
+        &request.cid_number,
+        &request.device_id,
+        &state_key,
+    )?;
+    let group_id = group_id_from_conversation(&request.group_id)?;
+    let mut group = MlsGroup::load(provider.storage(), &group_id)
+        .map_err(|error| format!("加载 MLS 群失败: {error:?}"))?
+        .ok_or_else(|| "MLS 群不存在，无法同步机构名册".to_string())?;
+
+    let diff = roster.diff(group.members().map(|m| cid_of(&m.credential)));
+    // 本机已卸任时不能自删,由上层走退群请求交给其他管理员处理。
+    let self_extraneous = diff.extraneous.contains(&request.cid_number);
+    let removed_cid_numbers: Vec<String> = diff
+        .extraneous
+        .into_iter()
+        .filter(|cid_number| cid_number != &request.cid_number)
+        .collect();
+    let indices: Vec<LeafNodeIndex> = group
+        .members()
+        .filter(|m| removed_cid_numbers.contains(&cid_of(&m.credential)))
+        .map(|m| m.index)
+        .collect();
+
+    let commit_wire_hex = if indices.is_empty() {
+        serde_json::Value::Null
+    } else {
+        let (commit, _welcome, _group_info) = group
+            .remove_members(&provider, &signer, &indices)
+            .map_err(|error| format!("MLS 移除卸任成员失败: {error:?}"))?;
+        group
+            .merge_pending_commit(&provider)
+            .map_err(|error| format!("合并 pending commit 失败: {error:?}"))?;
+        save_provider(state_dir, &provider)?;
+        json!(hex::encode(
+            commit
+                .tls_serialize_detached()
+                .map_err(|error| format!("序列化 Commit 失败: {error}"))?,
+        ))
+    };
+
+    let response = json!({
+        "group_id": request.group_id,
+        "epoch": group.epoch().as_u64(),
+        "commit_wire_hex": commit_wire_hex,
+        "removed_cid_numbers": removed_cid_numbers,
+        "missing_cid_numbers": diff.missing,
+        "self_extraneous": self_extraneous,
+    });
+    serde_json::to_string(&response).map_err(|error| error.to_string())
+}
+
+fn group_remove_devices_json(request_json: *const c_char) -> Result<String, String> {
+    let request: GroupRemoveDevicesRequest = parse_request(request_json)?;
+    require_non_empty("state_store_dir", &request.state_store_dir)?;
+    require_non_empty("cid_number", &request.cid_number)?;
+    require_non_empty("device_id", &request.device_id)?;
+    require_non_empty("group_id", &request.group_id)?;
+    if request.member_identities.is_empty() {
+        return Err("group_remove_devices 至少需要一个设备标识".to_string());
+    }
+    let own_identity = format!("{}:{}", request.cid_number, request.device_id);
+    if request.member_identities.contains(&own_identity) {
+        return Err("不能移除本设备，请走退群".to_string());
+    }
+    if let Some(roster) =
+        chat_institution::roster_for(&request.group_id, request.institution_roster.as_ref())?
+    {
+        let own_devices_only = request.member_identities.iter().all(|identity| {
+            identity
+                .split_once(':')
+                .is_some_and(|(cid_number, _)| cid_number == request.cid_number)
+        });
+        if !roster.is_admin(&request.cid_number)
+            && !(own_devices_only && roster.is_member(&request.cid_number))
+        {
+            return Err("非机构管理员只能移除本人其他设备".to_string());
+        }
+    }
+
+    let state_dir = Path::new(&request.state_store_dir);
+    let state_key = parse_state_key(&request.state_key_hex)?;
+    let provider = load_provider(state_dir, &state_key)?;
+    let (_credential, signer) = ensure_device_signer(
+        &provider,
+        state_dir,
+        &request.cid_number,
+        &request.device_id,
+        &state_key,
+    )?;
+    let group_id = group_id_from_conversation(&request.group_id)?;
+    let mut group = MlsGroup::load(provider.storage(), &group_id)
+        .map_err(|error| format!("加载 MLS 群失败: {error:?}"))?
+        .ok_or_else(|| "MLS 群不存在，无法移除设备".to_string())?;
+
+    let targets: HashSet<&str> = request
+        .member_identities
+        .iter()
+        .map(|value| value.as_str())
+        .collect();
+    let mut indices = Vec::new();
+    let mut removed_member_identities = Vec::new();
+    for member in group.members() {
+        let identity = identity_of(&member.credential);
+        if targets.contains(identity.as_str()) {
+            indices.push(member.index);
+            removed_member_identities.push(identity);
+        }
+    }
+    if indices.is_empty() {
+        return Err("未在群名册中找到要移除的设备".to_string());
+    }
+
+    let (commit, _welcome, _group_info) = group
+        .remove_members(&provider, &signer, &indices)
+        .map_err(|error| format!("MLS 移除设备失败: {error:?}"))?;
+    group
+        .merge_pending_commit(&provider)
+        .map_err(|error| format!("合并 pending commit 失败: {error:?}"))?;
+
+    let commit_wire_hex = hex::encode(
+        commit
+            .tls_serialize_detached()
+            .map_err(|error| format!("序列化 Commit 失败: {error}"))?,
+    );
+    let epoch = group.epoch().as_u64();
+    save_provider(state_dir, &provider)?;
+
+    let response = json!({
+        "group_id": request.group_id,
+        "epoch": epoch,
+        "commit_wire_hex": commit_wire_hex,
+        "removed_member_identities": removed_member_identities,
+    });
+    serde_json::to_string(&response).map_err(|error| error.to_string())
+}
+
+fn history_request_json(request_json: *const c_char) -> Result<String, String> {
+    let request: HistoryRequestRequest = parse_request(request_json)?;
+    require_non_empty("state_store_dir", &request.state_store_dir)?;
+    require_non_empty("cid_number", &request.cid_number)?;
+    require_non_empty("device_id", &request.device_id)?;
+
+    let state_dir = Path::new(&request.state_store_dir);
+    let state_key = parse_state_key(&request.state_key_hex)?;
+    // 请求方凭证必须带绑定证明，否则老设备无从核对它属于本 CID。
+    read_device_record(
+        state_dir,
+        &request.cid_number,
+        &request.device_id,
+        &state_key,
+    )?
+    .and_then(|record| record.binding_attestation)
+    .ok_or_else(|| "本设备尚未完成 CID 绑定证明，无法请求历史迁移".to_string())?;
+    let provider = load_provider(state_dir, &state_key)?;
+    let (credential, signer) = ensure_device_signer(
+        &provider,
+        state_dir,
+        &request.cid_number,
+        &request.device_id,
+        &state_key,
+    )?;
+
+    let ikm = provider
+        .rand()
+        .random_vec(32)
+        .map_err(|error| format!("生成历史迁移密钥种子失败: {error:?}"))?;
+    let keypair = provider
+        .crypto()
+        .derive_hpke_keypair(GMB_MLS_CIPHERSUITE.hpke_config(), &ikm)
+        .map_err(|error| format!("生成历史迁移密钥失败: {error:?}"))?;
+    let now = now_millis();
+    let history_request = chat_devices::build_request(
+        &signer,
+        &request.cid_number,
+        &request.device_id,
+        credential.credential.serialized_content(),
+        &signer.to_public_vec(),
+        &keypair.public,
+        now,
+    )?;
+    // 新请求覆盖旧的一次性私钥，迟到的旧迁移包随之作废。
+    let pending = PendingHistoryKey {
+        hpke_public_key_hex: history_request.hpke_public_key_hex.clone(),
+        hpke_private_key_hex: hex::encode(&keypair.private[..]),
+        created_at_ms: now,
+    };
+    let clear = serde_json::to_vec(&pending).map_err(|error| error.to_string())?;
+    atomic_write(
+        &history_key_path(state_dir),
+        &seal_state(&state_key, &clear, STATE_AAD_HISTORY)?,
+    )?;
+
+    serde_json::to_string(&json!({ "request": history_request })).map_err(|error| error.to_string())
+}
+
+fn history_seal_json(request_json: *const c_char) -> Result<String, String> {
+    let request: HistorySealRequest = parse_request(request_json)?;
+    require_non_empty("state_store_dir", &request.state_store_dir)?;
+    require_non_empty("cid_number", &request.cid_number)?;
+    require_non_empty("device_id", &request.device_id)?;
+    require_non_empty("history_hex", &request.history_hex)?;
+
+    let state_dir = Path::new(&request.state_store_dir);
+    let state_key = parse_state_key(&request.state_key_hex)?;
+    let provider = load_provider(state_dir, &state_key)?;
+    let (credential, signer) = ensure_device_signer(
+        &provider,
+        state_dir,
+        &request.cid_number,
+        &request.device_id,
+        &state_key,
+    )?;
+    let bindings = ChainBindings::new(request.chain_bindings)?;
+    let history = decode_hex_field("history_hex", &request.history_hex)?;
+    let bundle = chat_devices::seal_history(
+        provider.crypto(),
+        GMB_MLS_CIPHERSUITE.hpke_config(),
+        &signer,
+        &bindings,
+        &request.cid_number,
+        &request.device_id,
+        credential.credential.serialized_content(),
+        &signer.to_public_vec(),
+        &request.request,
+        &history,
+        now_millis(),
+    )?;
+    serde_json::to_string(&json!({ "bundle": bundle })).map_err(|error| error.to_string())
+}
+
+fn history_open_json(request_json: *const c_char) -> Result<String, String> {
+    let request: HistoryOpenRequest = parse_request(request_json)?;
+    require_non_empty("state_store_dir", &request.state_store_dir)?;
+    require_non_empty("cid_number", &request.cid_number)?;
+    require_non_empty("device_id", &request.device_id)?;
+
+    let state_dir = Path::new(&request.state_store_dir);
+    let state_key = parse_state_key(&request.state_key_hex)?;
+    read_device_record(
+        state_dir,
+        &request.cid_number,
+        &request.device_id,
+        &state_key,
+    )?
+    .ok_or_else(|| "MLS 设备记录缺失".to_string())?;
+    let path = history_key_path(state_dir);
+    if !path.exists() {
+        return Err("没有进行中的历史迁移请求".to_string());
+    }
+    let blob = fs::read(&path).map_err(|error| format!("读取历史迁移密钥失败: {error}"))?;
+    let clear = open_state(&state_key, &blob, STATE_AAD_HISTORY)?;
+    let pending: PendingHistoryKey =
+        serde_json::from_slice(&clear).map_err(|error| format!("解析历史迁移密钥失败: {error}"))?;
+    let mut private_key = decode_hex_field("hpke_private_key_hex", &pending.hpke_private_key_hex)?;
+
+    let bindings = ChainBindings::new(request.chain_bindings)?;
+    let opened = chat_devices::open_history(
+        &RustCrypto::default(),
+        GMB_MLS_CIPHERSUITE.hpke_config(),
+        &bindings,
+        &request.cid_number,
+        &request.device_id,
+        &pending.hpke_public_key_hex,
+        &private_key,
+        &request.bundle,
+    );
+    private_key.fill(0);
+    let history = opened?;
+    // 一次性私钥:解封成功即删除,同一请求不接受第二个迁移包。
+    fs::remove_file(&path).map_err(|error| format!("删除历史迁移密钥失败: {error}"))?;
+
+    let response = json!({
+        "from_device_id": request.bundle.from_device_id,
+        "history_hex": hex::encode(history),
+    });
+    serde_json::to_string(&response).map_err(|error| error.to_string())
+}
+
+fn attachment_seal_json(request_json: *const c_char) -> Result<String, String> {
+    let request: AttachmentSealRequest = parse_request(request_json)?;
+    require_non_empty("state_store_dir", &request.state_store_dir)?;
+    require_non_empty("cid_number", &request.cid_number)?;
+    require_non_empty("device_id", &request.device_id)?;
+    require_non_empty("group_id", &request.group_id)?;
+    require_non_empty("source_path", &request.source_path)?;
+    require_non_empty("output_path", &request.output_path)?;
+    chat_attachment::validate_attachment_id(&request.attachment_id)?;
+    let chunk_size = chat_attachment::resolve_chunk_size(request.chunk_size)?;
+
+    let state_dir = Path::new(&request.state_store_dir);
+    let state_key = parse_state_key(&request.state_key_hex)?;
+    let provider = load_provider(state_dir, &state_key)?;
+    read_device_record(
+        state_dir,
+        &request.cid_number,
+        &request.device_id,
+        &state_key,
+    )?
+    .ok_or_else(|| "MLS 设备记录缺失".to_string())?;
+    let group_id = group_id_from_conversation(&request.group_id)?;
+    let group = MlsGroup::load(provider.storage(), &group_id)
+        .map_err(|error| format!("加载 MLS 群失败: {error:?}"))?
+        .ok_or_else(|| "MLS 群不存在，无法加密附件".to_string())?;
+    if !group.is_active() {
+        return Err("本设备已不在该群，无法发送附件".to_string());
+    }
+
+    // exporter 只读当前 epoch 秘密，不推进 ratchet，无需回写状态。
+    let mut secret = group
+        .export_secret(
+            provider.crypto(),
+            chat_attachment::ATTACHMENT_EXPORTER_LABEL,
+            request.attachment_id.as_bytes(),
+            32,
+        )
+        .map_err(|error| format!("派生附件密钥失败: {error:?}"))?;
+    let mut key =
+        <[u8; 32]>::try_from(secret.as_slice()).map_err(|_| "附件密钥长度异常".to_string())?;
+    secret.fill(0);
+    let sealed = chat_attachment::encrypt_file(
+        &key,
+        &request.attachment_id,
+        chunk_size,
+        Path::new(&request.source_path),
+        Path::new(&request.output_path),
+    );
+    let key_hex = hex::encode(key);
+    key.fill(0);
+    let sealed = sealed?;
+
+    let response = json!({
+        "group_id": request.group_id,
+        "epoch": group.epoch().as_u64(),
+        "attachment_id": request.attachment_id,
+        "key_hex": key_hex,
+        "chunk_size": chunk_size,
+        "chunk_count": sealed.chunk_count,
+        "byte_size": sealed.byte_size,
+        "encrypted_size": sealed.encrypted_size,
+        "content_hash_hex": hex::encode(sealed.content_hash),
+        "ciphertext_hash_hex": hex::encode(sealed.ciphertext_hash),
+    });
+    serde_json::to_string(&response).map_err(|error| error.to_string())
+}
+
+fn attachment_open_json(request_json: *const c_char) -> Result<String, String> {
+    let request: AttachmentOpenRequest = parse_request(request_json)?;
+    require_non_empty("source_path", &request.source_path)?;
+    require_non_empty("output_path", &request.output_path)?;
+    chat_attachment::validate_attachment_id(&request.attachment_id)?;
+    let chunk_size = chat_attachment::resolve_chunk_size(Some(request.chunk_size))?;
+    let mut key = chat_attachment::parse_32("key_hex", &request.key_hex)?;
+    let content_hash = chat_attachment::parse_32("content_hash_hex", &request.content_hash_hex)?;
+    let source_path = Path::new(&request.source_path);
+    if let Some(value) = request.ciphertext_hash_hex.as_deref() {
+        let ciphertext_hash = chat_attachment::parse_32("ciphertext_hash_hex", value)?;
+        chat_attachment::verify_ciphertext_file(
+            source_path,
+            request.encrypted_size,
+            &ciphertext_hash,
+        )?;
+    }
+
+    let opened = chat_attachment::decrypt_file(
+        &key,
+        &request.attachment_id,
+        chunk_size,
+        &content_hash,
+        source_path,
+        Path::new(&request.output_path),
+    );
+    key.fill(0);
+    let opened = opened?;
+
+    let response = json!({
+        "attachment_id": request.attachment_id,
+        "byte_size": opened.byte_size,
+        "content_hash_hex": hex::encode(opened.content_hash),
+    });
+    serde_json::to_string(&response).map_err(|error| error.to_string())
+}
+
+fn attachment_verify_json(request_json: *const c_char) -> Result<String, String> {
+    let request: AttachmentVerifyRequest = parse_request(request_json)?;
+    require_non_empty("source_path", &request.source_path)?;
+    let ciphertext_hash =
+        chat_attachment::parse_32("ciphertext_hash_hex", &request.ciphertext_hash_hex)?;
+    chat_attachment::verify_ciphertext_file(
+        Path::new(&request.source_path),
+        request.encrypted_size,
+        &ciphertext_hash,
+    )?;
+    serde_json::to_string(&json!({ "verified": true })).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        atomic_write, attachment_open_json, attachment_seal_json, attachment_verify_json,
+        create_key_package_json, device_binding_json, group_add_members_json, group_create_json,
+        group_create_message_json, group_evict_stale_json, group_institution_sync_json,
+        group_process_json, group_remove_devices_json, group_remove_members_json, group_state_json,
+        history_open_json, history_request_json, history_seal_json, open_state, parse_state_key,
+        purge_legacy_plaintext_state, seal_state, two_party_smoke_json, STATE_AAD_DEVICE,
+        STATE_AAD_STORAGE,
+    };
+    use crate::chat_identity::tests::test_account;
+    use std::ffi::CString;
+    use std::fs;
+    use std::os::raw::c_char;
+    use std::path::Path;
+
+    /// MLS 状态信封测试密钥(64 hex = 32 字节)。
+    const TEST_STATE_KEY_HEX: &str =
+        "0101010101010101010101010101010101010101010101010101010101010101";
+
+    fn invoke(
+        f: fn(*const c_char) -> Result<String, String>,
+        req: serde_json::Value,
+    ) -> serde_json::Value {
+        let c = CString::new(serde_json::to_string(&req).unwrap()).unwrap();
+        let out = f(c.as_ptr()).expect("FFI 调用应成功");
+        serde_json::from_str::<serde_json::Value>(&out).expect("响应应为 JSON")
+    }
+
+    /// 让 `seed` 派生的测试账户为该设备签发 revision=1 的凭证授权证明,返回账户 hex。
+    fn attest_device(dir: &Path, cid_number: &str, device_id: &str, seed: u8) -> String {
+        use serde_json::json;
+        let (account, child) = test_account(seed);
+        let base = json!({
+            "state_key_hex": TEST_STATE_KEY_HEX,
+            "state_store_dir": dir.to_str().unwrap(),
+            "cid_number": cid_number,
+            "device_id": device_id,
+            "account_id_hex": account,
+            "binding_revision": 1,
+            "expires_at_ms": u64::MAX,
+            "nonce": format!("nonce-{device_id}"),
+        });
+        let mut prepare = base.clone();
+        prepare["action"] = json!("prepare");
+        let prepared = invoke(device_binding_json, prepare);
+        let message = hex::decode(prepared["signing_message_hex"].as_str().unwrap()).unwrap();
+        let mut signature = [0u8; 64];
+        let status = unsafe {
+            citizen_signer::citizen_sr25519_sign(
+                child.as_ptr(),
+                message.as_ptr(),
+                message.len(),
+                signature.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, citizen_signer::CITIZEN_SIGNER_OK);
+        let mut install = base;
+        install["action"] = json!("install");
+        install["signature_hex"] = json!(hex::encode(signature));
+        assert_eq!(invoke(device_binding_json, install)["attested"], true);
+        account
+    }
+
+    #[test]
+    fn creates_real_openmls_key_package() {
+        let request = CString::new(r#"{"cid_number":"CID-ALICE","device_id":"alice-phone"}"#)
+            .expect("request should be valid");
+        let response =
+            create_key_package_json(request.as_ptr()).expect("key package should be created");
+        let json: serde_json::Value =
+            serde_json::from_str(&response).expect("response should be json");
+        assert_eq!(json["cid_number"], "CID-ALICE");
+        assert!(json["key_package_hex"].as_str().unwrap().len() > 100);
+        // 键名即跨语言契约:Dart 侧 `mls_native.dart` 按这些名字读,漏 `_hex`
+        // 后缀会让设备公钥恒空并卡死 Chat 首启(2026-08-04)。改名必须两侧同改。
+        let device_public_key_hex = json["device_public_key_hex"]
+            .as_str()
+            .expect("响应必须含 device_public_key_hex");
+        assert!(!device_public_key_hex.is_empty());
+        assert!(device_public_key_hex
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c)));
+    }
+
+    #[test]
+    fn openmls_two_party_smoke_round_trips_plaintext() {
+        let request =
+            CString::new(r#"{"plaintext":"hello openmls"}"#).expect("request should be valid");
+        let response = two_party_smoke_json(request.as_ptr()).expect("smoke should pass");
+        let json: serde_json::Value =
+            serde_json::from_str(&response).expect("response should be json");
+        assert_eq!(json["plaintext"], "hello openmls");
+        assert_eq!(json["decrypted_plaintext"], "hello openmls");
+        assert!(json["alice_wire_message_hex"].as_str().unwrap().len() > 100);
+    }
+
+    #[test]
+    fn group_three_party_round_trip() {
+        use serde_json::json;
+
+        let base = std::env::temp_dir().join(format!("citizen_group_rt_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let dir_a = base.join("a");
+        let dir_b = base.join("b");
+        let dir_c = base.join("c");
+        for d in [&dir_a, &dir_b, &dir_c] {
+            fs::create_dir_all(d).expect("临时目录应可创建");
+        }
+        let group_id = "grp:CID-A:testnonce";
+        let path = |p: &Path| p.to_str().unwrap().to_string();
+        let chain = json!([
+            {"cid_number": "CID-A", "account_id_hex": attest_device(&dir_a, "CID-A", "devA", 1), "binding_revision": 1},
+            {"cid_number": "CID-B", "account_id_hex": attest_device(&dir_b, "CID-B", "devB", 2), "binding_revision": 1},
+            {"cid_number": "CID-C", "account_id_hex": attest_device(&dir_c, "CID-C", "devC", 3), "binding_revision": 1},
+        ]);
+
+        // A 建群(创建者=唯一成员,epoch 0)。
+        let created = invoke(
+            group_create_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id}),
+        );
+        assert_eq!(created["epoch"].as_u64(), Some(0));
+
+        // B / C 生成 KeyPackage。
+        let b_kp = invoke(
+            create_key_package_json,
+            json!({"cid_number": "CID-B", "device_id": "devB", "state_store_dir": path(&dir_b), "state_key_hex": TEST_STATE_KEY_HEX}),
+        )["key_package_hex"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let c_kp = invoke(
+            create_key_package_json,
+            json!({"cid_number": "CID-C", "device_id": "devC", "state_store_dir": path(&dir_c), "state_key_hex": TEST_STATE_KEY_HEX}),
+        )["key_package_hex"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        // A 批量加 B、C(1 Commit + 1 Welcome)。
         let added = invoke(
             group_add_members_json,
-            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "key_packages_hex": [b_kp, c_kp]}),
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "key_packages_hex": [b_kp, c_kp], "chain_bindings": chain}),
         );
         let welcome_hex = added["welcome_wire_hex"].as_str().unwrap().to_string();
         let tree_hex = added["ratchet_tree_hex"].as_str().unwrap().to_string();
@@ -1725,7 +3065,7 @@ mod tests {
         for (dir, owner, dev) in [(&dir_b, "CID-B", "devB"), (&dir_c, "CID-C", "devC")] {
             let joined = invoke(
                 group_process_json,
-                json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(dir.as_path()), "cid_number": owner, "device_id": dev, "group_id": group_id, "wire_message_hex": welcome_hex, "ratchet_tree_hex": tree_hex}),
+                json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(dir.as_path()), "cid_number": owner, "device_id": dev, "group_id": group_id, "wire_message_hex": welcome_hex, "ratchet_tree_hex": tree_hex, "chain_bindings": chain}),
             );
             assert_eq!(joined["message_kind"].as_str(), Some("welcome"));
             assert_eq!(joined["member_identities"].as_array().unwrap().len(), 3);
@@ -1741,7 +3081,7 @@ mod tests {
         for (dir, owner, dev) in [(&dir_b, "CID-B", "devB"), (&dir_c, "CID-C", "devC")] {
             let got = invoke(
                 group_process_json,
-                json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(dir.as_path()), "cid_number": owner, "device_id": dev, "group_id": group_id, "wire_message_hex": app_hex}),
+                json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(dir.as_path()), "cid_number": owner, "device_id": dev, "group_id": group_id, "wire_message_hex": app_hex, "chain_bindings": chain}),
             );
             assert_eq!(got["message_kind"].as_str(), Some("application"));
             assert_eq!(got["status"].as_str(), Some("applied"));
@@ -1759,7 +3099,7 @@ mod tests {
         // B 应用 Commit → 名册剩 A、B,未自我移除。
         let b_after = invoke(
             group_process_json,
-            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": remove_commit_hex}),
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": remove_commit_hex, "chain_bindings": chain}),
         );
         assert_eq!(b_after["message_kind"].as_str(), Some("commit"));
         assert_eq!(b_after["self_removed"].as_bool(), Some(false));
@@ -1768,7 +3108,7 @@ mod tests {
         // C 应用 Commit → 自身被移除(后向保密)。
         let c_after = invoke(
             group_process_json,
-            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_c), "cid_number": "CID-C", "device_id": "devC", "group_id": group_id, "wire_message_hex": remove_commit_hex}),
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_c), "cid_number": "CID-C", "device_id": "devC", "group_id": group_id, "wire_message_hex": remove_commit_hex, "chain_bindings": chain}),
         );
         assert_eq!(c_after["self_removed"].as_bool(), Some(true));
 
@@ -1782,6 +3122,427 @@ mod tests {
         let _ = fs::remove_dir_all(&base);
     }
 
+    #[test]
+    fn group_attachment_key_travels_inside_mls_message() {
+        use serde_json::json;
+
+        let base = std::env::temp_dir().join(format!("citizen_group_att_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let dir_a = base.join("a");
+        let dir_b = base.join("b");
+        for d in [&dir_a, &dir_b] {
+            fs::create_dir_all(d).expect("临时目录应可创建");
+        }
+        let group_id = "grp:CID-A:attnonce";
+        let path = |p: &Path| p.to_str().unwrap().to_string();
+        let chain = json!([
+            {"cid_number": "CID-A", "account_id_hex": attest_device(&dir_a, "CID-A", "devA", 1), "binding_revision": 1},
+            {"cid_number": "CID-B", "account_id_hex": attest_device(&dir_b, "CID-B", "devB", 2), "binding_revision": 1},
+        ]);
+        invoke(
+            group_create_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id}),
+        );
+        let b_kp = invoke(
+            create_key_package_json,
+            json!({"cid_number": "CID-B", "device_id": "devB", "state_store_dir": path(&dir_b), "state_key_hex": TEST_STATE_KEY_HEX}),
+        )["key_package_hex"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let added = invoke(
+            group_add_members_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "key_packages_hex": [b_kp], "chain_bindings": chain}),
+        );
+        invoke(
+            group_process_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": added["welcome_wire_hex"], "ratchet_tree_hex": added["ratchet_tree_hex"], "chain_bindings": chain}),
+        );
+
+        // A 加密 3 块多的文件；密钥与哈希随 MLS 消息端到端下发。
+        let original: Vec<u8> = (0..(3 * 4096 + 100)).map(|i| (i % 253) as u8).collect();
+        let source = base.join("photo.jpg");
+        let sealed_path = base.join("photo.enc");
+        fs::write(&source, &original).unwrap();
+        let sealed = invoke(
+            attachment_seal_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "attachment_id": "att-01", "source_path": path(&source), "output_path": path(&sealed_path), "chunk_size": 4096}),
+        );
+        assert_eq!(sealed["chunk_count"].as_u64(), Some(4));
+        assert_eq!(sealed["byte_size"].as_u64(), Some(original.len() as u64));
+        let on_disk = fs::read(&sealed_path).unwrap();
+        assert_eq!(
+            on_disk.len() as u64,
+            sealed["encrypted_size"].as_u64().unwrap()
+        );
+        assert!(!on_disk.windows(64).any(|w| w == &original[..64]));
+
+        let descriptor = serde_json::to_vec(&sealed).unwrap();
+        let msg = invoke(
+            group_create_message_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "plaintext_hex": hex::encode(&descriptor)}),
+        );
+        let got = invoke(
+            group_process_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": msg["application_wire_hex"], "chain_bindings": chain}),
+        );
+        let received: serde_json::Value =
+            serde_json::from_slice(&hex::decode(got["plaintext_hex"].as_str().unwrap()).unwrap())
+                .unwrap();
+
+        let verified = invoke(
+            attachment_verify_json,
+            json!({"source_path": path(&sealed_path), "ciphertext_hash_hex": received["ciphertext_hash_hex"], "encrypted_size": received["encrypted_size"]}),
+        );
+        assert_eq!(verified["verified"], true);
+        let opened_path = base.join("photo.out");
+        let open_request = json!({
+            "key_hex": received["key_hex"],
+            "attachment_id": received["attachment_id"],
+            "chunk_size": received["chunk_size"],
+            "content_hash_hex": received["content_hash_hex"],
+            "ciphertext_hash_hex": received["ciphertext_hash_hex"],
+            "encrypted_size": received["encrypted_size"],
+            "source_path": path(&sealed_path),
+            "output_path": path(&opened_path),
+        });
+        let opened = invoke(attachment_open_json, open_request.clone());
+        assert_eq!(opened["content_hash_hex"], received["content_hash_hex"]);
+        assert_eq!(fs::read(&opened_path).unwrap(), original);
+
+        // 篡改密文:哈希核对先拦下；跳过密文哈希则逐块认证失败，且不留输出。
+        let tampered_path = base.join("photo.bad");
+        let mut tampered = on_disk.clone();
+        tampered[5000] ^= 0x80;
+        fs::write(&tampered_path, &tampered).unwrap();
+        let bad_output = base.join("photo.bad.out");
+        let mut bad_request = open_request;
+        bad_request["source_path"] = json!(path(&tampered_path));
+        bad_request["output_path"] = json!(path(&bad_output));
+        let call = |value: &serde_json::Value| {
+            let c = CString::new(value.to_string()).unwrap();
+            attachment_open_json(c.as_ptr())
+        };
+        assert!(call(&bad_request).unwrap_err().contains("哈希"));
+        bad_request["ciphertext_hash_hex"] = serde_json::Value::Null;
+        assert!(call(&bad_request).unwrap_err().contains("解密失败"));
+        assert!(!bad_output.exists());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn second_device_joins_receives_history_and_is_removed() {
+        use serde_json::json;
+
+        let base =
+            std::env::temp_dir().join(format!("citizen_group_devices_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let dir_phone = base.join("phone");
+        let dir_laptop = base.join("laptop");
+        let dir_b = base.join("b");
+        for d in [&dir_phone, &dir_laptop, &dir_b] {
+            fs::create_dir_all(d).expect("临时目录应可创建");
+        }
+        let group_id = "grp:CID-A:devices";
+        let path = |p: &Path| p.to_str().unwrap().to_string();
+        // 同一 CID 的两台设备由同一链上账户授权。
+        let account_a = attest_device(&dir_phone, "CID-A", "phone", 1);
+        attest_device(&dir_laptop, "CID-A", "laptop", 1);
+        let chain = json!([
+            {"cid_number": "CID-A", "account_id_hex": account_a, "binding_revision": 1},
+            {"cid_number": "CID-B", "account_id_hex": attest_device(&dir_b, "CID-B", "devB", 2), "binding_revision": 1},
+        ]);
+
+        invoke(
+            group_create_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_phone), "cid_number": "CID-A", "device_id": "phone", "group_id": group_id}),
+        );
+        let key_package = |dir: &Path, cid: &str, dev: &str| {
+            invoke(
+                create_key_package_json,
+                json!({"cid_number": cid, "device_id": dev, "state_store_dir": path(dir), "state_key_hex": TEST_STATE_KEY_HEX}),
+            )["key_package_hex"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        let added = invoke(
+            group_add_members_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_phone), "cid_number": "CID-A", "device_id": "phone", "group_id": group_id, "key_packages_hex": [key_package(&dir_laptop, "CID-A", "laptop"), key_package(&dir_b, "CID-B", "devB")], "chain_bindings": chain}),
+        );
+        for (dir, owner, dev) in [(&dir_laptop, "CID-A", "laptop"), (&dir_b, "CID-B", "devB")] {
+            let joined = invoke(
+                group_process_json,
+                json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(dir.as_path()), "cid_number": owner, "device_id": dev, "group_id": group_id, "wire_message_hex": added["welcome_wire_hex"], "ratchet_tree_hex": added["ratchet_tree_hex"], "chain_bindings": chain}),
+            );
+            assert_eq!(joined["member_identities"].as_array().unwrap().len(), 3);
+        }
+
+        // 新设备请求历史,老设备核对同 CID 后封装,新设备用一次性私钥解开。
+        let laptop = json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_laptop), "cid_number": "CID-A", "device_id": "laptop", "chain_bindings": chain});
+        let requested = invoke(history_request_json, laptop.clone());
+        let history_hex = hex::encode("历史消息快照".as_bytes());
+        let sealed = invoke(
+            history_seal_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_phone), "cid_number": "CID-A", "device_id": "phone", "request": requested["request"], "chain_bindings": chain, "history_hex": history_hex}),
+        );
+        // 其他 CID 的设备不能冒充本人老设备索取历史。
+        let stranger = json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "request": requested["request"], "chain_bindings": chain, "history_hex": history_hex});
+        let stranger = CString::new(stranger.to_string()).unwrap();
+        assert!(history_seal_json(stranger.as_ptr()).is_err());
+
+        let mut open = laptop.clone();
+        open["bundle"] = sealed["bundle"].clone();
+        let opened = invoke(history_open_json, open.clone());
+        assert_eq!(opened["history_hex"].as_str(), Some(history_hex.as_str()));
+        assert_eq!(opened["from_device_id"].as_str(), Some("phone"));
+        // 一次性私钥已删除,重放同一迁移包失败。
+        let replay = CString::new(open.to_string()).unwrap();
+        assert!(history_open_json(replay.as_ptr()).is_err());
+
+        // 吊销笔记本:只移除该设备叶子,本 CID 的手机与 B 留在群内。
+        let removed = invoke(
+            group_remove_devices_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_phone), "cid_number": "CID-A", "device_id": "phone", "group_id": group_id, "member_identities": ["CID-A:laptop"]}),
+        );
+        assert_eq!(
+            removed["removed_member_identities"],
+            json!(["CID-A:laptop"])
+        );
+        let b_after = invoke(
+            group_process_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": removed["commit_wire_hex"], "chain_bindings": chain}),
+        );
+        assert_eq!(b_after["member_identities"].as_array().unwrap().len(), 2);
+        let laptop_after = invoke(
+            group_process_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_laptop), "cid_number": "CID-A", "device_id": "laptop", "group_id": group_id, "wire_message_hex": removed["commit_wire_hex"], "chain_bindings": chain}),
+        );
+        assert_eq!(laptop_after["self_removed"].as_bool(), Some(true));
+        let own = CString::new(
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_phone), "cid_number": "CID-A", "device_id": "phone", "group_id": group_id, "member_identities": ["CID-A:phone"]}).to_string(),
+        )
+        .unwrap();
+        assert!(group_remove_devices_json(own.as_ptr()).is_err());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn rebound_cid_is_refused_on_add_and_evicted_from_roster() {
+        use serde_json::json;
+
+        let base = std::env::temp_dir().join(format!("citizen_group_bind_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let dir_a = base.join("a");
+        let dir_b = base.join("b");
+        let dir_c = base.join("c");
+        for d in [&dir_a, &dir_b, &dir_c] {
+            fs::create_dir_all(d).expect("临时目录应可创建");
+        }
+        let group_id = "grp:CID-A:bindnonce";
+        let path = |p: &Path| p.to_str().unwrap().to_string();
+        let account_a = attest_device(&dir_a, "CID-A", "devA", 1);
+        let account_b = attest_device(&dir_b, "CID-B", "devB", 2);
+        let account_c = attest_device(&dir_c, "CID-C", "devC", 3);
+        let chain = |c_revision: u64| {
+            json!([
+                {"cid_number": "CID-A", "account_id_hex": account_a, "binding_revision": 1},
+                {"cid_number": "CID-B", "account_id_hex": account_b, "binding_revision": 1},
+                {"cid_number": "CID-C", "account_id_hex": account_c, "binding_revision": c_revision},
+            ])
+        };
+
+        invoke(
+            group_create_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id}),
+        );
+        let key_package = |dir: &Path, cid: &str, dev: &str| {
+            invoke(
+                create_key_package_json,
+                json!({"cid_number": cid, "device_id": dev, "state_store_dir": path(dir), "state_key_hex": TEST_STATE_KEY_HEX}),
+            )["key_package_hex"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        let b_kp = key_package(&dir_b, "CID-B", "devB");
+        let c_kp = key_package(&dir_c, "CID-C", "devC");
+
+        // C 已换绑(链上 revision 前进),其旧证明不得入群。
+        let refused = CString::new(
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "key_packages_hex": [&c_kp], "chain_bindings": chain(2)})
+                .to_string(),
+        )
+        .unwrap();
+        let error = group_add_members_json(refused.as_ptr()).expect_err("换绑后的旧证明必须被拒");
+        assert!(error.contains("rebound"), "{error}");
+
+        // 快照一致时 B、C 正常入群;B 入群时缺 C 快照须先要求补读。
+        let added = invoke(
+            group_add_members_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "key_packages_hex": [b_kp, c_kp], "chain_bindings": chain(1)}),
+        );
+        let welcome_hex = added["welcome_wire_hex"].as_str().unwrap().to_string();
+        let tree_hex = added["ratchet_tree_hex"].as_str().unwrap().to_string();
+        let partial = invoke(
+            group_process_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": welcome_hex, "ratchet_tree_hex": tree_hex, "chain_bindings": [
+                {"cid_number": "CID-A", "account_id_hex": account_a, "binding_revision": 1},
+                {"cid_number": "CID-B", "account_id_hex": account_b, "binding_revision": 1},
+            ]}),
+        );
+        assert_eq!(partial["status"].as_str(), Some("needs_bindings"));
+        assert_eq!(partial["missing_cid_numbers"], json!(["CID-C"]));
+        let joined = invoke(
+            group_process_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": welcome_hex, "ratchet_tree_hex": tree_hex, "chain_bindings": chain(1)}),
+        );
+        assert_eq!(joined["status"].as_str(), Some("applied"));
+        assert_eq!(joined["stale_members"], json!([]));
+
+        // C 换绑后 A 审计名册,驱逐 C 的旧叶子;B 应用驱逐 Commit 后名册只剩 A、B。
+        let evicted = invoke(
+            group_evict_stale_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "chain_bindings": chain(2)}),
+        );
+        assert_eq!(
+            evicted["evicted_members"],
+            json!([{"identity": "CID-C:devC", "reason": "rebound"}])
+        );
+        assert_eq!(evicted["self_stale"].as_bool(), Some(false));
+        let b_after = invoke(
+            group_process_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": evicted["commit_wire_hex"], "chain_bindings": chain(2)}),
+        );
+        assert_eq!(b_after["status"].as_str(), Some("applied"));
+        assert_eq!(b_after["member_identities"].as_array().unwrap().len(), 2);
+
+        // 名册已全部合格,再审计不产生 Commit。
+        let clean = invoke(
+            group_evict_stale_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "chain_bindings": chain(2)}),
+        );
+        assert!(clean["commit_wire_hex"].is_null());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn institution_group_follows_role_roster() {
+        use serde_json::json;
+
+        let base = std::env::temp_dir().join(format!("citizen_group_inst_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let dir_a = base.join("a");
+        let dir_b = base.join("b");
+        let dir_c = base.join("c");
+        for d in [&dir_a, &dir_b, &dir_c] {
+            fs::create_dir_all(d).expect("临时目录应可创建");
+        }
+        let group_id = "inst:NRC001:COMMITTEE";
+        let path = |p: &Path| p.to_str().unwrap().to_string();
+        let account_a = attest_device(&dir_a, "CID-A", "devA", 1);
+        let account_b = attest_device(&dir_b, "CID-B", "devB", 2);
+        let account_c = attest_device(&dir_c, "CID-C", "devC", 3);
+        let chain = json!([
+            {"cid_number": "CID-A", "account_id_hex": account_a, "binding_revision": 1},
+            {"cid_number": "CID-B", "account_id_hex": account_b, "binding_revision": 1},
+            {"cid_number": "CID-C", "account_id_hex": account_c, "binding_revision": 1},
+        ]);
+        let roster = |members: &[&str]| {
+            json!({
+                "institution_cid_number": "NRC001",
+                "scope": "COMMITTEE",
+                "member_cid_numbers": members,
+                "admin_cid_numbers": ["CID-A", "CID-B", "CID-C"],
+            })
+        };
+
+        // 机构群建群必须带名册快照。
+        let bare = CString::new(
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id})
+                .to_string(),
+        )
+        .unwrap();
+        assert!(group_create_json(bare.as_ptr()).is_err());
+        invoke(
+            group_create_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "institution_roster": roster(&["CID-A", "CID-B"])}),
+        );
+        let key_package = |dir: &Path, cid: &str, dev: &str| {
+            invoke(
+                create_key_package_json,
+                json!({"cid_number": cid, "device_id": dev, "state_store_dir": path(dir), "state_key_hex": TEST_STATE_KEY_HEX}),
+            )["key_package_hex"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        let b_kp = key_package(&dir_b, "CID-B", "devB");
+        let c_kp = key_package(&dir_c, "CID-C", "devC");
+
+        // 未任该岗位的 C 不得入群。
+        let outsider = CString::new(
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "key_packages_hex": [&c_kp], "chain_bindings": chain, "institution_roster": roster(&["CID-A", "CID-B"])})
+                .to_string(),
+        )
+        .unwrap();
+        let error = group_add_members_json(outsider.as_ptr()).expect_err("名册外 CID 必须被拒");
+        assert!(error.contains("不在机构群名册内"), "{error}");
+
+        let added = invoke(
+            group_add_members_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "key_packages_hex": [b_kp], "chain_bindings": chain, "institution_roster": roster(&["CID-A", "CID-B"])}),
+        );
+        let welcome_hex = added["welcome_wire_hex"].as_str().unwrap().to_string();
+        let tree_hex = added["ratchet_tree_hex"].as_str().unwrap().to_string();
+
+        // B 处理 Welcome:缺名册先要求补读,补齐后入群且差集为空。
+        let pending = invoke(
+            group_process_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": welcome_hex, "ratchet_tree_hex": tree_hex, "chain_bindings": chain}),
+        );
+        assert_eq!(pending["status"].as_str(), Some("needs_institution_roster"));
+        let joined = invoke(
+            group_process_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": welcome_hex, "ratchet_tree_hex": tree_hex, "chain_bindings": chain, "institution_roster": roster(&["CID-A", "CID-B"])}),
+        );
+        assert_eq!(joined["status"].as_str(), Some("applied"));
+        assert_eq!(joined["institution_missing"], json!([]));
+        assert_eq!(joined["institution_extraneous"], json!([]));
+
+        // 链上任职变更:B 卸任、C 就任。A 同步移除 B,并得知须加入 C。
+        let synced = invoke(
+            group_institution_sync_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "institution_roster": roster(&["CID-A", "CID-C"])}),
+        );
+        assert_eq!(synced["removed_cid_numbers"], json!(["CID-B"]));
+        assert_eq!(synced["missing_cid_numbers"], json!(["CID-C"]));
+        assert_eq!(synced["self_extraneous"].as_bool(), Some(false));
+        let b_after = invoke(
+            group_process_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_b), "cid_number": "CID-B", "device_id": "devB", "group_id": group_id, "wire_message_hex": synced["commit_wire_hex"], "chain_bindings": chain, "institution_roster": roster(&["CID-A", "CID-C"])}),
+        );
+        assert_eq!(b_after["status"].as_str(), Some("applied"));
+        assert_eq!(b_after["self_removed"].as_bool(), Some(true));
+
+        // 名册已一致,再同步不产生 Commit。
+        invoke(
+            group_add_members_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "key_packages_hex": [c_kp], "chain_bindings": chain, "institution_roster": roster(&["CID-A", "CID-C"])}),
+        );
+        let clean = invoke(
+            group_institution_sync_json,
+            json!({"state_key_hex": TEST_STATE_KEY_HEX, "state_store_dir": path(&dir_a), "cid_number": "CID-A", "device_id": "devA", "group_id": group_id, "institution_roster": roster(&["CID-A", "CID-C"])}),
+        );
+        assert!(clean["commit_wire_hex"].is_null());
+        assert_eq!(clean["missing_cid_numbers"], json!([]));
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
     #[test]
     fn state_envelope_round_trip() {
         let key = [7u8; 32];
@@ -0,0 +1,1269 @@
+//! OpenMLS 本地状态的加密嵌入式 KV 存储。
+//!
+//! 旧实现把整个 `MemoryStorage` 序列化成一个 JSON、整体 AES-GCM 加密后落盘，每次
+//! 加解密都要全量解密 + 重新加密，代价随会话数线性增长。这里改为 SQLite 单表，
+//! 每条 OpenMLS 记录独立 AEAD，并由 [`MlsKvStorage`] 直接实现 OpenMLS 的
+//! `StorageProvider`，OpenMLS 读一条解一条、写一条封一条：
+//!
+//! - 行主键 = `BLAKE2b-256(index_key, record_key)`，`index_key` 由状态密钥派生，
+//!   盘上不出现 group_id 等明文键；
+//! - 行值 = `seal_state(state_key, len(record_key) || record_key || value, aad)`，
+//!   `aad = STATE_AAD_STORAGE || 行主键`，两行密文互换即解密失败；
+//! - `record_key` 与 `openmls_memory_storage` 的布局逐字节一致
+//!   (`label || serde_json(key) || version_be`)，旧信封迁移来的记录原样可读。
+//!
+//! 每次 FFI 调用独占一个连接，打开即 `BEGIN IMMEDIATE`：同一状态库的写者(含并发
+//! isolate、多线程)在 SQLite 写锁上排队，整次调用的全部读写落在同一事务里，
+//! [`MlsKvStorage::commit`] 才提交；中途出错或放弃即回滚。进程内不缓存任何解密状态。
+
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use openmls_traits::storage::{traits, Entity, StorageProvider, CURRENT_VERSION};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use crate::chat_mls::{open_state, seal_state, SerializableMlsStorage, STATE_AAD_STORAGE};
+
+/// 记录主键派生域。
+const INDEX_KEY_DOMAIN: &[u8] = b"citizenapp.local/mls|kv_index";
+/// 等待其他写者释放状态库的上限；超时即本次调用失败，不会越过写锁。
+const WRITER_BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+// 与 `openmls_memory_storage` 相同的记录标签，保证旧信封迁移来的记录可直接读取。
+const KEY_PACKAGE_LABEL: &[u8] = b"KeyPackage";
+const PSK_LABEL: &[u8] = b"Psk";
+const ENCRYPTION_KEY_PAIR_LABEL: &[u8] = b"EncryptionKeyPair";
+const SIGNATURE_KEY_PAIR_LABEL: &[u8] = b"SignatureKeyPair";
+const EPOCH_KEY_PAIRS_LABEL: &[u8] = b"EpochKeyPairs";
+const TREE_LABEL: &[u8] = b"Tree";
+const GROUP_CONTEXT_LABEL: &[u8] = b"GroupContext";
+const INTERIM_TRANSCRIPT_HASH_LABEL: &[u8] = b"InterimTranscriptHash";
+const CONFIRMATION_TAG_LABEL: &[u8] = b"ConfirmationTag";
+const JOIN_CONFIG_LABEL: &[u8] = b"MlsGroupJoinConfig";
+const OWN_LEAF_NODES_LABEL: &[u8] = b"OwnLeafNodes";
+const GROUP_STATE_LABEL: &[u8] = b"GroupState";
+const QUEUED_PROPOSAL_LABEL: &[u8] = b"QueuedProposal";
+const PROPOSAL_QUEUE_REFS_LABEL: &[u8] = b"ProposalQueueRefs";
+const OWN_LEAF_NODE_INDEX_LABEL: &[u8] = b"OwnLeafNodeIndex";
+const EPOCH_SECRETS_LABEL: &[u8] = b"EpochSecrets";
+const RESUMPTION_PSK_STORE_LABEL: &[u8] = b"ResumptionPsk";
+const MESSAGE_SECRETS_LABEL: &[u8] = b"MessageSecrets";
+
+type Records = HashMap<Vec<u8>, Vec<u8>>;
+
+pub(crate) fn kv_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("openmls_kv.sqlite")
+}
+
+/// 旧版整文件信封(迁移源)。
+pub(crate) fn legacy_envelope_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("openmls_storage.bin")
+}
+
+/// OpenMLS 存储错误：解密失败、主键不符、SQLite 失败或序列化失败。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MlsKvError(String);
+
+impl fmt::Display for MlsKvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for MlsKvError {}
+
+impl From<String> for MlsKvError {
+    fn from(message: String) -> Self {
+        Self(message)
+    }
+}
+
+impl From<serde_json::Error> for MlsKvError {
+    fn from(error: serde_json::Error) -> Self {
+        Self(format!("OpenMLS 记录序列化失败: {error}"))
+    }
+}
+
+impl From<MlsKvError> for String {
+    fn from(error: MlsKvError) -> Self {
+        error.0
+    }
+}
+
+/// 单次 FFI 调用的 OpenMLS 存储：持有状态库写事务，逐条加解密读写。
+pub(crate) struct MlsKvStorage {
+    state_key: [u8; 32],
+    index_key: [u8; 32],
+    connection: Mutex<Connection>,
+}
+
+impl MlsKvStorage {
+    /// 打开状态库并取得写锁；首次打开时在同一把锁下完成旧信封迁移。
+    pub(crate) fn open(state_dir: &Path, state_key: &[u8; 32]) -> Result<Self, String> {
+        fs::create_dir_all(state_dir).map_err(|error| format!("创建 MLS 状态目录失败: {error}"))?;
+        let index_key = index_key(state_key);
+        let connection = open_connection(&kv_path(state_dir))?;
+        begin_immediate(&connection)?;
+        migrate_legacy_envelope(state_dir, &connection, state_key, &index_key)?;
+        Ok(Self {
+            state_key: *state_key,
+            index_key,
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// 提交本次调用的全部改动并释放写锁。提交后本存储不再接受读写。
+    pub(crate) fn commit(&self) -> Result<(), String> {
+        let connection = self.connection.lock();
+        if connection.is_autocommit() {
+            return Err("MLS 状态事务已结束".to_string());
+        }
+        connection
+            .execute_batch("COMMIT")
+            .map_err(|error| format!("提交 MLS 状态事务失败: {error}"))
+    }
+
+    fn get(&self, record_key: &[u8]) -> Result<Option<Vec<u8>>, MlsKvError> {
+        let connection = self.active_connection()?;
+        let id = record_id(&self.index_key, record_key);
+        let sealed: Option<Vec<u8>> = connection
+            .query_row(
+                "SELECT sealed FROM mls_records WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|error| format!("读取 MLS 状态记录失败: {error}"))?;
+        let Some(sealed) = sealed else {
+            return Ok(None);
+        };
+        let (stored_key, value) = open_record(&self.state_key, &id, &sealed)?;
+        if stored_key != record_key {
+            return Err(MlsKvError("MLS 状态记录主键与内容不符".to_string()));
+        }
+        Ok(Some(value))
+    }
+
+    fn put(&self, record_key: &[u8], value: &[u8]) -> Result<(), MlsKvError> {
+        let connection = self.active_connection()?;
+        upsert_record(
+            &connection,
+            &self.state_key,
+            &self.index_key,
+            record_key,
+            value,
+        )?;
+        Ok(())
+    }
+
+    fn remove(&self, record_key: &[u8]) -> Result<(), MlsKvError> {
+        let connection = self.active_connection()?;
+        connection
+            .execute(
+                "DELETE FROM mls_records WHERE id = ?1",
+                params![record_id(&self.index_key, record_key)],
+            )
+            .map_err(|error| format!("删除 MLS 状态记录失败: {error}"))?;
+        Ok(())
+    }
+
+    fn active_connection(&self) -> Result<parking_lot::MutexGuard<'_, Connection>, MlsKvError> {
+        let connection = self.connection.lock();
+        if connection.is_autocommit() {
+            // 事务外的写会逐条自动提交，破坏整次调用的原子性，直接拒绝。
+            return Err(MlsKvError("MLS 状态事务已结束".to_string()));
+        }
+        Ok(connection)
+    }
+
+    fn write(&self, label: &[u8], key: &[u8], value: Vec<u8>) -> Result<(), MlsKvError> {
+        self.put(&build_key(label, key), &value)
+    }
+
+    fn append(&self, label: &[u8], key: &[u8], value: Vec<u8>) -> Result<(), MlsKvError> {
+        let record_key = build_key(label, key);
+        let mut list = self.raw_list(&record_key)?;
+        list.push(value);
+        self.put(&record_key, &serde_json::to_vec(&list)?)
+    }
+
+    fn remove_item(&self, label: &[u8], key: &[u8], value: Vec<u8>) -> Result<(), MlsKvError> {
+        let record_key = build_key(label, key);
+        let mut list = self.raw_list(&record_key)?;
+        if let Some(position) = list.iter().position(|item| item == &value) {
+            list.remove(position);
+        }
+        self.put(&record_key, &serde_json::to_vec(&list)?)
+    }
+
+    fn read<V: Entity<CURRENT_VERSION>>(
+        &self,
+        label: &[u8],
+        key: &[u8],
+    ) -> Result<Option<V>, MlsKvError> {
+        self.get(&build_key(label, key))?
+            .map(|value| serde_json::from_slice(&value))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    fn read_list<V: Entity<CURRENT_VERSION>>(
+        &self,
+        label: &[u8],
+        key: &[u8],
+    ) -> Result<Vec<V>, MlsKvError> {
+        self.raw_list(&build_key(label, key))?
+            .iter()
+            .map(|value| serde_json::from_slice(value).map_err(Into::into))
+            .collect()
+    }
+
+    fn raw_list(&self, record_key: &[u8]) -> Result<Vec<Vec<u8>>, MlsKvError> {
+        match self.get(record_key)? {
+            Some(list) => Ok(serde_json::from_slice(&list)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn delete(&self, label: &[u8], key: &[u8]) -> Result<(), MlsKvError> {
+        self.remove(&build_key(label, key))
+    }
+}
+
+impl Drop for MlsKvStorage {
+    fn drop(&mut self) {
+        let connection = self.connection.get_mut();
+        if !connection.is_autocommit() {
+            // 未提交即放弃：整次调用的改动一起回滚。
+            let _ = connection.execute_batch("ROLLBACK");
+        }
+    }
+}
+
+/// 记录键布局与 `openmls_memory_storage` 一致：`label || key || version_be`。
+fn build_key(label: &[u8], key: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(label.len() + key.len() + 2);
+    out.extend_from_slice(label);
+    out.extend_from_slice(key);
+    out.extend_from_slice(&CURRENT_VERSION.to_be_bytes());
+    out
+}
+
+fn json_key(key: &impl Serialize) -> Result<Vec<u8>, MlsKvError> {
+    serde_json::to_vec(key).map_err(Into::into)
+}
+
+fn epoch_key_pairs_id(
+    group_id: &impl traits::GroupId<CURRENT_VERSION>,
+    epoch: &impl traits::EpochKey<CURRENT_VERSION>,
+    leaf_index: u32,
+) -> Result<Vec<u8>, MlsKvError> {
+    let mut key = json_key(group_id)?;
+    key.extend_from_slice(&json_key(epoch)?);
+    key.extend_from_slice(&json_key(&leaf_index)?);
+    Ok(key)
+}
+
+impl StorageProvider<CURRENT_VERSION> for MlsKvStorage {
+    type Error = MlsKvError;
+
+    fn write_mls_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        config: &MlsGroupJoinConfig,
+    ) -> Result<(), Self::Error> {
+        self.write(JOIN_CONFIG_LABEL, &json_key(group_id)?, json_key(config)?)
+    }
+
+    fn append_own_leaf_node<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        leaf_node: &LeafNode,
+    ) -> Result<(), Self::Error> {
+        self.append(
+            OWN_LEAF_NODES_LABEL,
+            &json_key(group_id)?,
+            json_key(leaf_node)?,
+        )
+    }
+
+    fn queue_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+        proposal: &QueuedProposal,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            QUEUED_PROPOSAL_LABEL,
+            &json_key(&(group_id, proposal_ref))?,
+            json_key(proposal)?,
+        )?;
+        self.append(
+            PROPOSAL_QUEUE_REFS_LABEL,
+            &json_key(group_id)?,
+            json_key(proposal_ref)?,
+        )
+    }
+
+    fn write_tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        tree: &TreeSync,
+    ) -> Result<(), Self::Error> {
+        self.write(TREE_LABEL, &json_key(group_id)?, json_key(tree)?)
+    }
+
+    fn write_interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        interim_transcript_hash: &InterimTranscriptHash,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            INTERIM_TRANSCRIPT_HASH_LABEL,
+            &json_key(group_id)?,
+            json_key(interim_transcript_hash)?,
+        )
+    }
+
+    fn write_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_context: &GroupContext,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            GROUP_CONTEXT_LABEL,
+            &json_key(group_id)?,
+            json_key(group_context)?,
+        )
+    }
+
+    fn write_confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        confirmation_tag: &ConfirmationTag,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            CONFIRMATION_TAG_LABEL,
+            &json_key(group_id)?,
+            json_key(confirmation_tag)?,
+        )
+    }
+
+    fn write_group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_state: &GroupState,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            GROUP_STATE_LABEL,
+            &json_key(group_id)?,
+            json_key(group_state)?,
+        )
+    }
+
+    fn write_message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        message_secrets: &MessageSecrets,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            MESSAGE_SECRETS_LABEL,
+            &json_key(group_id)?,
+            json_key(message_secrets)?,
+        )
+    }
+
+    fn write_resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        resumption_psk_store: &ResumptionPskStore,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            RESUMPTION_PSK_STORE_LABEL,
+            &json_key(group_id)?,
+            json_key(resumption_psk_store)?,
+        )
+    }
+
+    fn write_own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        own_leaf_index: &LeafNodeIndex,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            OWN_LEAF_NODE_INDEX_LABEL,
+            &json_key(group_id)?,
+            json_key(own_leaf_index)?,
+        )
+    }
+
+    fn write_group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        group_epoch_secrets: &GroupEpochSecrets,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            EPOCH_SECRETS_LABEL,
+            &json_key(group_id)?,
+            json_key(group_epoch_secrets)?,
+        )
+    }
+
+    fn write_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+        signature_key_pair: &SignatureKeyPair,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            SIGNATURE_KEY_PAIR_LABEL,
+            &json_key(public_key)?,
+            json_key(signature_key_pair)?,
+        )
+    }
+
+    fn write_encryption_key_pair<
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+        key_pair: &HpkeKeyPair,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            ENCRYPTION_KEY_PAIR_LABEL,
+            &json_key(public_key)?,
+            json_key(key_pair)?,
+        )
+    }
+
+    fn write_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+        key_pairs: &[HpkeKeyPair],
+    ) -> Result<(), Self::Error> {
+        self.write(
+            EPOCH_KEY_PAIRS_LABEL,
+            &epoch_key_pairs_id(group_id, epoch, leaf_index)?,
+            json_key(&key_pairs)?,
+        )
+    }
+
+    fn write_key_package<
+        HashReference: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &HashReference,
+        key_package: &KeyPackage,
+    ) -> Result<(), Self::Error> {
+        self.write(
+            KEY_PACKAGE_LABEL,
+            &json_key(hash_ref)?,
+            json_key(key_package)?,
+        )
+    }
+
+    fn write_psk<
+        PskId: traits::PskId<CURRENT_VERSION>,
+        PskBundle: traits::PskBundle<CURRENT_VERSION>,
+    >(
+        &self,
+        psk_id: &PskId,
+        psk: &PskBundle,
+    ) -> Result<(), Self::Error> {
+        self.write(PSK_LABEL, &json_key(psk_id)?, json_key(psk)?)
+    }
+
+    fn mls_group_join_config<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MlsGroupJoinConfig: traits::MlsGroupJoinConfig<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MlsGroupJoinConfig>, Self::Error> {
+        self.read(JOIN_CONFIG_LABEL, &json_key(group_id)?)
+    }
+
+    fn own_leaf_nodes<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNode: traits::LeafNode<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<LeafNode>, Self::Error> {
+        self.read_list(OWN_LEAF_NODES_LABEL, &json_key(group_id)?)
+    }
+
+    fn queued_proposal_refs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<ProposalRef>, Self::Error> {
+        self.read_list(PROPOSAL_QUEUE_REFS_LABEL, &json_key(group_id)?)
+    }
+
+    fn queued_proposals<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+        QueuedProposal: traits::QueuedProposal<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Vec<(ProposalRef, QueuedProposal)>, Self::Error> {
+        let refs: Vec<ProposalRef> = self.queued_proposal_refs(group_id)?;
+        refs.into_iter()
+            .map(|proposal_ref| {
+                let proposal = self
+                    .read(
+                        QUEUED_PROPOSAL_LABEL,
+                        &json_key(&(group_id, &proposal_ref))?,
+                    )?
+                    .ok_or_else(|| MlsKvError("OpenMLS 待处理提案缺失".to_string()))?;
+                Ok((proposal_ref, proposal))
+            })
+            .collect()
+    }
+
+    fn tree<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        TreeSync: traits::TreeSync<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<TreeSync>, Self::Error> {
+        self.read(TREE_LABEL, &json_key(group_id)?)
+    }
+
+    fn group_context<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupContext: traits::GroupContext<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupContext>, Self::Error> {
+        self.read(GROUP_CONTEXT_LABEL, &json_key(group_id)?)
+    }
+
+    fn interim_transcript_hash<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        InterimTranscriptHash: traits::InterimTranscriptHash<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<InterimTranscriptHash>, Self::Error> {
+        self.read(INTERIM_TRANSCRIPT_HASH_LABEL, &json_key(group_id)?)
+    }
+
+    fn confirmation_tag<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ConfirmationTag: traits::ConfirmationTag<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ConfirmationTag>, Self::Error> {
+        self.read(CONFIRMATION_TAG_LABEL, &json_key(group_id)?)
+    }
+
+    fn group_state<
+        GroupState: traits::GroupState<CURRENT_VERSION>,
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupState>, Self::Error> {
+        self.read(GROUP_STATE_LABEL, &json_key(group_id)?)
+    }
+
+    fn message_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        MessageSecrets: traits::MessageSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<MessageSecrets>, Self::Error> {
+        self.read(MESSAGE_SECRETS_LABEL, &json_key(group_id)?)
+    }
+
+    fn resumption_psk_store<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ResumptionPskStore: traits::ResumptionPskStore<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<ResumptionPskStore>, Self::Error> {
+        self.read(RESUMPTION_PSK_STORE_LABEL, &json_key(group_id)?)
+    }
+
+    fn own_leaf_index<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        LeafNodeIndex: traits::LeafNodeIndex<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<LeafNodeIndex>, Self::Error> {
+        self.read(OWN_LEAF_NODE_INDEX_LABEL, &json_key(group_id)?)
+    }
+
+    fn group_epoch_secrets<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        GroupEpochSecrets: traits::GroupEpochSecrets<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<Option<GroupEpochSecrets>, Self::Error> {
+        self.read(EPOCH_SECRETS_LABEL, &json_key(group_id)?)
+    }
+
+    fn signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+        SignatureKeyPair: traits::SignatureKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<Option<SignatureKeyPair>, Self::Error> {
+        self.read(SIGNATURE_KEY_PAIR_LABEL, &json_key(public_key)?)
+    }
+
+    fn encryption_key_pair<
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+        EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<Option<HpkeKeyPair>, Self::Error> {
+        self.read(ENCRYPTION_KEY_PAIR_LABEL, &json_key(public_key)?)
+    }
+
+    fn encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+        HpkeKeyPair: traits::HpkeKeyPair<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<Vec<HpkeKeyPair>, Self::Error> {
+        let key_pairs: Option<Vec<HpkeKeyPair>> = self.read(
+            EPOCH_KEY_PAIRS_LABEL,
+            &epoch_key_pairs_id(group_id, epoch, leaf_index)?,
+        )?;
+        Ok(key_pairs.unwrap_or_default())
+    }
+
+    fn key_package<
+        KeyPackageRef: traits::HashReference<CURRENT_VERSION>,
+        KeyPackage: traits::KeyPackage<CURRENT_VERSION>,
+    >(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<Option<KeyPackage>, Self::Error> {
+        self.read(KEY_PACKAGE_LABEL, &json_key(hash_ref)?)
+    }
+
+    fn psk<PskBundle: traits::PskBundle<CURRENT_VERSION>, PskId: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskId,
+    ) -> Result<Option<PskBundle>, Self::Error> {
+        self.read(PSK_LABEL, &json_key(psk_id)?)
+    }
+
+    fn remove_proposal<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        proposal_ref: &ProposalRef,
+    ) -> Result<(), Self::Error> {
+        self.remove_item(
+            PROPOSAL_QUEUE_REFS_LABEL,
+            &json_key(group_id)?,
+            json_key(proposal_ref)?,
+        )?;
+        self.delete(QUEUED_PROPOSAL_LABEL, &json_key(&(group_id, proposal_ref))?)
+    }
+
+    fn delete_own_leaf_nodes<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(OWN_LEAF_NODES_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_group_config<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(JOIN_CONFIG_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_tree<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(TREE_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_confirmation_tag<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(CONFIRMATION_TAG_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_group_state<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(GROUP_STATE_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_context<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(GROUP_CONTEXT_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_interim_transcript_hash<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(INTERIM_TRANSCRIPT_HASH_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_message_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(MESSAGE_SECRETS_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_all_resumption_psk_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(RESUMPTION_PSK_STORE_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_own_leaf_index<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(OWN_LEAF_NODE_INDEX_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_group_epoch_secrets<GroupId: traits::GroupId<CURRENT_VERSION>>(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        self.delete(EPOCH_SECRETS_LABEL, &json_key(group_id)?)
+    }
+
+    fn clear_proposal_queue<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        ProposalRef: traits::ProposalRef<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+    ) -> Result<(), Self::Error> {
+        let refs: Vec<ProposalRef> = self.queued_proposal_refs(group_id)?;
+        for proposal_ref in refs {
+            self.delete(
+                QUEUED_PROPOSAL_LABEL,
+                &json_key(&(group_id, &proposal_ref))?,
+            )?;
+        }
+        self.delete(PROPOSAL_QUEUE_REFS_LABEL, &json_key(group_id)?)
+    }
+
+    fn delete_signature_key_pair<
+        SignaturePublicKey: traits::SignaturePublicKey<CURRENT_VERSION>,
+    >(
+        &self,
+        public_key: &SignaturePublicKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(SIGNATURE_KEY_PAIR_LABEL, &json_key(public_key)?)
+    }
+
+    fn delete_encryption_key_pair<EncryptionKey: traits::EncryptionKey<CURRENT_VERSION>>(
+        &self,
+        public_key: &EncryptionKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(ENCRYPTION_KEY_PAIR_LABEL, &json_key(public_key)?)
+    }
+
+    fn delete_encryption_epoch_key_pairs<
+        GroupId: traits::GroupId<CURRENT_VERSION>,
+        EpochKey: traits::EpochKey<CURRENT_VERSION>,
+    >(
+        &self,
+        group_id: &GroupId,
+        epoch: &EpochKey,
+        leaf_index: u32,
+    ) -> Result<(), Self::Error> {
+        self.delete(
+            EPOCH_KEY_PAIRS_LABEL,
+            &epoch_key_pairs_id(group_id, epoch, leaf_index)?,
+        )
+    }
+
+    fn delete_key_package<KeyPackageRef: traits::HashReference<CURRENT_VERSION>>(
+        &self,
+        hash_ref: &KeyPackageRef,
+    ) -> Result<(), Self::Error> {
+        self.delete(KEY_PACKAGE_LABEL, &json_key(hash_ref)?)
+    }
+
+    fn delete_psk<PskKey: traits::PskId<CURRENT_VERSION>>(
+        &self,
+        psk_id: &PskKey,
+    ) -> Result<(), Self::Error> {
+        self.delete(PSK_LABEL, &json_key(psk_id)?)
+    }
+}
+
+/// 用新状态密钥把全部记录重封到暂存库 `staged`，写完再用新钥完整读一遍确认可解。
+///
+/// 读取源库时持有写锁，期间不会有调用改动记录。
+pub(crate) fn stage_rekey(
+    state_dir: &Path,
+    staged: &Path,
+    current_key: &[u8; 32],
+    new_key: &[u8; 32],
+) -> Result<(), String> {
+    let path = kv_path(state_dir);
+    if !path.exists() {
+        return Ok(());
+    }
+    let connection = open_connection(&path)?;
+    begin_immediate(&connection)?;
+    let records = read_records(&connection, current_key, &index_key(current_key))?;
+    let _ = connection.execute_batch("ROLLBACK");
+    drop(connection);
+
+    if staged.exists() {
+        fs::remove_file(staged).map_err(|error| format!("清理 MLS 换绑暂存失败: {error}"))?;
+    }
+    let new_index = index_key(new_key);
+    let staged_connection = open_connection(staged)?;
+    begin_immediate(&staged_connection)?;
+    for (key, value) in &records {
+        upsert_record(&staged_connection, new_key, &new_index, key, value)?;
+    }
+    staged_connection
+        .execute_batch("COMMIT")
+        .map_err(|error| format!("提交 MLS 换绑暂存失败: {error}"))?;
+    let verified = read_records(&staged_connection, new_key, &new_index)?;
+    if verified.len() != records.len() {
+        return Err("MLS 换绑暂存记录数不一致".to_string());
+    }
+    Ok(())
+}
+
+/// 旧整文件信封 → KV:在调用方已持有的写事务内整批写入并立即提交，提交成功后才删
+/// 旧文件，再重新取得写锁；中途崩溃则下次重放迁移(同键记录以信封为准覆盖，结果一致)。
+fn migrate_legacy_envelope(
+    state_dir: &Path,
+    connection: &Connection,
+    state_key: &[u8; 32],
+    index_key: &[u8; 32],
+) -> Result<(), String> {
+    use base64::Engine;
+    let legacy = legacy_envelope_path(state_dir);
+    if !legacy.exists() {
+        return Ok(());
+    }
+    let blob = fs::read(&legacy).map_err(|error| format!("读取 OpenMLS 旧信封失败: {error}"))?;
+    let clear = open_state(state_key, &blob, STATE_AAD_STORAGE)?;
+    let parsed: SerializableMlsStorage = serde_json::from_slice(&clear)
+        .map_err(|error| format!("解析 OpenMLS 旧信封失败: {error}"))?;
+    for (key, value) in parsed.values {
+        let key = base64::prelude::BASE64_STANDARD
+            .decode(key)
+            .map_err(|error| format!("OpenMLS 旧信封键解码失败: {error}"))?;
+        let value = base64::prelude::BASE64_STANDARD
+            .decode(value)
+            .map_err(|error| format!("OpenMLS 旧信封值解码失败: {error}"))?;
+        upsert_record(connection, state_key, index_key, &key, &value)?;
+    }
+    connection
+        .execute_batch("COMMIT")
+        .map_err(|error| format!("提交 OpenMLS 旧信封迁移失败: {error}"))?;
+    fs::remove_file(&legacy).map_err(|error| format!("删除 OpenMLS 旧信封失败: {error}"))?;
+    begin_immediate(connection)
+}
+
+fn open_connection(path: &Path) -> Result<Connection, String> {
+    let connection =
+        Connection::open(path).map_err(|error| format!("打开 MLS 状态库失败: {error}"))?;
+    connection
+        .busy_timeout(WRITER_BUSY_TIMEOUT)
+        .map_err(|error| format!("设置 MLS 状态库等待时限失败: {error}"))?;
+    // 回滚日志 + FULL 同步：事务提交即落盘；不用 WAL，换钥时单文件 rename 即可整体替换。
+    connection
+        .execute_batch(
+            "PRAGMA journal_mode = DELETE;
+             PRAGMA synchronous = FULL;
+             CREATE TABLE IF NOT EXISTS mls_records (
+                 id BLOB PRIMARY KEY NOT NULL,
+                 sealed BLOB NOT NULL
+             ) WITHOUT ROWID;",
+        )
+        .map_err(|error| format!("初始化 MLS 状态库失败: {error}"))?;
+    Ok(connection)
+}
+
+/// 立即取得写锁：其他写者在 busy_timeout 内排队，超时报错而不是并发改写。
+fn begin_immediate(connection: &Connection) -> Result<(), String> {
+    connection
+        .execute_batch("BEGIN IMMEDIATE")
+        .map_err(|error| format!("取得 MLS 状态库写锁失败: {error}"))
+}
+
+fn read_records(
+    connection: &Connection,
+    state_key: &[u8; 32],
+    index_key: &[u8; 32],
+) -> Result<Records, String> {
+    let mut statement = connection
+        .prepare("SELECT id, sealed FROM mls_records")
+        .map_err(|error| format!("读取 MLS 状态库失败: {error}"))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })
+        .map_err(|error| format!("读取 MLS 状态库失败: {error}"))?;
+    let mut records = Records::new();
+    for row in rows {
+        let (id, sealed) = row.map_err(|error| format!("读取 MLS 状态记录失败: {error}"))?;
+        let (key, value) = open_record(state_key, &id, &sealed)?;
+        if record_id(index_key, &key) != id {
+            return Err("MLS 状态记录主键与内容不符".to_string());
+        }
+        records.insert(key, value);
+    }
+    Ok(records)
+}
+
+fn upsert_record(
+    connection: &Connection,
+    state_key: &[u8; 32],
+    index_key: &[u8; 32],
+    key: &[u8],
+    value: &[u8],
+) -> Result<(), String> {
+    let id = record_id(index_key, key);
+    let sealed = seal_record(state_key, &id, key, value)?;
+    connection
+        .execute(
+            "INSERT INTO mls_records (id, sealed) VALUES (?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET sealed = excluded.sealed",
+            params![id, sealed],
+        )
+        .map_err(|error| format!("写入 MLS 状态记录失败: {error}"))?;
+    Ok(())
+}
+
+fn seal_record(
+    state_key: &[u8; 32],
+    id: &[u8],
+    key: &[u8],
+    value: &[u8],
+) -> Result<Vec<u8>, String> {
+    let key_len = u32::try_from(key.len()).map_err(|_| "MLS 状态记录键过长".to_string())?;
+    let mut clear = Vec::with_capacity(4 + key.len() + value.len());
+    clear.extend_from_slice(&key_len.to_le_bytes());
+    clear.extend_from_slice(key);
+    clear.extend_from_slice(value);
+    let sealed = seal_state(state_key, &clear, &record_aad(id));
+    clear.fill(0);
+    sealed
+}
+
+fn open_record(
+    state_key: &[u8; 32],
+    id: &[u8],
+    sealed: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let mut clear = open_state(state_key, sealed, &record_aad(id))?;
+    if clear.len() < 4 {
+        return Err("MLS 状态记录长度无效".to_string());
+    }
+    let key_len = u32::from_le_bytes([clear[0], clear[1], clear[2], clear[3]]) as usize;
+    if clear.len() < 4 + key_len {
+        return Err("MLS 状态记录长度无效".to_string());
+    }
+    let key = clear[4..4 + key_len].to_vec();
+    let value = clear[4 + key_len..].to_vec();
+    clear.fill(0);
+    Ok((key, value))
+}
+
+fn record_aad(id: &[u8]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(STATE_AAD_STORAGE.len() + 1 + id.len());
+    aad.extend_from_slice(STATE_AAD_STORAGE);
+    aad.push(b'|');
+    aad.extend_from_slice(id);
+    aad
+}
+
+fn index_key(state_key: &[u8; 32]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(blake2_rfc::blake2b::blake2b(32, state_key, INDEX_KEY_DOMAIN).as_bytes());
+    out
+}
+
+fn record_id(index_key: &[u8; 32], key: &[u8]) -> Vec<u8> {
+    blake2_rfc::blake2b::blake2b(32, index_key, key)
+        .as_bytes()
+        .to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openmls_basic_credential::SignatureKeyPair;
+    use openmls_memory_storage::MemoryStorage;
+    use openmls_traits::types::SignatureScheme;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("citizen_mls_kv_{tag}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn all_records(dir: &Path, key: &[u8; 32]) -> Records {
+        let connection = open_connection(&kv_path(dir)).unwrap();
+        read_records(&connection, key, &index_key(key)).unwrap()
+    }
+
+    #[test]
+    fn committed_records_survive_reopen_without_plaintext_on_disk() {
+        let dir = temp_dir("reopen");
+        let key = [9u8; 32];
+        let storage = MlsKvStorage::open(&dir, &key).unwrap();
+        storage.put(b"group:alpha", b"epoch-1").unwrap();
+        storage.put(b"group:beta", b"epoch-7").unwrap();
+        storage.commit().unwrap();
+        drop(storage);
+
+        let storage = MlsKvStorage::open(&dir, &key).unwrap();
+        assert_eq!(storage.get(b"group:alpha").unwrap().unwrap(), b"epoch-1");
+        storage.remove(b"group:beta").unwrap();
+        storage.put(b"group:alpha", b"epoch-2").unwrap();
+        storage.commit().unwrap();
+        drop(storage);
+
+        let records = all_records(&dir, &key);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[b"group:alpha".as_slice()], b"epoch-2");
+        // 盘上既无明文值，也无明文键。
+        let file = fs::read(kv_path(&dir)).unwrap();
+        assert!(!file.windows(7).any(|w| w == b"epoch-2"));
+        assert!(!file.windows(11).any(|w| w == b"group:alpha"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn uncommitted_changes_roll_back_and_closed_storage_rejects_writes() {
+        let dir = temp_dir("rollback");
+        let key = [10u8; 32];
+        let storage = MlsKvStorage::open(&dir, &key).unwrap();
+        storage.put(b"k", b"v1").unwrap();
+        storage.commit().unwrap();
+        assert!(storage.put(b"k", b"late").is_err());
+        drop(storage);
+
+        let scratch = MlsKvStorage::open(&dir, &key).unwrap();
+        scratch.put(b"k", b"v2").unwrap();
+        drop(scratch);
+        assert_eq!(all_records(&dir, &key)[b"k".as_slice()], b"v1");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn concurrent_writers_are_serialized_by_the_write_lock() {
+        let dir = temp_dir("writers");
+        let key = [16u8; 32];
+        let first = MlsKvStorage::open(&dir, &key).unwrap();
+        first.put(b"counter", b"1").unwrap();
+
+        let second_dir = dir.clone();
+        let second = std::thread::spawn(move || {
+            // 第一个写者提交前拿不到写锁，读到的必然是其提交后的值。
+            let storage = MlsKvStorage::open(&second_dir, &key).unwrap();
+            let seen = storage.get(b"counter").unwrap();
+            storage.put(b"counter", b"2").unwrap();
+            storage.commit().unwrap();
+            seen
+        });
+        std::thread::sleep(Duration::from_millis(100));
+        first.commit().unwrap();
+        drop(first);
+        assert_eq!(second.join().unwrap().unwrap(), b"1");
+        assert_eq!(all_records(&dir, &key)[b"counter".as_slice()], b"2");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn record_layout_matches_memory_storage() {
+        let dir = temp_dir("layout");
+        let key = [17u8; 32];
+        let signer = SignatureKeyPair::new(SignatureScheme::ED25519).unwrap();
+        let memory = MemoryStorage::default();
+        signer.store(&memory).unwrap();
+
+        let storage = MlsKvStorage::open(&dir, &key).unwrap();
+        signer.store(&storage).unwrap();
+        let read =
+            SignatureKeyPair::read(&storage, &signer.to_public_vec(), SignatureScheme::ED25519)
+                .expect("signer readable from kv");
+        assert_eq!(read.to_public_vec(), signer.to_public_vec());
+        storage.commit().unwrap();
+        drop(storage);
+
+        // 与 MemoryStorage 写出的键值逐字节一致，旧信封迁移来的记录可原样读取。
+        let expected = memory.values.read().unwrap().clone();
+        assert_eq!(all_records(&dir, &key), expected);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn swapped_or_wrong_key_records_fail_closed() {
+        let dir = temp_dir("swap");
+        let key = [11u8; 32];
+        let storage = MlsKvStorage::open(&dir, &key).unwrap();
+        storage.put(b"a", b"1").unwrap();
+        storage.put(b"b", b"2").unwrap();
+        storage.commit().unwrap();
+        drop(storage);
+
+        let wrong = MlsKvStorage::open(&dir, &[12u8; 32]).unwrap();
+        assert!(wrong.get(b"a").unwrap().is_none());
+        drop(wrong);
+
+        // 把 a 的密文搬到 b 的行：AAD 绑定主键，解密必须失败。
+        let connection = open_connection(&kv_path(&dir)).unwrap();
+        let sealed_a: Vec<u8> = connection
+            .query_row(
+                "SELECT sealed FROM mls_records WHERE id = ?1",
+                params![record_id(&index_key(&key), b"a")],
+                |row| row.get(0),
+            )
+            .unwrap();
+        connection
+            .execute(
+                "UPDATE mls_records SET sealed = ?1 WHERE id = ?2",
+                params![sealed_a, record_id(&index_key(&key), b"b")],
+            )
+            .unwrap();
+        drop(connection);
+        let storage = MlsKvStorage::open(&dir, &key).unwrap();
+        assert!(storage.get(b"b").is_err());
+        drop(storage);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn legacy_envelope_is_migrated_and_removed() {
+        use base64::Engine;
+        let dir = temp_dir("migrate");
+        let key = [13u8; 32];
+        let mut legacy = SerializableMlsStorage::default();
+        legacy.values.insert(
+            base64::prelude::BASE64_STANDARD.encode(b"sig-key"),
+            base64::prelude::BASE64_STANDARD.encode(b"secret"),
+        );
+        let clear = serde_json::to_vec(&legacy).unwrap();
+        fs::write(
+            legacy_envelope_path(&dir),
+            seal_state(&key, &clear, STATE_AAD_STORAGE).unwrap(),
+        )
+        .unwrap();
+
+        // 迁移在打开时独立提交，调用方放弃本次改动也不影响迁移结果。
+        let storage = MlsKvStorage::open(&dir, &key).unwrap();
+        assert_eq!(storage.get(b"sig-key").unwrap().unwrap(), b"secret");
+        drop(storage);
+        assert!(!legacy_envelope_path(&dir).exists());
+        assert_eq!(all_records(&dir, &key)[b"sig-key".as_slice()], b"secret");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn staged_rekey_reseals_every_record_under_new_key() {
+        let dir = temp_dir("rekey");
+        let old_key = [14u8; 32];
+        let new_key = [15u8; 32];
+        let storage = MlsKvStorage::open(&dir, &old_key).unwrap();
+        storage.put(b"x", b"y").unwrap();
+        storage.commit().unwrap();
+        drop(storage);
+
+        let staged = dir.join("openmls_kv.account_rekey");
+        stage_rekey(&dir, &staged, &old_key, &new_key).unwrap();
+        fs::rename(&staged, kv_path(&dir)).unwrap();
+        assert_eq!(all_records(&dir, &new_key)[b"x".as_slice()], b"y");
+        let connection = open_connection(&kv_path(&dir)).unwrap();
+        assert!(read_records(&connection, &old_key, &index_key(&old_key)).is_err());
+        drop(connection);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -16,7 +16,12 @@ use std::os::raw::{c_char, c_int};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{mpsc, Arc};
 
+mod chat_attachment;
+mod chat_devices;
+mod chat_identity;
+mod chat_institution;
 mod chat_mls;
+mod chat_mls_store;
 mod error;
 mod ffi_types;
 
@@ -0,0 +1,124 @@
+import 'package:citizenapp/chat/chat_models.dart';
+import 'package:citizenapp/chat/crypto/mls_session.dart';
+import 'package:citizenapp/chat/devices/device_history_transfer.dart';
+import 'package:citizenapp/chat/storage/chat_store.dart';
+import 'package:flutter_test/flutter_test.dart';
+
+import '../../support/isar_test_env.dart';
+
+const _ownerCidNumber = 'CN220-CTZN2-100000001-2026';
+const _bobCidNumber = 'CN220-CTZN2-100000002-2026';
+const _accountId =
+    '0x1111111111111111111111111111111111111111111111111111111111111111';
+
+void main() {
+  useIsolatedIsar();
+
+  test('迁移包按信令上限切片,乱序到达也能重组', () {
+    final bundle = <String, dynamic>{
+      'cid_number': _ownerCidNumber,
+      'ciphertext_hex': 'ab' * (kHistoryChunkChars + 100),
+    };
+    final chunks = splitHistoryBundle(transferId: 't-1', bundle: bundle);
+    expect(chunks.length, 3);
+    for (final chunk in chunks) {
+      expect(
+        (chunk['data'] as String).length,
+        lessThanOrEqualTo(kHistoryChunkChars),
+      );
+    }
+
+    final assembler = HistoryBundleAssembler();
+    expect(assembler.add(Map<String, dynamic>.from(chunks[2])), isNull);
+    expect(assembler.add(Map<String, dynamic>.from(chunks[0])), isNull);
+    expect(assembler.add(Map<String, dynamic>.from(chunks[1])), bundle);
+    // 越界分片直接丢弃。
+    expect(assembler.add({...chunks[0], 'index': 9, 'count': 3}), isNull);
+  });
+
+  test('导出 → 编解码 → 导入:正文还原,重复导入跳过', () async {
+    final store = ChatStore();
+    final envelope = const MlsWireMessage(
+      wireBytes: [1],
+      cipherSuite: '',
+      conversationId: 'conv-history',
+      messageKind: MlsMessageKind.application,
+    ).toEnvelope(
+      envelopeId: 'env-history',
+      senderCidNumber: _bobCidNumber,
+      recipientCidNumber: _ownerCidNumber,
+      senderDeviceId: 'bob-phone',
+      createdAtMillis: 10,
+      ttlMillis: 60000,
+    );
+    await store.saveIncomingEnvelope(
+      ownerCidNumber: _ownerCidNumber,
+      currentAccountId: _accountId,
+      envelope: envelope,
+      envelopeBytes: envelope.writeToBuffer(),
+      messageKind: ChatMessageKind.text,
+      plaintext: '旧设备上的消息',
+    );
+
+    final exported = await store.exportHistory(
+      ownerCidNumber: _ownerCidNumber,
+      currentAccountId: _accountId,
+    );
+    final records = ChatHistoryCodec.decode(ChatHistoryCodec.encode(exported));
+    expect(records.single.plaintext, '旧设备上的消息');
+
+    await store.clearAllForCidNumber(_ownerCidNumber);
+    expect(
+      await store.importHistory(
+        ownerCidNumber: _ownerCidNumber,
+        currentAccountId: _accountId,
+        records: records,
+      ),
+      1,
+    );
+    final restored = await store.readMessages(
+      ownerCidNumber: _ownerCidNumber,
+      currentAccountId: _accountId,
+      conversationId: 'conv-history',
+    );
+    expect(restored.single.plaintext, '旧设备上的消息');
+    expect(restored.single.direction, 'incoming');
+    expect(
+      await store.importHistory(
+        ownerCidNumber: _ownerCidNumber,
+        currentAccountId: _accountId,
+        records: records,
+      ),
+      0,
+    );
+  });
+
+  test('迁移请求只入待确认箱:同设备替换旧请求,取出一次即移除,过期不可取', () {
+    final inbox = HistoryRequestInbox.instance;
+    final now = DateTime.now().millisecondsSinceEpoch;
+    PendingHistoryRequest request(String deviceId, int createdAtMillis) =>
+        PendingHistoryRequest(
+          deviceId: deviceId,
+          createdAtMillis: createdAtMillis,
+          request: {'device_id': deviceId, 'created_at_ms': createdAtMillis},
+        );
+
+    inbox.hold(request('phone-new', now - 1000));
+    inbox.hold(request('phone-new', now));
+    inbox.hold(request('tablet', now));
+    expect(
+      inbox.pending.value.map((item) => item.requestId),
+      ['phone-new@$now', 'tablet@$now'],
+    );
+    expect(inbox.take('phone-new@${now - 1000}'), isNull);
+    expect(inbox.take('phone-new@$now')?.deviceId, 'phone-new');
+    expect(inbox.take('phone-new@$now'), isNull);
+
+    inbox.dropDevice('tablet');
+    expect(inbox.pending.value, isEmpty);
+
+    final stale = now - kHistoryRequestTtl.inMilliseconds;
+    inbox.hold(request('old-phone', stale));
+    expect(inbox.pending.value, isEmpty);
+  });
+}
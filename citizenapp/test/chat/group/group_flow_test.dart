@@ -9,6 +9,7 @@ import 'package:citizenapp/chat/crypto/mls_group_boundary.dart';
 import 'package:citizenapp/chat/group/group_control.dart';
 import 'package:citizenapp/chat/group/group_flow.dart';
 import 'package:citizenapp/chat/group/group_membership.dart';
+import 'package:citizenapp/chat/group/institution_group.dart';
 import 'package:citizenapp/chat/proto/chat_envelope.pb.dart';
 import 'package:citizenapp/chat/storage/chat_store.dart';
 import 'package:citizenapp/chat/transport/chat_transport.dart';
@@ -29,6 +30,9 @@ class _FakeGroupCrypto implements MlsGroupCrypto {
   final Map<String, List<String>> _roster = {};
   final Map<String, int> _epoch = {};
 
+  /// 机构群同步比对的链上名册(测试直接改写)。
+  InstitutionRoster? institutionRoster;
+
   String get _localIdentity => '$cidNumber:$localDeviceId';
 
   @override
@@ -73,6 +77,20 @@ class _FakeGroupCrypto implements MlsGroupCrypto {
     );
   }
 
+  @override
+  Future<GroupCommitBundle> removeDevices(
+    String groupId,
+    List<String> memberIdentities,
+  ) async {
+    _roster[groupId]!.removeWhere(memberIdentities.contains);
+    _epoch[groupId] = (_epoch[groupId] ?? 0) + 1;
+    return GroupCommitBundle(
+      groupId: groupId,
+      epoch: _epoch[groupId]!,
+      commit: _wire(groupId, 'commit'),
+    );
+  }
+
   @override
   Future<MlsWireMessage> groupCreateMessage(
     String groupId,
@@ -110,6 +128,32 @@ class _FakeGroupCrypto implements MlsGroupCrypto {
     );
   }
 
+  @override
+  Future<GroupCommitBundle?> evictStaleMembers(String groupId) async => null;
+
+  @override
+  Future<InstitutionSyncBundle> syncInstitutionGroup(String groupId) async {
+    final expected = institutionRoster!.memberCidNumbers.toSet();
+    final present =
+        _roster[groupId]!.map((identity) => identity.split(':').first).toSet();
+    final removed = present
+        .difference(expected)
+        .where((cid) => cid != cidNumber)
+        .toList();
+    MlsWireMessage? commit;
+    if (removed.isNotEmpty) {
+      commit = (await removeMembers(groupId, removed)).commit;
+    }
+    return InstitutionSyncBundle(
+      groupId: groupId,
+      epoch: _epoch[groupId] ?? 0,
+      commit: commit,
+      removedCidNumbers: removed,
+      missingCidNumbers: expected.difference(present).toList(),
+      selfExtraneous: !expected.contains(cidNumber),
+    );
+  }
+
   MlsWireMessage _wire(String groupId, String tag) => MlsWireMessage(
         wireBytes: utf8.encode(tag),
         cipherSuite: '',
@@ -270,6 +314,80 @@ void main() {
     expect(delivered.map((e) => e.recipientCidNumber).toSet(), {_cidB, _cidC});
   });
 
+  test('机构群按链上任职同步:卸任者移除、新任者补加、管理员取链上集合', () async {
+    final store = ChatStore();
+    final crypto = _FakeGroupCrypto(cidNumber: _cidA, localDeviceId: 'devA');
+    var chainAdmins = [
+      (cidNumber: _cidA, roleCodes: ['COMMITTEE']),
+      (cidNumber: _cidB, roleCodes: ['COMMITTEE']),
+      (cidNumber: _cidC, roleCodes: ['COMMITTEE']),
+      (cidNumber: _cidD, roleCodes: <String>[]),
+    ];
+    Future<InstitutionRoster> readRoster(String cid, String scope) async {
+      return InstitutionRoster.fromAdmins(
+        institutionCidNumber: cid,
+        scope: scope,
+        admins: chainAdmins,
+      );
+    }
+
+    final flow = ChatGroupFlow(
+      ownerCidNumber: _ownerCidNumber,
+      crypto: crypto,
+      store: store,
+      deliverer: _okDeliverer,
+      cidNumber: _cidA,
+      currentAccountId: _accountA,
+      localDeviceId: 'devA',
+      institutionRosterReader: readRoster,
+    );
+    final groupId = institutionGroupId('NRC001', 'COMMITTEE');
+    await flow.createGroup(
+      groupId: groupId,
+      name: '委员会',
+      cidNumber: _cidA,
+      localDeviceId: 'devA',
+      invitees: [_keyPackage(_cidB, 'devB'), _keyPackage(_cidC, 'devC')],
+    );
+    final created = await store.readGroup(_ownerCidNumber, groupId);
+    expect(created!.adminSet, {_cidA, _cidB, _cidC});
+
+    // C 卸任、D 新任。
+    chainAdmins = [
+      (cidNumber: _cidA, roleCodes: ['COMMITTEE']),
+      (cidNumber: _cidB, roleCodes: ['COMMITTEE']),
+      (cidNumber: _cidD, roleCodes: ['COMMITTEE']),
+    ];
+    crypto.institutionRoster = await readRoster('NRC001', 'COMMITTEE');
+    final fetched = <String>[];
+    final bundle = await flow.syncInstitutionGroup(
+      groupId: groupId,
+      fetchKeyPackages: (cidNumbers) async {
+        fetched.addAll(cidNumbers);
+        return [for (final cid in cidNumbers) _keyPackage(cid, 'dev')];
+      },
+    );
+    expect(bundle!.removedCidNumbers, [_cidC]);
+    expect(fetched, [_cidD]);
+    final synced = await store.readGroup(_ownerCidNumber, groupId);
+    expect(synced!.memberCidNumbers.toSet(), {_cidA, _cidB, _cidD});
+    expect(synced.adminSet, {_cidA, _cidB, _cidD});
+
+    // 普通群不参与同步。
+    const plainGroupId = 'grp:$_cidA:plain';
+    await flow.createGroup(
+      groupId: plainGroupId,
+      name: 'g',
+      cidNumber: _cidA,
+      localDeviceId: 'devA',
+    );
+    final plain = await flow.syncInstitutionGroup(
+      groupId: plainGroupId,
+      fetchKeyPackages: (_) async => const [],
+    );
+    expect(plain, isNull);
+  });
+
   test('非 admin 加人被拒', () async {
     final store = ChatStore();
     final crypto = _FakeGroupCrypto(
@@ -418,6 +536,108 @@ void main() {
     expect(group!.name, '新群名');
   });
 
+  test('本人多设备:新设备入群收 Welcome、群发含本人 CID、吊销只移除该设备', () async {
+    final store = ChatStore();
+    final crypto = _FakeGroupCrypto(cidNumber: _cidA, localDeviceId: 'devA');
+    final delivered = <ChatEnvelope>[];
+    final flow = ChatGroupFlow(
+      ownerCidNumber: _ownerCidNumber,
+      crypto: crypto,
+      store: store,
+      deliverer: (envelope, bytes, recipientCidNumber) async {
+        delivered.add(envelope);
+        return _okDeliverer(envelope, bytes, recipientCidNumber);
+      },
+      cidNumber: _cidA,
+      currentAccountId: _accountA,
+      localDeviceId: 'devA',
+    );
+    const groupId = 'grp:$_cidA:devices';
+    await flow.createGroup(
+      groupId: groupId,
+      name: 'g',
+      cidNumber: _cidA,
+      localDeviceId: 'devA',
+      invitees: [_keyPackage(_cidB, 'devB')],
+    );
+
+    // 本人平板入群:Welcome 扇给本人 CID,Commit 扇给 B;CID 名册不变。
+    delivered.clear();
+    await flow.addOwnDevices(
+      groupId: groupId,
+      keyPackages: [_keyPackage(_cidA, 'tabletA')],
+    );
+    expect(
+      (await crypto.groupState(groupId)).memberIdentities,
+      contains('$_cidA:tabletA'),
+    );
+    expect(delivered.map((e) => e.recipientCidNumber).toSet(), {_cidA, _cidB});
+    final group = await store.readGroup(_ownerCidNumber, groupId);
+    expect(group!.memberCidNumbers.toSet(), {_cidA, _cidB});
+    // 已在群的设备再加不产生握手。
+    delivered.clear();
+    await flow.addOwnDevices(
+      groupId: groupId,
+      keyPackages: [_keyPackage(_cidA, 'tabletA')],
+    );
+    expect(delivered, isEmpty);
+
+    // 群发同时扇给本人 CID,让平板同步本机发出的消息。
+    await flow.sendGroupText(
+      groupId: groupId,
+      senderCidNumber: _cidA,
+      senderDeviceId: 'devA',
+      text: '多设备',
+    );
+    expect(delivered.map((e) => e.recipientCidNumber).toSet(), {_cidA, _cidB});
+    // 本机回声丢弃,不重复落库。
+    expect(
+      await flow.processIncomingGroupEnvelope(delivered.first.writeToBuffer()),
+      isNull,
+    );
+
+    // 平板发来的消息按本人已发落库、不计未读。
+    final fromTablet = MlsWireMessage(
+      wireBytes: utf8.encode(ChatPayloadCodec.encode(ChatContent.text('平板'))),
+      cipherSuite: '',
+      conversationId: groupId,
+      messageKind: MlsMessageKind.application,
+    ).toEnvelope(
+      envelopeId: 'tablet-1',
+      senderCidNumber: _cidA,
+      recipientCidNumber: _cidA,
+      senderDeviceId: 'tabletA',
+      createdAtMillis: 200,
+      ttlMillis: 60,
+    );
+    await flow.processIncomingGroupEnvelope(fromTablet.writeToBuffer());
+    final messages = await store.readMessages(
+      ownerCidNumber: _ownerCidNumber,
+      currentAccountId: _accountA,
+      conversationId: groupId,
+    );
+    expect(
+      messages.singleWhere((m) => m.envelopeId == 'tablet-1').direction,
+      'outgoing',
+    );
+
+    // 吊销平板:只移除该叶子,Commit 也送达平板所在的本人 CID。
+    delivered.clear();
+    expect(
+      await flow.removeOwnDevice(groupId: groupId, deviceId: 'tabletA'),
+      isTrue,
+    );
+    expect(
+      (await crypto.groupState(groupId)).memberIdentities.toSet(),
+      {'$_cidA:devA', '$_cidB:devB'},
+    );
+    expect(delivered.map((e) => e.recipientCidNumber).toSet(), {_cidA, _cidB});
+    expect(
+      await flow.removeOwnDevice(groupId: groupId, deviceId: 'tabletA'),
+      isFalse,
+    );
+  });
+
   test('群发贴纸:落 sticker 消息 + 扇出', () async {
     final store = ChatStore();
     final crypto = _FakeGroupCrypto(
@@ -500,102 +720,75 @@ void main() {
       return flow;
     }
 
-    test('≤100MB → 对每个成员各发一次 WebRTC + 按成员登记 pending', () async {
-      final store = ChatStore();
-      final flow = await buildGroup(store);
-      final webrtcTo = <String>[];
-      final pending = <String>[];
-      var relayUploads = 0;
-
+    Future<String> sendAndReadPayload(
+      ChatStore store,
+      ChatGroupFlow flow,
+      int byteSize,
+      List<int> recipientCounts,
+    ) async {
       await flow.sendGroupMedia(
         groupId: 'grp:$_cidA:nm',
         senderCidNumber: _cidA,
         senderDeviceId: 'devA',
-        media: _mediaDraft(50 * 1024 * 1024),
-        sendMemberAttachment: ({
-          required recipientCidNumber,
-          required conversationId,
-          required attachmentId,
-          required fileName,
-          required contentType,
-          required sourcePath,
-          required byteSize,
-        }) async {
-          webrtcTo.add(recipientCidNumber);
-        },
+        media: _mediaDraft(byteSize),
         uploadRelayMedia: ({
           required conversationId,
           required attachmentId,
           required media,
           int recipientCount = 1,
         }) async {
-          relayUploads++;
-          return const ChatRelayDescriptor(
-              relayObjectKey: '', contentKeyB64: '', chunkSize: 0, encSize: 0);
+          recipientCounts.add(recipientCount);
+          return ChatRelayDescriptor(
+            relayObjectKey: 'chat-relay/x',
+            chunkSize: 1048576,
+            encSize: byteSize + 8192,
+            mlsKeyHex: 'ab' * 32,
+            contentHashHex: 'cd' * 32,
+            ciphertextHashHex: 'ef' * 32,
+          );
         },
-        recordPendingMember: (attachmentId, member) async =>
-            pending.add(member),
-        markMemberDelivered: (attachmentId, member) async {},
       );
+      return (await store.readMessages(
+            ownerCidNumber: _ownerCidNumber,
+            currentAccountId: _accountA,
+            conversationId: 'grp:$_cidA:nm',
+          ))
+              .last
+              .plaintext ??
+          '';
+    }
 
-      expect(webrtcTo.toSet(), {_cidB, _cidC}); // 每成员各一次(按成员 CID 路由)
-      expect(relayUploads, 0);
-      expect(pending.toSet(), {_cidB, _cidC});
-    });
-
-    test('>100MB → 中转一次上传,不走 WebRTC', () async {
+    test('≤100MB 普通图片同样 MLS 封装经中转一次上传', () async {
       final store = ChatStore();
       final flow = await buildGroup(store);
-      final webrtcTo = <String>[];
-      var relayUploads = 0;
+      final recipientCounts = <int>[];
 
-      await flow.sendGroupMedia(
-        groupId: 'grp:$_cidA:nm',
-        senderCidNumber: _cidA,
-        senderDeviceId: 'devA',
-        media: _mediaDraft(200 * 1024 * 1024),
-        sendMemberAttachment: ({
-          required recipientCidNumber,
-          required conversationId,
-          required attachmentId,
-          required fileName,
-          required contentType,
-          required sourcePath,
-          required byteSize,
-        }) async {
-          webrtcTo.add(recipientCidNumber);
-        },
-        uploadRelayMedia: ({
-          required conversationId,
-          required attachmentId,
-          required media,
-          int recipientCount = 1,
-        }) async {
-          relayUploads++;
-          return const ChatRelayDescriptor(
-            relayObjectKey: 'chat-relay/x',
-            contentKeyB64: 'a2V5',
-            chunkSize: 1048576,
-            encSize: 200 * 1024 * 1024 + 8192,
-          );
-        },
-        recordPendingMember: (attachmentId, member) async {},
-        markMemberDelivered: (attachmentId, member) async {},
+      final content = ChatPayloadCodec.decode(
+        await sendAndReadPayload(store, flow, 50 * 1024, recipientCounts),
       );
 
-      expect(relayUploads, 1); // 一次上传
-      expect(webrtcTo, isEmpty); // 不走 WebRTC
+      expect(recipientCounts, [2]); // 一次上传,ack 门槛为其余成员数
+      expect(content.isMlsSealed, isTrue);
+      expect(content.relayObjectKey, 'chat-relay/x');
+      expect(content.contentHashHex, 'cd' * 32);
+    });
+
+    test('>100MB → 中转一次上传', () async {
+      final store = ChatStore();
+      final flow = await buildGroup(store);
+      final recipientCounts = <int>[];
+
       final content = ChatPayloadCodec.decode(
-        (await store.readMessages(
-              ownerCidNumber: _ownerCidNumber,
-              currentAccountId: _accountA,
-              conversationId: 'grp:$_cidA:nm',
-            ))
-                .last
-                .plaintext ??
-            '',
+        await sendAndReadPayload(
+          store,
+          flow,
+          200 * 1024 * 1024,
+          recipientCounts,
+        ),
       );
-      expect(content.isRelayMedia, isTrue);
+
+      expect(recipientCounts, [2]);
+      expect(content.isMlsSealed, isTrue);
     });
   });
 }
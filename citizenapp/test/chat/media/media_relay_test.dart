@@ -111,6 +111,31 @@ void main() {
       expect(decoded.byteSize, content.byteSize);
     });
 
+    test('MLS 附件密钥与哈希随载荷保真,无一次性 K', () {
+      final content = ChatContent.media(
+        kind: ChatMessageKind.file,
+        attachmentId: 'att-3',
+        fileName: 'report.pdf',
+        mime: 'application/pdf',
+        byteSize: 300 * 1024 * 1024,
+        relayObjectKey: 'chat-relay/def456',
+        chunkSize: 65536,
+        encSize: 300 * 1024 * 1024 + 4800 * 16,
+        mlsKeyHex: 'ab' * 32,
+        contentHashHex: 'cd' * 32,
+        ciphertextHashHex: 'ef' * 32,
+      );
+      expect(content.isMlsSealed, isTrue);
+
+      final decoded = ChatPayloadCodec.decode(ChatPayloadCodec.encode(content));
+      expect(decoded.isMlsSealed, isTrue);
+      expect(decoded.contentKeyB64, isNull);
+      expect(decoded.mlsKeyHex, 'ab' * 32);
+      expect(decoded.contentHashHex, 'cd' * 32);
+      expect(decoded.ciphertextHashHex, 'ef' * 32);
+      expect(decoded.chunkSize, 65536);
+    });
+
     test('普通(WebRTC)媒体无 relay 字段', () {
       final content = ChatContent.media(
         kind: ChatMessageKind.image,
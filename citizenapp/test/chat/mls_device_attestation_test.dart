@@ -0,0 +1,150 @@
+import 'dart:io';
+import 'dart:typed_data';
+
+import 'package:citizenapp/chat/crypto/mls_boundary.dart';
+import 'package:citizenapp/chat/crypto/mls_group_boundary.dart';
+import 'package:citizenapp/chat/crypto/mls_native.dart';
+import 'package:citizenapp/chat/crypto/mls_state_store.dart';
+import 'package:citizenapp/wallet/core/native_sr25519.dart';
+import 'package:flutter_test/flutter_test.dart';
+
+import '../support/smoldot_native_probe.dart';
+
+/// MLS 本地状态信封测试密钥（固定 32 字节，仅测试用）。
+final Uint8List _testStateKey =
+    Uint8List.fromList(List<int>.generate(32, (i) => i));
+const _aliceCidNumber = 'CN220-CTZN2-100000001-2026';
+const _bobCidNumber = 'CN220-CTZN2-100000002-2026';
+
+/// 测试用 CID 绑定账户：child mini-secret + 由其派生的 AccountId hex。
+class _TestAccount {
+  _TestAccount(int seedByte) : child = List<int>.filled(32, seedByte);
+
+  final List<int> child;
+
+  String get accountIdHex {
+    final hex = NativeSr25519.publicKeyOf(child)
+        .map((b) => b.toRadixString(16).padLeft(2, '0'))
+        .join();
+    return '0x$hex';
+  }
+
+  Future<List<int>> sign(List<int> message) async =>
+      NativeSr25519.sign(child, message);
+}
+
+void main() {
+  // libsmoldot 不可用(纯 Dart CI 无宿主 .so)则跳过;真机/集成构建照跑。
+  final skip = smoldotNativeSkipReason();
+
+  test('设备初始化授权后 KeyPackage 可被加入群，新成员处理 Welcome 入群', () async {
+    final root = await Directory.systemTemp.createTemp('gmb-chat-attest-');
+    addTearDown(() => root.delete(recursive: true));
+    final aliceAccount = _TestAccount(1);
+    final bobAccount = _TestAccount(2);
+    final chain = <String, ChatChainBinding>{
+      _aliceCidNumber: ChatChainBinding(
+        cidNumber: _aliceCidNumber,
+        accountIdHex: aliceAccount.accountIdHex,
+        bindingRevision: 1,
+      ),
+      _bobCidNumber: ChatChainBinding(
+        cidNumber: _bobCidNumber,
+        accountIdHex: bobAccount.accountIdHex,
+        bindingRevision: 1,
+      ),
+    };
+    Future<List<ChatChainBinding>> readChain(List<String> cidNumbers) async =>
+        [for (final cid in cidNumbers) chain[cid]!];
+
+    NativeMlsCrypto device(String cidNumber, String deviceId) =>
+        NativeMlsCrypto(
+          identity: ChatDevice(
+            cidNumber: cidNumber,
+            deviceId: deviceId,
+            devicePublicKey: '00',
+          ),
+          stateStore: MlsStateStore(
+            Directory('${root.path}/$deviceId'),
+            ownerCidNumber: cidNumber,
+            stateKey: _testStateKey,
+          ),
+          chainBindingReader: readChain,
+        );
+
+    final alice = device(_aliceCidNumber, 'alice-phone');
+    final bob = device(_bobCidNumber, 'bob-phone');
+    final expiresAt = DateTime.now().toUtc().add(const Duration(days: 90));
+    await attestMlsDevice(
+      crypto: alice,
+      accountIdHex: aliceAccount.accountIdHex,
+      bindingRevision: 1,
+      expiresAt: expiresAt,
+      nonce: 'nonce-alice',
+      sign: aliceAccount.sign,
+    );
+    final bobPublicKey = await attestMlsDevice(
+      crypto: bob,
+      accountIdHex: bobAccount.accountIdHex,
+      bindingRevision: 1,
+      expiresAt: expiresAt,
+      nonce: 'nonce-bob',
+      sign: bobAccount.sign,
+    );
+
+    final bobKeyPackage = await bob.createKeyPackage(
+      const ChatDevice(
+        cidNumber: _bobCidNumber,
+        deviceId: 'bob-phone',
+        devicePublicKey: '00',
+      ),
+    );
+    expect(bobKeyPackage.devicePublicKey, bobPublicKey);
+
+    const groupId = 'grp:$_aliceCidNumber:attest';
+    await alice.createGroup(groupId);
+    final added = await alice.addMembers(groupId, [bobKeyPackage]);
+    expect(added.welcome, isNotNull);
+
+    final joined = await bob.groupProcess(added.welcome!);
+    expect(joined.kind, GroupInboundKind.welcome);
+    expect(joined.status, GroupProcessStatus.applied);
+    expect(
+      joined.memberIdentities,
+      unorderedEquals([
+        '$_aliceCidNumber:alice-phone',
+        '$_bobCidNumber:bob-phone',
+      ]),
+    );
+    expect(joined.staleMemberIdentities, isEmpty);
+  }, skip: skip);
+
+  test('非 CID 绑定账户签名的设备授权被 native 拒绝安装', () async {
+    final root = await Directory.systemTemp.createTemp('gmb-chat-attest-');
+    addTearDown(() => root.delete(recursive: true));
+    final bob = NativeMlsCrypto(
+      identity: const ChatDevice(
+        cidNumber: _bobCidNumber,
+        deviceId: 'bob-phone',
+        devicePublicKey: '00',
+      ),
+      stateStore: MlsStateStore(
+        Directory('${root.path}/bob-phone'),
+        ownerCidNumber: _bobCidNumber,
+        stateKey: _testStateKey,
+      ),
+    );
+
+    await expectLater(
+      attestMlsDevice(
+        crypto: bob,
+        accountIdHex: _TestAccount(2).accountIdHex,
+        bindingRevision: 1,
+        expiresAt: DateTime.now().toUtc().add(const Duration(days: 90)),
+        nonce: 'nonce-bob',
+        sign: _TestAccount(3).sign,
+      ),
+      throwsA(anything),
+    );
+  }, skip: skip);
+}
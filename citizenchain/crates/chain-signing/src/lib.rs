@@ -34,8 +34,101 @@ pub fn decode_runtime_call(call_data: &[u8]) -> Result<runtime::RuntimeCall, Str
     Ok(call)
 }
 
+/// 冷签交易默认有效期（区块数）。6 分钟出块下约 6.4 小时，足够扫码往返与排队出块。
+pub const DEFAULT_MORTAL_PERIOD: u64 = 64;
+
+/// 有效期上限：不超过 `BLOCK_HASH_COUNT` 的最大 2 的幂，保证出生块哈希仍可在链上查到。
+pub const MAX_MORTAL_PERIOD: u64 = 2048;
+
+/// 交易有效期：永久有效，或自出生块起 `period` 个区块内有效。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxLifetime {
+    Immortal,
+    Mortal {
+        period: u64,
+        birth_block: u64,
+        /// 出生块哈希，进入 `additional_signed` 替代创世哈希。
+        birth_hash: H256,
+    },
+}
+
+impl TxLifetime {
+    /// 以当前最佳块为出生块构建有限有效期；`period` 必须是 4..=2048 的 2 的幂。
+    pub fn mortal(period: u64, current_block: u64, birth_hash: H256) -> Result<Self, String> {
+        if !period.is_power_of_two() || !(4..=MAX_MORTAL_PERIOD).contains(&period) {
+            return Err(format!(
+                "交易有效期 {period} 块非法：必须是 4 到 {MAX_MORTAL_PERIOD} 之间的 2 的幂"
+            ));
+        }
+        // period < 4096 时量化因子为 1，出生块即当前块；仍以 Era 计算为准防止错位。
+        let birth_block = Era::mortal(period, current_block).birth(current_block);
+        if birth_block != current_block {
+            return Err(format!(
+                "交易出生块 {birth_block} 与当前块 {current_block} 不一致"
+            ));
+        }
+        Ok(Self::Mortal {
+            period,
+            birth_block,
+            birth_hash,
+        })
+    }
+
+    /// 写入 `CheckEra` 的 era。
+    pub fn era(&self) -> Era {
+        match self {
+            Self::Immortal => Era::Immortal,
+            Self::Mortal {
+                period,
+                birth_block,
+                ..
+            } => Era::mortal(*period, *birth_block),
+        }
+    }
+
+    /// 出生块号；永久有效交易固定为 0（创世块）。
+    pub fn birth_block(&self) -> u64 {
+        match self {
+            Self::Immortal => 0,
+            Self::Mortal { birth_block, .. } => *birth_block,
+        }
+    }
+
+    /// 首个不再接受该交易的区块号；永久有效交易返回 `None`。
+    pub fn death_block(&self) -> Option<u64> {
+        match self {
+            Self::Immortal => None,
+            Self::Mortal { birth_block, .. } => Some(self.era().death(*birth_block)),
+        }
+    }
+
+    /// `CheckEra` 附加签名数据：mortal 为出生块哈希，immortal 为创世哈希。
+    fn checkpoint_hash(&self, genesis_hash: H256) -> H256 {
+        match self {
+            Self::Immortal => genesis_hash,
+            Self::Mortal { birth_hash, .. } => *birth_hash,
+        }
+    }
+
+    /// 按最佳块判断交易是否仍可打包；下一块号达到失效块即拒绝，避免提交必然被丢弃的交易。
+    pub fn ensure_alive(&self, best_block: u64) -> Result<(), String> {
+        match self.death_block() {
+            Some(death) if best_block.saturating_add(1) >= death => Err(format!(
+                "二维码签名已超过交易有效期（第 {} 块签发，第 {death} 块失效，当前第 {best_block} 块），请重新生成签名请求",
+                self.birth_block()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
 /// 构建 runtime 定义的交易扩展，顺序必须与 `Runtime::TxExtension` 一致。
 pub fn build_tx_extension(nonce: u32) -> runtime::TxExtension {
+    build_tx_extension_with_lifetime(nonce, &TxLifetime::Immortal)
+}
+
+/// 按指定有效期构建交易扩展。
+pub fn build_tx_extension_with_lifetime(nonce: u32, lifetime: &TxLifetime) -> runtime::TxExtension {
     (
         frame_system::AuthorizeCall::<runtime::Runtime>::new(),
         frame_system::CheckNonZeroSender::<runtime::Runtime>::new(),
@@ -43,7 +136,7 @@ pub fn build_tx_extension(nonce: u32) -> runtime::TxExtension {
         frame_system::CheckSpecVersion::<runtime::Runtime>::new(),
         frame_system::CheckTxVersion::<runtime::Runtime>::new(),
         frame_system::CheckGenesis::<runtime::Runtime>::new(),
-        frame_system::CheckEra::<runtime::Runtime>::from(Era::Immortal),
+        frame_system::CheckEra::<runtime::Runtime>::from(lifetime.era()),
         frame_system::CheckNonce::<runtime::Runtime>::from(nonce),
         frame_system::CheckWeight::<runtime::Runtime>::new(),
         // tip 不属于 CitizenChain 五类费用，唯一协议值固定为零。
@@ -62,14 +155,34 @@ pub fn build_signing_material(
     nonce: u32,
     spec_version: u32,
     tx_version: u32,
+) -> Result<SigningMaterial, String> {
+    build_signing_material_with_lifetime(
+        call_data,
+        genesis_hash,
+        nonce,
+        spec_version,
+        tx_version,
+        &TxLifetime::Immortal,
+    )
+}
+
+/// 从 call_data 按指定有效期构建完整签名材料。
+pub fn build_signing_material_with_lifetime(
+    call_data: &[u8],
+    genesis_hash: &[u8; 32],
+    nonce: u32,
+    spec_version: u32,
+    tx_version: u32,
+    lifetime: &TxLifetime,
 ) -> Result<SigningMaterial, String> {
     let call = decode_runtime_call(call_data)?;
-    Ok(build_signing_material_from_call(
+    Ok(build_signing_material_from_call_with_lifetime(
         call,
         H256::from_slice(genesis_hash),
         nonce,
         spec_version,
         tx_version,
+        lifetime,
     ))
 }
 
@@ -81,7 +194,26 @@ pub fn build_signing_material_from_call(
     spec_version: u32,
     tx_version: u32,
 ) -> SigningMaterial {
-    let tx_ext = build_tx_extension(nonce);
+    build_signing_material_from_call_with_lifetime(
+        call,
+        genesis_hash,
+        nonce,
+        spec_version,
+        tx_version,
+        &TxLifetime::Immortal,
+    )
+}
+
+/// 从已解码 call 按指定有效期构建完整签名材料。
+pub fn build_signing_material_from_call_with_lifetime(
+    call: runtime::RuntimeCall,
+    genesis_hash: H256,
+    nonce: u32,
+    spec_version: u32,
+    tx_version: u32,
+    lifetime: &TxLifetime,
+) -> SigningMaterial {
+    let tx_ext = build_tx_extension_with_lifetime(nonce, lifetime);
     let additional_signed = (
         (),
         (),
@@ -89,7 +221,7 @@ pub fn build_signing_material_from_call(
         spec_version,
         tx_version,
         genesis_hash,
-        genesis_hash,
+        lifetime.checkpoint_hash(genesis_hash),
         (),
         (),
         (),
@@ -123,6 +255,26 @@ pub fn build_signing_payloads(
     Ok((material.payload, material.signing_bytes))
 }
 
+/// 按指定有效期构建 QR 冷签审阅 payload 和实际签名字节。
+pub fn build_signing_payloads_with_lifetime(
+    call_data: &[u8],
+    genesis_hash: &[u8; 32],
+    nonce: u32,
+    spec_version: u32,
+    tx_version: u32,
+    lifetime: &TxLifetime,
+) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let material = build_signing_material_with_lifetime(
+        call_data,
+        genesis_hash,
+        nonce,
+        spec_version,
+        tx_version,
+        lifetime,
+    )?;
+    Ok((material.payload, material.signing_bytes))
+}
+
 /// sha256 hex，用于会话校验和日志定位。
 pub fn sha256_hex(data: &[u8]) -> String {
     hex::encode(Sha256::digest(data))
@@ -255,6 +407,9 @@ pub fn dry_run_reject_message(result_bytes: &[u8], raw_hex: &str) -> String {
     if result_bytes.starts_with(&[0x01, 0x00, 0x02]) {
         return "上一笔交易尚未出块，请稍候再试".to_string();
     }
+    if result_bytes.starts_with(&[0x01, 0x00, 0x05]) {
+        return "二维码签名已超过交易有效期，请重新生成签名请求".to_string();
+    }
     let reason = classify_invalid_tx(result_bytes);
     format!("交易校验失败，已拒绝提交: {reason} (hex: {raw_hex})")
 }
@@ -303,6 +458,40 @@ mod tests {
         assert_ne!(big.payload, big.signing_bytes);
     }
 
+    #[test]
+    fn mortal_lifetime_signs_birth_hash_and_era() {
+        let call_data =
+            runtime::RuntimeCall::System(frame_system::Call::remark { remark: vec![1] }).encode();
+        let genesis = [9u8; 32];
+        let birth_hash = H256::repeat_byte(0x42);
+        let lifetime = TxLifetime::mortal(DEFAULT_MORTAL_PERIOD, 1000, birth_hash).expect("era");
+        assert_eq!(lifetime.birth_block(), 1000);
+        assert_eq!(lifetime.death_block(), Some(1000 + DEFAULT_MORTAL_PERIOD));
+
+        let mortal = build_signing_material_with_lifetime(&call_data, &genesis, 5, 1, 1, &lifetime)
+            .expect("material");
+        let immortal = build_signing_material(&call_data, &genesis, 5, 1, 1).expect("material");
+        // 审阅 payload 以 (出生块哈希) 收尾，且 mortal era 占 2 字节。
+        assert!(mortal
+            .payload
+            .ends_with(&[&birth_hash.0[..], &[0u8]].concat()));
+        assert_eq!(mortal.payload.len(), immortal.payload.len() + 1);
+        assert_ne!(mortal.signing_bytes, immortal.signing_bytes);
+    }
+
+    #[test]
+    fn mortal_lifetime_rejects_bad_period_and_expired_response() {
+        let hash = H256::zero();
+        assert!(TxLifetime::mortal(48, 10, hash).is_err());
+        assert!(TxLifetime::mortal(4096, 10, hash).is_err());
+        let lifetime = TxLifetime::mortal(64, 10, hash).expect("era");
+        assert!(lifetime.ensure_alive(72).is_ok());
+        let err = lifetime.ensure_alive(73).expect_err("expired");
+        assert!(err.contains("已超过交易有效期"));
+        assert!(TxLifetime::Immortal.ensure_alive(u64::MAX).is_ok());
+        assert!(dry_run_reject_message(&[0x01, 0x00, 0x05], "0x").contains("有效期"));
+    }
+
     #[test]
     fn tail_data_in_call_is_rejected() {
         let call = runtime::RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
@@ -0,0 +1,434 @@
+//! 独立矿池 worker:连接节点 `--pool-listen` 端口,领取 nonce 区间做纯哈希搜索,
+//! 回传满足份额难度的 nonce。worker 不运行节点、不持有出块密钥。
+//!
+//! 用法:
+//! `citizenchain-pool-worker --server 10.0.0.2:9955 --token-file pool.token --name rig-01`
+
+use clap::Parser;
+use pow_pool::{
+    kernel::{hash_meets_difficulty, pow_hash},
+    protocol::{
+        auth_response, read_message, write_message, Job, ServerMessage, WorkerMessage,
+        PROTOCOL_VERSION,
+    },
+};
+use primitive_types::U256;
+use std::{
+    io::{self, BufReader},
+    net::TcpStream,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// 断线重连间隔。
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// 自报算力(兼心跳)间隔;须小于服务端读超时。
+const STATS_INTERVAL: Duration = Duration::from_secs(30);
+/// CPU 线程每搜索多少个 nonce 检查一次 job 是否已换代。
+const CPU_CHECK_INTERVAL: u64 = 4096;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "citizenchain-pool-worker",
+    about = "CitizenChain 矿池哈希 worker"
+)]
+struct Args {
+    /// 节点矿池服务地址(host:port),对应节点 `--pool-listen`。
+    #[arg(long)]
+    server: String,
+
+    /// 共享口令文件,内容须与节点 `--pool-token-file` 一致。
+    #[arg(long)]
+    token_file: PathBuf,
+
+    /// worker 名称,显示在节点挖矿面板中。
+    #[arg(long)]
+    name: Option<String>,
+
+    /// CPU 搜索线程数;0 表示使用全部逻辑核。
+    #[arg(long, default_value_t = 0)]
+    threads: usize,
+
+    /// 使用 OpenCL GPU 搜索(设备序号);指定后不再启动 CPU 线程。
+    #[cfg(feature = "gpu")]
+    #[arg(long)]
+    gpu_device: Option<usize>,
+}
+
+/// 已解析的当前工作。
+#[derive(Clone)]
+struct ActiveJob {
+    job_id: u64,
+    pre_hash: [u8; 32],
+    share_difficulty: U256,
+    nonce_start: u64,
+    nonce_end: u64,
+}
+
+impl TryFrom<Job> for ActiveJob {
+    type Error = String;
+
+    fn try_from(job: Job) -> Result<Self, String> {
+        if job.nonce_start >= job.nonce_end {
+            return Err("nonce 区间为空".to_string());
+        }
+        Ok(Self {
+            job_id: job.job_id,
+            pre_hash: job.pre_hash_bytes()?,
+            share_difficulty: job.share_difficulty_u256()?,
+            nonce_start: job.nonce_start,
+            nonce_end: job.nonce_end,
+        })
+    }
+}
+
+/// 读线程与搜索线程之间共享的会话状态。
+struct Session {
+    job: Mutex<Option<ActiveJob>>,
+    changed: Condvar,
+    /// 每次收到 Job / Idle 递增;搜索循环据此放弃旧工作。
+    generation: AtomicU64,
+    closed: AtomicBool,
+    hashes: AtomicU64,
+    writer: Mutex<TcpStream>,
+}
+
+impl Session {
+    fn send(&self, message: &WorkerMessage) -> io::Result<()> {
+        let mut writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        write_message(&mut *writer, message)
+    }
+
+    fn set_job(&self, job: Option<ActiveJob>) {
+        let mut guard = self
+            .job
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *guard = job;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.changed.notify_all();
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.set_job(None);
+    }
+
+    fn is_current(&self, generation: u64) -> bool {
+        !self.closed.load(Ordering::Relaxed)
+            && self.generation.load(Ordering::Relaxed) == generation
+    }
+
+    /// 阻塞直到有新一代工作(或会话关闭)。
+    fn wait_for_work(&self, after_generation: u64) -> Option<(u64, ActiveJob)> {
+        let mut guard = self
+            .job
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        loop {
+            if self.closed.load(Ordering::SeqCst) {
+                return None;
+            }
+            let generation = self.generation.load(Ordering::SeqCst);
+            if generation != after_generation {
+                if let Some(job) = guard.as_ref() {
+                    return Some((generation, job.clone()));
+                }
+            }
+            guard = self
+                .changed
+                .wait_timeout(guard, Duration::from_secs(1))
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+        }
+    }
+
+    fn submit_share(&self, job_id: u64, nonce: u64) {
+        if let Err(e) = self.send(&WorkerMessage::Share { job_id, nonce }) {
+            eprintln!("提交份额失败:{e}");
+        }
+    }
+}
+
+enum Searcher {
+    Cpu(usize),
+    #[cfg(feature = "gpu")]
+    Gpu(pow_pool::gpu::GpuSearcher),
+}
+
+impl Searcher {
+    /// 搜索 `job` 的整个区间;返回 `true` 表示区间搜完且工作未换代。
+    fn search(&self, session: &Session, generation: u64, job: &ActiveJob) -> bool {
+        match self {
+            Searcher::Cpu(threads) => search_cpu(session, generation, job, *threads),
+            #[cfg(feature = "gpu")]
+            Searcher::Gpu(gpu) => search_gpu(gpu, session, generation, job),
+        }
+    }
+}
+
+fn search_cpu(session: &Session, generation: u64, job: &ActiveJob, threads: usize) -> bool {
+    let stride = threads as u64;
+    thread::scope(|scope| {
+        for offset in 0..stride {
+            scope.spawn(move || {
+                let mut nonce = job.nonce_start.saturating_add(offset);
+                let mut checked = 0u64;
+                while nonce < job.nonce_end {
+                    let hash = pow_hash(&job.pre_hash, nonce);
+                    if hash_meets_difficulty(&hash, job.share_difficulty) {
+                        session.submit_share(job.job_id, nonce);
+                    }
+                    checked += 1;
+                    if checked == CPU_CHECK_INTERVAL {
+                        session.hashes.fetch_add(checked, Ordering::Relaxed);
+                        checked = 0;
+                        if !session.is_current(generation) {
+                            return;
+                        }
+                    }
+                    nonce = match nonce.checked_add(stride) {
+                        Some(next) => next,
+                        None => break,
+                    };
+                }
+                session.hashes.fetch_add(checked, Ordering::Relaxed);
+            });
+        }
+    });
+    session.is_current(generation)
+}
+
+#[cfg(feature = "gpu")]
+fn search_gpu(
+    gpu: &pow_pool::gpu::GpuSearcher,
+    session: &Session,
+    generation: u64,
+    job: &ActiveJob,
+) -> bool {
+    let target_be = pow_pool::kernel::difficulty_to_target_be(job.share_difficulty);
+    let batch = u64::from(gpu.batch_size());
+    let mut base = job.nonce_start;
+    while base < job.nonce_end {
+        if !session.is_current(generation) {
+            return false;
+        }
+        match gpu.search_batch(&job.pre_hash, &target_be, base) {
+            Ok(Some(nonce)) if nonce >= base && nonce < job.nonce_end => {
+                session
+                    .hashes
+                    .fetch_add(nonce - base + 1, Ordering::Relaxed);
+                session.submit_share(job.job_id, nonce);
+                // kernel 只报告批内第一个解,从下一个 nonce 续搜以免漏掉份额。
+                base = nonce.saturating_add(1);
+            }
+            Ok(_) => {
+                session.hashes.fetch_add(batch, Ordering::Relaxed);
+                base = base.saturating_add(batch);
+            }
+            Err(e) => {
+                eprintln!("GPU 搜索失败:{e}");
+                thread::sleep(RECONNECT_DELAY);
+                return false;
+            }
+        }
+    }
+    session.is_current(generation)
+}
+
+fn spawn_reader(session: Arc<Session>, mut reader: BufReader<TcpStream>) {
+    thread::spawn(move || {
+        loop {
+            match read_message::<_, ServerMessage>(&mut reader) {
+                Ok(Some(ServerMessage::Job(job))) => match ActiveJob::try_from(job) {
+                    Ok(job) => session.set_job(Some(job)),
+                    Err(e) => {
+                        eprintln!("收到无效工作:{e}");
+                        session.set_job(None);
+                    }
+                },
+                Ok(Some(ServerMessage::Idle)) => session.set_job(None),
+                Ok(Some(ServerMessage::ShareAck {
+                    nonce,
+                    accepted,
+                    block,
+                    reason,
+                    ..
+                })) => {
+                    if block {
+                        eprintln!("份额 {nonce:#018x} 满足区块难度,节点已提交出块");
+                    } else if !accepted {
+                        eprintln!(
+                            "份额 {nonce:#018x} 被拒绝:{}",
+                            reason.as_deref().unwrap_or("未知原因")
+                        );
+                    }
+                }
+                Ok(Some(ServerMessage::Error { reason })) => {
+                    eprintln!("服务端报错:{reason}");
+                    break;
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => {
+                    eprintln!("服务端关闭连接");
+                    break;
+                }
+                Err(e) => {
+                    eprintln!("读取服务端消息失败:{e}");
+                    break;
+                }
+            }
+        }
+        session.close();
+    });
+}
+
+fn spawn_stats(session: Arc<Session>) {
+    thread::spawn(move || {
+        let mut last = Instant::now();
+        let mut last_hashes = session.hashes.load(Ordering::Relaxed);
+        while !session.closed.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_secs(1));
+            if last.elapsed() < STATS_INTERVAL {
+                continue;
+            }
+            let hashes = session.hashes.load(Ordering::Relaxed);
+            let hashrate = (hashes - last_hashes) as f64 / last.elapsed().as_secs_f64();
+            last = Instant::now();
+            last_hashes = hashes;
+            eprintln!("算力 {:.2} MH/s", hashrate / 1e6);
+            if session.send(&WorkerMessage::Stats { hashrate }).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// 一次连接会话:握手 → 循环领取并搜索工作,直到连接断开。
+fn run_session(args: &Args, name: &str, token: &str, searcher: &Searcher) -> Result<(), String> {
+    let stream =
+        TcpStream::connect(&args.server).map_err(|e| format!("连接 {} 失败:{e}", args.server))?;
+    let _ = stream.set_nodelay(true);
+    let mut reader = BufReader::new(
+        stream
+            .try_clone()
+            .map_err(|e| format!("复制连接句柄失败:{e}"))?,
+    );
+    let mut writer = stream;
+
+    let challenge = match read_message::<_, ServerMessage>(&mut reader) {
+        Ok(Some(ServerMessage::Challenge {
+            protocol,
+            challenge,
+        })) => {
+            if protocol != PROTOCOL_VERSION {
+                return Err(format!(
+                    "协议版本不一致:服务端 {protocol},本地 {PROTOCOL_VERSION}"
+                ));
+            }
+            hex::decode(challenge).map_err(|e| format!("握手挑战非法:{e}"))?
+        }
+        Ok(other) => return Err(format!("握手失败,收到 {other:?}")),
+        Err(e) => return Err(format!("握手失败:{e}")),
+    };
+    let auth = auth_response(token, &challenge, name);
+    write_message(
+        &mut writer,
+        &WorkerMessage::Hello {
+            protocol: PROTOCOL_VERSION,
+            worker: name.to_string(),
+            auth: hex::encode(auth),
+        },
+    )
+    .map_err(|e| format!("发送握手失败:{e}"))?;
+    match read_message::<_, ServerMessage>(&mut reader) {
+        Ok(Some(ServerMessage::Welcome { worker_id })) => {
+            eprintln!("已连接 {},worker #{worker_id}", args.server);
+        }
+        Ok(Some(ServerMessage::Error { reason })) => return Err(format!("握手被拒绝:{reason}")),
+        Ok(other) => return Err(format!("握手失败,收到 {other:?}")),
+        Err(e) => return Err(format!("握手失败:{e}")),
+    }
+
+    let session = Arc::new(Session {
+        job: Mutex::new(None),
+        changed: Condvar::new(),
+        generation: AtomicU64::new(0),
+        closed: AtomicBool::new(false),
+        hashes: AtomicU64::new(0),
+        writer: Mutex::new(writer),
+    });
+    spawn_reader(session.clone(), reader);
+    spawn_stats(session.clone());
+
+    let mut searched = 0u64;
+    while let Some((generation, job)) = session.wait_for_work(searched) {
+        searched = generation;
+        if searcher.search(&session, generation, &job) {
+            // 区间搜完:申请同一模板的下一段,收到新 Job 后 generation 递增。
+            session
+                .send(&WorkerMessage::MoreWork { job_id: job.job_id })
+                .map_err(|e| format!("申请新区间失败:{e}"))?;
+        }
+    }
+    Ok(())
+}
+
+fn read_token(path: &PathBuf) -> Result<String, String> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|e| format!("读取口令文件 {} 失败:{e}", path.display()))?;
+    let token = raw.trim().to_string();
+    if token.is_empty() {
+        return Err(format!("口令文件 {} 为空", path.display()));
+    }
+    Ok(token)
+}
+
+fn build_searcher(args: &Args) -> Result<Searcher, String> {
+    #[cfg(feature = "gpu")]
+    if let Some(device) = args.gpu_device {
+        return pow_pool::gpu::GpuSearcher::try_init(device).map(Searcher::Gpu);
+    }
+    let threads = match args.threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    Ok(Searcher::Cpu(threads))
+}
+
+fn main() {
+    let args = Args::parse();
+    let token = match read_token(&args.token_file) {
+        Ok(token) => token,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
+    let name = args
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("worker-{:04x}", rand::random::<u16>()));
+    let searcher = match build_searcher(&args) {
+        Ok(searcher) => searcher,
+        Err(e) => {
+            eprintln!("初始化搜索器失败:{e}");
+            std::process::exit(1);
+        }
+    };
+
+    loop {
+        if let Err(e) = run_session(&args, &name, &token, &searcher) {
+            eprintln!("{e}");
+        }
+        thread::sleep(RECONNECT_DELAY);
+    }
+}
@@ -0,0 +1,168 @@
+//! OpenCL blake2b PoW 搜索器。
+//!
+//! 节点本机 GPU 挖矿(`gpu-mining` feature)与独立矿池 worker 共用。kernel 只报告
+//! 批内第一个满足目标的 nonce;调用方按 `found + 1` 续搜即可不漏解。
+
+use ocl::{Buffer, MemFlags, ProQue};
+
+/// OpenCL kernel source embedded at compile time.
+const KERNEL_SRC: &str = include_str!("../kernels/blake2b_pow.cl");
+
+/// Number of nonces to test per GPU batch dispatch.
+/// 2^24 = ~16 million — good balance between GPU utilization and responsiveness.
+const DEFAULT_BATCH_SIZE: u32 = 1 << 24;
+
+/// GPU searcher state holding OpenCL resources.
+pub struct GpuSearcher {
+    pro_que: ProQue,
+    // Persistent GPU buffers (reused across batches).
+    buf_pre_hash: Buffer<u8>,
+    buf_target: Buffer<u64>,
+    buf_result_nonce: Buffer<u64>,
+    buf_found: Buffer<u32>,
+    batch_size: u32,
+}
+
+impl GpuSearcher {
+    /// Try to initialize the searcher on the given device.
+    /// Returns Err if no GPU is available or OpenCL initialization fails.
+    pub fn try_init(device_index: usize) -> Result<Self, String> {
+        let platform = ocl::Platform::default();
+        let devices = ocl::Device::list(platform, Some(ocl::flags::DeviceType::GPU))
+            .map_err(|e| format!("failed to list GPU devices: {e}"))?;
+
+        if devices.is_empty() {
+            return Err("no GPU devices found".into());
+        }
+
+        let device = devices
+            .get(device_index)
+            .ok_or_else(|| {
+                format!(
+                    "GPU device index {} out of range (found {} devices)",
+                    device_index,
+                    devices.len()
+                )
+            })?
+            .clone();
+
+        let device_name = device.name().unwrap_or_else(|_| "unknown".into());
+        log::info!(
+            "Initializing GPU miner on device {}: {}",
+            device_index,
+            device_name
+        );
+
+        let batch_size = DEFAULT_BATCH_SIZE;
+
+        let pro_que = ProQue::builder()
+            .platform(platform)
+            .device(device)
+            .src(KERNEL_SRC)
+            .dims(batch_size as usize)
+            .build()
+            .map_err(|e| format!("failed to build OpenCL program: {e}"))?;
+
+        let buf_pre_hash = Buffer::<u8>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(MemFlags::new().read_only())
+            .len(32)
+            .build()
+            .map_err(|e| format!("failed to create pre_hash buffer: {e}"))?;
+
+        let buf_target = Buffer::<u64>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(MemFlags::new().read_only())
+            .len(4)
+            .build()
+            .map_err(|e| format!("failed to create target buffer: {e}"))?;
+
+        let buf_result_nonce = Buffer::<u64>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(MemFlags::new().write_only())
+            .len(1)
+            .build()
+            .map_err(|e| format!("failed to create result_nonce buffer: {e}"))?;
+
+        let buf_found = Buffer::<u32>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(MemFlags::new().read_write())
+            .len(1)
+            .build()
+            .map_err(|e| format!("failed to create found buffer: {e}"))?;
+
+        Ok(GpuSearcher {
+            pro_que,
+            buf_pre_hash,
+            buf_target,
+            buf_result_nonce,
+            buf_found,
+            batch_size,
+        })
+    }
+
+    /// 每批搜索的 nonce 数。
+    pub fn batch_size(&self) -> u32 {
+        self.batch_size
+    }
+
+    /// Run a single batch of nonce searches on the GPU, covering
+    /// `[nonce_base, nonce_base + batch_size)`.
+    /// Returns Some(nonce) if a valid nonce is found, None otherwise.
+    pub fn search_batch(
+        &self,
+        pre_hash: &[u8],
+        target_be: &[u64; 4],
+        nonce_base: u64,
+    ) -> Result<Option<u64>, String> {
+        // Upload pre_hash and target to GPU.
+        self.buf_pre_hash
+            .write(pre_hash)
+            .enq()
+            .map_err(|e| format!("write pre_hash: {e}"))?;
+        self.buf_target
+            .write(target_be.as_slice())
+            .enq()
+            .map_err(|e| format!("write target: {e}"))?;
+
+        // Reset found flag to 0.
+        self.buf_found
+            .write(&[0u32] as &[u32])
+            .enq()
+            .map_err(|e| format!("reset found: {e}"))?;
+
+        // Build and enqueue the kernel.
+        let kernel = self
+            .pro_que
+            .kernel_builder("blake2b_pow_mine")
+            .arg(&self.buf_pre_hash)
+            .arg(nonce_base)
+            .arg(&self.buf_target)
+            .arg(&self.buf_result_nonce)
+            .arg(&self.buf_found)
+            .build()
+            .map_err(|e| format!("build kernel: {e}"))?;
+
+        unsafe {
+            kernel.enq().map_err(|e| format!("enqueue kernel: {e}"))?;
+        }
+
+        // Read back results.
+        let mut found = [0u32; 1];
+        self.buf_found
+            .read(&mut found as &mut [u32])
+            .enq()
+            .map_err(|e| format!("read found: {e}"))?;
+
+        if found[0] != 0 {
+            let mut result_nonce = [0u64; 1];
+            self.buf_result_nonce
+                .read(&mut result_nonce as &mut [u64])
+                .enq()
+                .map_err(|e| format!("read result_nonce: {e}"))?;
+            Ok(Some(result_nonce[0]))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! PoW 哈希与难度换算。
+//!
+//! 与节点 `core::service::SimplePow` 共识规则一致:
+//! `hash = blake2b-256(pre_hash || nonce.to_le_bytes())`,`hash`(大端)`<= U256::MAX / difficulty`
+//! 即有效。矿池份额沿用同一判定,只是把 `difficulty` 换成更低的份额难度。
+
+use primitive_types::U256;
+
+/// 计算 PoW 哈希(栈上拼接,无堆分配)。
+pub fn pow_hash(pre_hash: &[u8], nonce: u64) -> [u8; 32] {
+    let hash = blake2b_simd::Params::new()
+        .hash_length(32)
+        .to_state()
+        .update(pre_hash)
+        .update(&nonce.to_le_bytes())
+        .finalize();
+    let mut out = [0u8; 32];
+    out.copy_from_slice(hash.as_bytes());
+    out
+}
+
+/// `hash` 是否满足 `difficulty`;难度为零一律不满足。
+pub fn hash_meets_difficulty(hash: &[u8; 32], difficulty: U256) -> bool {
+    if difficulty.is_zero() {
+        return false;
+    }
+    let target = U256::MAX / difficulty;
+    U256::from_big_endian(hash) <= target
+}
+
+/// 难度 → GPU kernel 使用的目标值:`U256::MAX / difficulty`,4 个大端 u64 字。
+pub fn difficulty_to_target_be(difficulty: U256) -> [u64; 4] {
+    if difficulty.is_zero() {
+        return [0u64; 4];
+    }
+    let bytes: [u8; 32] = (U256::MAX / difficulty).to_big_endian();
+    let mut words = [0u64; 4];
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(8)) {
+        let mut be = [0u8; 8];
+        be.copy_from_slice(chunk);
+        *word = u64::from_be_bytes(be);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pow_hash_matches_one_shot_blake2b() {
+        let pre_hash = [7u8; 32];
+        let nonce = 123u64;
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&pre_hash);
+        payload.extend_from_slice(&nonce.to_le_bytes());
+        let expected = blake2b_simd::Params::new().hash_length(32).hash(&payload);
+        assert_eq!(&pow_hash(&pre_hash, nonce)[..], expected.as_bytes());
+    }
+
+    #[test]
+    fn difficulty_boundary() {
+        assert!(!hash_meets_difficulty(&[0u8; 32], U256::zero()));
+        assert!(hash_meets_difficulty(&[0xFF; 32], U256::one()));
+        let difficulty = U256::from(2);
+        let at_target: [u8; 32] = (U256::MAX / difficulty).to_big_endian();
+        assert!(hash_meets_difficulty(&at_target, difficulty));
+        let above: [u8; 32] = (U256::MAX / difficulty + U256::one()).to_big_endian();
+        assert!(!hash_meets_difficulty(&above, difficulty));
+    }
+
+    #[test]
+    fn difficulty_to_target_be_edge_cases() {
+        assert_eq!(difficulty_to_target_be(U256::zero()), [0u64; 4]);
+        assert_eq!(difficulty_to_target_be(U256::one()), [u64::MAX; 4]);
+    }
+
+    #[test]
+    fn difficulty_to_target_be_roundtrip() {
+        let difficulty = U256::from(1000);
+        let words = difficulty_to_target_be(difficulty);
+        let mut reconstructed = [0u8; 32];
+        for (i, word) in words.iter().enumerate() {
+            reconstructed[i * 8..(i + 1) * 8].copy_from_slice(&word.to_be_bytes());
+        }
+        assert_eq!(
+            U256::from_big_endian(&reconstructed),
+            U256::MAX / difficulty
+        );
+    }
+}
@@ -0,0 +1,13 @@
+//! 矿池挖矿真源包。
+//!
+//! 节点进程与独立 worker 共用本 crate,保证两端对"什么算有效工作"的判定一致:
+//! - `kernel`:PoW 哈希 `blake2_256(pre_hash || nonce_le)` 与难度/目标换算,
+//!   与节点 `SimplePow::verify` 逐字节对齐;
+//! - `protocol`:节点内矿池服务与远程 worker 之间的 JSON 行协议;
+//! - `gpu`(`gpu` feature):OpenCL blake2b kernel 的批量搜索器。
+
+pub mod kernel;
+pub mod protocol;
+
+#[cfg(feature = "gpu")]
+pub mod gpu;
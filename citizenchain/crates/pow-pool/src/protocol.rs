@@ -0,0 +1,236 @@
+//! 节点矿池服务 ↔ 远程 worker 的 JSON 行协议(每行一条消息,`\n` 结尾)。
+//!
+//! 握手:
+//! 1. 服务端连上即发 `Challenge { protocol, challenge }`;
+//! 2. worker 回 `Hello { protocol, worker, auth }`,
+//!    `auth = blake2b-256(key = blake2b-256(token), "citizenchain-pool/auth" || challenge || worker)`,
+//!    共享口令本身不上线;
+//! 3. 校验通过回 `Welcome`,随后推送 `Job` / `Idle`。
+//!
+//! 工作分配:`Job` = 模板(pre_hash + 区块难度 + 份额难度)+ 一段独占 nonce 区间
+//! `[nonce_start, nonce_end)`。区间搜完发 `MoreWork` 领下一段;模板更新时服务端
+//! 主动推新 `Job`,旧 job 上的份额记为过期。
+//!
+//! 份额:满足份额难度的 nonce 用 `Share` 提交。服务端按同一 `kernel` 重算哈希,
+//! 满足区块难度时由节点用自己的 powr 密钥签名出块,worker 不持有任何密钥。
+
+use primitive_types::U256;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::io::{self, BufRead, Read, Write};
+
+/// 协议版本;不一致时服务端拒绝握手。
+pub const PROTOCOL_VERSION: u32 = 1;
+/// 单行消息上限,防止对端用超长行耗尽内存。
+pub const MAX_LINE_BYTES: u64 = 16 * 1024;
+/// 每次分配的 nonce 区间长度。
+pub const NONCE_RANGE_SIZE: u64 = 1 << 32;
+
+const AUTH_DOMAIN: &[u8] = b"citizenchain-pool/auth";
+
+/// 服务端 → worker。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// 握手挑战(32 字节 hex)。
+    Challenge { protocol: u32, challenge: String },
+    /// 握手成功。
+    Welcome { worker_id: u64 },
+    /// 新工作或同一模板的下一段 nonce 区间。
+    Job(Job),
+    /// 节点暂不出块(交易池为空 / 同步中),worker 应停止搜索。
+    Idle,
+    /// 份额处理结果。
+    ShareAck {
+        job_id: u64,
+        nonce: u64,
+        accepted: bool,
+        /// 该份额同时满足区块难度并已提交出块。
+        block: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// 协议错误,服务端随后断开。
+    Error { reason: String },
+}
+
+/// worker → 服务端。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorkerMessage {
+    Hello {
+        protocol: u32,
+        worker: String,
+        auth: String,
+    },
+    Share {
+        job_id: u64,
+        nonce: u64,
+    },
+    /// 当前区间已搜完,申请同一 job 的下一段。
+    MoreWork {
+        job_id: u64,
+    },
+    /// 自报算力(hashes/sec),同时充当心跳。
+    Stats {
+        hashrate: f64,
+    },
+}
+
+/// 工作模板 + 独占 nonce 区间。难度以十进制字符串传输,避免 JSON 数字精度问题。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    pub job_id: u64,
+    /// 32 字节 pre_hash,hex(不带 0x)。
+    pub pre_hash: String,
+    pub difficulty: String,
+    pub share_difficulty: String,
+    pub nonce_start: u64,
+    pub nonce_end: u64,
+}
+
+impl Job {
+    pub fn pre_hash_bytes(&self) -> Result<[u8; 32], String> {
+        let raw = hex::decode(&self.pre_hash).map_err(|e| format!("pre_hash 非法 hex:{e}"))?;
+        raw.try_into()
+            .map_err(|_| "pre_hash 长度必须为 32 字节".to_string())
+    }
+
+    pub fn difficulty_u256(&self) -> Result<U256, String> {
+        parse_difficulty(&self.difficulty)
+    }
+
+    pub fn share_difficulty_u256(&self) -> Result<U256, String> {
+        parse_difficulty(&self.share_difficulty)
+    }
+}
+
+pub fn parse_difficulty(raw: &str) -> Result<U256, String> {
+    let value = U256::from_dec_str(raw).map_err(|_| format!("难度非法:{raw}"))?;
+    if value.is_zero() {
+        return Err("难度不能为零".to_string());
+    }
+    Ok(value)
+}
+
+/// 握手应答:`blake2b-256(key = blake2b-256(token), domain || challenge || worker)`。
+pub fn auth_response(token: &str, challenge: &[u8], worker: &str) -> [u8; 32] {
+    let key = blake2b_simd::Params::new()
+        .hash_length(32)
+        .hash(token.as_bytes());
+    let mac = blake2b_simd::Params::new()
+        .hash_length(32)
+        .key(key.as_bytes())
+        .to_state()
+        .update(AUTH_DOMAIN)
+        .update(challenge)
+        .update(worker.as_bytes())
+        .finalize();
+    let mut out = [0u8; 32];
+    out.copy_from_slice(mac.as_bytes());
+    out
+}
+
+/// 常量时间比较握手应答。
+pub fn auth_matches(expected: &[u8; 32], received_hex: &str) -> bool {
+    let Ok(received) = hex::decode(received_hex) else {
+        return false;
+    };
+    if received.len() != expected.len() {
+        return false;
+    }
+    expected
+        .iter()
+        .zip(received.iter())
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+        == 0
+}
+
+/// 写一条消息并 flush。
+pub fn write_message<W: Write, T: Serialize>(writer: &mut W, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    writer.write_all(&line)?;
+    writer.flush()
+}
+
+/// 读一条消息;对端正常关闭返回 `Ok(None)`。
+pub fn read_message<R: BufRead, T: DeserializeOwned>(reader: &mut R) -> io::Result<Option<T>> {
+    let mut line = Vec::new();
+    let read = reader
+        .by_ref()
+        .take(MAX_LINE_BYTES)
+        .read_until(b'\n', &mut line)?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if line.last() != Some(&b'\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "消息超长或连接中途断开",
+        ));
+    }
+    serde_json::from_slice(&line)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn messages_roundtrip_as_tagged_json_lines() {
+        let job = ServerMessage::Job(Job {
+            job_id: 7,
+            pre_hash: "11".repeat(32),
+            difficulty: "1000000".to_string(),
+            share_difficulty: "15625".to_string(),
+            nonce_start: 1 << 62,
+            nonce_end: (1 << 62) + NONCE_RANGE_SIZE,
+        });
+        let mut buf = Vec::new();
+        write_message(&mut buf, &job).unwrap();
+        write_message(&mut buf, &ServerMessage::Idle).unwrap();
+        assert!(String::from_utf8_lossy(&buf).starts_with("{\"type\":\"job\""));
+
+        let mut reader = Cursor::new(buf);
+        let decoded: ServerMessage = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(decoded, job);
+        let idle: ServerMessage = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(idle, ServerMessage::Idle);
+        assert!(read_message::<_, ServerMessage>(&mut reader)
+            .unwrap()
+            .is_none());
+
+        let ServerMessage::Job(job) = decoded else {
+            unreachable!()
+        };
+        assert_eq!(job.pre_hash_bytes().unwrap(), [0x11; 32]);
+        assert_eq!(job.share_difficulty_u256().unwrap(), U256::from(15625));
+    }
+
+    #[test]
+    fn oversized_line_is_rejected() {
+        let mut reader = Cursor::new(vec![b'a'; MAX_LINE_BYTES as usize + 10]);
+        assert!(read_message::<_, WorkerMessage>(&mut reader).is_err());
+    }
+
+    #[test]
+    fn auth_binds_token_challenge_and_worker_name() {
+        let expected = auth_response("team-secret", &[1u8; 32], "rig-01");
+        assert!(auth_matches(&expected, &hex::encode(expected)));
+        let other_worker = auth_response("team-secret", &[1u8; 32], "rig-02");
+        assert!(!auth_matches(&expected, &hex::encode(other_worker)));
+        let other_token = auth_response("wrong", &[1u8; 32], "rig-01");
+        assert!(!auth_matches(&expected, &hex::encode(other_token)));
+        assert!(!auth_matches(&expected, "zz"));
+    }
+
+    #[test]
+    fn zero_difficulty_is_invalid() {
+        assert!(parse_difficulty("0").is_err());
+        assert!(parse_difficulty("abc").is_err());
+        assert_eq!(parse_difficulty("42").unwrap(), U256::from(42));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::registry::{actions, fields, reject_reasons, RegistryError};
+use crate::registry::{actions, fields, pinned_calls, reject_reasons, RegistryError};
 
 /// 导出 registry JSON，供后续生成 Dart/TypeScript 产物。
 ///
@@ -9,6 +9,7 @@ pub fn export_registry_json() -> Result<String, RegistryError> {
         "actions": actions()?,
         "fields": fields()?,
         "reject_reasons": reject_reasons()?,
+        "pinned_calls": pinned_calls()?,
     });
     Ok(serde_json::to_string_pretty(&value)?)
 }
@@ -27,6 +28,15 @@ pub fn export_registry_dart() -> Result<String, RegistryError> {
     let mut reject_reasons = reject_reasons()?;
     reject_reasons.sort_by(|left, right| left.reject_reason_key.cmp(&right.reject_reason_key));
 
+    let mut pinned_calls = pinned_calls()?;
+    pinned_calls.sort_by_key(|entry| {
+        (
+            entry.spec_version,
+            entry.descriptor.pallet_index,
+            entry.descriptor.call_index,
+        )
+    });
+
     let mut out = String::new();
     out.push_str("// 本文件由 citizenchain/crates/qr-protocol 生成，禁止手改。\n");
     out.push_str(
@@ -105,6 +115,20 @@ pub fn export_registry_dart() -> Result<String, RegistryError> {
     }
     out.push_str("  };\n\n");
 
+    // 键为 `spec_version:pallet_index:call_index`,值为可信调用描述 JSON。
+    out.push_str("  static const Map<String, String> pinnedCallDescriptorJsonByKey = {\n");
+    for entry in &pinned_calls {
+        out.push_str(&format!(
+            "    {}: {},\n",
+            dart_string(&format!(
+                "{}:{}:{}",
+                entry.spec_version, entry.descriptor.pallet_index, entry.descriptor.call_index
+            )),
+            dart_string(&serde_json::to_string(&entry.descriptor)?)
+        ));
+    }
+    out.push_str("  };\n\n");
+
     out.push_str("  static String? actionKeyForCode(int actionCode) =>\n");
     out.push_str("      actionKeyByCode[actionCode];\n\n");
     out.push_str(
@@ -135,7 +159,13 @@ pub fn export_registry_dart() -> Result<String, RegistryError> {
     out.push_str("  static String? rejectReasonForKey(String reasonKey) =>\n");
     out.push_str("      rejectReasonZhByKey[reasonKey];\n\n");
     out.push_str("  static bool isHashOnlyAction(int actionCode) =>\n");
-    out.push_str("      hashOnlyActionCodes.contains(actionCode);\n");
+    out.push_str("      hashOnlyActionCodes.contains(actionCode);\n\n");
+    out.push_str("  static String? pinnedCallDescriptorJson(\n");
+    out.push_str("    int specVersion,\n");
+    out.push_str("    int palletIndex,\n");
+    out.push_str("    int callIndex,\n");
+    out.push_str("  ) =>\n");
+    out.push_str("      pinnedCallDescriptorJsonByKey['$specVersion:$palletIndex:$callIndex'];\n");
     out.push_str("}\n");
 
     Ok(out)
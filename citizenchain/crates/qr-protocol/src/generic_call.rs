@@ -0,0 +1,1005 @@
+//! 通用链调用(compose_call)描述与编解码唯一真源。
+//!
+//! 专用 decoder 只覆盖 registry 已登记的业务动作。高级用户在节点桌面端按 runtime 元数据
+//! 自行组装任意调用时，生成端把本文件的 [`CallDescriptor`] 随签名请求一起放进 QR `b.c`，
+//! 签名端据此逐字节严格解码 call_data 并中文展示。描述由生成端提供，所以解码必须做到:
+//! 前两字节必须等于描述中的 pallet/call 索引、每个参数完整消费、不接受任何夹带字节；
+//! 已有专用 decoder 的调用一律拒绝走通用路径，防止绕开专用语义校验。
+//!
+//! 描述里的名称与参数类型是生成端自报的，签名端只信 `registry/pinned_calls.yaml` 按
+//! spec_version 登记的可信描述：命中且一致才展示"已核验",登记但不一致直接拒绝；
+//! 未登记时名称标注未核验，并把原始索引与 call_data 十六进制放在确认页显著位置。
+
+use crate::decision::SignDisplayField;
+use crate::registry::{
+    action_by_code, field_label_zh, pinned_call, ActionEntry, ActionKind, RegistryError,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 通用调用 action_key,对应 registry 中的保留动作码。
+pub const COMPOSE_CALL_ACTION_KEY: &str = "compose_call";
+/// 参数类型允许的最大嵌套深度；更深的类型(含递归 `RuntimeCall`)不提供通用组装。
+pub const MAX_ARG_DEPTH: usize = 8;
+/// 单个 `Vec` 参数允许的最大元素数，防止零长元素类型让解码陷入超长循环。
+pub const MAX_VEC_ITEMS: usize = 4096;
+/// SigningPayload 扩展尾固定段：spec_version(4) + tx_version(4) + genesis_hash(32)
+/// + birth_hash(32) + CheckMetadataHash None(1)。
+const SIGNING_TAIL_FIXED_LEN: usize = 73;
+
+/// 通用调用编解码失败原因。
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GenericCallError {
+    #[error("调用描述无效: {0}")]
+    InvalidDescriptor(String),
+    #[error("调用 {0} 已有专用签名动作 {1},不能走通用组装")]
+    DedicatedActionExists(String, String),
+    #[error("参数 {path} 无效: {reason}")]
+    InvalidArgument { path: String, reason: String },
+    #[error("call_data 与描述的 pallet/call 索引不一致")]
+    IndexMismatch,
+    #[error("调用描述与 spec_version {0} 登记的可信描述不一致")]
+    PinnedMismatch(u32),
+    #[error("call_data 在 {0} 处提前结束")]
+    UnexpectedEnd(String),
+    #[error("call_data 在 {path} 处不可解码: {reason}")]
+    Malformed { path: String, reason: String },
+    #[error("{0}")]
+    Registry(String),
+}
+
+impl From<RegistryError> for GenericCallError {
+    fn from(error: RegistryError) -> Self {
+        Self::Registry(error.to_string())
+    }
+}
+
+/// 一个可组装调用的完整描述。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallDescriptor {
+    pub pallet_index: u8,
+    pub pallet_name: String,
+    pub call_index: u8,
+    pub call_name: String,
+    pub args: Vec<ArgDescriptor>,
+}
+
+/// 具名参数或结构体字段；元组结构体字段的 `name` 为空串。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArgDescriptor {
+    pub name: String,
+    pub ty: ArgType,
+}
+
+/// 枚举分支描述，`index` 是 SCALE 判别字节。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariantDescriptor {
+    pub index: u8,
+    pub name: String,
+    pub fields: Vec<ArgDescriptor>,
+}
+
+/// 通用组装支持的 SCALE 类型子集。
+///
+/// `Bytes` 覆盖 `Vec<u8>`/`BoundedVec<u8, _>`/`str`;`FixedBytes` 覆盖 `[u8; N]`;
+/// `Compact` 覆盖任意无符号 `Compact<T>`。不在此列的类型使整个调用不可组装。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArgType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Compact,
+    Bytes,
+    FixedBytes(u32),
+    AccountId,
+    Option(Box<ArgType>),
+    Vec(Box<ArgType>),
+    Tuple(Vec<ArgType>),
+    Composite(Vec<ArgDescriptor>),
+    Variant(Vec<VariantDescriptor>),
+}
+
+/// 调用描述与可信登记表的核对结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinStatus {
+    /// 与该 spec_version 登记的描述逐项一致，名称可信。
+    Verified { spec_version: u32 },
+    /// 该 spec_version 未登记此调用，名称与参数类型只是生成端自报。
+    Unpinned { spec_version: u32 },
+}
+
+/// 从完整 SigningPayload 扩展尾读取 spec_version;长度不足时返回 None。
+pub fn signing_spec_version(payload: &[u8]) -> Option<u32> {
+    let start = payload.len().checked_sub(SIGNING_TAIL_FIXED_LEN)?;
+    let raw: [u8; 4] = payload.get(start..start + 4)?.try_into().ok()?;
+    Some(u32::from_le_bytes(raw))
+}
+
+/// 严格解码后的参数展示值与 call_data 字节长度。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedCall {
+    pub args: Vec<(String, String)>,
+    pub call_len: usize,
+}
+
+impl CallDescriptor {
+    /// 与专用链交易相同的动作码口径 `(pallet_index << 8) | call_index`。
+    pub fn chain_action_code(&self) -> u16 {
+        (u16::from(self.pallet_index) << 8) | u16::from(self.call_index)
+    }
+
+    /// 校验描述自身：名称非空、参数名唯一、嵌套深度与枚举判别字节合法。
+    pub fn validate(&self) -> Result<(), GenericCallError> {
+        if self.pallet_name.trim().is_empty() || self.call_name.trim().is_empty() {
+            return Err(GenericCallError::InvalidDescriptor(
+                "pallet/call 名称不能为空".to_string(),
+            ));
+        }
+        validate_fields(&self.args, 0, true)
+    }
+
+    /// 已登记专用 decoder 的链交易动作；存在时通用路径必须拒绝。
+    pub fn dedicated_action(&self) -> Option<ActionEntry> {
+        action_by_code(self.chain_action_code())
+            .ok()
+            .filter(|action| action.kind == ActionKind::ChainCall)
+    }
+
+    fn ensure_generic(&self) -> Result<(), GenericCallError> {
+        self.validate()?;
+        if let Some(action) = self.dedicated_action() {
+            return Err(GenericCallError::DedicatedActionExists(
+                format!("{}::{}", self.pallet_name, self.call_name),
+                action.action_key,
+            ));
+        }
+        Ok(())
+    }
+
+    /// 与 `registry/pinned_calls.yaml` 核对；登记了同索引但描述不一致时拒绝。
+    pub fn pin_status(&self, spec_version: u32) -> Result<PinStatus, GenericCallError> {
+        let pinned = pinned_call(spec_version, self.pallet_index, self.call_index)?;
+        self.compare_pinned(spec_version, pinned.as_ref())
+    }
+
+    fn compare_pinned(
+        &self,
+        spec_version: u32,
+        pinned: Option<&CallDescriptor>,
+    ) -> Result<PinStatus, GenericCallError> {
+        match pinned {
+            Some(pinned) if pinned == self => Ok(PinStatus::Verified { spec_version }),
+            Some(_) => Err(GenericCallError::PinnedMismatch(spec_version)),
+            None => Ok(PinStatus::Unpinned { spec_version }),
+        }
+    }
+
+    /// 按描述把 JSON 参数对象编码成完整 call_data。
+    ///
+    /// 账户参数交给 `parse_account` 解析，生成端可同时接受 SS58 与 `0x` 公钥文本。
+    pub fn encode_call(
+        &self,
+        args: &Value,
+        parse_account: &dyn Fn(&str) -> Result<[u8; 32], String>,
+    ) -> Result<Vec<u8>, GenericCallError> {
+        self.ensure_generic()?;
+        let mut out = vec![self.pallet_index, self.call_index];
+        encode_fields(&self.args, args, "", parse_account, &mut out)?;
+        Ok(out)
+    }
+
+    /// 从完整 review_payload 头部严格解码 call_data,返回参数展示值与 call 长度。
+    pub fn decode_call(&self, bytes: &[u8]) -> Result<DecodedCall, GenericCallError> {
+        self.ensure_generic()?;
+        if bytes.len() < 2 {
+            return Err(GenericCallError::UnexpectedEnd(
+                "pallet/call 索引".to_string(),
+            ));
+        }
+        if bytes[0] != self.pallet_index || bytes[1] != self.call_index {
+            return Err(GenericCallError::IndexMismatch);
+        }
+        let mut reader = Reader { bytes, offset: 2 };
+        let mut args = Vec::with_capacity(self.args.len());
+        for arg in &self.args {
+            let value = decode_value(&arg.ty, &mut reader, &arg.name)?;
+            args.push((arg.name.clone(), value));
+        }
+        Ok(DecodedCall {
+            args,
+            call_len: reader.offset,
+        })
+    }
+
+    /// 生成签名端确认页字段：模块、调用、逐行参数、核验结论与原始 call_data,
+    /// 字段中文名全部来自 registry。未核验时名称只作参考，索引放在最前。
+    pub fn review_fields(
+        &self,
+        payload: &[u8],
+        decoded: &DecodedCall,
+        pin: PinStatus,
+    ) -> Result<Vec<SignDisplayField>, GenericCallError> {
+        let call_data = payload
+            .get(..decoded.call_len)
+            .ok_or_else(|| GenericCallError::UnexpectedEnd("call_data".to_string()))?;
+        let call_args = decoded
+            .args
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let (pallet_name, call_name, verification) = match pin {
+            PinStatus::Verified { spec_version } => (
+                format!("{}({})", self.pallet_name, self.pallet_index),
+                format!("{}({})", self.call_name, self.call_index),
+                format!("已核验：与 spec_version {spec_version} 登记的调用描述一致"),
+            ),
+            PinStatus::Unpinned { spec_version } => (
+                format!(
+                    "#{}(自报名称 {},未核验)",
+                    self.pallet_index, self.pallet_name
+                ),
+                format!("#{}(自报名称 {},未核验)", self.call_index, self.call_name),
+                format!(
+                    "未核验：spec_version {spec_version} 未登记该调用，名称与参数类型由请求方自报，\
+                     请以索引和原始调用数据为准"
+                ),
+            ),
+        };
+        [
+            ("call_verification", verification),
+            ("pallet_name", pallet_name),
+            ("call_name", call_name),
+            ("call_args", call_args),
+            ("call_data_hex", format!("0x{}", hex::encode(call_data))),
+        ]
+        .into_iter()
+        .map(|(field_key, field_value_zh)| {
+            Ok(SignDisplayField {
+                field_key: field_key.to_string(),
+                field_label_zh: field_label_zh(field_key)?,
+                field_value_zh,
+            })
+        })
+        .collect()
+    }
+}
+
+fn validate_fields(
+    fields: &[ArgDescriptor],
+    depth: usize,
+    require_names: bool,
+) -> Result<(), GenericCallError> {
+    let mut names = std::collections::BTreeSet::new();
+    for field in fields {
+        if require_names && field.name.trim().is_empty() {
+            return Err(GenericCallError::InvalidDescriptor(
+                "调用参数必须具名".to_string(),
+            ));
+        }
+        if !field.name.is_empty() && !names.insert(field.name.as_str()) {
+            return Err(GenericCallError::InvalidDescriptor(format!(
+                "字段名重复: {}",
+                field.name
+            )));
+        }
+        validate_type(&field.ty, depth + 1)?;
+    }
+    Ok(())
+}
+
+fn validate_type(ty: &ArgType, depth: usize) -> Result<(), GenericCallError> {
+    if depth > MAX_ARG_DEPTH {
+        return Err(GenericCallError::InvalidDescriptor(format!(
+            "参数嵌套超过 {MAX_ARG_DEPTH} 层"
+        )));
+    }
+    match ty {
+        ArgType::Option(inner) | ArgType::Vec(inner) => validate_type(inner, depth + 1),
+        ArgType::Tuple(items) => items
+            .iter()
+            .try_for_each(|item| validate_type(item, depth + 1)),
+        ArgType::Composite(fields) => validate_fields(fields, depth, false),
+        ArgType::Variant(variants) => {
+            let mut indices = std::collections::BTreeSet::new();
+            for variant in variants {
+                if variant.name.trim().is_empty() || !indices.insert(variant.index) {
+                    return Err(GenericCallError::InvalidDescriptor(
+                        "枚举分支名称为空或判别字节重复".to_string(),
+                    ));
+                }
+                validate_fields(&variant.fields, depth, false)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn child_path(parent: &str, name: &str) -> String {
+    match (parent.is_empty(), name.is_empty()) {
+        (true, _) => name.to_string(),
+        (false, true) => parent.to_string(),
+        (false, false) => format!("{parent}.{name}"),
+    }
+}
+
+fn invalid(path: &str, reason: impl Into<String>) -> GenericCallError {
+    GenericCallError::InvalidArgument {
+        path: path.to_string(),
+        reason: reason.into(),
+    }
+}
+
+/// 具名字段取 JSON 对象同名键；全部无名字段取 JSON 数组；键集必须精确一致。
+fn encode_fields(
+    fields: &[ArgDescriptor],
+    value: &Value,
+    path: &str,
+    parse_account: &dyn Fn(&str) -> Result<[u8; 32], String>,
+    out: &mut Vec<u8>,
+) -> Result<(), GenericCallError> {
+    let named = fields.iter().all(|field| !field.name.is_empty());
+    if named {
+        let object = match value {
+            Value::Object(object) => object,
+            Value::Null if fields.is_empty() => return Ok(()),
+            _ => return Err(invalid(path, "必须是 JSON 对象")),
+        };
+        if object.len() != fields.len() {
+            return Err(invalid(path, "字段数量与调用描述不一致"));
+        }
+        for field in fields {
+            let field_path = child_path(path, &field.name);
+            let field_value = object
+                .get(&field.name)
+                .ok_or_else(|| invalid(&field_path, "缺少该字段"))?;
+            encode_value(&field.ty, field_value, &field_path, parse_account, out)?;
+        }
+        return Ok(());
+    }
+    if fields.len() == 1 {
+        return encode_value(&fields[0].ty, value, path, parse_account, out);
+    }
+    let items = value
+        .as_array()
+        .filter(|items| items.len() == fields.len())
+        .ok_or_else(|| invalid(path, format!("必须是 {} 项 JSON 数组", fields.len())))?;
+    for (index, (field, item)) in fields.iter().zip(items).enumerate() {
+        let item_path = child_path(path, &index.to_string());
+        encode_value(&field.ty, item, &item_path, parse_account, out)?;
+    }
+    Ok(())
+}
+
+fn encode_value(
+    ty: &ArgType,
+    value: &Value,
+    path: &str,
+    parse_account: &dyn Fn(&str) -> Result<[u8; 32], String>,
+    out: &mut Vec<u8>,
+) -> Result<(), GenericCallError> {
+    match ty {
+        ArgType::Bool => {
+            let flag = value
+                .as_bool()
+                .ok_or_else(|| invalid(path, "必须是布尔值"))?;
+            out.push(u8::from(flag));
+        }
+        ArgType::U8 => out.push(parse_uint(value, path, u8::MAX.into())? as u8),
+        ArgType::U16 => {
+            out.extend_from_slice(&(parse_uint(value, path, u16::MAX.into())? as u16).to_le_bytes())
+        }
+        ArgType::U32 => {
+            out.extend_from_slice(&(parse_uint(value, path, u32::MAX.into())? as u32).to_le_bytes())
+        }
+        ArgType::U64 => {
+            out.extend_from_slice(&(parse_uint(value, path, u64::MAX.into())? as u64).to_le_bytes())
+        }
+        ArgType::U128 => out.extend_from_slice(&parse_uint(value, path, u128::MAX)?.to_le_bytes()),
+        ArgType::Compact => encode_compact(parse_uint(value, path, u128::MAX)?, out),
+        ArgType::Bytes => {
+            let text = value
+                .as_str()
+                .ok_or_else(|| invalid(path, "必须是字符串"))?;
+            // `0x` 前缀按十六进制字节解析，其余按 UTF-8 文本原样编码。
+            let bytes = match text.strip_prefix("0x") {
+                Some(hex_text) => {
+                    hex::decode(hex_text).map_err(|_| invalid(path, "0x 后必须是十六进制"))?
+                }
+                None => text.as_bytes().to_vec(),
+            };
+            encode_compact(bytes.len() as u128, out);
+            out.extend_from_slice(&bytes);
+        }
+        ArgType::FixedBytes(len) => {
+            let bytes = value
+                .as_str()
+                .and_then(|text| text.strip_prefix("0x"))
+                .and_then(|hex_text| hex::decode(hex_text).ok())
+                .filter(|bytes| bytes.len() == *len as usize)
+                .ok_or_else(|| invalid(path, format!("必须是 0x + {len} 字节十六进制")))?;
+            out.extend_from_slice(&bytes);
+        }
+        ArgType::AccountId => {
+            let text = value
+                .as_str()
+                .ok_or_else(|| invalid(path, "必须是账户字符串"))?;
+            out.extend_from_slice(&parse_account(text).map_err(|reason| invalid(path, reason))?);
+        }
+        ArgType::Option(inner) => {
+            if value.is_null() {
+                out.push(0);
+            } else {
+                out.push(1);
+                encode_value(inner, value, path, parse_account, out)?;
+            }
+        }
+        ArgType::Vec(inner) => {
+            let items = value
+                .as_array()
+                .ok_or_else(|| invalid(path, "必须是 JSON 数组"))?;
+            if items.len() > MAX_VEC_ITEMS {
+                return Err(invalid(path, format!("元素不能超过 {MAX_VEC_ITEMS} 个")));
+            }
+            encode_compact(items.len() as u128, out);
+            for (index, item) in items.iter().enumerate() {
+                encode_value(
+                    inner,
+                    item,
+                    &child_path(path, &index.to_string()),
+                    parse_account,
+                    out,
+                )?;
+            }
+        }
+        ArgType::Tuple(types) => {
+            let items = value
+                .as_array()
+                .filter(|items| items.len() == types.len())
+                .ok_or_else(|| invalid(path, format!("必须是 {} 项 JSON 数组", types.len())))?;
+            for (index, (item_ty, item)) in types.iter().zip(items).enumerate() {
+                encode_value(
+                    item_ty,
+                    item,
+                    &child_path(path, &index.to_string()),
+                    parse_account,
+                    out,
+                )?;
+            }
+        }
+        ArgType::Composite(fields) => encode_fields(fields, value, path, parse_account, out)?,
+        ArgType::Variant(variants) => {
+            // 无字段分支可直接写分支名；带字段分支写成 `{ "分支名": 字段 }`。
+            let (name, fields_value) = match value {
+                Value::String(name) => (name.as_str(), &Value::Null),
+                Value::Object(object) if object.len() == 1 => object
+                    .iter()
+                    .next()
+                    .map(|(name, fields)| (name.as_str(), fields))
+                    .ok_or_else(|| invalid(path, "缺少枚举分支"))?,
+                _ => return Err(invalid(path, "必须是分支名或 {分支名: 字段} 对象")),
+            };
+            let variant = variants
+                .iter()
+                .find(|variant| variant.name == name)
+                .ok_or_else(|| invalid(path, format!("未知枚举分支 {name}")))?;
+            out.push(variant.index);
+            encode_fields(
+                &variant.fields,
+                fields_value,
+                &child_path(path, name),
+                parse_account,
+                out,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// 无符号整数接受 JSON 数字或十进制字符串(超过 2^53 的金额必须用字符串)。
+fn parse_uint(value: &Value, path: &str, max: u128) -> Result<u128, GenericCallError> {
+    let parsed = match value {
+        Value::Number(number) => number.as_u64().map(u128::from),
+        Value::String(text) if !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit()) => {
+            text.parse::<u128>().ok()
+        }
+        _ => None,
+    };
+    parsed
+        .filter(|number| *number <= max)
+        .ok_or_else(|| invalid(path, format!("必须是不超过 {max} 的非负整数")))
+}
+
+fn encode_compact(value: u128, out: &mut Vec<u8>) {
+    if value < 1 << 6 {
+        out.push((value as u8) << 2);
+    } else if value < 1 << 14 {
+        out.extend_from_slice(&(((value as u16) << 2) | 0b01).to_le_bytes());
+    } else if value < 1 << 30 {
+        out.extend_from_slice(&(((value as u32) << 2) | 0b10).to_le_bytes());
+    } else {
+        let bytes = value.to_le_bytes();
+        let len = bytes
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(1, |last| last + 1)
+            .max(4);
+        out.push((((len - 4) as u8) << 2) | 0b11);
+        out.extend_from_slice(&bytes[..len]);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize, path: &str) -> Result<&'a [u8], GenericCallError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| GenericCallError::UnexpectedEnd(path.to_string()))?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self, path: &str) -> Result<u8, GenericCallError> {
+        Ok(self.take(1, path)?[0])
+    }
+
+    fn uint(&mut self, len: usize, path: &str) -> Result<u128, GenericCallError> {
+        let mut buf = [0u8; 16];
+        buf[..len].copy_from_slice(self.take(len, path)?);
+        Ok(u128::from_le_bytes(buf))
+    }
+
+    /// 只接受规范最短编码，与 `parity-scale-codec` 的 `Compact` 解码规则一致。
+    fn compact(&mut self, path: &str) -> Result<u128, GenericCallError> {
+        let malformed = || GenericCallError::Malformed {
+            path: path.to_string(),
+            reason: "Compact 编码不是最短形式".to_string(),
+        };
+        let first = self.byte(path)?;
+        let value = match first & 0b11 {
+            0b00 => u128::from(first >> 2),
+            0b01 => {
+                let value = u128::from(u16::from_le_bytes([first, self.byte(path)?]) >> 2);
+                if value < 1 << 6 {
+                    return Err(malformed());
+                }
+                value
+            }
+            0b10 => {
+                let rest = self.take(3, path)?;
+                let value = u128::from(u32::from_le_bytes([first, rest[0], rest[1], rest[2]]) >> 2);
+                if value < 1 << 14 {
+                    return Err(malformed());
+                }
+                value
+            }
+            _ => {
+                let len = usize::from(first >> 2) + 4;
+                if len > 16 {
+                    return Err(GenericCallError::Malformed {
+                        path: path.to_string(),
+                        reason: "Compact 超过 u128".to_string(),
+                    });
+                }
+                let value = self.uint(len, path)?;
+                let min_len = (128 - value.leading_zeros() as usize).div_ceil(8).max(4);
+                if value < 1 << 30 || len != min_len {
+                    return Err(malformed());
+                }
+                value
+            }
+        };
+        Ok(value)
+    }
+
+    fn length(&mut self, path: &str, max: usize) -> Result<usize, GenericCallError> {
+        let len = self.compact(path)?;
+        usize::try_from(len)
+            .ok()
+            .filter(|len| *len <= max)
+            .ok_or_else(|| GenericCallError::Malformed {
+                path: path.to_string(),
+                reason: format!("长度 {len} 超出上限"),
+            })
+    }
+}
+
+fn decode_value(
+    ty: &ArgType,
+    reader: &mut Reader<'_>,
+    path: &str,
+) -> Result<String, GenericCallError> {
+    let text = match ty {
+        ArgType::Bool => match reader.byte(path)? {
+            0 => "false".to_string(),
+            1 => "true".to_string(),
+            other => {
+                return Err(GenericCallError::Malformed {
+                    path: path.to_string(),
+                    reason: format!("布尔值字节 {other} 非法"),
+                })
+            }
+        },
+        ArgType::U8 => reader.uint(1, path)?.to_string(),
+        ArgType::U16 => reader.uint(2, path)?.to_string(),
+        ArgType::U32 => reader.uint(4, path)?.to_string(),
+        ArgType::U64 => reader.uint(8, path)?.to_string(),
+        ArgType::U128 => reader.uint(16, path)?.to_string(),
+        ArgType::Compact => reader.compact(path)?.to_string(),
+        ArgType::Bytes => {
+            let remaining = reader.bytes.len() - reader.offset;
+            let len = reader.length(path, remaining)?;
+            display_bytes(reader.take(len, path)?)
+        }
+        ArgType::FixedBytes(len) => {
+            format!("0x{}", hex::encode(reader.take(*len as usize, path)?))
+        }
+        ArgType::AccountId => format!("0x{}", hex::encode(reader.take(32, path)?)),
+        ArgType::Option(inner) => match reader.byte(path)? {
+            0 => "None".to_string(),
+            1 => decode_value(inner, reader, path)?,
+            other => {
+                return Err(GenericCallError::Malformed {
+                    path: path.to_string(),
+                    reason: format!("Option 判别字节 {other} 非法"),
+                })
+            }
+        },
+        ArgType::Vec(inner) => {
+            let len = reader.length(path, MAX_VEC_ITEMS)?;
+            let items = (0..len)
+                .map(|index| decode_value(inner, reader, &child_path(path, &index.to_string())))
+                .collect::<Result<Vec<_>, _>>()?;
+            format!("[{}]", items.join(", "))
+        }
+        ArgType::Tuple(types) => {
+            let items = types
+                .iter()
+                .enumerate()
+                .map(|(index, item_ty)| {
+                    decode_value(item_ty, reader, &child_path(path, &index.to_string()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            format!("({})", items.join(", "))
+        }
+        ArgType::Composite(fields) => decode_fields(fields, reader, path)?,
+        ArgType::Variant(variants) => {
+            let index = reader.byte(path)?;
+            let variant = variants
+                .iter()
+                .find(|variant| variant.index == index)
+                .ok_or_else(|| GenericCallError::Malformed {
+                    path: path.to_string(),
+                    reason: format!("未知枚举判别字节 {index}"),
+                })?;
+            if variant.fields.is_empty() {
+                variant.name.clone()
+            } else {
+                let fields =
+                    decode_fields(&variant.fields, reader, &child_path(path, &variant.name))?;
+                format!("{} {fields}", variant.name)
+            }
+        }
+    };
+    Ok(text)
+}
+
+fn decode_fields(
+    fields: &[ArgDescriptor],
+    reader: &mut Reader<'_>,
+    path: &str,
+) -> Result<String, GenericCallError> {
+    if let [only] = fields {
+        if only.name.is_empty() {
+            return decode_value(&only.ty, reader, path);
+        }
+    }
+    let items = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let label = if field.name.is_empty() {
+                index.to_string()
+            } else {
+                field.name.clone()
+            };
+            let value = decode_value(&field.ty, reader, &child_path(path, &label))?;
+            Ok(if field.name.is_empty() {
+                value
+            } else {
+                format!("{label}: {value}")
+            })
+        })
+        .collect::<Result<Vec<_>, GenericCallError>>()?;
+    Ok(format!("{{{}}}", items.join(", ")))
+}
+
+/// 可打印 UTF-8 原样展示，否则展示 `0x` 十六进制，避免控制字符伪造确认页排版。
+fn display_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) if !text.is_empty() && !text.chars().any(char::is_control) => {
+            format!("\"{text}\"")
+        }
+        _ => format!("0x{}", hex::encode(bytes)),
+    }
+}
+
+#[cfg(test)]
+// 通用调用夹具异常必须立即中止测试,断言式解包仅限本测试模块。
+#[allow(clippy::expect_used, clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn remark_like() -> CallDescriptor {
+        CallDescriptor {
+            pallet_index: 200,
+            pallet_name: "Sandbox".to_string(),
+            call_index: 3,
+            call_name: "configure".to_string(),
+            args: vec![
+                ArgDescriptor {
+                    name: "who".to_string(),
+                    ty: ArgType::AccountId,
+                },
+                ArgDescriptor {
+                    name: "amount".to_string(),
+                    ty: ArgType::Compact,
+                },
+                ArgDescriptor {
+                    name: "memo".to_string(),
+                    ty: ArgType::Option(Box::new(ArgType::Bytes)),
+                },
+                ArgDescriptor {
+                    name: "mode".to_string(),
+                    ty: ArgType::Variant(vec![
+                        VariantDescriptor {
+                            index: 0,
+                            name: "Off".to_string(),
+                            fields: vec![],
+                        },
+                        VariantDescriptor {
+                            index: 1,
+                            name: "Limit".to_string(),
+                            fields: vec![ArgDescriptor {
+                                name: String::new(),
+                                ty: ArgType::U32,
+                            }],
+                        },
+                    ]),
+                },
+            ],
+        }
+    }
+
+    fn parse_hex_account(text: &str) -> Result<[u8; 32], String> {
+        let raw = hex::decode(text.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+        raw.try_into().map_err(|_| "账户必须 32 字节".to_string())
+    }
+
+    #[test]
+    fn encode_then_decode_round_trips_with_exact_call_length() {
+        let descriptor = remark_like();
+        let account = format!("0x{}", "ab".repeat(32));
+        let call_data = descriptor
+            .encode_call(
+                &json!({
+                    "who": account,
+                    "amount": "340282366920938463463374607431768211455",
+                    "memo": "备注",
+                    "mode": { "Limit": 7 },
+                }),
+                &parse_hex_account,
+            )
+            .expect("encode");
+        assert_eq!(&call_data[..2], &[200, 3]);
+
+        let mut payload = call_data.clone();
+        payload.extend_from_slice(&[0u8; 40]);
+        let decoded = descriptor.decode_call(&payload).expect("decode");
+        assert_eq!(decoded.call_len, call_data.len());
+        assert_eq!(
+            decoded.args,
+            vec![
+                ("who".to_string(), account),
+                ("amount".to_string(), u128::MAX.to_string()),
+                ("memo".to_string(), "\"备注\"".to_string()),
+                ("mode".to_string(), "Limit 7".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn compact_encoding_matches_scale_boundaries() {
+        for (value, expected) in [
+            (0u128, vec![0x00]),
+            (63, vec![0xfc]),
+            (64, vec![0x01, 0x01]),
+            (16383, vec![0xfd, 0xff]),
+            (16384, vec![0x02, 0x00, 0x01, 0x00]),
+            (1 << 30, vec![0x03, 0x00, 0x00, 0x00, 0x40]),
+        ] {
+            let mut out = Vec::new();
+            encode_compact(value, &mut out);
+            assert_eq!(out, expected, "compact({value})");
+            let mut reader = Reader {
+                bytes: &out,
+                offset: 0,
+            };
+            assert_eq!(reader.compact("x"), Ok(value));
+        }
+        let mut reader = Reader {
+            bytes: &[0x01, 0x00],
+            offset: 0,
+        };
+        assert!(matches!(
+            reader.compact("x"),
+            Err(GenericCallError::Malformed { .. })
+        ));
+    }
+
+    #[test]
+    fn decode_rejects_index_mismatch_and_truncation() {
+        let descriptor = remark_like();
+        assert_eq!(
+            descriptor.decode_call(&[200, 4, 0]),
+            Err(GenericCallError::IndexMismatch)
+        );
+        assert!(matches!(
+            descriptor.decode_call(&[200, 3, 1, 2, 3]),
+            Err(GenericCallError::UnexpectedEnd(path)) if path == "who"
+        ));
+    }
+
+    #[test]
+    fn encode_rejects_unknown_or_missing_fields() {
+        let descriptor = remark_like();
+        let result = descriptor.encode_call(
+            &json!({ "who": format!("0x{}", "00".repeat(32)), "amount": 1, "memo": null }),
+            &parse_hex_account,
+        );
+        assert!(matches!(
+            result,
+            Err(GenericCallError::InvalidArgument { .. })
+        ));
+        let result = descriptor.encode_call(
+            &json!({
+                "who": format!("0x{}", "00".repeat(32)),
+                "amount": -1,
+                "memo": null,
+                "mode": "Off",
+            }),
+            &parse_hex_account,
+        );
+        assert!(matches!(
+            result,
+            Err(GenericCallError::InvalidArgument { path, .. }) if path == "amount"
+        ));
+    }
+
+    #[test]
+    fn calls_with_dedicated_decoder_cannot_use_generic_path() {
+        let transfer = action_by_code(0x0400).expect("transfer 已登记");
+        let descriptor = CallDescriptor {
+            pallet_index: 4,
+            pallet_name: "OnchainTransaction".to_string(),
+            call_index: 0,
+            call_name: "transfer_with_remark".to_string(),
+            args: vec![],
+        };
+        assert_eq!(
+            descriptor.decode_call(&[4, 0]),
+            Err(GenericCallError::DedicatedActionExists(
+                "OnchainTransaction::transfer_with_remark".to_string(),
+                transfer.action_key,
+            ))
+        );
+    }
+
+    #[test]
+    fn review_fields_use_registered_chinese_labels() {
+        let descriptor = remark_like();
+        let decoded = DecodedCall {
+            args: vec![("amount".to_string(), "1".to_string())],
+            call_len: 3,
+        };
+        let payload = [200, 3, 4, 0, 0];
+        let fields = descriptor
+            .review_fields(&payload, &decoded, PinStatus::Verified { spec_version: 7 })
+            .expect("字段已登记");
+        let keys = fields
+            .iter()
+            .map(|field| field.field_key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![
+                "call_verification",
+                "pallet_name",
+                "call_name",
+                "call_args",
+                "call_data_hex"
+            ]
+        );
+        assert_eq!(fields[1].field_value_zh, "Sandbox(200)");
+        assert_eq!(fields[3].field_value_zh, "amount: 1");
+        assert_eq!(fields[4].field_value_zh, "0xc80304");
+    }
+
+    #[test]
+    fn unpinned_descriptor_names_are_marked_unverified() {
+        let descriptor = remark_like();
+        let decoded = DecodedCall {
+            args: vec![],
+            call_len: 2,
+        };
+        let fields = descriptor
+            .review_fields(&[200, 3], &decoded, PinStatus::Unpinned { spec_version: 7 })
+            .expect("字段已登记");
+        assert!(fields[0].field_value_zh.starts_with("未核验"));
+        assert_eq!(fields[1].field_value_zh, "#200(自报名称 Sandbox,未核验)");
+        assert_eq!(fields[2].field_value_zh, "#3(自报名称 configure,未核验)");
+    }
+
+    #[test]
+    fn pinned_descriptor_must_match_exactly() {
+        let descriptor = remark_like();
+        assert_eq!(
+            descriptor.compare_pinned(7, Some(&remark_like())),
+            Ok(PinStatus::Verified { spec_version: 7 })
+        );
+        let renamed = CallDescriptor {
+            call_name: "transfer_all".to_string(),
+            ..remark_like()
+        };
+        assert_eq!(
+            renamed.compare_pinned(7, Some(&descriptor)),
+            Err(GenericCallError::PinnedMismatch(7))
+        );
+        assert_eq!(
+            descriptor.compare_pinned(7, None),
+            Ok(PinStatus::Unpinned { spec_version: 7 })
+        );
+    }
+
+    #[test]
+    fn spec_version_is_read_from_signing_tail() {
+        let mut payload = vec![200, 3, 0x00, 0x04, 0x00, 0x00];
+        payload.extend_from_slice(&42u32.to_le_bytes());
+        payload.extend_from_slice(&1u32.to_le_bytes());
+        payload.extend_from_slice(&[0u8; 65]);
+        assert_eq!(signing_spec_version(&payload), Some(42));
+        assert_eq!(signing_spec_version(&payload[..72]), None);
+    }
+
+    #[test]
+    fn descriptor_depth_is_bounded() {
+        let mut ty = ArgType::U8;
+        for _ in 0..MAX_ARG_DEPTH {
+            ty = ArgType::Option(Box::new(ty));
+        }
+        let descriptor = CallDescriptor {
+            args: vec![ArgDescriptor {
+                name: "deep".to_string(),
+                ty,
+            }],
+            ..remark_like()
+        };
+        assert!(matches!(
+            descriptor.validate(),
+            Err(GenericCallError::InvalidDescriptor(_))
+        ));
+    }
+}
@@ -6,6 +6,7 @@
 pub mod codec;
 pub mod decision;
 pub mod export;
+pub mod generic_call;
 pub mod registry;
 
 pub use codec::{
@@ -13,7 +14,11 @@ pub use codec::{
     SIGNATURE_BYTES,
 };
 pub use decision::{SignDecision, SignNormal, SignReject};
+pub use generic_call::{
+    signing_spec_version, ArgDescriptor, ArgType, CallDescriptor, DecodedCall, GenericCallError,
+    PinStatus, VariantDescriptor, COMPOSE_CALL_ACTION_KEY,
+};
 pub use registry::{
-    action_by_code, action_by_key, field_label_zh, reject_reason_zh, ActionEntry, ActionKind,
-    FieldEntry, RegistryError, RejectReasonEntry, SigningCategory,
+    action_by_code, action_by_key, field_label_zh, pinned_call, reject_reason_zh, ActionEntry,
+    ActionKind, FieldEntry, PinnedCallEntry, RegistryError, RejectReasonEntry, SigningCategory,
 };
@@ -1,9 +1,11 @@
+use crate::generic_call::CallDescriptor;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
 const ACTIONS_YAML: &str = include_str!("../registry/actions.yaml");
 const FIELDS_YAML: &str = include_str!("../registry/fields.yaml");
 const REJECT_REASONS_YAML: &str = include_str!("../registry/reject_reasons.yaml");
+const PINNED_CALLS_YAML: &str = include_str!("../registry/pinned_calls.yaml");
 
 /// action registry 读取和一致性错误。
 #[derive(Debug, thiserror::Error)]
@@ -81,6 +83,13 @@ pub struct RejectReasonEntry {
     pub reject_reason_zh: String,
 }
 
+/// 通用调用可信描述登记项：某 runtime 版本下调用索引对应的名称与参数类型。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedCallEntry {
+    pub spec_version: u32,
+    pub descriptor: CallDescriptor,
+}
+
 pub fn actions() -> Result<Vec<ActionEntry>, RegistryError> {
     Ok(serde_yaml::from_str(ACTIONS_YAML)?)
 }
@@ -93,6 +102,25 @@ pub fn reject_reasons() -> Result<Vec<RejectReasonEntry>, RegistryError> {
     Ok(serde_yaml::from_str(REJECT_REASONS_YAML)?)
 }
 
+pub fn pinned_calls() -> Result<Vec<PinnedCallEntry>, RegistryError> {
+    Ok(serde_yaml::from_str(PINNED_CALLS_YAML)?)
+}
+
+pub fn pinned_call(
+    spec_version: u32,
+    pallet_index: u8,
+    call_index: u8,
+) -> Result<Option<CallDescriptor>, RegistryError> {
+    Ok(pinned_calls()?
+        .into_iter()
+        .find(|entry| {
+            entry.spec_version == spec_version
+                && entry.descriptor.pallet_index == pallet_index
+                && entry.descriptor.call_index == call_index
+        })
+        .map(|entry| entry.descriptor))
+}
+
 pub fn action_by_code(action_code: u16) -> Result<ActionEntry, RegistryError> {
     actions()?
         .into_iter()
@@ -2,7 +2,7 @@
 #![allow(clippy::expect_used, clippy::unwrap_used)]
 
 use qr_protocol::export::export_registry_dart;
-use qr_protocol::registry::{actions, fields, reject_reasons, SigningCategory};
+use qr_protocol::registry::{actions, fields, pinned_calls, reject_reasons, SigningCategory};
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
@@ -206,6 +206,32 @@ fn fields_yaml_has_no_orphan_entries() {
     }
 }
 
+#[test]
+fn pinned_call_descriptors_are_unique_valid_and_generic() {
+    let mut keys = HashSet::new();
+    for entry in pinned_calls().expect("pinned_calls.yaml 必须可解析") {
+        let descriptor = &entry.descriptor;
+        assert!(
+            keys.insert((
+                entry.spec_version,
+                descriptor.pallet_index,
+                descriptor.call_index
+            )),
+            "可信调用描述重复: spec_version {} {}::{}",
+            entry.spec_version,
+            descriptor.pallet_name,
+            descriptor.call_name
+        );
+        descriptor.validate().expect("可信调用描述必须合法");
+        assert!(
+            descriptor.dedicated_action().is_none(),
+            "{}::{} 已有专用签名动作，不应登记为通用调用",
+            descriptor.pallet_name,
+            descriptor.call_name
+        );
+    }
+}
+
 #[test]
 fn generated_dart_registries_are_current() {
     let expected = export_registry_dart().expect("Dart registry 必须可生成");
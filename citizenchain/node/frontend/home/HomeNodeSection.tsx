@@ -10,7 +10,7 @@ const PARTIAL_REFRESH_ERROR_PREFIX = '部分数据刷新失败：';
 
 const EMPTY_CHAIN: ChainStatus = { blockHeight: null, finalizedHeight: null, syncing: null, specVersion: null, nodeVersion: '' };
 const EMPTY_IDENTITY: NodeIdentity = { peerId: null, role: null, genesisHash: null };
-const EMPTY_ISSUANCE: TotalIssuance = { totalIssuance: null };
+const EMPTY_ISSUANCE: TotalIssuance = { totalIssuance: null, vestingLocked: null };
 const EMPTY_STAKE: TotalStake = { totalStake: null };
 
 // 节点打开软件时自动启动；首页按钮仅提供手动停止和再次启动。
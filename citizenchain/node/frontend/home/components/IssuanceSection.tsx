@@ -24,6 +24,19 @@ export function IssuanceSection({ issuance, stake }: Props) {
             )}
           </div>
         </div>
+        <div className="metric-card">
+          <div className="metric-label">归属锁定金额 <span className="metric-hint">（决议发行已铸造、尚未按归属计划释放的部分）</span></div>
+          <div className="metric-value">
+            {issuance.vestingLocked ? (
+              <>
+                {formatAmount(issuance.vestingLocked)}元
+                <span className="metric-value-currency">（公民币）</span>
+              </>
+            ) : (
+              '-'
+            )}
+          </div>
+        </div>
         <div className="metric-card">
           <div className="metric-label">永久质押金额 <span className="metric-hint">（成立43个省储行的创立发行总额，永久质押于各省储行质押地址）</span></div>
           <div className="metric-value">
@@ -23,6 +23,7 @@ export type NodeIdentity = {
 
 export type TotalIssuance = {
   totalIssuance: string | null;
+  vestingLocked: string | null;
 };
 
 export type TotalStake = {
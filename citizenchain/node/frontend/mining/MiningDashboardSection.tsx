@@ -16,6 +16,20 @@ function formatIncomeDisplay(raw: string): string {
   });
 }
 
+function formatHashrate(hashesPerSec: number): string {
+  if (!Number.isFinite(hashesPerSec) || hashesPerSec <= 0) {
+    return '0 H/s';
+  }
+  const units = ['H/s', 'KH/s', 'MH/s', 'GH/s', 'TH/s'];
+  let value = hashesPerSec;
+  let unit = 0;
+  while (value >= 1000 && unit < units.length - 1) {
+    value /= 1000;
+    unit += 1;
+  }
+  return `${value.toFixed(2)} ${units[unit]}`;
+}
+
 export function MiningDashboardSection() {
   const [mining, setMining] = useState<MiningDashboard>({
     income: {
@@ -25,6 +39,7 @@ export function MiningDashboardSection() {
       todayIncome: '0.00',
     },
     records: [],
+    poolWorkers: [],
     warning: null,
   });
   const [error, setError] = useState<string | null>(null);
@@ -161,6 +176,43 @@ export function MiningDashboardSection() {
           </table>
         </div>
       </section>
+      {mining.poolWorkers.length > 0 ? (
+        <section className="section">
+          <h2>矿池工人</h2>
+          <div className="table-wrap">
+            <table className="mining-table">
+              <thead>
+                <tr>
+                  <th>名称</th>
+                  <th>地址</th>
+                  <th>估算算力</th>
+                  <th>自报算力</th>
+                  <th>有效份额</th>
+                  <th>拒绝 / 过期</th>
+                  <th>出块</th>
+                  <th>最近份额</th>
+                </tr>
+              </thead>
+              <tbody>
+                {mining.poolWorkers.map((w) => (
+                  <tr key={w.workerId}>
+                    <td>{w.name}</td>
+                    <td>{w.address}</td>
+                    <td>{formatHashrate(w.estimatedHashrate)}</td>
+                    <td>{formatHashrate(w.reportedHashrate)}</td>
+                    <td>{w.acceptedShares}</td>
+                    <td>
+                      {w.rejectedShares} / {w.staleShares}
+                    </td>
+                    <td>{w.blocksFound}</td>
+                    <td>{w.lastShareAtMs ? new Date(w.lastShareAtMs).toLocaleString() : '暂无'}</td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+        </section>
+      ) : null}
       {mining.warning ? <pre className="error">{mining.warning}</pre> : null}
       {error ? <pre className="error">{error}</pre> : null}
     </>
@@ -15,9 +15,25 @@ export type MiningBlockRecord = {
   author: string;
 };
 
+// 节点内矿池在线 worker，对齐后端 src/mining/pool_server::PoolWorkerStats。
+export type PoolWorkerStats = {
+  workerId: number;
+  name: string;
+  address: string;
+  connectedAtMs: number;
+  acceptedShares: number;
+  rejectedShares: number;
+  staleShares: number;
+  blocksFound: number;
+  lastShareAtMs: number | null;
+  reportedHashrate: number;
+  estimatedHashrate: number;
+};
+
 export type MiningDashboard = {
   income: MiningIncome;
   records: MiningBlockRecord[];
+  poolWorkers: PoolWorkerStats[];
   warning: string | null;
 };
 
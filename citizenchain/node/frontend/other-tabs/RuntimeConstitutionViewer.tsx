@@ -1,14 +1,20 @@
 import { useCallback, useEffect, useState } from 'react';
 import { sanitizeError } from '../tauri';
 import { otherTabsApi as api } from './api';
-import type { RuntimeConstitutionDocument } from './types';
+import type { RuntimeConstitutionDocument, UpcomingLawSunset } from './types';
 
 export function RuntimeConstitutionViewer() {
   const [doc, setDoc] = useState<RuntimeConstitutionDocument | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [sunsets, setSunsets] = useState<UpcomingLawSunset[]>([]);
 
   const load = useCallback(() => {
     setError(null);
+    // 日落提示只是附加信息,读取失败不影响宪法正文展示。
+    void api
+      .getUpcomingLawSunsets()
+      .then(setSunsets)
+      .catch(() => setSunsets([]));
     void api
       .getRuntimeConstitutionDocument()
       .then(setDoc)
@@ -41,6 +47,23 @@ export function RuntimeConstitutionViewer() {
 
   return (
     <section className="runtime-constitution-shell">
+      {sunsets.length > 0 && (
+        <ul className="runtime-constitution-sunsets" aria-label="即将到期的法律">
+          {sunsets.map((item) => (
+            <li key={`${item.lawId}-${item.version}-${item.kind}`}>
+              <span className={`sunset-kind sunset-kind-${item.kind}`}>
+                {item.kind === 'expiry' ? '到期失效' : '到期复审'}
+              </span>
+              <span className="sunset-title">
+                {item.title || `法律 #${item.lawId}`}(第 {item.version} 版)
+              </span>
+              <time dateTime={new Date(item.at).toISOString()}>
+                {new Date(item.at).toLocaleString('zh-CN', { hour12: false })}
+              </time>
+            </li>
+          ))}
+        </ul>
+      )}
       {/* runtime 宪法 HTML 只在隔离 iframe 内运行目录脚本，不开放 allow-same-origin。 */}
       <iframe
         className="runtime-constitution-frame"
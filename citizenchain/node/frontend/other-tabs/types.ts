@@ -27,6 +27,14 @@ export type RuntimeConstitutionDocument = {
   source: string;
 };
 
+export type UpcomingLawSunset = {
+  lawId: number;
+  version: number;
+  title: string;
+  kind: 'expiry' | 'review';
+  at: number;
+};
+
 export type OtherTabsPayload = {
   tabs: OtherTabItem[];
 };
@@ -1,7 +1,7 @@
 import { useEffect, useState } from 'react';
 import { sanitizeError } from '../tauri';
 import { settingsApi as api } from './api';
-import type { OnChinaPlatformState } from './types';
+import type { OnChinaBackupState, OnChinaPlatformState } from './types';
 
 type Props = {
   platform: OnChinaPlatformState | null;
@@ -10,10 +10,28 @@ type Props = {
 
 const fallbackUrl = 'https://onchina.local:8964';
 
+function formatTime(value: string | null): string {
+  if (!value) return '暂无';
+  const time = new Date(value);
+  return Number.isNaN(time.getTime()) ? value : time.toLocaleString();
+}
+
+// 备份健康度:未启用 / 备份中 / 异常(最近备份校验失败、上次任务报错或 WAL 归档失败)/ 正常。
+function backupStatus(backup: OnChinaBackupState): { status: string; label: string } {
+  if (!backup.backupEnabled) return { status: 'stopped', label: '未启用' };
+  if (backup.jobRunning) return { status: 'starting', label: '备份中' };
+  if (backup.latestBackupOk === false || backup.lastError || backup.archiveFailedCount > 0) {
+    return { status: 'error', label: '异常' };
+  }
+  if (backup.backupCount === 0) return { status: 'starting', label: '待首次备份' };
+  return { status: 'enabled', label: '正常' };
+}
+
 export function OnChinaPlatformSection({ platform, onUpdated }: Props) {
   const [pendingAction, setPendingAction] = useState<'start' | 'stop' | null>(null);
   const [saving, setSaving] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [backup, setBackup] = useState<OnChinaBackupState | null>(null);
   const running = platform?.running ?? false;
   const status = platform?.status ?? 'stopped';
   const statusLabel = platform?.statusLabel ?? '未开启';
@@ -34,6 +52,30 @@ export function OnChinaPlatformSection({ platform, onUpdated }: Props) {
     return () => window.clearInterval(timer);
   }, [onUpdated, status]);
 
+  useEffect(() => {
+    if (status !== 'enabled') {
+      setBackup(null);
+      return;
+    }
+    let active = true;
+    const load = async () => {
+      try {
+        const next = await api.getOnChinaBackupStatus();
+        if (active) setBackup(next);
+      } catch {
+        // 备份状态只作展示;读取失败保留上一次结果。
+      }
+    };
+    void load();
+    const timer = window.setInterval(() => {
+      void load();
+    }, 30000);
+    return () => {
+      active = false;
+      window.clearInterval(timer);
+    };
+  }, [status]);
+
   const confirmAction = async () => {
     if (!pendingAction) return;
     setSaving(true);
@@ -68,6 +110,26 @@ export function OnChinaPlatformSection({ platform, onUpdated }: Props) {
           {saving ? '处理中' : actionText}
         </button>
       </div>
+      {backup?.available ? (
+        <div className="onchina-backup-row">
+          <span className="onchina-platform-label">数据库备份</span>
+          <span className={`onchina-platform-status ${backupStatus(backup).status}`}>
+            {backupStatus(backup).label}
+          </span>
+          <span className="onchina-backup-detail">
+            {backup.backupEnabled
+              ? `最近备份 ${formatTime(backup.latestBackupAt)} · 共 ${backup.backupCount} 份 · 下次 ${formatTime(backup.nextDueAt)}`
+              : '未配置备份目录'}
+            {backup.walArchiveEnabled
+              ? ` · WAL 归档 ${backup.walSegments} 段,最近 ${formatTime(backup.lastArchivedAt)}`
+              : ' · WAL 归档未启用'}
+            {backup.archiveFailedCount > 0 ? ` · 归档失败 ${backup.archiveFailedCount} 次` : ''}
+            {backup.keyFingerprint ? ` · 密钥指纹 ${backup.keyFingerprint}` : ''}
+          </span>
+        </div>
+      ) : null}
+      {backup?.lastError ? <p className="section-inline-error">{backup.lastError}</p> : null}
+      {backup?.detail ? <p className="section-inline-error">{backup.detail}</p> : null}
       {platform?.detail && status === 'error' ? (
         <p className="section-inline-error">{platform.detail}</p>
       ) : null}
@@ -5,6 +5,7 @@ import type {
   GrandpaKey,
   NodeMode,
   NodeModeState,
+  OnChinaBackupState,
   OnChinaPlatformState,
   RewardAccount,
 } from './types';
@@ -16,6 +17,7 @@ export const settingsApi = {
   getOnChinaPlatform: () => invoke<OnChinaPlatformState>('get_onchina_platform'),
   startOnChinaPlatform: () => invoke<OnChinaPlatformState>('start_onchina_platform'),
   stopOnChinaPlatform: () => invoke<OnChinaPlatformState>('stop_onchina_platform'),
+  getOnChinaBackupStatus: () => invoke<OnChinaBackupState>('get_onchina_backup_status'),
   getRewardAccount: () => invoke<RewardAccount>('get_reward_account'),
   setRewardAccount: (ss58_address: string, unlockPassword: string) =>
     invoke<RewardAccount>('set_reward_account', {
@@ -47,6 +47,23 @@ export type OnChinaPlatformState = {
   detail?: string | null;
 };
 
+export type OnChinaBackupState = {
+  available: boolean;
+  backupEnabled: boolean;
+  walArchiveEnabled: boolean;
+  backupCount: number;
+  latestBackupAt: string | null;
+  latestBackupOk: boolean | null;
+  nextDueAt: string | null;
+  jobRunning: boolean;
+  lastError: string | null;
+  walSegments: number;
+  lastArchivedAt: string | null;
+  archiveFailedCount: number;
+  keyFingerprint: string | null;
+  detail: string | null;
+};
+
 export type DesktopUpdateStatus =
   | 'checking'
   | 'available'
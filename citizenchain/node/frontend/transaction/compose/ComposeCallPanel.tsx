@@ -0,0 +1,195 @@
+// 通用调用组装：从 runtime 元数据目录选调用 + JSON 参数 + QR 签名流程。
+import { useState, useEffect, useRef, useCallback } from 'react';
+import { sanitizeError } from '../../tauri';
+import { CitizenSignaturePanel } from '../../shared/qr/CitizenSignaturePanel';
+import type { ColdWallet } from '../onchain/types';
+import { composeCallApi as api } from './api';
+import type { ArgDescriptor, ArgType, CallDescriptor, VoteSignRequestResult } from './types';
+
+type Props = {
+  wallet: ColdWallet | null;
+};
+
+type Step = 'form' | 'qr' | 'submit' | 'done' | 'error';
+
+/** 按参数类型生成 JSON 占位值，帮助用户按描述填写。 */
+function templateOf(ty: ArgType): unknown {
+  if (typeof ty === 'string') {
+    switch (ty) {
+      case 'bool': return false;
+      case 'bytes': return '';
+      case 'account_id': return 'SS58 地址或 0x 公钥';
+      case 'u128':
+      case 'compact': return '0';
+      default: return 0;
+    }
+  }
+  if ('fixed_bytes' in ty) return `0x${'00'.repeat(ty.fixed_bytes)}`;
+  if ('option' in ty) return null;
+  if ('vec' in ty) return [];
+  if ('tuple' in ty) return ty.tuple.map(templateOf);
+  if ('composite' in ty) return fieldsTemplate(ty.composite);
+  const first = ty.variant[0];
+  return first.fields.length === 0 ? first.name : { [first.name]: fieldsTemplate(first.fields) };
+}
+
+function fieldsTemplate(fields: ArgDescriptor[]): unknown {
+  if (fields.every((f) => f.name !== '')) {
+    return Object.fromEntries(fields.map((f) => [f.name, templateOf(f.ty)]));
+  }
+  if (fields.length === 1) return templateOf(fields[0].ty);
+  return fields.map((f) => templateOf(f.ty));
+}
+
+const keyOf = (d: CallDescriptor) => `${d.pallet_index}/${d.call_index}`;
+
+export function ComposeCallPanel({ wallet }: Props) {
+  const [catalog, setCatalog] = useState<CallDescriptor[]>([]);
+  const [selectedKey, setSelectedKey] = useState('');
+  const [argsJson, setArgsJson] = useState('{}');
+  const [step, setStep] = useState<Step>('form');
+  const [formError, setFormError] = useState<string | null>(null);
+  const [submitting, setSubmitting] = useState(false);
+  const [signRequest, setSignRequest] = useState<VoteSignRequestResult | null>(null);
+  const [countdown, setCountdown] = useState(90);
+  const [error, setError] = useState<string | null>(null);
+  const [txHash, setTxHash] = useState<string | null>(null);
+
+  const selected = catalog.find((d) => keyOf(d) === selectedKey) ?? null;
+  const submittedRef = useRef<{ call: CallDescriptor; argsJson: string } | null>(null);
+  const signRequestRef = useRef(signRequest);
+  signRequestRef.current = signRequest;
+
+  useEffect(() => {
+    api.getComposeCallCatalog()
+      .then(setCatalog)
+      .catch((e) => setFormError(sanitizeError(e)));
+  }, []);
+
+  useEffect(() => {
+    if (step !== 'qr') return;
+    if (countdown <= 0) { setError('签名请求已过期'); setStep('error'); return; }
+    const timer = setTimeout(() => setCountdown((c) => c - 1), 1000);
+    return () => clearTimeout(timer);
+  }, [step, countdown]);
+
+  const handleSelect = (key: string) => {
+    setSelectedKey(key);
+    const call = catalog.find((d) => keyOf(d) === key);
+    setArgsJson(call ? JSON.stringify(fieldsTemplate(call.args), null, 2) : '{}');
+  };
+
+  const handleBuild = async () => {
+    if (!wallet || wallet.kind !== 'cold') { setFormError('请先在钱包管理中选择冷钱包'); return; }
+    if (!selected) { setFormError('请选择调用'); return; }
+    setFormError(null);
+    setSubmitting(true);
+    try {
+      const result = await api.buildComposeCallRequest(
+        wallet.account_id, selected.pallet_index, selected.call_index, argsJson,
+      );
+      submittedRef.current = { call: selected, argsJson };
+      setSignRequest(result);
+      setCountdown(90);
+      setStep('qr');
+    } catch (e) {
+      setFormError(sanitizeError(e));
+    } finally {
+      setSubmitting(false);
+    }
+  };
+
+  const handleScanResult = useCallback(async (responseText: string) => {
+    const req = signRequestRef.current;
+    const submitted = submittedRef.current;
+    if (!req || !submitted || !wallet) { setError('数据丢失，请重试'); setStep('error'); return; }
+    setStep('submit');
+    try {
+      const result = await api.submitComposeCall(
+        req.requestId, wallet.account_id, req.expectedPayloadHash,
+        submitted.call.pallet_index, submitted.call.call_index, submitted.argsJson,
+        req.signNonce, req.signBlockNumber, responseText,
+      );
+      setTxHash(result.txHash);
+      setStep('done');
+    } catch (e) {
+      setError(sanitizeError(e));
+      setStep('error');
+    }
+  }, [wallet]);
+
+  return (
+    <div className="governance-section">
+      <h3>通用链上调用</h3>
+
+      {step === 'form' && (
+        <div className="create-proposal-form">
+          {formError && <div className="error">{formError}</div>}
+          <div className="wallet-form-field">
+            <label>调用</label>
+            <select value={selectedKey} onChange={(e) => handleSelect(e.target.value)} disabled={submitting}>
+              <option value="">请选择…</option>
+              {catalog.map((d) => (
+                <option key={keyOf(d)} value={keyOf(d)}>
+                  {d.pallet_name}::{d.call_name}（{d.pallet_index}/{d.call_index}）
+                </option>
+              ))}
+            </select>
+          </div>
+          <div className="wallet-form-field">
+            <label>参数（JSON）</label>
+            <textarea
+              value={argsJson}
+              onChange={(e) => setArgsJson(e.target.value)}
+              rows={8}
+              spellCheck={false}
+              disabled={submitting || !selected}
+            />
+          </div>
+          <p style={{ fontSize: 12, color: '#888', marginTop: 4 }}>
+            大整数请填十进制字符串；字节参数以 0x 开头按十六进制，否则按文本；已有专用页面的调用不在此列
+          </p>
+          <button
+            className="vote-signing-confirm"
+            onClick={handleBuild}
+            disabled={submitting || !selected || !wallet}
+          >
+            {submitting ? '生成中…' : '生成签名请求'}
+          </button>
+        </div>
+      )}
+
+      {step === 'qr' && signRequest && (
+        <div className="vote-signing-body">
+          <CitizenSignaturePanel
+            qrValue={signRequest.requestJson}
+            countdownSeconds={countdown}
+            onScan={handleScanResult}
+            onScanError={(e) => { setError(e); setStep('error'); }}
+          />
+        </div>
+      )}
+
+      {step === 'submit' && (
+        <div className="vote-signing-body"><p className="qr-instruction">正在提交…</p></div>
+      )}
+
+      {step === 'done' && (
+        <div className="vote-signing-body">
+          <div className="vote-success">
+            <p>调用已提交</p>
+            {txHash && <code className="tx-hash">交易哈希: {txHash}</code>}
+          </div>
+          <button className="vote-signing-confirm" onClick={() => setStep('form')}>完成</button>
+        </div>
+      )}
+
+      {step === 'error' && (
+        <div className="vote-signing-body">
+          <div className="error">{error}</div>
+          <button className="vote-signing-confirm" onClick={() => { setError(null); setStep('form'); }}>重试</button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,42 @@
+import { invoke } from '../../tauri';
+import type { CallDescriptor, VoteSignRequestResult, VoteSubmitResult } from './types';
+
+// 通用调用组装 Tauri API，对齐后端 src/transaction/compose。
+// args_json 按调用描述填写：具名字段用对象，无名字段用数组，大整数用十进制字符串。
+export const composeCallApi = {
+  getComposeCallCatalog: () => invoke<CallDescriptor[]>('get_compose_call_catalog'),
+  buildComposeCallRequest: (
+    signer_public_key: string,
+    palletIndex: number,
+    callIndex: number,
+    argsJson: string,
+  ) =>
+    invoke<VoteSignRequestResult>('build_compose_call_request', {
+      signer_public_key,
+      pallet_index: palletIndex,
+      call_index: callIndex,
+      args_json: argsJson,
+    }),
+  submitComposeCall: (
+    requestId: string,
+    expected_signer_public_key: string,
+    expectedPayloadHash: string,
+    palletIndex: number,
+    callIndex: number,
+    argsJson: string,
+    signNonce: number,
+    signBlockNumber: number,
+    responseJson: string,
+  ) =>
+    invoke<VoteSubmitResult>('submit_compose_call', {
+      request_id: requestId,
+      expected_signer_public_key,
+      expected_payload_hash: expectedPayloadHash,
+      pallet_index: palletIndex,
+      call_index: callIndex,
+      args_json: argsJson,
+      sign_nonce: signNonce,
+      sign_block_number: signBlockNumber,
+      response_json: responseJson,
+    }),
+};
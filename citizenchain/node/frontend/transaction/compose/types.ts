@@ -0,0 +1,48 @@
+// 与 qr-protocol `generic_call.rs` 的 serde 形态一致(snake_case)。
+export type ArgType =
+  | 'bool'
+  | 'u8'
+  | 'u16'
+  | 'u32'
+  | 'u64'
+  | 'u128'
+  | 'compact'
+  | 'bytes'
+  | 'account_id'
+  | { fixed_bytes: number }
+  | { option: ArgType }
+  | { vec: ArgType }
+  | { tuple: ArgType[] }
+  | { composite: ArgDescriptor[] }
+  | { variant: VariantDescriptor[] };
+
+export type ArgDescriptor = {
+  name: string;
+  ty: ArgType;
+};
+
+export type VariantDescriptor = {
+  index: number;
+  name: string;
+  fields: ArgDescriptor[];
+};
+
+export type CallDescriptor = {
+  pallet_index: number;
+  pallet_name: string;
+  call_index: number;
+  call_name: string;
+  args: ArgDescriptor[];
+};
+
+export type VoteSignRequestResult = {
+  requestJson: string;
+  requestId: string;
+  expectedPayloadHash: string;
+  signNonce: number;
+  signBlockNumber: number;
+};
+
+export type VoteSubmitResult = {
+  txHash: string;
+};
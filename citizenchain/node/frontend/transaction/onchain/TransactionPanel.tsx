@@ -5,6 +5,7 @@ import type { ColdWallet, TransferDraft, WalletStore } from './types';
 import { WalletManagerModal } from './WalletManagerModal';
 import { TransferForm } from './TransferForm';
 import { TransferSigningFlow } from './TransferSigningFlow';
+import { ComposeCallPanel } from '../compose/ComposeCallPanel';
 
 export function TransactionPanel() {
   const [wallets, setWallets] = useState<ColdWallet[]>([]);
@@ -81,6 +82,8 @@ export function TransactionPanel() {
         disabled={signingFlow != null}
       />
 
+      <ComposeCallPanel wallet={activeWallet} />
+
       {signingFlow && activeWallet && (
         <TransferSigningFlow
           wallet={activeWallet}
@@ -407,6 +407,7 @@ pub async fn build_activate_admin_request(
             sig_alg: 1,
             signer_public_key: signing::public_key_b64(&public_key_bytes)?,
             payload: signing::payload_b64(&payload),
+            call_descriptor: None,
         },
     };
 
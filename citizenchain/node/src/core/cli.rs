@@ -18,6 +18,24 @@ pub struct Cli {
     #[arg(long)]
     pub no_gpu: bool,
 
+    /// 启动节点内矿池服务并监听该地址(如 `0.0.0.0:9955`),
+    /// 供远程 `citizenchain-pool-worker` 领取工作;出块仍由本节点 powr 密钥签名。
+    #[arg(long, value_name = "ADDR", requires = "pool_token_file")]
+    pub pool_listen: Option<std::net::SocketAddr>,
+
+    /// 矿池共享口令文件;worker 须持有同一口令才能通过握手。
+    #[arg(long, value_name = "PATH", requires = "pool_listen")]
+    pub pool_token_file: Option<std::path::PathBuf>,
+
+    /// 份额难度 = 区块难度 / 该值。越大份额越多、算力统计越平滑,服务端负载也越高。
+    #[arg(
+        long,
+        value_name = "DIVISOR",
+        default_value_t = crate::mining::pool_server::DEFAULT_SHARE_DIVISOR,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub pool_share_divisor: u64,
+
     /// 把本节点以清算行角色启动，参数为清算行机构 CID。
     /// 主账户由 CID 和统一账户派生协议确定，不再作为机构身份参数输入。若不设则节点不启动清算行组件
     /// (RPC / ledger / settlement/listener 全部跳过)。
@@ -32,9 +50,42 @@ pub struct Cli {
     /// 管理员 sr25519 私钥的密码。不提供时签名密钥保持 `None`,节点只保留
     /// 查询 RPC;扫码提交需要生成 L2 ACK 签名,packer 上链也需要批次签名,
     /// 二者都会 fail-fast,直到密码提供并重启。
+    ///
+    /// 已停用:明文会出现在进程列表中,给出即启动报错;请改用
+    /// `--clearing-bank-password-fd` 或 `--clearing-bank-password-prompt`。
     #[arg(long, value_name = "PASSWORD")]
     pub clearing_bank_password: Option<String>,
 
+    /// 从已打开的文件描述符(≥3)读取清算行密码的第一行,如 `3<password-file`。
+    #[arg(long, value_name = "FD")]
+    pub clearing_bank_password_fd: Option<i32>,
+
+    /// 启动时在控制终端无回显输入清算行密码。
+    #[arg(long)]
+    pub clearing_bank_password_prompt: bool,
+
+    /// PKCS#11 模块路径。设置后签名私钥由令牌内包裹密钥保护,密码即令牌用户 PIN。
+    #[arg(long, value_name = "PATH")]
+    pub clearing_bank_pkcs11_module: Option<std::path::PathBuf>,
+
+    /// PKCS#11 令牌标签;不指定时要求模块上恰好一个已初始化令牌。
+    #[arg(long, value_name = "LABEL", requires = "clearing_bank_pkcs11_module")]
+    pub clearing_bank_pkcs11_token: Option<String>,
+
+    /// 令牌内包裹密钥的标签。
+    #[arg(
+        long,
+        value_name = "LABEL",
+        default_value = crate::transaction::offchain::settlement::pkcs11::DEFAULT_KEY_LABEL,
+        requires = "clearing_bank_pkcs11_module"
+    )]
+    pub clearing_bank_pkcs11_key_label: String,
+
+    /// 一次性把口令加密的 `signing_key.enc` 导入令牌:启动时在终端输入旧文件口令。
+    /// 令牌包裹文件已存在时忽略。
+    #[arg(long, requires = "clearing_bank_pkcs11_module")]
+    pub clearing_bank_pkcs11_import: bool,
+
     /// `offchain::settlement::reserve` 主账对账触发周期(秒)。
     /// 缺省 300(5 分钟)。设为 0 则关闭对账 worker(不推荐,仅用于排障)。
     /// 仅在 `--clearing-bank-cid-number` 生效时启用。
@@ -7,6 +7,11 @@ use super::{
     cli::{Cli, Subcommand},
     service,
 };
+use crate::mining::pool_server::PoolServerConfig;
+use crate::transaction::offchain::settlement::{
+    pkcs11::Pkcs11Config,
+    unlock::{ClearingBankUnlock, PasswordSource},
+};
 use citizenchain::{Block, EXISTENTIAL_DEPOSIT};
 use frame_benchmarking_cli::{BenchmarkCmd, ExtrinsicFactory, SUBSTRATE_REFERENCE_HARDWARE};
 use primitives::core_const::{SS58_FORMAT, SUPPORT_URL};
@@ -227,21 +232,23 @@ pub fn run() -> sc_cli::Result<()> {
             } else {
                 Some(cli.gpu_device.unwrap_or(0))
             };
+            let pool_server = pool_server_config(&cli)?;
             let runner = cli.create_runner(&cli.run)?;
             // 固定使用 libp2p 后端（支持 WSS + DCUtR/Relay/AutoNAT），已清理 litep2p 代码路径。
             // 把清算行 CLI 参数透传给 service::new_full
             let clearing_bank_cid_number = cli.clearing_bank_cid_number.clone();
             let clearing_bank_role_code = cli.clearing_bank_role_code.clone();
-            let clearing_bank_password = cli.clearing_bank_password.clone();
+            let clearing_bank_unlock = clearing_bank_unlock(&cli)?;
             let clearing_reserve_monitor_interval_secs = cli.clearing_reserve_monitor_interval_secs;
             runner.run_node_until_exit(|config| async move {
                 service::new_full(
                     config,
                     mining_threads,
                     gpu_device,
+                    pool_server,
                     clearing_bank_cid_number,
                     clearing_bank_role_code,
-                    clearing_bank_password,
+                    clearing_bank_unlock,
                     clearing_reserve_monitor_interval_secs,
                 )
                 .map_err(sc_cli::Error::Service)
@@ -249,3 +256,70 @@ pub fn run() -> sc_cli::Result<()> {
         }
     }
 }
+
+/// 读取矿池口令文件。未设 `--pool-listen` 时不启动矿池服务。
+fn pool_server_config(cli: &Cli) -> sc_cli::Result<Option<PoolServerConfig>> {
+    let (Some(listen), Some(token_file)) = (cli.pool_listen, &cli.pool_token_file) else {
+        return Ok(None);
+    };
+    let token = std::fs::read_to_string(token_file)
+        .map_err(|e| {
+            sc_cli::Error::Input(format!(
+                "读取矿池口令文件 {} 失败:{e}",
+                token_file.display()
+            ))
+        })?
+        .trim()
+        .to_string();
+    if token.is_empty() {
+        return Err(sc_cli::Error::Input(format!(
+            "矿池口令文件 {} 为空",
+            token_file.display()
+        )));
+    }
+    Ok(Some(PoolServerConfig {
+        listen,
+        token,
+        share_divisor: cli.pool_share_divisor,
+    }))
+}
+
+/// 解析清算行口令来源与 PKCS#11 参数。只在以清算行角色启动时读取口令,
+/// 避免普通节点被 `--clearing-bank-password-prompt` 阻塞。
+fn clearing_bank_unlock(cli: &Cli) -> sc_cli::Result<ClearingBankUnlock> {
+    if cli.clearing_bank_cid_number.is_none() {
+        return Ok(ClearingBankUnlock::default());
+    }
+    let source = PasswordSource::from_cli(
+        cli.clearing_bank_password.as_deref(),
+        cli.clearing_bank_password_fd,
+        cli.clearing_bank_password_prompt,
+    )
+    .map_err(sc_cli::Error::Input)?;
+    let prompt_label = if cli.clearing_bank_pkcs11_module.is_some() {
+        "清算行 PKCS#11 令牌 PIN"
+    } else {
+        "清算行签名密钥密码"
+    };
+    let password = source.read(prompt_label).map_err(sc_cli::Error::Input)?;
+
+    let pkcs11 = match &cli.clearing_bank_pkcs11_module {
+        Some(module_path) => {
+            let import_password = if cli.clearing_bank_pkcs11_import {
+                PasswordSource::Prompt
+                    .read("旧 signing_key.enc 密码")
+                    .map_err(sc_cli::Error::Input)?
+            } else {
+                None
+            };
+            Some(Pkcs11Config {
+                module_path: module_path.clone(),
+                token_label: cli.clearing_bank_pkcs11_token.clone(),
+                key_label: cli.clearing_bank_pkcs11_key_label.clone(),
+                import_password,
+            })
+        }
+        None => None,
+    };
+    Ok(ClearingBankUnlock { password, pkcs11 })
+}
@@ -0,0 +1,160 @@
+//! 两个法律版本之间的逐款差异。
+//!
+//! 与链端 `legislation-yuan::amendment` 同一套规则:章 > 节 > 条 > 款各层按 `number` 对齐,
+//! 先列旧版本独有(删除),再按新版本顺序列新增与改动。节点侧自行 RAW 解码两版全文计算,
+//! 不依赖可升级 runtime 的 `law_diff` API 返回值。
+
+use super::*;
+
+/// 章/节/条/款的可比对视图:定位序号、本层文本与下级节点。
+trait DiffNode {
+    fn number(&self) -> u32;
+    fn fields(&self) -> serde_json::Value;
+    fn diff_children(path: &[u32], old: Option<&Self>, new: Option<&Self>, out: &mut Vec<Entry>);
+}
+
+/// 单条差异;`path` 为自章起的序号链(长度 1..=4 对应章/节/条/款)。
+struct Entry {
+    path: Vec<u32>,
+    change: &'static str,
+    before: Option<serde_json::Value>,
+    after: Option<serde_json::Value>,
+}
+
+fn text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn opt_text(bytes: &Option<Vec<u8>>) -> Option<String> {
+    bytes.as_ref().map(|b| text(b))
+}
+
+fn children<'a, P, C>(node: Option<&'a P>, get: impl Fn(&'a P) -> &'a [C]) -> &'a [C] {
+    node.map(get).unwrap_or(&[])
+}
+
+impl DiffNode for MChapter {
+    fn number(&self) -> u32 {
+        self.number
+    }
+    fn fields(&self) -> serde_json::Value {
+        serde_json::json!({ "title": text(&self.title), "title_en": opt_text(&self.title_en) })
+    }
+    fn diff_children(path: &[u32], old: Option<&Self>, new: Option<&Self>, out: &mut Vec<Entry>) {
+        diff_nodes(
+            path,
+            children(old, |c| c.sections.as_slice()),
+            children(new, |c| c.sections.as_slice()),
+            out,
+        );
+    }
+}
+
+impl DiffNode for MSection {
+    fn number(&self) -> u32 {
+        self.number
+    }
+    fn fields(&self) -> serde_json::Value {
+        serde_json::json!({ "title": text(&self.title), "title_en": opt_text(&self.title_en) })
+    }
+    fn diff_children(path: &[u32], old: Option<&Self>, new: Option<&Self>, out: &mut Vec<Entry>) {
+        diff_nodes(
+            path,
+            children(old, |s| s.articles.as_slice()),
+            children(new, |s| s.articles.as_slice()),
+            out,
+        );
+    }
+}
+
+impl DiffNode for MArticle {
+    fn number(&self) -> u32 {
+        self.number
+    }
+    fn fields(&self) -> serde_json::Value {
+        serde_json::json!({
+            "title": text(&self.title),
+            "title_en": opt_text(&self.title_en),
+            "text": text(&self.body),
+            "text_en": opt_text(&self.body_en),
+        })
+    }
+    fn diff_children(path: &[u32], old: Option<&Self>, new: Option<&Self>, out: &mut Vec<Entry>) {
+        diff_nodes(
+            path,
+            children(old, |a| a.clauses.as_slice()),
+            children(new, |a| a.clauses.as_slice()),
+            out,
+        );
+    }
+}
+
+impl DiffNode for MClause {
+    fn number(&self) -> u32 {
+        self.number
+    }
+    fn fields(&self) -> serde_json::Value {
+        serde_json::json!({ "text": text(&self.text), "text_en": opt_text(&self.text_en) })
+    }
+    fn diff_children(_: &[u32], _: Option<&Self>, _: Option<&Self>, _: &mut Vec<Entry>) {}
+}
+
+fn diff_nodes<X: DiffNode>(parent: &[u32], old: &[X], new: &[X], out: &mut Vec<Entry>) {
+    let path_of = |number: u32| [parent, &[number]].concat();
+    for removed in old
+        .iter()
+        .filter(|o| !new.iter().any(|n| n.number() == o.number()))
+    {
+        let path = path_of(removed.number());
+        out.push(Entry {
+            path: path.clone(),
+            change: "removed",
+            before: Some(removed.fields()),
+            after: None,
+        });
+        X::diff_children(&path, Some(removed), None, out);
+    }
+    for current in new {
+        let path = path_of(current.number());
+        let previous = old.iter().find(|o| o.number() == current.number());
+        let before = previous.map(DiffNode::fields);
+        let after = current.fields();
+        let change = match &before {
+            None => Some("added"),
+            Some(before) if *before != after => Some("modified"),
+            Some(_) => None,
+        };
+        if let Some(change) = change {
+            out.push(Entry {
+                path: path.clone(),
+                change,
+                before,
+                after: Some(after),
+            });
+        }
+        X::diff_children(&path, previous, Some(current), out);
+    }
+}
+
+/// 解码两份 `LawVersion` SCALE 字节,返回逐款差异 JSON 列表。
+pub fn law_version_diff(from_scale: &[u8], to_scale: &[u8]) -> Result<serde_json::Value, String> {
+    let decode = |raw: &[u8]| {
+        MLawVersionHead::decode(&mut &raw[..]).map_err(|e| format!("LawVersion 解码失败:{e}"))
+    };
+    let (from, to) = (decode(from_scale)?, decode(to_scale)?);
+    let mut entries = Vec::new();
+    diff_nodes(&[], &from.chapters, &to.chapters, &mut entries);
+    Ok(serde_json::Value::Array(
+        entries
+            .into_iter()
+            .map(|e| {
+                serde_json::json!({
+                    "path": e.path,
+                    "change": e.change,
+                    "before": e.before,
+                    "after": e.after,
+                })
+            })
+            .collect(),
+    ))
+}
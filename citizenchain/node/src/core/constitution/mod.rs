@@ -40,7 +40,8 @@ pub const CONSTITUTION_LAW_ID: u64 = 0;
 const GENESIS_CONSTITUTION_VERSION: u32 = 1;
 
 /// Law 元数据判别值(SCALE 变体索引,与 `legislation-yuan::types` 声明序一致:
-/// `Tier{Constitution=0,..}`、`LawStatus{Pending=0,Effective=1,Repealed=2}`)。
+/// `Tier{Constitution=0,..}`、`LawStatus{Pending=0,Effective=1,Repealed=2,Expired=3}`)。
+/// 宪法不设日落条款,故守卫不接受 `Expired`。
 /// 由 legislation-yuan 测试 `enum_discriminants_match_node_guard` 交叉钉死,防漂移。
 const TIER_CONSTITUTION: u8 = 0;
 const LAW_STATUS_PENDING: u8 = 0;
@@ -56,8 +57,8 @@ const LAW_VOTE_TYPE_SPECIAL: u8 = 4;
 // SCALE 按声明序顺序解码,解到所需字段即停 —— 尾部字段无需镜像。Encode 仅用于不可修改条款的
 // 规范字节比对(同一逻辑内容 → 同一字节)。
 
+/// 款号 number 不参与渲染/不可修改条款比对(text 已含「第N款」前缀),仅用于版本差异对齐。
 #[derive(Encode, Decode, PartialEq)]
-#[allow(dead_code)] // 款号 number 不参与渲染/比对(text 已含「第N款」前缀),仅占位保持字段序。
 struct MClause {
     number: u32,
     text: Vec<u8>,
@@ -140,8 +141,12 @@ fn find_article(chapters: &[MChapter], number: u32) -> Option<&MArticle> {
         .find(|a| a.number == number)
 }
 
+mod diff;
 mod render;
+mod sunset;
+pub use diff::law_version_diff;
 pub use render::{effective_version_of_law, immutable_article_numbers, render_constitution_html};
+pub use sunset::upcoming_sunsets;
 
 // ═════════════════════════════════════════════════════════════════════════
 // 二、不可修改条款守卫(L2 共识层)
@@ -244,6 +249,35 @@ pub mod storage_key {
         k
     }
 
+    /// `LegislationYuan::SunsetSchedule`(双键:大端到期时间戳 Identity + 条目 Blake2_128Concat)
+    /// 的前缀;按 RAW key 字典序枚举即按到期时间升序。
+    pub fn sunset_schedule_prefix() -> Vec<u8> {
+        map_prefix(b"SunsetSchedule")
+    }
+
+    /// `SunsetSchedule[到期时间戳][(law_id, version, 种类)]` 的完整 key。
+    pub fn sunset_schedule_entry(at: u64, law_id: u64, version: u32, kind: u8) -> Vec<u8> {
+        let mut k = sunset_schedule_prefix();
+        k.extend_from_slice(&at.to_be_bytes());
+        k.extend_from_slice(&blake2_128_concat(&(law_id, version, kind).encode()));
+        k
+    }
+
+    /// 从排程 RAW key 解出 `(到期时间戳, law_id, version, 种类)`;非排程键或哈希不符返回 `None`。
+    pub fn sunset_entry_from_key(key: &[u8]) -> Option<(u64, u64, u32, u8)> {
+        let rest = key.strip_prefix(sunset_schedule_prefix().as_slice())?;
+        if rest.len() < 8 + 16 {
+            return None;
+        }
+        let at = u64::from_be_bytes(rest[..8].try_into().ok()?);
+        let encoded = &rest[8 + 16..];
+        if blake2_128(encoded) != rest[8..8 + 16] {
+            return None;
+        }
+        let (law_id, version, kind) = <(u64, u32, u8)>::decode(&mut &encoded[..]).ok()?;
+        Some((at, law_id, version, kind))
+    }
+
     /// `LegislationYuan::ConstitutionImmutableManifest`(StorageValue,无 key hash)的完整 key。
     pub fn manifest() -> Vec<u8> {
         map_prefix(b"ConstitutionImmutableManifest")
@@ -1971,4 +2005,74 @@ mod tests {
             "<span class=\"doc-version-cn\">创世版</span><span class=\"doc-version-en\">Genesis Edition</span>"
         ));
     }
+
+    #[test]
+    fn law_version_diff_reports_per_clause_changes() {
+        let mut with_clause = article_bytes(2, "b");
+        with_clause.clauses.push(MClause {
+            number: 1,
+            text: "第1款".as_bytes().to_vec(),
+            text_en: None,
+        });
+        let from = law_version_scale(
+            1,
+            vec![
+                article_bytes(1, "a"),
+                article_bytes(2, "b"),
+                article_bytes(3, "c"),
+            ],
+        );
+        let to = law_version_scale(2, vec![article_bytes(1, "a2"), with_clause]);
+        let diff = law_version_diff(&from, &to).expect("应能比对");
+        let changes: Vec<(serde_json::Value, &str)> = diff
+            .as_array()
+            .expect("差异为列表")
+            .iter()
+            .map(|e| (e["path"].clone(), e["change"].as_str().unwrap()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (serde_json::json!([1, 1, 3]), "removed"),
+                (serde_json::json!([1, 1, 1]), "modified"),
+                (serde_json::json!([1, 1, 2, 1]), "added"),
+            ]
+        );
+        assert_eq!(diff[1]["before"]["text"], "a");
+        assert_eq!(diff[1]["after"]["text"], "a2");
+    }
+
+    #[test]
+    fn upcoming_sunsets_lists_current_versions_by_due_time() {
+        let mut law_bytes = law_scale_with_versions(Some(2), 2, None, LAW_STATUS_EFFECTIVE, vec![]);
+        // 借宪法夹具的 Law 编码,只替换 law_id 为 5。
+        law_bytes[..8].copy_from_slice(&5u64.encode());
+        let mut version_bytes = law_version_scale(2, vec![article_bytes(1, "a")]);
+        version_bytes[..8].copy_from_slice(&5u64.encode());
+        // RAW key 字典序即到期时间序,夹具按链上枚举顺序给出。
+        let schedule_keys = vec![
+            storage_key::sunset_schedule_entry(1_000, 5, 1, 0), // 已被修法取代
+            storage_key::sunset_schedule_entry(2_000, 8, 1, 0), // 法律不存在
+            storage_key::sunset_schedule_entry(4_000, 5, 2, 1),
+            storage_key::sunset_schedule_entry(9_000, 5, 2, 0),
+        ];
+        assert_eq!(
+            storage_key::sunset_entry_from_key(&schedule_keys[2]),
+            Some((4_000, 5, 2, 1))
+        );
+        let read = reader(vec![
+            (storage_key::law(5), law_bytes),
+            (storage_key::law_version(5, 2), version_bytes),
+        ]);
+        let listed = upcoming_sunsets(schedule_keys, |k: &[u8]| Ok(read(k))).expect("应能读取排程");
+        assert_eq!(
+            listed,
+            serde_json::json!([
+                {"law_id": 5, "version": 2, "title": "公民宪法", "kind": "review", "at": 4000},
+                {"law_id": 5, "version": 2, "title": "公民宪法", "kind": "expiry", "at": 9000},
+            ])
+        );
+        let empty = upcoming_sunsets(Vec::new(), |_: &[u8]| Ok(None)).expect("无排程");
+        assert_eq!(empty, serde_json::json!([]));
+    }
 }
@@ -0,0 +1,49 @@
+//! 法律日落排程的节点侧展示。
+//!
+//! 按前缀枚举 `SunsetSchedule` 的 RAW key(第一键为大端到期时间戳,枚举顺序即到期顺序),
+//! 逐条回查 `Laws` / `LawVersions` 取标题。与链端到点判定同口径:只列出法律当前生效版本
+//! 或待生效版本的条目,已续期或已被修法取代的残留条目不展示。
+
+use super::*;
+
+/// `SunsetKind::Expiry` 的 SCALE 变体索引(`Review` = 1)。
+const SUNSET_KIND_EXPIRY: u8 = 0;
+/// 单次最多列出的排程条目;排程本身不设上限,展示只取最早到期的一段。
+const MAX_LISTED_SUNSETS: usize = 200;
+
+/// 按到期时间升序列出日落排程 JSON;`schedule_keys` 为 `SunsetSchedule` 前缀下的 RAW key。
+pub fn upcoming_sunsets<K, F>(schedule_keys: K, read_raw: F) -> Result<serde_json::Value, String>
+where
+    K: IntoIterator<Item = Vec<u8>>,
+    F: Fn(&[u8]) -> Result<Option<Vec<u8>>, String>,
+{
+    let mut entries = Vec::new();
+    for key in schedule_keys {
+        if entries.len() >= MAX_LISTED_SUNSETS {
+            break;
+        }
+        let (at, law_id, version, kind) = storage_key::sunset_entry_from_key(&key)
+            .ok_or_else(|| "SunsetSchedule key 解码失败".to_string())?;
+        let Some(law_raw) = read_raw(&storage_key::law(law_id))? else {
+            continue;
+        };
+        let law = decode_law_head(&law_raw).map_err(|e| format!("Law 解码失败:{e:?}"))?;
+        if law.effective_version != Some(version) && law.pending_version != Some(version) {
+            continue;
+        }
+        let title = match read_raw(&storage_key::law_version(law_id, version))? {
+            Some(bytes) => MLawVersionHead::decode(&mut &bytes[..])
+                .map(|v| String::from_utf8_lossy(&v.title).into_owned())
+                .map_err(|e| format!("LawVersion 解码失败:{e}"))?,
+            None => String::new(),
+        };
+        entries.push(serde_json::json!({
+            "law_id": law_id,
+            "version": version,
+            "title": title,
+            "kind": if kind == SUNSET_KIND_EXPIRY { "expiry" } else { "review" },
+            "at": at,
+        }));
+    }
+    Ok(serde_json::Value::Array(entries))
+}
@@ -0,0 +1,467 @@
+//! 岗位任职任期届满的节点永久策略。
+//!
+//! runtime 在 `on_idle` 中按游标移除任期届满的任职，并为每条移除发出
+//! `InstitutionRoleAssignmentExpired`。节点不重放扫描游标，只逐条核对事件本身：届满只能
+//! 发生在区块收尾阶段；受保护创世机构的固定岗位与国家级成员机构的法定成员岗位只能经治理
+//! 原子轮换，不得因届满出现空缺；事件声明的任职须在父状态中真实存在、任期确已结束，
+//! 且在本块状态中已被移出。
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use codec::{Decode, Encode};
+use frame_system::Phase;
+use scale_info::{PortableRegistry, Registry, TypeDef};
+
+use citizenchain::RuntimeEvent;
+use entity_primitives::InstitutionAdminAssignment;
+
+use super::{governance_skeleton, runtime_policy};
+
+const PUBLIC_MANAGE_PALLET: &str = "PublicManage";
+const PRIVATE_MANAGE_PALLET: &str = "PrivateManage";
+const EXPIRED_VARIANT: &str = "InstitutionRoleAssignmentExpired";
+const MILLIS_PER_DAY: u64 = 86_400_000;
+
+type DecodedAssignment = InstitutionAdminAssignment<Vec<u8>, [u8; 32], Vec<u8>, Vec<u8>>;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum GuardError {
+    EventsDecodeFailed(String),
+    ExpiredEventDecodeFailed,
+    ExpiredOutsideFinalization,
+    ProtectedSeatExpired {
+        cid_number: Vec<u8>,
+        role_code: Vec<u8>,
+    },
+    TimestampUnavailable,
+    AssignmentsDecodeFailed,
+    ExpiredAssignmentNotFound {
+        cid_number: Vec<u8>,
+        role_code: Vec<u8>,
+    },
+    TermNotEnded {
+        term_end: u32,
+        current_day: u32,
+    },
+    ExpiredAssignmentRetained {
+        cid_number: Vec<u8>,
+        role_code: Vec<u8>,
+    },
+}
+
+/// 事件字段与公权/私权机构模块的 `InstitutionRoleAssignmentExpired` 逐字段同序。
+#[derive(Debug, Decode, Encode, Eq, PartialEq)]
+struct ExpiredAssignment {
+    cid_number: Vec<u8>,
+    role_code: Vec<u8>,
+    account_id: [u8; 32],
+    term_end: u32,
+    legal_representative_cleared: bool,
+}
+
+pub mod storage_key {
+    pub fn timestamp_now() -> Vec<u8> {
+        crate::shared::storage_keys::prefix(b"Timestamp", b"Now")
+    }
+
+    pub fn role_assignments(pallet: &str, cid_number: &[u8], role_code: &[u8]) -> Vec<u8> {
+        use codec::Encode;
+        crate::shared::storage_keys::blake2_double_map(
+            pallet.as_bytes(),
+            b"InstitutionRoleAssignments",
+            &cid_number.to_vec().encode(),
+            &role_code.to_vec().encode(),
+        )
+    }
+}
+
+/// 由本地 runtime 的 `RuntimeEvent` 类型元数据求出两个机构模块中届满事件的分支序号，
+/// 不在节点里硬编码事件枚举下标。
+fn expired_variant_indices() -> &'static BTreeMap<&'static str, u8> {
+    static INDICES: OnceLock<BTreeMap<&'static str, u8>> = OnceLock::new();
+    INDICES.get_or_init(|| {
+        let mut registry = Registry::new();
+        let root = registry
+            .register_type(&scale_info::meta_type::<RuntimeEvent>())
+            .id;
+        let registry = PortableRegistry::from(registry);
+        let mut indices = BTreeMap::new();
+        let Some(TypeDef::Variant(pallets)) = registry.resolve(root).map(|ty| &ty.type_def) else {
+            return indices;
+        };
+        for pallet_name in [PUBLIC_MANAGE_PALLET, PRIVATE_MANAGE_PALLET] {
+            let Some([event_field]) = pallets
+                .variants
+                .iter()
+                .find(|pallet| pallet.name == pallet_name)
+                .map(|pallet| pallet.fields.as_slice())
+            else {
+                continue;
+            };
+            let Some(TypeDef::Variant(events)) =
+                registry.resolve(event_field.ty.id).map(|ty| &ty.type_def)
+            else {
+                continue;
+            };
+            if let Some(event) = events
+                .variants
+                .iter()
+                .find(|event| event.name == EXPIRED_VARIANT)
+            {
+                indices.insert(pallet_name, event.index);
+            }
+        }
+        indices
+    })
+}
+
+/// 从机构模块事件的 SCALE 编码（首字节为事件分支序号）中取出届满事件字段。
+fn decode_expired(pallet: &str, encoded: &[u8]) -> Result<Option<ExpiredAssignment>, GuardError> {
+    let Some((&variant, mut fields)) = encoded.split_first() else {
+        return Err(GuardError::ExpiredEventDecodeFailed);
+    };
+    if expired_variant_indices().get(pallet) != Some(&variant) {
+        return Ok(None);
+    }
+    let event =
+        ExpiredAssignment::decode(&mut fields).map_err(|_| GuardError::ExpiredEventDecodeFailed)?;
+    if !fields.is_empty() {
+        return Err(GuardError::ExpiredEventDecodeFailed);
+    }
+    Ok(Some(event))
+}
+
+fn read_assignments<F>(
+    read: &F,
+    pallet: &str,
+    event: &ExpiredAssignment,
+) -> Result<Vec<DecodedAssignment>, GuardError>
+where
+    F: Fn(&[u8]) -> Option<Vec<u8>>,
+{
+    let Some(raw) = read(&storage_key::role_assignments(
+        pallet,
+        &event.cid_number,
+        &event.role_code,
+    )) else {
+        return Ok(Vec::new());
+    };
+    let mut input = raw.as_slice();
+    let assignments = Vec::<DecodedAssignment>::decode(&mut input)
+        .map_err(|_| GuardError::AssignmentsDecodeFailed)?;
+    if !input.is_empty() {
+        return Err(GuardError::AssignmentsDecodeFailed);
+    }
+    Ok(assignments)
+}
+
+fn current_day<F>(read_post: &F) -> Result<u32, GuardError>
+where
+    F: Fn(&[u8]) -> Option<Vec<u8>>,
+{
+    let raw = read_post(&storage_key::timestamp_now()).ok_or(GuardError::TimestampUnavailable)?;
+    let now = u64::decode(&mut raw.as_slice()).map_err(|_| GuardError::TimestampUnavailable)?;
+    u32::try_from(now / MILLIS_PER_DAY).map_err(|_| GuardError::TimestampUnavailable)
+}
+
+fn check_expired<FParent, FPost>(
+    pallet: &str,
+    phase: &Phase,
+    event: &ExpiredAssignment,
+    read_parent: &FParent,
+    read_post: &FPost,
+) -> Result<(), GuardError>
+where
+    FParent: Fn(&[u8]) -> Option<Vec<u8>>,
+    FPost: Fn(&[u8]) -> Option<Vec<u8>>,
+{
+    if *phase != Phase::Finalization {
+        return Err(GuardError::ExpiredOutsideFinalization);
+    }
+    let member_role = primitives::institution_constraints::member_composition_specs()
+        .iter()
+        .any(|spec| {
+            spec.institution.cid_number.as_bytes() == event.cid_number.as_slice()
+                && spec.role_code == event.role_code.as_slice()
+        });
+    if member_role || governance_skeleton::is_fixed_role(&event.cid_number, &event.role_code) {
+        return Err(GuardError::ProtectedSeatExpired {
+            cid_number: event.cid_number.clone(),
+            role_code: event.role_code.clone(),
+        });
+    }
+    let existed = read_assignments(read_parent, pallet, event)?
+        .iter()
+        .any(|assignment| {
+            assignment.account_id == event.account_id && assignment.term_end == event.term_end
+        });
+    if !existed || event.term_end == 0 {
+        return Err(GuardError::ExpiredAssignmentNotFound {
+            cid_number: event.cid_number.clone(),
+            role_code: event.role_code.clone(),
+        });
+    }
+    let current_day = current_day(read_post)?;
+    if event.term_end >= current_day {
+        return Err(GuardError::TermNotEnded {
+            term_end: event.term_end,
+            current_day,
+        });
+    }
+    if read_assignments(read_post, pallet, event)?
+        .iter()
+        .any(|assignment| assignment.account_id == event.account_id)
+    {
+        return Err(GuardError::ExpiredAssignmentRetained {
+            cid_number: event.cid_number.clone(),
+            role_code: event.role_code.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// 逐条核对本块公权/私权机构模块发出的任职届满事件。
+pub fn check_transition<FParent, FPost>(
+    read_parent: FParent,
+    read_post: FPost,
+) -> Result<(), GuardError>
+where
+    FParent: Fn(&[u8]) -> Option<Vec<u8>>,
+    FPost: Fn(&[u8]) -> Option<Vec<u8>>,
+{
+    let events =
+        runtime_policy::decode_events(&read_post).map_err(GuardError::EventsDecodeFailed)?;
+    for record in &events {
+        let (pallet, encoded) = match &record.event {
+            RuntimeEvent::PublicManage(event) => (PUBLIC_MANAGE_PALLET, event.encode()),
+            RuntimeEvent::PrivateManage(event) => (PRIVATE_MANAGE_PALLET, event.encode()),
+            _ => continue,
+        };
+        if let Some(expired) = decode_expired(pallet, &encoded)? {
+            check_expired(pallet, &record.phase, &expired, &read_parent, &read_post)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entity_primitives::{InstitutionAssignmentSource, InstitutionAssignmentStatus};
+    use frame_system::EventRecord;
+    use sp_runtime::AccountId32;
+
+    const ORDINARY_CID: &[u8] = b"ORDINARY-CID";
+    const DYNAMIC_ROLE: &[u8] = b"DYN-ROLE";
+    const TODAY: u32 = 20_635;
+
+    fn account(index: u8) -> [u8; 32] {
+        [index; 32]
+    }
+
+    fn assignment(account_id: [u8; 32], term_end: u32) -> DecodedAssignment {
+        InstitutionAdminAssignment {
+            cid_number: ORDINARY_CID.to_vec(),
+            account_id,
+            role_code: DYNAMIC_ROLE.to_vec(),
+            term_start: 20_000,
+            term_end,
+            assignment_source: InstitutionAssignmentSource::PopularElection,
+            assignment_source_ref: b"election".to_vec(),
+            assignment_status: InstitutionAssignmentStatus::Active,
+        }
+    }
+
+    fn expired_event(
+        cid_number: &[u8],
+        role_code: &[u8],
+        account_id: [u8; 32],
+        term_end: u32,
+        phase: Phase,
+    ) -> EventRecord<RuntimeEvent, sp_core::H256> {
+        EventRecord {
+            phase,
+            event: RuntimeEvent::PublicManage(
+                public_manage::pallet::Event::InstitutionRoleAssignmentExpired {
+                    cid_number: cid_number.to_vec().try_into().expect("cid fits"),
+                    role_code: role_code.to_vec().try_into().expect("role code fits"),
+                    account_id: AccountId32::new(account_id),
+                    term_end,
+                    legal_representative_cleared: false,
+                },
+            ),
+            topics: vec![],
+        }
+    }
+
+    fn state(
+        assignments: Vec<DecodedAssignment>,
+        events: Vec<EventRecord<RuntimeEvent, sp_core::H256>>,
+    ) -> BTreeMap<Vec<u8>, Vec<u8>> {
+        BTreeMap::from([
+            (
+                storage_key::role_assignments(PUBLIC_MANAGE_PALLET, ORDINARY_CID, DYNAMIC_ROLE),
+                assignments.encode(),
+            ),
+            (
+                storage_key::timestamp_now(),
+                (u64::from(TODAY) * MILLIS_PER_DAY).encode(),
+            ),
+            (runtime_policy::storage_key::events(), events.encode()),
+        ])
+    }
+
+    fn check(
+        parent: &BTreeMap<Vec<u8>, Vec<u8>>,
+        post: &BTreeMap<Vec<u8>, Vec<u8>>,
+    ) -> Result<(), GuardError> {
+        check_transition(|key| parent.get(key).cloned(), |key| post.get(key).cloned())
+    }
+
+    #[test]
+    fn expired_variant_index_is_resolved_from_runtime_metadata() {
+        let event = expired_event(
+            ORDINARY_CID,
+            DYNAMIC_ROLE,
+            account(1),
+            1,
+            Phase::Finalization,
+        );
+        let RuntimeEvent::PublicManage(inner) = event.event else {
+            panic!("test event must be a PublicManage event");
+        };
+        let decoded = decode_expired(PUBLIC_MANAGE_PALLET, &inner.encode())
+            .expect("expired event must decode")
+            .expect("expired event must be recognized");
+        assert_eq!(decoded.account_id, account(1));
+        assert!(expired_variant_indices().contains_key(PRIVATE_MANAGE_PALLET));
+    }
+
+    #[test]
+    fn genuine_expiry_removed_on_idle_passes() {
+        let parent = state(
+            vec![
+                assignment(account(1), TODAY - 1),
+                assignment(account(2), TODAY + 30),
+            ],
+            vec![],
+        );
+        let post = state(
+            vec![assignment(account(2), TODAY + 30)],
+            vec![expired_event(
+                ORDINARY_CID,
+                DYNAMIC_ROLE,
+                account(1),
+                TODAY - 1,
+                Phase::Finalization,
+            )],
+        );
+        assert_eq!(check(&parent, &post), Ok(()));
+    }
+
+    #[test]
+    fn expiry_must_match_a_removed_and_ended_assignment() {
+        let parent = state(vec![assignment(account(1), TODAY)], vec![]);
+        let not_ended = state(
+            vec![],
+            vec![expired_event(
+                ORDINARY_CID,
+                DYNAMIC_ROLE,
+                account(1),
+                TODAY,
+                Phase::Finalization,
+            )],
+        );
+        assert_eq!(
+            check(&parent, &not_ended),
+            Err(GuardError::TermNotEnded {
+                term_end: TODAY,
+                current_day: TODAY,
+            })
+        );
+
+        let parent = state(vec![assignment(account(1), TODAY - 1)], vec![]);
+        let retained = state(
+            vec![assignment(account(1), TODAY - 1)],
+            vec![expired_event(
+                ORDINARY_CID,
+                DYNAMIC_ROLE,
+                account(1),
+                TODAY - 1,
+                Phase::Finalization,
+            )],
+        );
+        assert!(matches!(
+            check(&parent, &retained),
+            Err(GuardError::ExpiredAssignmentRetained { .. })
+        ));
+
+        let unknown = state(
+            vec![],
+            vec![expired_event(
+                ORDINARY_CID,
+                DYNAMIC_ROLE,
+                account(9),
+                TODAY - 1,
+                Phase::Finalization,
+            )],
+        );
+        assert!(matches!(
+            check(&parent, &unknown),
+            Err(GuardError::ExpiredAssignmentNotFound { .. })
+        ));
+
+        let in_extrinsic = state(
+            vec![],
+            vec![expired_event(
+                ORDINARY_CID,
+                DYNAMIC_ROLE,
+                account(1),
+                TODAY - 1,
+                Phase::ApplyExtrinsic(0),
+            )],
+        );
+        assert_eq!(
+            check(&parent, &in_extrinsic),
+            Err(GuardError::ExpiredOutsideFinalization)
+        );
+    }
+
+    #[test]
+    fn fixed_and_member_seats_never_expire() {
+        let institution = primitives::governance_skeleton::fixed_institutions()[0];
+        let role = primitives::governance_skeleton::fixed_role_specs(institution.code)[0];
+        let parent = state(vec![], vec![]);
+        let fixed = state(
+            vec![],
+            vec![expired_event(
+                institution.cid_number.as_bytes(),
+                role.role_code,
+                account(1),
+                TODAY - 1,
+                Phase::Finalization,
+            )],
+        );
+        assert!(matches!(
+            check(&parent, &fixed),
+            Err(GuardError::ProtectedSeatExpired { .. })
+        ));
+
+        let spec = primitives::institution_constraints::member_composition_specs()[0];
+        let member = state(
+            vec![],
+            vec![expired_event(
+                spec.institution.cid_number.as_bytes(),
+                spec.role_code,
+                account(1),
+                TODAY - 1,
+                Phase::Finalization,
+            )],
+        );
+        assert!(matches!(
+            check(&parent, &member),
+            Err(GuardError::ProtectedSeatExpired { .. })
+        ));
+    }
+}
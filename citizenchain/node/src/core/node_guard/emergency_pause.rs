@@ -0,0 +1,164 @@
+//! 紧急暂停登记表节点永久策略。
+//!
+//! runtime 的暂停模块本身会拒绝受保护 pallet，但暂停登记表直接决定 `BaseCallFilter` 的放行结果，
+//! 一旦被异常 runtime 写入 `System`/`Grandpa`/投票引擎等目标，整条链可能失去出块或自救能力。
+//! 节点因此独立解码 `EmergencyPause::PausedTargets` 的键：任何新写入或已存在的暂停目标都必须
+//! 格式合法，且不得指向受保护 pallet。删除（解除或到期清理）始终放行。
+
+use std::collections::BTreeMap;
+
+use codec::Decode;
+use primitives::emergency_pause::PauseTarget;
+use sp_core::hashing::twox_128;
+
+const PALLET_NAME: &[u8] = b"EmergencyPause";
+
+/// `Blake2_128Concat` 哈希前缀长度。
+const BLAKE2_128_LEN: usize = 16;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum GuardError {
+    StorageKeyDecodeFailed,
+    MalformedTarget(PauseTarget),
+    ProtectedTarget(PauseTarget),
+}
+
+pub mod storage_key {
+    use super::*;
+
+    pub fn pallet_prefix() -> [u8; 16] {
+        twox_128(PALLET_NAME)
+    }
+
+    pub fn paused_targets_prefix() -> Vec<u8> {
+        crate::shared::storage_keys::prefix(PALLET_NAME, b"PausedTargets")
+    }
+
+    pub fn is_relevant(key: &[u8]) -> bool {
+        key.starts_with(&paused_targets_prefix())
+    }
+}
+
+fn decode_target(key: &[u8]) -> Result<PauseTarget, GuardError> {
+    let prefix_len = storage_key::paused_targets_prefix().len();
+    let mut input = key
+        .get(prefix_len + BLAKE2_128_LEN..)
+        .ok_or(GuardError::StorageKeyDecodeFailed)?;
+    let target = PauseTarget::decode(&mut input).map_err(|_| GuardError::StorageKeyDecodeFailed)?;
+    if !input.is_empty() {
+        return Err(GuardError::StorageKeyDecodeFailed);
+    }
+    Ok(target)
+}
+
+fn check_target_key(key: &[u8]) -> Result<(), GuardError> {
+    let target = decode_target(key)?;
+    if !target.is_well_formed() {
+        return Err(GuardError::MalformedTarget(target));
+    }
+    if target.is_protected() {
+        return Err(GuardError::ProtectedTarget(target));
+    }
+    Ok(())
+}
+
+/// 普通区块:只复核本块新写入的暂停目标，删除记录不受限制。
+pub fn check_transition(post_delta: &BTreeMap<Vec<u8>, Option<Vec<u8>>>) -> Result<(), GuardError> {
+    post_delta
+        .iter()
+        .filter(|(key, value)| value.is_some() && storage_key::is_relevant(key))
+        .try_for_each(|(key, _)| check_target_key(key))
+}
+
+/// 完整下载态复核全部暂停目标。
+pub fn check_imported_state<'a, I>(entries: I) -> Result<(), GuardError>
+where
+    I: IntoIterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>,
+{
+    entries
+        .into_iter()
+        .filter(|(key, _)| storage_key::is_relevant(key))
+        .try_for_each(|(key, _)| check_target_key(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codec::Encode;
+    use sp_core::hashing::blake2_128;
+
+    fn target(pallet_name: &[u8], call_name: Option<&[u8]>) -> PauseTarget {
+        PauseTarget {
+            pallet_name: pallet_name.to_vec().try_into().expect("pallet name fits"),
+            call_name: call_name.map(|name| name.to_vec().try_into().expect("call name fits")),
+        }
+    }
+
+    fn key_of(target: &PauseTarget) -> Vec<u8> {
+        let encoded = target.encode();
+        let mut key = storage_key::paused_targets_prefix();
+        key.extend_from_slice(&blake2_128(&encoded));
+        key.extend_from_slice(&encoded);
+        key
+    }
+
+    fn delta(entries: &[(Vec<u8>, Option<Vec<u8>>)]) -> BTreeMap<Vec<u8>, Option<Vec<u8>>> {
+        entries.iter().cloned().collect()
+    }
+
+    #[test]
+    fn raw_keys_match_runtime_contract() {
+        let mut expected = twox_128(b"EmergencyPause").to_vec();
+        expected.extend_from_slice(&twox_128(b"PausedTargets"));
+        assert_eq!(storage_key::paused_targets_prefix(), expected);
+        assert!(storage_key::paused_targets_prefix().starts_with(&storage_key::pallet_prefix()));
+        let counter = crate::shared::storage_keys::prefix(PALLET_NAME, b"CounterForPausedTargets");
+        assert!(!storage_key::is_relevant(&counter));
+    }
+
+    #[test]
+    fn new_pause_must_not_target_protected_pallet() {
+        let allowed = target(b"SquarePost", Some(b"subscribe"));
+        assert_eq!(
+            check_transition(&delta(&[(key_of(&allowed), Some(vec![0]))])),
+            Ok(())
+        );
+        let grandpa = target(b"Grandpa", None);
+        assert_eq!(
+            check_transition(&delta(&[(key_of(&grandpa), Some(vec![0]))])),
+            Err(GuardError::ProtectedTarget(grandpa.clone()))
+        );
+        // 清理历史异常记录不应被拦截。
+        assert_eq!(
+            check_transition(&delta(&[(key_of(&grandpa), None)])),
+            Ok(())
+        );
+        let malformed = target(b"Square Post", None);
+        assert_eq!(
+            check_transition(&delta(&[(key_of(&malformed), Some(vec![0]))])),
+            Err(GuardError::MalformedTarget(malformed))
+        );
+        let mut truncated = storage_key::paused_targets_prefix();
+        truncated.extend_from_slice(&[0u8; 4]);
+        assert_eq!(
+            check_transition(&delta(&[(truncated, Some(vec![0]))])),
+            Err(GuardError::StorageKeyDecodeFailed)
+        );
+    }
+
+    #[test]
+    fn imported_state_rejects_protected_targets() {
+        let state = BTreeMap::from([
+            (key_of(&target(b"MultisigTransfer", None)), vec![0]),
+            (key_of(&target(b"JointVote", Some(b"joint_vote"))), vec![0]),
+        ]);
+        assert_eq!(
+            check_imported_state(state.iter()),
+            Err(GuardError::ProtectedTarget(target(
+                b"JointVote",
+                Some(b"joint_vote")
+            )))
+        );
+        assert_eq!(check_imported_state(BTreeMap::new().iter()), Ok(()));
+    }
+}
@@ -761,6 +761,18 @@ where
     Ok(())
 }
 
+/// 受保护创世机构的固定岗位（含法定代表人）；其席位只能经治理原子轮换。
+pub(super) fn is_fixed_role(cid_number: &[u8], role_code: &[u8]) -> bool {
+    protected_institutions()
+        .iter()
+        .find(|institution| institution.cid_number.as_bytes() == cid_number)
+        .is_some_and(|institution| {
+            expected_roles(institution)
+                .iter()
+                .any(|role| role.role_code == role_code)
+        })
+}
+
 /// 普通区块仅复核实际被修改的受保护创世机构；runtime 升级仍全量复核。
 pub(super) fn check_affected_institutions<F>(
     delta: &BTreeMap<Vec<u8>, Option<Vec<u8>>>,
@@ -2,16 +2,19 @@
 //!
 //! 公民宪法是整条链最高规则，继续由独立的 `ConstitutionGuard` 在本包装器外层先行检查。
 //! 本模块只收口**除宪法外**的节点永久规则：统一预执行正常区块、统一提取后置 storage delta，
-//! 再把同一份检查上下文交给内部策略。当前已注册固定治理骨架、三类固定发行、GenesisPallet
-//! 五字段与 CID 生命周期；后续非宪法永久规则仍必须加在本包装器内部，不得新增平行包装器。
+//! 再把同一份检查上下文交给内部策略。当前已注册固定治理骨架、三类固定发行、决议发行分期归属、
+//! 紧急暂停受保护目标、岗位任职届满清理、GenesisPallet 五字段与 CID 生命周期；后续非宪法永久规则仍必须加在本包装器内部，不得新增平行包装器。
 
+mod assignment_expiry;
 mod cid_lifecycle;
 mod citizen_issuance;
+mod emergency_pause;
 mod fullnode_issuance;
 mod genesis_pallet;
 mod governance_skeleton;
 mod national_body_composition;
 mod provincialbank_interest;
+mod resolution_vesting;
 mod runtime_policy;
 
 use std::collections::BTreeMap;
@@ -155,6 +158,8 @@ struct ImportedPolicyState {
     citizen_issuance: BTreeMap<Vec<u8>, Vec<u8>>,
     genesis_pallet: BTreeMap<Vec<u8>, Vec<u8>>,
     provincialbank_interest: BTreeMap<Vec<u8>, Vec<u8>>,
+    resolution_vesting: BTreeMap<Vec<u8>, Vec<u8>>,
+    emergency_pause: BTreeMap<Vec<u8>, Vec<u8>>,
     runtime_policy: BTreeMap<Vec<u8>, Vec<u8>>,
     cid: BTreeMap<Vec<u8>, Vec<u8>>,
     scanned: usize,
@@ -169,6 +174,8 @@ struct ImportedPolicyStats {
     citizen_issuance: usize,
     genesis_pallet: usize,
     provincialbank_interest: usize,
+    resolution_vesting: usize,
+    emergency_pause: usize,
     runtime_policy: usize,
     cid: usize,
 }
@@ -183,6 +190,8 @@ impl ImportedPolicyState {
             citizen_issuance: self.citizen_issuance.len(),
             genesis_pallet: self.genesis_pallet.len(),
             provincialbank_interest: self.provincialbank_interest.len(),
+            resolution_vesting: self.resolution_vesting.len(),
+            emergency_pause: self.emergency_pause.len(),
             runtime_policy: self.runtime_policy.len(),
             cid: self.cid.len(),
         }
@@ -233,6 +242,12 @@ where
                 .provincialbank_interest
                 .insert(key.clone(), value.clone());
         }
+        if resolution_vesting::storage_key::is_relevant(key) {
+            state.resolution_vesting.insert(key.clone(), value.clone());
+        }
+        if emergency_pause::storage_key::is_relevant(key) {
+            state.emergency_pause.insert(key.clone(), value.clone());
+        }
         if runtime_policy::storage_key::is_relevant(key) {
             state.runtime_policy.insert(key.clone(), value.clone());
         }
@@ -265,6 +280,10 @@ where
         .map_err(|e| format!("创世模块:{e:?}"))?;
     provincialbank_interest::check_imported_genesis(state.provincialbank_interest.iter())
         .map_err(|e| format!("省储行固定发行:{e:?}"))?;
+    resolution_vesting::check_imported_state(state.resolution_vesting.iter())
+        .map_err(|e| format!("决议发行分期归属:{e:?}"))?;
+    emergency_pause::check_imported_state(state.emergency_pause.iter())
+        .map_err(|e| format!("紧急暂停登记表:{e:?}"))?;
     runtime_policy::check_imported_state(state.runtime_policy.iter())
         .map_err(|e| format!("手续费制度:{e}"))?;
     cid_lifecycle::check_imported_genesis(state.cid.iter(), cid_reference)
@@ -647,7 +666,7 @@ impl<I> NodeGuard<I> {
         let stats = verify_imported_state_params(params, self.cid_lifecycle.as_ref())?;
         log::debug!(
             target: "node-guard",
-            "完整状态单遍扫描:总键 {},治理 {},国家组成/固定阈值 {},全节点发行/账户 {},公民发行 {},创世模块 {},省储行固定发行 {},手续费制度 {},CID {}",
+            "完整状态单遍扫描:总键 {},治理 {},国家组成/固定阈值 {},全节点发行/账户 {},公民发行 {},创世模块 {},省储行固定发行 {},决议发行归属 {},紧急暂停 {},手续费制度 {},CID {}",
             stats.scanned,
             stats.governance,
             stats.national_body_composition,
@@ -655,6 +674,8 @@ impl<I> NodeGuard<I> {
             stats.citizen_issuance,
             stats.genesis_pallet,
             stats.provincialbank_interest,
+            stats.resolution_vesting,
+            stats.emergency_pause,
             stats.runtime_policy,
             stats.cid,
         );
@@ -822,6 +843,41 @@ impl<I> NodeGuard<I> {
             return Ok(true);
         }
 
+        if let Err(reason) =
+            resolution_vesting::check_transition(&post_delta, &read_parent, &read_post)
+        {
+            log::error!(
+                target: "node-guard",
+                "拒绝区块 #{} ({:?}):决议发行分期归属口径被破坏 —— {:?}",
+                params.header.number(),
+                params.post_hash(),
+                reason,
+            );
+            return Ok(true);
+        }
+
+        if let Err(reason) = emergency_pause::check_transition(&post_delta) {
+            log::error!(
+                target: "node-guard",
+                "拒绝区块 #{} ({:?}):紧急暂停登记表写入受保护目标 —— {:?}",
+                params.header.number(),
+                params.post_hash(),
+                reason,
+            );
+            return Ok(true);
+        }
+
+        if let Err(reason) = assignment_expiry::check_transition(&read_parent, &read_post) {
+            log::error!(
+                target: "node-guard",
+                "拒绝区块 #{} ({:?}):岗位任职届满清理非法 —— {:?}",
+                params.header.number(),
+                params.post_hash(),
+                reason,
+            );
+            return Ok(true);
+        }
+
         if let Err(reason) = verify_finalize_issuance(
             &pre_delta,
             &post_delta,
@@ -3,6 +3,8 @@
 //! 节点直接使用编译期 `CHINA_CH`、利率和年度常量复算，不读取 runtime metadata 或 runtime API。
 //! 43 个 `stake_account` 的创世本金必须永久保持原样；每个年度边界的利息只能在 finalize
 //! 发到对应 `main_account`，并与累计量、最近审计及统一原生发行计划逐项闭环。
+//! 决议发行的分期部分在执行时已整笔计入总发行量，释放只解锁不增发；年度结算块的 finalize
+//! 因而不得改动决议发行累计量或归属锁定量，否则已排期未释放的发行会混入本年度利息闭环。
 
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -18,7 +20,7 @@ use primitives::{
     pow_const::BLOCKS_PER_YEAR,
 };
 
-use super::{FinalizeIssuancePlan, MAccountInfo};
+use super::{resolution_vesting, FinalizeIssuancePlan, MAccountInfo};
 
 const PALLET_NAME: &[u8] = b"ProvincialBankInterest";
 const SYSTEM_PALLET: &[u8] = b"System";
@@ -48,6 +50,7 @@ pub enum GuardError {
     StakeAccountMissing([u8; 32]),
     StakeAccountDecodeFailed([u8; 32]),
     StakeAccountChanged([u8; 32]),
+    ResolutionIssuanceChangedInFinalize,
 }
 
 pub mod storage_key {
@@ -329,6 +332,14 @@ where
     }
 
     if expected_year(block) > expected_year(block.saturating_sub(1)) {
+        for key in [
+            resolution_vesting::storage_key::total_issued(),
+            resolution_vesting::storage_key::total_vesting_locked(),
+        ] {
+            if pre(&key) != post(&key) {
+                return Err(GuardError::ResolutionIssuanceChangedInFinalize);
+            }
+        }
         let year = expected_year(block);
         for bank in CHINA_CH.iter() {
             issuance_plan
@@ -560,6 +571,60 @@ mod tests {
         .expect("43 家余额增量与总发行量应和固定发行计划完全闭环");
     }
 
+    #[test]
+    fn annual_finalize_must_not_touch_scheduled_resolution_issuance() {
+        let mut parent = genesis_state();
+        parent.insert(
+            resolution_vesting::storage_key::total_issued(),
+            500u128.encode(),
+        );
+        parent.insert(
+            resolution_vesting::storage_key::total_vesting_locked(),
+            300u128.encode(),
+        );
+        let pre = parent.clone();
+        let mut post = parent.clone();
+        let audit = expected_annual_audit(1).unwrap();
+        post.insert(storage_key::last_settled_year(), 1u32.encode());
+        post.insert(
+            storage_key::total_interest_issued(),
+            audit.total_interest.encode(),
+        );
+        post.insert(storage_key::last_interest_audit(), audit.encode());
+        let check = |post: &BTreeMap<Vec<u8>, Vec<u8>>| {
+            check_transition(
+                BLOCKS_PER_YEAR as u32,
+                &BTreeMap::new(),
+                &BTreeMap::new(),
+                &|key| parent.get(key).cloned(),
+                &|key| pre.get(key).cloned(),
+                &|key| post.get(key).cloned(),
+                &mut FinalizeIssuancePlan::default(),
+            )
+        };
+        assert_eq!(check(&post), Ok(()));
+
+        // 年度结算中释放或新增归属锁定都会让未释放发行混入利息闭环。
+        let mut released = post.clone();
+        released.insert(
+            resolution_vesting::storage_key::total_vesting_locked(),
+            200u128.encode(),
+        );
+        assert_eq!(
+            check(&released),
+            Err(GuardError::ResolutionIssuanceChangedInFinalize)
+        );
+        let mut reissued = post.clone();
+        reissued.insert(
+            resolution_vesting::storage_key::total_issued(),
+            600u128.encode(),
+        );
+        assert_eq!(
+            check(&reissued),
+            Err(GuardError::ResolutionIssuanceChangedInFinalize)
+        );
+    }
+
     #[test]
     fn annual_transition_rejects_wrong_audit_and_stake_mutation() {
         let parent = genesis_state();
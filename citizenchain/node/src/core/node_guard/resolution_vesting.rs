@@ -0,0 +1,200 @@
+//! 决议发行分期归属节点永久策略。
+//!
+//! 分期分配在执行时整笔铸入并计入 `ResolutionIssuance::TotalIssued`，未释放部分记在
+//! `TotalVestingLocked`。节点只核对两条与 runtime 实现无关的口径：锁定量永远不超过累计发行量；
+//! 单块内锁定量的增加必须由同块决议发行增量覆盖，释放只能让锁定量下降，不能凭空新增锁定额。
+
+use std::collections::BTreeMap;
+
+use codec::Decode;
+use sp_core::hashing::twox_128;
+
+const PALLET_NAME: &[u8] = b"ResolutionIssuance";
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum GuardError {
+    StorageValueDecodeFailed(&'static str),
+    LockedExceedsIssued {
+        locked: u128,
+        issued: u128,
+    },
+    TotalIssuedDecreased {
+        before: u128,
+        after: u128,
+    },
+    UnbackedVestingLock {
+        locked_increase: u128,
+        issued_increase: u128,
+    },
+}
+
+pub mod storage_key {
+    use super::*;
+
+    pub fn pallet_prefix() -> [u8; 16] {
+        twox_128(PALLET_NAME)
+    }
+
+    pub fn total_issued() -> Vec<u8> {
+        crate::shared::storage_keys::prefix(PALLET_NAME, b"TotalIssued")
+    }
+
+    pub fn total_vesting_locked() -> Vec<u8> {
+        crate::shared::storage_keys::prefix(PALLET_NAME, b"TotalVestingLocked")
+    }
+
+    pub fn is_relevant(key: &[u8]) -> bool {
+        key == total_issued().as_slice() || key == total_vesting_locked().as_slice()
+    }
+}
+
+fn decode_or_zero(raw: Option<Vec<u8>>, label: &'static str) -> Result<u128, GuardError> {
+    let Some(bytes) = raw else {
+        return Ok(0);
+    };
+    let mut input = bytes.as_slice();
+    let value =
+        u128::decode(&mut input).map_err(|_| GuardError::StorageValueDecodeFailed(label))?;
+    if !input.is_empty() {
+        return Err(GuardError::StorageValueDecodeFailed(label));
+    }
+    Ok(value)
+}
+
+fn read_totals<F>(read: &F) -> Result<(u128, u128), GuardError>
+where
+    F: Fn(&[u8]) -> Option<Vec<u8>>,
+{
+    let issued = decode_or_zero(
+        read(&storage_key::total_issued()),
+        "ResolutionIssuance::TotalIssued",
+    )?;
+    let locked = decode_or_zero(
+        read(&storage_key::total_vesting_locked()),
+        "ResolutionIssuance::TotalVestingLocked",
+    )?;
+    Ok((issued, locked))
+}
+
+fn check_locked_within_issued(issued: u128, locked: u128) -> Result<(), GuardError> {
+    if locked > issued {
+        return Err(GuardError::LockedExceedsIssued { locked, issued });
+    }
+    Ok(())
+}
+
+/// 普通区块:两项累计值都未变化时直接放行，否则按父状态与块后状态复核。
+pub fn check_transition<FParent, FPost>(
+    post_delta: &BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+    read_parent: &FParent,
+    read_post: &FPost,
+) -> Result<(), GuardError>
+where
+    FParent: Fn(&[u8]) -> Option<Vec<u8>>,
+    FPost: Fn(&[u8]) -> Option<Vec<u8>>,
+{
+    if !post_delta.keys().any(|key| storage_key::is_relevant(key)) {
+        return Ok(());
+    }
+    let (issued_before, locked_before) = read_totals(read_parent)?;
+    let (issued_after, locked_after) = read_totals(read_post)?;
+    check_locked_within_issued(issued_after, locked_after)?;
+    if issued_after < issued_before {
+        return Err(GuardError::TotalIssuedDecreased {
+            before: issued_before,
+            after: issued_after,
+        });
+    }
+    let locked_increase = locked_after.saturating_sub(locked_before);
+    let issued_increase = issued_after - issued_before;
+    if locked_increase > issued_increase {
+        return Err(GuardError::UnbackedVestingLock {
+            locked_increase,
+            issued_increase,
+        });
+    }
+    Ok(())
+}
+
+/// 完整下载态只核对锁定量不超过累计发行量。
+pub fn check_imported_state<'a, I>(entries: I) -> Result<(), GuardError>
+where
+    I: IntoIterator<Item = (&'a Vec<u8>, &'a Vec<u8>)>,
+{
+    let map: BTreeMap<Vec<u8>, Vec<u8>> = entries
+        .into_iter()
+        .filter(|(key, _)| storage_key::is_relevant(key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    let (issued, locked) = read_totals(&|key: &[u8]| map.get(key).cloned())?;
+    check_locked_within_issued(issued, locked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codec::Encode;
+
+    fn state(issued: u128, locked: u128) -> BTreeMap<Vec<u8>, Vec<u8>> {
+        BTreeMap::from([
+            (storage_key::total_issued(), issued.encode()),
+            (storage_key::total_vesting_locked(), locked.encode()),
+        ])
+    }
+
+    fn check(
+        parent: &BTreeMap<Vec<u8>, Vec<u8>>,
+        post: &BTreeMap<Vec<u8>, Vec<u8>>,
+    ) -> Result<(), GuardError> {
+        let delta = post
+            .iter()
+            .map(|(key, value)| (key.clone(), Some(value.clone())))
+            .collect();
+        check_transition(
+            &delta,
+            &|key: &[u8]| parent.get(key).cloned(),
+            &|key: &[u8]| post.get(key).cloned(),
+        )
+    }
+
+    #[test]
+    fn raw_keys_match_runtime_contract() {
+        let mut expected = twox_128(b"ResolutionIssuance").to_vec();
+        expected.extend_from_slice(&twox_128(b"TotalVestingLocked"));
+        assert_eq!(storage_key::total_vesting_locked(), expected);
+        assert!(storage_key::total_vesting_locked().starts_with(&storage_key::pallet_prefix()));
+    }
+
+    #[test]
+    fn vesting_lock_must_be_backed_by_same_block_issuance() {
+        assert_eq!(check(&state(0, 0), &state(4300, 200)), Ok(()));
+        assert_eq!(check(&state(4300, 200), &state(4300, 170)), Ok(()));
+        assert_eq!(
+            check(&state(4300, 200), &state(4300, 300)),
+            Err(GuardError::UnbackedVestingLock {
+                locked_increase: 100,
+                issued_increase: 0,
+            })
+        );
+        assert_eq!(
+            check(&state(4300, 0), &state(4200, 0)),
+            Err(GuardError::TotalIssuedDecreased {
+                before: 4300,
+                after: 4200,
+            })
+        );
+    }
+
+    #[test]
+    fn locked_amount_never_exceeds_total_issued() {
+        assert_eq!(
+            check_imported_state(state(100, 101).iter()),
+            Err(GuardError::LockedExceedsIssued {
+                locked: 101,
+                issued: 100,
+            })
+        );
+        assert_eq!(check_imported_state(state(100, 100).iter()), Ok(()));
+        assert_eq!(check_imported_state(BTreeMap::new().iter()), Ok(()));
+    }
+}
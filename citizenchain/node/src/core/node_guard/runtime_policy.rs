@@ -172,10 +172,10 @@ fn signed_account(xt: &UncheckedExtrinsic) -> Option<[u8; 32]> {
 fn protected_fee(xt: &UncheckedExtrinsic) -> Option<ProtectedFee> {
     let payer_account_id = signed_account(xt)?;
     match &xt.function {
-        RuntimeCall::OnchainTransaction(onchain::pallet::Call::transfer_with_remark {
-            amount,
-            ..
-        }) => Some(ProtectedFee::Onchain {
+        RuntimeCall::OnchainTransaction(
+            onchain::pallet::Call::transfer_with_remark { amount, .. }
+            | onchain::pallet::Call::pay_invoice { amount, .. },
+        ) => Some(ProtectedFee::Onchain {
             payer_account_id,
             amount: *amount,
         }),
@@ -212,7 +212,9 @@ fn expected_fee(rule: ProtectedFee) -> ([u8; 32], u128) {
     }
 }
 
-fn decode_events<F>(read_post: &F) -> Result<Vec<EventRecord<RuntimeEvent, sp_core::H256>>, String>
+pub(super) fn decode_events<F>(
+    read_post: &F,
+) -> Result<Vec<EventRecord<RuntimeEvent, sp_core::H256>>, String>
 where
     F: Fn(&[u8]) -> Option<Vec<u8>>,
 {
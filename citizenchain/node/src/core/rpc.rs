@@ -72,6 +72,8 @@ pub struct FullDeps<C, P> {
     pub cpu_hashrate_fn: fn() -> f64,
     /// GPU 哈希率查询函数（仅在 gpu-mining feature 启用且有 GPU 时为 Some）。
     pub gpu_hashrate_fn: Option<fn() -> f64>,
+    /// 矿池 worker 统计查询函数（仅在启用 `--pool-listen` 时为 Some）。
+    pub pool_workers_fn: Option<fn() -> Vec<crate::mining::pool_server::PoolWorkerStats>>,
     /// 清算行节点的 RPC 命名空间实现。
     /// None 表示本节点未以清算行角色启动,跳过 `offchain_*` RPC 注入。
     pub offchain_clearing_rpc:
@@ -298,6 +300,7 @@ where
         keystore,
         cpu_hashrate_fn,
         gpu_hashrate_fn,
+        pool_workers_fn,
         offchain_clearing_rpc,
     } = deps;
 
@@ -384,6 +387,79 @@ where
         })?;
     }
 
+    // 法律版本逐款差异 RPC:同样 RAW 读两版 LawVersion,在节点侧解码比对,
+    // 不采信 runtime `LegislationApi::law_diff` 的返回。参数:(law_id, from_version, to_version)。
+    {
+        let client = client.clone();
+        use crate::core::constitution;
+        module.register_method("legislation_getLawDiff", move |params, _, _| {
+            use jsonrpsee::types::error::ErrorObject;
+            use sp_storage::StorageKey;
+
+            let (law_id, from_version, to_version): (u64, u32, u32) = params.parse()?;
+            let best_hash = client.info().best_hash;
+            let read_version = |version: u32| {
+                client
+                    .storage(
+                        best_hash,
+                        &StorageKey(constitution::storage_key::law_version(law_id, version)),
+                    )
+                    .map_err(|e| {
+                        ErrorObject::owned(-1, format!("读取链上法律存储失败: {e}"), None::<()>)
+                    })?
+                    .map(|d| d.0)
+                    .ok_or_else(|| {
+                        ErrorObject::owned(
+                            -1,
+                            format!("法律版本不存在(law_id={law_id}, v{version})"),
+                            None::<()>,
+                        )
+                    })
+            };
+            let from_bytes = read_version(from_version)?;
+            let to_bytes = read_version(to_version)?;
+            let changes = constitution::law_version_diff(&from_bytes, &to_bytes)
+                .map_err(|e| ErrorObject::owned(-1, e, None::<()>))?;
+
+            Ok::<serde_json::Value, jsonrpsee::types::ErrorObjectOwned>(serde_json::json!({
+                "law_id": law_id,
+                "from_version": from_version,
+                "to_version": to_version,
+                "changes": changes,
+                "source": "legislation-raw",
+            }))
+        })?;
+    }
+
+    // 法律日落排程 RPC:按到期顺序枚举 `SunsetSchedule` 并回查法律标题,供桌面端提示即将失效/复审的法律。
+    {
+        let client = client.clone();
+        use crate::core::constitution;
+        module.register_method("legislation_getUpcomingSunsets", move |_params, _, _| {
+            use jsonrpsee::types::error::ErrorObject;
+            use sp_storage::StorageKey;
+
+            let best_hash = client.info().best_hash;
+            let prefix = StorageKey(constitution::storage_key::sunset_schedule_prefix());
+            let schedule_keys = client
+                .storage_keys(best_hash, Some(&prefix), None)
+                .map_err(|e| ErrorObject::owned(-1, format!("枚举日落排程失败: {e}"), None::<()>))?
+                .map(|key| key.0);
+            let sunsets = constitution::upcoming_sunsets(schedule_keys, |key: &[u8]| {
+                client
+                    .storage(best_hash, &StorageKey(key.to_vec()))
+                    .map(|opt| opt.map(|d| d.0))
+                    .map_err(|e| format!("读取链上法律存储失败: {e}"))
+            })
+            .map_err(|e| ErrorObject::owned(-1, e, None::<()>))?;
+
+            Ok::<serde_json::Value, jsonrpsee::types::ErrorObjectOwned>(serde_json::json!({
+                "sunsets": sunsets,
+                "source": "legislation-raw",
+            }))
+        })?;
+    }
+
     // sync_state_genLightSyncState: 返回小体积 checkpoint,供 CitizenApp 注入
     // smoldot chainspec。旧的 full spec 响应会超过 RPC 限制,这里不再返回完整 chainspec。
     {
@@ -405,6 +481,12 @@ where
         module.register_method("mining_gpuHashrate", move |_, _, _| get_hashrate() as u64)?;
     }
 
+    // 矿池 worker 统计 RPC：mining_poolWorkers
+    // 返回值：在线 worker 列表（份额计数、自报与估算算力）。
+    if let Some(get_workers) = pool_workers_fn {
+        module.register_method("mining_poolWorkers", move |_, _, _| get_workers())?;
+    }
+
     // reward_bindAccount(reward_account_id: String)
     // 由 node 端签名并提交 bind_reward_account 交易。
     {
@@ -408,12 +408,14 @@ pub fn new_full(
     mut config: Configuration,
     mining_threads: usize,
     gpu_device: Option<usize>,
+    // 节点内矿池服务(None=不对外分发工作)
+    pool_server: Option<crate::mining::pool_server::PoolServerConfig>,
     // 清算行机构 CID(None=本节点不做清算行角色)
     clearing_bank_cid_number: Option<String>,
     // 清算批次提交岗位码；必须与 CID 和签名钱包任职同时匹配
     clearing_bank_role_code: Option<String>,
-    // 解锁 `offchain::settlement::keystore` 的密码
-    clearing_bank_password: Option<String>,
+    // 清算行签名密钥解锁参数(口令 / PKCS#11),由 `command.rs` 在启动前读好
+    clearing_bank_unlock: crate::transaction::offchain::settlement::unlock::ClearingBankUnlock,
     // offchain::settlement::reserve 对账周期(秒),None=默认 300,Some(0)=关闭
     clearing_reserve_monitor_interval_secs: Option<u64>,
 ) -> Result<TaskManager, ServiceError> {
@@ -530,11 +532,17 @@ pub fn new_full(
         }
     };
 
+    // 矿池 worker 统计函数指针：仅在启用矿池服务时传入。
+    let pool_workers_fn: Option<fn() -> Vec<crate::mining::pool_server::PoolWorkerStats>> =
+        pool_server
+            .is_some()
+            .then_some(crate::mining::pool_server::pool_worker_stats as fn() -> _);
+
     // 清算行启动细节归入 `transaction::offchain::settlement::bootstrap`,service.rs 只做节点通用接线。
     let clearing_rpc_impl = crate::transaction::offchain::settlement::bootstrap::start_from_cli(
         clearing_bank_cid_number.as_deref(),
         clearing_bank_role_code.as_deref(),
-        clearing_bank_password.as_deref(),
+        clearing_bank_unlock,
         clearing_reserve_monitor_interval_secs,
         config.base_path.path(),
         client.clone(),
@@ -555,6 +563,7 @@ pub fn new_full(
                 keystore: keystore.clone(),
                 cpu_hashrate_fn: cpu_hashrate as fn() -> f64,
                 gpu_hashrate_fn,
+                pool_workers_fn,
                 // 清算行 RPC 命名空间(None 时跳过注入)
                 offchain_clearing_rpc: clearing_rpc_impl.clone(),
             };
@@ -641,8 +650,9 @@ pub fn new_full(
         use sp_blockchain::HeaderBackend;
 
         let client = client.clone();
-        let mining_enabled =
-            mining_threads > 0 || cfg!(feature = "gpu-mining") && gpu_device.is_some();
+        let mining_enabled = mining_threads > 0
+            || cfg!(feature = "gpu-mining") && gpu_device.is_some()
+            || pool_server.is_some();
         let stable_best_hash = Arc::new(Mutex::new(client.info().best_hash));
 
         move || {
@@ -725,6 +735,21 @@ pub fn new_full(
         }
     }
 
+    // 节点内矿池服务：远程 worker 只做哈希，出块签名仍用本节点 powr 密钥。
+    if let Some(pool_config) = pool_server {
+        let listen = pool_config.listen;
+        match crate::mining::pool_server::try_start(
+            pool_config,
+            worker.clone(),
+            pool_ready.clone(),
+            keystore.clone(),
+            author_public,
+        ) {
+            Ok(()) => log::info!("Mining pool server listening on {}", listen),
+            Err(e) => log::warn!("Mining pool server not started: {}", e),
+        }
+    }
+
     // 避免 unused 警告（无 gpu-mining feature 时 gpu_device 未使用）。
     #[cfg(not(feature = "gpu-mining"))]
     let _ = gpu_device;
@@ -835,6 +860,18 @@ mod tests {
         assert_eq!(pow_hash(&pre_hash, nonce), blake2_256(&payload));
     }
 
+    #[test]
+    fn pool_kernel_matches_consensus_pow_hash() {
+        // 远程 worker 与矿池服务按 pow_pool::kernel 判定份额，必须与共识哈希逐字节一致。
+        for nonce in [0u64, 1, 0x4000_0000_0000_0000, u64::MAX] {
+            let pre_hash = [nonce as u8; 32];
+            assert_eq!(
+                pow_pool::kernel::pow_hash(&pre_hash, nonce),
+                pow_hash(&pre_hash, nonce)
+            );
+        }
+    }
+
     #[test]
     fn hash_meets_difficulty_zero_always_false() {
         assert!(!hash_meets_difficulty(&[0u8; 32], U256::zero()));
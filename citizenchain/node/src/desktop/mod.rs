@@ -38,6 +38,7 @@ pub fn run_desktop() {
             settings::node_mode::get_node_mode,
             settings::node_mode::set_node_mode,
             settings::onchina_platform::get_onchina_platform,
+            settings::onchina_platform::get_onchina_backup_status,
             settings::onchina_platform::start_onchina_platform,
             settings::onchina_platform::stop_onchina_platform,
             settings::reward_account::get_reward_account,
@@ -56,6 +57,7 @@ pub fn run_desktop() {
             mining::network_overview::get_network_overview,
             other::other_tabs::get_other_tabs_content,
             other::other_tabs::get_runtime_constitution_document,
+            other::other_tabs::get_upcoming_law_sunsets,
             governance::get_governance_overview,
             governance::get_institution_detail,
             governance::balance_watch::start_governance_balance_watch,
@@ -85,6 +87,9 @@ pub fn run_desktop() {
             crate::transaction::multisig::commands::submit_multisig_safety_fund,
             crate::transaction::multisig::commands::build_multisig_sweep_request,
             crate::transaction::multisig::commands::submit_multisig_sweep,
+            crate::transaction::compose::commands::get_compose_call_catalog,
+            crate::transaction::compose::commands::build_compose_call_request,
+            crate::transaction::compose::commands::submit_compose_call,
             governance::runtime_upgrade::commands::get_pow_difficulty_params,
             governance::runtime_upgrade::commands::build_propose_upgrade_request,
             governance::runtime_upgrade::commands::submit_propose_upgrade,
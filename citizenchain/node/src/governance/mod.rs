@@ -6,6 +6,7 @@
 pub(crate) mod balance_watch;
 pub(crate) mod chain_query;
 pub(crate) mod institution;
+pub(crate) mod nonce_reservation;
 pub mod proposal;
 pub(crate) mod registry;
 pub mod runtime_upgrade;
@@ -0,0 +1,242 @@
+// 冷签交易 nonce 预留：签名请求生成到交易进池之间，同一账户的并发二维码
+// 各自占用不同 nonce，避免两张二维码签出同一 nonce 后互相顶替。
+//
+// 预留只存于内存，节点重启即清空（未进池的二维码本来也随 session 失效）：
+// - 未提交：签名 session 过期即释放；
+// - 已提交：链上 next index 越过该 nonce，或交易 era 已失效（无法再被打包）后释放；
+// - 验签 / dry-run / 提交失败：立即释放。
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Reservation {
+    nonce: u32,
+    request_id: String,
+    /// 签名 session 过期时间（秒）；仅约束未提交的预留。
+    session_expires_at: u64,
+    /// 交易 era 失效块号；immortal 交易为 `None`，只能等链上 nonce 越过。
+    death_block: Option<u64>,
+    submitted: bool,
+}
+
+impl Reservation {
+    fn is_live(&self, chain_nonce: u32, now: u64, best_block: u64) -> bool {
+        if self.nonce < chain_nonce {
+            return false;
+        }
+        if self.submitted {
+            match self.death_block {
+                Some(death) => best_block < death,
+                None => true,
+            }
+        } else {
+            self.session_expires_at >= now
+        }
+    }
+}
+
+/// 按账户登记的 nonce 预留表。
+#[derive(Debug, Default)]
+struct NonceBook {
+    accounts: HashMap<String, Vec<Reservation>>,
+}
+
+impl NonceBook {
+    /// 清理失效预留后，分配 `chain_nonce` 起第一个未被占用的 nonce。
+    fn reserve(
+        &mut self,
+        account_id: &str,
+        chain_nonce: u32,
+        request_id: &str,
+        session_expires_at: u64,
+        death_block: Option<u64>,
+        now: u64,
+        best_block: u64,
+    ) -> Result<u32, String> {
+        let reservations = self.accounts.entry(account_id.to_string()).or_default();
+        reservations.retain(|item| item.is_live(chain_nonce, now, best_block));
+        let mut nonce = chain_nonce;
+        while reservations.iter().any(|item| item.nonce == nonce) {
+            nonce = nonce
+                .checked_add(1)
+                .ok_or_else(|| "账户 nonce 已耗尽".to_string())?;
+        }
+        reservations.push(Reservation {
+            nonce,
+            request_id: request_id.to_string(),
+            session_expires_at,
+            death_block,
+            submitted: false,
+        });
+        Ok(nonce)
+    }
+
+    fn mark_submitted(&mut self, account_id: &str, nonce: u32, request_id: &str) {
+        if let Some(item) = self.find_mut(account_id, nonce, request_id) {
+            item.submitted = true;
+        }
+    }
+
+    fn release(&mut self, account_id: &str, nonce: u32, request_id: &str) {
+        if let Some(reservations) = self.accounts.get_mut(account_id) {
+            reservations.retain(|item| !(item.nonce == nonce && item.request_id == request_id));
+            if reservations.is_empty() {
+                self.accounts.remove(account_id);
+            }
+        }
+    }
+
+    /// `[next_index, nonce)` 之间每个 nonce 都有已提交的预留，
+    /// 即本交易在 future 队列中只是等待前序交易进块，而不是永久空洞。
+    fn predecessors_submitted(&self, account_id: &str, next_index: u32, nonce: u32) -> bool {
+        let reservations = self
+            .accounts
+            .get(account_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+        (next_index..nonce).all(|missing| {
+            reservations
+                .iter()
+                .any(|item| item.nonce == missing && item.submitted)
+        })
+    }
+
+    fn find_mut(
+        &mut self,
+        account_id: &str,
+        nonce: u32,
+        request_id: &str,
+    ) -> Option<&mut Reservation> {
+        self.accounts
+            .get_mut(account_id)?
+            .iter_mut()
+            .find(|item| item.nonce == nonce && item.request_id == request_id)
+    }
+}
+
+static NONCE_BOOK: OnceLock<Mutex<NonceBook>> = OnceLock::new();
+
+fn with_book<T>(f: impl FnOnce(&mut NonceBook) -> T) -> Result<T, String> {
+    let mut book = NONCE_BOOK
+        .get_or_init(|| Mutex::new(NonceBook::default()))
+        .lock()
+        .map_err(|_| "nonce 预留状态异常".to_string())?;
+    Ok(f(&mut book))
+}
+
+/// 为签名请求预留 nonce；`chain_nonce` 取自 `system_accountNextIndex`。
+pub(crate) fn reserve(
+    account_id: &str,
+    chain_nonce: u32,
+    request_id: &str,
+    session_expires_at: u64,
+    death_block: Option<u64>,
+    now: u64,
+    best_block: u64,
+) -> Result<u32, String> {
+    with_book(|book| {
+        book.reserve(
+            account_id,
+            chain_nonce,
+            request_id,
+            session_expires_at,
+            death_block,
+            now,
+            best_block,
+        )
+    })?
+}
+
+/// 释放预留（签名请求构建失败时使用）。
+pub(crate) fn release(account_id: &str, nonce: u32, request_id: &str) {
+    let _ = with_book(|book| book.release(account_id, nonce, request_id));
+}
+
+/// 见 [`NonceBook::predecessors_submitted`]。
+pub(crate) fn predecessors_submitted(account_id: &str, next_index: u32, nonce: u32) -> bool {
+    with_book(|book| book.predecessors_submitted(account_id, next_index, nonce)).unwrap_or(false)
+}
+
+/// 提交守卫：交易成功进池前任何一步返回错误，drop 时自动释放预留。
+pub(crate) struct PendingSubmit {
+    account_id: String,
+    nonce: u32,
+    request_id: String,
+    submitted: bool,
+}
+
+impl PendingSubmit {
+    pub(crate) fn new(account_id: &str, nonce: u32, request_id: &str) -> Self {
+        Self {
+            account_id: account_id.to_string(),
+            nonce,
+            request_id: request_id.to_string(),
+            submitted: false,
+        }
+    }
+
+    /// 交易已被交易池接受，预留转为已提交，等 nonce 被消费或 era 失效后释放。
+    pub(crate) fn submitted(mut self) {
+        self.submitted = true;
+        let _ =
+            with_book(|book| book.mark_submitted(&self.account_id, self.nonce, &self.request_id));
+    }
+}
+
+impl Drop for PendingSubmit {
+    fn drop(&mut self) {
+        if !self.submitted {
+            release(&self.account_id, self.nonce, &self.request_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "0xaa";
+
+    #[test]
+    fn concurrent_requests_get_distinct_nonces_and_fill_gaps() {
+        let mut book = NonceBook::default();
+        assert_eq!(book.reserve(ACCOUNT, 5, "a", 100, Some(64), 10, 1), Ok(5));
+        assert_eq!(book.reserve(ACCOUNT, 5, "b", 100, Some(64), 10, 1), Ok(6));
+        book.release(ACCOUNT, 5, "a");
+        assert_eq!(book.reserve(ACCOUNT, 5, "c", 100, Some(64), 10, 1), Ok(5));
+        // 其他账户互不影响。
+        assert_eq!(book.reserve("0xbb", 5, "d", 100, Some(64), 10, 1), Ok(5));
+    }
+
+    #[test]
+    fn expired_sessions_dead_eras_and_consumed_nonces_are_reclaimed() {
+        let mut book = NonceBook::default();
+        book.reserve(ACCOUNT, 5, "idle", 100, Some(64), 10, 1)
+            .expect("idle");
+        book.reserve(ACCOUNT, 5, "sent", 100, Some(64), 10, 1)
+            .expect("sent");
+        book.mark_submitted(ACCOUNT, 6, "sent");
+        // session 过期：未提交的 5 释放，已提交的 6 仍在有效期内。
+        assert_eq!(book.reserve(ACCOUNT, 5, "x", 300, Some(64), 200, 10), Ok(5));
+        // era 失效：已提交的 6 也释放。
+        assert_eq!(book.reserve(ACCOUNT, 5, "y", 300, Some(64), 200, 64), Ok(6));
+        // 链上 nonce 越过：全部释放。
+        assert_eq!(book.reserve(ACCOUNT, 9, "z", 300, Some(64), 200, 64), Ok(9));
+    }
+
+    #[test]
+    fn future_nonce_requires_every_predecessor_submitted() {
+        let mut book = NonceBook::default();
+        book.reserve(ACCOUNT, 5, "a", 100, Some(64), 10, 1)
+            .expect("a");
+        book.reserve(ACCOUNT, 5, "b", 100, Some(64), 10, 1)
+            .expect("b");
+        assert!(!book.predecessors_submitted(ACCOUNT, 5, 6));
+        book.mark_submitted(ACCOUNT, 5, "a");
+        assert!(book.predecessors_submitted(ACCOUNT, 5, 6));
+        assert!(!book.predecessors_submitted(ACCOUNT, 4, 6));
+    }
+}
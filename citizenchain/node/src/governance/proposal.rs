@@ -166,6 +166,19 @@ pub struct IssuanceAllocationItem {
     #[serde(rename = "recipient_account_id")]
     pub recipient_account_id: String,
     pub amount_fen: String,
+    /// 分期归属条款；`None` 表示该项即时到账。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vesting: Option<IssuanceVestingTerms>,
+}
+
+/// 决议发行分配项的归属条款。
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuanceVestingTerms {
+    /// `BLOCKS` 或 `CALENDAR_MONTHS`。
+    pub period: &'static str,
+    pub cliff: u32,
+    pub duration: u32,
 }
 
 /// 决议销毁提案详情。
@@ -871,6 +884,8 @@ fn decode_resolution_issuance_action(
     // SCALE 布局：MODULE_TAG("res-iss":7) + actor_cid_number + proposer_role_code
     //            + proposer(32) + reason(Compact+bytes) + total_amount(u128:16)
     //            + allocations(Compact<count> + N × (recipient(32) + amount(u128:16)))
+    //            [+ vesting(Compact<N> + N × Option<(period:u8, cliff:u32, duration:u32)>)]
+    // vesting 仅分期发行提案携带；即时发行提案不写该尾部字段。
     let tag = TAG_RESOLUTION_ISSUANCE;
     if data.len() < tag.len() || &data[..tag.len()] != tag {
         return None;
@@ -922,9 +937,43 @@ fn decode_resolution_issuance_action(
         allocations.push(IssuanceAllocationItem {
             recipient_account_id,
             amount_fen: amount.to_string(),
+            vesting: None,
         });
     }
 
+    if offset < data.len() {
+        let (vesting_count, vesting_count_size) = read_compact_u32(data, offset).ok()?;
+        offset += vesting_count_size;
+        if vesting_count != alloc_count {
+            return None;
+        }
+        for item in allocations.iter_mut() {
+            match *data.get(offset)? {
+                0 => offset += 1,
+                1 => {
+                    if offset + 1 + 1 + 4 + 4 > data.len() {
+                        return None;
+                    }
+                    let period = match data[offset + 1] {
+                        0 => "BLOCKS",
+                        1 => "CALENDAR_MONTHS",
+                        _ => return None,
+                    };
+                    let cliff = u32::from_le_bytes(data[offset + 2..offset + 6].try_into().ok()?);
+                    let duration =
+                        u32::from_le_bytes(data[offset + 6..offset + 10].try_into().ok()?);
+                    offset += 10;
+                    item.vesting = Some(IssuanceVestingTerms {
+                        period,
+                        cliff,
+                        duration,
+                    });
+                }
+                _ => return None,
+            }
+        }
+    }
+
     if offset != data.len() {
         return None;
     }
@@ -1279,8 +1328,18 @@ fn format_runtime_upgrade_summary(d: &RuntimeUpgradeDetail) -> String {
 fn format_issuance_summary(d: &ResolutionIssuanceDetail) -> String {
     let total: u128 = d.total_amount_fen.parse().unwrap_or(0);
     let reason_short = truncate_chars(&d.reason, 30);
+    let vested = d
+        .allocations
+        .iter()
+        .filter(|item| item.vesting.is_some())
+        .count();
+    let vested_note = if vested > 0 {
+        format!("，{vested}条分期")
+    } else {
+        String::new()
+    };
     format!(
-        "决议发行 {}.{:02} 元（{}条分配）：{reason_short}",
+        "决议发行 {}.{:02} 元（{}条分配{vested_note}）：{reason_short}",
         total / 100,
         total % 100,
         d.allocations.len()
@@ -1508,10 +1567,12 @@ mod format_summary_tests {
                 IssuanceAllocationItem {
                     recipient_account_id: "a".repeat(64),
                     amount_fen: "50000".to_string(),
+                    vesting: None,
                 },
                 IssuanceAllocationItem {
                     recipient_account_id: "b".repeat(64),
                     amount_fen: "50000".to_string(),
+                    vesting: None,
                 },
             ],
         };
@@ -1547,6 +1608,47 @@ mod format_summary_tests {
         );
     }
 
+    #[test]
+    fn decode_resolution_issuance_action_reads_trailing_vesting_terms() {
+        let mut data = Vec::from(TAG_RESOLUTION_ISSUANCE);
+        data.extend_from_slice(&compact_bytes_for_test(b"LN001-NRC0G-944805165-2026"));
+        data.extend_from_slice(&compact_bytes_for_test(b"COMMITTEE_MEMBER"));
+        data.extend_from_slice(&[7u8; 32]);
+        data.extend_from_slice(&compact_bytes_for_test("分期发行".as_bytes()));
+        data.extend_from_slice(&300u128.to_le_bytes());
+        data.extend_from_slice(&encode_compact_u32(2));
+        data.extend_from_slice(&[8u8; 32]);
+        data.extend_from_slice(&100u128.to_le_bytes());
+        data.extend_from_slice(&[9u8; 32]);
+        data.extend_from_slice(&200u128.to_le_bytes());
+        data.extend_from_slice(&encode_compact_u32(2));
+        data.push(0);
+        data.extend_from_slice(&[1, 1]);
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(&24u32.to_le_bytes());
+
+        let detail = decode_resolution_issuance_action(12, &data)
+            .expect("vested issuance layout should decode");
+        assert_eq!(detail.allocations[0].vesting, None);
+        assert_eq!(
+            detail.allocations[1].vesting,
+            Some(IssuanceVestingTerms {
+                period: "CALENDAR_MONTHS",
+                cliff: 3,
+                duration: 24,
+            })
+        );
+        assert_eq!(
+            format_issuance_summary(&detail),
+            "决议发行 3.00 元（2条分配，1条分期）：分期发行"
+        );
+
+        // 归属条数必须与分配条数一致。
+        let mut short = data.clone();
+        short.truncate(short.len() - 10);
+        assert!(decode_resolution_issuance_action(12, &short).is_none());
+    }
+
     #[test]
     fn format_destroy_summary_falls_back_to_unknown_institution() {
         let d = ResolutionDestroyDetail {
@@ -1,10 +1,8 @@
 use super::call_data;
 use crate::governance::signing::{
-    build_signing_payloads, chain_action_code, fetch_genesis_hash, fetch_nonce,
-    fetch_runtime_version, generate_request_id, now_secs, payload_b64, public_key_b64,
+    chain_action_code, generate_request_id, payload_b64, prepare_chain_signing, public_key_b64,
     remember_chain_sign_request_session, sha256_hash, signer_account_id_from_public_key,
-    QrSignRequest, SignRequestBody, VoteSignRequestResult, DEFAULT_TTL_SECS,
-    IMMORTAL_SIGN_BLOCK_NUMBER, QR_KIND_SIGN_REQUEST, QR_V1,
+    QrSignRequest, SignRequestBody, VoteSignRequestResult, QR_KIND_SIGN_REQUEST, QR_V1,
 };
 
 fn normalize_signer_public_key(signer_public_key: &str) -> Result<(String, Vec<u8>), String> {
@@ -20,25 +18,19 @@ fn build_hashed_payload_request(
     signer_public_key_bytes: &[u8],
     call_data: &[u8],
 ) -> Result<VoteSignRequestResult, String> {
-    let (spec_version, tx_version) = fetch_runtime_version()?;
-    let genesis_hash = fetch_genesis_hash()?;
     let signer_account_id = signer_account_id_from_public_key(signer_public_key_clean)?;
-    let nonce = fetch_nonce(&signer_account_id)?;
-
-    let (full_payload, payload_for_qr) =
-        build_signing_payloads(call_data, &genesis_hash, nonce, spec_version, tx_version)?;
     let request_id = generate_request_id(request_prefix);
+    let prepared = prepare_chain_signing(&signer_account_id, call_data, &request_id)?;
+    let (full_payload, payload_for_qr) = (&prepared.payload, &prepared.signing_bytes);
 
     // Runtime WASM 交易 payload 远大于 QR 承载能力，是 QR_V1 唯一 hash-only 例外。
     // `full_payload` 只留在本地 session 校验；QR `b.d` 发送 `signing_bytes`，
     // 与 Substrate sr25519 实际签名输入完全一致，禁止 runtime-upgrade 另起哈希规则。
     // expected_payload_hash 必须对应 QR 中实际发送的 hash-only payload。
-    let payload_hash = sha256_hash(&payload_for_qr);
+    let payload_hash = sha256_hash(payload_for_qr);
     let payload_hash_hex = hex::encode(payload_hash);
-    let full_payload_hash_hex = hex::encode(sha256_hash(&full_payload));
-
-    let now = now_secs()?;
-    let expires_at = now + DEFAULT_TTL_SECS;
+    let full_payload_hash_hex = hex::encode(sha256_hash(full_payload));
+    let expires_at = prepared.expires_at;
     let request = QrSignRequest {
         proto: QR_V1.to_string(),
         kind: QR_KIND_SIGN_REQUEST,
@@ -48,7 +40,8 @@ fn build_hashed_payload_request(
             action: chain_action_code(call_data)?,
             sig_alg: 1,
             signer_public_key: public_key_b64(signer_public_key_bytes)?,
-            payload: payload_b64(&payload_for_qr),
+            payload: payload_b64(payload_for_qr),
+            call_descriptor: None,
         },
     };
 
@@ -60,7 +53,8 @@ fn build_hashed_payload_request(
         call_data,
         &full_payload_hash_hex,
         &payload_hash_hex,
-        nonce,
+        prepared.nonce,
+        prepared.lifetime,
         expires_at,
     )?;
 
@@ -69,8 +63,8 @@ fn build_hashed_payload_request(
         call_data_hex: hex::encode(call_data),
         request_id,
         expected_payload_hash: format!("0x{}", payload_hash_hex),
-        sign_nonce: nonce,
-        sign_block_number: IMMORTAL_SIGN_BLOCK_NUMBER,
+        sign_nonce: prepared.nonce,
+        sign_block_number: prepared.lifetime.birth_block(),
     })
 }
 
@@ -25,8 +25,9 @@ const POST_SUBMIT_AUDIT_DELAY: Duration = Duration::from_secs(10);
 use crate::shared::constants::RPC_RESPONSE_LIMIT_SMALL;
 /// SS58 前缀 2027。
 const SS58_PREFIX: u16 = 2027;
-/// 冷签交易统一使用 immortal era，前端保留的 sign_block_number 固定回传 0。
-pub(crate) const IMMORTAL_SIGN_BLOCK_NUMBER: u64 = 0;
+/// 冷签交易统一使用 mortal era：以生成请求时的最佳块为出生块，有效期内未进块即作废，
+/// 前端回传的 sign_block_number 即出生块号。
+pub(crate) const COLD_SIGN_MORTAL_PERIOD: u64 = chain_signing::DEFAULT_MORTAL_PERIOD;
 
 pub(crate) const QR_KIND_SIGN_REQUEST: u8 = primitives::sign::QR_KIND_SIGN_REQUEST;
 pub(crate) const QR_KIND_SIGN_RESPONSE: u8 = primitives::sign::QR_KIND_SIGN_RESPONSE;
@@ -34,12 +35,15 @@ pub(crate) const QR_KIND_SIGN_RESPONSE: u8 = primitives::sign::QR_KIND_SIGN_RESP
 #[derive(Debug, Clone)]
 struct ChainSignSession {
     expected_signer_public_key: String,
+    /// 签名账户 ID（0x hex），用于释放或确认 nonce 预留。
+    signer_account_id: String,
     call_data_hex: String,
     /// QR `b.d` 携带的完整审阅载荷 SHA-256。
     payload_hash_hex: String,
     /// sr25519 实际签名输入 SHA-256，用于提交前重建校验。
     signing_payload_hash_hex: String,
     nonce: u32,
+    lifetime: chain_signing::TxLifetime,
     expires_at: u64,
 }
 
@@ -104,6 +108,7 @@ pub(crate) fn remember_chain_sign_request_session(
     payload_hash_hex: &str,
     signing_payload_hash_hex: &str,
     nonce: u32,
+    lifetime: chain_signing::TxLifetime,
     expires_at: u64,
 ) -> Result<(), String> {
     remember_chain_sign_session(
@@ -112,6 +117,7 @@ pub(crate) fn remember_chain_sign_request_session(
             expected_signer_public_key: crate::shared::validation::normalize_public_key(
                 expected_signer_public_key,
             )?,
+            signer_account_id: signer_account_id_from_public_key(expected_signer_public_key)?,
             call_data_hex: hex::encode(call_data),
             payload_hash_hex: normalize_hash_hex(payload_hash_hex, "payload_hash")?,
             signing_payload_hash_hex: normalize_hash_hex(
@@ -119,11 +125,75 @@ pub(crate) fn remember_chain_sign_request_session(
                 "signing_payload_hash",
             )?,
             nonce,
+            lifetime,
             expires_at,
         },
     )
 }
 
+/// 冷签请求的链上签名参数：预留 nonce、mortal era 与签名材料。
+pub(crate) struct PreparedChainSigning {
+    pub(crate) nonce: u32,
+    pub(crate) lifetime: chain_signing::TxLifetime,
+    pub(crate) payload: Vec<u8>,
+    pub(crate) signing_bytes: Vec<u8>,
+    pub(crate) expires_at: u64,
+}
+
+/// 读取链上参数、以最佳块为出生块构建 mortal era，并为 `request_id` 预留 nonce。
+///
+/// 预留在签名 session 过期前不会分给同账户的其他请求，因此多张二维码可并发签名。
+pub(crate) fn prepare_chain_signing(
+    signer_account_id: &str,
+    call_data: &[u8],
+    request_id: &str,
+) -> Result<PreparedChainSigning, String> {
+    let (spec_version, tx_version) = fetch_runtime_version()?;
+    let genesis_hash = fetch_genesis_hash()?;
+    let best_block = fetch_best_block_number()?;
+    let birth_hash = fetch_block_hash(best_block)?;
+    let lifetime = chain_signing::TxLifetime::mortal(
+        COLD_SIGN_MORTAL_PERIOD,
+        best_block,
+        sp_core::H256(birth_hash),
+    )?;
+    let chain_nonce = fetch_nonce(signer_account_id)?;
+    let now = now_secs()?;
+    let expires_at = now + DEFAULT_TTL_SECS;
+    let nonce = super::nonce_reservation::reserve(
+        signer_account_id,
+        chain_nonce,
+        request_id,
+        expires_at,
+        lifetime.death_block(),
+        now,
+        best_block,
+    )?;
+
+    // 链交易签名材料只能由 runtime 类型构造，避免 node 冷签与热钱包
+    // 交易路径在 TxExtension 或 additional_signed 字节上分叉。
+    match chain_signing::build_signing_payloads_with_lifetime(
+        call_data,
+        &genesis_hash,
+        nonce,
+        spec_version,
+        tx_version,
+        &lifetime,
+    ) {
+        Ok((payload, signing_bytes)) => Ok(PreparedChainSigning {
+            nonce,
+            lifetime,
+            payload,
+            signing_bytes,
+            expires_at,
+        }),
+        Err(e) => {
+            super::nonce_reservation::release(signer_account_id, nonce, request_id);
+            Err(e)
+        }
+    }
+}
+
 fn take_chain_sign_session(request_id: &str) -> Result<ChainSignSession, String> {
     let mut sessions = chain_sign_sessions()
         .lock()
@@ -188,6 +258,9 @@ pub struct SignRequestBody {
     /// hash-only 请求允许进入 QR。
     #[serde(rename = "d")]
     pub payload: String,
+    /// c:通用链上调用描述，仅 `compose_call` 动作携带，钱包据此严格解码 `d`。
+    #[serde(rename = "c", skip_serializing_if = "Option::is_none")]
+    pub call_descriptor: Option<qr_protocol::CallDescriptor>,
 }
 
 /// QR_V1/k=1 sign_request envelope。
@@ -245,7 +318,7 @@ pub struct VoteSignRequestResult {
     pub expected_payload_hash: String,
     /// 签名时使用的 nonce（提交时必须复用）。
     pub sign_nonce: u32,
-    /// 冷签交易统一使用 immortal era；该字段保留给前端会话，固定为 0。
+    /// mortal era 出生块号（提交时必须复用）。
     pub sign_block_number: u64,
 }
 
@@ -279,11 +352,6 @@ pub fn build_vote_sign_request(
         .map_err(|e| format!("公钥解码失败: {e}"))?;
     let signer_account_id = signer_account_id_from_public_key(&signer_public_key)?;
 
-    // 获取链上参数
-    let (spec_version, tx_version) = fetch_runtime_version()?;
-    let genesis_hash = fetch_genesis_hash()?;
-    let nonce = fetch_nonce(&signer_account_id)?;
-
     // ticket_claim SCALE：Personal=0；InstitutionRole=1 + RoleCode(BoundedVec)。
     let mut call_data = Vec::with_capacity(12 + voter_role_code.map(str::len).unwrap_or_default());
     call_data.push(20u8); // InternalVote sub-pallet index
@@ -304,21 +372,15 @@ pub fn build_vote_sign_request(
     }
     call_data.push(if approve { 1u8 } else { 0u8 });
 
-    // 链交易签名材料只能由 runtime 类型构造，避免 node 冷签与热钱包
-    // 交易路径在 TxExtension 或 additional_signed 字节上分叉。
-    let (payload, signing_bytes) =
-        build_signing_payloads(&call_data, &genesis_hash, nonce, spec_version, tx_version)?;
+    // 生成请求 ID，并按请求预留 nonce、构建 mortal era 签名材料
+    let request_id = generate_request_id("vote");
+    let prepared = prepare_chain_signing(&signer_account_id, &call_data, &request_id)?;
 
     // 计算审阅 payload hash 与实际签名字节 hash，分别用于 QR 会话和提交校验。
-    let payload_hash = sha256_hash(&payload);
+    let payload_hash = sha256_hash(&prepared.payload);
     let payload_hash_hex = hex::encode(payload_hash);
-    let signing_payload_hash_hex = hex::encode(sha256_hash(&signing_bytes));
-
-    // 生成请求 ID
-    let request_id = generate_request_id("vote");
-
-    let now = now_secs()?;
-    let expires_at = now + DEFAULT_TTL_SECS;
+    let signing_payload_hash_hex = hex::encode(sha256_hash(&prepared.signing_bytes));
+    let expires_at = prepared.expires_at;
     let request = QrSignRequest {
         proto: QR_V1.to_string(),
         kind: QR_KIND_SIGN_REQUEST,
@@ -328,7 +390,8 @@ pub fn build_vote_sign_request(
             action: chain_action_code(&call_data)?,
             sig_alg: 1,
             signer_public_key: public_key_b64(&signer_public_key_bytes)?,
-            payload: payload_b64(&payload),
+            payload: payload_b64(&prepared.payload),
+            call_descriptor: None,
         },
     };
 
@@ -338,10 +401,12 @@ pub fn build_vote_sign_request(
         request_id.clone(),
         ChainSignSession {
             expected_signer_public_key: signer_public_key,
+            signer_account_id,
             call_data_hex: hex::encode(&call_data),
             payload_hash_hex: payload_hash_hex.clone(),
             signing_payload_hash_hex,
-            nonce,
+            nonce: prepared.nonce,
+            lifetime: prepared.lifetime,
             expires_at,
         },
     )?;
@@ -351,8 +416,8 @@ pub fn build_vote_sign_request(
         call_data_hex: hex::encode(&call_data),
         request_id,
         expected_payload_hash: format!("0x{}", payload_hash_hex),
-        sign_nonce: nonce,
-        sign_block_number: IMMORTAL_SIGN_BLOCK_NUMBER,
+        sign_nonce: prepared.nonce,
+        sign_block_number: prepared.lifetime.birth_block(),
     })
 }
 
@@ -385,10 +450,6 @@ pub fn build_joint_vote_sign_request(
         return Err("voter_role_code 长度超出链上岗位码范围".to_string());
     }
 
-    let (spec_version, tx_version) = fetch_runtime_version()?;
-    let genesis_hash = fetch_genesis_hash()?;
-    let nonce = fetch_nonce(&signer_account_id)?;
-
     // call data: [21][0][proposal_id][cid_number][voter_role_code][approve]
     let mut call_data = Vec::with_capacity(14 + actor_cid_number.len() + voter_role_code.len());
     call_data.push(21u8); // JointVote sub-pallet index
@@ -400,15 +461,12 @@ pub fn build_joint_vote_sign_request(
     call_data.extend_from_slice(voter_role_code.as_bytes());
     call_data.push(if approve { 1u8 } else { 0u8 });
 
-    let (payload, signing_bytes) =
-        build_signing_payloads(&call_data, &genesis_hash, nonce, spec_version, tx_version)?;
-    let payload_hash = sha256_hash(&payload);
-    let payload_hash_hex = hex::encode(payload_hash);
-    let signing_payload_hash_hex = hex::encode(sha256_hash(&signing_bytes));
     let request_id = generate_request_id("jvote");
-
-    let now = now_secs()?;
-    let expires_at = now + DEFAULT_TTL_SECS;
+    let prepared = prepare_chain_signing(&signer_account_id, &call_data, &request_id)?;
+    let payload_hash = sha256_hash(&prepared.payload);
+    let payload_hash_hex = hex::encode(payload_hash);
+    let signing_payload_hash_hex = hex::encode(sha256_hash(&prepared.signing_bytes));
+    let expires_at = prepared.expires_at;
     let request = QrSignRequest {
         proto: QR_V1.to_string(),
         kind: QR_KIND_SIGN_REQUEST,
@@ -418,7 +476,8 @@ pub fn build_joint_vote_sign_request(
             action: chain_action_code(&call_data)?,
             sig_alg: 1,
             signer_public_key: public_key_b64(&signer_public_key_bytes)?,
-            payload: payload_b64(&payload),
+            payload: payload_b64(&prepared.payload),
+            call_descriptor: None,
         },
     };
 
@@ -428,10 +487,12 @@ pub fn build_joint_vote_sign_request(
         request_id.clone(),
         ChainSignSession {
             expected_signer_public_key: signer_public_key,
+            signer_account_id,
             call_data_hex: hex::encode(&call_data),
             payload_hash_hex: payload_hash_hex.clone(),
             signing_payload_hash_hex,
-            nonce,
+            nonce: prepared.nonce,
+            lifetime: prepared.lifetime,
             expires_at,
         },
     )?;
@@ -441,8 +502,8 @@ pub fn build_joint_vote_sign_request(
         call_data_hex: hex::encode(&call_data),
         request_id,
         expected_payload_hash: format!("0x{}", payload_hash_hex),
-        sign_nonce: nonce,
-        sign_block_number: IMMORTAL_SIGN_BLOCK_NUMBER,
+        sign_nonce: prepared.nonce,
+        sign_block_number: prepared.lifetime.birth_block(),
     })
 }
 
@@ -512,6 +573,12 @@ pub fn verify_and_submit(
         return Err("请求 ID 不匹配,可能扫描了其他交易的签名".to_string());
     }
     let session = take_chain_sign_session(request_id)?;
+    // session 已取出：此后任何失败都释放 nonce 预留，成功进池才转为已提交。
+    let pending = super::nonce_reservation::PendingSubmit::new(
+        &session.signer_account_id,
+        session.nonce,
+        request_id,
+    );
     let now = now_secs()?;
     if session.expires_at < now {
         return Err("签名 session 已过期，请重新生成二维码".to_string());
@@ -540,6 +607,10 @@ pub fn verify_and_submit(
     if session.nonce != sign_nonce {
         return Err("提交参数 nonce 与本地签名 session 不匹配".to_string());
     }
+    if session.lifetime.birth_block() != sign_block_number {
+        return Err("提交参数出生块与本地签名 session 不匹配".to_string());
+    }
+    session.lifetime.ensure_alive(fetch_best_block_number()?)?;
     let call_data_hex = hex::encode(call_data);
     if session.call_data_hex != call_data_hex {
         return Err("提交参数 call_data 与本地签名 session 不匹配".to_string());
@@ -550,12 +621,13 @@ pub fn verify_and_submit(
 
     let (spec_version, tx_version) = fetch_runtime_version()?;
     let genesis_hash = fetch_genesis_hash()?;
-    let material = chain_signing::build_signing_material(
+    let material = chain_signing::build_signing_material_with_lifetime(
         call_data,
         &genesis_hash,
         sign_nonce,
         spec_version,
         tx_version,
+        &session.lifetime,
     )?;
     let payload_hash = hex::encode(sha256_hash(&material.payload));
     let signing_payload_hash = hex::encode(sha256_hash(&material.signing_bytes));
@@ -572,8 +644,10 @@ pub fn verify_and_submit(
         return Err("sr25519 本地验签失败，拒绝提交到链".to_string());
     }
 
-    let _ = sign_block_number; // immortal era 不再使用区块号，字段只保留给前端会话结构。
-    eprintln!("[签名提交] sign_nonce={sign_nonce}, era=immortal, runtime_typed=true");
+    eprintln!(
+        "[签名提交] sign_nonce={sign_nonce}, era=mortal(birth={sign_block_number}, death={:?}), runtime_typed=true",
+        session.lifetime.death_block()
+    );
     let extrinsic = chain_signing::assemble_signed_extrinsic(material, public, signature);
     let full_extrinsic = extrinsic.encode();
 
@@ -608,16 +682,20 @@ pub fn verify_and_submit(
             if result_bytes.is_empty() {
                 return Err("dry-run 返回空结果，拒绝提交".to_string());
             }
-            if result_bytes[0] != 0x00 {
+            if result_bytes.starts_with(&[0x01, 0x00, 0x02])
+                && future_nonce_is_queued(&session.signer_account_id, sign_nonce)?
+            {
+                // 前序预留交易均已进池，本交易只是排在其后，进 future 队列属预期。
+                eprintln!("[签名提交] nonce={sign_nonce} 排在已提交的前序交易之后，继续提交");
+            } else if result_bytes[0] != 0x00 {
                 // 外层 Result = Err → TransactionValidityError。
-                // Future/Stale 等交易提交后只会"看似成功永不上链"
+                // 除上一分支的预留排队外，Future/Stale 等交易提交后只会"看似成功永不上链"
                 // （Future 进 future 队列且不向 peer 广播），一律拒绝并把
                 // 原因抛给前端，绝不再"继续尝试提交"。
                 let reason = classify_invalid_tx(&result_bytes);
                 eprintln!("[签名提交] dry-run 拒绝: {reason} (hex: {s})");
                 return Err(dry_run_reject_message(&result_bytes, s));
-            }
-            if result_bytes.len() > 1 && result_bytes[1] != 0x00 {
+            } else if result_bytes.len() > 1 && result_bytes[1] != 0x00 {
                 // Ok(Err(DispatchError)) — 交易格式正确但执行会失败，阻止提交
                 return Err(format!("交易执行会失败: DispatchError (hex: {s})"));
             }
@@ -643,15 +721,34 @@ pub fn verify_and_submit(
         .as_str()
         .ok_or_else(|| format!("author_submitExtrinsic 返回非字符串: {result}"))?
         .to_string();
+    pending.submitted();
 
     // 被交易池接受 ≠ 已上链（nonce 错位时交易进 future 队列，永不
     // 被打包且不广播）。后台延迟核对一次 nonce 是否被消费，只打日志不阻塞。
-    let signer_account_id = signer_account_id_from_public_key(&expected_signer_public_key)?;
-    spawn_post_submit_audit(signer_account_id, sign_nonce, tx_hash.clone());
+    spawn_post_submit_audit(session.signer_account_id, sign_nonce, tx_hash.clone());
 
     Ok(VoteSubmitResult { tx_hash })
 }
 
+/// dry-run 报 Future 时判断是否只是排在本机已提交的预留交易之后。
+///
+/// next index 已包含就绪队列：等于本 nonce 说明前序交易都在池中；
+/// 小于时要求缺口中的每个 nonce 都是已提交的预留，否则交易会永久卡在 future 队列。
+fn future_nonce_is_queued(signer_account_id: &str, sign_nonce: u32) -> Result<bool, String> {
+    let next_index = fetch_nonce(signer_account_id)?;
+    if next_index > sign_nonce {
+        return Err(format!(
+            "nonce {sign_nonce} 已被其他交易占用(next={next_index})，请重新生成签名请求"
+        ));
+    }
+    Ok(next_index == sign_nonce
+        || super::nonce_reservation::predecessors_submitted(
+            signer_account_id,
+            next_index,
+            sign_nonce,
+        ))
+}
+
 /// 把 dry-run 拒绝结果转成抛给前端的报错文案。
 fn dry_run_reject_message(result_bytes: &[u8], raw_hex: &str) -> String {
     chain_signing::dry_run_reject_message(result_bytes, raw_hex)
@@ -713,26 +810,33 @@ pub(crate) fn fetch_runtime_version() -> Result<(u32, u32), String> {
     Ok((spec as u32, tx as u32))
 }
 
-/// 构建链交易冷签审阅 payload 与实际签名字节。
-pub(crate) fn build_signing_payloads(
-    call_data: &[u8],
-    genesis_hash: &[u8; 32],
-    nonce: u32,
-    spec_version: u32,
-    tx_version: u32,
-) -> Result<(Vec<u8>, Vec<u8>), String> {
-    chain_signing::build_signing_payloads(call_data, genesis_hash, nonce, spec_version, tx_version)
+pub(crate) fn fetch_genesis_hash() -> Result<[u8; 32], String> {
+    fetch_block_hash(0)
 }
 
-pub(crate) fn fetch_genesis_hash() -> Result<[u8; 32], String> {
+/// 按块号读取最佳链上的区块哈希。
+pub(crate) fn fetch_block_hash(block_number: u64) -> Result<[u8; 32], String> {
     let result = rpc_post(
         "chain_getBlockHash",
-        Value::Array(vec![Value::Number(0.into())]),
+        Value::Array(vec![Value::Number(block_number.into())]),
     )?;
-    let hash_str = result.as_str().ok_or("genesis hash 格式无效")?;
+    let hash_str = result
+        .as_str()
+        .ok_or_else(|| format!("区块 #{block_number} 哈希格式无效"))?;
     decode_hash32(hash_str)
 }
 
+/// 读取最佳块号（`chain_getHeader` 的 hex 块号）。
+pub(crate) fn fetch_best_block_number() -> Result<u64, String> {
+    let header = rpc_post("chain_getHeader", Value::Array(vec![]))?;
+    let number = header
+        .get("number")
+        .and_then(Value::as_str)
+        .ok_or("区块头缺少 number")?;
+    u64::from_str_radix(number.trim_start_matches("0x"), 16)
+        .map_err(|e| format!("区块号格式无效: {e}"))
+}
+
 /// 从当前 sr25519 签名公钥按 runtime 账户识别规则得到唯一账户 ID 文本。
 pub(crate) fn signer_account_id_from_public_key(signer_public_key: &str) -> Result<String, String> {
     let signer_public_key = crate::shared::validation::normalize_public_key(signer_public_key)?;
@@ -864,32 +968,62 @@ pub fn build_sign_request_from_call_data(
     signer_public_key: &str,
     signer_public_key_bytes: &[u8],
     call_data: &[u8],
+) -> Result<VoteSignRequestResult, String> {
+    build_chain_sign_request(
+        signer_public_key,
+        signer_public_key_bytes,
+        call_data,
+        chain_action_code(call_data)?,
+        None,
+    )
+}
+
+/// 通用链上调用签名请求：动作码固定为 registry 保留的 `compose_call`,
+/// 调用描述随 `b.c` 下发，供钱包在无专用 decoder 时严格解码 call_data。
+pub(crate) fn build_compose_call_sign_request_from_call_data(
+    signer_public_key: &str,
+    signer_public_key_bytes: &[u8],
+    call_data: &[u8],
+    descriptor: qr_protocol::CallDescriptor,
+) -> Result<VoteSignRequestResult, String> {
+    let action = qr_protocol::action_by_key(qr_protocol::COMPOSE_CALL_ACTION_KEY)
+        .map_err(|e| e.to_string())?
+        .action_code;
+    build_chain_sign_request(
+        signer_public_key,
+        signer_public_key_bytes,
+        call_data,
+        action,
+        Some(descriptor),
+    )
+}
+
+fn build_chain_sign_request(
+    signer_public_key: &str,
+    signer_public_key_bytes: &[u8],
+    call_data: &[u8],
+    action: u16,
+    call_descriptor: Option<qr_protocol::CallDescriptor>,
 ) -> Result<VoteSignRequestResult, String> {
     let signer_public_key = crate::shared::validation::normalize_public_key(signer_public_key)?;
     let signer_account_id = signer_account_id_from_public_key(&signer_public_key)?;
-    let (spec_version, tx_version) = fetch_runtime_version()?;
-    let genesis_hash = fetch_genesis_hash()?;
-    let nonce = fetch_nonce(&signer_account_id)?;
-
-    let (payload, signing_bytes) =
-        build_signing_payloads(call_data, &genesis_hash, nonce, spec_version, tx_version)?;
-    let payload_hash = sha256_hash(&payload);
-    let payload_hash_hex = hex::encode(payload_hash);
-    let signing_payload_hash_hex = hex::encode(sha256_hash(&signing_bytes));
     let request_id = generate_request_id("chain");
-
-    let now = now_secs()?;
-    let expires_at = now + DEFAULT_TTL_SECS;
+    let prepared = prepare_chain_signing(&signer_account_id, call_data, &request_id)?;
+    let payload_hash = sha256_hash(&prepared.payload);
+    let payload_hash_hex = hex::encode(payload_hash);
+    let signing_payload_hash_hex = hex::encode(sha256_hash(&prepared.signing_bytes));
+    let expires_at = prepared.expires_at;
     let request = QrSignRequest {
         proto: QR_V1.to_string(),
         kind: QR_KIND_SIGN_REQUEST,
         id: request_id.clone(),
         expires_at,
         body: SignRequestBody {
-            action: chain_action_code(call_data)?,
+            action,
             sig_alg: 1,
             signer_public_key: public_key_b64(signer_public_key_bytes)?,
-            payload: payload_b64(&payload),
+            payload: payload_b64(&prepared.payload),
+            call_descriptor,
         },
     };
 
@@ -899,10 +1033,12 @@ pub fn build_sign_request_from_call_data(
         request_id.clone(),
         ChainSignSession {
             expected_signer_public_key: signer_public_key,
+            signer_account_id,
             call_data_hex: hex::encode(call_data),
             payload_hash_hex: payload_hash_hex.clone(),
             signing_payload_hash_hex,
-            nonce,
+            nonce: prepared.nonce,
+            lifetime: prepared.lifetime,
             expires_at,
         },
     )?;
@@ -912,8 +1048,8 @@ pub fn build_sign_request_from_call_data(
         call_data_hex: hex::encode(call_data),
         request_id,
         expected_payload_hash: format!("0x{}", payload_hash_hex),
-        sign_nonce: nonce,
-        sign_block_number: IMMORTAL_SIGN_BLOCK_NUMBER,
+        sign_nonce: prepared.nonce,
+        sign_block_number: prepared.lifetime.birth_block(),
     })
 }
 
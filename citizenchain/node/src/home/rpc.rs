@@ -190,6 +190,19 @@ fn total_issuance_storage_key() -> String {
         .clone()
 }
 
+static VESTING_LOCKED_STORAGE_KEY_CACHE: OnceLock<String> = OnceLock::new();
+
+fn vesting_locked_storage_key() -> String {
+    VESTING_LOCKED_STORAGE_KEY_CACHE
+        .get_or_init(|| {
+            crate::shared::storage_keys::to_hex(&crate::shared::storage_keys::prefix(
+                b"ResolutionIssuance",
+                b"TotalVestingLocked",
+            ))
+        })
+        .clone()
+}
+
 fn hex_to_bytes(hex: &str) -> Option<Vec<u8>> {
     let trimmed = hex.strip_prefix("0x").unwrap_or(hex);
     if !trimmed.len().is_multiple_of(2) {
@@ -236,12 +249,15 @@ fn format_fen_to_yuan(amount_fen: u128) -> String {
 /// 全链发行总额。
 pub struct TotalIssuance {
     pub total_issuance: Option<String>,
+    /// 其中已发行但仍处于决议发行归属锁定期的金额。
+    pub vesting_locked: Option<String>,
 }
 
 fn get_total_issuance_sync(app: AppHandle) -> Result<TotalIssuance, String> {
     if !current_status(&app)?.running {
         return Ok(TotalIssuance {
             total_issuance: None,
+            vesting_locked: None,
         });
     }
 
@@ -249,15 +265,34 @@ fn get_total_issuance_sync(app: AppHandle) -> Result<TotalIssuance, String> {
     let finalized_hash = chain_query::fetch_finalized_head()?;
     let raw = rpc_post(
         "state_getStorage",
-        Value::Array(vec![Value::String(key), Value::String(finalized_hash)]),
+        Value::Array(vec![
+            Value::String(key),
+            Value::String(finalized_hash.clone()),
+        ]),
     )?;
     let amount = raw
         .as_str()
         .and_then(scale_u128_from_storage_hex)
         .map(format_fen_to_yuan);
 
+    // TotalVestingLocked 为 ValueQuery，未写入时即为 0。
+    let raw_locked = rpc_post(
+        "state_getStorage",
+        Value::Array(vec![
+            Value::String(vesting_locked_storage_key()),
+            Value::String(finalized_hash),
+        ]),
+    )?;
+    let vesting_locked = match raw_locked.as_str() {
+        Some(hex) => scale_u128_from_storage_hex(hex),
+        None if raw_locked.is_null() => Some(0),
+        None => None,
+    }
+    .map(format_fen_to_yuan);
+
     Ok(TotalIssuance {
         total_issuance: amount,
+        vesting_locked,
     })
 }
 
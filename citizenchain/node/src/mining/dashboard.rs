@@ -1,5 +1,6 @@
 use crate::{
     home,
+    mining::pool_server::PoolWorkerStats,
     settings::reward_account,
     shared::{constants, rpc, security},
 };
@@ -50,6 +51,8 @@ pub struct MiningBlockRecord {
 pub struct MiningDashboard {
     pub income: MiningIncome,
     pub records: Vec<MiningBlockRecord>,
+    /// 节点内矿池的在线 worker；未启用 `--pool-listen` 时为空。
+    pub pool_workers: Vec<PoolWorkerStats>,
     pub warning: Option<String>,
 }
 
@@ -792,6 +795,7 @@ fn dashboard_from_cache(
             today_income: format_2_decimals_fen(today_income_fen),
         },
         records,
+        pool_workers: vec![],
         warning,
     }
 }
@@ -805,6 +809,7 @@ fn empty_dashboard(warning: Option<String>) -> MiningDashboard {
             today_income: "0.00".to_string(),
         },
         records: vec![],
+        pool_workers: vec![],
         warning,
     }
 }
@@ -831,12 +836,28 @@ impl Drop for RefreshInFlightGuard {
 
 #[tauri::command]
 pub fn get_mining_dashboard(app: AppHandle) -> Result<MiningDashboard, String> {
+    let mut dashboard = load_mining_dashboard(&app)?;
+    // 矿池 worker 统计只存在于节点内存，不进收益缓存；未启用矿池时 RPC 不存在，按空列表展示。
+    if home::current_status(&app)?.running {
+        dashboard.pool_workers = fetch_pool_workers();
+    }
+    Ok(dashboard)
+}
+
+fn fetch_pool_workers() -> Vec<PoolWorkerStats> {
+    rpc_post("mining_poolWorkers", Value::Array(vec![]))
+        .ok()
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+fn load_mining_dashboard(app: &AppHandle) -> Result<MiningDashboard, String> {
     // 先加载最近一次落盘缓存，保证 RPC 短暂异常时仍能返回可展示的旧数据。
-    ensure_mining_cache_loaded(&app);
+    ensure_mining_cache_loaded(app);
     let today_utc = utc_day(unix_now_ms().unwrap_or(0));
     let mut warnings: Vec<String> = Vec::new();
 
-    if !home::current_status(&app)?.running {
+    if !home::current_status(app)?.running {
         return Ok(empty_dashboard(None));
     }
 
@@ -855,7 +876,7 @@ pub fn get_mining_dashboard(app: AppHandle) -> Result<MiningDashboard, String> {
         }
     };
 
-    let local_miner_account_id = match reward_account::local_powr_miner_account_id(&app) {
+    let local_miner_account_id = match reward_account::local_powr_miner_account_id(app) {
         Ok(v) => v,
         Err(err) => {
             warnings.push(format!("读取本节点矿工账号失败：{err}"));
@@ -880,7 +901,7 @@ pub fn get_mining_dashboard(app: AppHandle) -> Result<MiningDashboard, String> {
     let _refresh_guard = RefreshInFlightGuard;
 
     let warning_from_refresh = match refresh_cache(
-        &app,
+        app,
         finalized_height,
         today_utc,
         local_miner_account_id.as_deref(),
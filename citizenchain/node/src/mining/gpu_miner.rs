@@ -3,13 +3,14 @@
 //! This module is only compiled when the `gpu-mining` feature is enabled.
 //! It provides a GPU-accelerated blake2b-256 hash search that runs alongside
 //! the CPU miner, using the upper half of the nonce space (bit 63 = 1).
+//! The OpenCL searcher itself lives in `pow_pool::gpu`, shared with the
+//! standalone pool worker.
 
 use crate::core::service::SimplePow;
 use citizenchain::opaque::Block;
 use codec::Encode;
-use ocl::{Buffer, MemFlags, ProQue};
+use pow_pool::{gpu::GpuSearcher, kernel::difficulty_to_target_be};
 use sc_consensus_pow::MiningHandle;
-use sp_core::U256;
 use std::{
     sync::atomic::{AtomicU64, Ordering},
     thread,
@@ -25,188 +26,6 @@ pub fn gpu_hashrate() -> f64 {
     f64::from_bits(GPU_HASHRATE.load(Ordering::Relaxed))
 }
 
-/// OpenCL kernel source embedded at compile time.
-const KERNEL_SRC: &str = include_str!("../../kernels/blake2b_pow.cl");
-
-/// Number of nonces to test per GPU batch dispatch.
-/// 2^24 = ~16 million — good balance between GPU utilization and responsiveness.
-const DEFAULT_BATCH_SIZE: u32 = 1 << 24;
-
-/// GPU miner state holding OpenCL resources.
-struct GpuMiner {
-    pro_que: ProQue,
-    // Persistent GPU buffers (reused across batches).
-    buf_pre_hash: Buffer<u8>,
-    buf_target: Buffer<u64>,
-    buf_result_nonce: Buffer<u64>,
-    buf_found: Buffer<u32>,
-    batch_size: u32,
-}
-
-impl GpuMiner {
-    /// Try to initialize the GPU miner on the given device.
-    /// Returns Err if no GPU is available or OpenCL initialization fails.
-    fn try_init(device_index: usize) -> Result<Self, String> {
-        let platform = ocl::Platform::default();
-        let devices = ocl::Device::list(platform, Some(ocl::flags::DeviceType::GPU))
-            .map_err(|e| format!("failed to list GPU devices: {e}"))?;
-
-        if devices.is_empty() {
-            return Err("no GPU devices found".into());
-        }
-
-        let device = devices
-            .get(device_index)
-            .ok_or_else(|| {
-                format!(
-                    "GPU device index {} out of range (found {} devices)",
-                    device_index,
-                    devices.len()
-                )
-            })?
-            .clone();
-
-        let device_name = device.name().unwrap_or_else(|_| "unknown".into());
-        log::info!(
-            "Initializing GPU miner on device {}: {}",
-            device_index,
-            device_name
-        );
-
-        let batch_size = DEFAULT_BATCH_SIZE;
-
-        let pro_que = ProQue::builder()
-            .platform(platform)
-            .device(device)
-            .src(KERNEL_SRC)
-            .dims(batch_size as usize)
-            .build()
-            .map_err(|e| format!("failed to build OpenCL program: {e}"))?;
-
-        let buf_pre_hash = Buffer::<u8>::builder()
-            .queue(pro_que.queue().clone())
-            .flags(MemFlags::new().read_only())
-            .len(32)
-            .build()
-            .map_err(|e| format!("failed to create pre_hash buffer: {e}"))?;
-
-        let buf_target = Buffer::<u64>::builder()
-            .queue(pro_que.queue().clone())
-            .flags(MemFlags::new().read_only())
-            .len(4)
-            .build()
-            .map_err(|e| format!("failed to create target buffer: {e}"))?;
-
-        let buf_result_nonce = Buffer::<u64>::builder()
-            .queue(pro_que.queue().clone())
-            .flags(MemFlags::new().write_only())
-            .len(1)
-            .build()
-            .map_err(|e| format!("failed to create result_nonce buffer: {e}"))?;
-
-        let buf_found = Buffer::<u32>::builder()
-            .queue(pro_que.queue().clone())
-            .flags(MemFlags::new().read_write())
-            .len(1)
-            .build()
-            .map_err(|e| format!("failed to create found buffer: {e}"))?;
-
-        Ok(GpuMiner {
-            pro_que,
-            buf_pre_hash,
-            buf_target,
-            buf_result_nonce,
-            buf_found,
-            batch_size,
-        })
-    }
-
-    /// Run a single batch of nonce searches on the GPU.
-    /// Returns Some(nonce) if a valid nonce is found, None otherwise.
-    fn search_batch(
-        &self,
-        pre_hash: &[u8],
-        target_be: &[u64; 4],
-        nonce_base: u64,
-    ) -> Result<Option<u64>, String> {
-        // Upload pre_hash and target to GPU.
-        self.buf_pre_hash
-            .write(pre_hash)
-            .enq()
-            .map_err(|e| format!("write pre_hash: {e}"))?;
-        self.buf_target
-            .write(target_be.as_slice())
-            .enq()
-            .map_err(|e| format!("write target: {e}"))?;
-
-        // Reset found flag to 0.
-        self.buf_found
-            .write(&[0u32] as &[u32])
-            .enq()
-            .map_err(|e| format!("reset found: {e}"))?;
-
-        // Build and enqueue the kernel.
-        let kernel = self
-            .pro_que
-            .kernel_builder("blake2b_pow_mine")
-            .arg(&self.buf_pre_hash)
-            .arg(nonce_base)
-            .arg(&self.buf_target)
-            .arg(&self.buf_result_nonce)
-            .arg(&self.buf_found)
-            .build()
-            .map_err(|e| format!("build kernel: {e}"))?;
-
-        unsafe {
-            kernel.enq().map_err(|e| format!("enqueue kernel: {e}"))?;
-        }
-
-        // Read back results.
-        let mut found = [0u32; 1];
-        self.buf_found
-            .read(&mut found as &mut [u32])
-            .enq()
-            .map_err(|e| format!("read found: {e}"))?;
-
-        if found[0] != 0 {
-            let mut result_nonce = [0u64; 1];
-            self.buf_result_nonce
-                .read(&mut result_nonce as &mut [u64])
-                .enq()
-                .map_err(|e| format!("read result_nonce: {e}"))?;
-            Ok(Some(result_nonce[0]))
-        } else {
-            Ok(None)
-        }
-    }
-}
-
-/// Convert U256 difficulty to big-endian target bytes for the GPU kernel.
-/// target = U256::MAX / difficulty, stored as 4 x u64 in big-endian word order.
-fn difficulty_to_target_be(difficulty: U256) -> [u64; 4] {
-    if difficulty.is_zero() {
-        return [0u64; 4];
-    }
-    let target = U256::MAX / difficulty;
-    let bytes: [u8; 32] = target.to_big_endian();
-    // Convert 32 big-endian bytes to 4 big-endian u64 words.
-    let mut words = [0u64; 4];
-    for i in 0..4 {
-        let offset = i * 8;
-        words[i] = u64::from_be_bytes([
-            bytes[offset],
-            bytes[offset + 1],
-            bytes[offset + 2],
-            bytes[offset + 3],
-            bytes[offset + 4],
-            bytes[offset + 5],
-            bytes[offset + 6],
-            bytes[offset + 7],
-        ]);
-    }
-    words
-}
-
 /// Try to start the GPU miner. Spawns a background thread.
 /// Returns Ok(()) if GPU initialization succeeded, Err otherwise.
 pub fn try_start<Proof: Send + 'static>(
@@ -216,10 +35,10 @@ pub fn try_start<Proof: Send + 'static>(
     keystore: sp_keystore::KeystorePtr,
     author_public: sp_core::sr25519::Public,
 ) -> Result<(), String> {
-    let miner = GpuMiner::try_init(device_index)?;
+    let miner = GpuSearcher::try_init(device_index)?;
 
     thread::spawn(move || {
-        let batch_size = miner.batch_size;
+        let batch_size = miner.batch_size();
 
         loop {
             let Some(metadata) = worker.metadata() else {
@@ -302,59 +121,3 @@ pub fn try_start<Proof: Send + 'static>(
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn difficulty_to_target_be_zero_returns_zeros() {
-        assert_eq!(difficulty_to_target_be(U256::zero()), [0u64; 4]);
-    }
-
-    #[test]
-    fn difficulty_to_target_be_one_returns_max() {
-        let result = difficulty_to_target_be(U256::one());
-        // U256::MAX / 1 = U256::MAX → 全 0xFF
-        assert_eq!(result, [u64::MAX; 4]);
-    }
-
-    #[test]
-    fn difficulty_to_target_be_two() {
-        let result = difficulty_to_target_be(U256::from(2));
-        let target = U256::MAX / U256::from(2);
-        let bytes: [u8; 32] = target.to_big_endian();
-        let expected = bytes_to_words(&bytes);
-        assert_eq!(result, expected);
-    }
-
-    #[test]
-    fn difficulty_to_target_be_roundtrip() {
-        let difficulty = U256::from(1000);
-        let words = difficulty_to_target_be(difficulty);
-        let target = U256::MAX / difficulty;
-        let mut reconstructed = [0u8; 32];
-        for (i, word) in words.iter().enumerate() {
-            reconstructed[i * 8..(i + 1) * 8].copy_from_slice(&word.to_be_bytes());
-        }
-        assert_eq!(U256::from_big_endian(&reconstructed), target);
-    }
-
-    fn bytes_to_words(bytes: &[u8; 32]) -> [u64; 4] {
-        let mut words = [0u64; 4];
-        for i in 0..4 {
-            let o = i * 8;
-            words[i] = u64::from_be_bytes([
-                bytes[o],
-                bytes[o + 1],
-                bytes[o + 2],
-                bytes[o + 3],
-                bytes[o + 4],
-                bytes[o + 5],
-                bytes[o + 6],
-                bytes[o + 7],
-            ]);
-        }
-        words
-    }
-}
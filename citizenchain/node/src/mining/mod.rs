@@ -1,5 +1,6 @@
-// 挖矿模块入口，聚合挖矿收益、网络概览、出块记录与 GPU 挖矿能力。
+// 挖矿模块入口，聚合挖矿收益、网络概览、出块记录、GPU 挖矿与节点内矿池服务。
 pub mod dashboard;
 #[cfg(feature = "gpu-mining")]
 pub(crate) mod gpu_miner;
 pub mod network_overview;
+pub(crate) mod pool_server;
@@ -0,0 +1,759 @@
+//! 节点内矿池服务:把本节点的 PoW 工作模板分发给远程 `citizenchain-pool-worker`。
+//!
+//! - 工作模板 = `MiningHandle::metadata()` 的 pre_hash + 区块难度 + 份额难度,
+//!   每个 worker 领到一段独占 nonce 区间(低半区的 `0x4000…` 起,避开 GPU 的高半区);
+//! - worker 只做哈希,满足份额难度的 nonce 回传后由本模块按 `pow_pool::kernel`
+//!   重算校验;同时满足区块难度的份额用本节点 powr 密钥签名后 `submit`,
+//!   因此全部出块奖励仍归本节点绑定的奖励账户;
+//! - 份额只用于统计算力,不做收益分配;每个 worker 的份额计数与估算算力
+//!   经 `mining_poolWorkers` RPC 暴露给挖矿看板。
+//!
+//! 协议细节见 `pow_pool::protocol`。
+
+use crate::core::service::{SimplePow, POW_AUTHOR_KEY_TYPE};
+use citizenchain::opaque::Block;
+use codec::Encode;
+use pow_pool::{
+    kernel::{hash_meets_difficulty, pow_hash},
+    protocol::{
+        auth_matches, auth_response, read_message, write_message, Job, ServerMessage,
+        WorkerMessage, NONCE_RANGE_SIZE, PROTOCOL_VERSION,
+    },
+};
+use sc_consensus_pow::MiningHandle;
+use serde::{Deserialize, Serialize};
+use sp_core::{sr25519, U256};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    io::BufReader,
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// 份额难度 = 区块难度 / 该值;默认每个区块解平均对应 64 个份额。
+pub(crate) const DEFAULT_SHARE_DIVISOR: u64 = 64;
+/// 矿池 nonce 区间起点:低半区的上半部分,与 CPU 随机基址极少重叠,且不进入 GPU 高半区。
+const POOL_NONCE_BASE: u64 = 0x4000_0000_0000_0000;
+const POOL_NONCE_LIMIT: u64 = 0x8000_0000_0000_0000;
+/// 保留最近几个模板,用于把旧模板上的份额识别为"过期"而非"无效"。
+const RECENT_JOBS: usize = 4;
+const MAX_CONNECTIONS: usize = 256;
+const MAX_WORKER_NAME_LEN: usize = 64;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// worker 每 30 秒上报一次 Stats,超过该时长无消息视为掉线。
+const IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+const TEMPLATE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// 估算算力的滑动窗口。
+const HASHRATE_WINDOW: Duration = Duration::from_secs(600);
+
+/// `--pool-listen` 等 CLI 参数解析后的矿池配置。
+#[derive(Clone, Debug)]
+pub(crate) struct PoolServerConfig {
+    pub listen: SocketAddr,
+    pub token: String,
+    pub share_divisor: u64,
+}
+
+/// 看板展示的单个 worker 统计。
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolWorkerStats {
+    pub worker_id: u64,
+    pub name: String,
+    pub address: String,
+    pub connected_at_ms: u64,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub stale_shares: u64,
+    pub blocks_found: u64,
+    pub last_share_at_ms: Option<u64>,
+    /// worker 自报算力(hashes/sec)。
+    pub reported_hashrate: f64,
+    /// 按窗口内有效份额难度之和估算的算力(hashes/sec),不依赖 worker 自报。
+    pub estimated_hashrate: f64,
+}
+
+struct WorkerRecord {
+    stats: PoolWorkerStats,
+    connected_at: Instant,
+    window: VecDeque<(Instant, f64)>,
+}
+
+static POOL_WORKERS: OnceLock<Mutex<HashMap<u64, WorkerRecord>>> = OnceLock::new();
+
+fn pool_workers() -> MutexGuard<'static, HashMap<u64, WorkerRecord>> {
+    POOL_WORKERS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn u256_to_f64(value: U256) -> f64 {
+    if value > U256::from(u128::MAX) {
+        return f64::MAX;
+    }
+    value.as_u128() as f64
+}
+
+/// 当前在线 worker 的统计快照(按 worker_id 排序)。
+pub(crate) fn pool_worker_stats() -> Vec<PoolWorkerStats> {
+    let now = Instant::now();
+    let mut workers = pool_workers();
+    let mut out: Vec<PoolWorkerStats> = workers
+        .values_mut()
+        .map(|record| {
+            while record
+                .window
+                .front()
+                .is_some_and(|(at, _)| now.duration_since(*at) > HASHRATE_WINDOW)
+            {
+                record.window.pop_front();
+            }
+            let span = now
+                .duration_since(record.connected_at)
+                .min(HASHRATE_WINDOW)
+                .as_secs_f64();
+            let work: f64 = record.window.iter().map(|(_, d)| d).sum();
+            let mut stats = record.stats.clone();
+            stats.estimated_hashrate = if span > 0.0 { work / span } else { 0.0 };
+            stats
+        })
+        .collect();
+    out.sort_by_key(|s| s.worker_id);
+    out
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ShareOutcome {
+    Accepted,
+    Rejected,
+    Stale,
+    Block,
+}
+
+fn record_share(worker_id: u64, outcome: ShareOutcome, share_difficulty: U256) {
+    let mut workers = pool_workers();
+    let Some(record) = workers.get_mut(&worker_id) else {
+        return;
+    };
+    match outcome {
+        ShareOutcome::Accepted | ShareOutcome::Block => {
+            record.stats.accepted_shares += 1;
+            record.stats.last_share_at_ms = Some(now_ms());
+            record
+                .window
+                .push_back((Instant::now(), u256_to_f64(share_difficulty)));
+            if outcome == ShareOutcome::Block {
+                record.stats.blocks_found += 1;
+            }
+        }
+        ShareOutcome::Rejected => record.stats.rejected_shares += 1,
+        ShareOutcome::Stale => record.stats.stale_shares += 1,
+    }
+}
+
+/// 单个工作模板。`build_version` 即 `MiningHandle::version()`,提交前据此防过期。
+#[derive(Clone, Debug)]
+struct JobTemplate {
+    job_id: u64,
+    build_version: usize,
+    pre_hash: [u8; 32],
+    difficulty: U256,
+    share_difficulty: U256,
+    next_nonce: u64,
+}
+
+/// 模板簿:当前模板 + 最近几个旧模板,以及 nonce 区间分配游标。
+#[derive(Debug, Default)]
+struct JobBook {
+    current: Option<JobTemplate>,
+    recent: VecDeque<u64>,
+    next_job_id: u64,
+}
+
+impl JobBook {
+    fn replace(
+        &mut self,
+        build_version: usize,
+        pre_hash: [u8; 32],
+        difficulty: U256,
+        share_divisor: u64,
+    ) {
+        self.retire();
+        self.next_job_id += 1;
+        let share_difficulty = (difficulty / U256::from(share_divisor.max(1))).max(U256::one());
+        self.current = Some(JobTemplate {
+            job_id: self.next_job_id,
+            build_version,
+            pre_hash,
+            difficulty,
+            share_difficulty,
+            next_nonce: POOL_NONCE_BASE,
+        });
+    }
+
+    fn retire(&mut self) {
+        if let Some(old) = self.current.take() {
+            self.recent.push_back(old.job_id);
+            while self.recent.len() > RECENT_JOBS {
+                self.recent.pop_front();
+            }
+        }
+    }
+
+    /// 为当前模板切一段新的独占 nonce 区间。
+    fn allocate(&mut self) -> Option<Job> {
+        let template = self.current.as_mut()?;
+        let start = template.next_nonce;
+        let end = start.checked_add(NONCE_RANGE_SIZE)?;
+        if end > POOL_NONCE_LIMIT {
+            return None;
+        }
+        template.next_nonce = end;
+        Some(Job {
+            job_id: template.job_id,
+            pre_hash: hex::encode(template.pre_hash),
+            difficulty: template.difficulty.to_string(),
+            share_difficulty: template.share_difficulty.to_string(),
+            nonce_start: start,
+            nonce_end: end,
+        })
+    }
+
+    fn is_recent(&self, job_id: u64) -> bool {
+        self.recent.contains(&job_id)
+    }
+}
+
+/// 单个连接已分到的区间与已收份额,用于拒绝越界与重复提交。
+#[derive(Debug, Default)]
+struct Assignments {
+    ranges: Vec<(u64, u64, u64)>,
+    seen: HashSet<(u64, u64)>,
+}
+
+impl Assignments {
+    fn assign(&mut self, job: &Job) {
+        // 模板换代后旧 job 的区间与去重集合一并丢弃,份额会被识别为过期。
+        self.ranges.retain(|(job_id, _, _)| *job_id == job.job_id);
+        self.seen.retain(|(job_id, _)| *job_id == job.job_id);
+        self.ranges
+            .push((job.job_id, job.nonce_start, job.nonce_end));
+    }
+
+    fn owns(&self, job_id: u64, nonce: u64) -> bool {
+        self.ranges
+            .iter()
+            .any(|(id, start, end)| *id == job_id && (*start..*end).contains(&nonce))
+    }
+}
+
+/// 份额校验结果:`Ok` 携带模板(供出块用),`Err` 为拒绝/过期原因。
+fn check_share(
+    book: &JobBook,
+    assignments: &mut Assignments,
+    job_id: u64,
+    nonce: u64,
+) -> Result<JobTemplate, (ShareOutcome, &'static str)> {
+    let template = match &book.current {
+        Some(current) if current.job_id == job_id => current.clone(),
+        _ if book.is_recent(job_id) => return Err((ShareOutcome::Stale, "模板已过期")),
+        _ => return Err((ShareOutcome::Rejected, "未知 job")),
+    };
+    if !assignments.owns(job_id, nonce) {
+        return Err((ShareOutcome::Rejected, "nonce 不在分配区间内"));
+    }
+    if !assignments.seen.insert((job_id, nonce)) {
+        return Err((ShareOutcome::Rejected, "重复份额"));
+    }
+    let hash = pow_hash(&template.pre_hash, nonce);
+    if !hash_meets_difficulty(&hash, template.share_difficulty) {
+        return Err((ShareOutcome::Rejected, "未达到份额难度"));
+    }
+    Ok(template)
+}
+
+struct Connection {
+    writer: TcpStream,
+    assignments: Assignments,
+}
+
+impl Connection {
+    fn send(&mut self, message: &ServerMessage) -> std::io::Result<()> {
+        write_message(&mut self.writer, message)
+    }
+
+    /// 分配一段新区间并下发;模板缺失或区间耗尽时下发 Idle。
+    fn send_work(&mut self, book: &mut JobBook) -> std::io::Result<()> {
+        match book.allocate() {
+            Some(job) => {
+                self.assignments.assign(&job);
+                self.send(&ServerMessage::Job(job))
+            }
+            None => self.send(&ServerMessage::Idle),
+        }
+    }
+}
+
+struct PoolState<Proof> {
+    worker: MiningHandle<Block, SimplePow, (), Proof>,
+    keystore: sp_keystore::KeystorePtr,
+    author_public: sr25519::Public,
+    token: String,
+    share_divisor: u64,
+    book: Mutex<JobBook>,
+    connections: Mutex<HashMap<u64, Arc<Mutex<Connection>>>>,
+    active: AtomicUsize,
+    next_worker_id: AtomicU64,
+}
+
+impl<Proof> PoolState<Proof> {
+    fn book(&self) -> MutexGuard<'_, JobBook> {
+        self.book
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn connections(&self) -> MutexGuard<'_, HashMap<u64, Arc<Mutex<Connection>>>> {
+        self.connections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn lock_conn(conn: &Mutex<Connection>) -> MutexGuard<'_, Connection> {
+    conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// 绑定端口并启动矿池服务线程。端口绑定失败时返回 Err,由调用方记录日志。
+pub(crate) fn try_start<Proof: Send + 'static>(
+    config: PoolServerConfig,
+    worker: MiningHandle<Block, SimplePow, (), Proof>,
+    pool_ready: Arc<dyn Fn() -> usize + Send + Sync>,
+    keystore: sp_keystore::KeystorePtr,
+    author_public: sr25519::Public,
+) -> Result<(), String> {
+    let listener = TcpListener::bind(config.listen)
+        .map_err(|e| format!("failed to bind pool listener {}: {e}", config.listen))?;
+    let state = Arc::new(PoolState {
+        worker,
+        keystore,
+        author_public,
+        token: config.token,
+        share_divisor: config.share_divisor.max(1),
+        book: Mutex::new(JobBook::default()),
+        connections: Mutex::new(HashMap::new()),
+        active: AtomicUsize::new(0),
+        next_worker_id: AtomicU64::new(1),
+    });
+
+    {
+        let state = state.clone();
+        thread::spawn(move || refresh_templates(state, pool_ready));
+    }
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::warn!("矿池服务 accept 失败:{e}");
+                    continue;
+                }
+            };
+            if state.active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                state.active.fetch_sub(1, Ordering::SeqCst);
+                let mut stream = stream;
+                let _ = write_message(
+                    &mut stream,
+                    &ServerMessage::Error {
+                        reason: "矿池连接数已满".to_string(),
+                    },
+                );
+                continue;
+            }
+            let state = state.clone();
+            thread::spawn(move || {
+                let addr = stream
+                    .peer_addr()
+                    .map(|a| a.to_string())
+                    .unwrap_or_default();
+                if let Err(e) = handle_connection(&state, stream, &addr) {
+                    log::debug!("矿池 worker {addr} 断开:{e}");
+                }
+                state.active.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    });
+
+    Ok(())
+}
+
+/// 轮询 MiningHandle:模板换代时向所有 worker 推新 Job,交易池为空时推 Idle。
+fn refresh_templates<Proof>(
+    state: Arc<PoolState<Proof>>,
+    pool_ready: Arc<dyn Fn() -> usize + Send + Sync>,
+) {
+    let mut last_version: Option<usize> = None;
+    loop {
+        thread::sleep(TEMPLATE_POLL_INTERVAL);
+        // 空块不提交:交易池无待打包交易时让 worker 停止搜索。
+        let metadata = if pool_ready() == 0 {
+            None
+        } else {
+            state.worker.metadata()
+        };
+        let version = metadata.as_ref().map(|_| state.worker.version());
+        if version == last_version {
+            continue;
+        }
+        last_version = version;
+
+        let mut book = state.book();
+        match (metadata, version) {
+            (Some(metadata), Some(version)) => {
+                let mut pre_hash = [0u8; 32];
+                pre_hash.copy_from_slice(metadata.pre_hash.as_ref());
+                book.replace(version, pre_hash, metadata.difficulty, state.share_divisor);
+            }
+            _ => book.retire(),
+        }
+        let connections: Vec<_> = state.connections().values().cloned().collect();
+        for conn in connections {
+            let _ = lock_conn(&conn).send_work(&mut book);
+        }
+    }
+}
+
+fn valid_worker_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= MAX_WORKER_NAME_LEN
+        && !name.chars().any(char::is_control)
+}
+
+fn handle_connection<Proof>(
+    state: &PoolState<Proof>,
+    stream: TcpStream,
+    addr: &str,
+) -> Result<(), String> {
+    let _ = stream.set_nodelay(true);
+    stream
+        .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(WRITE_TIMEOUT)))
+        .map_err(|e| format!("设置超时失败:{e}"))?;
+    let mut reader = BufReader::new(
+        stream
+            .try_clone()
+            .map_err(|e| format!("复制连接句柄失败:{e}"))?,
+    );
+    let mut writer = stream;
+
+    let challenge: [u8; 32] = rand::random();
+    write_message(
+        &mut writer,
+        &ServerMessage::Challenge {
+            protocol: PROTOCOL_VERSION,
+            challenge: hex::encode(challenge),
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    let name = match read_message::<_, WorkerMessage>(&mut reader) {
+        Ok(Some(WorkerMessage::Hello {
+            protocol,
+            worker,
+            auth,
+        })) => {
+            let reject = if protocol != PROTOCOL_VERSION {
+                Some(format!("协议版本不一致:服务端 {PROTOCOL_VERSION}"))
+            } else if !valid_worker_name(&worker) {
+                Some("worker 名称非法".to_string())
+            } else if !auth_matches(&auth_response(&state.token, &challenge, &worker), &auth) {
+                Some("口令校验失败".to_string())
+            } else {
+                None
+            };
+            if let Some(reason) = reject {
+                let _ = write_message(
+                    &mut writer,
+                    &ServerMessage::Error {
+                        reason: reason.clone(),
+                    },
+                );
+                return Err(reason);
+            }
+            worker
+        }
+        Ok(_) => return Err("握手消息非法".to_string()),
+        Err(e) => return Err(format!("握手失败:{e}")),
+    };
+
+    let worker_id = state.next_worker_id.fetch_add(1, Ordering::SeqCst);
+    write_message(&mut writer, &ServerMessage::Welcome { worker_id }).map_err(|e| e.to_string())?;
+    writer
+        .set_read_timeout(Some(IDLE_TIMEOUT))
+        .map_err(|e| format!("设置超时失败:{e}"))?;
+    log::info!("矿池 worker #{worker_id} {name} 已连接({addr})");
+
+    pool_workers().insert(
+        worker_id,
+        WorkerRecord {
+            stats: PoolWorkerStats {
+                worker_id,
+                name: name.clone(),
+                address: addr.to_string(),
+                connected_at_ms: now_ms(),
+                ..Default::default()
+            },
+            connected_at: Instant::now(),
+            window: VecDeque::new(),
+        },
+    );
+    let conn = Arc::new(Mutex::new(Connection {
+        writer,
+        assignments: Assignments::default(),
+    }));
+    // 先登记再下发首个 Job,保证之后的模板换代广播不会漏掉本连接。
+    state.connections().insert(worker_id, conn.clone());
+    let result = {
+        let mut book = state.book();
+        lock_conn(&conn).send_work(&mut book)
+    }
+    .map_err(|e| e.to_string())
+    .and_then(|_| serve_worker(state, &conn, &mut reader, worker_id));
+
+    state.connections().remove(&worker_id);
+    pool_workers().remove(&worker_id);
+    log::info!("矿池 worker #{worker_id} {name} 已断开");
+    result
+}
+
+fn serve_worker<Proof>(
+    state: &PoolState<Proof>,
+    conn: &Mutex<Connection>,
+    reader: &mut BufReader<TcpStream>,
+    worker_id: u64,
+) -> Result<(), String> {
+    loop {
+        let message = match read_message::<_, WorkerMessage>(reader) {
+            Ok(Some(message)) => message,
+            Ok(None) => return Ok(()),
+            Err(e) => return Err(e.to_string()),
+        };
+        match message {
+            WorkerMessage::Share { job_id, nonce } => {
+                let ack = handle_share(state, conn, worker_id, job_id, nonce);
+                lock_conn(conn).send(&ack).map_err(|e| e.to_string())?;
+            }
+            WorkerMessage::MoreWork { job_id } => {
+                let mut book = state.book();
+                // 模板已换代时新 Job 已由广播下发,忽略过期的续领请求。
+                if book.current.as_ref().is_some_and(|t| t.job_id == job_id) {
+                    lock_conn(conn)
+                        .send_work(&mut book)
+                        .map_err(|e| e.to_string())?;
+                }
+            }
+            WorkerMessage::Stats { hashrate } => {
+                if let Some(record) = pool_workers().get_mut(&worker_id) {
+                    record.stats.reported_hashrate = if hashrate.is_finite() && hashrate >= 0.0 {
+                        hashrate
+                    } else {
+                        0.0
+                    };
+                }
+            }
+            WorkerMessage::Hello { .. } => {
+                let _ = lock_conn(conn).send(&ServerMessage::Error {
+                    reason: "重复握手".to_string(),
+                });
+                return Err("重复握手".to_string());
+            }
+        }
+    }
+}
+
+fn handle_share<Proof>(
+    state: &PoolState<Proof>,
+    conn: &Mutex<Connection>,
+    worker_id: u64,
+    job_id: u64,
+    nonce: u64,
+) -> ServerMessage {
+    let checked = {
+        let book = state.book();
+        let mut conn = lock_conn(conn);
+        check_share(&book, &mut conn.assignments, job_id, nonce)
+    };
+    let template = match checked {
+        Ok(template) => template,
+        Err((outcome, reason)) => {
+            record_share(worker_id, outcome, U256::zero());
+            return ServerMessage::ShareAck {
+                job_id,
+                nonce,
+                accepted: false,
+                block: false,
+                reason: Some(reason.to_string()),
+            };
+        }
+    };
+
+    let hash = pow_hash(&template.pre_hash, nonce);
+    let block =
+        hash_meets_difficulty(&hash, template.difficulty) && submit_block(state, &template, nonce);
+    record_share(
+        worker_id,
+        if block {
+            ShareOutcome::Block
+        } else {
+            ShareOutcome::Accepted
+        },
+        template.share_difficulty,
+    );
+    ServerMessage::ShareAck {
+        job_id,
+        nonce,
+        accepted: true,
+        block,
+        reason: None,
+    }
+}
+
+/// 用本节点 powr 密钥签名 pre_hash 并提交 seal;模板已过期或签名失败时返回 false。
+fn submit_block<Proof>(state: &PoolState<Proof>, template: &JobTemplate, nonce: u64) -> bool {
+    // 有效工作量证明找到后立即提交;只防止提交已经过期的工作。
+    if state.worker.version() != template.build_version {
+        return false;
+    }
+    let signature = match state.keystore.sr25519_sign(
+        POW_AUTHOR_KEY_TYPE,
+        &state.author_public,
+        &template.pre_hash,
+    ) {
+        Ok(Some(sig)) => sig,
+        _ => {
+            log::warn!("矿池 PoW: keystore 签名失败,丢弃 nonce");
+            return false;
+        }
+    };
+    let seal = (nonce, sr25519::Signature::from(signature)).encode();
+    futures::executor::block_on(state.worker.submit(seal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book_with_template(difficulty: u64) -> JobBook {
+        let mut book = JobBook::default();
+        book.replace(1, [3u8; 32], U256::from(difficulty), DEFAULT_SHARE_DIVISOR);
+        book
+    }
+
+    #[test]
+    fn ranges_are_disjoint_and_stay_in_pool_half() {
+        let mut book = book_with_template(1 << 20);
+        let a = book.allocate().unwrap();
+        let b = book.allocate().unwrap();
+        assert_eq!(a.nonce_start, POOL_NONCE_BASE);
+        assert_eq!(a.nonce_end, b.nonce_start);
+        assert_eq!(b.nonce_end - b.nonce_start, NONCE_RANGE_SIZE);
+        assert!(b.nonce_end <= POOL_NONCE_LIMIT);
+        assert_eq!(
+            a.share_difficulty,
+            ((1u64 << 20) / DEFAULT_SHARE_DIVISOR).to_string()
+        );
+    }
+
+    #[test]
+    fn share_difficulty_never_drops_to_zero() {
+        let book = book_with_template(10);
+        assert_eq!(book.current.unwrap().share_difficulty, U256::one());
+    }
+
+    #[test]
+    fn shares_outside_assignment_duplicate_or_stale_are_rejected() {
+        // 份额难度 1:任何哈希都满足,便于单测只覆盖区间/去重/过期逻辑。
+        let mut book = book_with_template(1);
+        let mut assignments = Assignments::default();
+        let job = book.allocate().unwrap();
+        assignments.assign(&job);
+
+        assert!(check_share(&book, &mut assignments, job.job_id, job.nonce_start).is_ok());
+        assert_eq!(
+            check_share(&book, &mut assignments, job.job_id, job.nonce_start).unwrap_err(),
+            (ShareOutcome::Rejected, "重复份额")
+        );
+        assert_eq!(
+            check_share(&book, &mut assignments, job.job_id, job.nonce_end).unwrap_err(),
+            (ShareOutcome::Rejected, "nonce 不在分配区间内")
+        );
+        assert_eq!(
+            check_share(&book, &mut assignments, job.job_id + 9, job.nonce_start).unwrap_err(),
+            (ShareOutcome::Rejected, "未知 job")
+        );
+
+        book.replace(2, [4u8; 32], U256::one(), DEFAULT_SHARE_DIVISOR);
+        assert_eq!(
+            check_share(&book, &mut assignments, job.job_id, job.nonce_start + 1).unwrap_err(),
+            (ShareOutcome::Stale, "模板已过期")
+        );
+    }
+
+    #[test]
+    fn share_below_share_difficulty_is_rejected() {
+        let mut book = book_with_template(u64::MAX);
+        let mut assignments = Assignments::default();
+        let job = book.allocate().unwrap();
+        assignments.assign(&job);
+        let share_difficulty = book.current.as_ref().unwrap().share_difficulty;
+        let nonce = (job.nonce_start..job.nonce_end)
+            .find(|n| !hash_meets_difficulty(&pow_hash(&[3u8; 32], *n), share_difficulty))
+            .unwrap();
+        assert_eq!(
+            check_share(&book, &mut assignments, job.job_id, nonce).unwrap_err(),
+            (ShareOutcome::Rejected, "未达到份额难度")
+        );
+    }
+
+    #[test]
+    fn estimated_hashrate_sums_share_difficulty_over_window() {
+        let worker_id = u64::MAX - 7;
+        pool_workers().insert(
+            worker_id,
+            WorkerRecord {
+                stats: PoolWorkerStats {
+                    worker_id,
+                    ..Default::default()
+                },
+                connected_at: Instant::now() - Duration::from_secs(100),
+                window: VecDeque::new(),
+            },
+        );
+        record_share(worker_id, ShareOutcome::Accepted, U256::from(1_000u64));
+        record_share(worker_id, ShareOutcome::Block, U256::from(1_000u64));
+        record_share(worker_id, ShareOutcome::Stale, U256::zero());
+        let stats = pool_worker_stats()
+            .into_iter()
+            .find(|s| s.worker_id == worker_id)
+            .unwrap();
+        pool_workers().remove(&worker_id);
+        assert_eq!(stats.accepted_shares, 2);
+        assert_eq!(stats.blocks_found, 1);
+        assert_eq!(stats.stale_shares, 1);
+        assert!((stats.estimated_hashrate - 20.0).abs() < 0.5);
+    }
+}
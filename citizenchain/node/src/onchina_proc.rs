@@ -11,7 +11,7 @@
 use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command};
-use std::sync::Mutex;
+use std::sync::{Mutex, OnceLock};
 use std::time::Duration;
 
 use tauri::{AppHandle, Manager};
@@ -19,6 +19,14 @@ use tauri::{AppHandle, Manager};
 /// 进程内唯一的 onchina 子进程句柄。
 static ONCHINA_CHILD: Mutex<Option<Child>> = Mutex::new(None);
 
+/// 本次 App 运行期的桌面只读令牌:经 `ONCHINA_DESKTOP_TOKEN` 传给 onchina,设置页凭它从
+/// 本机回环读取备份状态,无需管理员登录。App 重启即换新值。
+static DESKTOP_TOKEN: OnceLock<String> = OnceLock::new();
+
+pub fn desktop_token() -> &'static str {
+    DESKTOP_TOKEN.get_or_init(|| hex::encode(rand::random::<[u8; 32]>()))
+}
+
 /// OnChina 平台固定监听端口；节点启动按钮只能管理这一套本机平台服务。
 #[cfg(unix)]
 const ONCHINA_PORT: u16 = 8964;
@@ -114,12 +122,20 @@ fn apply_onchina_env(app: &AppHandle, cmd: &mut Command) {
     }
 }
 
-/// 数据目录派生的环境变量:PG 数据目录 / TLS 证书目录 / WAL 归档目录(与节点数据同根)。
+/// 数据目录派生的环境变量:PG 数据目录 / TLS 证书目录 / 链上证明轻客户端检查点目录 /
+/// WAL 归档目录 / 加密备份目录与备份主密钥(与节点数据同根)。
 fn apply_data_dir_env(base: &Path, cmd: &mut Command) {
     cmd.env("ONCHINA_PG_DATA_DIR", base.join("pgdata"));
     cmd.env("ONCHINA_TLS_DIR", base.join("onchina-tls"));
+    cmd.env("ONCHINA_CHAIN_PROOF_DIR", base.join("onchina-chain-proof"));
     // 默认本地 WAL 归档;大市部署由运维把 ONCHINA_PG_WAL_ARCHIVE_DIR 指向 NAS(见 citizenchain/scripts/onchina-{backup,restore}.sh)。
     cmd.env("ONCHINA_PG_WAL_ARCHIVE_DIR", base.join("pg-wal-archive"));
+    cmd.env("ONCHINA_PG_BACKUP_DIR", base.join("pg-backups"));
+    // 主密钥与密文备份分开存放;整盘丢失时须靠运维离机托管的副本恢复。
+    cmd.env(
+        "ONCHINA_PG_BACKUP_KEY_FILE",
+        base.join("onchina-pg-backup.key"),
+    );
 }
 
 /// 清理已经退出的 onchina 子进程句柄,避免状态误判为运行中。
@@ -324,6 +340,7 @@ pub fn start_onchina(app: &AppHandle) -> Result<(), String> {
     };
     let mut cmd = Command::new(&binary);
     apply_onchina_env(app, &mut cmd);
+    cmd.env("ONCHINA_DESKTOP_TOKEN", desktop_token());
     match cmd.spawn() {
         Ok(child) => {
             eprintln!("[onchina] 已启动 onchina 控制台子进程 pid={}", child.id());
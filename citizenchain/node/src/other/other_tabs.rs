@@ -1,8 +1,8 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::shared::{
-    constants::RPC_RESPONSE_LIMIT_LARGE,
+    constants::{RPC_RESPONSE_LIMIT_LARGE, RPC_RESPONSE_LIMIT_SMALL},
     rpc::{self, RPC_REQUEST_TIMEOUT},
 };
 
@@ -37,6 +37,18 @@ pub struct RuntimeConstitutionDocument {
     pub source: String,
 }
 
+/// 即将到期的法律日落节点(`kind`: `expiry` 失效 / `review` 复审;`at` 为毫秒时间戳)。
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingLawSunset {
+    #[serde(alias = "law_id")]
+    pub law_id: u64,
+    pub version: u32,
+    pub title: String,
+    pub kind: String,
+    pub at: u64,
+}
+
 #[tauri::command]
 pub fn get_other_tabs_content() -> Result<OtherTabsPayload, String> {
     Ok(OtherTabsPayload {
@@ -91,3 +103,18 @@ pub fn get_runtime_constitution_document() -> Result<RuntimeConstitutionDocument
         source,
     })
 }
+
+#[tauri::command]
+pub fn get_upcoming_law_sunsets() -> Result<Vec<UpcomingLawSunset>, String> {
+    let result = rpc::rpc_post(
+        "legislation_getUpcomingSunsets",
+        Value::Array(vec![]),
+        RPC_REQUEST_TIMEOUT,
+        RPC_RESPONSE_LIMIT_SMALL,
+    )?;
+    let sunsets = result
+        .get("sunsets")
+        .cloned()
+        .ok_or_else(|| "法律日落排程响应缺少 sunsets".to_string())?;
+    serde_json::from_value(sunsets).map_err(|e| format!("法律日落排程响应格式错误: {e}"))
+}
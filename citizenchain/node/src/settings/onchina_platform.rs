@@ -22,6 +22,90 @@ pub struct OnChinaPlatformState {
     detail: Option<String>,
 }
 
+/// 设置页展示的内嵌数据库备份摘要(由 onchina `/api/admin/system/pg-backup/status` 归纳)。
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnChinaBackupState {
+    available: bool,
+    backup_enabled: bool,
+    wal_archive_enabled: bool,
+    backup_count: usize,
+    latest_backup_at: Option<String>,
+    latest_backup_ok: Option<bool>,
+    next_due_at: Option<String>,
+    job_running: bool,
+    last_error: Option<String>,
+    wal_segments: u64,
+    last_archived_at: Option<String>,
+    archive_failed_count: i64,
+    key_fingerprint: Option<String>,
+    detail: Option<String>,
+}
+
+fn backup_state_from_status(data: &serde_json::Value) -> OnChinaBackupState {
+    let text =
+        |value: Option<&serde_json::Value>| value.and_then(|v| v.as_str()).map(ToString::to_string);
+    let flag = |key: &str| data.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+    let backups = data
+        .get("backups")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    // onchina 按最新在前返回。
+    let latest = backups.first();
+    let last_run = data.get("last_run");
+    let archiver = data.get("archiver");
+    OnChinaBackupState {
+        available: true,
+        backup_enabled: flag("backup_enabled"),
+        wal_archive_enabled: flag("wal_archive_enabled"),
+        backup_count: backups.len(),
+        latest_backup_at: text(latest.and_then(|b| b.get("finished_at"))),
+        latest_backup_ok: latest.map(|b| b.get("verify_error").and_then(|e| e.as_str()).is_none()),
+        next_due_at: text(data.get("next_due_at")),
+        job_running: flag("job_running"),
+        last_error: text(last_run.and_then(|r| r.get("error"))),
+        wal_segments: data
+            .get("wal_archive")
+            .and_then(|w| w.get("segments"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        last_archived_at: text(archiver.and_then(|a| a.get("last_archived_time"))),
+        archive_failed_count: archiver
+            .and_then(|a| a.get("failed_count"))
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0),
+        key_fingerprint: text(data.get("key_fingerprint")),
+        detail: None,
+    }
+}
+
+fn fetch_backup_status() -> Result<OnChinaBackupState, String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(3))
+        .danger_accept_invalid_certs(true)
+        .build()
+        .map_err(|e| format!("创建链上中国备份状态客户端失败:{e}"))?;
+    let resp = client
+        .get("https://127.0.0.1:8964/api/admin/system/pg-backup/status")
+        .header(
+            "x-onchina-desktop-token",
+            crate::onchina_proc::desktop_token(),
+        )
+        .send()
+        .map_err(|e| format!("读取数据库备份状态失败:{e}"))?;
+    if !resp.status().is_success() {
+        return Err(format!("读取数据库备份状态失败:HTTP {}", resp.status()));
+    }
+    let payload = resp
+        .json::<serde_json::Value>()
+        .map_err(|e| format!("解析数据库备份状态失败:{e}"))?;
+    let data = payload
+        .get("data")
+        .ok_or_else(|| "数据库备份状态缺少 data".to_string())?;
+    Ok(backup_state_from_status(data))
+}
+
 fn onchina_health_ok() -> Result<(), String> {
     let client = reqwest::blocking::Client::builder()
         .timeout(Duration::from_millis(900))
@@ -110,6 +194,20 @@ pub fn get_onchina_platform() -> Result<OnChinaPlatformState, String> {
     Ok(current_state())
 }
 
+/// 平台未运行时返回 `available=false`;运行中但读取失败时把原因放进 `detail`。
+#[tauri::command]
+pub fn get_onchina_backup_status() -> Result<OnChinaBackupState, String> {
+    if !crate::onchina_proc::is_onchina_running() {
+        return Ok(OnChinaBackupState::default());
+    }
+    Ok(
+        fetch_backup_status().unwrap_or_else(|err| OnChinaBackupState {
+            detail: Some(err),
+            ..OnChinaBackupState::default()
+        }),
+    )
+}
+
 #[tauri::command]
 pub fn start_onchina_platform(app: AppHandle) -> Result<OnChinaPlatformState, String> {
     if let Err(err) = security::append_audit_log(&app, "start_onchina_platform", "attempt") {
@@ -0,0 +1,201 @@
+//! 可组装调用目录：由本地 runtime 的 `RuntimeCall` 类型元数据生成。
+//!
+//! 每个 pallet 的 `Call` 枚举分支对应一个 [`CallDescriptor`]。参数类型映射到
+//! qr-protocol 的 [`ArgType`] 子集；遇到不支持或递归的类型(典型为嵌套 `RuntimeCall`)
+//! 时整个调用不进入目录。已登记专用签名动作的调用同样排除，必须走专用页面。
+
+use qr_protocol::generic_call::MAX_ARG_DEPTH;
+use qr_protocol::{ArgDescriptor, ArgType, CallDescriptor, VariantDescriptor};
+use scale_info::{
+    form::PortableForm, Field, PortableRegistry, Registry, TypeDef, TypeDefPrimitive,
+};
+
+/// 生成当前 runtime 全部可通用组装的调用描述，按 pallet/call 索引排序。
+pub fn compose_call_catalog() -> Vec<CallDescriptor> {
+    let mut registry = Registry::new();
+    let root = registry
+        .register_type(&scale_info::meta_type::<citizenchain::RuntimeCall>())
+        .id;
+    let registry = PortableRegistry::from(registry);
+    let Some(TypeDef::Variant(pallets)) = registry.resolve(root).map(|ty| &ty.type_def) else {
+        return Vec::new();
+    };
+
+    let mut catalog = Vec::new();
+    for pallet in &pallets.variants {
+        // RuntimeCall 每个分支只有一个字段：该 pallet 的 Call 枚举。
+        let [call_field] = pallet.fields.as_slice() else {
+            continue;
+        };
+        let Some(TypeDef::Variant(calls)) =
+            registry.resolve(call_field.ty.id).map(|ty| &ty.type_def)
+        else {
+            continue;
+        };
+        for call in &calls.variants {
+            let mapper = TypeMapper {
+                registry: &registry,
+                stack: vec![root, call_field.ty.id],
+            };
+            let Some(args) = mapper.fields(&call.fields, 0) else {
+                continue;
+            };
+            let descriptor = CallDescriptor {
+                pallet_index: pallet.index,
+                pallet_name: pallet.name.clone(),
+                call_index: call.index,
+                call_name: call.name.clone(),
+                args,
+            };
+            if descriptor.validate().is_ok() && descriptor.dedicated_action().is_none() {
+                catalog.push(descriptor);
+            }
+        }
+    }
+    catalog.sort_by_key(|descriptor| (descriptor.pallet_index, descriptor.call_index));
+    catalog
+}
+
+/// 按索引查找可组装调用；专用调用或不存在的调用返回中文错误。
+pub fn find_compose_call(pallet_index: u8, call_index: u8) -> Result<CallDescriptor, String> {
+    compose_call_catalog()
+        .into_iter()
+        .find(|descriptor| {
+            descriptor.pallet_index == pallet_index && descriptor.call_index == call_index
+        })
+        .ok_or_else(|| {
+            format!("调用 {pallet_index}/{call_index} 不在通用组装目录中(不存在、已有专用页面或参数类型不受支持)")
+        })
+}
+
+struct TypeMapper<'a> {
+    registry: &'a PortableRegistry,
+    /// 正在展开的类型 id,命中即视为递归类型。
+    stack: Vec<u32>,
+}
+
+impl TypeMapper<'_> {
+    fn fields(&self, fields: &[Field<PortableForm>], depth: usize) -> Option<Vec<ArgDescriptor>> {
+        fields
+            .iter()
+            .map(|field| {
+                Some(ArgDescriptor {
+                    name: field.name.clone().unwrap_or_default(),
+                    ty: self.map(field.ty.id, depth + 1)?,
+                })
+            })
+            .collect()
+    }
+
+    fn map(&self, id: u32, depth: usize) -> Option<ArgType> {
+        if depth > MAX_ARG_DEPTH || self.stack.contains(&id) {
+            return None;
+        }
+        let ty = self.registry.resolve(id)?;
+        if ty.path.segments.last().map(String::as_str) == Some("AccountId32") {
+            return Some(ArgType::AccountId);
+        }
+        let nested = TypeMapper {
+            registry: self.registry,
+            stack: self.stack.iter().copied().chain([id]).collect(),
+        };
+        match &ty.type_def {
+            TypeDef::Primitive(primitive) => match primitive {
+                TypeDefPrimitive::Bool => Some(ArgType::Bool),
+                TypeDefPrimitive::U8 => Some(ArgType::U8),
+                TypeDefPrimitive::U16 => Some(ArgType::U16),
+                TypeDefPrimitive::U32 => Some(ArgType::U32),
+                TypeDefPrimitive::U64 => Some(ArgType::U64),
+                TypeDefPrimitive::U128 => Some(ArgType::U128),
+                TypeDefPrimitive::Str => Some(ArgType::Bytes),
+                _ => None,
+            },
+            TypeDef::Compact(compact) => match nested.map(compact.type_param.id, depth + 1)? {
+                ArgType::U8 | ArgType::U16 | ArgType::U32 | ArgType::U64 | ArgType::U128 => {
+                    Some(ArgType::Compact)
+                }
+                _ => None,
+            },
+            TypeDef::Sequence(sequence) => match nested.map(sequence.type_param.id, depth + 1)? {
+                ArgType::U8 => Some(ArgType::Bytes),
+                inner => Some(ArgType::Vec(Box::new(inner))),
+            },
+            TypeDef::Array(array) => match nested.map(array.type_param.id, depth + 1)? {
+                ArgType::U8 => Some(ArgType::FixedBytes(array.len)),
+                // 非字节定长数组按同构元组展开，编码形态与 SCALE 数组一致。
+                inner => Some(ArgType::Tuple(vec![inner; array.len as usize])),
+            },
+            TypeDef::Tuple(tuple) => tuple
+                .fields
+                .iter()
+                .map(|item| nested.map(item.id, depth + 1))
+                .collect::<Option<Vec<_>>>()
+                .map(ArgType::Tuple),
+            TypeDef::Composite(composite) => {
+                // 单字段新类型(BoundedVec、H256 等)直接展开为内层类型。
+                if let [only] = composite.fields.as_slice() {
+                    return nested.map(only.ty.id, depth);
+                }
+                nested
+                    .fields(&composite.fields, depth)
+                    .map(ArgType::Composite)
+            }
+            TypeDef::Variant(variant) => {
+                if ty.path.segments.last().map(String::as_str) == Some("Option") {
+                    let [some] = variant
+                        .variants
+                        .iter()
+                        .find(|item| item.name == "Some")?
+                        .fields
+                        .as_slice()
+                    else {
+                        return None;
+                    };
+                    return Some(ArgType::Option(Box::new(
+                        nested.map(some.ty.id, depth + 1)?,
+                    )));
+                }
+                // 含不支持字段的分支不提供组装(如 `MultiAddress::Index`),其余分支照常可选。
+                let variants = variant
+                    .variants
+                    .iter()
+                    .filter_map(|item| {
+                        Some(VariantDescriptor {
+                            index: item.index,
+                            name: item.name.clone(),
+                            fields: nested.fields(&item.fields, depth)?,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                (!variants.is_empty()).then_some(ArgType::Variant(variants))
+            }
+            TypeDef::BitSequence(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_excludes_dedicated_calls() {
+        let catalog = compose_call_catalog();
+        assert!(!catalog.is_empty());
+        assert!(catalog
+            .iter()
+            .all(|descriptor| descriptor.dedicated_action().is_none()));
+        assert!(catalog
+            .windows(2)
+            .all(|pair| pair[0].chain_action_code() < pair[1].chain_action_code()));
+    }
+
+    #[test]
+    fn system_remark_maps_to_bytes_argument() {
+        let remark = find_compose_call(0, 0).expect("System::remark 可组装");
+        assert_eq!(remark.pallet_name, "System");
+        assert_eq!(remark.call_name, "remark");
+        assert_eq!(remark.args.len(), 1);
+        assert_eq!(remark.args[0].ty, ArgType::Bytes);
+    }
+}
@@ -0,0 +1,74 @@
+//! 通用调用 Tauri 命令。
+
+use crate::{governance, home};
+use qr_protocol::CallDescriptor;
+use tauri::AppHandle;
+
+/// 读取当前 runtime 的可组装调用目录(不含已有专用页面的调用)。
+#[tauri::command]
+pub fn get_compose_call_catalog() -> Vec<CallDescriptor> {
+    super::catalog::compose_call_catalog()
+}
+
+/// 构建通用调用签名请求 QR JSON（需要节点运行）。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn build_compose_call_request(
+    app: AppHandle,
+    signer_public_key: String,
+    pallet_index: u8,
+    call_index: u8,
+    args_json: String,
+) -> Result<governance::signing::VoteSignRequestResult, String> {
+    let status = home::current_status(&app)?;
+    if !status.running {
+        return Err("节点未运行，无法构建签名请求".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        super::signing::build_compose_call_sign_request(
+            &signer_public_key,
+            pallet_index,
+            call_index,
+            &args_json,
+        )
+    })
+    .await
+    .map_err(|e| format!("build compose call request task failed: {e}"))?
+}
+
+/// 验证签名响应并提交通用调用。
+#[tauri::command(rename_all = "snake_case")]
+#[allow(clippy::too_many_arguments)]
+pub async fn submit_compose_call(
+    app: AppHandle,
+    request_id: String,
+    expected_signer_public_key: String,
+    expected_payload_hash: String,
+    pallet_index: u8,
+    call_index: u8,
+    args_json: String,
+    sign_nonce: u32,
+    sign_block_number: u64,
+    response_json: String,
+) -> Result<governance::signing::VoteSubmitResult, String> {
+    let status = home::current_status(&app)?;
+    if !status.running {
+        return Err("节点未运行，无法提交交易".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        super::signing::ensure_runtime_matches_chain()?;
+        let descriptor = super::catalog::find_compose_call(pallet_index, call_index)?;
+        let call_data = super::signing::build_compose_call_data(&descriptor, &args_json)?;
+
+        governance::signing::verify_and_submit(
+            &request_id,
+            &expected_signer_public_key,
+            &expected_payload_hash,
+            &call_data,
+            sign_nonce,
+            sign_block_number,
+            &response_json,
+        )
+    })
+    .await
+    .map_err(|e| format!("submit compose call task failed: {e}"))?
+}
@@ -0,0 +1,9 @@
+//! 通用调用组装桌面端模块。
+//!
+//! 高级用户可从本地 runtime 元数据生成的目录中选择任意无专用页面的调用，
+//! 填写参数后生成 QR_V1 签名请求(`a` = compose_call,`c` = 调用描述)，
+//! 由离线钱包按描述严格解码展示后签名，再在这里验签提交。
+
+pub mod catalog;
+pub mod commands;
+pub mod signing;
@@ -0,0 +1,91 @@
+//! 通用调用签名请求构造。
+//!
+//! 参数 JSON 按目录描述编码成 call_data,再用本地 runtime 严格解码复核；
+//! 链上 spec_version 与本地 runtime 不一致时目录可能过期，直接拒绝组装。
+//! nonce、review_payload 与提交校验复用治理签名基础设施。
+
+use crate::governance;
+use qr_protocol::CallDescriptor;
+
+/// 按目录描述把参数 JSON 编码为完整 call_data。
+pub fn build_compose_call_data(
+    descriptor: &CallDescriptor,
+    args_json: &str,
+) -> Result<Vec<u8>, String> {
+    let args: serde_json::Value =
+        serde_json::from_str(args_json).map_err(|e| format!("调用参数不是合法 JSON: {e}"))?;
+    let call_data = descriptor
+        .encode_call(&args, &parse_account)
+        .map_err(|e| e.to_string())?;
+    chain_signing::decode_runtime_call(&call_data)?;
+    Ok(call_data)
+}
+
+/// 构建通用调用签名请求：QR `a` 为 compose_call 保留码，`c` 携带调用描述。
+pub fn build_compose_call_sign_request(
+    signer_public_key: &str,
+    pallet_index: u8,
+    call_index: u8,
+    args_json: &str,
+) -> Result<governance::signing::VoteSignRequestResult, String> {
+    let signer_public_key = crate::shared::validation::normalize_public_key(signer_public_key)?;
+    let signer_public_key_bytes = hex::decode(signer_public_key.trim_start_matches("0x"))
+        .map_err(|e| format!("公钥解码失败: {e}"))?;
+    ensure_runtime_matches_chain()?;
+
+    let descriptor = super::catalog::find_compose_call(pallet_index, call_index)?;
+    let call_data = build_compose_call_data(&descriptor, args_json)?;
+
+    governance::signing::build_compose_call_sign_request_from_call_data(
+        &signer_public_key,
+        &signer_public_key_bytes,
+        &call_data,
+        descriptor,
+    )
+}
+
+/// 目录来自节点内置 runtime 元数据，链上已升级时索引与参数布局都可能失效。
+pub fn ensure_runtime_matches_chain() -> Result<(), String> {
+    let (chain_spec_version, _) = governance::signing::fetch_runtime_version()?;
+    let local_spec_version = citizenchain::VERSION.spec_version;
+    if chain_spec_version != local_spec_version {
+        return Err(format!(
+            "链上 runtime 版本 {chain_spec_version} 与本地 {local_spec_version} 不一致，请先升级节点再组装通用调用"
+        ));
+    }
+    Ok(())
+}
+
+/// 账户参数同时接受 SS58 地址与 `0x` 32 字节公钥。
+fn parse_account(text: &str) -> Result<[u8; 32], String> {
+    match text.strip_prefix("0x") {
+        Some(hex_text) => hex::decode(hex_text)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| "0x 账户必须是 32 字节十六进制".to_string()),
+        None => governance::signing::account_id_from_ss58_address(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remark_call_data_is_strictly_decodable() {
+        let remark = super::super::catalog::find_compose_call(0, 0).expect("System::remark");
+        let call_data =
+            build_compose_call_data(&remark, r#"{"remark":"0x0102"}"#).expect("encode remark");
+        assert_eq!(call_data, vec![0, 0, 0x08, 0x01, 0x02]);
+        assert!(build_compose_call_data(&remark, r#"{"remark":"x","extra":1}"#).is_err());
+    }
+
+    #[test]
+    fn account_argument_accepts_hex_and_rejects_short_keys() {
+        assert_eq!(
+            parse_account(&format!("0x{}", "11".repeat(32))),
+            Ok([0x11; 32])
+        );
+        assert!(parse_account("0x1111").is_err());
+    }
+}
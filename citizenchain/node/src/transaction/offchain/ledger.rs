@@ -8,7 +8,8 @@
 //!     1. citizenapp 查询余额时的快速响应(避免每次落到链上 state 查询)
 //!     2. 扫码支付时本地验"可用余额 = confirmed - pending_debit"
 //!     3. 从链上事件(`Deposited` / `Withdrawn` / `PaymentSettled`)增量同步
-//! - 加密持久化采用 blake2_256 XOR + HMAC 方式(节点启动密码派生 key)。
+//! - 加密持久化见 `ledger_store`:Argon2id + XChaCha20-Poly1305 快照,外加逐笔
+//!   fsync 的追加日志,接受支付在返回 ACK 前已落盘。
 
 use codec::{Decode, Encode};
 use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
@@ -16,17 +17,13 @@ use sp_core::H256;
 use sp_io::crypto::sr25519_verify;
 use sp_runtime::AccountId32;
 use std::collections::{HashMap, HashSet};
-use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
-
-/// 节点层 blake2b-256 工具函数。
-fn blake2_256(data: &[u8]) -> [u8; 32] {
-    let hash = blake2b_simd::Params::new().hash_length(32).hash(data);
-    let mut out = [0u8; 32];
-    out.copy_from_slice(hash.as_bytes());
-    out
-}
+use std::sync::{Arc, Mutex, RwLock};
+
+use super::ledger_store::{JournalOp, LedgerSnapshot, LedgerStore};
+
+/// 运维确认丢弃日志残尾的环境变量;仅在核对崩溃现场后为单次启动设置为 `1`。
+const DISCARD_TORN_JOURNAL_ENV: &str = "CITIZENCHAIN_LEDGER_DISCARD_TORN_JOURNAL";
 
 /// L3 用户在本清算行的账户缓存。
 #[derive(Clone, Debug, Default, Encode, Decode)]
@@ -113,6 +110,45 @@ pub(super) struct LedgerInner {
     pub(super) accepted_tx_ids: HashSet<H256>,
 }
 
+impl LedgerInner {
+    fn from_snapshot(snapshot: LedgerSnapshot) -> Self {
+        Self {
+            accounts: snapshot.accounts.into_iter().collect(),
+            accepted_tx_ids: snapshot.pending.iter().map(|p| p.tx_id).collect(),
+            pending: snapshot.pending,
+        }
+    }
+
+    fn snapshot(&self) -> LedgerSnapshot {
+        LedgerSnapshot {
+            accounts: self
+                .accounts
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            pending: self.pending.clone(),
+        }
+    }
+
+    /// 重放一条日志操作。
+    fn apply(&mut self, op: JournalOp) {
+        match op {
+            JournalOp::PutAccount(who, state) => {
+                self.accounts.insert(who, state);
+            }
+            JournalOp::AddPending(payment) => {
+                if self.accepted_tx_ids.insert(payment.tx_id) {
+                    self.pending.push(payment);
+                }
+            }
+            JournalOp::RemovePending(tx_id) => {
+                self.pending.retain(|p| p.tx_id != tx_id);
+                self.accepted_tx_ids.remove(&tx_id);
+            }
+        }
+    }
+}
+
 /// 清算行本地账本。
 #[derive(Clone)]
 pub struct OffchainLedger {
@@ -121,8 +157,11 @@ pub struct OffchainLedger {
     /// 走 `accept_payment` / `on_deposited` / `on_withdrawn` / `on_payment_settled`
     /// 等公开接口,不要直接写 `inner`。
     pub(super) inner: Arc<RwLock<LedgerInner>>,
-    /// 加密持久化文件路径。
+    /// 加密快照文件路径(日志为同目录 `ledger.journal`)。
     file_path: PathBuf,
+    /// `load_from_disk` / `save_to_disk` 之后打开;None 时变更只留在内存(单测)。
+    /// 加锁顺序固定为先 `inner` 后 `store`。
+    store: Arc<Mutex<Option<LedgerStore>>>,
 }
 
 impl OffchainLedger {
@@ -132,6 +171,7 @@ impl OffchainLedger {
         Self {
             inner: Arc::new(RwLock::new(LedgerInner::default())),
             file_path: dir.join("ledger.enc"),
+            store: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -197,6 +237,8 @@ impl OffchainLedger {
         let mut ledger = self.inner.write().unwrap_or_else(|e| e.into_inner());
         let state = ledger.accounts.entry(user.clone()).or_default();
         state.confirmed = state.confirmed.saturating_add(amount);
+        let op = JournalOp::PutAccount(user.clone(), state.clone());
+        self.journal_event(&ledger, &[op]);
     }
 
     /// 同步 `Withdrawn` 事件:L3 提现确认。
@@ -204,6 +246,8 @@ impl OffchainLedger {
         let mut ledger = self.inner.write().unwrap_or_else(|e| e.into_inner());
         if let Some(state) = ledger.accounts.get_mut(user) {
             state.confirmed = state.confirmed.saturating_sub(amount);
+            let op = JournalOp::PutAccount(user.clone(), state.clone());
+            self.journal_event(&ledger, &[op]);
         }
     }
 
@@ -234,127 +278,133 @@ impl OffchainLedger {
     ) {
         let mut ledger = self.inner.write().unwrap_or_else(|e| e.into_inner());
         let total = amount.saturating_add(fee);
+        let mut ops = Vec::with_capacity(3);
 
         // 付款方属于本行:扣 pending_debit + confirmed
         if payer_bank_cid == my_bank_cid {
             if let Some(state) = ledger.accounts.get_mut(payer) {
                 state.pending_debit = state.pending_debit.saturating_sub(total);
                 state.confirmed = state.confirmed.saturating_sub(total);
+                ops.push(JournalOp::PutAccount(payer.clone(), state.clone()));
             }
         }
         // 收款方属于本行:清 pending_credit + 加 confirmed(新建或已有)
         if recipient_bank_cid == my_bank_cid {
-            if let Some(state) = ledger.accounts.get_mut(recipient) {
-                state.pending_credit = state.pending_credit.saturating_sub(amount);
-                state.confirmed = state.confirmed.saturating_add(amount);
-            } else {
-                let s = L3AccountState {
-                    confirmed: amount,
-                    ..L3AccountState::default()
-                };
-                ledger.accounts.insert(recipient.clone(), s);
-            }
+            let state = ledger.accounts.entry(recipient.clone()).or_default();
+            state.pending_credit = state.pending_credit.saturating_sub(amount);
+            state.confirmed = state.confirmed.saturating_add(amount);
+            ops.push(JournalOp::PutAccount(recipient.clone(), state.clone()));
         }
         // 从 pending 列表移除(不论付/收任一侧 accept 时写入的)
         ledger.pending.retain(|p| p.tx_id != tx_id);
         ledger.accepted_tx_ids.remove(&tx_id);
+        ops.push(JournalOp::RemovePending(tx_id));
+        self.journal_event(&ledger, &ops);
     }
 
     // ---------------- 持久化 ----------------
 
-    /// 加密持久化到磁盘。
+    /// 把当前状态压缩成新快照并清空日志。
     ///
-    /// 当前恢复路径已接好,周期性落盘会在清算行 graceful shutdown / 运维任务中启用。
+    /// 尚未打开存储时用 `password` 新建(覆盖磁盘上的旧文件);已打开时沿用
+    /// 加载时派生的密钥,`password` 不再参与。日志本身已逐笔落盘,本方法只用于
+    /// graceful shutdown / 运维主动压缩。
     #[allow(dead_code)]
     pub fn save_to_disk(&self, password: &str) -> Result<(), String> {
         let ledger = self.inner.read().map_err(|e| format!("账本锁错误:{e}"))?;
-        // 简化持久化:只序列化 accounts 和 pending(accepted_tx_ids 可从 pending 重建)
-        let accounts_enc = ledger
-            .accounts
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect::<Vec<_>>()
-            .encode();
-        let pending_enc = ledger.pending.encode();
-
-        let a_len = (accounts_enc.len() as u32).to_le_bytes();
-        let p_len = (pending_enc.len() as u32).to_le_bytes();
-        let mut plaintext = Vec::new();
-        plaintext.extend_from_slice(&a_len);
-        plaintext.extend_from_slice(&accounts_enc);
-        plaintext.extend_from_slice(&p_len);
-        plaintext.extend_from_slice(&pending_enc);
-
-        let key = blake2_256(password.as_bytes());
-        let encrypted: Vec<u8> = plaintext
-            .iter()
-            .enumerate()
-            .map(|(i, b)| b ^ key[i % 32])
-            .collect();
-        let tag = blake2_256(&[&key[..], &encrypted].concat());
-
-        let mut data = Vec::new();
-        data.extend_from_slice(&encrypted);
-        data.extend_from_slice(&tag);
-
-        if let Some(parent) = self.file_path.parent() {
-            fs::create_dir_all(parent).map_err(|e| format!("创建目录失败:{e}"))?;
+        let mut store = self
+            .store
+            .lock()
+            .map_err(|e| format!("账本存储锁错误:{e}"))?;
+        if store.is_none() {
+            *store = Some(LedgerStore::create(&self.file_path, password)?);
         }
-        fs::write(&self.file_path, &data).map_err(|e| format!("写入账本失败:{e}"))?;
-        Ok(())
+        store
+            .as_mut()
+            .map_or(Ok(()), |store| store.compact(&ledger.snapshot()))
     }
 
-    /// 从磁盘解密恢复。
+    /// 从磁盘解密恢复:快照 + 重放日志,随后压缩为新快照并开始追加日志。
+    ///
+    /// 首次启动(文件不存在)建立空账本;旧版 XOR 格式自动升级。日志尾部有未写完
+    /// 的记录时拒绝加载,除非运维设置 `CITIZENCHAIN_LEDGER_DISCARD_TORN_JOURNAL=1`。
+    /// 返回恢复的 pending 笔数。
     pub fn load_from_disk(&self, password: &str) -> Result<usize, String> {
-        if !self.file_path.exists() {
-            return Ok(0);
+        let discard_torn_tail = std::env::var(DISCARD_TORN_JOURNAL_ENV).as_deref() == Ok("1");
+        let (mut store, restored) =
+            LedgerStore::open(&self.file_path, password, discard_torn_tail)?;
+        let replayed = restored.journal.len();
+        let mut inner = LedgerInner::from_snapshot(restored.snapshot);
+        for op in restored.journal {
+            inner.apply(op);
         }
-        let data = fs::read(&self.file_path).map_err(|e| format!("读取账本失败:{e}"))?;
-        if data.len() < 32 {
-            return Err("账本文件格式错误".to_string());
+
+        let mut ledger = self.inner.write().map_err(|e| format!("账本锁错误:{e}"))?;
+        store.compact(&inner.snapshot())?;
+        *ledger = inner;
+        let count = ledger.pending.len();
+        *self
+            .store
+            .lock()
+            .map_err(|e| format!("账本存储锁错误:{e}"))? = Some(store);
+        if restored.upgraded_legacy {
+            log::info!("[OffchainLedger] 旧版账本已升级为 v2 格式");
         }
-        let (encrypted, tag) = data.split_at(data.len() - 32);
-        let key = blake2_256(password.as_bytes());
-        let expected = blake2_256(&[&key[..], encrypted].concat());
-        if tag != expected {
-            return Err("密码错误或账本损坏".to_string());
+        if replayed > 0 {
+            log::info!("[OffchainLedger] 重放 {replayed} 条日志操作,恢复 {count} 笔 pending");
         }
-        let plaintext: Vec<u8> = encrypted
-            .iter()
-            .enumerate()
-            .map(|(i, b)| b ^ key[i % 32])
-            .collect();
+        Ok(count)
+    }
 
-        if plaintext.len() < 4 {
-            return Err("账本数据不完整".to_string());
+    /// 换加密口令:以 `new_password` 新建存储,把当前状态写成快照并换掉日志。
+    ///
+    /// 须在 `load_from_disk` 之后调用;新快照原子替换旧快照,旧日志 epoch 不匹配
+    /// 随之作废,中途崩溃时磁盘上仍是旧口令或新口令之一的完整账本。
+    pub fn rekey(&self, new_password: &str) -> Result<(), String> {
+        let ledger = self.inner.read().map_err(|e| format!("账本锁错误:{e}"))?;
+        let mut store = self
+            .store
+            .lock()
+            .map_err(|e| format!("账本存储锁错误:{e}"))?;
+        if store.is_none() {
+            return Err("账本尚未加载,不能更换口令".to_string());
         }
-        let a_len = u32::from_le_bytes(
-            plaintext[..4]
-                .try_into()
-                .map_err(|_| "账本账户区长度字段损坏".to_string())?,
-        ) as usize;
-        if plaintext.len() < 4 + a_len + 4 {
-            return Err("账本数据不完整".to_string());
+        let mut fresh = LedgerStore::create(&self.file_path, new_password)?;
+        fresh.compact(&ledger.snapshot())?;
+        *store = Some(fresh);
+        Ok(())
+    }
+
+    /// 追加一条日志记录;未打开存储时为空操作。
+    fn journal(&self, ops: &[JournalOp]) -> Result<(), String> {
+        let mut store = self
+            .store
+            .lock()
+            .map_err(|e| format!("账本存储锁错误:{e}"))?;
+        match store.as_mut() {
+            Some(store) => store.append(ops),
+            None => Ok(()),
         }
-        let a_data = &plaintext[4..4 + a_len];
-        let p_len = u32::from_le_bytes(
-            plaintext[4 + a_len..4 + a_len + 4]
-                .try_into()
-                .map_err(|_| "账本待处理区长度字段损坏".to_string())?,
-        ) as usize;
-        let p_data = &plaintext[4 + a_len + 4..4 + a_len + 4 + p_len];
-
-        let accounts_vec: Vec<(AccountId32, L3AccountState)> =
-            Decode::decode(&mut &a_data[..]).map_err(|e| format!("解码 accounts 失败:{e}"))?;
-        let pending: Vec<PendingPayment> =
-            Decode::decode(&mut &p_data[..]).map_err(|e| format!("解码 pending 失败:{e}"))?;
+    }
 
-        let mut ledger = self.inner.write().map_err(|e| format!("账本锁错误:{e}"))?;
-        ledger.accounts = accounts_vec.into_iter().collect();
-        ledger.accepted_tx_ids = pending.iter().map(|p| p.tx_id).collect();
-        let count = pending.len();
-        ledger.pending = pending;
-        Ok(count)
+    /// 链上事件 / 回滚路径落日志:失败只告警,不阻断同步;随后按需压缩。
+    fn journal_event(&self, inner: &LedgerInner, ops: &[JournalOp]) {
+        if let Err(e) = self.journal(ops) {
+            log::warn!("[OffchainLedger] 账本日志写入失败:{e}");
+        }
+        self.compact_if_due(inner);
+    }
+
+    /// 日志过长时压缩。调用方持有 `inner` 锁,`inner` 已是变更后的状态。
+    fn compact_if_due(&self, inner: &LedgerInner) {
+        let Ok(mut store) = self.store.lock() else {
+            return;
+        };
+        if let Some(store) = store.as_mut().filter(|store| store.compaction_due()) {
+            if let Err(e) = store.compact(&inner.snapshot()) {
+                log::warn!("[OffchainLedger] 账本压缩失败:{e}");
+            }
+        }
     }
 
     // ---------------- 扫码支付核心业务逻辑 ----------------
@@ -444,8 +494,12 @@ impl OffchainLedger {
         }
 
         let total_debit = intent.amount.saturating_add(intent.fee);
-        if intent.payer_bank_cid.as_slice() == my_bank {
-            let state = ledger.accounts.entry(intent.payer.clone()).or_default();
+        let payer_state = if intent.payer_bank_cid.as_slice() == my_bank {
+            let mut state = ledger
+                .accounts
+                .get(&intent.payer)
+                .cloned()
+                .unwrap_or_default();
             let expected_nonce = state.cached_nonce.saturating_add(1);
             if intent.nonce != expected_nonce {
                 return Err(format!(
@@ -464,6 +518,7 @@ impl OffchainLedger {
             // 本行付款才更新本地账户缓存;跨行收款方节点不能生成付款方 ghost 账户。
             state.pending_debit = state.pending_debit.saturating_add(total_debit);
             state.cached_nonce = intent.nonce;
+            Some(state)
         } else if intent.recipient_bank_cid.as_slice() == my_bank {
             let base_nonce = chain_nonce.unwrap_or(0);
             let local_max_nonce = ledger
@@ -495,13 +550,12 @@ impl OffchainLedger {
                     "清算行存款余额不足:需 {total_debit}, 可用 {available}"
                 ));
             }
+            None
         } else {
             return Err("支付意图不属于当前清算行节点".to_string());
-        }
-
-        ledger.accepted_tx_ids.insert(intent.tx_id);
+        };
 
-        ledger.pending.push(PendingPayment {
+        let payment = PendingPayment {
             tx_id: intent.tx_id,
             payer: intent.payer.clone(),
             payer_bank_cid: intent.payer_bank_cid.clone(),
@@ -513,7 +567,22 @@ impl OffchainLedger {
             expires_at: intent.expires_at,
             payer_sig,
             accepted_at,
-        });
+        };
+
+        // 先落日志再改内存:ACK 返回前这笔 pending 必须已持久化,崩溃重启不丢单。
+        let mut ops = Vec::with_capacity(2);
+        if let Some(state) = &payer_state {
+            ops.push(JournalOp::PutAccount(intent.payer.clone(), state.clone()));
+        }
+        ops.push(JournalOp::AddPending(payment.clone()));
+        self.journal(&ops)?;
+
+        if let Some(state) = payer_state {
+            ledger.accounts.insert(intent.payer.clone(), state);
+        }
+        ledger.accepted_tx_ids.insert(intent.tx_id);
+        ledger.pending.push(payment);
+        self.compact_if_due(&ledger);
 
         Ok((intent.tx_id, l2_ack_sig_provider))
     }
@@ -529,6 +598,7 @@ impl OffchainLedger {
         };
         let p = ledger.pending.remove(pos);
         let total = p.amount.saturating_add(p.fee);
+        let mut ops = Vec::with_capacity(2);
         if let Some(state) = ledger.accounts.get_mut(&p.payer) {
             let had_local_debit = total > 0 && state.pending_debit >= total;
             if had_local_debit {
@@ -537,8 +607,11 @@ impl OffchainLedger {
             if had_local_debit && state.cached_nonce == p.nonce {
                 state.cached_nonce = state.cached_nonce.saturating_sub(1);
             }
+            ops.push(JournalOp::PutAccount(p.payer.clone(), state.clone()));
         }
         ledger.accepted_tx_ids.remove(&tx_id);
+        ops.push(JournalOp::RemovePending(tx_id));
+        self.journal_event(&ledger, &ops);
         Ok(())
     }
 
@@ -556,6 +629,7 @@ impl OffchainLedger {
 mod tests {
     use super::*;
     use sp_core::{sr25519, Pair};
+    use std::fs;
 
     fn acc(b: u8) -> AccountId32 {
         AccountId32::new([b; 32])
@@ -645,6 +719,57 @@ mod tests {
         assert!(ledger2.load_from_disk("wrong").is_err());
     }
 
+    #[test]
+    fn rekey_keeps_state_and_retires_old_password() {
+        let tmp = std::env::temp_dir().join("offchain_ledger_rekey_test");
+        let _ = fs::remove_dir_all(&tmp);
+        let ledger = OffchainLedger::new(&tmp);
+        assert!(ledger.rekey("new").is_err());
+        ledger.load_from_disk("old").unwrap();
+        ledger.on_deposited(&acc(5), 321);
+        ledger.rekey("new").unwrap();
+        ledger.on_deposited(&acc(5), 9);
+
+        assert!(OffchainLedger::new(&tmp).load_from_disk("old").is_err());
+        let reopened = OffchainLedger::new(&tmp);
+        reopened.load_from_disk("new").unwrap();
+        assert_eq!(reopened.available_balance(&acc(5)), 330);
+    }
+
+    #[test]
+    fn accepted_payment_survives_restart_without_save() {
+        // 不调用 save_to_disk,模拟接受支付后进程崩溃:pending 靠日志恢复。
+        let tmp = std::env::temp_dir().join("offchain_ledger_journal_recovery_test");
+        let _ = fs::remove_dir_all(&tmp);
+        let ledger = OffchainLedger::new(&tmp);
+        assert_eq!(ledger.load_from_disk("pw").unwrap(), 0);
+        let pair = <sr25519::Pair as Pair>::from_seed(&[0x44u8; 32]);
+        let payer = AccountId32::new(pair.public().0);
+        let bank_cid = b"GD001-PRB0T-239565809-2026".to_vec();
+        ledger.on_deposited(&payer, 1_000);
+        let (intent, sig) = signed_intent(&pair, 0x31, bank_cid.clone(), bank_cid, 1);
+        ledger
+            .accept_payment(intent, sig, Some(1), [7u8; 64], 1_717_000_000)
+            .unwrap();
+        drop(ledger);
+
+        let restarted = OffchainLedger::new(&tmp);
+        assert_eq!(restarted.load_from_disk("pw").unwrap(), 1);
+        assert_eq!(restarted.available_balance(&payer), 900);
+        assert_eq!(restarted.next_nonce(&payer), 2);
+        let (dup, dup_sig) = signed_intent(
+            &pair,
+            0x31,
+            b"GD001-PRB0T-239565809-2026".to_vec(),
+            b"GD001-PRB0T-239565809-2026".to_vec(),
+            2,
+        );
+        assert!(restarted
+            .accept_payment(dup, dup_sig, Some(1), [7u8; 64], 1_717_000_001)
+            .unwrap_err()
+            .contains("tx_id"));
+    }
+
     #[test]
     fn settled_same_bank_moves_pending_to_confirmed() {
         let tmp = std::env::temp_dir().join("offchain_ledger_settled_same_bank_test");
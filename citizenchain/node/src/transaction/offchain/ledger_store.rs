@@ -0,0 +1,680 @@
+//! 清算行本地 L3 账本的加密持久化(快照 + 追加日志)。
+//!
+//! - 加密:XChaCha20-Poly1305,密钥由节点启动密码经 Argon2id 派生;KDF 参数与
+//!   salt 写在快照头里,调参后旧文件仍按自身参数解开;头里的参数在派生前先按
+//!   固定上限校验,篡改的快照头不能让启动耗尽内存或 CPU。
+//! - 快照 `ledger.enc`:`magic | version | m_cost | t_cost | p_cost | salt | epoch`
+//!   为头(同时作 AAD),其后 `nonce:24 | SCALE(LedgerSnapshot)+tag`。
+//!   写入走 `*.tmp` → fsync → rename,任何时刻磁盘上都有一份完整快照。
+//! - 日志 `ledger.journal`:`magic | version | epoch` 头 + 逐条
+//!   `len:4 | nonce:24 | SCALE(Vec<JournalOp>)+tag`,每条记录 fsync 后才算落盘;
+//!   AAD 绑定 (epoch, 序号),记录不能被挪用到别的快照或调换顺序。
+//! - 压缩:把内存状态写成新 epoch 的快照,再换一份空日志;两步之间崩溃时旧日志
+//!   epoch 不匹配,加载时整份丢弃(内容已在新快照里)。
+//! - 崩溃恢复:日志尾部未写完的记录(长度不足,或文件已扩展但数据块未落盘导致
+//!   认证失败的最后一条)无法与截断篡改区分,默认拒绝启动;运维核对后显式要求
+//!   丢弃残尾才继续加载。中间记录认证失败一律按损坏报错。
+//! - 旧版 `blake2_256 XOR + HMAC` 格式在加载时自动识别,解开后立即以新格式重写。
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use codec::{Decode, Encode};
+use sp_core::H256;
+use sp_runtime::AccountId32;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+use super::ledger::{L3AccountState, PendingPayment};
+
+/// 快照魔数(8 字节,旧格式首字节为密文,误判概率可忽略)。
+const SNAPSHOT_MAGIC: &[u8; 8] = b"CBLEDGER";
+/// 快照格式版本;旧的 XOR 格式无头,视为版本 1。
+const SNAPSHOT_VERSION: u8 = 2;
+const JOURNAL_MAGIC: &[u8; 8] = b"CBJOURNL";
+const JOURNAL_VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const EPOCH_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+/// 快照头长度:magic + version + 3×u32 KDF 参数 + salt + epoch。
+const SNAPSHOT_HEADER_LEN: usize = SNAPSHOT_MAGIC.len() + 1 + 12 + SALT_LEN + EPOCH_LEN;
+const JOURNAL_HEADER_LEN: usize = JOURNAL_MAGIC.len() + 1 + EPOCH_LEN;
+/// 日志记录前缀:密文长度 + nonce。
+const RECORD_PREFIX_LEN: usize = 4 + NONCE_LEN;
+/// 日志累计到该条数后压缩为新快照。
+const JOURNAL_COMPACT_RECORDS: u64 = 4096;
+
+/// Argon2id 参数(m_cost 单位 KiB)。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct KdfParams {
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+}
+
+/// 快照头 KDF 参数上限:内存 1 GiB、迭代 16 次、并行 16 路,超出即视为损坏。
+const MAX_KDF: KdfParams = KdfParams {
+    m_cost: 1024 * 1024,
+    t_cost: 16,
+    p_cost: 16,
+};
+
+/// 新建账本使用的 KDF 参数;单测用最小参数避免拖慢用例。
+#[cfg(not(test))]
+const DEFAULT_KDF: KdfParams = KdfParams {
+    m_cost: 64 * 1024,
+    t_cost: 3,
+    p_cost: 1,
+};
+#[cfg(test)]
+const DEFAULT_KDF: KdfParams = KdfParams {
+    m_cost: 256,
+    t_cost: 1,
+    p_cost: 1,
+};
+
+/// 快照明文:账户缓存 + 未上链 pending(`accepted_tx_ids` 由 pending 重建)。
+#[derive(Clone, Debug, Default, Encode, Decode)]
+pub(super) struct LedgerSnapshot {
+    pub(super) accounts: Vec<(AccountId32, L3AccountState)>,
+    pub(super) pending: Vec<PendingPayment>,
+}
+
+/// 日志操作。记录的是变更后的状态而非业务入参,重放幂等、无需再验签。
+#[derive(Clone, Debug, Encode, Decode)]
+pub(super) enum JournalOp {
+    /// 覆盖某 L3 账户缓存。
+    PutAccount(AccountId32, L3AccountState),
+    /// 新接受一笔 pending。
+    AddPending(PendingPayment),
+    /// 移除一笔 pending(上链结算或回滚)。
+    RemovePending(H256),
+}
+
+/// 加载结果:快照 + 需按序重放的日志操作。
+pub(super) struct Restored {
+    pub(super) snapshot: LedgerSnapshot,
+    pub(super) journal: Vec<JournalOp>,
+    /// 是否从旧版 XOR 格式升级而来。
+    pub(super) upgraded_legacy: bool,
+}
+
+/// 已解锁的账本存储句柄。派生密钥只在内存保留一份,drop 时清零。
+pub(super) struct LedgerStore {
+    snapshot_path: PathBuf,
+    journal_path: PathBuf,
+    key: Zeroizing<[u8; KEY_LEN]>,
+    kdf: KdfParams,
+    salt: [u8; SALT_LEN],
+    epoch: [u8; EPOCH_LEN],
+    /// `compact` 之后才打开;加载阶段为 None。
+    journal: Option<File>,
+    journal_len: u64,
+    next_seq: u64,
+}
+
+impl LedgerStore {
+    /// 用新 salt 建立空存储(不落盘,首次 `compact` 时写出)。
+    pub(super) fn create(snapshot_path: &Path, password: &str) -> Result<Self, String> {
+        let salt: [u8; SALT_LEN] = random_bytes();
+        let key = derive_key(password, &salt, DEFAULT_KDF)?;
+        Ok(Self::with_key(snapshot_path, key, DEFAULT_KDF, salt))
+    }
+
+    /// 打开已有账本;文件不存在时建立空存储。调用方重放完日志后必须 `compact`,
+    /// 之后才能 `append`。
+    ///
+    /// 日志尾部存在未写完的记录时,仅当 `discard_torn_tail` 为真(运维显式确认)
+    /// 才丢弃残尾继续加载,否则报错。
+    pub(super) fn open(
+        snapshot_path: &Path,
+        password: &str,
+        discard_torn_tail: bool,
+    ) -> Result<(Self, Restored), String> {
+        let journal_path = journal_path_for(snapshot_path);
+        if !snapshot_path.exists() {
+            if journal_path.exists() {
+                return Err("账本快照缺失但日志存在,拒绝以空账本启动".to_string());
+            }
+            let store = Self::create(snapshot_path, password)?;
+            let restored = Restored {
+                snapshot: LedgerSnapshot::default(),
+                journal: Vec::new(),
+                upgraded_legacy: false,
+            };
+            return Ok((store, restored));
+        }
+
+        let data = fs::read(snapshot_path).map_err(|e| format!("读取账本失败:{e}"))?;
+        if !data.starts_with(SNAPSHOT_MAGIC) {
+            let snapshot = decode_legacy(&data, password)?;
+            let store = Self::create(snapshot_path, password)?;
+            log::info!(
+                "[OffchainLedger] 检测到旧版账本格式,将以 Argon2id + XChaCha20-Poly1305 重写"
+            );
+            let restored = Restored {
+                snapshot,
+                journal: Vec::new(),
+                upgraded_legacy: true,
+            };
+            return Ok((store, restored));
+        }
+
+        if data.len() < SNAPSHOT_HEADER_LEN + NONCE_LEN {
+            return Err("账本文件格式错误".to_string());
+        }
+        let (header, body) = data.split_at(SNAPSHOT_HEADER_LEN);
+        let version = header[SNAPSHOT_MAGIC.len()];
+        if version != SNAPSHOT_VERSION {
+            return Err(format!("不支持的账本版本:{version}"));
+        }
+        let mut cursor = SNAPSHOT_MAGIC.len() + 1;
+        let kdf = KdfParams {
+            m_cost: read_u32(header, &mut cursor),
+            t_cost: read_u32(header, &mut cursor),
+            p_cost: read_u32(header, &mut cursor),
+        };
+        if kdf.m_cost > MAX_KDF.m_cost || kdf.t_cost > MAX_KDF.t_cost || kdf.p_cost > MAX_KDF.p_cost
+        {
+            return Err(format!("账本 KDF 参数超出上限:{kdf:?}"));
+        }
+        let mut salt = [0u8; SALT_LEN];
+        salt.copy_from_slice(&header[cursor..cursor + SALT_LEN]);
+        cursor += SALT_LEN;
+        let mut epoch = [0u8; EPOCH_LEN];
+        epoch.copy_from_slice(&header[cursor..cursor + EPOCH_LEN]);
+
+        let key = derive_key(password, &salt, kdf)?;
+        let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+        let plaintext = Zeroizing::new(
+            open(&key, nonce, ciphertext, header)
+                .ok_or_else(|| "密码错误或账本损坏".to_string())?,
+        );
+        let snapshot = LedgerSnapshot::decode(&mut &plaintext[..])
+            .map_err(|e| format!("解码账本快照失败:{e}"))?;
+
+        let mut store = Self::with_key(snapshot_path, key, kdf, salt);
+        store.epoch = epoch;
+        let journal = store.read_journal(discard_torn_tail)?;
+        let restored = Restored {
+            snapshot,
+            journal,
+            upgraded_legacy: false,
+        };
+        Ok((store, restored))
+    }
+
+    /// 追加一条日志记录并 fsync。多个操作同处一条记录,重放时全有或全无。
+    pub(super) fn append(&mut self, ops: &[JournalOp]) -> Result<(), String> {
+        let journal = self
+            .journal
+            .as_mut()
+            .ok_or_else(|| "账本日志未就绪".to_string())?;
+        let nonce: [u8; NONCE_LEN] = random_bytes();
+        let aad = record_aad(&self.epoch, self.next_seq);
+        let plaintext = Zeroizing::new(ops.encode());
+        let ciphertext = seal(&self.key, &nonce, &plaintext, &aad)?;
+        let mut record = Vec::with_capacity(RECORD_PREFIX_LEN + ciphertext.len());
+        record.extend_from_slice(&(ciphertext.len() as u32).to_le_bytes());
+        record.extend_from_slice(&nonce);
+        record.extend_from_slice(&ciphertext);
+
+        let written = journal.write_all(&record).and_then(|_| journal.sync_data());
+        if let Err(e) = written {
+            // 截回写入前长度,避免半条记录夹在后续记录之前。
+            let _ = journal.set_len(self.journal_len);
+            return Err(format!("写入账本日志失败:{e}"));
+        }
+        self.journal_len += record.len() as u64;
+        self.next_seq += 1;
+        Ok(())
+    }
+
+    /// 日志是否已长到应当压缩。
+    pub(super) fn compaction_due(&self) -> bool {
+        self.next_seq >= JOURNAL_COMPACT_RECORDS
+    }
+
+    /// 把 `snapshot` 写成新 epoch 的快照并换一份空日志。
+    pub(super) fn compact(&mut self, snapshot: &LedgerSnapshot) -> Result<(), String> {
+        let epoch: [u8; EPOCH_LEN] = random_bytes();
+        let header = snapshot_header(self.kdf, &self.salt, &epoch);
+        let nonce: [u8; NONCE_LEN] = random_bytes();
+        let plaintext = Zeroizing::new(snapshot.encode());
+        let ciphertext = seal(&self.key, &nonce, &plaintext, &header)?;
+        let mut data = Vec::with_capacity(header.len() + NONCE_LEN + ciphertext.len());
+        data.extend_from_slice(&header);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+        write_atomic(&self.snapshot_path, &data)?;
+
+        let mut journal_header = Vec::with_capacity(JOURNAL_HEADER_LEN);
+        journal_header.extend_from_slice(JOURNAL_MAGIC);
+        journal_header.push(JOURNAL_VERSION);
+        journal_header.extend_from_slice(&epoch);
+        write_atomic(&self.journal_path, &journal_header)?;
+        let journal = OpenOptions::new()
+            .append(true)
+            .open(&self.journal_path)
+            .map_err(|e| format!("打开账本日志失败:{e}"))?;
+
+        self.epoch = epoch;
+        self.journal = Some(journal);
+        self.journal_len = JOURNAL_HEADER_LEN as u64;
+        self.next_seq = 0;
+        Ok(())
+    }
+
+    fn with_key(
+        snapshot_path: &Path,
+        key: Zeroizing<[u8; KEY_LEN]>,
+        kdf: KdfParams,
+        salt: [u8; SALT_LEN],
+    ) -> Self {
+        Self {
+            snapshot_path: snapshot_path.to_path_buf(),
+            journal_path: journal_path_for(snapshot_path),
+            key,
+            kdf,
+            salt,
+            epoch: [0u8; EPOCH_LEN],
+            journal: None,
+            journal_len: 0,
+            next_seq: 0,
+        }
+    }
+
+    /// 读出与当前快照 epoch 匹配的日志操作。
+    fn read_journal(&self, discard_torn_tail: bool) -> Result<Vec<JournalOp>, String> {
+        if !self.journal_path.exists() {
+            return Ok(Vec::new());
+        }
+        let data = fs::read(&self.journal_path).map_err(|e| format!("读取账本日志失败:{e}"))?;
+        if data.len() < JOURNAL_HEADER_LEN
+            || !data.starts_with(JOURNAL_MAGIC)
+            || data[JOURNAL_MAGIC.len()] != JOURNAL_VERSION
+        {
+            return Err("账本日志格式错误".to_string());
+        }
+        if data[JOURNAL_MAGIC.len() + 1..JOURNAL_HEADER_LEN] != self.epoch {
+            log::info!("[OffchainLedger] 日志属于上一份快照,已在压缩时并入,忽略");
+            return Ok(Vec::new());
+        }
+
+        let mut ops = Vec::new();
+        let mut offset = JOURNAL_HEADER_LEN;
+        let mut seq = 0u64;
+        while offset < data.len() {
+            let rest = &data[offset..];
+            if rest.len() < RECORD_PREFIX_LEN {
+                break;
+            }
+            let ct_len = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+            if rest.len() - RECORD_PREFIX_LEN < ct_len {
+                break;
+            }
+            let nonce = &rest[4..RECORD_PREFIX_LEN];
+            let ciphertext = &rest[RECORD_PREFIX_LEN..RECORD_PREFIX_LEN + ct_len];
+            let record_end = offset + RECORD_PREFIX_LEN + ct_len;
+            let Some(plaintext) = open(&self.key, nonce, ciphertext, &record_aad(&self.epoch, seq))
+            else {
+                if record_end == data.len() {
+                    break;
+                }
+                return Err(format!("账本日志第 {seq} 条记录认证失败"));
+            };
+            let plaintext = Zeroizing::new(plaintext);
+            let record = Vec::<JournalOp>::decode(&mut &plaintext[..])
+                .map_err(|e| format!("解码账本日志失败:{e}"))?;
+            ops.extend(record);
+            offset = record_end;
+            seq += 1;
+        }
+        if offset < data.len() {
+            let torn = data.len() - offset;
+            if !discard_torn_tail {
+                return Err(format!(
+                    "账本日志第 {seq} 条记录不完整(尾部 {torn} 字节),拒绝启动;核对后须显式确认丢弃残尾"
+                ));
+            }
+            log::warn!("[OffchainLedger] 经运维确认,丢弃日志尾部 {torn} 字节未完成写入的记录");
+        }
+        Ok(ops)
+    }
+}
+
+fn journal_path_for(snapshot_path: &Path) -> PathBuf {
+    snapshot_path.with_extension("journal")
+}
+
+fn snapshot_header(kdf: KdfParams, salt: &[u8; SALT_LEN], epoch: &[u8; EPOCH_LEN]) -> Vec<u8> {
+    let mut header = Vec::with_capacity(SNAPSHOT_HEADER_LEN);
+    header.extend_from_slice(SNAPSHOT_MAGIC);
+    header.push(SNAPSHOT_VERSION);
+    header.extend_from_slice(&kdf.m_cost.to_le_bytes());
+    header.extend_from_slice(&kdf.t_cost.to_le_bytes());
+    header.extend_from_slice(&kdf.p_cost.to_le_bytes());
+    header.extend_from_slice(salt);
+    header.extend_from_slice(epoch);
+    header
+}
+
+fn record_aad(epoch: &[u8; EPOCH_LEN], seq: u64) -> Vec<u8> {
+    [
+        JOURNAL_MAGIC.as_slice(),
+        epoch.as_slice(),
+        &seq.to_le_bytes(),
+    ]
+    .concat()
+}
+
+fn read_u32(data: &[u8], cursor: &mut usize) -> u32 {
+    let value = u32::from_le_bytes([
+        data[*cursor],
+        data[*cursor + 1],
+        data[*cursor + 2],
+        data[*cursor + 3],
+    ]);
+    *cursor += 4;
+    value
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut out = [0u8; N];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut out);
+    out
+}
+
+/// Argon2id(password, salt) → 32 字节密钥。
+fn derive_key(
+    password: &str,
+    salt: &[u8; SALT_LEN],
+    kdf: KdfParams,
+) -> Result<Zeroizing<[u8; KEY_LEN]>, String> {
+    let params = argon2::Params::new(kdf.m_cost, kdf.t_cost, kdf.p_cost, Some(KEY_LEN))
+        .map_err(|e| format!("账本 KDF 参数无效:{e}"))?;
+    let argon = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    argon
+        .hash_password_into(password.as_bytes(), salt, &mut key[..])
+        .map_err(|e| format!("账本密钥派生失败:{e}"))?;
+    Ok(key)
+}
+
+fn seal(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, String> {
+    XChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| "账本加密失败".to_string())
+}
+
+fn open(key: &[u8; KEY_LEN], nonce: &[u8], ciphertext: &[u8], aad: &[u8]) -> Option<Vec<u8>> {
+    XChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .ok()
+}
+
+/// 原子替换:写 `<name>.tmp` → fsync → rename → fsync 目录。
+fn write_atomic(path: &Path, data: &[u8]) -> Result<(), String> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| "账本路径缺少父目录".to_string())?;
+    fs::create_dir_all(dir).map_err(|e| format!("创建目录失败:{e}"))?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| "账本路径缺少文件名".to_string())?
+        .to_string_lossy();
+    let tmp = dir.join(format!("{file_name}.tmp"));
+    let mut file = File::create(&tmp).map_err(|e| format!("写入账本失败:{e}"))?;
+    file.write_all(data)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("写入账本失败:{e}"))?;
+    drop(file);
+    fs::rename(&tmp, path).map_err(|e| format!("替换账本文件失败:{e}"))?;
+    #[cfg(unix)]
+    {
+        File::open(dir)
+            .and_then(|d| d.sync_all())
+            .map_err(|e| format!("同步账本目录失败:{e}"))?;
+    }
+    Ok(())
+}
+
+/// 节点层 blake2b-256 工具函数(仅旧格式解码使用)。
+fn blake2_256(data: &[u8]) -> [u8; 32] {
+    let hash = blake2b_simd::Params::new().hash_length(32).hash(data);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(hash.as_bytes());
+    out
+}
+
+/// 解开旧版 `blake2_256(password)` XOR 流 + blake2 标签格式:
+/// `xor(a_len:4 | accounts | p_len:4 | pending) | tag:32`。
+fn decode_legacy(data: &[u8], password: &str) -> Result<LedgerSnapshot, String> {
+    if data.len() < 32 {
+        return Err("账本文件格式错误".to_string());
+    }
+    let (encrypted, tag) = data.split_at(data.len() - 32);
+    let key = Zeroizing::new(blake2_256(password.as_bytes()));
+    let expected = blake2_256(&[&key[..], encrypted].concat());
+    if tag != expected {
+        return Err("密码错误或账本损坏".to_string());
+    }
+    let plaintext: Zeroizing<Vec<u8>> = Zeroizing::new(
+        encrypted
+            .iter()
+            .enumerate()
+            .map(|(i, b)| b ^ key[i % 32])
+            .collect(),
+    );
+
+    if plaintext.len() < 4 {
+        return Err("账本数据不完整".to_string());
+    }
+    let a_len =
+        u32::from_le_bytes([plaintext[0], plaintext[1], plaintext[2], plaintext[3]]) as usize;
+    if plaintext.len() < 4 + a_len + 4 {
+        return Err("账本数据不完整".to_string());
+    }
+    let a_data = &plaintext[4..4 + a_len];
+    let p_start = 4 + a_len + 4;
+    let p_len = u32::from_le_bytes([
+        plaintext[4 + a_len],
+        plaintext[4 + a_len + 1],
+        plaintext[4 + a_len + 2],
+        plaintext[4 + a_len + 3],
+    ]) as usize;
+    if plaintext.len() < p_start + p_len {
+        return Err("账本数据不完整".to_string());
+    }
+    let p_data = &plaintext[p_start..p_start + p_len];
+
+    let accounts =
+        Decode::decode(&mut &a_data[..]).map_err(|e| format!("解码 accounts 失败:{e}"))?;
+    let pending = Decode::decode(&mut &p_data[..]).map_err(|e| format!("解码 pending 失败:{e}"))?;
+    Ok(LedgerSnapshot { accounts, pending })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(tx: u8) -> PendingPayment {
+        PendingPayment {
+            tx_id: H256::repeat_byte(tx),
+            payer: AccountId32::new([1u8; 32]),
+            payer_bank_cid: b"ZS001-PRB08-233384677-2026".to_vec(),
+            recipient: AccountId32::new([2u8; 32]),
+            recipient_bank_cid: b"ZS001-PRB08-233384677-2026".to_vec(),
+            amount: 99,
+            fee: 1,
+            nonce: tx as u64,
+            expires_at: 100,
+            payer_sig: [3u8; 64],
+            accepted_at: 1_717_000_000,
+        }
+    }
+
+    fn fresh_dir(name: &str) -> PathBuf {
+        let tmp = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&tmp);
+        tmp.join("ledger.enc")
+    }
+
+    /// 按旧版 `save_to_disk` 写出 XOR 格式文件。
+    fn write_legacy(path: &Path, password: &str, snapshot: &LedgerSnapshot) {
+        let accounts_enc = snapshot.accounts.encode();
+        let pending_enc = snapshot.pending.encode();
+        let mut plaintext = Vec::new();
+        plaintext.extend_from_slice(&(accounts_enc.len() as u32).to_le_bytes());
+        plaintext.extend_from_slice(&accounts_enc);
+        plaintext.extend_from_slice(&(pending_enc.len() as u32).to_le_bytes());
+        plaintext.extend_from_slice(&pending_enc);
+        let key = blake2_256(password.as_bytes());
+        let encrypted: Vec<u8> = plaintext
+            .iter()
+            .enumerate()
+            .map(|(i, b)| b ^ key[i % 32])
+            .collect();
+        let tag = blake2_256(&[&key[..], &encrypted].concat());
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, [encrypted, tag.to_vec()].concat()).unwrap();
+    }
+
+    #[test]
+    fn journal_survives_restart_without_compaction() {
+        let path = fresh_dir("offchain_ledger_store_journal_test");
+        let (mut store, restored) = LedgerStore::open(&path, "pw", false).unwrap();
+        assert!(restored.snapshot.pending.is_empty());
+        store.compact(&restored.snapshot).unwrap();
+        store.append(&[JournalOp::AddPending(pending(1))]).unwrap();
+        store
+            .append(&[
+                JournalOp::AddPending(pending(2)),
+                JournalOp::RemovePending(H256::repeat_byte(1)),
+            ])
+            .unwrap();
+        drop(store);
+
+        let (_, restored) = LedgerStore::open(&path, "pw", false).unwrap();
+        assert_eq!(restored.journal.len(), 3);
+        assert!(
+            matches!(&restored.journal[2], JournalOp::RemovePending(tx) if *tx == H256::repeat_byte(1))
+        );
+    }
+
+    #[test]
+    fn torn_tail_requires_explicit_discard() {
+        let path = fresh_dir("offchain_ledger_store_torn_tail_test");
+        let (mut store, restored) = LedgerStore::open(&path, "pw", false).unwrap();
+        store.compact(&restored.snapshot).unwrap();
+        store.append(&[JournalOp::AddPending(pending(1))]).unwrap();
+        drop(store);
+
+        // 模拟崩溃:长度已写、数据块仍为零。
+        let mut journal = OpenOptions::new()
+            .append(true)
+            .open(journal_path_for(&path))
+            .unwrap();
+        journal.write_all(&64u32.to_le_bytes()).unwrap();
+        journal.write_all(&[0u8; NONCE_LEN + 64]).unwrap();
+        drop(journal);
+
+        assert!(LedgerStore::open(&path, "pw", false).is_err());
+        let (_, restored) = LedgerStore::open(&path, "pw", true).unwrap();
+        assert_eq!(restored.journal.len(), 1);
+    }
+
+    #[test]
+    fn oversized_kdf_params_are_rejected_before_derivation() {
+        let path = fresh_dir("offchain_ledger_store_kdf_bounds_test");
+        let (mut store, restored) = LedgerStore::open(&path, "pw", false).unwrap();
+        store.compact(&restored.snapshot).unwrap();
+        drop(store);
+
+        let mut data = fs::read(&path).unwrap();
+        let m_cost_at = SNAPSHOT_MAGIC.len() + 1;
+        data[m_cost_at..m_cost_at + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        fs::write(&path, data).unwrap();
+        let err = LedgerStore::open(&path, "pw", false).err().unwrap();
+        assert!(err.contains("超出上限"), "{err}");
+    }
+
+    #[test]
+    fn stale_journal_from_previous_epoch_is_ignored() {
+        let path = fresh_dir("offchain_ledger_store_stale_epoch_test");
+        let (mut store, restored) = LedgerStore::open(&path, "pw", false).unwrap();
+        store.compact(&restored.snapshot).unwrap();
+        store.append(&[JournalOp::AddPending(pending(1))]).unwrap();
+        let old_journal = fs::read(journal_path_for(&path)).unwrap();
+
+        let snapshot = LedgerSnapshot {
+            accounts: Vec::new(),
+            pending: vec![pending(1)],
+        };
+        store.compact(&snapshot).unwrap();
+        drop(store);
+        // 模拟压缩在换日志前崩溃:快照已是新 epoch,日志仍是旧的。
+        fs::write(journal_path_for(&path), old_journal).unwrap();
+
+        let (_, restored) = LedgerStore::open(&path, "pw", false).unwrap();
+        assert_eq!(restored.snapshot.pending.len(), 1);
+        assert!(restored.journal.is_empty(), "旧 epoch 日志不能被重放两次");
+    }
+
+    #[test]
+    fn wrong_password_is_rejected() {
+        let path = fresh_dir("offchain_ledger_store_password_test");
+        let (mut store, restored) = LedgerStore::open(&path, "right", false).unwrap();
+        store.compact(&restored.snapshot).unwrap();
+        drop(store);
+        assert!(LedgerStore::open(&path, "wrong", false).is_err());
+    }
+
+    #[test]
+    fn legacy_file_is_detected_and_decoded() {
+        let path = fresh_dir("offchain_ledger_store_legacy_test");
+        let legacy = LedgerSnapshot {
+            accounts: vec![(
+                AccountId32::new([7u8; 32]),
+                L3AccountState {
+                    confirmed: 500,
+                    ..L3AccountState::default()
+                },
+            )],
+            pending: vec![pending(4)],
+        };
+        write_legacy(&path, "pw", &legacy);
+        assert!(LedgerStore::open(&path, "wrong", false).is_err());
+
+        let (mut store, restored) = LedgerStore::open(&path, "pw", false).unwrap();
+        assert!(restored.upgraded_legacy);
+        assert_eq!(restored.snapshot.accounts[0].1.confirmed, 500);
+        assert_eq!(restored.snapshot.pending.len(), 1);
+        store.compact(&restored.snapshot).unwrap();
+        drop(store);
+
+        assert!(fs::read(&path).unwrap().starts_with(SNAPSHOT_MAGIC));
+        let (_, reopened) = LedgerStore::open(&path, "pw", false).unwrap();
+        assert!(!reopened.upgraded_legacy);
+        assert_eq!(reopened.snapshot.pending.len(), 1);
+    }
+}
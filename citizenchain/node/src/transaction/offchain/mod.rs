@@ -25,6 +25,7 @@ pub mod endpoint;
 pub mod health;
 pub mod institution_read;
 pub mod ledger;
+mod ledger_store;
 pub mod rpc;
 pub mod settlement;
 pub mod signing;
@@ -69,13 +70,14 @@ pub struct OffchainComponents {
 
 /// 启动清算行节点所需的 offchain 组件套件。
 ///
-/// [`base_path`]  节点数据根目录(下挂 `offchain_step1/ledger.enc`)。
+/// [`base_path`]  节点数据根目录(下挂 `offchain_step1/ledger.enc` + `ledger.journal`)。
 /// [`actor_cid_number`] 本清算行机构唯一主键。
 /// [`actor_role_code`] 提交批次的机构岗位码。
 /// [`institution_account_id`] 本清算行**主账户 ID**（身份锚），用于 packer 批次 signing
 ///                message 拼接与发 extrinsic;`EventListener` 事件过滤按 CID(actor_cid_number)。
-/// [`password`]   节点启动时用于 AES-256-GCM 风格加密 ledger 的对称密钥字符串
-///                (目前实现为 blake2b_256(password) XOR 流 + HMAC,见 `ledger.rs`)。
+/// [`ledger_password`] ledger 加密口令,经 Argon2id 派生快照与日志的
+///                XChaCha20-Poly1305 密钥(见 `ledger_store.rs`)。口令模式下即节点
+///                启动密码;PKCS#11 模式下是令牌封装的独立口令,不是 PIN。
 /// [`signer`]     批次签名器。未接入时传 `NoopBatchSigner`;接入后
 ///                传真实 `KeystoreBatchSigner`(从 `offchain::settlement::keystore` 派生)。
 /// [`submitter`]  extrinsic 提交器。未接入时传 `NoopBatchSubmitter`;接入后
@@ -88,14 +90,14 @@ pub fn start_clearing_bank_components(
     actor_cid_number: Vec<u8>,
     actor_role_code: Vec<u8>,
     institution_account_id: AccountId32,
-    password: &str,
+    ledger_password: &str,
     signer: Arc<dyn BatchSigner>,
     submitter: Arc<dyn BatchSubmitter>,
     client: Arc<crate::core::service::FullClient>,
 ) -> Result<OffchainComponents, String> {
     let ledger = Arc::new(OffchainLedger::new(base_path));
     // 若磁盘有上次加密持久化的 ledger,尝试恢复;首次启动(文件不存在)返回 Ok(0)。
-    ledger.load_from_disk(password)?;
+    ledger.load_from_disk(ledger_password)?;
     // 节点自身身份 = 清算行 CID(actor_cid_number,来自节点配置);主账户仅用于发 extrinsic / 偿付监控。
     let initial_batch_seq = read_last_clearing_batch_seq(client.as_ref(), &actor_cid_number)
         .map_err(|e| format!("读取 LastClearingBatchSeq 失败:{e}"))?;
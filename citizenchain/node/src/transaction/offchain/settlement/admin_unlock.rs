@@ -6,9 +6,9 @@
 // - 激活(activation):写入 activated-admins.json 长期持久化
 // - 解密(decrypt):仅写入内存 HashMap,节点重启自动清空,无 TTL
 //
-// 实际私钥的 AES-GCM 加密文件由 CLI 启动路径(`--clearing-bank-password`)生成,
-// `settlement::keystore::OffchainKeystore` 加载到 `KeystoreBatchSigner` 的
-// `Arc<RwLock<Option<SigningKey>>>` 槽位。本模块的"解密"含义是:
+// 实际私钥的 AES-GCM 加密文件(或 PKCS#11 令牌包裹文件)由 CLI 启动路径
+// (`settlement::unlock` 从 fd/TTY 读取口令)解锁,`settlement::keystore` 加载到
+// `KeystoreBatchSigner` 的 `Arc<RwLock<Option<SigningKey>>>` 槽位。本模块的"解密"含义是:
 //   1. citizenwallet 签 challenge → 节点 sr25519 验签 → 证明操作员持有该公钥的冷钱包
 //   2. 把 (signer_public_key, cid_number) 标记为内存内“授权可用”，packer 攒批前 cross-check
 //      该入口存在才会启动签名(防误用启动密码加载的 SigningKey)
@@ -147,6 +147,7 @@ pub fn build_decrypt_admin_request(
             sig_alg: 1,
             signer_public_key: public_key_b64(&signer_public_key_bytes)?,
             payload: payload_b64(&payload),
+            call_descriptor: None,
         },
     };
 
@@ -12,17 +12,24 @@ use sc_service::TaskManager;
 use sp_core::crypto::Ss58Codec;
 use sp_runtime::AccountId32;
 use std::{
+    fs,
     path::Path,
     sync::{Arc, RwLock},
     time::Duration,
 };
+use zeroize::Zeroizing;
 
+use super::super::ledger::OffchainLedger;
 use super::super::rpc::OffchainClearingRpcImpl;
 use super::super::start_clearing_bank_components;
 use super::keystore::{OffchainKeystore, SigningKey};
 use super::packer::{BatchSigner, BatchSubmitter};
+use super::pkcs11::{
+    ledger_secret_path, pending_ledger_secret_path, sealed_seed_path, Pkcs11Config, Pkcs11Key,
+};
 use super::signer::KeystoreBatchSigner;
 use super::submitter::{PoolBatchSubmitter, TxPool};
+use super::unlock::ClearingBankUnlock;
 
 /// 根据 CLI 参数启动清算行 offchain 运行组件。
 ///
@@ -31,7 +38,7 @@ use super::submitter::{PoolBatchSubmitter, TxPool};
 pub(crate) fn start_from_cli(
     clearing_bank_cid_number: Option<&str>,
     clearing_bank_role_code: Option<&str>,
-    clearing_bank_password: Option<&str>,
+    clearing_bank_unlock: ClearingBankUnlock,
     reserve_monitor_interval_secs: Option<u64>,
     base_path: &Path,
     client: Arc<crate::core::service::FullClient>,
@@ -62,27 +69,33 @@ pub(crate) fn start_from_cli(
         .derive(SS58_FORMAT),
     );
 
-    let password = clearing_bank_password.unwrap_or("");
+    let password: &str = clearing_bank_unlock
+        .password
+        .as_ref()
+        .map(|value| value.as_str())
+        .unwrap_or("");
     let signing_key_slot: Arc<RwLock<Option<SigningKey>>> = Arc::new(RwLock::new(None));
-    let keystore = OffchainKeystore::new(base_path);
 
-    if keystore.has_signing_key() && !password.is_empty() {
-        match keystore.load_signing_key(password) {
-            Ok(key) => {
-                match signing_key_slot.write() {
-                    Ok(mut slot) => *slot = Some(key),
-                    Err(err) => *err.into_inner() = Some(key),
-                }
-                log::info!("[ClearingBank] 签名密钥已解锁");
-            }
-            Err(e) => {
-                log::warn!("[ClearingBank] 签名密钥解锁失败:{e},packer 将拒绝提交 extrinsic");
+    let unlocked = match unlock_keys(password, clearing_bank_unlock.pkcs11.as_ref(), base_path) {
+        Ok(unlocked) => unlocked,
+        Err(e) => {
+            log::warn!("[ClearingBank] PKCS#11 解锁失败:{e},ledger 无法解密,清算行组件不启动");
+            return None;
+        }
+    };
+    match unlocked.signing_key {
+        Some(key) => {
+            match signing_key_slot.write() {
+                Ok(mut slot) => *slot = Some(key),
+                Err(err) => *err.into_inner() = Some(key),
             }
+            log::info!("[ClearingBank] 签名密钥已解锁");
+        }
+        None => {
+            log::warn!(
+                "[ClearingBank] 签名密钥未加载(密码或密钥文件缺失),packer 会在有 pending 时 rollback"
+            );
         }
-    } else {
-        log::warn!(
-            "[ClearingBank] 签名密钥未加载(密码或密钥文件缺失),packer 会在有 pending 时 rollback"
-        );
     }
 
     let signer: Arc<dyn BatchSigner> = Arc::new(KeystoreBatchSigner::new(signing_key_slot.clone()));
@@ -97,7 +110,7 @@ pub(crate) fn start_from_cli(
         actor_cid_number.as_bytes().to_vec(),
         actor_role_code.as_bytes().to_vec(),
         institution_account_id.clone(),
-        password,
+        unlocked.ledger_password.as_str(),
         signer,
         submitter,
         client.clone(),
@@ -130,6 +143,105 @@ pub(crate) fn start_from_cli(
     Some(components.rpc_impl.clone())
 }
 
+/// 解锁结果:签名密钥(可能未加载)与 ledger 加密口令。
+struct UnlockedKeys {
+    signing_key: Option<SigningKey>,
+    ledger_password: Zeroizing<String>,
+}
+
+/// 按解锁参数加载签名密钥并确定 ledger 口令。
+///
+/// - 口令模式:签名密钥走口令加密的 `signing_key.enc`,ledger 沿用同一口令;密钥
+///   缺失或解锁失败只告警,节点照常运行但不签批次。
+/// - PKCS#11 模式:口令只是令牌 PIN;ledger 改用令牌封装的独立随机口令
+///   (见 [`pkcs11_ledger_password`])。令牌解锁失败时 ledger 也解不开,返回错误。
+fn unlock_keys(
+    password: &str,
+    pkcs11: Option<&Pkcs11Config>,
+    base_path: &Path,
+) -> Result<UnlockedKeys, String> {
+    let Some(config) = pkcs11 else {
+        let signing_key = load_keystore_key(password, base_path).unwrap_or_else(|e| {
+            log::warn!("[ClearingBank] 签名密钥解锁失败:{e},packer 将拒绝提交 extrinsic");
+            None
+        });
+        return Ok(UnlockedKeys {
+            signing_key,
+            ledger_password: Zeroizing::new(password.to_string()),
+        });
+    };
+    if password.is_empty() {
+        return Err("PKCS#11 模式缺少令牌 PIN".to_string());
+    }
+
+    // 迁移前 ledger 用旧密钥文件口令加密;已在令牌模式下运行过的节点则是 PIN。
+    let mut legacy_ledger_password = password;
+    if !sealed_seed_path(base_path).exists() {
+        let Some(import_password) = config.import_password.as_ref() else {
+            return Err(
+                "令牌内尚无签名密钥,首次启用请用 --clearing-bank-pkcs11-import 导入".to_string(),
+            );
+        };
+        // 一次性迁移:旧口令解出 seed → 令牌加密封装。旧文件保留,由运维确认后手工删除。
+        let keystore = OffchainKeystore::new(base_path);
+        let (seed, cid_number) = keystore.load_seed(import_password)?;
+        Pkcs11Key::provision(config, password, base_path, &seed, &cid_number)?;
+        legacy_ledger_password = import_password.as_str();
+        log::warn!(
+            "[ClearingBank] 已导入 PKCS#11 令牌,请确认节点正常签批后删除旧的 offchain/signing_key.enc"
+        );
+    }
+    let key = Pkcs11Key::open(config, password, base_path)?;
+    let ledger_password = pkcs11_ledger_password(&key, base_path, legacy_ledger_password)?;
+    Ok(UnlockedKeys {
+        signing_key: Some(SigningKey::pkcs11(key)),
+        ledger_password,
+    })
+}
+
+/// 口令模式加载 `signing_key.enc`;`Ok(None)` 表示口令或密钥文件缺失。
+fn load_keystore_key(password: &str, base_path: &Path) -> Result<Option<SigningKey>, String> {
+    let keystore = OffchainKeystore::new(base_path);
+    if password.is_empty() || !keystore.has_signing_key() {
+        return Ok(None);
+    }
+    keystore.load_signing_key(password).map(Some)
+}
+
+/// PKCS#11 模式的 ledger 口令:令牌封装的 `ledger_key.p11`。
+///
+/// 尚无该文件时生成新口令先写到 `ledger_key.p11.new`,把磁盘上的 ledger 从
+/// `legacy_password` 重加密到新口令,完成后才改名为正式文件。任一步崩溃,重启时
+/// `.new` 仍在:账本若已是新口令直接改名,否则继续从旧口令重加密。
+fn pkcs11_ledger_password(
+    key: &Pkcs11Key,
+    base_path: &Path,
+    legacy_password: &str,
+) -> Result<Zeroizing<String>, String> {
+    let path = ledger_secret_path(base_path);
+    if let Some(secret) = key.open_ledger_secret(&path)? {
+        return Ok(secret);
+    }
+    let pending = pending_ledger_secret_path(base_path);
+    let secret = match key.open_ledger_secret(&pending)? {
+        Some(secret) => secret,
+        None => key.seal_ledger_secret(&pending)?,
+    };
+    if OffchainLedger::new(base_path)
+        .load_from_disk(secret.as_str())
+        .is_err()
+    {
+        let ledger = OffchainLedger::new(base_path);
+        ledger
+            .load_from_disk(legacy_password)
+            .map_err(|e| format!("用迁移前口令解开 ledger 失败:{e}"))?;
+        ledger.rekey(secret.as_str())?;
+        log::info!("[ClearingBank] ledger 已改用令牌封装的独立口令加密");
+    }
+    fs::rename(&pending, &path).map_err(|e| format!("写入 ledger 口令包裹文件失败:{e}"))?;
+    Ok(secret)
+}
+
 fn spawn_packer_worker(
     task_manager: &TaskManager,
     client: Arc<crate::core::service::FullClient>,
@@ -196,3 +308,60 @@ fn spawn_reserve_worker(
         },
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::pkcs11::tests::{softhsm_token, SOFTHSM_USER_PIN};
+    use super::*;
+
+    fn account(byte: u8) -> AccountId32 {
+        AccountId32::new([byte; 32])
+    }
+
+    /// 口令模式的节点导入 PKCS#11 令牌后重启:ledger 保留原状态,改由令牌封装的
+    /// 口令加密,PIN 与旧口令都解不开。
+    #[test]
+    #[ignore = "需要 SoftHSM v2:设置 SOFTHSM2_MODULE 后以 --ignored 运行"]
+    fn pkcs11_import_then_restart_keeps_ledger_under_token_secret() {
+        let base = std::env::temp_dir().join(format!("clearing_import_{}", std::process::id()));
+        let (mut config, _guard) = softhsm_token(&base);
+        let old_password = "old-keystore-password";
+        OffchainKeystore::new(&base)
+            .save_signing_key(old_password, &[7u8; 32], "AH001-SCB0V-123456789-2026")
+            .unwrap();
+        let ledger = OffchainLedger::new(&base);
+        ledger.load_from_disk(old_password).unwrap();
+        ledger.on_deposited(&account(3), 700);
+        drop(ledger);
+
+        // 不带导入参数时令牌内没有密钥,拒绝启动而不是拿 PIN 当 ledger 口令。
+        assert!(unlock_keys(SOFTHSM_USER_PIN, Some(&config), &base).is_err());
+
+        config.import_password = Some(Zeroizing::new(old_password.to_string()));
+        let imported = unlock_keys(SOFTHSM_USER_PIN, Some(&config), &base).unwrap();
+        assert!(imported.signing_key.is_some());
+        assert_ne!(imported.ledger_password.as_str(), SOFTHSM_USER_PIN);
+        let ledger_password = imported.ledger_password.clone();
+        drop(imported);
+
+        // 重启:导入参数已撤掉,账本由令牌封装的口令解开。
+        config.import_password = None;
+        let restarted = unlock_keys(SOFTHSM_USER_PIN, Some(&config), &base).unwrap();
+        assert_eq!(restarted.ledger_password, ledger_password);
+        assert!(!pending_ledger_secret_path(&base).exists());
+        let ledger = OffchainLedger::new(&base);
+        ledger
+            .load_from_disk(restarted.ledger_password.as_str())
+            .unwrap();
+        assert_eq!(ledger.available_balance(&account(3)), 700);
+        drop(restarted);
+
+        assert!(OffchainLedger::new(&base)
+            .load_from_disk(SOFTHSM_USER_PIN)
+            .is_err());
+        assert!(OffchainLedger::new(&base)
+            .load_from_disk(old_password)
+            .is_err());
+        let _ = fs::remove_dir_all(&base);
+    }
+}
@@ -4,8 +4,10 @@
 //! 私钥仅在内存中以明文存在,磁盘上始终为密文。
 //!
 //! **当前用途**:清算行节点 CLI 启动路径(`service.rs::new_full` 接
-//! `--clearing-bank` / `--clearing-bank-password` flag)在此加密存取管理员
+//! `--clearing-bank` + `settlement::unlock` 解析出的口令)在此加密存取管理员
 //! sr25519 seed,`offchain::settlement::{signer, submitter}` 消费。
+//! 配置 PKCS#11 模块时私钥改由 `settlement::pkcs11` 持有,本文件只在一次性
+//! 导入(`--clearing-bank-pkcs11-import`)时解出 seed 交给令牌封装。
 //!
 //! `SigningKey.cid_number` 表示清算行管理员身份标识，由 CLI 启动参数传入，
 //! 链上不存储该字段。本模块通过 `save_signing_key` / `load_signing_key`
@@ -14,7 +16,9 @@
 use sp_core::{sr25519, Pair};
 use std::fs;
 use std::path::{Path, PathBuf};
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
+
+use super::pkcs11::Pkcs11Key;
 
 /// AES-256-GCM nonce 长度（12 字节）。
 const NONCE_LEN: usize = 12;
@@ -27,16 +31,58 @@ const SALT_LEN: usize = 16;
 
 /// 加密存储文件格式：[salt:16][nonce:12][cid_number_len:1][cid_number:N][ciphertext+tag:48+16]
 /// cid_number 最长 48 字节，私钥固定 32 字节。
-/// 清算行管理员密钥（内存中的解密状态）。
+/// 清算行管理员密钥（内存中的解密状态或 PKCS#11 句柄）。
 pub struct SigningKey {
-    /// sr25519 密钥对（含私钥）。
-    pub pair: sr25519::Pair,
+    material: KeyMaterial,
     /// 清算行管理员身份标识(CLI 启动时外部传入;字段名保留以避免 blast radius,
     /// 清算行 UI 收敛时可 rename 为 `admin_id`)。
     #[allow(dead_code)]
     pub cid_number: String,
 }
 
+/// 私钥的存放方式。
+enum KeyMaterial {
+    /// 口令解密后常驻内存的 sr25519 密钥对。
+    Memory(sr25519::Pair),
+    /// 令牌内包裹,按次在 HSM 会话中解封。
+    Pkcs11(Pkcs11Key),
+}
+
+impl SigningKey {
+    /// 内存密钥(口令加密的 `signing_key.enc` 解出)。
+    pub fn in_memory(pair: sr25519::Pair, cid_number: String) -> Self {
+        Self {
+            material: KeyMaterial::Memory(pair),
+            cid_number,
+        }
+    }
+
+    /// PKCS#11 令牌密钥。
+    pub fn pkcs11(key: Pkcs11Key) -> Self {
+        let cid_number = key.cid_number().to_string();
+        Self {
+            material: KeyMaterial::Pkcs11(key),
+            cid_number,
+        }
+    }
+
+    /// 签名公钥;PKCS#11 模式下无需解封。
+    pub fn public(&self) -> sr25519::Public {
+        match &self.material {
+            KeyMaterial::Memory(pair) => pair.public(),
+            KeyMaterial::Pkcs11(key) => key.public(),
+        }
+    }
+
+    /// 把密钥对借给 `f` 使用;调用方不得把密钥对带出闭包。
+    pub fn with_pair<R>(&self, f: impl FnOnce(&sr25519::Pair) -> R) -> Result<R, String> {
+        match &self.material {
+            KeyMaterial::Memory(pair) => Ok(f(pair)),
+            KeyMaterial::Pkcs11(key) => key.with_pair(f),
+        }
+    }
+}
+
 /// 链下签名密钥管理器。
 pub struct OffchainKeystore {
     /// 加密文件路径。
@@ -109,6 +155,17 @@ impl OffchainKeystore {
 
     /// 用节点启动密码解密签名私钥到内存。
     pub fn load_signing_key(&self, password: &str) -> Result<SigningKey, String> {
+        let (seed, cid_number) = self.load_seed(password)?;
+        let pair = <sr25519::Pair as Pair>::from_seed(&seed);
+
+        log::info!("[Offchain] 签名管理员私钥已解密到内存（{}）", cid_number);
+        Ok(SigningKey::in_memory(pair, cid_number))
+    }
+
+    /// 用节点启动密码解出 seed 与 cid_number;seed 随返回值 drop 清零。
+    ///
+    /// 供 PKCS#11 一次性导入使用,常规启动走 `load_signing_key`。
+    pub fn load_seed(&self, password: &str) -> Result<(Zeroizing<[u8; 32]>, String), String> {
         let data = fs::read(&self.file_path).map_err(|e| format!("读取密钥文件失败：{e}"))?;
 
         // 解析文件格式
@@ -132,16 +189,14 @@ impl OffchainKeystore {
         let mut seed = decrypt_aes256_gcm(&aes_key, nonce, ciphertext, tag)?;
         aes_key.zeroize();
 
-        // 从 seed 构造密钥对
-        let seed_array: [u8; 32] = seed
-            .as_slice()
-            .try_into()
-            .map_err(|_| "私钥长度错误".to_string())?;
+        let mut seed_array = Zeroizing::new([0u8; 32]);
+        if seed.len() != 32 {
+            seed.zeroize();
+            return Err("私钥长度错误".to_string());
+        }
+        seed_array.copy_from_slice(&seed);
         seed.zeroize();
-        let pair = <sr25519::Pair as Pair>::from_seed(&seed_array);
-
-        log::info!("[Offchain] 签名管理员私钥已解密到内存（{}）", cid_number);
-        Ok(SigningKey { pair, cid_number })
+        Ok((seed_array, cid_number))
     }
 
     /// 删除本地加密密钥文件。
@@ -11,6 +11,8 @@ pub(crate) mod commands;
 pub mod keystore;
 pub mod listener;
 pub mod packer;
+pub mod pkcs11;
 pub mod reserve;
 pub mod signer;
 pub mod submitter;
+pub mod unlock;
@@ -0,0 +1,522 @@
+//! 清算行签名密钥的 PKCS#11 硬件保护。
+//!
+//! 链上批次签名是 sr25519,通用 PKCS#11 令牌没有 sr25519 机制,因此采用"令牌内
+//! 包裹密钥"方案:
+//! - 令牌内生成一把不可导出的 AES-256 包裹密钥(`CKA_SENSITIVE` + 非 `CKA_EXTRACTABLE`);
+//! - sr25519 seed 经令牌 `CKM_AES_GCM` 加密后存为 `offchain/signing_key.p11`,
+//!   磁盘与进程内常驻的只有密文与公钥;
+//! - 每次签名在已登录的 HSM 会话内解封 seed → 构造密钥对 → 签名 → 立即清零,
+//!   明文私钥不跨越单次签名,也不会在会话关闭后残留。
+//! - 本地 ledger 不再用令牌 PIN 加密:另生成 32 字节随机口令,同样经包裹密钥加密后
+//!   存为 `offchain/ledger_key.p11`,拿到 PIN 而拿不到令牌的人解不开账本。
+//!
+//! 本地测试用 SoftHSM v2 充当令牌(见本文件测试,`#[ignore]`,设置 `SOFTHSM2_MODULE`
+//! 后以 `--ignored` 运行)。
+
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::mechanism::aead::GcmParams;
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, KeyType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::slot::Slot;
+use cryptoki::types::AuthPin;
+use sp_core::{sr25519, Pair};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use zeroize::{Zeroize, Zeroizing};
+
+/// 包裹文件魔数与版本。
+const SEALED_MAGIC: &[u8; 6] = b"CBP11\x01";
+const IV_LEN: usize = 12;
+/// seed 32 字节 + GCM tag 16 字节。
+const SEALED_SEED_LEN: usize = 48;
+const GCM_TAG_BITS: u64 = 128;
+/// ledger 口令包裹文件魔数与版本。
+const LEDGER_SECRET_MAGIC: &[u8; 6] = b"CBL11\x01";
+/// ledger 口令 32 字节 + GCM tag 16 字节。
+const SEALED_LEDGER_SECRET_LEN: usize = 48;
+/// 未指定 `--clearing-bank-pkcs11-key-label` 时的包裹密钥标签。
+pub const DEFAULT_KEY_LABEL: &str = "citizenchain-clearing-bank";
+
+/// `--clearing-bank-pkcs11-*` 参数。
+pub struct Pkcs11Config {
+    /// PKCS#11 模块路径(如 `/usr/lib/softhsm/libsofthsm2.so`)。
+    pub module_path: PathBuf,
+    /// 令牌标签;不指定时要求恰好一个已初始化令牌。
+    pub token_label: Option<String>,
+    /// 包裹密钥的 `CKA_LABEL`。
+    pub key_label: String,
+    /// 一次性迁移:用旧口令解密 `signing_key.enc` 后封入令牌。
+    pub import_password: Option<Zeroizing<String>>,
+}
+
+/// 已登录令牌上的清算行签名密钥句柄。
+pub struct Pkcs11Key {
+    /// 登录态会话;`Session` 非 `Sync`,签名时串行使用。
+    session: Mutex<Session>,
+    wrapping_key: ObjectHandle,
+    sealed: SealedSeed,
+    // 会话依赖模块上下文存活。
+    _context: Pkcs11,
+}
+
+/// `signing_key.p11` 的内容:`magic | cid_len:1 | cid | public:32 | iv:12 | ciphertext+tag:48`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SealedSeed {
+    cid_number: String,
+    public: [u8; 32],
+    iv: [u8; IV_LEN],
+    ciphertext: Vec<u8>,
+}
+
+impl SealedSeed {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(
+            SEALED_MAGIC.len() + 1 + self.cid_number.len() + 32 + IV_LEN + SEALED_SEED_LEN,
+        );
+        out.extend_from_slice(SEALED_MAGIC);
+        out.push(self.cid_number.len() as u8);
+        out.extend_from_slice(self.cid_number.as_bytes());
+        out.extend_from_slice(&self.public);
+        out.extend_from_slice(&self.iv);
+        out.extend_from_slice(&self.ciphertext);
+        out
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, String> {
+        let magic_len = SEALED_MAGIC.len();
+        if data.len() < magic_len + 1 || &data[..magic_len] != SEALED_MAGIC {
+            return Err("PKCS#11 包裹文件格式错误".to_string());
+        }
+        let cid_len = data[magic_len] as usize;
+        let cid_end = magic_len + 1 + cid_len;
+        if data.len() != cid_end + 32 + IV_LEN + SEALED_SEED_LEN {
+            return Err("PKCS#11 包裹文件长度错误".to_string());
+        }
+        let cid_number = String::from_utf8(data[magic_len + 1..cid_end].to_vec())
+            .map_err(|_| "cid_number 编码错误".to_string())?;
+        let mut public = [0u8; 32];
+        public.copy_from_slice(&data[cid_end..cid_end + 32]);
+        let mut iv = [0u8; IV_LEN];
+        iv.copy_from_slice(&data[cid_end + 32..cid_end + 32 + IV_LEN]);
+        Ok(Self {
+            cid_number,
+            public,
+            iv,
+            ciphertext: data[cid_end + 32 + IV_LEN..].to_vec(),
+        })
+    }
+
+    /// GCM AAD:把密文钉在 (cid, 公钥) 上,换文件头即解封失败。
+    fn aad(cid_number: &str, public: &[u8; 32]) -> Vec<u8> {
+        [
+            SEALED_MAGIC.as_slice(),
+            cid_number.as_bytes(),
+            public.as_slice(),
+        ]
+        .concat()
+    }
+}
+
+/// 包裹文件路径(与 `OffchainKeystore` 的 `signing_key.enc` 同目录)。
+pub fn sealed_seed_path(base_path: &Path) -> PathBuf {
+    base_path.join("offchain").join("signing_key.p11")
+}
+
+/// ledger 口令包裹文件路径。
+pub fn ledger_secret_path(base_path: &Path) -> PathBuf {
+    base_path.join("offchain").join("ledger_key.p11")
+}
+
+/// 迁移中的 ledger 口令包裹文件:账本换口令完成后才改名为正式文件。
+pub fn pending_ledger_secret_path(base_path: &Path) -> PathBuf {
+    base_path.join("offchain").join("ledger_key.p11.new")
+}
+
+impl Pkcs11Key {
+    /// 加载模块、登录令牌、找到包裹密钥并读入包裹文件。PIN 只在登录时使用。
+    pub fn open(config: &Pkcs11Config, pin: &str, base_path: &Path) -> Result<Self, String> {
+        let data = fs::read(sealed_seed_path(base_path))
+            .map_err(|e| format!("读取 PKCS#11 包裹文件失败:{e}"))?;
+        let sealed = SealedSeed::decode(&data)?;
+        let (context, session) = login(config, pin)?;
+        let wrapping_key = find_wrapping_key(&session, &config.key_label)?
+            .ok_or_else(|| format!("令牌内找不到包裹密钥 {}", config.key_label))?;
+        let key = Self {
+            session: Mutex::new(session),
+            wrapping_key,
+            sealed,
+            _context: context,
+        };
+        // 启动时试解一次:PIN 对但包裹密钥不符/文件被换时立刻失败,而不是等到首个批次。
+        key.with_pair(|_| ())?;
+        Ok(key)
+    }
+
+    /// 把 seed 封入令牌:包裹密钥不存在则在令牌内生成,再写 `signing_key.p11`。
+    pub fn provision(
+        config: &Pkcs11Config,
+        pin: &str,
+        base_path: &Path,
+        seed: &[u8; 32],
+        cid_number: &str,
+    ) -> Result<(), String> {
+        if cid_number.len() > u8::MAX as usize {
+            return Err("cid_number 过长".to_string());
+        }
+        let (_context, session) = login(config, pin)?;
+        let wrapping_key = match find_wrapping_key(&session, &config.key_label)? {
+            Some(handle) => handle,
+            None => generate_wrapping_key(&session, &config.key_label)?,
+        };
+        let public = <sr25519::Pair as Pair>::from_seed(seed).public().0;
+        let mut iv = [0u8; IV_LEN];
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut iv);
+        let aad = SealedSeed::aad(cid_number, &public);
+        let mut gcm_iv = iv;
+        let params = GcmParams::new(&mut gcm_iv, &aad, GCM_TAG_BITS.into())
+            .map_err(|e| format!("构造 GCM 参数失败:{e}"))?;
+        let ciphertext = session
+            .encrypt(&Mechanism::AesGcm(params), wrapping_key, seed)
+            .map_err(|e| format!("令牌加密 seed 失败:{e}"))?;
+        if ciphertext.len() != SEALED_SEED_LEN {
+            return Err("令牌返回的密文长度异常".to_string());
+        }
+        let sealed = SealedSeed {
+            cid_number: cid_number.to_string(),
+            public,
+            iv,
+            ciphertext,
+        };
+        let path = sealed_seed_path(base_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建目录失败:{e}"))?;
+        }
+        fs::write(&path, sealed.encode()).map_err(|e| format!("写入 PKCS#11 包裹文件失败:{e}"))?;
+        log::info!("[ClearingBank] 签名密钥已封入 PKCS#11 令牌({cid_number})");
+        Ok(())
+    }
+
+    /// 生成随机 ledger 口令,经包裹密钥加密后写入 `path`(fsync 后才返回)。
+    pub fn seal_ledger_secret(&self, path: &Path) -> Result<Zeroizing<String>, String> {
+        let mut secret = Zeroizing::new([0u8; 32]);
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut secret[..]);
+        let mut iv = [0u8; IV_LEN];
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut iv);
+        let aad = self.ledger_secret_aad();
+        let mut gcm_iv = iv;
+        let params = GcmParams::new(&mut gcm_iv, &aad, GCM_TAG_BITS.into())
+            .map_err(|e| format!("构造 GCM 参数失败:{e}"))?;
+        let ciphertext = self
+            .session
+            .lock()
+            .map_err(|e| format!("PKCS#11 会话锁失败:{e}"))?
+            .encrypt(&Mechanism::AesGcm(params), self.wrapping_key, &secret[..])
+            .map_err(|e| format!("令牌加密 ledger 口令失败:{e}"))?;
+        if ciphertext.len() != SEALED_LEDGER_SECRET_LEN {
+            return Err("令牌返回的密文长度异常".to_string());
+        }
+        let data = [LEDGER_SECRET_MAGIC.as_slice(), &iv, &ciphertext].concat();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建目录失败:{e}"))?;
+        }
+        let mut file =
+            fs::File::create(path).map_err(|e| format!("写入 ledger 口令包裹文件失败:{e}"))?;
+        std::io::Write::write_all(&mut file, &data)
+            .and_then(|_| file.sync_all())
+            .map_err(|e| format!("写入 ledger 口令包裹文件失败:{e}"))?;
+        Ok(Zeroizing::new(hex::encode(&secret[..])))
+    }
+
+    /// 解封 `path` 处的 ledger 口令;文件不存在返回 `Ok(None)`。
+    pub fn open_ledger_secret(&self, path: &Path) -> Result<Option<Zeroizing<String>>, String> {
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("读取 ledger 口令包裹文件失败:{e}")),
+        };
+        let magic_len = LEDGER_SECRET_MAGIC.len();
+        if data.len() != magic_len + IV_LEN + SEALED_LEDGER_SECRET_LEN
+            || &data[..magic_len] != LEDGER_SECRET_MAGIC
+        {
+            return Err("ledger 口令包裹文件格式错误".to_string());
+        }
+        let mut iv = [0u8; IV_LEN];
+        iv.copy_from_slice(&data[magic_len..magic_len + IV_LEN]);
+        let aad = self.ledger_secret_aad();
+        let params = GcmParams::new(&mut iv, &aad, GCM_TAG_BITS.into())
+            .map_err(|e| format!("构造 GCM 参数失败:{e}"))?;
+        let mut clear = self
+            .session
+            .lock()
+            .map_err(|e| format!("PKCS#11 会话锁失败:{e}"))?
+            .decrypt(
+                &Mechanism::AesGcm(params),
+                self.wrapping_key,
+                &data[magic_len + IV_LEN..],
+            )
+            .map_err(|e| format!("令牌解封 ledger 口令失败:{e}"))?;
+        let secret = (clear.len() == 32).then(|| Zeroizing::new(hex::encode(&clear)));
+        clear.zeroize();
+        secret
+            .map(Some)
+            .ok_or_else(|| "令牌解封出的 ledger 口令长度异常".to_string())
+    }
+
+    /// ledger 口令密文的 AAD:钉在本行 (cid, 签名公钥) 上,不能挪给别的包裹文件。
+    fn ledger_secret_aad(&self) -> Vec<u8> {
+        [
+            LEDGER_SECRET_MAGIC.as_slice(),
+            self.sealed.cid_number.as_bytes(),
+            self.sealed.public.as_slice(),
+        ]
+        .concat()
+    }
+
+    /// 清算行管理员身份标识(包裹文件头,无需解封)。
+    pub fn cid_number(&self) -> &str {
+        &self.sealed.cid_number
+    }
+
+    /// 签名公钥(包裹文件头,无需解封)。
+    pub fn public(&self) -> sr25519::Public {
+        sr25519::Public::from_raw(self.sealed.public)
+    }
+
+    /// 在 HSM 会话内解封 seed 并把临时密钥对交给 `f`;返回前 seed 清零、密钥对随之
+    /// drop(schnorrkel 私钥 drop 时自清零)。
+    pub fn with_pair<R>(&self, f: impl FnOnce(&sr25519::Pair) -> R) -> Result<R, String> {
+        let session = self
+            .session
+            .lock()
+            .map_err(|e| format!("PKCS#11 会话锁失败:{e}"))?;
+        let aad = SealedSeed::aad(&self.sealed.cid_number, &self.sealed.public);
+        let mut iv = self.sealed.iv;
+        let params = GcmParams::new(&mut iv, &aad, GCM_TAG_BITS.into())
+            .map_err(|e| format!("构造 GCM 参数失败:{e}"))?;
+        let mut clear = session
+            .decrypt(
+                &Mechanism::AesGcm(params),
+                self.wrapping_key,
+                &self.sealed.ciphertext,
+            )
+            .map_err(|e| format!("令牌解封签名密钥失败:{e}"))?;
+        let mut seed = Zeroizing::new([0u8; 32]);
+        let length_ok = clear.len() == 32;
+        if length_ok {
+            seed.copy_from_slice(&clear);
+        }
+        clear.zeroize();
+        if !length_ok {
+            return Err("令牌解封出的 seed 长度异常".to_string());
+        }
+        let pair = <sr25519::Pair as Pair>::from_seed(&seed);
+        if pair.public().0 != self.sealed.public {
+            return Err("解封出的密钥与包裹文件公钥不一致".to_string());
+        }
+        Ok(f(&pair))
+    }
+}
+
+/// 加载模块并以用户身份登录目标令牌。
+fn login(config: &Pkcs11Config, pin: &str) -> Result<(Pkcs11, Session), String> {
+    let context = Pkcs11::new(&config.module_path).map_err(|e| {
+        format!(
+            "加载 PKCS#11 模块 {} 失败:{e}",
+            config.module_path.display()
+        )
+    })?;
+    context
+        .initialize(CInitializeArgs::OsThreads)
+        .map_err(|e| format!("初始化 PKCS#11 模块失败:{e}"))?;
+    let slot = select_slot(&context, config.token_label.as_deref())?;
+    let session = context
+        .open_rw_session(slot)
+        .map_err(|e| format!("打开 PKCS#11 会话失败:{e}"))?;
+    session
+        .login(UserType::User, Some(&AuthPin::new(pin.to_string())))
+        .map_err(|e| format!("PKCS#11 令牌登录失败:{e}"))?;
+    Ok((context, session))
+}
+
+fn select_slot(context: &Pkcs11, token_label: Option<&str>) -> Result<Slot, String> {
+    let slots = context
+        .get_slots_with_initialized_token()
+        .map_err(|e| format!("枚举 PKCS#11 令牌失败:{e}"))?;
+    match token_label {
+        Some(label) => {
+            for slot in slots {
+                let info = context
+                    .get_token_info(slot)
+                    .map_err(|e| format!("读取令牌信息失败:{e}"))?;
+                if info.label().trim_end() == label {
+                    return Ok(slot);
+                }
+            }
+            Err(format!("找不到标签为 {label} 的 PKCS#11 令牌"))
+        }
+        None => match slots.as_slice() {
+            [slot] => Ok(*slot),
+            [] => Err("没有已初始化的 PKCS#11 令牌".to_string()),
+            _ => Err("存在多个 PKCS#11 令牌,请用 --clearing-bank-pkcs11-token 指定".to_string()),
+        },
+    }
+}
+
+fn find_wrapping_key(session: &Session, label: &str) -> Result<Option<ObjectHandle>, String> {
+    let found = session
+        .find_objects(&[
+            Attribute::Class(ObjectClass::SECRET_KEY),
+            Attribute::KeyType(KeyType::AES),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ])
+        .map_err(|e| format!("查找包裹密钥失败:{e}"))?;
+    match found.as_slice() {
+        [] => Ok(None),
+        [handle] => Ok(Some(*handle)),
+        _ => Err(format!("令牌内存在多把标签为 {label} 的包裹密钥")),
+    }
+}
+
+fn generate_wrapping_key(session: &Session, label: &str) -> Result<ObjectHandle, String> {
+    session
+        .generate_key(
+            &Mechanism::AesKeyGen,
+            &[
+                Attribute::Class(ObjectClass::SECRET_KEY),
+                Attribute::KeyType(KeyType::AES),
+                Attribute::ValueLen(32.into()),
+                Attribute::Token(true),
+                Attribute::Private(true),
+                Attribute::Sensitive(true),
+                Attribute::Extractable(false),
+                Attribute::Encrypt(true),
+                Attribute::Decrypt(true),
+                Attribute::Label(label.as_bytes().to_vec()),
+            ],
+        )
+        .map_err(|e| format!("令牌内生成包裹密钥失败:{e}"))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_seed_round_trips_and_rejects_bad_input() {
+        let sealed = SealedSeed {
+            cid_number: "AH001-SCB0V-123456789-2026".to_string(),
+            public: [3u8; 32],
+            iv: [4u8; IV_LEN],
+            ciphertext: vec![5u8; SEALED_SEED_LEN],
+        };
+        let bytes = sealed.encode();
+        assert_eq!(SealedSeed::decode(&bytes).unwrap(), sealed);
+        assert!(SealedSeed::decode(&bytes[..bytes.len() - 1]).is_err());
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] ^= 1;
+        assert!(SealedSeed::decode(&wrong_magic).is_err());
+    }
+
+    /// SoftHSM 令牌初始化、`SOFTHSM2_CONF` 都是进程级状态,用例之间串行。
+    static SOFTHSM_LOCK: Mutex<()> = Mutex::new(());
+
+    pub(crate) const SOFTHSM_USER_PIN: &str = "user-pin-5678";
+
+    /// 在 `base/tokens` 下初始化一个临时 SoftHSM 令牌(用户 PIN 为 [`SOFTHSM_USER_PIN`]),
+    /// 返回指向它的配置与串行锁。需要 `SOFTHSM2_MODULE` 指向 `libsofthsm2.so`。
+    pub(crate) fn softhsm_token(base: &Path) -> (Pkcs11Config, std::sync::MutexGuard<'static, ()>) {
+        let guard = SOFTHSM_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let module_path = std::env::var_os("SOFTHSM2_MODULE")
+            .map(PathBuf::from)
+            .expect("SoftHSM 用例需要设置 SOFTHSM2_MODULE");
+        let _ = fs::remove_dir_all(base);
+        let token_dir = base.join("tokens");
+        fs::create_dir_all(&token_dir).unwrap();
+        let conf = base.join("softhsm2.conf");
+        fs::write(
+            &conf,
+            format!(
+                "directories.tokendir = {}\nobjectstore.backend = file\n",
+                token_dir.display()
+            ),
+        )
+        .unwrap();
+        std::env::set_var("SOFTHSM2_CONF", &conf);
+
+        // 初始化一个临时令牌并设置用户 PIN。
+        let so_pin = AuthPin::new("so-pin-1234".to_string());
+        {
+            let context = Pkcs11::new(&module_path).unwrap();
+            context.initialize(CInitializeArgs::OsThreads).unwrap();
+            let slot = context.get_slots_with_token().unwrap()[0];
+            context.init_token(slot, &so_pin, "clearing-test").unwrap();
+            let session = context.open_rw_session(slot).unwrap();
+            session.login(UserType::So, Some(&so_pin)).unwrap();
+            session
+                .init_pin(&AuthPin::new(SOFTHSM_USER_PIN.to_string()))
+                .unwrap();
+        }
+        let config = Pkcs11Config {
+            module_path,
+            token_label: Some("clearing-test".to_string()),
+            key_label: DEFAULT_KEY_LABEL.to_string(),
+            import_password: None,
+        };
+        (config, guard)
+    }
+
+    /// SoftHSM v2 端到端:初始化临时令牌 → 封入 seed → 重新登录签名 → 错 PIN 拒绝。
+    #[test]
+    #[ignore = "需要 SoftHSM v2:设置 SOFTHSM2_MODULE 后以 --ignored 运行"]
+    fn softhsm_seals_and_signs_without_exposing_seed() {
+        let base = std::env::temp_dir().join(format!("clearing_p11_{}", std::process::id()));
+        let (config, _guard) = softhsm_token(&base);
+        let user_pin = SOFTHSM_USER_PIN;
+        let seed = [9u8; 32];
+        let expected = <sr25519::Pair as Pair>::from_seed(&seed).public();
+        Pkcs11Key::provision(
+            &config,
+            user_pin,
+            &base,
+            &seed,
+            "AH001-SCB0V-123456789-2026",
+        )
+        .unwrap();
+
+        // 磁盘上只有密文:seed 明文不得出现在包裹文件里。
+        let on_disk = fs::read(sealed_seed_path(&base)).unwrap();
+        assert!(!on_disk.windows(32).any(|window| window == seed));
+
+        let key = Pkcs11Key::open(&config, user_pin, &base).unwrap();
+        assert_eq!(key.public(), expected);
+        assert_eq!(key.cid_number(), "AH001-SCB0V-123456789-2026");
+        let signature = key.with_pair(|pair| pair.sign(b"batch")).unwrap();
+        assert!(<sr25519::Pair as Pair>::verify(
+            &signature, b"batch", &expected
+        ));
+        // ledger 口令与 PIN 无关,只能经令牌解封;文件被换成别的密文即失败。
+        let ledger_path = ledger_secret_path(&base);
+        let secret = key.seal_ledger_secret(&ledger_path).unwrap();
+        assert_ne!(secret.as_str(), user_pin);
+        assert_eq!(
+            key.open_ledger_secret(&ledger_path).unwrap().unwrap(),
+            secret
+        );
+        assert!(key
+            .open_ledger_secret(&pending_ledger_secret_path(&base))
+            .unwrap()
+            .is_none());
+        let mut tampered = fs::read(&ledger_path).unwrap();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        fs::write(&ledger_path, tampered).unwrap();
+        assert!(key.open_ledger_secret(&ledger_path).is_err());
+        // 模块同一时刻只初始化一次:先释放会话与上下文再验错 PIN。
+        drop(key);
+
+        assert!(Pkcs11Key::open(&config, "wrong-pin", &base).is_err());
+        let _ = fs::remove_dir_all(&base);
+    }
+}
@@ -3,7 +3,8 @@
 //!
 //! - 本文件实现 `packer::BatchSigner` trait,把 batch 的签名消息转交给清算行
 //!   管理员的 sr25519 私钥。
-//! - `SigningKey` 沿用 `settlement/keystore.rs` 的密钥容器,
+//! - `SigningKey` 沿用 `settlement/keystore.rs` 的密钥容器(内存密钥或
+//!   PKCS#11 令牌密钥,签名统一走 `with_pair`),
 //!   持有 `Arc<RwLock<Option<..>>>`
 //!   是为了支持**热切换密钥**(节点运行中重新解锁后替换 inner)+ 未加载时 None
 //!   的情况下签名直接返回 Err,便于 packer 通过 `rollback` 路径回滚 pending。
@@ -44,7 +45,7 @@ impl BatchSigner for KeystoreBatchSigner {
         let key = guard
             .as_ref()
             .ok_or_else(|| "清算行签名管理员密钥未加载(密码错误或节点未提供密码)".to_string())?;
-        let signature = <sr25519::Pair as Pair>::sign(&key.pair, message);
+        let signature = key.with_pair(|pair| <sr25519::Pair as Pair>::sign(pair, message))?;
         Ok(signature.0)
     }
 }
@@ -56,16 +57,13 @@ mod tests {
     fn mk_signing_key(seed_byte: u8) -> SigningKey {
         let seed = [seed_byte; 32];
         let pair = <sr25519::Pair as Pair>::from_seed(&seed);
-        SigningKey {
-            pair,
-            cid_number: "AH001-SCB0V-123456789-2026".to_string(),
-        }
+        SigningKey::in_memory(pair, "AH001-SCB0V-123456789-2026".to_string())
     }
 
     #[test]
     fn sign_produces_verifiable_signature() {
         let key = mk_signing_key(42);
-        let public = key.pair.public();
+        let public = key.public();
         let slot: Arc<RwLock<Option<SigningKey>>> = Arc::new(RwLock::new(Some(key)));
         let signer = KeystoreBatchSigner::new(slot);
 
@@ -108,7 +106,7 @@ mod tests {
     fn signature_does_not_verify_against_wrong_key() {
         let key_a = mk_signing_key(1);
         let key_b = mk_signing_key(2);
-        let wrong_pub = key_b.pair.public();
+        let wrong_pub = key_b.public();
         let slot: Arc<RwLock<Option<SigningKey>>> = Arc::new(RwLock::new(Some(key_a)));
         let signer = KeystoreBatchSigner::new(slot);
 
@@ -123,7 +121,7 @@ mod tests {
     #[test]
     fn hot_swap_key_takes_effect() {
         let key_a = mk_signing_key(10);
-        let pub_a = key_a.pair.public();
+        let pub_a = key_a.public();
         let slot: Arc<RwLock<Option<SigningKey>>> = Arc::new(RwLock::new(Some(key_a)));
         let signer = KeystoreBatchSigner::new(slot.clone());
 
@@ -136,7 +134,7 @@ mod tests {
 
         // 热替换到新密钥
         let key_b = mk_signing_key(11);
-        let pub_b = key_b.pair.public();
+        let pub_b = key_b.public();
         *slot.write().unwrap() = Some(key_b);
 
         let sig2 = signer.sign_batch(b"same-msg").unwrap();
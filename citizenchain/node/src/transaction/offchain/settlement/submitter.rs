@@ -100,7 +100,7 @@ impl BatchSubmitter for PoolBatchSubmitter {
             },
         );
 
-        // 5. 从 signing_key 拿 sr25519 pair
+        // 5. 从 signing_key 取签名账户;PKCS#11 模式下私钥不出闭包,不再 clone pair
         let guard = self
             .signing_key
             .read()
@@ -108,15 +108,15 @@ impl BatchSubmitter for PoolBatchSubmitter {
         let key = guard
             .as_ref()
             .ok_or_else(|| "清算行签名管理员密钥未加载".to_string())?;
-        let sender_pair = key.pair.clone();
-        drop(guard);
 
-        // 6. 查链上 nonce(对 sender_pair 对应账户)
-        let sender_account_id = AccountId32::from(sender_pair.public());
+        // 6. 查链上 nonce(对签名公钥对应账户)
+        let sender_account_id = AccountId32::from(key.public());
         let nonce = lookup_nonce(&self.client, &sender_account_id)?;
 
         // 7. 构造签名过的 extrinsic
-        let extrinsic = build_signed_extrinsic(&self.client, &sender_pair, call, nonce)?;
+        let extrinsic =
+            key.with_pair(|pair| build_signed_extrinsic(&self.client, pair, call, nonce))??;
+        drop(guard);
 
         // 8. 真实提交到 TransactionPool。
         //    `pool.submit_one` 返回 Future,packer 侧已经是 async 环境,但本
@@ -0,0 +1,166 @@
+//! 清算行签名密钥的解锁参数:口令来源 + 可选 PKCS#11 硬件模块。
+//!
+//! - 口令不再要求写在命令行(`ps` / `/proc/<pid>/cmdline` 对同机用户可见):
+//!   `--clearing-bank-password-fd` 从继承的文件描述符读一行,
+//!   `--clearing-bank-password-prompt` 在交互终端无回显输入。
+//!   旧的 `--clearing-bank-password` 已停用,给出即启动报错。
+//! - 配置 `--clearing-bank-pkcs11-module` 时口令即令牌用户 PIN,签名密钥由
+//!   `settlement::pkcs11` 在 HSM 会话内按次解封(见该模块);ledger 改用令牌封装的
+//!   独立口令,PIN 不再兼作 ledger 口令。
+//! - 口令在内存中始终包在 `Zeroizing` 里,读完即清零中间缓冲。
+
+use std::io::{BufRead, BufReader};
+use zeroize::Zeroizing;
+
+use super::pkcs11::Pkcs11Config;
+
+/// 节点启动时解析好的清算行解锁参数,由 `command.rs` 交给 `service::new_full`。
+#[derive(Default)]
+pub struct ClearingBankUnlock {
+    /// 密钥文件口令,口令模式下同时用作 ledger 加密口令;PKCS#11 模式下为令牌用户 PIN。
+    pub password: Option<Zeroizing<String>>,
+    /// 配置后签名密钥走 PKCS#11 模块,不再读取口令加密的 `signing_key.enc`。
+    pub pkcs11: Option<Pkcs11Config>,
+}
+
+/// 口令来源。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordSource {
+    /// 不提供口令:签名密钥保持未加载。
+    None,
+    /// 从已打开的文件描述符读取第一行(如 `3<secret-file` 或 systemd 凭据管道)。
+    Fd(i32),
+    /// 在控制终端无回显提示输入。
+    Prompt,
+}
+
+impl PasswordSource {
+    /// 由 CLI 参数确定口令来源;命令行明文口令或同时给出多个来源视为配置错误。
+    pub fn from_cli(
+        inline: Option<&str>,
+        fd: Option<i32>,
+        prompt: bool,
+    ) -> Result<PasswordSource, String> {
+        if inline.is_some() {
+            return Err(
+                "--clearing-bank-password 会出现在进程列表中,已停用;请改用 --clearing-bank-password-fd 或 --clearing-bank-password-prompt"
+                    .to_string(),
+            );
+        }
+        let given = usize::from(fd.is_some()) + usize::from(prompt);
+        if given > 1 {
+            return Err(
+                "--clearing-bank-password-fd / --clearing-bank-password-prompt 只能选一个"
+                    .to_string(),
+            );
+        }
+        Ok(match (fd, prompt) {
+            (Some(fd), _) => PasswordSource::Fd(fd),
+            (_, true) => PasswordSource::Prompt,
+            _ => PasswordSource::None,
+        })
+    }
+
+    /// 读取口令。空口令视为未提供。
+    pub fn read(self, prompt_label: &str) -> Result<Option<Zeroizing<String>>, String> {
+        let secret = match self {
+            PasswordSource::None => return Ok(None),
+            PasswordSource::Fd(fd) => read_secret_from_fd(fd)?,
+            PasswordSource::Prompt => Zeroizing::new(
+                rpassword::prompt_password(format!("{prompt_label}: "))
+                    .map_err(|e| format!("读取终端口令失败:{e}"))?,
+            ),
+        };
+        Ok(if secret.is_empty() {
+            None
+        } else {
+            Some(secret)
+        })
+    }
+}
+
+/// 从文件描述符读取第一行作为口令(去掉行尾 `\n` / `\r\n`),读完关闭该描述符。
+#[cfg(unix)]
+pub fn read_secret_from_fd(fd: i32) -> Result<Zeroizing<String>, String> {
+    use std::os::fd::FromRawFd;
+
+    if fd < 0 {
+        return Err(format!("口令文件描述符无效:{fd}"));
+    }
+    // 标准输入/输出/错误不允许被接管关闭;口令通过 fd>=3 传入。
+    if fd <= 2 {
+        return Err("口令文件描述符不能是 0/1/2,请用 3 及以上(如 3<password-file)".to_string());
+    }
+    // SAFETY: fd 由启动方显式传入,本进程此前不持有它的所有权;读取后随 File drop 关闭,
+    // 其他代码不会再使用该描述符。
+    let file = unsafe { std::fs::File::from_raw_fd(fd) };
+    read_first_line(BufReader::new(file))
+}
+
+#[cfg(not(unix))]
+pub fn read_secret_from_fd(_fd: i32) -> Result<Zeroizing<String>, String> {
+    Err("当前平台不支持 --clearing-bank-password-fd".to_string())
+}
+
+fn read_first_line<R: BufRead>(mut reader: R) -> Result<Zeroizing<String>, String> {
+    let mut line = Zeroizing::new(String::new());
+    reader
+        .read_line(&mut line)
+        .map_err(|e| format!("读取口令描述符失败:{e}"))?;
+    let trimmed = line.trim_end_matches(['\n', '\r']).len();
+    line.truncate(trimmed);
+    Ok(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_sources_are_mutually_exclusive() {
+        assert_eq!(
+            PasswordSource::from_cli(None, None, false).unwrap(),
+            PasswordSource::None
+        );
+        assert_eq!(
+            PasswordSource::from_cli(None, Some(3), false).unwrap(),
+            PasswordSource::Fd(3)
+        );
+        assert_eq!(
+            PasswordSource::from_cli(None, None, true).unwrap(),
+            PasswordSource::Prompt
+        );
+        assert!(PasswordSource::from_cli(Some("pw"), None, false).is_err());
+        assert!(PasswordSource::from_cli(Some("pw"), Some(3), false).is_err());
+        assert!(PasswordSource::from_cli(None, Some(3), true).is_err());
+    }
+
+    #[test]
+    fn first_line_is_trimmed_and_rest_ignored() {
+        let read = read_first_line(&b"s3cret pass\r\nsecond line\n"[..]).unwrap();
+        assert_eq!(read.as_str(), "s3cret pass");
+        let read = read_first_line(&b"no-newline"[..]).unwrap();
+        assert_eq!(read.as_str(), "no-newline");
+    }
+
+    #[test]
+    fn absent_source_reads_nothing() {
+        assert!(PasswordSource::None.read("unused").unwrap().is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reads_secret_from_pipe_fd() {
+        use std::io::Write;
+        use std::os::fd::FromRawFd;
+
+        let mut fds = [0i32; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let mut writer = unsafe { std::fs::File::from_raw_fd(fds[1]) };
+        writer.write_all(b"pipe-secret\n").unwrap();
+        drop(writer);
+        let secret = read_secret_from_fd(fds[0]).unwrap();
+        assert_eq!(secret.as_str(), "pipe-secret");
+        assert!(read_secret_from_fd(0).is_err());
+    }
+}
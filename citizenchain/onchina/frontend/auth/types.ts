@@ -27,6 +27,8 @@ export type TokenAdminAuth = {
   scope_town_name?: string | null;
   /** 当前管理员所属机构简称,字段名与 subjects.cid_short_name 保持唯一命名。 */
   cid_short_name?: string | null;
+  /** 只读管理员:可见范围照常,写入口一律置灰。 */
+  read_only?: boolean;
 };
 
 export type AdminAuth = TokenAdminAuth;
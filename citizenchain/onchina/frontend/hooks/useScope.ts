@@ -1,11 +1,14 @@
 // 前端镜像后端 scope::rules::VisibleScope,按机构行政层级(admin_level)派生可见域。
 //
-// 五档(与后端 get_visible_scope 逐档一致):
+// 六档(与后端 get_visible_scope 逐档一致):
 //   - 全国(NATIONAL,部委等)        → 不限省/市/镇
 //   - 省级(PROVINCE / 联邦注册局)   → 本省,所有市(skipProvinceList=true 直进本省)
 //   - 市级(CITY)                    → 本市,所有镇(skipCityList=true 直进本市)
 //   - 镇级(TOWN)                    → 本镇(skipTownList=true 直进本镇)
-//   - 自机构(私权法人/非法人,无层级)→ 暂沿用本市范围
+//   - 自机构(私权法人/非法人,无层级)→ 仅本机构 CID(ownCidNumber),锁定本市
+//   - 未登录/缺省域                   → 一律不可写
+//
+// 只读管理员(read_only)在任一档内 canWrite = false。
 //
 // 鉴权真源在后端;本 hook 只服务前端 UX:写按钮置灰(canWrite*)+ 进 tab 跳级导航(skip*)。
 
@@ -22,6 +25,8 @@ export interface VisibleScope {
   cities: string[];
   /** 可见镇列表。空数组 = "不限镇"(全国/省级/市级)。 */
   towns: string[];
+  /** 自机构档锁定的机构 CID;其余档为 null。 */
+  ownCidNumber: string | null;
   /** 是否可以增删改(本辖区内才有写权限)。 */
   canWrite: boolean;
   /** 进 tab 时跳过省列表直接进入省详情(省级及以下)。 */
@@ -109,6 +114,7 @@ function noAuthScope(): VisibleScope {
     provinces: ['__NO_AUTH__'],
     cities: ['__NO_AUTH__'],
     towns: ['__NO_AUTH__'],
+    ownCidNumber: null,
     canWrite: false,
     skipProvinceList: false,
     skipCityList: false,
@@ -125,6 +131,7 @@ function nationalScope(): VisibleScope {
     provinces: [],
     cities: [],
     towns: [],
+    ownCidNumber: null,
     canWrite: true,
     skipProvinceList: false,
     skipCityList: false,
@@ -142,6 +149,7 @@ function provinceScope(auth: AdminAuth): VisibleScope {
     provinces: province_name ? [province_name] : [],
     cities: [],
     towns: [],
+    ownCidNumber: null,
     // 缺省域是后端投影错误,前端进入只读错误态,不造伪省名。
     canWrite: !!province_name,
     skipProvinceList: true,
@@ -161,6 +169,7 @@ function cityScope(auth: AdminAuth): VisibleScope {
     provinces: province_name ? [province_name] : [],
     cities: city_name ? [city_name] : [],
     towns: [],
+    ownCidNumber: null,
     canWrite: !!province_name && !!city_name,
     skipProvinceList: true,
     skipCityList: true,
@@ -180,6 +189,7 @@ function townScope(auth: AdminAuth): VisibleScope {
     provinces: province_name ? [province_name] : [],
     cities: city_name ? [city_name] : [],
     towns: town_name ? [town_name] : [],
+    ownCidNumber: null,
     canWrite: !!province_name && !!city_name && !!town_name,
     skipProvinceList: true,
     skipCityList: true,
@@ -190,27 +200,45 @@ function townScope(auth: AdminAuth): VisibleScope {
   });
 }
 
+function ownInstitutionScope(auth: AdminAuth): VisibleScope {
+  const city = cityScope(auth);
+  const ownCidNumber = auth.institution_cid_number?.trim() || null;
+  return makeScope({
+    ...city,
+    ownCidNumber,
+    canWrite: city.canWrite && !!ownCidNumber,
+  });
+}
+
+function deriveScope(auth: AdminAuth): VisibleScope {
+  // Tier1 创世注册局特判:admin_level 虽为 NATIONAL,但管理员按省分区(每节点单省)→ 省级范围。
+  if (isTier1Registry(auth.institution_code)) return provinceScope(auth);
+  switch (auth.admin_level) {
+    case 'NATIONAL':
+      return nationalScope();
+    case 'PROVINCE':
+      return provinceScope(auth);
+    case 'CITY':
+      return cityScope(auth);
+    case 'TOWN':
+      return townScope(auth);
+    default:
+      // 私权法人/非法人(无 admin_level):仅本机构(与后端 scope_own_institution_or_empty 一致)。
+      return ownInstitutionScope(auth);
+  }
+}
+
 export function useScope(auth: AdminAuth | null): VisibleScope {
   return useMemo<VisibleScope>(() => {
     if (!auth) return noAuthScope();
-    // Tier1 创世注册局特判:admin_level 虽为 NATIONAL,但管理员按省分区(每节点单省)→ 省级范围。
-    if (isTier1Registry(auth.institution_code)) return provinceScope(auth);
-    switch (auth.admin_level) {
-      case 'NATIONAL':
-        return nationalScope();
-      case 'PROVINCE':
-        return provinceScope(auth);
-      case 'CITY':
-        return cityScope(auth);
-      case 'TOWN':
-        return townScope(auth);
-      default:
-        // 私权法人/非法人(无 admin_level):暂沿用本市范围(与后端决策一致)。
-        return cityScope(auth);
-    }
+    const scope = deriveScope(auth);
+    // 只读管理员:可见范围不变,写权限统一关闭(与后端 VisibleScope::read_only 一致)。
+    return auth.read_only ? makeScope({ ...scope, canWrite: false }) : scope;
   }, [
+    auth?.institution_cid_number,
     auth?.institution_code,
     auth?.admin_level,
+    auth?.read_only,
     auth?.scope_province_name,
     auth?.scope_city_name,
     auth?.scope_town_name,
@@ -127,6 +127,32 @@ export function DisplayScreen() {
         />
       )}
 
+      {view.upcomingSunsets.length > 0 && (
+        <section aria-label="日落条款" style={{ ...glassCardStyle, padding: 0, marginBottom: 28 }}>
+          <h2 style={{ ...glassCardHeadStyle, padding: '18px 24px', fontSize: 24, margin: 0 }}>
+            即将到期的法律
+          </h2>
+          <ul style={{ listStyle: 'none', margin: 0, padding: '12px 24px', fontSize: 18 }}>
+            {view.upcomingSunsets.map((item) => (
+              <li
+                key={`${item.lawId}-${item.version}-${item.kind}`}
+                style={{ display: 'flex', gap: 16, padding: '8px 0', color: '#0f172a' }}
+              >
+                <span style={{ color: item.kind === 'expiry' ? '#c2410c' : '#2563eb', minWidth: 88 }}>
+                  {item.kind === 'expiry' ? '到期失效' : '到期复审'}
+                </span>
+                <span style={{ flex: 1 }}>
+                  {item.title || `法律 #${item.lawId}`}(第 {item.version} 版)
+                </span>
+                <span style={{ color: '#475569' }}>
+                  {new Date(item.at).toLocaleString('zh-CN', { hour12: false })}
+                </span>
+              </li>
+            ))}
+          </ul>
+        </section>
+      )}
+
       {view.activeProposals.length === 0 ? (
         <div style={{ ...glassCardStyle, padding: 64 }}>
           <Empty description={<span style={{ fontSize: 20 }}>当前无活跃立法提案</span>} />
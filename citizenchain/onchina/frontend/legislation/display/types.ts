@@ -20,6 +20,16 @@ export interface ActiveProposalView {
   pendingCount: number;
 }
 
+/** 本机构表决院所属法律的日落节点(expiry 到期失效 / review 到期复审)。 */
+export interface UpcomingSunsetView {
+  lawId: number;
+  version: number;
+  title: string;
+  kind: 'expiry' | 'review';
+  /** 到期时间戳(毫秒)。 */
+  at: number;
+}
+
 /** 大屏看板顶层快照(本节点机构 + 名册规模 + 活跃提案列表)。 */
 export interface DisplayBoard {
   institutionCode: string;
@@ -27,4 +37,5 @@ export interface DisplayBoard {
   scopeLabel: string;
   rosterTotal: number;
   activeProposals: ActiveProposalView[];
+  upcomingSunsets: UpcomingSunsetView[];
 }
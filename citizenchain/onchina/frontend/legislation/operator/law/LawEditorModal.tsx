@@ -70,6 +70,8 @@ export function LawEditorModal({ open, auth, lawAction, tier, voteType, onClose
   const [proposerRoleCode, setProposerRoleCode] = useState('');
   const [titleEn, setTitleEn] = useState('');
   const [effectiveAt, setEffectiveAt] = useState<number>(() => Date.now());
+  const [sunsetExpiresAt, setSunsetExpiresAt] = useState<number | null>(null);
+  const [sunsetReviewBy, setSunsetReviewBy] = useState<number | null>(null);
   const [lawId, setLawId] = useState<number | null>(null);
   const [chapters, setChapters] = useState<LawChapter[]>([]);
   const [submitting, setSubmitting] = useState(false);
@@ -96,6 +98,8 @@ export function LawEditorModal({ open, auth, lawAction, tier, voteType, onClose
     titleEn: titleEn || null,
     chapters: needsChapters ? chapters : [],
     effectiveAt,
+    sunsetExpiresAt: needsChapters ? sunsetExpiresAt : null,
+    sunsetReviewBy: needsChapters ? sunsetReviewBy : null,
     lawId: needsLawId ? lawId : null,
   });
 
@@ -162,6 +166,28 @@ export function LawEditorModal({ open, auth, lawAction, tier, voteType, onClose
             />
           </Space>
         )}
+        {needsChapters && (
+          <Space wrap>
+            <span>日落失效:</span>
+            <Input
+              type="datetime-local"
+              value={sunsetExpiresAt === null ? '' : toLocalDateTimeValue(sunsetExpiresAt)}
+              onChange={(e) =>
+                setSunsetExpiresAt(e.target.value ? fromLocalDateTimeValue(e.target.value) : null)
+              }
+              style={{ width: 220 }}
+            />
+            <span>日落复审:</span>
+            <Input
+              type="datetime-local"
+              value={sunsetReviewBy === null ? '' : toLocalDateTimeValue(sunsetReviewBy)}
+              onChange={(e) =>
+                setSunsetReviewBy(e.target.value ? fromLocalDateTimeValue(e.target.value) : null)
+              }
+              style={{ width: 220 }}
+            />
+          </Space>
+        )}
       </Space>
 
       {needsChapters && (
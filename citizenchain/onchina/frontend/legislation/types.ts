@@ -123,6 +123,10 @@ export interface ProposeLawInput {
   /** 正文:章>节>条>款(立法/修法携带;废法为空)。 */
   chapters: LawChapter[];
   effectiveAt: number;
+  /** 日落失效时间戳(毫秒,立法/修法);null = 不设。 */
+  sunsetExpiresAt?: number | null;
+  /** 日落复审时间戳(毫秒,立法/修法);null = 不设。 */
+  sunsetReviewBy?: number | null;
   /** 修法/废法目标法律 ID;立法为 null。 */
   lawId?: number | null;
 }
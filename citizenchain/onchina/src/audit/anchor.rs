@@ -0,0 +1,1130 @@
+//! 审计链上锚定。
+//!
+//! 周期任务按省把「上次锚点之后到当前链头」封成一个检查点(区间 Merkle 根 + 链头 hash),
+//! 入 `audit_anchors` 为 PENDING。OnChina 不持签名私钥:管理员 prepare 拿到
+//! `System.remark_with_event` call data 冷签上链,再 confirm 块哈希,本模块回读该块核对
+//! extrinsic 字节、ExtrinsicSuccess 与签名人(须为该省注册局链上 Active 管理员)后置为
+//! ANCHORED。此后数据库侧任何整段改写都无法同时改掉链上的 remark:校验时按库内
+//! block_hash 回读 finalized 块里的 remark,以链上的省码、区间首尾 id、链头 hash 与
+//! Merkle 根对照重算值,而不是信库内锚点行;超过宽限期仍未被已上链锚点覆盖的行
+//! 按锚点缺失处理,删库内锚点行也藏不住改写。
+
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use codec::{Compact, DecodeAll, Encode};
+use postgres::Client;
+use serde::{Deserialize, Serialize};
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::blocks::ExtrinsicDetails;
+use subxt::{OnlineClient, PolkadotConfig};
+
+use crate::core::chain_runtime::{self, FinalizedChainView, NodeInstitutionIdentity};
+use crate::core::chain_url::chain_ws_url;
+use crate::crypto::pubkey::same_account_id;
+use crate::*;
+
+use super::chain::{self, hash_hex, parse_hash_hex, ChainVerifyReport};
+
+/// frame_system 在 runtime 中的索引与 `remark_with_event` 调用号。
+pub(crate) const SYSTEM_PALLET_INDEX: u8 = 0;
+pub(crate) const CALL_REMARK_WITH_EVENT: u8 = 7;
+/// remark 载荷魔数;链上检索锚点只认此前缀。
+const ANCHOR_REMARK_MAGIC: &[u8] = b"GMB_AUDIT_ANCHOR_V1";
+const DEFAULT_ANCHOR_INTERVAL_SECS: u64 = 3600;
+/// 行写入后须在此宽限期内冷签上链;超期仍无已上链锚点覆盖即视为锚点缺失。
+const DEFAULT_ANCHOR_GRACE_SECS: i64 = 7 * 24 * 3600;
+
+pub(crate) const ANCHOR_STATUS_PENDING: &str = "PENDING";
+pub(crate) const ANCHOR_STATUS_ANCHORED: &str = "ANCHORED";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(crate) struct AuditAnchor {
+    pub(crate) anchor_id: i64,
+    pub(crate) province_code: String,
+    pub(crate) first_id: i64,
+    pub(crate) last_id: i64,
+    pub(crate) last_entry_hash: String,
+    pub(crate) merkle_root: String,
+    pub(crate) entry_count: i64,
+    pub(crate) remark_hex: String,
+    pub(crate) status: String,
+    pub(crate) block_number: Option<i64>,
+    pub(crate) block_hash: Option<String>,
+    pub(crate) tx_hash: Option<String>,
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) anchored_at: Option<DateTime<Utc>>,
+}
+
+/// remark 载荷:魔数 + SCALE(省码, 区间首尾 id, 链头 hash, 区间 Merkle 根)。
+pub(crate) fn anchor_remark(
+    province_code: &str,
+    first_id: i64,
+    last_id: i64,
+    last_entry_hash: &[u8; 32],
+    merkle_root: &[u8; 32],
+) -> Vec<u8> {
+    let mut out = ANCHOR_REMARK_MAGIC.to_vec();
+    out.extend(
+        (
+            province_code,
+            first_id as u64,
+            last_id as u64,
+            last_entry_hash,
+            merkle_root,
+        )
+            .encode(),
+    );
+    out
+}
+
+/// 链上 remark 解出的锚点内容。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AnchorRemark {
+    pub(crate) province_code: String,
+    pub(crate) first_id: u64,
+    pub(crate) last_id: u64,
+    pub(crate) last_entry_hash: [u8; 32],
+    pub(crate) merkle_root: [u8; 32],
+}
+
+/// [`anchor_remark`] 的逆过程;魔数不符或有尾随字节时返回 None。
+pub(crate) fn decode_anchor_remark(remark: &[u8]) -> Option<AnchorRemark> {
+    let mut body = remark.strip_prefix(ANCHOR_REMARK_MAGIC)?;
+    let (province_code, first_id, last_id, last_entry_hash, merkle_root) =
+        <(String, u64, u64, [u8; 32], [u8; 32])>::decode_all(&mut body).ok()?;
+    Some(AnchorRemark {
+        province_code,
+        first_id,
+        last_id,
+        last_entry_hash,
+        merkle_root,
+    })
+}
+
+/// 从 call 字节取 `System.remark_with_event` 的 remark;其他调用返回 None。
+fn remark_from_call(call: &[u8]) -> Option<Vec<u8>> {
+    let mut body = call.strip_prefix(&[SYSTEM_PALLET_INDEX, CALL_REMARK_WITH_EVENT])?;
+    Vec::<u8>::decode_all(&mut body).ok()
+}
+
+/// 裸 call data:`System.remark_with_event(remark)`。
+pub(crate) fn anchor_call_data(remark: &[u8]) -> Vec<u8> {
+    let mut out = vec![SYSTEM_PALLET_INDEX, CALL_REMARK_WITH_EVENT];
+    out.extend(Compact(remark.len() as u32).encode());
+    out.extend_from_slice(remark);
+    out
+}
+
+/// 签名 extrinsic 的 `MultiAddress::Id` 转规范账户 ID;未签名或非 Id 地址返回 None。
+fn signer_account_id(address: Option<&[u8]>) -> Option<String> {
+    let mut address = address?;
+    match citizenchain::Address::decode_all(&mut address).ok()? {
+        sp_runtime::MultiAddress::Id(account) => {
+            Some(format!("0x{}", hex::encode(<[u8; 32]>::from(account))))
+        }
+        _ => None,
+    }
+}
+
+/// 省码对应的联邦注册局省管理员身份;注册局 CID 取创世常量,不信本地机构投影。
+fn province_registry_identity(province_code: &str) -> Result<NodeInstitutionIdentity, String> {
+    let code = <[u8; 2]>::try_from(province_code.as_bytes())
+        .ok()
+        .filter(|code| primitives::cid::code::province_code_text(code) == Some(province_code))
+        .ok_or_else(|| format!("audit anchor province code {province_code} invalid"))?;
+    let registry = primitives::cid::china::china_zf::CHINA_ZF
+        .iter()
+        .find(|inst| {
+            primitives::cid::code::institution_code_from_cid_number(inst.cid_number)
+                == Some(primitives::cid::code::FRG)
+        })
+        .ok_or_else(|| "federal registry missing from genesis institutions".to_string())?;
+    chain_runtime::identity_from_binding_parts(
+        chain_runtime::TIER1_REGISTRY_CODE,
+        Some(registry.cid_number),
+        Some(hex::encode(code).as_str()),
+    )
+}
+
+/// 锚点 remark 的签名人须是该省注册局在 `view` 区块的 Active 管理员;
+/// 任何人都能复制同一 remark 上链,不核签名人就等于谁都能替省里"锚定"。
+async fn ensure_anchor_signer(
+    view: &FinalizedChainView,
+    province_code: &str,
+    address: Option<&[u8]>,
+) -> Result<(), String> {
+    let signer = signer_account_id(address)
+        .ok_or_else(|| "audit anchor extrinsic is not signed by an account".to_string())?;
+    let identity = province_registry_identity(province_code)?;
+    let admins = chain_runtime::fetch_active_admins_onchain_at(&identity, view)
+        .await?
+        .ok_or_else(|| "province registry has no active admins on chain".to_string())?;
+    if !admins
+        .iter()
+        .any(|admin| same_account_id(&admin.account_id, &signer))
+    {
+        return Err(format!(
+            "audit anchor signer {signer} is not an active admin of the province registry"
+        ));
+    }
+    Ok(())
+}
+
+/// extrinsic 的事件里是否有 `System.ExtrinsicSuccess`。
+async fn extrinsic_succeeded(
+    extrinsic: &ExtrinsicDetails<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+) -> Result<bool, String> {
+    let events = extrinsic
+        .events()
+        .await
+        .map_err(|e| format!("read audit anchor extrinsic events failed: {e}"))?;
+    for event in events.iter() {
+        let event = event.map_err(|e| format!("decode audit anchor event failed: {e}"))?;
+        if event.pallet_name() == "System" && event.variant_name() == "ExtrinsicSuccess" {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn anchor_from_row(row: &postgres::Row) -> AuditAnchor {
+    AuditAnchor {
+        anchor_id: row.get(0),
+        province_code: row.get(1),
+        first_id: row.get(2),
+        last_id: row.get(3),
+        last_entry_hash: row.get(4),
+        merkle_root: row.get(5),
+        entry_count: row.get(6),
+        remark_hex: row.get(7),
+        status: row.get(8),
+        block_number: row.get(9),
+        block_hash: row.get(10),
+        tx_hash: row.get(11),
+        created_at: row.get(12),
+        anchored_at: row.get(13),
+    }
+}
+
+const ANCHOR_COLUMNS: &str = "anchor_id, province_code, first_id, last_id, last_entry_hash,
+     merkle_root, entry_count, remark_hex, status, block_number, block_hash, tx_hash,
+     created_at, anchored_at";
+
+pub(crate) fn list_anchors_conn(
+    conn: &mut Client,
+    province_code: Option<&str>,
+) -> Result<Vec<AuditAnchor>, String> {
+    let sql = format!(
+        "SELECT {ANCHOR_COLUMNS} FROM audit_anchors
+         WHERE ($1::text IS NULL OR province_code = $1)
+         ORDER BY province_code ASC, last_id ASC"
+    );
+    let rows = conn
+        .query(sql.as_str(), &[&province_code])
+        .map_err(|e| format!("query audit anchors failed: {e}"))?;
+    Ok(rows.iter().map(anchor_from_row).collect())
+}
+
+fn load_anchor_conn(conn: &mut Client, anchor_id: i64) -> Result<Option<AuditAnchor>, String> {
+    let sql = format!("SELECT {ANCHOR_COLUMNS} FROM audit_anchors WHERE anchor_id = $1");
+    let row = conn
+        .query_opt(sql.as_str(), &[&anchor_id])
+        .map_err(|e| format!("query audit anchor failed: {e}"))?;
+    Ok(row.as_ref().map(anchor_from_row))
+}
+
+/// 为所有有新行的省封一个 PENDING 检查点;返回本轮新建数。
+pub(crate) fn seal_pending_checkpoints_conn(conn: &mut Client) -> Result<usize, String> {
+    let heads = conn
+        .query(
+            "SELECT h.province_code, h.last_id,
+                    COALESCE((SELECT max(a.last_id) FROM audit_anchors a
+                              WHERE a.province_code = h.province_code), 0)
+             FROM audit_chain_heads h
+             ORDER BY h.province_code",
+            &[],
+        )
+        .map_err(|e| format!("query audit chain heads failed: {e}"))?;
+    let mut sealed = 0;
+    for head in heads {
+        let province_code: String = head.get(0);
+        let head_id: i64 = head.get(1);
+        let anchored_id: i64 = head.get(2);
+        if head_id <= anchored_id {
+            continue;
+        }
+        let rows = chain::load_rows_conn(conn, province_code.as_str(), anchored_id, Some(head_id))?;
+        let (Some(first), Some(last)) = (rows.first(), rows.last()) else {
+            continue;
+        };
+        let root = chain::range_merkle_root(&rows)?;
+        let last_hash = parse_hash_hex(last.entry_hash.as_str())?;
+        let remark = anchor_remark(province_code.as_str(), first.id, last.id, &last_hash, &root);
+        let entry_count = rows.len() as i64;
+        let inserted = conn
+            .execute(
+                "INSERT INTO audit_anchors(
+                province_code, first_id, last_id, last_entry_hash, merkle_root,
+                entry_count, remark_hex, status
+             )
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             ON CONFLICT (province_code, last_id) DO NOTHING",
+                &[
+                    &province_code,
+                    &first.id,
+                    &last.id,
+                    &last.entry_hash,
+                    &hash_hex(&root),
+                    &entry_count,
+                    &format!("0x{}", hex::encode(&remark)),
+                    &ANCHOR_STATUS_PENDING,
+                ],
+            )
+            .map_err(|e| format!("insert audit anchor failed: {e}"))?;
+        // 并发封存撞上唯一键时 DO NOTHING 不插行,不计入本轮新建数。
+        if inserted > 0 {
+            sealed += 1;
+        }
+    }
+    Ok(sealed)
+}
+
+pub(crate) async fn audit_anchor_loop(db: Db) {
+    let interval_secs = std::env::var("ONCHINA_AUDIT_ANCHOR_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_ANCHOR_INTERVAL_SECS);
+    let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+    loop {
+        ticker.tick().await;
+        match db.with_client(seal_pending_checkpoints_conn) {
+            Ok(0) => {}
+            Ok(sealed) => tracing::info!(sealed, "sealed audit anchor checkpoints"),
+            Err(err) => tracing::warn!(error = %err, "seal audit anchor checkpoints failed"),
+        }
+    }
+}
+
+/// 在指定块内找 call 字节完全一致、成功且由本省注册局 Active 管理员签名的 extrinsic,
+/// 返回 (块号, tx hash)。签名人按当前 finalized 名册核对。
+async fn find_anchor_extrinsic(
+    block_hash: [u8; 32],
+    province_code: &str,
+    call_data: &[u8],
+) -> Result<(u64, String), String> {
+    let view = FinalizedChainView::connect().await?;
+    let block = view
+        .client
+        .blocks()
+        .at(subxt::utils::H256(block_hash))
+        .await
+        .map_err(|e| format!("read audit anchor block failed: {e}"))?;
+    let extrinsics = block
+        .extrinsics()
+        .await
+        .map_err(|e| format!("read audit anchor block extrinsics failed: {e}"))?;
+    let mut rejected = None;
+    for extrinsic in extrinsics.iter() {
+        if extrinsic.call_bytes() != call_data || !extrinsic_succeeded(&extrinsic).await? {
+            continue;
+        }
+        if let Err(err) =
+            ensure_anchor_signer(&view, province_code, extrinsic.address_bytes()).await
+        {
+            rejected = Some(err);
+            continue;
+        }
+        return Ok((
+            u64::from(block.number()),
+            format!("0x{}", hex::encode(extrinsic.hash().0)),
+        ));
+    }
+    Err(rejected
+        .unwrap_or_else(|| "audit anchor remark not found in block or did not succeed".to_string()))
+}
+
+/// 按 anchor_id 索引的链上锚点回读结果;Err 为块缺失、未 finalized 或找不到 remark 的原因。
+pub(crate) type OnchainAnchors = HashMap<i64, Result<AnchorRemark, String>>;
+
+/// 读取存储块哈希对应的 finalized 块,找出其中成功执行、且由本省注册局当时 Active 管理员
+/// 签名的本锚点 remark。
+async fn read_onchain_anchor(
+    client: &OnlineClient<PolkadotConfig>,
+    rpc: &LegacyRpcMethods<PolkadotConfig>,
+    anchor: &AuditAnchor,
+    finalized_number: u64,
+) -> Result<AnchorRemark, String> {
+    let (Some(block_hash), Some(block_number)) =
+        (anchor.block_hash.as_deref(), anchor.block_number)
+    else {
+        return Err("anchored row has no block hash".to_string());
+    };
+    let block_hash = subxt::utils::H256(parse_hash_hex(block_hash)?);
+    let block_number =
+        u32::try_from(block_number).map_err(|_| format!("block number {block_number} invalid"))?;
+    if u64::from(block_number) > finalized_number {
+        return Err(format!("block #{block_number} is not finalized"));
+    }
+    let canonical = rpc
+        .chain_get_block_hash(Some(block_number.into()))
+        .await
+        .map_err(|e| format!("read block hash #{block_number} failed: {e}"))?;
+    if canonical != Some(block_hash) {
+        return Err(format!(
+            "block {} is not the finalized block #{block_number}",
+            hash_hex(&block_hash.0)
+        ));
+    }
+    let block = client
+        .blocks()
+        .at(block_hash)
+        .await
+        .map_err(|e| format!("read audit anchor block failed: {e}"))?;
+    let extrinsics = block
+        .extrinsics()
+        .await
+        .map_err(|e| format!("read audit anchor block extrinsics failed: {e}"))?;
+    let view = FinalizedChainView::at_finalized_block(client.clone(), block_hash, block_number);
+    let mut rejected = None;
+    for extrinsic in extrinsics.iter() {
+        let Some(remark) = remark_from_call(extrinsic.call_bytes())
+            .as_deref()
+            .and_then(decode_anchor_remark)
+        else {
+            continue;
+        };
+        if remark.province_code != anchor.province_code
+            || i64::try_from(remark.last_id).ok() != Some(anchor.last_id)
+            || !extrinsic_succeeded(&extrinsic).await?
+        {
+            continue;
+        }
+        if let Err(err) = ensure_anchor_signer(
+            &view,
+            anchor.province_code.as_str(),
+            extrinsic.address_bytes(),
+        )
+        .await
+        {
+            rejected = Some(err);
+            continue;
+        }
+        return Ok(remark);
+    }
+    Err(rejected
+        .unwrap_or_else(|| "audit anchor remark not found in block or did not succeed".to_string()))
+}
+
+/// 回读全部 ANCHORED 锚点;链不可达时每个锚点都记为失败(校验 fail-closed)。
+pub(crate) async fn fetch_onchain_anchors(anchors: &[AuditAnchor]) -> OnchainAnchors {
+    let anchored = anchors
+        .iter()
+        .filter(|anchor| anchor.status == ANCHOR_STATUS_ANCHORED)
+        .collect::<Vec<_>>();
+    let mut out = OnchainAnchors::new();
+    if anchored.is_empty() {
+        return out;
+    }
+    let connected = async {
+        let ws_url = chain_ws_url()?;
+        let rpc_client = crate::core::chain_proof::connect_rpc(ws_url.as_str())
+            .await
+            .map_err(|e| format!("connect chain rpc for audit verify failed: {e}"))?;
+        let rpc = LegacyRpcMethods::<PolkadotConfig>::new(rpc_client.clone());
+        let client = OnlineClient::<PolkadotConfig>::from_rpc_client(rpc_client)
+            .await
+            .map_err(|e| format!("connect chain for audit verify failed: {e}"))?;
+        let finalized = rpc
+            .chain_get_finalized_head()
+            .await
+            .map_err(|e| format!("read finalized head failed: {e}"))?;
+        let header = rpc
+            .chain_get_header(Some(finalized))
+            .await
+            .map_err(|e| format!("read finalized header failed: {e}"))?
+            .ok_or_else(|| "finalized header not found".to_string())?;
+        Ok::<_, String>((client, rpc, u64::from(header.number)))
+    }
+    .await;
+    match connected {
+        Ok((client, rpc, finalized_number)) => {
+            for anchor in anchored {
+                let result = read_onchain_anchor(&client, &rpc, anchor, finalized_number).await;
+                out.insert(anchor.anchor_id, result);
+            }
+        }
+        Err(err) => {
+            for anchor in anchored {
+                out.insert(anchor.anchor_id, Err(err.clone()));
+            }
+        }
+    }
+    out
+}
+
+/// 完整性校验:逐省从创世重算;已上链锚点以链上 remark 为准核对重算的区间根与链头 hash,
+/// 待上链锚点只能核对库内记录。截止时刻前写入的行必须落在某个已上链锚点区间内,
+/// 否则删掉库内锚点行再改写区间就能绕过链上核对。
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ProvinceVerifyReport {
+    pub(crate) province_code: String,
+    pub(crate) chain: ChainVerifyReport,
+    pub(crate) anchors_checked: usize,
+    pub(crate) anchors_anchored: usize,
+    pub(crate) anchor_mismatches: Vec<i64>,
+    /// 已上链锚点回读失败或与链上 remark 不符的原因,`anchor_id: 原因`。
+    pub(crate) anchor_problems: Vec<String>,
+    /// 截止时刻前写入、却不在任何已上链锚点区间内的首行 id。
+    pub(crate) first_unanchored_id: Option<i64>,
+}
+
+impl ProvinceVerifyReport {
+    pub(crate) fn is_intact(&self) -> bool {
+        self.chain.is_intact()
+            && self.anchor_mismatches.is_empty()
+            && self.first_unanchored_id.is_none()
+    }
+}
+
+/// 已上链锚点区间须首尾相接地覆盖截止时刻前的全部行;返回首个未覆盖的旧行 id。
+fn first_unanchored_row(
+    rows: &[chain::ChainedAuditRow],
+    anchors: &[&AuditAnchor],
+    cutoff: DateTime<Utc>,
+) -> Option<i64> {
+    let mut ranges = anchors
+        .iter()
+        .filter(|anchor| anchor.status == ANCHOR_STATUS_ANCHORED)
+        .map(|anchor| (anchor.first_id, anchor.last_id))
+        .collect::<Vec<_>>();
+    ranges.sort_unstable();
+    let mut ranges = ranges.into_iter().peekable();
+    for row in rows {
+        while ranges.next_if(|(_, last_id)| *last_id < row.id).is_some() {}
+        let covered = ranges
+            .peek()
+            .is_some_and(|(first_id, last_id)| *first_id <= row.id && row.id <= *last_id);
+        if !covered && row.created_at < cutoff {
+            return Some(row.id);
+        }
+    }
+    None
+}
+
+/// 校验截止时刻:当前时间减宽限期,宽限期可由 `ONCHINA_AUDIT_ANCHOR_GRACE_SECS` 调整。
+pub(crate) fn anchor_cutoff() -> DateTime<Utc> {
+    let grace_secs = std::env::var("ONCHINA_AUDIT_ANCHOR_GRACE_SECS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_ANCHOR_GRACE_SECS);
+    Utc::now() - chrono::Duration::seconds(grace_secs)
+}
+
+/// 按链上 remark 核对:省码、区间首尾 id 与库内锚点一致,重算的区间根与链头 hash 一致。
+fn check_onchain_anchor(
+    anchor: &AuditAnchor,
+    rows: &[chain::ChainedAuditRow],
+    onchain: Option<&Result<AnchorRemark, String>>,
+) -> Result<(), String> {
+    let remark = match onchain {
+        Some(Ok(remark)) => remark,
+        Some(Err(err)) => return Err(err.clone()),
+        None => return Err("anchor was not read back from chain".to_string()),
+    };
+    if remark.province_code != anchor.province_code {
+        return Err("on-chain province code differs".to_string());
+    }
+    let (Ok(first_id), Ok(last_id)) = (
+        i64::try_from(remark.first_id),
+        i64::try_from(remark.last_id),
+    ) else {
+        return Err("on-chain id range invalid".to_string());
+    };
+    if first_id != anchor.first_id || last_id != anchor.last_id {
+        return Err("on-chain id range differs from stored anchor".to_string());
+    }
+    let range = rows
+        .iter()
+        .filter(|row| row.id >= first_id && row.id <= last_id)
+        .cloned()
+        .collect::<Vec<_>>();
+    if range.first().map(|row| row.id) != Some(first_id)
+        || range.last().map(|row| row.id) != Some(last_id)
+    {
+        return Err("rows of the anchored range are missing".to_string());
+    }
+    if range.last().map(|row| row.entry_hash.as_str())
+        != Some(hash_hex(&remark.last_entry_hash).as_str())
+    {
+        return Err("recomputed last entry hash differs from chain".to_string());
+    }
+    if chain::range_merkle_root(&range)? != remark.merkle_root {
+        return Err("recomputed merkle root differs from chain".to_string());
+    }
+    Ok(())
+}
+
+/// 用已载入的行核对锚点;锚点区间找不到对应行(被删/截尾)同样算不一致。
+pub(crate) fn verify_province(
+    province_code: &str,
+    rows: &[chain::ChainedAuditRow],
+    head: Option<&chain::AuditChainHead>,
+    anchors: &[AuditAnchor],
+    onchain: &OnchainAnchors,
+    cutoff: DateTime<Utc>,
+) -> ProvinceVerifyReport {
+    let chain = chain::verify_rows(rows, head);
+    let anchors = anchors
+        .iter()
+        .filter(|a| a.province_code == province_code)
+        .collect::<Vec<_>>();
+    let mut anchor_mismatches = Vec::new();
+    let mut anchor_problems = Vec::new();
+    let mut anchors_checked = 0;
+    let mut anchors_anchored = 0;
+    for anchor in anchors.iter().copied() {
+        anchors_checked += 1;
+        if anchor.status == ANCHOR_STATUS_ANCHORED {
+            anchors_anchored += 1;
+            if let Err(err) = check_onchain_anchor(anchor, rows, onchain.get(&anchor.anchor_id)) {
+                anchor_problems.push(format!("{}: {err}", anchor.anchor_id));
+                anchor_mismatches.push(anchor.anchor_id);
+                continue;
+            }
+        }
+        let range = rows
+            .iter()
+            .filter(|row| row.id >= anchor.first_id && row.id <= anchor.last_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        let matches = range.len() as i64 == anchor.entry_count
+            && range.last().map(|row| row.entry_hash.as_str())
+                == Some(anchor.last_entry_hash.as_str())
+            && chain::range_merkle_root(&range)
+                .map(|root| hash_hex(&root) == anchor.merkle_root)
+                .unwrap_or(false);
+        if !matches {
+            anchor_mismatches.push(anchor.anchor_id);
+        }
+    }
+    ProvinceVerifyReport {
+        province_code: province_code.to_string(),
+        chain,
+        anchors_checked,
+        anchors_anchored,
+        anchor_mismatches,
+        anchor_problems,
+        first_unanchored_id: first_unanchored_row(rows, &anchors, cutoff),
+    }
+}
+
+/// 一省的待校验数据:全部行与链头。
+pub(crate) struct ProvinceAuditData {
+    pub(crate) province_code: String,
+    pub(crate) rows: Vec<chain::ChainedAuditRow>,
+    pub(crate) head: Option<chain::AuditChainHead>,
+}
+
+/// 在线或离线校验的输入:逐省数据与全部锚点。
+pub(crate) struct AuditVerifyInput {
+    pub(crate) provinces: Vec<ProvinceAuditData>,
+    pub(crate) anchors: Vec<AuditAnchor>,
+}
+
+impl AuditVerifyInput {
+    pub(crate) fn verify(
+        &self,
+        onchain: &OnchainAnchors,
+        cutoff: DateTime<Utc>,
+    ) -> Vec<ProvinceVerifyReport> {
+        self.provinces
+            .iter()
+            .map(|data| {
+                verify_province(
+                    data.province_code.as_str(),
+                    &data.rows,
+                    data.head.as_ref(),
+                    &self.anchors,
+                    onchain,
+                    cutoff,
+                )
+            })
+            .collect()
+    }
+
+    /// 回读链上锚点后校验,截止时刻取 [`anchor_cutoff`]。
+    pub(crate) async fn verify_against_chain(&self) -> Vec<ProvinceVerifyReport> {
+        let onchain = fetch_onchain_anchors(&self.anchors).await;
+        self.verify(&onchain, anchor_cutoff())
+    }
+}
+
+pub(crate) fn load_verify_input_conn(
+    conn: &mut Client,
+    province_code: Option<&str>,
+) -> Result<AuditVerifyInput, String> {
+    let provinces: Vec<String> = conn
+        .query(
+            "SELECT province_code FROM audit_chain_heads
+             WHERE ($1::text IS NULL OR province_code = $1)
+             ORDER BY province_code",
+            &[&province_code],
+        )
+        .map_err(|e| format!("query audit chain heads failed: {e}"))?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    let anchors = list_anchors_conn(conn, province_code)?;
+    let mut data = Vec::with_capacity(provinces.len());
+    for province in provinces {
+        let rows = chain::load_rows_conn(conn, province.as_str(), 0, None)?;
+        let head = chain::load_head_conn(conn, province.as_str())?;
+        data.push(ProvinceAuditData {
+            province_code: province,
+            rows,
+            head,
+        });
+    }
+    Ok(AuditVerifyInput {
+        provinces: data,
+        anchors,
+    })
+}
+
+fn admin_province_code(ctx: &AdminAuthContext) -> Option<String> {
+    ctx.scope_province_name
+        .as_deref()
+        .and_then(crate::cid::china::province_code_by_name)
+        .map(ToOwned::to_owned)
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AuditAnchorsQuery {
+    pub(crate) province_code: Option<String>,
+}
+
+/// 省市管理员只看本省锚点;全国管理员可按省过滤。
+fn query_province(ctx: &AdminAuthContext, query: Option<String>) -> Option<String> {
+    admin_province_code(ctx).or_else(|| {
+        query
+            .map(|v| v.trim().to_uppercase())
+            .filter(|v| !v.is_empty())
+    })
+}
+
+pub(crate) async fn admin_list_audit_anchors(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<AuditAnchorsQuery>,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let province = query_province(&ctx, query.province_code);
+    match state
+        .db
+        .with_client(move |conn| list_anchors_conn(conn, province.as_deref()))
+    {
+        Ok(rows) => Json(ApiResponse {
+            code: 0,
+            message: "ok".to_string(),
+            data: rows,
+        })
+        .into_response(),
+        Err(err) => api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, err.as_str()),
+    }
+}
+
+pub(crate) async fn admin_verify_audit_chain(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<AuditAnchorsQuery>,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let province = query_province(&ctx, query.province_code);
+    let input = match state
+        .db
+        .with_client(move |conn| load_verify_input_conn(conn, province.as_deref()))
+    {
+        Ok(v) => v,
+        Err(err) => return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, err.as_str()),
+    };
+    Json(ApiResponse {
+        code: 0,
+        message: "ok".to_string(),
+        data: input.verify_against_chain().await,
+    })
+    .into_response()
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AuditAnchorPrepareInput {
+    pub(crate) anchor_id: i64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct AuditAnchorCallOutput {
+    pub(crate) anchor_id: i64,
+    pub(crate) action: u16,
+    pub(crate) pallet_index: u8,
+    pub(crate) call_index: u8,
+    pub(crate) call_data_hex: String,
+    pub(crate) review_title: &'static str,
+}
+
+/// 读锚点并确认属于管理员本省、仍待上链。
+fn load_writable_anchor(
+    state: &AppState,
+    ctx: &AdminAuthContext,
+    anchor_id: i64,
+) -> Result<AuditAnchor, axum::response::Response> {
+    if !crate::scope::get_visible_scope(ctx).can_write {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            1003,
+            "read-only admin cannot anchor audit logs",
+        ));
+    }
+    let anchor = match state
+        .db
+        .with_client(move |conn| load_anchor_conn(conn, anchor_id))
+    {
+        Ok(Some(anchor)) => anchor,
+        Ok(None) => {
+            return Err(api_error(
+                StatusCode::NOT_FOUND,
+                1004,
+                "audit anchor not found",
+            ))
+        }
+        Err(err) => {
+            return Err(api_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                5001,
+                err.as_str(),
+            ))
+        }
+    };
+    if let Some(province) = admin_province_code(ctx) {
+        if province != anchor.province_code {
+            return Err(api_error(
+                StatusCode::FORBIDDEN,
+                1003,
+                "audit anchor is outside admin scope",
+            ));
+        }
+    }
+    if anchor.status != ANCHOR_STATUS_PENDING {
+        return Err(api_error(
+            StatusCode::CONFLICT,
+            1005,
+            "audit anchor already anchored",
+        ));
+    }
+    Ok(anchor)
+}
+
+fn anchor_call_bytes(anchor: &AuditAnchor) -> Result<Vec<u8>, String> {
+    let remark = hex::decode(anchor.remark_hex.trim_start_matches("0x"))
+        .map_err(|e| format!("stored audit anchor remark invalid: {e}"))?;
+    Ok(anchor_call_data(&remark))
+}
+
+pub(crate) async fn admin_prepare_audit_anchor(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(input): Json<AuditAnchorPrepareInput>,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    // 链上写(PasskeyColdSign 档):与地址上链同一入口约束,先断言 passkey。
+    if let Err(resp) =
+        crate::auth::passkey::require_passkey_assertion(&state, &headers, ctx.account_id.as_str())
+    {
+        return resp;
+    }
+    let anchor = match load_writable_anchor(&state, &ctx, input.anchor_id) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let call_data = match anchor_call_bytes(&anchor) {
+        Ok(v) => v,
+        Err(err) => return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, err.as_str()),
+    };
+    Json(ApiResponse {
+        code: 0,
+        message: "ok".to_string(),
+        data: AuditAnchorCallOutput {
+            anchor_id: anchor.anchor_id,
+            action: crate::core::institution_call::chain_action_code(
+                SYSTEM_PALLET_INDEX,
+                CALL_REMARK_WITH_EVENT,
+            ),
+            pallet_index: SYSTEM_PALLET_INDEX,
+            call_index: CALL_REMARK_WITH_EVENT,
+            call_data_hex: format!("0x{}", hex::encode(call_data)),
+            review_title: "审计日志上链锚定",
+        },
+    })
+    .into_response()
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AuditAnchorConfirmInput {
+    pub(crate) anchor_id: i64,
+    pub(crate) block_hash: String,
+}
+
+pub(crate) async fn admin_confirm_audit_anchor(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(input): Json<AuditAnchorConfirmInput>,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let anchor = match load_writable_anchor(&state, &ctx, input.anchor_id) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let block_hash = match parse_hash_hex(input.block_hash.as_str()) {
+        Ok(v) => v,
+        Err(err) => return api_error(StatusCode::BAD_REQUEST, 1001, err.as_str()),
+    };
+    let call_data = match anchor_call_bytes(&anchor) {
+        Ok(v) => v,
+        Err(err) => return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, err.as_str()),
+    };
+    let (block_number, tx_hash) =
+        match find_anchor_extrinsic(block_hash, anchor.province_code.as_str(), &call_data).await {
+            Ok(v) => v,
+            Err(err) => return api_error(StatusCode::BAD_REQUEST, 1001, err.as_str()),
+        };
+    let anchor_id = anchor.anchor_id;
+    let block_number = block_number as i64;
+    let block_hash = hash_hex(&block_hash);
+    let result = state.db.with_client(move |conn| {
+        conn.execute(
+            "UPDATE audit_anchors
+             SET status = $2, block_number = $3, block_hash = $4, tx_hash = $5, anchored_at = now()
+             WHERE anchor_id = $1 AND status = $6",
+            &[
+                &anchor_id,
+                &ANCHOR_STATUS_ANCHORED,
+                &block_number,
+                &block_hash,
+                &tx_hash,
+                &ANCHOR_STATUS_PENDING,
+            ],
+        )
+        .map_err(|e| format!("update audit anchor failed: {e}"))?;
+        load_anchor_conn(conn, anchor_id)
+    });
+    match result {
+        Ok(Some(anchor)) => Json(ApiResponse {
+            code: 0,
+            message: "ok".to_string(),
+            data: anchor,
+        })
+        .into_response(),
+        Ok(None) => api_error(StatusCode::NOT_FOUND, 1004, "audit anchor not found"),
+        Err(err) => api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, err.as_str()),
+    }
+}
+
+#[cfg(test)]
+// 测试需要在前置条件失效时立即失败，断言式解包仅限本测试模块。
+#[allow(clippy::expect_used, clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchor_call_data_is_system_remark_with_event() {
+        let remark = anchor_remark("AH", 1, 9, &[1u8; 32], &[2u8; 32]);
+        assert!(remark.starts_with(ANCHOR_REMARK_MAGIC));
+        let call = anchor_call_data(&remark);
+        let expected = citizenchain::RuntimeCall::System(frame_system::Call::remark_with_event {
+            remark: remark.clone(),
+        })
+        .encode();
+        assert_eq!(call, expected);
+        let decoded = remark_from_call(&call)
+            .as_deref()
+            .and_then(decode_anchor_remark)
+            .unwrap();
+        assert_eq!(decoded.province_code, "AH");
+        assert_eq!((decoded.first_id, decoded.last_id), (1, 9));
+        assert_eq!(decoded.merkle_root, [2u8; 32]);
+        assert!(decode_anchor_remark(&remark[1..]).is_none());
+    }
+
+    fn chained_rows() -> Vec<chain::ChainedAuditRow> {
+        let created_at = DateTime::<Utc>::from_timestamp_micros(1_700_000_000_000_000).unwrap();
+        let mut prev = chain::GENESIS_PREV_HASH;
+        (1..=3)
+            .map(|id| {
+                let mut row = chain::ChainedAuditRow {
+                    id,
+                    province_code: "AH".to_string(),
+                    city_code: None,
+                    actor_account_id: None,
+                    action: "CITIZEN_CREATE".to_string(),
+                    target_cid: None,
+                    detail: serde_json::json!({ "id": id }),
+                    created_at,
+                    prev_hash: hash_hex(&prev),
+                    entry_hash: String::new(),
+                };
+                prev = row.compute_hash(&prev);
+                row.entry_hash = hash_hex(&prev);
+                row
+            })
+            .collect()
+    }
+
+    fn anchored(rows: &[chain::ChainedAuditRow]) -> (AuditAnchor, AnchorRemark) {
+        let root = chain::range_merkle_root(rows).unwrap();
+        let last_hash = parse_hash_hex(rows[rows.len() - 1].entry_hash.as_str()).unwrap();
+        let anchor = AuditAnchor {
+            anchor_id: 7,
+            province_code: "AH".to_string(),
+            first_id: 1,
+            last_id: 3,
+            last_entry_hash: hash_hex(&last_hash),
+            merkle_root: hash_hex(&root),
+            entry_count: 3,
+            remark_hex: String::new(),
+            status: ANCHOR_STATUS_ANCHORED.to_string(),
+            block_number: Some(10),
+            block_hash: Some(hash_hex(&[9u8; 32])),
+            tx_hash: None,
+            created_at: DateTime::<Utc>::from_timestamp_micros(0).unwrap(),
+            anchored_at: None,
+        };
+        let remark = AnchorRemark {
+            province_code: "AH".to_string(),
+            first_id: 1,
+            last_id: 3,
+            last_entry_hash: last_hash,
+            merkle_root: root,
+        };
+        (anchor, remark)
+    }
+
+    #[test]
+    fn anchored_checkpoint_is_checked_against_onchain_remark() {
+        let rows = chained_rows();
+        let (anchor, remark) = anchored(&rows);
+        let anchors = vec![anchor];
+
+        let onchain = OnchainAnchors::from([(7, Ok(remark.clone()))]);
+        let report = verify_province("AH", &rows, None, &anchors, &onchain, Utc::now());
+        assert!(report.is_intact());
+        assert_eq!(report.anchors_anchored, 1);
+
+        // 链上读不到(块缺失或未 finalized)时不能信库内锚点行。
+        let missing = OnchainAnchors::from([(7, Err("block #10 is not finalized".to_string()))]);
+        let report = verify_province("AH", &rows, None, &anchors, &missing, Utc::now());
+        assert_eq!(report.anchor_mismatches, vec![7]);
+        assert_eq!(report.anchor_problems.len(), 1);
+        let report = verify_province(
+            "AH",
+            &rows,
+            None,
+            &anchors,
+            &OnchainAnchors::new(),
+            Utc::now(),
+        );
+        assert_eq!(report.anchor_mismatches, vec![7]);
+
+        // 库内锚点行与行一起被改写,只有链上 remark 能发现。
+        let rewritten = OnchainAnchors::from([(
+            7,
+            Ok(AnchorRemark {
+                merkle_root: [0u8; 32],
+                ..remark.clone()
+            }),
+        )]);
+        let report = verify_province("AH", &rows, None, &anchors, &rewritten, Utc::now());
+        assert_eq!(report.anchor_mismatches, vec![7]);
+
+        let other_range = OnchainAnchors::from([(
+            7,
+            Ok(AnchorRemark {
+                first_id: 2,
+                ..remark
+            }),
+        )]);
+        let report = verify_province("AH", &rows, None, &anchors, &other_range, Utc::now());
+        assert_eq!(report.anchor_mismatches, vec![7]);
+    }
+    #[test]
+    fn rows_past_cutoff_must_be_covered_by_onchain_anchors() {
+        let rows = chained_rows();
+        let (anchor, _) = anchored(&rows);
+        let now = Utc::now();
+        let with_range = |status: &str, first_id, last_id| AuditAnchor {
+            status: status.to_string(),
+            first_id,
+            last_id,
+            ..anchor.clone()
+        };
+
+        assert_eq!(first_unanchored_row(&rows, &[&anchor], now), None);
+        // 库内锚点行被整体删除:旧行全部失去链上覆盖。
+        assert_eq!(first_unanchored_row(&rows, &[], now), Some(1));
+        // 宽限期内的新行尚未锚定不算缺失。
+        assert_eq!(first_unanchored_row(&rows, &[], rows[0].created_at), None);
+        // 只有 PENDING 锚点不算覆盖。
+        let pending = with_range(ANCHOR_STATUS_PENDING, 1, 3);
+        assert_eq!(first_unanchored_row(&rows, &[&pending], now), Some(1));
+        // 中间锚点被删留下的缺口。
+        let head = with_range(ANCHOR_STATUS_ANCHORED, 1, 1);
+        let tail = with_range(ANCHOR_STATUS_ANCHORED, 3, 3);
+        assert_eq!(first_unanchored_row(&rows, &[&tail, &head], now), Some(2));
+
+        let onchain = OnchainAnchors::from([(7, Err("anchor row deleted".to_string()))]);
+        let report = verify_province("AH", &rows, None, &[], &onchain, now);
+        assert_eq!(report.first_unanchored_id, Some(1));
+        assert!(!report.is_intact());
+    }
+
+    #[test]
+    fn anchor_signer_is_decoded_from_account_id_address() {
+        let account = sp_runtime::AccountId32::new([7u8; 32]);
+        let address = citizenchain::Address::Id(account).encode();
+        assert_eq!(
+            signer_account_id(Some(&address)),
+            Some(format!("0x{}", hex::encode([7u8; 32])))
+        );
+        let raw = citizenchain::Address::Address32([7u8; 32]).encode();
+        assert_eq!(signer_account_id(Some(&raw)), None);
+        assert_eq!(signer_account_id(Some(&address[..32])), None);
+        assert_eq!(signer_account_id(None), None);
+    }
+
+    #[test]
+    fn province_registry_identity_uses_genesis_federal_registry() {
+        let identity = province_registry_identity("AH").unwrap();
+        assert_eq!(identity.cid_number, "ZS001-FRG07-249474503-2026");
+        assert_eq!(identity.frg_province_code, Some(*b"AH"));
+        assert!(province_registry_identity("ah").is_err());
+        assert!(province_registry_identity("ZZ").is_err());
+    }
+}
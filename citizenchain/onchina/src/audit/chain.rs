@@ -0,0 +1,446 @@
+//! 审计日志哈希链。
+//!
+//! 每个省分区是一条独立的链:`entry_hash = blake2_256(SCALE(域标签, prev_hash, 行字段))`,
+//! 首行的 `prev_hash` 为全零。链头(最后一行的 id 与 hash)另存 `audit_chain_heads`,
+//! 追加时 `SELECT ... FOR UPDATE` 串行化同省写入,保证 id 顺序即链顺序。
+//!
+//! 数据库管理员删改任一行都会让其后所有行的 `prev_hash`/`entry_hash` 对不上;
+//! 整段截尾则由链头与已上链锚点(`anchor.rs`)发现。
+
+use chrono::{DateTime, Utc};
+use codec::Encode;
+use postgres::Client;
+use serde::{Deserialize, Serialize};
+use sp_core::hashing::blake2_256;
+
+/// 哈希输入域标签;字段布局变化必须换版本号,旧行按旧版本校验。
+const AUDIT_ENTRY_DOMAIN: &[u8] = b"GMB_ONCHINA_AUDIT_V1";
+/// Merkle 叶/内部节点前缀,防止叶子与内部节点互相伪造。
+const MERKLE_LEAF_PREFIX: u8 = 0x00;
+const MERKLE_NODE_PREFIX: u8 = 0x01;
+
+/// 每条链的起点。
+pub(crate) const GENESIS_PREV_HASH: [u8; 32] = [0u8; 32];
+
+/// 参与哈希的行字段。`id` 不入哈希:链顺序由 `prev_hash` 决定,id 只是定位键。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(crate) struct ChainedAuditRow {
+    pub(crate) id: i64,
+    pub(crate) province_code: String,
+    pub(crate) city_code: Option<String>,
+    pub(crate) actor_account_id: Option<String>,
+    pub(crate) action: String,
+    pub(crate) target_cid: Option<String>,
+    pub(crate) detail: serde_json::Value,
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) prev_hash: String,
+    pub(crate) entry_hash: String,
+}
+
+impl ChainedAuditRow {
+    /// 按本行字段与给定 prev_hash 重算 entry_hash。
+    pub(crate) fn compute_hash(&self, prev_hash: &[u8; 32]) -> [u8; 32] {
+        entry_hash(
+            prev_hash,
+            &self.province_code,
+            self.city_code.as_deref(),
+            self.actor_account_id.as_deref(),
+            &self.action,
+            self.target_cid.as_deref(),
+            &self.detail,
+            &self.created_at,
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn entry_hash(
+    prev_hash: &[u8; 32],
+    province_code: &str,
+    city_code: Option<&str>,
+    actor_account_id: Option<&str>,
+    action: &str,
+    target_cid: Option<&str>,
+    detail: &serde_json::Value,
+    created_at: &DateTime<Utc>,
+) -> [u8; 32] {
+    let encoded = (
+        AUDIT_ENTRY_DOMAIN,
+        prev_hash,
+        province_code,
+        city_code,
+        actor_account_id,
+        action,
+        target_cid,
+        canonical_json(detail),
+        created_at.timestamp_micros(),
+    )
+        .encode();
+    blake2_256(&encoded)
+}
+
+/// JSONB 入库会重排键序,哈希只能基于与存储无关的规范化文本:对象键递归按字典序,
+/// 数组保序,标量沿用 serde_json 输出。
+pub(crate) fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let body = keys
+                .into_iter()
+                .map(|k| {
+                    format!(
+                        "{}:{}",
+                        serde_json::Value::String(k.clone()),
+                        canonical_json(&map[k])
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("{{{body}}}")
+        }
+        serde_json::Value::Array(items) => {
+            let body = items
+                .iter()
+                .map(canonical_json)
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("[{body}]")
+        }
+        other => other.to_string(),
+    }
+}
+
+/// 二叉 Merkle 根;奇数节点原样上提。空集合返回全零。
+pub(crate) fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+    let mut level: Vec<[u8; 32]> = leaves
+        .iter()
+        .map(|leaf| {
+            let mut buf = Vec::with_capacity(33);
+            buf.push(MERKLE_LEAF_PREFIX);
+            buf.extend_from_slice(leaf);
+            blake2_256(&buf)
+        })
+        .collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut buf = Vec::with_capacity(65);
+                    buf.push(MERKLE_NODE_PREFIX);
+                    buf.extend_from_slice(left);
+                    buf.extend_from_slice(right);
+                    blake2_256(&buf)
+                }
+                [single] => *single,
+                _ => unreachable!("chunks(2) yields one or two items"),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+pub(crate) fn hash_hex(hash: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(hash))
+}
+
+pub(crate) fn parse_hash_hex(raw: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(raw.trim().strip_prefix("0x").unwrap_or(raw.trim()))
+        .map_err(|e| format!("audit hash hex invalid: {e}"))?;
+    <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| "audit hash must be 32 bytes".to_string())
+}
+
+/// 追加一行并推进本省链头。必须在同一事务内完成,链头行锁串行化并发写入。
+pub(crate) fn append_chained_conn(
+    conn: &mut Client,
+    province_code: &str,
+    city_code: Option<&str>,
+    actor_account_id: Option<&str>,
+    action: &str,
+    target_cid: Option<&str>,
+    detail: &serde_json::Value,
+) -> Result<i64, String> {
+    // TIMESTAMPTZ 只保留到微秒;先截断再入哈希,读回重算才能一致。
+    let now = Utc::now();
+    let created_at = DateTime::<Utc>::from_timestamp_micros(now.timestamp_micros())
+        .ok_or_else(|| "audit timestamp out of range".to_string())?;
+    let mut tx = conn
+        .transaction()
+        .map_err(|e| format!("begin audit tx failed: {e}"))?;
+    tx.execute(
+        "INSERT INTO audit_chain_heads(province_code, last_id, last_entry_hash)
+         VALUES ($1, 0, $2)
+         ON CONFLICT (province_code) DO NOTHING",
+        &[&province_code, &hash_hex(&GENESIS_PREV_HASH)],
+    )
+    .map_err(|e| format!("init audit chain head failed: {e}"))?;
+    let head = tx
+        .query_one(
+            "SELECT last_entry_hash FROM audit_chain_heads
+             WHERE province_code = $1
+             FOR UPDATE",
+            &[&province_code],
+        )
+        .map_err(|e| format!("lock audit chain head failed: {e}"))?;
+    let prev_hash = parse_hash_hex(head.get::<_, String>(0).as_str())?;
+    let hash = entry_hash(
+        &prev_hash,
+        province_code,
+        city_code,
+        actor_account_id,
+        action,
+        target_cid,
+        detail,
+        &created_at,
+    );
+    let row = tx
+        .query_one(
+            "INSERT INTO audit(
+                province_code, city_code, actor_account_id, action, target_cid, detail,
+                created_at, prev_hash, entry_hash
+             )
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             RETURNING id",
+            &[
+                &province_code,
+                &city_code,
+                &actor_account_id,
+                &action,
+                &target_cid,
+                detail,
+                &created_at,
+                &hash_hex(&prev_hash),
+                &hash_hex(&hash),
+            ],
+        )
+        .map_err(|e| format!("insert audit failed: {e}"))?;
+    let id: i64 = row.get(0);
+    tx.execute(
+        "UPDATE audit_chain_heads SET last_id = $2, last_entry_hash = $3
+         WHERE province_code = $1",
+        &[&province_code, &id, &hash_hex(&hash)],
+    )
+    .map_err(|e| format!("advance audit chain head failed: {e}"))?;
+    tx.commit()
+        .map_err(|e| format!("commit audit tx failed: {e}"))?;
+    Ok(id)
+}
+
+/// 按 id 升序读出本省链上 `(after_id, up_to_id]` 区间。
+pub(crate) fn load_rows_conn(
+    conn: &mut Client,
+    province_code: &str,
+    after_id: i64,
+    up_to_id: Option<i64>,
+) -> Result<Vec<ChainedAuditRow>, String> {
+    let rows = conn
+        .query(
+            "SELECT id, province_code, city_code, actor_account_id, action, target_cid, detail,
+                    created_at, prev_hash, entry_hash
+             FROM audit
+             WHERE province_code = $1
+               AND id > $2
+               AND ($3::bigint IS NULL OR id <= $3)
+             ORDER BY id ASC",
+            &[&province_code, &after_id, &up_to_id],
+        )
+        .map_err(|e| format!("query audit chain failed: {e}"))?;
+    Ok(rows
+        .iter()
+        .map(|row| ChainedAuditRow {
+            id: row.get(0),
+            province_code: row.get(1),
+            city_code: row.get(2),
+            actor_account_id: row.get(3),
+            action: row.get(4),
+            target_cid: row.get(5),
+            detail: row.get(6),
+            created_at: row.get(7),
+            prev_hash: row.get(8),
+            entry_hash: row.get(9),
+        })
+        .collect())
+}
+
+/// 链头快照;导出文件与在线校验都以它判断尾部是否被截断。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(crate) struct AuditChainHead {
+    pub(crate) province_code: String,
+    pub(crate) last_id: i64,
+    pub(crate) last_entry_hash: String,
+}
+
+pub(crate) fn load_head_conn(
+    conn: &mut Client,
+    province_code: &str,
+) -> Result<Option<AuditChainHead>, String> {
+    let row = conn
+        .query_opt(
+            "SELECT province_code, last_id, last_entry_hash FROM audit_chain_heads
+             WHERE province_code = $1",
+            &[&province_code],
+        )
+        .map_err(|e| format!("query audit chain head failed: {e}"))?;
+    Ok(row.map(|row| AuditChainHead {
+        province_code: row.get(0),
+        last_id: row.get(1),
+        last_entry_hash: row.get(2),
+    }))
+}
+
+/// 链完整性校验结论。`first_broken_id` 指向第一条重算不一致或断链的行。
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub(crate) struct ChainVerifyReport {
+    pub(crate) entries_checked: u64,
+    pub(crate) last_id: i64,
+    pub(crate) last_entry_hash: String,
+    pub(crate) first_broken_id: Option<i64>,
+    pub(crate) problem: Option<String>,
+}
+
+impl ChainVerifyReport {
+    pub(crate) fn is_intact(&self) -> bool {
+        self.first_broken_id.is_none() && self.problem.is_none()
+    }
+}
+
+/// 从创世逐行重算;不依赖库内存的 entry_hash,只用它与重算值比对。
+pub(crate) fn verify_rows(
+    rows: &[ChainedAuditRow],
+    head: Option<&AuditChainHead>,
+) -> ChainVerifyReport {
+    let mut report = ChainVerifyReport {
+        last_entry_hash: hash_hex(&GENESIS_PREV_HASH),
+        ..ChainVerifyReport::default()
+    };
+    let mut prev = GENESIS_PREV_HASH;
+    for row in rows {
+        report.entries_checked += 1;
+        if row.prev_hash != hash_hex(&prev) {
+            report.first_broken_id = Some(row.id);
+            report.problem = Some("prev_hash does not link to the previous entry".to_string());
+            return report;
+        }
+        let recomputed = row.compute_hash(&prev);
+        if row.entry_hash != hash_hex(&recomputed) {
+            report.first_broken_id = Some(row.id);
+            report.problem = Some("entry_hash does not match row contents".to_string());
+            return report;
+        }
+        prev = recomputed;
+        report.last_id = row.id;
+        report.last_entry_hash = hash_hex(&recomputed);
+    }
+    if let Some(head) = head {
+        if head.last_id != report.last_id || head.last_entry_hash != report.last_entry_hash {
+            report.problem = Some("chain head does not match the last entry".to_string());
+        }
+    }
+    report
+}
+
+/// 取本省 `(after_id, up_to_id]` 区间的 Merkle 根,叶子为各行 entry_hash。
+pub(crate) fn range_merkle_root(rows: &[ChainedAuditRow]) -> Result<[u8; 32], String> {
+    let leaves = rows
+        .iter()
+        .map(|row| parse_hash_hex(row.entry_hash.as_str()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(merkle_root(&leaves))
+}
+
+#[cfg(test)]
+// 测试需要在前置条件失效时立即失败，断言式解包仅限本测试模块。
+#[allow(clippy::expect_used, clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn chain(n: usize) -> Vec<ChainedAuditRow> {
+        let mut prev = GENESIS_PREV_HASH;
+        let mut rows = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut row = ChainedAuditRow {
+                id: i as i64 + 1,
+                province_code: "AH".to_string(),
+                city_code: Some("001".to_string()),
+                actor_account_id: None,
+                action: "INSTITUTION_UPDATE".to_string(),
+                target_cid: Some(format!("CID-{i}")),
+                detail: serde_json::json!({ "b": i, "a": [true, null] }),
+                created_at: DateTime::<Utc>::from_timestamp_micros(
+                    1_700_000_000_000_000 + i as i64,
+                )
+                .unwrap(),
+                prev_hash: hash_hex(&prev),
+                entry_hash: String::new(),
+            };
+            let hash = row.compute_hash(&prev);
+            row.entry_hash = hash_hex(&hash);
+            prev = hash;
+            rows.push(row);
+        }
+        rows
+    }
+
+    #[test]
+    fn canonical_json_sorts_keys_recursively() {
+        let a = serde_json::json!({ "z": 1, "a": { "y": "x", "b": [1, 2] } });
+        assert_eq!(canonical_json(&a), r#"{"a":{"b":[1,2],"y":"x"},"z":1}"#);
+    }
+
+    #[test]
+    fn intact_chain_verifies_and_matches_head() {
+        let rows = chain(5);
+        let head = AuditChainHead {
+            province_code: "AH".to_string(),
+            last_id: 5,
+            last_entry_hash: rows[4].entry_hash.clone(),
+        };
+        let report = verify_rows(&rows, Some(&head));
+        assert!(report.is_intact(), "{report:?}");
+        assert_eq!(report.entries_checked, 5);
+    }
+
+    #[test]
+    fn edited_detail_is_detected_at_that_row() {
+        let mut rows = chain(5);
+        rows[2].detail = serde_json::json!({ "b": 99 });
+        let report = verify_rows(&rows, None);
+        assert_eq!(report.first_broken_id, Some(3));
+    }
+
+    #[test]
+    fn deleted_row_breaks_the_next_link() {
+        let mut rows = chain(5);
+        rows.remove(1);
+        let report = verify_rows(&rows, None);
+        assert_eq!(report.first_broken_id, Some(3));
+    }
+
+    #[test]
+    fn truncated_tail_is_caught_by_head() {
+        let rows = chain(5);
+        let head = AuditChainHead {
+            province_code: "AH".to_string(),
+            last_id: 5,
+            last_entry_hash: rows[4].entry_hash.clone(),
+        };
+        let report = verify_rows(&rows[..3], Some(&head));
+        assert!(!report.is_intact());
+        assert_eq!(report.first_broken_id, None);
+    }
+
+    #[test]
+    fn merkle_root_depends_on_every_leaf_and_order() {
+        let rows = chain(3);
+        let root = range_merkle_root(&rows).unwrap();
+        let mut swapped = rows.clone();
+        swapped.swap(0, 1);
+        assert_ne!(root, range_merkle_root(&swapped).unwrap());
+        assert_ne!(root, range_merkle_root(&rows[..2]).unwrap());
+        assert_eq!(merkle_root(&[]), [0u8; 32]);
+    }
+}
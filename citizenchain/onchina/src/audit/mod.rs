@@ -1,7 +1,19 @@
 //! 审计日志 list handler(注册局管理员只读)
 //!
 //! 审计日志是独立后台能力,不属于权限范围规则本身,因此从
-//! `scope` 目录迁到后端根层 `audit.rs`。
+//! `scope` 目录迁到后端根层 `audit/`。
+//! - `chain`:按省哈希链追加与逐行重算校验(防篡改)。
+//! - `anchor`:周期检查点 + `System.remark_with_event` 冷签上链锚定。
+//! - `export`:JSONL 离线导出与校验。
+
+pub(crate) mod anchor;
+pub(crate) mod chain;
+pub(crate) mod export;
+
+pub(crate) use anchor::{
+    admin_confirm_audit_anchor, admin_list_audit_anchors, admin_prepare_audit_anchor,
+    admin_verify_audit_chain,
+};
 
 use axum::{
     extract::{Query, State},
@@ -28,6 +40,11 @@ pub(crate) struct AuditLogEntry {
     /// 结构化事实字段(JSON 对象,键小写蛇形,值为系统原值);人话翻译归前端渲染器。
     pub(crate) detail: serde_json::Value,
     pub(crate) created_at: DateTime<Utc>,
+    /// 哈希链字段(0x hex),前端可据此展示/抽查链接关系。
+    #[serde(default)]
+    pub(crate) prev_hash: String,
+    #[serde(default)]
+    pub(crate) entry_hash: String,
 }
 
 #[derive(Deserialize)]
@@ -81,7 +98,7 @@ pub(crate) async fn admin_list_audit_logs(
         let limit_i64 = i64::try_from(limit).map_err(|_| "limit too large".to_string())?;
         let rows = conn
             .query(
-                "SELECT id, action, actor_account_id, target_cid, detail, created_at
+                "SELECT id, action, actor_account_id, target_cid, detail, created_at, prev_hash, entry_hash
                  FROM audit
                  WHERE ($1::text IS NULL OR province_code = $1)
                    AND ($2::text IS NULL OR city_code = $2)
@@ -121,6 +138,8 @@ pub(crate) async fn admin_list_audit_logs(
                 result: "SUCCESS".to_string(),
                 detail: row.get(4),
                 created_at: row.get(5),
+                prev_hash: row.get(6),
+                entry_hash: row.get(7),
             });
         }
         Ok(output)
@@ -196,7 +196,7 @@ async fn ensure_account_id_on_chain_admin(
 }
 
 /// 校验扫码签名 signer ∈ 本机构链上 Active 管理员集合(冷签 step-up,与登录同源)。
-async fn ensure_signer_on_chain_admin(
+pub(crate) async fn ensure_signer_on_chain_admin(
     db: &Db,
     actor_cid_number: &str,
     account_id: &str,
@@ -591,6 +591,7 @@ fn preview_action_conn(
                 "given_name": given_name,
                 "city_name": city,
                 "creator_account_id": creator_account_id,
+                "read_only": input.read_only,
             });
             let after_hash = hash_json(&after);
             Ok(ActionPreview {
@@ -650,10 +651,7 @@ fn precheck_institution_target_scope_conn(
         return Err("http:not_found:institution not found".to_string());
     };
     let scope = crate::scope::rules::get_visible_scope(ctx);
-    if !scope.includes_province(&inst.province_name)
-        || !scope.includes_city(&inst.city_name)
-        || !scope.includes_town(&inst.town_name)
-    {
+    if !scope.includes_institution(&inst) {
         return Err("http:forbidden:institution out of current admin scope".to_string());
     }
     Ok(())
@@ -668,6 +666,10 @@ fn precheck_institution_create_scope(
     payload: &serde_json::Value,
 ) -> Result<(), String> {
     let scope = crate::scope::rules::get_visible_scope(ctx);
+    // 自机构档只管本机构,不得在本市新建其他机构。
+    if scope.is_own_institution() {
+        return Err("http:forbidden:own-institution admin cannot create institutions".to_string());
+    }
     check_locked_field(
         scope.locked_province_name.as_deref(),
         payload.get("province_name").and_then(|v| v.as_str()),
@@ -927,6 +929,7 @@ fn apply_create_city_registry_conn(
         created_at: now,
         updated_at: Some(now),
         city_name: city,
+        read_only: input.read_only,
     };
     repo::upsert_admin_conn(conn, &row)?;
     serde_json::to_value(city_registry_row_from_user_conn(conn, &row)?)
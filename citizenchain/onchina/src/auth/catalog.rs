@@ -148,6 +148,7 @@ pub(crate) async fn list_federal_registry_admins(
                             created_at: now,
                             updated_at: None,
                             city_name: String::new(),
+                            read_only: false,
                         };
                         repo::upsert_admin_conn(conn, &row)?;
                         repo::get_admin_by_account_id_conn(conn, account_id)?
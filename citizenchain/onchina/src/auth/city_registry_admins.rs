@@ -165,6 +165,7 @@ pub(crate) fn city_registry_row_from_user_conn(
         creator_given_name,
         created_at: city_registry.created_at,
         city_name: city_registry.city_name.clone(),
+        read_only: city_registry.read_only,
     })
 }
 
@@ -123,6 +123,7 @@ pub(super) fn admin_auth(
             scope_city_name,
             scope_town_name,
             cid_short_name,
+            read_only: admin.read_only,
         })
     });
 
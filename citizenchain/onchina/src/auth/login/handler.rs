@@ -63,6 +63,7 @@ pub(crate) async fn admin_auth_check(
             scope_city_name: ctx.scope_city_name,
             scope_town_name: ctx.scope_town_name,
             cid_short_name: ctx.cid_short_name,
+            read_only: ctx.read_only,
         },
     })
     .into_response()
@@ -21,7 +21,9 @@ pub(crate) use model::{
     AdminAuthContext, AdminInstitutionCandidate, AdminSession, LoginSignRequest,
     NodeBindingChallenge, NodeInstitutionBinding, QrLoginResultRecord,
 };
-pub(crate) use onchain_gate::revoke_stale_admin_sessions_loop;
+pub(crate) use onchain_gate::{
+    revoke_expired_assignment_sessions, revoke_stale_admin_sessions_loop,
+};
 pub(crate) use qr_login::{
     admin_auth_qr_complete, admin_auth_qr_result, admin_auth_qr_sign_request,
 };
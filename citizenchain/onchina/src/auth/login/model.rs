@@ -100,6 +100,8 @@ pub(crate) struct AdminAuthContext {
     pub(crate) scope_town_name: Option<String>,
     /// 当前管理员所属机构简称,字段名与 subjects.cid_short_name 保持唯一命名。
     pub(crate) cid_short_name: Option<String>,
+    /// 只读管理员,由 `admins.read_only` 派生;`get_visible_scope` 据此关闭写权限。
+    pub(crate) read_only: bool,
 }
 
 #[derive(Serialize)]
@@ -119,6 +121,8 @@ pub(crate) struct AdminAuthOutput {
     pub(crate) scope_city_name: Option<String>,
     pub(crate) scope_town_name: Option<String>,
     pub(crate) cid_short_name: Option<String>,
+    /// 只读管理员,前端据此置灰全部写入口。
+    pub(crate) read_only: bool,
 }
 
 #[derive(Serialize)]
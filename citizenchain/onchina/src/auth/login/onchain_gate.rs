@@ -383,6 +383,7 @@ async fn issue_session_for_candidate(
                     created_at: now,
                     updated_at: Some(now),
                     city_name: scope_city_name.clone().unwrap_or_default(),
+                    read_only: false,
                 },
             };
 
@@ -503,3 +504,57 @@ async fn revoke_stale_admin_sessions_once(db: &Db) -> Result<(), String> {
         Ok(())
     })
 }
+
+/// 岗位任职任期届满后即时清退该管理员的会话,迫使其按链上现状重新登录取权限。
+///
+/// 只处理本节点绑定机构;事件里的账户是岗位名册锚点,需同时清退其 CID 当前绑定的签名账户。
+/// 链不可达时返回错误,由调用方告警,后台周期复查仍会兜底移除已不在链上的管理员。
+pub(crate) async fn revoke_expired_assignment_sessions(
+    db: &Db,
+    cid_number: &str,
+    role_assignment_account_id: [u8; 32],
+) -> Result<(), String> {
+    let Some(binding) = repo::active_node_binding(db)? else {
+        return Ok(());
+    };
+    if binding.institution_cid_number != cid_number {
+        return Ok(());
+    }
+    let identity = chain_runtime::identity_from_binding_parts(
+        &binding.institution_code,
+        Some(binding.institution_cid_number.as_str()),
+        binding.frg_province_code.as_deref(),
+    )?;
+    let mut account_ids = vec![format!("0x{}", hex::encode(role_assignment_account_id))];
+    if let Some(onchain_admins) = chain_runtime::fetch_active_admins_onchain(&identity).await? {
+        account_ids.extend(
+            onchain_admins
+                .into_iter()
+                .filter(|admin| admin.role_assignment_account_id == role_assignment_account_id)
+                .map(|admin| admin.account_id),
+        );
+    }
+    let institution_code = binding.institution_code.clone();
+    db.with_client(move |conn| {
+        let session_account_ids =
+            repo::list_session_admin_account_ids_conn(conn, &institution_code)?;
+        for session_account_id in session_account_ids {
+            if !account_ids
+                .iter()
+                .any(|account_id| same_account_id(account_id, session_account_id.as_str()))
+            {
+                continue;
+            }
+            let removed =
+                repo::delete_admin_sessions_for_account_id_conn(conn, session_account_id.as_str())?;
+            if removed > 0 {
+                tracing::info!(
+                    account_id = %session_account_id,
+                    sessions = removed,
+                    "revoked sessions for admin whose role assignment expired"
+                );
+            }
+        }
+        Ok(())
+    })
+}
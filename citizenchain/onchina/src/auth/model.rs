@@ -23,6 +23,9 @@ pub(crate) struct AdminUser {
     /// 市级机构所属的市名称(市级机构必填,其它机构为空字符串)。
     #[serde(default)]
     pub(crate) city_name: String,
+    /// 只读管理员:可见范围照常派生,但一切写操作被 `VisibleScope::can_write` 拒绝。
+    #[serde(default)]
+    pub(crate) read_only: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -40,6 +43,7 @@ pub(crate) struct CityRegistryAdminRow {
     pub(crate) creator_given_name: String,
     pub(crate) created_at: DateTime<Utc>,
     pub(crate) city_name: String,
+    pub(crate) read_only: bool,
 }
 
 #[derive(Serialize)]
@@ -113,6 +117,9 @@ pub(crate) struct CreateCityRegistryAdminInput {
     /// 不指定则默认为调用者自身。
     #[serde(default)]
     pub(crate) creator_account_id: Option<String>,
+    /// 可选：创建为只读管理员(只可查看本市数据,不可办理)。缺省为可写。
+    #[serde(default)]
+    pub(crate) read_only: bool,
 }
 
 #[derive(Deserialize)]
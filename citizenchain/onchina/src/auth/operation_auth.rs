@@ -194,6 +194,14 @@ pub(crate) fn ensure_action_role_allowed(
             "admin province scope missing",
         ));
     }
+    // 所有写动作都经此闸;只读管理员的 scope 统一 can_write = false,在签发 grant 前拒绝。
+    if !crate::scope::get_visible_scope(ctx).can_write {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            1003,
+            "read-only admin cannot perform write actions",
+        ));
+    }
     if action_type.requires_governing_capability()
         && !crate::core::chain_runtime::is_tier1_registry(&ctx.institution_code)
     {
@@ -248,6 +256,32 @@ mod tests {
         assert!(!AdminActionType::NodeBindingUnbind.requires_governing_capability());
     }
 
+    fn admin_ctx(read_only: bool) -> AdminAuthContext {
+        AdminAuthContext {
+            account_id: "0x1111111111111111111111111111111111111111111111111111111111111111"
+                .to_string(),
+            institution_cid_number: "AH001-SFLP0-000000123-2026".to_string(),
+            institution_code: "SFLP".to_string(),
+            admin_level: None,
+            family_name: "管理".to_string(),
+            given_name: "员".to_string(),
+            scope_province_name: Some("安徽省".to_string()),
+            scope_city_name: Some("合肥市".to_string()),
+            scope_town_name: None,
+            cid_short_name: None,
+            read_only,
+        }
+    }
+
+    #[test]
+    fn read_only_admin_is_rejected_before_any_write_grant() {
+        let action = AdminActionType::InstitutionUpdate;
+        assert!(ensure_action_role_allowed(&admin_ctx(false), &action).is_ok());
+        let denied = ensure_action_role_allowed(&admin_ctx(true), &action)
+            .expect_err("read-only admin must not obtain write grants");
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+    }
+
     #[test]
     fn citizen_onchain_push_uses_one_passkey_and_round_trips() {
         // 最终链签已是管理员唯一钱包签名，操作创建阶段只额外消费一次 Passkey。
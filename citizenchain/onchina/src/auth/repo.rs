@@ -29,6 +29,7 @@ fn admin_from_row(row: &postgres::Row) -> Result<AdminUser, String> {
         created_at: row.get(7),
         updated_at: row.get(8),
         city_name: row.get(9),
+        read_only: row.get(10),
     })
 }
 
@@ -56,7 +57,7 @@ pub(crate) fn get_admin_by_id_and_registry_org_conn(
     let id = id as i64;
     let row = conn
         .query_opt(
-            "SELECT admin_id, account_id, family_name, given_name, institution_code, built_in, creator_account_id, created_at, updated_at, city_name
+            "SELECT admin_id, account_id, family_name, given_name, institution_code, built_in, creator_account_id, created_at, updated_at, city_name, read_only
              FROM admins
              WHERE admin_id = $1 AND institution_code = $2",
             &[&id, &institution_code],
@@ -87,7 +88,7 @@ pub(crate) fn list_city_registry_admins_by_scope_conn(
             .map_err(|e| format!("count city registry admins by city failed: {e}"))?;
         let rows = conn
             .query(
-                "SELECT admin_id, account_id, family_name, given_name, institution_code, built_in, creator_account_id, created_at, updated_at, city_name
+                "SELECT admin_id, account_id, family_name, given_name, institution_code, built_in, creator_account_id, created_at, updated_at, city_name, read_only
                  FROM admins
                  WHERE institution_code = $1 AND city_name = $2
                  ORDER BY admin_id DESC
@@ -105,7 +106,7 @@ pub(crate) fn list_city_registry_admins_by_scope_conn(
             .map_err(|e| format!("count city registry admins failed: {e}"))?;
         let rows = conn
             .query(
-                "SELECT admin_id, account_id, family_name, given_name, institution_code, built_in, creator_account_id, created_at, updated_at, city_name
+                "SELECT admin_id, account_id, family_name, given_name, institution_code, built_in, creator_account_id, created_at, updated_at, city_name, read_only
                  FROM admins
                  WHERE institution_code = $1
                  ORDER BY admin_id DESC
@@ -153,7 +154,7 @@ pub(crate) fn get_admin_by_account_id_conn(
 ) -> Result<Option<AdminUser>, String> {
     let row = conn
         .query_opt(
-            "SELECT admin_id, account_id, family_name, given_name, institution_code, built_in, creator_account_id, created_at, updated_at, city_name
+            "SELECT admin_id, account_id, family_name, given_name, institution_code, built_in, creator_account_id, created_at, updated_at, city_name, read_only
              FROM admins
              WHERE account_id = $1",
             &[&account_id],
@@ -626,8 +627,8 @@ pub(crate) fn next_admin_id_conn(conn: &mut Client) -> Result<u64, String> {
 /// 本函数只维护 `admins` 登录元数据缓存本身。
 pub(crate) fn upsert_admin_conn(conn: &mut Client, admin: &AdminUser) -> Result<(), String> {
     conn.execute(
-        "INSERT INTO admins(admin_id, account_id, family_name, given_name, institution_code, built_in, creator_account_id, created_at, updated_at, city_name)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "INSERT INTO admins(admin_id, account_id, family_name, given_name, institution_code, built_in, creator_account_id, created_at, updated_at, city_name, read_only)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
          ON CONFLICT (account_id) DO UPDATE SET
             family_name = EXCLUDED.family_name,
             given_name = EXCLUDED.given_name,
@@ -635,7 +636,8 @@ pub(crate) fn upsert_admin_conn(conn: &mut Client, admin: &AdminUser) -> Result<
             built_in = EXCLUDED.built_in,
             creator_account_id = EXCLUDED.creator_account_id,
             updated_at = EXCLUDED.updated_at,
-            city_name = EXCLUDED.city_name",
+            city_name = EXCLUDED.city_name,
+            read_only = EXCLUDED.read_only",
         &[
             &(admin.id as i64),
             &admin.account_id,
@@ -647,6 +649,7 @@ pub(crate) fn upsert_admin_conn(conn: &mut Client, admin: &AdminUser) -> Result<
             &admin.created_at,
             &admin.updated_at,
             &admin.city_name,
+            &admin.read_only,
         ],
     )
     .map_err(|e| format!("upsert admin failed: {e}"))?;
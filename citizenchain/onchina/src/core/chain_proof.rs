@@ -0,0 +1,1582 @@
+//! 链上读取的 GRANDPA 轻客户端校验（`ONCHINA_CHAIN_PROOF_VERIFY` 开启时生效）。
+//!
+//! 默认情况下 OnChina 直接信任节点 RPC 返回的 finalized head 与存储值；开启后改为：
+//! - 信任锚：启动时校验过的创世哈希 + 源码内镜像的创世 GRANDPA 权威集（set_id = 0）；
+//! - 区块头逐块按 parent hash 链接，跟随 `ScheduledChange` 换届，遇到 `ForcedChange` 拒绝继续；
+//! - 只有被当前权威集 2/3 以上签名的 GRANDPA justification 覆盖的区块才算可信 finalized；
+//! - 存储值一律改走 `state_getReadProof`，在可信区块头的 `state_root` 上重放 trie 证明。
+//!
+//! - `state_getKeysPaged` 枚举结果在同一可信 `state_root` 上用读取证明重放前缀子树，
+//!   RPC 少报或多报 key 都会因证明缺节点或结果不符而拒绝；其余无法证明的枚举与运行时调用
+//!   （`state_getKeys`、`state_getPairs`、子树存储、非 metadata 的 `state_call` 等）一律拒绝；
+//! - runtime metadata 不取 RPC 返回值：先证明可信区块上的 `:code` 与钉扎哈希一致，
+//!   再由编译进本程序的 runtime 直接生成。
+//!
+//! 可信检查点与 GRANDPA 权威集落盘到 `ONCHINA_CHAIN_PROOF_DIR`，重启后从落盘的可信头继续
+//! 同步；文件缺失、损坏或创世哈希不符时才从创世重新同步。
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::PathBuf,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use codec::{Decode, DecodeAll, Encode};
+use finality_grandpa::voter_set::VoterSet;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, value::RawValue, Value};
+use sp_consensus_grandpa::{
+    AuthorityId, AuthorityList, ConsensusLog, GrandpaJustification, GRANDPA_ENGINE_ID,
+};
+use sp_core::{OpaqueMetadata, H256};
+use sp_runtime::{
+    traits::{BlakeTwo256, Hash as _, Header as _},
+    DigestItem, StateVersion,
+};
+use sp_state_machine::{Backend as _, IterArgs};
+use subxt::backend::rpc::{RawRpcFuture, RawRpcSubscription, RpcClient, RpcClientT};
+use subxt::ext::futures::StreamExt;
+use subxt::{OnlineClient, PolkadotConfig};
+
+type Header = citizenchain::Header;
+
+/// 与节点 `GRANDPA_JUSTIFICATION_PERIOD` 一致：每跨过一个周期边界节点持久化一份 justification。
+const JUSTIFICATION_PERIOD: u32 = 64;
+/// 找最新 justification 时最多回看的周期窗口数。
+const JUSTIFICATION_PROBE_WINDOWS: u32 = 4;
+/// 单轮同步最多扫描的区块头数，避免从创世追赶时一次性持有全部区块头。
+const SYNC_SCAN_SPAN: u32 = JUSTIFICATION_PERIOD * 64;
+/// 单次 JSON-RPC 批量请求的条目上限。
+const RPC_BATCH_SIZE: u32 = 256;
+/// 两次向 RPC 追新 justification 的最小间隔。
+const MIN_SYNC_INTERVAL: Duration = Duration::from_secs(6);
+/// finalized 订阅等待 justification 覆盖新区块时的轮询间隔。
+const JUSTIFICATION_WAIT_INTERVAL: Duration = Duration::from_secs(6);
+/// 已校验区块头缓存上限，超出后整体清空重建。
+const RECENT_HEADER_LIMIT: usize = 4096;
+/// 可信头每推进这么多区块（或权威集换届时）落盘一次。
+const PERSIST_INTERVAL: u32 = JUSTIFICATION_PERIOD * 16;
+/// 轻客户端落盘文件名。
+const LIGHT_CLIENT_STATE_FILE: &str = "light-client.scale";
+
+/// 是否开启链上读取证明校验。
+pub(crate) fn proof_verification_enabled() -> bool {
+    super::http_security::env_flag_enabled("ONCHINA_CHAIN_PROOF_VERIFY")
+}
+
+/// 经 GRANDPA justification 与区块头链接证明为 finalized 的区块头摘要。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TrustedHeader {
+    pub(crate) hash: H256,
+    pub(crate) number: u32,
+    pub(crate) state_root: H256,
+}
+
+impl TrustedHeader {
+    fn of(header: &Header) -> Self {
+        Self {
+            hash: header.hash(),
+            number: header.number,
+            state_root: header.state_root,
+        }
+    }
+
+    pub(crate) fn hash_hex(&self) -> String {
+        hex_0x(self.hash.as_bytes())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AuthoritySet {
+    set_id: u64,
+    authorities: AuthorityList,
+}
+
+#[derive(Debug, Clone)]
+struct PendingChange {
+    enact_at: u32,
+    next_authorities: AuthorityList,
+}
+
+/// 轻客户端落盘状态：只保存已被 GRANDPA 证明的数据，恢复时按创世哈希与首尾检查点自检。
+#[derive(Debug, Encode, Decode)]
+struct PersistedLightClient {
+    genesis_hash: H256,
+    set_id: u64,
+    authorities: AuthorityList,
+    head_hash: H256,
+    head_number: u32,
+    head_state_root: H256,
+    pending: Option<(u32, AuthorityList)>,
+    checkpoints: Vec<(u32, H256)>,
+}
+
+/// 落盘目录：`ONCHINA_CHAIN_PROOF_DIR`（node 传 `base_path/onchina-chain-proof`）；兜底 exe 同目录 `chain-proof`。
+fn light_client_state_path() -> PathBuf {
+    std::env::var("ONCHINA_CHAIN_PROOF_DIR")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            std::env::current_exe()
+                .ok()
+                .and_then(|exe| exe.parent().map(|p| p.to_path_buf()))
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("chain-proof")
+        })
+        .join(LIGHT_CLIENT_STATE_FILE)
+}
+
+/// 创世权威集，镜像 runtime genesis：开发期只有国家储委会第 1 把 GRANDPA 密钥，权重 1。
+fn genesis_authority_set() -> AuthoritySet {
+    let key = primitives::cid::china::china_cb::CHINA_CB[0].grandpa_key;
+    AuthoritySet {
+        set_id: 0,
+        authorities: vec![(
+            AuthorityId::from(sp_core::ed25519::Public::from_raw(key)),
+            1,
+        )],
+    }
+}
+
+/// 解析区块头中的 GRANDPA 换届信号；强制换届无法由原权威集背书，直接拒绝。
+fn grandpa_change(header: &Header) -> Result<Option<PendingChange>, String> {
+    let mut change = None;
+    for log in header.digest.logs() {
+        let DigestItem::Consensus(engine_id, data) = log else {
+            continue;
+        };
+        if *engine_id != GRANDPA_ENGINE_ID {
+            continue;
+        }
+        match ConsensusLog::<u32>::decode(&mut data.as_slice()) {
+            Ok(ConsensusLog::ScheduledChange(scheduled)) => {
+                if change.is_some() {
+                    return Err(format!("区块 #{} 含多条 GRANDPA 换届信号", header.number));
+                }
+                let enact_at = header.number.checked_add(scheduled.delay).ok_or_else(|| {
+                    format!("区块 #{} 的 GRANDPA 换届生效高度溢出", header.number)
+                })?;
+                change = Some(PendingChange {
+                    enact_at,
+                    next_authorities: scheduled.next_authorities,
+                });
+            }
+            Ok(ConsensusLog::ForcedChange(..)) => {
+                return Err(format!(
+                    "区块 #{} 含 GRANDPA 强制换届，轻客户端无法延续信任，需要重新锚定",
+                    header.number
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Err(format!(
+                    "解码区块 #{} 的 GRANDPA digest 失败: {e}",
+                    header.number
+                ))
+            }
+        }
+    }
+    Ok(change)
+}
+
+/// 用 justification 自带的祖先区块头实现 `finality_grandpa::Chain`。
+struct AncestryChain {
+    ancestry: HashMap<H256, Header>,
+}
+
+impl AncestryChain {
+    fn new(headers: &[Header]) -> Self {
+        Self {
+            ancestry: headers
+                .iter()
+                .map(|header| (header.hash(), header.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl finality_grandpa::Chain<H256, u32> for AncestryChain {
+    fn ancestry(&self, base: H256, block: H256) -> Result<Vec<H256>, finality_grandpa::Error> {
+        let mut route = Vec::new();
+        let mut current = block;
+        while current != base {
+            let header = self
+                .ancestry
+                .get(&current)
+                .ok_or(finality_grandpa::Error::NotDescendent)?;
+            current = header.parent_hash;
+            route.push(current);
+        }
+        route.pop();
+        Ok(route)
+    }
+}
+
+/// 校验 justification 由 `set` 中 2/3 以上权重签名，且祖先证明无多余区块头。
+///
+/// 与节点 vendored `GrandpaJustification::verify_with_voter_set` 规则一致。
+fn verify_justification(
+    justification: &GrandpaJustification<Header>,
+    set: &AuthoritySet,
+) -> Result<(), String> {
+    use finality_grandpa::Chain;
+
+    let voters = VoterSet::new(set.authorities.iter().cloned())
+        .ok_or_else(|| "GRANDPA 权威集为空或权重非法".to_string())?;
+    let ancestry_chain = AncestryChain::new(&justification.votes_ancestries);
+    match finality_grandpa::validate_commit(&justification.commit, &voters, &ancestry_chain) {
+        Ok(result) if result.is_valid() => {}
+        _ => return Err("GRANDPA justification 未获当前权威集 2/3 以上签名".to_string()),
+    }
+
+    let base_hash = justification
+        .commit
+        .precommits
+        .iter()
+        .map(|signed| &signed.precommit)
+        .min_by_key(|precommit| precommit.target_number)
+        .map(|precommit| precommit.target_hash)
+        .ok_or_else(|| "GRANDPA justification 不含 precommit".to_string())?;
+    let mut buf = Vec::new();
+    let mut visited = HashSet::new();
+    for signed in &justification.commit.precommits {
+        match sp_consensus_grandpa::check_message_signature_with_buffer(
+            &finality_grandpa::Message::Precommit(signed.precommit.clone()),
+            &signed.id,
+            &signed.signature,
+            justification.round,
+            set.set_id,
+            &mut buf,
+        ) {
+            sp_consensus_grandpa::SignatureResult::Valid => {}
+            sp_consensus_grandpa::SignatureResult::Invalid => {
+                return Err("GRANDPA justification 含无效 precommit 签名".to_string())
+            }
+            sp_consensus_grandpa::SignatureResult::OutdatedSet => {
+                return Err("GRANDPA justification 由过期权威集签名".to_string())
+            }
+        }
+        if signed.precommit.target_hash == base_hash {
+            continue;
+        }
+        let route = ancestry_chain
+            .ancestry(base_hash, signed.precommit.target_hash)
+            .map_err(|_| "GRANDPA justification 祖先证明不完整".to_string())?;
+        visited.insert(signed.precommit.target_hash);
+        visited.extend(route);
+    }
+    let ancestry: HashSet<_> = justification
+        .votes_ancestries
+        .iter()
+        .map(|header| header.hash())
+        .collect();
+    if visited != ancestry {
+        return Err("GRANDPA justification 含多余祖先区块头".to_string());
+    }
+    Ok(())
+}
+
+/// 在可信 `state_root` 上重放读取证明，按 `keys` 顺序返回值。
+fn check_read_proof(
+    state_root: H256,
+    proof: Vec<Vec<u8>>,
+    keys: &[Vec<u8>],
+) -> Result<Vec<Option<Vec<u8>>>, String> {
+    let mut values = sp_state_machine::read_proof_check::<BlakeTwo256, _>(
+        state_root,
+        sp_state_machine::StorageProof::new(proof),
+        keys.iter(),
+    )
+    .map_err(|e| format!("链上存储证明与可信 state_root 不符: {e}"))?;
+    keys.iter()
+        .map(|key| {
+            values
+                .remove(key.as_slice())
+                .ok_or_else(|| "链上存储证明缺少请求的 key".to_string())
+        })
+        .collect()
+}
+
+/// 在可信 `state_root` 上重放 `prefix` 子树，返回 `start_key` 之后（不含）的至多 `count` 个 key。
+///
+/// 证明只要漏掉子树中任何一个应被枚举到的节点，迭代就会因数据库不完整而报错。
+fn check_key_enumeration(
+    state_root: H256,
+    proof: Vec<Vec<u8>>,
+    prefix: &[u8],
+    start_key: Option<&[u8]>,
+    count: usize,
+) -> Result<Vec<Vec<u8>>, String> {
+    let backend = sp_state_machine::create_proof_check_backend::<BlakeTwo256>(
+        state_root,
+        sp_state_machine::StorageProof::new(proof),
+    )
+    .map_err(|e| format!("链上存储证明与可信 state_root 不符: {e}"))?;
+    let mut args = IterArgs::default();
+    args.prefix = Some(prefix);
+    args.start_at = start_key;
+    args.start_at_exclusive = true;
+    backend
+        .keys(args)
+        .map_err(|e| format!("重放 key 枚举证明失败: {e}"))?
+        .take(count)
+        .map(|key| key.map_err(|e| format!("key 枚举证明不完整: {e}")))
+        .collect()
+}
+
+/// 本程序编译所用 runtime 的 `:code` 哈希。
+///
+/// `ONCHINA_CHAIN_RUNTIME_CODE_HASH`（0x blake2_256）只给未内置 WASM 的构建使用，
+/// 必须对应本程序编译的同一个 runtime。
+fn pinned_runtime_code_hash() -> Result<H256, String> {
+    if let Some(raw) = std::env::var("ONCHINA_CHAIN_RUNTIME_CODE_HASH")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    {
+        let bytes = decode_hex_param(&raw)?;
+        return <[u8; 32]>::try_from(bytes.as_slice())
+            .map(H256::from)
+            .map_err(|_| "ONCHINA_CHAIN_RUNTIME_CODE_HASH 必须是 32 字节哈希".to_string());
+    }
+    citizenchain::WASM_BINARY
+        .map(|wasm| H256::from(sp_core::hashing::blake2_256(wasm)))
+        .ok_or_else(|| {
+            "未内置 runtime WASM 且未配置 ONCHINA_CHAIN_RUNTIME_CODE_HASH，无法校验 metadata"
+                .to_string()
+        })
+}
+
+/// `System::LastRuntimeUpgrade` 的存储 key；每次 runtime 升级都会改写它。
+fn last_runtime_upgrade_key() -> Vec<u8> {
+    let mut key = sp_core::hashing::twox_128(b"System").to_vec();
+    key.extend(sp_core::hashing::twox_128(b"LastRuntimeUpgrade"));
+    key
+}
+
+/// 用编译进本程序的 runtime 回答 metadata 运行时调用，返回 SCALE 编码结果。
+fn local_metadata_call(function: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+    match function {
+        "Metadata_metadata" => {
+            Ok(OpaqueMetadata::new(citizenchain::Runtime::metadata().into()).encode())
+        }
+        "Metadata_metadata_versions" => Ok(citizenchain::Runtime::metadata_versions().encode()),
+        "Metadata_metadata_at_version" => {
+            let version = u32::decode_all(&mut &data[..])
+                .map_err(|e| format!("decode metadata version param failed: {e}"))?;
+            Ok(citizenchain::Runtime::metadata_at_version(version).encode())
+        }
+        _ => Err(format!(
+            "运行时调用 {function} 的结果无法证明，证明校验模式下拒绝"
+        )),
+    }
+}
+
+fn hex_0x(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn decode_hex_param(raw: &str) -> Result<Vec<u8>, String> {
+    let clean = raw
+        .strip_prefix("0x")
+        .or_else(|| raw.strip_prefix("0X"))
+        .unwrap_or(raw);
+    hex::decode(clean).map_err(|e| format!("decode storage key hex failed: {e}"))
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    id: u64,
+    result: Option<Value>,
+    error: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct ReadProofResponse {
+    proof: Vec<sp_core::Bytes>,
+}
+
+#[derive(Deserialize)]
+struct SignedBlockJustifications {
+    justifications: Option<Vec<([u8; 4], Vec<u8>)>>,
+}
+
+#[derive(Deserialize)]
+struct SignedBlockBody {
+    block: BlockBody,
+}
+
+#[derive(Deserialize)]
+struct BlockBody {
+    header: Header,
+    extrinsics: Vec<sp_core::Bytes>,
+}
+
+/// 轻客户端只把 RPC 当作不可信的数据源，所有返回值都在本地校验后才使用。
+struct HttpRpc {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl HttpRpc {
+    fn from_env() -> Result<Self, String> {
+        Ok(Self {
+            client: reqwest::Client::new(),
+            url: super::chain_url::chain_http_url()?,
+        })
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T, String> {
+        let mut results = self.batch(method, vec![params]).await?;
+        results
+            .pop()
+            .ok_or_else(|| format!("chain http rpc {method} returned no result"))
+    }
+
+    /// 同一方法的批量请求，按请求顺序返回。
+    async fn batch<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Vec<Value>,
+    ) -> Result<Vec<T>, String> {
+        let requests = params
+            .into_iter()
+            .enumerate()
+            .map(|(index, params)| {
+                json!({
+                    "id": (index + 1) as u64,
+                    "jsonrpc": "2.0",
+                    "method": method,
+                    "params": params,
+                })
+            })
+            .collect::<Vec<_>>();
+        let expected = requests.len();
+        let response = self
+            .client
+            .post(self.url.as_str())
+            .json(&requests)
+            .send()
+            .await
+            .map_err(|e| format!("connect chain http rpc for {method} failed: {e}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("chain http rpc returned status {status}"));
+        }
+        let mut payload = response
+            .json::<Vec<RpcResponse>>()
+            .await
+            .map_err(|e| format!("decode chain http rpc {method} response failed: {e}"))?;
+        if payload.len() != expected {
+            return Err(format!("chain http rpc {method} batch size mismatch"));
+        }
+        payload.sort_by_key(|item| item.id);
+        payload
+            .into_iter()
+            .map(|item| {
+                if let Some(error) = item.error {
+                    return Err(format!("chain http rpc returned error: {error}"));
+                }
+                serde_json::from_value(item.result.unwrap_or(Value::Null))
+                    .map_err(|e| format!("decode chain http rpc {method} result failed: {e}"))
+            })
+            .collect()
+    }
+
+    async fn finalized_head(&self) -> Result<H256, String> {
+        self.call("chain_getFinalizedHead", json!([])).await
+    }
+
+    /// 取区块头并确认其哈希就是请求的哈希。
+    async fn header(&self, hash: H256) -> Result<Header, String> {
+        let header = self
+            .call::<Option<Header>>("chain_getHeader", json!([hex_0x(hash.as_bytes())]))
+            .await?
+            .ok_or_else(|| format!("chain header {hash:?} not found"))?;
+        if header.hash() != hash {
+            return Err(format!("RPC 返回的区块头与请求哈希 {hash:?} 不符"));
+        }
+        Ok(header)
+    }
+
+    /// 取 `from..=to` 的连续区块头，并校验彼此的 parent hash 链接。
+    async fn headers(&self, from: u32, to: u32) -> Result<Vec<Header>, String> {
+        let mut headers: Vec<Header> = Vec::with_capacity(to.saturating_sub(from) as usize + 1);
+        let mut start = from;
+        while start <= to {
+            let end = to.min(start.saturating_add(RPC_BATCH_SIZE - 1));
+            let hashes = self
+                .batch::<Option<H256>>(
+                    "chain_getBlockHash",
+                    (start..=end).map(|number| json!([number])).collect(),
+                )
+                .await?
+                .into_iter()
+                .zip(start..=end)
+                .map(|(hash, number)| hash.ok_or_else(|| format!("block #{number} not found")))
+                .collect::<Result<Vec<_>, _>>()?;
+            let batch = self
+                .batch::<Option<Header>>(
+                    "chain_getHeader",
+                    hashes
+                        .iter()
+                        .map(|hash| json!([hex_0x(hash.as_bytes())]))
+                        .collect(),
+                )
+                .await?;
+            for ((header, hash), number) in batch.into_iter().zip(hashes).zip(start..=end) {
+                let header = header.ok_or_else(|| format!("block #{number} header not found"))?;
+                if header.number != number || header.hash() != hash {
+                    return Err(format!("RPC 返回的区块头 #{number} 与区块哈希不符"));
+                }
+                if let Some(parent) = headers.last() {
+                    if header.parent_hash != parent.hash() {
+                        return Err(format!("RPC 返回的区块头 #{number} 未链接到父区块"));
+                    }
+                }
+                headers.push(header);
+            }
+            start = match end.checked_add(1) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+        Ok(headers)
+    }
+
+    /// 批量读取各区块持久化的 GRANDPA justification（未持久化为 `None`）。
+    async fn justifications(&self, hashes: &[H256]) -> Result<Vec<Option<Vec<u8>>>, String> {
+        let blocks = self
+            .batch::<Option<SignedBlockJustifications>>(
+                "chain_getBlock",
+                hashes
+                    .iter()
+                    .map(|hash| json!([hex_0x(hash.as_bytes())]))
+                    .collect(),
+            )
+            .await?;
+        Ok(blocks
+            .into_iter()
+            .map(|block| {
+                block
+                    .and_then(|block| block.justifications)
+                    .and_then(|items| {
+                        items
+                            .into_iter()
+                            .find(|(engine_id, _)| *engine_id == GRANDPA_ENGINE_ID)
+                            .map(|(_, encoded)| encoded)
+                    })
+            })
+            .collect())
+    }
+
+    async fn read_proof(&self, keys: &[Vec<u8>], at: H256) -> Result<Vec<Vec<u8>>, String> {
+        let keys = keys.iter().map(|key| hex_0x(key)).collect::<Vec<_>>();
+        let response = self
+            .call::<ReadProofResponse>("state_getReadProof", json!([keys, hex_0x(at.as_bytes())]))
+            .await?;
+        Ok(response.proof.into_iter().map(|node| node.0).collect())
+    }
+}
+
+struct LightClient {
+    set: AuthoritySet,
+    head: TrustedHeader,
+    pending: Option<PendingChange>,
+    /// 可信链上每个 justification 周期边界与每个 justified 区块的哈希，用于回溯校验历史区块。
+    checkpoints: BTreeMap<u32, H256>,
+    recent: HashMap<H256, TrustedHeader>,
+    last_sync: Option<Instant>,
+    genesis_hash: H256,
+    /// 上次落盘时的可信头高度与权威集编号。
+    persisted: (u32, u64),
+}
+
+impl LightClient {
+    async fn bootstrap(rpc: &HttpRpc) -> Result<Self, String> {
+        let genesis_hex = super::chain_runtime::cached_chain_genesis_hash_hex()
+            .ok_or_else(|| "链创世哈希尚未完成启动校验，无法建立轻客户端信任锚".to_string())?;
+        let genesis_hash: H256 = serde_json::from_value(Value::String(genesis_hex))
+            .map_err(|e| format!("parse cached genesis hash failed: {e}"))?;
+        if let Some(client) = Self::restore(genesis_hash) {
+            tracing::info!(
+                trusted = client.head.number,
+                set_id = client.set.set_id,
+                "chain proof light client resumed from persisted checkpoint"
+            );
+            return Ok(client);
+        }
+        let genesis = rpc.header(genesis_hash).await?;
+        if genesis.number != 0 {
+            return Err("RPC 返回的创世区块头高度不为 0".to_string());
+        }
+        let head = TrustedHeader::of(&genesis);
+        tracing::info!(
+            genesis_hash = %head.hash_hex(),
+            "chain proof light client anchored at genesis"
+        );
+        Ok(Self {
+            set: genesis_authority_set(),
+            head,
+            pending: None,
+            checkpoints: BTreeMap::from([(0, genesis_hash)]),
+            recent: HashMap::from([(genesis_hash, head)]),
+            last_sync: None,
+            genesis_hash,
+            persisted: (0, 0),
+        })
+    }
+
+    /// 读取落盘状态；缺失、损坏或与当前链不符时返回 `None`，由调用方从创世重新同步。
+    fn restore(genesis_hash: H256) -> Option<Self> {
+        let path = light_client_state_path();
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "read chain proof state failed");
+                return None;
+            }
+        };
+        let restored = PersistedLightClient::decode_all(&mut &bytes[..])
+            .map_err(|e| format!("decode chain proof state failed: {e}"))
+            .and_then(|state| Self::from_persisted(genesis_hash, state));
+        match restored {
+            Ok(client) => Some(client),
+            Err(error) => {
+                tracing::warn!(path = %path.display(), %error, "discarding chain proof state");
+                None
+            }
+        }
+    }
+
+    fn from_persisted(genesis_hash: H256, state: PersistedLightClient) -> Result<Self, String> {
+        if state.genesis_hash != genesis_hash {
+            return Err("落盘状态属于另一条链".to_string());
+        }
+        let checkpoints = state.checkpoints.into_iter().collect::<BTreeMap<_, _>>();
+        if checkpoints.get(&0) != Some(&genesis_hash)
+            || checkpoints.get(&state.head_number) != Some(&state.head_hash)
+            || checkpoints.keys().next_back() != Some(&state.head_number)
+        {
+            return Err("落盘检查点与可信头不一致".to_string());
+        }
+        let head = TrustedHeader {
+            hash: state.head_hash,
+            number: state.head_number,
+            state_root: state.head_state_root,
+        };
+        Ok(Self {
+            set: AuthoritySet {
+                set_id: state.set_id,
+                authorities: state.authorities,
+            },
+            head,
+            pending: state
+                .pending
+                .map(|(enact_at, next_authorities)| PendingChange {
+                    enact_at,
+                    next_authorities,
+                }),
+            checkpoints,
+            recent: HashMap::from([(head.hash, head)]),
+            last_sync: None,
+            genesis_hash,
+            persisted: (head.number, state.set_id),
+        })
+    }
+
+    fn to_persisted(&self) -> PersistedLightClient {
+        PersistedLightClient {
+            genesis_hash: self.genesis_hash,
+            set_id: self.set.set_id,
+            authorities: self.set.authorities.clone(),
+            head_hash: self.head.hash,
+            head_number: self.head.number,
+            head_state_root: self.head.state_root,
+            pending: self
+                .pending
+                .as_ref()
+                .map(|change| (change.enact_at, change.next_authorities.clone())),
+            checkpoints: self.checkpoints.iter().map(|(n, h)| (*n, *h)).collect(),
+        }
+    }
+
+    /// 可信头推进满 [`PERSIST_INTERVAL`] 或权威集换届后落盘（先写临时文件再原子替换）。
+    fn persist_if_due(&mut self) {
+        let (number, set_id) = self.persisted;
+        if self.set.set_id == set_id && self.head.number < number.saturating_add(PERSIST_INTERVAL) {
+            return;
+        }
+        let path = light_client_state_path();
+        let staged = path.with_extension("scale.tmp");
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&staged, self.to_persisted().encode()))
+            .and_then(|_| fs::rename(&staged, &path));
+        match written {
+            Ok(()) => self.persisted = (self.head.number, self.set.set_id),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "persist chain proof state failed")
+            }
+        }
+    }
+
+    fn remember(&mut self, header: TrustedHeader) {
+        if self.recent.len() >= RECENT_HEADER_LIMIT {
+            self.recent.clear();
+        }
+        self.recent.insert(header.hash, header);
+    }
+
+    /// 距上次同步超过 [`MIN_SYNC_INTERVAL`] 时追新，返回当前可信 finalized 头。
+    async fn refresh(&mut self, rpc: &HttpRpc) -> Result<TrustedHeader, String> {
+        let due = match self.last_sync {
+            Some(at) => at.elapsed() >= MIN_SYNC_INTERVAL,
+            None => true,
+        };
+        if due {
+            self.sync(rpc).await?;
+            self.last_sync = Some(Instant::now());
+            self.persist_if_due();
+        }
+        Ok(self.head)
+    }
+
+    /// 以 RPC 报告的 finalized 高度为上限推进可信头；RPC 高度只决定扫描范围，不被信任。
+    async fn sync(&mut self, rpc: &HttpRpc) -> Result<(), String> {
+        let hint = rpc.header(rpc.finalized_head().await?).await?.number;
+        while self.head.number < hint {
+            let mut limit = hint.min(self.head.number.saturating_add(SYNC_SCAN_SPAN));
+            if let Some(change) = &self.pending {
+                limit = limit.min(change.enact_at);
+            }
+            if limit <= self.head.number {
+                break;
+            }
+            let mut headers = rpc.headers(self.head.number + 1, limit).await?;
+            if headers.first().map(|header| header.parent_hash) != Some(self.head.hash) {
+                return Err("RPC 区块头未链接到可信 finalized 区块".to_string());
+            }
+
+            // 换届生效块必须由旧权威集单独 justify，扫描在生效块截止。
+            let mut pending = self.pending.clone();
+            let mut signaled_at = None;
+            for (index, header) in headers.iter().enumerate() {
+                if let Some(change) = grandpa_change(header)? {
+                    if pending.is_some() {
+                        return Err(format!(
+                            "区块 #{} 的 GRANDPA 换届与未生效换届重叠",
+                            header.number
+                        ));
+                    }
+                    pending = Some(change);
+                    signaled_at = Some(index);
+                }
+                if pending
+                    .as_ref()
+                    .is_some_and(|change| change.enact_at == header.number)
+                {
+                    headers.truncate(index + 1);
+                    break;
+                }
+            }
+            let Some(last) = headers.last() else {
+                break;
+            };
+            let enacts = pending
+                .as_ref()
+                .is_some_and(|change| change.enact_at == last.number);
+            let target = if enacts {
+                let encoded = rpc
+                    .justifications(&[last.hash()])
+                    .await?
+                    .pop()
+                    .flatten()
+                    .ok_or_else(|| {
+                        format!("GRANDPA 换届生效区块 #{} 缺少 justification", last.number)
+                    })?;
+                Some((headers.len() - 1, encoded))
+            } else {
+                probe_justification(rpc, &headers).await?
+            };
+            let Some((index, encoded)) = target else {
+                tracing::warn!(
+                    trusted = self.head.number,
+                    scanned_to = limit,
+                    "no GRANDPA justification found in scanned range, keeping trusted head"
+                );
+                break;
+            };
+            self.commit(&headers[..=index], &encoded)?;
+            if signaled_at.is_some_and(|at| at > index) {
+                pending = None;
+            }
+            if enacts && index == headers.len() - 1 {
+                if let Some(change) = pending.take() {
+                    self.set = AuthoritySet {
+                        set_id: self.set.set_id + 1,
+                        authorities: change.next_authorities,
+                    };
+                    tracing::info!(
+                        set_id = self.set.set_id,
+                        block = self.head.number,
+                        "chain proof light client followed GRANDPA authority set change"
+                    );
+                }
+            }
+            self.pending = pending;
+        }
+        Ok(())
+    }
+
+    /// 用当前权威集校验 `headers` 末块的 justification，通过后整段区块头成为可信链。
+    fn commit(&mut self, headers: &[Header], encoded: &[u8]) -> Result<(), String> {
+        let target = headers
+            .last()
+            .ok_or_else(|| "没有待确认的区块头".to_string())?;
+        let justification =
+            GrandpaJustification::<Header>::decode_all(&mut &encoded[..]).map_err(|e| {
+                format!(
+                    "解码区块 #{} 的 GRANDPA justification 失败: {e}",
+                    target.number
+                )
+            })?;
+        if justification.commit.target_hash != target.hash()
+            || justification.commit.target_number != target.number
+        {
+            return Err(format!(
+                "区块 #{} 的 GRANDPA justification 目标与区块头不符",
+                target.number
+            ));
+        }
+        verify_justification(&justification, &self.set)?;
+        for header in headers {
+            let trusted = TrustedHeader::of(header);
+            if trusted.number % JUSTIFICATION_PERIOD == 0 {
+                self.checkpoints.insert(trusted.number, trusted.hash);
+            }
+            self.remember(trusted);
+        }
+        self.head = TrustedHeader::of(target);
+        self.checkpoints.insert(self.head.number, self.head.hash);
+        Ok(())
+    }
+
+    /// 确认 `hash` 位于可信 finalized 链上并返回其区块头摘要。
+    async fn ensure_canonical(
+        &mut self,
+        rpc: &HttpRpc,
+        hash: H256,
+    ) -> Result<TrustedHeader, String> {
+        if let Some(trusted) = self.recent.get(&hash) {
+            return Ok(*trusted);
+        }
+        let header = rpc.header(hash).await?;
+        if header.number > self.head.number {
+            self.refresh(rpc).await?;
+            if let Some(trusted) = self.recent.get(&hash) {
+                return Ok(*trusted);
+            }
+            if header.number > self.head.number {
+                return Err(format!(
+                    "区块 #{} 尚未被 GRANDPA justification 证明 finalized",
+                    header.number
+                ));
+            }
+        }
+        let (&checkpoint_number, &checkpoint_hash) = self
+            .checkpoints
+            .range(header.number..)
+            .next()
+            .ok_or_else(|| "轻客户端缺少可信检查点".to_string())?;
+        if checkpoint_number == header.number {
+            if checkpoint_hash != hash {
+                return Err(format!("区块 {hash:?} 不在 GRANDPA finalized 链上"));
+            }
+            let trusted = TrustedHeader::of(&header);
+            self.remember(trusted);
+            return Ok(trusted);
+        }
+        let segment = rpc.headers(header.number, checkpoint_number).await?;
+        if segment.last().map(|header| header.hash()) != Some(checkpoint_hash) {
+            return Err(format!("区块 #{} 未链接到可信检查点", header.number));
+        }
+        for item in &segment {
+            self.remember(TrustedHeader::of(item));
+        }
+        match segment.first() {
+            Some(first) if first.hash() == hash => Ok(TrustedHeader::of(first)),
+            _ => Err(format!("区块 {hash:?} 不在 GRANDPA finalized 链上")),
+        }
+    }
+}
+
+/// 自上而下按 justification 周期窗口探测节点持久化的 justification，返回最高的一份。
+async fn probe_justification(
+    rpc: &HttpRpc,
+    headers: &[Header],
+) -> Result<Option<(usize, Vec<u8>)>, String> {
+    let (Some(first), Some(last)) = (headers.first(), headers.last()) else {
+        return Ok(None);
+    };
+    let (first, last) = (first.number, last.number);
+    let mut window_start = last - last % JUSTIFICATION_PERIOD;
+    for _ in 0..JUSTIFICATION_PROBE_WINDOWS {
+        let lo = window_start.max(first);
+        let hi = window_start
+            .saturating_add(JUSTIFICATION_PERIOD - 1)
+            .min(last);
+        if lo <= hi {
+            let offset = (lo - first) as usize;
+            let candidates = &headers[offset..=(hi - first) as usize];
+            let hashes = candidates
+                .iter()
+                .map(|header| header.hash())
+                .collect::<Vec<_>>();
+            let found = rpc
+                .justifications(&hashes)
+                .await?
+                .into_iter()
+                .enumerate()
+                .rev()
+                .find_map(|(index, encoded)| encoded.map(|encoded| (offset + index, encoded)));
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+        if window_start <= first {
+            break;
+        }
+        window_start -= JUSTIFICATION_PERIOD;
+    }
+    Ok(None)
+}
+
+static LIGHT_CLIENT: OnceLock<tokio::sync::Mutex<Option<LightClient>>> = OnceLock::new();
+
+async fn lock_light_client(
+    rpc: &HttpRpc,
+) -> Result<tokio::sync::MutexGuard<'static, Option<LightClient>>, String> {
+    let mut guard = LIGHT_CLIENT
+        .get_or_init(|| tokio::sync::Mutex::new(None))
+        .lock()
+        .await;
+    if guard.is_none() {
+        *guard = Some(LightClient::bootstrap(rpc).await?);
+    }
+    Ok(guard)
+}
+
+/// 返回当前可信 finalized 区块头（必要时先向 RPC 追新 justification）。
+pub(crate) async fn verified_finalized_head() -> Result<TrustedHeader, String> {
+    let rpc = HttpRpc::from_env()?;
+    let mut guard = lock_light_client(&rpc).await?;
+    let client = guard
+        .as_mut()
+        .ok_or_else(|| "轻客户端未初始化".to_string())?;
+    client.refresh(&rpc).await
+}
+
+/// 确认 `hash` 是可信 finalized 链上的区块。
+pub(crate) async fn ensure_finalized_block(hash: H256) -> Result<TrustedHeader, String> {
+    let rpc = HttpRpc::from_env()?;
+    let mut guard = lock_light_client(&rpc).await?;
+    let client = guard
+        .as_mut()
+        .ok_or_else(|| "轻客户端未初始化".to_string())?;
+    client.ensure_canonical(&rpc, hash).await
+}
+
+/// 可信 finalized 区块头：`at` 为空时取最新可信头，否则确认其位于可信链上。
+async fn trusted_header(rpc: &HttpRpc, at: Option<H256>) -> Result<TrustedHeader, String> {
+    let mut guard = lock_light_client(rpc).await?;
+    let client = guard
+        .as_mut()
+        .ok_or_else(|| "轻客户端未初始化".to_string())?;
+    match at {
+        Some(hash) => client.ensure_canonical(rpc, hash).await,
+        None => client.refresh(rpc).await,
+    }
+}
+
+/// 在可信 finalized 区块（`at` 为空时取最新可信头）上读取并证明一组存储值。
+pub(crate) async fn read_storage_proven(
+    keys: &[Vec<u8>],
+    at: Option<H256>,
+) -> Result<(TrustedHeader, Vec<Option<Vec<u8>>>), String> {
+    let rpc = HttpRpc::from_env()?;
+    let header = trusted_header(&rpc, at).await?;
+    let proof = rpc.read_proof(keys, header.hash).await?;
+    let values = check_read_proof(header.state_root, proof, keys)?;
+    Ok((header, values))
+}
+
+/// 校验 RPC 报告的一页前缀 key 枚举：证明覆盖前缀、起始 key 与所报 key，
+/// 在可信 `state_root` 上重放出的同一页必须与 RPC 结果逐项一致。
+async fn enumerate_keys_proven(
+    prefix: &[u8],
+    count: usize,
+    start_key: Option<&[u8]>,
+    at: Option<H256>,
+    reported: &[Vec<u8>],
+) -> Result<(), String> {
+    let rpc = HttpRpc::from_env()?;
+    let header = trusted_header(&rpc, at).await?;
+    let mut proof_keys = vec![prefix.to_vec()];
+    proof_keys.extend(start_key.map(<[u8]>::to_vec));
+    proof_keys.extend(reported.iter().cloned());
+    let proof = rpc.read_proof(&proof_keys, header.hash).await?;
+    let proven = check_key_enumeration(header.state_root, proof, prefix, start_key, count)?;
+    if proven != reported {
+        return Err(format!(
+            "RPC 枚举的 key 与区块 #{} 的存储证明不符",
+            header.number
+        ));
+    }
+    Ok(())
+}
+
+/// 确认可信区块上的 runtime 代码就是本程序编译的 runtime。
+///
+/// `:code` 证明较大，按已证明的 `LastRuntimeUpgrade` 缓存：该值不变即 runtime 未升级。
+async fn ensure_pinned_runtime(at: Option<H256>) -> Result<(), String> {
+    static VERIFIED_UPGRADE: OnceLock<tokio::sync::Mutex<Option<Option<Vec<u8>>>>> =
+        OnceLock::new();
+    let expected = pinned_runtime_code_hash()?;
+    let (header, mut marker) = read_storage_proven(&[last_runtime_upgrade_key()], at).await?;
+    let marker = marker.pop().flatten();
+    let mut verified = VERIFIED_UPGRADE
+        .get_or_init(|| tokio::sync::Mutex::new(None))
+        .lock()
+        .await;
+    if verified.as_ref() == Some(&marker) {
+        return Ok(());
+    }
+    let code_key = sp_core::storage::well_known_keys::CODE.to_vec();
+    let (_, mut code) = read_storage_proven(&[code_key], Some(header.hash)).await?;
+    let code = code
+        .pop()
+        .flatten()
+        .ok_or_else(|| format!("可信区块 #{} 缺少 runtime 代码", header.number))?;
+    if H256::from(sp_core::hashing::blake2_256(&code)) != expected {
+        return Err(format!(
+            "区块 #{} 的 runtime 代码与本程序钉扎的版本不符，拒绝使用该区块的 metadata",
+            header.number
+        ));
+    }
+    *verified = Some(marker);
+    Ok(())
+}
+
+/// 连接链 RPC；开启证明校验时包一层 [`ProvenRpcClient`]，上层 subxt 读取无需改动。
+pub(crate) async fn connect_rpc(ws_url: &str) -> Result<RpcClient, String> {
+    let inner = RpcClient::from_insecure_url(ws_url)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !proof_verification_enabled() {
+        return Ok(inner);
+    }
+    Ok(RpcClient::new(ProvenRpcClient { inner }))
+}
+
+/// [`connect_rpc`] 之上的 subxt 在线客户端。
+pub(crate) async fn online_client(ws_url: &str) -> Result<OnlineClient<PolkadotConfig>, String> {
+    let rpc_client = connect_rpc(ws_url).await?;
+    OnlineClient::<PolkadotConfig>::from_rpc_client(rpc_client)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// subxt RPC 拦截层：finalized head 换成轻客户端可信头，存储读取改为证明读取，
+/// 区块头、区块体与 finalized 订阅只放行已被 GRANDPA 证明的区块。
+struct ProvenRpcClient {
+    inner: RpcClient,
+}
+
+fn rpc_error(message: String) -> subxt::ext::subxt_rpcs::Error {
+    subxt::ext::subxt_rpcs::Error::Client(message.into())
+}
+
+fn to_raw(value: &Value) -> Result<Box<RawValue>, String> {
+    serde_json::value::to_raw_value(value).map_err(|e| format!("encode rpc result failed: {e}"))
+}
+
+fn parse_params(params: Option<&RawValue>) -> Result<Vec<Value>, String> {
+    match params {
+        None => Ok(Vec::new()),
+        Some(raw) => {
+            serde_json::from_str(raw.get()).map_err(|e| format!("decode rpc params failed: {e}"))
+        }
+    }
+}
+
+fn param_hash(params: &[Value], index: usize) -> Result<Option<H256>, String> {
+    match params.get(index) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|e| format!("decode block hash param failed: {e}")),
+    }
+}
+
+fn param_key(value: Option<&Value>) -> Result<Vec<u8>, String> {
+    value
+        .and_then(Value::as_str)
+        .ok_or_else(|| "storage key param must be hex".to_string())
+        .and_then(decode_hex_param)
+}
+
+impl ProvenRpcClient {
+    async fn proven_request(
+        &self,
+        method: &str,
+        params: Option<Box<RawValue>>,
+    ) -> Result<Box<RawValue>, String> {
+        let args = parse_params(params.as_deref())?;
+        match method {
+            "chain_getFinalizedHead" => {
+                to_raw(&Value::String(verified_finalized_head().await?.hash_hex()))
+            }
+            "state_getStorage" => {
+                let key = param_key(args.first())?;
+                let (_, mut values) = read_storage_proven(&[key], param_hash(&args, 1)?).await?;
+                to_raw(&json!(values.pop().flatten().map(|value| hex_0x(&value))))
+            }
+            "state_queryStorageAt" => {
+                let keys = args
+                    .first()
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| "state_queryStorageAt keys param must be array".to_string())?
+                    .iter()
+                    .map(|key| param_key(Some(key)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let (header, values) = read_storage_proven(&keys, param_hash(&args, 1)?).await?;
+                let changes = keys
+                    .iter()
+                    .zip(values)
+                    .map(|(key, value)| json!([hex_0x(key), value.map(|value| hex_0x(&value))]))
+                    .collect::<Vec<_>>();
+                to_raw(&json!([{ "block": header.hash_hex(), "changes": changes }]))
+            }
+            "state_getKeysPaged" => {
+                let prefix = param_key(args.first())?;
+                let count =
+                    args.get(1).and_then(Value::as_u64).ok_or_else(|| {
+                        "state_getKeysPaged count param must be integer".to_string()
+                    })? as usize;
+                let start_key = match args.get(2) {
+                    None | Some(Value::Null) => None,
+                    value => Some(param_key(value)?),
+                };
+                let at = param_hash(&args, 3)?;
+                let raw = self
+                    .inner
+                    .request_raw(method, params)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let reported: Vec<sp_core::Bytes> = serde_json::from_str(raw.get())
+                    .map_err(|e| format!("decode state_getKeysPaged result failed: {e}"))?;
+                let reported = reported.into_iter().map(|key| key.0).collect::<Vec<_>>();
+                enumerate_keys_proven(&prefix, count, start_key.as_deref(), at, &reported).await?;
+                Ok(raw)
+            }
+            "state_getMetadata" => {
+                ensure_pinned_runtime(param_hash(&args, 0)?).await?;
+                let metadata: Vec<u8> = citizenchain::Runtime::metadata().into();
+                to_raw(&Value::String(hex_0x(&metadata)))
+            }
+            "state_call" => {
+                let function = args
+                    .first()
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| "state_call method param must be string".to_string())?;
+                let data = match args.get(1) {
+                    None | Some(Value::Null) => Vec::new(),
+                    value => param_key(value)?,
+                };
+                let result = local_metadata_call(function, &data)?;
+                ensure_pinned_runtime(param_hash(&args, 2)?).await?;
+                to_raw(&Value::String(hex_0x(&result)))
+            }
+            "state_getKeys"
+            | "state_getPairs"
+            | "state_getStorageHash"
+            | "state_getStorageSize"
+            | "state_queryStorage"
+            | "state_getChildReadProof" => Err(format!(
+                "{method} 的结果无法由存储证明覆盖，证明校验模式下拒绝"
+            )),
+            _ if method.starts_with("childstate_") => Err(format!(
+                "{method} 的结果无法由存储证明覆盖，证明校验模式下拒绝"
+            )),
+            "chain_getBlockHash" => {
+                let raw = self
+                    .inner
+                    .request_raw(method, params)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let hash: Option<H256> = serde_json::from_str(raw.get())
+                    .map_err(|e| format!("decode chain_getBlockHash result failed: {e}"))?;
+                if let Some(hash) = hash {
+                    ensure_finalized_block(hash).await?;
+                }
+                Ok(raw)
+            }
+            "chain_getHeader" => {
+                let hash = match param_hash(&args, 0)? {
+                    Some(hash) => hash,
+                    None => verified_finalized_head().await?.hash,
+                };
+                let raw = self
+                    .inner
+                    .request_raw(method, Some(to_raw(&json!([hex_0x(hash.as_bytes())]))?))
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let header: Option<Header> = serde_json::from_str(raw.get())
+                    .map_err(|e| format!("decode chain_getHeader result failed: {e}"))?;
+                if header.is_some_and(|header| header.hash() != hash) {
+                    return Err(format!("RPC 返回的区块头与请求哈希 {hash:?} 不符"));
+                }
+                Ok(raw)
+            }
+            "chain_getBlock" => {
+                let hash = param_hash(&args, 0)?
+                    .ok_or_else(|| "chain_getBlock requires block hash".to_string())?;
+                ensure_finalized_block(hash).await?;
+                let raw = self
+                    .inner
+                    .request_raw(method, params)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let block: Option<SignedBlockBody> = serde_json::from_str(raw.get())
+                    .map_err(|e| format!("decode chain_getBlock result failed: {e}"))?;
+                if let Some(SignedBlockBody { block }) = block {
+                    if block.header.hash() != hash {
+                        return Err(format!("RPC 返回的区块与请求哈希 {hash:?} 不符"));
+                    }
+                    let extrinsics_root = BlakeTwo256::ordered_trie_root(
+                        block.extrinsics.into_iter().map(|item| item.0).collect(),
+                        StateVersion::V0,
+                    );
+                    if extrinsics_root != block.header.extrinsics_root {
+                        return Err(format!("区块 {hash:?} 的交易列表与 extrinsics_root 不符"));
+                    }
+                }
+                Ok(raw)
+            }
+            _ => self
+                .inner
+                .request_raw(method, params)
+                .await
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// finalized 订阅推送的区块要等轻客户端拿到覆盖它的 justification 后才交给上层。
+async fn wait_until_justified(raw: &RawValue) -> Result<(), String> {
+    let header: Header = serde_json::from_str(raw.get())
+        .map_err(|e| format!("decode finalized head notification failed: {e}"))?;
+    loop {
+        if verified_finalized_head().await?.number >= header.number {
+            ensure_finalized_block(header.hash()).await?;
+            return Ok(());
+        }
+        tokio::time::sleep(JUSTIFICATION_WAIT_INTERVAL).await;
+    }
+}
+
+impl RpcClientT for ProvenRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RawRpcFuture<'a, Box<RawValue>> {
+        Box::pin(async move {
+            match method {
+                "chain_getFinalizedHead"
+                | "state_getStorage"
+                | "state_queryStorageAt"
+                | "state_getKeysPaged"
+                | "state_getMetadata"
+                | "state_call"
+                | "state_getKeys"
+                | "state_getPairs"
+                | "state_getStorageHash"
+                | "state_getStorageSize"
+                | "state_queryStorage"
+                | "state_getChildReadProof"
+                | "chain_getBlockHash"
+                | "chain_getHeader"
+                | "chain_getBlock" => self.proven_request(method, params).await.map_err(rpc_error),
+                _ if method.starts_with("childstate_") => {
+                    self.proven_request(method, params).await.map_err(rpc_error)
+                }
+                _ => self.inner.request_raw(method, params).await,
+            }
+        })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        unsub: &'a str,
+    ) -> RawRpcFuture<'a, RawRpcSubscription> {
+        Box::pin(async move {
+            let subscription = self.inner.subscribe_raw(sub, params, unsub).await?;
+            if sub != "chain_subscribeFinalizedHeads" {
+                return Ok(subscription);
+            }
+            let RawRpcSubscription { stream, id } = subscription;
+            let stream = stream.then(|item| async move {
+                let raw = item?;
+                wait_until_justified(&raw).await.map_err(rpc_error)?;
+                Ok::<_, subxt::ext::subxt_rpcs::Error>(raw)
+            });
+            Ok(RawRpcSubscription {
+                stream: Box::pin(stream),
+                id,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_consensus_grandpa::{AuthoritySignature, ScheduledChange};
+    use sp_core::Pair as _;
+
+    fn header(number: u32, parent_hash: H256) -> Header {
+        Header {
+            parent_hash,
+            number,
+            state_root: H256::repeat_byte(0x11),
+            extrinsics_root: H256::repeat_byte(0x22),
+            digest: Default::default(),
+        }
+    }
+
+    fn justification(
+        signers: &[sp_core::ed25519::Pair],
+        set_id: u64,
+        target: &Header,
+    ) -> GrandpaJustification<Header> {
+        let round = 9;
+        let precommit = finality_grandpa::Precommit {
+            target_hash: target.hash(),
+            target_number: target.number,
+        };
+        let message = finality_grandpa::Message::Precommit(precommit.clone());
+        let payload = sp_consensus_grandpa::localized_payload(round, set_id, &message);
+        let precommits = signers
+            .iter()
+            .map(|pair| finality_grandpa::SignedPrecommit {
+                precommit: precommit.clone(),
+                signature: AuthoritySignature::from(pair.sign(&payload)),
+                id: AuthorityId::from(pair.public()),
+            })
+            .collect();
+        GrandpaJustification {
+            round,
+            commit: finality_grandpa::Commit {
+                target_hash: target.hash(),
+                target_number: target.number,
+                precommits,
+            },
+            votes_ancestries: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn justification_requires_supermajority_of_current_set() {
+        let pairs = (1..=4u8)
+            .map(|seed| sp_core::ed25519::Pair::from_seed(&[seed; 32]))
+            .collect::<Vec<_>>();
+        let set = AuthoritySet {
+            set_id: 3,
+            authorities: pairs
+                .iter()
+                .map(|pair| (AuthorityId::from(pair.public()), 1))
+                .collect(),
+        };
+        let target = header(128, H256::repeat_byte(0x33));
+
+        assert!(verify_justification(&justification(&pairs[..3], 3, &target), &set).is_ok());
+        // 4 票中 2 票不到 2/3 门限。
+        assert!(verify_justification(&justification(&pairs[..2], 3, &target), &set).is_err());
+        // 旧权威集签名不能证明当前集合下的 finality。
+        assert!(verify_justification(&justification(&pairs[..3], 2, &target), &set).is_err());
+    }
+
+    #[test]
+    fn scheduled_change_is_followed_and_forced_change_rejected() {
+        let next = sp_core::ed25519::Pair::from_seed(&[7; 32]);
+        let scheduled = ScheduledChange {
+            next_authorities: vec![(AuthorityId::from(next.public()), 1)],
+            delay: 10u32,
+        };
+        let mut signal = header(100, H256::repeat_byte(0x44));
+        signal.digest.push(DigestItem::Consensus(
+            GRANDPA_ENGINE_ID,
+            ConsensusLog::ScheduledChange(scheduled.clone()).encode(),
+        ));
+        let change = grandpa_change(&signal)
+            .expect("scheduled change decodes")
+            .expect("scheduled change present");
+        assert_eq!(change.enact_at, 110);
+        assert_eq!(change.next_authorities, scheduled.next_authorities);
+
+        let mut forced = header(100, H256::repeat_byte(0x44));
+        forced.digest.push(DigestItem::Consensus(
+            GRANDPA_ENGINE_ID,
+            ConsensusLog::ForcedChange(90u32, scheduled).encode(),
+        ));
+        assert!(grandpa_change(&forced).is_err());
+        assert!(grandpa_change(&header(101, H256::zero()))
+            .expect("plain header")
+            .is_none());
+    }
+
+    #[test]
+    fn read_proof_is_checked_against_trusted_state_root() {
+        // 值超过 32 字节，叶子节点不会内联进父节点，未请求的 key 不会随证明带出。
+        let storage = BTreeMap::from([
+            (b"citizen:1".to_vec(), vec![0xaa; 40]),
+            (b"citizen:2".to_vec(), vec![0xbb; 40]),
+        ]);
+        let backend =
+            sp_state_machine::InMemoryBackend::<BlakeTwo256>::from((storage, StateVersion::V0));
+        let root = *backend.root();
+        let keys = vec![b"citizen:1".to_vec(), b"citizen:9".to_vec()];
+        let proof = sp_state_machine::prove_read(backend, keys.iter())
+            .expect("prove read")
+            .into_iter_nodes()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            check_read_proof(root, proof.clone(), &keys).expect("valid proof"),
+            vec![Some(vec![0xaa; 40]), None]
+        );
+        // RPC 伪造 state_root 之外的数据时证明不成立。
+        assert!(check_read_proof(H256::repeat_byte(0x55), proof.clone(), &keys).is_err());
+        // 证明未覆盖的 key 不能被当作"不存在"。
+        assert!(check_read_proof(root, proof, &[b"citizen:2".to_vec()]).is_err());
+    }
+
+    #[test]
+    fn key_enumeration_must_be_covered_by_proof() {
+        let storage = (1..=3u8)
+            .map(|n| (format!("citizen:{n}").into_bytes(), vec![n; 40]))
+            .chain([(b"other:1".to_vec(), vec![0xcc; 40])])
+            .collect::<BTreeMap<_, _>>();
+        let backend = || {
+            sp_state_machine::InMemoryBackend::<BlakeTwo256>::from((
+                storage.clone(),
+                StateVersion::V0,
+            ))
+        };
+        let root = *backend().root();
+        let prove = |keys: &[&[u8]]| {
+            sp_state_machine::prove_read(backend(), keys.iter())
+                .expect("prove read")
+                .into_iter_nodes()
+                .collect::<Vec<_>>()
+        };
+        let prefix = b"citizen:".as_slice();
+        let all = (1..=3u8)
+            .map(|n| format!("citizen:{n}").into_bytes())
+            .collect::<Vec<_>>();
+
+        let full = prove(&[prefix, b"citizen:1", b"citizen:2", b"citizen:3"]);
+        assert_eq!(
+            check_key_enumeration(root, full.clone(), prefix, None, 10).expect("full listing"),
+            all
+        );
+        // 分页:从上一页末尾 key 之后继续,满一页即停止。
+        assert_eq!(
+            check_key_enumeration(root, full, prefix, Some(b"citizen:1".as_slice()), 1)
+                .expect("second page"),
+            all[1..2].to_vec()
+        );
+        // RPC 漏报中间的 key 时,证明缺少对应节点,重放失败。
+        let partial = prove(&[prefix, b"citizen:1", b"citizen:3"]);
+        assert!(check_key_enumeration(root, partial, prefix, None, 10).is_err());
+        // 前缀下确实没有 key 时,前缀路径本身即可证明为空。
+        let empty = prove(&[b"absent:".as_slice()]);
+        assert!(check_key_enumeration(root, empty, b"absent:", None, 10)
+            .expect("empty listing")
+            .is_empty());
+    }
+
+    #[test]
+    fn metadata_is_served_from_compiled_runtime_only() {
+        let versions = Vec::<u32>::decode_all(
+            &mut &local_metadata_call("Metadata_metadata_versions", &[]).expect("versions")[..],
+        )
+        .expect("decode versions");
+        let latest = *versions
+            .iter()
+            .max()
+            .expect("at least one metadata version");
+        let at_version = local_metadata_call("Metadata_metadata_at_version", &latest.encode())
+            .expect("metadata");
+        assert!(Option::<OpaqueMetadata>::decode_all(&mut &at_version[..])
+            .expect("decode metadata")
+            .is_some());
+        assert!(local_metadata_call("TransactionPaymentApi_query_info", &[]).is_err());
+    }
+
+    #[test]
+    fn persisted_state_round_trips_and_rejects_foreign_chain() {
+        let genesis = header(0, H256::zero());
+        let genesis_hash = genesis.hash();
+        let head = header(128, H256::repeat_byte(0x66));
+        let pending_key = sp_core::ed25519::Pair::from_seed(&[9; 32]);
+        let client = LightClient {
+            set: AuthoritySet {
+                set_id: 2,
+                authorities: genesis_authority_set().authorities,
+            },
+            head: TrustedHeader::of(&head),
+            pending: Some(PendingChange {
+                enact_at: 200,
+                next_authorities: vec![(AuthorityId::from(pending_key.public()), 1)],
+            }),
+            checkpoints: BTreeMap::from([
+                (0, genesis_hash),
+                (64, H256::repeat_byte(0x77)),
+                (128, head.hash()),
+            ]),
+            recent: HashMap::new(),
+            last_sync: None,
+            genesis_hash,
+            persisted: (0, 0),
+        };
+        let bytes = client.to_persisted().encode();
+
+        let restored = LightClient::from_persisted(
+            genesis_hash,
+            PersistedLightClient::decode_all(&mut &bytes[..]).expect("decode state"),
+        )
+        .expect("restore");
+        assert_eq!(restored.head, client.head);
+        assert_eq!(restored.set.set_id, 2);
+        assert_eq!(restored.checkpoints, client.checkpoints);
+        assert_eq!(restored.pending.map(|change| change.enact_at), Some(200));
+        assert_eq!(restored.persisted, (128, 2));
+
+        let foreign = PersistedLightClient::decode_all(&mut &bytes[..]).expect("decode state");
+        assert!(LightClient::from_persisted(H256::repeat_byte(0x99), foreign).is_err());
+        let mut stale = client.to_persisted();
+        stale.head_number = 192;
+        assert!(LightClient::from_persisted(genesis_hash, stale).is_err());
+    }
+}
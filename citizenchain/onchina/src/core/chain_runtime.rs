@@ -2,7 +2,6 @@ use codec::Decode;
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, hash::Hasher, sync::OnceLock};
 use subxt::backend::legacy::LegacyRpcMethods;
-use subxt::backend::rpc::RpcClient;
 use subxt::{dynamic, OnlineClient, PolkadotConfig};
 use twox_hash::XxHash64;
 
@@ -207,6 +206,13 @@ fn parse_header_number(raw: &str) -> Result<i64, String> {
 
 /// 读取当前 finalized head 作为链投影版本锚点。
 pub(crate) async fn fetch_finalized_anchor() -> Result<ChainFinalizedAnchor, String> {
+    if super::chain_proof::proof_verification_enabled() {
+        let head = super::chain_proof::verified_finalized_head().await?;
+        return Ok(ChainFinalizedAnchor {
+            block_hash: head.hash_hex(),
+            block_number: i64::from(head.number),
+        });
+    }
     let http_url = super::chain_url::chain_http_url()?;
     let client = reqwest::Client::new();
     let block_hash = fetch_finalized_head_via_http(&client, http_url.as_str()).await?;
@@ -282,15 +288,51 @@ fn decode_scale_u128(storage_hex: &str) -> Result<u128, String> {
 /// 不读取 best head，不使用 PostgreSQL 缓存；任一 RPC 错误由上层 fail-closed 处理。
 pub(crate) async fn fetch_platform_membership_snapshot(
 ) -> Result<PlatformMembershipSnapshot, String> {
-    let http_url = super::chain_url::chain_http_url()?;
-    let client = reqwest::Client::new();
-    let block_hash = fetch_finalized_head_via_http(&client, http_url.as_str()).await?;
     // 平台机构 CID 为创世固定常量，不再从链上存储读取；仅批量读取三档 finalized 价格。
     let keys = [
         twox64_concat_storage_map_key(b"SquarePost", b"PlatformPrice", &[0]),
         twox64_concat_storage_map_key(b"SquarePost", b"PlatformPrice", &[1]),
         twox64_concat_storage_map_key(b"SquarePost", b"PlatformPrice", &[2]),
     ];
+    let (block_hash, values) = if super::chain_proof::proof_verification_enabled() {
+        let (head, proven) = super::chain_proof::read_storage_proven(&keys, None).await?;
+        let values = proven
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| ((index + 1) as u64, value.map(hex::encode)))
+            .collect();
+        (head.hash_hex(), values)
+    } else {
+        fetch_platform_prices_via_http(&keys).await?
+    };
+    let decode_price = |id: u64, values: &BTreeMap<u64, Option<String>>| {
+        values
+            .get(&id)
+            .cloned()
+            .flatten()
+            .map(|value| decode_scale_u128(&value))
+            .transpose()
+    };
+    Ok(PlatformMembershipSnapshot {
+        block_hash,
+        // 平台机构永久固定为公民链基金会，CID 单源自创世常量，不读链上存储。
+        platform_cid_number: Some(
+            primitives::cid::china::citizenchain::CITIZENCHAIN_FOUNDATION
+                .cid_number
+                .to_string(),
+        ),
+        freedom_price_fen: decode_price(1, &values)?,
+        democracy_price_fen: decode_price(2, &values)?,
+        spark_price_fen: decode_price(3, &values)?,
+    })
+}
+
+async fn fetch_platform_prices_via_http(
+    keys: &[Vec<u8>],
+) -> Result<(String, BTreeMap<u64, Option<String>>), String> {
+    let http_url = super::chain_url::chain_http_url()?;
+    let client = reqwest::Client::new();
+    let block_hash = fetch_finalized_head_via_http(&client, http_url.as_str()).await?;
     let requests = keys
         .iter()
         .enumerate()
@@ -331,29 +373,10 @@ pub(crate) async fn fetch_platform_membership_snapshot(
         };
         values.insert(item.id, value);
     }
-    let decode_price = |id: u64, values: &BTreeMap<u64, Option<String>>| {
-        values
-            .get(&id)
-            .cloned()
-            .flatten()
-            .map(|value| decode_scale_u128(&value))
-            .transpose()
-    };
-    Ok(PlatformMembershipSnapshot {
-        block_hash,
-        // 平台机构永久固定为公民链基金会，CID 单源自创世常量，不读链上存储。
-        platform_cid_number: Some(
-            primitives::cid::china::citizenchain::CITIZENCHAIN_FOUNDATION
-                .cid_number
-                .to_string(),
-        ),
-        freedom_price_fen: decode_price(1, &values)?,
-        democracy_price_fen: decode_price(2, &values)?,
-        spark_price_fen: decode_price(3, &values)?,
-    })
+    Ok((block_hash, values))
 }
 
-fn system_account_storage_key(account_id: &[u8; 32]) -> String {
+fn system_account_storage_key_bytes(account_id: &[u8; 32]) -> Vec<u8> {
     let pallet_hash = twox_128(b"System");
     let storage_hash = twox_128(b"Account");
     let account_hash = sp_core::hashing::blake2_128(account_id);
@@ -362,7 +385,14 @@ fn system_account_storage_key(account_id: &[u8; 32]) -> String {
     key.extend_from_slice(&storage_hash);
     key.extend_from_slice(&account_hash);
     key.extend_from_slice(account_id);
-    format!("0x{}", hex::encode(key))
+    key
+}
+
+fn system_account_storage_key(account_id: &[u8; 32]) -> String {
+    format!(
+        "0x{}",
+        hex::encode(system_account_storage_key_bytes(account_id))
+    )
 }
 
 fn decode_account_free_balance_fen(storage_hex: &str) -> Result<Option<String>, String> {
@@ -403,6 +433,21 @@ pub(crate) async fn fetch_account_balances_onchain(
         return Ok(result);
     }
 
+    if super::chain_proof::proof_verification_enabled() {
+        let keys = unique_accounts
+            .values()
+            .map(system_account_storage_key_bytes)
+            .collect::<Vec<_>>();
+        let (_, values) = super::chain_proof::read_storage_proven(&keys, None).await?;
+        for (account_id, value) in unique_accounts.keys().zip(values) {
+            let balance = value
+                .and_then(|bytes| decode_account_free_balance_fen(&hex::encode(bytes)).ok())
+                .flatten();
+            result.insert(account_id.clone(), balance);
+        }
+        return Ok(result);
+    }
+
     let http_url = super::chain_url::chain_http_url()?;
     let client = reqwest::Client::new();
     let finalized_hash = fetch_finalized_head_via_http(&client, http_url.as_str()).await?;
@@ -666,7 +711,7 @@ pub(crate) struct FinalizedChainView {
 impl FinalizedChainView {
     pub(crate) async fn connect() -> Result<Self, String> {
         let ws_url = super::chain_url::chain_ws_url()?;
-        let rpc_client = RpcClient::from_insecure_url(ws_url.as_str())
+        let rpc_client = super::chain_proof::connect_rpc(ws_url.as_str())
             .await
             .map_err(|e| format!("connect chain rpc for finalized admin view failed: {e}"))?;
         let rpc = LegacyRpcMethods::<PolkadotConfig>::new(rpc_client.clone());
@@ -690,6 +735,19 @@ impl FinalizedChainView {
         })
     }
 
+    /// 固定到调用方已确认为 finalized 的历史区块(如审计锚点所在块),按当时状态读取名册。
+    pub(crate) fn at_finalized_block(
+        client: OnlineClient<PolkadotConfig>,
+        block_hash: subxt::utils::H256,
+        block_number: u32,
+    ) -> Self {
+        Self {
+            client,
+            block_hash,
+            block_number,
+        }
+    }
+
     pub(crate) fn storage(
         &self,
     ) -> subxt::storage::Storage<PolkadotConfig, OnlineClient<PolkadotConfig>> {
@@ -1248,7 +1306,7 @@ pub(crate) async fn institution_lookup(
     cid_number: &str,
 ) -> Result<Option<OnChainInstitution>, String> {
     let ws_url = super::chain_url::chain_ws_url()?;
-    let client = OnlineClient::<PolkadotConfig>::from_insecure_url(ws_url.as_str())
+    let client = super::chain_proof::online_client(ws_url.as_str())
         .await
         .map_err(|e| format!("connect chain ws for institutions failed: {e}"))?;
     let storage = client
@@ -1289,7 +1347,7 @@ pub(crate) async fn for_each_chain_institution(
     mut f: impl FnMut(Vec<u8>, OnChainInstitution),
 ) -> Result<usize, String> {
     let ws_url = super::chain_url::chain_ws_url()?;
-    let client = OnlineClient::<PolkadotConfig>::from_insecure_url(ws_url.as_str())
+    let client = super::chain_proof::online_client(ws_url.as_str())
         .await
         .map_err(|e| format!("connect chain ws for institutions failed: {e}"))?;
     let storage = client
@@ -1335,7 +1393,7 @@ pub(crate) async fn for_each_chain_institution_account(
     mut f: impl FnMut(OnChainInstitutionAccount),
 ) -> Result<usize, String> {
     let ws_url = super::chain_url::chain_ws_url()?;
-    let client = OnlineClient::<PolkadotConfig>::from_insecure_url(ws_url.as_str())
+    let client = super::chain_proof::online_client(ws_url.as_str())
         .await
         .map_err(|e| format!("connect chain ws for institution accounts failed: {e}"))?;
     let storage = client
@@ -1394,7 +1452,7 @@ pub(crate) async fn institution_accounts_lookup(
         "PublicManage"
     };
     let ws_url = super::chain_url::chain_ws_url()?;
-    let client = OnlineClient::<PolkadotConfig>::from_insecure_url(ws_url.as_str())
+    let client = super::chain_proof::online_client(ws_url.as_str())
         .await
         .map_err(|e| format!("connect chain ws for institution accounts failed: {e}"))?;
     let storage = client
@@ -1561,7 +1619,7 @@ pub(crate) async fn for_each_chain_citizen_cid_in_scope(
         _revoked_at: Option<u32>,
     }
     let ws_url = super::chain_url::chain_ws_url()?;
-    let client = OnlineClient::<PolkadotConfig>::from_insecure_url(ws_url.as_str())
+    let client = super::chain_proof::online_client(ws_url.as_str())
         .await
         .map_err(|e| format!("connect chain ws for citizen scan failed: {e}"))?;
     let storage = client
@@ -1606,7 +1664,7 @@ pub(crate) async fn for_each_chain_private_institution_cid(
     mut f: impl FnMut(String),
 ) -> Result<usize, String> {
     let ws_url = super::chain_url::chain_ws_url()?;
-    let client = OnlineClient::<PolkadotConfig>::from_insecure_url(ws_url.as_str())
+    let client = super::chain_proof::online_client(ws_url.as_str())
         .await
         .map_err(|e| format!("connect chain ws for private institutions failed: {e}"))?;
     let storage = client
@@ -4,6 +4,10 @@
 //! OnChina 回扫后由本模块「重建签名材料 → 本地 sr25519 验签 → system_dryRun
 //! 拒 Future/Stale → submit-and-watch → finalized + ExtrinsicSuccess」。
 //! 签名材料构建统一调用 `chain-signing`,避免 OnChina 和 node 各自拼 payload。
+//!
+//! 冷签交易一律 mortal era(出生块 = prepare 时最佳块),nonce 经 `chain_nonce_reservations`
+//! 预留:同一管理员并发发起的多个会话各占一个 nonce,未提交的预留随会话 TTL
+//! 过期回收,已提交的在链上 nonce 越过或 era 失效后回收。
 
 use codec::Encode;
 use serde_json::{json, Value};
@@ -11,12 +15,15 @@ use sp_core::crypto::Ss58Codec;
 use subxt::{tx::SubmittableTransaction, OnlineClient, PolkadotConfig};
 
 use super::chain_url::{chain_http_url, chain_ws_url};
+use super::db::{postgres_error_text, Db};
 
 const RPC_TIMEOUT_SECS: u64 = 10;
 /// 客户端等待确认的观察窗口，不是 PoW 最晚出块期限；窗口结束后交易仍可能继续等待进块。
 const WAIT_CONFIRMATION_OBSERVATION_SECS: u64 = 20 * 60;
 /// 单次请求最多扫描的 finalized 块数，防止长期会话把一次 HTTP 请求放大成无界 O(N)。
 const FINALIZED_RECOVERY_BATCH_BLOCKS: usize = 128;
+/// 未提交 nonce 预留的保留时长，不短于冷签会话 TTL(600 秒)。
+const NONCE_RESERVATION_TTL_SECS: i64 = 15 * 60;
 
 #[derive(Debug, PartialEq, Eq)]
 pub(crate) struct FinalizedChainSubmit {
@@ -80,6 +87,198 @@ impl From<String> for ChainSubmitError {
     }
 }
 
+impl Db {
+    /// 串行化同账户预留:先回收失效预留，再分配 `chain_nonce` 起第一个空闲 nonce。
+    ///
+    /// `sign` 按分配到的 nonce 构建签名材料并返回签名哈希，与预留同事务落库，
+    /// 提交阶段凭 (账户, nonce, 签名哈希) 找回本次预留的 era。
+    pub(crate) fn reserve_chain_nonce(
+        &self,
+        account_id: &str,
+        chain_nonce: u32,
+        best_block: u64,
+        lifetime: chain_signing::TxLifetime,
+        sign: impl FnOnce(u32) -> Result<String, String> + Send,
+    ) -> Result<(u32, String), String> {
+        let chain_signing::TxLifetime::Mortal {
+            birth_block,
+            birth_hash,
+            ..
+        } = lifetime
+        else {
+            return Err("冷签交易必须使用 mortal era".to_string());
+        };
+        let death_block = lifetime.death_block().ok_or("mortal era 缺少失效块")?;
+        let account_id = account_id.to_string();
+        self.with_client(move |conn| {
+            let mut tx = conn.transaction().map_err(|e| {
+                format!(
+                    "begin nonce reservation failed: {}",
+                    postgres_error_text(&e)
+                )
+            })?;
+            tx.execute(
+                "SELECT pg_advisory_xact_lock(hashtext('chain_nonce_reservations:' || $1))",
+                &[&account_id],
+            )
+            .map_err(|e| format!("lock nonce reservation failed: {}", postgres_error_text(&e)))?;
+            tx.execute(
+                "DELETE FROM chain_nonce_reservations
+                 WHERE account_id = $1
+                   AND (nonce < $2
+                        OR (submitted_at IS NULL AND expires_at < now())
+                        OR (submitted_at IS NOT NULL AND era_death_block <= $3))",
+                &[&account_id, &i64::from(chain_nonce), &(best_block as i64)],
+            )
+            .map_err(|e| {
+                format!(
+                    "reclaim nonce reservations failed: {}",
+                    postgres_error_text(&e)
+                )
+            })?;
+            let reserved: Vec<i64> = tx
+                .query(
+                    "SELECT nonce FROM chain_nonce_reservations WHERE account_id = $1 ORDER BY nonce",
+                    &[&account_id],
+                )
+                .map_err(|e| {
+                    format!(
+                        "query nonce reservations failed: {}",
+                        postgres_error_text(&e)
+                    )
+                })?
+                .iter()
+                .map(|row| row.get(0))
+                .collect();
+            let mut nonce = chain_nonce;
+            while reserved.contains(&i64::from(nonce)) {
+                nonce = nonce.checked_add(1).ok_or("账户 nonce 已耗尽")?;
+            }
+            let signing_hash = sign(nonce)?;
+            tx.execute(
+                "INSERT INTO chain_nonce_reservations
+                    (account_id, nonce, signing_hash, era_birth_block, era_birth_hash,
+                     era_death_block, expires_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, now() + make_interval(secs => $7))",
+                &[
+                    &account_id,
+                    &i64::from(nonce),
+                    &signing_hash,
+                    &(birth_block as i64),
+                    &hex::encode(birth_hash.0),
+                    &(death_block as i64),
+                    &(NONCE_RESERVATION_TTL_SECS as f64),
+                ],
+            )
+            .map_err(|e| {
+                format!(
+                    "insert nonce reservation failed: {}",
+                    postgres_error_text(&e)
+                )
+            })?;
+            tx.commit().map_err(|e| {
+                format!(
+                    "commit nonce reservation failed: {}",
+                    postgres_error_text(&e)
+                )
+            })?;
+            Ok((nonce, signing_hash))
+        })
+    }
+
+    /// 按 (账户, nonce, 签名哈希) 找回预留的交易有效期；已被回收或改派给其他会话时返回 `None`。
+    pub(crate) fn find_chain_nonce(
+        &self,
+        account_id: &str,
+        nonce: u32,
+        signing_hash: &str,
+    ) -> Result<Option<chain_signing::TxLifetime>, String> {
+        let account_id = account_id.to_string();
+        let signing_hash = signing_hash.to_string();
+        self.with_client(move |conn| {
+            let row = conn
+                .query_opt(
+                    "SELECT era_birth_block, era_birth_hash, era_death_block
+                     FROM chain_nonce_reservations
+                     WHERE account_id = $1 AND nonce = $2 AND signing_hash = $3",
+                    &[&account_id, &i64::from(nonce), &signing_hash],
+                )
+                .map_err(|e| {
+                    format!(
+                        "query nonce reservation failed: {}",
+                        postgres_error_text(&e)
+                    )
+                })?;
+            let Some(row) = row else {
+                return Ok(None);
+            };
+            let birth_block = row.get::<_, i64>(0) as u64;
+            let birth_hash = hex::decode(row.get::<_, String>(1))
+                .ok()
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes.as_slice()).ok())
+                .ok_or("nonce 预留出生块哈希损坏")?;
+            let period = (row.get::<_, i64>(2) as u64).saturating_sub(birth_block);
+            chain_signing::TxLifetime::mortal(period, birth_block, sp_core::H256(birth_hash))
+                .map(Some)
+        })
+    }
+
+    /// 交易即将进池：预留转为已提交，此后只随 nonce 消费或 era 失效回收。
+    pub(crate) fn mark_chain_nonce_submitted(
+        &self,
+        account_id: &str,
+        nonce: u32,
+        signing_hash: &str,
+    ) -> Result<(), String> {
+        let account_id = account_id.to_string();
+        let signing_hash = signing_hash.to_string();
+        self.with_client(move |conn| {
+            conn.execute(
+                "UPDATE chain_nonce_reservations SET submitted_at = COALESCE(submitted_at, now())
+                 WHERE account_id = $1 AND nonce = $2 AND signing_hash = $3",
+                &[&account_id, &i64::from(nonce), &signing_hash],
+            )
+            .map_err(|e| format!("mark nonce submitted failed: {}", postgres_error_text(&e)))?;
+            Ok(())
+        })
+    }
+
+    /// `[next_index, nonce)` 中每个 nonce 都是已提交的预留。
+    fn chain_nonce_predecessors_submitted(
+        &self,
+        account_id: &str,
+        next_index: u32,
+        nonce: u32,
+    ) -> Result<bool, String> {
+        let account_id = account_id.to_string();
+        self.with_client(move |conn| {
+            let submitted: i64 = conn
+                .query_one(
+                    "SELECT count(*) FROM chain_nonce_reservations
+                     WHERE account_id = $1 AND nonce >= $2 AND nonce < $3
+                       AND submitted_at IS NOT NULL",
+                    &[&account_id, &i64::from(next_index), &i64::from(nonce)],
+                )
+                .map_err(|e| {
+                    format!(
+                        "query nonce predecessors failed: {}",
+                        postgres_error_text(&e)
+                    )
+                })?
+                .get(0);
+            Ok(submitted == i64::from(nonce) - i64::from(next_index))
+        })
+    }
+}
+
+/// 冷签交易有效期(区块数),可用 `ONCHINA_TX_MORTAL_PERIOD` 覆盖，必须是 4..=2048 的 2 的幂。
+fn mortal_period() -> u64 {
+    std::env::var("ONCHINA_TX_MORTAL_PERIOD")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(chain_signing::DEFAULT_MORTAL_PERIOD)
+}
+
 /// prepare 阶段产物:随会话持久化,submit 阶段重建校验。
 pub(crate) struct PreparedChainSign {
     pub nonce: u32,
@@ -139,7 +338,30 @@ fn public_key_to_ss58(account_id: &str) -> Result<String, String> {
     Ok(public.to_ss58check_with_version(sp_core::crypto::Ss58AddressFormat::custom(2027)))
 }
 
-/// 实时读链上 nonce(死规则 P-SIGN-001:nonce 只来自链,不缓存不自增)。
+/// 读最佳块号与其哈希，作为 mortal era 出生块。
+async fn fetch_best_block() -> Result<(u64, [u8; 32]), String> {
+    let header = rpc_post("chain_getHeader", Value::Array(vec![])).await?;
+    let number = header
+        .get("number")
+        .and_then(Value::as_str)
+        .ok_or("best header missing number")?;
+    let number = u64::from_str_radix(number.trim_start_matches("0x"), 16)
+        .map_err(|e| format!("best block number malformed: {e}"))?;
+    let result = rpc_post(
+        "chain_getBlockHash",
+        Value::Array(vec![Value::from(number)]),
+    )
+    .await?;
+    let text = result.as_str().ok_or("block hash malformed")?;
+    let bytes = hex::decode(text.strip_prefix("0x").unwrap_or(text))
+        .map_err(|e| format!("block hash decode failed: {e}"))?;
+    let hash = <[u8; 32]>::try_from(bytes.as_slice())
+        .map_err(|_| "block hash must be 32 bytes".to_string())?;
+    Ok((number, hash))
+}
+
+/// 实时读链上 next index(死规则 P-SIGN-001:起点只来自链，不缓存不自增；
+/// 其上的并发会话由 `chain_nonce_reservations` 预留错开)。
 pub(crate) async fn fetch_nonce(account_id: &str) -> Result<u32, String> {
     let ss58 = public_key_to_ss58(account_id)?;
     let result = rpc_post(
@@ -153,30 +375,42 @@ pub(crate) async fn fetch_nonce(account_id: &str) -> Result<u32, String> {
         .ok_or_else(|| "accountNextIndex malformed".to_string())
 }
 
-/// prepare:实时取 nonce/版本/创世哈希,构建 QR 审阅载荷与签名校验哈希。
+/// prepare:实时取版本/创世哈希/最佳块，预留 nonce 并构建 mortal era 审阅载荷与签名校验哈希。
 pub(crate) async fn prepare_signing(
+    db: &Db,
     call_data: &[u8],
     account_id: &str,
 ) -> Result<PreparedChainSign, String> {
-    let nonce = fetch_nonce(account_id).await?;
+    let chain_nonce = fetch_nonce(account_id).await?;
     let (spec_version, tx_version) = fetch_runtime_version().await?;
     let genesis_hash = fetch_genesis_hash().await?;
-    let material = chain_signing::build_signing_material(
-        call_data,
-        &genesis_hash,
-        nonce,
-        spec_version,
-        tx_version,
-    )?;
+    let (best_block, best_hash) = fetch_best_block().await?;
+    let lifetime =
+        chain_signing::TxLifetime::mortal(mortal_period(), best_block, sp_core::H256(best_hash))?;
+    let mut payload = Vec::new();
+    let (nonce, signing_hash_hex) =
+        db.reserve_chain_nonce(account_id, chain_nonce, best_block, lifetime, |nonce| {
+            let material = chain_signing::build_signing_material_with_lifetime(
+                call_data,
+                &genesis_hash,
+                nonce,
+                spec_version,
+                tx_version,
+                &lifetime,
+            )?;
+            payload = material.payload;
+            Ok(chain_signing::sha256_hex(&material.signing_bytes))
+        })?;
     Ok(PreparedChainSign {
         nonce,
-        signing_hash_hex: chain_signing::sha256_hex(&material.signing_bytes),
-        payload: material.payload,
+        signing_hash_hex,
+        payload,
     })
 }
 
 /// submit:重建材料校验哈希 → 本地验签 → dry-run → 提交,返回交易哈希。
 pub(crate) async fn assemble_and_submit<F>(
+    db: &Db,
     call_data: &[u8],
     account_id: &str,
     signature_hex: &str,
@@ -187,14 +421,20 @@ pub(crate) async fn assemble_and_submit<F>(
 where
     F: FnOnce(&SubmissionAttempt) -> Result<(), String>,
 {
+    let lifetime = db
+        .find_chain_nonce(account_id, nonce, expected_signing_hash_hex)?
+        .ok_or_else(|| "该签名请求的 nonce 预留已过期回收,请重新发起".to_string())?;
+    let (best_block, _) = fetch_best_block().await?;
+    lifetime.ensure_alive(best_block)?;
     let (spec_version, tx_version) = fetch_runtime_version().await?;
     let genesis_hash = fetch_genesis_hash().await?;
-    let material = chain_signing::build_signing_material(
+    let material = chain_signing::build_signing_material_with_lifetime(
         call_data,
         &genesis_hash,
         nonce,
         spec_version,
         tx_version,
+        &lifetime,
     )?;
     // 会话期间 runtime 版本/创世哈希不得漂移,否则签名对不上载荷。
     if chain_signing::sha256_hex(&material.signing_bytes) != expected_signing_hash_hex {
@@ -231,7 +471,10 @@ where
             if bytes.is_empty() {
                 return Err("dry-run 返回空结果,拒绝提交".to_string().into());
             }
-            if bytes[0] != 0x00 {
+            // Future 只在前序 nonce 全部是已提交预留时放行：本交易排在其后进块。
+            let queued_behind_reservations = bytes.starts_with(&[0x01, 0x00, 0x02])
+                && future_nonce_is_queued(db, account_id, nonce).await?;
+            if bytes[0] != 0x00 && !queued_behind_reservations {
                 return Err(chain_signing::dry_run_reject_message(&bytes, raw).into());
             }
             if bytes.len() > 1 && bytes[1] != 0x00 {
@@ -263,6 +506,7 @@ where
     };
     // 在任何网络提交前先持久化 tx_hash 与 finalized 扫描锚点；DB 失败时绝不提交。
     before_submit(&attempt)?;
+    db.mark_chain_nonce_submitted(account_id, nonce, expected_signing_hash_hex)?;
     // submit-and-watch 精确绑定本次 extrinsic；只有进入 finalized block 且出现
     // System.ExtrinsicSuccess 才返回，Dropped/Invalid/ExtrinsicFailed/超时全部失败。
     let finalized_result = tokio::time::timeout(
@@ -312,6 +556,18 @@ where
     })
 }
 
+/// next index 已含就绪队列：等于本 nonce 即前序都在池中；小于时缺口必须全是已提交预留。
+async fn future_nonce_is_queued(db: &Db, account_id: &str, nonce: u32) -> Result<bool, String> {
+    let next_index = fetch_nonce(account_id).await?;
+    if next_index > nonce {
+        return Err(format!(
+            "nonce {nonce} 已被其他交易占用(next={next_index}),请重新发起"
+        ));
+    }
+    Ok(next_index == nonce
+        || db.chain_nonce_predecessors_submitted(account_id, next_index, nonce)?)
+}
+
 /// 恢复 submit-and-watch 断线/超时后的结果：从 finalized head（或持久化游标）
 /// 分批向提交前锚点回扫，并要求目标 extrinsic 自身存在 System.ExtrinsicSuccess。
 /// 只有确实扫描到锚点才返回 Exhausted；中途必须返回并持久化下一批游标。
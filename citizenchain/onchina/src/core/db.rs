@@ -142,7 +142,8 @@ impl Db {
                     CHECK (creator_account_id ~ '^0x[0-9a-f]{64}$'),
                 created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
                 updated_at TIMESTAMPTZ,
-                city_name TEXT NOT NULL DEFAULT ''
+                city_name TEXT NOT NULL DEFAULT '',
+                read_only BOOLEAN NOT NULL DEFAULT FALSE
              );
              CREATE INDEX IF NOT EXISTS idx_admins_institution_code
                 ON admins(institution_code);
@@ -282,11 +283,23 @@ impl Db {
              CREATE INDEX IF NOT EXISTS idx_chain_requests_received
                 ON chain_requests(received_at);
 
-             CREATE TABLE IF NOT EXISTS chain_nonces (
-                nonce TEXT PRIMARY KEY,
-                seen_at TIMESTAMPTZ NOT NULL
+             -- 旧版 `chain_nonces(nonce TEXT PRIMARY KEY, seen_at)` 已无读写方,先删再建新表,
+             -- 避免 CREATE TABLE IF NOT EXISTS 遇到旧表静默跳过、预留写入时报列不存在。
+             DROP TABLE IF EXISTS chain_nonces;
+             CREATE TABLE IF NOT EXISTS chain_nonce_reservations (
+                account_id TEXT NOT NULL CHECK (account_id ~ '^0x[0-9a-f]{64}$'),
+                nonce BIGINT NOT NULL CHECK (nonce >= 0),
+                signing_hash TEXT NOT NULL,
+                era_birth_block BIGINT NOT NULL,
+                era_birth_hash TEXT NOT NULL,
+                era_death_block BIGINT NOT NULL CHECK (era_death_block > era_birth_block),
+                reserved_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                expires_at TIMESTAMPTZ NOT NULL,
+                submitted_at TIMESTAMPTZ,
+                PRIMARY KEY (account_id, nonce)
              );
-             CREATE INDEX IF NOT EXISTS idx_chain_nonces_seen ON chain_nonces(seen_at);
+             CREATE INDEX IF NOT EXISTS idx_chain_nonce_reservations_pending
+                ON chain_nonce_reservations(expires_at) WHERE submitted_at IS NULL;
 
              CREATE TABLE IF NOT EXISTS tx_records (
                 id BIGSERIAL PRIMARY KEY,
@@ -298,11 +311,15 @@ impl Db {
                     CHECK (sender_account_id IS NULL OR sender_account_id ~ '^0x[0-9a-f]{64}$'),
                 recipient_account_id TEXT
                     CHECK (recipient_account_id IS NULL OR recipient_account_id ~ '^0x[0-9a-f]{64}$'),
-                amount_fen BIGINT NOT NULL,
-                fee_fen BIGINT,
+                amount_fen NUMERIC(39, 0) NOT NULL,
+                fee_fen NUMERIC(39, 0),
                 block_timestamp TIMESTAMPTZ,
                 created_at TIMESTAMPTZ NOT NULL DEFAULT now()
              );
+             -- 链上余额为 u128，早期以 BIGINT 建的表就地放宽，避免大额被截断。
+             ALTER TABLE tx_records
+                ALTER COLUMN amount_fen TYPE NUMERIC(39, 0),
+                ALTER COLUMN fee_fen TYPE NUMERIC(39, 0);
              CREATE INDEX IF NOT EXISTS idx_tx_records_sender_account_id
                 ON tx_records(sender_account_id, block_number DESC);
              CREATE INDEX IF NOT EXISTS idx_tx_records_recipient_account_id
@@ -319,6 +336,53 @@ impl Db {
              INSERT INTO tx_indexer_state(id, last_indexed_block)
              VALUES (1, 0) ON CONFLICT (id) DO NOTHING;
 
+             CREATE TABLE IF NOT EXISTS chain_invoices (
+                invoice_id BIGINT PRIMARY KEY,
+                issuer_account_id TEXT NOT NULL CHECK (issuer_account_id ~ '^0x[0-9a-f]{64}$'),
+                payer_account_id TEXT
+                    CHECK (payer_account_id IS NULL OR payer_account_id ~ '^0x[0-9a-f]{64}$'),
+                amount_fen NUMERIC(39, 0) NOT NULL CHECK (amount_fen > 0),
+                paid_fen NUMERIC(39, 0) NOT NULL DEFAULT 0 CHECK (paid_fen >= 0),
+                deposit_fen NUMERIC(39, 0) NOT NULL DEFAULT 0,
+                due_block BIGINT NOT NULL,
+                memo_hash TEXT NOT NULL,
+                status TEXT NOT NULL
+                    CHECK (status IN ('OPEN', 'PARTIALLY_PAID', 'PAID', 'CANCELLED', 'EXPIRED',
+                        'DECLINED')),
+                created_block BIGINT NOT NULL,
+                closed_block BIGINT,
+                created_at TIMESTAMPTZ,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+             );
+             -- 同上放宽金额列，并补上押金列与拒付状态。
+             ALTER TABLE chain_invoices
+                ALTER COLUMN amount_fen TYPE NUMERIC(39, 0),
+                ALTER COLUMN paid_fen TYPE NUMERIC(39, 0),
+                ADD COLUMN IF NOT EXISTS deposit_fen NUMERIC(39, 0) NOT NULL DEFAULT 0,
+                DROP CONSTRAINT IF EXISTS chain_invoices_status_check,
+                ADD CONSTRAINT chain_invoices_status_check
+                    CHECK (status IN ('OPEN', 'PARTIALLY_PAID', 'PAID', 'CANCELLED', 'EXPIRED',
+                        'DECLINED'));
+             CREATE INDEX IF NOT EXISTS idx_chain_invoices_issuer
+                ON chain_invoices(issuer_account_id, invoice_id DESC);
+             CREATE INDEX IF NOT EXISTS idx_chain_invoices_payer
+                ON chain_invoices(payer_account_id, invoice_id DESC);
+
+             CREATE TABLE IF NOT EXISTS chain_invoice_payments (
+                id BIGSERIAL PRIMARY KEY,
+                invoice_id BIGINT NOT NULL,
+                block_number BIGINT NOT NULL,
+                extrinsic_index SMALLINT,
+                event_index SMALLINT NOT NULL,
+                payer_account_id TEXT NOT NULL CHECK (payer_account_id ~ '^0x[0-9a-f]{64}$'),
+                amount_fen NUMERIC(39, 0) NOT NULL,
+                block_timestamp TIMESTAMPTZ,
+                UNIQUE (block_number, event_index)
+             );
+             ALTER TABLE chain_invoice_payments ALTER COLUMN amount_fen TYPE NUMERIC(39, 0);
+             CREATE INDEX IF NOT EXISTS idx_chain_invoice_payments_invoice
+                ON chain_invoice_payments(invoice_id, block_number);
+
              CREATE TABLE IF NOT EXISTS chain_projection_state (
                 projection_key TEXT PRIMARY KEY,
                 chain_genesis_hash TEXT NOT NULL,
@@ -529,8 +593,33 @@ impl Db {
                 target_cid TEXT,
                 detail JSONB NOT NULL,
                 created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                prev_hash TEXT NOT NULL CHECK (prev_hash ~ '^0x[0-9a-f]{64}$'),
+                entry_hash TEXT NOT NULL CHECK (entry_hash ~ '^0x[0-9a-f]{64}$'),
                 PRIMARY KEY (province_code, id)
              ) PARTITION BY LIST (province_code);
+             -- 每省审计哈希链链头;追加时行锁串行化,同时用于发现尾部截断。
+             CREATE TABLE IF NOT EXISTS audit_chain_heads (
+                province_code TEXT PRIMARY KEY,
+                last_id BIGINT NOT NULL,
+                last_entry_hash TEXT NOT NULL CHECK (last_entry_hash ~ '^0x[0-9a-f]{64}$')
+             );
+             CREATE TABLE IF NOT EXISTS audit_anchors (
+                anchor_id BIGSERIAL PRIMARY KEY,
+                province_code TEXT NOT NULL,
+                first_id BIGINT NOT NULL,
+                last_id BIGINT NOT NULL,
+                last_entry_hash TEXT NOT NULL,
+                merkle_root TEXT NOT NULL,
+                entry_count BIGINT NOT NULL,
+                remark_hex TEXT NOT NULL,
+                status TEXT NOT NULL CHECK (status IN ('PENDING', 'ANCHORED')),
+                block_number BIGINT,
+                block_hash TEXT,
+                tx_hash TEXT,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                anchored_at TIMESTAMPTZ,
+                UNIQUE (province_code, last_id)
+             );
 
              CREATE TABLE IF NOT EXISTS sequence_counters (
                 seq_key TEXT PRIMARY KEY,
@@ -552,6 +641,43 @@ impl Db {
                 used_at TIMESTAMPTZ,
                 used_by_cid_number TEXT
              );
+             -- 公民居住地跨市迁入记录:indexer 比对 update_voting_identity 等事件所在块与父块得出,
+             -- 档案导入时据此核对移交方是不是公民迁出前的注册局。
+             CREATE TABLE IF NOT EXISTS citizen_residence_moves (
+                cid_number TEXT NOT NULL,
+                block_number BIGINT NOT NULL,
+                block_hash TEXT NOT NULL CHECK (block_hash ~ '^0x[0-9a-f]{64}$'),
+                from_province_code TEXT NOT NULL,
+                from_city_code TEXT NOT NULL,
+                to_province_code TEXT NOT NULL,
+                to_city_code TEXT NOT NULL,
+                recorded_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                PRIMARY KEY (cid_number, block_number)
+             );
+             -- 注册局间公民档案移交:EXPORT = 本局封包待冷签/已交付,IMPORT = 本局已导入(防重放)。
+             CREATE TABLE IF NOT EXISTS citizen_dossier_transfers (
+                transfer_id TEXT NOT NULL,
+                direction TEXT NOT NULL CHECK (direction IN ('EXPORT', 'IMPORT')),
+                cid_number TEXT NOT NULL,
+                sender_cid_number TEXT NOT NULL,
+                recipient_cid_number TEXT NOT NULL,
+                dossier_digest TEXT NOT NULL CHECK (dossier_digest ~ '^0x[0-9a-f]{64}$'),
+                header_json TEXT NOT NULL,
+                ciphertext BYTEA,
+                payload_text TEXT NOT NULL,
+                payload_hash TEXT NOT NULL,
+                actor_account_id TEXT NOT NULL
+                    CHECK (actor_account_id ~ '^0x[0-9a-f]{64}$'),
+                signer_account_id TEXT
+                    CHECK (signer_account_id IS NULL OR signer_account_id ~ '^0x[0-9a-f]{64}$'),
+                signature TEXT,
+                document_count INTEGER NOT NULL DEFAULT 0,
+                status TEXT NOT NULL CHECK (status IN ('PENDING', 'EXPORTED', 'IMPORTED')),
+                expires_at TIMESTAMPTZ,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                completed_at TIMESTAMPTZ,
+                PRIMARY KEY (direction, transfer_id)
+             );
 
              CREATE INDEX IF NOT EXISTS idx_passport_number_recycle_available
                 ON passport_number_recycle_pool(released_at, pool_id) WHERE used_at IS NULL;
@@ -584,6 +710,8 @@ impl Db {
                 ON citizen_documents(province_code, cid_number, uploaded_at DESC, id DESC);
              CREATE INDEX IF NOT EXISTS idx_citizen_documents_type
                 ON citizen_documents(province_code, cid_number, document_type);
+             CREATE INDEX IF NOT EXISTS idx_citizen_dossier_transfers_cid
+                ON citizen_dossier_transfers(cid_number, created_at DESC);
              CREATE INDEX IF NOT EXISTS idx_gov_city
                 ON gov(province_code, city_code, institution_code);
              CREATE INDEX IF NOT EXISTS idx_private_city
@@ -631,3 +759,70 @@ impl Db {
         Ok(())
     }
 }
+
+#[cfg(test)]
+// 需要真实 PostgreSQL:设置 ONCHINA_TEST_DATABASE_URL 后以 `cargo test -- --ignored` 运行。
+#[allow(clippy::expect_used, clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "需要 ONCHINA_TEST_DATABASE_URL 指向可写的 PostgreSQL"]
+    fn schema_init_replaces_legacy_chain_nonces_table() {
+        let url = std::env::var("ONCHINA_TEST_DATABASE_URL")
+            .expect("ONCHINA_TEST_DATABASE_URL must be set");
+        let mut conn = postgres::Client::connect(url.as_str(), postgres::NoTls).expect("connect");
+        let schema = format!("schema_init_{}", std::process::id());
+        conn.batch_execute(
+            format!(
+                "DROP SCHEMA IF EXISTS {schema} CASCADE;
+                 CREATE SCHEMA {schema};
+                 SET search_path TO {schema};
+                 CREATE TABLE chain_nonces (
+                    nonce TEXT PRIMARY KEY,
+                    seen_at TIMESTAMPTZ NOT NULL
+                 );
+                 CREATE INDEX idx_chain_nonces_seen ON chain_nonces(seen_at);"
+            )
+            .as_str(),
+        )
+        .expect("create legacy layout");
+
+        let result =
+            Db::init_current_schema(&mut conn).and_then(|()| Db::init_current_schema(&mut conn));
+        let legacy = conn
+            .query_one("SELECT to_regclass('chain_nonces') IS NOT NULL", &[])
+            .map(|row| row.get::<_, bool>(0));
+        let columns = conn
+            .query(
+                "SELECT column_name FROM information_schema.columns
+                 WHERE table_schema = current_schema() AND table_name = 'chain_nonce_reservations'
+                 ORDER BY ordinal_position",
+                &[],
+            )
+            .map(|rows| {
+                rows.iter()
+                    .map(|row| row.get::<_, String>(0))
+                    .collect::<Vec<_>>()
+            });
+        conn.batch_execute(format!("DROP SCHEMA {schema} CASCADE").as_str())
+            .expect("drop test schema");
+
+        result.expect("schema init over legacy layout");
+        assert!(!legacy.expect("query legacy table"));
+        assert_eq!(
+            columns.expect("query reservation columns"),
+            vec![
+                "account_id",
+                "nonce",
+                "signing_hash",
+                "era_birth_block",
+                "era_birth_hash",
+                "era_death_block",
+                "reserved_at",
+                "expires_at",
+                "submitted_at",
+            ]
+        );
+    }
+}
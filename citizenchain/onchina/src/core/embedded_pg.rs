@@ -16,7 +16,7 @@ use std::time::Duration;
 const DEFAULT_PG_PORT: &str = "5433";
 /// onchina 业务库名 / 超级用户名(本机 trust 鉴权,口令为空)。
 const PG_DB_NAME: &str = "onchina";
-const PG_SUPERUSER: &str = "postgres";
+pub(crate) const PG_SUPERUSER: &str = "postgres";
 /// 就绪探活:最多等待轮次 × 间隔。
 const READY_MAX_ATTEMPTS: u32 = 60;
 const READY_INTERVAL: Duration = Duration::from_millis(500);
@@ -32,7 +32,7 @@ pub(crate) fn is_enabled() -> bool {
         .unwrap_or(false)
 }
 
-fn pg_port() -> String {
+pub(crate) fn pg_port() -> String {
     std::env::var("ONCHINA_PG_PORT")
         .ok()
         .map(|v| v.trim().to_string())
@@ -56,8 +56,17 @@ fn pg_bin_dir() -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("."))
 }
 
+/// WAL 归档目录:`ONCHINA_PG_WAL_ARCHIVE_DIR`(node 传 `base_path/pg-wal-archive`);未设即不归档。
+pub(crate) fn wal_archive_dir() -> Option<PathBuf> {
+    std::env::var("ONCHINA_PG_WAL_ARCHIVE_DIR")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
 /// PG 数据目录:`ONCHINA_PG_DATA_DIR`(node 传 `base_path/pgdata`);兜底 exe 同目录 `pgdata`。
-fn pg_data_dir() -> PathBuf {
+pub(crate) fn pg_data_dir() -> PathBuf {
     if let Some(dir) = std::env::var("ONCHINA_PG_DATA_DIR")
         .ok()
         .map(|v| v.trim().to_string())
@@ -69,7 +78,7 @@ fn pg_data_dir() -> PathBuf {
 }
 
 /// 解析某个 PG 可执行文件(带平台后缀)。
-fn pg_tool(name: &str) -> PathBuf {
+pub(crate) fn pg_tool(name: &str) -> PathBuf {
     let exe = if cfg!(windows) {
         format!("{name}.exe")
     } else {
@@ -85,7 +94,7 @@ fn pg_tool(name: &str) -> PathBuf {
 ///
 /// 必须显式设置:双击启动(macOS LaunchServices)与 launchd 都是无 `LANG` 的精简
 /// 环境,只有从 Terminal 启动才会带 shell 的 locale——不能依赖用户机器环境。
-fn apply_pg_locale(cmd: &mut Command) {
+pub(crate) fn apply_pg_locale(cmd: &mut Command) {
     cmd.env("LC_ALL", "C");
     cmd.env("LANG", "C");
 }
@@ -109,7 +118,7 @@ fn run(tool: &Path, args: &[&str]) -> Result<(), String> {
 }
 
 /// 本机 trust 鉴权下的连接串(无口令);`db` 指定连接库。
-fn database_url_for(db: &str) -> String {
+pub(crate) fn database_url_for(db: &str) -> String {
     format!("postgres://{PG_SUPERUSER}@127.0.0.1:{}/{db}", pg_port())
 }
 
@@ -144,26 +153,7 @@ pub(crate) fn ensure_started() -> Result<String, String> {
 
     // 2) 未在运行则 pg_ctl start(-w 等待就绪);已在运行直接复用。
     if !is_running(&data_dir) {
-        let data = data_dir.to_string_lossy().to_string();
-        let log = data_dir.join("postgres.log");
-        let log_arg = log.to_string_lossy().to_string();
-        // -o 透传 postmaster 选项:私有端口 + 只监听 127.0.0.1。
-        let options = format!("-p {port} -h 127.0.0.1");
-        run(
-            &pg_tool("pg_ctl"),
-            &[
-                "start",
-                "-D",
-                data.as_str(),
-                "-l",
-                log_arg.as_str(),
-                "-o",
-                options.as_str(),
-                "-w",
-                "-t",
-                "60",
-            ],
-        )?;
+        start_postmaster(&data_dir, "60")?;
         tracing::info!(port = %port, "embedded postgres started");
     }
 
@@ -173,9 +163,36 @@ pub(crate) fn ensure_started() -> Result<String, String> {
     // 4) 幂等建 onchina 业务库。
     ensure_database()?;
 
+    // 5) WAL 归档命令随当前 onchina 路径/密钥刷新;早先未开归档的实例在此补开。
+    sync_wal_archiving(&data_dir)?;
+
     Ok(database_url_for(PG_DB_NAME))
 }
 
+/// `pg_ctl start -w`;`timeout_secs` 为等待就绪上限(PITR 回放可能远超常规启动)。
+pub(crate) fn start_postmaster(data_dir: &Path, timeout_secs: &str) -> Result<(), String> {
+    let data = data_dir.to_string_lossy().to_string();
+    let log = data_dir.join("postgres.log");
+    let log_arg = log.to_string_lossy().to_string();
+    // -o 透传 postmaster 选项:私有端口 + 只监听 127.0.0.1。
+    let options = format!("-p {} -h 127.0.0.1", pg_port());
+    run(
+        &pg_tool("pg_ctl"),
+        &[
+            "start",
+            "-D",
+            data.as_str(),
+            "-l",
+            log_arg.as_str(),
+            "-o",
+            options.as_str(),
+            "-w",
+            "-t",
+            timeout_secs,
+        ],
+    )
+}
+
 /// 退出期优雅停 PG(best-effort,不阻塞退出)。
 pub(crate) fn stop() {
     let data_dir = pg_data_dir();
@@ -192,7 +209,7 @@ pub(crate) fn stop() {
     }
 }
 
-fn is_running(data_dir: &Path) -> bool {
+pub(crate) fn is_running(data_dir: &Path) -> bool {
     let data = data_dir.to_string_lossy().to_string();
     let mut cmd = Command::new(pg_tool("pg_ctl"));
     cmd.args(["status", "-D", data.as_str()]);
@@ -206,21 +223,13 @@ fn configure_postgresql_conf(data_dir: &Path) -> Result<(), String> {
     let conf = data_dir.join("postgresql.conf");
     let mut extra =
         String::from("\n# ── onchina 内嵌实例(Card 05)──\nlisten_addresses = '127.0.0.1'\n");
-    if let Some(archive_dir) = std::env::var("ONCHINA_PG_WAL_ARCHIVE_DIR")
-        .ok()
-        .map(|v| v.trim().to_string())
-        .filter(|v| !v.is_empty())
-    {
-        // 每日 pg_basebackup + 持续 WAL 归档到 NAS = PITR(见 citizenchain/scripts/onchina-{backup,restore}.sh)。
+    if let Some(archive_dir) = wal_archive_dir() {
+        // 定时加密基础备份 + 持续加密 WAL 归档 = PITR(见 `core::pg_backup`)。
         std::fs::create_dir_all(&archive_dir)
             .map_err(|e| format!("create wal archive dir failed: {e}"))?;
-        let cmd = if cfg!(windows) {
-            format!("copy \"%p\" \"{archive_dir}\\\\%f\"")
-        } else {
-            format!("test ! -f {archive_dir}/%f && cp %p {archive_dir}/%f")
-        };
+        let cmd = super::pg_backup::wal_archive_command(&archive_dir)?;
         extra.push_str("wal_level = replica\narchive_mode = on\n");
-        extra.push_str(&format!("archive_command = '{cmd}'\n"));
+        extra.push_str(&format!("archive_command = {}\n", conf_literal(&cmd)));
     } else {
         extra.push_str(
             "# WAL 归档未配置(ONCHINA_PG_WAL_ARCHIVE_DIR 未设);PITR 关闭。\narchive_mode = off\n",
@@ -234,7 +243,61 @@ fn configure_postgresql_conf(data_dir: &Path) -> Result<(), String> {
         .map_err(|e| format!("write postgresql.conf failed: {e}"))
 }
 
-fn wait_ready() -> Result<(), String> {
+/// PostgreSQL 配置/SQL 字符串字面量(单引号包裹,内部单引号加倍)。
+pub(crate) fn conf_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// 每次启动把 `archive_command` 刷成当前 onchina 可执行文件的加密归档子命令
+/// (升级后 exe 路径可能变化);实例首启时未开归档、后来才配目录的,补开
+/// `wal_level`/`archive_mode` 并重启一次使其生效。未配归档目录时不动现有设置。
+fn sync_wal_archiving(data_dir: &Path) -> Result<(), String> {
+    let Some(archive_dir) = wal_archive_dir() else {
+        return Ok(());
+    };
+    std::fs::create_dir_all(&archive_dir)
+        .map_err(|e| format!("create wal archive dir failed: {e}"))?;
+    let command = super::pg_backup::wal_archive_command(&archive_dir)?;
+    let admin_url = database_url_for("postgres");
+    let mut client = postgres::Client::connect(admin_url.as_str(), postgres::NoTls)
+        .map_err(|e| format!("connect embedded postgres failed: {e}"))?;
+    let show = |client: &mut postgres::Client, name: &str| -> Result<String, String> {
+        client
+            .query_one(format!("SHOW {name}").as_str(), &[])
+            .map(|row| row.get::<_, String>(0))
+            .map_err(|e| format!("show {name} failed: {e}"))
+    };
+    let archive_mode = show(&mut client, "archive_mode")?;
+    let wal_level = show(&mut client, "wal_level")?;
+    client
+        .batch_execute(&format!(
+            "ALTER SYSTEM SET archive_command = {}",
+            conf_literal(&command)
+        ))
+        .map_err(|e| format!("set archive_command failed: {e}"))?;
+    if archive_mode == "off" || wal_level == "minimal" {
+        client
+            .batch_execute(
+                "ALTER SYSTEM SET wal_level = 'replica'; ALTER SYSTEM SET archive_mode = 'on'",
+            )
+            .map_err(|e| format!("enable wal archiving failed: {e}"))?;
+        drop(client);
+        let data = data_dir.to_string_lossy().to_string();
+        run(
+            &pg_tool("pg_ctl"),
+            &["stop", "-D", data.as_str(), "-m", "fast", "-w"],
+        )?;
+        start_postmaster(data_dir, "60")?;
+        wait_ready()?;
+        tracing::info!(archive_dir = %archive_dir.display(), "embedded postgres wal archiving enabled");
+        return Ok(());
+    }
+    client
+        .batch_execute("SELECT pg_reload_conf()")
+        .map_err(|e| format!("reload postgres config failed: {e}"))
+}
+
+pub(crate) fn wait_ready() -> Result<(), String> {
     let admin_url = database_url_for("postgres");
     for _ in 0..READY_MAX_ATTEMPTS {
         if postgres::Client::connect(admin_url.as_str(), postgres::NoTls).is_ok() {
@@ -1,5 +1,7 @@
 /// 公民 CID finalized 六读闭环快照；注册局办理、查询和投影共用。
 pub(crate) mod chain_citizen_identity;
+/// 可选的链上读取 GRANDPA 轻客户端与存储证明校验。
+pub(crate) mod chain_proof;
 /// 跨业务复用的链上凭证签名、SCALE payload 与 genesis hash 对齐工具。
 pub(crate) mod chain_runtime;
 pub(crate) mod chain_submit;
@@ -12,6 +14,8 @@ pub(crate) mod embedded_pg;
 pub(crate) mod http_security;
 /// 机构治理裸 SCALE call data 编码器（OnChina 唯一真源）。
 pub(crate) mod institution_call;
+/// 内嵌 PostgreSQL 加密基础备份、加密 WAL 归档、PITR 恢复与备份校验。
+pub(crate) mod pg_backup;
 /// QR_V1 协议和链上中国平台签名二维码构造。
 pub(crate) mod qr;
 /// HTTP API 通用响应、分页和健康检查输出模型。
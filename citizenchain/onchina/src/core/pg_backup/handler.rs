@@ -0,0 +1,244 @@
+//! 内嵌 PostgreSQL 备份状态 / 手动备份 / 校验接口。
+//!
+//! 状态接口除管理员会话外,还接受节点桌面端从本机回环地址携带的
+//! `x-onchina-desktop-token`(节点拉起 onchina 时经 `ONCHINA_DESKTOP_TOKEN` 传入的一次性随机值),
+//! 供设置页在无管理员登录时展示备份健康度;该令牌只能读状态,不能触发备份或校验。
+
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::*;
+
+use super::{
+    job_running, last_run, list_manifests, next_due_at, run_base_backup, stream::BackupKey,
+    verify_all, wal_archive_summary, BackupConfig, BackupManifest, BackupRunOutcome,
+    WalArchiveSummary,
+};
+
+const DESKTOP_TOKEN_HEADER: &str = "x-onchina-desktop-token";
+
+/// `pg_stat_archiver` 快照:PG 自己记录的归档成功/失败计数。
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ArchiverStats {
+    pub(crate) archived_count: i64,
+    pub(crate) last_archived_wal: Option<String>,
+    pub(crate) last_archived_time: Option<DateTime<Utc>>,
+    pub(crate) failed_count: i64,
+    pub(crate) last_failed_wal: Option<String>,
+    pub(crate) last_failed_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct PgBackupStatus {
+    pub(crate) embedded: bool,
+    pub(crate) backup_enabled: bool,
+    pub(crate) wal_archive_enabled: bool,
+    pub(crate) backup_dir: Option<String>,
+    pub(crate) wal_archive_dir: Option<String>,
+    pub(crate) interval_secs: u64,
+    pub(crate) keep: usize,
+    /// 密钥文件不存在时为空(状态接口不代为生成密钥)。
+    pub(crate) key_fingerprint: Option<String>,
+    pub(crate) job_running: bool,
+    pub(crate) last_run: Option<BackupRunOutcome>,
+    pub(crate) next_due_at: Option<DateTime<Utc>>,
+    /// 最新在前。
+    pub(crate) backups: Vec<BackupManifest>,
+    pub(crate) wal_archive: Option<WalArchiveSummary>,
+    pub(crate) archiver: Option<ArchiverStats>,
+}
+
+fn desktop_token_matches(headers: &HeaderMap, peer: &SocketAddr) -> bool {
+    let Some(expected) = optional_env("ONCHINA_DESKTOP_TOKEN") else {
+        return false;
+    };
+    let Some(presented) = chain_header_value(headers, DESKTOP_TOKEN_HEADER) else {
+        return false;
+    };
+    // 只认本机直连;经反代转发的请求 peer 不是回环地址。
+    if !peer.ip().is_loopback() || presented.len() != expected.len() {
+        return false;
+    }
+    presented
+        .bytes()
+        .zip(expected.bytes())
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+        == 0
+}
+
+fn collect_status(config: &BackupConfig) -> Result<PgBackupStatus, String> {
+    let mut backups = match config.backup_dir.as_deref() {
+        Some(dir) => list_manifests(dir)?,
+        None => Vec::new(),
+    };
+    let next_due = config
+        .backup_dir
+        .as_ref()
+        .map(|_| next_due_at(config, &backups));
+    backups.reverse();
+    let key_fingerprint = config
+        .key_file
+        .is_file()
+        .then(|| BackupKey::load(&config.key_file))
+        .transpose()?
+        .map(|key| key.fingerprint());
+    let wal_archive = config
+        .wal_archive_dir
+        .as_deref()
+        .map(wal_archive_summary)
+        .transpose()?;
+    Ok(PgBackupStatus {
+        embedded: crate::core::embedded_pg::is_enabled(),
+        backup_enabled: config.backup_dir.is_some(),
+        wal_archive_enabled: config.wal_archive_dir.is_some(),
+        backup_dir: config.backup_dir.as_ref().map(|p| p.display().to_string()),
+        wal_archive_dir: config
+            .wal_archive_dir
+            .as_ref()
+            .map(|p| p.display().to_string()),
+        interval_secs: config.interval_secs,
+        keep: config.keep,
+        key_fingerprint,
+        job_running: job_running(),
+        last_run: last_run(),
+        next_due_at: next_due,
+        backups,
+        wal_archive,
+        archiver: None,
+    })
+}
+
+fn archiver_stats_conn(conn: &mut postgres::Client) -> Result<ArchiverStats, String> {
+    let row = conn
+        .query_one(
+            "SELECT archived_count, last_archived_wal, last_archived_time,
+                    failed_count, last_failed_wal, last_failed_time
+             FROM pg_stat_archiver",
+            &[],
+        )
+        .map_err(|e| format!("query pg_stat_archiver failed: {e}"))?;
+    Ok(ArchiverStats {
+        archived_count: row.get(0),
+        last_archived_wal: row.get(1),
+        last_archived_time: row.get(2),
+        failed_count: row.get(3),
+        last_failed_wal: row.get(4),
+        last_failed_time: row.get(5),
+    })
+}
+
+/// GET /api/admin/system/pg-backup/status
+pub(crate) async fn pg_backup_status(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if !desktop_token_matches(&headers, &peer) {
+        if let Err(resp) = require_admin_any(&state, &headers) {
+            return resp;
+        }
+    }
+    let config = BackupConfig::from_env();
+    let mut status = match collect_status(&config) {
+        Ok(status) => status,
+        Err(err) => return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, err.as_str()),
+    };
+    match state.db.with_client(archiver_stats_conn) {
+        Ok(stats) => status.archiver = Some(stats),
+        Err(err) => tracing::warn!(error = %err, "read pg_stat_archiver failed"),
+    }
+    Json(ApiResponse {
+        code: 0,
+        message: "ok".to_string(),
+        data: status,
+    })
+    .into_response()
+}
+
+/// POST /api/admin/system/pg-backup/run:立即拉一份加密基础备份(同步等待完成)。
+pub(crate) async fn pg_backup_run(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    if !crate::core::embedded_pg::is_enabled() {
+        return api_error(
+            StatusCode::CONFLICT,
+            1009,
+            "外部托管 PostgreSQL 由其运维体系备份",
+        );
+    }
+    let config = BackupConfig::from_env();
+    let result = tokio::task::spawn_blocking(move || run_base_backup(&config))
+        .await
+        .unwrap_or_else(|e| Err(format!("backup task failed: {e}")));
+    match result {
+        Ok(manifest) => {
+            crate::core::runtime_ops::append_audit_log(
+                &state,
+                "PG_BACKUP_RUN",
+                ctx.account_id.as_str(),
+                None,
+                serde_json::json!({
+                    "backup_id": manifest.backup_id,
+                    "plaintext_bytes": manifest.plaintext_bytes,
+                    "ciphertext_sha256": manifest.ciphertext_sha256,
+                }),
+            );
+            Json(ApiResponse {
+                code: 0,
+                message: "ok".to_string(),
+                data: manifest,
+            })
+            .into_response()
+        }
+        Err(err) => api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, err.as_str()),
+    }
+}
+
+/// POST /api/admin/system/pg-backup/verify:认证全部基础备份并核对 WAL 链。
+pub(crate) async fn pg_backup_verify(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let config = BackupConfig::from_env();
+    let result = tokio::task::spawn_blocking(move || verify_all(&config))
+        .await
+        .unwrap_or_else(|e| Err(format!("verify task failed: {e}")));
+    match result {
+        Ok(report) => {
+            crate::core::runtime_ops::append_audit_log(
+                &state,
+                "PG_BACKUP_VERIFY",
+                ctx.account_id.as_str(),
+                None,
+                serde_json::json!({
+                    "ok": report.ok,
+                    "backups_checked": report.backups_checked,
+                    "backups_failed": report.backups_failed,
+                }),
+            );
+            Json(ApiResponse {
+                code: 0,
+                message: "ok".to_string(),
+                data: report,
+            })
+            .into_response()
+        }
+        Err(err) => api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, err.as_str()),
+    }
+}
@@ -0,0 +1,1148 @@
+//! 内嵌 PostgreSQL 的加密备份、WAL 归档与按时间点恢复(PITR)。
+//!
+//! - 基础备份:周期任务以 `pg_basebackup -Ft -X fetch -D -` 把整库 tar 流直接管道进
+//!   [`stream::encrypt_stream`],落 `ONCHINA_PG_BACKUP_DIR/<id>.tar.enc`,旁写 `<id>.json`
+//!   清单(明文/密文 sha256、起始 WAL 段、密钥指纹),写完立即整包解密复核一次,再按
+//!   `ONCHINA_PG_BACKUP_KEEP` 轮转;
+//! - WAL 归档:`archive_command` / `restore_command` 回调 onchina 自身的
+//!   `pg-wal-archive` / `pg-wal-restore` 子命令,段文件加密后才落到归档目录;
+//! - 恢复:`onchina pg-restore --backup <id|latest> [--target-time <RFC3339>]` 在平台停止时
+//!   把旧数据目录挪开保留、解密解包、写 `recovery.signal`,起实例回放到目标时间后 promote;
+//! - 校验:逐帧认证全部基础备份,并核对最新备份起点之后的 WAL 段连续且可解密。
+//!
+//! 主密钥在 `ONCHINA_PG_BACKUP_KEY_FILE`(默认数据目录旁 `pg-backup.key`),只在备份目录里
+//! 尚无任何清单的首次基础备份时生成,此后绝不覆盖;恢复、校验与 WAL 归档/取回回调只读取
+//! 既有密钥,缺失即失败。备份与归档目录里只有密文,运维须把密钥文件另行离机托管,否则备份无法恢复。
+
+pub(crate) mod handler;
+pub(crate) mod stream;
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::embedded_pg;
+use stream::{
+    decrypt_stream, encrypt_stream, is_wal_segment_name, start_wal_from_label, BackupKey,
+    HashingReader, HashingWriter, TarScanner,
+};
+
+const DEFAULT_BACKUP_INTERVAL_SECS: u64 = 86_400;
+const DEFAULT_BACKUP_KEEP: usize = 14;
+const BACKUP_FILE_PREFIX: &str = "basebackup_";
+const BACKUP_FILE_SUFFIX: &str = ".tar.enc";
+const WAL_FILE_SUFFIX: &str = ".enc";
+/// 默认 `initdb` 的 WAL 段大小 16 MiB:每个日志号含 0x100 个段。
+const SEGMENTS_PER_XLOGID: u32 = 0x100;
+/// 备份周期上限(一年),防止异常配置让到期时间溢出。
+const MAX_INTERVAL_SECS: u64 = 365 * 86_400;
+/// PITR 回放等待上限;回放期间实例只读,须等 promote 完成才交还给平台。
+const RECOVERY_TIMEOUT: Duration = Duration::from_secs(6 * 3600);
+const RECOVERY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 同一进程内只允许一个备份/校验任务,避免定时任务与管理员手动触发重叠。
+static JOB_RUNNING: AtomicBool = AtomicBool::new(false);
+static LAST_RUN: Mutex<Option<BackupRunOutcome>> = Mutex::new(None);
+
+/// 备份配置,全部来自环境变量(node 桌面端在 `onchina_proc` 里按数据目录传入)。
+#[derive(Debug, Clone)]
+pub(crate) struct BackupConfig {
+    pub(crate) backup_dir: Option<PathBuf>,
+    pub(crate) wal_archive_dir: Option<PathBuf>,
+    pub(crate) key_file: PathBuf,
+    pub(crate) interval_secs: u64,
+    pub(crate) keep: usize,
+}
+
+impl BackupConfig {
+    pub(crate) fn from_env() -> Self {
+        let backup_dir = crate::optional_env("ONCHINA_PG_BACKUP_DIR").map(PathBuf::from);
+        let key_file = crate::optional_env("ONCHINA_PG_BACKUP_KEY_FILE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| embedded_pg::pg_data_dir().with_file_name("pg-backup.key"));
+        let interval_secs = crate::optional_env("ONCHINA_PG_BACKUP_INTERVAL_SECS")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_BACKUP_INTERVAL_SECS);
+        let keep = crate::optional_env("ONCHINA_PG_BACKUP_KEEP")
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_BACKUP_KEEP);
+        Self {
+            backup_dir,
+            wal_archive_dir: embedded_pg::wal_archive_dir(),
+            key_file,
+            interval_secs,
+            keep,
+        }
+    }
+
+    fn require_backup_dir(&self) -> Result<&Path, String> {
+        self.backup_dir
+            .as_deref()
+            .ok_or_else(|| "未配置 ONCHINA_PG_BACKUP_DIR,备份未启用".to_string())
+    }
+
+    fn load_key(&self) -> Result<BackupKey, String> {
+        BackupKey::load(&self.key_file)
+    }
+}
+
+/// 一份基础备份的清单(`<id>.json`)。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(crate) struct BackupManifest {
+    pub(crate) backup_id: String,
+    pub(crate) started_at: DateTime<Utc>,
+    pub(crate) finished_at: DateTime<Utc>,
+    pub(crate) plaintext_bytes: u64,
+    pub(crate) plaintext_sha256: String,
+    pub(crate) ciphertext_bytes: u64,
+    pub(crate) ciphertext_sha256: String,
+    pub(crate) key_fingerprint: String,
+    pub(crate) start_wal: Option<String>,
+    pub(crate) verified_at: Option<DateTime<Utc>>,
+    pub(crate) verify_error: Option<String>,
+}
+
+/// 最近一次备份任务结果(进程内,供状态页展示)。
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct BackupRunOutcome {
+    pub(crate) finished_at: DateTime<Utc>,
+    pub(crate) backup_id: Option<String>,
+    pub(crate) error: Option<String>,
+}
+
+/// WAL 连续性校验结果。
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct WalChainReport {
+    pub(crate) start_wal: String,
+    pub(crate) latest_wal: Option<String>,
+    pub(crate) segments_checked: u64,
+    /// 同一时间线内缺失的段(首个缺口起最多列 32 个)。
+    pub(crate) missing: Vec<String>,
+    pub(crate) corrupt: Vec<String>,
+}
+
+/// 一次完整校验的结果。
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct VerifyReport {
+    pub(crate) backups_checked: usize,
+    pub(crate) backups_failed: Vec<String>,
+    pub(crate) wal: Option<WalChainReport>,
+    pub(crate) ok: bool,
+}
+
+/// 归档目录概况。
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct WalArchiveSummary {
+    pub(crate) segments: u64,
+    pub(crate) bytes: u64,
+    pub(crate) oldest: Option<String>,
+    pub(crate) newest: Option<String>,
+}
+
+struct JobGuard;
+
+impl JobGuard {
+    fn acquire() -> Result<Self, String> {
+        JOB_RUNNING
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .map(|_| Self)
+            .map_err(|_| "已有备份或校验任务在运行".to_string())
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        JOB_RUNNING.store(false, Ordering::Release);
+    }
+}
+
+pub(crate) fn job_running() -> bool {
+    JOB_RUNNING.load(Ordering::Acquire)
+}
+
+pub(crate) fn last_run() -> Option<BackupRunOutcome> {
+    LAST_RUN.lock().ok().and_then(|guard| guard.clone())
+}
+
+fn record_run(outcome: BackupRunOutcome) {
+    if let Ok(mut guard) = LAST_RUN.lock() {
+        *guard = Some(outcome);
+    }
+}
+
+// ───────────────────────── 归档 / 恢复回调命令 ─────────────────────────
+
+/// 命令行参数引用:Unix 走 `sh -c`(单引号),Windows 走 `cmd /c`(双引号)。
+/// `%` 须写成 `%%`,否则会被 PostgreSQL 当作 `%p`/`%f` 占位符展开。
+fn shell_quote(value: &str) -> String {
+    let value = value.replace('%', "%%");
+    if cfg!(windows) {
+        format!("\"{value}\"")
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+fn callback_command(subcommand: &str, archive_dir: &Path, tail: &str) -> Result<String, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("resolve onchina exe failed: {e}"))?;
+    let config = BackupConfig::from_env();
+    // 密钥只由首次基础备份生成;此前的归档回调会失败,PG 保留段文件并重试,不会丢 WAL。
+    if !config.key_file.is_file() {
+        tracing::warn!(
+            path = %config.key_file.display(),
+            "embedded postgres backup key not present yet; WAL archiving fails until the first base backup creates it"
+        );
+    }
+    Ok(format!(
+        "{} {subcommand} --dir {} --key {} {tail}",
+        shell_quote(&exe.to_string_lossy()),
+        shell_quote(&archive_dir.to_string_lossy()),
+        shell_quote(&config.key_file.to_string_lossy()),
+    ))
+}
+
+/// `archive_command`:`onchina pg-wal-archive --dir <归档目录> --key <密钥> --path %p --name %f`。
+pub(crate) fn wal_archive_command(archive_dir: &Path) -> Result<String, String> {
+    let tail = if cfg!(windows) {
+        "--path \"%p\" --name \"%f\""
+    } else {
+        "--path '%p' --name '%f'"
+    };
+    callback_command("pg-wal-archive", archive_dir, tail)
+}
+
+/// `restore_command`:`onchina pg-wal-restore --dir <归档目录> --key <密钥> --name %f --path %p`。
+fn wal_restore_command(archive_dir: &Path) -> Result<String, String> {
+    let tail = if cfg!(windows) {
+        "--name \"%f\" --path \"%p\""
+    } else {
+        "--name '%f' --path '%p'"
+    };
+    callback_command("pg-wal-restore", archive_dir, tail)
+}
+
+fn validate_wal_file_name(name: &str) -> Result<(), String> {
+    // %f 只会是段名、`.history`、`.partial` 或 `.backup` 文件;拒绝任何路径成分。
+    let ok = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'.' || b == b'_');
+    if ok {
+        Ok(())
+    } else {
+        Err(format!("非法 WAL 文件名: {name}"))
+    }
+}
+
+#[cfg(unix)]
+fn sync_dir(dir: &Path) {
+    if let Ok(handle) = File::open(dir) {
+        let _ = handle.sync_all();
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) {}
+
+/// 把 `src` 加密写到 `dest`:先写同目录临时文件并 fsync,再原子改名。
+fn encrypt_file_atomically(
+    key: &BackupKey,
+    src: &Path,
+    dest: &Path,
+) -> Result<stream::StreamStats, String> {
+    let dir = dest
+        .parent()
+        .ok_or_else(|| format!("{} 没有父目录", dest.display()))?;
+    let tmp = dir.join(format!(
+        ".{}.tmp-{}",
+        dest.file_name().and_then(|n| n.to_str()).unwrap_or("wal"),
+        std::process::id()
+    ));
+    let result = (|| {
+        let mut input =
+            File::open(src).map_err(|e| format!("open {} failed: {e}", src.display()))?;
+        let file =
+            File::create(&tmp).map_err(|e| format!("create {} failed: {e}", tmp.display()))?;
+        let mut writer = BufWriter::new(file);
+        let stats = encrypt_stream(key, &mut input, &mut writer)
+            .map_err(|e| format!("encrypt {} failed: {e}", src.display()))?;
+        let file = writer
+            .into_inner()
+            .map_err(|e| format!("flush {} failed: {e}", tmp.display()))?;
+        file.sync_all()
+            .map_err(|e| format!("fsync {} failed: {e}", tmp.display()))?;
+        std::fs::rename(&tmp, dest)
+            .map_err(|e| format!("rename to {} failed: {e}", dest.display()))?;
+        sync_dir(dir);
+        Ok(stats)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+fn plaintext_sha256(path: &Path) -> Result<String, String> {
+    use sha2::{Digest, Sha256};
+    let mut file = File::open(path).map_err(|e| format!("open {} failed: {e}", path.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| format!("read {} failed: {e}", path.display()))?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// `pg-wal-archive`:加密归档一个 WAL 文件。
+///
+/// 目标已存在时(归档成功但 PG 未收到确认后重试)解密比对:内容相同返回成功,
+/// 不同则失败——绝不覆盖已归档段。
+pub(crate) fn archive_wal_file(
+    archive_dir: &Path,
+    key_file: &Path,
+    wal_path: &Path,
+    wal_name: &str,
+) -> Result<(), String> {
+    validate_wal_file_name(wal_name)?;
+    let key = BackupKey::load(key_file)?;
+    std::fs::create_dir_all(archive_dir)
+        .map_err(|e| format!("create {} failed: {e}", archive_dir.display()))?;
+    let dest = archive_dir.join(format!("{wal_name}{WAL_FILE_SUFFIX}"));
+    if dest.exists() {
+        let mut reader = BufReader::new(
+            File::open(&dest).map_err(|e| format!("open {} failed: {e}", dest.display()))?,
+        );
+        let archived = decrypt_stream(&key, &mut reader, &mut std::io::sink())
+            .map_err(|e| format!("已归档 {wal_name} 无法解密: {e}"))?;
+        if archived.plaintext_sha256 == plaintext_sha256(wal_path)? {
+            return Ok(());
+        }
+        return Err(format!("{wal_name} 已归档且内容不同,拒绝覆盖"));
+    }
+    encrypt_file_atomically(&key, wal_path, &dest).map(|_| ())
+}
+
+/// `pg-wal-restore`:从归档解密一个 WAL 文件到 PG 指定位置;返回 `false` 表示归档中没有。
+pub(crate) fn restore_wal_file(
+    archive_dir: &Path,
+    key_file: &Path,
+    wal_name: &str,
+    dest_path: &Path,
+) -> Result<bool, String> {
+    validate_wal_file_name(wal_name)?;
+    let src = archive_dir.join(format!("{wal_name}{WAL_FILE_SUFFIX}"));
+    let file = match File::open(&src) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(format!("open {} failed: {err}", src.display())),
+    };
+    let key = BackupKey::load(key_file)?;
+    let out = File::create(dest_path)
+        .map_err(|e| format!("create {} failed: {e}", dest_path.display()))?;
+    let mut writer = BufWriter::new(out);
+    let result = decrypt_stream(&key, &mut BufReader::new(file), &mut writer)
+        .map_err(|e| format!("解密归档 {wal_name} 失败: {e}"));
+    if result.is_err() {
+        drop(writer);
+        let _ = std::fs::remove_file(dest_path);
+    }
+    result.map(|_| true)
+}
+
+// ───────────────────────── 基础备份 ─────────────────────────
+
+fn backup_file(dir: &Path, backup_id: &str) -> PathBuf {
+    dir.join(format!("{backup_id}{BACKUP_FILE_SUFFIX}"))
+}
+
+fn manifest_file(dir: &Path, backup_id: &str) -> PathBuf {
+    dir.join(format!("{backup_id}.json"))
+}
+
+fn write_manifest(dir: &Path, manifest: &BackupManifest) -> Result<(), String> {
+    let path = manifest_file(dir, &manifest.backup_id);
+    let tmp = dir.join(format!(".{}.json.tmp", manifest.backup_id));
+    let body = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| format!("serialize backup manifest failed: {e}"))?;
+    std::fs::write(&tmp, body).map_err(|e| format!("write {} failed: {e}", tmp.display()))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("rename {} failed: {e}", path.display()))?;
+    sync_dir(dir);
+    Ok(())
+}
+
+/// 按备份编号(即时间戳)升序列出清单;无法解析的清单跳过并告警。
+pub(crate) fn list_manifests(dir: &Path) -> Result<Vec<BackupManifest>, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("read {} failed: {err}", dir.display())),
+    };
+    let mut manifests = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !name.starts_with(BACKUP_FILE_PREFIX) || !name.ends_with(".json") {
+            continue;
+        }
+        match std::fs::read(entry.path())
+            .map_err(|e| e.to_string())
+            .and_then(|raw| {
+                serde_json::from_slice::<BackupManifest>(&raw).map_err(|e| e.to_string())
+            }) {
+            Ok(manifest) => manifests.push(manifest),
+            Err(err) => {
+                tracing::warn!(file = %name, error = %err, "skip unreadable backup manifest")
+            }
+        }
+    }
+    manifests.sort_by(|a, b| a.backup_id.cmp(&b.backup_id));
+    Ok(manifests)
+}
+
+/// 拉一份加密基础备份,写清单、立即复核并轮转。
+pub(crate) fn run_base_backup(config: &BackupConfig) -> Result<BackupManifest, String> {
+    let _guard = JobGuard::acquire()?;
+    let result = run_base_backup_locked(config);
+    record_run(BackupRunOutcome {
+        finished_at: Utc::now(),
+        backup_id: result.as_ref().ok().map(|m| m.backup_id.clone()),
+        error: result.as_ref().err().cloned(),
+    });
+    result
+}
+
+fn run_base_backup_locked(config: &BackupConfig) -> Result<BackupManifest, String> {
+    let dir = config.require_backup_dir()?;
+    std::fs::create_dir_all(dir).map_err(|e| format!("create {} failed: {e}", dir.display()))?;
+    // 只有首次启用(目录里还没有任何清单)才允许生成密钥;之后密钥缺失说明被误删,
+    // 换新密钥会让既有备份与归档全部作废,必须报错等运维放回。
+    let key = if list_manifests(dir)?.is_empty() {
+        BackupKey::load_or_create(&config.key_file)?
+    } else {
+        config.load_key()?
+    };
+    let started_at = Utc::now();
+    let backup_id = format!(
+        "{BACKUP_FILE_PREFIX}{}",
+        started_at.format("%Y%m%dT%H%M%SZ")
+    );
+    let dest = backup_file(dir, &backup_id);
+    let partial = dir.join(format!(".{backup_id}{BACKUP_FILE_SUFFIX}.partial"));
+
+    let port = embedded_pg::pg_port();
+    let mut cmd = Command::new(embedded_pg::pg_tool("pg_basebackup"));
+    // -D - 把单个 tar 写到 stdout(要求无额外表空间,且只能 -X fetch 把 WAL 收进同一 tar)。
+    cmd.args([
+        "-h",
+        "127.0.0.1",
+        "-p",
+        port.as_str(),
+        "-U",
+        embedded_pg::PG_SUPERUSER,
+        "-w",
+        "-D",
+        "-",
+        "-F",
+        "tar",
+        "-X",
+        "fetch",
+        "-c",
+        "fast",
+    ])
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped());
+    embedded_pg::apply_pg_locale(&mut cmd);
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("spawn pg_basebackup failed: {e}"))?;
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "pg_basebackup stdout unavailable".to_string())?;
+    let mut stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| "pg_basebackup stderr unavailable".to_string())?;
+    let stderr_reader = std::thread::spawn(move || {
+        let mut text = String::new();
+        let _ = stderr.read_to_string(&mut text);
+        text
+    });
+
+    let written = (|| {
+        let file = File::create(&partial)
+            .map_err(|e| format!("create {} failed: {e}", partial.display()))?;
+        let mut writer = HashingWriter::new(BufWriter::new(file));
+        let stats = encrypt_stream(&key, &mut stdout, &mut writer)
+            .map_err(|e| format!("encrypt base backup failed: {e}"))?;
+        let (buffered, ciphertext_bytes, ciphertext_sha256) = writer.finish();
+        let file = buffered
+            .into_inner()
+            .map_err(|e| format!("flush {} failed: {e}", partial.display()))?;
+        file.sync_all()
+            .map_err(|e| format!("fsync {} failed: {e}", partial.display()))?;
+        Ok((stats, ciphertext_bytes, ciphertext_sha256))
+    })();
+    let status = child.wait();
+    let stderr_text = stderr_reader.join().unwrap_or_default();
+    let (stats, ciphertext_bytes, ciphertext_sha256) = match (written, status) {
+        (Ok(written), Ok(status)) if status.success() => written,
+        (written, status) => {
+            let _ = std::fs::remove_file(&partial);
+            let reason = match (written, status) {
+                (Err(err), _) => err,
+                (_, Err(err)) => format!("wait pg_basebackup failed: {err}"),
+                (_, Ok(status)) => {
+                    format!("pg_basebackup exited with {status}: {}", stderr_text.trim())
+                }
+            };
+            return Err(reason);
+        }
+    };
+    std::fs::rename(&partial, &dest)
+        .map_err(|e| format!("rename to {} failed: {e}", dest.display()))?;
+    sync_dir(dir);
+
+    let mut manifest = BackupManifest {
+        backup_id: backup_id.clone(),
+        started_at,
+        finished_at: Utc::now(),
+        plaintext_bytes: stats.plaintext_bytes,
+        plaintext_sha256: stats.plaintext_sha256,
+        ciphertext_bytes,
+        ciphertext_sha256,
+        key_fingerprint: key.fingerprint(),
+        start_wal: None,
+        verified_at: None,
+        verify_error: None,
+    };
+    // 写完立即整包复核(同时从 backup_label 取起始 WAL 段,供轮转与 WAL 连续性校验)。
+    match verify_backup_file(dir, &key, &manifest) {
+        Ok(start_wal) => {
+            manifest.start_wal = Some(start_wal);
+            manifest.verified_at = Some(Utc::now());
+        }
+        Err(err) => manifest.verify_error = Some(err),
+    }
+    write_manifest(dir, &manifest)?;
+    if let Some(err) = manifest.verify_error.as_ref() {
+        return Err(format!("备份 {backup_id} 写入后复核失败: {err}"));
+    }
+    tracing::info!(
+        backup_id = %backup_id,
+        bytes = manifest.plaintext_bytes,
+        "embedded postgres encrypted base backup completed"
+    );
+    prune_backups(config, dir)?;
+    Ok(manifest)
+}
+
+/// 保留最新 `keep` 份;删除更旧备份,以及最旧保留备份起点之前的 WAL 段。
+fn prune_backups(config: &BackupConfig, dir: &Path) -> Result<(), String> {
+    let manifests = list_manifests(dir)?;
+    let verified = manifests
+        .iter()
+        .filter(|m| m.verify_error.is_none() && m.start_wal.is_some())
+        .count();
+    // 可用备份不足 keep 份时不轮转,避免坏备份把好备份挤掉。
+    if verified <= config.keep {
+        return Ok(());
+    }
+    let cut = manifests.len().saturating_sub(config.keep);
+    for old in &manifests[..cut] {
+        for path in [
+            backup_file(dir, &old.backup_id),
+            manifest_file(dir, &old.backup_id),
+        ] {
+            if let Err(err) = std::fs::remove_file(&path) {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    tracing::warn!(path = %path.display(), error = %err, "remove expired backup failed");
+                }
+            }
+        }
+        tracing::info!(backup_id = %old.backup_id, "expired embedded postgres base backup removed");
+    }
+    let oldest_start = manifests[cut..]
+        .iter()
+        .filter_map(|m| m.start_wal.as_deref())
+        .min();
+    if let (Some(archive_dir), Some(oldest_start)) =
+        (config.wal_archive_dir.as_deref(), oldest_start)
+    {
+        prune_wal_archive(archive_dir, oldest_start)?;
+    }
+    Ok(())
+}
+
+/// 删除早于 `oldest_start` 的段(含旧时间线);时间线 `.history` 文件永远保留。
+fn prune_wal_archive(archive_dir: &Path, oldest_start: &str) -> Result<(), String> {
+    let entries = match std::fs::read_dir(archive_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(format!("read {} failed: {err}", archive_dir.display())),
+    };
+    let mut removed = 0u64;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some(stem) = name.strip_suffix(WAL_FILE_SUFFIX) else {
+            continue;
+        };
+        if stem.ends_with(".history") || stem.len() < 24 || !is_wal_segment_name(&stem[..24]) {
+            continue;
+        }
+        if &stem[..24] < oldest_start && std::fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        tracing::info!(
+            removed,
+            oldest_start,
+            "expired archived wal segments removed"
+        );
+    }
+    Ok(())
+}
+
+// ───────────────────────── 校验 ─────────────────────────
+
+/// 解密认证整个备份,核对清单摘要与 tar 结构;返回 `backup_label` 中的起始 WAL 段。
+fn verify_backup_file(
+    dir: &Path,
+    key: &BackupKey,
+    manifest: &BackupManifest,
+) -> Result<String, String> {
+    if manifest.key_fingerprint != key.fingerprint() {
+        return Err(format!(
+            "备份使用密钥 {},与当前密钥 {} 不符",
+            manifest.key_fingerprint,
+            key.fingerprint()
+        ));
+    }
+    let path = backup_file(dir, &manifest.backup_id);
+    let file = File::open(&path).map_err(|e| format!("open {} failed: {e}", path.display()))?;
+    let mut reader = HashingReader::new(BufReader::new(file));
+    let mut scanner = TarScanner::new();
+    let stats = decrypt_stream(key, &mut reader, &mut scanner).map_err(|e| e.to_string())?;
+    let (ciphertext_bytes, ciphertext_sha256) = reader.finish();
+    if ciphertext_bytes != manifest.ciphertext_bytes
+        || ciphertext_sha256 != manifest.ciphertext_sha256
+    {
+        return Err("密文摘要与清单不符".to_string());
+    }
+    if stats.plaintext_sha256 != manifest.plaintext_sha256 {
+        return Err("明文摘要与清单不符".to_string());
+    }
+    scanner.finish()?;
+    scanner
+        .backup_label
+        .as_deref()
+        .and_then(start_wal_from_label)
+        .ok_or_else(|| "backup_label 缺少 START WAL LOCATION".to_string())
+}
+
+fn next_segment(name: &str) -> Option<String> {
+    let timeline = u32::from_str_radix(&name[..8], 16).ok()?;
+    let log = u32::from_str_radix(&name[8..16], 16).ok()?;
+    let seg = u32::from_str_radix(&name[16..24], 16).ok()?;
+    let (log, seg) = if seg + 1 >= SEGMENTS_PER_XLOGID {
+        (log.checked_add(1)?, 0)
+    } else {
+        (log, seg + 1)
+    };
+    Some(format!("{timeline:08X}{log:08X}{seg:08X}"))
+}
+
+/// 归档目录中的段名(去掉 `.enc`),升序。
+fn archived_segments(archive_dir: &Path) -> Result<Vec<(String, u64)>, String> {
+    let entries = match std::fs::read_dir(archive_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("read {} failed: {err}", archive_dir.display())),
+    };
+    let mut segments = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let stem = name.strip_suffix(WAL_FILE_SUFFIX)?;
+            is_wal_segment_name(stem).then(|| {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                (stem.to_string(), size)
+            })
+        })
+        .collect::<Vec<_>>();
+    segments.sort();
+    Ok(segments)
+}
+
+/// 从 `start_wal` 起逐时间线核对段连续,并逐个解密认证。
+///
+/// 新时间线(PITR promote 之后)的首段可从任意位置开始;同一时间线内出现跳号即为缺口。
+fn verify_wal_chain(
+    archive_dir: &Path,
+    key: &BackupKey,
+    start_wal: &str,
+) -> Result<WalChainReport, String> {
+    const MAX_LISTED: usize = 32;
+    let segments = archived_segments(archive_dir)?;
+    let mut report = WalChainReport {
+        start_wal: start_wal.to_string(),
+        ..WalChainReport::default()
+    };
+    let mut expected: Option<String> = Some(start_wal.to_string());
+    for (name, _) in segments
+        .iter()
+        .filter(|(name, _)| name.as_str() >= start_wal)
+    {
+        if let Some(want) = expected.as_deref() {
+            if want[..8] == name[..8] {
+                let mut gap = want.to_string();
+                while gap.as_str() < name.as_str() && report.missing.len() < MAX_LISTED {
+                    report.missing.push(gap.clone());
+                    match next_segment(&gap) {
+                        Some(next) => gap = next,
+                        None => break,
+                    }
+                }
+            }
+        }
+        let path = archive_dir.join(format!("{name}{WAL_FILE_SUFFIX}"));
+        let ok = File::open(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| {
+                decrypt_stream(key, &mut BufReader::new(file), &mut std::io::sink())
+                    .map_err(|e| e.to_string())
+            })
+            .is_ok();
+        if !ok && report.corrupt.len() < MAX_LISTED {
+            report.corrupt.push(name.clone());
+        }
+        report.segments_checked += 1;
+        report.latest_wal = Some(name.clone());
+        expected = next_segment(name);
+    }
+    if report.latest_wal.is_none() {
+        // 起始段本身都没归档:备份之后的 WAL 全部缺失。
+        report.missing.push(start_wal.to_string());
+    }
+    Ok(report)
+}
+
+/// 全量校验:逐份认证基础备份并回写清单,再核对最新可用备份之后的 WAL 链。
+pub(crate) fn verify_all(config: &BackupConfig) -> Result<VerifyReport, String> {
+    let _guard = JobGuard::acquire()?;
+    let dir = config.require_backup_dir()?;
+    let key = config.load_key()?;
+    let mut report = VerifyReport::default();
+    let mut latest_start = None;
+    for mut manifest in list_manifests(dir)? {
+        report.backups_checked += 1;
+        match verify_backup_file(dir, &key, &manifest) {
+            Ok(start_wal) => {
+                manifest.start_wal = Some(start_wal.clone());
+                manifest.verify_error = None;
+                latest_start = Some(start_wal);
+            }
+            Err(err) => {
+                tracing::warn!(backup_id = %manifest.backup_id, error = %err, "backup verification failed");
+                report.backups_failed.push(manifest.backup_id.clone());
+                manifest.verify_error = Some(err);
+            }
+        }
+        manifest.verified_at = Some(Utc::now());
+        write_manifest(dir, &manifest)?;
+    }
+    if let (Some(archive_dir), Some(start)) = (config.wal_archive_dir.as_deref(), latest_start) {
+        report.wal = Some(verify_wal_chain(archive_dir, &key, &start)?);
+    }
+    report.ok = report.backups_checked > 0
+        && report.backups_failed.is_empty()
+        && report
+            .wal
+            .as_ref()
+            .map(|wal| wal.missing.is_empty() && wal.corrupt.is_empty())
+            .unwrap_or(true);
+    Ok(report)
+}
+
+pub(crate) fn wal_archive_summary(archive_dir: &Path) -> Result<WalArchiveSummary, String> {
+    let segments = archived_segments(archive_dir)?;
+    Ok(WalArchiveSummary {
+        segments: segments.len() as u64,
+        bytes: segments.iter().map(|(_, size)| size).sum(),
+        oldest: segments.first().map(|(name, _)| name.clone()),
+        newest: segments.last().map(|(name, _)| name.clone()),
+    })
+}
+
+// ───────────────────────── 恢复 ─────────────────────────
+
+/// 解析恢复目标时间:RFC3339,或 PostgreSQL 惯用的 `2026-10-01 12:00:00+08`。
+fn parse_target_time(raw: &str) -> Result<DateTime<Utc>, String> {
+    let raw = raw.trim();
+    DateTime::parse_from_rfc3339(raw)
+        .or_else(|_| DateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S%#z"))
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|_| format!("--target-time 须带时区(如 2026-10-01T12:00:00+08:00): {raw}"))
+}
+
+/// 选择恢复所用的基础备份:显式编号,或 `latest`/缺省时取目标时间之前最新的一份。
+fn select_backup<'a>(
+    manifests: &'a [BackupManifest],
+    backup: Option<&str>,
+    target: Option<DateTime<Utc>>,
+) -> Result<&'a BackupManifest, String> {
+    match backup
+        .map(str::trim)
+        .filter(|b| !b.is_empty() && *b != "latest")
+    {
+        Some(id) => manifests
+            .iter()
+            .find(|m| m.backup_id == id)
+            .ok_or_else(|| format!("找不到备份 {id}")),
+        None => manifests
+            .iter()
+            .rfind(|m| {
+                m.verify_error.is_none() && target.map(|t| m.finished_at <= t).unwrap_or(true)
+            })
+            .ok_or_else(|| "没有早于目标时间的可用备份".to_string()),
+    }
+}
+
+/// 把实例重建到指定备份 + 目标时间点。平台必须已停止;原数据目录改名保留、不删除。
+pub(crate) fn restore(
+    config: &BackupConfig,
+    backup: Option<&str>,
+    target_time: Option<&str>,
+) -> Result<(), String> {
+    if !embedded_pg::is_enabled() {
+        return Err("仅内嵌 PostgreSQL(ONCHINA_EMBEDDED_PG=1)支持 pg-restore".to_string());
+    }
+    let dir = config.require_backup_dir()?;
+    let target = target_time.map(parse_target_time).transpose()?;
+    let manifests = list_manifests(dir)?;
+    let manifest = select_backup(&manifests, backup, target)?;
+    if let Some(target) = target {
+        if target < manifest.finished_at {
+            return Err(format!(
+                "目标时间早于备份 {} 的完成时间 {},请选更早的备份",
+                manifest.backup_id,
+                manifest
+                    .finished_at
+                    .to_rfc3339_opts(SecondsFormat::Secs, true)
+            ));
+        }
+        if config.wal_archive_dir.is_none() {
+            return Err("未配置 ONCHINA_PG_WAL_ARCHIVE_DIR,只能恢复到备份完成时刻".to_string());
+        }
+    }
+    let data_dir = embedded_pg::pg_data_dir();
+    if embedded_pg::is_running(&data_dir) {
+        return Err("内嵌 PostgreSQL 仍在运行;请先停止链上中国平台再恢复".to_string());
+    }
+    let key = config.load_key()?;
+    // 先完整认证一遍再动数据目录,坏备份不会把现有实例挪走。
+    verify_backup_file(dir, &key, manifest)?;
+
+    if data_dir.exists() {
+        let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+        let name = data_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "pgdata".to_string());
+        let aside = data_dir.with_file_name(format!("{name}.pre-restore-{stamp}"));
+        std::fs::rename(&data_dir, &aside)
+            .map_err(|e| format!("move {} aside failed: {e}", data_dir.display()))?;
+        tracing::info!(kept = %aside.display(), "previous embedded postgres data dir kept");
+    }
+    create_private_dir(&data_dir)?;
+    extract_backup(dir, &key, manifest, &data_dir)?;
+    write_recovery_settings(config, &data_dir, target)?;
+
+    // 起实例回放 WAL;-w 在可接受只读连接时返回,随后轮询到 promote 完成再停机交还。
+    let timeout = RECOVERY_TIMEOUT.as_secs().to_string();
+    embedded_pg::start_postmaster(&data_dir, timeout.as_str())?;
+    let recovered = wait_recovery_finished(&data_dir);
+    embedded_pg::stop();
+    recovered?;
+    tracing::info!(
+        backup_id = %manifest.backup_id,
+        target_time = ?target,
+        "embedded postgres restored"
+    );
+    Ok(())
+}
+
+fn create_private_dir(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("create {} failed: {e}", dir.display()))?;
+    // postmaster 拒绝组/其他用户可读的数据目录。
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+            .map_err(|e| format!("chmod {} failed: {e}", dir.display()))?;
+    }
+    Ok(())
+}
+
+/// 解密流直接管道进系统 `tar -x`(Windows 10+ 自带 bsdtar),明文不落临时文件。
+fn extract_backup(
+    dir: &Path,
+    key: &BackupKey,
+    manifest: &BackupManifest,
+    data_dir: &Path,
+) -> Result<(), String> {
+    let path = backup_file(dir, &manifest.backup_id);
+    let file = File::open(&path).map_err(|e| format!("open {} failed: {e}", path.display()))?;
+    let mut child = Command::new("tar")
+        .arg("-x")
+        .arg("-f")
+        .arg("-")
+        .arg("-C")
+        .arg(data_dir)
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("spawn tar failed: {e}"))?;
+    let decrypted = {
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| "tar stdin unavailable".to_string())?;
+        decrypt_stream(key, &mut BufReader::new(file), &mut stdin)
+            .map_err(|e| format!("decrypt backup failed: {e}"))
+    };
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("wait tar failed: {e}"))?;
+    decrypted?;
+    if !output.status.success() {
+        return Err(format!(
+            "tar exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+fn write_recovery_settings(
+    config: &BackupConfig,
+    data_dir: &Path,
+    target: Option<DateTime<Utc>>,
+) -> Result<(), String> {
+    // 无归档时备份自带 WAL(-X fetch),起库走普通崩溃恢复即一致;
+    // 此时写 recovery.signal 反而会因缺 restore_command 拒绝启动。
+    let Some(archive_dir) = config.wal_archive_dir.as_deref() else {
+        return Ok(());
+    };
+    let mut extra = String::from("\n# ── onchina pg-restore ──\n");
+    let command = wal_restore_command(archive_dir)?;
+    extra.push_str(&format!(
+        "restore_command = {}\n",
+        embedded_pg::conf_literal(&command)
+    ));
+    if let Some(target) = target {
+        extra.push_str(&format!(
+            "recovery_target_time = {}\n",
+            embedded_pg::conf_literal(&target.format("%Y-%m-%d %H:%M:%S%.6f+00").to_string())
+        ));
+    }
+    extra.push_str("recovery_target_action = 'promote'\n");
+    let conf = data_dir.join("postgresql.conf");
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&conf)
+        .map_err(|e| format!("open {} failed: {e}", conf.display()))?;
+    file.write_all(extra.as_bytes())
+        .map_err(|e| format!("write {} failed: {e}", conf.display()))?;
+    File::create(data_dir.join("recovery.signal"))
+        .map_err(|e| format!("create recovery.signal failed: {e}"))?;
+    Ok(())
+}
+
+fn wait_recovery_finished(data_dir: &Path) -> Result<(), String> {
+    let admin_url = embedded_pg::database_url_for("postgres");
+    let deadline = std::time::Instant::now() + RECOVERY_TIMEOUT;
+    while std::time::Instant::now() < deadline {
+        if !embedded_pg::is_running(data_dir) {
+            return Err(format!(
+                "恢复过程中 PostgreSQL 已退出(目标时间可能超出已归档 WAL),详见 {}",
+                data_dir.join("postgres.log").display()
+            ));
+        }
+        if let Ok(mut client) = postgres::Client::connect(admin_url.as_str(), postgres::NoTls) {
+            if let Ok(row) = client.query_one("SELECT pg_is_in_recovery()", &[]) {
+                if !row.get::<_, bool>(0) {
+                    return Ok(());
+                }
+            }
+        }
+        std::thread::sleep(RECOVERY_POLL_INTERVAL);
+    }
+    Err("等待 PITR 回放完成超时".to_string())
+}
+
+// ───────────────────────── 定时任务 ─────────────────────────
+
+/// 下一次应备份时间:无可用备份时立即,否则最新备份完成后一个周期。
+pub(crate) fn next_due_at(config: &BackupConfig, manifests: &[BackupManifest]) -> DateTime<Utc> {
+    manifests
+        .iter()
+        .filter(|m| m.verify_error.is_none())
+        .map(|m| m.finished_at)
+        .max()
+        .map(|last| {
+            last + chrono::Duration::seconds(config.interval_secs.min(MAX_INTERVAL_SECS) as i64)
+        })
+        .unwrap_or_else(Utc::now)
+}
+
+/// 周期检查是否到期;到期则在阻塞线程里拉备份。失败只告警,下一轮重试。
+pub(crate) async fn pg_backup_loop(config: BackupConfig) {
+    // 到期判定按分钟粒度复查,周期本身由 `ONCHINA_PG_BACKUP_INTERVAL_SECS` 决定。
+    let mut ticker = tokio::time::interval(Duration::from_secs(config.interval_secs.min(60)));
+    loop {
+        ticker.tick().await;
+        let Some(dir) = config.backup_dir.clone() else {
+            return;
+        };
+        let due = match list_manifests(&dir) {
+            Ok(manifests) => next_due_at(&config, &manifests) <= Utc::now(),
+            Err(err) => {
+                tracing::warn!(error = %err, "list embedded postgres backups failed");
+                false
+            }
+        };
+        if !due || job_running() {
+            continue;
+        }
+        let job_config = config.clone();
+        match tokio::task::spawn_blocking(move || run_base_backup(&job_config)).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(err)) => tracing::warn!(error = %err, "embedded postgres base backup failed"),
+            Err(err) => tracing::warn!(error = %err, "embedded postgres backup task panicked"),
+        }
+    }
+}
+
+#[cfg(test)]
+// 测试用固定夹具,失败即应立刻中止。
+#[allow(clippy::expect_used, clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn manifest(id: &str, finished_at: &str, failed: bool) -> BackupManifest {
+        BackupManifest {
+            backup_id: id.to_string(),
+            started_at: finished_at.parse().unwrap(),
+            finished_at: finished_at.parse().unwrap(),
+            plaintext_bytes: 0,
+            plaintext_sha256: String::new(),
+            ciphertext_bytes: 0,
+            ciphertext_sha256: String::new(),
+            key_fingerprint: String::new(),
+            start_wal: Some("000000010000000000000002".to_string()),
+            verified_at: None,
+            verify_error: failed.then(|| "bad".to_string()),
+        }
+    }
+
+    #[test]
+    fn next_segment_wraps_log_id() {
+        assert_eq!(
+            next_segment("0000000100000000000000FE").as_deref(),
+            Some("0000000100000000000000FF")
+        );
+        assert_eq!(
+            next_segment("0000000100000000000000FF").as_deref(),
+            Some("000000010000000100000000")
+        );
+    }
+
+    #[test]
+    fn select_backup_prefers_latest_usable_before_target() {
+        let manifests = vec![
+            manifest("basebackup_a", "2026-10-01T00:00:00Z", false),
+            manifest("basebackup_b", "2026-10-02T00:00:00Z", false),
+            manifest("basebackup_c", "2026-10-03T00:00:00Z", true),
+        ];
+        let latest = select_backup(&manifests, None, None).unwrap();
+        assert_eq!(latest.backup_id, "basebackup_b");
+        let target = parse_target_time("2026-10-01T12:00:00+08:00").unwrap();
+        let picked = select_backup(&manifests, Some("latest"), Some(target)).unwrap();
+        assert_eq!(picked.backup_id, "basebackup_a");
+        assert!(select_backup(&manifests, Some("basebackup_x"), None).is_err());
+        assert_eq!(parse_target_time("2026-10-01 12:00:00+08").unwrap(), target);
+        assert!(parse_target_time("yesterday").is_err());
+    }
+
+    #[test]
+    fn wal_file_names_reject_path_components() {
+        assert!(validate_wal_file_name("000000010000000000000002").is_ok());
+        assert!(validate_wal_file_name("00000002.history").is_ok());
+        assert!(validate_wal_file_name("../postgresql.conf").is_err());
+        assert!(validate_wal_file_name("a/b").is_err());
+    }
+
+    #[test]
+    fn wal_archive_roundtrip_and_chain_report() {
+        let root =
+            std::env::temp_dir().join(format!("onchina-pg-backup-test-{}", std::process::id()));
+        let archive = root.join("archive");
+        let key_file = root.join("backup.key");
+        std::fs::create_dir_all(&root).unwrap();
+        let segment = root.join("000000010000000000000002");
+        std::fs::write(&segment, vec![9u8; 4096]).unwrap();
+
+        // 回调不生成密钥:首次基础备份之前归档/取回都必须失败,且不落密钥文件。
+        assert!(
+            archive_wal_file(&archive, &key_file, &segment, "000000010000000000000002").is_err()
+        );
+        assert!(!key_file.exists());
+        BackupKey::load_or_create(&key_file).unwrap();
+
+        archive_wal_file(&archive, &key_file, &segment, "000000010000000000000002").unwrap();
+        // 重复归档同内容幂等;内容不同必须拒绝。
+        archive_wal_file(&archive, &key_file, &segment, "000000010000000000000002").unwrap();
+        std::fs::write(&segment, vec![8u8; 4096]).unwrap();
+        assert!(
+            archive_wal_file(&archive, &key_file, &segment, "000000010000000000000002").is_err()
+        );
+
+        std::fs::write(&segment, vec![7u8; 4096]).unwrap();
+        archive_wal_file(&archive, &key_file, &segment, "000000010000000000000004").unwrap();
+
+        let restored = root.join("restored");
+        assert!(
+            restore_wal_file(&archive, &key_file, "000000010000000000000002", &restored).unwrap()
+        );
+        assert_eq!(std::fs::read(&restored).unwrap(), vec![9u8; 4096]);
+        assert!(
+            !restore_wal_file(&archive, &key_file, "000000010000000000000003", &restored).unwrap()
+        );
+
+        // 密钥被挪走时取回失败,也不会就地换一把新密钥。
+        let moved_key = root.join("backup.key.moved");
+        std::fs::rename(&key_file, &moved_key).unwrap();
+        assert!(
+            restore_wal_file(&archive, &key_file, "000000010000000000000002", &restored).is_err()
+        );
+        assert!(!key_file.exists());
+        std::fs::rename(&moved_key, &key_file).unwrap();
+        let key = BackupKey::load(&key_file).unwrap();
+        let report = verify_wal_chain(&archive, &key, "000000010000000000000002").unwrap();
+        assert_eq!(report.segments_checked, 2);
+        assert_eq!(report.missing, vec!["000000010000000000000003".to_string()]);
+        assert!(report.corrupt.is_empty());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,622 @@
+//! 备份流式加密格式与 tar 结构扫描。
+//!
+//! 文件格式:`MAGIC(8) || salt(16) || 帧*`。每帧 `u32 BE 长度 || AES-256-GCM 密文`,
+//! 明文按 [`CHUNK_SIZE`] 切块;文件密钥 = HKDF-SHA256(主密钥, salt),nonce 为帧序号,
+//! AAD = `MAGIC || 帧序号 || 末帧标记`。末帧在长度最高位打标,解密端读到末帧后要求 EOF,
+//! 因此整帧删除、截断、调序、拼接都会认证失败。
+
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use hkdf::Hkdf;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+const MAGIC: &[u8; 8] = b"OCPGBK01";
+const SALT_LEN: usize = 16;
+const KDF_INFO: &[u8] = b"citizenchain/onchina/pg-backup/v1";
+/// 单帧明文上限;PG 段文件 16 MiB,正好 16 帧。
+pub(crate) const CHUNK_SIZE: usize = 1 << 20;
+const TAG_LEN: usize = 16;
+const FINAL_FLAG: u32 = 0x8000_0000;
+
+/// 备份主密钥(32 字节,磁盘上为 hex)。
+pub(crate) struct BackupKey(Zeroizing<[u8; 32]>);
+
+impl BackupKey {
+    pub(crate) fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(Zeroizing::new(bytes))
+    }
+
+    /// 读取已有主密钥;缺失即报错,绝不就地重新生成(新密钥解不开任何既有备份与归档)。
+    pub(crate) fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path).map(Zeroizing::new) {
+            Ok(text) => Self::parse(text.as_str()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Err(format!(
+                "备份主密钥 {} 不存在:须放回离机托管的密钥文件,不得重新生成",
+                path.display()
+            )),
+            Err(err) => Err(format!("read {} failed: {err}", path.display())),
+        }
+    }
+
+    /// 读取主密钥;不存在时生成并以 0600 落盘,此后绝不覆盖(覆盖即作废全部既有备份)。
+    /// 只供首次基础备份调用,其余路径一律走 [`Self::load`]。
+    pub(crate) fn load_or_create(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path).map(Zeroizing::new) {
+            Ok(text) => return Self::parse(text.as_str()),
+            Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                return Err(format!("read {} failed: {err}", path.display()));
+            }
+            Err(_) => {}
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("create {} failed: {e}", parent.display()))?;
+        }
+        let mut bytes = Zeroizing::new([0u8; 32]);
+        getrandom::getrandom(bytes.as_mut()).map_err(|e| format!("系统随机数不可用: {e}"))?;
+        let text = Zeroizing::new(hex::encode(*bytes));
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = match options.open(path) {
+            Ok(file) => file,
+            // 并发首次生成:以先落盘者为准。
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                return Self::load(path);
+            }
+            Err(err) => return Err(format!("create {} failed: {err}", path.display())),
+        };
+        file.write_all(text.as_bytes())
+            .and_then(|()| file.sync_all())
+            .map_err(|e| format!("write {} failed: {e}", path.display()))?;
+        tracing::warn!(
+            path = %path.display(),
+            "generated embedded postgres backup key; copy it off this machine, backups cannot be restored without it"
+        );
+        Ok(Self(bytes))
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let raw = Zeroizing::new(
+            hex::decode(text.trim().trim_start_matches("0x"))
+                .map_err(|_| "备份密钥文件不是合法 hex".to_string())?,
+        );
+        let bytes: [u8; 32] = raw
+            .as_slice()
+            .try_into()
+            .map_err(|_| "备份密钥必须为 32 字节".to_string())?;
+        Ok(Self::from_bytes(bytes))
+    }
+
+    /// 密钥指纹 = sha256(密钥) 前 16 字节;清单与状态页只展示指纹,供核对离机托管副本。
+    pub(crate) fn fingerprint(&self) -> String {
+        format!("0x{}", hex::encode(&Sha256::digest(self.0.as_ref())[..16]))
+    }
+
+    fn file_cipher(&self, salt: &[u8; SALT_LEN]) -> Result<Aes256Gcm, String> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        Hkdf::<Sha256>::new(Some(salt), self.0.as_ref())
+            .expand(KDF_INFO, key.as_mut())
+            .map_err(|_| "派生备份文件密钥失败".to_string())?;
+        Aes256Gcm::new_from_slice(key.as_ref()).map_err(|_| "初始化备份加密失败".to_string())
+    }
+}
+
+/// 一次加/解密的明文统计。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct StreamStats {
+    pub(crate) plaintext_bytes: u64,
+    pub(crate) plaintext_sha256: String,
+}
+
+fn nonce_for(index: u64) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[4..].copy_from_slice(&index.to_be_bytes());
+    nonce
+}
+
+fn frame_aad(index: u64, last: bool) -> [u8; 17] {
+    let mut aad = [0u8; 17];
+    aad[..8].copy_from_slice(MAGIC);
+    aad[8..16].copy_from_slice(&index.to_be_bytes());
+    aad[16] = u8::from(last);
+    aad
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// 尽量读满 `buf`;返回实际读到的字节数(小于 `buf.len()` 即已到 EOF)。
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+/// 把 `reader` 的全部明文加密写入 `writer`。
+pub(crate) fn encrypt_stream<R: Read, W: Write>(
+    key: &BackupKey,
+    reader: &mut R,
+    writer: &mut W,
+) -> io::Result<StreamStats> {
+    let mut salt = [0u8; SALT_LEN];
+    getrandom::getrandom(&mut salt).map_err(|e| io::Error::other(format!("随机数不可用: {e}")))?;
+    let cipher = key.file_cipher(&salt).map_err(io::Error::other)?;
+    writer.write_all(MAGIC)?;
+    writer.write_all(&salt)?;
+
+    let mut buf = Zeroizing::new(vec![0u8; CHUNK_SIZE]);
+    let mut hasher = Sha256::new();
+    let mut total = 0u64;
+    let mut index = 0u64;
+    loop {
+        let n = read_full(reader, buf.as_mut_slice())?;
+        // 恰好整块时多写一个空末帧,解密端据此确认流完整。
+        let last = n < CHUNK_SIZE;
+        hasher.update(&buf[..n]);
+        total += n as u64;
+        let aad = frame_aad(index, last);
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce_for(index)),
+                Payload {
+                    msg: &buf[..n],
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| io::Error::other("备份分块加密失败"))?;
+        let mut len = ciphertext.len() as u32;
+        if last {
+            len |= FINAL_FLAG;
+        }
+        writer.write_all(&len.to_be_bytes())?;
+        writer.write_all(&ciphertext)?;
+        if last {
+            break;
+        }
+        index += 1;
+    }
+    writer.flush()?;
+    Ok(StreamStats {
+        plaintext_bytes: total,
+        plaintext_sha256: hex::encode(hasher.finalize()),
+    })
+}
+
+/// 解密并认证整个流,明文写入 `writer`;任一帧失败、缺末帧或末帧后仍有数据均报错。
+pub(crate) fn decrypt_stream<R: Read, W: Write>(
+    key: &BackupKey,
+    reader: &mut R,
+    writer: &mut W,
+) -> io::Result<StreamStats> {
+    let mut header = [0u8; 8 + SALT_LEN];
+    if read_full(reader, &mut header)? != header.len() || &header[..8] != MAGIC {
+        return Err(invalid("不是 onchina 加密备份文件"));
+    }
+    let mut salt = [0u8; SALT_LEN];
+    salt.copy_from_slice(&header[8..]);
+    let cipher = key.file_cipher(&salt).map_err(io::Error::other)?;
+
+    let mut frame = vec![0u8; CHUNK_SIZE + TAG_LEN];
+    let mut hasher = Sha256::new();
+    let mut total = 0u64;
+    let mut index = 0u64;
+    loop {
+        let mut len_bytes = [0u8; 4];
+        if read_full(reader, &mut len_bytes)? != 4 {
+            return Err(invalid("备份文件被截断(缺少末帧)"));
+        }
+        let raw_len = u32::from_be_bytes(len_bytes);
+        let last = raw_len & FINAL_FLAG != 0;
+        let len = (raw_len & !FINAL_FLAG) as usize;
+        if !(TAG_LEN..=CHUNK_SIZE + TAG_LEN).contains(&len) {
+            return Err(invalid(format!("备份第 {index} 帧长度非法")));
+        }
+        if read_full(reader, &mut frame[..len])? != len {
+            return Err(invalid("备份文件被截断"));
+        }
+        let aad = frame_aad(index, last);
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(
+                    Nonce::from_slice(&nonce_for(index)),
+                    Payload {
+                        msg: &frame[..len],
+                        aad: &aad,
+                    },
+                )
+                .map_err(|_| invalid(format!("备份第 {index} 帧认证失败(密钥不符或数据被篡改)")))?,
+        );
+        hasher.update(plaintext.as_slice());
+        total += plaintext.len() as u64;
+        writer.write_all(plaintext.as_slice())?;
+        if last {
+            break;
+        }
+        index += 1;
+    }
+    let mut trailing = [0u8; 1];
+    if read_full(reader, &mut trailing)? != 0 {
+        return Err(invalid("备份末帧之后存在多余数据"));
+    }
+    writer.flush()?;
+    Ok(StreamStats {
+        plaintext_bytes: total,
+        plaintext_sha256: hex::encode(hasher.finalize()),
+    })
+}
+
+/// 透传写入并累计 sha256 / 字节数(用于记录密文摘要)。
+pub(crate) struct HashingWriter<W> {
+    inner: W,
+    hasher: Sha256,
+    bytes: u64,
+}
+
+impl<W: Write> HashingWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            bytes: 0,
+        }
+    }
+
+    pub(crate) fn finish(self) -> (W, u64, String) {
+        (self.inner, self.bytes, hex::encode(self.hasher.finalize()))
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.bytes += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// 透传读取并累计 sha256 / 字节数(校验时核对清单里的密文摘要)。
+pub(crate) struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+    bytes: u64,
+}
+
+impl<R: Read> HashingReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            bytes: 0,
+        }
+    }
+
+    pub(crate) fn finish(self) -> (u64, String) {
+        (self.bytes, hex::encode(self.hasher.finalize()))
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.bytes += n as u64;
+        Ok(n)
+    }
+}
+
+const TAR_BLOCK: usize = 512;
+/// `backup_label` 只有几百字节;超过上限视为异常归档,不再收集。
+const MAX_LABEL_BYTES: u64 = 64 * 1024;
+
+/// 流式 tar 结构扫描:逐块核对头部校验和,收集条目名,并截取 `backup_label` 内容。
+///
+/// 作为解密输出的 `Write` 端使用,校验时不必落盘解包。
+#[derive(Default)]
+pub(crate) struct TarScanner {
+    header: Vec<u8>,
+    remaining: u64,
+    capturing: bool,
+    label: Vec<u8>,
+    label_size: u64,
+    zero_blocks: u8,
+    finished: bool,
+    pub(crate) entries: u64,
+    pub(crate) has_pg_version: bool,
+    pub(crate) backup_label: Option<String>,
+}
+
+impl TarScanner {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// 流结束时调用:必须已见到两个全零结束块。
+    pub(crate) fn finish(&self) -> Result<(), String> {
+        if !self.finished {
+            return Err("tar 归档不完整(缺少结束块)".to_string());
+        }
+        if !self.has_pg_version {
+            return Err("备份缺少 PG_VERSION".to_string());
+        }
+        if self.backup_label.is_none() {
+            return Err("备份缺少 backup_label".to_string());
+        }
+        Ok(())
+    }
+
+    fn on_header(&mut self) -> io::Result<()> {
+        let block = std::mem::take(&mut self.header);
+        if block.iter().all(|b| *b == 0) {
+            self.zero_blocks += 1;
+            if self.zero_blocks >= 2 {
+                self.finished = true;
+            }
+            return Ok(());
+        }
+        if self.zero_blocks > 0 {
+            return Err(invalid("tar 结束块之间出现数据"));
+        }
+        let stored =
+            parse_octal(&block[148..156]).ok_or_else(|| invalid("tar 头校验和字段非法"))?;
+        let computed: u64 = block
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                if (148..156).contains(&i) {
+                    32
+                } else {
+                    u64::from(*b)
+                }
+            })
+            .sum();
+        if stored != computed {
+            return Err(invalid(format!(
+                "tar 第 {} 个条目头校验和不符",
+                self.entries + 1
+            )));
+        }
+        let size = parse_size(&block[124..136]).ok_or_else(|| invalid("tar 条目长度非法"))?;
+        let name = entry_name(&block);
+        self.entries += 1;
+        let typeflag = block[156];
+        let regular = typeflag == b'0' || typeflag == 0;
+        if regular && name == "PG_VERSION" {
+            self.has_pg_version = true;
+        }
+        self.capturing = regular && name == "backup_label" && size <= MAX_LABEL_BYTES;
+        self.label.clear();
+        self.remaining = size.div_ceil(TAR_BLOCK as u64) * TAR_BLOCK as u64;
+        if self.capturing && size == 0 {
+            self.backup_label = Some(String::new());
+            self.capturing = false;
+        }
+        self.label_size = size;
+        Ok(())
+    }
+}
+
+impl Write for TarScanner {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut rest = buf;
+        while !rest.is_empty() {
+            if self.finished {
+                // 结束块之后只允许全零填充(pg_basebackup 按记录块补齐)。
+                if rest.iter().any(|b| *b != 0) {
+                    return Err(invalid("tar 结束块之后存在数据"));
+                }
+                break;
+            }
+            if self.remaining > 0 {
+                let take = rest
+                    .len()
+                    .min(usize::try_from(self.remaining).unwrap_or(usize::MAX));
+                if self.capturing {
+                    let want = (self.label_size as usize).saturating_sub(self.label.len());
+                    self.label.extend_from_slice(&rest[..take.min(want)]);
+                    if self.label.len() as u64 == self.label_size {
+                        self.backup_label = Some(String::from_utf8_lossy(&self.label).into_owned());
+                        self.capturing = false;
+                    }
+                }
+                self.remaining -= take as u64;
+                rest = &rest[take..];
+                continue;
+            }
+            let take = rest.len().min(TAR_BLOCK - self.header.len());
+            self.header.extend_from_slice(&rest[..take]);
+            rest = &rest[take..];
+            if self.header.len() == TAR_BLOCK {
+                self.on_header()?;
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn parse_octal(field: &[u8]) -> Option<u64> {
+    let text = std::str::from_utf8(field).ok()?;
+    let text = text.trim_matches(|c: char| c == '\0' || c == ' ');
+    if text.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(text, 8).ok()
+}
+
+/// 条目长度:常规八进制;超过 8 GiB 的文件用 GNU base-256(首字节最高位为 1)。
+fn parse_size(field: &[u8]) -> Option<u64> {
+    if field[0] & 0x80 != 0 {
+        let mut value = u64::from(field[0] & 0x7f);
+        for b in &field[1..] {
+            value = value.checked_mul(256)?.checked_add(u64::from(*b))?;
+        }
+        return Some(value);
+    }
+    parse_octal(field)
+}
+
+fn entry_name(block: &[u8]) -> String {
+    let field = |range: std::ops::Range<usize>| {
+        let raw = &block[range];
+        let end = raw.iter().position(|b| *b == 0).unwrap_or(raw.len());
+        String::from_utf8_lossy(&raw[..end]).into_owned()
+    };
+    let name = field(0..100);
+    let prefix = if &block[257..262] == b"ustar" {
+        field(345..500)
+    } else {
+        String::new()
+    };
+    let full = if prefix.is_empty() {
+        name
+    } else {
+        format!("{prefix}/{name}")
+    };
+    full.trim_start_matches("./").to_string()
+}
+
+/// 从 `backup_label` 取起始 WAL 段名:`START WAL LOCATION: 0/2000028 (file 000000010000000000000002)`。
+pub(crate) fn start_wal_from_label(label: &str) -> Option<String> {
+    let line = label
+        .lines()
+        .find(|line| line.starts_with("START WAL LOCATION:"))?;
+    let start = line.find("(file ")? + "(file ".len();
+    let name = line[start..].trim_end_matches(')').trim();
+    is_wal_segment_name(name).then(|| name.to_string())
+}
+
+/// WAL 段名:24 位大写 hex(时间线 8 + 日志号 8 + 段号 8)。
+pub(crate) fn is_wal_segment_name(name: &str) -> bool {
+    name.len() == 24
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'A'..=b'F').contains(&b))
+}
+
+#[cfg(test)]
+// 测试用固定夹具,失败即应立刻中止。
+#[allow(clippy::expect_used, clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn key() -> BackupKey {
+        BackupKey::from_bytes([7u8; 32])
+    }
+
+    fn encrypt(data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        encrypt_stream(&key(), &mut &data[..], &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn stream_roundtrip_covers_exact_and_partial_chunks() {
+        for len in [0, 10, CHUNK_SIZE, CHUNK_SIZE + 1, 2 * CHUNK_SIZE] {
+            let data = (0..len).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+            let sealed = encrypt(&data);
+            let mut plain = Vec::new();
+            let stats = decrypt_stream(&key(), &mut sealed.as_slice(), &mut plain).unwrap();
+            assert_eq!(plain, data);
+            assert_eq!(stats.plaintext_bytes, len as u64);
+            assert_eq!(stats.plaintext_sha256, hex::encode(Sha256::digest(&data)));
+        }
+    }
+
+    #[test]
+    fn stream_rejects_wrong_key_tamper_and_truncation() {
+        let data = vec![3u8; CHUNK_SIZE + 100];
+        let sealed = encrypt(&data);
+
+        let other = BackupKey::from_bytes([8u8; 32]);
+        assert!(decrypt_stream(&other, &mut sealed.as_slice(), &mut io::sink()).is_err());
+
+        let mut tampered = sealed.clone();
+        tampered[40] ^= 1;
+        assert!(decrypt_stream(&key(), &mut tampered.as_slice(), &mut io::sink()).is_err());
+
+        // 恰好截在整帧边界(只剩首帧),必须因缺末帧失败。
+        let first_frame_end = 8 + SALT_LEN + 4 + CHUNK_SIZE + TAG_LEN;
+        let truncated = &sealed[..first_frame_end];
+        assert!(decrypt_stream(&key(), &mut &truncated[..], &mut io::sink()).is_err());
+
+        let mut extended = sealed.clone();
+        extended.push(0);
+        assert!(decrypt_stream(&key(), &mut extended.as_slice(), &mut io::sink()).is_err());
+    }
+
+    fn tar_entry(name: &str, body: &[u8]) -> Vec<u8> {
+        let mut header = [0u8; TAR_BLOCK];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[100..107].copy_from_slice(b"0000600");
+        header[124..135].copy_from_slice(format!("{:011o}", body.len()).as_bytes());
+        header[156] = b'0';
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[148..156].copy_from_slice(b"        ");
+        let sum: u32 = header.iter().map(|b| u32::from(*b)).sum();
+        header[148..155].copy_from_slice(format!("{sum:06o}\0").as_bytes());
+        let mut out = header.to_vec();
+        out.extend_from_slice(body);
+        out.resize(out.len().div_ceil(TAR_BLOCK) * TAR_BLOCK, 0);
+        out
+    }
+
+    #[test]
+    fn tar_scanner_finds_label_and_detects_corruption() {
+        let label = b"START WAL LOCATION: 0/2000028 (file 000000010000000000000002)\nCHECKPOINT LOCATION: 0/2000060\n";
+        let mut archive = tar_entry("PG_VERSION", b"16\n");
+        archive.extend(tar_entry("backup_label", label));
+        archive.extend(tar_entry("base/1/1259", &[1u8; 700]));
+        archive.extend([0u8; TAR_BLOCK * 4]);
+
+        let mut scanner = TarScanner::new();
+        // 逐段小块写入,覆盖头部跨写调用的情形。
+        for chunk in archive.chunks(100) {
+            scanner.write_all(chunk).unwrap();
+        }
+        scanner.finish().unwrap();
+        assert_eq!(scanner.entries, 3);
+        assert_eq!(
+            scanner
+                .backup_label
+                .as_deref()
+                .and_then(start_wal_from_label)
+                .as_deref(),
+            Some("000000010000000000000002")
+        );
+
+        let mut corrupt = archive.clone();
+        corrupt[TAR_BLOCK * 2 + 5] ^= 1;
+        assert!(TarScanner::new().write_all(&corrupt).is_err());
+
+        let mut truncated = TarScanner::new();
+        truncated.write_all(&archive[..TAR_BLOCK * 4]).unwrap();
+        assert!(truncated.finish().is_err());
+    }
+}
@@ -39,22 +39,16 @@ pub(crate) fn append_audit_log(
         }
     };
     if let Err(err) = state.db.with_client(move |conn| {
-        conn.execute(
-            "INSERT INTO audit(
-                province_code, city_code, actor_account_id, action, target_cid, detail
-             )
-             VALUES ($1, $2, $3, $4, $5, $6)",
-            &[
-                &province_code,
-                &city_code,
-                &actor_account_id,
-                &action,
-                &target_cid,
-                &detail,
-            ],
+        crate::audit::chain::append_chained_conn(
+            conn,
+            province_code.as_str(),
+            city_code.as_deref(),
+            actor_account_id.as_deref(),
+            action.as_str(),
+            target_cid.as_deref(),
+            &detail,
         )
-        .map_err(|e| format!("insert audit failed: {e}"))?;
-        Ok(())
+        .map(|_| ())
     }) {
         tracing::warn!(action = %log_action, error = %err, "append audit failed");
     }
@@ -101,7 +101,7 @@ pub(crate) fn validate_citizen_input(
     // 匿名占号:居住省/市取办理注册局作用域(给本地记录一个省分区键);其余档案(姓名/出生/
     // 性别/出生地/居住镇)后期在详情页编辑完善(现有严格逻辑),占号阶段一律留空。
     let scope = crate::scope::get_visible_scope(ctx);
-    if !scope.can_write {
+    if !scope.can_write || scope.is_own_institution() {
         return Err(api_error(StatusCode::FORBIDDEN, 1003, "当前登录无办理权限"));
     }
     let Some(province_name) = scope.locked_province_name.clone() else {
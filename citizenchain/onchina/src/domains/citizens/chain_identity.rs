@@ -460,7 +460,9 @@ pub(crate) async fn complete_citizen_onchain_signature(
     // CitizenWallet 只签名一次并显示响应二维码；OnChina 回扫后经
     // /api/admin/chain/submit 统一组装和提交。
     let prepared =
-        match crate::core::chain_submit::prepare_signing(&call, ctx.account_id.as_str()).await {
+        match crate::core::chain_submit::prepare_signing(&state.db, &call, ctx.account_id.as_str())
+            .await
+        {
             Ok(v) => v,
             Err(err) => {
                 tracing::error!(error = %err, "prepare identity push signing failed");
@@ -700,6 +702,13 @@ pub(crate) fn ensure_record_in_admin_scope(
     record: &CitizenRecord,
 ) -> Result<(), axum::response::Response> {
     let scope = crate::scope::get_visible_scope(ctx);
+    if scope.is_own_institution() {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            1003,
+            "公民档案不在当前注册局办理范围内",
+        ));
+    }
     let province_name =
         crate::cid::china::area_name_by_codes(record.province_code.as_str(), None, None)
             .map(|(province, _, _)| province.to_string())
@@ -18,9 +18,9 @@ use uuid::Uuid;
 
 use crate::*;
 
-struct StoredCitizenDocument {
-    meta: CitizenDocument,
-    file_path: String,
+pub(crate) struct StoredCitizenDocument {
+    pub(crate) meta: CitizenDocument,
+    pub(crate) file_path: String,
 }
 
 pub(crate) async fn admin_list_citizens(
@@ -80,14 +80,7 @@ fn resolve_citizen_query_scope(
     auth_ctx: &crate::auth::login::AdminAuthContext,
     query: &CitizensQuery,
 ) -> Result<(Option<String>, Option<String>), axum::response::Response> {
-    let scope = crate::scope::get_visible_scope(auth_ctx);
-    if !scope.can_write {
-        return Err(api_error(
-            StatusCode::FORBIDDEN,
-            1003,
-            "当前登录无公民办理权限",
-        ));
-    }
+    let scope = citizen_read_scope(auth_ctx, "当前登录无公民办理权限")?;
     let province_name = query
         .province_name
         .as_deref()
@@ -225,6 +218,13 @@ fn legal_representative_scope_from_create_context(
         .filter(|v| !v.is_empty())
         .ok_or_else(|| api_error(StatusCode::BAD_REQUEST, 1001, "city_name is required"))?;
     let scope = crate::scope::get_visible_scope(auth_ctx);
+    if scope.is_own_institution() {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            1003,
+            "当前登录无公民查询权限",
+        ));
+    }
     if !scope.includes_province(province_name) {
         return Err(api_error(
             StatusCode::FORBIDDEN,
@@ -367,7 +367,7 @@ pub(crate) async fn admin_search_legal_representative_citizens(
 }
 
 impl Db {
-    fn list_citizen_documents(
+    pub(crate) fn list_citizen_documents(
         &self,
         province_code: &str,
         cid_number: &str,
@@ -404,7 +404,7 @@ impl Db {
         })
     }
 
-    fn insert_citizen_document(
+    pub(crate) fn insert_citizen_document(
         &self,
         doc: &CitizenDocument,
         province_code: &str,
@@ -416,38 +416,49 @@ impl Db {
         let city_code = city_code.to_string();
         let file_path = file_path.to_string();
         self.with_client(move |conn| {
-            let file_size = i64::try_from(doc.file_size)
-                .map_err(|_| "citizen document file size too large".to_string())?;
-            let row = conn
-                .query_one(
-                    "INSERT INTO citizen_documents (
-                        cid_number, province_code, city_code, file_name, document_type,
-                        file_size, file_path, file_hash, uploader_account_id, uploaded_at
-                     ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-                     RETURNING id",
-                    &[
-                        &doc.cid_number,
-                        &province_code,
-                        &city_code,
-                        &doc.file_name,
-                        &doc.document_type,
-                        &file_size,
-                        &file_path,
-                        &doc.file_hash,
-                        &doc.uploader_account_id,
-                        &doc.uploaded_at,
-                    ],
-                )
-                .map_err(|e| format!("insert citizen document failed: {e}"))?;
-            let id: i64 = row.get(0);
-            Ok(CitizenDocument {
-                id: u64::try_from(id).unwrap_or(0),
-                ..doc
-            })
+            Self::insert_citizen_document_row(conn, doc, &province_code, &city_code, &file_path)
+        })
+    }
+
+    /// 在给定连接/事务上登记一条资料文件(档案移交导入与记录落库同一事务)。
+    pub(crate) fn insert_citizen_document_row<C: postgres::GenericClient>(
+        conn: &mut C,
+        doc: CitizenDocument,
+        province_code: &str,
+        city_code: &str,
+        file_path: &str,
+    ) -> Result<CitizenDocument, String> {
+        let file_size = i64::try_from(doc.file_size)
+            .map_err(|_| "citizen document file size too large".to_string())?;
+        let row = conn
+            .query_one(
+                "INSERT INTO citizen_documents (
+                    cid_number, province_code, city_code, file_name, document_type,
+                    file_size, file_path, file_hash, uploader_account_id, uploaded_at
+                 ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                 RETURNING id",
+                &[
+                    &doc.cid_number,
+                    &province_code,
+                    &city_code,
+                    &doc.file_name,
+                    &doc.document_type,
+                    &file_size,
+                    &file_path,
+                    &doc.file_hash,
+                    &doc.uploader_account_id,
+                    &doc.uploaded_at,
+                ],
+            )
+            .map_err(|e| format!("insert citizen document failed: {e}"))?;
+        let id: i64 = row.get(0);
+        Ok(CitizenDocument {
+            id: u64::try_from(id).unwrap_or(0),
+            ..doc
         })
     }
 
-    fn get_citizen_document(
+    pub(crate) fn get_citizen_document(
         &self,
         province_code: &str,
         cid_number: &str,
@@ -509,19 +520,72 @@ impl Db {
     }
 }
 
-fn ensure_citizen_document_scope(
-    state: &AppState,
+/// 公民档案读路径的可见范围:空范围与自机构档(私权机构只见本机构)一律拒绝;
+/// 只读管理员照常可读,写路径另经 [`ensure_citizen_write_allowed`]。
+fn citizen_read_scope(
     auth_ctx: &crate::auth::login::AdminAuthContext,
-    cid_number: &str,
-) -> Result<CitizenRecord, axum::response::Response> {
+    denied_message: &'static str,
+) -> Result<crate::scope::rules::VisibleScope, axum::response::Response> {
     let scope = crate::scope::get_visible_scope(auth_ctx);
-    if !scope.can_write {
+    if scope.is_empty() || scope.is_own_institution() {
+        return Err(api_error(StatusCode::FORBIDDEN, 1003, denied_message));
+    }
+    Ok(scope)
+}
+
+/// 公民资料上传、删除与档案移交导出的写权限:只读管理员一律拒绝。
+fn ensure_citizen_write_allowed(
+    auth_ctx: &crate::auth::login::AdminAuthContext,
+) -> Result<(), axum::response::Response> {
+    if !crate::scope::get_visible_scope(auth_ctx).can_write {
         return Err(api_error(
             StatusCode::FORBIDDEN,
             1003,
-            "当前登录无公民资料库权限",
+            "只读管理员不能修改公民资料",
         ));
     }
+    Ok(())
+}
+
+/// 校验公民档案落在可见省/市内;不在范围内按 403 拒绝,不暴露档案是否存在以外的信息。
+fn ensure_citizen_record_in_scope(
+    scope: &crate::scope::rules::VisibleScope,
+    province_code: &str,
+    city_code: &str,
+) -> Result<(), axum::response::Response> {
+    let province_name = crate::cid::china::area_name_by_codes(province_code, None, None)
+        .map(|(province, _, _)| province.to_string())
+        .unwrap_or_default();
+    let city_name = crate::cid::china::area_name_by_codes(province_code, Some(city_code), None)
+        .and_then(|(_, city, _)| city.map(str::to_string))
+        .unwrap_or_default();
+    if !scope.includes_province(province_name.as_str()) || !scope.includes_city(city_name.as_str())
+    {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            1003,
+            "公民档案不在当前注册局办理范围内",
+        ));
+    }
+    Ok(())
+}
+
+/// 写路径的公民档案定位:先拒只读管理员,再按读路径校验可见范围。
+pub(crate) fn ensure_citizen_document_write_scope(
+    state: &AppState,
+    auth_ctx: &crate::auth::login::AdminAuthContext,
+    cid_number: &str,
+) -> Result<CitizenRecord, axum::response::Response> {
+    ensure_citizen_write_allowed(auth_ctx)?;
+    ensure_citizen_document_scope(state, auth_ctx, cid_number)
+}
+
+pub(crate) fn ensure_citizen_document_scope(
+    state: &AppState,
+    auth_ctx: &crate::auth::login::AdminAuthContext,
+    cid_number: &str,
+) -> Result<CitizenRecord, axum::response::Response> {
+    let scope = citizen_read_scope(auth_ctx, "当前登录无公民资料库权限")?;
     let record = match state.db.find_citizen_by_cid(cid_number) {
         Ok(Some(v)) => v,
         Ok(None) => {
@@ -536,25 +600,11 @@ fn ensure_citizen_document_scope(
             ));
         }
     };
-    let province_name =
-        crate::cid::china::area_name_by_codes(record.province_code.as_str(), None, None)
-            .map(|(province, _, _)| province.to_string())
-            .unwrap_or_default();
-    let city_name = crate::cid::china::area_name_by_codes(
+    ensure_citizen_record_in_scope(
+        &scope,
         record.province_code.as_str(),
-        Some(record.city_code.as_str()),
-        None,
-    )
-    .and_then(|(_, city, _)| city.map(str::to_string))
-    .unwrap_or_default();
-    if !scope.includes_province(province_name.as_str()) || !scope.includes_city(city_name.as_str())
-    {
-        return Err(api_error(
-            StatusCode::FORBIDDEN,
-            1003,
-            "公民档案不在当前注册局办理范围内",
-        ));
-    }
+        record.city_code.as_str(),
+    )?;
     Ok(record)
 }
 
@@ -603,7 +653,7 @@ pub(crate) async fn upload_citizen_document(
         Ok(v) => v,
         Err(resp) => return resp,
     };
-    let record = match ensure_citizen_document_scope(&state, &ctx, cid_number.as_str()) {
+    let record = match ensure_citizen_document_write_scope(&state, &ctx, cid_number.as_str()) {
         Ok(v) => v,
         Err(resp) => return resp,
     };
@@ -790,7 +840,7 @@ pub(crate) async fn delete_citizen_document(
         Ok(v) => v,
         Err(resp) => return resp,
     };
-    let record = match ensure_citizen_document_scope(&state, &ctx, cid_number.as_str()) {
+    let record = match ensure_citizen_document_write_scope(&state, &ctx, cid_number.as_str()) {
         Ok(v) => v,
         Err(resp) => return resp,
     };
@@ -927,3 +977,83 @@ pub(crate) async fn public_identity_search(
     })
     .into_response()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::login::AdminAuthContext;
+
+    fn ctx(institution_code: &str, admin_level: Option<&str>, read_only: bool) -> AdminAuthContext {
+        AdminAuthContext {
+            account_id: "0x1111111111111111111111111111111111111111111111111111111111111111"
+                .to_string(),
+            institution_cid_number: "AH001-CGOV0-000000001-2026".to_string(),
+            institution_code: institution_code.to_string(),
+            admin_level: admin_level.map(str::to_string),
+            family_name: "管理".to_string(),
+            given_name: "员".to_string(),
+            scope_province_name: Some("安徽省".to_string()),
+            scope_city_name: Some("合肥市".to_string()),
+            scope_town_name: None,
+            cid_short_name: None,
+            read_only,
+        }
+    }
+
+    fn empty_query() -> CitizensQuery {
+        CitizensQuery {
+            keyword: None,
+            province_name: None,
+            city_name: None,
+            cursor: None,
+            page_size: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    #[test]
+    fn read_only_admin_can_list_citizens_but_cannot_write() {
+        let admin = ctx("CGOV", Some("CITY"), true);
+        let (province_code, city_code) = resolve_citizen_query_scope(&admin, &empty_query())
+            .expect("read-only admin keeps citizen read access");
+        assert_eq!(
+            province_code.as_deref(),
+            crate::cid::china::province_code_by_name("安徽省")
+        );
+        assert_eq!(
+            city_code.as_deref(),
+            crate::cid::china::city_code_by_name("安徽省", "合肥市")
+        );
+        assert!(citizen_read_scope(&admin, "denied").is_ok());
+
+        let denied = ensure_citizen_write_allowed(&admin)
+            .expect_err("read-only admin must not upload, delete or export");
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+        assert!(ensure_citizen_write_allowed(&ctx("CGOV", Some("CITY"), false)).is_ok());
+    }
+
+    #[test]
+    fn own_institution_admin_cannot_see_other_citizens() {
+        let admin = ctx("SFLP", None, false);
+        let listed = resolve_citizen_query_scope(&admin, &empty_query())
+            .expect_err("own-institution admin must not list citizens");
+        assert_eq!(listed.status(), StatusCode::FORBIDDEN);
+        let documents = citizen_read_scope(&admin, "denied")
+            .expect_err("own-institution admin must not open citizen documents");
+        assert_eq!(documents.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn city_admin_only_reads_citizens_of_own_city() {
+        let scope = citizen_read_scope(&ctx("CGOV", Some("CITY"), true), "denied")
+            .expect("city admin has a read scope");
+        let province_code = crate::cid::china::province_code_by_name("安徽省").expect("province");
+        let own_city = crate::cid::china::city_code_by_name("安徽省", "合肥市").expect("city");
+        let other_city = crate::cid::china::city_code_by_name("安徽省", "芜湖市").expect("city");
+        assert!(ensure_citizen_record_in_scope(&scope, province_code, own_city).is_ok());
+        let denied = ensure_citizen_record_in_scope(&scope, province_code, other_city)
+            .expect_err("other city citizen is out of scope");
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -16,3 +16,5 @@ pub(crate) mod model;
 pub(crate) mod occupy;
 /// OnChina 自持的护照号与护照有效期生成逻辑。
 pub(crate) mod passport_no;
+/// 居住地跨市迁移时注册局间的加密签名档案移交。
+pub(crate) mod transfer;
@@ -1050,17 +1050,18 @@ pub(crate) async fn submit_citizen_occupy(
         authorization_expires_at,
         &occupy_signature_bytes,
     );
-    let prepared = match chain_submit::prepare_signing(&call, ctx.account_id.as_str()).await {
-        Ok(v) => v,
-        Err(err) => {
-            tracing::error!(error = %err, "prepare occupy signing failed");
-            return api_error(
-                StatusCode::BAD_GATEWAY,
-                1004,
-                "链签名载荷准备失败(链不可用)",
-            );
-        }
-    };
+    let prepared =
+        match chain_submit::prepare_signing(&state.db, &call, ctx.account_id.as_str()).await {
+            Ok(v) => v,
+            Err(err) => {
+                tracing::error!(error = %err, "prepare occupy signing failed");
+                return api_error(
+                    StatusCode::BAD_GATEWAY,
+                    1004,
+                    "链签名载荷准备失败(链不可用)",
+                );
+            }
+        };
 
     let issued_at = Utc::now();
     let action = crate::core::institution_call::chain_action_code(
@@ -1493,17 +1494,18 @@ pub(crate) async fn submit_citizen_rebind(
         authorization_expires_at,
         &new_account_signature_bytes,
     );
-    let prepared = match chain_submit::prepare_signing(&call, ctx.account_id.as_str()).await {
-        Ok(v) => v,
-        Err(err) => {
-            tracing::error!(error = %err, "prepare rebind signing failed");
-            return api_error(
-                StatusCode::BAD_GATEWAY,
-                1004,
-                "链签名载荷准备失败(链不可用)",
-            );
-        }
-    };
+    let prepared =
+        match chain_submit::prepare_signing(&state.db, &call, ctx.account_id.as_str()).await {
+            Ok(v) => v,
+            Err(err) => {
+                tracing::error!(error = %err, "prepare rebind signing failed");
+                return api_error(
+                    StatusCode::BAD_GATEWAY,
+                    1004,
+                    "链签名载荷准备失败(链不可用)",
+                );
+            }
+        };
 
     let issued_at = Utc::now();
     let action = crate::core::institution_call::chain_action_code(
@@ -1605,17 +1607,18 @@ pub(crate) async fn prepare_citizen_revoke(
         actor_role_code.as_str(),
         cid_number.as_str(),
     );
-    let prepared = match chain_submit::prepare_signing(&call, ctx.account_id.as_str()).await {
-        Ok(v) => v,
-        Err(err) => {
-            tracing::error!(error = %err, "prepare revoke signing failed");
-            return api_error(
-                StatusCode::BAD_GATEWAY,
-                1004,
-                "链签名载荷准备失败(链不可用)",
-            );
-        }
-    };
+    let prepared =
+        match chain_submit::prepare_signing(&state.db, &call, ctx.account_id.as_str()).await {
+            Ok(v) => v,
+            Err(err) => {
+                tracing::error!(error = %err, "prepare revoke signing failed");
+                return api_error(
+                    StatusCode::BAD_GATEWAY,
+                    1004,
+                    "链签名载荷准备失败(链不可用)",
+                );
+            }
+        };
     let issued_at = Utc::now();
     let expires_at = issued_at + Duration::seconds(SESSION_TTL_SECS);
     let request_id = format!("citizen-revoke-{}", Uuid::new_v4());
@@ -1920,6 +1923,7 @@ async fn submit_with_persisted_attempt(
     signature: &str,
 ) -> Result<chain_submit::FinalizedChainSubmit, chain_submit::ChainSubmitError> {
     chain_submit::assemble_and_submit(
+        &state.db,
         &session.call_data,
         session.account_id.as_str(),
         signature,
@@ -0,0 +1,1918 @@
+//! 居住地跨市迁移时的注册局间公民档案移交。
+//!
+//! 链上 `update_voting_identity` 把居住地改到另一市后,新注册局经投影只拿到链上事实,
+//! 原注册局的链下正本(护照号、补录姓名、资料库文件等)不会随之过去。本模块做点对点移交:
+//!
+//! 1. 接收局管理员经 `recipient-card/prepare` → `confirm` 冷签本局移交公钥卡片(本局 CID +
+//!    X25519 公钥),`recipient-card` 公布已签卡片;
+//! 2. 原注册局 `export/prepare`:验卡片签名且签名人 ∈ 接收局链上 Active 管理员,核对链上居住地
+//!    已迁入接收局所在市、接收方是链上市注册局,把本地档案 + 资料库文件封成密文(临时 X25519 →
+//!    HKDF-SHA256 → AES-256-GCM,头部作 AAD),对 `sha256(头部 || 密文)` 摘要出冷签二维码;
+//! 3. `export/confirm` 回填冷签,signer ∈ 本机构链上 Active 管理员后交付移交包;
+//! 4. 接收局 `import`:核对收件人与摘要、验签、signer ∈ 原注册局链上 Active 管理员,且原注册局
+//!    是 indexer 记录的公民迁入本市前居住地注册局;本地已有链下正本则拒绝覆盖。解密后按投影规则
+//!    合并(链上列以链为准,链下正本取移交包),记录、资料文件登记与移交行同一事务落库。
+//!
+//! 两端都落 `citizen_dossier_transfers` 并写审计日志;同一 transfer_id 只能导入一次。
+
+// 移交 handler 的失败分支统一携带完整 Axum Response,与公民资料库入口同一错误模型。
+#![allow(clippy::result_large_err)]
+
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use base64::Engine as _;
+use chrono::{DateTime, Duration, Utc};
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use x25519_dalek::{PublicKey, StaticSecret};
+use zeroize::Zeroizing;
+
+use crate::auth::action_sign::{
+    hash_json, payload_hash_for_text, signed_payload_text, verify_account_signature,
+    AdminSignedPayload, ADMIN_ACTION_TTL_SECONDS,
+};
+use crate::core::chain_runtime;
+use crate::crypto::pubkey::same_account_id;
+use crate::domains::projection::{
+    chain_citizen_from_detail, ensure_binding_projection_monotonic, merge_citizen_record,
+    resolve_node_scope, NodeScope,
+};
+use crate::*;
+
+const TRANSFER_KEY_DIR: &str = "data/registry-transfer";
+const TRANSFER_KEY_FILE: &str = "dossier-x25519.key";
+const DOSSIER_VERSION: u32 = 1;
+const DOSSIER_KDF_INFO: &[u8] = b"citizenchain/onchina/citizen-dossier/v1";
+const ACTION_DOSSIER_EXPORT: &str = "CITIZEN_DOSSIER_EXPORT";
+const ACTION_DOSSIER_RECIPIENT_KEY: &str = "CITIZEN_DOSSIER_RECIPIENT_KEY";
+const RECIPIENT_CARD_FILE: &str = "recipient-card.json";
+/// 移交公钥卡片冷签有效期;过期后接收局管理员须重新签发。
+const RECIPIENT_CARD_TTL_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+const DIRECTION_EXPORT: &str = "EXPORT";
+const DIRECTION_IMPORT: &str = "IMPORT";
+const STATUS_PENDING: &str = "PENDING";
+const STATUS_EXPORTED: &str = "EXPORTED";
+const STATUS_IMPORTED: &str = "IMPORTED";
+
+/// 单份档案明文(记录 + 全部资料文件)上限。
+const MAX_DOSSIER_BYTES: usize = 48 * 1024 * 1024;
+/// 导入接口请求体上限:密文 base64 膨胀 4/3,另留头部与签名余量。
+pub(crate) const MAX_PACKAGE_BODY_BYTES: usize = MAX_DOSSIER_BYTES / 3 * 4 + 1024 * 1024;
+
+/// 接收局公布的移交公钥卡片:本局链上管理员冷签绑定 (本局 CID, 移交公钥),原注册局导出前
+/// 验签并核对签名人 ∈ 接收局链上 Active 管理员。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RecipientCard {
+    pub(crate) institution_cid_number: String,
+    pub(crate) public_key: String,
+    pub(crate) signer_account_id: String,
+    pub(crate) payload_text: String,
+    pub(crate) signature: String,
+}
+
+/// 移交包头部。整体 JSON 作 AES-GCM 附加数据,任何字段被改都会解密失败。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DossierHeader {
+    pub(crate) version: u32,
+    pub(crate) transfer_id: String,
+    pub(crate) cid_number: String,
+    pub(crate) sender_cid_number: String,
+    pub(crate) recipient_cid_number: String,
+    pub(crate) recipient_public_key: String,
+    pub(crate) ephemeral_public_key: String,
+    pub(crate) nonce: String,
+    pub(crate) created_at: i64,
+}
+
+/// 档案内的单个资料文件;`content` 为 base64 文件本体,导入时按 `file_hash` 复核。
+#[derive(Serialize, Deserialize)]
+struct DossierDocument {
+    file_name: String,
+    document_type: String,
+    file_hash: String,
+    uploader_account_id: String,
+    uploaded_at: DateTime<Utc>,
+    content: String,
+}
+
+/// 密文内的档案明文。
+#[derive(Serialize, Deserialize)]
+struct DossierContent {
+    record: CitizenRecord,
+    documents: Vec<DossierDocument>,
+}
+
+/// 交付给接收局的移交包:密文 + 原注册局管理员对摘要的冷签。
+#[derive(Serialize, Deserialize)]
+pub(crate) struct DossierPackage {
+    pub(crate) header: DossierHeader,
+    /// base64 密文。
+    pub(crate) ciphertext: String,
+    pub(crate) signer_account_id: String,
+    pub(crate) payload_text: String,
+    pub(crate) signature: String,
+}
+
+/// 接收局从冷签文本中复核的字段(与 `AdminSignedPayload` 同名,其余字段忽略)。
+#[derive(Deserialize)]
+struct SignedExportPayload {
+    action_id: String,
+    action_type: String,
+    account_id: String,
+    actor_cid_number: String,
+    target: String,
+    request_hash: String,
+}
+
+/// 卡片冷签文本中复核的字段。
+#[derive(Deserialize)]
+struct SignedRecipientPayload {
+    action_type: String,
+    account_id: String,
+    actor_cid_number: String,
+    target: String,
+    request_hash: String,
+    expires_at: i64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct RecipientCardPrepareOutput {
+    pub(crate) sign_request: String,
+    pub(crate) payload_text: String,
+    pub(crate) payload_hash: String,
+    pub(crate) expires_at: i64,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RecipientCardConfirmInput {
+    pub(crate) payload_text: String,
+    pub(crate) account_id: String,
+    pub(crate) signature: String,
+    pub(crate) payload_hash: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ExportPrepareInput {
+    pub(crate) recipient: RecipientCard,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ExportPrepareOutput {
+    pub(crate) transfer_id: String,
+    pub(crate) sign_request: String,
+    pub(crate) payload_hash: String,
+    pub(crate) dossier_digest: String,
+    pub(crate) document_count: usize,
+    pub(crate) expires_at: i64,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ExportConfirmInput {
+    pub(crate) transfer_id: String,
+    pub(crate) account_id: String,
+    pub(crate) signature: String,
+    pub(crate) payload_hash: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ImportOutput {
+    pub(crate) transfer_id: String,
+    pub(crate) cid_number: String,
+    pub(crate) sender_cid_number: String,
+    pub(crate) documents_imported: usize,
+    pub(crate) documents_skipped: usize,
+}
+
+// ───────────────────────── 封包 / 解包(纯函数,离线单测) ─────────────────────────
+
+fn hex32(label: &str, raw: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(raw.trim().trim_start_matches("0x"))
+        .map_err(|_| format!("{label} 不是合法 hex"))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("{label} 长度必须为 32 字节"))
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+    let mut out = [0u8; N];
+    getrandom::getrandom(&mut out).map_err(|e| format!("系统随机数不可用: {e}"))?;
+    Ok(out)
+}
+
+/// 公钥指纹 = sha256(公钥) 前 16 字节,写入导出冷签文本供审计对照。
+pub(crate) fn public_key_fingerprint(public_key: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(&Sha256::digest(public_key)[..16]))
+}
+
+fn header_aad(header: &DossierHeader) -> Vec<u8> {
+    serde_json::to_vec(header).unwrap_or_default()
+}
+
+/// 移交包摘要 = sha256(len(头部) || 头部 || 密文);原注册局管理员冷签的就是它。
+pub(crate) fn dossier_digest(header: &DossierHeader, ciphertext: &[u8]) -> String {
+    let aad = header_aad(header);
+    let mut hasher = Sha256::new();
+    hasher.update((aad.len() as u64).to_be_bytes());
+    hasher.update(&aad);
+    hasher.update(ciphertext);
+    format!("0x{}", hex::encode(hasher.finalize()))
+}
+
+fn dossier_cipher(
+    shared: &[u8; 32],
+    ephemeral_public: &[u8; 32],
+    recipient_public: &[u8; 32],
+) -> Result<Aes256Gcm, String> {
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(ephemeral_public);
+    salt[32..].copy_from_slice(recipient_public);
+    let mut key = Zeroizing::new([0u8; 32]);
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(DOSSIER_KDF_INFO, key.as_mut())
+        .map_err(|_| "派生档案密钥失败".to_string())?;
+    Aes256Gcm::new_from_slice(key.as_ref()).map_err(|_| "初始化档案密钥失败".to_string())
+}
+
+/// 为接收局公钥封包;填好头部的收件公钥、临时公钥与 nonce 后返回 (头部, 密文)。
+pub(crate) fn seal_dossier(
+    mut header: DossierHeader,
+    recipient_public: &[u8; 32],
+    plaintext: &[u8],
+) -> Result<(DossierHeader, Vec<u8>), String> {
+    let ephemeral = StaticSecret::from(random_bytes::<32>()?);
+    let ephemeral_public = PublicKey::from(&ephemeral);
+    let shared = ephemeral.diffie_hellman(&PublicKey::from(*recipient_public));
+    if !shared.was_contributory() {
+        return Err("接收局公钥无效".to_string());
+    }
+    let nonce = random_bytes::<12>()?;
+    header.recipient_public_key = format!("0x{}", hex::encode(recipient_public));
+    header.ephemeral_public_key = format!("0x{}", hex::encode(ephemeral_public.as_bytes()));
+    header.nonce = format!("0x{}", hex::encode(nonce));
+    let cipher = dossier_cipher(
+        shared.as_bytes(),
+        ephemeral_public.as_bytes(),
+        recipient_public,
+    )?;
+    let aad = header_aad(&header);
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: &aad,
+            },
+        )
+        .map_err(|_| "档案加密失败".to_string())?;
+    Ok((header, ciphertext))
+}
+
+/// 用本节点移交私钥解包;收件公钥不符、头部或密文被改都返回 Err。
+pub(crate) fn open_dossier(
+    secret: &StaticSecret,
+    header: &DossierHeader,
+    ciphertext: &[u8],
+) -> Result<Zeroizing<Vec<u8>>, String> {
+    let own_public = PublicKey::from(secret);
+    if hex32("recipient_public_key", &header.recipient_public_key)? != *own_public.as_bytes() {
+        return Err("移交包不是发给本注册局移交公钥的".to_string());
+    }
+    let ephemeral_public = hex32("ephemeral_public_key", &header.ephemeral_public_key)?;
+    let nonce: [u8; 12] = hex::decode(header.nonce.trim_start_matches("0x"))
+        .ok()
+        .and_then(|raw| raw.try_into().ok())
+        .ok_or_else(|| "nonce 必须为 12 字节 hex".to_string())?;
+    let shared = secret.diffie_hellman(&PublicKey::from(ephemeral_public));
+    if !shared.was_contributory() {
+        return Err("临时公钥无效".to_string());
+    }
+    let cipher = dossier_cipher(shared.as_bytes(), &ephemeral_public, own_public.as_bytes())?;
+    let aad = header_aad(header);
+    cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: ciphertext,
+                aad: &aad,
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| "移交包解密失败(密文或头部被篡改)".to_string())
+}
+
+/// 移交公钥卡片的离线校验:冷签文本绑定卡片 CID 与公钥、未过期、sr25519 验签成立。
+/// 返回卡片公钥;签名人是否为该局链上管理员由调用方再查链。
+pub(crate) fn verify_recipient_card_signature(
+    card: &RecipientCard,
+    now: i64,
+) -> Result<[u8; 32], String> {
+    let public_key = hex32("public_key", card.public_key.as_str())?;
+    let payload: SignedRecipientPayload = serde_json::from_str(card.payload_text.as_str())
+        .map_err(|_| "移交公钥卡片签名文本格式非法".to_string())?;
+    let institution_cid_number = card.institution_cid_number.trim();
+    if payload.action_type != ACTION_DOSSIER_RECIPIENT_KEY
+        || payload.actor_cid_number != institution_cid_number
+        || payload.target != institution_cid_number
+        || payload.request_hash != format!("0x{}", hex::encode(public_key))
+        || !same_account_id(&payload.account_id, &card.signer_account_id)
+    {
+        return Err("移交公钥卡片签名文本与卡片不符".to_string());
+    }
+    if payload.expires_at <= now {
+        return Err("移交公钥卡片已过期".to_string());
+    }
+    let signing_bytes = primitives::sign::signing_message(
+        primitives::sign::OP_SIGN_ONCHINA_ADMIN,
+        card.payload_text.as_bytes(),
+    );
+    if !crate::auth::login::verify_admin_signature_bytes(
+        card.signer_account_id.as_str(),
+        &signing_bytes,
+        card.signature.as_str(),
+    ) {
+        return Err("移交公钥卡片签名无效".to_string());
+    }
+    Ok(public_key)
+}
+
+// ───────────────────────── 本节点移交密钥 ─────────────────────────
+
+fn transfer_key_path() -> PathBuf {
+    PathBuf::from(TRANSFER_KEY_DIR).join(TRANSFER_KEY_FILE)
+}
+
+/// 读取本节点移交私钥;首次使用时生成并以 0600 落盘,此后绝不覆盖(覆盖即作废在途移交包)。
+fn load_or_create_transfer_key() -> Result<StaticSecret, String> {
+    let path = transfer_key_path();
+    match std::fs::read_to_string(&path).map(Zeroizing::new) {
+        Ok(text) => {
+            let bytes = Zeroizing::new(hex32("dossier transfer key", text.as_str())?);
+            return Ok(StaticSecret::from(*bytes));
+        }
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            return Err(format!("read {} failed: {err}", path.display()));
+        }
+        Err(_) => {}
+    }
+    std::fs::create_dir_all(TRANSFER_KEY_DIR)
+        .map_err(|e| format!("create {TRANSFER_KEY_DIR} failed: {e}"))?;
+    let bytes = Zeroizing::new(random_bytes::<32>()?);
+    let text = Zeroizing::new(hex::encode(*bytes));
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = match options.open(&path) {
+        Ok(file) => file,
+        // 并发首次生成:另一请求已抢先落盘,以磁盘为准。
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+            return load_or_create_transfer_key();
+        }
+        Err(err) => return Err(format!("create {} failed: {err}", path.display())),
+    };
+    file.write_all(text.as_bytes())
+        .and_then(|()| file.sync_all())
+        .map_err(|e| format!("write {} failed: {e}", path.display()))?;
+    tracing::info!(path = %path.display(), "generated citizen dossier transfer key");
+    Ok(StaticSecret::from(*bytes))
+}
+
+fn recipient_card_path() -> PathBuf {
+    PathBuf::from(TRANSFER_KEY_DIR).join(RECIPIENT_CARD_FILE)
+}
+
+/// 读取本局已签发的移交公钥卡片;未签发返回 None。
+fn load_recipient_card() -> Result<Option<RecipientCard>, String> {
+    let path = recipient_card_path();
+    match std::fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| format!("parse {} failed: {e}", path.display())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(format!("read {} failed: {err}", path.display())),
+    }
+}
+
+/// 先写临时文件再改名,避免并发读到半份卡片。
+fn store_recipient_card(card: &RecipientCard) -> Result<(), String> {
+    std::fs::create_dir_all(TRANSFER_KEY_DIR)
+        .map_err(|e| format!("create {TRANSFER_KEY_DIR} failed: {e}"))?;
+    let path = recipient_card_path();
+    let tmp = path.with_extension("json.tmp");
+    let bytes = serde_json::to_vec(card).map_err(|e| format!("encode recipient card: {e}"))?;
+    std::fs::write(&tmp, bytes).map_err(|e| format!("write {} failed: {e}", tmp.display()))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("rename {} failed: {e}", path.display()))
+}
+
+fn transfer_key() -> Result<StaticSecret, axum::response::Response> {
+    load_or_create_transfer_key().map_err(|err| {
+        tracing::error!(error = %err, "load citizen dossier transfer key failed");
+        api_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            5001,
+            "档案移交密钥不可用",
+        )
+    })
+}
+
+// ───────────────────────── 作用域 / 链上校验 ─────────────────────────
+
+/// 本节点须为市注册局;返回 (本机构 CID, 本节点作用域)。
+fn node_city_registry(
+    state: &AppState,
+    ctx: &AdminAuthContext,
+) -> Result<(String, NodeScope), axum::response::Response> {
+    if !chain_runtime::is_subordinate_registry(ctx.institution_code.as_str()) {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            1003,
+            "只有市注册局可以办理公民档案移交",
+        ));
+    }
+    let institution_cid_number =
+        crate::auth::actions::actor_cid_number_for_context(&state.db, ctx)?;
+    match resolve_node_scope(&state.db) {
+        Ok(Some((false, scope))) => Ok((institution_cid_number, scope)),
+        Ok(_) => Err(api_error(
+            StatusCode::FORBIDDEN,
+            2002,
+            "node binding missing",
+        )),
+        Err(err) => {
+            tracing::error!(error = %err, "resolve node scope for dossier transfer failed");
+            Err(api_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                5001,
+                "node binding invalid",
+            ))
+        }
+    }
+}
+
+fn registry_scope_codes(institution_cid_number: &str) -> Option<(String, String)> {
+    let (province, city) =
+        primitives::cid::number::cid_scope_codes(institution_cid_number.as_bytes()).ok()?;
+    Some((
+        String::from_utf8_lossy(&province).into_owned(),
+        String::from_utf8_lossy(&city).into_owned(),
+    ))
+}
+
+fn is_city_registry_cid(institution_cid_number: &str) -> bool {
+    primitives::cid::code::institution_code_from_cid_number(institution_cid_number)
+        .and_then(|code| primitives::cid::code::institution_code_text(&code))
+        .is_some_and(chain_runtime::is_subordinate_registry)
+}
+
+/// signer ∈ 指定市注册局链上 Active 管理员集合;`party` 为报错用的"移交方"/"接收方"。
+async fn ensure_registry_admin(
+    registry_cid_number: &str,
+    account_id: &str,
+    party: &str,
+) -> Result<(), axum::response::Response> {
+    if !is_city_registry_cid(registry_cid_number) {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            2002,
+            format!("{party}不是市注册局").as_str(),
+        ));
+    }
+    let identity = chain_runtime::identity_from_binding_parts(
+        chain_runtime::TIER2_REGISTRY_CODE,
+        Some(registry_cid_number),
+        None,
+    )
+    .map_err(|err| api_error(StatusCode::BAD_REQUEST, 1001, err.as_str()))?;
+    let admins = chain_runtime::fetch_active_admins_onchain(&identity)
+        .await
+        .map_err(|err| {
+            tracing::warn!(error = %err, "chain unreachable during dossier transfer");
+            api_error(StatusCode::BAD_GATEWAY, 5002, "chain unreachable")
+        })?
+        .ok_or_else(|| {
+            api_error(
+                StatusCode::FORBIDDEN,
+                2002,
+                format!("{party}注册局无链上 Active 管理员").as_str(),
+            )
+        })?;
+    if !admins
+        .iter()
+        .any(|admin| same_account_id(&admin.account_id, account_id))
+    {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            2002,
+            format!("签名人不是{party}注册局的链上管理员").as_str(),
+        ));
+    }
+    Ok(())
+}
+
+/// indexer 记录的最近一次迁入:移交方须是公民迁入本市前居住地的市注册局。
+fn sender_is_previous_registry(
+    last_move: &ResidenceMoveRow,
+    scope: &NodeScope,
+    sender_cid_number: &str,
+) -> bool {
+    last_move.to_province_code == scope.province_code
+        && last_move.to_city_code == scope.city_code
+        && registry_scope_codes(sender_cid_number)
+            == Some((
+                last_move.from_province_code.clone(),
+                last_move.from_city_code.clone(),
+            ))
+}
+
+async fn chain_residence(
+    cid_number: &str,
+) -> Result<crate::domains::projection::ChainCitizen, axum::response::Response> {
+    match chain_runtime::read_chain_citizen_detail(cid_number).await {
+        Ok(Some(detail)) => Ok(chain_citizen_from_detail(detail)),
+        Ok(None) => Err(api_error(StatusCode::NOT_FOUND, 1004, "链上无此公民")),
+        Err(err) => {
+            tracing::warn!(error = %err, "read chain citizen for dossier transfer failed");
+            Err(api_error(
+                StatusCode::BAD_GATEWAY,
+                5002,
+                "chain unreachable",
+            ))
+        }
+    }
+}
+
+fn transfer_target(cid_number: &str, recipient_cid_number: &str) -> String {
+    format!("{cid_number}->{recipient_cid_number}")
+}
+
+// ───────────────────────── 移交记录 ─────────────────────────
+
+struct ExportRow {
+    cid_number: String,
+    sender_cid_number: String,
+    recipient_cid_number: String,
+    dossier_digest: String,
+    header_json: String,
+    ciphertext: Vec<u8>,
+    payload_text: String,
+    payload_hash: String,
+    actor_account_id: String,
+    document_count: i32,
+    status: String,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// 公民最近一次迁入本市的链上记录(indexer 写入)。
+struct ResidenceMoveRow {
+    from_province_code: String,
+    from_city_code: String,
+    to_province_code: String,
+    to_city_code: String,
+}
+
+/// 导入落库被拒的业务原因(整体回滚)。
+enum ImportConflict {
+    /// 本地已有链下正本(护照号或资料文件),不允许被移交包覆盖。
+    LocalRecordExists,
+    AlreadyImported,
+}
+
+/// 一条待写入的移交记录;导出登记单独落库,导入登记与档案写入同一事务。
+struct TransferInsert {
+    transfer_id: String,
+    direction: &'static str,
+    header: DossierHeader,
+    header_json: String,
+    dossier_digest: String,
+    ciphertext: Option<Vec<u8>>,
+    payload_text: String,
+    payload_hash: String,
+    actor_account_id: String,
+    signer_account_id: Option<String>,
+    signature: Option<String>,
+    document_count: i32,
+    status: &'static str,
+    expires_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+impl TransferInsert {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        transfer_id: &str,
+        direction: &'static str,
+        header: &DossierHeader,
+        dossier_digest: &str,
+        ciphertext: Option<Vec<u8>>,
+        payload_text: &str,
+        payload_hash: &str,
+        actor_account_id: &str,
+        signer: Option<(&str, &str)>,
+        document_count: usize,
+        status: &'static str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            transfer_id: transfer_id.to_string(),
+            direction,
+            header: header.clone(),
+            header_json: serde_json::to_string(header).unwrap_or_default(),
+            dossier_digest: dossier_digest.to_string(),
+            ciphertext,
+            payload_text: payload_text.to_string(),
+            payload_hash: payload_hash.to_string(),
+            actor_account_id: actor_account_id.to_string(),
+            signer_account_id: signer.map(|(account, _)| account.to_string()),
+            signature: signer.map(|(_, signature)| signature.to_string()),
+            document_count: i32::try_from(document_count).unwrap_or(i32::MAX),
+            status,
+            expires_at,
+            completed_at: (status != STATUS_PENDING).then(Utc::now),
+        }
+    }
+
+    /// 写入一行;同方向同 transfer_id 已存在时返回 false。
+    fn insert<C: postgres::GenericClient>(&self, conn: &mut C) -> Result<bool, String> {
+        let affected = conn
+            .execute(
+                "INSERT INTO citizen_dossier_transfers (
+                    transfer_id, direction, cid_number, sender_cid_number,
+                    recipient_cid_number, dossier_digest, header_json, ciphertext,
+                    payload_text, payload_hash, actor_account_id, signer_account_id,
+                    signature, document_count, status, expires_at, completed_at
+                 ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14,
+                           $15, $16, $17)
+                 ON CONFLICT (direction, transfer_id) DO NOTHING",
+                &[
+                    &self.transfer_id,
+                    &self.direction,
+                    &self.header.cid_number,
+                    &self.header.sender_cid_number,
+                    &self.header.recipient_cid_number,
+                    &self.dossier_digest,
+                    &self.header_json,
+                    &self.ciphertext,
+                    &self.payload_text,
+                    &self.payload_hash,
+                    &self.actor_account_id,
+                    &self.signer_account_id,
+                    &self.signature,
+                    &self.document_count,
+                    &self.status,
+                    &self.expires_at,
+                    &self.completed_at,
+                ],
+            )
+            .map_err(|e| format!("insert citizen dossier transfer failed: {e}"))?;
+        Ok(affected > 0)
+    }
+}
+
+impl Db {
+    fn insert_dossier_transfer(&self, row: TransferInsert) -> Result<bool, String> {
+        self.with_client(move |conn| row.insert(conn))
+    }
+
+    fn latest_residence_move(&self, cid_number: &str) -> Result<Option<ResidenceMoveRow>, String> {
+        let cid_number = cid_number.trim().to_string();
+        self.with_client(move |conn| {
+            let row = conn
+                .query_opt(
+                    "SELECT from_province_code, from_city_code, to_province_code, to_city_code
+                     FROM citizen_residence_moves
+                     WHERE cid_number = $1
+                     ORDER BY block_number DESC
+                     LIMIT 1",
+                    &[&cid_number],
+                )
+                .map_err(|e| format!("query citizen residence move failed: {e}"))?;
+            Ok(row.map(|row| ResidenceMoveRow {
+                from_province_code: row.get(0),
+                from_city_code: row.get(1),
+                to_province_code: row.get(2),
+                to_city_code: row.get(3),
+            }))
+        })
+    }
+
+    /// 导入落库:公民记录、资料文件登记与导入移交行同一事务。事务内对本地行加锁后再核对
+    /// 链下正本,与其他写入并发时不会覆盖。
+    fn commit_dossier_import(
+        &self,
+        record: &CitizenRecord,
+        documents: Vec<(CitizenDocument, String)>,
+        transfer: TransferInsert,
+    ) -> Result<Result<(), ImportConflict>, String> {
+        let record = record.clone();
+        self.with_client(move |conn| {
+            let mut tx = conn
+                .transaction()
+                .map_err(|e| format!("begin citizen dossier import failed: {e}"))?;
+            let local_passport_no: Option<String> = tx
+                .query_opt(
+                    "SELECT passport_no FROM citizens WHERE cid_number = $1 FOR UPDATE",
+                    &[&record.cid_number],
+                )
+                .map_err(|e| format!("lock citizen before dossier import failed: {e}"))?
+                .and_then(|row| row.get(0));
+            let has_documents = tx
+                .query_opt(
+                    "SELECT 1 FROM citizen_documents WHERE cid_number = $1 LIMIT 1",
+                    &[&record.cid_number],
+                )
+                .map_err(|e| format!("query citizen documents before import failed: {e}"))?
+                .is_some();
+            if has_documents || local_passport_no.is_some_and(|no| !no.trim().is_empty()) {
+                return Ok(Err(ImportConflict::LocalRecordExists));
+            }
+            Db::upsert_target_citizen_rows(&mut tx, &record)?;
+            for (meta, stored_path) in documents {
+                Db::insert_citizen_document_row(
+                    &mut tx,
+                    meta,
+                    record.province_code.as_str(),
+                    record.city_code.as_str(),
+                    stored_path.as_str(),
+                )?;
+            }
+            if !transfer.insert(&mut tx)? {
+                return Ok(Err(ImportConflict::AlreadyImported));
+            }
+            tx.commit()
+                .map_err(|e| format!("commit citizen dossier import failed: {e}"))?;
+            Ok(Ok(()))
+        })
+    }
+
+    fn get_dossier_export(&self, transfer_id: &str) -> Result<Option<ExportRow>, String> {
+        let transfer_id = transfer_id.trim().to_string();
+        self.with_client(move |conn| {
+            let row = conn
+                .query_opt(
+                    "SELECT cid_number, sender_cid_number, recipient_cid_number, dossier_digest,
+                            header_json, COALESCE(ciphertext, ''::bytea), payload_text,
+                            payload_hash, actor_account_id, document_count, status, expires_at
+                     FROM citizen_dossier_transfers
+                     WHERE direction = $1 AND transfer_id = $2",
+                    &[&DIRECTION_EXPORT, &transfer_id],
+                )
+                .map_err(|e| format!("query citizen dossier export failed: {e}"))?;
+            Ok(row.map(|row| ExportRow {
+                cid_number: row.get(0),
+                sender_cid_number: row.get(1),
+                recipient_cid_number: row.get(2),
+                dossier_digest: row.get(3),
+                header_json: row.get(4),
+                ciphertext: row.get(5),
+                payload_text: row.get(6),
+                payload_hash: row.get(7),
+                actor_account_id: row.get(8),
+                document_count: row.get(9),
+                status: row.get(10),
+                expires_at: row.get(11),
+            }))
+        })
+    }
+
+    fn mark_dossier_exported(
+        &self,
+        transfer_id: &str,
+        signer_account_id: &str,
+        signature: &str,
+    ) -> Result<bool, String> {
+        let transfer_id = transfer_id.trim().to_string();
+        let signer_account_id = signer_account_id.to_string();
+        let signature = signature.to_string();
+        self.with_client(move |conn| {
+            let affected = conn
+                .execute(
+                    "UPDATE citizen_dossier_transfers
+                     SET status = $1, signer_account_id = $2, signature = $3, completed_at = now()
+                     WHERE direction = $4 AND transfer_id = $5 AND status = $6",
+                    &[
+                        &STATUS_EXPORTED,
+                        &signer_account_id,
+                        &signature,
+                        &DIRECTION_EXPORT,
+                        &transfer_id,
+                        &STATUS_PENDING,
+                    ],
+                )
+                .map_err(|e| format!("mark citizen dossier exported failed: {e}"))?;
+            Ok(affected > 0)
+        })
+    }
+
+    fn dossier_imported(&self, transfer_id: &str) -> Result<bool, String> {
+        let transfer_id = transfer_id.trim().to_string();
+        self.with_client(move |conn| {
+            let row = conn
+                .query_opt(
+                    "SELECT 1 FROM citizen_dossier_transfers
+                     WHERE direction = $1 AND transfer_id = $2",
+                    &[&DIRECTION_IMPORT, &transfer_id],
+                )
+                .map_err(|e| format!("query citizen dossier import failed: {e}"))?;
+            Ok(row.is_some())
+        })
+    }
+}
+
+// ───────────────────────── handlers ─────────────────────────
+
+/// 接收局:公布本局已冷签的移交公钥卡片;未签发、已过期或与当前移交密钥不符时须重新签发。
+pub(crate) async fn get_recipient_card(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let (institution_cid_number, _scope) = match node_city_registry(&state, &ctx) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let secret = match transfer_key() {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let card = match load_recipient_card() {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::error!(error = %err, "load citizen dossier recipient card failed");
+            return api_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                5001,
+                "移交公钥卡片读取失败",
+            );
+        }
+    };
+    let own_public = *PublicKey::from(&secret).as_bytes();
+    match card {
+        Some(card)
+            if card.institution_cid_number == institution_cid_number
+                && verify_recipient_card_signature(&card, Utc::now().timestamp())
+                    .is_ok_and(|public| public == own_public) =>
+        {
+            Json(ApiResponse {
+                code: 0,
+                message: "ok".to_string(),
+                data: card,
+            })
+            .into_response()
+        }
+        _ => api_error(
+            StatusCode::NOT_FOUND,
+            1004,
+            "本局移交公钥卡片未签发或已过期,请管理员冷签",
+        ),
+    }
+}
+
+/// 接收局:生成移交公钥卡片的冷签二维码(绑定本局 CID 与本节点移交公钥)。
+pub(crate) async fn prepare_recipient_card(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let (institution_cid_number, _scope) = match node_city_registry(&state, &ctx) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let secret = match transfer_key() {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let public_key = format!("0x{}", hex::encode(PublicKey::from(&secret).as_bytes()));
+    let now = Utc::now();
+    let card_expires_at = now + Duration::seconds(RECIPIENT_CARD_TTL_SECONDS);
+    let request_expires_at = now + Duration::seconds(ADMIN_ACTION_TTL_SECONDS);
+    let action_id = format!("citizen-dossier-recipient-{}", Uuid::new_v4());
+    let province = ctx.scope_province_name.clone().unwrap_or_default();
+    let payload_text = signed_payload_text(AdminSignedPayload {
+        domain: "onchina_admin_governance",
+        qr_proto: crate::core::qr::QR_V1,
+        action_id: action_id.as_str(),
+        action_type: ACTION_DOSSIER_RECIPIENT_KEY,
+        account_id: ctx.account_id.as_str(),
+        actor_cid_number: institution_cid_number.as_str(),
+        actor_province_name: province.as_str(),
+        target: institution_cid_number.as_str(),
+        request_hash: public_key.as_str(),
+        before_hash: "",
+        after_hash: "",
+        expires_at: card_expires_at.timestamp(),
+    });
+    let payload_hash = payload_hash_for_text(payload_text.as_str());
+    let sign_request = match crate::core::qr::build_sign_request(
+        action_id.as_str(),
+        now.timestamp(),
+        request_expires_at.timestamp(),
+        ctx.account_id.as_str(),
+        payload_text.as_str(),
+        crate::core::qr::action_onchina_admin(),
+    ) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    Json(ApiResponse {
+        code: 0,
+        message: "ok".to_string(),
+        data: RecipientCardPrepareOutput {
+            sign_request,
+            payload_text,
+            payload_hash,
+            expires_at: card_expires_at.timestamp(),
+        },
+    })
+    .into_response()
+}
+
+/// 接收局:回填卡片冷签;签名人须为本局链上 Active 管理员,校验通过后落盘并公布。
+pub(crate) async fn confirm_recipient_card(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(input): Json<RecipientCardConfirmInput>,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let (institution_cid_number, _scope) = match node_city_registry(&state, &ctx) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let secret = match transfer_key() {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    if let Err(resp) = verify_account_signature(
+        ctx.account_id.as_str(),
+        input.account_id.as_str(),
+        input.signature.as_str(),
+        input.payload_hash.as_str(),
+        payload_hash_for_text(input.payload_text.as_str()).as_str(),
+        input.payload_text.as_str(),
+    ) {
+        return resp;
+    }
+    let card = RecipientCard {
+        institution_cid_number: institution_cid_number.clone(),
+        public_key: format!("0x{}", hex::encode(PublicKey::from(&secret).as_bytes())),
+        signer_account_id: input.account_id,
+        payload_text: input.payload_text,
+        signature: input.signature,
+    };
+    if let Err(err) = verify_recipient_card_signature(&card, Utc::now().timestamp()) {
+        return api_error(StatusCode::UNPROCESSABLE_ENTITY, 2004, err.as_str());
+    }
+    if let Err(resp) = ensure_registry_admin(
+        institution_cid_number.as_str(),
+        card.signer_account_id.as_str(),
+        "接收方",
+    )
+    .await
+    {
+        return resp;
+    }
+    if let Err(err) = store_recipient_card(&card) {
+        tracing::error!(error = %err, "store citizen dossier recipient card failed");
+        return api_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            5001,
+            "移交公钥卡片落盘失败",
+        );
+    }
+    crate::core::runtime_ops::append_audit_log(
+        &state,
+        "CITIZEN_DOSSIER_RECIPIENT_KEY",
+        &ctx.account_id,
+        Some(institution_cid_number),
+        serde_json::json!({
+            "public_key": card.public_key,
+            "signer_account_id": card.signer_account_id,
+            "request_id": request_id_from_headers(&headers),
+            "actor_ip": actor_ip_from_headers(&headers),
+        }),
+    );
+    Json(ApiResponse {
+        code: 0,
+        message: "ok".to_string(),
+        data: card,
+    })
+    .into_response()
+}
+
+fn read_dossier_documents(
+    state: &AppState,
+    record: &CitizenRecord,
+) -> Result<Vec<DossierDocument>, axum::response::Response> {
+    let docs = state
+        .db
+        .list_citizen_documents(record.province_code.as_str(), record.cid_number.as_str())
+        .map_err(|err| {
+            tracing::error!(error = %err, "list citizen documents for dossier failed");
+            api_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                1004,
+                "公民资料库查询失败",
+            )
+        })?;
+    let mut total = 0usize;
+    let mut out = Vec::with_capacity(docs.len());
+    for doc in docs {
+        let stored = state
+            .db
+            .get_citizen_document(
+                record.province_code.as_str(),
+                record.cid_number.as_str(),
+                doc.id,
+            )
+            .map_err(|err| {
+                tracing::error!(error = %err, "get citizen document for dossier failed");
+                api_error(StatusCode::INTERNAL_SERVER_ERROR, 1004, "公民资料查询失败")
+            })?
+            .ok_or_else(|| api_error(StatusCode::CONFLICT, 1004, "公民资料在打包期间被删除"))?;
+        let bytes = std::fs::read(stored.file_path.as_str()).map_err(|err| {
+            tracing::error!(error = %err, path = %stored.file_path, "read citizen document for dossier failed");
+            api_error(StatusCode::NOT_FOUND, 1004, "公民资料文件不存在")
+        })?;
+        if format!("0x{}", hex::encode(Sha256::digest(&bytes))) != stored.meta.file_hash {
+            return Err(api_error(
+                StatusCode::CONFLICT,
+                1004,
+                "公民资料文件与登记哈希不符",
+            ));
+        }
+        total = total.saturating_add(bytes.len());
+        if total > MAX_DOSSIER_BYTES {
+            return Err(api_error(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                1001,
+                "公民资料总量超过单次移交上限",
+            ));
+        }
+        out.push(DossierDocument {
+            file_name: stored.meta.file_name,
+            document_type: stored.meta.document_type,
+            file_hash: stored.meta.file_hash,
+            uploader_account_id: stored.meta.uploader_account_id,
+            uploaded_at: stored.meta.uploaded_at,
+            content: base64::engine::general_purpose::STANDARD.encode(&bytes),
+        });
+    }
+    Ok(out)
+}
+
+/// 原注册局:封包并生成冷签二维码。链上居住地须已迁入接收局所在市。
+pub(crate) async fn prepare_dossier_export(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(cid_number): Path<String>,
+    Json(input): Json<ExportPrepareInput>,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    if let Err(resp) =
+        crate::auth::passkey::require_passkey_assertion(&state, &headers, ctx.account_id.as_str())
+    {
+        return resp;
+    }
+    let (sender_cid_number, scope) = match node_city_registry(&state, &ctx) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let record = match super::handler::ensure_citizen_document_write_scope(
+        &state,
+        &ctx,
+        cid_number.as_str(),
+    ) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    if record.province_code != scope.province_code || record.city_code != scope.city_code {
+        return api_error(
+            StatusCode::CONFLICT,
+            1004,
+            "本地档案不属于本注册局,不能移交",
+        );
+    }
+
+    let recipient = input.recipient;
+    let recipient_cid_number = recipient.institution_cid_number.trim().to_string();
+    if recipient_cid_number == sender_cid_number || !is_city_registry_cid(&recipient_cid_number) {
+        return api_error(StatusCode::BAD_REQUEST, 1001, "接收方必须是其他市注册局");
+    }
+    let recipient_public = match verify_recipient_card_signature(&recipient, Utc::now().timestamp())
+    {
+        Ok(v) => v,
+        Err(err) => return api_error(StatusCode::UNPROCESSABLE_ENTITY, 2004, err.as_str()),
+    };
+    if let Err(resp) = ensure_registry_admin(
+        recipient_cid_number.as_str(),
+        recipient.signer_account_id.as_str(),
+        "接收方",
+    )
+    .await
+    {
+        return resp;
+    }
+
+    let chain = match chain_residence(record.cid_number.as_str()).await {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    if chain.province_code == scope.province_code && chain.city_code == scope.city_code {
+        return api_error(StatusCode::CONFLICT, 1004, "链上居住地仍在本市,无需移交");
+    }
+    if registry_scope_codes(&recipient_cid_number)
+        != Some((chain.province_code.clone(), chain.city_code.clone()))
+    {
+        return api_error(
+            StatusCode::CONFLICT,
+            1004,
+            "接收局不是公民链上居住地所在市的注册局",
+        );
+    }
+    match chain_runtime::institution_lookup(&recipient_cid_number).await {
+        Ok(Some(institution))
+            if chain_runtime::institution_code_label(&institution.institution_code)
+                == chain_runtime::TIER2_REGISTRY_CODE => {}
+        Ok(_) => {
+            return api_error(StatusCode::NOT_FOUND, 1004, "接收局不是链上市注册局");
+        }
+        Err(err) => {
+            tracing::warn!(error = %err, "lookup recipient registry failed");
+            return api_error(StatusCode::BAD_GATEWAY, 5002, "chain unreachable");
+        }
+    }
+
+    let documents = match read_dossier_documents(&state, &record) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let document_count = documents.len();
+    let before_hash = hash_json(&serde_json::to_value(&record).unwrap_or_default());
+    let plaintext = Zeroizing::new(
+        serde_json::to_vec(&DossierContent { record, documents }).unwrap_or_default(),
+    );
+    let now = Utc::now();
+    let transfer_id = format!("citizen-dossier-{}", Uuid::new_v4());
+    let template = DossierHeader {
+        version: DOSSIER_VERSION,
+        transfer_id: transfer_id.clone(),
+        cid_number: cid_number.trim().to_string(),
+        sender_cid_number: sender_cid_number.clone(),
+        recipient_cid_number: recipient_cid_number.clone(),
+        recipient_public_key: String::new(),
+        ephemeral_public_key: String::new(),
+        nonce: String::new(),
+        created_at: now.timestamp(),
+    };
+    let (header, ciphertext) = match seal_dossier(template, &recipient_public, &plaintext) {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::error!(error = %err, "seal citizen dossier failed");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, "档案封包失败");
+        }
+    };
+    let digest = dossier_digest(&header, &ciphertext);
+    let expires_at = now + Duration::seconds(ADMIN_ACTION_TTL_SECONDS);
+    let province = ctx.scope_province_name.clone().unwrap_or_default();
+    let target = transfer_target(header.cid_number.as_str(), &recipient_cid_number);
+    let payload_text = signed_payload_text(AdminSignedPayload {
+        domain: "onchina_admin_governance",
+        qr_proto: crate::core::qr::QR_V1,
+        action_id: transfer_id.as_str(),
+        action_type: ACTION_DOSSIER_EXPORT,
+        account_id: ctx.account_id.as_str(),
+        actor_cid_number: sender_cid_number.as_str(),
+        actor_province_name: province.as_str(),
+        target: target.as_str(),
+        request_hash: digest.as_str(),
+        before_hash: before_hash.as_str(),
+        after_hash: public_key_fingerprint(&recipient_public).as_str(),
+        expires_at: expires_at.timestamp(),
+    });
+    let payload_hash = payload_hash_for_text(payload_text.as_str());
+    let sign_request = match crate::core::qr::build_sign_request(
+        transfer_id.as_str(),
+        now.timestamp(),
+        expires_at.timestamp(),
+        ctx.account_id.as_str(),
+        payload_text.as_str(),
+        crate::core::qr::action_onchina_admin(),
+    ) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    if let Err(err) = state.db.insert_dossier_transfer(TransferInsert::new(
+        transfer_id.as_str(),
+        DIRECTION_EXPORT,
+        &header,
+        digest.as_str(),
+        Some(ciphertext),
+        payload_text.as_str(),
+        payload_hash.as_str(),
+        ctx.account_id.as_str(),
+        None,
+        document_count,
+        STATUS_PENDING,
+        Some(expires_at),
+    )) {
+        tracing::error!(error = %err, "insert citizen dossier export failed");
+        return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, "档案移交落库失败");
+    }
+    Json(ApiResponse {
+        code: 0,
+        message: "ok".to_string(),
+        data: ExportPrepareOutput {
+            transfer_id,
+            sign_request,
+            payload_hash,
+            dossier_digest: digest,
+            document_count,
+            expires_at: expires_at.timestamp(),
+        },
+    })
+    .into_response()
+}
+
+/// 原注册局:回填冷签,signer ∈ 本机构链上 Active 管理员后交付移交包。
+pub(crate) async fn confirm_dossier_export(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(input): Json<ExportConfirmInput>,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let (sender_cid_number, _scope) = match node_city_registry(&state, &ctx) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let row = match state.db.get_dossier_export(input.transfer_id.as_str()) {
+        Ok(Some(v)) => v,
+        Ok(None) => return api_error(StatusCode::NOT_FOUND, 1004, "档案移交不存在"),
+        Err(err) => {
+            tracing::error!(error = %err, "query citizen dossier export failed");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, "档案移交查询失败");
+        }
+    };
+    if row.status != STATUS_PENDING || row.expires_at.is_none_or(|at| Utc::now() > at) {
+        return api_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            2004,
+            "档案移交已过期或已完成",
+        );
+    }
+    if !same_account_id(row.actor_account_id.as_str(), ctx.account_id.as_str())
+        || row.sender_cid_number != sender_cid_number
+    {
+        return api_error(StatusCode::FORBIDDEN, 1003, "档案移交发起人不符");
+    }
+    if let Err(resp) = verify_account_signature(
+        ctx.account_id.as_str(),
+        input.account_id.as_str(),
+        input.signature.as_str(),
+        input.payload_hash.as_str(),
+        row.payload_hash.as_str(),
+        row.payload_text.as_str(),
+    ) {
+        return resp;
+    }
+    if let Err(resp) = crate::auth::actions::ensure_signer_on_chain_admin(
+        &state.db,
+        &sender_cid_number,
+        input.account_id.as_str(),
+    )
+    .await
+    {
+        return resp;
+    }
+    let header: DossierHeader = match serde_json::from_str(row.header_json.as_str()) {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::error!(error = %err, "stored citizen dossier header invalid");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, "档案移交记录损坏");
+        }
+    };
+    match state.db.mark_dossier_exported(
+        input.transfer_id.as_str(),
+        input.account_id.as_str(),
+        input.signature.as_str(),
+    ) {
+        Ok(true) => {}
+        Ok(false) => {
+            return api_error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                2004,
+                "档案移交已过期或已完成",
+            )
+        }
+        Err(err) => {
+            tracing::error!(error = %err, "mark citizen dossier exported failed");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, "档案移交落库失败");
+        }
+    }
+    crate::core::runtime_ops::append_audit_log(
+        &state,
+        "CITIZEN_DOSSIER_EXPORT",
+        &ctx.account_id,
+        Some(row.cid_number.clone()),
+        serde_json::json!({
+            "cid_number": row.cid_number,
+            "transfer_id": input.transfer_id,
+            "recipient_cid_number": row.recipient_cid_number,
+            "dossier_digest": row.dossier_digest,
+            "document_count": row.document_count,
+            "signer_account_id": input.account_id,
+            "request_id": request_id_from_headers(&headers),
+            "actor_ip": actor_ip_from_headers(&headers),
+        }),
+    );
+    Json(ApiResponse {
+        code: 0,
+        message: "ok".to_string(),
+        data: DossierPackage {
+            header,
+            ciphertext: base64::engine::general_purpose::STANDARD.encode(&row.ciphertext),
+            signer_account_id: input.account_id,
+            payload_text: row.payload_text,
+            signature: input.signature,
+        },
+    })
+    .into_response()
+}
+
+/// 校验冷签文本与移交包一一对应,并对文本验 sr25519 签名(与管理员动作同一 GMB 签名域)。
+fn verify_package_signature(
+    package: &DossierPackage,
+    digest: &str,
+) -> Result<(), axum::response::Response> {
+    let payload: SignedExportPayload = serde_json::from_str(package.payload_text.as_str())
+        .map_err(|_| api_error(StatusCode::BAD_REQUEST, 1001, "签名文本格式非法"))?;
+    let header = &package.header;
+    if payload.action_type != ACTION_DOSSIER_EXPORT
+        || payload.action_id != header.transfer_id
+        || payload.actor_cid_number != header.sender_cid_number
+        || payload.target
+            != transfer_target(header.cid_number.as_str(), &header.recipient_cid_number)
+        || payload.request_hash != digest
+        || !same_account_id(&payload.account_id, &package.signer_account_id)
+    {
+        return Err(api_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            2004,
+            "签名文本与移交包不符",
+        ));
+    }
+    let signing_bytes = primitives::sign::signing_message(
+        primitives::sign::OP_SIGN_ONCHINA_ADMIN,
+        package.payload_text.as_bytes(),
+    );
+    if !crate::auth::login::verify_admin_signature_bytes(
+        package.signer_account_id.as_str(),
+        &signing_bytes,
+        package.signature.as_str(),
+    ) {
+        return Err(api_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            2004,
+            "signature verify failed",
+        ));
+    }
+    Ok(())
+}
+
+fn decode_dossier_documents(
+    content: &DossierContent,
+) -> Result<Vec<(&DossierDocument, Vec<u8>)>, axum::response::Response> {
+    content
+        .documents
+        .iter()
+        .map(|doc| {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(doc.content.as_bytes())
+                .map_err(|_| api_error(StatusCode::BAD_REQUEST, 1001, "资料文件编码非法"))?;
+            if format!("0x{}", hex::encode(Sha256::digest(&bytes))) != doc.file_hash
+                || !CITIZEN_DOCUMENT_TYPES.contains(&doc.document_type.as_str())
+                || crate::crypto::pubkey::normalize_account_id(&doc.uploader_account_id).as_deref()
+                    != Some(doc.uploader_account_id.as_str())
+            {
+                return Err(api_error(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    2004,
+                    "资料文件与登记信息不符",
+                ));
+            }
+            Ok((doc, bytes))
+        })
+        .collect()
+}
+
+/// 落盘 + 落库单个资料文件;落库失败时清理已写文件。
+/// 资料文件先落盘暂存,元数据随导入事务登记;事务失败时由调用方清理。
+fn stage_imported_document(
+    cid_number: &str,
+    doc: &DossierDocument,
+    bytes: &[u8],
+) -> Result<(CitizenDocument, String), String> {
+    let doc_dir = format!("data/citizen-documents/{cid_number}");
+    std::fs::create_dir_all(&doc_dir).map_err(|e| format!("create {doc_dir} failed: {e}"))?;
+    let file_ext = std::path::Path::new(&doc.file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("bin");
+    let stored_path = format!(
+        "{doc_dir}/{}_{}.{}",
+        Utc::now().format("%Y%m%d%H%M%S"),
+        Uuid::new_v4().as_simple(),
+        file_ext
+    );
+    std::fs::write(&stored_path, bytes).map_err(|e| format!("write {stored_path} failed: {e}"))?;
+    let meta = CitizenDocument {
+        id: 0,
+        cid_number: cid_number.to_string(),
+        file_name: doc.file_name.clone(),
+        document_type: doc.document_type.clone(),
+        file_size: bytes.len() as u64,
+        file_hash: doc.file_hash.clone(),
+        uploader_account_id: doc.uploader_account_id.clone(),
+        uploaded_at: doc.uploaded_at,
+    };
+    Ok((meta, stored_path))
+}
+
+fn remove_staged_documents(staged: &[(CitizenDocument, String)]) {
+    for (_, path) in staged {
+        if let Err(err) = std::fs::remove_file(path) {
+            tracing::warn!(error = %err, path = %path, "remove staged citizen document file failed");
+        }
+    }
+}
+
+/// 接收局:验签、验原注册局管理员身份、解密并按投影规则合并档案。
+pub(crate) async fn import_dossier(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(package): Json<DossierPackage>,
+) -> impl IntoResponse {
+    let ctx = match require_admin_any(&state, &headers) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    if let Err(resp) =
+        crate::auth::passkey::require_passkey_assertion(&state, &headers, ctx.account_id.as_str())
+    {
+        return resp;
+    }
+    let (own_cid_number, scope) = match node_city_registry(&state, &ctx) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let header = package.header.clone();
+    if header.version != DOSSIER_VERSION {
+        return api_error(StatusCode::BAD_REQUEST, 1001, "不支持的移交包版本");
+    }
+    if header.recipient_cid_number != own_cid_number {
+        return api_error(StatusCode::CONFLICT, 1004, "移交包不是发给本注册局的");
+    }
+    let ciphertext =
+        match base64::engine::general_purpose::STANDARD.decode(package.ciphertext.as_bytes()) {
+            Ok(v) => v,
+            Err(_) => return api_error(StatusCode::BAD_REQUEST, 1001, "密文编码非法"),
+        };
+    let digest = dossier_digest(&header, &ciphertext);
+    if let Err(resp) = verify_package_signature(&package, digest.as_str()) {
+        return resp;
+    }
+    match state.db.dossier_imported(header.transfer_id.as_str()) {
+        Ok(false) => {}
+        Ok(true) => return api_error(StatusCode::CONFLICT, 1004, "该移交包已导入"),
+        Err(err) => {
+            tracing::error!(error = %err, "query citizen dossier import failed");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, "档案移交查询失败");
+        }
+    }
+    if let Err(resp) = ensure_registry_admin(
+        header.sender_cid_number.as_str(),
+        package.signer_account_id.as_str(),
+        "移交方",
+    )
+    .await
+    {
+        return resp;
+    }
+    let chain = match chain_residence(header.cid_number.as_str()).await {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    if chain.province_code != scope.province_code || chain.city_code != scope.city_code {
+        return api_error(StatusCode::CONFLICT, 1004, "公民链上居住地不在本注册局");
+    }
+    // 移交方必须是公民迁入前的居住地注册局;indexer 未记录迁入时拒绝(fail-closed)。
+    match state.db.latest_residence_move(header.cid_number.as_str()) {
+        Ok(Some(last_move))
+            if sender_is_previous_registry(
+                &last_move,
+                &scope,
+                header.sender_cid_number.as_str(),
+            ) => {}
+        Ok(Some(_)) => {
+            return api_error(
+                StatusCode::CONFLICT,
+                1004,
+                "移交方不是公民迁入前居住地的注册局",
+            )
+        }
+        Ok(None) => {
+            return api_error(
+                StatusCode::CONFLICT,
+                1004,
+                "未索引到公民迁入本市的链上记录,请待同步后重试",
+            )
+        }
+        Err(err) => {
+            tracing::error!(error = %err, "query citizen residence move failed");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, "迁入记录查询失败");
+        }
+    }
+
+    let secret = match transfer_key() {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let plaintext = match open_dossier(&secret, &header, &ciphertext) {
+        Ok(v) => v,
+        Err(err) => return api_error(StatusCode::UNPROCESSABLE_ENTITY, 2004, err.as_str()),
+    };
+    let content: DossierContent = match serde_json::from_slice(&plaintext) {
+        Ok(v) => v,
+        Err(_) => return api_error(StatusCode::BAD_REQUEST, 1001, "档案内容格式非法"),
+    };
+    if content.record.cid_number != header.cid_number {
+        return api_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            2004,
+            "档案内容与移交包头部不符",
+        );
+    }
+    let documents = match decode_dossier_documents(&content) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    // 链上列以链为准,链下正本取移交包;本地仅有投影行时沿用本地 id,已有正本则拒绝覆盖。
+    let existing = match state.db.find_citizen_by_cid(header.cid_number.as_str()) {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::error!(error = %err, "query citizen before dossier import failed");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, 1004, "公民档案查询失败");
+        }
+    };
+    if existing
+        .as_ref()
+        .is_some_and(|local| !local.passport_no.trim().is_empty())
+    {
+        return api_error(
+            StatusCode::CONFLICT,
+            1004,
+            "本地已有该公民档案正本,拒绝覆盖",
+        );
+    }
+    if let Err(err) = ensure_binding_projection_monotonic(&chain, existing.as_ref()) {
+        tracing::warn!(error = %err, "dossier import blocked by binding projection order");
+        return api_error(
+            StatusCode::CONFLICT,
+            1004,
+            "本地投影比链上视图更新,请稍后重试",
+        );
+    }
+    let mut original = content.record.clone();
+    if let Some(local) = existing.as_ref() {
+        original.id = local.id;
+    }
+    let now = Utc::now();
+    let Some(mut merged) = merge_citizen_record(&chain, Some(&original), &scope, now) else {
+        return api_error(StatusCode::CONFLICT, 1004, "公民链上居住地不在本注册局");
+    };
+    merged.updater_account_id = Some(ctx.account_id.clone());
+
+    let mut known_hashes = HashSet::new();
+    let mut staged = Vec::with_capacity(documents.len());
+    let mut documents_skipped = 0usize;
+    for (doc, bytes) in &documents {
+        if !known_hashes.insert(doc.file_hash.clone()) {
+            documents_skipped += 1;
+            continue;
+        }
+        match stage_imported_document(merged.cid_number.as_str(), doc, bytes) {
+            Ok(v) => staged.push(v),
+            Err(err) => {
+                remove_staged_documents(&staged);
+                tracing::error!(error = %err, "stage imported citizen document failed");
+                return api_error(StatusCode::INTERNAL_SERVER_ERROR, 1004, "公民资料落库失败");
+            }
+        }
+    }
+    let documents_imported = staged.len();
+
+    let payload_hash = payload_hash_for_text(package.payload_text.as_str());
+    let transfer = TransferInsert::new(
+        header.transfer_id.as_str(),
+        DIRECTION_IMPORT,
+        &header,
+        digest.as_str(),
+        None,
+        package.payload_text.as_str(),
+        payload_hash.as_str(),
+        ctx.account_id.as_str(),
+        Some((
+            package.signer_account_id.as_str(),
+            package.signature.as_str(),
+        )),
+        documents.len(),
+        STATUS_IMPORTED,
+        None,
+    );
+    match state
+        .db
+        .commit_dossier_import(&merged, staged.clone(), transfer)
+    {
+        Ok(Ok(())) => {}
+        Ok(Err(conflict)) => {
+            remove_staged_documents(&staged);
+            let message = match conflict {
+                ImportConflict::LocalRecordExists => "本地已有该公民档案正本,拒绝覆盖",
+                ImportConflict::AlreadyImported => "该移交包已导入",
+            };
+            return api_error(StatusCode::CONFLICT, 1004, message);
+        }
+        Err(err) => {
+            remove_staged_documents(&staged);
+            tracing::error!(error = %err, "commit citizen dossier import failed");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, 5001, "档案移交落库失败");
+        }
+    }
+    crate::core::runtime_ops::append_audit_log(
+        &state,
+        "CITIZEN_DOSSIER_IMPORT",
+        &ctx.account_id,
+        Some(header.cid_number.clone()),
+        serde_json::json!({
+            "cid_number": header.cid_number,
+            "transfer_id": header.transfer_id,
+            "sender_cid_number": header.sender_cid_number,
+            "dossier_digest": digest,
+            "signer_account_id": package.signer_account_id,
+            "documents_imported": documents_imported,
+            "documents_skipped": documents_skipped,
+            "request_id": request_id_from_headers(&headers),
+            "actor_ip": actor_ip_from_headers(&headers),
+        }),
+    );
+    Json(ApiResponse {
+        code: 0,
+        message: "ok".to_string(),
+        data: ImportOutput {
+            transfer_id: header.transfer_id,
+            cid_number: header.cid_number,
+            sender_cid_number: header.sender_cid_number,
+            documents_imported,
+            documents_skipped,
+        },
+    })
+    .into_response()
+}
+
+#[cfg(test)]
+// 封包夹具均为固定输入，解包失败即代表加解密契约回归。
+#[allow(clippy::expect_used, clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn header() -> DossierHeader {
+        DossierHeader {
+            version: DOSSIER_VERSION,
+            transfer_id: "citizen-dossier-1".to_string(),
+            cid_number: "GD000-CTZN1-2026-TEST".to_string(),
+            sender_cid_number: "GD001-CREG0-000000001-2026".to_string(),
+            recipient_cid_number: "GD002-CREG0-000000001-2026".to_string(),
+            recipient_public_key: String::new(),
+            ephemeral_public_key: String::new(),
+            nonce: String::new(),
+            created_at: 1_780_000_000,
+        }
+    }
+
+    fn recipient() -> (StaticSecret, [u8; 32]) {
+        let secret = StaticSecret::from([7u8; 32]);
+        let public = *PublicKey::from(&secret).as_bytes();
+        (secret, public)
+    }
+
+    #[test]
+    fn sealed_dossier_opens_with_recipient_key() {
+        let (secret, public) = recipient();
+        let (sealed, ciphertext) = seal_dossier(header(), &public, b"dossier").unwrap();
+        assert_eq!(
+            sealed.recipient_public_key,
+            format!("0x{}", hex::encode(public))
+        );
+        let plaintext = open_dossier(&secret, &sealed, &ciphertext).unwrap();
+        assert_eq!(plaintext.as_slice(), b"dossier");
+    }
+
+    #[test]
+    fn tampered_header_or_ciphertext_fails_to_open() {
+        let (secret, public) = recipient();
+        let (sealed, ciphertext) = seal_dossier(header(), &public, b"dossier").unwrap();
+
+        let mut other_cid = sealed.clone();
+        other_cid.cid_number = "GD000-CTZN1-2026-OTHER".to_string();
+        assert!(open_dossier(&secret, &other_cid, &ciphertext).is_err());
+
+        let mut flipped = ciphertext.clone();
+        flipped[0] ^= 1;
+        assert!(open_dossier(&secret, &sealed, &flipped).is_err());
+
+        let wrong = StaticSecret::from([9u8; 32]);
+        assert!(open_dossier(&wrong, &sealed, &ciphertext).is_err());
+    }
+
+    #[test]
+    fn digest_binds_header_and_ciphertext() {
+        let (_, public) = recipient();
+        let (sealed, ciphertext) = seal_dossier(header(), &public, b"dossier").unwrap();
+        let digest = dossier_digest(&sealed, &ciphertext);
+        assert_eq!(digest, dossier_digest(&sealed, &ciphertext));
+
+        let mut other_recipient = sealed.clone();
+        other_recipient.recipient_cid_number = "GD003-CREG0-000000001-2026".to_string();
+        assert_ne!(digest, dossier_digest(&other_recipient, &ciphertext));
+        assert_ne!(digest, dossier_digest(&sealed, &ciphertext[1..]));
+    }
+
+    fn registry_cid(account_id: &str, city_name: &str) -> String {
+        crate::cid::generate_cid_number(crate::cid::GenerateCidInput {
+            account_id,
+            p1: "0",
+            province_name: "广东省",
+            city_name,
+            institution: chain_runtime::TIER2_REGISTRY_CODE,
+        })
+        .expect("registry cid should generate")
+    }
+
+    fn signed_card(
+        pair: &sp_core::sr25519::Pair,
+        institution_cid_number: &str,
+        public_key: &[u8; 32],
+        expires_at: i64,
+    ) -> RecipientCard {
+        use sp_core::Pair as _;
+        let signer_account_id = format!("0x{}", hex::encode(pair.public().0));
+        let public_key = format!("0x{}", hex::encode(public_key));
+        let payload_text = signed_payload_text(AdminSignedPayload {
+            domain: "onchina_admin_governance",
+            qr_proto: crate::core::qr::QR_V1,
+            action_id: "citizen-dossier-recipient-1",
+            action_type: ACTION_DOSSIER_RECIPIENT_KEY,
+            account_id: signer_account_id.as_str(),
+            actor_cid_number: institution_cid_number,
+            actor_province_name: "广东省",
+            target: institution_cid_number,
+            request_hash: public_key.as_str(),
+            before_hash: "",
+            after_hash: "",
+            expires_at,
+        });
+        let message = primitives::sign::signing_message(
+            primitives::sign::OP_SIGN_ONCHINA_ADMIN,
+            payload_text.as_bytes(),
+        );
+        RecipientCard {
+            institution_cid_number: institution_cid_number.to_string(),
+            public_key,
+            signature: format!("0x{}", hex::encode(pair.sign(&message).0)),
+            signer_account_id,
+            payload_text,
+        }
+    }
+
+    #[test]
+    fn recipient_card_signature_binds_registry_key_and_expiry() {
+        use sp_core::Pair as _;
+        let pair = sp_core::sr25519::Pair::from_seed(&[3u8; 32]);
+        let (_, public) = recipient();
+        let cid = header().recipient_cid_number;
+        let now = 1_780_000_000;
+        let card = signed_card(&pair, cid.as_str(), &public, now + 60);
+        assert_eq!(verify_recipient_card_signature(&card, now), Ok(public));
+
+        // 卡片公钥被替换:签名文本里的公钥与卡片不符。
+        let mut swapped_key = card.clone();
+        swapped_key.public_key = format!("0x{}", hex::encode([9u8; 32]));
+        assert!(verify_recipient_card_signature(&swapped_key, now).is_err());
+
+        // 卡片改挂到其他注册局。
+        let mut other_registry = card.clone();
+        other_registry.institution_cid_number = header().sender_cid_number;
+        assert!(verify_recipient_card_signature(&other_registry, now).is_err());
+
+        // 签名人声明被替换为他人账户。
+        let other = sp_core::sr25519::Pair::from_seed(&[4u8; 32]);
+        let mut forged = card.clone();
+        forged.signer_account_id = format!("0x{}", hex::encode(other.public().0));
+        assert!(verify_recipient_card_signature(&forged, now).is_err());
+
+        assert!(verify_recipient_card_signature(&card, now + 60).is_err());
+    }
+
+    #[test]
+    fn sender_must_be_registry_of_previous_residence() {
+        let previous = registry_cid(
+            "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "荔湾市",
+        );
+        let (from_province_code, from_city_code) =
+            registry_scope_codes(&previous).expect("registry scope");
+        let scope = NodeScope {
+            province_code: from_province_code.clone(),
+            city_code: "999".to_string(),
+        };
+        let last_move = ResidenceMoveRow {
+            from_province_code: from_province_code.clone(),
+            from_city_code: from_city_code.clone(),
+            to_province_code: scope.province_code.clone(),
+            to_city_code: scope.city_code.clone(),
+        };
+        assert!(sender_is_previous_registry(
+            &last_move,
+            &scope,
+            previous.as_str()
+        ));
+
+        // 迁入记录指向别的市:移交方不是前居住地注册局。
+        let elsewhere = ResidenceMoveRow {
+            from_city_code: "998".to_string(),
+            ..last_move
+        };
+        assert!(!sender_is_previous_registry(
+            &elsewhere,
+            &scope,
+            previous.as_str()
+        ));
+
+        // 最近一次迁入不是迁入本市(已再次迁出)。
+        let moved_away = ResidenceMoveRow {
+            from_province_code,
+            from_city_code,
+            to_province_code: scope.province_code.clone(),
+            to_city_code: "997".to_string(),
+        };
+        assert!(!sender_is_previous_registry(
+            &moved_away,
+            &scope,
+            previous.as_str()
+        ));
+        assert!(!sender_is_previous_registry(
+            &moved_away,
+            &scope,
+            "not-a-cid"
+        ));
+    }
+
+    #[test]
+    fn fingerprint_is_prefixed_truncated_sha256() {
+        let fingerprint = public_key_fingerprint(&[1u8; 32]);
+        assert!(fingerprint.starts_with("0x"));
+        assert_eq!(fingerprint.len(), 2 + 32);
+    }
+}
@@ -58,6 +58,15 @@ pub(crate) async fn list_official_institutions(
         manifest_version: None,
         catalog_status: None,
     };
+    // 自机构档不承担辖区职能,公权目录不向其铺开。
+    if scope.is_own_institution() {
+        return Json(ApiResponse {
+            code: 0,
+            message: "ok".to_string(),
+            data: empty_page(),
+        })
+        .into_response();
+    }
     if let (Some(locked), Some(requested)) = (&scope.locked_province_name, &query.province_name) {
         if locked != requested {
             return Json(ApiResponse {
@@ -37,6 +37,20 @@ pub(crate) struct ActiveProposalView {
     pub(crate) pending_count: u32,
 }
 
+/// 本机构参与表决的法律即将到来的日落节点(失效或复审)。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UpcomingSunsetView {
+    pub(crate) law_id: u64,
+    pub(crate) version: u32,
+    /// 该版本法律标题(版本缺失时为空串)。
+    pub(crate) title: String,
+    /// `expiry` = 到期失效 / `review` = 到期复审。
+    pub(crate) kind: &'static str,
+    /// 到期时间戳(毫秒,链上 `pallet_timestamp` 口径)。
+    pub(crate) at: u64,
+}
+
 /// 大屏看板顶层快照(本节点机构 + 名册规模 + 活跃提案列表)。
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -51,4 +65,6 @@ pub(crate) struct DisplayBoard {
     pub(crate) roster_total: u32,
     /// 活跃立法提案看板(仅 kind=立法;无活跃提案时为空)。
     pub(crate) active_proposals: Vec<ActiveProposalView>,
+    /// 本机构表决院所属法律的日落排程(按到期时间升序)。
+    pub(crate) upcoming_sunsets: Vec<UpcomingSunsetView>,
 }
@@ -11,13 +11,23 @@ use crate::institution::admins::chain_roles::{
     fetch_active_assignments_onchain, InstitutionAssignmentView,
 };
 
+use crate::domains::legislation::law::chain_read::{
+    fetch_law, fetch_law_version_title, fetch_sunset_schedule, OnChainLaw, OnChainSunsetEntry,
+};
+
 use super::chain_read::{fetch_active_proposal_ids, fetch_representative_ballots};
-use super::model::{ActiveProposalView, DisplayBoard, SeatView};
+use super::model::{ActiveProposalView, DisplayBoard, SeatView, UpcomingSunsetView};
 
 /// 立法提案种类判别式(对齐链端 votingengine `PROPOSAL_KIND_LEGISLATION`)。
 const PROPOSAL_KIND_LEGISLATION: u8 = 2;
 
-/// 装配本节点机构的大屏看板:名册 + 活跃立法提案(逐席投票)。
+/// 日落排程中 `SunsetKind::Expiry` 的单字节值。
+const SUNSET_KIND_EXPIRY: u8 = 0;
+
+/// 大屏单次读取的日落排程上限(按到期顺序取最早的若干条)。
+const MAX_BOARD_SUNSET_ENTRIES: usize = 200;
+
+/// 装配本节点机构的大屏看板:名册 + 活跃立法提案(逐席投票)+ 日落排程。
 ///
 /// 活跃提案来自 `ActiveProposalsBySubject[InstitutionCid(cid_number)]`;逐个取进度投影,
 /// 非法律提案（无 `LegislationMetas`）或已清理者跳过。
@@ -49,15 +59,67 @@ pub(crate) async fn build_display_board(
         active_proposals.push(build_active_proposal_view(state, &roster, &ballots));
     }
 
+    // 只点查排程里出现的法律及其版本标题,不整表扫描法律 / 版本全文。
+    let schedule = fetch_sunset_schedule(MAX_BOARD_SUNSET_ENTRIES).await?;
+    let mut laws = HashMap::new();
+    for entry in &schedule {
+        if !laws.contains_key(&entry.law_id) {
+            if let Some(law) = fetch_law(entry.law_id).await? {
+                laws.insert(entry.law_id, law);
+            }
+        }
+    }
+    let mut upcoming_sunsets = build_upcoming_sunsets(&identity.cid_number, &schedule, &laws);
+    for sunset in &mut upcoming_sunsets {
+        sunset.title = fetch_law_version_title(sunset.law_id, sunset.version)
+            .await?
+            .unwrap_or_default();
+    }
+
     Ok(DisplayBoard {
         institution_code,
         cid_short_name,
         scope_label,
         roster_total: roster.len() as u32,
         active_proposals,
+        upcoming_sunsets,
     })
 }
 
+/// 排程 × 法律表决院 → 本机构相关的日落节点(纯装配,可单测)。
+///
+/// 排程里可能残留已续期/已被修法取代的条目(链端到点时才丢弃),这里只保留
+/// 法律当前生效版本或待生效版本的条目。标题留空,由调用方按命中条目点查填充。
+fn build_upcoming_sunsets(
+    cid_number: &str,
+    schedule: &[OnChainSunsetEntry],
+    laws: &HashMap<u64, OnChainLaw>,
+) -> Vec<UpcomingSunsetView> {
+    let mut out: Vec<UpcomingSunsetView> = schedule
+        .iter()
+        .filter(|entry| {
+            laws.get(&entry.law_id).is_some_and(|law| {
+                (law.effective_version == Some(entry.version)
+                    || law.pending_version == Some(entry.version))
+                    && law.houses.iter().any(|h| h == cid_number.as_bytes())
+            })
+        })
+        .map(|entry| UpcomingSunsetView {
+            law_id: entry.law_id,
+            version: entry.version,
+            title: String::new(),
+            kind: if entry.kind == SUNSET_KIND_EXPIRY {
+                "expiry"
+            } else {
+                "review"
+            },
+            at: entry.at,
+        })
+        .collect();
+    out.sort_by_key(|s| (s.at, s.law_id));
+    out
+}
+
 /// 名册左连接逐席投票 → 席位板 + 聚合计数(纯装配,可单测)。
 fn build_active_proposal_view(
     state: LegProposalState,
@@ -151,6 +213,50 @@ mod tests {
         assert_eq!(view.state.proposal_id, 7);
     }
 
+    fn law(law_id: u64, effective: Option<u32>, houses: &[&str]) -> OnChainLaw {
+        OnChainLaw {
+            law_id,
+            tier: 1,
+            scope_code: 0,
+            houses: houses.iter().map(|h| h.as_bytes().to_vec()).collect(),
+            effective_version: effective,
+            latest_version: effective.unwrap_or(1),
+            pending_version: None,
+            status: 1,
+        }
+    }
+
+    #[test]
+    fn upcoming_sunsets_keep_own_current_versions_sorted() {
+        let nrp = "LN001-NRP0G-000000001-2026";
+        let laws: HashMap<u64, OnChainLaw> = [
+            law(1, Some(2), &[nrp]),
+            law(2, Some(1), &["LN001-PRP0G-000000001-2026"]),
+            law(3, Some(1), &[nrp]),
+        ]
+        .into_iter()
+        .map(|law| (law.law_id, law))
+        .collect();
+        let entry = |law_id, version, kind, at| OnChainSunsetEntry {
+            law_id,
+            version,
+            kind,
+            at,
+        };
+        let schedule = vec![
+            entry(1, 2, 0, 9_000),
+            entry(1, 1, 0, 1_000), // 已被修法取代的旧版本
+            entry(2, 1, 0, 2_000), // 非本机构表决院
+            entry(3, 1, 1, 4_000),
+            entry(4, 1, 0, 5_000), // 法律记录缺失
+        ];
+        let sunsets = build_upcoming_sunsets(nrp, &schedule, &laws);
+        assert_eq!(sunsets.len(), 2);
+        assert_eq!((sunsets[0].law_id, sunsets[0].kind), (3, "review"));
+        assert_eq!((sunsets[1].law_id, sunsets[1].kind), (1, "expiry"));
+        assert!(sunsets[1].title.is_empty());
+    }
+
     #[test]
     fn empty_roster_yields_no_seats() {
         let view = build_active_proposal_view(sample_state(), &[], &HashMap::new());
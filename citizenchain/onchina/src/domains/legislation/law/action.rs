@@ -121,16 +121,17 @@ async fn prepare_legislation_sign(
         chain,
         operation_context,
     } = request;
-    let prepared = crate::core::chain_submit::prepare_signing(&chain.call_data, account_id)
-        .await
-        .map_err(|error| {
-            tracing::error!(error = %error, purpose, "prepare legislation signing failed");
-            api_error(
-                StatusCode::BAD_GATEWAY,
-                5002,
-                "链签名载荷准备失败(链不可用)",
-            )
-        })?;
+    let prepared =
+        crate::core::chain_submit::prepare_signing(&state.db, &chain.call_data, account_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(error = %error, purpose, "prepare legislation signing failed");
+                api_error(
+                    StatusCode::BAD_GATEWAY,
+                    5002,
+                    "链签名载荷准备失败(链不可用)",
+                )
+            })?;
     let issued_at = Utc::now();
     let expires_at = issued_at + Duration::seconds(SESSION_TTL_SECS);
     let request_id = format!("{request_prefix}-{}", Uuid::new_v4());
@@ -11,9 +11,10 @@
 //! - `legislature_cid_number` = `Option<CidNumber>`;
 //! - `title` / `title_en` = `Vec<u8>` / `Option<Vec<u8>>`(`Compact<u32>` 长度前缀);
 //! - `chapters` = 章>节>条>款 嵌套(链端 `BoundedVec` 与 `Vec` 的 SCALE 同布局,由 `ChapterArg` 派生 `Encode`);
-//! - `scope_code` = u32 小端;`effective_at` = 生效时间戳毫秒(u64 小端);`law_id` = u64 小端。
+//! - `scope_code` = u32 小端;`effective_at` = 生效时间戳毫秒(u64 小端);`law_id` = u64 小端;
+//! - `sunset` = 立法/修法末位 `LawSunset { expires_at, review_by }`,两个 `Option<u64>` 毫秒时间戳。
 //!
-//! `tests` 用链端真实 `legislation_yuan::{Tier,VoteType}` 与 codec `.encode()` 逐字节交叉校验,杜绝静默漂移。
+//! `tests` 用链端真实 `legislation_yuan::{Tier,VoteType,LawSunset}` 与 codec `.encode()` 逐字节交叉校验,杜绝静默漂移。
 //!
 //! 随 Phase 1B 接入,届时移除本 allow。
 
@@ -56,6 +57,12 @@ pub struct SectionArg {
     pub title_en: Option<Vec<u8>>,
     pub articles: Vec<ArticleArg>,
 }
+/// 日落条款镜像(字段顺序锁死链端 `LawSunset`);两项皆空 = 不设日落。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode)]
+pub struct SunsetArg {
+    pub expires_at: Option<u64>,
+    pub review_by: Option<u64>,
+}
 /// 章(目录 + 节列表)。
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct ChapterArg {
@@ -113,6 +120,7 @@ pub fn encode_propose_enact_law(
     title_en: Option<&[u8]>,
     chapters: &[ChapterArg],
     effective_at: u64,
+    sunset: &SunsetArg,
 ) -> ChainCall {
     let mut out = vec![LEGISLATION_YUAN_PALLET_INDEX, PROPOSE_ENACT_LAW_CALL_INDEX];
     out.push(tier);
@@ -127,6 +135,7 @@ pub fn encode_propose_enact_law(
     encode_opt_bytes(&mut out, title_en);
     encode_chapters(&mut out, chapters);
     out.extend(effective_at.to_le_bytes());
+    out.extend(sunset.encode());
     ChainCall {
         action: chain_action_code(LEGISLATION_YUAN_PALLET_INDEX, PROPOSE_ENACT_LAW_CALL_INDEX),
         call_data: out,
@@ -146,6 +155,7 @@ pub fn encode_propose_amend_law(
     title_en: Option<&[u8]>,
     chapters: &[ChapterArg],
     effective_at: u64,
+    sunset: &SunsetArg,
 ) -> ChainCall {
     let mut out = vec![LEGISLATION_YUAN_PALLET_INDEX, PROPOSE_AMEND_LAW_CALL_INDEX];
     out.extend(law_id.to_le_bytes());
@@ -158,6 +168,7 @@ pub fn encode_propose_amend_law(
     encode_opt_bytes(&mut out, title_en);
     encode_chapters(&mut out, chapters);
     out.extend(effective_at.to_le_bytes());
+    out.extend(sunset.encode());
     ChainCall {
         action: chain_action_code(LEGISLATION_YUAN_PALLET_INDEX, PROPOSE_AMEND_LAW_CALL_INDEX),
         call_data: out,
@@ -189,7 +200,7 @@ pub fn encode_propose_repeal_law(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use legislation_yuan::{Tier, VoteType};
+    use legislation_yuan::{LawSunset, Tier, VoteType};
 
     /// 本编码器的 `tier`/`vote_type` 单字节序号必须与链端真实枚举 `.encode()` 逐字节一致。
     #[test]
@@ -267,6 +278,10 @@ mod tests {
             Some(title_en),
             &chapters,
             1000,
+            &SunsetArg {
+                expires_at: Some(90_000),
+                review_by: None,
+            },
         );
 
         // 前缀 [25,0] + QR 动作码 0x1900 = (25<<8)|0。
@@ -286,6 +301,13 @@ mod tests {
         golden.extend(Some(title_en.to_vec()).encode());
         golden.extend(chapters.encode());
         golden.extend(1000u64.encode());
+        golden.extend(
+            LawSunset {
+                expires_at: Some(90_000),
+                review_by: None,
+            }
+            .encode(),
+        );
 
         assert_eq!(
             &chain.call_data[2..],
@@ -313,10 +335,24 @@ mod tests {
             None,
             &sample_chapters(),
             50,
+            &SunsetArg {
+                expires_at: None,
+                review_by: Some(60_000),
+            },
         );
         assert_eq!(&amend.call_data[..2], &[25, 1]);
         assert_eq!(amend.action, 0x1901);
         assert_eq!(&amend.call_data[2..10], &7u64.to_le_bytes());
+        // 修法尾 = effective_at + LawSunset。
+        let mut amend_tail = 50u64.encode();
+        amend_tail.extend(
+            LawSunset {
+                expires_at: None,
+                review_by: Some(60_000),
+            }
+            .encode(),
+        );
+        assert!(amend.call_data.ends_with(&amend_tail));
 
         let repeal = encode_propose_repeal_law(
             7,
@@ -15,7 +15,7 @@ use super::model::{house_ref, to_law_chapters, LawView};
 use crate::core::chain_url;
 use crate::core::db::Db;
 use codec::Decode;
-use subxt::{dynamic, OnlineClient, PolkadotConfig};
+use subxt::dynamic;
 
 const TIER_CONSTITUTION: u8 = 0;
 
@@ -34,7 +34,7 @@ pub struct OnChainLaw {
     pub latest_version: u32,
     /// 已通过但未到生效时间的版本。
     pub pending_version: Option<u32>,
-    /// LawStatus 单字节枚举(0 待生效 / 1 生效 / 2 废止)。
+    /// LawStatus 单字节枚举(0 待生效 / 1 生效 / 2 废止 / 3 日落失效)。
     pub status: u8,
 }
 
@@ -61,6 +61,16 @@ pub struct OnChainLawVersionLabel {
     pub title_en: Option<Vec<u8>>,
 }
 
+/// 链上 `SunsetSchedule[大端到期时间戳][(law_id, version, SunsetKind)]` 单条排程,由存储 key 解出。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnChainSunsetEntry {
+    pub law_id: u64,
+    pub version: u32,
+    /// SunsetKind 单字节枚举(0 失效 / 1 复审)。
+    pub kind: u8,
+    pub at: u64,
+}
+
 /// 链上 `ConstitutionImmutableManifest` 解码镜像。
 #[derive(Debug, Decode)]
 struct OnChainImmutableManifest {
@@ -85,6 +95,41 @@ pub fn decode_law_version_label(bytes: &[u8]) -> Result<OnChainLawVersionLabel,
         .map_err(|e| format!("decode LawVersionLabel failed: {e}"))
 }
 
+/// 从 `SunsetSchedule` 存储 key 解出排程条目。
+///
+/// 固定前缀 32;第一键 Identity 为 8 字节大端时间戳;第二键 Blake2_128Concat 为
+/// 16 字节哈希 + `(u64, u32, SunsetKind)` 原始 SCALE 编码。
+pub fn decode_sunset_schedule_key(key_bytes: &[u8]) -> Result<OnChainSunsetEntry, String> {
+    let at_bytes: [u8; 8] = key_bytes
+        .get(32..40)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| "SunsetSchedule key is too short".to_string())?;
+    let mut input = key_bytes
+        .get(56..)
+        .ok_or_else(|| "SunsetSchedule key is too short".to_string())?;
+    let (law_id, version, kind) = <(u64, u32, u8)>::decode(&mut input)
+        .map_err(|e| format!("decode SunsetSchedule key failed: {e}"))?;
+    if !input.is_empty() {
+        return Err("SunsetSchedule key has trailing bytes".to_string());
+    }
+    Ok(OnChainSunsetEntry {
+        law_id,
+        version,
+        kind,
+        at: u64::from_be_bytes(at_bytes),
+    })
+}
+
+/// `LawVersion` 的头部镜像:只解 `law_id`/`version`/`title`,不解全文章节。
+#[derive(Decode)]
+struct OnChainLawVersionHead {
+    #[allow(dead_code)]
+    law_id: u64,
+    #[allow(dead_code)]
+    version: u32,
+    title: Vec<u8>,
+}
+
 /// 链上 `Law` + 办理端展示版本 → 展示用 `LawView`(字节→String、账户→0x hex、章节→可读)。
 pub fn build_law_view(
     law: &OnChainLaw,
@@ -182,9 +227,9 @@ pub(crate) fn resolve_institution_cid_number(
 ///
 /// ADR-018——整表扫描一次 + 客户端按已解码字段过滤(law_id/tier/scope_code 均在 value 内,
 /// 无需 storage key 反解)。真实运行态验收随本函数接入 handler(Phase 1B-5)时进行。
-async fn fetch_all_laws() -> Result<Vec<OnChainLaw>, String> {
+pub(crate) async fn fetch_all_laws() -> Result<Vec<OnChainLaw>, String> {
     let ws_url = chain_url::chain_ws_url()?;
-    let client = OnlineClient::<PolkadotConfig>::from_insecure_url(ws_url.as_str())
+    let client = crate::core::chain_proof::online_client(ws_url.as_str())
         .await
         .map_err(|e| format!("connect chain ws for laws failed: {e}"))?;
     let storage = client
@@ -205,12 +250,43 @@ async fn fetch_all_laws() -> Result<Vec<OnChainLaw>, String> {
     Ok(laws)
 }
 
-/// 读取链上全部 `LawVersion`(iterate + 镜像 decode)。
-async fn fetch_all_law_versions() -> Result<Vec<OnChainLawVersion>, String> {
+/// 读取宪法不可修改条款号清单。缺失时返回空,由展示层降级为不显示徽章。
+pub async fn fetch_immutable_article_numbers() -> Result<Vec<u32>, String> {
+    let ws_url = chain_url::chain_ws_url()?;
+    let client = crate::core::chain_proof::online_client(ws_url.as_str())
+        .await
+        .map_err(|e| format!("connect chain ws for immutable manifest failed: {e}"))?;
+    let storage = client
+        .storage()
+        .at_latest()
+        .await
+        .map_err(|e| format!("get latest chain storage failed: {e}"))?;
+    let address = dynamic::storage(
+        "LegislationYuan",
+        "ConstitutionImmutableManifest",
+        Vec::<dynamic::Value>::new(),
+    );
+    let Some(thunk) = storage
+        .fetch(&address)
+        .await
+        .map_err(|e| format!("fetch ConstitutionImmutableManifest failed: {e}"))?
+    else {
+        return Ok(Vec::new());
+    };
+    let mut raw = thunk.encoded();
+    let manifest = OnChainImmutableManifest::decode(&mut raw)
+        .map_err(|e| format!("decode ConstitutionImmutableManifest failed: {e}"))?;
+    Ok(manifest.article_numbers)
+}
+
+/// 按到期顺序读取最早的至多 `limit` 条日落排程(失效 / 复审到期点)。
+///
+/// 第一键为大端时间戳,存储 key 字典序即到期顺序;条目本身无值,只解 key。
+pub async fn fetch_sunset_schedule(limit: usize) -> Result<Vec<OnChainSunsetEntry>, String> {
     let ws_url = chain_url::chain_ws_url()?;
-    let client = OnlineClient::<PolkadotConfig>::from_insecure_url(ws_url.as_str())
+    let client = crate::core::chain_proof::online_client(ws_url.as_str())
         .await
-        .map_err(|e| format!("connect chain ws for law versions failed: {e}"))?;
+        .map_err(|e| format!("connect chain ws for sunset schedule failed: {e}"))?;
     let storage = client
         .storage()
         .at_latest()
@@ -218,27 +294,30 @@ async fn fetch_all_law_versions() -> Result<Vec<OnChainLawVersion>, String> {
         .map_err(|e| format!("get latest chain storage failed: {e}"))?;
     let query = dynamic::storage(
         "LegislationYuan",
-        "LawVersions",
+        "SunsetSchedule",
         Vec::<dynamic::Value>::new(),
     );
     let mut iter = storage
         .iter(query)
         .await
-        .map_err(|e| format!("iterate LawVersions failed: {e}"))?;
-    let mut versions = Vec::new();
-    while let Some(item) = iter.next().await {
-        let kv = item.map_err(|e| format!("read LawVersion failed: {e}"))?;
-        versions.push(decode_law_version(kv.value.encoded())?);
+        .map_err(|e| format!("iterate SunsetSchedule failed: {e}"))?;
+    let mut schedule = Vec::new();
+    while schedule.len() < limit {
+        let Some(item) = iter.next().await else {
+            break;
+        };
+        let kv = item.map_err(|e| format!("read SunsetSchedule failed: {e}"))?;
+        schedule.push(decode_sunset_schedule_key(&kv.key_bytes)?);
     }
-    Ok(versions)
+    Ok(schedule)
 }
 
-/// 读取宪法不可修改条款号清单。缺失时返回空,由展示层降级为不显示徽章。
-pub async fn fetch_immutable_article_numbers() -> Result<Vec<u32>, String> {
+/// 按 law_id 点查单部法律主体记录。
+pub async fn fetch_law(law_id: u64) -> Result<Option<OnChainLaw>, String> {
     let ws_url = chain_url::chain_ws_url()?;
-    let client = OnlineClient::<PolkadotConfig>::from_insecure_url(ws_url.as_str())
+    let client = crate::core::chain_proof::online_client(ws_url.as_str())
         .await
-        .map_err(|e| format!("connect chain ws for immutable manifest failed: {e}"))?;
+        .map_err(|e| format!("connect chain ws for law failed: {e}"))?;
     let storage = client
         .storage()
         .at_latest()
@@ -246,28 +325,48 @@ pub async fn fetch_immutable_article_numbers() -> Result<Vec<u32>, String> {
         .map_err(|e| format!("get latest chain storage failed: {e}"))?;
     let address = dynamic::storage(
         "LegislationYuan",
-        "ConstitutionImmutableManifest",
-        Vec::<dynamic::Value>::new(),
+        "Laws",
+        vec![dynamic::Value::u128(law_id as u128)],
     );
     let Some(thunk) = storage
         .fetch(&address)
         .await
-        .map_err(|e| format!("fetch ConstitutionImmutableManifest failed: {e}"))?
+        .map_err(|e| format!("fetch Laws failed: {e}"))?
     else {
-        return Ok(Vec::new());
+        return Ok(None);
     };
-    let mut raw = thunk.encoded();
-    let manifest = OnChainImmutableManifest::decode(&mut raw)
-        .map_err(|e| format!("decode ConstitutionImmutableManifest failed: {e}"))?;
-    Ok(manifest.article_numbers)
+    Ok(Some(decode_law(thunk.encoded())?))
 }
 
-/// 按 law_id 取单部法律主体记录。
-pub async fn fetch_law(law_id: u64) -> Result<Option<OnChainLaw>, String> {
-    Ok(fetch_all_laws()
-        .await?
-        .into_iter()
-        .find(|law| law.law_id == law_id))
+/// 按 (law_id, version) 点查法律版本标题(只解头部,不展开全文章节)。
+pub async fn fetch_law_version_title(law_id: u64, version: u32) -> Result<Option<String>, String> {
+    let ws_url = chain_url::chain_ws_url()?;
+    let client = crate::core::chain_proof::online_client(ws_url.as_str())
+        .await
+        .map_err(|e| format!("connect chain ws for law version title failed: {e}"))?;
+    let storage = client
+        .storage()
+        .at_latest()
+        .await
+        .map_err(|e| format!("get latest chain storage failed: {e}"))?;
+    let address = dynamic::storage(
+        "LegislationYuan",
+        "LawVersions",
+        vec![
+            dynamic::Value::u128(law_id as u128),
+            dynamic::Value::u128(version as u128),
+        ],
+    );
+    let Some(thunk) = storage
+        .fetch(&address)
+        .await
+        .map_err(|e| format!("fetch LawVersions failed: {e}"))?
+    else {
+        return Ok(None);
+    };
+    let head = OnChainLawVersionHead::decode(&mut thunk.encoded())
+        .map_err(|e| format!("decode LawVersion head failed: {e}"))?;
+    Ok(Some(String::from_utf8_lossy(&head.title).into_owned()))
 }
 
 /// 按层级 + 行政区码列出法律主体(scope 过滤在客户端按已解码字段,符合 ADR-018)。
@@ -284,10 +383,31 @@ pub async fn fetch_law_version(
     law_id: u64,
     version: u32,
 ) -> Result<Option<OnChainLawVersion>, String> {
-    Ok(fetch_all_law_versions()
-        .await?
-        .into_iter()
-        .find(|v| v.law_id == law_id && v.version == version))
+    let ws_url = chain_url::chain_ws_url()?;
+    let client = crate::core::chain_proof::online_client(ws_url.as_str())
+        .await
+        .map_err(|e| format!("connect chain ws for law version failed: {e}"))?;
+    let storage = client
+        .storage()
+        .at_latest()
+        .await
+        .map_err(|e| format!("get latest chain storage failed: {e}"))?;
+    let address = dynamic::storage(
+        "LegislationYuan",
+        "LawVersions",
+        vec![
+            dynamic::Value::u128(law_id as u128),
+            dynamic::Value::u128(version as u128),
+        ],
+    );
+    let Some(thunk) = storage
+        .fetch(&address)
+        .await
+        .map_err(|e| format!("fetch LawVersions failed: {e}"))?
+    else {
+        return Ok(None);
+    };
+    Ok(Some(decode_law_version(thunk.encoded())?))
 }
 
 /// 按 (law_id, version) 取单个法律版本标签。
@@ -296,7 +416,7 @@ pub async fn fetch_law_version_label(
     version: u32,
 ) -> Result<Option<OnChainLawVersionLabel>, String> {
     let ws_url = chain_url::chain_ws_url()?;
-    let client = OnlineClient::<PolkadotConfig>::from_insecure_url(ws_url.as_str())
+    let client = crate::core::chain_proof::online_client(ws_url.as_str())
         .await
         .map_err(|e| format!("connect chain ws for law version label failed: {e}"))?;
     let storage = client
@@ -329,7 +449,7 @@ mod tests {
     use super::super::chain_propose::{ArticleArg, ClauseArg, SectionArg};
     use super::*;
     use codec::Encode;
-    use legislation_yuan::{LawStatus, Tier, VoteType};
+    use legislation_yuan::{LawStatus, SunsetKind, Tier, VoteType};
 
     fn sample_chapters() -> Vec<ChapterArg> {
         vec![ChapterArg {
@@ -451,4 +571,27 @@ mod tests {
         assert_eq!(constitution_view.immutable_article_numbers, vec![1, 2]);
         assert!(constitution_view.version_title.is_none());
     }
+
+    /// 日落排程条目按链端双键布局从存储 key 解出;失效状态值 = 3。
+    #[test]
+    fn sunset_schedule_key_decodes_from_runtime_layout() {
+        let entry = (7u64, 2u32, SunsetKind::Review).encode();
+        let mut key = vec![0xAB; 32];
+        key.extend(5_000u64.to_be_bytes());
+        key.extend(sp_core::hashing::blake2_128(&entry));
+        key.extend(&entry);
+        assert_eq!(
+            decode_sunset_schedule_key(&key).expect("decode SunsetSchedule key"),
+            OnChainSunsetEntry {
+                law_id: 7,
+                version: 2,
+                kind: 1,
+                at: 5_000,
+            }
+        );
+        key.push(0);
+        assert!(decode_sunset_schedule_key(&key).is_err());
+        assert!(decode_sunset_schedule_key(&key[..40]).is_err());
+        assert_eq!(LawStatus::Expired.encode(), vec![3u8]);
+    }
 }
@@ -83,6 +83,12 @@ pub struct ProposeLawInput {
     pub chapters: Vec<LawChapter>,
     /// 生效时间戳(毫秒,立法/修法)。
     pub effective_at: u64,
+    /// 日落失效时间戳(毫秒,立法/修法;缺省不设)。
+    #[serde(default)]
+    pub sunset_expires_at: Option<u64>,
+    /// 日落复审时间戳(毫秒,立法/修法;缺省不设)。
+    #[serde(default)]
+    pub sunset_review_by: Option<u64>,
     /// 修法/废法目标法律 ID;立法(Enact)为 None。
     pub law_id: Option<u64>,
 }
@@ -162,7 +168,7 @@ pub struct LawView {
     /// 层级(0 宪法 / 1 国家 / 2 省 / 3 市)。
     pub tier: u8,
     pub scope_code: u32,
-    /// 法律状态(0 待生效 / 1 生效 / 2 废止)。
+    /// 法律状态(0 待生效 / 1 生效 / 2 废止 / 3 日落失效)。
     pub status: u8,
     /// 表决类型(0 常规 / 1 常规教育 / 2 重要 / 3 重要教育 / 4 特别)。
     pub vote_type: u8,
@@ -6,7 +6,7 @@
 //!
 
 use super::chain_propose::{
-    encode_propose_amend_law, encode_propose_enact_law, encode_propose_repeal_law,
+    encode_propose_amend_law, encode_propose_enact_law, encode_propose_repeal_law, SunsetArg,
 };
 use super::chain_vote::encode_cast_representative_vote;
 use super::model::{to_chapter_args, LawActionInput, ProposeLawInput};
@@ -138,6 +138,7 @@ pub fn build_propose_law_call(
                 input.title_en.as_deref().map(str::as_bytes),
                 &chapters,
                 input.effective_at,
+                &sunset_arg(input),
             ))
         }
         LawActionInput::Amend => {
@@ -155,6 +156,7 @@ pub fn build_propose_law_call(
                 input.title_en.as_deref().map(str::as_bytes),
                 &chapters,
                 input.effective_at,
+                &sunset_arg(input),
             ))
         }
         LawActionInput::Repeal => {
@@ -171,6 +173,14 @@ pub fn build_propose_law_call(
     }
 }
 
+/// 请求体日落字段 → 链编码器入参。
+fn sunset_arg(input: &ProposeLawInput) -> SunsetArg {
+    SunsetArg {
+        expires_at: input.sunset_expires_at,
+        review_by: input.sunset_review_by,
+    }
+}
+
 /// 立法/修法必须有非空标题与正文。
 fn ensure_title_and_chapters(input: &ProposeLawInput) -> Result<(), LegislationError> {
     if input.title.trim().is_empty() {
@@ -197,6 +207,7 @@ pub fn build_representative_vote_call(
 mod tests {
     use super::*;
     use crate::domains::legislation::law::model::{LawChapter, LawSection};
+    use codec::Encode;
 
     /// 夹具解析器:任意机构码 → 确定性机构 CID。
     fn fixture_resolver(code: &[u8; 4]) -> Option<String> {
@@ -227,6 +238,8 @@ mod tests {
                 }],
             }],
             effective_at: 1000,
+            sunset_expires_at: None,
+            sunset_review_by: None,
             law_id: None,
         }
     }
@@ -300,6 +313,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn enact_call_carries_requested_sunset_terms() {
+        let mut input = enact_input(1, 2);
+        input.sunset_expires_at = Some(90_000);
+        let call = build_propose_law_call(&input, *b"NRP\0", fixture_resolver).expect("build");
+        let tail = SunsetArg {
+            expires_at: Some(90_000),
+            review_by: None,
+        }
+        .encode();
+        assert!(call.call_data.ends_with(&tail));
+    }
+
     #[test]
     fn representative_vote_call_targets_legislation_vote_pallet() {
         let call = build_representative_vote_call(42, "REPRESENTATIVE", true);
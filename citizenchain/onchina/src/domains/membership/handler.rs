@@ -190,7 +190,9 @@ pub(crate) async fn propose_platform_price(
         Err(message) => return api_error(StatusCode::BAD_REQUEST, 1001, message.as_str()),
     };
     let prepared =
-        match crate::core::chain_submit::prepare_signing(&call, ctx.account_id.as_str()).await {
+        match crate::core::chain_submit::prepare_signing(&state.db, &call, ctx.account_id.as_str())
+            .await
+        {
             Ok(value) => value,
             Err(err) => {
                 tracing::error!(error = %err, "prepare platform price signing failed");
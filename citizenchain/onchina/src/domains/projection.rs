@@ -291,7 +291,7 @@ pub(crate) fn resolve_node_scope(db: &Db) -> Result<Option<(bool, NodeScope)>, S
     )))
 }
 
-fn chain_citizen_from_detail(d: OnChainCitizenDetail) -> ChainCitizen {
+pub(crate) fn chain_citizen_from_detail(d: OnChainCitizenDetail) -> ChainCitizen {
     let fmt_u32 = |v: Option<u32>| v.map(|n| n.to_string());
     let (family_name, given_name, citizen_sex, birth_date, bp, bc, bt) = match d.candidate {
         Some(c) => (
@@ -362,7 +362,7 @@ pub(crate) async fn project_citizen_by_cid_at(
 }
 
 /// 防止旧 finalized 视图覆盖较新绑定；同 revision 的账户不一致同样是链/投影冲突。
-fn ensure_binding_projection_monotonic(
+pub(crate) fn ensure_binding_projection_monotonic(
     chain: &ChainCitizen,
     existing: Option<&CitizenRecord>,
 ) -> Result<(), String> {
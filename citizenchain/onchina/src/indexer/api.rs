@@ -1,4 +1,4 @@
-//! Indexer API 路由：按规范账户 ID 查询交易记录。
+//! Indexer API 路由：按规范账户 ID 查询交易记录与链上账单。
 
 use axum::{
     extract::{Path, Query, State},
@@ -122,3 +122,200 @@ fn determine_direction(my_account_id: &str, from: Option<&str>, to: Option<&str>
         "info"
     }
 }
+
+#[derive(Deserialize)]
+pub(crate) struct InvoiceListQuery {
+    pub limit: Option<i64>,
+    pub before_id: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct InvoicePaymentOutput {
+    block_number: i64,
+    extrinsic_index: Option<i16>,
+    payer_account_id: String,
+    /// 链上 u128 原值(分)的十进制字符串，`*_yuan` 仅供展示。
+    amount_fen: String,
+    amount_yuan: f64,
+    block_timestamp: Option<String>,
+}
+
+#[derive(Serialize)]
+struct InvoiceOutput {
+    invoice_id: i64,
+    issuer_account_id: String,
+    payer_account_id: Option<String>,
+    /// 链上 u128 原值(分)的十进制字符串，`*_yuan` 仅供展示。
+    amount_fen: String,
+    paid_fen: String,
+    deposit_fen: String,
+    amount_yuan: f64,
+    paid_yuan: f64,
+    remaining_yuan: f64,
+    due_block: i64,
+    memo_hash: String,
+    /// 链上事件推进出的状态。
+    status: String,
+    /// 结合索引进度判定的状态:已过到期区块但链上尚无人标记过期时为 EXPIRED。
+    effective_status: &'static str,
+    created_block: i64,
+    closed_block: Option<i64>,
+    created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payments: Option<Vec<InvoicePaymentOutput>>,
+}
+
+#[derive(Serialize)]
+struct InvoiceListOutput {
+    invoices: Vec<InvoiceOutput>,
+    has_more: bool,
+    indexed_block: i64,
+}
+
+/// 账单对外状态。链上到期后 `pay_invoice` 即被拒绝,但 `expire_invoice` 需要有人提交,
+/// 因此未结清且已过到期区块的账单在这里直接视为过期。
+fn effective_invoice_status(status: &str, due_block: i64, indexed_block: i64) -> &'static str {
+    match status {
+        "PAID" => "PAID",
+        "CANCELLED" => "CANCELLED",
+        "EXPIRED" => "EXPIRED",
+        "DECLINED" => "DECLINED",
+        _ if indexed_block >= due_block => "EXPIRED",
+        "PARTIALLY_PAID" => "PARTIALLY_PAID",
+        _ => "OPEN",
+    }
+}
+
+fn invoice_output(
+    row: &db::InvoiceRow,
+    indexed_block: i64,
+    payments: Option<Vec<InvoicePaymentOutput>>,
+) -> InvoiceOutput {
+    InvoiceOutput {
+        invoice_id: row.invoice_id,
+        issuer_account_id: row.issuer_account_id.clone(),
+        payer_account_id: row.payer_account_id.clone(),
+        amount_fen: row.amount_fen.to_string(),
+        paid_fen: row.paid_fen.to_string(),
+        deposit_fen: row.deposit_fen.to_string(),
+        amount_yuan: row.amount_fen as f64 / 100.0,
+        paid_yuan: row.paid_fen as f64 / 100.0,
+        remaining_yuan: row.amount_fen.saturating_sub(row.paid_fen) as f64 / 100.0,
+        due_block: row.due_block,
+        memo_hash: row.memo_hash.clone(),
+        status: row.status.clone(),
+        effective_status: effective_invoice_status(&row.status, row.due_block, indexed_block),
+        created_block: row.created_block,
+        closed_block: row.closed_block,
+        created_at: row.created_at.map(|ts| ts.to_rfc3339()),
+        payments,
+    }
+}
+
+/// GET /api/app/invoices/:invoice_id
+pub(crate) async fn invoice_detail(
+    State(state): State<AppState>,
+    Path(invoice_id): Path<i64>,
+) -> impl IntoResponse {
+    let result = state.db.with_client(|conn| {
+        let indexed_block = db::read_last_indexed_block(conn)?;
+        Ok::<_, String>((indexed_block, db::query_invoice(conn, invoice_id)?))
+    });
+    let (indexed_block, found) = match result {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::warn!(error = %err, "query chain_invoice failed");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, 1500, "query failed");
+        }
+    };
+    let Some((row, payments)) = found else {
+        return api_error(StatusCode::NOT_FOUND, 1004, "invoice not found");
+    };
+    let payments = payments
+        .into_iter()
+        .map(|p| InvoicePaymentOutput {
+            block_number: p.block_number,
+            extrinsic_index: p.extrinsic_index,
+            payer_account_id: p.payer_account_id,
+            amount_fen: p.amount_fen.to_string(),
+            amount_yuan: p.amount_fen as f64 / 100.0,
+            block_timestamp: p.block_timestamp.map(|ts| ts.to_rfc3339()),
+        })
+        .collect();
+
+    Json(ApiResponse {
+        code: 0,
+        message: "ok".to_string(),
+        data: invoice_output(&row, indexed_block, Some(payments)),
+    })
+    .into_response()
+}
+
+/// GET /api/app/accounts/:account_id/invoices
+pub(crate) async fn account_invoices(
+    State(state): State<AppState>,
+    Path(account_id): Path<String>,
+    Query(query): Query<InvoiceListQuery>,
+) -> impl IntoResponse {
+    let Some(account_id) = crate::crypto::pubkey::normalize_account_id(&account_id) else {
+        return api_error(
+            StatusCode::BAD_REQUEST,
+            1001,
+            "account_id must be lowercase 0x plus 64 hexadecimal characters",
+        );
+    };
+
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let result = state.db.with_client(|conn| {
+        let indexed_block = db::read_last_indexed_block(conn)?;
+        let rows = db::query_account_invoices(conn, &account_id, query.before_id, limit + 1)?;
+        Ok::<_, String>((indexed_block, rows))
+    });
+    let (indexed_block, rows) = match result {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::warn!(error = %err, "query chain_invoices failed");
+            return api_error(StatusCode::INTERNAL_SERVER_ERROR, 1500, "query failed");
+        }
+    };
+
+    let has_more = rows.len() as i64 > limit;
+    let invoices = rows
+        .iter()
+        .take(limit as usize)
+        .map(|row| invoice_output(row, indexed_block, None))
+        .collect();
+
+    Json(ApiResponse {
+        code: 0,
+        message: "ok".to_string(),
+        data: InvoiceListOutput {
+            invoices,
+            has_more,
+            indexed_block,
+        },
+    })
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effective_invoice_status_expires_unsettled_invoices_past_due() {
+        assert_eq!(effective_invoice_status("OPEN", 10, 9), "OPEN");
+        assert_eq!(
+            effective_invoice_status("PARTIALLY_PAID", 10, 9),
+            "PARTIALLY_PAID"
+        );
+        assert_eq!(effective_invoice_status("OPEN", 10, 10), "EXPIRED");
+        assert_eq!(
+            effective_invoice_status("PARTIALLY_PAID", 10, 11),
+            "EXPIRED"
+        );
+        assert_eq!(effective_invoice_status("PAID", 10, 11), "PAID");
+        assert_eq!(effective_invoice_status("CANCELLED", 10, 11), "CANCELLED");
+        assert_eq!(effective_invoice_status("DECLINED", 10, 11), "DECLINED");
+    }
+}
@@ -11,11 +11,51 @@ pub(crate) struct TxRecordInsert {
     pub tx_type: &'static str,
     pub sender_account_id: Option<String>,
     pub recipient_account_id: Option<String>,
-    pub amount_fen: i64,
-    pub fee_fen: Option<i64>,
+    pub amount_fen: u128,
+    pub fee_fen: Option<u128>,
     pub block_timestamp: Option<DateTime<Utc>>,
 }
 
+/// 一条链上账单事件（`OnchainTransaction::Invoice*`）。金额保持链上 u128 原值，
+/// 以 `NUMERIC(39, 0)` 入库，不截断。
+pub(crate) struct InvoiceEvent {
+    pub invoice_id: i64,
+    pub extrinsic_index: Option<i16>,
+    pub event_index: i16,
+    pub kind: InvoiceEventKind,
+}
+
+/// 一条公民居住地跨市迁移(事件所在块 vs 父块的链上居住地)。
+pub(crate) struct ResidenceMove<'a> {
+    pub cid_number: &'a str,
+    pub block_number: i64,
+    pub block_hash: &'a str,
+    pub from_province_code: &'a str,
+    pub from_city_code: &'a str,
+    pub to_province_code: &'a str,
+    pub to_city_code: &'a str,
+}
+
+pub(crate) enum InvoiceEventKind {
+    Created {
+        issuer_account_id: String,
+        payer_account_id: Option<String>,
+        amount_fen: u128,
+        deposit_fen: u128,
+        due_block: i64,
+        memo_hash: String,
+    },
+    Paid {
+        payer_account_id: String,
+        amount_fen: u128,
+        paid_total_fen: u128,
+    },
+    Settled,
+    Cancelled,
+    Expired,
+    Declined,
+}
+
 /// 读取当前索引进度（last_indexed_block）。
 pub(crate) fn read_last_indexed_block(conn: &mut Client) -> Result<i64, String> {
     let row = conn
@@ -27,18 +67,20 @@ pub(crate) fn read_last_indexed_block(conn: &mut Client) -> Result<i64, String>
     Ok(row.get(0))
 }
 
-/// 在一个事务中批量写入一个区块的所有交易记录，并更新索引进度。
+/// 在一个事务中批量写入一个区块的所有交易记录和账单事件，并更新索引进度。
 pub(crate) fn insert_block_records(
     conn: &mut Client,
     block_number: i64,
+    block_timestamp: Option<DateTime<Utc>>,
     records: &[TxRecordInsert],
+    invoice_events: &[InvoiceEvent],
 ) -> Result<(), String> {
     let mut tx = conn.transaction().map_err(|e| format!("begin tx: {e}"))?;
 
     for r in records {
         tx.execute(
             "INSERT INTO tx_records(block_number, extrinsic_index, event_index, tx_type, sender_account_id, recipient_account_id, amount_fen, fee_fen, block_timestamp)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+             VALUES ($1, $2, $3, $4, $5, $6, $7::TEXT::NUMERIC, $8::TEXT::NUMERIC, $9)",
             &[
                 &r.block_number,
                 &r.extrinsic_index,
@@ -46,14 +88,18 @@ pub(crate) fn insert_block_records(
                 &r.tx_type,
                 &r.sender_account_id,
                 &r.recipient_account_id,
-                &r.amount_fen,
-                &r.fee_fen,
+                &r.amount_fen.to_string(),
+                &r.fee_fen.map(|fee| fee.to_string()),
                 &r.block_timestamp,
             ],
         )
         .map_err(|e| format!("insert tx_record: {e}"))?;
     }
 
+    for ev in invoice_events {
+        apply_invoice_event(&mut tx, block_number, block_timestamp, ev)?;
+    }
+
     tx.execute(
         "UPDATE tx_indexer_state SET last_indexed_block=$1, updated_at=now() WHERE id=1",
         &[&block_number],
@@ -64,6 +110,114 @@ pub(crate) fn insert_block_records(
     Ok(())
 }
 
+/// 把一条账单事件投影到 `chain_invoices` / `chain_invoice_payments`。
+///
+/// 状态只向前推进:终态(PAID/CANCELLED/EXPIRED/DECLINED)行不会被后续事件改回。
+fn apply_invoice_event(
+    tx: &mut postgres::Transaction<'_>,
+    block_number: i64,
+    block_timestamp: Option<DateTime<Utc>>,
+    ev: &InvoiceEvent,
+) -> Result<(), String> {
+    let closed_status = match &ev.kind {
+        InvoiceEventKind::Created {
+            issuer_account_id,
+            payer_account_id,
+            amount_fen,
+            deposit_fen,
+            due_block,
+            memo_hash,
+        } => {
+            tx.execute(
+                "INSERT INTO chain_invoices(invoice_id, issuer_account_id, payer_account_id, amount_fen,
+                    deposit_fen, due_block, memo_hash, status, created_block, created_at)
+                 VALUES ($1, $2, $3, $4::TEXT::NUMERIC, $5::TEXT::NUMERIC, $6, $7, 'OPEN', $8, $9)
+                 ON CONFLICT (invoice_id) DO NOTHING",
+                &[
+                    &ev.invoice_id,
+                    issuer_account_id,
+                    payer_account_id,
+                    &amount_fen.to_string(),
+                    &deposit_fen.to_string(),
+                    due_block,
+                    memo_hash,
+                    &block_number,
+                    &block_timestamp,
+                ],
+            )
+            .map_err(|e| format!("insert chain_invoice: {e}"))?;
+            return Ok(());
+        }
+        InvoiceEventKind::Paid {
+            payer_account_id,
+            amount_fen,
+            paid_total_fen,
+        } => {
+            tx.execute(
+                "INSERT INTO chain_invoice_payments(invoice_id, block_number, extrinsic_index,
+                    event_index, payer_account_id, amount_fen, block_timestamp)
+                 VALUES ($1, $2, $3, $4, $5, $6::TEXT::NUMERIC, $7)
+                 ON CONFLICT (block_number, event_index) DO NOTHING",
+                &[
+                    &ev.invoice_id,
+                    &block_number,
+                    &ev.extrinsic_index,
+                    &ev.event_index,
+                    payer_account_id,
+                    &amount_fen.to_string(),
+                    &block_timestamp,
+                ],
+            )
+            .map_err(|e| format!("insert chain_invoice_payment: {e}"))?;
+            tx.execute(
+                "UPDATE chain_invoices
+                 SET paid_fen=$2::TEXT::NUMERIC, status='PARTIALLY_PAID', updated_at=now()
+                 WHERE invoice_id=$1 AND status IN ('OPEN', 'PARTIALLY_PAID')",
+                &[&ev.invoice_id, &paid_total_fen.to_string()],
+            )
+            .map_err(|e| format!("update chain_invoice paid: {e}"))?;
+            return Ok(());
+        }
+        InvoiceEventKind::Settled => "PAID",
+        InvoiceEventKind::Cancelled => "CANCELLED",
+        InvoiceEventKind::Expired => "EXPIRED",
+        InvoiceEventKind::Declined => "DECLINED",
+    };
+    tx.execute(
+        "UPDATE chain_invoices
+         SET status=$2, closed_block=$3, updated_at=now()
+         WHERE invoice_id=$1 AND status IN ('OPEN', 'PARTIALLY_PAID')",
+        &[&ev.invoice_id, &closed_status, &block_number],
+    )
+    .map_err(|e| format!("close chain_invoice: {e}"))?;
+    Ok(())
+}
+
+/// 写入一条居住地迁移记录;同一 CID 同一区块重复索引时保持首次写入。
+pub(crate) fn insert_residence_move(
+    conn: &mut Client,
+    mv: ResidenceMove<'_>,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO citizen_residence_moves (
+            cid_number, block_number, block_hash, from_province_code, from_city_code,
+            to_province_code, to_city_code
+         ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+         ON CONFLICT (cid_number, block_number) DO NOTHING",
+        &[
+            &mv.cid_number,
+            &mv.block_number,
+            &mv.block_hash,
+            &mv.from_province_code,
+            &mv.from_city_code,
+            &mv.to_province_code,
+            &mv.to_city_code,
+        ],
+    )
+    .map_err(|e| format!("insert citizen residence move: {e}"))?;
+    Ok(())
+}
+
 /// 查询某账户的交易记录（游标分页）。
 pub(crate) fn query_tx_records(
     conn: &mut Client,
@@ -75,7 +229,7 @@ pub(crate) fn query_tx_records(
     // 动态构建 SQL 以支持可选的 tx_type 筛选
     let mut sql = String::from(
         "SELECT id, block_number, extrinsic_index, event_index, tx_type, \
-         sender_account_id, recipient_account_id, amount_fen, fee_fen, block_timestamp \
+         sender_account_id, recipient_account_id, amount_fen::TEXT, fee_fen::TEXT, block_timestamp \
          FROM tx_records WHERE (sender_account_id=$1 OR recipient_account_id=$1)",
     );
     let mut param_idx = 2u32;
@@ -124,21 +278,23 @@ pub(crate) fn query_tx_records(
         .query(&sql, &param_refs)
         .map_err(|e| format!("query tx_records: {e}"))?;
 
-    Ok(rows
-        .iter()
-        .map(|row| TxRecordRow {
-            id: row.get(0),
-            block_number: row.get(1),
-            extrinsic_index: row.get(2),
-            event_index: row.get(3),
-            tx_type: row.get(4),
-            sender_account_id: row.get(5),
-            recipient_account_id: row.get(6),
-            amount_fen: row.get(7),
-            fee_fen: row.get(8),
-            block_timestamp: row.get(9),
+    rows.iter()
+        .map(|row| {
+            let fee_fen: Option<String> = row.get(8);
+            Ok(TxRecordRow {
+                id: row.get(0),
+                block_number: row.get(1),
+                extrinsic_index: row.get(2),
+                event_index: row.get(3),
+                tx_type: row.get(4),
+                sender_account_id: row.get(5),
+                recipient_account_id: row.get(6),
+                amount_fen: numeric_fen(row, 7)?,
+                fee_fen: fee_fen.map(|fee| parse_fen(&fee)).transpose()?,
+                block_timestamp: row.get(9),
+            })
         })
-        .collect())
+        .collect()
 }
 
 // DB SELECT 列序投影:字段按 row.get(索引) 位置读入,保留以对齐查询列顺序。
@@ -151,7 +307,119 @@ pub(crate) struct TxRecordRow {
     pub tx_type: String,
     pub sender_account_id: Option<String>,
     pub recipient_account_id: Option<String>,
-    pub amount_fen: i64,
-    pub fee_fen: Option<i64>,
+    pub amount_fen: u128,
+    pub fee_fen: Option<u128>,
+    pub block_timestamp: Option<DateTime<Utc>>,
+}
+
+/// 链上账单投影行。
+pub(crate) struct InvoiceRow {
+    pub invoice_id: i64,
+    pub issuer_account_id: String,
+    pub payer_account_id: Option<String>,
+    pub amount_fen: u128,
+    pub paid_fen: u128,
+    pub deposit_fen: u128,
+    pub due_block: i64,
+    pub memo_hash: String,
+    pub status: String,
+    pub created_block: i64,
+    pub closed_block: Option<i64>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+pub(crate) struct InvoicePaymentRow {
+    pub block_number: i64,
+    pub extrinsic_index: Option<i16>,
+    pub payer_account_id: String,
+    pub amount_fen: u128,
     pub block_timestamp: Option<DateTime<Utc>>,
 }
+
+const INVOICE_COLUMNS: &str = "invoice_id, issuer_account_id, payer_account_id, \
+     amount_fen::TEXT, paid_fen::TEXT, deposit_fen::TEXT, due_block, memo_hash, status, \
+     created_block, closed_block, created_at";
+
+/// 读取以 `::TEXT` 取出的 NUMERIC 金额列。
+fn numeric_fen(row: &postgres::Row, idx: usize) -> Result<u128, String> {
+    parse_fen(&row.get::<_, String>(idx))
+}
+
+fn parse_fen(text: &str) -> Result<u128, String> {
+    text.parse()
+        .map_err(|e| format!("invalid NUMERIC amount {text:?}: {e}"))
+}
+
+fn invoice_row(row: &postgres::Row) -> Result<InvoiceRow, String> {
+    Ok(InvoiceRow {
+        invoice_id: row.get(0),
+        issuer_account_id: row.get(1),
+        payer_account_id: row.get(2),
+        amount_fen: numeric_fen(row, 3)?,
+        paid_fen: numeric_fen(row, 4)?,
+        deposit_fen: numeric_fen(row, 5)?,
+        due_block: row.get(6),
+        memo_hash: row.get(7),
+        status: row.get(8),
+        created_block: row.get(9),
+        closed_block: row.get(10),
+        created_at: row.get(11),
+    })
+}
+
+/// 按编号读取一张账单及其全部付款(按上链顺序)。
+pub(crate) fn query_invoice(
+    conn: &mut Client,
+    invoice_id: i64,
+) -> Result<Option<(InvoiceRow, Vec<InvoicePaymentRow>)>, String> {
+    let Some(row) = conn
+        .query_opt(
+            &format!("SELECT {INVOICE_COLUMNS} FROM chain_invoices WHERE invoice_id=$1"),
+            &[&invoice_id],
+        )
+        .map_err(|e| format!("query chain_invoice: {e}"))?
+    else {
+        return Ok(None);
+    };
+    let payments = conn
+        .query(
+            "SELECT block_number, extrinsic_index, payer_account_id, amount_fen::TEXT, block_timestamp
+             FROM chain_invoice_payments WHERE invoice_id=$1
+             ORDER BY block_number, event_index",
+            &[&invoice_id],
+        )
+        .map_err(|e| format!("query chain_invoice_payments: {e}"))?
+        .iter()
+        .map(|row| {
+            Ok(InvoicePaymentRow {
+                block_number: row.get(0),
+                extrinsic_index: row.get(1),
+                payer_account_id: row.get(2),
+                amount_fen: numeric_fen(row, 3)?,
+                block_timestamp: row.get(4),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(Some((invoice_row(&row)?, payments)))
+}
+
+/// 查询某账户开出或被指定付款的账单(游标分页,新账单在前)。
+pub(crate) fn query_account_invoices(
+    conn: &mut Client,
+    account_id: &str,
+    before_id: Option<i64>,
+    limit: i64,
+) -> Result<Vec<InvoiceRow>, String> {
+    let rows = conn
+        .query(
+            &format!(
+                "SELECT {INVOICE_COLUMNS} FROM chain_invoices
+                 WHERE (issuer_account_id=$1 OR payer_account_id=$1)
+                   AND ($2::BIGINT IS NULL OR invoice_id < $2)
+                 ORDER BY invoice_id DESC LIMIT $3"
+            ),
+            &[&account_id, &before_id, &limit],
+        )
+        .map_err(|e| format!("query chain_invoices: {e}"))?;
+    rows.iter().map(invoice_row).collect()
+}
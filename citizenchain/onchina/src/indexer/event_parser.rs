@@ -1,7 +1,7 @@
 //! 链上事件解析器。
 //!
 //! 使用 subxt 动态 API 解码区块事件，匹配所有余额变动事件，
-//! 转换为 `TxRecordInsert` 写入数据库。
+//! 转换为 `TxRecordInsert` 写入数据库；链上账单事件另行转换为 `InvoiceEvent`。
 
 use chrono::{DateTime, TimeZone, Utc};
 use subxt::events::{EventDetails, Phase};
@@ -9,7 +9,7 @@ use subxt::ext::scale_value::{At, Composite, Value};
 use subxt::PolkadotConfig;
 use tracing::warn;
 
-use super::db::TxRecordInsert;
+use super::db::{InvoiceEvent, InvoiceEventKind, TxRecordInsert};
 
 /// 将链上 32 字节 AccountId 规范化为全仓唯一账户标识。
 fn account_id_text(bytes: &[u8; 32]) -> String {
@@ -42,6 +42,23 @@ fn extract_bytes_from_composite<T>(composite: &Composite<T>) -> Option<[u8; 32]>
     Some(bytes)
 }
 
+/// 从 `Option<AccountId>` 字段提取账户:外层 `None` 表示字段无法解码,`Some(None)` 表示链上即为空。
+fn extract_optional_account_id<T>(val: &Value<T>) -> Option<Option<[u8; 32]>> {
+    match &val.value {
+        subxt::ext::scale_value::ValueDef::Variant(variant) => match variant.name.as_str() {
+            "None" => Some(None),
+            "Some" => variant
+                .values
+                .values()
+                .next()
+                .and_then(extract_account_id)
+                .map(Some),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// 从 subxt Value 提取 u128 金额。
 fn extract_balance<T>(val: &Value<T>) -> Option<u128> {
     val.as_u128()
@@ -92,9 +109,73 @@ pub(crate) fn collect_entity_projection_cids(
     (citizen_cids, institution_cids)
 }
 
-/// 将 u128 余额（分）转为 i64。超过 i64::MAX 截断（实际不会发生）。
-fn balance_to_i64(amount: u128) -> i64 {
-    amount.min(i64::MAX as u128) as i64
+/// 一条由 `on_idle` 任期届满清理移出的岗位任职(公权/私权机构同构)。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ExpiredRoleAssignment {
+    pub(crate) cid_number: String,
+    /// 岗位任职使用的名册锚点账户,不一定是管理员当前签名账户。
+    pub(crate) account_id: [u8; 32],
+}
+
+/// 扫描一个区块的 `InstitutionRoleAssignmentExpired` 事件,供清退按旧岗位签发的会话。
+pub(crate) fn collect_expired_role_assignments(
+    events: &subxt::events::Events<PolkadotConfig>,
+) -> Vec<ExpiredRoleAssignment> {
+    let mut expired = Vec::new();
+    for event in events.iter().flatten() {
+        if !matches!(event.pallet_name(), "PublicManage" | "PrivateManage")
+            || event.variant_name() != "InstitutionRoleAssignmentExpired"
+        {
+            continue;
+        }
+        let Ok(fields) = event.field_values() else {
+            continue;
+        };
+        let cid_number = fields.at("cid_number").and_then(extract_cid_number);
+        let account_id = fields.at("account_id").and_then(extract_account_id);
+        match (cid_number, account_id) {
+            (Some(cid_number), Some(account_id)) => expired.push(ExpiredRoleAssignment {
+                cid_number,
+                account_id,
+            }),
+            _ => warn!("InstitutionRoleAssignmentExpired fields undecodable, skipping"),
+        }
+    }
+    expired.sort_by(|a, b| (&a.cid_number, a.account_id).cmp(&(&b.cid_number, b.account_id)));
+    expired.dedup();
+    expired
+}
+
+/// 扫描一个区块的事件,收集可能改动居住地的公民 CID(`update_voting_identity` 及竞选身份升级/更新)。
+///
+/// 事件本身不带新旧居住地,由 indexer 比对事件所在块与父块的链上状态得出迁移记录。
+pub(crate) fn collect_residence_update_cids(
+    events: &subxt::events::Events<PolkadotConfig>,
+) -> Vec<String> {
+    let mut cids = Vec::new();
+    for event in events.iter().flatten() {
+        if event.pallet_name() != "CitizenIdentity"
+            || !matches!(
+                event.variant_name(),
+                "VotingIdentityUpdated" | "CandidateIdentityUpgraded" | "CandidateIdentityUpdated"
+            )
+        {
+            continue;
+        }
+        if let Ok(fields) = event.field_values() {
+            if let Some(cid) = fields.at("cid_number").and_then(extract_cid_number) {
+                cids.push(cid);
+            }
+        }
+    }
+    cids.sort();
+    cids.dedup();
+    cids
+}
+
+/// 把 `Timestamp::set` 的毫秒值转为 UTC 时间。
+pub(crate) fn block_timestamp(block_timestamp_ms: Option<u64>) -> Option<DateTime<Utc>> {
+    block_timestamp_ms.and_then(|ms| Utc.timestamp_millis_opt(ms as i64).single())
 }
 
 /// 解析一个区块的所有事件，返回需要写入的交易记录。
@@ -103,7 +184,7 @@ pub(crate) fn parse_block_events(
     block_number: i64,
     block_timestamp_ms: Option<u64>,
 ) -> Vec<TxRecordInsert> {
-    let block_ts = block_timestamp_ms.and_then(|ms| Utc.timestamp_millis_opt(ms as i64).single());
+    let block_ts = block_timestamp(block_timestamp_ms);
 
     let mut records = Vec::new();
 
@@ -137,6 +218,95 @@ pub(crate) fn parse_block_events(
     records
 }
 
+/// 解析一个区块内 `OnchainTransaction` 的账单事件，按事件顺序返回。
+///
+/// 账单付款对应的余额变动已由 `Balances::Transfer` 写入 `tx_records`，
+/// 这里只负责把付款与账单编号绑定，并推进账单状态。
+pub(crate) fn parse_invoice_events(
+    events: &subxt::events::Events<PolkadotConfig>,
+    block_number: i64,
+) -> Vec<InvoiceEvent> {
+    let mut out = Vec::new();
+    for (event_index, event_result) in events.iter().enumerate() {
+        let Ok(event) = event_result else {
+            continue;
+        };
+        if event.pallet_name() != "OnchainTransaction" {
+            continue;
+        }
+        let extrinsic_index = match event.phase() {
+            Phase::ApplyExtrinsic(i) => Some(i as i16),
+            _ => None,
+        };
+        let decoded = event.field_values().ok().and_then(|fields| {
+            let invoice_id = fields.at("invoice_id").and_then(|v| v.as_u128())?;
+            Some((
+                invoice_id,
+                match_invoice_event(event.variant_name(), &fields)?,
+            ))
+        });
+        let Some((invoice_id, kind)) = decoded else {
+            if event.variant_name().starts_with("Invoice") {
+                warn!(
+                    block = block_number,
+                    event_index,
+                    variant = event.variant_name(),
+                    "failed to decode invoice event, skipping"
+                );
+            }
+            continue;
+        };
+        out.push(InvoiceEvent {
+            invoice_id: invoice_id.min(i64::MAX as u128) as i64,
+            extrinsic_index,
+            event_index: event_index as i16,
+            kind,
+        });
+    }
+    out
+}
+
+fn match_invoice_event(variant: &str, fields: &Composite<u32>) -> Option<InvoiceEventKind> {
+    match variant {
+        "InvoiceCreated" => {
+            let issuer = fields.at("issuer").and_then(extract_account_id)?;
+            let payer = fields.at("payer").and_then(extract_optional_account_id)?;
+            let amount = fields.at("amount").and_then(extract_balance)?;
+            let deposit = fields.at("deposit").and_then(extract_balance)?;
+            let due_block = fields.at("due_block").and_then(|v| v.as_u128())?;
+            let memo_hash = fields.at("memo_hash").and_then(|v| match &v.value {
+                subxt::ext::scale_value::ValueDef::Composite(composite) => {
+                    extract_bytes_from_composite(composite)
+                }
+                _ => None,
+            })?;
+            Some(InvoiceEventKind::Created {
+                issuer_account_id: account_id_text(&issuer),
+                payer_account_id: payer.as_ref().map(account_id_text),
+                amount_fen: amount,
+                deposit_fen: deposit,
+                due_block: due_block.min(i64::MAX as u128) as i64,
+                memo_hash: format!("0x{}", hex::encode(memo_hash)),
+            })
+        }
+        "InvoicePaid" => {
+            let payer = fields.at("payer_account_id").and_then(extract_account_id)?;
+            let amount = fields.at("amount").and_then(extract_balance)?;
+            let paid_total = fields.at("paid_total").and_then(extract_balance)?;
+            Some(InvoiceEventKind::Paid {
+                payer_account_id: account_id_text(&payer),
+                amount_fen: amount,
+                paid_total_fen: paid_total,
+            })
+        }
+        "InvoiceSettled" => Some(InvoiceEventKind::Settled),
+        "InvoiceCancelled" => Some(InvoiceEventKind::Cancelled),
+        "InvoiceExpired" => Some(InvoiceEventKind::Expired),
+        "InvoiceDeclined" => Some(InvoiceEventKind::Declined),
+        _ => None,
+    }
+}
+
 /// 匹配单个事件，返回 Some(TxRecordInsert) 如果是余额变动事件。
 fn match_event(
     pallet: &str,
@@ -161,7 +331,7 @@ fn match_event(
                 tx_type: "transfer",
                 sender_account_id: Some(account_id_text(&from)),
                 recipient_account_id: Some(account_id_text(&to)),
-                amount_fen: balance_to_i64(amount),
+                amount_fen: amount,
                 fee_fen: None,
                 block_timestamp: block_ts,
             })
@@ -176,7 +346,7 @@ fn match_event(
                 tx_type: "fee_withdraw",
                 sender_account_id: Some(account_id_text(&who)),
                 recipient_account_id: None,
-                amount_fen: balance_to_i64(amount),
+                amount_fen: amount,
                 fee_fen: None,
                 block_timestamp: block_ts,
             })
@@ -191,7 +361,7 @@ fn match_event(
                 tx_type: "fee_deposit",
                 sender_account_id: None,
                 recipient_account_id: Some(account_id_text(&who)),
-                amount_fen: balance_to_i64(amount),
+                amount_fen: amount,
                 fee_fen: None,
                 block_timestamp: block_ts,
             })
@@ -208,7 +378,7 @@ fn match_event(
                 tx_type: "block_reward",
                 sender_account_id: None,
                 recipient_account_id: Some(account_id_text(&wallet)),
-                amount_fen: balance_to_i64(amount),
+                amount_fen: amount,
                 fee_fen: None,
                 block_timestamp: block_ts,
             })
@@ -225,7 +395,7 @@ fn match_event(
                 tx_type: "bank_interest",
                 sender_account_id: None,
                 recipient_account_id: Some(account_id_text(&account)),
-                amount_fen: balance_to_i64(amount),
+                amount_fen: amount,
                 fee_fen: None,
                 block_timestamp: block_ts,
             })
@@ -241,7 +411,7 @@ fn match_event(
                 tx_type: "gov_issuance",
                 sender_account_id: None,
                 recipient_account_id: None,
-                amount_fen: balance_to_i64(total),
+                amount_fen: total,
                 fee_fen: None,
                 block_timestamp: block_ts,
             })
@@ -258,7 +428,7 @@ fn match_event(
                 tx_type: "lightnode_reward",
                 sender_account_id: None,
                 recipient_account_id: Some(account_id_text(&who)),
-                amount_fen: balance_to_i64(reward),
+                amount_fen: reward,
                 fee_fen: None,
                 block_timestamp: block_ts,
             })
@@ -276,8 +446,8 @@ fn match_event(
                 tx_type: "proposal_transfer",
                 sender_account_id: None,
                 recipient_account_id: Some(account_id_text(&beneficiary)),
-                amount_fen: balance_to_i64(amount),
-                fee_fen: fee.map(balance_to_i64),
+                amount_fen: amount,
+                fee_fen: fee,
                 block_timestamp: block_ts,
             })
         }
@@ -297,8 +467,8 @@ fn match_event(
                 tx_type: "institution_multisig_create",
                 sender_account_id: Some(account_id_text(&creator)),
                 recipient_account_id: Some(account_id_text(&multisig)),
-                amount_fen: balance_to_i64(amount),
-                fee_fen: fee.map(balance_to_i64),
+                amount_fen: amount,
+                fee_fen: fee,
                 block_timestamp: block_ts,
             })
         }
@@ -314,8 +484,8 @@ fn match_event(
                 tx_type: "institution_multisig_close",
                 sender_account_id: Some(account_id_text(&multisig)),
                 recipient_account_id: Some(account_id_text(&beneficiary)),
-                amount_fen: balance_to_i64(amount),
-                fee_fen: fee.map(balance_to_i64),
+                amount_fen: amount,
+                fee_fen: fee,
                 block_timestamp: block_ts,
             })
         }
@@ -330,7 +500,7 @@ fn match_event(
                 tx_type: "fund_destroy",
                 sender_account_id: None,
                 recipient_account_id: None,
-                amount_fen: balance_to_i64(amount),
+                amount_fen: amount,
                 fee_fen: None,
                 block_timestamp: block_ts,
             })
@@ -3,7 +3,6 @@
 use std::time::Duration;
 
 use subxt::backend::legacy::LegacyRpcMethods;
-use subxt::backend::rpc::RpcClient;
 use subxt::ext::scale_value::At;
 use subxt::{OnlineClient, PolkadotConfig};
 use tracing::{error, info, warn};
@@ -56,7 +55,7 @@ pub(crate) async fn indexer_worker(db_pool: Db) {
 
 /// 主索引循环：连接链，追赶历史区块，然后订阅新区块。
 async fn run_indexer_loop(ws_url: &str, db_pool: &Db) -> Result<(), String> {
-    let rpc_client = RpcClient::from_insecure_url(ws_url)
+    let rpc_client = crate::core::chain_proof::connect_rpc(ws_url)
         .await
         .map_err(|e| format!("connect rpc: {e}"))?;
     let rpc = LegacyRpcMethods::<PolkadotConfig>::new(rpc_client.clone());
@@ -172,10 +171,28 @@ async fn run_indexer_loop(ws_url: &str, db_pool: &Db) -> Result<(), String> {
             .map_err(|e| format!("fetch events #{block_num}: {e}"))?;
         let block_ts = extract_block_timestamp_from_block(&block).await;
         let records = event_parser::parse_block_events(&events, block_num, block_ts);
-        db_pool.with_client(|conn| db::insert_block_records(conn, block_num, &records))?;
+        let invoice_events = event_parser::parse_invoice_events(&events, block_num);
+        db_pool.with_client(|conn| {
+            db::insert_block_records(
+                conn,
+                block_num,
+                event_parser::block_timestamp(block_ts),
+                &records,
+                &invoice_events,
+            )
+        })?;
         if let Some(scope) = projection_scope.as_ref() {
-            project_block_entities(db_pool, &events, block.hash(), scope).await;
+            project_block_entities(
+                db_pool,
+                &events,
+                block_num,
+                block.hash(),
+                block.header().parent_hash,
+                scope,
+            )
+            .await;
         }
+        revoke_expired_assignment_sessions(db_pool, &events).await;
     }
 }
 
@@ -200,11 +217,29 @@ async fn process_block_at_hash(
 
     let block_ts = extract_block_timestamp_from_block(&block).await;
     let records = event_parser::parse_block_events(&events, block_number, block_ts);
-    db_pool.with_client(|conn| db::insert_block_records(conn, block_number, &records))?;
+    let invoice_events = event_parser::parse_invoice_events(&events, block_number);
+    db_pool.with_client(|conn| {
+        db::insert_block_records(
+            conn,
+            block_number,
+            event_parser::block_timestamp(block_ts),
+            &records,
+            &invoice_events,
+        )
+    })?;
 
     if let Some(scope) = projection_scope {
-        project_block_entities(db_pool, &events, block_hash, scope).await;
+        project_block_entities(
+            db_pool,
+            &events,
+            block_number,
+            block_hash,
+            block.header().parent_hash,
+            scope,
+        )
+        .await;
     }
+    revoke_expired_assignment_sessions(db_pool, &events).await;
 
     Ok(())
 }
@@ -214,9 +249,18 @@ async fn process_block_at_hash(
 async fn project_block_entities(
     db_pool: &Db,
     events: &subxt::events::Events<PolkadotConfig>,
+    block_number: i64,
     block_hash: subxt::utils::H256,
+    parent_hash: subxt::utils::H256,
     scope: &crate::domains::projection::NodeScope,
 ) {
+    for cid in event_parser::collect_residence_update_cids(events) {
+        if let Err(err) =
+            record_residence_move(db_pool, &cid, block_number, block_hash, parent_hash, scope).await
+        {
+            warn!(cid = %cid, error = %err, "indexer residence move record failed");
+        }
+    }
     let (citizen_cids, institution_cids) = event_parser::collect_entity_projection_cids(events);
     for cid in citizen_cids {
         if let Err(err) = crate::domains::projection::project_citizen_by_cid_at(
@@ -240,6 +284,71 @@ async fn project_block_entities(
     }
 }
 
+/// 任期届满被移出岗位的管理员即时清退会话;失败仅告警,后台周期复查兜底。
+async fn revoke_expired_assignment_sessions(
+    db_pool: &Db,
+    events: &subxt::events::Events<PolkadotConfig>,
+) {
+    for expired in event_parser::collect_expired_role_assignments(events) {
+        if let Err(err) = crate::auth::login::revoke_expired_assignment_sessions(
+            db_pool,
+            &expired.cid_number,
+            expired.account_id,
+        )
+        .await
+        {
+            warn!(
+                cid = %expired.cid_number,
+                error = %err,
+                "indexer expired assignment session revocation failed"
+            );
+        }
+    }
+}
+
+/// 比对事件所在块与父块的链上居住地;迁入本市时落一条迁移记录,供档案移交核对原注册局。
+async fn record_residence_move(
+    db_pool: &Db,
+    cid_number: &str,
+    block_number: i64,
+    block_hash: subxt::utils::H256,
+    parent_hash: subxt::utils::H256,
+    scope: &crate::domains::projection::NodeScope,
+) -> Result<(), String> {
+    use crate::core::chain_runtime::read_chain_citizen_detail_at;
+    use crate::domains::projection::chain_citizen_from_detail;
+
+    let Some(after) = read_chain_citizen_detail_at(cid_number, Some(block_hash.0)).await? else {
+        return Ok(());
+    };
+    let Some(before) = read_chain_citizen_detail_at(cid_number, Some(parent_hash.0)).await? else {
+        return Ok(());
+    };
+    let after = chain_citizen_from_detail(after);
+    let before = chain_citizen_from_detail(before);
+    if after.province_code != scope.province_code
+        || after.city_code != scope.city_code
+        || (before.province_code == after.province_code && before.city_code == after.city_code)
+    {
+        return Ok(());
+    }
+    let block_hash = format!("0x{}", hex::encode(block_hash.0));
+    db_pool.with_client(|conn| {
+        db::insert_residence_move(
+            conn,
+            db::ResidenceMove {
+                cid_number,
+                block_number,
+                block_hash: block_hash.as_str(),
+                from_province_code: before.province_code.as_str(),
+                from_city_code: before.city_code.as_str(),
+                to_province_code: after.province_code.as_str(),
+                to_city_code: after.city_code.as_str(),
+            },
+        )
+    })
+}
+
 /// 从区块的 extrinsics 中提取 Timestamp::set 的值。
 async fn extract_block_timestamp_from_block(
     block: &subxt::blocks::Block<PolkadotConfig, OnlineClient<PolkadotConfig>>,
@@ -92,16 +92,20 @@ async fn authorize_own_institution_proposal(
         return Err(api_error(StatusCode::NOT_FOUND, 1004, "机构不存在"));
     };
     let scope = get_visible_scope(ctx);
-    if !scope.includes_province(&inst.province_name)
-        || !scope.includes_city(&inst.city_name)
-        || !scope.includes_town(&inst.town_name)
-    {
+    if !scope.includes_institution(&inst) {
         return Err(api_error(
             StatusCode::FORBIDDEN,
             1003,
             "institution out of current admin scope",
         ));
     }
+    if !scope.can_write {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            1003,
+            "当前登录为只读管理员",
+        ));
+    }
     code_bytes(&inst.institution_code)
 }
 
@@ -212,10 +216,7 @@ pub(crate) async fn list_accounts(
         return api_error(StatusCode::NOT_FOUND, 1004, "institution not found");
     };
     let scope = get_visible_scope(&ctx);
-    if !scope.includes_province(&inst.province_name)
-        || !scope.includes_city(&inst.city_name)
-        || !scope.includes_town(&inst.town_name)
-    {
+    if !scope.includes_institution(&inst) {
         return api_error(StatusCode::FORBIDDEN, 1003, "out of admin scope");
     }
     let code = match code_bytes(&inst.institution_code) {
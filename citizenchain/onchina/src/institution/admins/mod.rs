@@ -619,7 +619,7 @@ pub(crate) async fn build_chain_sign_output(
         chain_action,
         context,
     } = request;
-    let prepared = chain_submit::prepare_signing(&call_data, account_id)
+    let prepared = chain_submit::prepare_signing(&state.db, &call_data, account_id)
         .await
         .map_err(|err| {
             tracing::error!(error = %err, "prepare institution governance signing failed");
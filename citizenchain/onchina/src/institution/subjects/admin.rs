@@ -248,10 +248,7 @@ pub(crate) async fn update_institution(
     let old_cid_full_name = existing.cid_full_name.clone().unwrap_or_default();
     let old_parent_cid_number = existing.parent_cid_number.clone().unwrap_or_default();
     let scope = get_visible_scope(&ctx);
-    if !scope.includes_province(&existing.province_name)
-        || !scope.includes_city(&existing.city_name)
-        || !scope.includes_town(&existing.town_name)
-    {
+    if !scope.includes_institution(&existing) {
         return api_error(StatusCode::FORBIDDEN, 1003, "out of admin scope");
     }
 
@@ -475,8 +472,12 @@ pub(crate) async fn search_parent_institutions(
     }
     // 管辖校验:父机构候选搜索必须落在当前管理员可见域内,绝不允许跨省/市探查他辖区机构。
     // 不校验镇:SearchParentsQuery 无镇维度,父机构粒度到市;镇级管理员已被 includes_city 限定本市。
+    // 自机构档不得借父机构搜索浏览同市其他机构。
     let scope = get_visible_scope(&ctx);
-    if !scope.includes_province(&province) || !scope.includes_city(&city) {
+    if scope.is_own_institution()
+        || !scope.includes_province(&province)
+        || !scope.includes_city(&city)
+    {
         return api_error(StatusCode::FORBIDDEN, 1003, "out of admin scope");
     }
     // subjects 已不存行政区名字,地域预过滤改按 china.sqlite 派生的 code 走单源。
@@ -613,10 +614,7 @@ pub(crate) async fn get_institution(
         return api_error(StatusCode::NOT_FOUND, 1004, "institution not found");
     };
     let scope = get_visible_scope(&ctx);
-    if !scope.includes_province(&inst.province_name)
-        || !scope.includes_city(&inst.city_name)
-        || !scope.includes_town(&inst.town_name)
-    {
+    if !scope.includes_institution(&inst) {
         return api_error(StatusCode::FORBIDDEN, 1003, "out of admin scope");
     }
     let (creator_family_name, creator_given_name, creator_institution_code) =
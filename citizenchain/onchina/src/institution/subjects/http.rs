@@ -50,6 +50,13 @@ pub(crate) fn ensure_institution_visible_to_admin(
     if !scope.includes_town(&inst.town_name) {
         return Err(api_error(StatusCode::FORBIDDEN, 1003, "town out of scope"));
     }
+    if !scope.includes_cid_number(&inst.cid_number) {
+        return Err(api_error(
+            StatusCode::FORBIDDEN,
+            1003,
+            "institution out of scope",
+        ));
+    }
     Ok(())
 }
 
@@ -75,3 +82,82 @@ pub(crate) fn resolve_created_by(
     });
     result.unwrap_or((None, None, None))
 }
+
+#[cfg(test)]
+// 测试需要在前置条件失效时立即失败，断言式解包仅限本测试模块。
+#[allow(clippy::expect_used, clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::institution::subjects::model::InstitutionCategory;
+
+    const OWN_CID: &str = "AH001-SFLP0-000000123-2026";
+    const PEER_CID: &str = "AH001-SFLP0-000000456-2026";
+
+    fn institution(cid_number: &str, city_name: &str) -> Institution {
+        Institution {
+            cid_number: cid_number.to_string(),
+            cid_full_name: None,
+            cid_short_name: None,
+            category: InstitutionCategory::PrivateInstitution,
+            p1: "1".to_string(),
+            province_name: "安徽省".to_string(),
+            city_name: city_name.to_string(),
+            town_name: String::new(),
+            province_code: "AH".to_string(),
+            city_code: "001".to_string(),
+            town_code: String::new(),
+            institution_code: "SFLP".to_string(),
+            education_type: None,
+            private_type: None,
+            partnership_kind: None,
+            has_legal_personality: Some(true),
+            parent_cid_number: None,
+            legal_representative: None,
+            legal_representative_photo_path: None,
+            legal_representative_photo_name: None,
+            legal_representative_photo_mime: None,
+            legal_representative_photo_size: None,
+            creator_account_id: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn ctx(institution_code: &str, admin_level: Option<&str>) -> AdminAuthContext {
+        AdminAuthContext {
+            account_id: "0x1111111111111111111111111111111111111111111111111111111111111111"
+                .to_string(),
+            institution_cid_number: OWN_CID.to_string(),
+            institution_code: institution_code.to_string(),
+            admin_level: admin_level.map(str::to_string),
+            family_name: "管理".to_string(),
+            given_name: "员".to_string(),
+            scope_province_name: Some("安徽省".to_string()),
+            scope_city_name: Some("合肥市".to_string()),
+            scope_town_name: None,
+            cid_short_name: None,
+            read_only: false,
+        }
+    }
+
+    #[test]
+    fn private_admin_sees_only_own_institution() {
+        let admin = ctx("SFLP", None);
+        assert!(
+            ensure_institution_visible_to_admin(&institution(OWN_CID, "合肥市"), &admin).is_ok()
+        );
+        let denied = ensure_institution_visible_to_admin(&institution(PEER_CID, "合肥市"), &admin)
+            .expect_err("same-city peer must be hidden");
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn city_admin_still_sees_every_institution_in_city() {
+        let admin = ctx("CGOV", Some("CITY"));
+        assert!(
+            ensure_institution_visible_to_admin(&institution(PEER_CID, "合肥市"), &admin).is_ok()
+        );
+        assert!(
+            ensure_institution_visible_to_admin(&institution(PEER_CID, "芜湖市"), &admin).is_err()
+        );
+    }
+}
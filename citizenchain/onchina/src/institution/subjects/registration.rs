@@ -174,12 +174,17 @@ async fn list_institutions_inner(
             }
         }
     }
+    // 自机构档:关键字固定为本机构 CID(精确命中 s.cid_number),列表至多返回本机构一行。
+    let keyword = match scope.own_cid_number.as_deref() {
+        Some(own_cid_number) => own_cid_number,
+        None => query.q.as_deref().unwrap_or(""),
+    };
     let page = match state.db.list_institutions_exact(
         filter,
         query.private_type.as_deref(),
         province_code,
         city_code,
-        query.q.as_deref().unwrap_or(""),
+        keyword,
         query.cursor.as_deref(),
         page_size,
     ) {
@@ -1,4 +1,5 @@
 use axum::{
+    extract::DefaultBodyLimit,
     http::{header, HeaderMap, HeaderValue, StatusCode},
     middleware,
     response::IntoResponse,
@@ -544,8 +545,8 @@ impl Db {
         })
     }
 
-    fn upsert_target_citizen_rows(
-        conn: &mut postgres::Client,
+    pub(crate) fn upsert_target_citizen_rows<C: postgres::GenericClient>(
+        conn: &mut C,
         record: &CitizenRecord,
     ) -> Result<(), String> {
         let cid_number = record.cid_number.trim().to_string();
@@ -1267,15 +1268,14 @@ impl Db {
                 "-- cid purge-orphan-institutions backup\n-- 删除前导出的待删孤儿行(TSV/COPY 格式),删除唯一回滚保证。"
             )
             .map_err(|e| format!("write orphan backup header failed: {e}"))?;
-            // 按 (表, 主键关联列) 分别导出;subjects/gov/private/accounts 用 cid_number,
-            // docs 用 cid_number,audit 用 target_cid。
-            const EXPORTS: [(&str, &str); 6] = [
+            // 按 (表, 主键关联列) 分别导出;subjects/gov/private/accounts/docs 用 cid_number。
+            // audit 是哈希链(只追加),不随孤儿机构删除,也无需导出。
+            const EXPORTS: [(&str, &str); 5] = [
                 ("subjects", "cid_number"),
                 ("gov", "cid_number"),
                 ("private", "cid_number"),
                 ("accounts", "cid_number"),
                 ("docs", "cid_number"),
-                ("audit", "target_cid"),
             ];
             for (province, cids) in &by_province {
                 for (table, key_col) in EXPORTS {
@@ -1304,9 +1304,10 @@ impl Db {
 
     // 逐省单事务级联删孤儿机构。每省一条事务,WHERE province_code=$1 命中子分区,
     // cid_number = ANY($2) 限定本省孤儿集合;禁止跨省一条 SQL。级联删顺序遵守关联依赖:
-    // accounts → docs → audit → gov|private → ids → subjects(子承载表先删,主登记表 ids
+    // accounts → docs → gov|private → ids → subjects(子承载表先删,主登记表 ids
     // 与父表 subjects 最后)。gov/private 按 subjects.kind 区分(本方法传入已分好的两类 cid)。
-    // audit 关联列为 target_cid。ids 表不分区,仅按 cid_number 删。
+    // audit 是哈希链,删任一行都会断链,保留原行(target_cid 仅为历史关联)。
+    // ids 表不分区,仅按 cid_number 删。
     pub(crate) fn delete_orphan_institutions_by_province(
         &self,
         province: &str,
@@ -1344,14 +1345,7 @@ impl Db {
             )
             .map_err(|e| format!("delete docs for {province} failed: {e}"))?;
 
-            // 3. audit(province_code 分区,关联列 target_cid)。
-            tx.execute(
-                "DELETE FROM audit WHERE province_code = $1 AND target_cid = ANY($2)",
-                &[&province, &all_cids],
-            )
-            .map_err(|e| format!("delete audit for {province} failed: {e}"))?;
-
-            // 4. gov / private(province_code 分区,按 kind 区分各自的 cid 集合)。
+            // 3. gov / private(province_code 分区,按 kind 区分各自的 cid 集合)。
             if !gov_cids.is_empty() {
                 tx.execute(
                     "DELETE FROM gov WHERE province_code = $1 AND cid_number = ANY($2)",
@@ -1367,11 +1361,11 @@ impl Db {
                 .map_err(|e| format!("delete private for {province} failed: {e}"))?;
             }
 
-            // 5. ids(不分区,主登记表,仅按 cid_number)。
+            // 4. ids(不分区,主登记表,仅按 cid_number)。
             tx.execute("DELETE FROM ids WHERE cid_number = ANY($1)", &[&all_cids])
                 .map_err(|e| format!("delete ids for {province} failed: {e}"))?;
 
-            // 6. subjects(province_code 分区,父表最后删)。
+            // 5. subjects(province_code 分区,父表最后删)。
             let deleted = tx
                 .execute(
                     "DELETE FROM subjects WHERE province_code = $1 AND cid_number = ANY($2)",
@@ -1772,6 +1766,37 @@ enum BackendCommand {
     },
     /// 创世机构目录全量链上双向比对(部署验收,ADR-031 D9)。
     AuditChainCatalog,
+    /// 审计哈希链 + 锚点导出为 JSONL。
+    AuditExport {
+        out_path: Option<String>,
+    },
+    /// 校验审计哈希链;给 --file 时校验导出文件,否则直接校验本库。
+    AuditVerify {
+        file_path: Option<String>,
+    },
+    /// 立即拉一份加密基础备份(内嵌 PG)。
+    PgBackup,
+    /// 认证全部基础备份并核对 WAL 链。
+    PgVerify,
+    /// 平台停止时把内嵌 PG 重建到指定备份 + 目标时间点。
+    PgRestore {
+        backup: Option<String>,
+        target_time: Option<String>,
+    },
+    /// PostgreSQL `archive_command` 回调:加密归档一个 WAL 文件。
+    PgWalArchive {
+        archive_dir: Option<String>,
+        key_file: Option<String>,
+        wal_path: Option<String>,
+        wal_name: Option<String>,
+    },
+    /// PostgreSQL `restore_command` 回调:从加密归档取回一个 WAL 文件。
+    PgWalRestore {
+        archive_dir: Option<String>,
+        key_file: Option<String>,
+        wal_name: Option<String>,
+        wal_path: Option<String>,
+    },
 }
 
 fn parse_backend_command() -> BackendCommand {
@@ -1783,6 +1808,30 @@ fn parse_backend_command() -> BackendCommand {
         "serve" => BackendCommand::Serve,
         "sync-gov" => BackendCommand::SyncGov,
         "audit-chain-catalog" => BackendCommand::AuditChainCatalog,
+        "audit-export" => BackendCommand::AuditExport {
+            out_path: parse_cli_option(&args, "--out"),
+        },
+        "audit-verify" => BackendCommand::AuditVerify {
+            file_path: parse_cli_option(&args, "--file"),
+        },
+        "pg-backup" => BackendCommand::PgBackup,
+        "pg-verify" => BackendCommand::PgVerify,
+        "pg-restore" => BackendCommand::PgRestore {
+            backup: parse_cli_option(&args, "--backup"),
+            target_time: parse_cli_option(&args, "--target-time"),
+        },
+        "pg-wal-archive" => BackendCommand::PgWalArchive {
+            archive_dir: parse_cli_option(&args, "--dir"),
+            key_file: parse_cli_option(&args, "--key"),
+            wal_path: parse_cli_option(&args, "--path"),
+            wal_name: parse_cli_option(&args, "--name"),
+        },
+        "pg-wal-restore" => BackendCommand::PgWalRestore {
+            archive_dir: parse_cli_option(&args, "--dir"),
+            key_file: parse_cli_option(&args, "--key"),
+            wal_name: parse_cli_option(&args, "--name"),
+            wal_path: parse_cli_option(&args, "--path"),
+        },
         "purge-legacy-cid" => BackendCommand::PurgeLegacyCid {
             dry_run: args.iter().any(|arg| arg == "--dry-run"),
         },
@@ -1834,7 +1883,12 @@ fn log_gov_projection_report(
 
 fn run_gov_chain_projection_command(state: &AppState, command: BackendCommand) -> bool {
     match command {
-        BackendCommand::Serve => false,
+        BackendCommand::Serve
+        | BackendCommand::PgBackup
+        | BackendCommand::PgVerify
+        | BackendCommand::PgRestore { .. }
+        | BackendCommand::PgWalArchive { .. }
+        | BackendCommand::PgWalRestore { .. } => false,
         BackendCommand::AuditChainCatalog => {
             init_chain_genesis_hash_blocking()
                 .unwrap_or_else(|e| panic!("init chain genesis hash failed: {e}"));
@@ -1851,6 +1905,14 @@ fn run_gov_chain_projection_command(state: &AppState, command: BackendCommand) -
             log_gov_projection_report("sync-gov", &report);
             true
         }
+        BackendCommand::AuditExport { out_path } => {
+            run_audit_export(state, out_path.as_deref());
+            true
+        }
+        BackendCommand::AuditVerify { file_path } => {
+            run_audit_verify(state, file_path.as_deref());
+            true
+        }
         BackendCommand::PurgeLegacyCid { dry_run } => {
             run_purge_legacy_cid(state, dry_run);
             true
@@ -1865,6 +1927,159 @@ fn run_gov_chain_projection_command(state: &AppState, command: BackendCommand) -
     }
 }
 
+/// 内嵌 PG 备份类子命令:不连业务库、不读链,必须在 `ensure_started` 之前分派——
+/// WAL 回调由 postmaster 子进程调用,恢复要求实例处于停止状态。
+/// 回调失败以非零退出码告知 PostgreSQL(归档稍后重试;取回失败即回放到此为止)。
+fn run_pg_backup_command(command: &BackendCommand) -> bool {
+    use core::pg_backup::{self, BackupConfig};
+    let required = |value: &Option<String>, flag: &str| -> String {
+        value.clone().unwrap_or_else(|| {
+            eprintln!("missing {flag}");
+            std::process::exit(2)
+        })
+    };
+    match command {
+        BackendCommand::PgWalArchive {
+            archive_dir,
+            key_file,
+            wal_path,
+            wal_name,
+        } => {
+            let archive_dir = required(archive_dir, "--dir");
+            let key_file = required(key_file, "--key");
+            let wal_path = required(wal_path, "--path");
+            let wal_name = required(wal_name, "--name");
+            if let Err(err) = pg_backup::archive_wal_file(
+                std::path::Path::new(&archive_dir),
+                std::path::Path::new(&key_file),
+                std::path::Path::new(&wal_path),
+                &wal_name,
+            ) {
+                eprintln!("pg-wal-archive {wal_name} failed: {err}");
+                std::process::exit(1);
+            }
+            true
+        }
+        BackendCommand::PgWalRestore {
+            archive_dir,
+            key_file,
+            wal_name,
+            wal_path,
+        } => {
+            let archive_dir = required(archive_dir, "--dir");
+            let key_file = required(key_file, "--key");
+            let wal_name = required(wal_name, "--name");
+            let wal_path = required(wal_path, "--path");
+            match pg_backup::restore_wal_file(
+                std::path::Path::new(&archive_dir),
+                std::path::Path::new(&key_file),
+                &wal_name,
+                std::path::Path::new(&wal_path),
+            ) {
+                Ok(true) => true,
+                // 归档中没有该文件是回放终点的正常信号,不打印错误。
+                Ok(false) => std::process::exit(1),
+                Err(err) => {
+                    eprintln!("pg-wal-restore {wal_name} failed: {err}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        BackendCommand::PgRestore {
+            backup,
+            target_time,
+        } => {
+            pg_backup::restore(
+                &BackupConfig::from_env(),
+                backup.as_deref(),
+                target_time.as_deref(),
+            )
+            .unwrap_or_else(|e| panic!("pg-restore failed: {e}"));
+            info!("pg-restore finished; start the platform normally to serve the restored data");
+            true
+        }
+        BackendCommand::PgBackup => {
+            core::embedded_pg::ensure_started()
+                .unwrap_or_else(|e| panic!("embedded postgres start failed: {e}"));
+            let manifest = pg_backup::run_base_backup(&BackupConfig::from_env())
+                .unwrap_or_else(|e| panic!("pg-backup failed: {e}"));
+            info!(backup_id = %manifest.backup_id, "pg-backup finished");
+            true
+        }
+        BackendCommand::PgVerify => {
+            let report = pg_backup::verify_all(&BackupConfig::from_env())
+                .unwrap_or_else(|e| panic!("pg-verify failed: {e}"));
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).unwrap_or_default()
+            );
+            if !report.ok {
+                std::process::exit(1);
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+fn run_audit_export(state: &AppState, out_path: Option<&str>) {
+    let out_path = out_path
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| format!("audit_export_{}.jsonl", Utc::now().format("%Y%m%d%H%M%S")));
+    let path = out_path.clone();
+    let written = state
+        .db
+        .with_client(move |conn| {
+            let file = std::fs::File::create(&path)
+                .map_err(|e| format!("create audit export file {path} failed: {e}"))?;
+            let mut writer = std::io::BufWriter::new(file);
+            let written = audit::export::export_jsonl_conn(conn, &mut writer)?;
+            std::io::Write::flush(&mut writer)
+                .map_err(|e| format!("flush audit export file failed: {e}"))?;
+            Ok(written)
+        })
+        .unwrap_or_else(|e| panic!("audit-export failed: {e}"));
+    info!(out_path = %out_path, records = written, "audit-export finished");
+}
+
+// 校验失败以非零退出码结束,便于部署脚本/定时任务直接判定。
+fn run_audit_verify(state: &AppState, file_path: Option<&str>) {
+    let input = match file_path {
+        Some(path) => {
+            let file = std::fs::File::open(path)
+                .unwrap_or_else(|e| panic!("open audit export file {path} failed: {e}"));
+            audit::export::read_jsonl(std::io::BufReader::new(file))
+        }
+        None => state
+            .db
+            .with_client(|conn| audit::anchor::load_verify_input_conn(conn, None)),
+    }
+    .unwrap_or_else(|e| panic!("audit-verify failed: {e}"));
+    let runtime = tokio::runtime::Runtime::new()
+        .unwrap_or_else(|e| panic!("create audit-verify runtime failed: {e}"));
+    let reports = runtime.block_on(input.verify_against_chain());
+    let mut intact = true;
+    for report in &reports {
+        intact &= report.is_intact();
+        info!(
+            province_code = %report.province_code,
+            entries = report.chain.entries_checked,
+            last_id = report.chain.last_id,
+            first_broken_id = ?report.chain.first_broken_id,
+            problem = ?report.chain.problem,
+            anchors = report.anchors_checked,
+            anchored = report.anchors_anchored,
+            anchor_mismatches = ?report.anchor_mismatches,
+            anchor_problems = ?report.anchor_problems,
+            first_unanchored_id = ?report.first_unanchored_id,
+            "audit-verify province"
+        );
+    }
+    if !intact {
+        panic!("audit-verify found tampered or truncated audit chain");
+    }
+}
+
 #[derive(Debug)]
 struct PurgeReport {
     legacy_count: usize,
@@ -1936,7 +2151,7 @@ struct OrphanInstitution {
 //   - dry-run:只打印孤儿清单(onchina/town/town_code/category/institution_code/原因)+ 总数,
 //     供人工核对无一命中冻结常量号(储委会/部委)。
 //   - apply:先把待删行导出到 purge_orphan_backup_<...>.sql(删除唯一回滚保证),
-//     再逐省单事务级联删(accounts→docs→audit→gov|private→ids→subjects)。
+//     再逐省单事务级联删(accounts→docs→gov|private→ids→subjects),audit 哈希链保留。
 // 红线:绝不动 cid_number;绝不删空 town_code 行(已在扫描层白名单过滤);不碰号生成/链/省市码。
 fn run_purge_orphan_institutions(state: &AppState, dry_run: bool, backup_path: Option<&str>) {
     let orphans = state
@@ -2060,6 +2275,9 @@ fn main() {
         .init();
     disable_core_dumps();
     let command = parse_backend_command();
+    if run_pg_backup_command(&command) {
+        return;
+    }
 
     // OnChina 后端不持有任何链上签名钥:机构操作全部由发起管理员使用签名钱包直接冷签,
     // 鉴权真源是链上 Active 管理员集合。
@@ -2160,6 +2378,13 @@ fn main() {
                 state.db.clone(),
             ));
         }
+        // 审计检查点只落本地 PENDING,不依赖链可达;上链由管理员冷签完成。
+        tokio::spawn(audit::anchor::audit_anchor_loop(state.db.clone()));
+        // 内嵌 PG 且配置了备份目录才跑定时加密基础备份;外部托管 PG 由其运维体系备份。
+        let pg_backup_config = core::pg_backup::BackupConfig::from_env();
+        if core::embedded_pg::is_enabled() && pg_backup_config.backup_dir.is_some() {
+            tokio::spawn(core::pg_backup::pg_backup_loop(pg_backup_config));
+        }
 
         let auth_routes = Router::new()
             .route("/api/admin/auth/check", get(auth::login::admin_auth_check))
@@ -2375,6 +2600,30 @@ fn main() {
                 get(institution::subjects::admin::get_federal_registry),
             )
             .route("/api/admin/audit-logs", get(audit::admin_list_audit_logs))
+            .route(
+                "/api/admin/system/pg-backup/run",
+                post(core::pg_backup::handler::pg_backup_run),
+            )
+            .route(
+                "/api/admin/system/pg-backup/verify",
+                post(core::pg_backup::handler::pg_backup_verify),
+            )
+            .route(
+                "/api/admin/audit-logs/anchors",
+                get(audit::admin_list_audit_anchors),
+            )
+            .route(
+                "/api/admin/audit-logs/anchors/prepare",
+                post(audit::admin_prepare_audit_anchor),
+            )
+            .route(
+                "/api/admin/audit-logs/anchors/confirm",
+                post(audit::admin_confirm_audit_anchor),
+            )
+            .route(
+                "/api/admin/audit-logs/verify",
+                get(audit::admin_verify_audit_chain),
+            )
             // 建档占号先行(ADR-031):POST = 占号 prepare（返回 CitizenWallet 签名请求二维码），
             // 链上进块后经全业务统一 chain/submit 才落档案;列表查询走 GET。
             .route(
@@ -2432,6 +2681,32 @@ fn main() {
                 "/api/admin/citizens/:cid_number/documents/:doc_id",
                 delete(domains::citizens::handler::delete_citizen_document),
             )
+            .route(
+                "/api/admin/citizens/dossier-transfer/recipient-card",
+                get(domains::citizens::transfer::get_recipient_card),
+            )
+            .route(
+                "/api/admin/citizens/dossier-transfer/recipient-card/prepare",
+                post(domains::citizens::transfer::prepare_recipient_card),
+            )
+            .route(
+                "/api/admin/citizens/dossier-transfer/recipient-card/confirm",
+                post(domains::citizens::transfer::confirm_recipient_card),
+            )
+            .route(
+                "/api/admin/citizens/:cid_number/dossier-transfer/export/prepare",
+                post(domains::citizens::transfer::prepare_dossier_export),
+            )
+            .route(
+                "/api/admin/citizens/dossier-transfer/export/confirm",
+                post(domains::citizens::transfer::confirm_dossier_export),
+            )
+            .route(
+                "/api/admin/citizens/dossier-transfer/import",
+                post(domains::citizens::transfer::import_dossier).layer(DefaultBodyLimit::max(
+                    domains::citizens::transfer::MAX_PACKAGE_BODY_BYTES,
+                )),
+            )
             .route(
                 "/api/admin/citizens/:cid_number/onchain/prepare",
                 post(domains::citizens::chain_identity::prepare_citizen_onchain_signature),
@@ -2472,6 +2747,11 @@ fn main() {
             // 根路径 `/` 让给前端 SPA(经 fallback_service 的 ServeDir → index.html),
             // 健康检查走专用 `/api/health`;否则浏览器访问注册局只会看到健康 JSON。
             .route("/api/health", get(health))
+            // 备份状态自带鉴权(管理员会话或本机桌面令牌),故不挂管理员会话中间件。
+            .route(
+                "/api/admin/system/pg-backup/status",
+                get(core::pg_backup::handler::pg_backup_status),
+            )
             .route(
                 "/api/platform/ca-certificate",
                 get(organization_ca_certificate),
@@ -2513,6 +2793,15 @@ fn main() {
                 "/api/app/accounts/:account_id/transactions",
                 get(indexer::api::account_transactions),
             )
+            // ── 链上账单(OnchainTransaction 账单事件投影) ──
+            .route(
+                "/api/app/accounts/:account_id/invoices",
+                get(indexer::api::account_invoices),
+            )
+            .route(
+                "/api/app/invoices/:invoice_id",
+                get(indexer::api::invoice_detail),
+            )
             // ── 机构信息查询(链端/钱包 pull):机构搜索 / 详情 / 账户列表 ──
             .route(
                 "/api/app/institutions/search",
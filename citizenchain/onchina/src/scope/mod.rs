@@ -5,12 +5,15 @@
 //! 1. `let ctx = require_admin_any(...)?;`
 //! 2. `let scope = scope::get_visible_scope(&ctx);` 传给 SQL 层收窄查询。
 //!
-//! 五档范围(按机构 admin_level 派生):
+//! 六档范围(按机构 admin_level 派生):
 //! - 全国(NATIONAL,部委等)        → 不限省/市/镇
 //! - 省级(PROVINCE / 联邦注册局)   → 本省,所有市
 //! - 市级(CITY)                    → 本市,所有镇
 //! - 镇级(TOWN)                    → 本镇
-//! - 自机构(私权法人/非法人,无层级)→ 暂沿用本市范围
+//! - 自机构(私权法人/非法人,无层级)→ 仅本机构 CID 及其账户
+//! - 空范围(登录投影缺字段)        → 一律不可见
+//!
+//! 只读管理员在任一档内都不可写(`can_write = false`)。
 //!
 //! 详细规则见 `rules.rs` 的 `VisibleScope`。
 //!
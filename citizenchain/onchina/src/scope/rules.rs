@@ -3,14 +3,18 @@
 //! 按登录管理员的机构行政层级(admin_level)派生其可见/可操作的数据范围
 //! (VisibleScope)。所有 list/CRUD API 都应先派生 scope,再用它过滤数据。
 //!
-//! 五档范围:
+//! 六档范围:
 //! - 全国(NATIONAL,部委等)        → 不限省/市/镇
 //! - 省级(PROVINCE / 联邦注册局)   → 本省,所有市
 //! - 市级(CITY)                    → 本市,所有镇
 //! - 镇级(TOWN)                    → 本镇
-//! - 自机构(私权法人/非法人,无层级)→ 暂沿用本市范围(精确"仅本机构"范围见后续 admin 泛化卡)
+//! - 自机构(私权法人/非法人,无层级)→ 仅本机构 CID 及其账户,锁定本省本市
+//! - 空范围                         → 登录投影缺字段,fail-closed
+//!
+//! 只读管理员(`admins.read_only`)在任一档内 `can_write = false`,写操作统一拒绝。
 
 use crate::auth::login::AdminAuthContext;
+use crate::institution::subjects::model::Institution;
 
 /// 登录管理员可见的数据范围。
 #[derive(Debug, Clone)]
@@ -23,7 +27,10 @@ pub struct VisibleScope {
     pub cities: Vec<String>,
     /// 可见镇列表。空 vec 表示"不限镇"(全国/省级/市级)。
     pub towns: Vec<String>,
-    /// 是否可以增删改。各档在自己范围内都能写,保留字段供将来扩展只读角色。
+    /// 自机构档锁定的机构 CID。有值时只可见/可操作该 CID 本身及其账户,
+    /// 同市其他机构一律不可见;其余档为 None。
+    pub own_cid_number: Option<String>,
+    /// 是否可以增删改。各档在自己范围内都能写;只读管理员由 `get_visible_scope` 统一置 false。
     pub can_write: bool,
     /// 进入 tab 时是否跳过省份列表直接进入详情(省级及以下锁定本省)。
     /// 前端 tab 跳级预留:VisibleScope 暂不序列化,当前后端不读,留待 UI 接线。
@@ -51,6 +58,7 @@ impl VisibleScope {
             provinces: vec![],
             cities: vec![],
             towns: vec![],
+            own_cid_number: None,
             can_write: true,
             skip_province_list: false,
             skip_city_list: false,
@@ -68,6 +76,7 @@ impl VisibleScope {
             provinces: vec![province_name.clone()],
             cities: vec![],
             towns: vec![],
+            own_cid_number: None,
             can_write: true,
             skip_province_list: true,
             skip_city_list: false,
@@ -85,6 +94,7 @@ impl VisibleScope {
             provinces: vec![province_name.clone()],
             cities: vec![city_name.clone()],
             towns: vec![],
+            own_cid_number: None,
             can_write: true,
             skip_province_list: true,
             skip_city_list: true,
@@ -102,6 +112,7 @@ impl VisibleScope {
             provinces: vec![province_name.clone()],
             cities: vec![city_name.clone()],
             towns: vec![town_name.clone()],
+            own_cid_number: None,
             can_write: true,
             skip_province_list: true,
             skip_city_list: true,
@@ -112,6 +123,22 @@ impl VisibleScope {
         }
     }
 
+    /// 自机构:锁定本省本市,且只认本机构 CID,可写。
+    pub fn own_institution(cid_number: String, province_name: String, city_name: String) -> Self {
+        Self {
+            own_cid_number: Some(cid_number),
+            ..Self::city(province_name, city_name)
+        }
+    }
+
+    /// 只读化:保留可见范围,关闭增删改。
+    pub fn read_only(self) -> Self {
+        Self {
+            can_write: false,
+            ..self
+        }
+    }
+
     /// 空范围。缺省域代表登录投影错误,调用方应优先拒绝请求。
     pub fn empty() -> Self {
         Self {
@@ -119,6 +146,7 @@ impl VisibleScope {
             provinces: vec![],
             cities: vec![],
             towns: vec![],
+            own_cid_number: None,
             can_write: false,
             skip_province_list: false,
             skip_city_list: false,
@@ -145,15 +173,51 @@ impl VisibleScope {
     pub fn includes_town(&self, town: &str) -> bool {
         self.towns.is_empty() || town.trim().is_empty() || self.towns.iter().any(|t| t == town)
     }
+
+    /// 判断某机构 CID 是否在范围内。非自机构档恒真(由省/市/镇维度约束)。
+    pub fn includes_cid_number(&self, cid_number: &str) -> bool {
+        match self.own_cid_number.as_deref() {
+            Some(own) => own == cid_number.trim(),
+            None => true,
+        }
+    }
+
+    /// 是否为自机构档。自机构管理员不承担辖区职能,按行政区浏览的目录与公民档案一律不可见。
+    pub fn is_own_institution(&self) -> bool {
+        self.own_cid_number.is_some()
+    }
+
+    /// 是否空范围(登录投影缺省域):非全国、无可见省、也不是自机构档。
+    pub fn is_empty(&self) -> bool {
+        !self.nationwide && self.provinces.is_empty() && self.own_cid_number.is_none()
+    }
+
+    /// 机构可见性单点判定:省/市/镇三维 + 自机构 CID,所有按机构定位的 handler 统一走这里。
+    pub fn includes_institution(&self, inst: &Institution) -> bool {
+        self.includes_province(&inst.province_name)
+            && self.includes_city(&inst.city_name)
+            && self.includes_town(&inst.town_name)
+            && self.includes_cid_number(&inst.cid_number)
+    }
 }
 
 /// 根据登录管理员上下文派生 VisibleScope。
 ///
 /// Tier1 创世注册局(FRG)的 admin_level 虽为 NATIONAL,但其管理员按省分区
 /// (每节点单省,省作用域来自节点 env / 链上省组),故先于 admin_level 经 `is_tier1_registry`
-/// 谓词特判为省级范围。其余机构按 admin_level 派生;私权法人/非法人无层级,暂沿用本市范围。
-/// 任一档缺必要 scope 字段返回空范围,不制造伪行政区参与查询。
+/// 谓词特判为省级范围。其余机构按 admin_level 派生;私权法人/非法人无层级,收窄为仅本机构。
+/// 任一档缺必要 scope 字段返回空范围,不制造伪行政区参与查询。只读管理员在派生出的范围上
+/// 统一关闭写权限,业务 handler 不再各自判断角色。
 pub fn get_visible_scope(ctx: &AdminAuthContext) -> VisibleScope {
+    let scope = derive_scope_by_level(ctx);
+    if ctx.read_only {
+        scope.read_only()
+    } else {
+        scope
+    }
+}
+
+fn derive_scope_by_level(ctx: &AdminAuthContext) -> VisibleScope {
     if crate::core::chain_runtime::is_tier1_registry(&ctx.institution_code) {
         return ctx
             .scope_province_name
@@ -179,8 +243,8 @@ pub fn get_visible_scope(ctx: &AdminAuthContext) -> VisibleScope {
             };
             VisibleScope::town(province, city, town)
         }
-        // 私权法人/非法人(无 admin_level):暂沿用本市范围;精确"仅本机构"范围留 admin 泛化卡。
-        None => scope_city_or_empty(ctx),
+        // 私权法人/非法人(无 admin_level):仅本机构 CID;缺省省/市或 CID 一律空范围。
+        None => scope_own_institution_or_empty(ctx),
         // 防御性兜底:admin_level_label_for 只产出 NATIONAL/PROVINCE/CITY/TOWN 或 None(均已覆盖),
         // 出现未知层级一律 fail-closed 空范围。
         _ => VisibleScope::empty(),
@@ -196,6 +260,19 @@ fn scope_city_or_empty(ctx: &AdminAuthContext) -> VisibleScope {
     VisibleScope::city(province, city)
 }
 
+fn scope_own_institution_or_empty(ctx: &AdminAuthContext) -> VisibleScope {
+    let cid_number = ctx.institution_cid_number.trim();
+    let (Some(province), Some(city)) =
+        (ctx.scope_province_name.clone(), ctx.scope_city_name.clone())
+    else {
+        return VisibleScope::empty();
+    };
+    if cid_number.is_empty() {
+        return VisibleScope::empty();
+    }
+    VisibleScope::own_institution(cid_number.to_string(), province, city)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,6 +327,7 @@ mod tests {
     #[test]
     fn empty_scope_matches_nothing() {
         let s = VisibleScope::empty();
+        assert!(s.is_empty());
         assert!(!s.includes_province("安徽省"));
         assert!(!s.can_write);
     }
@@ -273,6 +351,7 @@ mod tests {
             scope_city_name: city.map(str::to_string),
             scope_town_name: town.map(str::to_string),
             cid_short_name: None,
+            read_only: false,
         }
     }
 
@@ -345,10 +424,52 @@ mod tests {
     }
 
     #[test]
-    fn private_none_level_falls_back_to_city() {
-        // 私权法人/非法人无 admin_level:暂沿用本市范围(决策 1①)。
-        let s = get_visible_scope(&ctx("SFLP", None, Some("安徽省"), Some("合肥市"), None));
+    fn private_none_level_limited_to_own_institution() {
+        // 私权法人/非法人无 admin_level:仅本机构 CID,同市其他机构不可见。
+        let mut c = ctx("SFLP", None, Some("安徽省"), Some("合肥市"), None);
+        c.institution_cid_number = "AH001-SFLP0-000000123-2026".to_string();
+        let s = get_visible_scope(&c);
+        assert!(s.is_own_institution());
+        assert!(s.can_write);
         assert!(s.includes_city("合肥市"));
         assert!(!s.includes_city("芜湖市"));
+        assert!(s.includes_cid_number("AH001-SFLP0-000000123-2026"));
+        assert!(!s.includes_cid_number("AH001-SFLP0-000000456-2026"));
+        assert_eq!(s.locked_city_name.as_deref(), Some("合肥市"));
+    }
+
+    #[test]
+    fn private_none_level_missing_city_fails_closed() {
+        let s = get_visible_scope(&ctx("SFLP", None, Some("安徽省"), None, None));
+        assert!(!s.includes_province("安徽省"));
+        assert!(!s.can_write);
+    }
+
+    #[test]
+    fn public_levels_do_not_restrict_cid_number() {
+        let s = get_visible_scope(&ctx(
+            "CGOV",
+            Some("CITY"),
+            Some("安徽省"),
+            Some("合肥市"),
+            None,
+        ));
+        assert!(!s.is_own_institution());
+        assert!(s.includes_cid_number("AH001-SFLP0-000000456-2026"));
+    }
+
+    #[test]
+    fn read_only_admin_keeps_visibility_without_write() {
+        let mut c = ctx("PGV", Some("PROVINCE"), Some("安徽省"), None, None);
+        c.read_only = true;
+        let s = get_visible_scope(&c);
+        assert!(s.includes_province("安徽省"));
+        assert!(!s.can_write);
+
+        let mut c = ctx("SFLP", None, Some("安徽省"), Some("合肥市"), None);
+        c.read_only = true;
+        let s = get_visible_scope(&c);
+        assert!(s.is_own_institution());
+        assert!(!s.can_write);
     }
 }
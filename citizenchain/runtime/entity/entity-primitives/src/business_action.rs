@@ -38,6 +38,7 @@ pub const MODULE_ONCHAIN_ISSUANCE: &[u8] = b"onc-iss";
 pub const MODULE_OFFCHAIN: &[u8] = b"offchain";
 pub const MODULE_LEGISLATION_YUAN: &[u8] = b"leg-yuan";
 pub const MODULE_SQUARE_SUBSCRIPTION: &[u8] = b"sqr-sub";
+pub const MODULE_EMERGENCY_PAUSE: &[u8] = b"em-pause";
 /// 第 7 步接入投票前先冻结公民身份业务标签，禁止复用为其它业务。
 pub const MODULE_CITIZEN_IDENTITY: &[u8] = b"cit-id";
 /// 第 7 步接入投票前先冻结地址登记业务标签，禁止复用为其它业务。
@@ -55,6 +56,10 @@ pub const ACTION_RESOLUTION_DESTROY: u32 = 0;
 pub const ACTION_GRANDPA_KEY_EMERGENCY_RECOVERY: u32 = 0;
 /// GRANDPA 验证密钥仍可用时，由本机构单个委员直接发起的双密钥签名更换。
 pub const ACTION_GRANDPA_KEY_ROTATION: u32 = 1;
+/// 联合投票决定的暂停、续期或解除暂停。
+pub const ACTION_EMERGENCY_PAUSE: u32 = 0;
+/// 国家储委会委员达到门槛后的短期快速暂停，到期自动解除。
+pub const ACTION_EMERGENCY_PAUSE_FAST_TRACK: u32 = 1;
 pub const ACTION_MULTISIG_TRANSFER: u32 = 0;
 pub const ACTION_SAFETY_FUND_TRANSFER: u32 = 1;
 pub const ACTION_FEE_SWEEP_TO_MAIN: u32 = 2;
@@ -191,6 +196,14 @@ pub fn fixed_role_permission_specs(
                 ACTION_INSTITUTION_GOVERNANCE,
             );
             push_both(&mut out, MODULE_RUNTIME_UPGRADE, ACTION_RUNTIME_UPGRADE);
+            push_both(&mut out, MODULE_EMERGENCY_PAUSE, ACTION_EMERGENCY_PAUSE);
+            // 快速暂停只由国家储委会委员逐人确认，不经投票引擎，因此只有 Propose。
+            push_permission(
+                &mut out,
+                MODULE_EMERGENCY_PAUSE,
+                ACTION_EMERGENCY_PAUSE_FAST_TRACK,
+                RolePermissionOperation::Propose,
+            );
             push_both(
                 &mut out,
                 MODULE_RESOLUTION_ISSUANCE,
@@ -241,6 +254,7 @@ pub fn fixed_role_permission_specs(
             );
             // 协议升级与决议发行相同，由 NRC + 43 个 PRC 委员岗位共同拥有。
             push_both(&mut out, MODULE_RUNTIME_UPGRADE, ACTION_RUNTIME_UPGRADE);
+            push_both(&mut out, MODULE_EMERGENCY_PAUSE, ACTION_EMERGENCY_PAUSE);
             push_both(
                 &mut out,
                 MODULE_RESOLUTION_ISSUANCE,
@@ -277,6 +291,12 @@ pub fn fixed_role_permission_specs(
                 ACTION_RUNTIME_UPGRADE,
                 RolePermissionOperation::Vote,
             );
+            push_permission(
+                &mut out,
+                MODULE_EMERGENCY_PAUSE,
+                ACTION_EMERGENCY_PAUSE,
+                RolePermissionOperation::Vote,
+            );
             push_permission(
                 &mut out,
                 MODULE_RESOLUTION_ISSUANCE,
@@ -547,6 +567,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn emergency_pause_fast_track_is_reserved_for_nrc_committee() {
+        for (index, entry) in CHINA_CB.iter().enumerate() {
+            let permissions = fixed_role_permission_specs(
+                primitives::cid::code::institution_code_from_cid_number(entry.cid_number)
+                    .expect("CHINA_CB CID encodes institution code"),
+                entry.cid_number.as_bytes(),
+                ROLE_CODE_COMMITTEE_MEMBER,
+            );
+            assert!(has(
+                &permissions,
+                MODULE_EMERGENCY_PAUSE,
+                ACTION_EMERGENCY_PAUSE,
+                RolePermissionOperation::Propose,
+            ));
+            assert_eq!(
+                has(
+                    &permissions,
+                    MODULE_EMERGENCY_PAUSE,
+                    ACTION_EMERGENCY_PAUSE_FAST_TRACK,
+                    RolePermissionOperation::Propose,
+                ),
+                index == 0
+            );
+        }
+        let prb = &CHINA_CH[0];
+        let director =
+            fixed_role_permission_specs(PRB, prb.cid_number.as_bytes(), ROLE_CODE_DIRECTOR);
+        assert!(has(
+            &director,
+            MODULE_EMERGENCY_PAUSE,
+            ACTION_EMERGENCY_PAUSE,
+            RolePermissionOperation::Vote,
+        ));
+        assert!(!has(
+            &director,
+            MODULE_EMERGENCY_PAUSE,
+            ACTION_EMERGENCY_PAUSE,
+            RolePermissionOperation::Propose,
+        ));
+    }
+
     #[test]
     fn grandpa_rotation_is_direct_but_emergency_recovery_is_internal_vote() {
         for entry in CHINA_CB {
@@ -280,6 +280,54 @@ pub struct InstitutionAdminAssignment<CidNumber, AccountId, RoleCode, SourceRef>
     pub assignment_status: InstitutionAssignmentStatus,
 }
 
+/// 同一管理员在同一机构岗位上的选举连任记录。
+///
+/// 只由普选/互选结果写入；新任期开始日不晚于上一任期结束日次日视为连任，
+/// 否则连任计数重置为 1。重复提交同一任期不改变计数。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    PartialEq,
+    Eq,
+    Default,
+)]
+pub struct AssignmentTermRecord {
+    /// 截至最近一次当选的连续任期数。
+    pub consecutive_terms: u32,
+    /// 最近一次当选任期开始日。
+    pub last_term_start: u32,
+    /// 最近一次当选任期结束日。
+    pub last_term_end: u32,
+}
+
+impl AssignmentTermRecord {
+    /// 计算写入 `[term_start, term_end]` 任期后的连任记录。
+    pub fn next(previous: Option<Self>, term_start: u32, term_end: u32) -> Self {
+        let consecutive_terms = match previous {
+            Some(record)
+                if record.last_term_start == term_start && record.last_term_end == term_end =>
+            {
+                return record;
+            }
+            Some(record) if term_start <= record.last_term_end.saturating_add(1) => {
+                record.consecutive_terms.saturating_add(1)
+            }
+            _ => 1,
+        };
+        Self {
+            consecutive_terms,
+            last_term_start: term_start,
+            last_term_end: term_end,
+        }
+    }
+}
+
 /// 机构岗位与任职只读接口。
 ///
 /// 业务模块必须按“机构 CID + 稳定岗位代码 + 有效任职”鉴权，不能比较岗位名称。
@@ -520,4 +568,15 @@ mod tests {
                 .encode()
         );
     }
+    /// 连任按任期首尾相接判定；重复提交同一任期不重复计数。
+    #[test]
+    fn term_record_counts_only_adjacent_new_terms() {
+        let first = AssignmentTermRecord::next(None, 100, 199);
+        assert_eq!(first.consecutive_terms, 1);
+        assert_eq!(AssignmentTermRecord::next(Some(first), 100, 199), first);
+        let second = AssignmentTermRecord::next(Some(first), 200, 299);
+        assert_eq!(second.consecutive_terms, 2);
+        let after_gap = AssignmentTermRecord::next(Some(second), 400, 499);
+        assert_eq!(after_gap.consecutive_terms, 1);
+    }
 }
@@ -0,0 +1,144 @@
+//! 机构合并/分立承继共用类型(公权/私权逐字段一致)。
+//!
+//! 承继只在同一 entity pallet 内发生:前身机构本身不删除(协议账户永久存在),
+//! 执行后由 `SuccessorOf[前身 CID]` 永久指向承继机构,前身从此不得再发起任何机构提案。
+//! 承继机构必须先以本机构内部投票同意承继(`SuccessionConsentAction`),
+//! 前身的承继提案执行时逐一消费这些同意记录。
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::DecodeWithMemTracking;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// 单次分立允许的承继机构上限。
+pub const MAX_INSTITUTION_SUCCESSORS: u32 = 8;
+
+/// 承继类型。合并只有一个承继机构;分立至少两个。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    PartialEq,
+    Eq,
+)]
+pub enum SuccessionKind {
+    /// 前身并入唯一承继机构。
+    Merger,
+    /// 前身分立为多个承继机构。
+    Split,
+}
+
+impl SuccessionKind {
+    /// 承继机构数量是否与类型相符。
+    pub fn accepts_successor_count(self, count: usize) -> bool {
+        match self {
+            Self::Merger => count == 1,
+            Self::Split => count >= 2 && count <= MAX_INSTITUTION_SUCCESSORS as usize,
+        }
+    }
+}
+
+/// 余额承继项:前身某账户执行时的全部可用余额转入承继机构某账户。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    PartialEq,
+    Eq,
+)]
+pub struct SuccessionBalanceTransfer<CidNumber, AccountName> {
+    pub source_account_name: AccountName,
+    pub successor_cid_number: CidNumber,
+    pub successor_account_name: AccountName,
+}
+
+/// 岗位定义承继项:前身岗位的名称、任期规则与业务权限复制到承继机构的新动态岗位码;
+/// 任职不随迁,由承继机构自身治理补任。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    PartialEq,
+    Eq,
+)]
+pub struct SuccessionRoleTransfer<CidNumber, RoleCode> {
+    pub role_code: RoleCode,
+    pub successor_cid_number: CidNumber,
+}
+
+/// 承继提案业务数据(存入投票引擎 ProposalData,不进 storage 值,故不派生 `MaxEncodedLen`)。
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct InstitutionSuccessionAction<AccountId, CidNumber, AccountName, RoleCode> {
+    pub kind: SuccessionKind,
+    /// 前身机构 CID,也是提案发起与投票主体。
+    pub predecessor_cid_number: CidNumber,
+    pub successor_cid_numbers: Vec<CidNumber>,
+    pub balance_transfers: Vec<SuccessionBalanceTransfer<CidNumber, AccountName>>,
+    pub role_transfers: Vec<SuccessionRoleTransfer<CidNumber, RoleCode>>,
+    pub proposer_account_id: AccountId,
+}
+
+/// 承继机构同意承继的提案业务数据。
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct SuccessionConsentAction<AccountId, CidNumber> {
+    /// 同意方,也是提案发起与投票主体。
+    pub successor_cid_number: CidNumber,
+    pub predecessor_cid_number: CidNumber,
+    pub proposer_account_id: AccountId,
+}
+
+/// `SuccessorOf` 存储值:前身机构的永久承继记录。
+///
+/// `predecessor_legal_representative` 保存承继执行时前身的法定代表人公开信息;
+/// 前身 `InstitutionInfo.legal_representative` 同时清空,承继机构的法定代表人
+/// 仍只由其自身治理任免(须为其在册管理员),不在此自动改写。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    PartialEq,
+    Eq,
+)]
+pub struct SuccessionRecord<CidNumbers, LegalRepresentative, BlockNumber> {
+    pub kind: SuccessionKind,
+    pub successor_cid_numbers: CidNumbers,
+    pub predecessor_legal_representative: Option<LegalRepresentative>,
+    pub proposal_id: u64,
+    pub succeeded_at: BlockNumber,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn successor_count_matches_kind() {
+        assert!(SuccessionKind::Merger.accepts_successor_count(1));
+        assert!(!SuccessionKind::Merger.accepts_successor_count(2));
+        assert!(!SuccessionKind::Split.accepts_successor_count(1));
+        assert!(SuccessionKind::Split.accepts_successor_count(2));
+        assert!(
+            !SuccessionKind::Split.accepts_successor_count(MAX_INSTITUTION_SUCCESSORS as usize + 1)
+        );
+    }
+}
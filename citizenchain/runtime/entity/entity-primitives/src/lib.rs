@@ -25,6 +25,7 @@ pub use primitives::multisig::{AccountValidator, ProtectedSourceChecker, Reserve
 pub mod business_action;
 pub mod institution_governance;
 pub mod institution_role;
+pub mod institution_succession;
 pub use business_action::{
     fixed_institution_capability_allows, fixed_role_permission_specs, FixedRolePermissionSpec,
 };
@@ -35,13 +36,17 @@ pub use institution_governance::{
     RolePermissionSpec,
 };
 pub use institution_role::{
-    generate_dynamic_role_code, AuthorizationSubject, BusinessActionId, InstitutionAdminAssignment,
-    InstitutionAssignmentSource, InstitutionAssignmentStatus, InstitutionCapabilityPolicy,
-    InstitutionRole, InstitutionRoleAuthorizationQuery, InstitutionRoleQuery,
-    InstitutionRoleStatus, RoleBusinessPermission, RolePermissionOperation, RoleSubject,
-    ASSIGNMENT_SOURCE_REF_MAX_BYTES, BUSINESS_MODULE_TAG_MAX_BYTES,
+    generate_dynamic_role_code, AssignmentTermRecord, AuthorizationSubject, BusinessActionId,
+    InstitutionAdminAssignment, InstitutionAssignmentSource, InstitutionAssignmentStatus,
+    InstitutionCapabilityPolicy, InstitutionRole, InstitutionRoleAuthorizationQuery,
+    InstitutionRoleQuery, InstitutionRoleStatus, RoleBusinessPermission, RolePermissionOperation,
+    RoleSubject, ASSIGNMENT_SOURCE_REF_MAX_BYTES, BUSINESS_MODULE_TAG_MAX_BYTES,
     INSTITUTION_ROLE_CODE_MAX_BYTES, MAX_ROLE_PERMISSIONS_PER_ROLE,
 };
+pub use institution_succession::{
+    InstitutionSuccessionAction, SuccessionBalanceTransfer, SuccessionConsentAction,
+    SuccessionKind, SuccessionRecord, SuccessionRoleTransfer, MAX_INSTITUTION_SUCCESSORS,
+};
 
 // ===== 机构生命周期共用 storage 值类型(唯一真源) =====
 // public-manage / private-manage 逐字段一致地复用以下类型;两 pallet 各自
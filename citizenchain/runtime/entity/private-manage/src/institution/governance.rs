@@ -25,9 +25,11 @@ use crate::institution::role::{
     AssignmentSourceRefOf, InstitutionRoleOf, ModuleTagOf, RoleAssignmentsOf, RoleCodeOf,
     RolePermissionsOf,
 };
+use crate::institution::term::TermRecordWritesOf;
 use crate::pallet::{
-    AccountNameOf, CidNumberOf, Config, Error, InstitutionRoleAssignments, InstitutionRoleNonce,
-    InstitutionRolePermissions, InstitutionRoles, Institutions, Pallet, UsedRoleCodes,
+    AccountNameOf, AssignmentTermRecords, CidNumberOf, Config, Error, InstitutionRoleAssignments,
+    InstitutionRoleNonce, InstitutionRolePermissions, InstitutionRoles, Institutions, Pallet,
+    UsedRoleCodes,
 };
 
 enum LegalRepresentativeTarget<T: Config> {
@@ -97,6 +99,7 @@ impl<T: Config> Pallet<T> {
         let mut role_writes = BTreeMap::<RoleCodeOf, InstitutionRoleOf<T>>::new();
         let mut permission_writes = BTreeMap::<RoleCodeOf, RolePermissionsOf<T>>::new();
         let mut created_assignment_writes = BTreeMap::<RoleCodeOf, RoleAssignmentsOf<T>>::new();
+        let mut term_record_writes = TermRecordWritesOf::<T>::new();
         let mut role_deletes = BTreeSet::<RoleCodeOf>::new();
         let mut next_role_nonce = InstitutionRoleNonce::<T>::get(&cid_number);
         for mutation in result.role_mutations {
@@ -174,6 +177,7 @@ impl<T: Config> Pallet<T> {
                         &role,
                         assignments,
                         &current_admin_set,
+                        &mut term_record_writes,
                     )?;
                     role_writes.insert(role_code.clone(), role.clone());
                     permission_writes.insert(role_code.clone(), stored_permissions);
@@ -273,6 +277,7 @@ impl<T: Config> Pallet<T> {
                 role,
                 change.assignments,
                 &current_admin_set,
+                &mut term_record_writes,
             )?;
             assignment_changes.insert(role_code, bounded);
         }
@@ -444,6 +449,13 @@ impl<T: Config> Pallet<T> {
                     assignments.clone(),
                 );
             }
+            for (role_code, account_id, record) in &term_record_writes {
+                AssignmentTermRecords::<T>::insert(
+                    (cid_number.clone(), role_code.clone()),
+                    account_id,
+                    record,
+                );
+            }
             InstitutionRoleNonce::<T>::insert(&cid_number, next_role_nonce);
             if let Some(change) = legal_representative_change {
                 Institutions::<T>::mutate(&cid_number, |maybe| {
@@ -488,6 +500,7 @@ impl<T: Config> Pallet<T> {
         role: &InstitutionRoleOf<T>,
         targets: Vec<entity_primitives::InstitutionAssignmentTarget<T::AccountId>>,
         current_admin_set: &BTreeSet<T::AccountId>,
+        term_record_writes: &mut TermRecordWritesOf<T>,
     ) -> Result<RoleAssignmentsOf<T>, sp_runtime::DispatchError> {
         ensure!(
             targets.len() as u32 <= T::MaxAdmins::get(),
@@ -523,6 +536,23 @@ impl<T: Config> Pallet<T> {
                 Error::<T>::DuplicateAssignment
             );
             Self::ensure_governance_assignment_term(role, target.term_start, target.term_end)?;
+            // 连任上限只约束选举产生的有任期任职；提名任免与内部治理不计入连任。
+            if role.term_required
+                && matches!(
+                    target.assignment_source,
+                    InstitutionAssignmentSource::PopularElection
+                        | InstitutionAssignmentSource::MutualElection
+                )
+            {
+                let record = Self::next_election_term_record(
+                    cid_number,
+                    role_code,
+                    &target.account_id,
+                    target.term_start,
+                    target.term_end,
+                )?;
+                term_record_writes.push((role_code.clone(), target.account_id.clone(), record));
+            }
             let assignment_source_ref: AssignmentSourceRefOf = target
                 .assignment_source_ref
                 .try_into()
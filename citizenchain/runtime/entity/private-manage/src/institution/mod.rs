@@ -10,6 +10,7 @@ pub mod accounts;
 pub mod governance;
 pub mod maintain;
 pub mod role;
+pub mod term;
 pub mod types;
 
 pub use types::*;
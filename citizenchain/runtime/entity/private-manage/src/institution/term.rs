@@ -0,0 +1,155 @@
+//! 岗位任职任期届满清理与选举连任上限。
+//!
+//! 任期日与 `is_assignment_effective` 同一口径（`UnixTime` 秒 / 86_400）。届满任职在
+//! `on_idle` 剩余权重内按游标分批移出任职集合，投票快照与授权查询不再读到过期任职人；
+//! 治理结果校验只接受 `Active` 任职，故届满任职直接移除而非原地改为 `Ended`，
+//! 历史由 `InstitutionRoleAssignmentExpired` 事件保留。法定代表人岗位任职届满时同步清空
+//! `InstitutionInfo.legal_representative`，保持二者一致。
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use entity_primitives::AssignmentTermRecord;
+use frame_support::{
+    ensure,
+    traits::{Get, UnixTime},
+    weights::Weight,
+};
+
+use crate::institution::role::{RoleAssignmentsOf, RoleCodeOf};
+use crate::pallet::{
+    AssignmentExpiryCursor, AssignmentTermRecords, CidNumberOf, Config, Error, Event,
+    InstitutionRoleAssignments, InstitutionRoles, Institutions, Pallet,
+};
+use crate::weights::WeightInfo;
+
+/// 治理结果中待写入的选举连任记录：(岗位, 任职账户, 新记录)。
+pub(crate) type TermRecordWritesOf<T> = Vec<(
+    RoleCodeOf,
+    <T as frame_system::Config>::AccountId,
+    AssignmentTermRecord,
+)>;
+
+impl<T: Config> Pallet<T> {
+    /// 当前自纪元起的天数；超出 u32 时视为不可判定，不做任何到期处理。
+    pub(crate) fn current_term_day() -> Option<u32> {
+        u32::try_from(<T::TimeProvider as UnixTime>::now().as_secs() / 86_400).ok()
+    }
+
+    /// 计算选举任职写入后的连任记录，并按 `MaxConsecutiveTerms` 拒绝超限连任。
+    pub(crate) fn next_election_term_record(
+        cid_number: &CidNumberOf<T>,
+        role_code: &RoleCodeOf,
+        account_id: &T::AccountId,
+        term_start: u32,
+        term_end: u32,
+    ) -> Result<AssignmentTermRecord, sp_runtime::DispatchError> {
+        let previous =
+            AssignmentTermRecords::<T>::get((cid_number.clone(), role_code.clone()), account_id);
+        let record = AssignmentTermRecord::next(previous, term_start, term_end);
+        let limit = T::MaxConsecutiveTerms::get();
+        ensure!(
+            limit == 0 || record.consecutive_terms <= limit,
+            Error::<T>::ConsecutiveTermLimitExceeded
+        );
+        Ok(record)
+    }
+
+    /// 在剩余权重内从游标处继续扫描任职集合，移除任期已届满的任职。
+    ///
+    /// 单块最多检查 `MaxAssignmentExpiryScan` 个 (机构, 岗位) 集合；扫描到表尾后清空游标，
+    /// 下一次从头开始。返回实际消耗的权重。
+    pub(crate) fn process_assignment_expiry(remaining_weight: Weight) -> Weight {
+        let step = T::WeightInfo::expire_role_assignments_step();
+        let mut used = T::DbWeight::get().reads_writes(1, 1);
+        if remaining_weight.any_lt(used.saturating_add(step)) {
+            return Weight::zero();
+        }
+        let Some(current_day) = Self::current_term_day() else {
+            return Weight::zero();
+        };
+        let mut iter = match AssignmentExpiryCursor::<T>::get() {
+            Some((cid_number, role_code)) => InstitutionRoleAssignments::<T>::iter_from(
+                InstitutionRoleAssignments::<T>::hashed_key_for(&cid_number, &role_code),
+            ),
+            None => InstitutionRoleAssignments::<T>::iter(),
+        };
+        let mut cursor = None;
+        let mut exhausted = false;
+        for _ in 0..T::MaxAssignmentExpiryScan::get() {
+            if remaining_weight.any_lt(used.saturating_add(step)) {
+                break;
+            }
+            let Some((cid_number, role_code, assignments)) = iter.next() else {
+                exhausted = true;
+                break;
+            };
+            used = used.saturating_add(step);
+            Self::expire_role_assignments(&cid_number, &role_code, assignments, current_day);
+            cursor = Some((cid_number, role_code));
+        }
+        match (exhausted, cursor) {
+            (false, Some(cursor)) => AssignmentExpiryCursor::<T>::put(cursor),
+            _ => AssignmentExpiryCursor::<T>::kill(),
+        }
+        used
+    }
+
+    fn expire_role_assignments(
+        cid_number: &CidNumberOf<T>,
+        role_code: &RoleCodeOf,
+        assignments: RoleAssignmentsOf<T>,
+        current_day: u32,
+    ) {
+        let Some(role) = InstitutionRoles::<T>::get(cid_number, role_code) else {
+            return;
+        };
+        if !role.term_required {
+            return;
+        }
+        let is_expired = |term_end: u32| term_end > 0 && term_end < current_day;
+        let expired = assignments
+            .iter()
+            .filter(|assignment| is_expired(assignment.term_end))
+            .map(|assignment| (assignment.account_id.clone(), assignment.term_end))
+            .collect::<Vec<_>>();
+        if expired.is_empty() {
+            return;
+        }
+        let mut kept = assignments;
+        kept.retain(|assignment| !is_expired(assignment.term_end));
+        if kept.is_empty() {
+            InstitutionRoleAssignments::<T>::remove(cid_number, role_code);
+        } else {
+            InstitutionRoleAssignments::<T>::insert(cid_number, role_code, kept);
+        }
+
+        let mut cleared_representative = None;
+        if primitives::institution_constraints::is_legal_representative_role(role_code.as_slice()) {
+            Institutions::<T>::mutate(cid_number, |maybe| {
+                if let Some(info) = maybe {
+                    let expired_representative =
+                        info.legal_representative.as_ref().is_some_and(|lr| {
+                            expired
+                                .iter()
+                                .any(|(account_id, _)| *account_id == lr.account_id)
+                        });
+                    if expired_representative {
+                        cleared_representative =
+                            info.legal_representative.take().map(|lr| lr.account_id);
+                    }
+                }
+            });
+        }
+        for (account_id, term_end) in expired {
+            let legal_representative_cleared = cleared_representative.as_ref() == Some(&account_id);
+            Self::deposit_event(Event::<T>::InstitutionRoleAssignmentExpired {
+                cid_number: cid_number.clone(),
+                role_code: role_code.clone(),
+                account_id,
+                term_end,
+                legal_representative_cleared,
+            });
+        }
+    }
+}
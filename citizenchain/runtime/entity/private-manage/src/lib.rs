@@ -10,6 +10,7 @@ pub mod add;
 mod benchmarks;
 pub mod close;
 pub mod institution;
+pub mod succession;
 pub mod traits;
 pub mod weights;
 
@@ -43,10 +44,13 @@ use votingengine::{
 };
 
 pub use entity_primitives::{
-    InstitutionAdminAssignment, InstitutionAssignmentSource, InstitutionAssignmentStatus,
-    InstitutionGovernanceAction, InstitutionGovernanceProposal, InstitutionGovernanceResult,
-    InstitutionRole, InstitutionRoleAuthorizationQuery, InstitutionRoleMutation,
-    InstitutionRoleStatus, RolePermissionOperation, RolePermissionSpec,
+    AssignmentTermRecord, InstitutionAdminAssignment, InstitutionAssignmentSource,
+    InstitutionAssignmentStatus, InstitutionGovernanceAction, InstitutionGovernanceProposal,
+    InstitutionGovernanceResult, InstitutionRole, InstitutionRoleAuthorizationQuery,
+    InstitutionRoleMutation, InstitutionRoleStatus, InstitutionSuccessionAction,
+    RolePermissionOperation, RolePermissionSpec, SuccessionBalanceTransfer,
+    SuccessionConsentAction, SuccessionKind, SuccessionRecord, SuccessionRoleTransfer,
+    MAX_INSTITUTION_SUCCESSORS,
 };
 pub use institution::role::{
     InstitutionAdminAssignmentOf, InstitutionAdminAssignmentsOf, InstitutionRoleOf,
@@ -125,6 +129,14 @@ pub mod pallet {
         #[pallet::constant]
         type MaxInstitutionAccounts: Get<u32>;
 
+        /// 同一管理员在同一岗位上经普选/互选连续当选的任期数上限；0 表示不限。
+        #[pallet::constant]
+        type MaxConsecutiveTerms: Get<u32>;
+
+        /// `on_idle` 单块最多检查的 (机构, 岗位) 任职集合数。
+        #[pallet::constant]
+        type MaxAssignmentExpiryScan: Get<u32>;
+
         type WeightInfo: crate::weights::WeightInfo;
     }
 
@@ -169,6 +181,33 @@ pub mod pallet {
         BalanceOf<T>,
         BlockNumberFor<T>,
     >;
+    /// 承继机构 CID 列表(合并 1 个,分立 2..=`MAX_INSTITUTION_SUCCESSORS` 个)。
+    pub type SuccessorCidNumbersOf<T> =
+        BoundedVec<CidNumberOf<T>, ConstU32<MAX_INSTITUTION_SUCCESSORS>>;
+    pub type SuccessionBalanceTransfersOf<T> = BoundedVec<
+        SuccessionBalanceTransfer<CidNumberOf<T>, AccountNameOf<T>>,
+        <T as Config>::MaxInstitutionAccounts,
+    >;
+    pub type SuccessionRoleTransfersOf<T> =
+        BoundedVec<SuccessionRoleTransfer<CidNumberOf<T>, RoleCodeOf>, <T as Config>::MaxAdmins>;
+    pub type InstitutionSuccessionActionOf<T> = InstitutionSuccessionAction<
+        <T as frame_system::Config>::AccountId,
+        CidNumberOf<T>,
+        AccountNameOf<T>,
+        RoleCodeOf,
+    >;
+    pub type SuccessionConsentActionOf<T> =
+        SuccessionConsentAction<<T as frame_system::Config>::AccountId, CidNumberOf<T>>;
+    /// 前身机构的永久承继记录。
+    pub type SuccessionRecordOf<T> = SuccessionRecord<
+        SuccessorCidNumbersOf<T>,
+        entity_primitives::LegalRepresentative<
+            AccountNameOf<T>,
+            CidNumberOf<T>,
+            <T as frame_system::Config>::AccountId,
+        >,
+        BlockNumberFor<T>,
+    >;
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
@@ -291,6 +330,82 @@ pub mod pallet {
     pub type InstitutionPendingAdd<T: Config> =
         StorageMap<_, Blake2_128Concat, CidNumberOf<T>, u64, OptionQuery>;
 
+    /// 前身机构 CID -> 承继记录。执行后永久保留,不随任何流程删除;
+    /// 命中即表示该机构已被合并/分立,不得再发起任何机构提案。
+    #[pallet::storage]
+    #[pallet::getter(fn successor_of)]
+    pub type SuccessorOf<T: Config> =
+        StorageMap<_, Blake2_128Concat, CidNumberOf<T>, SuccessionRecordOf<T>, OptionQuery>;
+
+    /// 承继机构 CID -> 前身机构 CID 反向索引,与 `SuccessorOf` 同时写入。
+    #[pallet::storage]
+    #[pallet::getter(fn predecessor_of)]
+    pub type PredecessorsOf<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        CidNumberOf<T>,
+        Blake2_128Concat,
+        CidNumberOf<T>,
+        (),
+        OptionQuery,
+    >;
+
+    /// (承继机构, 前身机构) -> 承继机构同意承继的已通过提案 ID;承继执行时消费。
+    #[pallet::storage]
+    #[pallet::getter(fn succession_consent)]
+    pub type SuccessionConsents<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        CidNumberOf<T>,
+        Blake2_128Concat,
+        CidNumberOf<T>,
+        u64,
+        OptionQuery,
+    >;
+
+    /// (承继机构, 前身机构) -> 进行中的同意承继提案 ID(防止并发重复提案)。
+    #[pallet::storage]
+    #[pallet::getter(fn pending_succession_consent)]
+    pub type PendingSuccessionConsents<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        CidNumberOf<T>,
+        Blake2_128Concat,
+        CidNumberOf<T>,
+        u64,
+        OptionQuery,
+    >;
+
+    /// 前身机构当前进行中的承继提案 ID;执行成功、否决或执行失败终态后清除。
+    #[pallet::storage]
+    #[pallet::getter(fn institution_pending_succession)]
+    pub type InstitutionPendingSuccession<T: Config> =
+        StorageMap<_, Blake2_128Concat, CidNumberOf<T>, u64, OptionQuery>;
+
+    /// 选举任职连任记录：((cid_number, role_code), account_id) -> 连续当选任期数。
+    /// 只由普选/互选结果写入，供 `MaxConsecutiveTerms` 校验。
+    #[pallet::storage]
+    #[pallet::getter(fn assignment_term_record)]
+    pub type AssignmentTermRecords<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (CidNumberOf<T>, crate::institution::role::RoleCodeOf),
+        Blake2_128Concat,
+        T::AccountId,
+        AssignmentTermRecord,
+        OptionQuery,
+    >;
+
+    /// 已承继、同意记录待 `on_idle` 分批清理的前身机构 CID;两张同意表删空后出队。
+    #[pallet::storage]
+    pub type SuccessionCleanupQueue<T: Config> =
+        StorageMap<_, Blake2_128Concat, CidNumberOf<T>, (), OptionQuery>;
+
+    /// 任期届满扫描游标：上一块最后检查的 (cid_number, role_code)；None 表示从表头开始。
+    #[pallet::storage]
+    pub type AssignmentExpiryCursor<T: Config> =
+        StorageValue<_, (CidNumberOf<T>, crate::institution::role::RoleCodeOf), OptionQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub _phantom: core::marker::PhantomData<T>,
@@ -401,6 +516,74 @@ pub mod pallet {
             proposal_id: u64,
             cid_number: CidNumberOf<T>,
         },
+        /// 承继机构同意承继提案已发起。
+        SuccessionConsentProposed {
+            proposal_id: u64,
+            successor_cid_number: CidNumberOf<T>,
+            predecessor_cid_number: CidNumberOf<T>,
+            proposer_account_id: T::AccountId,
+        },
+        /// 承继机构已以内部投票同意承继前身机构。
+        SuccessionConsented {
+            proposal_id: u64,
+            successor_cid_number: CidNumberOf<T>,
+            predecessor_cid_number: CidNumberOf<T>,
+        },
+        /// 同意承继执行失败。
+        SuccessionConsentExecutionFailed {
+            proposal_id: u64,
+            successor_cid_number: CidNumberOf<T>,
+        },
+        /// 前身机构合并/分立提案已发起。
+        InstitutionSuccessionProposed {
+            proposal_id: u64,
+            predecessor_cid_number: CidNumberOf<T>,
+            kind: SuccessionKind,
+            successor_count: u32,
+            proposer_account_id: T::AccountId,
+        },
+        /// 承继执行中前身账户余额已转入承继机构账户。
+        SuccessionBalanceTransferred {
+            proposal_id: u64,
+            source_account_id: T::AccountId,
+            target_account_id: T::AccountId,
+            amount: BalanceOf<T>,
+            fee: BalanceOf<T>,
+        },
+        /// 前身岗位定义与业务权限已复制为承继机构的新岗位(任职留空)。
+        InstitutionRoleInherited {
+            proposal_id: u64,
+            predecessor_cid_number: CidNumberOf<T>,
+            predecessor_role_code: RoleCodeOf,
+            successor_cid_number: CidNumberOf<T>,
+            successor_role_code: RoleCodeOf,
+        },
+        /// 承继完成:`SuccessorOf` 已永久指向承继机构。
+        InstitutionSucceeded {
+            proposal_id: u64,
+            predecessor_cid_number: CidNumberOf<T>,
+            kind: SuccessionKind,
+            successor_cid_numbers: SuccessorCidNumbersOf<T>,
+        },
+        /// 承继执行失败。
+        InstitutionSuccessionExecutionFailed {
+            proposal_id: u64,
+            predecessor_cid_number: CidNumberOf<T>,
+        },
+        /// 任职任期届满，已由 `on_idle` 移出岗位任职集合；法定代表人任职届满时
+        /// `legal_representative_cleared` 为 true，机构法定代表人字段同步清空。
+        InstitutionRoleAssignmentExpired {
+            cid_number: CidNumberOf<T>,
+            role_code: RoleCodeOf,
+            account_id: T::AccountId,
+            term_end: u32,
+            legal_representative_cleared: bool,
+        },
+        /// 承继执行时否决了前身机构仍在投票中的提案。
+        SuccessionProposalRejected {
+            predecessor_cid_number: CidNumberOf<T>,
+            proposal_id: u64,
+        },
     }
 
     #[pallet::error]
@@ -566,6 +749,31 @@ pub mod pallet {
         RoleNonceOverflow,
         /// 有限次碰撞重试后仍无法生成未使用岗位码。
         RoleCodeGenerationExhausted,
+        /// 机构已被合并/分立,不得再发起机构提案或作为承继方。
+        InstitutionSucceeded,
+        /// 承继机构不存在(承继只在本 pallet 内发生)。
+        SuccessorNotFound,
+        /// 承继类型与承继机构数量不符,或余额/岗位承继项非法、重复。
+        InvalidSuccession,
+        /// 已有进行中的承继或同意承继提案。
+        SuccessionAlreadyPending,
+        /// 承继机构尚未以内部投票同意承继。
+        SuccessorConsentMissing,
+        /// 承继机构已同意承继该前身机构。
+        SuccessionConsentAlreadyGranted,
+        /// 选举结果使该管理员在本岗位的连续任期数超过 `MaxConsecutiveTerms`。
+        ConsecutiveTermLimitExceeded,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 任期届满的任职与已承继机构的同意记录只在剩余权重内有界清理。
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let used = Self::process_assignment_expiry(remaining_weight);
+            used.saturating_add(Self::process_succession_cleanup(
+                remaining_weight.saturating_sub(used),
+            ))
+        }
     }
 
     /// 提案操作类型标记：存储在 ProposalData 的第一个字节。
@@ -575,6 +783,9 @@ pub mod pallet {
     /// 新增账户提案:仅用于 ProposalData 内部 finalizer 路由,与投票授权用的
     /// BusinessActionId(复用 `ACTION_INSTITUTION_CLOSE` 账户生命周期能力)相互正交。
     pub const ACTION_ADD_ACCOUNT: u8 = 4;
+    /// 合并/分立承继提案与承继机构同意提案;授权同样复用账户生命周期能力。
+    pub const ACTION_SUCCESSION: u8 = 5;
+    pub const ACTION_SUCCESSION_CONSENT: u8 = 6;
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -704,6 +915,53 @@ pub mod pallet {
         }
 
         // call_index(4) 已永久废弃：拒绝和执行失败清理由 votingengine 终态回调完成。
+
+        /// 前身机构发起合并/分立承继提案。
+        ///
+        /// 每个承继机构须已通过 `propose_succession_consent` 同意;通过后余额按
+        /// `balance_transfers` 整体转入、岗位按 `role_transfers` 复制定义,
+        /// 并写入永久 `SuccessorOf` 链接。
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::propose_institution_succession())]
+        #[allow(clippy::too_many_arguments)]
+        pub fn propose_institution_succession(
+            origin: OriginFor<T>,
+            predecessor_cid_number: CidNumberOf<T>,
+            kind: SuccessionKind,
+            successor_cid_numbers: SuccessorCidNumbersOf<T>,
+            balance_transfers: SuccessionBalanceTransfersOf<T>,
+            role_transfers: SuccessionRoleTransfersOf<T>,
+            proposer_role_code: RoleCodeOf,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            crate::succession::do_propose_institution_succession::<T>(
+                who,
+                predecessor_cid_number,
+                kind,
+                successor_cid_numbers,
+                balance_transfers,
+                role_transfers,
+                proposer_role_code,
+            )
+        }
+
+        /// 承继机构发起"同意承继指定前身机构"的内部投票提案。
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::propose_succession_consent())]
+        pub fn propose_succession_consent(
+            origin: OriginFor<T>,
+            successor_cid_number: CidNumberOf<T>,
+            predecessor_cid_number: CidNumberOf<T>,
+            proposer_role_code: RoleCodeOf,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            crate::succession::do_propose_succession_consent::<T>(
+                who,
+                successor_cid_number,
+                predecessor_cid_number,
+                proposer_role_code,
+            )
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -1000,6 +1258,13 @@ pub mod pallet {
             action_code: u32,
             business_data: &[u8],
         ) -> Result<VotePlanOf<T::AccountId>, sp_runtime::DispatchError> {
+            // 已被合并/分立的机构永久失去提案资格。
+            if let Ok(cid) = CidNumberOf::<T>::try_from(cid_number.to_vec()) {
+                ensure!(
+                    !SuccessorOf::<T>::contains_key(&cid),
+                    Error::<T>::InstitutionSucceeded
+                );
+            }
             let business_action_id = BusinessActionId {
                 module_tag: crate::MODULE_TAG.to_vec(),
                 action_code,
@@ -1107,6 +1372,10 @@ pub mod pallet {
                 .clone()
                 .try_into()
                 .map_err(|_| Error::<T>::InvalidAssignmentResultInstitution)?;
+            ensure!(
+                !SuccessorOf::<T>::contains_key(&cid_number),
+                Error::<T>::InstitutionSucceeded
+            );
             let result_source_ref = proposal_id.to_le_bytes().to_vec();
             match proposal.action {
                 InstitutionGovernanceAction::ReplaceAdmins { admins } => {
@@ -1191,6 +1460,7 @@ pub mod pallet {
 
         // 投票回调执行体:
         // - ACTION_CLOSE → crate::close::execute_institution_close_with_finalizer
+        // - ACTION_SUCCESSION / ACTION_SUCCESSION_CONSENT → crate::succession
         // (ACTION_CREATE_PERSONAL 在 personal-manage 独立 pallet)
     }
 }
@@ -1290,7 +1560,8 @@ impl<T: pallet::Config> traits::InstitutionLegalRepresentativeQuery<T::AccountId
 // 本 Executor(机构侧)按 `MODULE_TAG + ACTION 字节` 认领机构管理提案:
 // - `ACTION_ADD_ACCOUNT` + approved → 分派到 `add::execute_institution_add_account_with_finalizer`;
 // - `ACTION_CLOSE` + approved → 分派到 `close::execute_institution_close_with_finalizer`;
-// - `approved = false` → 清理对应 Pending(新增按 CID、关闭按账户),释放占用。
+// - `ACTION_SUCCESSION_CONSENT` / `ACTION_SUCCESSION` + approved → 分派到 `succession`;
+// - `approved = false` → 清理对应 Pending(新增/承继按 CID、关闭按账户),释放占用。
 // (ACTION_CREATE_PERSONAL 在 personal-manage::InternalVoteExecutor)
 pub struct InternalVoteExecutor<T>(core::marker::PhantomData<T>);
 
@@ -1384,6 +1655,55 @@ impl<T: pallet::Config> InternalVoteResultCallback for InternalVoteExecutor<T> {
                     }
                     return Ok(ProposalExecutionOutcome::Executed);
                 }
+                ACTION_SUCCESSION_CONSENT => {
+                    let action =
+                        pallet::SuccessionConsentActionOf::<T>::decode(&mut &raw[tag.len() + 1..])
+                            .map_err(|_| pallet::Error::<T>::ProposalActionNotFound)?;
+                    let exec_result = with_transaction(|| {
+                        match crate::succession::execute_succession_consent_with_finalizer::<T>(
+                            proposal_id,
+                            &action,
+                        ) {
+                            Ok(()) => TransactionOutcome::Commit(Ok(())),
+                            Err(e) => TransactionOutcome::Rollback(Err(e)),
+                        }
+                    });
+                    if exec_result.is_err() {
+                        pallet::Pallet::<T>::deposit_event(
+                            pallet::Event::<T>::SuccessionConsentExecutionFailed {
+                                proposal_id,
+                                successor_cid_number: action.successor_cid_number,
+                            },
+                        );
+                        return Ok(ProposalExecutionOutcome::RetryableFailed);
+                    }
+                    return Ok(ProposalExecutionOutcome::Executed);
+                }
+                ACTION_SUCCESSION => {
+                    let action = pallet::InstitutionSuccessionActionOf::<T>::decode(
+                        &mut &raw[tag.len() + 1..],
+                    )
+                    .map_err(|_| pallet::Error::<T>::ProposalActionNotFound)?;
+                    let exec_result = with_transaction(|| {
+                        match crate::succession::execute_institution_succession_with_finalizer::<T>(
+                            proposal_id,
+                            &action,
+                        ) {
+                            Ok(()) => TransactionOutcome::Commit(Ok(())),
+                            Err(e) => TransactionOutcome::Rollback(Err(e)),
+                        }
+                    });
+                    if exec_result.is_err() {
+                        pallet::Pallet::<T>::deposit_event(
+                            pallet::Event::<T>::InstitutionSuccessionExecutionFailed {
+                                proposal_id,
+                                predecessor_cid_number: action.predecessor_cid_number,
+                            },
+                        );
+                        return Ok(ProposalExecutionOutcome::RetryableFailed);
+                    }
+                    return Ok(ProposalExecutionOutcome::Executed);
+                }
                 _ => return Ok(ProposalExecutionOutcome::Ignored),
             }
         } else {
@@ -1403,6 +1723,21 @@ impl<T: pallet::Config> InternalVoteResultCallback for InternalVoteExecutor<T> {
                 ) {
                     InstitutionPendingClose::<T>::remove(&action.institution_account_id);
                 }
+            } else if action_byte == ACTION_SUCCESSION {
+                if let Ok(action) =
+                    pallet::InstitutionSuccessionActionOf::<T>::decode(&mut &raw[tag.len() + 1..])
+                {
+                    InstitutionPendingSuccession::<T>::remove(&action.predecessor_cid_number);
+                }
+            } else if action_byte == ACTION_SUCCESSION_CONSENT {
+                if let Ok(action) =
+                    pallet::SuccessionConsentActionOf::<T>::decode(&mut &raw[tag.len() + 1..])
+                {
+                    PendingSuccessionConsents::<T>::remove(
+                        &action.successor_cid_number,
+                        &action.predecessor_cid_number,
+                    );
+                }
             }
         }
         Ok(ProposalExecutionOutcome::Executed)
@@ -1432,6 +1767,18 @@ impl<T: pallet::Config> InternalVoteResultCallback for InternalVoteExecutor<T> {
             )
             .map_err(|_| pallet::Error::<T>::ProposalActionNotFound)?;
             InstitutionPendingClose::<T>::remove(&action.institution_account_id);
+        } else if raw[tag.len()] == ACTION_SUCCESSION {
+            let action =
+                pallet::InstitutionSuccessionActionOf::<T>::decode(&mut &raw[tag.len() + 1..])
+                    .map_err(|_| pallet::Error::<T>::ProposalActionNotFound)?;
+            InstitutionPendingSuccession::<T>::remove(&action.predecessor_cid_number);
+        } else if raw[tag.len()] == ACTION_SUCCESSION_CONSENT {
+            let action = pallet::SuccessionConsentActionOf::<T>::decode(&mut &raw[tag.len() + 1..])
+                .map_err(|_| pallet::Error::<T>::ProposalActionNotFound)?;
+            PendingSuccessionConsents::<T>::remove(
+                &action.successor_cid_number,
+                &action.predecessor_cid_number,
+            );
         }
         Ok(())
     }
@@ -1042,3 +1042,346 @@ fn non_admin_cannot_start_institution_account_close() {
         );
     });
 }
+
+#[test]
+fn approved_merger_moves_balance_and_blocks_predecessor_proposals() {
+    new_test_ext().execute_with(|| {
+        let role_code: crate::RoleCodeOf =
+            b"TEST_CLOSE_ROLE".to_vec().try_into().expect("role fits");
+        let predecessor = generated_cid("private-merge-from", "SFLP");
+        let successor = generated_cid("private-merge-into", "SFLP");
+        assert_ok!(create_institution(
+            predecessor.clone(),
+            code_bytes("SFLP"),
+            initial_accounts(&[
+                (crate::RESERVED_NAME_MAIN, 0),
+                (crate::RESERVED_NAME_FEE, 1_000),
+                ("项目账户".as_bytes(), 1_000),
+            ]),
+        ));
+        assert_ok!(create_institution(
+            successor.clone(),
+            code_bytes("SFLP"),
+            initial_accounts(&[
+                (crate::RESERVED_NAME_MAIN, 0),
+                (crate::RESERVED_NAME_FEE, 0)
+            ]),
+        ));
+        let merger = |origin| {
+            PrivateManage::propose_institution_succession(
+                origin,
+                predecessor.clone(),
+                crate::SuccessionKind::Merger,
+                alloc::vec![successor.clone()]
+                    .try_into()
+                    .expect("successors fit"),
+                alloc::vec![crate::SuccessionBalanceTransfer {
+                    source_account_name: account_name("项目账户".as_bytes()),
+                    successor_cid_number: successor.clone(),
+                    successor_account_name: account_name(crate::RESERVED_NAME_MAIN),
+                }]
+                .try_into()
+                .expect("balance transfers fit"),
+                Default::default(),
+                b"TEST_CLOSE_ROLE".to_vec().try_into().expect("role fits"),
+            )
+        };
+        assert_noop!(
+            merger(RuntimeOrigin::signed(admin(1))),
+            Error::<Test>::SuccessorConsentMissing
+        );
+
+        assert_ok!(PrivateManage::propose_succession_consent(
+            RuntimeOrigin::signed(admin(1)),
+            successor.clone(),
+            predecessor.clone(),
+            role_code.clone(),
+        ));
+        assert_ok!(cast_yes_votes(
+            VotingEngine::next_proposal_id().saturating_sub(1)
+        ));
+        assert_ok!(merger(RuntimeOrigin::signed(admin(1))));
+        let proposal_id = VotingEngine::next_proposal_id().saturating_sub(1);
+        assert_ok!(cast_yes_votes(proposal_id));
+
+        assert_eq!(
+            Balances::free_balance(account_of(&successor, crate::RESERVED_NAME_MAIN)),
+            1_000
+        );
+        assert_eq!(
+            Balances::free_balance(account_of(&predecessor, crate::RESERVED_NAME_FEE)),
+            990
+        );
+        let record = pallet::SuccessorOf::<Test>::get(&predecessor).expect("successor linked");
+        assert_eq!(record.proposal_id, proposal_id);
+        assert!(pallet::PredecessorsOf::<Test>::contains_key(
+            &successor,
+            &predecessor
+        ));
+        assert_noop!(
+            propose_add_custom_account(
+                RuntimeOrigin::signed(admin(1)),
+                predecessor.clone(),
+                &["承继后新增".as_bytes()],
+            ),
+            Error::<Test>::InstitutionSucceeded
+        );
+    });
+}
+
+#[test]
+fn succession_rejects_live_predecessor_proposals_and_cleans_consents_on_idle() {
+    new_test_ext().execute_with(|| {
+        use frame_support::{traits::Hooks, weights::Weight};
+
+        let role_code: crate::RoleCodeOf =
+            b"TEST_CLOSE_ROLE".to_vec().try_into().expect("role fits");
+        let predecessor = generated_cid("private-sweep-from", "SFLP");
+        let successor = generated_cid("private-sweep-into", "SFLP");
+        let other = generated_cid("private-sweep-other", "SFLP");
+        for cid in [&predecessor, &successor] {
+            assert_ok!(create_institution(
+                cid.clone(),
+                code_bytes("SFLP"),
+                initial_accounts(&[
+                    (crate::RESERVED_NAME_MAIN, 0),
+                    (crate::RESERVED_NAME_FEE, 1_000)
+                ]),
+            ));
+        }
+        // 前身此前已同意承继另一机构,承继后该记录须被分批清理。
+        pallet::SuccessionConsents::<Test>::insert(&predecessor, &other, 7);
+
+        assert_ok!(PrivateManage::propose_succession_consent(
+            RuntimeOrigin::signed(admin(1)),
+            successor.clone(),
+            predecessor.clone(),
+            role_code.clone(),
+        ));
+        assert_ok!(cast_yes_votes(
+            VotingEngine::next_proposal_id().saturating_sub(1)
+        ));
+        assert_ok!(propose_add_custom_account(
+            RuntimeOrigin::signed(admin(1)),
+            predecessor.clone(),
+            &["承继前新增".as_bytes()],
+        ));
+        let live_add = VotingEngine::next_proposal_id().saturating_sub(1);
+        assert_ok!(PrivateManage::propose_institution_succession(
+            RuntimeOrigin::signed(admin(1)),
+            predecessor.clone(),
+            crate::SuccessionKind::Merger,
+            alloc::vec![successor.clone()]
+                .try_into()
+                .expect("successors fit"),
+            Default::default(),
+            Default::default(),
+            role_code,
+        ));
+        assert_ok!(cast_yes_votes(
+            VotingEngine::next_proposal_id().saturating_sub(1)
+        ));
+
+        assert!(pallet::SuccessorOf::<Test>::contains_key(&predecessor));
+        assert_eq!(
+            VotingEngine::proposals(live_add).map(|proposal| proposal.status),
+            Some(votingengine::STATUS_REJECTED)
+        );
+        assert!(!pallet::InstitutionPendingAdd::<Test>::contains_key(
+            &predecessor
+        ));
+
+        assert!(pallet::SuccessionCleanupQueue::<Test>::contains_key(
+            &predecessor
+        ));
+        PrivateManage::on_idle(System::block_number(), Weight::MAX);
+        assert!(!pallet::SuccessionConsents::<Test>::contains_key(
+            &predecessor,
+            &other
+        ));
+        assert!(!pallet::SuccessionCleanupQueue::<Test>::contains_key(
+            &predecessor
+        ));
+    });
+}
+
+fn termed_assignment(
+    account_id: AccountId32,
+    term_start: u32,
+    term_end: u32,
+) -> entity_primitives::InstitutionAssignmentTarget<AccountId32> {
+    let mut assignment = governance_assignment(account_id);
+    assignment.term_start = term_start;
+    assignment.term_end = term_end;
+    assignment
+}
+
+fn vote_permission() -> entity_primitives::RolePermissionSpec {
+    entity_primitives::RolePermissionSpec {
+        business_action_id: entity_primitives::BusinessActionId {
+            module_tag: b"pri-mgmt".to_vec(),
+            action_code: 3,
+        },
+        operation: entity_primitives::RolePermissionOperation::Vote,
+    }
+}
+
+fn create_sflp(tag: &str) -> pallet::CidNumberOf<Test> {
+    let cid_number = generated_cid(tag, "SFLP");
+    assert_ok!(create_institution(
+        cid_number.clone(),
+        code_bytes("SFLP"),
+        initial_accounts(&[
+            (crate::RESERVED_NAME_MAIN, 0),
+            (crate::RESERVED_NAME_FEE, 0),
+        ]),
+    ));
+    cid_number
+}
+
+#[test]
+fn on_idle_removes_expired_assignments_and_reports_them() {
+    new_test_ext().execute_with(|| {
+        use entity_primitives::InstitutionRoleMutation;
+        use frame_support::{traits::Hooks, weights::Weight};
+
+        let cid_number = create_sflp("expire-role-term");
+        let role_code = entity_primitives::generate_dynamic_role_code(
+            crate::MODULE_TAG,
+            cid_number.as_slice(),
+            0,
+            78,
+        );
+        assert_ok!(PrivateManage::apply_institution_governance_result(
+            entity_primitives::InstitutionGovernanceResult {
+                institution_code: code_bytes("SFLP"),
+                cid_number: cid_number.to_vec(),
+                proposal_id: 78,
+                role_mutations: vec![InstitutionRoleMutation::Create {
+                    role_name: "届满岗位".as_bytes().to_vec(),
+                    term_required: true,
+                    permissions: vec![vote_permission()],
+                    assignments: vec![
+                        termed_assignment(admin(1), 20_635, 20_700),
+                        termed_assignment(admin(2), 20_600, 20_634),
+                    ],
+                }],
+                assignment_changes: vec![],
+                legal_representative_change: None,
+                result_source_ref: b"proposal-78".to_vec(),
+            }
+        ));
+
+        // 权重不足时不做任何扫描。
+        assert_eq!(
+            PrivateManage::on_idle(System::block_number(), Weight::zero()),
+            Weight::zero()
+        );
+        assert_eq!(
+            pallet::InstitutionRoleAssignments::<Test>::get(&cid_number, &role_code).len(),
+            2
+        );
+
+        // 测试上限每块只检查 4 个任职集合，按游标推进直到扫描回到表头。
+        for _ in 0..64 {
+            PrivateManage::on_idle(System::block_number(), Weight::MAX);
+            if pallet::AssignmentExpiryCursor::<Test>::get().is_none() {
+                break;
+            }
+        }
+        let remaining = pallet::InstitutionRoleAssignments::<Test>::get(&cid_number, &role_code);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].account_id, admin(1));
+        assert!(System::events().iter().any(|record| {
+            record.event
+                == RuntimeEvent::PrivateManage(pallet::Event::InstitutionRoleAssignmentExpired {
+                    cid_number: cid_number.clone(),
+                    role_code: role_code.clone(),
+                    account_id: admin(2),
+                    term_end: 20_634,
+                    legal_representative_cleared: false,
+                })
+        }));
+    });
+}
+
+#[test]
+fn election_results_respect_consecutive_term_limit() {
+    new_test_ext().execute_with(|| {
+        use entity_primitives::{InstitutionRoleAssignmentChange, InstitutionRoleMutation};
+
+        let cid_number = create_sflp("consecutive-term");
+        let role_code = entity_primitives::generate_dynamic_role_code(
+            crate::MODULE_TAG,
+            cid_number.as_slice(),
+            0,
+            79,
+        );
+        let elected = |term_start: u32, term_end: u32| {
+            let mut assignment = termed_assignment(admin(1), term_start, term_end);
+            assignment.assignment_source =
+                entity_primitives::InstitutionAssignmentSource::MutualElection;
+            assignment
+        };
+        let result = |proposal_id: u64, role_mutations, assignment_changes| {
+            entity_primitives::InstitutionGovernanceResult {
+                institution_code: code_bytes("SFLP"),
+                cid_number: cid_number.to_vec(),
+                proposal_id,
+                role_mutations,
+                assignment_changes,
+                legal_representative_change: None,
+                result_source_ref: b"election-result".to_vec(),
+            }
+        };
+        let reelect = |term_start: u32, term_end: u32| {
+            vec![InstitutionRoleAssignmentChange {
+                role_code: role_code.to_vec(),
+                assignments: vec![elected(term_start, term_end)],
+            }]
+        };
+        assert_ok!(PrivateManage::apply_institution_governance_result(result(
+            79,
+            vec![InstitutionRoleMutation::Create {
+                role_name: "互选岗位".as_bytes().to_vec(),
+                term_required: true,
+                permissions: vec![vote_permission()],
+                assignments: vec![elected(20_000, 20_364)],
+            }],
+            vec![],
+        )));
+        // 重复提交同一任期不计为连任。
+        assert_ok!(PrivateManage::apply_institution_governance_result(result(
+            80,
+            vec![],
+            reelect(20_000, 20_364),
+        )));
+        assert_ok!(PrivateManage::apply_institution_governance_result(result(
+            81,
+            vec![],
+            reelect(20_365, 20_729),
+        )));
+        assert_eq!(
+            pallet::AssignmentTermRecords::<Test>::get(
+                (cid_number.clone(), role_code.clone()),
+                admin(1)
+            )
+            .map(|record| record.consecutive_terms),
+            Some(2)
+        );
+        assert_noop!(
+            PrivateManage::apply_institution_governance_result(result(
+                82,
+                vec![],
+                reelect(20_730, 21_094),
+            )),
+            Error::<Test>::ConsecutiveTermLimitExceeded
+        );
+        // 任期间隔后重新当选，连任计数重置。
+        assert_ok!(PrivateManage::apply_institution_governance_result(result(
+            83,
+            vec![],
+            reelect(21_100, 21_464),
+        )));
+    });
+}
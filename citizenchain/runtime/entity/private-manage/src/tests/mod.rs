@@ -437,6 +437,8 @@ impl pallet::Config for Test {
     type MaxCidNumberLength = ConstU32<{ primitives::core_const::CID_NUMBER_MAX_BYTES }>;
     type MaxAccountNameLength = ConstU32<128>;
     type MaxInstitutionAccounts = ConstU32<8>;
+    type MaxConsecutiveTerms = ConstU32<2>;
+    type MaxAssignmentExpiryScan = ConstU32<4>;
     type WeightInfo = ();
 }
 
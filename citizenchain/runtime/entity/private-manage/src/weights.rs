@@ -19,6 +19,13 @@ pub trait WeightInfo {
 	fn propose_institution_governance() -> Weight;
 	/// 机构岗位任职人发起关闭提案。
 	fn propose_close_private_institution() -> Weight;
+	/// 前身机构岗位任职人发起合并/分立承继提案。
+	fn propose_institution_succession() -> Weight;
+	/// 承继机构岗位任职人发起同意承继提案。
+	fn propose_succession_consent() -> Weight;
+	/// `on_idle` 检查并清理单个 (机构, 岗位) 任职集合中的届满任职。
+	fn expire_role_assignments_step() -> Weight;
+	fn clear_succession_consents_step() -> Weight;
 }
 
 pub struct SubstrateWeight<T>(PhantomData<T>);
@@ -49,6 +56,32 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(35))
 			.saturating_add(T::DbWeight::get().writes(30))
 	}
+	fn propose_institution_succession() -> Weight {
+		Weight::from_parts(400_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 700_000))
+			.saturating_add(T::DbWeight::get().reads(35))
+			.saturating_add(T::DbWeight::get().writes(30))
+	}
+	fn propose_succession_consent() -> Weight {
+		Weight::from_parts(400_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 700_000))
+			.saturating_add(T::DbWeight::get().reads(35))
+			.saturating_add(T::DbWeight::get().writes(30))
+	}
+	fn expire_role_assignments_step() -> Weight {
+		// 读任职集合、岗位定义、机构信息；写回任职集合与机构信息。
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 12_000))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn clear_succession_consents_step() -> Weight {
+		// 两张同意表各删至多 SUCCESSION_CLEANUP_BATCH(64) 条,另读写一次清理队列。
+		Weight::from_parts(200_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 200_000))
+			.saturating_add(T::DbWeight::get().reads(129))
+			.saturating_add(T::DbWeight::get().writes(129))
+	}
 }
 
 impl WeightInfo for () {
@@ -76,4 +109,30 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(35))
 			.saturating_add(RocksDbWeight::get().writes(30))
 	}
+	fn propose_institution_succession() -> Weight {
+		Weight::from_parts(400_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 700_000))
+			.saturating_add(RocksDbWeight::get().reads(35))
+			.saturating_add(RocksDbWeight::get().writes(30))
+	}
+	fn propose_succession_consent() -> Weight {
+		Weight::from_parts(400_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 700_000))
+			.saturating_add(RocksDbWeight::get().reads(35))
+			.saturating_add(RocksDbWeight::get().writes(30))
+	}
+	fn expire_role_assignments_step() -> Weight {
+		// 读任职集合、岗位定义、机构信息；写回任职集合与机构信息。
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 12_000))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn clear_succession_consents_step() -> Weight {
+		// 两张同意表各删至多 SUCCESSION_CLEANUP_BATCH(64) 条,另读写一次清理队列。
+		Weight::from_parts(200_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 200_000))
+			.saturating_add(RocksDbWeight::get().reads(129))
+			.saturating_add(RocksDbWeight::get().writes(129))
+	}
 }
@@ -0,0 +1,572 @@
+//! 公权机构合并/分立承继流程(call_index=10/11)。
+//!
+//! 两段式投票:每个承继机构先以本机构内部投票同意承继(call 11,通过后写
+//! `SuccessionConsents[承继][前身]`);前身再发起承继提案(call 10),通过后
+//! finalizer 一次性完成余额转移、岗位定义复制、法定代表人解除与 `SuccessorOf` 永久链接。
+//! 前身机构及其协议账户不删除,只是从此不能再发起任何机构提案;执行时否决前身
+//! 仍在投票中的提案,它作为承继方留下的同意记录由 `on_idle` 分批清理。
+//! 授权复用 `ACTION_INSTITUTION_CLOSE` 账户生命周期能力,与新增/关闭账户同源。
+
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use codec::Encode;
+use entity_primitives::{
+    InstitutionCapabilityPolicy as _, InstitutionMultisigQuery as _, InstitutionRole,
+    InstitutionRoleStatus, RoleBusinessPermission, RoleSubject, SuccessionKind, SuccessionRecord,
+};
+use frame_support::{
+    ensure,
+    traits::{Currency, ExistenceRequirement, Get},
+    weights::Weight,
+};
+use primitives::institution_asset::{InstitutionAsset, InstitutionAssetAction};
+use primitives::{account_derive::RESERVED_NAME_FEE, fee_policy::OnchainFeeCharger};
+use sp_runtime::{traits::Zero, DispatchError, DispatchResult};
+use votingengine::InternalVoteEngine;
+
+use crate::institution::role::RolePermissionsOf;
+use crate::pallet::{
+    CidNumberOf, Config, Error, Event, InstitutionAccounts, InstitutionInfoOf,
+    InstitutionPendingAdd, InstitutionPendingClose, InstitutionPendingSuccession,
+    InstitutionRoleAssignments, InstitutionRoleNonce, InstitutionRolePermissions, InstitutionRoles,
+    InstitutionSuccessionActionOf, Institutions, Pallet, PendingSuccessionConsents, PredecessorsOf,
+    SuccessionBalanceTransfersOf, SuccessionCleanupQueue, SuccessionConsentActionOf,
+    SuccessionConsents, SuccessionRoleTransfersOf, SuccessorCidNumbersOf, SuccessorOf,
+    UsedRoleCodes, ACTION_SUCCESSION, ACTION_SUCCESSION_CONSENT,
+};
+use crate::traits::ProtectedSourceChecker;
+use crate::weights::WeightInfo;
+use crate::{BalanceOf, RoleCodeOf};
+
+/// `on_idle` 每步对单张同意表删除的条目上限。
+pub(crate) const SUCCESSION_CLEANUP_BATCH: u32 = 64;
+
+/// 承继机构发起"同意承继某前身机构"提案。
+pub(crate) fn do_propose_succession_consent<T: Config>(
+    who: T::AccountId,
+    successor_cid_number: CidNumberOf<T>,
+    predecessor_cid_number: CidNumberOf<T>,
+    proposer_role_code: RoleCodeOf,
+) -> DispatchResult {
+    let info = ensure_consent_valid::<T>(&successor_cid_number, &predecessor_cid_number)?;
+    ensure!(
+        !PendingSuccessionConsents::<T>::contains_key(
+            &successor_cid_number,
+            &predecessor_cid_number
+        ),
+        Error::<T>::SuccessionAlreadyPending
+    );
+
+    let action = SuccessionConsentActionOf::<T> {
+        successor_cid_number: successor_cid_number.clone(),
+        predecessor_cid_number: predecessor_cid_number.clone(),
+        proposer_account_id: who.clone(),
+    };
+    let mut data = Vec::from(crate::MODULE_TAG);
+    data.push(ACTION_SUCCESSION_CONSENT);
+    data.extend_from_slice(&action.encode());
+    let vote_plan = Pallet::<T>::build_institution_vote_plan(
+        &who,
+        successor_cid_number.as_slice(),
+        proposer_role_code.as_slice(),
+        entity_primitives::business_action::ACTION_INSTITUTION_CLOSE,
+        &data,
+    )?;
+    let proposal_id = T::InternalVoteEngine::create_institution_proposal_with_data(
+        who.clone(),
+        info.institution_code,
+        successor_cid_number.to_vec(),
+        None,
+        alloc::vec![successor_cid_number.to_vec()],
+        vote_plan,
+        data,
+    )?;
+    PendingSuccessionConsents::<T>::insert(
+        &successor_cid_number,
+        &predecessor_cid_number,
+        proposal_id,
+    );
+
+    Pallet::<T>::deposit_event(Event::<T>::SuccessionConsentProposed {
+        proposal_id,
+        successor_cid_number,
+        predecessor_cid_number,
+        proposer_account_id: who,
+    });
+    Ok(())
+}
+
+/// 同意承继投票通过回调执行体:写入同意记录,供前身承继提案消费。
+pub(crate) fn execute_succession_consent_with_finalizer<T: Config>(
+    proposal_id: u64,
+    action: &SuccessionConsentActionOf<T>,
+) -> DispatchResult {
+    let info =
+        ensure_consent_valid::<T>(&action.successor_cid_number, &action.predecessor_cid_number)?;
+    ensure_passed_proposal::<T>(proposal_id, &info, action.successor_cid_number.as_slice())?;
+    ensure!(
+        PendingSuccessionConsents::<T>::get(
+            &action.successor_cid_number,
+            &action.predecessor_cid_number
+        ) == Some(proposal_id),
+        Error::<T>::ProposalActionNotFound
+    );
+
+    SuccessionConsents::<T>::insert(
+        &action.successor_cid_number,
+        &action.predecessor_cid_number,
+        proposal_id,
+    );
+    PendingSuccessionConsents::<T>::remove(
+        &action.successor_cid_number,
+        &action.predecessor_cid_number,
+    );
+    Pallet::<T>::deposit_event(Event::<T>::SuccessionConsented {
+        proposal_id,
+        successor_cid_number: action.successor_cid_number.clone(),
+        predecessor_cid_number: action.predecessor_cid_number.clone(),
+    });
+    Ok(())
+}
+
+/// 前身机构发起"合并/分立"承继提案。
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn do_propose_institution_succession<T: Config>(
+    who: T::AccountId,
+    predecessor_cid_number: CidNumberOf<T>,
+    kind: SuccessionKind,
+    successor_cid_numbers: SuccessorCidNumbersOf<T>,
+    balance_transfers: SuccessionBalanceTransfersOf<T>,
+    role_transfers: SuccessionRoleTransfersOf<T>,
+    proposer_role_code: RoleCodeOf,
+) -> DispatchResult {
+    ensure!(
+        !InstitutionPendingSuccession::<T>::contains_key(&predecessor_cid_number),
+        Error::<T>::SuccessionAlreadyPending
+    );
+    let action = InstitutionSuccessionActionOf::<T> {
+        kind,
+        predecessor_cid_number: predecessor_cid_number.clone(),
+        successor_cid_numbers: successor_cid_numbers.into_inner(),
+        balance_transfers: balance_transfers.into_inner(),
+        role_transfers: role_transfers.into_inner(),
+        proposer_account_id: who.clone(),
+    };
+    let info = ensure_succession_valid::<T>(&action)?;
+
+    let mut data = Vec::from(crate::MODULE_TAG);
+    data.push(ACTION_SUCCESSION);
+    data.extend_from_slice(&action.encode());
+    let vote_plan = Pallet::<T>::build_institution_vote_plan(
+        &who,
+        predecessor_cid_number.as_slice(),
+        proposer_role_code.as_slice(),
+        entity_primitives::business_action::ACTION_INSTITUTION_CLOSE,
+        &data,
+    )?;
+    let proposal_id = T::InternalVoteEngine::create_institution_proposal_with_data(
+        who.clone(),
+        info.institution_code,
+        predecessor_cid_number.to_vec(),
+        None,
+        alloc::vec![predecessor_cid_number.to_vec()],
+        vote_plan,
+        data,
+    )?;
+    InstitutionPendingSuccession::<T>::insert(&predecessor_cid_number, proposal_id);
+
+    Pallet::<T>::deposit_event(Event::<T>::InstitutionSuccessionProposed {
+        proposal_id,
+        predecessor_cid_number,
+        kind,
+        successor_count: action.successor_cid_numbers.len() as u32,
+        proposer_account_id: who,
+    });
+    Ok(())
+}
+
+/// 承继投票通过回调执行体:重校验后转移余额、复制岗位、解除法定代表人并写入永久链接。
+pub(crate) fn execute_institution_succession_with_finalizer<T: Config>(
+    proposal_id: u64,
+    action: &InstitutionSuccessionActionOf<T>,
+) -> DispatchResult {
+    let predecessor = &action.predecessor_cid_number;
+    let mut info = ensure_succession_valid::<T>(action)?;
+    ensure_passed_proposal::<T>(proposal_id, &info, predecessor.as_slice())?;
+    ensure!(
+        InstitutionPendingSuccession::<T>::get(predecessor) == Some(proposal_id),
+        Error::<T>::ProposalActionNotFound
+    );
+
+    // 余额:手续费统一从前身费用账户扣取,费用账户自身作为来源时排在最后,
+    // 保证此前每笔转移的手续费都能从它扣到。
+    let fee_account =
+        T::InstitutionQuery::lookup_institution_account(predecessor.as_slice(), RESERVED_NAME_FEE)
+            .ok_or(Error::<T>::FeeWithdrawFailed)?;
+    let mut transfers = Vec::with_capacity(action.balance_transfers.len());
+    for transfer in action.balance_transfers.iter() {
+        let source = InstitutionAccounts::<T>::get(predecessor, &transfer.source_account_name)
+            .ok_or(Error::<T>::AccountNotFound)?
+            .account_id;
+        let target = InstitutionAccounts::<T>::get(
+            &transfer.successor_cid_number,
+            &transfer.successor_account_name,
+        )
+        .ok_or(Error::<T>::AccountNotFound)?
+        .account_id;
+        transfers.push((source, target));
+    }
+    transfers.sort_by_key(|(source, _)| *source == fee_account);
+    for (source, target) in transfers {
+        let balance = T::Currency::free_balance(&source);
+        if balance.is_zero() {
+            continue;
+        }
+        let fee = T::OnchainFeeCharger::charge(&fee_account, balance)
+            .map_err(|_| Error::<T>::FeeWithdrawFailed)?;
+        // 来源即费用账户时,扣费后按剩余可用余额整体转出。
+        let amount: BalanceOf<T> = T::Currency::free_balance(&source);
+        if !amount.is_zero() {
+            T::Currency::transfer(&source, &target, amount, ExistenceRequirement::AllowDeath)
+                .map_err(|_| Error::<T>::TransferFailed)?;
+        }
+        Pallet::<T>::deposit_event(Event::<T>::SuccessionBalanceTransferred {
+            proposal_id,
+            source_account_id: source,
+            target_account_id: target,
+            amount,
+            fee,
+        });
+    }
+
+    // 岗位:只复制定义与业务权限,任职留空由承继机构自身治理补任。
+    let mut role_nonces = BTreeMap::<CidNumberOf<T>, u64>::new();
+    for transfer in action.role_transfers.iter() {
+        let successor = &transfer.successor_cid_number;
+        let source_role = InstitutionRoles::<T>::get(predecessor, &transfer.role_code)
+            .ok_or(Error::<T>::AssignmentRoleNotFound)?;
+        ensure!(
+            !InstitutionRoles::<T>::iter_prefix(successor)
+                .any(|(_, existing)| existing.role_name == source_role.role_name),
+            Error::<T>::DuplicateRoleName
+        );
+        let nonce = role_nonces
+            .get(successor)
+            .copied()
+            .unwrap_or_else(|| InstitutionRoleNonce::<T>::get(successor));
+        let (role_code, next_nonce) =
+            Pallet::<T>::allocate_dynamic_role_code(successor, nonce, proposal_id)?;
+        role_nonces.insert(successor.clone(), next_nonce);
+        let mut permissions = Vec::new();
+        for permission in InstitutionRolePermissions::<T>::get(predecessor, &transfer.role_code) {
+            let business_action_id = entity_primitives::BusinessActionId {
+                module_tag: permission.business_action_id.module_tag.to_vec(),
+                action_code: permission.business_action_id.action_code,
+            };
+            ensure!(
+                T::InstitutionCapabilityPolicy::allows(
+                    successor.as_slice(),
+                    &business_action_id,
+                    permission.operation,
+                ),
+                Error::<T>::InstitutionCapabilityDenied
+            );
+            permissions.push(RoleBusinessPermission {
+                role_subject: RoleSubject {
+                    cid_number: successor.clone(),
+                    role_code: role_code.clone(),
+                },
+                business_action_id: permission.business_action_id,
+                operation: permission.operation,
+            });
+        }
+        ensure!(!permissions.is_empty(), Error::<T>::RolePermissionsEmpty);
+        let permissions: RolePermissionsOf<T> = permissions
+            .try_into()
+            .map_err(|_| Error::<T>::TooManyRolePermissions)?;
+        InstitutionRoles::<T>::insert(
+            successor,
+            &role_code,
+            InstitutionRole {
+                cid_number: successor.clone(),
+                role_code: role_code.clone(),
+                role_name: source_role.role_name,
+                term_required: source_role.term_required,
+                role_status: InstitutionRoleStatus::Active,
+            },
+        );
+        InstitutionRolePermissions::<T>::insert(successor, &role_code, permissions);
+        UsedRoleCodes::<T>::insert(successor, &role_code, true);
+        InstitutionRoleNonce::<T>::insert(successor, next_nonce);
+        Pallet::<T>::deposit_event(Event::<T>::InstitutionRoleInherited {
+            proposal_id,
+            predecessor_cid_number: predecessor.clone(),
+            predecessor_role_code: transfer.role_code.clone(),
+            successor_cid_number: successor.clone(),
+            successor_role_code: role_code,
+        });
+    }
+
+    // 前身所有进行中的提案一律否决;已通过待执行的账户提案随 Pending 锁释放而失效。
+    // 账户数以 `MaxInstitutionAccounts` 为界;它作为承继方给出的同意数量无界,
+    // 登记进清理队列由 `on_idle` 分批删除。
+    reject_live_proposals::<T>(predecessor)?;
+    InstitutionPendingAdd::<T>::remove(predecessor);
+    for (_, account) in InstitutionAccounts::<T>::iter_prefix(predecessor) {
+        InstitutionPendingClose::<T>::remove(&account.account_id);
+    }
+    SuccessionCleanupQueue::<T>::insert(predecessor, ());
+    for successor in action.successor_cid_numbers.iter() {
+        SuccessionConsents::<T>::remove(successor, predecessor);
+        PredecessorsOf::<T>::insert(successor, predecessor, ());
+    }
+
+    // 法定代表人:前身不再对外代表,字段与 LR 岗位任职同步清空以维持一致性不变量。
+    let predecessor_legal_representative = info.legal_representative.take();
+    Institutions::<T>::insert(predecessor, info);
+    let legal_role_code: RoleCodeOf =
+        primitives::institution_constraints::ROLE_CODE_LEGAL_REPRESENTATIVE
+            .to_vec()
+            .try_into()
+            .map_err(|_| Error::<T>::InvalidRoleCode)?;
+    InstitutionRoleAssignments::<T>::remove(predecessor, &legal_role_code);
+
+    let successor_cid_numbers: SuccessorCidNumbersOf<T> = action
+        .successor_cid_numbers
+        .clone()
+        .try_into()
+        .map_err(|_| Error::<T>::InvalidSuccession)?;
+    SuccessorOf::<T>::insert(
+        predecessor,
+        SuccessionRecord {
+            kind: action.kind,
+            successor_cid_numbers: successor_cid_numbers.clone(),
+            predecessor_legal_representative,
+            proposal_id,
+            succeeded_at: <frame_system::Pallet<T>>::block_number(),
+        },
+    );
+    InstitutionPendingSuccession::<T>::remove(predecessor);
+    Pallet::<T>::deposit_event(Event::<T>::InstitutionSucceeded {
+        proposal_id,
+        predecessor_cid_number: predecessor.clone(),
+        kind: action.kind,
+        successor_cid_numbers,
+    });
+    Ok(())
+}
+
+/// 否决以前身机构为主体、仍在投票中的全部提案(含其他模块发起的)。
+///
+/// 活跃提案数以投票引擎 `MaxActiveProposals` 为界;否决回调照常释放各业务模块的
+/// Pending 占用。任一提案无法否决时整笔承继回滚,由投票引擎按可重试失败处理。
+fn reject_live_proposals<T: Config>(predecessor: &CidNumberOf<T>) -> DispatchResult {
+    let subject = votingengine::ProposalSubject::InstitutionCid(
+        votingengine::CidNumber::try_from(predecessor.to_vec())
+            .map_err(|_| Error::<T>::InvalidSuccession)?,
+    );
+    for live_id in votingengine::Pallet::<T>::active_proposals_by_subject(subject) {
+        let voting = votingengine::Pallet::<T>::proposals(live_id)
+            .is_some_and(|proposal| proposal.status == votingengine::STATUS_VOTING);
+        if voting {
+            votingengine::Pallet::<T>::set_status_and_emit(live_id, votingengine::STATUS_REJECTED)?;
+            Pallet::<T>::deposit_event(Event::<T>::SuccessionProposalRejected {
+                predecessor_cid_number: predecessor.clone(),
+                proposal_id: live_id,
+            });
+        }
+    }
+    Ok(())
+}
+
+impl<T: Config> Pallet<T> {
+    /// 在剩余权重内分批删除已承继前身机构作为承继方留下的同意记录。
+    ///
+    /// 每步对队列中一个前身机构的两张同意表各删至多 [`SUCCESSION_CLEANUP_BATCH`] 条;
+    /// 两表都删空后出队,否则下一块继续。返回实际消耗的权重。
+    pub(crate) fn process_succession_cleanup(remaining_weight: Weight) -> Weight {
+        let step = T::WeightInfo::clear_succession_consents_step();
+        let mut used = T::DbWeight::get().reads(1);
+        if remaining_weight.any_lt(used.saturating_add(step)) {
+            return Weight::zero();
+        }
+        while remaining_weight.all_gte(used.saturating_add(step)) {
+            let Some(predecessor) = SuccessionCleanupQueue::<T>::iter_keys().next() else {
+                break;
+            };
+            used = used.saturating_add(step);
+            let consents =
+                SuccessionConsents::<T>::clear_prefix(&predecessor, SUCCESSION_CLEANUP_BATCH, None);
+            let pending = PendingSuccessionConsents::<T>::clear_prefix(
+                &predecessor,
+                SUCCESSION_CLEANUP_BATCH,
+                None,
+            );
+            if consents.maybe_cursor.is_some() || pending.maybe_cursor.is_some() {
+                // 同一块内重复清同一前缀会重读已删键,留待下一块。
+                break;
+            }
+            SuccessionCleanupQueue::<T>::remove(&predecessor);
+        }
+        used
+    }
+}
+
+/// 同意承继的状态校验:双方均为本 pallet 未被承继的在册机构,且尚未同意过。
+fn ensure_consent_valid<T: Config>(
+    successor_cid_number: &CidNumberOf<T>,
+    predecessor_cid_number: &CidNumberOf<T>,
+) -> Result<InstitutionInfoOf<T>, DispatchError> {
+    ensure!(!successor_cid_number.is_empty(), Error::<T>::EmptyCidNumber);
+    ensure!(
+        successor_cid_number != predecessor_cid_number,
+        Error::<T>::InvalidSuccession
+    );
+    let info =
+        Institutions::<T>::get(successor_cid_number).ok_or(Error::<T>::InstitutionNotFound)?;
+    ensure!(
+        Institutions::<T>::contains_key(predecessor_cid_number),
+        Error::<T>::InstitutionNotFound
+    );
+    ensure!(
+        !SuccessorOf::<T>::contains_key(successor_cid_number)
+            && !SuccessorOf::<T>::contains_key(predecessor_cid_number),
+        Error::<T>::InstitutionSucceeded
+    );
+    ensure!(
+        !SuccessionConsents::<T>::contains_key(successor_cid_number, predecessor_cid_number),
+        Error::<T>::SuccessionConsentAlreadyGranted
+    );
+    Ok(info)
+}
+
+/// 承继载荷的状态校验;发起与执行共用,返回前身机构信息。
+fn ensure_succession_valid<T: Config>(
+    action: &InstitutionSuccessionActionOf<T>,
+) -> Result<InstitutionInfoOf<T>, DispatchError> {
+    let predecessor = &action.predecessor_cid_number;
+    ensure!(!predecessor.is_empty(), Error::<T>::EmptyCidNumber);
+    let info = Institutions::<T>::get(predecessor).ok_or(Error::<T>::InstitutionNotFound)?;
+    ensure!(
+        !SuccessorOf::<T>::contains_key(predecessor),
+        Error::<T>::InstitutionSucceeded
+    );
+    // 创世封存机构与关闭同样永久存在,不得被合并或分立。
+    ensure!(
+        primitives::governance_skeleton::fixed_institution_by_identity(
+            info.institution_code,
+            predecessor.as_slice(),
+        )
+        .is_none(),
+        Error::<T>::CannotCloseProtectedInstitution
+    );
+    ensure!(
+        action
+            .kind
+            .accepts_successor_count(action.successor_cid_numbers.len()),
+        Error::<T>::InvalidSuccession
+    );
+
+    let mut successors = BTreeSet::new();
+    for successor in action.successor_cid_numbers.iter() {
+        ensure!(
+            successor != predecessor && successors.insert(successor.clone()),
+            Error::<T>::InvalidSuccession
+        );
+        ensure!(
+            Institutions::<T>::contains_key(successor),
+            Error::<T>::SuccessorNotFound
+        );
+        ensure!(
+            !SuccessorOf::<T>::contains_key(successor),
+            Error::<T>::InstitutionSucceeded
+        );
+        ensure!(
+            SuccessionConsents::<T>::contains_key(successor, predecessor),
+            Error::<T>::SuccessorConsentMissing
+        );
+    }
+
+    let mut sources = BTreeSet::new();
+    for transfer in action.balance_transfers.iter() {
+        ensure!(
+            successors.contains(&transfer.successor_cid_number)
+                && sources.insert(transfer.source_account_name.clone()),
+            Error::<T>::InvalidSuccession
+        );
+        let source = InstitutionAccounts::<T>::get(predecessor, &transfer.source_account_name)
+            .ok_or(Error::<T>::AccountNotFound)?;
+        ensure!(
+            InstitutionAccounts::<T>::contains_key(
+                &transfer.successor_cid_number,
+                &transfer.successor_account_name,
+            ),
+            Error::<T>::AccountNotFound
+        );
+        ensure!(
+            !T::ProtectedSourceChecker::is_protected(&source.account_id)
+                && T::InstitutionAsset::can_spend(
+                    &source.account_id,
+                    InstitutionAssetAction::MultisigCloseExecute,
+                ),
+            Error::<T>::ProtectedSource
+        );
+    }
+
+    let mut roles = BTreeSet::new();
+    for transfer in action.role_transfers.iter() {
+        ensure!(
+            successors.contains(&transfer.successor_cid_number)
+                && roles.insert((
+                    transfer.role_code.clone(),
+                    transfer.successor_cid_number.clone()
+                )),
+            Error::<T>::InvalidSuccession
+        );
+        // 每个机构自带法定代表人岗位,不随承继复制。
+        ensure!(
+            !primitives::institution_constraints::is_legal_representative_role(
+                transfer.role_code.as_slice()
+            ),
+            Error::<T>::InvalidSuccession
+        );
+        ensure!(
+            InstitutionRoles::<T>::contains_key(predecessor, &transfer.role_code),
+            Error::<T>::AssignmentRoleNotFound
+        );
+        let successor_info = Institutions::<T>::get(&transfer.successor_cid_number)
+            .ok_or(Error::<T>::SuccessorNotFound)?;
+        ensure!(
+            primitives::governance_skeleton::fixed_institution_by_identity(
+                successor_info.institution_code,
+                transfer.successor_cid_number.as_slice(),
+            )
+            .is_none(),
+            Error::<T>::FixedRoleDefinitionImmutable
+        );
+    }
+    Ok(info)
+}
+
+fn ensure_passed_proposal<T: Config>(
+    proposal_id: u64,
+    info: &InstitutionInfoOf<T>,
+    actor_cid_number: &[u8],
+) -> DispatchResult {
+    let proposal = votingengine::Pallet::<T>::proposals(proposal_id)
+        .ok_or(Error::<T>::ProposalActionNotFound)?;
+    ensure!(
+        votingengine::Pallet::<T>::is_callback_execution_scope(proposal_id)
+            && votingengine::Pallet::<T>::is_proposal_owner(proposal_id, crate::MODULE_TAG)
+            && proposal.kind == votingengine::PROPOSAL_KIND_INTERNAL
+            && proposal.stage == votingengine::STAGE_INTERNAL
+            && proposal.status == votingengine::STATUS_PASSED
+            && proposal.internal_code == Some(info.institution_code)
+            && proposal.actor_cid_number.as_ref().map(|cid| cid.as_slice())
+                == Some(actor_cid_number),
+        Error::<T>::ProposalActionNotFound
+    );
+    Ok(())
+}
@@ -445,6 +445,144 @@ fn assignment_authorization_respects_inclusive_term_window() {
     });
 }
 
+#[test]
+fn on_idle_removes_expired_assignments_and_reports_them() {
+    new_test_ext().execute_with(|| {
+        use entity_primitives::{InstitutionRoleMutation, RolePermissionOperation};
+        use frame_support::{traits::Hooks, weights::Weight};
+
+        let cid = create_cgov("expire-role-term");
+        let role_code =
+            entity_primitives::generate_dynamic_role_code(crate::MODULE_TAG, cid.as_slice(), 0, 78);
+        assert_ok!(PublicManage::apply_institution_governance_result(
+            entity_primitives::InstitutionGovernanceResult {
+                institution_code: code_bytes("CGOV"),
+                cid_number: cid.to_vec(),
+                proposal_id: 78,
+                role_mutations: vec![InstitutionRoleMutation::Create {
+                    role_name: "届满岗位".as_bytes().to_vec(),
+                    term_required: true,
+                    permissions: vec![governance_permission(RolePermissionOperation::Vote)],
+                    assignments: vec![
+                        governance_assignment(admin(0), 20_635, 20_700),
+                        governance_assignment(admin(1), 20_600, 20_634),
+                    ],
+                }],
+                assignment_changes: vec![],
+                legal_representative_change: None,
+                result_source_ref: b"proposal-78".to_vec(),
+            }
+        ));
+
+        // 权重不足时不做任何扫描。
+        assert_eq!(
+            PublicManage::on_idle(System::block_number(), Weight::zero()),
+            Weight::zero()
+        );
+        assert_eq!(
+            pallet::InstitutionRoleAssignments::<Test>::get(&cid, &role_code).len(),
+            2
+        );
+
+        // 测试上限每块只检查 4 个任职集合，按游标推进直到扫描回到表头。
+        for _ in 0..64 {
+            PublicManage::on_idle(System::block_number(), Weight::MAX);
+            if pallet::AssignmentExpiryCursor::<Test>::get().is_none() {
+                break;
+            }
+        }
+        let remaining = pallet::InstitutionRoleAssignments::<Test>::get(&cid, &role_code);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].account_id, admin(0));
+        assert!(System::events().iter().any(|record| {
+            record.event
+                == RuntimeEvent::PublicManage(pallet::Event::InstitutionRoleAssignmentExpired {
+                    cid_number: cid.clone(),
+                    role_code: role_code.clone(),
+                    account_id: admin(1),
+                    term_end: 20_634,
+                    legal_representative_cleared: false,
+                })
+        }));
+    });
+}
+
+#[test]
+fn election_results_respect_consecutive_term_limit() {
+    new_test_ext().execute_with(|| {
+        use entity_primitives::{
+            InstitutionRoleAssignmentChange, InstitutionRoleMutation, RolePermissionOperation,
+        };
+
+        let cid = create_cgov("consecutive-term");
+        let role_code =
+            entity_primitives::generate_dynamic_role_code(crate::MODULE_TAG, cid.as_slice(), 0, 79);
+        let elected = |term_start: u32, term_end: u32| {
+            let mut assignment = governance_assignment(admin(0), term_start, term_end);
+            assignment.assignment_source =
+                entity_primitives::InstitutionAssignmentSource::PopularElection;
+            assignment
+        };
+        let result = |proposal_id: u64, role_mutations, assignment_changes| {
+            entity_primitives::InstitutionGovernanceResult {
+                institution_code: code_bytes("CGOV"),
+                cid_number: cid.to_vec(),
+                proposal_id,
+                role_mutations,
+                assignment_changes,
+                legal_representative_change: None,
+                result_source_ref: b"election-result".to_vec(),
+            }
+        };
+        let reelect = |term_start: u32, term_end: u32| {
+            vec![InstitutionRoleAssignmentChange {
+                role_code: role_code.to_vec(),
+                assignments: vec![elected(term_start, term_end)],
+            }]
+        };
+        assert_ok!(PublicManage::apply_institution_governance_result(result(
+            79,
+            vec![InstitutionRoleMutation::Create {
+                role_name: "民选岗位".as_bytes().to_vec(),
+                term_required: true,
+                permissions: vec![governance_permission(RolePermissionOperation::Vote)],
+                assignments: vec![elected(20_000, 20_364)],
+            }],
+            vec![],
+        )));
+        // 重复提交同一任期不计为连任。
+        assert_ok!(PublicManage::apply_institution_governance_result(result(
+            80,
+            vec![],
+            reelect(20_000, 20_364),
+        )));
+        assert_ok!(PublicManage::apply_institution_governance_result(result(
+            81,
+            vec![],
+            reelect(20_365, 20_729),
+        )));
+        assert_eq!(
+            pallet::AssignmentTermRecords::<Test>::get((cid.clone(), role_code.clone()), admin(0))
+                .map(|record| record.consecutive_terms),
+            Some(2)
+        );
+        assert_noop!(
+            PublicManage::apply_institution_governance_result(result(
+                82,
+                vec![],
+                reelect(20_730, 21_094),
+            )),
+            Error::<Test>::ConsecutiveTermLimitExceeded
+        );
+        // 任期间隔后重新当选，连任计数重置。
+        assert_ok!(PublicManage::apply_institution_governance_result(result(
+            83,
+            vec![],
+            reelect(21_100, 21_464),
+        )));
+    });
+}
+
 fn account_of(cid: &pallet::CidNumberOf<Test>, name: &[u8]) -> AccountId32 {
     pallet::InstitutionAccounts::<Test>::get(cid, account_name(name))
         .expect("institution account_id must exist")
@@ -797,3 +935,316 @@ fn duplicate_close_proposal_is_rejected_while_pending() {
         );
     });
 }
+
+fn test_close_role_code() -> crate::RoleCodeOf {
+    b"TEST_CLOSE_ROLE".to_vec().try_into().expect("role fits")
+}
+
+/// 承继机构以 `TEST_CLOSE_ROLE` 发起同意承继并投票通过。
+fn consent_to_succession(
+    successor: &pallet::CidNumberOf<Test>,
+    predecessor: &pallet::CidNumberOf<Test>,
+) {
+    assert_ok!(PublicManage::propose_succession_consent(
+        RuntimeOrigin::signed(admin(0)),
+        successor.clone(),
+        predecessor.clone(),
+        test_close_role_code(),
+    ));
+    let proposal_id = last_proposal_id();
+    assert_ok!(cast_yes_votes(&[admin(1), admin(2)], 2, proposal_id));
+    assert_eq!(
+        pallet::SuccessionConsents::<Test>::get(successor, predecessor),
+        Some(proposal_id)
+    );
+}
+
+fn propose_merger(
+    predecessor: &pallet::CidNumberOf<Test>,
+    successor: &pallet::CidNumberOf<Test>,
+    balance_transfers: Vec<
+        crate::SuccessionBalanceTransfer<pallet::CidNumberOf<Test>, pallet::AccountNameOf<Test>>,
+    >,
+    role_transfers: Vec<
+        crate::SuccessionRoleTransfer<pallet::CidNumberOf<Test>, crate::RoleCodeOf>,
+    >,
+) -> sp_runtime::DispatchResult {
+    PublicManage::propose_institution_succession(
+        RuntimeOrigin::signed(admin(0)),
+        predecessor.clone(),
+        crate::SuccessionKind::Merger,
+        vec![successor.clone()].try_into().expect("successors fit"),
+        balance_transfers.try_into().expect("balance transfers fit"),
+        role_transfers.try_into().expect("role transfers fit"),
+        test_close_role_code(),
+    )
+}
+
+#[test]
+fn approved_merger_moves_balances_copies_roles_and_links_successor() {
+    new_test_ext().execute_with(|| {
+        let predecessor = create_cgov_with_custom("merge-from");
+        let successor = create_cgov_with_custom("merge-into");
+        let archive_role: crate::RoleCodeOf =
+            b"TEST_ARCHIVE_ROLE".to_vec().try_into().expect("role fits");
+        pallet::InstitutionRoles::<Test>::insert(
+            &predecessor,
+            &archive_role,
+            entity_primitives::InstitutionRole {
+                cid_number: predecessor.clone(),
+                role_code: archive_role.clone(),
+                role_name: account_name("档案岗位".as_bytes()),
+                term_required: false,
+                role_status: entity_primitives::InstitutionRoleStatus::Active,
+            },
+        );
+        pallet::InstitutionRolePermissions::<Test>::insert(
+            &predecessor,
+            &archive_role,
+            BoundedVec::try_from(vec![entity_primitives::RoleBusinessPermission {
+                role_subject: entity_primitives::RoleSubject {
+                    cid_number: predecessor.clone(),
+                    role_code: archive_role.clone(),
+                },
+                business_action_id: entity_primitives::BusinessActionId {
+                    module_tag: crate::MODULE_TAG.to_vec().try_into().expect("tag fits"),
+                    action_code: entity_primitives::business_action::ACTION_INSTITUTION_CLOSE,
+                },
+                operation: entity_primitives::RolePermissionOperation::Vote,
+            }])
+            .expect("permissions fit"),
+        );
+
+        consent_to_succession(&successor, &predecessor);
+        assert_ok!(propose_merger(
+            &predecessor,
+            &successor,
+            vec![
+                crate::SuccessionBalanceTransfer {
+                    source_account_name: account_name(CUSTOM_ACCOUNT_NAME),
+                    successor_cid_number: successor.clone(),
+                    successor_account_name: account_name(CUSTOM_ACCOUNT_NAME),
+                },
+                crate::SuccessionBalanceTransfer {
+                    source_account_name: account_name(RESERVED_NAME_MAIN),
+                    successor_cid_number: successor.clone(),
+                    successor_account_name: account_name(RESERVED_NAME_MAIN),
+                },
+            ],
+            vec![crate::SuccessionRoleTransfer {
+                role_code: archive_role.clone(),
+                successor_cid_number: successor.clone(),
+            }],
+        ));
+        let proposal_id = last_proposal_id();
+        assert_eq!(
+            pallet::InstitutionPendingSuccession::<Test>::get(&predecessor),
+            Some(proposal_id)
+        );
+        assert_ok!(cast_yes_votes(&[admin(1), admin(2)], 2, proposal_id));
+
+        assert_eq!(
+            Balances::free_balance(account_of(&successor, CUSTOM_ACCOUNT_NAME)),
+            2 * ACCOUNT_AMOUNT
+        );
+        assert_eq!(
+            Balances::free_balance(account_of(&successor, RESERVED_NAME_MAIN)),
+            2 * ACCOUNT_AMOUNT
+        );
+        assert_eq!(
+            Balances::free_balance(account_of(&predecessor, CUSTOM_ACCOUNT_NAME)),
+            0
+        );
+        // 两笔转移的手续费均由前身费用账户承担。
+        assert_eq!(
+            Balances::free_balance(account_of(&predecessor, RESERVED_NAME_FEE)),
+            980
+        );
+
+        let (inherited_code, inherited) = pallet::InstitutionRoles::<Test>::iter_prefix(&successor)
+            .find(|(_, role)| role.role_name.as_slice() == "档案岗位".as_bytes())
+            .expect("role definition copied");
+        assert_ne!(inherited_code, archive_role);
+        assert_eq!(inherited.cid_number, successor);
+        let permissions =
+            pallet::InstitutionRolePermissions::<Test>::get(&successor, &inherited_code);
+        assert_eq!(permissions.len(), 1);
+        assert_eq!(permissions[0].role_subject.cid_number, successor);
+        assert!(
+            pallet::InstitutionRoleAssignments::<Test>::get(&successor, &inherited_code).is_empty()
+        );
+
+        let record = pallet::SuccessorOf::<Test>::get(&predecessor).expect("successor linked");
+        assert_eq!(record.kind, crate::SuccessionKind::Merger);
+        assert_eq!(
+            record.successor_cid_numbers.to_vec(),
+            vec![successor.clone()]
+        );
+        assert_eq!(record.proposal_id, proposal_id);
+        assert!(pallet::PredecessorsOf::<Test>::contains_key(
+            &successor,
+            &predecessor
+        ));
+        assert!(!pallet::SuccessionConsents::<Test>::contains_key(
+            &successor,
+            &predecessor
+        ));
+        assert!(!pallet::InstitutionPendingSuccession::<Test>::contains_key(
+            &predecessor
+        ));
+        // 前身机构本身与协议账户保留,但永久失去提案资格。
+        assert!(pallet::Institutions::<Test>::contains_key(&predecessor));
+        assert_noop!(
+            propose_add_custom_account(
+                RuntimeOrigin::signed(admin(0)),
+                predecessor.clone(),
+                &["承继后新增".as_bytes()],
+            ),
+            Error::<Test>::InstitutionSucceeded
+        );
+    });
+}
+
+#[test]
+fn succession_requires_consent_and_matching_successor_count() {
+    new_test_ext().execute_with(|| {
+        let predecessor = create_cgov_with_custom("split-from");
+        let successor = create_cgov_with_custom("split-into");
+        assert_noop!(
+            propose_merger(&predecessor, &successor, Vec::new(), Vec::new()),
+            Error::<Test>::SuccessorConsentMissing
+        );
+
+        consent_to_succession(&successor, &predecessor);
+        assert_noop!(
+            PublicManage::propose_institution_succession(
+                RuntimeOrigin::signed(admin(0)),
+                predecessor.clone(),
+                crate::SuccessionKind::Split,
+                vec![successor.clone()].try_into().expect("successors fit"),
+                Default::default(),
+                Default::default(),
+                test_close_role_code(),
+            ),
+            Error::<Test>::InvalidSuccession
+        );
+        assert_noop!(
+            propose_merger(&predecessor, &predecessor, Vec::new(), Vec::new()),
+            Error::<Test>::InvalidSuccession
+        );
+        // 法定代表人岗位各机构自带,不随承继复制。
+        assert_noop!(
+            propose_merger(
+                &predecessor,
+                &successor,
+                Vec::new(),
+                vec![crate::SuccessionRoleTransfer {
+                    role_code: primitives::institution_constraints::ROLE_CODE_LEGAL_REPRESENTATIVE
+                        .to_vec()
+                        .try_into()
+                        .expect("role fits"),
+                    successor_cid_number: successor.clone(),
+                }],
+            ),
+            Error::<Test>::InvalidSuccession
+        );
+    });
+}
+
+#[test]
+fn rejected_succession_releases_pending_lock() {
+    new_test_ext().execute_with(|| {
+        let predecessor = create_cgov_with_custom("reject-from");
+        let successor = create_cgov_with_custom("reject-into");
+        consent_to_succession(&successor, &predecessor);
+        assert_ok!(propose_merger(
+            &predecessor,
+            &successor,
+            Vec::new(),
+            Vec::new()
+        ));
+        let proposal_id = last_proposal_id();
+        assert_noop!(
+            propose_merger(&predecessor, &successor, Vec::new(), Vec::new()),
+            Error::<Test>::SuccessionAlreadyPending
+        );
+
+        assert_eq!(
+            <crate::InternalVoteExecutor<Test> as votingengine::InternalVoteResultCallback>::on_internal_vote_finalized(
+                proposal_id,
+                false,
+            ),
+            Ok(votingengine::ProposalExecutionOutcome::Executed)
+        );
+        assert!(!pallet::InstitutionPendingSuccession::<Test>::contains_key(
+            &predecessor
+        ));
+        assert!(!pallet::SuccessorOf::<Test>::contains_key(&predecessor));
+        // 否决不消费承继机构的同意记录。
+        assert!(pallet::SuccessionConsents::<Test>::contains_key(
+            &successor,
+            &predecessor
+        ));
+    });
+}
+
+#[test]
+fn succession_rejects_live_predecessor_proposals_and_cleans_consents_on_idle() {
+    new_test_ext().execute_with(|| {
+        use frame_support::{traits::Hooks, weights::Weight};
+
+        let predecessor = create_cgov_with_custom("sweep-from");
+        let successor = create_cgov_with_custom("sweep-into");
+        let other = create_cgov_with_custom("sweep-other");
+        // 前身此前已同意承继另一机构,承继后该记录须被分批清理。
+        pallet::SuccessionConsents::<Test>::insert(&predecessor, &other, 7);
+
+        consent_to_succession(&successor, &predecessor);
+        assert_ok!(propose_add_custom_account(
+            RuntimeOrigin::signed(admin(0)),
+            predecessor.clone(),
+            &["承继前新增".as_bytes()],
+        ));
+        let live_add = last_proposal_id();
+        assert_ok!(propose_merger(
+            &predecessor,
+            &successor,
+            Vec::new(),
+            Vec::new()
+        ));
+        assert_ok!(cast_yes_votes(&[admin(1), admin(2)], 2, last_proposal_id()));
+
+        assert!(pallet::SuccessorOf::<Test>::contains_key(&predecessor));
+        assert_eq!(
+            votingengine::Pallet::<Test>::proposals(live_add).map(|proposal| proposal.status),
+            Some(votingengine::STATUS_REJECTED)
+        );
+        assert!(!pallet::InstitutionPendingAdd::<Test>::contains_key(
+            &predecessor
+        ));
+        assert!(System::events().iter().any(|record| {
+            record.event
+                == RuntimeEvent::PublicManage(pallet::Event::SuccessionProposalRejected {
+                    predecessor_cid_number: predecessor.clone(),
+                    proposal_id: live_add,
+                })
+        }));
+
+        // 同意记录不在执行体内删除,由 on_idle 在剩余权重内清理后出队。
+        assert!(pallet::SuccessionConsents::<Test>::contains_key(
+            &predecessor,
+            &other
+        ));
+        assert!(pallet::SuccessionCleanupQueue::<Test>::contains_key(
+            &predecessor
+        ));
+        PublicManage::on_idle(System::block_number(), Weight::MAX);
+        assert!(!pallet::SuccessionConsents::<Test>::contains_key(
+            &predecessor,
+            &other
+        ));
+        assert!(!pallet::SuccessionCleanupQueue::<Test>::contains_key(
+            &predecessor
+        ));
+    });
+}
@@ -0,0 +1,116 @@
+//! 紧急暂停模块 Benchmark 定义。
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::{EnsureOrigin, Get};
+use sp_runtime::sp_std::vec;
+use votingengine::CitizenIdentityReader;
+
+use crate::pallet::{Config, FastTrackTallies, PauseAction, ReasonOf};
+use crate::{Pallet, PauseTarget};
+
+const BENCH_MAX_REASON_LEN: u32 = 1024;
+
+fn reason_max<T: Config>() -> ReasonOf<T> {
+    assert_eq!(
+        T::MaxReasonLen::get(),
+        BENCH_MAX_REASON_LEN,
+        "update BENCH_MAX_REASON_LEN when runtime MaxReasonLen changes"
+    );
+    vec![b'r'; BENCH_MAX_REASON_LEN as usize]
+        .try_into()
+        .expect("benchmark reason should fit")
+}
+
+fn target() -> PauseTarget {
+    PauseTarget {
+        pallet_name: b"MultisigTransfer"
+            .to_vec()
+            .try_into()
+            .expect("pallet name fits"),
+        call_name: None,
+    }
+}
+
+fn nrc_cid_number() -> votingengine::CidNumber {
+    primitives::cid::china::china_cb::CHINA_CB[0]
+        .cid_number
+        .as_bytes()
+        .to_vec()
+        .try_into()
+        .expect("NRC CID fits runtime bound")
+}
+
+fn committee_role_code() -> votingengine::RoleCode {
+    primitives::governance_skeleton::ROLE_CODE_COMMITTEE_MEMBER
+        .to_vec()
+        .try_into()
+        .expect("committee role fits runtime bound")
+}
+
+fn seed_population<T>()
+where
+    T: Config + joint_vote::Config,
+{
+    let scope = votingengine::PopulationScope::Country;
+    let citizen: T::AccountId = account("emergency-pause-citizen", 0, 0);
+    <T as votingengine::Config>::CitizenIdentityReader::benchmark_seed_identity(&citizen, &scope);
+}
+
+#[benchmarks(where T: Config + joint_vote::Config)]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn propose_pause_action() {
+        let origin = T::ProposeOrigin::try_successful_origin()
+            .expect("benchmark proposer origin must be available");
+        let reason = reason_max::<T>();
+        seed_population::<T>();
+        let action = PauseAction::Pause {
+            target: target(),
+            duration: T::MaxPauseDuration::get(),
+        };
+
+        #[block]
+        {
+            Pallet::<T>::propose_pause_action(
+                origin,
+                nrc_cid_number(),
+                committee_role_code(),
+                action,
+                reason,
+            )
+            .expect("benchmark pause proposal should succeed");
+        }
+
+        let proposal_id = votingengine::Pallet::<T>::next_proposal_id().saturating_sub(1);
+        assert!(
+            votingengine::Pallet::<T>::get_proposal_data(proposal_id).is_some(),
+            "pause benchmark should store proposal data in voting engine"
+        );
+    }
+
+    #[benchmark]
+    fn fast_track_pause() {
+        let origin = T::ProposeOrigin::try_successful_origin()
+            .expect("benchmark proposer origin must be available");
+
+        #[block]
+        {
+            Pallet::<T>::fast_track_pause(
+                origin,
+                nrc_cid_number(),
+                committee_role_code(),
+                target(),
+            )
+            .expect("benchmark fast-track approval should succeed");
+        }
+
+        assert!(
+            FastTrackTallies::<T>::contains_key(target()),
+            "first approval should open a fast-track tally"
+        );
+    }
+}
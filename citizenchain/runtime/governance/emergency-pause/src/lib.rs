@@ -0,0 +1,742 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarks;
+pub mod weights;
+
+pub use pallet::*;
+pub use primitives::emergency_pause::{PauseName, PauseTarget};
+use votingengine::JointVoteResultCallback;
+
+/// 模块标识前缀，用于在 ProposalData 中区分不同业务模块，防止跨模块误解码。
+pub const MODULE_TAG: &[u8] = entity_primitives::business_action::MODULE_EMERGENCY_PAUSE;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use entity_primitives::{
+        AuthorizationSubject, BusinessActionId, InstitutionRoleAuthorizationQuery,
+        RolePermissionOperation, RoleSubject,
+    };
+    use frame_support::{pallet_prelude::*, storage::with_storage_layer};
+    use frame_system::pallet_prelude::*;
+    use primitives::{
+        cid::{china::china_cb::CHINA_CB, china::china_ch::CHINA_CH},
+        governance_skeleton::{ROLE_CODE_COMMITTEE_MEMBER, ROLE_CODE_DIRECTOR},
+    };
+    use sp_runtime::{
+        traits::{Hash, Saturating, Zero},
+        DispatchError,
+    };
+    use votingengine::JointVoteEngine;
+
+    pub type ReasonOf<T> = BoundedVec<u8, <T as Config>::MaxReasonLen>;
+    pub type PauseRecordOf<T> = PauseRecord<BlockNumberFor<T>>;
+    pub type PauseActionOf<T> = PauseAction<BlockNumberFor<T>>;
+
+    /// 暂停记录的来源；快速通道记录只能被联合投票覆盖或自然到期。
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Clone,
+        Copy,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+        PartialEq,
+        Eq,
+    )]
+    pub enum PauseSource {
+        JointVote { proposal_id: u64 },
+        FastTrack,
+    }
+
+    /// 生效中的暂停：`expires_at` 所在区块起自动解除，过滤器不再拦截。
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Clone,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+        PartialEq,
+        Eq,
+    )]
+    pub struct PauseRecord<BlockNumber> {
+        pub paused_at: BlockNumber,
+        pub expires_at: BlockNumber,
+        pub source: PauseSource,
+    }
+
+    /// 联合投票可表决的动作：暂停（含续期）与提前解除。
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Clone,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+        PartialEq,
+        Eq,
+    )]
+    pub enum PauseAction<BlockNumber> {
+        /// 从执行区块起暂停 `duration` 个区块；已暂停目标按新期限覆盖，即续期。
+        Pause {
+            target: PauseTarget,
+            duration: BlockNumber,
+        },
+        Resume {
+            target: PauseTarget,
+        },
+    }
+
+    /// 提案摘要数据：序列化后存入 votingengine 的 ProposalData。
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Clone,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+        PartialEq,
+        Eq,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct Proposal<T: Config> {
+        pub actor_cid_number: votingengine::types::CidNumber,
+        pub actor_role_code: votingengine::types::RoleCode,
+        pub proposer_account_id: T::AccountId,
+        pub reason: ReasonOf<T>,
+        pub action: PauseActionOf<T>,
+    }
+
+    /// 快速通道确认簿：窗口内累计不同 NRC 委员确认，达到门槛即生效并清除。
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Clone,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+        PartialEq,
+        Eq,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct FastTrackTally<T: Config> {
+        pub opened_at: BlockNumberFor<T>,
+        pub approvers: BoundedVec<T::AccountId, T::FastTrackThreshold>,
+    }
+
+    use crate::weights::WeightInfo;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + votingengine::Config {
+        #[allow(deprecated)]
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// 只完成签名来源校验；暂停业务权限随后按机构 CID + 委员岗位校验。
+        type ProposeOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+        type JointVoteEngine: JointVoteEngine<Self::AccountId>;
+        /// 联合投票与快速通道分别按各自业务动作校验提案权限。
+        type InstitutionRoleAuthorization: InstitutionRoleAuthorizationQuery<Self::AccountId>;
+
+        #[pallet::constant]
+        type MaxReasonLen: Get<u32>;
+
+        /// 同时生效的暂停目标上限，也是同时打开的快速通道确认簿上限。
+        #[pallet::constant]
+        type MaxPausedTargets: Get<u32>;
+
+        /// 联合投票单次暂停或续期允许的最长区块数。
+        #[pallet::constant]
+        type MaxPauseDuration: Get<BlockNumberFor<Self>>;
+
+        /// 快速通道生效所需的不同 NRC 委员确认数。
+        #[pallet::constant]
+        type FastTrackThreshold: Get<u32>;
+
+        /// 快速通道暂停的固定时长；不能由发起人指定。
+        #[pallet::constant]
+        type FastTrackDuration: Get<BlockNumberFor<Self>>;
+
+        /// 快速通道确认簿从首个确认起的有效区块数，过期未达门槛即作废。
+        #[pallet::constant]
+        type FastTrackApprovalWindow: Get<BlockNumberFor<Self>>;
+
+        type WeightInfo: crate::weights::WeightInfo;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// 暂停登记表；`RuntimeCallFilter` 与手续费路由按 pallet 名与 call 名查询。
+    #[pallet::storage]
+    pub type PausedTargets<T: Config> =
+        CountedStorageMap<_, Blake2_128Concat, PauseTarget, PauseRecordOf<T>, OptionQuery>;
+
+    #[pallet::storage]
+    pub type FastTrackTallies<T: Config> =
+        CountedStorageMap<_, Blake2_128Concat, PauseTarget, FastTrackTally<T>, OptionQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        PauseActionProposed {
+            proposal_id: u64,
+            actor_cid_number: votingengine::types::CidNumber,
+            actor_role_code: votingengine::types::RoleCode,
+            proposer_account_id: T::AccountId,
+            action: PauseActionOf<T>,
+        },
+        JointVoteFinalized {
+            proposal_id: u64,
+            approved: bool,
+        },
+        /// 联合投票通过但动作执行失败（例如登记表已满），状态已回滚。
+        PauseActionExecutionFailed {
+            proposal_id: u64,
+        },
+        TargetPaused {
+            target: PauseTarget,
+            expires_at: BlockNumberFor<T>,
+            source: PauseSource,
+        },
+        TargetResumed {
+            target: PauseTarget,
+            proposal_id: u64,
+        },
+        PauseExpired {
+            target: PauseTarget,
+        },
+        FastTrackApproved {
+            target: PauseTarget,
+            who: T::AccountId,
+            approvals: u32,
+        },
+        FastTrackLapsed {
+            target: PauseTarget,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        EmptyReason,
+        InvalidActorCid,
+        /// 发起人没有目标机构岗位的对应暂停权限。
+        UnauthorizedActorRole,
+        /// pallet 名或 call 名为空、超长或含非标识符字符。
+        InvalidTarget,
+        /// 目标属于共识或治理自救路径，永久不可暂停。
+        ProtectedTarget,
+        InvalidDuration,
+        TargetNotPaused,
+        TooManyPausedTargets,
+        TooManyFastTracks,
+        AlreadyApproved,
+        ProposalNotFound,
+        ProposalNotVoting,
+        JointVoteCreateFailed,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            Self::sweep_expired(now)
+        }
+
+        fn integrity_test() {
+            assert!(
+                T::MaxPausedTargets::get() > 0,
+                "MaxPausedTargets must be greater than 0"
+            );
+            assert!(
+                T::FastTrackThreshold::get() > 0,
+                "FastTrackThreshold must be greater than 0"
+            );
+            assert!(
+                !T::FastTrackDuration::get().is_zero()
+                    && T::FastTrackDuration::get() <= T::MaxPauseDuration::get(),
+                "FastTrackDuration must be within (0, MaxPauseDuration]"
+            );
+            assert!(
+                !T::FastTrackApprovalWindow::get().is_zero(),
+                "FastTrackApprovalWindow must be greater than 0"
+            );
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            frame_support::ensure!(
+                PausedTargets::<T>::count() <= T::MaxPausedTargets::get()
+                    && FastTrackTallies::<T>::count() <= T::MaxPausedTargets::get(),
+                "暂停登记表或快速通道确认簿超过 MaxPausedTargets"
+            );
+            for (target, record) in PausedTargets::<T>::iter() {
+                frame_support::ensure!(
+                    target.is_well_formed() && !target.is_protected(),
+                    "暂停登记表含非法或受保护目标"
+                );
+                frame_support::ensure!(
+                    record.paused_at <= record.expires_at,
+                    "暂停记录到期区块早于生效区块"
+                );
+            }
+            Ok(())
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// NRC/PRC 委员岗位任职人发起暂停、续期或解除暂停提案，走与协议升级相同的联合投票。
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::propose_pause_action())]
+        pub fn propose_pause_action(
+            origin: OriginFor<T>,
+            actor_cid_number: votingengine::types::CidNumber,
+            actor_role_code: votingengine::types::RoleCode,
+            action: PauseActionOf<T>,
+            reason: ReasonOf<T>,
+        ) -> DispatchResult {
+            let proposer_account_id = T::ProposeOrigin::ensure_origin(origin)?;
+            Self::ensure_actor(
+                &proposer_account_id,
+                &actor_cid_number,
+                &actor_role_code,
+                entity_primitives::business_action::ACTION_EMERGENCY_PAUSE,
+                false,
+            )?;
+            ensure!(!reason.is_empty(), Error::<T>::EmptyReason);
+            match &action {
+                PauseAction::Pause { target, duration } => {
+                    Self::ensure_pausable(target)?;
+                    ensure!(
+                        !duration.is_zero() && *duration <= T::MaxPauseDuration::get(),
+                        Error::<T>::InvalidDuration
+                    );
+                }
+                PauseAction::Resume { target } => {
+                    ensure!(
+                        PausedTargets::<T>::contains_key(target),
+                        Error::<T>::TargetNotPaused
+                    );
+                }
+            }
+
+            let proposal = Proposal::<T> {
+                actor_cid_number: actor_cid_number.clone(),
+                actor_role_code: actor_role_code.clone(),
+                proposer_account_id: proposer_account_id.clone(),
+                reason,
+                action: action.clone(),
+            };
+            let mut encoded = sp_runtime::sp_std::vec::Vec::from(crate::MODULE_TAG);
+            encoded.extend_from_slice(&proposal.encode());
+            let encoded_hash = T::Hashing::hash(encoded.as_slice());
+            let mut business_object_hash = [0u8; 32];
+            business_object_hash.copy_from_slice(encoded_hash.as_ref());
+            let vote_plan =
+                Self::build_vote_plan(&actor_cid_number, &actor_role_code, business_object_hash)?;
+            let proposal_id = T::JointVoteEngine::create_joint_proposal_with_data(
+                proposer_account_id.clone(),
+                actor_cid_number.to_vec(),
+                vote_plan,
+                encoded,
+            )
+            .map_err(|_| Error::<T>::JointVoteCreateFailed)?;
+
+            Self::deposit_event(Event::<T>::PauseActionProposed {
+                proposal_id,
+                actor_cid_number,
+                actor_role_code,
+                proposer_account_id,
+                action,
+            });
+            Ok(())
+        }
+
+        /// 国家储委会委员快速通道：窗口内不同委员确认达到门槛后，立即暂停固定时长并到期自动解除。
+        /// 对已暂停目标再次达到门槛只会延长期限，不会缩短联合投票给出的期限。
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::fast_track_pause())]
+        pub fn fast_track_pause(
+            origin: OriginFor<T>,
+            actor_cid_number: votingengine::types::CidNumber,
+            actor_role_code: votingengine::types::RoleCode,
+            target: PauseTarget,
+        ) -> DispatchResult {
+            let who = T::ProposeOrigin::ensure_origin(origin)?;
+            Self::ensure_actor(
+                &who,
+                &actor_cid_number,
+                &actor_role_code,
+                entity_primitives::business_action::ACTION_EMERGENCY_PAUSE_FAST_TRACK,
+                true,
+            )?;
+            Self::ensure_pausable(&target)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let window = T::FastTrackApprovalWindow::get();
+            let existing = FastTrackTallies::<T>::get(&target);
+            let mut tally = match existing {
+                Some(tally) if now < tally.opened_at.saturating_add(window) => tally,
+                stale => {
+                    ensure!(
+                        stale.is_some()
+                            || FastTrackTallies::<T>::count() < T::MaxPausedTargets::get(),
+                        Error::<T>::TooManyFastTracks
+                    );
+                    FastTrackTally::<T> {
+                        opened_at: now,
+                        approvers: BoundedVec::new(),
+                    }
+                }
+            };
+            ensure!(!tally.approvers.contains(&who), Error::<T>::AlreadyApproved);
+            tally
+                .approvers
+                .try_push(who.clone())
+                .map_err(|_| Error::<T>::AlreadyApproved)?;
+            let approvals = tally.approvers.len() as u32;
+            Self::deposit_event(Event::<T>::FastTrackApproved {
+                target: target.clone(),
+                who,
+                approvals,
+            });
+
+            if approvals >= T::FastTrackThreshold::get() {
+                FastTrackTallies::<T>::remove(&target);
+                let expires_at = now.saturating_add(T::FastTrackDuration::get());
+                Self::apply_pause(target, expires_at, PauseSource::FastTrack, true)?;
+            } else {
+                FastTrackTallies::<T>::insert(&target, tally);
+            }
+            Ok(())
+        }
+
+        // 联合投票终结只能由 votingengine 回调进入；到期解除由 on_initialize 完成，
+        // 不提供手工清理入口。
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// `RuntimeCallFilter` 与手续费路由的查询入口：整模块暂停或单个 call 暂停均拦截。
+        /// 受保护 pallet 即使登记表被异常写入也永远放行，过期记录在清理前同样视为已解除。
+        pub fn is_call_paused(pallet_name: &str, call_name: &str) -> bool {
+            let pallet_name = pallet_name.as_bytes();
+            if primitives::emergency_pause::is_protected_pallet(pallet_name) {
+                return false;
+            }
+            let Ok(pallet_name) = PauseName::try_from(pallet_name.to_vec()) else {
+                return false;
+            };
+            let now = frame_system::Pallet::<T>::block_number();
+            let active = |target: &PauseTarget| {
+                PausedTargets::<T>::get(target).is_some_and(|record| now < record.expires_at)
+            };
+            let whole_pallet = PauseTarget {
+                pallet_name: pallet_name.clone(),
+                call_name: None,
+            };
+            if active(&whole_pallet) {
+                return true;
+            }
+            let Ok(call_name) = PauseName::try_from(call_name.as_bytes().to_vec()) else {
+                return false;
+            };
+            active(&PauseTarget {
+                pallet_name,
+                call_name: Some(call_name),
+            })
+        }
+
+        fn ensure_actor(
+            who: &T::AccountId,
+            actor_cid_number: &votingengine::types::CidNumber,
+            actor_role_code: &votingengine::types::RoleCode,
+            action_code: u32,
+            nrc_only: bool,
+        ) -> DispatchResult {
+            let actor_text = core::str::from_utf8(actor_cid_number.as_slice())
+                .map_err(|_| Error::<T>::InvalidActorCid)?;
+            let actor_code = votingengine::types::institution_code_from_cid_number(actor_text)
+                .ok_or(Error::<T>::InvalidActorCid)?;
+            ensure!(
+                actor_code == votingengine::types::NRC
+                    || (!nrc_only && actor_code == votingengine::types::PRC),
+                Error::<T>::InvalidActorCid
+            );
+            ensure!(
+                T::InstitutionRoleAuthorization::is_authorized(
+                    who,
+                    &RoleSubject {
+                        cid_number: actor_cid_number.to_vec(),
+                        role_code: actor_role_code.to_vec(),
+                    },
+                    &BusinessActionId {
+                        module_tag: crate::MODULE_TAG.to_vec(),
+                        action_code,
+                    },
+                    RolePermissionOperation::Propose,
+                ),
+                Error::<T>::UnauthorizedActorRole
+            );
+            Ok(())
+        }
+
+        fn ensure_pausable(target: &PauseTarget) -> DispatchResult {
+            ensure!(target.is_well_formed(), Error::<T>::InvalidTarget);
+            ensure!(!target.is_protected(), Error::<T>::ProtectedTarget);
+            Ok(())
+        }
+
+        /// 写入暂停记录。`extend_only` 为快速通道语义：只延长不缩短，且保留原来源。
+        fn apply_pause(
+            target: PauseTarget,
+            expires_at: BlockNumberFor<T>,
+            source: PauseSource,
+            extend_only: bool,
+        ) -> DispatchResult {
+            Self::ensure_pausable(&target)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            let record = match PausedTargets::<T>::get(&target) {
+                Some(existing) if extend_only && existing.expires_at >= expires_at => existing,
+                Some(existing) if extend_only => PauseRecord {
+                    expires_at,
+                    ..existing
+                },
+                Some(existing) => PauseRecord {
+                    paused_at: existing.paused_at,
+                    expires_at,
+                    source,
+                },
+                None => {
+                    ensure!(
+                        PausedTargets::<T>::count() < T::MaxPausedTargets::get(),
+                        Error::<T>::TooManyPausedTargets
+                    );
+                    PauseRecord {
+                        paused_at: now,
+                        expires_at,
+                        source,
+                    }
+                }
+            };
+            Self::deposit_event(Event::<T>::TargetPaused {
+                target: target.clone(),
+                expires_at: record.expires_at,
+                source: record.source,
+            });
+            PausedTargets::<T>::insert(target, record);
+            Ok(())
+        }
+
+        /// 区块初始化时移除到期暂停与过期确认簿；两表都为空时只读两个计数器。
+        pub(crate) fn sweep_expired(now: BlockNumberFor<T>) -> Weight {
+            let paused = PausedTargets::<T>::count();
+            let tallies = FastTrackTallies::<T>::count();
+            let mut weight = T::DbWeight::get().reads(2);
+            if paused == 0 && tallies == 0 {
+                return weight;
+            }
+
+            let expired: sp_runtime::sp_std::vec::Vec<PauseTarget> = PausedTargets::<T>::iter()
+                .filter(|(_, record)| record.expires_at <= now)
+                .map(|(target, _)| target)
+                .collect();
+            let window = T::FastTrackApprovalWindow::get();
+            let lapsed: sp_runtime::sp_std::vec::Vec<PauseTarget> = FastTrackTallies::<T>::iter()
+                .filter(|(_, tally)| tally.opened_at.saturating_add(window) <= now)
+                .map(|(target, _)| target)
+                .collect();
+            weight = weight.saturating_add(
+                T::DbWeight::get().reads(u64::from(paused).saturating_add(u64::from(tallies))),
+            );
+            let removed = expired.len().saturating_add(lapsed.len()) as u64;
+            for target in expired {
+                PausedTargets::<T>::remove(&target);
+                Self::deposit_event(Event::<T>::PauseExpired { target });
+            }
+            for target in lapsed {
+                FastTrackTallies::<T>::remove(&target);
+                Self::deposit_event(Event::<T>::FastTrackLapsed { target });
+            }
+            // 每次删除同时改写计数器。
+            weight.saturating_add(T::DbWeight::get().writes(removed.saturating_mul(2)))
+        }
+
+        fn bounded_role_subject(
+            cid_number: &[u8],
+            role_code: &[u8],
+        ) -> Result<
+            entity_primitives::RoleSubject<
+                votingengine::types::CidNumber,
+                votingengine::types::RoleCode,
+            >,
+            DispatchError,
+        > {
+            Ok(entity_primitives::RoleSubject {
+                cid_number: cid_number
+                    .to_vec()
+                    .try_into()
+                    .map_err(|_| Error::<T>::InvalidActorCid)?,
+                role_code: role_code
+                    .to_vec()
+                    .try_into()
+                    .map_err(|_| Error::<T>::InvalidActorCid)?,
+            })
+        }
+
+        /// 暂停与协议升级同属协议级治理，固定使用 NRC/PRC 委员与 PRB 董事组成的联合投票计划。
+        fn build_vote_plan(
+            actor_cid_number: &votingengine::types::CidNumber,
+            actor_role_code: &votingengine::types::RoleCode,
+            business_object_hash: [u8; 32],
+        ) -> Result<votingengine::types::VotePlanOf<T::AccountId>, DispatchError> {
+            let proposer_role = Self::bounded_role_subject(
+                actor_cid_number.as_slice(),
+                actor_role_code.as_slice(),
+            )?;
+            let mut voters = sp_runtime::sp_std::vec::Vec::new();
+            for entry in CHINA_CB.iter() {
+                voters.push(AuthorizationSubject::Institution(
+                    Self::bounded_role_subject(
+                        entry.cid_number.as_bytes(),
+                        ROLE_CODE_COMMITTEE_MEMBER,
+                    )?,
+                ));
+            }
+            for entry in CHINA_CH.iter() {
+                voters.push(AuthorizationSubject::Institution(
+                    Self::bounded_role_subject(entry.cid_number.as_bytes(), ROLE_CODE_DIRECTOR)?,
+                ));
+            }
+            let module_tag: BoundedVec<
+                u8,
+                ConstU32<{ entity_primitives::BUSINESS_MODULE_TAG_MAX_BYTES }>,
+            > = crate::MODULE_TAG
+                .to_vec()
+                .try_into()
+                .map_err(|_| Error::<T>::JointVoteCreateFailed)?;
+            votingengine::types::VotePlanOf::<T::AccountId>::try_new(
+                BusinessActionId {
+                    module_tag: module_tag.clone(),
+                    action_code: entity_primitives::business_action::ACTION_EMERGENCY_PAUSE,
+                },
+                module_tag,
+                AuthorizationSubject::Institution(proposer_role),
+                voters,
+                votingengine::types::VotingEngineKind::Joint,
+                business_object_hash,
+            )
+            .map_err(|_| Error::<T>::JointVoteCreateFailed.into())
+        }
+
+        /// 快速判断 proposal_id 是否属于本模块（通过 ProposalOwner 匹配）。
+        pub fn owns_proposal(proposal_id: u64) -> bool {
+            votingengine::Pallet::<T>::is_proposal_owner(proposal_id, crate::MODULE_TAG)
+        }
+
+        /// 从投票引擎 ProposalData 中读取并解码本模块的提案摘要。
+        pub(crate) fn load_proposal(proposal_id: u64) -> Result<Proposal<T>, DispatchError> {
+            let raw = votingengine::Pallet::<T>::get_proposal_data(proposal_id)
+                .ok_or(Error::<T>::ProposalNotFound)?;
+            let tag = crate::MODULE_TAG;
+            if raw.len() < tag.len() || &raw[..tag.len()] != tag {
+                return Err(Error::<T>::ProposalNotFound.into());
+            }
+            Proposal::<T>::decode(&mut &raw[tag.len()..])
+                .map_err(|_| Error::<T>::ProposalNotFound.into())
+        }
+
+        fn execute_action(proposal_id: u64, action: PauseActionOf<T>) -> DispatchResult {
+            match action {
+                PauseAction::Pause { target, duration } => {
+                    let expires_at =
+                        frame_system::Pallet::<T>::block_number().saturating_add(duration);
+                    Self::apply_pause(
+                        target,
+                        expires_at,
+                        PauseSource::JointVote { proposal_id },
+                        false,
+                    )
+                }
+                PauseAction::Resume { target } => {
+                    // 投票期间暂停可能已自然到期；解除动作幂等，照常清掉残留确认簿。
+                    PausedTargets::<T>::remove(&target);
+                    FastTrackTallies::<T>::remove(&target);
+                    Self::deposit_event(Event::<T>::TargetResumed {
+                        target,
+                        proposal_id,
+                    });
+                    Ok(())
+                }
+            }
+        }
+
+        /// 联合投票结果回调（由 votingengine 的 set_status_and_emit 在事务内调用）。
+        ///
+        /// - approved + 执行成功 → 返回 `Executed`。
+        /// - approved + 执行失败 → 回滚本模块写入并返回 `FatalFailed`。
+        /// - rejected → 返回 `Executed`，投票引擎保留 STATUS_REJECTED。
+        pub(crate) fn apply_joint_vote_result(
+            proposal_id: u64,
+            approved: bool,
+        ) -> Result<votingengine::ProposalExecutionOutcome, DispatchError> {
+            let proposal = Self::load_proposal(proposal_id)?;
+            let engine_proposal = votingengine::Pallet::<T>::proposals(proposal_id)
+                .ok_or(Error::<T>::ProposalNotFound)?;
+            ensure!(
+                votingengine::Pallet::<T>::is_callback_execution_scope(proposal_id)
+                    && votingengine::Pallet::<T>::is_proposal_owner(proposal_id, crate::MODULE_TAG)
+                    && engine_proposal.kind == votingengine::PROPOSAL_KIND_JOINT
+                    && matches!(
+                        engine_proposal.stage,
+                        votingengine::STAGE_JOINT | votingengine::STAGE_REFERENDUM
+                    ),
+                Error::<T>::ProposalNotVoting
+            );
+            let expected_status = if approved {
+                votingengine::STATUS_PASSED
+            } else {
+                votingengine::STATUS_REJECTED
+            };
+            ensure!(
+                engine_proposal.status == expected_status,
+                Error::<T>::ProposalNotVoting
+            );
+
+            Self::deposit_event(Event::<T>::JointVoteFinalized {
+                proposal_id,
+                approved,
+            });
+            if !approved {
+                return Ok(votingengine::ProposalExecutionOutcome::Executed);
+            }
+            let exec_ok =
+                with_storage_layer(|| Self::execute_action(proposal_id, proposal.action)).is_ok();
+            if exec_ok {
+                Ok(votingengine::ProposalExecutionOutcome::Executed)
+            } else {
+                Self::deposit_event(Event::<T>::PauseActionExecutionFailed { proposal_id });
+                Ok(votingengine::ProposalExecutionOutcome::FatalFailed)
+            }
+        }
+    }
+}
+
+impl<T: pallet::Config> JointVoteResultCallback for pallet::Pallet<T> {
+    fn on_joint_vote_finalized(
+        vote_proposal_id: u64,
+        approved: bool,
+    ) -> Result<votingengine::ProposalExecutionOutcome, sp_runtime::DispatchError> {
+        pallet::Pallet::<T>::apply_joint_vote_result(vote_proposal_id, approved)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,273 @@
+use super::*;
+
+#[test]
+fn committee_members_propose_pause_but_protected_targets_are_rejected() {
+    new_test_ext().execute_with(|| {
+        let pause_multisig = PauseAction::Pause {
+            target: target(b"MultisigTransfer", None),
+            duration: 50,
+        };
+        assert_noop!(
+            EmergencyPause::propose_pause_action(
+                RuntimeOrigin::signed(outsider()),
+                nrc_cid(),
+                committee_role(),
+                pause_multisig.clone(),
+                reason_ok(),
+            ),
+            Error::<Test>::UnauthorizedActorRole
+        );
+        for protected in [
+            target(b"System", None),
+            target(b"JointVote", Some(b"joint_vote")),
+            target(b"EmergencyPause", None),
+        ] {
+            assert_noop!(
+                EmergencyPause::propose_pause_action(
+                    RuntimeOrigin::signed(nrc_admin()),
+                    nrc_cid(),
+                    committee_role(),
+                    PauseAction::Pause {
+                        target: protected,
+                        duration: 50,
+                    },
+                    reason_ok(),
+                ),
+                Error::<Test>::ProtectedTarget
+            );
+        }
+        assert_noop!(
+            EmergencyPause::propose_pause_action(
+                RuntimeOrigin::signed(nrc_admin()),
+                nrc_cid(),
+                committee_role(),
+                PauseAction::Pause {
+                    target: target(b"MultisigTransfer", None),
+                    duration: 101,
+                },
+                reason_ok(),
+            ),
+            Error::<Test>::InvalidDuration
+        );
+        assert_noop!(
+            EmergencyPause::propose_pause_action(
+                RuntimeOrigin::signed(nrc_admin()),
+                nrc_cid(),
+                committee_role(),
+                PauseAction::Resume {
+                    target: target(b"MultisigTransfer", None),
+                },
+                reason_ok(),
+            ),
+            Error::<Test>::TargetNotPaused
+        );
+
+        assert_ok!(EmergencyPause::propose_pause_action(
+            RuntimeOrigin::signed(prc_admin()),
+            prc_cid(),
+            committee_role(),
+            pause_multisig,
+            reason_ok(),
+        ));
+        let plan = votingengine::ProposalVotePlans::<Test>::get(100)
+            .expect("pause proposal must bind vote plan");
+        assert_eq!(plan.proposal_owner.as_slice(), crate::MODULE_TAG);
+        assert_eq!(
+            plan.business_action_id.action_code,
+            entity_primitives::business_action::ACTION_EMERGENCY_PAUSE
+        );
+        assert_eq!(plan.voter_subjects.len(), 87);
+    });
+}
+
+#[test]
+fn approved_pause_blocks_calls_until_expiry() {
+    new_test_ext().execute_with(|| {
+        let proposal_id = propose(PauseAction::Pause {
+            target: target(b"SquarePost", None),
+            duration: 30,
+        });
+        assert!(!EmergencyPause::is_call_paused("SquarePost", "subscribe"));
+        assert_eq!(
+            finalize(proposal_id, true),
+            Ok(votingengine::ProposalExecutionOutcome::Executed)
+        );
+        assert!(EmergencyPause::is_call_paused("SquarePost", "subscribe"));
+        assert!(EmergencyPause::is_call_paused("SquarePost", "publish_post"));
+        assert!(!EmergencyPause::is_call_paused(
+            "MultisigTransfer",
+            "propose_transfer"
+        ));
+        assert_eq!(
+            PausedTargets::<Test>::get(target(b"SquarePost", None))
+                .expect("pause recorded")
+                .source,
+            PauseSource::JointVote { proposal_id }
+        );
+
+        run_to_block(30);
+        assert!(EmergencyPause::is_call_paused("SquarePost", "subscribe"));
+        run_to_block(31);
+        assert!(!EmergencyPause::is_call_paused("SquarePost", "subscribe"));
+        assert_eq!(PausedTargets::<Test>::count(), 0);
+        System::assert_has_event(RuntimeEvent::EmergencyPause(Event::PauseExpired {
+            target: target(b"SquarePost", None),
+        }));
+    });
+}
+
+#[test]
+fn call_level_pause_and_resume_only_touch_named_call() {
+    new_test_ext().execute_with(|| {
+        let billing = target(b"SquarePost", Some(b"change_subscription_plan"));
+        let proposal_id = propose(PauseAction::Pause {
+            target: billing.clone(),
+            duration: 100,
+        });
+        assert_ok!(finalize(proposal_id, true));
+        assert!(EmergencyPause::is_call_paused(
+            "SquarePost",
+            "change_subscription_plan"
+        ));
+        assert!(!EmergencyPause::is_call_paused(
+            "SquarePost",
+            "publish_post"
+        ));
+
+        let rejected = propose(PauseAction::Resume {
+            target: billing.clone(),
+        });
+        assert_eq!(
+            finalize(rejected, false),
+            Ok(votingengine::ProposalExecutionOutcome::Executed)
+        );
+        assert!(PausedTargets::<Test>::contains_key(&billing));
+
+        let resume = propose(PauseAction::Resume {
+            target: billing.clone(),
+        });
+        assert_ok!(finalize(resume, true));
+        assert!(!EmergencyPause::is_call_paused(
+            "SquarePost",
+            "change_subscription_plan"
+        ));
+    });
+}
+
+#[test]
+fn fast_track_requires_distinct_nrc_members_and_expires() {
+    new_test_ext().execute_with(|| {
+        let multisig = target(b"MultisigTransfer", None);
+        assert_noop!(
+            EmergencyPause::fast_track_pause(
+                RuntimeOrigin::signed(prc_admin()),
+                prc_cid(),
+                committee_role(),
+                multisig.clone(),
+            ),
+            Error::<Test>::InvalidActorCid
+        );
+        assert_noop!(
+            fast_track(nrc_admin(), target(b"Grandpa", None)),
+            Error::<Test>::ProtectedTarget
+        );
+
+        assert_ok!(fast_track(nrc_admin(), multisig.clone()));
+        assert_noop!(
+            fast_track(nrc_admin(), multisig.clone()),
+            Error::<Test>::AlreadyApproved
+        );
+        assert!(!EmergencyPause::is_call_paused(
+            "MultisigTransfer",
+            "propose_transfer"
+        ));
+
+        assert_ok!(fast_track(nrc_admins()[1].clone(), multisig.clone()));
+        assert!(EmergencyPause::is_call_paused(
+            "MultisigTransfer",
+            "propose_transfer"
+        ));
+        assert!(!FastTrackTallies::<Test>::contains_key(&multisig));
+        let record = PausedTargets::<Test>::get(&multisig).expect("fast-track pause recorded");
+        assert_eq!(record.expires_at, 21);
+        assert_eq!(record.source, PauseSource::FastTrack);
+
+        run_to_block(21);
+        assert!(!EmergencyPause::is_call_paused(
+            "MultisigTransfer",
+            "propose_transfer"
+        ));
+        assert!(!PausedTargets::<Test>::contains_key(&multisig));
+    });
+}
+
+#[test]
+fn fast_track_renewal_never_shortens_joint_vote_pause() {
+    new_test_ext().execute_with(|| {
+        let multisig = target(b"MultisigTransfer", None);
+        let proposal_id = propose(PauseAction::Pause {
+            target: multisig.clone(),
+            duration: 100,
+        });
+        assert_ok!(finalize(proposal_id, true));
+
+        assert_ok!(fast_track(nrc_admins()[1].clone(), multisig.clone()));
+        assert_ok!(fast_track(nrc_admins()[2].clone(), multisig.clone()));
+        let record = PausedTargets::<Test>::get(&multisig).expect("pause kept");
+        assert_eq!(record.expires_at, 101);
+        assert_eq!(record.source, PauseSource::JointVote { proposal_id });
+    });
+}
+
+#[test]
+fn stale_fast_track_tally_lapses_after_window() {
+    new_test_ext().execute_with(|| {
+        let multisig = target(b"MultisigTransfer", None);
+        assert_ok!(fast_track(nrc_admin(), multisig.clone()));
+        run_to_block(11);
+        assert!(!FastTrackTallies::<Test>::contains_key(&multisig));
+        System::assert_has_event(RuntimeEvent::EmergencyPause(Event::FastTrackLapsed {
+            target: multisig.clone(),
+        }));
+
+        // 窗口过后重新计票，旧确认不再计入门槛。
+        assert_ok!(fast_track(nrc_admins()[1].clone(), multisig.clone()));
+        assert!(!PausedTargets::<Test>::contains_key(&multisig));
+        assert_eq!(
+            FastTrackTallies::<Test>::get(&multisig)
+                .expect("new tally opened")
+                .approvers
+                .len(),
+            1
+        );
+    });
+}
+
+#[test]
+fn approved_pause_beyond_registry_capacity_fails_without_partial_state() {
+    new_test_ext().execute_with(|| {
+        for pallet_name in [&b"MultisigTransfer"[..], &b"SquarePost"[..]] {
+            let proposal_id = propose(PauseAction::Pause {
+                target: target(pallet_name, None),
+                duration: 100,
+            });
+            assert_ok!(finalize(proposal_id, true));
+        }
+        let proposal_id = propose(PauseAction::Pause {
+            target: target(b"OffchainTransaction", None),
+            duration: 100,
+        });
+        assert_eq!(
+            finalize(proposal_id, true),
+            Ok(votingengine::ProposalExecutionOutcome::FatalFailed)
+        );
+        assert_eq!(PausedTargets::<Test>::count(), 2);
+        assert!(!EmergencyPause::is_call_paused(
+            "OffchainTransaction",
+            "bind_clearing_bank"
+        ));
+        System::assert_has_event(RuntimeEvent::EmergencyPause(
+            Event::PauseActionExecutionFailed { proposal_id },
+        ));
+    });
+}
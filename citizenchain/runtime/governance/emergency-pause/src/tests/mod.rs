@@ -0,0 +1,407 @@
+#![cfg(test)]
+
+use super::*;
+use core::cell::RefCell;
+use frame_support::{
+    assert_noop, assert_ok, derive_impl,
+    traits::{ConstU32, ConstU64, Hooks},
+};
+use frame_system as system;
+use sp_runtime::{
+    traits::{Hash, IdentityLookup},
+    AccountId32, BuildStorage, DispatchError, DispatchResult,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system;
+
+    #[runtime::pallet_index(1)]
+    pub type VotingEngine = votingengine;
+
+    #[runtime::pallet_index(99)]
+    pub type InternalVote = internal_vote;
+
+    #[runtime::pallet_index(2)]
+    pub type EmergencyPause = super;
+
+    #[runtime::pallet_index(3)]
+    pub type Timestamp = pallet_timestamp;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl system::Config for Test {
+    type Block = Block;
+    type AccountId = AccountId32;
+    type Lookup = IdentityLookup<Self::AccountId>;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<1>;
+    type WeightInfo = ();
+}
+
+pub struct EnsureSignedForTest;
+impl frame_support::traits::EnsureOrigin<RuntimeOrigin> for EnsureSignedForTest {
+    type Success = AccountId32;
+
+    fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+        frame_system::EnsureSigned::<AccountId32>::try_origin(o)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+        Err(())
+    }
+}
+
+thread_local! {
+    static NEXT_JOINT_ID: RefCell<u64> = const { RefCell::new(100) };
+}
+
+pub struct TestJointVoteEngine;
+impl votingengine::JointVoteEngine<AccountId32> for TestJointVoteEngine {
+    fn create_joint_proposal_with_data(
+        _who: AccountId32,
+        _actor_cid_number: Vec<u8>,
+        vote_plan: votingengine::types::VotePlanOf<AccountId32>,
+        data: Vec<u8>,
+    ) -> Result<u64, DispatchError> {
+        let hash = <Test as frame_system::Config>::Hashing::hash(data.as_slice());
+        if hash.as_ref() != vote_plan.business_object_hash.as_slice() {
+            return Err(DispatchError::Other("business object hash mismatch"));
+        }
+        let proposal_id = NEXT_JOINT_ID.with(|id| {
+            let mut id = id.borrow_mut();
+            let proposal_id = *id;
+            *id = id.saturating_add(1);
+            proposal_id
+        });
+        let bounded_data: frame_support::BoundedVec<
+            u8,
+            <Test as votingengine::Config>::MaxProposalDataLen,
+        > = data
+            .try_into()
+            .map_err(|_| DispatchError::Other("proposal data too large"))?;
+        let owner: frame_support::BoundedVec<u8, <Test as votingengine::Config>::MaxModuleTagLen> =
+            vote_plan
+                .proposal_owner
+                .to_vec()
+                .try_into()
+                .map_err(|_| DispatchError::Other("module tag too large"))?;
+        votingengine::ProposalData::<Test>::insert(proposal_id, bounded_data);
+        votingengine::ProposalOwner::<Test>::insert(proposal_id, owner);
+        votingengine::ProposalVotePlans::<Test>::insert(proposal_id, vote_plan);
+        Ok(proposal_id)
+    }
+
+    fn create_joint_proposal_with_data_and_object(
+        _who: AccountId32,
+        _actor_cid_number: Vec<u8>,
+        _vote_plan: votingengine::types::VotePlanOf<AccountId32>,
+        _data: Vec<u8>,
+        _object_kind: u8,
+        _object_data: Vec<u8>,
+    ) -> Result<u64, DispatchError> {
+        Err(DispatchError::Other(
+            "emergency pause proposals carry no object",
+        ))
+    }
+}
+
+pub struct TestTimeProvider;
+impl frame_support::traits::UnixTime for TestTimeProvider {
+    fn now() -> core::time::Duration {
+        core::time::Duration::from_secs(1_782_864_000) // 2026-07-01
+    }
+}
+
+impl votingengine::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxVoteNonceLength = ConstU32<64>;
+    type MaxVoteSignatureLength = ConstU32<64>;
+    type MaxAutoFinalizePerBlock = ConstU32<64>;
+    type MaxAutoFinalizeWeightPerBlock = votingengine::BlockWeightFraction<Test, 4>;
+    type MaxExecutionWeightPerBlock = votingengine::BlockWeightFraction<Test, 4>;
+    type MaxCleanupWeightPerBlock = votingengine::BlockWeightFraction<Test, 8>;
+    type MaxProposalsPerExpiry = ConstU32<128>;
+    type MaxInternalProposalMutexBindings = ConstU32<256>;
+    type MaxActiveProposals = ConstU32<10>;
+    type MaxCleanupStepsPerBlock = ConstU32<8>;
+    type MaxCleanupActivationsPerBlock = ConstU32<50>;
+    type CleanupKeysPerStep = ConstU32<64>;
+    type MaxProposalDataLen = ConstU32<{ 100 * 1024 }>;
+    type MaxProposalObjectLen = ConstU32<{ 10 * 1024 }>;
+    type MaxModuleTagLen = ConstU32<32>;
+    type MaxManualExecutionAttempts = ConstU32<3>;
+    type ExecutionRetryGraceBlocks = ConstU64<216>;
+    type MaxExecutionRetryDeadlinesPerBlock = ConstU32<128>;
+    type MaxPendingRetryExpirationsPerBlock = ConstU32<16>;
+    type CitizenIdentityReader = ();
+    type JointVoteResultCallback = ();
+    type InternalVoteResultCallback = ();
+    type InternalAdminProvider = TestInternalAdminProvider;
+    type MaxAdminsPerInstitution = ConstU32<32>;
+    type TimeProvider = TestTimeProvider;
+    type WeightInfo = ();
+    type TrackHandlers = (InternalVote, ());
+    type LegislationVoteResultCallback = ();
+    type ElectionVoteResultCallback = ();
+}
+
+pub struct TestInternalAdminProvider;
+impl votingengine::InternalAdminProvider<AccountId32> for TestInternalAdminProvider {
+    fn is_institution_admin(
+        institution_code: votingengine::InstitutionCode,
+        cid_number: &[u8],
+        who: &AccountId32,
+    ) -> bool {
+        (institution_code == votingengine::types::NRC
+            && cid_number == nrc_cid().as_slice()
+            && nrc_admins().contains(who))
+            || (institution_code == votingengine::types::PRC
+                && cid_number == prc_cid().as_slice()
+                && *who == prc_admin())
+    }
+}
+
+/// NRC 委员拥有联合投票与快速通道两项提案权；PRC 委员只有联合投票提案权。
+pub struct TestInstitutionRoleAuthorization;
+impl entity_primitives::InstitutionRoleAuthorizationQuery<AccountId32>
+    for TestInstitutionRoleAuthorization
+{
+    fn role_has_permission(
+        role_subject: &entity_primitives::RoleSubject<Vec<u8>, Vec<u8>>,
+        business_action_id: &entity_primitives::BusinessActionId<Vec<u8>>,
+        operation: entity_primitives::RolePermissionOperation,
+    ) -> bool {
+        use entity_primitives::business_action::{
+            ACTION_EMERGENCY_PAUSE, ACTION_EMERGENCY_PAUSE_FAST_TRACK,
+        };
+        let action_allowed = business_action_id.action_code == ACTION_EMERGENCY_PAUSE
+            || (business_action_id.action_code == ACTION_EMERGENCY_PAUSE_FAST_TRACK
+                && role_subject.cid_number == nrc_cid().to_vec());
+        role_subject.role_code == primitives::governance_skeleton::ROLE_CODE_COMMITTEE_MEMBER
+            && business_action_id.module_tag == crate::MODULE_TAG
+            && action_allowed
+            && operation == entity_primitives::RolePermissionOperation::Propose
+    }
+
+    fn is_authorized(
+        admin: &AccountId32,
+        role_subject: &entity_primitives::RoleSubject<Vec<u8>, Vec<u8>>,
+        business_action_id: &entity_primitives::BusinessActionId<Vec<u8>>,
+        operation: entity_primitives::RolePermissionOperation,
+    ) -> bool {
+        let valid_actor = (role_subject.cid_number == nrc_cid().to_vec()
+            && nrc_admins().contains(admin))
+            || (role_subject.cid_number == prc_cid().to_vec() && admin == &prc_admin());
+        valid_actor && Self::role_has_permission(role_subject, business_action_id, operation)
+    }
+
+    fn role_subjects_with_permission(
+        cid_number: &[u8],
+        business_action_id: &entity_primitives::BusinessActionId<Vec<u8>>,
+        operation: entity_primitives::RolePermissionOperation,
+    ) -> Vec<entity_primitives::RoleSubject<Vec<u8>, Vec<u8>>> {
+        let role_subject = entity_primitives::RoleSubject {
+            cid_number: cid_number.to_vec(),
+            role_code: primitives::governance_skeleton::ROLE_CODE_COMMITTEE_MEMBER.to_vec(),
+        };
+        Self::role_has_permission(&role_subject, business_action_id, operation)
+            .then_some(role_subject)
+            .into_iter()
+            .collect()
+    }
+}
+
+impl votingengine::InstitutionRoleProvider<AccountId32> for TestInstitutionRoleAuthorization {
+    fn is_active_assignment(cid_number: &[u8], who: &AccountId32, role_code: &[u8]) -> bool {
+        role_code == primitives::governance_skeleton::ROLE_CODE_COMMITTEE_MEMBER
+            && [votingengine::types::NRC, votingengine::types::PRC]
+                .into_iter()
+                .any(|code| {
+                    <TestInternalAdminProvider as votingengine::InternalAdminProvider<
+                        AccountId32,
+                    >>::is_institution_admin(code, cid_number, who)
+                })
+    }
+
+    fn active_accounts_for_role(cid_number: &[u8], role_code: &[u8]) -> Vec<AccountId32> {
+        nrc_admins()
+            .into_iter()
+            .chain([prc_admin()])
+            .filter(|admin| Self::is_active_assignment(cid_number, admin, role_code))
+            .collect()
+    }
+}
+
+impl internal_vote::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type InstitutionRoleProvider = TestInstitutionRoleAuthorization;
+    type WeightInfo = ();
+}
+
+impl pallet::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type ProposeOrigin = EnsureSignedForTest;
+    type JointVoteEngine = TestJointVoteEngine;
+    type InstitutionRoleAuthorization = TestInstitutionRoleAuthorization;
+    type MaxReasonLen = ConstU32<64>;
+    type MaxPausedTargets = ConstU32<2>;
+    type MaxPauseDuration = ConstU64<100>;
+    type FastTrackThreshold = ConstU32<2>;
+    type FastTrackDuration = ConstU64<20>;
+    type FastTrackApprovalWindow = ConstU64<10>;
+    type WeightInfo = ();
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+    let storage = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .expect("test storage should build");
+    let mut ext: sp_io::TestExternalities = storage.into();
+    ext.execute_with(|| {
+        NEXT_JOINT_ID.with(|id| *id.borrow_mut() = 100);
+        System::set_block_number(1);
+    });
+    ext
+}
+
+fn nrc_admins() -> Vec<AccountId32> {
+    vec![
+        AccountId32::new([1u8; 32]),
+        AccountId32::new([5u8; 32]),
+        AccountId32::new([6u8; 32]),
+    ]
+}
+
+fn nrc_admin() -> AccountId32 {
+    nrc_admins()[0].clone()
+}
+
+fn outsider() -> AccountId32 {
+    AccountId32::new([2u8; 32])
+}
+
+fn prc_admin() -> AccountId32 {
+    AccountId32::new([3u8; 32])
+}
+
+fn nrc_cid() -> votingengine::types::CidNumber {
+    primitives::cid::china::china_cb::CHINA_CB[0]
+        .cid_number
+        .as_bytes()
+        .to_vec()
+        .try_into()
+        .expect("NRC CID fits runtime bound")
+}
+
+fn prc_cid() -> votingengine::types::CidNumber {
+    primitives::cid::china::china_cb::CHINA_CB[1]
+        .cid_number
+        .as_bytes()
+        .to_vec()
+        .try_into()
+        .expect("PRC CID fits runtime bound")
+}
+
+fn committee_role() -> votingengine::types::RoleCode {
+    primitives::governance_skeleton::ROLE_CODE_COMMITTEE_MEMBER
+        .to_vec()
+        .try_into()
+        .expect("committee role fits runtime bound")
+}
+
+fn reason_ok() -> pallet::ReasonOf<Test> {
+    b"pause reason"
+        .to_vec()
+        .try_into()
+        .expect("reason should fit")
+}
+
+fn target(pallet_name: &[u8], call_name: Option<&[u8]>) -> PauseTarget {
+    PauseTarget {
+        pallet_name: pallet_name.to_vec().try_into().expect("name fits"),
+        call_name: call_name.map(|name| name.to_vec().try_into().expect("name fits")),
+    }
+}
+
+fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        let next = System::block_number() + 1;
+        System::set_block_number(next);
+        EmergencyPause::on_initialize(next);
+    }
+}
+
+fn propose(action: pallet::PauseActionOf<Test>) -> u64 {
+    assert_ok!(EmergencyPause::propose_pause_action(
+        RuntimeOrigin::signed(nrc_admin()),
+        nrc_cid(),
+        committee_role(),
+        action,
+        reason_ok(),
+    ));
+    NEXT_JOINT_ID.with(|id| *id.borrow() - 1)
+}
+
+fn fast_track(who: AccountId32, target: PauseTarget) -> DispatchResult {
+    EmergencyPause::fast_track_pause(
+        RuntimeOrigin::signed(who),
+        nrc_cid(),
+        committee_role(),
+        target,
+    )
+}
+
+/// 插入投票引擎侧 Proposal 并在回调作用域内终结，模拟 votingengine 的真实回调上下文。
+fn finalize(
+    proposal_id: u64,
+    approved: bool,
+) -> Result<votingengine::ProposalExecutionOutcome, DispatchError> {
+    votingengine::pallet::Proposals::<Test>::insert(
+        proposal_id,
+        votingengine::Proposal {
+            kind: votingengine::PROPOSAL_KIND_JOINT,
+            stage: votingengine::STAGE_JOINT,
+            status: if approved {
+                votingengine::STATUS_PASSED
+            } else {
+                votingengine::STATUS_REJECTED
+            },
+            internal_code: None,
+            actor_cid_number: Some(nrc_cid()),
+            execution_account_id: None,
+            subject_cid_numbers: Default::default(),
+            start: 0u64,
+            end: 100u64,
+        },
+    );
+    votingengine::pallet::CallbackExecutionScopes::<Test>::insert(proposal_id, ());
+    let result = EmergencyPause::on_joint_vote_finalized(proposal_id, approved);
+    votingengine::pallet::CallbackExecutionScopes::<Test>::remove(proposal_id);
+    result
+}
+
+mod cases;
@@ -0,0 +1,52 @@
+//! 手工估算占位 weights，待 benchmark CLI 生成后替换。
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions for `emergency_pause`.
+pub trait WeightInfo {
+	/// 委员岗位任职人创建暂停/解除联合投票提案。
+	fn propose_pause_action() -> Weight;
+	/// NRC 委员快速通道确认；达到门槛时同块写入暂停记录。
+	fn fast_track_pause() -> Weight;
+}
+
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn propose_pause_action() -> Weight {
+		// 联合投票创建成本由人口与岗位快照主导，按决议发行提案实测值取上界。
+		Weight::from_parts(4_861_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 56460075))
+			.saturating_add(T::DbWeight::get().reads(626))
+			.saturating_add(T::DbWeight::get().writes(278))
+	}
+	fn fast_track_pause() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 700_000))
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+}
+
+impl WeightInfo for () {
+	fn propose_pause_action() -> Weight {
+		Weight::from_parts(4_861_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 56460075))
+			.saturating_add(RocksDbWeight::get().reads(626))
+			.saturating_add(RocksDbWeight::get().writes(278))
+	}
+	fn fast_track_pause() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 700_000))
+			.saturating_add(RocksDbWeight::get().reads(12))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+}
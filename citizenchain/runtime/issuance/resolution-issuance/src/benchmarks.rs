@@ -5,15 +5,20 @@
 use codec::Decode;
 use frame_benchmarking::v2::*;
 use frame_support::{
-    traits::{EnsureOrigin, Get},
+    traits::{Currency, EnsureOrigin, Get},
     BoundedVec,
 };
+use frame_system::RawOrigin;
 use primitives::cid::china::china_cb::CHINA_CB;
 use sp_runtime::traits::{CheckedAdd, SaturatedConversion, Zero};
 use sp_std::{vec, vec::Vec};
 use votingengine::CitizenIdentityReader;
 
-use crate::{pallet, AllowedRecipients, Config, Pallet, VotingProposalCount};
+use crate::{
+    pallet,
+    vesting::{VestingPeriod, VestingTerms},
+    AllowedRecipients, Config, Pallet, TotalVestingLocked, VestingSchedules, VotingProposalCount,
+};
 
 fn decode_account<T: pallet::Config>(raw: [u8; 32]) -> T::AccountId {
     T::AccountId::decode(&mut &raw[..]).expect("benchmark account must decode")
@@ -108,4 +113,81 @@ mod benchmarks {
 
         assert_eq!(VotingProposalCount::<T>::get(), 1u32);
     }
+
+    #[benchmark]
+    fn propose_vested_issuance() {
+        let origin = T::ProposeOrigin::try_successful_origin()
+            .expect("benchmark proposer_account_id origin must be available");
+        let recipients = prc_recipients::<T>();
+        AllowedRecipients::<T>::put(recipients);
+        VotingProposalCount::<T>::put(0u32);
+        let actor_cid_number = nrc_cid_number();
+        let proposer_role_code: votingengine::types::RoleCode =
+            primitives::governance_skeleton::ROLE_CODE_COMMITTEE_MEMBER
+                .to_vec()
+                .try_into()
+                .expect("committee role code must fit");
+        let scope = votingengine::PopulationScope::Country;
+        let citizen: T::AccountId = frame_benchmarking::account("resolution-citizen", 0, 0);
+        <T as votingengine::Config>::CitizenIdentityReader::benchmark_seed_identity(
+            &citizen, &scope,
+        );
+
+        let reason = reason_max::<T>();
+        let (allocations, total_amount) = full_allocations::<T>();
+        // 最坏情况：每条分配都带归属条款，逐个检查收款账户的计划余量。
+        let vesting: pallet::VestingTermsOf<T> = vec![
+            Some(VestingTerms {
+                period: VestingPeriod::CalendarMonths,
+                cliff: 12,
+                duration: 48,
+            });
+            allocations.len()
+        ]
+        .try_into()
+        .expect("benchmark vesting should fit MaxAllocations");
+
+        #[block]
+        {
+            Pallet::<T>::propose_vested_issuance(
+                origin,
+                actor_cid_number,
+                proposer_role_code,
+                reason,
+                total_amount,
+                allocations,
+                vesting,
+            )
+            .expect("benchmark vested resolution issuance proposal should succeed");
+        }
+
+        assert_eq!(VotingProposalCount::<T>::get(), 1u32);
+    }
+
+    #[benchmark]
+    fn claim_vested() {
+        let caller: T::AccountId = whitelisted_caller();
+        let recipient: T::AccountId = frame_benchmarking::account("resolution-vesting", 0, 0);
+        let amount: pallet::BalanceOf<T> = 1_000_000u128.saturated_into();
+        let terms = VestingTerms {
+            period: VestingPeriod::Blocks,
+            cliff: 0,
+            duration: 10,
+        };
+        // 最坏情况：计划数满额，且全部到期被一次清空。
+        for proposal_id in 0..T::MaxVestingSchedules::get() {
+            let _ = T::Currency::deposit_creating(&recipient, amount);
+            Pallet::<T>::add_vesting_schedule(proposal_id as u64, &recipient, amount, terms)
+                .expect("benchmark vesting schedule should be added");
+        }
+        frame_system::Pallet::<T>::set_block_number(
+            frame_system::Pallet::<T>::block_number() + 10u32.into(),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), recipient.clone());
+
+        assert!(VestingSchedules::<T>::get(&recipient).is_empty());
+        assert!(TotalVestingLocked::<T>::get().is_zero());
+    }
 }
@@ -1,8 +1,11 @@
 //! 决议发行执行与审计记录逻辑。
 
-use crate::pallet::{
-    AllocationOf, BalanceOf, Config, Error, Event, EverExecuted, Executed, Pallet, ReasonOf,
-    TotalIssued,
+use crate::{
+    pallet::{
+        AllocationOf, BalanceOf, Config, Error, Event, EverExecuted, Executed, Pallet, ReasonOf,
+        TotalIssued,
+    },
+    vesting::VestingTerms,
 };
 use frame_support::{
     dispatch::DispatchResult,
@@ -18,10 +21,17 @@ impl<T: Config> Pallet<T> {
         reason: &ReasonOf<T>,
         total_amount: BalanceOf<T>,
         allocations: &AllocationOf<T>,
+        vesting: &[Option<VestingTerms>],
     ) -> DispatchResult {
         // 执行发行必须整笔成功或整笔回滚，不能出现部分账户已到账的半状态。
         with_storage_layer(|| {
-            Self::do_execute_inner(proposal_id, reason.as_slice(), total_amount, allocations)
+            Self::do_execute_inner(
+                proposal_id,
+                reason.as_slice(),
+                total_amount,
+                allocations,
+                vesting,
+            )
         })
     }
 
@@ -30,6 +40,7 @@ impl<T: Config> Pallet<T> {
         reason: &[u8],
         total_amount: BalanceOf<T>,
         allocations: &AllocationOf<T>,
+        vesting: &[Option<VestingTerms>],
     ) -> DispatchResult {
         // 重放判断只认永久标记 EverExecuted。
         ensure!(
@@ -42,6 +53,7 @@ impl<T: Config> Pallet<T> {
             Error::<T>::ReasonTooLong
         );
         Self::validate_execution_allocations(&total_amount, allocations)?;
+        Self::validate_vesting_terms(allocations.len(), vesting)?;
 
         let existential_deposit = T::Currency::minimum_balance();
         for item in allocations.iter() {
@@ -74,6 +86,18 @@ impl<T: Config> Pallet<T> {
         // 统一 drop 合并后的 imbalance，让 Currency 在这一点完成总发行量记账。
         drop(total_imbalance);
 
+        // 分期项同样整笔铸入，未归属部分立即上锁；TotalIssued 仍按整笔计。
+        for (item, terms) in allocations.iter().zip(vesting.iter()) {
+            if let Some(terms) = terms {
+                Self::add_vesting_schedule(
+                    proposal_id,
+                    &item.recipient_account_id,
+                    item.amount,
+                    *terms,
+                )?;
+            }
+        }
+
         let current_block = frame_system::Pallet::<T>::block_number();
         EverExecuted::<T>::insert(proposal_id, ());
         Executed::<T>::insert(proposal_id, current_block);
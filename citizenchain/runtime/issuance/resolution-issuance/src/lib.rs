@@ -2,7 +2,7 @@
 //!
 //! 本模块把决议发行治理与执行合并为一个完整业务 pallet：
 //! 在同一模块内完成决议发行提案、联合投票回调、发行执行、
-//! 防重放、暂停和审计维护。
+//! 分期归属释放、防重放和审计维护。
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -13,6 +13,7 @@ pub mod proposal;
 #[cfg(test)]
 mod tests;
 pub mod validation;
+pub mod vesting;
 pub mod weights;
 
 pub use pallet::*;
@@ -25,10 +26,17 @@ pub const MODULE_TAG: &[u8] = b"res-iss";
 
 #[frame_support::pallet]
 pub mod pallet {
-    use crate::{proposal::RecipientAmount, weights::WeightInfo};
+    use crate::{
+        proposal::RecipientAmount,
+        vesting::{VestingSchedule, VestingTerms},
+        weights::WeightInfo,
+    };
     use codec::Decode;
     use entity_primitives::InstitutionRoleAuthorizationQuery;
-    use frame_support::{pallet_prelude::*, traits::Currency};
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{Currency, LockableCurrency},
+    };
     use frame_system::pallet_prelude::*;
     use primitives::cid::china::china_cb::CHINA_CB;
     #[cfg(feature = "std")]
@@ -43,6 +51,9 @@ pub mod pallet {
         RecipientAmount<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
         <T as Config>::MaxAllocations,
     >;
+    /// 与 `AllocationOf` 逐项对齐的归属条款，`None` 表示该项即时到账。
+    pub type VestingTermsOf<T> = BoundedVec<Option<VestingTerms>, <T as Config>::MaxAllocations>;
+    pub type VestingScheduleOf<T> = VestingSchedule<BalanceOf<T>, BlockNumberFor<T>>;
 
     /// 联合投票终结后的业务执行结果，用于回调时告知投票引擎写入最终执行状态。
     pub(crate) enum FinalizeOutcome {
@@ -56,7 +67,8 @@ pub mod pallet {
         #[allow(deprecated)]
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-        type Currency: Currency<Self::AccountId>;
+        /// 分期归属用余额锁锁定未释放部分。
+        type Currency: LockableCurrency<Self::AccountId>;
 
         /// 允许国家储委会或省储委会管理员发起决议发行提案。
         type ProposeOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
@@ -74,6 +86,9 @@ pub mod pallet {
         type MaxTotalIssuance: Get<BalanceOf<Self>>;
         #[pallet::constant]
         type MaxSingleIssuance: Get<BalanceOf<Self>>;
+        /// 单个收款账户同时持有的归属计划上限。
+        #[pallet::constant]
+        type MaxVestingSchedules: Get<u32>;
 
         type WeightInfo: crate::weights::WeightInfo;
     }
@@ -104,10 +119,24 @@ pub mod pallet {
     #[pallet::storage]
     pub type EverExecuted<T: Config> = StorageMap<_, Twox64Concat, u64, (), OptionQuery>;
 
-    /// 决议发行累计执行量。
+    /// 决议发行累计执行量(含尚在归属锁定中的部分)。
     #[pallet::storage]
     pub type TotalIssued<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+    /// 收款账户的未释放归属计划。
+    #[pallet::storage]
+    pub type VestingSchedules<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<VestingScheduleOf<T>, T::MaxVestingSchedules>,
+        ValueQuery,
+    >;
+
+    /// 已发行但仍被归属锁定的总量，恒不大于 `TotalIssued`。
+    #[pallet::storage]
+    pub type TotalVestingLocked<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub allowed_recipients: Vec<T::AccountId>,
@@ -164,6 +193,30 @@ pub mod pallet {
                 "MaxSingleIssuance must not exceed MaxTotalIssuance"
             );
             assert!(T::MaxReasonLen::get() > 0, "MaxReasonLen must be > 0");
+            assert!(
+                T::MaxVestingSchedules::get() > 0,
+                "MaxVestingSchedules must be > 0"
+            );
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            use sp_runtime::traits::{Saturating, Zero};
+
+            let outstanding = VestingSchedules::<T>::iter_values()
+                .flat_map(|schedules| schedules.into_iter())
+                .fold(BalanceOf::<T>::zero(), |sum, s| {
+                    sum.saturating_add(s.total.saturating_sub(s.claimed))
+                });
+            frame_support::ensure!(
+                outstanding == TotalVestingLocked::<T>::get(),
+                "TotalVestingLocked 与归属计划未释放额之和不一致"
+            );
+            frame_support::ensure!(
+                outstanding <= TotalIssued::<T>::get(),
+                "归属锁定量不得超过决议发行累计量"
+            );
+            Ok(())
         }
     }
 
@@ -196,6 +249,19 @@ pub mod pallet {
             reason_hash: T::Hash,
             allocations_hash: T::Hash,
         },
+        /// 分期分配已铸入收款账户并整笔锁定，按条款逐步释放。
+        VestingScheduled {
+            proposal_id: u64,
+            recipient_account_id: T::AccountId,
+            amount: BalanceOf<T>,
+            terms: VestingTerms,
+        },
+        /// 已归属金额解锁。
+        VestedClaimed {
+            recipient_account_id: T::AccountId,
+            amount: BalanceOf<T>,
+            still_locked: BalanceOf<T>,
+        },
     }
 
     #[pallet::error]
@@ -227,6 +293,16 @@ pub mod pallet {
         InvalidActorCid,
         /// 发起人没有目标机构委员岗位的决议发行提案权限。
         UnauthorizedActorRole,
+        /// 归属条款条数必须为零或与分配条数一致。
+        VestingLengthMismatch,
+        /// 归属条款无效:期限为零、cliff 超过期限或自然月期限过长。
+        InvalidVestingTerms,
+        /// 收款账户的归属计划数已达上限。
+        TooManyVestingSchedules,
+        /// 账户没有归属计划。
+        NoVestingSchedule,
+        /// 当前没有新归属的金额可释放。
+        NothingToClaim,
     }
 
     #[pallet::call]
@@ -252,9 +328,46 @@ pub mod pallet {
                 reason,
                 total_amount,
                 allocations,
+                Vec::new(),
+            )
+        }
+
+        /// 创建“分期决议发行”联合投票提案：`vesting` 与 `allocations` 逐项对齐，
+        /// `None` 项即时到账，其余项按归属条款锁定后逐步释放。
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::propose_vested_issuance())]
+        pub fn propose_vested_issuance(
+            origin: OriginFor<T>,
+            actor_cid_number: votingengine::types::CidNumber,
+            proposer_role_code: votingengine::types::RoleCode,
+            reason: ReasonOf<T>,
+            total_amount: BalanceOf<T>,
+            allocations: AllocationOf<T>,
+            vesting: VestingTermsOf<T>,
+        ) -> DispatchResult {
+            let proposer_account_id = T::ProposeOrigin::ensure_origin(origin)?;
+            Self::create_resolution_issuance_proposal(
+                proposer_account_id,
+                actor_cid_number,
+                proposer_role_code,
+                reason,
+                total_amount,
+                allocations,
+                vesting.into_inner(),
             )
         }
 
+        /// 释放收款账户已归属的金额。任何签名账户都可代为触发，只解锁不转账。
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::claim_vested())]
+        pub fn claim_vested(
+            origin: OriginFor<T>,
+            recipient_account_id: T::AccountId,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            Self::do_claim_vested(&recipient_account_id)
+        }
+
         // call_index 2/3/4(set_allowed_recipients / clear_executed / set_paused)已删除:
         // 三者均以 EnsureRoot 门控,而本 runtime 无 Sudo/治理派发 Root,永久不可达=死入口。
         // 收款集合按创世固定,应急运维走开发期直升 runtime(dev-direct upgrade);
@@ -1,8 +1,11 @@
 //! 决议发行提案与联合投票回调逻辑。
 
-use crate::pallet::{
-    AllocationOf, BalanceOf, Config, Error, Event, FinalizeOutcome, Pallet, ReasonOf,
-    VotingProposalCount,
+use crate::{
+    pallet::{
+        AllocationOf, BalanceOf, Config, Error, Event, FinalizeOutcome, Pallet, ReasonOf,
+        VotingProposalCount,
+    },
+    vesting::VestingTerms,
 };
 use codec::{Decode, Encode};
 use entity_primitives::{
@@ -45,7 +48,10 @@ pub struct RecipientAmount<AccountId, Balance> {
 }
 
 /// 存入 votingengine ProposalData 的业务数据结构。
-#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+///
+/// `vesting` 为空时编码与未引入分期归属前完全一致；非空时作为尾部字段追加，
+/// 已上链的即时发行提案和只认前六个字段的解码方都不受影响。
+#[derive(Clone, RuntimeDebug, PartialEq, Eq)]
 pub struct IssuanceProposalData<AccountId, Balance> {
     pub actor_cid_number: votingengine::types::CidNumber,
     pub proposer_role_code: votingengine::types::RoleCode,
@@ -53,6 +59,45 @@ pub struct IssuanceProposalData<AccountId, Balance> {
     pub reason: Vec<u8>,
     pub total_amount: Balance,
     pub allocations: Vec<RecipientAmount<AccountId, Balance>>,
+    pub vesting: Vec<Option<VestingTerms>>,
+}
+
+impl<AccountId: Encode, Balance: Encode> Encode for IssuanceProposalData<AccountId, Balance> {
+    fn encode_to<W: codec::Output + ?Sized>(&self, dest: &mut W) {
+        self.actor_cid_number.encode_to(dest);
+        self.proposer_role_code.encode_to(dest);
+        self.proposer_account_id.encode_to(dest);
+        self.reason.encode_to(dest);
+        self.total_amount.encode_to(dest);
+        self.allocations.encode_to(dest);
+        if !self.vesting.is_empty() {
+            self.vesting.encode_to(dest);
+        }
+    }
+}
+
+impl<AccountId: Decode, Balance: Decode> Decode for IssuanceProposalData<AccountId, Balance> {
+    fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let actor_cid_number = Decode::decode(input)?;
+        let proposer_role_code = Decode::decode(input)?;
+        let proposer_account_id = Decode::decode(input)?;
+        let reason = Decode::decode(input)?;
+        let total_amount = Decode::decode(input)?;
+        let allocations = Decode::decode(input)?;
+        let vesting = match input.remaining_len()? {
+            Some(0) => Vec::new(),
+            _ => Decode::decode(input)?,
+        };
+        Ok(Self {
+            actor_cid_number,
+            proposer_role_code,
+            proposer_account_id,
+            reason,
+            total_amount,
+            allocations,
+            vesting,
+        })
+    }
 }
 
 impl<T: Config> Pallet<T> {
@@ -128,6 +173,7 @@ impl<T: Config> Pallet<T> {
         reason: ReasonOf<T>,
         total_amount: BalanceOf<T>,
         allocations: AllocationOf<T>,
+        vesting: Vec<Option<VestingTerms>>,
     ) -> DispatchResult {
         ensure!(!reason.is_empty(), Error::<T>::EmptyReason);
         let actor_text = core::str::from_utf8(actor_cid_number.as_slice())
@@ -159,6 +205,8 @@ impl<T: Config> Pallet<T> {
             Error::<T>::UnauthorizedActorRole
         );
         Self::validate_proposal_allocations(&total_amount, allocations.as_slice())?;
+        Self::validate_vesting_terms(allocations.len(), vesting.as_slice())?;
+        Self::ensure_vesting_capacity(allocations.as_slice(), vesting.as_slice())?;
 
         // 联合投票提案创建、业务数据写入和计数递增必须原子提交；
         // 任一步失败都不能留下孤儿提案或错误的 VotingProposalCount。
@@ -170,6 +218,7 @@ impl<T: Config> Pallet<T> {
                 reason: reason.to_vec(),
                 total_amount,
                 allocations: allocations.to_vec(),
+                vesting,
             };
             let mut encoded = Vec::from(crate::MODULE_TAG);
             encoded.extend_from_slice(&data.encode());
@@ -278,6 +327,7 @@ impl<T: Config> Pallet<T> {
                     &execute_reason,
                     data.total_amount,
                     &execute_allocations,
+                    data.vesting.as_slice(),
                 )
                 .is_ok()
                 {
@@ -291,6 +291,7 @@ fn callback_rejects_corrupted_reason_with_reason_too_long() {
                 reason: vec![b'x'; 129],
                 total_amount: 4300,
                 allocations: allocations_ok(4300).to_vec(),
+                vesting: Vec::new(),
             },
         );
         insert_engine_proposal(100);
@@ -331,3 +332,284 @@ fn issuance_event_comes_from_unified_pallet() {
         }));
     });
 }
+
+fn vesting_for_first_two(
+    first: crate::vesting::VestingTerms,
+    second: crate::vesting::VestingTerms,
+) -> pallet::VestingTermsOf<Test> {
+    let mut vesting = vec![None; reserve_council_accounts().len()];
+    vesting[0] = Some(first);
+    vesting[1] = Some(second);
+    vesting.try_into().expect("vesting should fit")
+}
+
+fn block_terms(cliff: u32, duration: u32) -> crate::vesting::VestingTerms {
+    crate::vesting::VestingTerms {
+        period: crate::vesting::VestingPeriod::Blocks,
+        cliff,
+        duration,
+    }
+}
+
+fn month_terms(cliff: u32, duration: u32) -> crate::vesting::VestingTerms {
+    crate::vesting::VestingTerms {
+        period: crate::vesting::VestingPeriod::CalendarMonths,
+        cliff,
+        duration,
+    }
+}
+
+fn vesting_lock(who: &AccountId32) -> Balance {
+    pallet_balances::Locks::<Test>::get(who)
+        .iter()
+        .find(|lock| lock.id == crate::vesting::VESTING_LOCK_ID)
+        .map(|lock| lock.amount)
+        .unwrap_or(0)
+}
+
+#[test]
+fn vested_issuance_rejects_misaligned_or_invalid_terms() {
+    new_test_ext().execute_with(|| {
+        let short: pallet::VestingTermsOf<Test> = vec![Some(block_terms(0, 10))]
+            .try_into()
+            .expect("vesting should fit");
+        assert_noop!(
+            ResolutionIssuance::propose_vested_issuance(
+                RuntimeOrigin::signed(AccountId32::new([1u8; 32])),
+                actor_cid_number(),
+                committee_role_code(),
+                reason_ok(),
+                4300,
+                allocations_ok(4300),
+                short
+            ),
+            pallet::Error::<Test>::VestingLengthMismatch
+        );
+        assert_noop!(
+            ResolutionIssuance::propose_vested_issuance(
+                RuntimeOrigin::signed(AccountId32::new([1u8; 32])),
+                actor_cid_number(),
+                committee_role_code(),
+                reason_ok(),
+                4300,
+                allocations_ok(4300),
+                vesting_for_first_two(block_terms(11, 10), month_terms(0, 12))
+            ),
+            pallet::Error::<Test>::InvalidVestingTerms
+        );
+        assert_noop!(
+            ResolutionIssuance::propose_vested_issuance(
+                RuntimeOrigin::signed(AccountId32::new([1u8; 32])),
+                actor_cid_number(),
+                committee_role_code(),
+                reason_ok(),
+                4300,
+                allocations_ok(4300),
+                vesting_for_first_two(block_terms(0, 10), month_terms(0, 1_201))
+            ),
+            pallet::Error::<Test>::InvalidVestingTerms
+        );
+    });
+}
+
+#[test]
+fn vested_issuance_checks_schedule_capacity_at_proposal_time() {
+    new_test_ext().execute_with(|| {
+        let recipients = reserve_council_accounts();
+        // mock 上限为 2:首个收款账户已满额，第二个仍有余量。
+        for proposal_id in 0..2 {
+            assert_ok!(ResolutionIssuance::add_vesting_schedule(
+                proposal_id,
+                &recipients[0],
+                10,
+                block_terms(0, 10)
+            ));
+        }
+        assert_noop!(
+            ResolutionIssuance::propose_vested_issuance(
+                RuntimeOrigin::signed(AccountId32::new([1u8; 32])),
+                actor_cid_number(),
+                committee_role_code(),
+                reason_ok(),
+                4300,
+                allocations_ok(4300),
+                vesting_for_first_two(block_terms(0, 10), block_terms(0, 10))
+            ),
+            pallet::Error::<Test>::TooManyVestingSchedules
+        );
+
+        // 满额账户只收即时项时不受计划上限约束。
+        let mut vesting = vec![None; recipients.len()];
+        vesting[1] = Some(block_terms(0, 10));
+        assert_ok!(ResolutionIssuance::propose_vested_issuance(
+            RuntimeOrigin::signed(AccountId32::new([1u8; 32])),
+            actor_cid_number(),
+            committee_role_code(),
+            reason_ok(),
+            4300,
+            allocations_ok(4300),
+            vesting.try_into().expect("vesting should fit")
+        ));
+    });
+}
+
+#[test]
+fn vesting_capacity_counts_new_schedules_per_recipient() {
+    new_test_ext().execute_with(|| {
+        let recipient = reserve_council_accounts()[0].clone();
+        assert_ok!(ResolutionIssuance::add_vesting_schedule(
+            0,
+            &recipient,
+            10,
+            block_terms(0, 10)
+        ));
+        let item = crate::proposal::RecipientAmount {
+            recipient_account_id: recipient,
+            amount: 10,
+        };
+        let allocations = vec![item.clone(), item];
+        // 已持 1 条、上限 2:同一提案再给两条会在执行时溢出，提案时即须拒绝。
+        assert_noop!(
+            ResolutionIssuance::ensure_vesting_capacity(
+                &allocations,
+                &[Some(block_terms(0, 10)), Some(block_terms(0, 10))]
+            ),
+            pallet::Error::<Test>::TooManyVestingSchedules
+        );
+        assert_ok!(ResolutionIssuance::ensure_vesting_capacity(
+            &allocations,
+            &[Some(block_terms(0, 10)), None]
+        ));
+    });
+}
+
+#[test]
+fn vested_issuance_mints_in_full_and_releases_by_blocks() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ResolutionIssuance::propose_vested_issuance(
+            RuntimeOrigin::signed(AccountId32::new([1u8; 32])),
+            actor_cid_number(),
+            committee_role_code(),
+            reason_ok(),
+            4300,
+            allocations_ok(4300),
+            vesting_for_first_two(block_terms(2, 10), month_terms(1, 4))
+        ));
+        let data =
+            ResolutionIssuance::load_proposal_data(100).expect("vested proposal data must decode");
+        assert_eq!(data.vesting.len(), 43);
+
+        insert_engine_proposal(100);
+        assert_ok!(call_joint_callback(100, true));
+
+        let recipients = reserve_council_accounts();
+        let vested = &recipients[0];
+        // 即时项不上锁；分期项整笔到账并整笔锁定，TotalIssued 仍按全额计。
+        assert_eq!(Balances::free_balance(vested), 100);
+        assert_eq!(vesting_lock(vested), 100);
+        assert_eq!(vesting_lock(&recipients[2]), 0);
+        assert_eq!(pallet::TotalIssued::<Test>::get(), 4300);
+        assert_eq!(pallet::TotalVestingLocked::<Test>::get(), 200);
+
+        System::set_block_number(2);
+        assert_noop!(
+            ResolutionIssuance::claim_vested(
+                RuntimeOrigin::signed(AccountId32::new([9u8; 32])),
+                vested.clone()
+            ),
+            pallet::Error::<Test>::NothingToClaim
+        );
+
+        System::set_block_number(4);
+        assert_ok!(ResolutionIssuance::claim_vested(
+            RuntimeOrigin::signed(AccountId32::new([9u8; 32])),
+            vested.clone()
+        ));
+        assert_eq!(vesting_lock(vested), 70);
+        assert_eq!(pallet::TotalVestingLocked::<Test>::get(), 170);
+
+        System::set_block_number(11);
+        assert_ok!(ResolutionIssuance::claim_vested(
+            RuntimeOrigin::signed(vested.clone()),
+            vested.clone()
+        ));
+        assert_eq!(vesting_lock(vested), 0);
+        assert!(pallet::VestingSchedules::<Test>::get(vested).is_empty());
+        assert_eq!(pallet::TotalVestingLocked::<Test>::get(), 100);
+        assert_noop!(
+            ResolutionIssuance::claim_vested(RuntimeOrigin::signed(vested.clone()), vested.clone()),
+            pallet::Error::<Test>::NoVestingSchedule
+        );
+    });
+}
+
+#[test]
+fn calendar_month_vesting_follows_utc_calendar() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ResolutionIssuance::propose_vested_issuance(
+            RuntimeOrigin::signed(AccountId32::new([1u8; 32])),
+            actor_cid_number(),
+            committee_role_code(),
+            reason_ok(),
+            4300,
+            allocations_ok(4300),
+            vesting_for_first_two(block_terms(0, 10), month_terms(1, 4))
+        ));
+        insert_engine_proposal(100);
+        assert_ok!(call_joint_callback(100, true));
+        let monthly = reserve_council_accounts()[1].clone();
+        assert_eq!(vesting_lock(&monthly), 100);
+
+        // 起点 2026-07-01 00:00 UTC；差一秒不满一个月。
+        set_now_secs(GENESIS_NOW_SECS + 31 * 86_400 - 1);
+        assert_noop!(
+            ResolutionIssuance::claim_vested(
+                RuntimeOrigin::signed(monthly.clone()),
+                monthly.clone()
+            ),
+            pallet::Error::<Test>::NothingToClaim
+        );
+        set_now_secs(GENESIS_NOW_SECS + 31 * 86_400);
+        assert_ok!(ResolutionIssuance::claim_vested(
+            RuntimeOrigin::signed(monthly.clone()),
+            monthly.clone()
+        ));
+        assert_eq!(vesting_lock(&monthly), 75);
+
+        // 2026-11-01 满四个月，全部释放。
+        set_now_secs(GENESIS_NOW_SECS + (31 + 31 + 30 + 31) * 86_400);
+        assert_ok!(ResolutionIssuance::claim_vested(
+            RuntimeOrigin::signed(monthly.clone()),
+            monthly.clone()
+        ));
+        assert_eq!(vesting_lock(&monthly), 0);
+        assert!(frame_system::Pallet::<Test>::events().iter().any(|record| {
+            matches!(
+                &record.event,
+                RuntimeEvent::ResolutionIssuance(pallet::Event::<Test>::VestedClaimed {
+                    amount: 75,
+                    still_locked: 0,
+                    ..
+                })
+            )
+        }));
+    });
+}
+
+#[test]
+fn elapsed_calendar_months_handles_month_ends() {
+    // 2026-01-31 00:00 UTC 起算，2 月没有 31 日，到 3 月 31 日才满两个月。
+    let jan_31 = 1_769_817_600;
+    assert_eq!(
+        crate::vesting::elapsed_calendar_months(jan_31, jan_31 + 28 * 86_400),
+        0
+    );
+    assert_eq!(
+        crate::vesting::elapsed_calendar_months(jan_31, jan_31 + 59 * 86_400),
+        2
+    );
+    assert_eq!(
+        crate::vesting::elapsed_calendar_months(jan_31, jan_31 - 1),
+        0
+    );
+}
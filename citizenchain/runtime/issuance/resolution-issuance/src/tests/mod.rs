@@ -63,7 +63,7 @@ impl pallet_balances::Config for Test {
     type DustRemoval = ();
     type ExistentialDeposit = ConstU128<10>;
     type AccountStore = System;
-    type MaxLocks = ConstU32<0>;
+    type MaxLocks = ConstU32<50>;
     type MaxReserves = ();
     type ReserveIdentifier = [u8; 8];
     type FreezeIdentifier = RuntimeFreezeReason;
@@ -93,8 +93,15 @@ impl frame_support::traits::EnsureOrigin<RuntimeOrigin> for EnsureNrcAdminForTes
     }
 }
 
+const GENESIS_NOW_SECS: u64 = 1_782_864_000;
+
 thread_local! {
     static NEXT_JOINT_ID: RefCell<u64> = const { RefCell::new(100) };
+    static NOW_SECS: RefCell<u64> = const { RefCell::new(GENESIS_NOW_SECS) };
+}
+
+fn set_now_secs(secs: u64) {
+    NOW_SECS.with(|now| *now.borrow_mut() = secs);
 }
 
 pub struct TestJointVoteEngine;
@@ -290,7 +297,7 @@ fn test_citizen_subject(who: &AccountId32) -> votingengine::CitizenSubject<Accou
 pub struct TestTimeProvider;
 impl frame_support::traits::UnixTime for TestTimeProvider {
     fn now() -> core::time::Duration {
-        core::time::Duration::from_secs(1_782_864_000)
+        core::time::Duration::from_secs(NOW_SECS.with(|now| *now.borrow()))
     }
 }
 
@@ -343,6 +350,7 @@ impl pallet::Config for Test {
     type MaxAllocations = ConstU32<64>;
     type MaxTotalIssuance = ConstU128<14_434_973_780_000>;
     type MaxSingleIssuance = ConstU128<14_434_973_780_000>;
+    type MaxVestingSchedules = ConstU32<2>;
     type WeightInfo = ();
 }
 
@@ -354,6 +362,7 @@ fn new_test_ext() -> sp_io::TestExternalities {
     ext.execute_with(|| {
         System::set_block_number(1);
         NEXT_JOINT_ID.with(|id| *id.borrow_mut() = 100);
+        set_now_secs(GENESIS_NOW_SECS);
         let recipients = reserve_council_accounts();
         let bounded: BoundedVec<AccountId32, ConstU32<64>> =
             recipients.try_into().expect("recipients should fit");
@@ -0,0 +1,300 @@
+//! 决议发行分期释放(归属)逻辑。
+//!
+//! 分期分配在执行发行时仍整笔铸入收款账户，TotalIssued 口径不变；未归属部分用
+//! `VESTING_LOCK_ID` 余额锁锁住，`TotalVestingLocked` 记录“已发行但尚未释放”的总量。
+//! 归属按区块或自然月线性计算，到达 cliff 前为零；任何签名账户都可以替收款账户
+//! 触发 `claim_vested`，该操作只会缩小锁定额，不会移动资金。
+
+use crate::{
+    pallet::{
+        BalanceOf, Config, Error, Event, Pallet, TotalVestingLocked, VestingScheduleOf,
+        VestingSchedules,
+    },
+    proposal::RecipientAmount,
+};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{Get, LockIdentifier, LockableCurrency, UnixTime, WithdrawReasons},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{SaturatedConversion, Saturating, Zero},
+    RuntimeDebug,
+};
+use sp_std::collections::btree_map::BTreeMap;
+
+/// 决议发行归属锁标识。
+pub const VESTING_LOCK_ID: LockIdentifier = *b"resvest ";
+
+/// 自然月归属的最长期限(100 年)。
+pub const MAX_VESTING_MONTHS: u32 = 1_200;
+
+/// 归属计时单位。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    PartialEq,
+    Eq,
+)]
+pub enum VestingPeriod {
+    /// 按区块高度线性归属。
+    Blocks,
+    /// 按自然月(UTC 日历)逐月归属，起点为执行发行时的链上时间。
+    CalendarMonths,
+}
+
+/// 单条分配的归属条款：经过 `cliff` 个单位前不释放，`duration` 个单位后全部释放，
+/// 中间按已过单位数线性释放。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    PartialEq,
+    Eq,
+)]
+pub struct VestingTerms {
+    pub period: VestingPeriod,
+    pub cliff: u32,
+    pub duration: u32,
+}
+
+impl VestingTerms {
+    pub fn is_valid(&self) -> bool {
+        self.duration > 0
+            && self.cliff <= self.duration
+            && (self.period == VestingPeriod::Blocks || self.duration <= MAX_VESTING_MONTHS)
+    }
+}
+
+/// 链上保存的单条归属计划。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    PartialEq,
+    Eq,
+)]
+pub struct VestingSchedule<Balance, BlockNumber> {
+    /// 来源决议发行提案。
+    pub proposal_id: u64,
+    /// 本计划发行总额。
+    pub total: Balance,
+    /// 已释放(已解锁)金额。
+    pub claimed: Balance,
+    pub terms: VestingTerms,
+    /// 执行发行所在区块。
+    pub start_block: BlockNumber,
+    /// 执行发行时的链上 UNIX 秒。
+    pub start_secs: u64,
+}
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// UNIX 日序号转 UTC 公历 (年, 月, 日)。
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// 两个 UNIX 秒之间经过的完整自然月数：到达起点同日同时刻才算满一个月。
+pub(crate) fn elapsed_calendar_months(start_secs: u64, now_secs: u64) -> u32 {
+    if now_secs <= start_secs {
+        return 0;
+    }
+    let (y1, m1, d1) = civil_from_days((start_secs / SECS_PER_DAY) as i64);
+    let (y2, m2, d2) = civil_from_days((now_secs / SECS_PER_DAY) as i64);
+    let mut months = (y2 - y1) * 12 + i64::from(m2) - i64::from(m1);
+    if (d2, now_secs % SECS_PER_DAY) < (d1, start_secs % SECS_PER_DAY) {
+        months -= 1;
+    }
+    months.clamp(0, i64::from(u32::MAX)) as u32
+}
+
+/// 已过 `elapsed` 个单位时的累计可释放额。
+pub(crate) fn vested_amount(total: u128, terms: &VestingTerms, elapsed: u32) -> u128 {
+    if elapsed < terms.cliff {
+        return 0;
+    }
+    if elapsed >= terms.duration {
+        return total;
+    }
+    sp_runtime::helpers_128bit::multiply_by_rational_with_rounding(
+        total,
+        u128::from(elapsed),
+        u128::from(terms.duration),
+        sp_runtime::Rounding::Down,
+    )
+    .unwrap_or(0)
+}
+
+impl<T: Config> Pallet<T> {
+    pub(crate) fn validate_vesting_terms(
+        allocation_count: usize,
+        vesting: &[Option<VestingTerms>],
+    ) -> DispatchResult {
+        // 空表示整笔即时发行；非空时必须与分配逐项对齐。
+        ensure!(
+            vesting.is_empty() || vesting.len() == allocation_count,
+            Error::<T>::VestingLengthMismatch
+        );
+        ensure!(
+            vesting.iter().flatten().all(VestingTerms::is_valid),
+            Error::<T>::InvalidVestingTerms
+        );
+        Ok(())
+    }
+
+    /// 提案时确认每个分期收款账户仍有空余计划位，避免表决通过后才因满额整笔执行失败。
+    /// 同一提案内给同一账户的多条分期项按条数累计后再与上限比较。
+    pub(crate) fn ensure_vesting_capacity(
+        allocations: &[RecipientAmount<T::AccountId, BalanceOf<T>>],
+        vesting: &[Option<VestingTerms>],
+    ) -> DispatchResult {
+        let mut added: BTreeMap<&T::AccountId, u32> = BTreeMap::new();
+        for (item, _) in allocations
+            .iter()
+            .zip(vesting.iter())
+            .filter(|(_, terms)| terms.is_some())
+        {
+            let count = added.entry(&item.recipient_account_id).or_insert(0);
+            *count = count.saturating_add(1);
+        }
+        for (recipient, new) in added {
+            let held = VestingSchedules::<T>::decode_len(recipient).unwrap_or(0) as u32;
+            ensure!(
+                held.saturating_add(new) <= T::MaxVestingSchedules::get(),
+                Error::<T>::TooManyVestingSchedules
+            );
+        }
+        Ok(())
+    }
+
+    fn now_secs() -> u64 {
+        <T as votingengine::Config>::TimeProvider::now().as_secs()
+    }
+
+    fn schedule_vested(schedule: &VestingScheduleOf<T>) -> BalanceOf<T> {
+        let elapsed = match schedule.terms.period {
+            VestingPeriod::Blocks => frame_system::Pallet::<T>::block_number()
+                .saturating_sub(schedule.start_block)
+                .saturated_into::<u32>(),
+            VestingPeriod::CalendarMonths => {
+                elapsed_calendar_months(schedule.start_secs, Self::now_secs())
+            }
+        };
+        vested_amount(
+            schedule.total.saturated_into::<u128>(),
+            &schedule.terms,
+            elapsed,
+        )
+        .saturated_into()
+    }
+
+    fn apply_vesting_lock(who: &T::AccountId, locked: BalanceOf<T>) {
+        if locked.is_zero() {
+            T::Currency::remove_lock(VESTING_LOCK_ID, who);
+        } else {
+            T::Currency::set_lock(VESTING_LOCK_ID, who, locked, WithdrawReasons::all());
+        }
+    }
+
+    fn outstanding(schedules: &[VestingScheduleOf<T>]) -> BalanceOf<T> {
+        schedules.iter().fold(Zero::zero(), |sum: BalanceOf<T>, s| {
+            sum.saturating_add(s.total.saturating_sub(s.claimed))
+        })
+    }
+
+    /// 发行执行时登记一条归属计划并把整笔金额锁住。调用方负责外层存储事务。
+    pub(crate) fn add_vesting_schedule(
+        proposal_id: u64,
+        who: &T::AccountId,
+        amount: BalanceOf<T>,
+        terms: VestingTerms,
+    ) -> DispatchResult {
+        let schedule = VestingScheduleOf::<T> {
+            proposal_id,
+            total: amount,
+            claimed: Zero::zero(),
+            terms,
+            start_block: frame_system::Pallet::<T>::block_number(),
+            start_secs: Self::now_secs(),
+        };
+        let locked = VestingSchedules::<T>::try_mutate(who, |schedules| {
+            schedules
+                .try_push(schedule)
+                .map_err(|_| Error::<T>::TooManyVestingSchedules)?;
+            Ok::<_, Error<T>>(Self::outstanding(schedules))
+        })?;
+        Self::apply_vesting_lock(who, locked);
+        TotalVestingLocked::<T>::mutate(|total| *total = total.saturating_add(amount));
+        Self::deposit_event(Event::<T>::VestingScheduled {
+            proposal_id,
+            recipient_account_id: who.clone(),
+            amount,
+            terms,
+        });
+        Ok(())
+    }
+
+    /// 按当前区块和链上时间释放 `who` 全部已归属金额。
+    pub(crate) fn do_claim_vested(who: &T::AccountId) -> DispatchResult {
+        let mut schedules = VestingSchedules::<T>::get(who);
+        ensure!(!schedules.is_empty(), Error::<T>::NoVestingSchedule);
+
+        let mut released = BalanceOf::<T>::zero();
+        for schedule in schedules.iter_mut() {
+            let vested = Self::schedule_vested(schedule);
+            if vested > schedule.claimed {
+                released = released.saturating_add(vested.saturating_sub(schedule.claimed));
+                schedule.claimed = vested;
+            }
+        }
+        ensure!(!released.is_zero(), Error::<T>::NothingToClaim);
+
+        schedules.retain(|s| s.claimed < s.total);
+        let locked = Self::outstanding(&schedules);
+        if schedules.is_empty() {
+            VestingSchedules::<T>::remove(who);
+        } else {
+            VestingSchedules::<T>::insert(who, schedules);
+        }
+        Self::apply_vesting_lock(who, locked);
+        TotalVestingLocked::<T>::mutate(|total| *total = total.saturating_sub(released));
+        Self::deposit_event(Event::<T>::VestedClaimed {
+            recipient_account_id: who.clone(),
+            amount: released,
+            still_locked: locked,
+        });
+        Ok(())
+    }
+
+    /// 账户当前仍被归属锁定的金额。
+    pub fn vesting_locked(who: &T::AccountId) -> BalanceOf<T> {
+        Self::outstanding(&VestingSchedules::<T>::get(who))
+    }
+}
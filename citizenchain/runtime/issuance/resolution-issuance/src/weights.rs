@@ -99,12 +99,86 @@ pub trait WeightInfo {
 	/// Storage: `VotingEngine::YearProposalCounter` (r:0 w:1)
 	/// Proof: `VotingEngine::YearProposalCounter` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
 	fn propose_issuance() -> Weight;
+	/// Storage: `PublicManage::Institutions` (r:87 w:0)
+	/// Proof: `PublicManage::Institutions` (`max_values`: None, `max_size`: Some(645), added: 3120, mode: `MaxEncodedLen`)
+	/// Storage: `PublicAdmins::AdminAccounts` (r:87 w:0)
+	/// Proof: `PublicAdmins::AdminAccounts` (`max_values`: None, `max_size`: Some(646480), added: 648955, mode: `MaxEncodedLen`)
+	/// Storage: `PublicManage::InstitutionRoles` (r:87 w:0)
+	/// Proof: `PublicManage::InstitutionRoles` (`max_values`: None, `max_size`: Some(362), added: 2837, mode: `MaxEncodedLen`)
+	/// Storage: `PublicManage::InstitutionRoleAssignments` (r:87 w:0)
+	/// Proof: `PublicManage::InstitutionRoleAssignments` (`max_values`: None, `max_size`: Some(539152), added: 541627, mode: `MaxEncodedLen`)
+	/// Storage: `PrivateManage::Institutions` (r:87 w:0)
+	/// Proof: `PrivateManage::Institutions` (`max_values`: None, `max_size`: Some(645), added: 3120, mode: `MaxEncodedLen`)
+	/// Storage: `PublicManage::InstitutionRolePermissions` (r:1 w:0)
+	/// Proof: `PublicManage::InstitutionRolePermissions` (`max_values`: None, `max_size`: Some(35205), added: 37680, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::AllowedRecipients` (r:1 w:0)
+	/// Proof: `ResolutionIssuance::AllowedRecipients` (`max_values`: Some(1), `max_size`: Some(1377), added: 1872, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::VestingSchedules` (r:43 w:0)
+	/// Proof: `ResolutionIssuance::VestingSchedules` (`max_values`: None, `max_size`: Some(1025), added: 3500, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::NextProposalId` (r:1 w:1)
+	/// Proof: `VotingEngine::NextProposalId` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::CurrentProposalYear` (r:1 w:1)
+	/// Proof: `VotingEngine::CurrentProposalYear` (`max_values`: Some(1), `max_size`: Some(2), added: 497, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ActiveProposalsBySubject` (r:1 w:1)
+	/// Proof: `VotingEngine::ActiveProposalsBySubject` (`max_values`: None, `max_size`: Some(131), added: 2606, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::InternalProposalMutexes` (r:1 w:1)
+	/// Proof: `VotingEngine::InternalProposalMutexes` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalMutexBindings` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalMutexBindings` (`max_values`: None, `max_size`: Some(8986), added: 11461, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::VoterSnapshot` (r:87 w:87)
+	/// Proof: `VotingEngine::VoterSnapshot` (`max_values`: None, `max_size`: Some(63790), added: 66265, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::InstitutionTicketCountSnapshot` (r:87 w:87)
+	/// Proof: `VotingEngine::InstitutionTicketCountSnapshot` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalVotePlans` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalVotePlans` (`max_values`: None, `max_size`: Some(25829), added: 28304, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalOwner` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalOwner` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalPopulationSnapshots` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalPopulationSnapshots` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `CitizenIdentity::CountryVotingCount` (r:1 w:0)
+	/// Proof: `CitizenIdentity::CountryVotingCount` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `CitizenIdentity::NextEligibilityRevision` (r:1 w:0)
+	/// Proof: `CitizenIdentity::NextEligibilityRevision` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByExpiry` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalsByExpiry` (`max_values`: None, `max_size`: Some(16406), added: 18881, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalData` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalData` (`max_values`: None, `max_size`: Some(102428), added: 104903, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::VotingProposalCount` (r:1 w:1)
+	/// Proof: `ResolutionIssuance::VotingProposalCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByOwner` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalsByOwner` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalMeta` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalMeta` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByYear` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalsByYear` (`max_values`: None, `max_size`: Some(26), added: 2501, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::Proposals` (r:0 w:1)
+	/// Proof: `VotingEngine::Proposals` (`max_values`: None, `max_size`: Some(8557), added: 11032, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByCid` (r:0 w:87)
+	/// Proof: `VotingEngine::ProposalsByCid` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalDisplayId` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalDisplayId` (`max_values`: None, `max_size`: Some(30), added: 2505, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::YearProposalCounter` (r:0 w:1)
+	/// Proof: `VotingEngine::YearProposalCounter` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn propose_vested_issuance() -> Weight;
 	/// Storage: `ResolutionIssuance::Executed` (r:1 w:1)
 	/// Proof: `ResolutionIssuance::Executed` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
 	fn clear_executed() -> Weight;
 	/// Storage: `ResolutionIssuance::Paused` (r:1 w:1)
 	/// Proof: `ResolutionIssuance::Paused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
 	fn set_paused() -> Weight;
+	/// Storage: `ResolutionIssuance::VestingSchedules` (r:1 w:1)
+	/// Proof: `ResolutionIssuance::VestingSchedules` (`max_values`: None, `max_size`: Some(1025), added: 3500, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::TotalVestingLocked` (r:1 w:1)
+	/// Proof: `ResolutionIssuance::TotalVestingLocked` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn claim_vested() -> Weight;
 }
 
 pub struct SubstrateWeight<T>(PhantomData<T>);
@@ -193,6 +267,78 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(625))
 			.saturating_add(T::DbWeight::get().writes(278))
 	}
+	/// Storage: `PublicManage::Institutions` (r:87 w:0)
+	/// Proof: `PublicManage::Institutions` (`max_values`: None, `max_size`: Some(645), added: 3120, mode: `MaxEncodedLen`)
+	/// Storage: `PublicAdmins::AdminAccounts` (r:87 w:0)
+	/// Proof: `PublicAdmins::AdminAccounts` (`max_values`: None, `max_size`: Some(646480), added: 648955, mode: `MaxEncodedLen`)
+	/// Storage: `PublicManage::InstitutionRoles` (r:87 w:0)
+	/// Proof: `PublicManage::InstitutionRoles` (`max_values`: None, `max_size`: Some(362), added: 2837, mode: `MaxEncodedLen`)
+	/// Storage: `PublicManage::InstitutionRoleAssignments` (r:87 w:0)
+	/// Proof: `PublicManage::InstitutionRoleAssignments` (`max_values`: None, `max_size`: Some(539152), added: 541627, mode: `MaxEncodedLen`)
+	/// Storage: `PrivateManage::Institutions` (r:87 w:0)
+	/// Proof: `PrivateManage::Institutions` (`max_values`: None, `max_size`: Some(645), added: 3120, mode: `MaxEncodedLen`)
+	/// Storage: `PublicManage::InstitutionRolePermissions` (r:1 w:0)
+	/// Proof: `PublicManage::InstitutionRolePermissions` (`max_values`: None, `max_size`: Some(35205), added: 37680, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::AllowedRecipients` (r:1 w:0)
+	/// Proof: `ResolutionIssuance::AllowedRecipients` (`max_values`: Some(1), `max_size`: Some(1377), added: 1872, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::VestingSchedules` (r:43 w:0)
+	/// Proof: `ResolutionIssuance::VestingSchedules` (`max_values`: None, `max_size`: Some(1025), added: 3500, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::NextProposalId` (r:1 w:1)
+	/// Proof: `VotingEngine::NextProposalId` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::CurrentProposalYear` (r:1 w:1)
+	/// Proof: `VotingEngine::CurrentProposalYear` (`max_values`: Some(1), `max_size`: Some(2), added: 497, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ActiveProposalsBySubject` (r:1 w:1)
+	/// Proof: `VotingEngine::ActiveProposalsBySubject` (`max_values`: None, `max_size`: Some(131), added: 2606, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::InternalProposalMutexes` (r:1 w:1)
+	/// Proof: `VotingEngine::InternalProposalMutexes` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalMutexBindings` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalMutexBindings` (`max_values`: None, `max_size`: Some(8986), added: 11461, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::VoterSnapshot` (r:87 w:87)
+	/// Proof: `VotingEngine::VoterSnapshot` (`max_values`: None, `max_size`: Some(63790), added: 66265, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::InstitutionTicketCountSnapshot` (r:87 w:87)
+	/// Proof: `VotingEngine::InstitutionTicketCountSnapshot` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalVotePlans` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalVotePlans` (`max_values`: None, `max_size`: Some(25829), added: 28304, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalOwner` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalOwner` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalPopulationSnapshots` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalPopulationSnapshots` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `CitizenIdentity::CountryVotingCount` (r:1 w:0)
+	/// Proof: `CitizenIdentity::CountryVotingCount` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `CitizenIdentity::NextEligibilityRevision` (r:1 w:0)
+	/// Proof: `CitizenIdentity::NextEligibilityRevision` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByExpiry` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalsByExpiry` (`max_values`: None, `max_size`: Some(16406), added: 18881, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalData` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalData` (`max_values`: None, `max_size`: Some(102428), added: 104903, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::VotingProposalCount` (r:1 w:1)
+	/// Proof: `ResolutionIssuance::VotingProposalCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByOwner` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalsByOwner` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalMeta` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalMeta` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByYear` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalsByYear` (`max_values`: None, `max_size`: Some(26), added: 2501, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::Proposals` (r:0 w:1)
+	/// Proof: `VotingEngine::Proposals` (`max_values`: None, `max_size`: Some(8557), added: 11032, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByCid` (r:0 w:87)
+	/// Proof: `VotingEngine::ProposalsByCid` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalDisplayId` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalDisplayId` (`max_values`: None, `max_size`: Some(30), added: 2505, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::YearProposalCounter` (r:0 w:1)
+	/// Proof: `VotingEngine::YearProposalCounter` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn propose_vested_issuance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `336267`
+		//  Estimated: `56610575`
+		// Minimum execution time: 4_812_000_000 picoseconds.
+		Weight::from_parts(4_943_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 56610575))
+			.saturating_add(T::DbWeight::get().reads(668))
+			.saturating_add(T::DbWeight::get().writes(278))
+	}
 	/// Storage: `ResolutionIssuance::Executed` (r:1 w:1)
 	/// Proof: `ResolutionIssuance::Executed` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
 	fn clear_executed() -> Weight {
@@ -217,6 +363,26 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `ResolutionIssuance::VestingSchedules` (r:1 w:1)
+	/// Proof: `ResolutionIssuance::VestingSchedules` (`max_values`: None, `max_size`: Some(1025), added: 3500, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::TotalVestingLocked` (r:1 w:1)
+	/// Proof: `ResolutionIssuance::TotalVestingLocked` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn claim_vested() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1180`
+		//  Estimated: `4764`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(33_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4764))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
 }
 
 impl WeightInfo for () {
@@ -304,6 +470,78 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(625))
 			.saturating_add(RocksDbWeight::get().writes(278))
 	}
+	/// Storage: `PublicManage::Institutions` (r:87 w:0)
+	/// Proof: `PublicManage::Institutions` (`max_values`: None, `max_size`: Some(645), added: 3120, mode: `MaxEncodedLen`)
+	/// Storage: `PublicAdmins::AdminAccounts` (r:87 w:0)
+	/// Proof: `PublicAdmins::AdminAccounts` (`max_values`: None, `max_size`: Some(646480), added: 648955, mode: `MaxEncodedLen`)
+	/// Storage: `PublicManage::InstitutionRoles` (r:87 w:0)
+	/// Proof: `PublicManage::InstitutionRoles` (`max_values`: None, `max_size`: Some(362), added: 2837, mode: `MaxEncodedLen`)
+	/// Storage: `PublicManage::InstitutionRoleAssignments` (r:87 w:0)
+	/// Proof: `PublicManage::InstitutionRoleAssignments` (`max_values`: None, `max_size`: Some(539152), added: 541627, mode: `MaxEncodedLen`)
+	/// Storage: `PrivateManage::Institutions` (r:87 w:0)
+	/// Proof: `PrivateManage::Institutions` (`max_values`: None, `max_size`: Some(645), added: 3120, mode: `MaxEncodedLen`)
+	/// Storage: `PublicManage::InstitutionRolePermissions` (r:1 w:0)
+	/// Proof: `PublicManage::InstitutionRolePermissions` (`max_values`: None, `max_size`: Some(35205), added: 37680, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::AllowedRecipients` (r:1 w:0)
+	/// Proof: `ResolutionIssuance::AllowedRecipients` (`max_values`: Some(1), `max_size`: Some(1377), added: 1872, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::VestingSchedules` (r:43 w:0)
+	/// Proof: `ResolutionIssuance::VestingSchedules` (`max_values`: None, `max_size`: Some(1025), added: 3500, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::NextProposalId` (r:1 w:1)
+	/// Proof: `VotingEngine::NextProposalId` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::CurrentProposalYear` (r:1 w:1)
+	/// Proof: `VotingEngine::CurrentProposalYear` (`max_values`: Some(1), `max_size`: Some(2), added: 497, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ActiveProposalsBySubject` (r:1 w:1)
+	/// Proof: `VotingEngine::ActiveProposalsBySubject` (`max_values`: None, `max_size`: Some(131), added: 2606, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::InternalProposalMutexes` (r:1 w:1)
+	/// Proof: `VotingEngine::InternalProposalMutexes` (`max_values`: None, `max_size`: Some(63), added: 2538, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalMutexBindings` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalMutexBindings` (`max_values`: None, `max_size`: Some(8986), added: 11461, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::VoterSnapshot` (r:87 w:87)
+	/// Proof: `VotingEngine::VoterSnapshot` (`max_values`: None, `max_size`: Some(63790), added: 66265, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::InstitutionTicketCountSnapshot` (r:87 w:87)
+	/// Proof: `VotingEngine::InstitutionTicketCountSnapshot` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalVotePlans` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalVotePlans` (`max_values`: None, `max_size`: Some(25829), added: 28304, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalOwner` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalOwner` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalPopulationSnapshots` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalPopulationSnapshots` (`max_values`: None, `max_size`: Some(100), added: 2575, mode: `MaxEncodedLen`)
+	/// Storage: `CitizenIdentity::CountryVotingCount` (r:1 w:0)
+	/// Proof: `CitizenIdentity::CountryVotingCount` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `CitizenIdentity::NextEligibilityRevision` (r:1 w:0)
+	/// Proof: `CitizenIdentity::NextEligibilityRevision` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByExpiry` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalsByExpiry` (`max_values`: None, `max_size`: Some(16406), added: 18881, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalData` (r:1 w:1)
+	/// Proof: `VotingEngine::ProposalData` (`max_values`: None, `max_size`: Some(102428), added: 104903, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::VotingProposalCount` (r:1 w:1)
+	/// Proof: `ResolutionIssuance::VotingProposalCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByOwner` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalsByOwner` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalMeta` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalMeta` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByYear` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalsByYear` (`max_values`: None, `max_size`: Some(26), added: 2501, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::Proposals` (r:0 w:1)
+	/// Proof: `VotingEngine::Proposals` (`max_values`: None, `max_size`: Some(8557), added: 11032, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalsByCid` (r:0 w:87)
+	/// Proof: `VotingEngine::ProposalsByCid` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::ProposalDisplayId` (r:0 w:1)
+	/// Proof: `VotingEngine::ProposalDisplayId` (`max_values`: None, `max_size`: Some(30), added: 2505, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::YearProposalCounter` (r:0 w:1)
+	/// Proof: `VotingEngine::YearProposalCounter` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn propose_vested_issuance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `336267`
+		//  Estimated: `56610575`
+		// Minimum execution time: 4_812_000_000 picoseconds.
+		Weight::from_parts(4_943_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 56610575))
+			.saturating_add(RocksDbWeight::get().reads(668))
+			.saturating_add(RocksDbWeight::get().writes(278))
+	}
 	/// Storage: `ResolutionIssuance::Executed` (r:1 w:1)
 	/// Proof: `ResolutionIssuance::Executed` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
 	fn clear_executed() -> Weight {
@@ -328,4 +566,24 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1))
 			.saturating_add(RocksDbWeight::get().writes(1))
 	}
+	/// Storage: `ResolutionIssuance::VestingSchedules` (r:1 w:1)
+	/// Proof: `ResolutionIssuance::VestingSchedules` (`max_values`: None, `max_size`: Some(1025), added: 3500, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ResolutionIssuance::TotalVestingLocked` (r:1 w:1)
+	/// Proof: `ResolutionIssuance::TotalVestingLocked` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn claim_vested() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1180`
+		//  Estimated: `4764`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(33_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4764))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
 }
@@ -2,27 +2,27 @@
 //!
 //! 用户签名订阅后，链上订阅状态就是持续扣款授权；只有签名取消才撤销。每个区块结束时
 //! 使用已经写入的共识时间戳处理到期调度，不需要 CitizenApp、Cloudflare 或外部交易。
+//!
+//! 到期扣款余额不足时先进入宽限期（`PastDue`），按 `MaxRenewalRetries` 次重试均匀分布在
+//! `RenewalGracePeriodMs` 内；重试成功从原到期时间续接公历周期，重试用尽才挂起。
 
 use crate::{
     pallet::{
-        BalanceOf, CidNumberOf, Config, CreatorPlans, Error, Event, IssuerKeyOf, Pallet,
-        RenewalIndex, RenewalSchedule, SubKeyOf, Subscriptions,
+        CidNumberOf, Config, CreatorPlans, CreatorSubscribers, Error, Event, IssuerKeyOf, Pallet,
+        RenewalIndex, RenewalSchedule, SubKeyOf, SubscriptionDunning, Subscriptions,
     },
     subscription::{
-        add_calendar_period, CreatorTier, CreatorTiers, IssuerKey, SubscriptionPlan,
+        add_calendar_period, CreatorTier, CreatorTiers, DunningRecord, IssuerKey, SubscriptionPlan,
         SubscriptionState, SubscriptionStatus, SuspendReason,
     },
 };
-use frame_support::{
-    ensure,
-    storage::with_storage_layer,
-    traits::{Currency, ExistenceRequirement},
-};
+use frame_support::{ensure, storage::with_storage_layer, traits::Get};
 use sp_runtime::{traits::SaturatedConversion, DispatchResult};
 use sp_std::vec::Vec;
 
 impl<T: Config> Pallet<T> {
-    /// 订阅并立即完成首次扣款。已有 Active 同计划幂等；已取消但尚未到期时恢复调度且不重扣。
+    /// 订阅并立即完成首次扣款。已有 Active 同计划幂等；已取消但尚未到期时恢复调度且不重扣；
+    /// 宽限期内（`PastDue`）再签名视为立即补缴，新周期从现在起算。
     pub(crate) fn do_subscribe(
         subscriber_account_id: T::AccountId,
         issuer: IssuerKeyOf<T>,
@@ -101,16 +101,14 @@ impl<T: Config> Pallet<T> {
             }
             let paid_until = add_calendar_period(now, plan.billing_period())
                 .ok_or(Error::<T>::CalendarOverflow)?;
-            let amount: BalanceOf<T> = price_fen.saturated_into();
-            T::Currency::transfer(
-                &subscriber_account_id,
-                &payee,
-                amount,
-                ExistenceRequirement::KeepAlive,
-            )?;
+            Self::collect_payment(&subscriber_account_id, &issuer, &payee, price_fen, now)?;
 
             let key = (subscriber_cid_number.clone(), issuer.clone());
             Self::unschedule_renewal(&key);
+            SubscriptionDunning::<T>::remove(&key);
+            if let IssuerKey::Creator(creator_cid_number) = &issuer {
+                CreatorSubscribers::<T>::insert(creator_cid_number, &subscriber_cid_number, ());
+            }
             let started_at = now;
             Subscriptions::<T>::insert(
                 &key,
@@ -162,13 +160,19 @@ impl<T: Config> Pallet<T> {
         let Some(mut state) = Subscriptions::<T>::get(&key) else {
             return;
         };
-        // 只有留在调度里的 Active / IssuerPaused 才处理；双向一致性由 try_state 守护。
+        // 催缴记录只在继续欠费时重新写回；任何其他结局都离开 PastDue。
+        let dunning = SubscriptionDunning::<T>::take(&key);
+        // 只有留在调度里的 Active / IssuerPaused / PastDue 才处理；双向一致性由 try_state 守护。
         if !matches!(
             state.subscription_status,
-            SubscriptionStatus::Active | SubscriptionStatus::IssuerPaused
+            SubscriptionStatus::Active
+                | SubscriptionStatus::IssuerPaused
+                | SubscriptionStatus::PastDue
         ) {
             return;
         }
+        // 宽限重试仍以首次失败的计划扣款时间为周期起点，保持公历边界不漂移。
+        let due_at = dunning.map_or(due_at, |record| record.due_at);
         let (subscriber_cid_number, issuer) = key.clone();
         let plan = state.plan.clone();
         let subscriber_account_id = match Self::current_account_id_for_cid(&subscriber_cid_number) {
@@ -284,22 +288,15 @@ impl<T: Config> Pallet<T> {
             });
             return;
         };
-        let amount: BalanceOf<T> = price_fen.saturated_into();
-        if T::Currency::transfer(
-            &subscriber_account_id,
-            &payee,
-            amount,
-            ExistenceRequirement::KeepAlive,
-        )
-        .is_err()
-        {
-            // 余额不足 → 挂起待充值再签名，不终止、不重排调度。
-            Self::suspend_subscription(
+        if Self::collect_payment(&subscriber_account_id, &issuer, &payee, price_fen, now).is_err() {
+            // 余额不足 → 进入宽限重试；重试用尽才挂起待充值再签名，不终止。
+            Self::enter_dunning(
                 &key,
                 state,
                 subscriber_cid_number,
                 issuer,
-                SuspendReason::InsufficientBalance,
+                due_at,
+                dunning,
                 now,
             );
             return;
@@ -337,6 +334,60 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// 扣款失败后记一次失败并排下一次重试；未配置宽限或重试已用尽时挂起为余额不足。
+    ///
+    /// 第 k 次失败后的重试落在 `due_at + 宽限 × k ÷ 重试次数`，最后一次恰在宽限截止；
+    /// 链停后恢复时重试时间至少推迟到下一毫秒，避免同一区块内连续耗尽重试。
+    fn enter_dunning(
+        key: &SubKeyOf<T>,
+        mut state: SubscriptionState,
+        subscriber_cid_number: CidNumberOf<T>,
+        issuer: IssuerKeyOf<T>,
+        due_at: u64,
+        previous: Option<DunningRecord>,
+        now: u64,
+    ) {
+        let failed_attempts = previous.map_or(1, |record| record.failed_attempts.saturating_add(1));
+        let grace = T::RenewalGracePeriodMs::get();
+        let max_retries = T::MaxRenewalRetries::get();
+        if grace == 0 || failed_attempts > max_retries {
+            Self::suspend_subscription(
+                key,
+                state,
+                subscriber_cid_number,
+                issuer,
+                SuspendReason::InsufficientBalance,
+                now,
+            );
+            return;
+        }
+        let grace_until = due_at.saturating_add(grace);
+        let offset =
+            u128::from(grace).saturating_mul(u128::from(failed_attempts)) / u128::from(max_retries);
+        let next_retry_at = due_at
+            .saturating_add(offset.saturated_into::<u64>())
+            .max(now.saturating_add(1));
+        state.subscription_status = SubscriptionStatus::PastDue;
+        state.suspend_reason = None;
+        Subscriptions::<T>::insert(key, state);
+        SubscriptionDunning::<T>::insert(
+            key,
+            DunningRecord {
+                due_at,
+                grace_until,
+                failed_attempts,
+            },
+        );
+        Self::schedule_renewal(key, next_retry_at);
+        Self::deposit_event(Event::SubscriptionPastDue {
+            subscriber_cid_number,
+            issuer,
+            failed_attempts,
+            next_retry_at,
+            grace_until,
+        });
+    }
+
     /// 挂起订阅：保留粉丝关系、写挂起原因、退出续费调度（调用方保证已离调度），等用户再签名/充值恢复。
     fn suspend_subscription(
         key: &SubKeyOf<T>,
@@ -371,6 +422,7 @@ impl<T: Config> Pallet<T> {
             Ok::<_, Error<T>>(state.paid_until)
         })?;
         Self::unschedule_renewal(&key);
+        SubscriptionDunning::<T>::remove(&key);
         Self::deposit_event(Event::SubscriptionCancelled {
             subscriber_cid_number,
             issuer,
@@ -421,13 +473,7 @@ impl<T: Config> Pallet<T> {
             let (charged_now, paid_until) = if new_price > credit {
                 // 升档：立即补扣差额，新周期从现在起算。
                 let charge = new_price.saturating_sub(credit);
-                let amount: BalanceOf<T> = charge.saturated_into();
-                T::Currency::transfer(
-                    &subscriber_account_id,
-                    &payee,
-                    amount,
-                    ExistenceRequirement::KeepAlive,
-                )?;
+                Self::collect_payment(&subscriber_account_id, &issuer, &payee, charge, now)?;
                 (charge, base_end)
             } else {
                 // 降档：不扣款，剩余信用按新档单价折算成额外时长叠加（new_price > 0 已由定价保证）。
@@ -448,6 +494,7 @@ impl<T: Config> Pallet<T> {
             state.subscription_status = SubscriptionStatus::Active;
             state.suspend_reason = None;
             Subscriptions::<T>::insert(&key, state);
+            SubscriptionDunning::<T>::remove(&key);
             Self::schedule_renewal(&key, paid_until);
             Self::deposit_event(Event::SubscriptionPlanChanged {
                 subscriber_cid_number,
@@ -465,7 +512,7 @@ impl<T: Config> Pallet<T> {
     ///
     /// 基准价只接受 `last_charged_price_fen`；`authorized_price_fen` 是已同意的下期价，
     /// 只用于判断是否需要再签名，不参与金额计算。
-    fn remaining_credit(
+    pub(crate) fn remaining_credit(
         last_charged_price_fen: u128,
         last_charged_at: u64,
         paid_until: u64,
@@ -0,0 +1,465 @@
+//! 创作者收益台账、周期结算与吊销退款。
+//!
+//! 创作者订阅款不再直接转给创作者，而是先进入托管账户并记入 [`CreatorEarnings`]。
+//! runtime 按真实公历结算周期把待结算收益扣除平台抽成后付给创作者当前绑定账户，
+//! 抽成交 [`Config::PlatformFeeRouter`]（runtime 接链上交易费同一分账器）。创作者 CID
+//! 被吊销后，托管中的待结算收益按剩余权益比例退还订阅者，余款同样交费用分账器。
+//!
+//! 托管账户在创世与 v1 迁移时补足一笔 ED 保活底仓，底仓不计入任何台账 `pending_fen`；
+//! 此后转入不受 ED 限制，转出一律保活，托管账户永不被回收，台账始终有足额覆盖。
+
+use crate::{
+    pallet::{
+        BalanceOf, CidNumberOf, Config, CreatorEarnings, CreatorPlans, CreatorSubscribers, Error,
+        Event, IssuerKeyOf, Pallet, PayoutSchedule, RevokedRefundCredits, RevokedSettlementOf,
+        RevokedSettlements, SubscriptionDunning, Subscriptions, MAX_REVOKED_REFUNDS_PER_CALL,
+    },
+    subscription::{add_calendar_period, IssuerKey, SubscriptionStatus},
+    SquarePostCitizenIdentityProvider,
+};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{
+    ensure,
+    storage::with_storage_layer,
+    traits::{Currency, ExistenceRequirement, Get, OnUnbalanced, WithdrawReasons},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational_with_rounding, traits::SaturatedConversion,
+    DispatchResult, Rounding, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+/// 单个创作者的收益台账。所有金额单位为分。
+#[derive(
+    Clone,
+    Default,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct CreatorEarningsLedger {
+    /// 托管中尚未结算的收益。
+    pub pending_fen: u128,
+    pub total_earned_fen: u128,
+    /// 累计已付给创作者的净额。
+    pub total_paid_fen: u128,
+    /// 累计交费用分账器的金额（结算抽成与吊销后无人可领的余款）。
+    pub total_platform_fee_fen: u128,
+    /// 累计因创作者吊销退还订阅者的金额。
+    pub total_refunded_fen: u128,
+    /// 下一次结算时间；`None` 表示当前不在结算调度中。
+    pub next_payout_at: Option<u64>,
+}
+
+/// 吊销创作者的分批结算进度；首次结算调用时写入，待退款集合清空后删除。
+#[derive(
+    Clone,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct RevokedSettlement<CidNumber> {
+    /// 剩余权益统一按此时间点计算（首次结算调用的共识时间）。
+    pub settle_at: u64,
+    /// 已登记的剩余权益合计。
+    pub total_credit_fen: u128,
+    /// 全部订阅登记完成时托管中的待结算收益；`None` 表示仍在登记阶段。
+    pub refund_pool_fen: Option<u128>,
+    /// 退款阶段上次处理到的订阅者；`None` 表示从表头开始。
+    pub cursor: Option<CidNumber>,
+}
+
+impl<T: Config> Pallet<T> {
+    /// 收取一笔订阅款：平台款直达费用账户，创作者款进入托管并记入收益台账。
+    ///
+    /// 创作者分支仍要求调用方已经解析出创作者当前收款账户，保持「无完整绑定不收款」的既有约束。
+    pub(crate) fn collect_payment(
+        payer_account_id: &T::AccountId,
+        issuer: &IssuerKeyOf<T>,
+        payee: &T::AccountId,
+        amount_fen: u128,
+        now: u64,
+    ) -> DispatchResult {
+        let amount: BalanceOf<T> = amount_fen.saturated_into();
+        match issuer {
+            IssuerKey::Platform => T::Currency::transfer(
+                payer_account_id,
+                payee,
+                amount,
+                ExistenceRequirement::KeepAlive,
+            ),
+            IssuerKey::Creator(creator_cid_number) => {
+                T::Currency::transfer(
+                    payer_account_id,
+                    &T::CreatorEarningsEscrow::get(),
+                    amount,
+                    ExistenceRequirement::KeepAlive,
+                )?;
+                Self::accrue_creator_earnings(creator_cid_number, amount_fen, now);
+                Ok(())
+            }
+        }
+    }
+
+    fn accrue_creator_earnings(creator_cid_number: &CidNumberOf<T>, amount_fen: u128, now: u64) {
+        let mut ledger = CreatorEarnings::<T>::get(creator_cid_number).unwrap_or_default();
+        ledger.pending_fen = ledger.pending_fen.saturating_add(amount_fen);
+        ledger.total_earned_fen = ledger.total_earned_fen.saturating_add(amount_fen);
+        if ledger.next_payout_at.is_none() {
+            if let Some(payout_at) = add_calendar_period(now, T::CreatorPayoutPeriod::get()) {
+                Self::schedule_payout(creator_cid_number, &mut ledger, payout_at);
+            }
+        }
+        let pending_fen = ledger.pending_fen;
+        CreatorEarnings::<T>::insert(creator_cid_number, ledger);
+        Self::deposit_event(Event::CreatorEarningsAccrued {
+            creator_cid_number: creator_cid_number.clone(),
+            amount_fen,
+            pending_fen,
+        });
+    }
+
+    fn schedule_payout(
+        creator_cid_number: &CidNumberOf<T>,
+        ledger: &mut CreatorEarningsLedger,
+        payout_at: u64,
+    ) {
+        Self::unschedule_payout(creator_cid_number, ledger);
+        PayoutSchedule::<T>::insert(payout_at.to_be_bytes(), creator_cid_number, ());
+        ledger.next_payout_at = Some(payout_at);
+    }
+
+    fn unschedule_payout(creator_cid_number: &CidNumberOf<T>, ledger: &mut CreatorEarningsLedger) {
+        if let Some(previous) = ledger.next_payout_at.take() {
+            PayoutSchedule::<T>::remove(previous.to_be_bytes(), creator_cid_number);
+        }
+    }
+
+    /// 按时间戳有序处理到期结算，与续费共用每块处理上限。
+    pub(crate) fn process_due_payouts(now: u64, limit: u32) -> u32 {
+        let mut processed = 0u32;
+        while processed < limit {
+            let Some((due_key, creator_cid_number)) = PayoutSchedule::<T>::iter_keys().next()
+            else {
+                break;
+            };
+            if u64::from_be_bytes(due_key) > now {
+                break;
+            }
+            PayoutSchedule::<T>::remove(due_key, &creator_cid_number);
+            Self::process_one_payout(creator_cid_number, now);
+            processed = processed.saturating_add(1);
+        }
+        processed
+    }
+
+    fn process_one_payout(creator_cid_number: CidNumberOf<T>, now: u64) {
+        let Some(mut ledger) = CreatorEarnings::<T>::get(&creator_cid_number) else {
+            return;
+        };
+        // 调度项已由调用方移除，这里只清台账上的反向记录。
+        ledger.next_payout_at = None;
+        if ledger.pending_fen == 0 {
+            CreatorEarnings::<T>::insert(&creator_cid_number, ledger);
+            return;
+        }
+        // 已吊销的创作者不再结算，托管款留给吊销退款。
+        if T::CitizenIdentity::is_cid_revoked(creator_cid_number.as_slice()) {
+            let pending_fen = ledger.pending_fen;
+            CreatorEarnings::<T>::insert(&creator_cid_number, ledger);
+            Self::deposit_event(Event::CreatorPayoutDeferred {
+                creator_cid_number,
+                pending_fen,
+                next_payout_at: None,
+            });
+            return;
+        }
+        let gross_fen = ledger.pending_fen;
+        let platform_fee_fen = T::CreatorPlatformFee::get().mul_floor(gross_fen);
+        let net_fen = gross_fen.saturating_sub(platform_fee_fen);
+        let settled = Self::current_account_id_for_cid(&creator_cid_number)
+            .map_err(Into::into)
+            .and_then(|payee| {
+                Self::pay_out(&payee, net_fen, platform_fee_fen)?;
+                Ok::<_, sp_runtime::DispatchError>(payee)
+            });
+        match settled {
+            Ok(payee_account_id) => {
+                ledger.pending_fen = 0;
+                ledger.total_paid_fen = ledger.total_paid_fen.saturating_add(net_fen);
+                ledger.total_platform_fee_fen = ledger
+                    .total_platform_fee_fen
+                    .saturating_add(platform_fee_fen);
+                CreatorEarnings::<T>::insert(&creator_cid_number, ledger);
+                Self::deposit_event(Event::CreatorPayout {
+                    creator_cid_number,
+                    payee_account_id,
+                    gross_fen,
+                    platform_fee_fen,
+                    net_fen,
+                    paid_at: now,
+                });
+            }
+            // 创作者暂无完整绑定或转账失败：整笔推迟到下个周期。
+            Err(_) => {
+                let next_payout_at = add_calendar_period(now, T::CreatorPayoutPeriod::get());
+                if let Some(payout_at) = next_payout_at {
+                    Self::schedule_payout(&creator_cid_number, &mut ledger, payout_at);
+                }
+                CreatorEarnings::<T>::insert(&creator_cid_number, ledger);
+                Self::deposit_event(Event::CreatorPayoutDeferred {
+                    creator_cid_number,
+                    pending_fen: gross_fen,
+                    next_payout_at,
+                });
+            }
+        }
+    }
+
+    /// 创作者净额与平台抽成原子转出，任一失败整体回滚。
+    fn pay_out(payee: &T::AccountId, net_fen: u128, platform_fee_fen: u128) -> DispatchResult {
+        with_storage_layer(|| -> DispatchResult {
+            Self::escrow_transfer(payee, net_fen)?;
+            Self::route_platform_fee(platform_fee_fen)
+        })
+    }
+
+    fn escrow_transfer(to: &T::AccountId, amount_fen: u128) -> DispatchResult {
+        let amount: BalanceOf<T> = amount_fen.saturated_into();
+        T::Currency::transfer(
+            &T::CreatorEarningsEscrow::get(),
+            to,
+            amount,
+            ExistenceRequirement::KeepAlive,
+        )
+    }
+
+    /// 平台抽成从托管提出后交给 [`Config::PlatformFeeRouter`]，与链上交易费同一分账。
+    fn route_platform_fee(amount_fen: u128) -> DispatchResult {
+        if amount_fen == 0 {
+            return Ok(());
+        }
+        let amount: BalanceOf<T> = amount_fen.saturated_into();
+        let fee = T::Currency::withdraw(
+            &T::CreatorEarningsEscrow::get(),
+            amount,
+            WithdrawReasons::FEE,
+            ExistenceRequirement::KeepAlive,
+        )?;
+        T::PlatformFeeRouter::on_unbalanced(fee);
+        Ok(())
+    }
+
+    /// 把托管账户补足到「全部台账待结算收益 + ED」，不足部分新铸入托管，返回补足额（分）。
+    ///
+    /// 只在创世与 v1 迁移时调用：空托管收不进低于 ED 的订阅款，转空时又会被回收，
+    /// 这笔底仓让转入不受 ED 限制、转出恒可保活。
+    pub(crate) fn endow_earnings_escrow() -> u128 {
+        let escrow = T::CreatorEarningsEscrow::get();
+        let pending_total = CreatorEarnings::<T>::iter_values().fold(0u128, |total, ledger| {
+            total.saturating_add(ledger.pending_fen)
+        });
+        let required =
+            pending_total.saturating_add(T::Currency::minimum_balance().saturated_into::<u128>());
+        let shortfall =
+            required.saturating_sub(T::Currency::free_balance(&escrow).saturated_into::<u128>());
+        if shortfall > 0 {
+            // 正向不平衡随作用域释放，总发行量同步增加。
+            let _ = T::Currency::deposit_creating(&escrow, shortfall.saturated_into());
+        }
+        shortfall
+    }
+
+    /// 创作者 CID 吊销后分批结算其全部订阅：先登记剩余权益并终止订阅，再按比例退款。
+    ///
+    /// 剩余权益统一按首次调用时点计算。登记阶段逐个终止订阅、把剩余权益记入
+    /// [`RevokedRefundCredits`] 并累计总额，不依赖订阅者绑定，不会卡住批次；全部登记后
+    /// 冻结托管中的待结算收益为退款池。退款阶段按游标遍历待退款集合：池不足以全额退还时
+    /// 各订阅者按剩余权益占比分得退款池，已结算给创作者的款项不追回。订阅者当前没有
+    /// 完整绑定时留在集合中、游标越过，遍历到表尾后回到表头重试。集合清空后收回创作者
+    /// 套餐，台账余款交链上费用分账器。
+    pub(crate) fn do_settle_revoked_creator(
+        creator_cid_number: CidNumberOf<T>,
+        max_subscribers: u32,
+    ) -> DispatchResult {
+        ensure!(
+            T::CitizenIdentity::is_cid_revoked(creator_cid_number.as_slice()),
+            Error::<T>::CreatorNotRevoked
+        );
+        let mut ledger = CreatorEarnings::<T>::get(&creator_cid_number).unwrap_or_default();
+        Self::unschedule_payout(&creator_cid_number, &mut ledger);
+        let mut settlement =
+            RevokedSettlements::<T>::get(&creator_cid_number).unwrap_or(RevokedSettlement {
+                settle_at: Self::now_ms(),
+                total_credit_fen: 0,
+                refund_pool_fen: None,
+                cursor: None,
+            });
+        let mut budget = max_subscribers.min(MAX_REVOKED_REFUNDS_PER_CALL) as usize;
+
+        if settlement.refund_pool_fen.is_none() {
+            budget = budget.saturating_sub(Self::register_revoked_credits(
+                &creator_cid_number,
+                &mut settlement,
+                budget,
+            ));
+            if CreatorSubscribers::<T>::iter_key_prefix(&creator_cid_number)
+                .next()
+                .is_some()
+            {
+                RevokedSettlements::<T>::insert(&creator_cid_number, settlement);
+                CreatorEarnings::<T>::insert(&creator_cid_number, ledger);
+                return Ok(());
+            }
+            settlement.refund_pool_fen = Some(ledger.pending_fen);
+        }
+
+        Self::refund_revoked_credits(&creator_cid_number, &mut settlement, &mut ledger, budget);
+        if RevokedRefundCredits::<T>::iter_key_prefix(&creator_cid_number)
+            .next()
+            .is_some()
+        {
+            RevokedSettlements::<T>::insert(&creator_cid_number, settlement);
+            CreatorEarnings::<T>::insert(&creator_cid_number, ledger);
+            return Ok(());
+        }
+
+        RevokedSettlements::<T>::remove(&creator_cid_number);
+        CreatorPlans::<T>::remove(&creator_cid_number);
+        let mut forfeited_fen = 0u128;
+        if ledger.pending_fen > 0 && Self::route_platform_fee(ledger.pending_fen).is_ok() {
+            forfeited_fen = ledger.pending_fen;
+            ledger.pending_fen = 0;
+            ledger.total_platform_fee_fen =
+                ledger.total_platform_fee_fen.saturating_add(forfeited_fen);
+        }
+        CreatorEarnings::<T>::insert(&creator_cid_number, ledger);
+        Self::deposit_event(Event::RevokedCreatorSettled {
+            creator_cid_number,
+            forfeited_fen,
+        });
+        Ok(())
+    }
+
+    /// 登记阶段：终止至多 `budget` 个订阅并记下其在结算时点的剩余权益，返回处理数。
+    fn register_revoked_credits(
+        creator_cid_number: &CidNumberOf<T>,
+        settlement: &mut RevokedSettlementOf<T>,
+        budget: usize,
+    ) -> usize {
+        let issuer: IssuerKeyOf<T> = IssuerKey::Creator(creator_cid_number.clone());
+        let batch: Vec<CidNumberOf<T>> =
+            CreatorSubscribers::<T>::iter_key_prefix(creator_cid_number)
+                .take(budget)
+                .collect();
+        let processed = batch.len();
+        for subscriber_cid_number in batch {
+            let key = (subscriber_cid_number.clone(), issuer.clone());
+            let state = Subscriptions::<T>::get(&key);
+            let credit = match &state {
+                Some(state)
+                    if matches!(
+                        state.subscription_status,
+                        SubscriptionStatus::Active | SubscriptionStatus::Cancelled
+                    ) =>
+                {
+                    Self::remaining_credit(
+                        state.last_charged_price_fen,
+                        state.last_charged_at,
+                        state.paid_until,
+                        settlement.settle_at,
+                    )
+                }
+                _ => 0,
+            };
+            if let Some(mut state) = state {
+                state.subscription_status = SubscriptionStatus::Terminated;
+                state.suspend_reason = None;
+                Subscriptions::<T>::insert(&key, state);
+            }
+            Self::unschedule_renewal(&key);
+            SubscriptionDunning::<T>::remove(&key);
+            CreatorSubscribers::<T>::remove(creator_cid_number, &subscriber_cid_number);
+            if credit > 0 {
+                RevokedRefundCredits::<T>::insert(
+                    creator_cid_number,
+                    &subscriber_cid_number,
+                    credit,
+                );
+                settlement.total_credit_fen = settlement.total_credit_fen.saturating_add(credit);
+            } else {
+                Self::deposit_event(Event::RevokedCreatorSubscriptionRefunded {
+                    subscriber_cid_number,
+                    creator_cid_number: creator_cid_number.clone(),
+                    refund_fen: 0,
+                });
+            }
+        }
+        processed
+    }
+
+    /// 退款阶段：从游标处按比例退款至多 `budget` 个订阅者；遍历到表尾时游标回到表头。
+    fn refund_revoked_credits(
+        creator_cid_number: &CidNumberOf<T>,
+        settlement: &mut RevokedSettlementOf<T>,
+        ledger: &mut CreatorEarningsLedger,
+        budget: usize,
+    ) {
+        let pool_fen = settlement.refund_pool_fen.unwrap_or_default();
+        let mut iter = match &settlement.cursor {
+            Some(cursor) => RevokedRefundCredits::<T>::iter_prefix_from(
+                creator_cid_number,
+                RevokedRefundCredits::<T>::hashed_key_for(creator_cid_number, cursor),
+            ),
+            None => RevokedRefundCredits::<T>::iter_prefix(creator_cid_number),
+        };
+        let batch: Vec<(CidNumberOf<T>, u128)> = iter.by_ref().take(budget).collect();
+        let reached_end = iter.next().is_none();
+        for (subscriber_cid_number, credit_fen) in batch {
+            settlement.cursor = Some(subscriber_cid_number.clone());
+            let refund_fen = pro_rata_refund(credit_fen, pool_fen, settlement.total_credit_fen)
+                .min(ledger.pending_fen);
+            if refund_fen > 0 {
+                let Ok(subscriber_account_id) =
+                    Self::current_account_id_for_cid(&subscriber_cid_number)
+                else {
+                    continue;
+                };
+                if Self::escrow_transfer(&subscriber_account_id, refund_fen).is_err() {
+                    continue;
+                }
+                ledger.pending_fen = ledger.pending_fen.saturating_sub(refund_fen);
+                ledger.total_refunded_fen = ledger.total_refunded_fen.saturating_add(refund_fen);
+            }
+            RevokedRefundCredits::<T>::remove(creator_cid_number, &subscriber_cid_number);
+            Self::deposit_event(Event::RevokedCreatorSubscriptionRefunded {
+                subscriber_cid_number,
+                creator_cid_number: creator_cid_number.clone(),
+                refund_fen,
+            });
+        }
+        if reached_end {
+            settlement.cursor = None;
+        }
+    }
+}
+
+/// 退款池足以覆盖全部剩余权益时全额退还，否则按剩余权益占比向下取整分配退款池。
+fn pro_rata_refund(credit_fen: u128, pool_fen: u128, total_credit_fen: u128) -> u128 {
+    if total_credit_fen <= pool_fen {
+        return credit_fen;
+    }
+    multiply_by_rational_with_rounding(credit_fen, pool_fen, total_credit_fen, Rounding::Down)
+        .unwrap_or(0)
+}
@@ -7,20 +7,25 @@
 //! 2. 会员订阅自动扣款核（公民币单轨）——用户签名订阅后由 runtime 按共识时间戳和
 //!    真实 UTC 公历自动扣款，只有用户签名取消才撤销持续扣款授权。
 //!
-//! 订阅类型和公历换算见 [`subscription`]，自动扣款见 [`billing`]。
-//! 开发期零用户、重新创世模型：平台三档价由创世播种，不设任何迁移。
+//! 订阅类型和公历换算见 [`subscription`]，自动扣款与欠费宽限见 [`billing`]，
+//! 创作者收益台账、周期结算与吊销退款见 [`earnings`]。
+//! 平台三档价由创世播种；存量链升级时的存储迁移见 [`migrations`]。
 
 pub use pallet::*;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 pub mod billing;
+pub mod earnings;
+pub mod migrations;
 pub mod proposal;
 pub mod subscription;
 pub mod weights;
 
+pub use earnings::{CreatorEarningsLedger, RevokedSettlement};
 pub use subscription::{
-    BillingPeriod, CreatorTier, CreatorTiers, IssuerKey, MembershipLevel, PeriodPrice,
-    PeriodPrices, SubscriptionPlan, SubscriptionState, SubscriptionStatus, SuspendReason, TierId,
+    BillingPeriod, CreatorTier, CreatorTiers, DunningRecord, IssuerKey, MembershipLevel,
+    PeriodPrice, PeriodPrices, SubscriptionPlan, SubscriptionState, SubscriptionStatus,
+    SuspendReason, TierId,
 };
 
 use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
@@ -88,6 +93,8 @@ pub trait SquarePostCitizenIdentityProvider<AccountId> {
     fn current_account_id(cid_number: &[u8]) -> Option<AccountId>;
     /// Campaign 在 active CID 基础上额外要求有效竞选身份。
     fn is_campaign_eligible(cid_number: &[u8], account_id: &AccountId) -> bool;
+    /// CID 已被永久吊销；仅暂时缺少双向绑定不算吊销。
+    fn is_cid_revoked(cid_number: &[u8]) -> bool;
     /// benchmark externalities 中播种可用身份；生产构建不暴露此入口。
     #[cfg(feature = "runtime-benchmarks")]
     fn benchmark_seed_identity(account_id: &AccountId) -> Vec<u8>;
@@ -106,6 +113,10 @@ impl<AccountId> SquarePostCitizenIdentityProvider<AccountId> for () {
         false
     }
 
+    fn is_cid_revoked(_cid_number: &[u8]) -> bool {
+        false
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn benchmark_seed_identity(_account_id: &AccountId) -> Vec<u8> {
         Vec::new()
@@ -119,14 +130,17 @@ pub mod pallet {
     use frame_support::{
         ensure,
         pallet_prelude::*,
-        traits::{Currency, UnixTime},
+        traits::{Currency, OnUnbalanced, UnixTime},
     };
     use frame_system::pallet_prelude::*;
+    use sp_runtime::Perbill;
 
-    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
     pub(crate) const FREEDOM_PRICE_FEN: u128 = 199_900;
     pub(crate) const DEMOCRACY_PRICE_FEN: u128 = 599_900;
     pub(crate) const SPARK_PRICE_FEN: u128 = 5_999_900;
+    /// 单次吊销结算调用最多处理的订阅者数。
+    pub(crate) const MAX_REVOKED_REFUNDS_PER_CALL: u32 = 100;
 
     pub type PostIdOf<T> = BoundedVec<u8, <T as Config>::MaxSquarePostIdLen>;
     pub type CidNumberOf<T> = BoundedVec<u8, <T as Config>::MaxSquareCidNumberLen>;
@@ -143,6 +157,14 @@ pub mod pallet {
     pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+    /// 托管提出的平台抽成负失衡。
+    pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+
+    /// 吊销创作者结算进度。
+    pub type RevokedSettlementOf<T> = RevokedSettlement<CidNumberOf<T>>;
+
     /// 创作者收款主体以永久 CID 标识，实际收款账户在每次扣款时解析。
     pub type IssuerKeyOf<T> = IssuerKey<CidNumberOf<T>>;
     /// 订阅关系键：`(订阅者 CID, 收款主体)`，CID 换绑不改写业务真源。
@@ -179,9 +201,25 @@ pub mod pallet {
         type MaxSquareCidNumberLen: Get<u32>;
         #[pallet::constant]
         type MaxSquareStorageReceiptIdLen: Get<u32>;
-        /// 单区块最多处理的到期周期数；包含链停后恢复时的历史到期周期。
+        /// 单区块最多处理的到期周期数；包含链停后恢复时的历史到期周期。创作者结算共用该上限。
         #[pallet::constant]
         type MaxSubscriptionRenewalsPerBlock: Get<u32>;
+        /// 到期扣款失败后的宽限时长（毫秒）；为 0 时首次失败即挂起。
+        #[pallet::constant]
+        type RenewalGracePeriodMs: Get<u64>;
+        /// 宽限期内的重试次数，均匀分布在宽限期内、最后一次落在宽限截止；为 0 时首次失败即挂起。
+        #[pallet::constant]
+        type MaxRenewalRetries: Get<u32>;
+        /// 创作者订阅款托管账户；扣款先入托管，按结算周期付给创作者。
+        type CreatorEarningsEscrow: Get<Self::AccountId>;
+        /// 创作者收益结算周期（真实公历）。
+        #[pallet::constant]
+        type CreatorPayoutPeriod: Get<BillingPeriod>;
+        /// 创作者收益结算时的平台抽成比例。
+        #[pallet::constant]
+        type CreatorPlatformFee: Get<Perbill>;
+        /// 平台抽成与吊销余款的分账落点；runtime 接链上交易费同一分账器。
+        type PlatformFeeRouter: OnUnbalanced<NegativeImbalanceOf<Self>>;
         type WeightInfo: crate::weights::WeightInfo;
     }
 
@@ -214,6 +252,51 @@ pub mod pallet {
     pub type RenewalIndex<T: Config> =
         StorageMap<_, Blake2_128Concat, SubKeyOf<T>, u64, OptionQuery>;
 
+    /// 欠费催缴记录：仅 `PastDue` 订阅持有，离开宽限期即移除。
+    #[pallet::storage]
+    pub type SubscriptionDunning<T: Config> =
+        StorageMap<_, Blake2_128Concat, SubKeyOf<T>, DunningRecord, OptionQuery>;
+
+    /// 创作者收益台账：托管中的待结算收益与累计结算、抽成、退款。
+    #[pallet::storage]
+    pub type CreatorEarnings<T: Config> =
+        StorageMap<_, Blake2_128Concat, CidNumberOf<T>, CreatorEarningsLedger, OptionQuery>;
+
+    /// 创作者结算时间索引，第一键使用大端时间戳；反向记录在台账 `next_payout_at`。
+    #[pallet::storage]
+    pub type PayoutSchedule<T: Config> =
+        StorageDoubleMap<_, Identity, [u8; 8], Blake2_128Concat, CidNumberOf<T>, (), OptionQuery>;
+
+    /// 创作者 → 订阅者 CID 索引，供创作者吊销后逐一退款。
+    #[pallet::storage]
+    pub type CreatorSubscribers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        CidNumberOf<T>,
+        Blake2_128Concat,
+        CidNumberOf<T>,
+        (),
+        OptionQuery,
+    >;
+
+    /// 吊销创作者的分批结算进度。
+    #[pallet::storage]
+    pub type RevokedSettlements<T: Config> =
+        StorageMap<_, Blake2_128Concat, CidNumberOf<T>, RevokedSettlementOf<T>, OptionQuery>;
+
+    /// 吊销创作者待退款集合：`(创作者, 订阅者) -> 结算时点剩余权益`。退款成功才移除，
+    /// 暂时无法退款的订阅者留待后续调用重试。
+    #[pallet::storage]
+    pub type RevokedRefundCredits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        CidNumberOf<T>,
+        Blake2_128Concat,
+        CidNumberOf<T>,
+        u128,
+        OptionQuery,
+    >;
+
     /// 平台三档价（分）。链上可变真源，由公民链基金会经治理写入。
     #[pallet::storage]
     pub type PlatformPrice<T: Config> =
@@ -224,7 +307,8 @@ pub mod pallet {
     pub type CreatorPlans<T: Config> =
         StorageMap<_, Blake2_128Concat, CidNumberOf<T>, CreatorTiers, ValueQuery>;
 
-    /// 平台三档价创世播种：重新创世时无条件写入默认价，之后仅经统一内部投票调整。
+    /// 平台三档价创世播种：重新创世时无条件写入默认价，之后仅经统一内部投票调整；
+    /// 同时为创作者收益托管补足 ED 保活底仓。
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub _marker: PhantomData<T>,
@@ -244,6 +328,7 @@ pub mod pallet {
             PlatformPrice::<T>::insert(MembershipLevel::Freedom, FREEDOM_PRICE_FEN);
             PlatformPrice::<T>::insert(MembershipLevel::Democracy, DEMOCRACY_PRICE_FEN);
             PlatformPrice::<T>::insert(MembershipLevel::Spark, SPARK_PRICE_FEN);
+            Pallet::<T>::endow_earnings_escrow();
         }
     }
 
@@ -296,6 +381,14 @@ pub mod pallet {
             issuer: IssuerKeyOf<T>,
             paused_at: u64,
         },
+        /// 到期扣款失败进入宽限期：权益保留到 `grace_until`，`next_retry_at` 自动重试。
+        SubscriptionPastDue {
+            subscriber_cid_number: CidNumberOf<T>,
+            issuer: IssuerKeyOf<T>,
+            failed_attempts: u32,
+            next_retry_at: u64,
+            grace_until: u64,
+        },
         /// 公历换算失效等真实失败，自动扣款已经终止。
         SubscriptionRenewalStopped {
             subscriber_cid_number: CidNumberOf<T>,
@@ -317,6 +410,38 @@ pub mod pallet {
             charged_now: u128,
             paid_until: u64,
         },
+        /// 创作者订阅款已入托管并记入收益台账。
+        CreatorEarningsAccrued {
+            creator_cid_number: CidNumberOf<T>,
+            amount_fen: u128,
+            pending_fen: u128,
+        },
+        /// 周期结算完成：`net_fen` 付给创作者当前账户，`platform_fee_fen` 交费用分账器。
+        CreatorPayout {
+            creator_cid_number: CidNumberOf<T>,
+            payee_account_id: T::AccountId,
+            gross_fen: u128,
+            platform_fee_fen: u128,
+            net_fen: u128,
+            paid_at: u64,
+        },
+        /// 本期结算推迟；`next_payout_at` 为 `None` 表示创作者已吊销，待吊销结算退款。
+        CreatorPayoutDeferred {
+            creator_cid_number: CidNumberOf<T>,
+            pending_fen: u128,
+            next_payout_at: Option<u64>,
+        },
+        /// 创作者吊销后，订阅者按剩余权益比例从托管收益获得退款。
+        RevokedCreatorSubscriptionRefunded {
+            subscriber_cid_number: CidNumberOf<T>,
+            creator_cid_number: CidNumberOf<T>,
+            refund_fen: u128,
+        },
+        /// 吊销创作者的全部订阅已结算，套餐已收回；无人可领的余款交费用分账器。
+        RevokedCreatorSettled {
+            creator_cid_number: CidNumberOf<T>,
+            forfeited_fen: u128,
+        },
         /// 创作者已经覆盖式更新自己的链上付款套餐。
         CreatorPlansSet {
             creator_cid_number: CidNumberOf<T>,
@@ -387,6 +512,8 @@ pub mod pallet {
         ProposalActionNotFound,
         /// 投票尚未通过或提案作用域不匹配。
         ProposalNotPassed,
+        /// 创作者 CID 未被吊销，不能执行吊销结算。
+        CreatorNotRevoked,
     }
 
     #[pallet::hooks]
@@ -401,18 +528,26 @@ pub mod pallet {
             if cap == 0 {
                 return Weight::zero();
             }
-            let processed = Self::process_due_subscriptions(Self::now_ms(), cap);
-            per.saturating_mul(u64::from(processed))
+            let now = Self::now_ms();
+            let processed = Self::process_due_subscriptions(now, cap);
+            let payouts = Self::process_due_payouts(now, cap.saturating_sub(processed));
+            per.saturating_mul(u64::from(processed)).saturating_add(
+                T::WeightInfo::process_one_payout().saturating_mul(u64::from(payouts)),
+            )
         }
 
         #[cfg(feature = "try-runtime")]
         fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            use sp_runtime::traits::SaturatedConversion;
+
             // 运行期不变量（非迁移专属）：订阅状态与到期调度双向索引必须一致。
             for (key, state) in Subscriptions::<T>::iter() {
                 match state.subscription_status {
-                    // 留在调度里的两态：IssuerPaused 的重试 due 不等于 paid_until，
+                    // 留在调度里的三态：IssuerPaused / PastDue 的重试 due 不等于 paid_until，
                     // 故只校验「有调度项且双向一致」，不再绑定 paid_until。
-                    SubscriptionStatus::Active | SubscriptionStatus::IssuerPaused => {
+                    SubscriptionStatus::Active
+                    | SubscriptionStatus::IssuerPaused
+                    | SubscriptionStatus::PastDue => {
                         ensure!(
                             matches!(
                                 RenewalIndex::<T>::get(&key),
@@ -437,6 +572,32 @@ pub mod pallet {
                     "square-post try_state: renewal reverse index mismatch"
                 );
             }
+            for key in SubscriptionDunning::<T>::iter_keys() {
+                ensure!(
+                    Subscriptions::<T>::get(&key).map(|state| state.subscription_status)
+                        == Some(SubscriptionStatus::PastDue),
+                    "square-post try_state: dunning record without past-due subscription"
+                );
+            }
+            // 托管余额必须覆盖全部台账待结算收益，外加不计入台账的 ED 保活底仓。
+            let pending_total = CreatorEarnings::<T>::iter_values().fold(0u128, |total, ledger| {
+                total.saturating_add(ledger.pending_fen)
+            });
+            ensure!(
+                T::Currency::free_balance(&T::CreatorEarningsEscrow::get())
+                    .saturated_into::<u128>()
+                    >= pending_total
+                        .saturating_add(T::Currency::minimum_balance().saturated_into::<u128>()),
+                "square-post try_state: escrow balance below pending creator earnings plus ED"
+            );
+            for (due_key, creator_cid_number, ()) in PayoutSchedule::<T>::iter() {
+                ensure!(
+                    CreatorEarnings::<T>::get(&creator_cid_number)
+                        .and_then(|ledger| ledger.next_payout_at)
+                        == Some(u64::from_be_bytes(due_key)),
+                    "square-post try_state: payout reverse index mismatch"
+                );
+            }
             Ok(())
         }
     }
@@ -580,6 +741,21 @@ pub mod pallet {
                 new_price_fen,
             )
         }
+
+        /// 创作者 CID 吊销后分批结算其订阅：先终止订阅并登记剩余权益，再按比例从托管收益退款。
+        /// 任何签名账户都可推进；单次最多处理 `MAX_REVOKED_REFUNDS_PER_CALL` 个订阅者。
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::settle_revoked_creator(
+            (*max_subscribers).min(MAX_REVOKED_REFUNDS_PER_CALL)
+        ))]
+        pub fn settle_revoked_creator(
+            origin: OriginFor<T>,
+            creator_cid_number: CidNumberOf<T>,
+            max_subscribers: u32,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            Self::do_settle_revoked_creator(creator_cid_number, max_subscribers)
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -0,0 +1,52 @@
+//! square-post 存储迁移。
+//!
+//! v0 → v1：创作者吊销结算依赖 [`CreatorSubscribers`] 索引逐一终止订阅并退款。该索引晚于
+//! 订阅关系上线，存量创作者订阅没有索引项，吊销后会被漏结算；升级时按 [`Subscriptions`]
+//! 一次性回填全部未终止的创作者订阅；同时为创作者收益托管补足 ED 保活底仓，存量链的
+//! 托管账户由此与新创世链一致。
+
+use frame_support::{
+    migrations::VersionedMigration,
+    traits::{Get, UncheckedOnRuntimeUpgrade},
+    weights::Weight,
+};
+
+use crate::pallet::{Config, CreatorEarnings, CreatorSubscribers, Pallet, Subscriptions};
+use crate::{IssuerKey, SubscriptionStatus};
+
+/// 按订阅关系真源回填创作者 → 订阅者索引，不改动订阅本身；并补足托管保活底仓。
+pub struct BackfillCreatorSubscribers<T>(core::marker::PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for BackfillCreatorSubscribers<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let mut reads = 0u64;
+        let mut writes = 0u64;
+        for ((subscriber_cid_number, issuer), state) in Subscriptions::<T>::iter() {
+            reads = reads.saturating_add(1);
+            let IssuerKey::Creator(creator_cid_number) = issuer else {
+                continue;
+            };
+            if state.subscription_status == SubscriptionStatus::Terminated {
+                continue;
+            }
+            CreatorSubscribers::<T>::insert(&creator_cid_number, &subscriber_cid_number, ());
+            writes = writes.saturating_add(1);
+        }
+        // 补足底仓：遍历台账、读托管余额，补足时写托管账户与总发行量。
+        let creators = CreatorEarnings::<T>::iter_keys().count() as u64;
+        reads = reads.saturating_add(creators).saturating_add(1);
+        if Pallet::<T>::endow_earnings_escrow() > 0 {
+            writes = writes.saturating_add(2);
+        }
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
+
+/// 仅在链上存储版本为 0 时执行一次，完成后写入版本 1。
+pub type MigrateToV1<T> = VersionedMigration<
+    0,
+    1,
+    BackfillCreatorSubscribers<T>,
+    Pallet<T>,
+    <T as frame_system::Config>::DbWeight,
+>;
@@ -13,7 +13,9 @@ use frame_support::{
 use scale_info::TypeInfo;
 use sp_runtime::{traits::SaturatedConversion, DispatchError, RuntimeDebug};
 
-use crate::pallet::{Config, CreatorPlans, Error, Pallet, PlatformPrice, Subscriptions};
+use crate::pallet::{
+    Config, CreatorPlans, Error, Pallet, PlatformPrice, SubscriptionDunning, Subscriptions,
+};
 
 /// 创作者付款档位编号只承担链上引用，不保存名称、说明或权益文案。
 pub type TierId = BoundedVec<u8, ConstU32<32>>;
@@ -179,6 +181,9 @@ pub enum SubscriptionStatus {
     /// 签发方暂时不具备收款条件：创作者掉平台会员，或平台侧价格/收款账户暂不可解析。
     /// 暂停扣费但仍留在续费调度，签发方恢复即自动续，不要求订阅者重新签名。
     IssuerPaused = 4,
+    /// 到期扣款失败后的宽限期：权益保留到宽限截止，按重试表留在续费调度中；
+    /// 重试成功则从原到期时间续接周期，重试用尽才挂起为 `InsufficientBalance`。
+    PastDue = 5,
 }
 
 /// 挂起原因。`Suspended` 时为 `Some`，其余状态为 `None`。
@@ -232,6 +237,28 @@ pub struct SubscriptionState {
     pub suspend_reason: Option<SuspendReason>,
 }
 
+/// 欠费催缴记录，仅 `PastDue` 订阅持有；与订阅真源分开存放，不改变已发布的状态编码。
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct DunningRecord {
+    /// 首次扣款失败的计划扣款时间；重试成功后新周期从此处续接。
+    pub due_at: u64,
+    /// 宽限截止（独占上界）；之前权益仍有效，之后最后一次重试失败即挂起。
+    pub grace_until: u64,
+    /// 已失败的扣款次数（含首次到期扣款）。
+    pub failed_attempts: u32,
+}
+
 /// 将 UTC Unix 毫秒时间戳增加一个真实公历周期。
 ///
 /// 算法只使用确定性整数运算。目标月份没有原日期时使用该月最后一个有效日期，并保留
@@ -307,19 +334,36 @@ impl<T: Config> Pallet<T> {
         T::TimeProvider::now().as_millis().saturated_into::<u64>()
     }
 
-    /// 已付款且尚未到期的 Active/Cancelled 平台订阅都继续提供本周期权益。
+    /// 已付款且尚未到期的 Active/Cancelled 平台订阅都继续提供本周期权益；
+    /// 欠费宽限期内的 PastDue 订阅保留权益到宽限截止。
     pub(crate) fn has_effective_platform_subscription(
         cid_number: &crate::pallet::CidNumberOf<T>,
         now: u64,
     ) -> bool {
-        Subscriptions::<T>::get((cid_number.clone(), IssuerKey::Platform))
-            .map(|state| {
-                matches!(
-                    state.subscription_status,
-                    SubscriptionStatus::Active | SubscriptionStatus::Cancelled
-                ) && now < state.paid_until
-            })
-            .unwrap_or(false)
+        let key = (cid_number.clone(), IssuerKey::Platform);
+        let Some(state) = Subscriptions::<T>::get(&key) else {
+            return false;
+        };
+        match state.subscription_status {
+            SubscriptionStatus::Active | SubscriptionStatus::Cancelled => now < state.paid_until,
+            SubscriptionStatus::PastDue => {
+                SubscriptionDunning::<T>::get(&key).is_some_and(|dunning| now < dunning.grace_until)
+            }
+            _ => false,
+        }
+    }
+
+    /// 公民链基金会费用账户：平台会员收款和创作者收益平台抽成共用同一收费落点。
+    pub(crate) fn platform_fee_account() -> Result<T::AccountId, Error<T>> {
+        // 平台订阅机构永久固定为公民链基金会，CID 单源自创世常量，不读可写存储。
+        let cid = primitives::cid::china::citizenchain::CITIZENCHAIN_FOUNDATION
+            .cid_number
+            .as_bytes();
+        T::InstitutionAccountQuery::lookup_institution_account(
+            cid,
+            primitives::account_derive::RESERVED_NAME_FEE,
+        )
+        .ok_or(Error::<T>::PlatformNotBound)
     }
 
     /// 从链上当前价格和计划解析本次收款账户。每次真实扣款都重新调用，禁止永久锁价。
@@ -333,16 +377,7 @@ impl<T: Config> Pallet<T> {
                 let price = PlatformPrice::<T>::get(membership_level)
                     .ok_or(Error::<T>::PlatformPriceNotSet)?;
                 ensure!(price > 0, Error::<T>::ZeroPrice);
-                // 平台订阅机构永久固定为公民链基金会，CID 单源自创世常量，不读可写存储。
-                let cid = primitives::cid::china::citizenchain::CITIZENCHAIN_FOUNDATION
-                    .cid_number
-                    .as_bytes();
-                let payee = T::InstitutionAccountQuery::lookup_institution_account(
-                    cid,
-                    primitives::account_derive::RESERVED_NAME_FEE,
-                )
-                .ok_or(Error::<T>::PlatformNotBound)?;
-                Ok((price, payee))
+                Ok((price, Self::platform_fee_account()?))
             }
             (
                 IssuerKey::Creator(creator_cid_number),
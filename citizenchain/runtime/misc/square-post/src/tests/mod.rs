@@ -1,13 +1,19 @@
 #![cfg(test)]
 
 use super::*;
-use crate::pallet::{CreatorPlans, PlatformPrice, RenewalIndex, Subscriptions};
+use crate::pallet::{
+    CreatorEarnings, CreatorPlans, CreatorSubscribers, NegativeImbalanceOf, PlatformPrice,
+    RenewalIndex, RevokedRefundCredits, RevokedSettlements, SubscriptionDunning, Subscriptions,
+};
 use frame_support::{
-    assert_noop, assert_ok, derive_impl,
-    traits::{ConstU128, ConstU32, Hooks, UnixTime},
+    assert_noop, assert_ok, derive_impl, parameter_types,
+    traits::{
+        ConstU32, ConstU64, Currency, Get, GetStorageVersion, Hooks, OnRuntimeUpgrade,
+        OnUnbalanced, StorageVersion, UnixTime,
+    },
 };
 use frame_system as system;
-use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage};
+use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage, Perbill};
 use std::cell::RefCell;
 
 type Balance = u128;
@@ -52,7 +58,7 @@ impl pallet_balances::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type Balance = Balance;
     type DustRemoval = ();
-    type ExistentialDeposit = ConstU128<1>;
+    type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type MaxLocks = ConstU32<0>;
     type MaxReserves = ();
@@ -69,6 +75,8 @@ thread_local! {
     static NOW_MS: RefCell<u64> = const { RefCell::new(1_700_000_000_000) };
     /// 测试中的当前 CID↔账户双向绑定；重绑时同一 CID 只保留一个当前账户。
     static IDENTITY_BINDINGS: RefCell<Vec<(Vec<u8>, AccountId32)>> = const { RefCell::new(Vec::new()) };
+    /// 测试中已被永久吊销的 CID。
+    static REVOKED_CIDS: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
 }
 
 pub struct MockTime;
@@ -114,6 +122,15 @@ impl SquarePostCitizenIdentityProvider<AccountId32> for TestCitizenIdentity {
         cid_number == VERIFIED_CID && account_id == &verified_account()
     }
 
+    fn is_cid_revoked(cid_number: &[u8]) -> bool {
+        REVOKED_CIDS.with(|revoked| {
+            revoked
+                .borrow()
+                .iter()
+                .any(|current| current.as_slice() == cid_number)
+        })
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn benchmark_seed_identity(account_id: &AccountId32) -> Vec<u8> {
         let cid_number = b"GD001-CTZN1-999999999-2026".to_vec();
@@ -122,6 +139,14 @@ impl SquarePostCitizenIdentityProvider<AccountId32> for TestCitizenIdentity {
     }
 }
 
+/// 测试用费用分账器：抽成全部落入单一汇集账户，便于核对金额。
+pub struct MockFeeRouter;
+impl OnUnbalanced<NegativeImbalanceOf<Test>> for MockFeeRouter {
+    fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Test>) {
+        Balances::resolve_creating(&fee_sink_account(), amount);
+    }
+}
+
 pub struct MockInstitutionQuery;
 impl entity_primitives::InstitutionMultisigQuery<AccountId32> for MockInstitutionQuery {
     fn lookup_institution_account(cid_number: &[u8], account_name: &[u8]) -> Option<AccountId32> {
@@ -146,6 +171,17 @@ impl entity_primitives::InstitutionMultisigQuery<AccountId32> for MockInstitutio
     }
 }
 
+const DAY_MS: u64 = 86_400_000;
+
+parameter_types! {
+    // 默认取 1 便于小额夹具；真实 ED 用例经 [`new_test_ext_with_existential_deposit`] 覆盖。
+    pub static ExistentialDeposit: u128 = 1;
+    pub EarningsEscrow: AccountId32 = AccountId32::new([10u8; 32]);
+    // 结算周期取一年，使按月续费的用例不被同块结算打断。
+    pub const CreatorPayoutPeriod: BillingPeriod = BillingPeriod::Yearly;
+    pub const CreatorPlatformFee: Perbill = Perbill::from_percent(10);
+}
+
 impl crate::pallet::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type CitizenIdentity = TestCitizenIdentity;
@@ -158,6 +194,12 @@ impl crate::pallet::Config for Test {
     type MaxSquareCidNumberLen = ConstU32<32>;
     type MaxSquareStorageReceiptIdLen = ConstU32<96>;
     type MaxSubscriptionRenewalsPerBlock = ConstU32<64>;
+    type RenewalGracePeriodMs = ConstU64<{ 3 * DAY_MS }>;
+    type MaxRenewalRetries = ConstU32<3>;
+    type CreatorEarningsEscrow = EarningsEscrow;
+    type CreatorPayoutPeriod = CreatorPayoutPeriod;
+    type CreatorPlatformFee = CreatorPlatformFee;
+    type PlatformFeeRouter = MockFeeRouter;
     type WeightInfo = ();
 }
 
@@ -199,6 +241,9 @@ fn rebound_creator_account_id() -> AccountId32 {
 fn platform_fee_account() -> AccountId32 {
     account(9)
 }
+fn fee_sink_account() -> AccountId32 {
+    account(11)
+}
 
 fn bounded_cid_number(value: &[u8]) -> crate::pallet::CidNumberOf<Test> {
     value.to_vec().try_into().expect("test CID fits")
@@ -230,8 +275,22 @@ fn unbind_identity(cid_number: &[u8]) {
     });
 }
 
+fn revoke_cid(cid_number: &[u8]) {
+    unbind_identity(cid_number);
+    REVOKED_CIDS.with(|revoked| revoked.borrow_mut().push(cid_number.to_vec()));
+}
+
+fn creator_pending() -> u128 {
+    CreatorEarnings::<Test>::get(creator_cid_number()).map_or(0, |ledger| ledger.pending_fen)
+}
+
+fn next_payout_at() -> Option<u64> {
+    CreatorEarnings::<Test>::get(creator_cid_number()).and_then(|ledger| ledger.next_payout_at)
+}
+
 fn reset_identity_bindings() {
     IDENTITY_BINDINGS.with(|bindings| bindings.borrow_mut().clear());
+    REVOKED_CIDS.with(|revoked| revoked.borrow_mut().clear());
     bind_identity(VERIFIED_CID, verified_account());
     bind_identity(VISITOR_CID, visitor_account());
     bind_identity(SUBSCRIBER_CID, subscriber_account_id());
@@ -293,6 +352,14 @@ fn set_active_platform_member(account: AccountId32) {
 }
 
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+    new_test_ext_with_existential_deposit(1)
+}
+
+/// 按指定 ED 构建测试环境；低于 ED 的夹具账户不建户，托管账户按创世逻辑补足底仓。
+pub(crate) fn new_test_ext_with_existential_deposit(
+    existential_deposit: u128,
+) -> sp_io::TestExternalities {
+    ExistentialDeposit::set(existential_deposit);
     let mut storage = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
         .expect("test storage should build");
@@ -302,7 +369,10 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
             (poor_account(), 100),
             (creator_account_id(), 1_000_000_000),
             (rebound_subscriber_account_id(), 1_000_000_000),
-        ],
+        ]
+        .into_iter()
+        .filter(|(_, balance)| *balance >= existential_deposit)
+        .collect(),
         ..Default::default()
     }
     .assimilate_storage(&mut storage)
@@ -312,6 +382,7 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
         System::set_block_number(1);
         set_now(1_700_000_000_000);
         reset_identity_bindings();
+        SquarePost::endow_earnings_escrow();
     });
     ext
 }
@@ -554,27 +625,61 @@ fn runtime_catches_up_every_due_period_after_blocks_resume() {
     });
 }
 
+fn insert_poor_platform_subscription_due_at(due: u64) -> crate::pallet::SubKeyOf<Test> {
+    let key = (bounded_cid_number(POOR_CID), IssuerKey::Platform);
+    Subscriptions::<Test>::insert(
+        &key,
+        SubscriptionState {
+            plan: platform_plan(),
+            started_at: 1,
+            last_charged_at: 1,
+            last_charged_price_fen: PLATFORM_PRICE,
+            paid_until: due,
+            subscription_status: SubscriptionStatus::Active,
+            authorized_price_fen: PLATFORM_PRICE,
+            suspend_reason: None,
+        },
+    );
+    SquarePost::schedule_renewal(&key, due);
+    key
+}
+
 #[test]
-fn payment_failure_suspends_and_removes_schedule() {
+fn payment_failure_retries_through_grace_then_suspends() {
     new_test_ext().execute_with(|| {
         setup_platform();
-        let key = (bounded_cid_number(POOR_CID), IssuerKey::Platform);
-        Subscriptions::<Test>::insert(
-            &key,
-            SubscriptionState {
-                plan: platform_plan(),
-                started_at: 1,
-                last_charged_at: 1,
-                last_charged_price_fen: PLATFORM_PRICE,
-                paid_until: 2,
-                subscription_status: SubscriptionStatus::Active,
-                authorized_price_fen: PLATFORM_PRICE,
-                suspend_reason: None,
-            },
-        );
-        SquarePost::schedule_renewal(&key, 2);
+        let key = insert_poor_platform_subscription_due_at(2);
         initialize_at(2);
-        // 余额不足 → 挂起（不再终止），退出续费调度，保留订阅。
+        // 余额不足 → 进入宽限期，权益保留到宽限截止，重试均匀分布在宽限期内。
+        let state = Subscriptions::<Test>::get(&key).expect("state exists");
+        assert_eq!(state.subscription_status, SubscriptionStatus::PastDue);
+        assert_eq!(state.paid_until, 2);
+        let dunning = SubscriptionDunning::<Test>::get(&key).expect("dunning recorded");
+        assert_eq!(dunning.due_at, 2);
+        assert_eq!(dunning.grace_until, 2 + 3 * DAY_MS);
+        assert_eq!(dunning.failed_attempts, 1);
+        assert_eq!(RenewalIndex::<Test>::get(&key), Some(2 + DAY_MS));
+        assert!(SquarePost::has_effective_platform_subscription(
+            &bounded_cid_number(POOR_CID),
+            2 + DAY_MS
+        ));
+
+        initialize_at(2 + DAY_MS);
+        assert_eq!(RenewalIndex::<Test>::get(&key), Some(2 + 2 * DAY_MS));
+        initialize_at(2 + 2 * DAY_MS);
+        assert_eq!(RenewalIndex::<Test>::get(&key), Some(2 + 3 * DAY_MS));
+        assert_eq!(
+            SubscriptionDunning::<Test>::get(&key).map(|record| record.failed_attempts),
+            Some(3)
+        );
+
+        // 宽限截止的最后一次重试仍失败 → 挂起（不再终止），退出续费调度，保留订阅。
+        initialize_at(2 + 3 * DAY_MS);
+        assert!(!SquarePost::has_effective_platform_subscription(
+            &bounded_cid_number(POOR_CID),
+            2 + 3 * DAY_MS
+        ));
+        assert!(!SubscriptionDunning::<Test>::contains_key(&key));
         let state = Subscriptions::<Test>::get(&key).expect("state exists");
         assert_eq!(state.subscription_status, SubscriptionStatus::Suspended);
         assert_eq!(
@@ -585,6 +690,38 @@ fn payment_failure_suspends_and_removes_schedule() {
     });
 }
 
+#[test]
+fn grace_retry_success_continues_period_from_original_due() {
+    new_test_ext().execute_with(|| {
+        setup_platform();
+        let due = 1_700_000_000_000u64;
+        let key = insert_poor_platform_subscription_due_at(due);
+        initialize_at(due);
+        assert_eq!(
+            Subscriptions::<Test>::get(&key)
+                .expect("state exists")
+                .subscription_status,
+            SubscriptionStatus::PastDue
+        );
+
+        // 充值后下一次重试扣款成功，新周期从原到期时间续接，不按重试时间漂移。
+        Balances::make_free_balance_be(&poor_account(), 1_000_000_000);
+        initialize_at(due + DAY_MS);
+        let state = Subscriptions::<Test>::get(&key).expect("state exists");
+        let expected_until = crate::subscription::add_calendar_period(due, BillingPeriod::Monthly)
+            .expect("calendar fits");
+        assert_eq!(state.subscription_status, SubscriptionStatus::Active);
+        assert_eq!(state.last_charged_at, due + DAY_MS);
+        assert_eq!(state.paid_until, expected_until);
+        assert_eq!(RenewalIndex::<Test>::get(&key), Some(expected_until));
+        assert!(!SubscriptionDunning::<Test>::contains_key(&key));
+        assert_eq!(
+            Balances::free_balance(poor_account()),
+            1_000_000_000 - PLATFORM_PRICE
+        );
+    });
+}
+
 #[test]
 fn cancellation_preserves_paid_rights_and_revokes_future_charges() {
     new_test_ext().execute_with(|| {
@@ -744,13 +881,10 @@ fn creator_subscription_renews_at_authorized_price_when_unchanged() {
             IssuerKey::Creator(creator_cid_number()),
         ))
         .expect("scheduled");
-        let creator_before = Balances::free_balance(creator_account_id());
+        let creator_before = creator_pending();
         // 价格未变 → 自动续扣当前授权价。
         initialize_at(due);
-        assert_eq!(
-            Balances::free_balance(creator_account_id()),
-            creator_before + 50
-        );
+        assert_eq!(creator_pending(), creator_before + 50);
     });
 }
 
@@ -778,13 +912,21 @@ fn creator_rebind_keeps_plans_and_routes_renewal_to_current_account() {
 
         bind_identity(CREATOR_CID, rebound_creator_account_id());
         initialize_at(due);
+        assert_eq!(creator_pending(), 100);
+        assert_eq!(CreatorPlans::<Test>::get(creator_cid_number()).len(), 1);
 
+        // 取消后不再续扣；结算时按创作者 CID 解析当前账户，旧账户不再收款。
+        assert_ok!(SquarePost::cancel(
+            RuntimeOrigin::signed(subscriber_account_id()),
+            IssuerKey::Creator(creator_cid_number()),
+        ));
+        let payout_at = next_payout_at().expect("payout scheduled");
+        initialize_at(payout_at);
         assert_eq!(Balances::free_balance(creator_account_id()), old_before);
         assert_eq!(
             Balances::free_balance(rebound_creator_account_id()),
-            new_before + 50
+            new_before + 90
         );
-        assert_eq!(CreatorPlans::<Test>::get(creator_cid_number()).len(), 1);
     });
 }
 
@@ -835,7 +977,7 @@ fn creator_price_change_suspends_renewal_until_reconsent() {
             CreatorTiers::try_from(vec![creator_tier(75)]).expect("tiers fit"),
         );
         let due = RenewalIndex::<Test>::get(&ck).expect("scheduled");
-        let creator_before = Balances::free_balance(creator_account_id());
+        let creator_before = creator_pending();
         initialize_at(due);
         // 续费挂起、创作者未收款、离调度。
         let state = Subscriptions::<Test>::get(&ck).expect("state exists");
@@ -845,7 +987,7 @@ fn creator_price_change_suspends_renewal_until_reconsent() {
             Some(crate::SuspendReason::NeedReconsent)
         );
         assert_eq!(state.authorized_price_fen, 50);
-        assert_eq!(Balances::free_balance(creator_account_id()), creator_before);
+        assert_eq!(creator_pending(), creator_before);
         assert!(!RenewalIndex::<Test>::contains_key(&ck));
         // 订阅者按新价再签名 → 恢复 Active 并扣新价。
         assert_ok!(SquarePost::subscribe(
@@ -857,10 +999,7 @@ fn creator_price_change_suspends_renewal_until_reconsent() {
         let resumed = Subscriptions::<Test>::get(&ck).expect("state exists");
         assert_eq!(resumed.subscription_status, SubscriptionStatus::Active);
         assert_eq!(resumed.authorized_price_fen, 75);
-        assert_eq!(
-            Balances::free_balance(creator_account_id()),
-            creator_before + 75
-        );
+        assert_eq!(creator_pending(), creator_before + 75);
         assert!(RenewalIndex::<Test>::contains_key(&ck));
     });
 }
@@ -889,7 +1028,7 @@ fn creator_price_change_reconsent_before_lapse_keeps_active_without_charge() {
             creator_cid_number(),
             CreatorTiers::try_from(vec![creator_tier(75)]).expect("tiers fit"),
         );
-        let creator_before = Balances::free_balance(creator_account_id());
+        let creator_before = creator_pending();
         assert_ok!(SquarePost::subscribe(
             RuntimeOrigin::signed(subscriber_account_id()),
             IssuerKey::Creator(creator_cid_number()),
@@ -900,13 +1039,10 @@ fn creator_price_change_reconsent_before_lapse_keeps_active_without_charge() {
         let state = Subscriptions::<Test>::get(&ck).expect("state exists");
         assert_eq!(state.subscription_status, SubscriptionStatus::Active);
         assert_eq!(state.authorized_price_fen, 75);
-        assert_eq!(Balances::free_balance(creator_account_id()), creator_before);
+        assert_eq!(creator_pending(), creator_before);
         // 下期按新价自动扣。
         initialize_at(due);
-        assert_eq!(
-            Balances::free_balance(creator_account_id()),
-            creator_before + 75
-        );
+        assert_eq!(creator_pending(), creator_before + 75);
     });
 }
 
@@ -931,12 +1067,12 @@ fn creator_loses_membership_pauses_fans_and_resumes() {
         let due = RenewalIndex::<Test>::get(&ck).expect("scheduled");
         // 创作者掉平台会员。
         Subscriptions::<Test>::remove((creator_cid_number(), IssuerKey::Platform));
-        let creator_before = Balances::free_balance(creator_account_id());
+        let creator_before = creator_pending();
         initialize_at(due);
         // 粉丝暂停：IssuerPaused、未扣、未终止、仍在调度、下周期重试。
         let state = Subscriptions::<Test>::get(&ck).expect("state exists");
         assert_eq!(state.subscription_status, SubscriptionStatus::IssuerPaused);
-        assert_eq!(Balances::free_balance(creator_account_id()), creator_before);
+        assert_eq!(creator_pending(), creator_before);
         let retry = RenewalIndex::<Test>::get(&ck).expect("still scheduled");
         assert!(retry > due);
         // 创作者恢复平台会员 → 下个重试自动续扣、回 Active。
@@ -944,10 +1080,7 @@ fn creator_loses_membership_pauses_fans_and_resumes() {
         initialize_at(retry);
         let resumed = Subscriptions::<Test>::get(&ck).expect("state exists");
         assert_eq!(resumed.subscription_status, SubscriptionStatus::Active);
-        assert_eq!(
-            Balances::free_balance(creator_account_id()),
-            creator_before + 50
-        );
+        assert_eq!(creator_pending(), creator_before + 50);
     });
 }
 
@@ -1199,3 +1332,342 @@ fn plan_change_credit_uses_last_charged_price_not_authorized_price() {
         );
     });
 }
+
+fn subscribe_creator_at_price(price_fen: u128) {
+    set_active_platform_member(creator_account_id());
+    CreatorPlans::<Test>::insert(
+        creator_cid_number(),
+        CreatorTiers::try_from(vec![creator_tier(price_fen)]).expect("tiers fit"),
+    );
+    assert_ok!(SquarePost::subscribe(
+        RuntimeOrigin::signed(subscriber_account_id()),
+        IssuerKey::Creator(creator_cid_number()),
+        creator_plan(),
+        price_fen,
+    ));
+}
+
+#[test]
+fn creator_payout_splits_platform_fee_and_clears_ledger() {
+    new_test_ext().execute_with(|| {
+        subscribe_creator_at_price(500);
+        // 订阅款进入托管并记账，不直接到创作者账户。
+        let creator_before = Balances::free_balance(creator_account_id());
+        assert_eq!(
+            Balances::free_balance(EarningsEscrow::get()),
+            500 + ExistentialDeposit::get()
+        );
+        assert_eq!(creator_pending(), 500);
+        assert!(CreatorSubscribers::<Test>::contains_key(
+            creator_cid_number(),
+            subscriber_cid_number()
+        ));
+        assert_ok!(SquarePost::cancel(
+            RuntimeOrigin::signed(subscriber_account_id()),
+            IssuerKey::Creator(creator_cid_number()),
+        ));
+
+        let payout_at = next_payout_at().expect("payout scheduled");
+        initialize_at(payout_at - 1);
+        assert_eq!(creator_pending(), 500);
+        initialize_at(payout_at);
+        assert_eq!(
+            Balances::free_balance(creator_account_id()),
+            creator_before + 450
+        );
+        // 抽成交费用分账器，不再直接转入平台费用账户。
+        assert_eq!(Balances::free_balance(fee_sink_account()), 50);
+        assert_eq!(Balances::free_balance(platform_fee_account()), 0);
+        // 托管只剩不计入台账的 ED 底仓，不被回收。
+        assert_eq!(
+            Balances::free_balance(EarningsEscrow::get()),
+            ExistentialDeposit::get()
+        );
+        let ledger = CreatorEarnings::<Test>::get(creator_cid_number()).expect("ledger kept");
+        assert_eq!(ledger.pending_fen, 0);
+        assert_eq!(ledger.total_earned_fen, 500);
+        assert_eq!(ledger.total_paid_fen, 450);
+        assert_eq!(ledger.total_platform_fee_fen, 50);
+        assert_eq!(ledger.next_payout_at, None);
+    });
+}
+
+#[test]
+fn creator_escrow_accepts_sub_ed_payments_and_survives_payout_with_realistic_ed() {
+    new_test_ext_with_existential_deposit(primitives::core_const::ACCOUNT_EXISTENTIAL_DEPOSIT)
+        .execute_with(|| {
+            let ed = ExistentialDeposit::get();
+            assert_eq!(Balances::free_balance(EarningsEscrow::get()), ed);
+            // 低于 ED 的订阅款也能进入已有底仓的托管。
+            subscribe_creator_at_price(100);
+            assert_eq!(Balances::free_balance(EarningsEscrow::get()), ed + 100);
+            assert_eq!(creator_pending(), 100);
+            assert_ok!(SquarePost::cancel(
+                RuntimeOrigin::signed(subscriber_account_id()),
+                IssuerKey::Creator(creator_cid_number()),
+            ));
+            let creator_before = Balances::free_balance(creator_account_id());
+
+            initialize_at(next_payout_at().expect("payout scheduled"));
+
+            // 全额结算后托管仍保留 ED 底仓，账户不被回收。
+            assert_eq!(
+                Balances::free_balance(creator_account_id()),
+                creator_before + 90
+            );
+            assert_eq!(creator_pending(), 0);
+            assert_eq!(Balances::free_balance(EarningsEscrow::get()), ed);
+            assert!(System::account_exists(&EarningsEscrow::get()));
+        });
+}
+
+#[test]
+fn creator_payout_defers_while_creator_binding_unavailable() {
+    new_test_ext().execute_with(|| {
+        subscribe_creator_at_price(500);
+        assert_ok!(SquarePost::cancel(
+            RuntimeOrigin::signed(subscriber_account_id()),
+            IssuerKey::Creator(creator_cid_number()),
+        ));
+        let payout_at = next_payout_at().expect("payout scheduled");
+        unbind_identity(CREATOR_CID);
+        initialize_at(payout_at);
+        // 收益留在托管，推迟一个结算周期。
+        assert_eq!(creator_pending(), 500);
+        assert_eq!(
+            Balances::free_balance(EarningsEscrow::get()),
+            500 + ExistentialDeposit::get()
+        );
+        assert_eq!(
+            next_payout_at(),
+            crate::subscription::add_calendar_period(payout_at, BillingPeriod::Yearly)
+        );
+    });
+}
+
+#[test]
+fn revoked_creator_refunds_remaining_credit_and_forfeits_rest() {
+    new_test_ext().execute_with(|| {
+        subscribe_creator_at_price(1_000);
+        let key = (
+            subscriber_cid_number(),
+            IssuerKey::Creator(creator_cid_number()),
+        );
+        let state = Subscriptions::<Test>::get(&key).expect("state exists");
+        assert_noop!(
+            SquarePost::settle_revoked_creator(
+                RuntimeOrigin::signed(visitor_account()),
+                creator_cid_number(),
+                10,
+            ),
+            Error::<Test>::CreatorNotRevoked
+        );
+
+        // 本期过半时创作者被吊销：结算期到也不再付给创作者。
+        let midpoint = state.last_charged_at + (state.paid_until - state.last_charged_at) / 2;
+        set_now(midpoint);
+        revoke_cid(CREATOR_CID);
+        let subscriber_before = Balances::free_balance(subscriber_account_id());
+        assert_ok!(SquarePost::settle_revoked_creator(
+            RuntimeOrigin::signed(visitor_account()),
+            creator_cid_number(),
+            10,
+        ));
+
+        let refund = 1_000 * u128::from(state.paid_until - midpoint)
+            / u128::from(state.paid_until - state.last_charged_at);
+        assert_eq!(
+            Balances::free_balance(subscriber_account_id()),
+            subscriber_before + refund
+        );
+        let state = Subscriptions::<Test>::get(&key).expect("state kept");
+        assert_eq!(state.subscription_status, SubscriptionStatus::Terminated);
+        assert!(!RenewalIndex::<Test>::contains_key(&key));
+        assert!(!CreatorSubscribers::<Test>::contains_key(
+            creator_cid_number(),
+            subscriber_cid_number()
+        ));
+        assert!(CreatorPlans::<Test>::get(creator_cid_number()).is_empty());
+        // 退款后余款无人可领，交费用分账器。
+        assert_eq!(Balances::free_balance(fee_sink_account()), 1_000 - refund);
+        assert!(!RevokedSettlements::<Test>::contains_key(
+            creator_cid_number()
+        ));
+        assert_eq!(
+            Balances::free_balance(EarningsEscrow::get()),
+            ExistentialDeposit::get()
+        );
+        let ledger = CreatorEarnings::<Test>::get(creator_cid_number()).expect("ledger kept");
+        assert_eq!(ledger.pending_fen, 0);
+        assert_eq!(ledger.total_refunded_fen, refund);
+        assert_eq!(ledger.next_payout_at, None);
+    });
+}
+
+fn subscribe_verified_to_creator(price_fen: u128) {
+    Balances::make_free_balance_be(&verified_account(), 1_000_000_000);
+    assert_ok!(SquarePost::subscribe(
+        RuntimeOrigin::signed(verified_account()),
+        IssuerKey::Creator(creator_cid_number()),
+        creator_plan(),
+        price_fen,
+    ));
+}
+
+#[test]
+fn revoked_creator_splits_short_pool_pro_rata() {
+    new_test_ext().execute_with(|| {
+        subscribe_creator_at_price(1_000);
+        subscribe_verified_to_creator(1_000);
+        let state = Subscriptions::<Test>::get((
+            subscriber_cid_number(),
+            IssuerKey::Creator(creator_cid_number()),
+        ))
+        .expect("state exists");
+        // 模拟已有部分收益结算给创作者：托管只剩 600，不足以全额退还两位订阅者。
+        CreatorEarnings::<Test>::mutate(creator_cid_number(), |ledger| {
+            ledger.as_mut().expect("ledger exists").pending_fen = 600;
+        });
+        Balances::make_free_balance_be(&EarningsEscrow::get(), 600 + ExistentialDeposit::get());
+
+        let midpoint = state.last_charged_at + (state.paid_until - state.last_charged_at) / 2;
+        set_now(midpoint);
+        revoke_cid(CREATOR_CID);
+        let subscriber_before = Balances::free_balance(subscriber_account_id());
+        let verified_before = Balances::free_balance(verified_account());
+        assert_ok!(SquarePost::settle_revoked_creator(
+            RuntimeOrigin::signed(visitor_account()),
+            creator_cid_number(),
+            10,
+        ));
+
+        // 剩余权益相同，按比例各得一半，不因处理先后多退。
+        assert_eq!(
+            Balances::free_balance(subscriber_account_id()),
+            subscriber_before + 300
+        );
+        assert_eq!(
+            Balances::free_balance(verified_account()),
+            verified_before + 300
+        );
+        assert_eq!(Balances::free_balance(fee_sink_account()), 0);
+        assert_eq!(
+            Balances::free_balance(EarningsEscrow::get()),
+            ExistentialDeposit::get()
+        );
+        let ledger = CreatorEarnings::<Test>::get(creator_cid_number()).expect("ledger kept");
+        assert_eq!(ledger.pending_fen, 0);
+        assert_eq!(ledger.total_refunded_fen, 600);
+        assert!(!RevokedSettlements::<Test>::contains_key(
+            creator_cid_number()
+        ));
+    });
+}
+
+#[test]
+fn revoked_creator_retries_unbound_subscriber_without_blocking_others() {
+    new_test_ext().execute_with(|| {
+        subscribe_creator_at_price(1_000);
+        subscribe_verified_to_creator(1_000);
+        revoke_cid(CREATOR_CID);
+        unbind_identity(SUBSCRIBER_CID);
+        let verified_before = Balances::free_balance(verified_account());
+        assert_ok!(SquarePost::settle_revoked_creator(
+            RuntimeOrigin::signed(visitor_account()),
+            creator_cid_number(),
+            10,
+        ));
+
+        // 暂无绑定的订阅者留在待退款集合，其他订阅者照常退款，结算不收尾。
+        assert_eq!(
+            Balances::free_balance(verified_account()),
+            verified_before + 1_000
+        );
+        assert!(RevokedRefundCredits::<Test>::contains_key(
+            creator_cid_number(),
+            subscriber_cid_number()
+        ));
+        assert!(RevokedSettlements::<Test>::contains_key(
+            creator_cid_number()
+        ));
+        assert!(!CreatorPlans::<Test>::get(creator_cid_number()).is_empty());
+        let state = Subscriptions::<Test>::get((
+            subscriber_cid_number(),
+            IssuerKey::Creator(creator_cid_number()),
+        ))
+        .expect("state kept");
+        assert_eq!(state.subscription_status, SubscriptionStatus::Terminated);
+
+        // 重绑后再次推进即可退款并收尾。
+        bind_identity(SUBSCRIBER_CID, rebound_subscriber_account_id());
+        let rebound_before = Balances::free_balance(rebound_subscriber_account_id());
+        assert_ok!(SquarePost::settle_revoked_creator(
+            RuntimeOrigin::signed(visitor_account()),
+            creator_cid_number(),
+            10,
+        ));
+        assert_eq!(
+            Balances::free_balance(rebound_subscriber_account_id()),
+            rebound_before + 1_000
+        );
+        assert!(!RevokedRefundCredits::<Test>::contains_key(
+            creator_cid_number(),
+            subscriber_cid_number()
+        ));
+        assert!(!RevokedSettlements::<Test>::contains_key(
+            creator_cid_number()
+        ));
+        assert!(CreatorPlans::<Test>::get(creator_cid_number()).is_empty());
+        assert_eq!(
+            Balances::free_balance(EarningsEscrow::get()),
+            ExistentialDeposit::get()
+        );
+    });
+}
+
+#[test]
+fn migration_v1_backfills_creator_subscriber_index() {
+    new_test_ext().execute_with(|| {
+        subscribe_creator_at_price(1_000);
+        let creator_key = (
+            subscriber_cid_number(),
+            IssuerKey::Creator(creator_cid_number()),
+        );
+        let mut terminated = Subscriptions::<Test>::get(&creator_key).expect("state exists");
+        terminated.subscription_status = SubscriptionStatus::Terminated;
+        Subscriptions::<Test>::insert(
+            (
+                bounded_cid_number(VISITOR_CID),
+                IssuerKey::Creator(creator_cid_number()),
+            ),
+            terminated,
+        );
+        // 模拟索引上线前的存量链：只有订阅真源，没有创作者 → 订阅者索引。
+        let _ = CreatorSubscribers::<Test>::clear(u32::MAX, None);
+        // 存量托管只有台账款、没有 ED 底仓。
+        Balances::make_free_balance_be(&EarningsEscrow::get(), creator_pending());
+        StorageVersion::new(0).put::<SquarePost>();
+
+        crate::migrations::MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(
+            Balances::free_balance(EarningsEscrow::get()),
+            creator_pending() + ExistentialDeposit::get()
+        );
+
+        assert!(CreatorSubscribers::<Test>::contains_key(
+            creator_cid_number(),
+            subscriber_cid_number()
+        ));
+        assert!(!CreatorSubscribers::<Test>::contains_key(
+            creator_cid_number(),
+            bounded_cid_number(VISITOR_CID)
+        ));
+        // 平台订阅不属于任何创作者，不进索引。
+        assert_eq!(CreatorSubscribers::<Test>::iter().count(), 1);
+        assert_eq!(
+            SquarePost::on_chain_storage_version(),
+            StorageVersion::new(1)
+        );
+    });
+}
@@ -10,6 +10,10 @@ pub trait WeightInfo {
     fn propose_set_platform_price() -> Weight;
     /// 单笔到期续费（读价/收款方 + 转账 + 状态写 + 双向调度索引）；on_initialize 据实记账。
     fn process_one_due() -> Weight;
+    /// 单笔创作者周期结算（台账 + 身份/费用账户解析 + 两笔托管转出 + 调度索引）。
+    fn process_one_payout() -> Weight;
+    /// 吊销创作者结算，按本次处理的订阅者数线性计费。
+    fn settle_revoked_creator(subscribers: u32) -> Weight;
 }
 
 pub struct SubstrateWeight<T>(PhantomData<T>);
@@ -53,6 +57,23 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn process_one_due() -> Weight {
         Weight::from_parts(85_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(8, 7))
     }
+
+    // 正式 benchmark 补齐前，按净额转账加抽成三方分账的续费路径取保守上界。
+    fn process_one_payout() -> Weight {
+        Weight::from_parts(120_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(12, 10))
+    }
+
+    // 固定部分含结算进度与余款三方分账；每个订阅者：订阅状态、订阅者绑定、退款转账、
+    // 待退款集合与调度/催缴/索引清理。
+    fn settle_revoked_creator(subscribers: u32) -> Weight {
+        Weight::from_parts(90_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads_writes(10, 10))
+            .saturating_add(
+                Weight::from_parts(70_000_000, 0)
+                    .saturating_add(T::DbWeight::get().reads_writes(6, 8))
+                    .saturating_mul(subscribers.into()),
+            )
+    }
 }
 
 impl WeightInfo for () {
@@ -87,4 +108,12 @@ impl WeightInfo for () {
     fn process_one_due() -> Weight {
         Weight::zero()
     }
+
+    fn process_one_payout() -> Weight {
+        Weight::zero()
+    }
+
+    fn settle_revoked_creator(_subscribers: u32) -> Weight {
+        Weight::zero()
+    }
 }
@@ -32,6 +32,10 @@ pub const JOINT_VOTE_PASS_THRESHOLD: u32 = 105; // 联合投票通过条件：
 pub const VOTING_DURATION_DAYS: u32 = 30; // 投票默认期限30天
 pub const BLOCKS_PER_DAY: u32 = pow_const::BLOCKS_PER_DAY as u32; // 每天区块数（统一来源：pow_const）
 pub const VOTING_DURATION_BLOCKS: u32 = BLOCKS_PER_DAY * VOTING_DURATION_DAYS; // 投票默认期限（区块）= 30 * BLOCKS_PER_DAY
+pub const VOTE_DELEGATION_MAX_DAYS: u32 = 90; // 岗位投票委托单次最长有效期90天
+pub const VOTE_DELEGATION_MAX_BLOCKS: u32 = BLOCKS_PER_DAY * VOTE_DELEGATION_MAX_DAYS; // 岗位投票委托最长有效期（区块）
+pub const PETITION_DURATION_DAYS: u32 = 90; // 公民创制联署征集期90天
+pub const PETITION_DURATION_BLOCKS: u32 = BLOCKS_PER_DAY * PETITION_DURATION_DAYS; // 公民创制联署征集期（区块）
 
 // 决议发行常量。
 pub const RESOLUTION_ISSUANCE_MAX_REASON_LEN: u32 = 1024; // 决议发行理由最大长度
@@ -0,0 +1,104 @@
+//! 紧急暂停（熔断）目标协议：runtime 暂停登记表与原生节点守卫共用。
+//!
+//! 暂停目标按 `RuntimeCall` 元数据中的 pallet 名与 call 名识别，不按 pallet/call 索引编码，
+//! 这样 runtime 调整 call 顺序不会让既有暂停记录指向别的入口。共识、出块最终性和治理自救
+//! 路径所在 pallet 永久受保护：任何投票或快速通道都不能把它们写进暂停登记表。
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::DecodeWithMemTracking, traits::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// pallet 名与 call 名的最大字节数。
+pub const MAX_PAUSE_NAME_LEN: u32 = 64;
+
+pub type PauseName = BoundedVec<u8, ConstU32<MAX_PAUSE_NAME_LEN>>;
+
+/// 永不允许暂停的 pallet：系统/时间戳/GRANDPA 维持出块与最终性，投票引擎与联合投票
+/// 承载解除暂停的治理表决，协议升级与 GRANDPA 密钥治理是最后的自救通道，
+/// 暂停模块自身被暂停则无法续期或解除。
+pub const PROTECTED_PALLET_NAMES: &[&[u8]] = &[
+    b"System",
+    b"Timestamp",
+    b"Grandpa",
+    b"VotingEngine",
+    b"InternalVote",
+    b"JointVote",
+    b"RuntimeUpgrade",
+    b"GrandpaKeyChange",
+    b"EmergencyPause",
+];
+
+/// 暂停目标：`call_name` 为空表示暂停整个 pallet 的外部调用。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct PauseTarget {
+    pub pallet_name: PauseName,
+    pub call_name: Option<PauseName>,
+}
+
+impl PauseTarget {
+    /// 名称只允许 Rust 标识符字符，拒绝空串与任何无法对应 call 元数据的输入。
+    pub fn is_well_formed(&self) -> bool {
+        is_valid_name(&self.pallet_name)
+            && self
+                .call_name
+                .as_ref()
+                .map_or(true, |name| is_valid_name(name))
+    }
+
+    pub fn is_protected(&self) -> bool {
+        is_protected_pallet(&self.pallet_name)
+    }
+}
+
+fn is_valid_name(name: &[u8]) -> bool {
+    !name.is_empty()
+        && name
+            .iter()
+            .all(|byte| byte.is_ascii_alphanumeric() || *byte == b'_')
+}
+
+pub fn is_protected_pallet(pallet_name: &[u8]) -> bool {
+    PROTECTED_PALLET_NAMES.contains(&pallet_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(pallet_name: &[u8], call_name: Option<&[u8]>) -> PauseTarget {
+        PauseTarget {
+            pallet_name: pallet_name.to_vec().try_into().expect("name fits"),
+            call_name: call_name.map(|name| name.to_vec().try_into().expect("name fits")),
+        }
+    }
+
+    #[test]
+    fn consensus_and_recovery_pallets_are_protected() {
+        assert!(target(b"System", None).is_protected());
+        assert!(target(b"JointVote", Some(b"joint_vote")).is_protected());
+        assert!(target(b"EmergencyPause", None).is_protected());
+        assert!(!target(b"MultisigTransfer", None).is_protected());
+        assert!(!target(b"SquarePost", Some(b"subscribe")).is_protected());
+    }
+
+    #[test]
+    fn names_must_match_call_metadata_charset() {
+        assert!(target(b"SquarePost", Some(b"change_subscription_plan")).is_well_formed());
+        assert!(!target(b"", None).is_well_formed());
+        assert!(!target(b"SquarePost", Some(b"")).is_well_formed());
+        assert!(!target(b"Square Post", None).is_well_formed());
+    }
+}
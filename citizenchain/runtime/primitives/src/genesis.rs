@@ -44,6 +44,9 @@ sp_api::decl_runtime_apis! {
 
         /// 读取 SCALE 编码的法律版本标签。
         fn law_version_label(law_id: u64, version: u32) -> Option<Vec<u8>>;
+
+        /// 读取同一法律两个版本间 SCALE 编码的逐款差异(`Vec<LawDiffEntry>`)。
+        fn law_diff(law_id: u64, from_version: u32, to_version: u32) -> Option<Vec<u8>>;
     }
 }
 
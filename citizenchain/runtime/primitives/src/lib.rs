@@ -12,6 +12,7 @@ pub mod citizen_const; // 公民发行常量
 pub mod constitution; // 宪法修改「章→档位」分类(第十九条)
 pub mod core_const; // 核心常量
 pub mod count_const; // 投票治理常量
+pub mod emergency_pause; // 紧急暂停目标与受保护 pallet 清单
 pub mod fee_policy; // 费率规则常量
 pub mod genesis; // 创世常量
 pub mod governance_skeleton; // 固定治理骨架冻结规格(档 A)
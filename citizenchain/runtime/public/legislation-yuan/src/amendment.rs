@@ -0,0 +1,339 @@
+//! 条文级修法补丁套用与版本逐款差异。
+//!
+//! 补丁与差异共用同一套序号定位规则:章 > 节 > 条 > 款各层按条文自身 `number` 寻址,
+//! 同级序号必须唯一。补丁只负责结构改写,套用后的全文仍交给整部修法的既有校验
+//! (宪法三层编号唯一、不可修改条款、第十九条档位),不另设一套规则。
+
+use frame_support::{ensure, pallet_prelude::DispatchError, traits::Get, BoundedVec};
+use sp_runtime::sp_std::vec::Vec;
+
+use crate::pallet::{
+    AmendmentNode, AmendmentOperation, Article, Chapter, ChaptersOf, Clause, Config, Error,
+    LawAmendmentPatch, LawVersions, Laws, Pallet, Section,
+};
+use crate::types::{LawDiffChange, LawDiffEntry, LawTextPath, LawTextSnapshot};
+
+/// 章/节/条/款的公共视图:序号、可比对文本与下级差异递归。
+trait LawTextNode {
+    fn number(&self) -> u32;
+    fn snapshot(&self) -> LawTextSnapshot;
+    fn diff_children(
+        path: &LawTextPath,
+        old: Option<&Self>,
+        new: Option<&Self>,
+        out: &mut Vec<LawDiffEntry>,
+    );
+}
+
+fn children<'a, P, C>(node: Option<&'a P>, get: impl Fn(&'a P) -> &'a [C]) -> &'a [C] {
+    node.map(get).unwrap_or(&[])
+}
+
+impl<T: Config> LawTextNode for Chapter<T> {
+    fn number(&self) -> u32 {
+        self.number
+    }
+    fn snapshot(&self) -> LawTextSnapshot {
+        LawTextSnapshot {
+            title: self.title.to_vec(),
+            title_en: self.title_en.as_ref().map(|t| t.to_vec()),
+            ..Default::default()
+        }
+    }
+    fn diff_children(
+        path: &LawTextPath,
+        old: Option<&Self>,
+        new: Option<&Self>,
+        out: &mut Vec<LawDiffEntry>,
+    ) {
+        diff_nodes(
+            Some(path),
+            children(old, |c| c.sections.as_slice()),
+            children(new, |c| c.sections.as_slice()),
+            out,
+        );
+    }
+}
+
+impl<T: Config> LawTextNode for Section<T> {
+    fn number(&self) -> u32 {
+        self.number
+    }
+    fn snapshot(&self) -> LawTextSnapshot {
+        LawTextSnapshot {
+            title: self.title.to_vec(),
+            title_en: self.title_en.as_ref().map(|t| t.to_vec()),
+            ..Default::default()
+        }
+    }
+    fn diff_children(
+        path: &LawTextPath,
+        old: Option<&Self>,
+        new: Option<&Self>,
+        out: &mut Vec<LawDiffEntry>,
+    ) {
+        diff_nodes(
+            Some(path),
+            children(old, |s| s.articles.as_slice()),
+            children(new, |s| s.articles.as_slice()),
+            out,
+        );
+    }
+}
+
+impl<T: Config> LawTextNode for Article<T> {
+    fn number(&self) -> u32 {
+        self.number
+    }
+    fn snapshot(&self) -> LawTextSnapshot {
+        LawTextSnapshot {
+            title: self.title.to_vec(),
+            title_en: self.title_en.as_ref().map(|t| t.to_vec()),
+            text: self.body.to_vec(),
+            text_en: self.body_en.as_ref().map(|t| t.to_vec()),
+        }
+    }
+    fn diff_children(
+        path: &LawTextPath,
+        old: Option<&Self>,
+        new: Option<&Self>,
+        out: &mut Vec<LawDiffEntry>,
+    ) {
+        diff_nodes(
+            Some(path),
+            children(old, |a| a.clauses.as_slice()),
+            children(new, |a| a.clauses.as_slice()),
+            out,
+        );
+    }
+}
+
+impl<T: Config> LawTextNode for Clause<T> {
+    fn number(&self) -> u32 {
+        self.number
+    }
+    fn snapshot(&self) -> LawTextSnapshot {
+        LawTextSnapshot {
+            text: self.text.to_vec(),
+            text_en: self.text_en.as_ref().map(|t| t.to_vec()),
+            ..Default::default()
+        }
+    }
+    fn diff_children(
+        _: &LawTextPath,
+        _: Option<&Self>,
+        _: Option<&Self>,
+        _: &mut Vec<LawDiffEntry>,
+    ) {
+    }
+}
+
+/// 同级节点差异:先列旧版本独有(删除),再按新版本顺序列新增与改动,逐层递归到款。
+fn diff_nodes<X: LawTextNode>(
+    parent: Option<&LawTextPath>,
+    old: &[X],
+    new: &[X],
+    out: &mut Vec<LawDiffEntry>,
+) {
+    let path_of = |number: u32| match parent {
+        None => Some(LawTextPath::Chapter(number)),
+        Some(parent) => parent.child(number),
+    };
+    for removed in old
+        .iter()
+        .filter(|o| !new.iter().any(|n| n.number() == o.number()))
+    {
+        let Some(path) = path_of(removed.number()) else {
+            continue;
+        };
+        out.push(LawDiffEntry {
+            path,
+            change: LawDiffChange::Removed,
+            before: Some(removed.snapshot()),
+            after: None,
+        });
+        X::diff_children(&path, Some(removed), None, out);
+    }
+    for current in new {
+        let Some(path) = path_of(current.number()) else {
+            continue;
+        };
+        let previous = old.iter().find(|o| o.number() == current.number());
+        let before = previous.map(LawTextNode::snapshot);
+        let after = current.snapshot();
+        let change = match &before {
+            None => Some(LawDiffChange::Added),
+            Some(before) if *before != after => Some(LawDiffChange::Modified),
+            Some(_) => None,
+        };
+        if let Some(change) = change {
+            out.push(LawDiffEntry {
+                path,
+                change,
+                before,
+                after: Some(after),
+            });
+        }
+        X::diff_children(&path, previous, Some(current), out);
+    }
+}
+
+fn position<X: LawTextNode>(items: &[X], number: u32) -> Option<usize> {
+    items.iter().position(|item| item.number() == number)
+}
+
+impl<T: Config> Pallet<T> {
+    /// 对生效版本套用补丁,返回新全文;基准版本必须是当前生效版本且结果确有改动。
+    pub(crate) fn resolve_amendment_patch(
+        law_id: u64,
+        patch: &LawAmendmentPatch<T>,
+    ) -> Result<ChaptersOf<T>, DispatchError> {
+        ensure!(!patch.operations.is_empty(), Error::<T>::EmptyAmendment);
+        let law = Laws::<T>::get(law_id).ok_or(Error::<T>::LawNotFound)?;
+        ensure!(
+            law.effective_version == Some(patch.base_version),
+            Error::<T>::AmendmentBaseMismatch
+        );
+        let base = LawVersions::<T>::get(law_id, patch.base_version)
+            .ok_or(Error::<T>::LawVersionNotFound)?;
+        let mut chapters = base.chapters.clone();
+        for operation in patch.operations.iter() {
+            Self::apply_amendment_operation(&mut chapters, operation)?;
+        }
+        ensure!(chapters != base.chapters, Error::<T>::EmptyAmendment);
+        Ok(chapters)
+    }
+
+    /// 两份全文的逐款差异(章/节标题、条标题与正文、款正文)。
+    pub(crate) fn diff_chapters(old: &ChaptersOf<T>, new: &ChaptersOf<T>) -> Vec<LawDiffEntry> {
+        let mut out = Vec::new();
+        diff_nodes(None, old.as_slice(), new.as_slice(), &mut out);
+        out
+    }
+
+    fn apply_amendment_operation(
+        chapters: &mut ChaptersOf<T>,
+        operation: &AmendmentOperation<T>,
+    ) -> Result<(), DispatchError> {
+        match operation {
+            AmendmentOperation::Insert {
+                parent,
+                before,
+                node,
+            } => match (parent, node) {
+                (None, AmendmentNode::Chapter(chapter)) => {
+                    Self::insert_node(chapters, *before, chapter.clone())
+                }
+                (Some(LawTextPath::Chapter(c)), AmendmentNode::Section(section)) => {
+                    let chapter = Self::child_mut(chapters, *c)?;
+                    Self::insert_node(&mut chapter.sections, *before, section.clone())
+                }
+                (Some(LawTextPath::Section(c, s)), AmendmentNode::Article(article)) => {
+                    let section =
+                        Self::child_mut(&mut Self::child_mut(chapters, *c)?.sections, *s)?;
+                    Self::insert_node(&mut section.articles, *before, article.clone())
+                }
+                (Some(LawTextPath::Article(c, s, a)), AmendmentNode::Clause(clause)) => {
+                    let section =
+                        Self::child_mut(&mut Self::child_mut(chapters, *c)?.sections, *s)?;
+                    let article = Self::child_mut(&mut section.articles, *a)?;
+                    Self::insert_node(&mut article.clauses, *before, clause.clone())
+                }
+                _ => Err(Error::<T>::InvalidAmendmentPatch.into()),
+            },
+            AmendmentOperation::Replace { path, node } => match (path, node) {
+                (LawTextPath::Chapter(c), AmendmentNode::Chapter(chapter)) => {
+                    Self::replace_node(chapters, *c, chapter.clone())
+                }
+                (LawTextPath::Section(c, s), AmendmentNode::Section(section)) => {
+                    let chapter = Self::child_mut(chapters, *c)?;
+                    Self::replace_node(&mut chapter.sections, *s, section.clone())
+                }
+                (LawTextPath::Article(c, s, a), AmendmentNode::Article(article)) => {
+                    let section =
+                        Self::child_mut(&mut Self::child_mut(chapters, *c)?.sections, *s)?;
+                    Self::replace_node(&mut section.articles, *a, article.clone())
+                }
+                (LawTextPath::Clause(c, s, a, k), AmendmentNode::Clause(clause)) => {
+                    let section =
+                        Self::child_mut(&mut Self::child_mut(chapters, *c)?.sections, *s)?;
+                    let article = Self::child_mut(&mut section.articles, *a)?;
+                    Self::replace_node(&mut article.clauses, *k, clause.clone())
+                }
+                _ => Err(Error::<T>::InvalidAmendmentPatch.into()),
+            },
+            AmendmentOperation::Delete { path } => match *path {
+                LawTextPath::Chapter(c) => Self::remove_node(chapters, c),
+                LawTextPath::Section(c, s) => {
+                    Self::remove_node(&mut Self::child_mut(chapters, c)?.sections, s)
+                }
+                LawTextPath::Article(c, s, a) => {
+                    let section = Self::child_mut(&mut Self::child_mut(chapters, c)?.sections, s)?;
+                    Self::remove_node(&mut section.articles, a)
+                }
+                LawTextPath::Clause(c, s, a, k) => {
+                    let section = Self::child_mut(&mut Self::child_mut(chapters, c)?.sections, s)?;
+                    let article = Self::child_mut(&mut section.articles, a)?;
+                    Self::remove_node(&mut article.clauses, k)
+                }
+            },
+        }
+    }
+
+    fn child_mut<X: LawTextNode, S: Get<u32>>(
+        items: &mut BoundedVec<X, S>,
+        number: u32,
+    ) -> Result<&mut X, DispatchError> {
+        items
+            .iter_mut()
+            .find(|item| item.number() == number)
+            .ok_or_else(|| Error::<T>::InvalidAmendmentPatch.into())
+    }
+
+    fn insert_node<X: LawTextNode, S: Get<u32>>(
+        items: &mut BoundedVec<X, S>,
+        before: Option<u32>,
+        node: X,
+    ) -> Result<(), DispatchError> {
+        ensure!(
+            position(items, node.number()).is_none(),
+            Error::<T>::InvalidAmendmentPatch
+        );
+        let index = match before {
+            Some(number) => position(items, number).ok_or(Error::<T>::InvalidAmendmentPatch)?,
+            None => items.len(),
+        };
+        items
+            .try_insert(index, node)
+            .map_err(|_| Error::<T>::InvalidAmendmentPatch.into())
+    }
+
+    fn replace_node<X: LawTextNode, S: Get<u32>>(
+        items: &mut BoundedVec<X, S>,
+        number: u32,
+        node: X,
+    ) -> Result<(), DispatchError> {
+        let index = position(items, number).ok_or(Error::<T>::InvalidAmendmentPatch)?;
+        ensure!(
+            items
+                .iter()
+                .enumerate()
+                .all(|(i, item)| i == index || item.number() != node.number()),
+            Error::<T>::InvalidAmendmentPatch
+        );
+        let slot = items
+            .get_mut(index)
+            .ok_or(Error::<T>::InvalidAmendmentPatch)?;
+        *slot = node;
+        Ok(())
+    }
+
+    fn remove_node<X: LawTextNode, S: Get<u32>>(
+        items: &mut BoundedVec<X, S>,
+        number: u32,
+    ) -> Result<(), DispatchError> {
+        let index = position(items, number).ok_or(Error::<T>::InvalidAmendmentPatch)?;
+        items.remove(index);
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
 //! # 立法院模块 (legislation-yuan)
 //!
 //! 法律结构化上链 + 修法一律走投票引擎(ADR-027)。本 pallet 是「业务壳」:
-//! 只承载法律数据(Law / LawVersion)、状态机、提案入口(立法/修法/废法)、
+//! 只承载法律数据(Law / LawVersion)、状态机、提案入口(立法/修法/废法/续期、公民创制联署)、
+//! 日落条款到期排程、
 //! 投票通过回调写入、不可修改条款硬拒与查询;表决规则、计票、两院顺序、强制公投
 //! 全部归属投票引擎 `legislation-vote` sub-pallet。
 //!
@@ -10,11 +11,17 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod amendment;
+pub mod petition;
+pub mod sunset;
 pub mod types;
 pub mod weights;
 
 pub use pallet::*;
-pub use types::{LawAction, LawStatus, Tier, VoteType};
+pub use types::{
+    LawAction, LawDiffChange, LawDiffEntry, LawStatus, LawSunset, LawTextPath, LawTextSnapshot,
+    PetitionStatus, SunsetKind, Tier, VoteType,
+};
 
 /// 模块标识前缀,用于在 votingengine `ProposalData` 中区分本模块提案,防止跨模块误解码。
 pub const MODULE_TAG: &[u8] = b"leg-yuan";
@@ -57,7 +64,7 @@ pub mod pallet {
     use primitives::count_const::IMMUTABLE_CONSTITUTION_ARTICLES;
     use primitives::genesis::GENESIS_LAW_VERSION_LABELS;
     use sp_runtime::sp_std::{collections::btree_set::BTreeSet, vec::Vec};
-    use sp_runtime::DispatchError;
+    use sp_runtime::{DispatchError, Permill};
     use votingengine::{ProposalExecutionOutcome, VotePlanOf, VotingEngineKind};
 
     // 受 Config 常量约束的有界字符串别名。
@@ -175,6 +182,75 @@ pub mod pallet {
     /// 法律全文章节别名:章 > 节 > 条 > 款。
     pub type ChaptersOf<T> = BoundedVec<Chapter<T>, <T as Config>::MaxChaptersPerLaw>;
 
+    /// 修法补丁中插入或替换的条文节点,层级须与目标路径一致。
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        CloneNoBound,
+        PartialEqNoBound,
+        EqNoBound,
+        RuntimeDebugNoBound,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub enum AmendmentNode<T: Config> {
+        Chapter(Chapter<T>),
+        Section(Section<T>),
+        Article(Article<T>),
+        Clause(Clause<T>),
+    }
+
+    /// 修法补丁单步操作,按序套用到基准版本全文。
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        CloneNoBound,
+        PartialEqNoBound,
+        EqNoBound,
+        RuntimeDebugNoBound,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub enum AmendmentOperation<T: Config> {
+        /// 在 `parent` 下插入节点(`parent = None` 插入章);`before` 为同级已有节点序号,
+        /// None 追加到末尾。
+        Insert {
+            parent: Option<LawTextPath>,
+            before: Option<u32>,
+            node: AmendmentNode<T>,
+        },
+        /// 整体替换 `path` 处节点(含其下级);新节点序号不得与其他同级节点重复。
+        Replace {
+            path: LawTextPath,
+            node: AmendmentNode<T>,
+        },
+        /// 删除 `path` 处节点及其下级。
+        Delete { path: LawTextPath },
+    }
+
+    /// 条文级修法补丁:声明基准版本 + 有序操作列表,取代整部全文重交。
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        CloneNoBound,
+        PartialEqNoBound,
+        EqNoBound,
+        RuntimeDebugNoBound,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct LawAmendmentPatch<T: Config> {
+        /// 必须等于提案与写入时法律的当前生效版本。
+        pub base_version: u32,
+        pub operations: BoundedVec<AmendmentOperation<T>, <T as Config>::MaxAmendmentOperations>,
+    }
+
     /// 法律主体记录(状态 + 版本指针 + 归属立法机构)。
     #[derive(
         Encode,
@@ -287,6 +363,63 @@ pub mod pallet {
         pub title_en: Option<TitleOf<T>>,
         pub content_hash: [u8; 32],
         pub effective_at: u64,
+        /// 新版本的日落条款:`Some` 为提案显式给出(两项皆空即无日落);`None` 仅用于
+        /// 条文补丁与创制修法,沿用旧生效版本尚未到期的条款。
+        pub sunset: Option<LawSunset>,
+    }
+
+    /// 公民创制联署目标:新立法律(指定层级/行政区/院序列)或整部修改既有法律。
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Clone,
+        PartialEq,
+        Eq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub enum PetitionTarget {
+        Enact {
+            tier: Tier,
+            scope_code: u32,
+            houses: Houses,
+        },
+        Amend {
+            law_id: u64,
+        },
+    }
+
+    /// 公民创制联署。`summary` 即达门槛后提交立法投票的提案摘要,发起机构固定为发起院
+    /// (`houses[0]`);门槛在发起时按作用域有效投票人口冻结,征集期内人口变动不改门槛。
+    #[derive(
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        CloneNoBound,
+        PartialEqNoBound,
+        EqNoBound,
+        RuntimeDebugNoBound,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct Petition<T: Config> {
+        /// 发起公民账户(同时计为第一个联署人)。
+        pub initiator: T::AccountId,
+        /// 发起公民 CID;全文存续期间占用其 `OpenPetitionsByCitizen` 名额。
+        pub initiator_cid_number: votingengine::types::CidNumber,
+        pub summary: LawProposalSummary<T>,
+        /// 联署资格作用域:国家/宪法层级为全国,省/市层级按发起院 CID 所属行政区。
+        pub population_scope: votingengine::PopulationScope,
+        pub required_signatures: u64,
+        pub signatures: u64,
+        /// 征集截止区块(含)。
+        pub expires_at: BlockNumberFor<T>,
+        pub status: PetitionStatus,
+        /// 建案后的立法投票提案 ID。
+        pub proposal_id: Option<u64>,
     }
 
     #[pallet::config]
@@ -324,6 +457,24 @@ pub mod pallet {
         type MaxLawsPerScope: Get<u32>;
         #[pallet::constant]
         type MaxPendingActivations: Get<u32>;
+        /// 每个区块最多处理的到期日落排程条目数,剩余条目顺延到后续区块。
+        #[pallet::constant]
+        type MaxSunsetsPerBlock: Get<u32>;
+        /// 单个条文补丁最多操作数。
+        #[pallet::constant]
+        type MaxAmendmentOperations: Get<u32>;
+        /// 公民创制联署门槛:发起时作用域内有效投票公民数的比例(向上取整,至少 1 人)。
+        #[pallet::constant]
+        type PetitionThreshold: Get<Permill>;
+        /// 联署征集期(区块)。
+        #[pallet::constant]
+        type PetitionDuration: Get<BlockNumberFor<Self>>;
+        /// 单次清理联署记录的最大条数。
+        #[pallet::constant]
+        type MaxPetitionCleanup: Get<u32>;
+        /// 每个公民 CID 同时持有未建案联署全文的上限(限制 `PetitionTexts` 占用)。
+        #[pallet::constant]
+        type MaxOpenPetitionsPerCitizen: Get<u32>;
 
         type WeightInfo: crate::weights::WeightInfo;
     }
@@ -389,6 +540,28 @@ pub mod pallet {
     pub type PendingActivations<T: Config> =
         StorageValue<_, BoundedVec<(u64, u32), <T as Config>::MaxPendingActivations>, ValueQuery>;
 
+    /// 法律版本日落条款:(law_id, version) → LawSunset。无日落条款的版本不落表。
+    #[pallet::storage]
+    pub type LawVersionSunsets<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u64, Blake2_128Concat, u32, LawSunset, OptionQuery>;
+
+    /// 日落排程索引。第一键使用大端到期时间戳毫秒,使 storage 迭代顺序等于到期顺序;
+    /// 第二键为 (law_id, version, 种类)。条目数不设上限,每个区块按序处理有限条。
+    #[pallet::storage]
+    pub type SunsetSchedule<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        [u8; 8],
+        Blake2_128Concat,
+        (u64, u32, SunsetKind),
+        (),
+        OptionQuery,
+    >;
+
+    /// 复审期限已过仍未续期的法律:law_id → 待复审版本。续期、失效或新版本生效时清除。
+    #[pallet::storage]
+    pub type LawReviewsDue<T: Config> = StorageMap<_, Blake2_128Concat, u64, u32, OptionQuery>;
+
     /// 不可修改条款 manifest(创世冻结,无 setter,见 [`ImmutableManifest`])。
     #[pallet::storage]
     pub type ConstitutionImmutableManifest<T: Config> =
@@ -410,6 +583,36 @@ pub mod pallet {
     pub type ConstitutionGuardVoteProof<T: Config> =
         StorageMap<_, Blake2_128Concat, u32, u32, OptionQuery>;
 
+    /// 公民创制联署自增 ID。
+    #[pallet::storage]
+    pub type NextPetitionId<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// 公民创制联署主表:petition_id → Petition。
+    #[pallet::storage]
+    pub type Petitions<T: Config> = StorageMap<_, Blake2_128Concat, u64, Petition<T>, OptionQuery>;
+
+    /// 联署法律全文;建案时移入投票引擎 `ProposalObject` 并删除。
+    #[pallet::storage]
+    pub type PetitionTexts<T: Config> =
+        StorageMap<_, Blake2_128Concat, u64, ChaptersOf<T>, OptionQuery>;
+
+    /// 公民 CID → 其发起且全文仍在链上的联署数;建案或清理时释放。
+    #[pallet::storage]
+    pub type OpenPetitionsByCitizen<T: Config> =
+        StorageMap<_, Blake2_128Concat, votingengine::types::CidNumber, u32, ValueQuery>;
+
+    /// 联署记录:(petition_id, 公民 CID) → 联署账户。按永久 CID 去重,换绑账户不能再签。
+    #[pallet::storage]
+    pub type PetitionSignatures<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,
+        Blake2_128Concat,
+        votingengine::types::CidNumber,
+        T::AccountId,
+        OptionQuery,
+    >;
+
     /// 创世配置:注入内置公民宪法作为 `tier=宪法`、`law_id=0` 的链上法律(宪法唯一真源)。
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
@@ -550,6 +753,38 @@ pub mod pallet {
         LawRepealed { law_id: u64 },
         /// 法律版本已生效。
         LawEffective { law_id: u64, version: u32 },
+        /// 生效版本到达日落期限,法律已自动失效。
+        LawExpired { law_id: u64, version: u32 },
+        /// 生效版本到达复审期限仍未续期。
+        LawReviewDue { law_id: u64, version: u32 },
+        /// 续期案通过,生效版本的日落条款已更新(两项皆空即取消日落)。
+        LawSunsetRenewed {
+            law_id: u64,
+            version: u32,
+            sunset: LawSunset,
+        },
+        /// 公民创制联署已发起。
+        PetitionOpened {
+            petition_id: u64,
+            initiator: T::AccountId,
+            content_hash: [u8; 32],
+            required_signatures: u64,
+        },
+        /// 公民已联署。
+        PetitionSigned {
+            petition_id: u64,
+            signer: T::AccountId,
+            signatures: u64,
+        },
+        /// 联署达门槛,已提交立法投票。
+        PetitionFiled { petition_id: u64, proposal_id: u64 },
+        /// 联署达门槛但建案失败,保留为可重试状态。
+        PetitionFilingFailed {
+            petition_id: u64,
+            error: DispatchError,
+        },
+        /// 联署记录已清理完毕。
+        PetitionCleared { petition_id: u64 },
     }
 
     #[pallet::error]
@@ -610,11 +845,43 @@ pub mod pallet {
         TooManyLawsInScope,
         /// 待生效版本队列超上限
         TooManyActivations,
+        /// 修法补丁路径不存在、节点层级不匹配、同级序号重复或超出容量
+        InvalidAmendmentPatch,
+        /// 修法补丁的基准版本不是法律当前生效版本(含表决期间生效版本已被替换)
+        AmendmentBaseMismatch,
+        /// 联署不存在
+        PetitionNotFound,
+        /// 联署不在征集状态
+        PetitionNotCollecting,
+        /// 联署征集期已过
+        PetitionExpired,
+        /// 联署不在可重试建案状态
+        PetitionNotQualified,
+        /// 联署仍在征集期内或仍可建案,不能清理
+        PetitionStillActive,
+        /// 不是联署作用域内的有效投票公民
+        NotPetitionVoter,
+        /// 该公民 CID 已联署
+        AlreadySignedPetition,
+        /// 联署人确认的全文哈希与联署不一致
+        PetitionContentMismatch,
+        /// 联署作用域的有效投票人口尚不可用
+        PetitionPopulationUnavailable,
+        /// 发起人未建案的联署数已达上限
+        TooManyOpenPetitions,
+        /// 日落期限不晚于当前时间,或复审期限晚于失效期限
+        InvalidSunset,
+        /// 宪法不设日落条款
+        CannotSunsetConstitution,
+        /// 法律不在生效状态(待生效修订中、已失效或已废止),不能续期
+        LawNotEffective,
+        /// 续期案的基准版本不再是法律当前生效版本
+        SunsetBaseMismatch,
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        /// 用链上时间戳扫描待生效队列,到时间后自动切换生效版本。
+        /// 用链上时间戳扫描待生效队列与日落排程,到时间后自动切换生效版本或失效。
         fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
             let now_ms = Self::now_ms();
             let pending = PendingActivations::<T>::take();
@@ -635,10 +902,12 @@ pub mod pallet {
             if !remain.is_empty() {
                 PendingActivations::<T>::put(remain);
             }
-            T::DbWeight::get().reads_writes(
-                activated.saturating_add(retained).saturating_add(2),
-                activated.saturating_add(2),
-            )
+            T::DbWeight::get()
+                .reads_writes(
+                    activated.saturating_add(retained).saturating_add(2),
+                    activated.saturating_add(2),
+                )
+                .saturating_add(Self::process_sunsets(now_ms))
         }
     }
 
@@ -661,6 +930,7 @@ pub mod pallet {
             title_en: Option<TitleOf<T>>,
             chapters: ChaptersOf<T>,
             effective_at: u64,
+            sunset: LawSunset,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             // 宪法唯一真源 = 创世注入的 law_id=0,立法入口永不能新立第二部宪法(ADR-027 §6.1)。
@@ -686,6 +956,7 @@ pub mod pallet {
                 vote_type,
                 &legislature_cid_number,
             )?;
+            Self::ensure_valid_sunset(tier, &sunset, effective_at.max(Self::now_ms()))?;
 
             let summary = LawProposalSummary::<T> {
                 action: LawAction::Enact,
@@ -702,9 +973,15 @@ pub mod pallet {
                 title_en,
                 content_hash: Self::hash_chapters(&chapters),
                 effective_at,
+                sunset: Some(sunset),
             };
-            let proposal_id =
-                Self::dispatch_to_engine(&who, &houses, vote_type, &summary, &chapters)?;
+            let proposal_id = Self::dispatch_to_engine(
+                Some(&who),
+                &houses,
+                vote_type,
+                &summary,
+                chapters.encode(),
+            )?;
             Self::deposit_event(Event::<T>::LawProposalCreated {
                 proposal_id,
                 action: LawAction::Enact,
@@ -714,7 +991,7 @@ pub mod pallet {
             Ok(())
         }
 
-        /// 修法:针对既有法律提交新版本(整部全文快照),走立法投票。
+        /// 修法:针对既有法律提交新版本(整部全文快照)及其日落条款,走立法投票。
         #[pallet::call_index(1)]
         #[pallet::weight(<T as Config>::WeightInfo::propose_amend_law())]
         pub fn propose_amend_law(
@@ -729,51 +1006,20 @@ pub mod pallet {
             title_en: Option<TitleOf<T>>,
             chapters: ChaptersOf<T>,
             effective_at: u64,
+            sunset: LawSunset,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            ensure!(!title.is_empty(), Error::<T>::EmptyTitle);
-            ensure!(!chapters.is_empty(), Error::<T>::EmptyChapters);
-            let law = Laws::<T>::get(law_id).ok_or(Error::<T>::LawNotFound)?;
-            ensure!(
-                law.status != LawStatus::Repealed,
-                Error::<T>::LawAlreadyRepealed
-            );
-            // 至多一个待生效版本:有未生效版本时不得再修,避免新版本互相覆盖。
-            ensure!(
-                law.pending_version.is_none(),
-                Error::<T>::AmendmentAlreadyPending
-            );
-            Self::ensure_legislator(
-                &actor_cid_number,
-                &proposer_role_code,
-                &who,
-                entity_primitives::business_action::ACTION_AMEND_LAW,
-            )?;
-            Self::ensure_tier_vote_type(law.tier, vote_type)?;
-            Self::ensure_routing(
-                law.tier,
-                law.scope_code,
+            let law = Self::ensure_amend_proposal(
+                Some((&who, &proposer_role_code)),
+                law_id,
                 &actor_cid_number,
-                &law.houses,
                 &executive_cid_number,
-                vote_type,
                 &legislature_cid_number,
+                vote_type,
+                &title,
+                &chapters,
             )?;
-            if law.tier == Tier::Constitution {
-                // 宪法目录身份先按章/节/条三层唯一性校验，避免后续按条号查找时
-                // `.find()` 或集合去重掩盖重复条文。
-                Self::ensure_unique_constitution_numbers(&chapters)?;
-                let effective_version = law
-                    .effective_version
-                    .ok_or(Error::<T>::LawVersionNotFound)?;
-                // 第十九条章→档位强制 + 不可修改条款冻结(提案入口)。
-                Self::ensure_constitution_amend_ok(
-                    law_id,
-                    effective_version,
-                    vote_type,
-                    &chapters,
-                )?;
-            }
+            Self::ensure_valid_sunset(law.tier, &sunset, effective_at.max(Self::now_ms()))?;
 
             let summary = LawProposalSummary::<T> {
                 action: LawAction::Amend,
@@ -790,9 +1036,15 @@ pub mod pallet {
                 title_en,
                 content_hash: Self::hash_chapters(&chapters),
                 effective_at,
+                sunset: Some(sunset),
             };
-            let proposal_id =
-                Self::dispatch_to_engine(&who, &law.houses, vote_type, &summary, &chapters)?;
+            let proposal_id = Self::dispatch_to_engine(
+                Some(&who),
+                &law.houses,
+                vote_type,
+                &summary,
+                chapters.encode(),
+            )?;
             Self::deposit_event(Event::<T>::LawProposalCreated {
                 proposal_id,
                 action: LawAction::Amend,
@@ -856,10 +1108,16 @@ pub mod pallet {
                 title_en: None,
                 content_hash: [0u8; 32],
                 effective_at: Default::default(),
+                sunset: None,
             };
             let empty: ChaptersOf<T> = Default::default();
-            let proposal_id =
-                Self::dispatch_to_engine(&who, &law.houses, vote_type, &summary, &empty)?;
+            let proposal_id = Self::dispatch_to_engine(
+                Some(&who),
+                &law.houses,
+                vote_type,
+                &summary,
+                empty.encode(),
+            )?;
             Self::deposit_event(Event::<T>::LawProposalCreated {
                 proposal_id,
                 action: LawAction::Repeal,
@@ -868,6 +1126,158 @@ pub mod pallet {
             });
             Ok(())
         }
+
+        /// 条文级修法:只提交相对当前生效版本的章/节/条/款增删改补丁,走立法投票。
+        ///
+        /// 提案时即套用补丁得到新全文并复用整部修法的全部校验;投票载荷只存补丁,
+        /// 通过后重新对基准版本套用,结果哈希须与提案时一致才写入新版本。
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::propose_amend_law_patch())]
+        pub fn propose_amend_law_patch(
+            origin: OriginFor<T>,
+            law_id: u64,
+            actor_cid_number: votingengine::types::CidNumber,
+            proposer_role_code: votingengine::types::RoleCode,
+            executive_cid_number: votingengine::types::CidNumber,
+            legislature_cid_number: Option<votingengine::types::CidNumber>,
+            vote_type: VoteType,
+            title: TitleOf<T>,
+            title_en: Option<TitleOf<T>>,
+            patch: LawAmendmentPatch<T>,
+            effective_at: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let chapters = Self::resolve_amendment_patch(law_id, &patch)?;
+            let law = Self::ensure_amend_proposal(
+                Some((&who, &proposer_role_code)),
+                law_id,
+                &actor_cid_number,
+                &executive_cid_number,
+                &legislature_cid_number,
+                vote_type,
+                &title,
+                &chapters,
+            )?;
+
+            let summary = LawProposalSummary::<T> {
+                action: LawAction::AmendPatch,
+                law_id,
+                tier: law.tier,
+                scope_code: law.scope_code,
+                houses: law.houses.clone(),
+                actor_cid_number,
+                proposer_role_code,
+                executive_cid_number,
+                legislature_cid_number,
+                vote_type,
+                title,
+                title_en,
+                content_hash: Self::hash_chapters(&chapters),
+                effective_at,
+                sunset: None,
+            };
+            let proposal_id = Self::dispatch_to_engine(
+                Some(&who),
+                &law.houses,
+                vote_type,
+                &summary,
+                patch.encode(),
+            )?;
+            Self::deposit_event(Event::<T>::LawProposalCreated {
+                proposal_id,
+                action: LawAction::AmendPatch,
+                law_id: Some(law_id),
+                proposer_account_id: who,
+            });
+            Ok(())
+        }
+
+        /// 公民创制:作用域内有效投票公民发起联署,提交法律全文(链上记录其哈希)。
+        ///
+        /// 路由、层级表决类型与宪法修改约束和议员提案相同,发起机构固定为发起院;
+        /// 发起人计为第一个联署人。
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::open_petition(chapters.encoded_size() as u32))]
+        pub fn open_petition(
+            origin: OriginFor<T>,
+            target: PetitionTarget,
+            executive_cid_number: votingengine::types::CidNumber,
+            legislature_cid_number: Option<votingengine::types::CidNumber>,
+            vote_type: VoteType,
+            title: TitleOf<T>,
+            title_en: Option<TitleOf<T>>,
+            chapters: ChaptersOf<T>,
+            effective_at: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_open_petition(
+                who,
+                target,
+                executive_cid_number,
+                legislature_cid_number,
+                vote_type,
+                title,
+                title_en,
+                chapters,
+                effective_at,
+            )
+        }
+
+        /// 联署:每个公民 CID 限一次,须确认全文哈希;达门槛即自动提交立法投票。
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::sign_petition())]
+        pub fn sign_petition(
+            origin: OriginFor<T>,
+            petition_id: u64,
+            content_hash: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_sign_petition(who, petition_id, content_hash)
+        }
+
+        /// 重试已达门槛但建案失败的联署,任何签名账户均可触发。
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::file_qualified_petition())]
+        pub fn file_qualified_petition(origin: OriginFor<T>, petition_id: u64) -> DispatchResult {
+            ensure_signed(origin)?;
+            Self::do_file_qualified_petition(petition_id)
+        }
+
+        /// 分批清理已建案或已过征集期的联署记录,清完后删除联署本身。
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::clear_petition(T::MaxPetitionCleanup::get()))]
+        pub fn clear_petition(origin: OriginFor<T>, petition_id: u64) -> DispatchResult {
+            ensure_signed(origin)?;
+            Self::do_clear_petition(petition_id)
+        }
+
+        /// 续期:为当前生效版本重新设定失效与复审期限(两项皆空即取消日落条款),走立法投票。
+        ///
+        /// 权限与路由同修法;不改条文、不产生新版本,只能在法律生效期间提出。
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::propose_renew_law())]
+        pub fn propose_renew_law(
+            origin: OriginFor<T>,
+            law_id: u64,
+            actor_cid_number: votingengine::types::CidNumber,
+            proposer_role_code: votingengine::types::RoleCode,
+            executive_cid_number: votingengine::types::CidNumber,
+            legislature_cid_number: Option<votingengine::types::CidNumber>,
+            vote_type: VoteType,
+            sunset: LawSunset,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_propose_renew_law(
+                who,
+                law_id,
+                actor_cid_number,
+                proposer_role_code,
+                executive_cid_number,
+                legislature_cid_number,
+                vote_type,
+                sunset,
+            )
+        }
     }
 
     // ──────────────── 内部 helper:校验 / 编排 / 执行器 / 查询 ────────────────
@@ -875,7 +1285,7 @@ pub mod pallet {
         /// 校验发起人是提案机构(proposer_body)的现任管理员(议员/委员)。
         /// ADR-027 修订:提案方与表决院解耦——市行政区 市自治会/市教委会 委员可提案,
         /// 但表决院恒为 houses[0]=市立法会,故 auth 校验对 proposer_body 而非 houses[0]。
-        fn ensure_legislator(
+        pub(crate) fn ensure_legislator(
             actor_cid_number: &votingengine::types::CidNumber,
             proposer_role_code: &votingengine::types::RoleCode,
             who: &T::AccountId,
@@ -960,7 +1370,7 @@ pub mod pallet {
         /// 调用方仍传原有字段，但链端只接受固定的层级×表决类型路由；所有机构账户
         /// 必须由 entity 真源解析为预期机构码。省、市路由还必须共享同一 CID R5，
         /// 防止把不同省市的院、政府或提案机构拼进同一法律案。
-        fn ensure_routing(
+        pub(crate) fn ensure_routing(
             tier: Tier,
             scope_code: u32,
             actor_cid_number: &votingengine::types::CidNumber,
@@ -1040,8 +1450,64 @@ pub mod pallet {
             Ok(())
         }
 
+        /// 修法提案入口共用校验(整部全文与条文补丁两种形式),返回目标法律。
+        ///
+        /// `proposer` 为 `(发起账户, 岗位码)`;公民创制案传 None,不查议员岗位权限。
+        pub(crate) fn ensure_amend_proposal(
+            proposer: Option<(&T::AccountId, &votingengine::types::RoleCode)>,
+            law_id: u64,
+            actor_cid_number: &votingengine::types::CidNumber,
+            executive_cid_number: &votingengine::types::CidNumber,
+            legislature_cid_number: &Option<votingengine::types::CidNumber>,
+            vote_type: VoteType,
+            title: &TitleOf<T>,
+            chapters: &ChaptersOf<T>,
+        ) -> Result<Law, DispatchError> {
+            ensure!(!title.is_empty(), Error::<T>::EmptyTitle);
+            ensure!(!chapters.is_empty(), Error::<T>::EmptyChapters);
+            let law = Laws::<T>::get(law_id).ok_or(Error::<T>::LawNotFound)?;
+            ensure!(
+                law.status != LawStatus::Repealed,
+                Error::<T>::LawAlreadyRepealed
+            );
+            // 至多一个待生效版本:有未生效版本时不得再修,避免新版本互相覆盖。
+            ensure!(
+                law.pending_version.is_none(),
+                Error::<T>::AmendmentAlreadyPending
+            );
+            if let Some((who, proposer_role_code)) = proposer {
+                Self::ensure_legislator(
+                    actor_cid_number,
+                    proposer_role_code,
+                    who,
+                    entity_primitives::business_action::ACTION_AMEND_LAW,
+                )?;
+            }
+            Self::ensure_tier_vote_type(law.tier, vote_type)?;
+            Self::ensure_routing(
+                law.tier,
+                law.scope_code,
+                actor_cid_number,
+                &law.houses,
+                executive_cid_number,
+                vote_type,
+                legislature_cid_number,
+            )?;
+            if law.tier == Tier::Constitution {
+                // 宪法目录身份先按章/节/条三层唯一性校验，避免后续按条号查找时
+                // `.find()` 或集合去重掩盖重复条文。
+                Self::ensure_unique_constitution_numbers(chapters)?;
+                let effective_version = law
+                    .effective_version
+                    .ok_or(Error::<T>::LawVersionNotFound)?;
+                // 第十九条章→档位强制 + 不可修改条款冻结(提案入口)。
+                Self::ensure_constitution_amend_ok(law_id, effective_version, vote_type, chapters)?;
+            }
+            Ok(law)
+        }
+
         /// 宪法修改只能走特别案或重要案(宪法第十九条);教育变体不适用于宪法。
-        fn ensure_tier_vote_type(tier: Tier, vt: VoteType) -> DispatchResult {
+        pub(crate) fn ensure_tier_vote_type(tier: Tier, vt: VoteType) -> DispatchResult {
             if tier == Tier::Constitution {
                 ensure!(
                     matches!(vt, VoteType::Special | VoteType::Major),
@@ -1344,11 +1810,15 @@ pub mod pallet {
             sp_io::hashing::blake2_256(&chapters.encode())
         }
 
-        fn action_code(action: LawAction) -> u32 {
+        pub(crate) fn action_code(action: LawAction) -> u32 {
             match action {
                 LawAction::Enact => entity_primitives::business_action::ACTION_ENACT_LAW,
-                LawAction::Amend => entity_primitives::business_action::ACTION_AMEND_LAW,
+                LawAction::Amend | LawAction::AmendPatch => {
+                    entity_primitives::business_action::ACTION_AMEND_LAW
+                }
                 LawAction::Repeal => entity_primitives::business_action::ACTION_REPEAL_LAW,
+                // 续期只改日落条款,与修法同一权限。
+                LawAction::Renew => entity_primitives::business_action::ACTION_AMEND_LAW,
             }
         }
 
@@ -1377,7 +1847,7 @@ pub mod pallet {
 
         /// 每个代表机构阶段必须解析为一个非 LR 表决岗位。岗位码来自 entity 权限真源，
         /// 不是客户端或投票引擎硬编码；LR 的同动作 Vote 仅用于后续签署阶段。
-        fn representative_vote_subject(
+        pub(crate) fn representative_vote_subject(
             cid_number: &votingengine::types::CidNumber,
             action_code: u32,
         ) -> Result<RepresentativeBody, DispatchError> {
@@ -1418,17 +1888,18 @@ pub mod pallet {
         }
 
         /// 编码载荷并调立法投票引擎建提案,返回真实提案 ID。
+        /// `object` 为法律全文或条文补丁的 SCALE 字节,按 `summary.action` 解读。
         /// 代表机构序列由提案携带；单机构与顺序多机构路由均由立法投票引擎执行。
-        fn dispatch_to_engine(
-            who: &T::AccountId,
+        /// `who = None` 为公民创制案:联署达门槛后以发起院代表岗位为提案主体建案。
+        pub(crate) fn dispatch_to_engine(
+            who: Option<&T::AccountId>,
             houses: &Houses,
             vote_type: VoteType,
             summary: &LawProposalSummary<T>,
-            chapters: &ChaptersOf<T>,
+            object: Vec<u8>,
         ) -> Result<u64, DispatchError> {
             let mut data = sp_runtime::sp_std::vec::Vec::from(MODULE_TAG);
             data.extend_from_slice(&summary.encode());
-            let object = chapters.encode();
             let action_code = Self::action_code(summary.action);
             // 修宪(tier=宪法)走护宪大法官终审(宪法第21条)。
             let needs_guard = summary.tier == Tier::Constitution;
@@ -1536,23 +2007,35 @@ pub mod pallet {
                 sp_io::hashing::blake2_256(&(data.clone(), object.clone()).encode()),
             )
             .map_err(|_| Error::<T>::VoteEngineCreateFailed)?;
-            let proposal_id = T::LegislationVoteEngine::create_legislation_vote(
-                who.clone(),
-                summary.actor_cid_number.clone(),
-                vote_plan,
-                route,
-                vote_type.representative_rule(),
-                procedure,
-                MODULE_TAG,
-                data,
-                object,
-            )
+            let proposal_id = match who {
+                Some(who) => T::LegislationVoteEngine::create_legislation_vote(
+                    who.clone(),
+                    summary.actor_cid_number.clone(),
+                    vote_plan,
+                    route,
+                    vote_type.representative_rule(),
+                    procedure,
+                    MODULE_TAG,
+                    data,
+                    object,
+                ),
+                None => T::LegislationVoteEngine::create_initiative_legislation_vote(
+                    summary.actor_cid_number.clone(),
+                    vote_plan,
+                    route,
+                    vote_type.representative_rule(),
+                    procedure,
+                    MODULE_TAG,
+                    data,
+                    object,
+                ),
+            }
             .map_err(|_| Error::<T>::VoteEngineCreateFailed)?;
             Ok(proposal_id)
         }
 
         /// 当前链上时间戳(毫秒)。法律生效时间统一使用时间戳,不再暴露区块号给业务端。
-        fn now_ms() -> u64 {
+        pub(crate) fn now_ms() -> u64 {
             pallet_timestamp::Pallet::<T>::now()
         }
 
@@ -1564,10 +2047,13 @@ pub mod pallet {
                         law.effective_version = Some(version);
                         law.pending_version = None;
                         law.status = LawStatus::Effective;
+                        LawReviewsDue::<T>::remove(law_id);
                         Self::deposit_event(Event::<T>::LawEffective { law_id, version });
                     }
                 }
             });
+            // 待生效期间到点的条目已被丢弃,生效时按版本条款重新排程(已过的期限下个扫描即处理)。
+            Self::reschedule_sunset(law_id, version);
         }
 
         /// 到时间即生效,否则排入待生效队列。
@@ -1586,6 +2072,7 @@ pub mod pallet {
         fn ensure_write_law_version_allowed(
             summary: &LawProposalSummary<T>,
             chapters: &ChaptersOf<T>,
+            now: u64,
         ) -> DispatchResult {
             match summary.action {
                 LawAction::Enact => {
@@ -1607,7 +2094,7 @@ pub mod pallet {
                         &summary.legislature_cid_number,
                     )?;
                 }
-                LawAction::Amend => {
+                LawAction::Amend | LawAction::AmendPatch => {
                     ensure!(!summary.title.is_empty(), Error::<T>::EmptyTitle);
                     ensure!(!chapters.is_empty(), Error::<T>::EmptyChapters);
                     let law = Laws::<T>::get(summary.law_id).ok_or(Error::<T>::LawNotFound)?;
@@ -1677,6 +2164,12 @@ pub mod pallet {
                         &summary.legislature_cid_number,
                     )?;
                 }
+                // 续期案不产生新版本,经 `write_law_sunset` 写入。
+                LawAction::Renew => return Err(Error::<T>::ProposalPayloadInvalid.into()),
+            }
+            // 表决期间时间已推进:显式日落条款须仍晚于生效时刻与写入时刻。
+            if let Some(sunset) = &summary.sunset {
+                Self::ensure_valid_sunset(summary.tier, sunset, summary.effective_at.max(now))?;
             }
             Ok(())
         }
@@ -1694,8 +2187,13 @@ pub mod pallet {
                 return Ok(ProposalExecutionOutcome::Executed);
             }
             let summary = Self::load_summary(proposal_id)?;
-            let chapters = Self::load_chapters(proposal_id)?;
             let now = Self::now_ms();
+            if summary.action == LawAction::Renew {
+                let sunset = Self::load_sunset(proposal_id)?;
+                Self::write_law_sunset(summary, sunset, now)?;
+                return Ok(ProposalExecutionOutcome::Executed);
+            }
+            let chapters = Self::load_chapters(proposal_id, &summary)?;
             Self::write_law_version(proposal_id, summary, chapters, now)?;
             Ok(ProposalExecutionOutcome::Executed)
         }
@@ -1712,12 +2210,28 @@ pub mod pallet {
                 .map_err(|_| Error::<T>::ProposalPayloadInvalid.into())
         }
 
-        /// 从 votingengine ProposalObject 读回并解码法律全文(章>节>条>款)。
-        fn load_chapters(proposal_id: u64) -> Result<ChaptersOf<T>, DispatchError> {
+        /// 从 votingengine ProposalObject 读回法律全文(章>节>条>款)。
+        ///
+        /// 条文补丁提案存的是补丁:重新对当前生效版本套用,结果须与提案时的 `content_hash`
+        /// 一致;表决期间生效版本被其他修法替换时补丁语义已失效,拒绝写入。
+        fn load_chapters(
+            proposal_id: u64,
+            summary: &LawProposalSummary<T>,
+        ) -> Result<ChaptersOf<T>, DispatchError> {
             let raw = votingengine::Pallet::<T>::get_proposal_object(proposal_id)
                 .ok_or(Error::<T>::ProposalPayloadInvalid)?;
-            ChaptersOf::<T>::decode(&mut &raw[..])
-                .map_err(|_| Error::<T>::ProposalPayloadInvalid.into())
+            if summary.action != LawAction::AmendPatch {
+                return ChaptersOf::<T>::decode(&mut &raw[..])
+                    .map_err(|_| Error::<T>::ProposalPayloadInvalid.into());
+            }
+            let patch = LawAmendmentPatch::<T>::decode(&mut &raw[..])
+                .map_err(|_| Error::<T>::ProposalPayloadInvalid)?;
+            let chapters = Self::resolve_amendment_patch(summary.law_id, &patch)?;
+            ensure!(
+                Self::hash_chapters(&chapters) == summary.content_hash,
+                Error::<T>::AmendmentBaseMismatch
+            );
+            Ok(chapters)
         }
 
         /// 把通过的提案写入法律存储(立法新增 / 修法升版 / 废法置废)。
@@ -1727,7 +2241,7 @@ pub mod pallet {
             chapters: ChaptersOf<T>,
             now: u64,
         ) -> DispatchResult {
-            Self::ensure_write_law_version_allowed(&summary, &chapters)?;
+            Self::ensure_write_law_version_allowed(&summary, &chapters, now)?;
             match summary.action {
                 LawAction::Enact => {
                     let law_id = NextLawId::<T>::mutate(|n| {
@@ -1764,10 +2278,13 @@ pub mod pallet {
                         v.try_push(law_id)
                     })
                     .map_err(|_| Error::<T>::TooManyLawsInScope)?;
+                    if let Some(sunset) = summary.sunset {
+                        Self::set_version_sunset(law_id, version, sunset);
+                    }
                     Self::deposit_event(Event::<T>::LawEnacted { law_id, version });
                     Self::activate_or_schedule(law_id, version, summary.effective_at)?;
                 }
-                LawAction::Amend => {
+                LawAction::Amend | LawAction::AmendPatch => {
                     let mut law = Laws::<T>::get(summary.law_id).ok_or(Error::<T>::LawNotFound)?;
                     let version = law.latest_version.saturating_add(1);
                     // 核心修宪(第一章总则核心条款)落永久公投凭据 —— 须在 chapters 被移动前算。
@@ -1794,6 +2311,15 @@ pub mod pallet {
                         effective_at: summary.effective_at,
                     };
                     LawVersions::<T>::insert(summary.law_id, version, lv);
+                    match summary.sunset {
+                        Some(sunset) => Self::set_version_sunset(summary.law_id, version, sunset),
+                        None => Self::inherit_sunset(
+                            summary.law_id,
+                            law.effective_version,
+                            version,
+                            now,
+                        ),
+                    }
                     law.latest_version = version;
                     law.pending_version = Some(version);
                     law.status = LawStatus::Pending;
@@ -1810,10 +2336,12 @@ pub mod pallet {
                             law.status = LawStatus::Repealed;
                         }
                     });
+                    LawReviewsDue::<T>::remove(summary.law_id);
                     Self::deposit_event(Event::<T>::LawRepealed {
                         law_id: summary.law_id,
                     });
                 }
+                LawAction::Renew => return Err(Error::<T>::ProposalPayloadInvalid.into()),
             }
             Ok(())
         }
@@ -1834,10 +2362,26 @@ pub mod pallet {
             LawVersionLabels::<T>::get(law_id, version)
         }
 
+        /// 读取法律版本的日落条款。
+        pub fn law_sunset(law_id: u64, version: u32) -> Option<LawSunset> {
+            LawVersionSunsets::<T>::get(law_id, version)
+        }
+
         /// 列出某层级 + 行政区下的法律 ID。
         pub fn list_laws(tier: Tier, scope_code: u32) -> sp_runtime::sp_std::vec::Vec<u64> {
             LawsByScope::<T>::get(tier, scope_code).into_inner()
         }
+
+        /// 同一法律两个版本间的逐款差异;任一版本不存在时返回 None。
+        pub fn law_diff(
+            law_id: u64,
+            from_version: u32,
+            to_version: u32,
+        ) -> Option<sp_runtime::sp_std::vec::Vec<LawDiffEntry>> {
+            let from = LawVersions::<T>::get(law_id, from_version)?;
+            let to = LawVersions::<T>::get(law_id, to_version)?;
+            Some(Self::diff_chapters(&from.chapters, &to.chapters))
+        }
     }
 }
 
@@ -0,0 +1,339 @@
+//! 公民创制联署:有效投票公民发起法律案,作用域内公民按永久 CID 联署,
+//! 达到作用域有效投票人口的门槛比例后自动提交立法投票。
+//!
+//! 联署只替代「议员提案」这一步:建案后的代表表决、行政签署、特别案公投与护宪终审
+//! 全部沿用立法投票引擎的既有程序,通过后同样经 `apply_legislation_vote_result` 写入法律。
+
+use codec::Encode;
+use frame_support::{
+    ensure,
+    pallet_prelude::{DispatchError, DispatchResult},
+    traits::Get,
+};
+use sp_runtime::{traits::Saturating, Permill};
+
+use crate::pallet::{
+    ChaptersOf, Config, Error, Event, LawProposalSummary, Laws, NextPetitionId,
+    OpenPetitionsByCitizen, Pallet, Petition, PetitionSignatures, PetitionTarget, PetitionTexts,
+    Petitions, TitleOf,
+};
+use crate::types::{LawAction, PetitionStatus, Tier, VoteType};
+use votingengine::{CitizenIdentityReader, PopulationScope};
+
+impl<T: Config> Pallet<T> {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn do_open_petition(
+        who: T::AccountId,
+        target: PetitionTarget,
+        executive_cid_number: votingengine::types::CidNumber,
+        legislature_cid_number: Option<votingengine::types::CidNumber>,
+        vote_type: VoteType,
+        title: TitleOf<T>,
+        title_en: Option<TitleOf<T>>,
+        chapters: ChaptersOf<T>,
+        effective_at: u64,
+    ) -> DispatchResult {
+        let (action, law_id, tier, scope_code, houses) = match target {
+            PetitionTarget::Enact {
+                tier,
+                scope_code,
+                houses,
+            } => (LawAction::Enact, 0, tier, scope_code, houses),
+            PetitionTarget::Amend { law_id } => {
+                let law = Laws::<T>::get(law_id).ok_or(Error::<T>::LawNotFound)?;
+                (
+                    LawAction::Amend,
+                    law_id,
+                    law.tier,
+                    law.scope_code,
+                    law.houses,
+                )
+            }
+        };
+        let actor_cid_number = houses.first().cloned().ok_or(Error::<T>::EmptyHouses)?;
+        let summary = LawProposalSummary::<T> {
+            action,
+            law_id,
+            tier,
+            scope_code,
+            houses,
+            actor_cid_number,
+            proposer_role_code: Default::default(),
+            executive_cid_number,
+            legislature_cid_number,
+            vote_type,
+            title,
+            title_en,
+            content_hash: Self::hash_chapters(&chapters),
+            effective_at,
+            sunset: None,
+        };
+        let summary = Self::validate_petition_bill(summary, &chapters)?;
+
+        let population_scope = Self::petition_population_scope(&summary)?;
+        let eligible_total = T::CitizenIdentityReader::population_data(&population_scope)
+            .map(|data| data.eligible_total)
+            .filter(|total| *total > 0)
+            .ok_or(Error::<T>::PetitionPopulationUnavailable)?;
+        let required_signatures =
+            Self::petition_required_signatures(T::PetitionThreshold::get(), eligible_total);
+        let cid_number = Self::petition_signer_cid(&who, &population_scope)?;
+        OpenPetitionsByCitizen::<T>::try_mutate(&cid_number, |open| {
+            ensure!(
+                *open < T::MaxOpenPetitionsPerCitizen::get(),
+                Error::<T>::TooManyOpenPetitions
+            );
+            *open = open.saturating_add(1);
+            Ok::<_, DispatchError>(())
+        })?;
+
+        let petition_id = NextPetitionId::<T>::mutate(|n| {
+            let id = *n;
+            *n = n.saturating_add(1);
+            id
+        });
+        let content_hash = summary.content_hash;
+        let now = frame_system::Pallet::<T>::block_number();
+        let mut petition = Petition::<T> {
+            initiator: who.clone(),
+            initiator_cid_number: cid_number.clone(),
+            summary,
+            population_scope,
+            required_signatures,
+            signatures: 1,
+            expires_at: now.saturating_add(T::PetitionDuration::get()),
+            status: PetitionStatus::Collecting,
+            proposal_id: None,
+        };
+        PetitionSignatures::<T>::insert(petition_id, cid_number, who.clone());
+        PetitionTexts::<T>::insert(petition_id, chapters);
+        Self::deposit_event(Event::<T>::PetitionOpened {
+            petition_id,
+            initiator: who,
+            content_hash,
+            required_signatures,
+        });
+        Self::try_file_reached_petition(petition_id, &mut petition);
+        Petitions::<T>::insert(petition_id, petition);
+        Ok(())
+    }
+
+    pub(crate) fn do_sign_petition(
+        who: T::AccountId,
+        petition_id: u64,
+        content_hash: [u8; 32],
+    ) -> DispatchResult {
+        let mut petition = Petitions::<T>::get(petition_id).ok_or(Error::<T>::PetitionNotFound)?;
+        ensure!(
+            petition.status == PetitionStatus::Collecting,
+            Error::<T>::PetitionNotCollecting
+        );
+        ensure!(
+            frame_system::Pallet::<T>::block_number() <= petition.expires_at,
+            Error::<T>::PetitionExpired
+        );
+        ensure!(
+            petition.summary.content_hash == content_hash,
+            Error::<T>::PetitionContentMismatch
+        );
+        let cid_number = Self::petition_signer_cid(&who, &petition.population_scope)?;
+        ensure!(
+            !PetitionSignatures::<T>::contains_key(petition_id, &cid_number),
+            Error::<T>::AlreadySignedPetition
+        );
+        PetitionSignatures::<T>::insert(petition_id, cid_number, who.clone());
+        petition.signatures = petition.signatures.saturating_add(1);
+        Self::deposit_event(Event::<T>::PetitionSigned {
+            petition_id,
+            signer: who,
+            signatures: petition.signatures,
+        });
+        Self::try_file_reached_petition(petition_id, &mut petition);
+        Petitions::<T>::insert(petition_id, petition);
+        Ok(())
+    }
+
+    pub(crate) fn do_file_qualified_petition(petition_id: u64) -> DispatchResult {
+        let mut petition = Petitions::<T>::get(petition_id).ok_or(Error::<T>::PetitionNotFound)?;
+        ensure!(
+            petition.status == PetitionStatus::Qualified,
+            Error::<T>::PetitionNotQualified
+        );
+        ensure!(
+            frame_system::Pallet::<T>::block_number() <= petition.expires_at,
+            Error::<T>::PetitionExpired
+        );
+        let proposal_id = Self::file_petition(petition_id, &mut petition)?;
+        Petitions::<T>::insert(petition_id, petition);
+        Self::deposit_event(Event::<T>::PetitionFiled {
+            petition_id,
+            proposal_id,
+        });
+        Ok(())
+    }
+
+    /// 已建案,或征集期已过仍未建案的联署可清理;每次最多删 `MaxPetitionCleanup` 条联署记录。
+    pub(crate) fn do_clear_petition(petition_id: u64) -> DispatchResult {
+        let petition = Petitions::<T>::get(petition_id).ok_or(Error::<T>::PetitionNotFound)?;
+        ensure!(
+            petition.status == PetitionStatus::Filed
+                || frame_system::Pallet::<T>::block_number() > petition.expires_at,
+            Error::<T>::PetitionStillActive
+        );
+        Self::release_petition_text(petition_id, &petition);
+        let result =
+            PetitionSignatures::<T>::clear_prefix(petition_id, T::MaxPetitionCleanup::get(), None);
+        if result.maybe_cursor.is_none() {
+            Petitions::<T>::remove(petition_id);
+            Self::deposit_event(Event::<T>::PetitionCleared { petition_id });
+        }
+        Ok(())
+    }
+
+    /// 删除联署全文并归还发起人的未建案联署名额;全文已删时不重复归还。
+    fn release_petition_text(petition_id: u64, petition: &Petition<T>) {
+        if PetitionTexts::<T>::take(petition_id).is_none() {
+            return;
+        }
+        OpenPetitionsByCitizen::<T>::mutate_exists(&petition.initiator_cid_number, |open| {
+            *open = open.map(|n| n.saturating_sub(1)).filter(|n| *n > 0);
+        });
+    }
+
+    /// 达门槛即建案;建案失败不回滚联署本身,只把联署置为可重试状态。
+    fn try_file_reached_petition(petition_id: u64, petition: &mut Petition<T>) {
+        if petition.signatures < petition.required_signatures {
+            return;
+        }
+        match Self::file_petition(petition_id, petition) {
+            Ok(proposal_id) => Self::deposit_event(Event::<T>::PetitionFiled {
+                petition_id,
+                proposal_id,
+            }),
+            Err(error) => {
+                petition.status = PetitionStatus::Qualified;
+                Self::deposit_event(Event::<T>::PetitionFilingFailed { petition_id, error });
+            }
+        }
+    }
+
+    /// 以发起院为提案机构提交立法投票;建案前按当前链上状态重新校验整条法律案。
+    pub(crate) fn file_petition(
+        petition_id: u64,
+        petition: &mut Petition<T>,
+    ) -> Result<u64, DispatchError> {
+        let chapters = PetitionTexts::<T>::get(petition_id).ok_or(Error::<T>::PetitionNotFound)?;
+        let (summary, proposal_id) = frame_support::storage::with_storage_layer(|| {
+            let summary = Self::validate_petition_bill(petition.summary.clone(), &chapters)?;
+            let proposal_id = Self::dispatch_to_engine(
+                None,
+                &summary.houses,
+                summary.vote_type,
+                &summary,
+                chapters.encode(),
+            )?;
+            Ok::<_, DispatchError>((summary, proposal_id))
+        })?;
+        Self::release_petition_text(petition_id, petition);
+        petition.summary = summary;
+        petition.status = PetitionStatus::Filed;
+        petition.proposal_id = Some(proposal_id);
+        Self::deposit_event(Event::<T>::LawProposalCreated {
+            proposal_id,
+            action: petition.summary.action,
+            law_id: (petition.summary.action == LawAction::Amend)
+                .then_some(petition.summary.law_id),
+            proposer_account_id: petition.initiator.clone(),
+        });
+        Ok(proposal_id)
+    }
+
+    /// 与议员提案相同的法律案校验(不含议员岗位权限),并把提案岗位解析为发起院代表岗位。
+    fn validate_petition_bill(
+        mut summary: LawProposalSummary<T>,
+        chapters: &ChaptersOf<T>,
+    ) -> Result<LawProposalSummary<T>, DispatchError> {
+        match summary.action {
+            LawAction::Enact => {
+                ensure!(
+                    summary.tier != Tier::Constitution,
+                    Error::<T>::CannotEnactConstitution
+                );
+                ensure!(!summary.title.is_empty(), Error::<T>::EmptyTitle);
+                ensure!(!chapters.is_empty(), Error::<T>::EmptyChapters);
+                Self::ensure_tier_vote_type(summary.tier, summary.vote_type)?;
+                Self::ensure_routing(
+                    summary.tier,
+                    summary.scope_code,
+                    &summary.actor_cid_number,
+                    &summary.houses,
+                    &summary.executive_cid_number,
+                    summary.vote_type,
+                    &summary.legislature_cid_number,
+                )?;
+            }
+            LawAction::Amend => {
+                let law = Self::ensure_amend_proposal(
+                    None,
+                    summary.law_id,
+                    &summary.actor_cid_number,
+                    &summary.executive_cid_number,
+                    &summary.legislature_cid_number,
+                    summary.vote_type,
+                    &summary.title,
+                    chapters,
+                )?;
+                ensure!(law.houses == summary.houses, Error::<T>::RoutingMismatch);
+            }
+            LawAction::Repeal | LawAction::AmendPatch | LawAction::Renew => {
+                return Err(Error::<T>::ProposalPayloadInvalid.into())
+            }
+        }
+        let action_code = Self::action_code(summary.action);
+        summary.proposer_role_code =
+            Self::representative_vote_subject(&summary.actor_cid_number, action_code)?.role_code;
+        Ok(summary)
+    }
+
+    /// 国家/宪法层级为全国;省、市层级按发起院 CID 的行政区段确定。
+    fn petition_population_scope(
+        summary: &LawProposalSummary<T>,
+    ) -> Result<PopulationScope, DispatchError> {
+        if matches!(summary.tier, Tier::Constitution | Tier::National) {
+            return Ok(PopulationScope::Country);
+        }
+        let (province_code, city_code) =
+            primitives::cid::number::cid_scope_codes(summary.actor_cid_number.as_slice())
+                .map_err(|_| Error::<T>::RoutingMismatch)?;
+        let province_code = province_code
+            .to_vec()
+            .try_into()
+            .map_err(|_| Error::<T>::RoutingMismatch)?;
+        if summary.tier == Tier::Provincial {
+            return Ok(PopulationScope::Province(province_code));
+        }
+        let city_code = city_code
+            .to_vec()
+            .try_into()
+            .map_err(|_| Error::<T>::RoutingMismatch)?;
+        Ok(PopulationScope::City(province_code, city_code))
+    }
+
+    /// 门槛 = ceil(比例 × 有效投票人口),至少 1 人。
+    pub(crate) fn petition_required_signatures(threshold: Permill, eligible_total: u64) -> u64 {
+        threshold.mul_ceil(eligible_total).max(1)
+    }
+
+    fn petition_signer_cid(
+        who: &T::AccountId,
+        scope: &PopulationScope,
+    ) -> Result<votingengine::types::CidNumber, DispatchError> {
+        let subject = T::CitizenIdentityReader::voting_subject(who, scope)
+            .ok_or(Error::<T>::NotPetitionVoter)?;
+        subject
+            .cid_number
+            .to_vec()
+            .try_into()
+            .map_err(|_| Error::<T>::NotPetitionVoter.into())
+    }
+}
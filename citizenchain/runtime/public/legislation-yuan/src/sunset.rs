@@ -0,0 +1,290 @@
+//! 法律日落条款:版本级失效/复审期限、按时间戳的有界排程与续期案。
+//!
+//! 日落条款挂在具体版本上(`LawVersionSunsets`),由立法/修法提案显式给出,条文补丁与
+//! 创制修法沿用旧版本未到期的条款。`SunsetSchedule` 以大端到期时间戳为第一键,
+//! `on_initialize` 按到期顺序每块处理有限条;条目与该版本当前日落条款不一致(已续期)或
+//! 该版本已不是生效版本(已被修法取代)时直接丢弃,待生效版本在生效时重新排程。
+//! 续期案与废法案一样走立法投票,通过后只改日落条款,不产生新版本。
+
+use codec::{Decode, Encode};
+use frame_support::{
+    ensure,
+    pallet_prelude::{DispatchError, DispatchResult, Weight},
+    traits::Get,
+};
+
+use crate::pallet::{
+    Config, Error, Event, LawProposalSummary, LawReviewsDue, LawVersionSunsets, LawVersions, Laws,
+    Pallet, SunsetSchedule,
+};
+use crate::types::{LawAction, LawStatus, LawSunset, SunsetKind, Tier, VoteType};
+
+impl<T: Config> Pallet<T> {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn do_propose_renew_law(
+        who: T::AccountId,
+        law_id: u64,
+        actor_cid_number: votingengine::types::CidNumber,
+        proposer_role_code: votingengine::types::RoleCode,
+        executive_cid_number: votingengine::types::CidNumber,
+        legislature_cid_number: Option<votingengine::types::CidNumber>,
+        vote_type: VoteType,
+        sunset: LawSunset,
+    ) -> DispatchResult {
+        let law = Laws::<T>::get(law_id).ok_or(Error::<T>::LawNotFound)?;
+        ensure!(
+            law.tier != Tier::Constitution,
+            Error::<T>::CannotSunsetConstitution
+        );
+        ensure!(
+            law.status == LawStatus::Effective,
+            Error::<T>::LawNotEffective
+        );
+        let version = law
+            .effective_version
+            .ok_or(Error::<T>::LawVersionNotFound)?;
+        let current =
+            LawVersions::<T>::get(law_id, version).ok_or(Error::<T>::LawVersionNotFound)?;
+        Self::ensure_legislator(
+            &actor_cid_number,
+            &proposer_role_code,
+            &who,
+            entity_primitives::business_action::ACTION_AMEND_LAW,
+        )?;
+        Self::ensure_tier_vote_type(law.tier, vote_type)?;
+        Self::ensure_routing(
+            law.tier,
+            law.scope_code,
+            &actor_cid_number,
+            &law.houses,
+            &executive_cid_number,
+            vote_type,
+            &legislature_cid_number,
+        )?;
+        Self::ensure_valid_sunset(law.tier, &sunset, Self::now_ms())?;
+
+        // 续期案绑定提案时的生效版本全文哈希,表决期间版本被替换则写入时拒绝。
+        let summary = LawProposalSummary::<T> {
+            action: LawAction::Renew,
+            law_id,
+            tier: law.tier,
+            scope_code: law.scope_code,
+            houses: law.houses.clone(),
+            actor_cid_number,
+            proposer_role_code,
+            executive_cid_number,
+            legislature_cid_number,
+            vote_type,
+            title: current.title,
+            title_en: current.title_en,
+            content_hash: current.content_hash,
+            effective_at: Default::default(),
+            // 续期案的新条款在 `ProposalObject` 中,不走版本写入。
+            sunset: None,
+        };
+        let proposal_id = Self::dispatch_to_engine(
+            Some(&who),
+            &law.houses,
+            vote_type,
+            &summary,
+            sunset.encode(),
+        )?;
+        Self::deposit_event(Event::<T>::LawProposalCreated {
+            proposal_id,
+            action: LawAction::Renew,
+            law_id: Some(law_id),
+            proposer_account_id: who,
+        });
+        Ok(())
+    }
+
+    /// 期限必须晚于 `not_before`,复审不得晚于失效;宪法不设日落条款。
+    pub(crate) fn ensure_valid_sunset(
+        tier: Tier,
+        sunset: &LawSunset,
+        not_before: u64,
+    ) -> DispatchResult {
+        if sunset.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            tier != Tier::Constitution,
+            Error::<T>::CannotSunsetConstitution
+        );
+        ensure!(
+            sunset.expires_at.is_none_or(|at| at > not_before)
+                && sunset.review_by.is_none_or(|at| at > not_before),
+            Error::<T>::InvalidSunset
+        );
+        if let (Some(expires_at), Some(review_by)) = (sunset.expires_at, sunset.review_by) {
+            ensure!(review_by <= expires_at, Error::<T>::InvalidSunset);
+        }
+        Ok(())
+    }
+
+    /// 写入某版本的日落条款并替换其排程条目(两项皆空即取消)。
+    pub(crate) fn set_version_sunset(law_id: u64, version: u32, sunset: LawSunset) {
+        if let Some(previous) = LawVersionSunsets::<T>::get(law_id, version) {
+            Self::for_each_sunset_entry(law_id, version, &previous, |due, key| {
+                SunsetSchedule::<T>::remove(due, key)
+            });
+        }
+        if sunset.is_empty() {
+            LawVersionSunsets::<T>::remove(law_id, version);
+            return;
+        }
+        LawVersionSunsets::<T>::insert(law_id, version, sunset);
+        Self::reschedule_sunset(law_id, version);
+    }
+
+    /// 按版本当前日落条款(重新)插入排程条目;条目键幂等,重复插入无副作用。
+    pub(crate) fn reschedule_sunset(law_id: u64, version: u32) {
+        if let Some(sunset) = LawVersionSunsets::<T>::get(law_id, version) {
+            Self::for_each_sunset_entry(law_id, version, &sunset, |due, key| {
+                SunsetSchedule::<T>::insert(due, key, ())
+            });
+        }
+    }
+
+    fn for_each_sunset_entry(
+        law_id: u64,
+        version: u32,
+        sunset: &LawSunset,
+        mut f: impl FnMut([u8; 8], (u64, u32, SunsetKind)),
+    ) {
+        for (kind, at) in [
+            (SunsetKind::Expiry, sunset.expires_at),
+            (SunsetKind::Review, sunset.review_by),
+        ] {
+            if let Some(at) = at {
+                f(at.to_be_bytes(), (law_id, version, kind));
+            }
+        }
+    }
+
+    /// 条文补丁/创制修法的新版本沿用旧生效版本尚未到期的日落条款;已过的期限不再继承
+    /// (过期法律经修法恢复)。
+    pub(crate) fn inherit_sunset(
+        law_id: u64,
+        from_version: Option<u32>,
+        to_version: u32,
+        now: u64,
+    ) {
+        let Some(previous) = from_version.and_then(|v| LawVersionSunsets::<T>::get(law_id, v))
+        else {
+            return;
+        };
+        let sunset = LawSunset {
+            expires_at: previous.expires_at.filter(|at| *at > now),
+            review_by: previous.review_by.filter(|at| *at > now),
+        };
+        Self::set_version_sunset(law_id, to_version, sunset);
+    }
+
+    /// 从 votingengine ProposalObject 读回续期案的新日落条款。
+    pub(crate) fn load_sunset(proposal_id: u64) -> Result<LawSunset, DispatchError> {
+        let raw = votingengine::Pallet::<T>::get_proposal_object(proposal_id)
+            .ok_or(Error::<T>::ProposalPayloadInvalid)?;
+        LawSunset::decode(&mut &raw[..]).map_err(|_| Error::<T>::ProposalPayloadInvalid.into())
+    }
+
+    /// 续期案通过后的写入:复核法律仍在生效、路由未变、生效版本即提案时版本。
+    pub(crate) fn write_law_sunset(
+        summary: LawProposalSummary<T>,
+        sunset: LawSunset,
+        now: u64,
+    ) -> DispatchResult {
+        ensure!(
+            summary.action == LawAction::Renew,
+            Error::<T>::ProposalPayloadInvalid
+        );
+        let law = Laws::<T>::get(summary.law_id).ok_or(Error::<T>::LawNotFound)?;
+        ensure!(
+            law.tier != Tier::Constitution,
+            Error::<T>::CannotSunsetConstitution
+        );
+        ensure!(
+            law.status == LawStatus::Effective,
+            Error::<T>::LawNotEffective
+        );
+        ensure!(
+            summary.tier == law.tier
+                && summary.scope_code == law.scope_code
+                && summary.houses == law.houses,
+            Error::<T>::RoutingMismatch
+        );
+        let version = law
+            .effective_version
+            .ok_or(Error::<T>::LawVersionNotFound)?;
+        let current =
+            LawVersions::<T>::get(summary.law_id, version).ok_or(Error::<T>::LawVersionNotFound)?;
+        ensure!(
+            current.content_hash == summary.content_hash,
+            Error::<T>::SunsetBaseMismatch
+        );
+        Self::ensure_valid_sunset(law.tier, &sunset, now)?;
+
+        Self::set_version_sunset(summary.law_id, version, sunset);
+        LawReviewsDue::<T>::remove(summary.law_id);
+        Self::deposit_event(Event::<T>::LawSunsetRenewed {
+            law_id: summary.law_id,
+            version,
+            sunset,
+        });
+        Ok(())
+    }
+
+    /// 按到期顺序处理至多 `MaxSunsetsPerBlock` 条排程:生效版本到失效期 → Expired;
+    /// 到复审期 → 记入待复审。其余过时条目(已续期、已被取代、仍待生效)直接丢弃。
+    pub(crate) fn process_sunsets(now_ms: u64) -> Weight {
+        let limit = <T as Config>::MaxSunsetsPerBlock::get();
+        let (mut reads, mut writes) = (1u64, 0u64);
+        let mut processed = 0u32;
+        while processed < limit {
+            let Some((due_key, entry)) = SunsetSchedule::<T>::iter_keys().next() else {
+                break;
+            };
+            let at = u64::from_be_bytes(due_key);
+            if at > now_ms {
+                break;
+            }
+            SunsetSchedule::<T>::remove(due_key, entry);
+            processed = processed.saturating_add(1);
+            reads = reads.saturating_add(3);
+            writes = writes.saturating_add(1);
+            let (law_id, version, kind) = entry;
+            let Some(law) = Laws::<T>::get(law_id) else {
+                continue;
+            };
+            let current = LawVersionSunsets::<T>::get(law_id, version).unwrap_or_default();
+            let still_scheduled = match kind {
+                SunsetKind::Expiry => current.expires_at == Some(at),
+                SunsetKind::Review => current.review_by == Some(at),
+            };
+            if !still_scheduled
+                || matches!(law.status, LawStatus::Repealed | LawStatus::Expired)
+                || law.effective_version != Some(version)
+            {
+                continue;
+            }
+            match kind {
+                SunsetKind::Expiry => {
+                    Laws::<T>::mutate(law_id, |maybe| {
+                        if let Some(law) = maybe {
+                            law.status = LawStatus::Expired;
+                        }
+                    });
+                    LawReviewsDue::<T>::remove(law_id);
+                    writes = writes.saturating_add(2);
+                    Self::deposit_event(Event::<T>::LawExpired { law_id, version });
+                }
+                SunsetKind::Review => {
+                    LawReviewsDue::<T>::insert(law_id, version);
+                    writes = writes.saturating_add(1);
+                    Self::deposit_event(Event::<T>::LawReviewDue { law_id, version });
+                }
+            }
+        }
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
@@ -152,6 +152,7 @@ fn propose_enact_by_legislator_reaches_engine_notconfigured() {
                 None,
                 one_chapter(),
                 100,
+                LawSunset::default(),
             ),
             Error::<Test>::VoteEngineCreateFailed
         );
@@ -176,6 +177,7 @@ fn municipal_education_route_reaches_engine_notconfigured() {
                 None,
                 one_chapter(),
                 100,
+                LawSunset::default(),
             ),
             Error::<Test>::VoteEngineCreateFailed
         );
@@ -203,6 +205,7 @@ fn routing_rejects_cid_code_mismatch() {
                 None,
                 one_chapter(),
                 100,
+                LawSunset::default(),
             ),
             Error::<Test>::RoutingMismatch
         );
@@ -227,6 +230,7 @@ fn propose_enact_by_non_legislator_rejected() {
                 None,
                 one_chapter(),
                 100,
+                LawSunset::default(),
             ),
             Error::<Test>::NotLegislator
         );
@@ -252,6 +256,7 @@ fn propose_enact_constitution_rejected() {
                 None,
                 one_chapter(),
                 100,
+                LawSunset::default(),
             ),
             Error::<Test>::CannotEnactConstitution
         );
@@ -266,6 +271,7 @@ fn enum_discriminants_match_node_guard() {
     assert_eq!(LawStatus::Pending.encode(), vec![0u8]);
     assert_eq!(LawStatus::Effective.encode(), vec![1u8]);
     assert_eq!(LawStatus::Repealed.encode(), vec![2u8]);
+    assert_eq!(LawStatus::Expired.encode(), vec![3u8]);
     // 特别案业务 wire 值 = 4：节点守卫据此判定核心章档位背书（第十九条）。
     assert_eq!(VoteType::Special.encode(), vec![4u8]);
     assert_eq!(
@@ -292,6 +298,7 @@ fn propose_enact_empty_title_and_articles_rejected() {
                 None,
                 one_chapter(),
                 100,
+                LawSunset::default(),
             ),
             Error::<Test>::EmptyTitle
         );
@@ -310,6 +317,7 @@ fn propose_enact_empty_title_and_articles_rejected() {
                 None,
                 crate::pallet::ChaptersOf::<Test>::default(),
                 100,
+                LawSunset::default(),
             ),
             Error::<Test>::EmptyChapters
         );
@@ -372,6 +380,7 @@ fn amend_constitution_immutable_article_rejected() {
                 None,
                 chapters_of(vec![article(1, b"CHANGED"), article(17, b"yuan-17")]),
                 200,
+                LawSunset::default(),
             ),
             Error::<Test>::ImmutableArticleViolation
         );
@@ -449,6 +458,7 @@ fn amend_core_chapter_with_major_rejected() {
                     vec![article(60, b"gen-60"), article(61, b"gen-61")],
                 ),
                 200,
+                LawSunset::default(),
             ),
             Error::<Test>::CoreClauseRequiresSpecial
         );
@@ -480,6 +490,7 @@ fn amend_core_chapter_with_special_passes_gate() {
                     vec![article(60, b"gen-60"), article(61, b"gen-61")],
                 ),
                 200,
+                LawSunset::default(),
             ),
             Error::<Test>::VoteEngineCreateFailed
         );
@@ -511,6 +522,7 @@ fn amend_general_chapter_with_special_rejected() {
                     vec![article(60, b"CHANGED"), article(61, b"gen-61")],
                 ),
                 200,
+                LawSunset::default(),
             ),
             Error::<Test>::GeneralClauseRequiresMajor
         );
@@ -542,6 +554,7 @@ fn amend_general_chapter_with_major_passes_gate() {
                     vec![article(60, b"CHANGED"), article(61, b"gen-61")],
                 ),
                 200,
+                LawSunset::default(),
             ),
             Error::<Test>::VoteEngineCreateFailed
         );
@@ -573,6 +586,7 @@ fn amend_constitution_no_change_rejected() {
                     vec![article(60, b"gen-60"), article(61, b"gen-61")],
                 ),
                 200,
+                LawSunset::default(),
             ),
             Error::<Test>::EmptyAmendment
         );
@@ -661,6 +675,7 @@ fn rejects_amend_while_pending() {
                 None,
                 one_chapter(),
                 200,
+                LawSunset::default(),
             ),
             Error::<Test>::AmendmentAlreadyPending
         );
@@ -766,6 +781,7 @@ fn amend_constitution_preserving_immutable_reaches_engine() {
                     article(99, b"new")
                 ]),
                 200,
+                LawSunset::default(),
             ),
             Error::<Test>::VoteEngineCreateFailed
         );
@@ -789,6 +805,7 @@ fn amend_constitution_with_regular_vote_type_rejected() {
                 None,
                 chapters_of(vec![article(1, b"yuan-1"), article(17, b"yuan-17")]),
                 200,
+                LawSunset::default(),
             ),
             Error::<Test>::InvalidVoteTypeForConstitution
         );
@@ -830,6 +847,7 @@ fn amend_nonexistent_law_rejected() {
                 None,
                 one_chapter(),
                 100,
+                LawSunset::default(),
             ),
             Error::<Test>::LawNotFound
         );
@@ -929,6 +947,7 @@ fn amend_constitution_rejects_duplicate_chapter_before_article_lookup() {
                 None,
                 chapters,
                 200,
+                LawSunset::default(),
             ),
             Error::<Test>::DuplicateChapterNumber
         );
@@ -1191,3 +1210,705 @@ fn mixed_change_takes_stricter_scope() {
         AmendmentScope::CoreChapter
     );
 }
+
+// ───────────────── 条文级修法补丁 / 逐款差异 ─────────────────
+
+fn patch_of(
+    base_version: u32,
+    operations: Vec<AmendmentOperation<Test>>,
+) -> LawAmendmentPatch<Test> {
+    LawAmendmentPatch::<Test> {
+        base_version,
+        operations: BoundedVec::try_from(operations).expect("operations within bound"),
+    }
+}
+
+fn replace_article(c: u32, s: u32, a: u32, body: &[u8]) -> AmendmentOperation<Test> {
+    AmendmentOperation::Replace {
+        path: LawTextPath::Article(c, s, a),
+        node: AmendmentNode::Article(article(a, body)),
+    }
+}
+
+fn propose_patch(patch: LawAmendmentPatch<Test>) -> sp_runtime::DispatchResult {
+    Lib::propose_amend_law_patch(
+        RuntimeOrigin::signed(legislator()),
+        0,
+        actor_cid_number(),
+        proposer_role_code(),
+        executive_cid_number(),
+        legislature_cid_number(),
+        VoteType::Major,
+        title(b"amended-constitution"),
+        None,
+        patch,
+        200,
+    )
+}
+
+#[test]
+fn amend_patch_general_article_passes_gate() {
+    // 补丁只替换一般章第 60 条 → 套用后全文走整部修法同一闸门 → 到引擎 ()。
+    new_test_ext().execute_with(|| {
+        seed_constitution_tiered();
+        assert_noop!(
+            propose_patch(patch_of(1, vec![replace_article(2, 1, 60, b"CHANGED")])),
+            Error::<Test>::VoteEngineCreateFailed
+        );
+    });
+}
+
+#[test]
+fn amend_patch_core_article_with_major_rejected() {
+    // 补丁改核心章第 5 条仍受第十九条约束:重要案被拒。
+    new_test_ext().execute_with(|| {
+        seed_constitution_tiered();
+        assert_noop!(
+            propose_patch(patch_of(1, vec![replace_article(1, 1, 5, b"CHANGED")])),
+            Error::<Test>::CoreClauseRequiresSpecial
+        );
+    });
+}
+
+#[test]
+fn amend_patch_rejects_stale_base_and_bad_path() {
+    new_test_ext().execute_with(|| {
+        seed_constitution_tiered();
+        assert_noop!(
+            propose_patch(patch_of(2, vec![replace_article(2, 1, 60, b"CHANGED")])),
+            Error::<Test>::AmendmentBaseMismatch
+        );
+        assert_noop!(
+            propose_patch(patch_of(1, vec![replace_article(2, 9, 60, b"CHANGED")])),
+            Error::<Test>::InvalidAmendmentPatch
+        );
+        // 插入同级已存在的条号
+        assert_noop!(
+            propose_patch(patch_of(
+                1,
+                vec![AmendmentOperation::Insert {
+                    parent: Some(LawTextPath::Section(2, 1)),
+                    before: None,
+                    node: AmendmentNode::Article(article(61, b"dup")),
+                }]
+            )),
+            Error::<Test>::InvalidAmendmentPatch
+        );
+        assert_noop!(
+            propose_patch(patch_of(1, vec![])),
+            Error::<Test>::EmptyAmendment
+        );
+    });
+}
+
+#[test]
+fn amend_patch_write_and_law_diff_per_clause() {
+    new_test_ext().execute_with(|| {
+        let s0 = enact_summary(Tier::National, 0, VoteType::Regular, b"law");
+        assert_ok!(Lib::write_law_version(
+            1,
+            s0,
+            chapters_of(vec![article(1, b"a"), article(2, b"b"), article(3, b"c")]),
+            Timestamp::now()
+        ));
+
+        let clause = Clause::<Test> {
+            number: 1,
+            text: BoundedVec::try_from(b"new clause".to_vec()).expect("text within bound"),
+            text_en: None,
+        };
+        let patch = patch_of(
+            1,
+            vec![
+                replace_article(1, 1, 1, b"a2"),
+                AmendmentOperation::Insert {
+                    parent: Some(LawTextPath::Article(1, 1, 2)),
+                    before: None,
+                    node: AmendmentNode::Clause(clause),
+                },
+                AmendmentOperation::Delete {
+                    path: LawTextPath::Article(1, 1, 3),
+                },
+            ],
+        );
+        let chapters = Lib::resolve_amendment_patch(0, &patch).expect("patch applies");
+
+        let mut s1 = enact_summary(Tier::National, 0, VoteType::Regular, b"law");
+        s1.action = LawAction::AmendPatch;
+        s1.law_id = 0;
+        assert_ok!(Lib::write_law_version(2, s1, chapters, Timestamp::now()));
+        assert_eq!(Laws::<Test>::get(0).unwrap().effective_version, Some(2));
+
+        let diff = Lib::law_diff(0, 1, 2).expect("both versions exist");
+        let changes: Vec<_> = diff.iter().map(|e| (e.path, e.change)).collect();
+        assert_eq!(
+            changes,
+            vec![
+                (LawTextPath::Article(1, 1, 3), LawDiffChange::Removed),
+                (LawTextPath::Article(1, 1, 1), LawDiffChange::Modified),
+                (LawTextPath::Clause(1, 1, 2, 1), LawDiffChange::Added),
+            ]
+        );
+        assert_eq!(diff[1].before.as_ref().unwrap().text, b"a".to_vec());
+        assert_eq!(diff[1].after.as_ref().unwrap().text, b"a2".to_vec());
+        assert!(Lib::law_diff(0, 1, 3).is_none());
+
+        // 基准已不再是生效版本的旧补丁不可再套用
+        assert_eq!(
+            Lib::resolve_amendment_patch(0, &patch),
+            Err(Error::<Test>::AmendmentBaseMismatch.into())
+        );
+    });
+}
+
+// ───────────────── 公民创制联署 ─────────────────
+
+fn open_national_petition(who: AccountId32) -> frame_support::dispatch::DispatchResult {
+    Lib::open_petition(
+        RuntimeOrigin::signed(who),
+        PetitionTarget::Enact {
+            tier: Tier::National,
+            scope_code: 0,
+            houses: houses(),
+        },
+        executive_cid_number(),
+        legislature_cid_number(),
+        VoteType::Regular,
+        title(b"citizen law"),
+        None,
+        one_chapter(),
+        100,
+    )
+}
+
+#[test]
+fn petition_threshold_rounds_up_to_at_least_one() {
+    let one_percent = sp_runtime::Permill::from_percent(1);
+    assert_eq!(Lib::petition_required_signatures(one_percent, 300), 3);
+    assert_eq!(Lib::petition_required_signatures(one_percent, 301), 4);
+    assert_eq!(Lib::petition_required_signatures(one_percent, 1), 1);
+}
+
+#[test]
+fn open_petition_requires_voting_citizen_and_valid_bill() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            open_national_petition(outsider()),
+            Error::<Test>::NotPetitionVoter
+        );
+        assert_noop!(
+            Lib::open_petition(
+                RuntimeOrigin::signed(citizen(100)),
+                PetitionTarget::Enact {
+                    tier: Tier::Constitution,
+                    scope_code: 0,
+                    houses: houses(),
+                },
+                executive_cid_number(),
+                legislature_cid_number(),
+                VoteType::Special,
+                title(b"constitution"),
+                None,
+                one_chapter(),
+                100,
+            ),
+            Error::<Test>::CannotEnactConstitution
+        );
+        assert_noop!(
+            Lib::open_petition(
+                RuntimeOrigin::signed(citizen(100)),
+                PetitionTarget::Amend { law_id: 9 },
+                executive_cid_number(),
+                legislature_cid_number(),
+                VoteType::Regular,
+                title(b"law"),
+                None,
+                one_chapter(),
+                100,
+            ),
+            Error::<Test>::LawNotFound
+        );
+    });
+}
+
+#[test]
+fn petition_signatures_dedupe_by_cid_and_file_on_threshold() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(open_national_petition(citizen(100)));
+        let petition = Petitions::<Test>::get(0).expect("petition opened");
+        assert_eq!(petition.required_signatures, 3);
+        assert_eq!(petition.signatures, 1);
+        assert_eq!(petition.status, PetitionStatus::Collecting);
+        assert_eq!(
+            petition.population_scope,
+            votingengine::PopulationScope::Country
+        );
+        let hash = petition.summary.content_hash;
+
+        assert_noop!(
+            Lib::sign_petition(RuntimeOrigin::signed(citizen(100)), 0, hash),
+            Error::<Test>::AlreadySignedPetition
+        );
+        assert_noop!(
+            Lib::sign_petition(RuntimeOrigin::signed(citizen(101)), 0, [0u8; 32]),
+            Error::<Test>::PetitionContentMismatch
+        );
+        assert_noop!(
+            Lib::sign_petition(RuntimeOrigin::signed(outsider()), 0, hash),
+            Error::<Test>::NotPetitionVoter
+        );
+        assert_noop!(
+            Lib::file_qualified_petition(RuntimeOrigin::signed(citizen(101)), 0),
+            Error::<Test>::PetitionNotQualified
+        );
+
+        assert_ok!(Lib::sign_petition(
+            RuntimeOrigin::signed(citizen(101)),
+            0,
+            hash
+        ));
+        // 第 3 人达门槛即建案;单测引擎为 (),建案失败只把联署置为可重试。
+        assert_ok!(Lib::sign_petition(
+            RuntimeOrigin::signed(citizen(102)),
+            0,
+            hash
+        ));
+        let petition = Petitions::<Test>::get(0).expect("petition kept");
+        assert_eq!(petition.signatures, 3);
+        assert_eq!(petition.status, PetitionStatus::Qualified);
+        assert_eq!(petition.proposal_id, None);
+        assert!(PetitionTexts::<Test>::contains_key(0));
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::LegislationYuan(crate::Event::PetitionFilingFailed {
+                petition_id: 0,
+                ..
+            })
+        )));
+
+        assert_noop!(
+            Lib::sign_petition(RuntimeOrigin::signed(citizen(103)), 0, hash),
+            Error::<Test>::PetitionNotCollecting
+        );
+        assert_noop!(
+            Lib::file_qualified_petition(RuntimeOrigin::signed(outsider()), 0),
+            Error::<Test>::VoteEngineCreateFailed
+        );
+    });
+}
+
+#[test]
+fn petition_expires_and_clears_in_batches() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(open_national_petition(citizen(100)));
+        let hash = Petitions::<Test>::get(0).unwrap().summary.content_hash;
+        assert_ok!(Lib::sign_petition(
+            RuntimeOrigin::signed(citizen(101)),
+            0,
+            hash
+        ));
+        assert_noop!(
+            Lib::clear_petition(RuntimeOrigin::signed(outsider()), 0),
+            Error::<Test>::PetitionStillActive
+        );
+
+        System::set_block_number(1 + PetitionDuration::get() + 1);
+        assert_noop!(
+            Lib::sign_petition(RuntimeOrigin::signed(citizen(102)), 0, hash),
+            Error::<Test>::PetitionExpired
+        );
+
+        // 第二个联署达门槛后建案失败(Qualified),过期后同样可清理。
+        assert_ok!(open_national_petition(citizen(100)));
+        for n in [101, 102] {
+            assert_ok!(Lib::sign_petition(
+                RuntimeOrigin::signed(citizen(n)),
+                1,
+                hash
+            ));
+        }
+        assert_eq!(
+            Petitions::<Test>::get(1).unwrap().status,
+            PetitionStatus::Qualified
+        );
+        System::set_block_number(2 * (PetitionDuration::get() + 1) + 1);
+        // 过期的可重试联署不能再建案,只能清理。
+        assert_noop!(
+            Lib::file_qualified_petition(RuntimeOrigin::signed(outsider()), 1),
+            Error::<Test>::PetitionExpired
+        );
+
+        // 每次最多清 MaxPetitionCleanup(=2) 条联署;清完才删联署本体与正文。
+        assert_ok!(Lib::clear_petition(RuntimeOrigin::signed(outsider()), 1));
+        assert!(Petitions::<Test>::get(1).is_some());
+        assert_eq!(PetitionSignatures::<Test>::iter_prefix(1).count(), 1);
+        assert_ok!(Lib::clear_petition(RuntimeOrigin::signed(outsider()), 1));
+        assert!(Petitions::<Test>::get(1).is_none());
+        assert!(PetitionTexts::<Test>::get(1).is_none());
+        assert_eq!(PetitionSignatures::<Test>::iter_prefix(1).count(), 0);
+
+        assert_ok!(Lib::clear_petition(RuntimeOrigin::signed(outsider()), 0));
+        assert!(Petitions::<Test>::get(0).is_none());
+        assert!(PetitionTexts::<Test>::get(0).is_none());
+        assert_eq!(PetitionSignatures::<Test>::iter_prefix(0).count(), 0);
+    });
+}
+
+#[test]
+fn open_petitions_are_capped_per_citizen_until_released() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(open_national_petition(citizen(100)));
+        assert_ok!(open_national_petition(citizen(100)));
+        let cid_number = Petitions::<Test>::get(0).unwrap().initiator_cid_number;
+        assert_eq!(OpenPetitionsByCitizen::<Test>::get(&cid_number), 2);
+        assert_noop!(
+            open_national_petition(citizen(100)),
+            Error::<Test>::TooManyOpenPetitions
+        );
+        // 其他公民的名额独立计算。
+        assert_ok!(open_national_petition(citizen(101)));
+
+        // 清理过期联署时删除全文并归还名额;重复清理不重复归还。
+        System::set_block_number(1 + PetitionDuration::get() + 1);
+        assert_ok!(Lib::clear_petition(RuntimeOrigin::signed(outsider()), 0));
+        assert!(PetitionTexts::<Test>::get(0).is_none());
+        assert_eq!(OpenPetitionsByCitizen::<Test>::get(&cid_number), 1);
+        assert_ok!(open_national_petition(citizen(100)));
+        assert_eq!(OpenPetitionsByCitizen::<Test>::get(&cid_number), 2);
+    });
+}
+
+#[test]
+fn open_petition_weight_grows_with_text_length() {
+    use crate::weights::WeightInfo;
+    let short = <() as WeightInfo>::open_petition(one_chapter().encoded_size() as u32);
+    let long = <() as WeightInfo>::open_petition(64 * 1024);
+    assert!(long.ref_time() > short.ref_time());
+    assert!(long.proof_size() > short.proof_size());
+}
+
+// ───────────────── 日落条款 / 续期 ─────────────────
+
+fn enact_national_law() {
+    let summary = enact_summary(Tier::National, 0, VoteType::Regular, b"law");
+    assert_ok!(Lib::write_law_version(
+        1,
+        summary,
+        chapters_of(vec![article(1, b"a")]),
+        Timestamp::now()
+    ));
+}
+
+fn renew_summary(law_id: u64) -> LawProposalSummary<Test> {
+    let law = Laws::<Test>::get(law_id).unwrap();
+    let current = LawVersions::<Test>::get(law_id, law.effective_version.unwrap()).unwrap();
+    let mut summary = enact_summary(Tier::National, 0, VoteType::Regular, b"law");
+    summary.action = LawAction::Renew;
+    summary.law_id = law_id;
+    summary.content_hash = current.content_hash;
+    summary
+}
+
+fn sunset(expires_at: Option<u64>, review_by: Option<u64>) -> LawSunset {
+    LawSunset {
+        expires_at,
+        review_by,
+    }
+}
+
+fn propose_renew(law_id: u64, terms: LawSunset) -> frame_support::dispatch::DispatchResult {
+    Lib::propose_renew_law(
+        RuntimeOrigin::signed(legislator()),
+        law_id,
+        actor_cid_number(),
+        proposer_role_code(),
+        executive_cid_number(),
+        legislature_cid_number(),
+        VoteType::Regular,
+        terms,
+    )
+}
+
+#[test]
+fn propose_renew_validates_law_state_and_terms() {
+    new_test_ext().execute_with(|| {
+        seed_constitution();
+        assert_noop!(
+            propose_renew(0, sunset(Some(5_000), None)),
+            Error::<Test>::CannotSunsetConstitution
+        );
+        assert_noop!(
+            propose_renew(9, sunset(Some(5_000), None)),
+            Error::<Test>::LawNotFound
+        );
+
+        enact_national_law();
+        let law_id = NextLawId::<Test>::get() - 1;
+        assert_noop!(
+            propose_renew(law_id, sunset(Some(1_000), None)),
+            Error::<Test>::InvalidSunset
+        );
+        assert_noop!(
+            propose_renew(law_id, sunset(Some(5_000), Some(6_000))),
+            Error::<Test>::InvalidSunset
+        );
+        assert_noop!(
+            propose_renew(law_id, sunset(Some(5_000), Some(3_000))),
+            Error::<Test>::VoteEngineCreateFailed
+        );
+        // 两项皆空 = 提议取消日落条款,同样合法。
+        assert_noop!(
+            propose_renew(law_id, LawSunset::default()),
+            Error::<Test>::VoteEngineCreateFailed
+        );
+    });
+}
+
+#[test]
+fn sunset_review_then_expiry_and_amend_revives_without_stale_terms() {
+    new_test_ext().execute_with(|| {
+        enact_national_law();
+        assert_ok!(Lib::write_law_sunset(
+            renew_summary(0),
+            sunset(Some(5_000), Some(3_000)),
+            Timestamp::now()
+        ));
+        assert_eq!(
+            Lib::law_sunset(0, 1),
+            Some(sunset(Some(5_000), Some(3_000)))
+        );
+        assert_eq!(SunsetSchedule::<Test>::iter_keys().count(), 2);
+
+        Timestamp::set_timestamp(3_000);
+        Lib::on_initialize(2);
+        assert_eq!(LawReviewsDue::<Test>::get(0), Some(1));
+        assert_eq!(Laws::<Test>::get(0).unwrap().status, LawStatus::Effective);
+        assert_eq!(SunsetSchedule::<Test>::iter_keys().count(), 1);
+
+        Timestamp::set_timestamp(5_000);
+        Lib::on_initialize(3);
+        let law = Laws::<Test>::get(0).unwrap();
+        assert_eq!(law.status, LawStatus::Expired);
+        assert_eq!(law.effective_version, Some(1));
+        assert!(LawReviewsDue::<Test>::get(0).is_none());
+        assert_eq!(SunsetSchedule::<Test>::iter_keys().count(), 0);
+
+        // 失效法律不能续期,只能修法恢复;已过期的期限不随新版本继承。
+        assert_noop!(
+            Lib::write_law_sunset(renew_summary(0), sunset(Some(9_000), None), 5_000),
+            Error::<Test>::LawNotEffective
+        );
+        let mut amend = enact_summary(Tier::National, 0, VoteType::Regular, b"law");
+        amend.action = LawAction::Amend;
+        amend.law_id = 0;
+        assert_ok!(Lib::write_law_version(
+            2,
+            amend,
+            chapters_of(vec![article(1, b"a2")]),
+            Timestamp::now()
+        ));
+        let law = Laws::<Test>::get(0).unwrap();
+        assert_eq!(law.status, LawStatus::Effective);
+        assert_eq!(law.effective_version, Some(2));
+        assert!(Lib::law_sunset(0, 2).is_none());
+    });
+}
+
+#[test]
+fn renewal_replaces_schedule_and_pending_amend_inherits_terms() {
+    new_test_ext().execute_with(|| {
+        enact_national_law();
+        assert_ok!(Lib::write_law_sunset(
+            renew_summary(0),
+            sunset(Some(9_000), None),
+            Timestamp::now()
+        ));
+        assert_ok!(Lib::write_law_sunset(
+            renew_summary(0),
+            sunset(Some(20_000), None),
+            Timestamp::now()
+        ));
+        assert_eq!(
+            SunsetSchedule::<Test>::iter_keys().collect::<Vec<_>>(),
+            vec![(20_000u64.to_be_bytes(), (0, 1, SunsetKind::Expiry))]
+        );
+
+        // 旧期限到点不再生效。
+        Timestamp::set_timestamp(9_000);
+        Lib::on_initialize(2);
+        assert_eq!(Laws::<Test>::get(0).unwrap().status, LawStatus::Effective);
+
+        // 条文补丁不带日落条款,沿用旧版本未到期的期限。
+        let mut amend = enact_summary(Tier::National, 0, VoteType::Regular, b"law");
+        amend.action = LawAction::AmendPatch;
+        amend.law_id = 0;
+        amend.effective_at = 12_000;
+        amend.sunset = None;
+        assert_ok!(Lib::write_law_version(
+            2,
+            amend,
+            chapters_of(vec![article(1, b"a2")]),
+            Timestamp::now()
+        ));
+        assert_eq!(Lib::law_sunset(0, 2), Some(sunset(Some(20_000), None)));
+        // 有待生效修订时不得续期。
+        assert_noop!(
+            Lib::write_law_sunset(renew_summary(0), sunset(Some(30_000), None), 9_000),
+            Error::<Test>::LawNotEffective
+        );
+
+        // 新版本先生效,旧版本条目作废,新版本条目到期失效。
+        Timestamp::set_timestamp(20_000);
+        Lib::on_initialize(3);
+        let law = Laws::<Test>::get(0).unwrap();
+        assert_eq!(law.effective_version, Some(2));
+        assert_eq!(law.status, LawStatus::Expired);
+        assert_eq!(SunsetSchedule::<Test>::iter_keys().count(), 0);
+    });
+}
+
+#[test]
+fn propose_enact_and_amend_validate_sunset_terms() {
+    new_test_ext().execute_with(|| {
+        Timestamp::set_timestamp(1_000);
+        // 期限须晚于生效时刻,而不只是晚于当前时间。
+        assert_noop!(
+            Lib::propose_enact_law(
+                RuntimeOrigin::signed(legislator()),
+                Tier::Municipal,
+                1001,
+                municipal_houses(),
+                municipal_actor_cid_number(),
+                proposer_role_code(),
+                municipal_executive_cid_number(),
+                None,
+                VoteType::Regular,
+                title(b"law"),
+                None,
+                one_chapter(),
+                5_000,
+                sunset(Some(4_000), None),
+            ),
+            Error::<Test>::InvalidSunset
+        );
+        assert_noop!(
+            Lib::propose_enact_law(
+                RuntimeOrigin::signed(legislator()),
+                Tier::Municipal,
+                1001,
+                municipal_houses(),
+                municipal_actor_cid_number(),
+                proposer_role_code(),
+                municipal_executive_cid_number(),
+                None,
+                VoteType::Regular,
+                title(b"law"),
+                None,
+                one_chapter(),
+                5_000,
+                sunset(Some(9_000), Some(6_000)),
+            ),
+            Error::<Test>::VoteEngineCreateFailed
+        );
+
+        enact_national_law();
+        assert_noop!(
+            Lib::propose_amend_law(
+                RuntimeOrigin::signed(legislator()),
+                0,
+                actor_cid_number(),
+                proposer_role_code(),
+                executive_cid_number(),
+                legislature_cid_number(),
+                VoteType::Regular,
+                title(b"law"),
+                None,
+                chapters_of(vec![article(1, b"a2")]),
+                2_000,
+                sunset(Some(9_000), Some(9_500)),
+            ),
+            Error::<Test>::InvalidSunset
+        );
+    });
+}
+
+#[test]
+fn enact_and_amend_write_explicit_sunset_terms() {
+    new_test_ext().execute_with(|| {
+        let mut enact = enact_summary(Tier::National, 0, VoteType::Regular, b"law");
+        enact.effective_at = 2_000;
+        enact.sunset = Some(sunset(Some(8_000), Some(6_000)));
+        assert_ok!(Lib::write_law_version(
+            1,
+            enact,
+            chapters_of(vec![article(1, b"a")]),
+            Timestamp::now()
+        ));
+        assert_eq!(
+            Lib::law_sunset(0, 1),
+            Some(sunset(Some(8_000), Some(6_000)))
+        );
+
+        // 写入时刻已到期限(表决期间时间推进)则拒绝写入。
+        let mut stale = enact_summary(Tier::National, 0, VoteType::Regular, b"law");
+        stale.sunset = Some(sunset(Some(1_000), None));
+        assert_noop!(
+            Lib::write_law_version(
+                2,
+                stale,
+                chapters_of(vec![article(1, b"b")]),
+                Timestamp::now()
+            ),
+            Error::<Test>::InvalidSunset
+        );
+
+        // 修法显式给出的条款取代旧版本条款,空条款即取消日落。
+        Timestamp::set_timestamp(2_000);
+        Lib::on_initialize(2);
+        let mut amend = enact_summary(Tier::National, 0, VoteType::Regular, b"law");
+        amend.action = LawAction::Amend;
+        amend.law_id = 0;
+        assert_ok!(Lib::write_law_version(
+            3,
+            amend,
+            chapters_of(vec![article(1, b"a2")]),
+            Timestamp::now()
+        ));
+        assert_eq!(Laws::<Test>::get(0).unwrap().effective_version, Some(2));
+        assert!(Lib::law_sunset(0, 2).is_none());
+        Timestamp::set_timestamp(8_000);
+        Lib::on_initialize(3);
+        assert_eq!(Laws::<Test>::get(0).unwrap().status, LawStatus::Effective);
+        assert!(LawReviewsDue::<Test>::get(0).is_none());
+    });
+}
+
+#[test]
+fn due_sunsets_are_processed_in_bounded_batches() {
+    new_test_ext().execute_with(|| {
+        // 排程不设总上限;每块最多处理 MaxSunsetsPerBlock(=2)条,余下顺延。
+        for _ in 0..3 {
+            let mut enact = enact_summary(Tier::National, 0, VoteType::Regular, b"law");
+            enact.sunset = Some(sunset(Some(5_000), None));
+            assert_ok!(Lib::write_law_version(
+                1,
+                enact,
+                chapters_of(vec![article(1, b"a")]),
+                Timestamp::now()
+            ));
+        }
+        assert_eq!(SunsetSchedule::<Test>::iter_keys().count(), 3);
+
+        Timestamp::set_timestamp(5_000);
+        Lib::on_initialize(2);
+        assert_eq!(SunsetSchedule::<Test>::iter_keys().count(), 1);
+        let expired = (0..3)
+            .filter(|id| Laws::<Test>::get(id).unwrap().status == LawStatus::Expired)
+            .count();
+        assert_eq!(expired, 2);
+
+        Lib::on_initialize(3);
+        assert_eq!(SunsetSchedule::<Test>::iter_keys().count(), 0);
+        assert!((0..3).all(|id| Laws::<Test>::get(id).unwrap().status == LawStatus::Expired));
+    });
+}
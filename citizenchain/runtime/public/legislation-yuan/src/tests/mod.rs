@@ -16,7 +16,7 @@ use frame_support::{
 };
 use frame_system as system;
 use primitives::cid::code::InstitutionCode;
-use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage};
+use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage, Permill};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -80,12 +80,24 @@ pub fn outsider() -> AccountId32 {
 pub const OWNER_CODE: InstitutionCode = *b"NRP\0";
 
 pub struct TestCitizenIdentityReader;
+/// 联署测试公民:首字节 ≥ 100 的账户视为有效投票公民,CID 取账户字节。
+pub fn citizen(n: u8) -> AccountId32 {
+    assert!(n >= 100, "citizen accounts start at 100");
+    AccountId32::new([n; 32])
+}
+/// 联署测试的作用域有效投票人口;1% 门槛下需 3 人联署。
+pub const PETITION_POPULATION: u64 = 300;
+
 impl votingengine::CitizenIdentityReader<AccountId32> for TestCitizenIdentityReader {
     fn voting_subject(
-        _who: &AccountId32,
+        who: &AccountId32,
         _scope: &votingengine::PopulationScope,
     ) -> Option<votingengine::CitizenSubject<AccountId32>> {
-        None
+        let raw: &[u8] = who.as_ref();
+        (raw[0] >= 100).then(|| votingengine::CitizenSubject {
+            cid_number: raw.to_vec().try_into().expect("account fits CID"),
+            account_id: who.clone(),
+        })
     }
 
     fn candidate_subject(
@@ -94,6 +106,17 @@ impl votingengine::CitizenIdentityReader<AccountId32> for TestCitizenIdentityRea
     ) -> Option<votingengine::CitizenSubject<AccountId32>> {
         None
     }
+
+    fn population_data(
+        scope: &votingengine::PopulationScope,
+    ) -> Option<votingengine::PopulationData> {
+        Some(votingengine::PopulationData {
+            scope: scope.clone(),
+            eligible_total: PETITION_POPULATION,
+            eligibility_revision: 1,
+            eligibility_date: 20_000,
+        })
+    }
 }
 
 pub struct TestInternalAdminProvider;
@@ -293,6 +316,12 @@ parameter_types! {
     pub const MaxChaptersPerLaw: u32 = 50;
     pub const MaxLawsPerScope: u32 = 1000;
     pub const MaxPendingActivations: u32 = 100;
+    pub const MaxSunsetsPerBlock: u32 = 2;
+    pub const MaxAmendmentOperations: u32 = 64;
+    pub const PetitionThreshold: Permill = Permill::from_percent(1);
+    pub const PetitionDuration: u64 = 100;
+    pub const MaxPetitionCleanup: u32 = 2;
+    pub const MaxOpenPetitionsPerCitizen: u32 = 2;
 }
 
 impl crate::pallet::Config for Test {
@@ -309,6 +338,12 @@ impl crate::pallet::Config for Test {
     type MaxChaptersPerLaw = MaxChaptersPerLaw;
     type MaxLawsPerScope = MaxLawsPerScope;
     type MaxPendingActivations = MaxPendingActivations;
+    type MaxSunsetsPerBlock = MaxSunsetsPerBlock;
+    type MaxAmendmentOperations = MaxAmendmentOperations;
+    type PetitionThreshold = PetitionThreshold;
+    type PetitionDuration = PetitionDuration;
+    type MaxPetitionCleanup = MaxPetitionCleanup;
+    type MaxOpenPetitionsPerCitizen = MaxOpenPetitionsPerCitizen;
     type WeightInfo = ();
 }
 
@@ -477,11 +512,13 @@ pub fn enact_summary(
         title_en: None,
         content_hash: [0u8; 32],
         effective_at: 0,
+        sunset: Some(LawSunset::default()),
     }
 }
 
 pub use crate::pallet::{
-    Law, LawVersion, LawVersions, Laws, LawsByScope, NextLawId, PendingActivations,
+    Law, LawReviewsDue, LawVersion, LawVersions, Laws, LawsByScope, NextLawId, PendingActivations,
+    SunsetSchedule,
 };
 pub type Lib = crate::pallet::Pallet<Test>;
 
@@ -1,12 +1,14 @@
-//! 立法院模块数据类型(ADR-027):法律层级 / 状态 / 表决类型 / 立法动作枚举。
+//! 立法院模块数据类型(ADR-027):法律层级 / 状态 / 表决类型 / 立法动作枚举,
+//! 以及条文路径与版本差异结果。
 //!
-//! 这里只放与泛型 `T` 无关的纯枚举;带 `BoundedVec` 上限的法律结构体
+//! 这里只放与泛型 `T` 无关的类型;带 `BoundedVec` 上限的法律结构体
 //! (Article / Clause / Item / Law / LawVersion)因依赖 `Config` 常量,定义在 `lib.rs` 的 pallet 模块内。
 
 use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use frame_support::pallet_prelude::RuntimeDebug;
 use legislation_vote::RepresentativeVoteRule;
 use scale_info::TypeInfo;
+use sp_runtime::sp_std::vec::Vec;
 
 /// 法律层级。宪法为最高层级,只能由国家立法院按宪法第十九条修改。
 #[derive(
@@ -56,6 +58,8 @@ pub enum LawStatus {
     Effective,
     /// 已废止
     Repealed,
+    /// 日落条款到期自动失效;修法通过新版本生效后恢复为生效中
+    Expired,
 }
 
 /// 立法业务表决类型（公民宪法第45/46条规定的五类）。
@@ -121,4 +125,149 @@ pub enum LawAction {
     Amend,
     /// 废法
     Repeal,
+    /// 修法(条文补丁):按章/节/条/款路径增删改,通过后对基准版本套用生成新版本
+    AmendPatch,
+    /// 续期:重新设定当前生效版本的失效与复审期限(含取消日落条款),不改条文
+    Renew,
+}
+
+/// 法律版本的日落条款(时间戳毫秒,与 `effective_at` 同口径)。
+///
+/// `expires_at` 到期后法律自动失效;`review_by` 到期仍未续期则标记为待复审。两者都为空
+/// 即无日落条款。修法产生的新版本沿用旧版本的日落条款,只有续期案可以改动。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct LawSunset {
+    pub expires_at: Option<u64>,
+    pub review_by: Option<u64>,
+}
+
+impl LawSunset {
+    pub fn is_empty(&self) -> bool {
+        self.expires_at.is_none() && self.review_by.is_none()
+    }
+}
+
+/// 日落排程条目的种类。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum SunsetKind {
+    /// 到期失效
+    Expiry,
+    /// 复审期限
+    Review,
+}
+
+/// 公民创制联署状态。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum PetitionStatus {
+    /// 征集联署中
+    Collecting,
+    /// 已达门槛但建案失败(如目标法律有待生效修订),任何人可重试建案
+    Qualified,
+    /// 已建案进入立法投票
+    Filed,
+}
+
+/// 法律条文定位路径:章号 > 节号 > 条号 > 款号。
+///
+/// 各层均按条文自身序号定位,不按数组下标,补丁与差异结果不随同级插删漂移。
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum LawTextPath {
+    /// 第 N 章
+    Chapter(u32),
+    /// 章 > 节
+    Section(u32, u32),
+    /// 章 > 节 > 条
+    Article(u32, u32, u32),
+    /// 章 > 节 > 条 > 款
+    Clause(u32, u32, u32, u32),
+}
+
+impl LawTextPath {
+    /// 下一层子路径;款已是叶子,返回 None。
+    pub fn child(&self, number: u32) -> Option<LawTextPath> {
+        match *self {
+            LawTextPath::Chapter(c) => Some(LawTextPath::Section(c, number)),
+            LawTextPath::Section(c, s) => Some(LawTextPath::Article(c, s, number)),
+            LawTextPath::Article(c, s, a) => Some(LawTextPath::Clause(c, s, a, number)),
+            LawTextPath::Clause(..) => None,
+        }
+    }
+}
+
+/// 两版本间单个条文单元的变化类型。
+#[derive(
+    Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo,
+)]
+pub enum LawDiffChange {
+    /// 新版本新增
+    Added,
+    /// 新版本删除
+    Removed,
+    /// 标题或正文(含英文)改动
+    Modified,
+}
+
+/// 条文单元的可比对文本。章/节只有标题;条有标题与正文;款只有正文。
+#[derive(
+    Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, Default,
+)]
+pub struct LawTextSnapshot {
+    pub title: Vec<u8>,
+    pub title_en: Option<Vec<u8>>,
+    pub text: Vec<u8>,
+    pub text_en: Option<Vec<u8>>,
+}
+
+/// 逐款差异中的一项。`before`/`after` 分别为旧/新版本中的文本,新增或删除时一侧为 None。
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct LawDiffEntry {
+    pub path: LawTextPath,
+    pub change: LawDiffChange,
+    pub before: Option<LawTextSnapshot>,
+    pub after: Option<LawTextSnapshot>,
 }
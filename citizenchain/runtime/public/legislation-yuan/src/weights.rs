@@ -1,15 +1,21 @@
 //! 立法院模块权重(ADR-027)。
 //!
-//! 当前使用固定保守权重。三个入口会解析岗位权限、冻结多机构岗位任职并创建
+//! 当前使用固定保守权重。各提案入口会解析岗位权限、冻结多机构岗位任职并创建
 //! 立法投票提案；在业务 pallet 获得完整可执行 benchmark 夹具前不得恢复旧的低占位值。
 
 use frame_support::weights::Weight;
 
-/// 立法院三个提案入口的权重接口。
+/// 立法院提案入口的权重接口。
 pub trait WeightInfo {
     fn propose_enact_law() -> Weight;
     fn propose_amend_law() -> Weight;
     fn propose_repeal_law() -> Weight;
+    fn propose_amend_law_patch() -> Weight;
+    fn open_petition(l: u32) -> Weight;
+    fn sign_petition() -> Weight;
+    fn file_qualified_petition() -> Weight;
+    fn clear_petition(n: u32) -> Weight;
+    fn propose_renew_law() -> Weight;
 }
 
 /// 默认实现：为法律正文校验、岗位目录读取、VotePlan 和多岗位快照预留保守上界。
@@ -23,4 +29,28 @@ impl WeightInfo for () {
     fn propose_repeal_law() -> Weight {
         Weight::from_parts(3_000_000_000, 1_000_000)
     }
+    // 额外读取基准版本全文并逐步套用补丁,其后校验与整部修法相同。
+    fn propose_amend_law_patch() -> Weight {
+        Weight::from_parts(6_000_000_000, 2_000_000)
+    }
+    // 发起与联署都可能在达门槛时当场建案,按建案上界再加人口与身份读取。
+    // `l` 为全文编码字节数:哈希、校验与 `PetitionTexts` 写入都随全文线性增长。
+    fn open_petition(l: u32) -> Weight {
+        Weight::from_parts(6_000_000_000, 2_000_000)
+            .saturating_add(Weight::from_parts(20_000, 1).saturating_mul(l as u64))
+    }
+    fn sign_petition() -> Weight {
+        Weight::from_parts(6_000_000_000, 2_000_000)
+    }
+    fn file_qualified_petition() -> Weight {
+        Weight::from_parts(5_000_000_000, 1_500_000)
+    }
+    fn clear_petition(n: u32) -> Weight {
+        Weight::from_parts(50_000_000, 10_000)
+            .saturating_add(Weight::from_parts(10_000_000, 100).saturating_mul(n as u64))
+    }
+    // 与废法同:不携带正文,只读生效版本取哈希绑定。
+    fn propose_renew_law() -> Weight {
+        Weight::from_parts(3_000_000_000, 1_000_000)
+    }
 }
@@ -294,6 +294,11 @@ impl_runtime_apis! {
             LegislationYuan::law_version_label(law_id, version)
                 .map(|v| codec::Encode::encode(&v))
         }
+
+        fn law_diff(law_id: u64, from_version: u32, to_version: u32) -> Option<Vec<u8>> {
+            LegislationYuan::law_diff(law_id, from_version, to_version)
+                .map(|d| codec::Encode::encode(&d))
+        }
     }
 
     impl primitives::cid::china::BuiltinInstitutionNameApi<Block> for Runtime {
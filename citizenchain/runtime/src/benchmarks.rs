@@ -51,4 +51,5 @@ frame_benchmarking::define_benchmarks!(
     [election_vote, ElectionVote]
     [square_post, SquarePost]
     [runtime_upgrade, RuntimeUpgrade]
+    [emergency_pause, EmergencyPause]
 );
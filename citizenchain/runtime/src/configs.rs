@@ -39,8 +39,8 @@ use frame_support::{
     parameter_types,
     traits::{
         fungible::{Balanced, Credit},
-        ConstU128, ConstU32, ConstU64, ConstU8, Contains, EnsureOrigin, FindAuthor, OnUnbalanced,
-        VariantCountOf,
+        ConstU128, ConstU32, ConstU64, ConstU8, Contains, EnsureOrigin, FindAuthor,
+        GetCallMetadata, OnUnbalanced, VariantCountOf,
     },
     weights::{
         constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND},
@@ -55,7 +55,10 @@ use sp_core::Void;
 use sp_io::crypto::sr25519_verify;
 #[cfg(feature = "runtime-benchmarks")]
 use sp_runtime::{traits::IdentifyAccount, MultiSigner};
-use sp_runtime::{traits::One, Perbill};
+use sp_runtime::{
+    traits::{AccountIdConversion, One},
+    Perbill,
+};
 use sp_version::RuntimeVersion;
 
 // Local module imports
@@ -67,7 +70,7 @@ use super::{
     BLOCK_HASH_COUNT, EXISTENTIAL_DEPOSIT, VERSION,
 };
 #[cfg(not(feature = "runtime-benchmarks"))]
-use super::{ResolutionIssuance, RuntimeUpgrade};
+use super::{EmergencyPause, ResolutionIssuance, RuntimeUpgrade};
 
 const NORMAL_DISPATCH_RATIO: Perbill =
     Perbill::from_percent(primitives::core_const::NORMAL_DISPATCH_PERCENT);
@@ -93,7 +96,7 @@ parameter_types! {
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 ///
-/// 开发期零用户、重新创世模型：各 pallet 不设迁移；这里仅保留 runtime 级独立迁移集合，当前为空。
+/// pallet 迁移统一登记在 `lib.rs` 的 `Migrations`；这里仅保留 runtime 级独立迁移集合，当前为空。
 type SingleBlockMigrations = ();
 
 pub fn is_stake_account(address: &AccountId) -> bool {
@@ -170,9 +173,10 @@ pub struct RuntimeCallFilter;
 
 impl Contains<RuntimeCall> for RuntimeCallFilter {
     fn contains(call: &RuntimeCall) -> bool {
-        match call {
+        let statically_allowed = match call {
             // Balances 只作为底层余额账本和内部 Currency 能力保留。
-            // 外部单账户链上转账唯一入口是 OnchainTransaction::transfer_with_remark。
+            // 外部单账户链上转账入口只有 OnchainTransaction::transfer_with_remark
+            // 与账单付款 OnchainTransaction::pay_invoice。
             RuntimeCall::Balances(_) => false,
             // ADR-011 铁律:pallet_assets 内核所有原生 extrinsic 一律 reject。
             // 业务调用必须经由 OnchainIssuance::propose_* → InternalVote/JointVote callback → 内部 root 调用。
@@ -208,6 +212,7 @@ impl Contains<RuntimeCall> for RuntimeCallFilter {
             | RuntimeCall::LegislationVote(_) => true,
             // 治理与协议升级。
             RuntimeCall::RuntimeUpgrade(_)
+            | RuntimeCall::EmergencyPause(_)
             | RuntimeCall::ResolutionDestroy(_)
             | RuntimeCall::GrandpaKeyChange(_)
             | RuntimeCall::LegislationYuan(_) => true,
@@ -220,7 +225,15 @@ impl Contains<RuntimeCall> for RuntimeCallFilter {
             RuntimeCall::CitizenIdentity(_)
             | RuntimeCall::AddressRegistry(_)
             | RuntimeCall::SquarePost(_) => true,
-        }
+        };
+        // 静态放行后再查紧急暂停登记表；共识与治理自救 pallet 由暂停模块永远放行，
+        // 到期记录即使尚未被 on_initialize 清理也不再拦截。
+        let metadata = call.get_call_metadata();
+        statically_allowed
+            && !emergency_pause::Pallet::<Runtime>::is_call_paused(
+                metadata.pallet_name,
+                metadata.function_name,
+            )
     }
 }
 
@@ -341,6 +354,11 @@ impl onchain::pallet::Config for Runtime {
     type OnchainMinFee = ConstU128<{ primitives::fee_policy::ONCHAIN_MIN_FEE }>;
     type OnchainFeeRate = RuntimeOnchainFeeRate;
     type VoteFlatFee = ConstU128<{ primitives::fee_policy::VOTE_FLAT_FEE }>;
+    type MaxOpenInvoicesPerIssuer = ConstU32<256>;
+    /// 每张账单锁定 10 元押金，关闭时退还；被指定付款方拒付时归付款方。
+    type InvoiceDeposit = ConstU128<1_000>;
+    /// 账单最长 90 天有效。
+    type MaxInvoiceDuration = ConstU32<{ primitives::count_const::BLOCKS_PER_DAY * 90 }>;
 }
 
 pub struct RuntimeNrcAccountProvider;
@@ -477,11 +495,30 @@ impl onchain::CallFeeRoute<AccountId, RuntimeCall, Balance> for RuntimeFeeRouter
     ) -> primitives::fee_policy::FeeRoute<AccountId, Balance> {
         use primitives::fee_policy::FeeRoute;
 
+        // 被紧急暂停的调用在交易池即判无效：不扣手续费，也不会进块后才被过滤器拒绝。
+        let metadata = call.get_call_metadata();
+        if emergency_pause::Pallet::<Runtime>::is_call_paused(
+            metadata.pallet_name,
+            metadata.function_name,
+        ) {
+            return FeeRoute::Reject;
+        }
+
         match call {
             RuntimeCall::OnchainTransaction(onchain::pallet::Call::transfer_with_remark {
                 amount,
                 ..
             }) => signer_onchain_route(who, *amount),
+            // 账单付款与普通转账同口径按付款金额计费；开单、撤销、拒付、标记过期只付最低费。
+            RuntimeCall::OnchainTransaction(onchain::pallet::Call::pay_invoice {
+                amount, ..
+            }) => signer_onchain_route(who, *amount),
+            RuntimeCall::OnchainTransaction(
+                onchain::pallet::Call::create_invoice { .. }
+                | onchain::pallet::Call::cancel_invoice { .. }
+                | onchain::pallet::Call::decline_invoice { .. }
+                | onchain::pallet::Call::expire_invoice { .. },
+            ) => signer_onchain_route(who, 0),
 
             // 个人多签不是机构；创建提案和管理员变更属于普通链上操作，只有 cast 才是投票。
             RuntimeCall::PersonalManage(personal_manage::pallet::Call::propose_create {
@@ -580,8 +617,15 @@ impl onchain::CallFeeRoute<AccountId, RuntimeCall, Balance> for RuntimeFeeRouter
             RuntimeCall::ResolutionIssuance(
                 resolution_issuance::pallet::Call::propose_issuance {
                     actor_cid_number, ..
+                }
+                | resolution_issuance::pallet::Call::propose_vested_issuance {
+                    actor_cid_number, ..
                 },
             ) => institution_onchain_route(who, actor_cid_number.as_slice()),
+            // 释放归属只缩小签名者指定账户的锁，不移动资金，按签名者最低手续费收取。
+            RuntimeCall::ResolutionIssuance(resolution_issuance::pallet::Call::claim_vested {
+                ..
+            }) => signer_onchain_route(who, 0),
             RuntimeCall::ResolutionDestroy(resolution_destroy::pallet::Call::propose_destroy {
                 actor_cid_number,
                 institution_account_id,
@@ -634,14 +678,15 @@ impl onchain::CallFeeRoute<AccountId, RuntimeCall, Balance> for RuntimeFeeRouter
                 },
             ) => institution_onchain_route(who, actor_cid_number.as_slice()),
 
-            // 广场内容域：只有发帖、订阅、取消、换档和创作者档位管理属于签名动作；
-            // 到期续费由 runtime 内部时间戳调度执行，不进入外部 call 路由。
+            // 广场内容域：发帖、订阅、取消、换档、创作者档位管理和吊销结算属于签名动作；
+            // 到期续费与创作者结算由 runtime 内部时间戳调度执行，不进入外部 call 路由。
             RuntimeCall::SquarePost(
                 square_post::pallet::Call::publish_post { .. }
                 | square_post::pallet::Call::subscribe { .. }
                 | square_post::pallet::Call::cancel { .. }
                 | square_post::pallet::Call::set_creator_plans { .. }
-                | square_post::pallet::Call::change_subscription_plan { .. },
+                | square_post::pallet::Call::change_subscription_plan { .. }
+                | square_post::pallet::Call::settle_revoked_creator { .. },
             )
             | RuntimeCall::FullnodeIssuance(
                 fullnode_issuance::pallet::Call::bind_reward_account { .. }
@@ -660,6 +705,14 @@ impl onchain::CallFeeRoute<AccountId, RuntimeCall, Balance> for RuntimeFeeRouter
                     actor_cid_number, ..
                 },
             ) => institution_onchain_route(who, actor_cid_number.as_slice()),
+            RuntimeCall::EmergencyPause(
+                emergency_pause::pallet::Call::propose_pause_action {
+                    actor_cid_number, ..
+                }
+                | emergency_pause::pallet::Call::fast_track_pause {
+                    actor_cid_number, ..
+                },
+            ) => institution_onchain_route(who, actor_cid_number.as_slice()),
             RuntimeCall::GrandpaKeyChange(
                 grandpakey_change::pallet::Call::propose_emergency_grandpa_key_recovery {
                     actor_cid_number,
@@ -677,10 +730,23 @@ impl onchain::CallFeeRoute<AccountId, RuntimeCall, Balance> for RuntimeFeeRouter
                 | legislation_yuan::pallet::Call::propose_amend_law {
                     actor_cid_number, ..
                 }
+                | legislation_yuan::pallet::Call::propose_amend_law_patch {
+                    actor_cid_number, ..
+                }
                 | legislation_yuan::pallet::Call::propose_repeal_law {
                     actor_cid_number, ..
+                }
+                | legislation_yuan::pallet::Call::propose_renew_law {
+                    actor_cid_number, ..
                 },
             ) => institution_onchain_route(who, actor_cid_number.as_slice()),
+            // 公民创制联署由公民本人签名付费;建案由达门槛的那一笔联署顺带完成。
+            RuntimeCall::LegislationYuan(
+                legislation_yuan::pallet::Call::open_petition { .. }
+                | legislation_yuan::pallet::Call::sign_petition { .. }
+                | legislation_yuan::pallet::Call::file_qualified_petition { .. }
+                | legislation_yuan::pallet::Call::clear_petition { .. },
+            ) => signer_onchain_route(who, 0),
 
             RuntimeCall::MultisigTransfer(multisig::pallet::Call::propose_transfer {
                 actor_cid_number,
@@ -804,6 +870,7 @@ impl onchain::CallFeeRoute<AccountId, RuntimeCall, Balance> for RuntimeFeeRouter
             | RuntimeCall::ElectionVote(_)
             | RuntimeCall::CitizenIdentity(_)
             | RuntimeCall::RuntimeUpgrade(_)
+            | RuntimeCall::EmergencyPause(_)
             | RuntimeCall::ResolutionDestroy(_)
             | RuntimeCall::GrandpaKeyChange(_)
             | RuntimeCall::PersonalManage(_)
@@ -1379,6 +1446,8 @@ impl public_manage::Config for Runtime {
     type MaxCidNumberLength = ConstU32<{ primitives::core_const::CID_NUMBER_MAX_BYTES }>;
     type MaxAccountNameLength = ConstU32<128>;
     type MaxInstitutionAccounts = ConstU32<16>;
+    type MaxConsecutiveTerms = ConstU32<2>;
+    type MaxAssignmentExpiryScan = ConstU32<32>;
     type WeightInfo = public_manage::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1403,6 +1472,9 @@ impl private_manage::Config for Runtime {
     type MaxCidNumberLength = ConstU32<{ primitives::core_const::CID_NUMBER_MAX_BYTES }>;
     type MaxAccountNameLength = ConstU32<128>;
     type MaxInstitutionAccounts = ConstU32<16>;
+    // 私权机构连任规则由其章程自定，链上不设上限。
+    type MaxConsecutiveTerms = ConstU32<0>;
+    type MaxAssignmentExpiryScan = ConstU32<32>;
     type WeightInfo = private_manage::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1691,6 +1763,14 @@ impl square_post::SquarePostCitizenIdentityProvider<AccountId>
         })
     }
 
+    fn is_cid_revoked(cid_number: &[u8]) -> bool {
+        let Ok(cid_number) = citizen_identity::CidNumberBound::try_from(cid_number.to_vec()) else {
+            return false;
+        };
+        citizen_identity::CidRegistry::<Runtime>::get(&cid_number)
+            .is_some_and(|record| record.status == citizen_identity::CidRecordStatus::Revoked)
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     #[allow(clippy::expect_used)]
     fn benchmark_seed_identity(account_id: &AccountId) -> Vec<u8> {
@@ -1704,6 +1784,19 @@ impl square_post::SquarePostCitizenIdentityProvider<AccountId>
     }
 }
 
+parameter_types! {
+    /// 广场订阅欠费宽限 7 天，期内均匀重试 3 次，最后一次落在宽限截止。
+    pub const SquarePostRenewalGracePeriodMs: u64 = 7 * 86_400_000;
+    pub const SquarePostMaxRenewalRetries: u32 = 3;
+    /// 创作者订阅款托管账户，只由 square-post 按收益台账转出。
+    pub SquarePostCreatorEarningsEscrow: AccountId =
+        frame_support::PalletId(*b"sqr/earn").into_account_truncating();
+    pub const SquarePostCreatorPayoutPeriod: square_post::BillingPeriod =
+        square_post::BillingPeriod::Monthly;
+    /// 创作者收益结算平台抽成 10%，与链上交易费同一分账。
+    pub const SquarePostCreatorPlatformFee: Perbill = Perbill::from_percent(10);
+}
+
 impl square_post::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type CitizenIdentity = RuntimeSquarePostCitizenIdentity;
@@ -1716,6 +1809,12 @@ impl square_post::Config for Runtime {
     type MaxSquareCidNumberLen = ConstU32<32>;
     type MaxSquareStorageReceiptIdLen = ConstU32<96>;
     type MaxSubscriptionRenewalsPerBlock = ConstU32<50_000>;
+    type RenewalGracePeriodMs = SquarePostRenewalGracePeriodMs;
+    type MaxRenewalRetries = SquarePostMaxRenewalRetries;
+    type CreatorEarningsEscrow = SquarePostCreatorEarningsEscrow;
+    type CreatorPayoutPeriod = SquarePostCreatorPayoutPeriod;
+    type CreatorPlatformFee = SquarePostCreatorPlatformFee;
+    type PlatformFeeRouter = OnchainExecutionFeeDistributor;
     type WeightInfo = square_post::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1731,10 +1830,26 @@ parameter_types! {
     pub const ResolutionIssuanceMaxAllocations: u32 = primitives::count_const::RESOLUTION_ISSUANCE_MAX_ALLOCATIONS;
     pub const ResolutionIssuanceMaxTotalIssuance: u128 = u128::MAX;
     pub const ResolutionIssuanceMaxSingleIssuance: u128 = 14_434_973_780_000;
+    /// 单个收款账户同时持有的决议发行归属计划上限。
+    pub const ResolutionIssuanceMaxVestingSchedules: u32 = 16;
     /// Runtime 升级治理提案备注最大长度。
     pub const RuntimeUpgradeMaxReasonLen: u32 = 1024;
     /// Runtime wasm 最大长度（字节）。
     pub const RuntimeUpgradeMaxCodeSize: u32 = 5 * 1024 * 1024;
+    /// 紧急暂停：同时生效的暂停目标上限。
+    pub const EmergencyPauseMaxPausedTargets: u32 = 32;
+    /// 联合投票单次暂停/续期最长 90 天。
+    pub const EmergencyPauseMaxDuration: BlockNumber =
+        (90 * primitives::pow_const::BLOCKS_PER_DAY) as BlockNumber;
+    /// 快速通道门槛：国家储委会委员超过三分之二。
+    pub const EmergencyPauseFastTrackThreshold: u32 =
+        primitives::count_const::NRC_ADMIN_COUNT * 2 / 3 + 1;
+    /// 快速通道暂停固定 3 天，到期自动解除。
+    pub const EmergencyPauseFastTrackDuration: BlockNumber =
+        (3 * primitives::pow_const::BLOCKS_PER_DAY) as BlockNumber;
+    /// 快速通道确认窗口 1 天。
+    pub const EmergencyPauseFastTrackApprovalWindow: BlockNumber =
+        primitives::pow_const::BLOCKS_PER_DAY as BlockNumber;
     /// 管理员治理：单个注册机构账户管理员上限。
     ///
     /// 物理 BoundedVec 上限必须覆盖机构账户 1989 人场景；个人账户
@@ -2500,6 +2615,7 @@ impl resolution_issuance::Config for Runtime {
     type MaxAllocations = ResolutionIssuanceMaxAllocations;
     type MaxTotalIssuance = ResolutionIssuanceMaxTotalIssuance;
     type MaxSingleIssuance = ResolutionIssuanceMaxSingleIssuance;
+    type MaxVestingSchedules = ResolutionIssuanceMaxVestingSchedules;
 }
 
 impl runtime_upgrade::Config for Runtime {
@@ -2515,6 +2631,20 @@ impl runtime_upgrade::Config for Runtime {
     type WeightInfo = runtime_upgrade::weights::SubstrateWeight<Runtime>;
 }
 
+impl emergency_pause::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type ProposeOrigin = EnsureJointProposer;
+    type JointVoteEngine = JointVote;
+    type InstitutionRoleAuthorization = public_manage::Pallet<Runtime>;
+    type MaxReasonLen = RuntimeUpgradeMaxReasonLen;
+    type MaxPausedTargets = EmergencyPauseMaxPausedTargets;
+    type MaxPauseDuration = EmergencyPauseMaxDuration;
+    type FastTrackThreshold = EmergencyPauseFastTrackThreshold;
+    type FastTrackDuration = EmergencyPauseFastTrackDuration;
+    type FastTrackApprovalWindow = EmergencyPauseFastTrackApprovalWindow;
+    type WeightInfo = emergency_pause::weights::SubstrateWeight<Runtime>;
+}
+
 pub struct RuntimeSetCodeExecutor;
 
 impl runtime_upgrade::RuntimeCodeExecutor for RuntimeSetCodeExecutor {
@@ -2558,6 +2688,12 @@ parameter_types! {
     pub const LegislationMaxChaptersPerLaw: u32 = 50;
     pub const LegislationMaxLawsPerScope: u32 = 1000;
     pub const LegislationMaxPendingActivations: u32 = 100;
+    pub const LegislationMaxSunsetsPerBlock: u32 = 50; // 每块处理的到期日落排程条目
+    pub const LegislationMaxAmendmentOperations: u32 = 64; // 单个条文补丁操作数
+    pub const LegislationPetitionThreshold: sp_runtime::Permill = sp_runtime::Permill::from_percent(1); // 作用域有效投票人口的 1%
+    pub const LegislationPetitionDuration: BlockNumber = primitives::count_const::PETITION_DURATION_BLOCKS;
+    pub const LegislationMaxPetitionCleanup: u32 = 500;
+    pub const LegislationMaxOpenPetitionsPerCitizen: u32 = 3; // 每个公民未建案联署全文上限
 }
 
 impl legislation_yuan::Config for Runtime {
@@ -2574,6 +2710,12 @@ impl legislation_yuan::Config for Runtime {
     type MaxChaptersPerLaw = LegislationMaxChaptersPerLaw;
     type MaxLawsPerScope = LegislationMaxLawsPerScope;
     type MaxPendingActivations = LegislationMaxPendingActivations;
+    type MaxSunsetsPerBlock = LegislationMaxSunsetsPerBlock;
+    type MaxAmendmentOperations = LegislationMaxAmendmentOperations;
+    type PetitionThreshold = LegislationPetitionThreshold;
+    type PetitionDuration = LegislationPetitionDuration;
+    type MaxPetitionCleanup = LegislationMaxPetitionCleanup;
+    type MaxOpenPetitionsPerCitizen = LegislationMaxOpenPetitionsPerCitizen;
     type WeightInfo = ();
 }
 
@@ -2606,6 +2748,13 @@ impl votingengine::JointVoteResultCallback for RuntimeJointVoteResultCallback {
                 );
             }
 
+            if emergency_pause::Pallet::<Runtime>::owns_proposal(vote_proposal_id) {
+                return <EmergencyPause as votingengine::JointVoteResultCallback>::on_joint_vote_finalized(
+                    vote_proposal_id,
+                    approved,
+                );
+            }
+
             Err(sp_runtime::DispatchError::Other(
                 "joint vote proposal not found in any module",
             ))
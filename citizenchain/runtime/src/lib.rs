@@ -227,7 +227,7 @@ pub type UncheckedExtrinsic =
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, TxExtension>;
 
 /// Runtime upgrade migrations 集合；元组按执行顺序排列。
-pub type Migrations = ();
+pub type Migrations = (square_post::migrations::MigrateToV1<Runtime>,);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -395,6 +395,10 @@ mod runtime {
     // 广场动态发布索引模块：只记录 post_id/content_hash/storage_receipt_id 等链上索引。
     #[runtime::pallet_index(34)]
     pub type SquarePost = square_post;
+
+    // 紧急暂停（熔断）模块：联合投票或 NRC 委员快速通道按 pallet/call 名限时暂停外部调用。
+    #[runtime::pallet_index(35)]
+    pub type EmergencyPause = emergency_pause;
 }
 
 #[cfg(test)]
@@ -439,6 +439,7 @@ fn joint_vote_callback_routes_to_resolution_issuance_and_executes() {
             reason: b"runtime-integration".to_vec(),
             total_amount,
             allocations,
+            vesting: Vec::new(),
         };
         let mut encoded = Vec::from(resolution_issuance::MODULE_TAG);
         encoded.extend_from_slice(&data.encode());
@@ -627,6 +628,54 @@ fn runtime_fee_router_covers_free_onchain_vote_institution_and_reject_paths() {
             }
         );
 
+        let pay_invoice_call =
+            RuntimeCall::OnchainTransaction(onchain::pallet::Call::pay_invoice {
+                invoice_id: 0,
+                amount: 789,
+            });
+        assert_eq!(
+            <RuntimeFeeRouter as CallFeeRoute<AccountId, RuntimeCall, Balance>>::fee_route(
+                &who,
+                &pay_invoice_call,
+            ),
+            primitives::fee_policy::FeeRoute::Onchain {
+                transaction_amount: 789,
+                payer_account_id: who.clone(),
+            }
+        );
+        let create_invoice_call =
+            RuntimeCall::OnchainTransaction(onchain::pallet::Call::create_invoice {
+                payer: None,
+                amount: 789,
+                due_block: 100,
+                memo_hash: [9u8; 32],
+            });
+        assert_eq!(
+            <RuntimeFeeRouter as CallFeeRoute<AccountId, RuntimeCall, Balance>>::fee_route(
+                &who,
+                &create_invoice_call,
+            ),
+            primitives::fee_policy::FeeRoute::Onchain {
+                transaction_amount: 0,
+                payer_account_id: who.clone(),
+            }
+        );
+
+        let claim_vested_call =
+            RuntimeCall::ResolutionIssuance(resolution_issuance::pallet::Call::claim_vested {
+                recipient_account_id: who.clone(),
+            });
+        assert_eq!(
+            <RuntimeFeeRouter as CallFeeRoute<AccountId, RuntimeCall, Balance>>::fee_route(
+                &who,
+                &claim_vested_call,
+            ),
+            primitives::fee_policy::FeeRoute::Onchain {
+                transaction_amount: 0,
+                payer_account_id: who.clone(),
+            }
+        );
+
         let internal_vote_call = RuntimeCall::InternalVote(internal_vote::pallet::Call::cast {
             proposal_id: 1,
             ticket_claim: internal_vote::InternalVoteTicketClaim::Personal,
@@ -698,6 +747,37 @@ fn runtime_fee_router_covers_free_onchain_vote_institution_and_reject_paths() {
             );
         assert_eq!(unauthorized, primitives::fee_policy::FeeRoute::Reject);
 
+        let fast_track_call =
+            RuntimeCall::EmergencyPause(emergency_pause::pallet::Call::fast_track_pause {
+                actor_cid_number: CHINA_CB[0]
+                    .cid_number
+                    .as_bytes()
+                    .to_vec()
+                    .try_into()
+                    .expect("NRC CID fits"),
+                actor_role_code: primitives::governance_skeleton::ROLE_CODE_COMMITTEE_MEMBER
+                    .to_vec()
+                    .try_into()
+                    .expect("committee role fits"),
+                target: emergency_pause::PauseTarget {
+                    pallet_name: b"MultisigTransfer"
+                        .to_vec()
+                        .try_into()
+                        .expect("pallet name fits"),
+                    call_name: None,
+                },
+            });
+        assert_eq!(
+            <RuntimeFeeRouter as CallFeeRoute<AccountId, RuntimeCall, Balance>>::fee_route(
+                &nrc_admin,
+                &fast_track_call,
+            ),
+            primitives::fee_policy::FeeRoute::Onchain {
+                transaction_amount: 0,
+                payer_account_id: AccountId::new(CHINA_CB[0].fee_account),
+            }
+        );
+
         let issuance_placeholder =
             RuntimeCall::OnchainIssuance(onchain_issuance::pallet::Call::propose_mint {
                 actor_cid_number: CHINA_CB[0]
@@ -900,111 +980,178 @@ fn multisig_reserved_checker_rejects_stake_and_fee_accounts() {
 
 #[test]
 fn runtime_call_filter_blocks_disabled_and_low_level_calls() {
-    let stake = AccountId::new(primitives::cid::china::china_ch::CHINA_CH[0].stake_account);
-    let dst = AccountId::new([9u8; 32]);
-
-    let blocked_by_id = RuntimeCall::Balances(pallet_balances::Call::force_transfer {
-        source: sp_runtime::MultiAddress::Id(stake),
-        dest: sp_runtime::MultiAddress::Id(dst.clone()),
-        value: 1,
-    });
-    assert!(!RuntimeCallFilter::contains(&blocked_by_id));
+    // 放行调用还要查询紧急暂停登记表，需要存储环境。
+    new_test_ext().execute_with(|| {
+        let stake = AccountId::new(primitives::cid::china::china_ch::CHINA_CH[0].stake_account);
+        let dst = AccountId::new([9u8; 32]);
 
-    let stake_raw = primitives::cid::china::china_ch::CHINA_CH[0].stake_account;
-    let blocked_by_32 = RuntimeCall::Balances(pallet_balances::Call::force_transfer {
-        source: sp_runtime::MultiAddress::Address32(stake_raw),
-        dest: sp_runtime::MultiAddress::Id(dst.clone()),
-        value: 1,
-    });
-    assert!(!RuntimeCallFilter::contains(&blocked_by_32));
+        let blocked_by_id = RuntimeCall::Balances(pallet_balances::Call::force_transfer {
+            source: sp_runtime::MultiAddress::Id(stake),
+            dest: sp_runtime::MultiAddress::Id(dst.clone()),
+            value: 1,
+        });
+        assert!(!RuntimeCallFilter::contains(&blocked_by_id));
 
-    let blocked_by_raw = RuntimeCall::Balances(pallet_balances::Call::force_transfer {
-        source: sp_runtime::MultiAddress::Raw(stake_raw.to_vec()),
-        dest: sp_runtime::MultiAddress::Id(dst.clone()),
-        value: 1,
-    });
-    assert!(!RuntimeCallFilter::contains(&blocked_by_raw));
+        let stake_raw = primitives::cid::china::china_ch::CHINA_CH[0].stake_account;
+        let blocked_by_32 = RuntimeCall::Balances(pallet_balances::Call::force_transfer {
+            source: sp_runtime::MultiAddress::Address32(stake_raw),
+            dest: sp_runtime::MultiAddress::Id(dst.clone()),
+            value: 1,
+        });
+        assert!(!RuntimeCallFilter::contains(&blocked_by_32));
 
-    let blocked_from_regular_account =
-        RuntimeCall::Balances(pallet_balances::Call::force_transfer {
-            source: sp_runtime::MultiAddress::Id(AccountId::new([8u8; 32])),
-            dest: sp_runtime::MultiAddress::Id(dst),
+        let blocked_by_raw = RuntimeCall::Balances(pallet_balances::Call::force_transfer {
+            source: sp_runtime::MultiAddress::Raw(stake_raw.to_vec()),
+            dest: sp_runtime::MultiAddress::Id(dst.clone()),
             value: 1,
         });
-    assert!(!RuntimeCallFilter::contains(&blocked_from_regular_account));
+        assert!(!RuntimeCallFilter::contains(&blocked_by_raw));
 
-    let blocked_force_unreserve = RuntimeCall::Balances(pallet_balances::Call::force_unreserve {
-        who: sp_runtime::MultiAddress::Id(AccountId::new(
-            primitives::cid::china::china_ch::CHINA_CH[0].stake_account,
-        )),
-        amount: 1,
-    });
-    assert!(!RuntimeCallFilter::contains(&blocked_force_unreserve));
+        let blocked_from_regular_account =
+            RuntimeCall::Balances(pallet_balances::Call::force_transfer {
+                source: sp_runtime::MultiAddress::Id(AccountId::new([8u8; 32])),
+                dest: sp_runtime::MultiAddress::Id(dst),
+                value: 1,
+            });
+        assert!(!RuntimeCallFilter::contains(&blocked_from_regular_account));
+
+        let blocked_force_unreserve =
+            RuntimeCall::Balances(pallet_balances::Call::force_unreserve {
+                who: sp_runtime::MultiAddress::Id(AccountId::new(
+                    primitives::cid::china::china_ch::CHINA_CH[0].stake_account,
+                )),
+                amount: 1,
+            });
+        assert!(!RuntimeCallFilter::contains(&blocked_force_unreserve));
+
+        let blocked_force_set_balance =
+            RuntimeCall::Balances(pallet_balances::Call::force_set_balance {
+                who: sp_runtime::MultiAddress::Id(AccountId::new(
+                    primitives::cid::china::china_ch::CHINA_CH[0].stake_account,
+                )),
+                new_free: 1,
+            });
+        assert!(!RuntimeCallFilter::contains(&blocked_force_set_balance));
 
-    let blocked_force_set_balance =
-        RuntimeCall::Balances(pallet_balances::Call::force_set_balance {
-            who: sp_runtime::MultiAddress::Id(AccountId::new(
-                primitives::cid::china::china_ch::CHINA_CH[0].stake_account,
-            )),
-            new_free: 1,
-        });
-    assert!(!RuntimeCallFilter::contains(&blocked_force_set_balance));
+        let blocked_transfer_allow_death =
+            RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+                dest: sp_runtime::MultiAddress::Id(AccountId::new([7u8; 32])),
+                value: 1,
+            });
+        assert!(!RuntimeCallFilter::contains(&blocked_transfer_allow_death));
+
+        let blocked_transfer_keep_alive =
+            RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+                dest: sp_runtime::MultiAddress::Id(AccountId::new([7u8; 32])),
+                value: 1,
+            });
+        assert!(!RuntimeCallFilter::contains(&blocked_transfer_keep_alive));
 
-    let blocked_transfer_allow_death =
-        RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+        let blocked_transfer_all = RuntimeCall::Balances(pallet_balances::Call::transfer_all {
             dest: sp_runtime::MultiAddress::Id(AccountId::new([7u8; 32])),
-            value: 1,
+            keep_alive: true,
         });
-    assert!(!RuntimeCallFilter::contains(&blocked_transfer_allow_death));
+        assert!(!RuntimeCallFilter::contains(&blocked_transfer_all));
 
-    let blocked_transfer_keep_alive =
-        RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
-            dest: sp_runtime::MultiAddress::Id(AccountId::new([7u8; 32])),
+        let blocked_burn = RuntimeCall::Balances(pallet_balances::Call::burn {
             value: 1,
+            keep_alive: true,
         });
-    assert!(!RuntimeCallFilter::contains(&blocked_transfer_keep_alive));
+        assert!(!RuntimeCallFilter::contains(&blocked_burn));
 
-    let blocked_transfer_all = RuntimeCall::Balances(pallet_balances::Call::transfer_all {
-        dest: sp_runtime::MultiAddress::Id(AccountId::new([7u8; 32])),
-        keep_alive: true,
-    });
-    assert!(!RuntimeCallFilter::contains(&blocked_transfer_all));
+        let remark =
+            frame_support::BoundedVec::<u8, frame_support::traits::ConstU32<99>>::try_from(
+                b"ordinary transfer remark".to_vec(),
+            )
+            .expect("remark should fit");
+        let allowed_onchain_transfer =
+            RuntimeCall::OnchainTransaction(onchain::pallet::Call::transfer_with_remark {
+                beneficiary_account_id: AccountId::new([7u8; 32]),
+                amount: 1,
+                remark,
+            });
+        assert!(RuntimeCallFilter::contains(&allowed_onchain_transfer));
+        let allowed_invoice_payment =
+            RuntimeCall::OnchainTransaction(onchain::pallet::Call::pay_invoice {
+                invoice_id: 0,
+                amount: 1,
+            });
+        assert!(RuntimeCallFilter::contains(&allowed_invoice_payment));
 
-    let blocked_burn = RuntimeCall::Balances(pallet_balances::Call::burn {
-        value: 1,
-        keep_alive: true,
+        let disabled_issuance =
+            RuntimeCall::OnchainIssuance(onchain_issuance::pallet::Call::propose_mint {
+                actor_cid_number: CHINA_CB[0]
+                    .cid_number
+                    .as_bytes()
+                    .to_vec()
+                    .try_into()
+                    .expect("NRC CID fits"),
+                actor_role_code: primitives::governance_skeleton::ROLE_CODE_COMMITTEE_MEMBER
+                    .to_vec()
+                    .try_into()
+                    .expect("committee role fits"),
+                asset_id: 1,
+                to_account_id: AccountId::new([6u8; 32]),
+                amount: 100,
+            });
+        assert!(!RuntimeCallFilter::contains(&disabled_issuance));
     });
-    assert!(!RuntimeCallFilter::contains(&blocked_burn));
-
-    let remark = frame_support::BoundedVec::<u8, frame_support::traits::ConstU32<99>>::try_from(
-        b"ordinary transfer remark".to_vec(),
-    )
-    .expect("remark should fit");
-    let allowed_onchain_transfer =
-        RuntimeCall::OnchainTransaction(onchain::pallet::Call::transfer_with_remark {
-            beneficiary_account_id: AccountId::new([7u8; 32]),
+}
+
+/// 暂停登记表在静态放行之后生效：被暂停的调用过滤器拒绝、交易池判无效，受保护 pallet 永远放行。
+#[test]
+fn emergency_pause_registry_blocks_calls_until_expiry() {
+    use emergency_pause::{PauseRecord, PauseSource, PauseTarget};
+    use onchain::CallFeeRoute;
+
+    let target = |pallet_name: &[u8], call_name: Option<&[u8]>| PauseTarget {
+        pallet_name: pallet_name.to_vec().try_into().expect("pallet name fits"),
+        call_name: call_name.map(|name| name.to_vec().try_into().expect("call name fits")),
+    };
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let who = AccountId::new([7u8; 32]);
+        let pay_invoice = RuntimeCall::OnchainTransaction(onchain::pallet::Call::pay_invoice {
+            invoice_id: 0,
             amount: 1,
-            remark,
         });
-    assert!(RuntimeCallFilter::contains(&allowed_onchain_transfer));
+        let create_invoice =
+            RuntimeCall::OnchainTransaction(onchain::pallet::Call::create_invoice {
+                payer: None,
+                amount: 1,
+                due_block: 100,
+                memo_hash: [0u8; 32],
+            });
+        assert!(RuntimeCallFilter::contains(&pay_invoice));
 
-    let disabled_issuance =
-        RuntimeCall::OnchainIssuance(onchain_issuance::pallet::Call::propose_mint {
-            actor_cid_number: CHINA_CB[0]
-                .cid_number
-                .as_bytes()
-                .to_vec()
-                .try_into()
-                .expect("NRC CID fits"),
-            actor_role_code: primitives::governance_skeleton::ROLE_CODE_COMMITTEE_MEMBER
-                .to_vec()
-                .try_into()
-                .expect("committee role fits"),
-            asset_id: 1,
-            to_account_id: AccountId::new([6u8; 32]),
-            amount: 100,
-        });
-    assert!(!RuntimeCallFilter::contains(&disabled_issuance));
+        let record = PauseRecord {
+            paused_at: 1,
+            expires_at: 10,
+            source: PauseSource::FastTrack,
+        };
+        emergency_pause::PausedTargets::<Runtime>::insert(
+            target(b"OnchainTransaction", Some(b"pay_invoice")),
+            record.clone(),
+        );
+        assert!(!RuntimeCallFilter::contains(&pay_invoice));
+        assert_eq!(
+            <RuntimeFeeRouter as CallFeeRoute<AccountId, RuntimeCall, Balance>>::fee_route(
+                &who,
+                &pay_invoice,
+            ),
+            primitives::fee_policy::FeeRoute::Reject
+        );
+        assert!(RuntimeCallFilter::contains(&create_invoice));
+
+        // 即使登记表被异常写入，受保护 pallet 也不会被拦截。
+        emergency_pause::PausedTargets::<Runtime>::insert(target(b"System", None), record);
+        assert!(RuntimeCallFilter::contains(&RuntimeCall::System(
+            frame_system::Call::remark { remark: Vec::new() }
+        )));
+
+        System::set_block_number(10);
+        assert!(RuntimeCallFilter::contains(&pay_invoice));
+    });
 }
 
 #[test]
@@ -16,25 +16,28 @@ use sp_runtime::{
 };
 use sp_std::{marker::PhantomData, prelude::*};
 
-/// 链上资金交易 pallet：承载普通转账备注调用和统一手续费审计事件。
+/// 链上资金交易 pallet：承载普通转账备注调用、链上账单(发票)和统一手续费审计事件。
 #[frame_support::pallet]
 pub mod pallet {
     use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
     use frame_support::{
         pallet_prelude::*,
-        traits::{Currency, ExistenceRequirement, Get},
+        traits::{BalanceStatus, Currency, ExistenceRequirement, Get, ReservableCurrency},
     };
     use frame_system::pallet_prelude::*;
     use scale_info::TypeInfo;
-    use sp_runtime::{traits::Zero, Perbill, RuntimeDebug};
+    use sp_runtime::{
+        traits::{Saturating, Zero},
+        Perbill, RuntimeDebug,
+    };
 
     #[pallet::pallet]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
     pub trait Config: frame_system::Config<RuntimeEvent: From<Event<Self>>> {
-        /// 普通链上转账使用的余额系统。
-        type Currency: Currency<Self::AccountId>;
+        /// 普通链上转账使用的余额系统；账单押金以 reserve 锁定。
+        type Currency: ReservableCurrency<Self::AccountId>;
 
         /// 普通转账备注最大 UTF-8 字节数。
         #[pallet::constant]
@@ -56,6 +59,19 @@ pub mod pallet {
         /// 实际投票统一费(分/票)。
         #[pallet::constant]
         type VoteFlatFee: Get<u128>;
+
+        /// 单个开单方同时未结清的账单上限，防止开单方无限占用链上存储。
+        #[pallet::constant]
+        type MaxOpenInvoicesPerIssuer: Get<u32>;
+
+        /// 每张账单开单时锁定的押金；结清、撤销或过期时退还开单方，
+        /// 被指定付款方拒付时转给该付款方。
+        #[pallet::constant]
+        type InvoiceDeposit: Get<BalanceOf<Self>>;
+
+        /// 账单有效期上限(区块)：`due_block - 开单区块` 不得超过该值。
+        #[pallet::constant]
+        type MaxInvoiceDuration: Get<BlockNumberFor<Self>>;
     }
 
     /// 普通转账金额类型。
@@ -65,6 +81,60 @@ pub mod pallet {
     /// 普通转账备注，编码为 SCALE BoundedVec<u8>。
     pub type TransferRemarkOf<T> = BoundedVec<u8, <T as Config>::MaxTransferRemarkLen>;
 
+    /// 账单编号，链上自增，从 0 开始。
+    pub type InvoiceId = u64;
+
+    /// 未结清的链上账单。
+    ///
+    /// 只有未结清账单留在存储里；结清、撤销、拒付、过期后立即删除，
+    /// 完整生命周期以事件为准，由 OnChina 索引器投影成账单状态。
+    #[derive(
+        Clone,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Eq,
+        PartialEq,
+        RuntimeDebug,
+        TypeInfo,
+        MaxEncodedLen,
+    )]
+    pub struct Invoice<AccountId, Balance, BlockNumber> {
+        /// 开单方，也是全部付款的收款账户。
+        pub issuer: AccountId,
+        /// 指定付款方；为空时任何账户都可付款。
+        pub payer: Option<AccountId>,
+        /// 应收总额(分)。
+        pub amount: Balance,
+        /// 已收金额(分)，允许分多次付款。
+        pub paid: Balance,
+        /// 到期区块：该区块及以后不再接受付款。
+        pub due_block: BlockNumber,
+        /// 账单备注/商品明细的哈希，明文由开单方链下保存。
+        pub memo_hash: [u8; 32],
+        /// 开单区块。
+        pub created_at: BlockNumber,
+        /// 开单时锁定的押金；按开单时的配置记录，配置调整不影响已开账单的退还。
+        pub deposit: Balance,
+    }
+
+    /// 按 runtime 配置实例化的账单。
+    pub type InvoiceOf<T> =
+        Invoice<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+    /// 下一个账单编号。
+    #[pallet::storage]
+    pub type NextInvoiceId<T> = StorageValue<_, InvoiceId, ValueQuery>;
+
+    /// 未结清账单。
+    #[pallet::storage]
+    pub type Invoices<T: Config> = StorageMap<_, Blake2_128Concat, InvoiceId, InvoiceOf<T>>;
+
+    /// 开单方当前未结清账单数。
+    #[pallet::storage]
+    pub type OpenInvoiceCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
     /// 手续费份额销毁原因，供链上事件审计和运维聚合。
     #[derive(
         Clone,
@@ -108,6 +178,44 @@ pub mod pallet {
             amount: BalanceOf<T>,
             remark: TransferRemarkOf<T>,
         },
+        /// 账单已开出，开单方押金已锁定。
+        InvoiceCreated {
+            invoice_id: InvoiceId,
+            issuer: T::AccountId,
+            payer: Option<T::AccountId>,
+            amount: BalanceOf<T>,
+            due_block: BlockNumberFor<T>,
+            memo_hash: [u8; 32],
+            deposit: BalanceOf<T>,
+        },
+        /// 账单收到一笔付款，转账与账单编号在同一事件里绑定。
+        InvoicePaid {
+            invoice_id: InvoiceId,
+            payer_account_id: T::AccountId,
+            issuer: T::AccountId,
+            amount: BalanceOf<T>,
+            paid_total: BalanceOf<T>,
+            remaining: BalanceOf<T>,
+        },
+        /// 账单已全额结清并移出存储。
+        InvoiceSettled { invoice_id: InvoiceId },
+        /// 开单方在到期前撤销了账单；已收款项不退回。
+        InvoiceCancelled {
+            invoice_id: InvoiceId,
+            paid: BalanceOf<T>,
+        },
+        /// 账单到期未结清，已标记过期并移出存储。
+        InvoiceExpired {
+            invoice_id: InvoiceId,
+            paid: BalanceOf<T>,
+        },
+        /// 指定付款方拒付账单；开单方押金转给该付款方，账单移出存储。
+        InvoiceDeclined {
+            invoice_id: InvoiceId,
+            payer_account_id: T::AccountId,
+            paid: BalanceOf<T>,
+            deposit: BalanceOf<T>,
+        },
     }
 
     #[pallet::error]
@@ -118,6 +226,30 @@ pub mod pallet {
         SelfTransferNotAllowed,
         /// 余额模块拒绝转账。
         TransferFailed,
+        /// 账单不存在或已结清/撤销/过期。
+        InvoiceNotFound,
+        /// 到期区块必须晚于当前区块。
+        InvoiceDueInPast,
+        /// 账单已到期，不再接受付款或撤销。
+        InvoiceExpired,
+        /// 账单尚未到期，不能标记过期。
+        InvoiceNotDue,
+        /// 付款金额超过账单剩余应付额。
+        InvoiceOverpaid,
+        /// 账单指定了付款方，当前签名账户不是该付款方。
+        NotInvoicePayer,
+        /// 只有开单方可以撤销账单。
+        NotInvoiceIssuer,
+        /// 开单方未结清账单数已达上限。
+        TooManyOpenInvoices,
+        /// 账单编号耗尽。
+        InvoiceIdOverflow,
+        /// 到期区块超出账单有效期上限。
+        InvoiceDurationTooLong,
+        /// 开单方可用余额不足以锁定账单押金。
+        InsufficientInvoiceDeposit,
+        /// 账单未指定付款方或当前签名账户不是指定付款方，不能拒付。
+        NotInvoicePayerToDecline,
     }
 
     #[pallet::call]
@@ -154,6 +286,207 @@ pub mod pallet {
             });
             Ok(())
         }
+
+        /// 开出一张链上账单；开单方即收款方，`payer` 为空表示任何人可付。
+        ///
+        /// 开单方须锁定 `InvoiceDeposit` 押金，账单关闭时才释放；指定付款方可拒付
+        /// 未经请求的账单并获得该押金，以此抑制向任意账户滥发账单。
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(4, 4))]
+        pub fn create_invoice(
+            origin: OriginFor<T>,
+            payer: Option<T::AccountId>,
+            amount: BalanceOf<T>,
+            due_block: BlockNumberFor<T>,
+            memo_hash: [u8; 32],
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            ensure!(
+                payer.as_ref() != Some(&issuer),
+                Error::<T>::SelfTransferNotAllowed
+            );
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(due_block > now, Error::<T>::InvoiceDueInPast);
+            ensure!(
+                due_block.saturating_sub(now) <= T::MaxInvoiceDuration::get(),
+                Error::<T>::InvoiceDurationTooLong
+            );
+            let open = OpenInvoiceCount::<T>::get(&issuer);
+            ensure!(
+                open < T::MaxOpenInvoicesPerIssuer::get(),
+                Error::<T>::TooManyOpenInvoices
+            );
+            let invoice_id = NextInvoiceId::<T>::get();
+            let next = invoice_id
+                .checked_add(1)
+                .ok_or(Error::<T>::InvoiceIdOverflow)?;
+            let deposit = T::InvoiceDeposit::get();
+            T::Currency::reserve(&issuer, deposit)
+                .map_err(|_| Error::<T>::InsufficientInvoiceDeposit)?;
+
+            Invoices::<T>::insert(
+                invoice_id,
+                Invoice {
+                    issuer: issuer.clone(),
+                    payer: payer.clone(),
+                    amount,
+                    paid: Zero::zero(),
+                    due_block,
+                    memo_hash,
+                    created_at: now,
+                    deposit,
+                },
+            );
+            NextInvoiceId::<T>::put(next);
+            OpenInvoiceCount::<T>::insert(&issuer, open.saturating_add(1));
+
+            Self::deposit_event(Event::InvoiceCreated {
+                invoice_id,
+                issuer,
+                payer,
+                amount,
+                due_block,
+                memo_hash,
+                deposit,
+            });
+            Ok(())
+        }
+
+        /// 向账单付款：资金直接转给开单方，允许部分付款，付满即结清。
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+        pub fn pay_invoice(
+            origin: OriginFor<T>,
+            invoice_id: InvoiceId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            let mut invoice = Invoices::<T>::get(invoice_id).ok_or(Error::<T>::InvoiceNotFound)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() < invoice.due_block,
+                Error::<T>::InvoiceExpired
+            );
+            if let Some(payer) = invoice.payer.as_ref() {
+                ensure!(payer == &from, Error::<T>::NotInvoicePayer);
+            }
+            ensure!(from != invoice.issuer, Error::<T>::SelfTransferNotAllowed);
+            let remaining = invoice.amount.saturating_sub(invoice.paid);
+            ensure!(amount <= remaining, Error::<T>::InvoiceOverpaid);
+
+            T::Currency::transfer(
+                &from,
+                &invoice.issuer,
+                amount,
+                ExistenceRequirement::KeepAlive,
+            )
+            .map_err(|_| Error::<T>::TransferFailed)?;
+
+            invoice.paid = invoice.paid.saturating_add(amount);
+            let remaining = invoice.amount.saturating_sub(invoice.paid);
+            Self::deposit_event(Event::InvoicePaid {
+                invoice_id,
+                payer_account_id: from,
+                issuer: invoice.issuer.clone(),
+                amount,
+                paid_total: invoice.paid,
+                remaining,
+            });
+            if remaining.is_zero() {
+                Self::close_invoice(invoice_id, &invoice);
+                Self::deposit_event(Event::InvoiceSettled { invoice_id });
+            } else {
+                Invoices::<T>::insert(invoice_id, invoice);
+            }
+            Ok(())
+        }
+
+        /// 开单方在到期前撤销未结清账单；已收的部分款项不由链上退回。
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+        pub fn cancel_invoice(origin: OriginFor<T>, invoice_id: InvoiceId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let invoice = Invoices::<T>::get(invoice_id).ok_or(Error::<T>::InvoiceNotFound)?;
+            ensure!(who == invoice.issuer, Error::<T>::NotInvoiceIssuer);
+            ensure!(
+                frame_system::Pallet::<T>::block_number() < invoice.due_block,
+                Error::<T>::InvoiceExpired
+            );
+
+            Self::close_invoice(invoice_id, &invoice);
+            Self::deposit_event(Event::InvoiceCancelled {
+                invoice_id,
+                paid: invoice.paid,
+            });
+            Ok(())
+        }
+
+        /// 把到期未结清的账单标记为过期并释放存储；任何账户都可调用。
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+        pub fn expire_invoice(origin: OriginFor<T>, invoice_id: InvoiceId) -> DispatchResult {
+            ensure_signed(origin)?;
+            let invoice = Invoices::<T>::get(invoice_id).ok_or(Error::<T>::InvoiceNotFound)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= invoice.due_block,
+                Error::<T>::InvoiceNotDue
+            );
+
+            Self::close_invoice(invoice_id, &invoice);
+            Self::deposit_event(Event::InvoiceExpired {
+                invoice_id,
+                paid: invoice.paid,
+            });
+            Ok(())
+        }
+
+        /// 指定付款方在到期前拒付账单；开单方押金转给付款方作为滥发补偿，已付款项不退回。
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(4, 4))]
+        pub fn decline_invoice(origin: OriginFor<T>, invoice_id: InvoiceId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let invoice = Invoices::<T>::get(invoice_id).ok_or(Error::<T>::InvoiceNotFound)?;
+            ensure!(
+                invoice.payer.as_ref() == Some(&who),
+                Error::<T>::NotInvoicePayerToDecline
+            );
+            ensure!(
+                frame_system::Pallet::<T>::block_number() < invoice.due_block,
+                Error::<T>::InvoiceExpired
+            );
+
+            // 押金不足部分(理论上不会发生)由 repatriate 返回，按实转金额记入事件。
+            let unmoved = T::Currency::repatriate_reserved(
+                &invoice.issuer,
+                &who,
+                invoice.deposit,
+                BalanceStatus::Free,
+            )?;
+            Self::remove_invoice(invoice_id, &invoice.issuer);
+            Self::deposit_event(Event::InvoiceDeclined {
+                invoice_id,
+                payer_account_id: who,
+                paid: invoice.paid,
+                deposit: invoice.deposit.saturating_sub(unmoved),
+            });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// 关闭账单并把押金退还开单方。
+        fn close_invoice(invoice_id: InvoiceId, invoice: &InvoiceOf<T>) {
+            T::Currency::unreserve(&invoice.issuer, invoice.deposit);
+            Self::remove_invoice(invoice_id, &invoice.issuer);
+        }
+
+        fn remove_invoice(invoice_id: InvoiceId, issuer: &T::AccountId) {
+            Invoices::<T>::remove(invoice_id);
+            OpenInvoiceCount::<T>::mutate_exists(issuer, |count| {
+                *count = count.and_then(|c| c.checked_sub(1)).filter(|c| *c > 0);
+            });
+        }
     }
 }
 
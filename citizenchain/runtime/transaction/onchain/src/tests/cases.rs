@@ -69,6 +69,287 @@ fn transfer_with_remark_rejects_invalid_transfer_and_caps_remark_bytes() {
     });
 }
 
+#[test]
+fn invoice_accepts_partial_payments_and_settles_to_issuer() {
+    new_test_ext().execute_with(|| {
+        let issuer = account(1);
+        let payer = account(2);
+        assert_ok!(OnchainTransaction::create_invoice(
+            RuntimeOrigin::signed(issuer.clone()),
+            Some(payer.clone()),
+            300,
+            10,
+            [7u8; 32],
+        ));
+        assert_eq!(pallet::NextInvoiceId::<Test>::get(), 1);
+        assert_eq!(pallet::OpenInvoiceCount::<Test>::get(&issuer), 1);
+
+        assert_noop!(
+            OnchainTransaction::pay_invoice(RuntimeOrigin::signed(account(3)), 0, 1),
+            pallet::Error::<Test>::NotInvoicePayer
+        );
+        assert_ok!(OnchainTransaction::pay_invoice(
+            RuntimeOrigin::signed(payer.clone()),
+            0,
+            100,
+        ));
+        let invoice = pallet::Invoices::<Test>::get(0).expect("partially paid invoice stays open");
+        assert_eq!(invoice.paid, 100);
+        assert_noop!(
+            OnchainTransaction::pay_invoice(RuntimeOrigin::signed(payer.clone()), 0, 201),
+            pallet::Error::<Test>::InvoiceOverpaid
+        );
+        assert_ok!(OnchainTransaction::pay_invoice(
+            RuntimeOrigin::signed(payer.clone()),
+            0,
+            200,
+        ));
+
+        assert_eq!(Balances::free_balance(&issuer), 1_300);
+        assert_eq!(Balances::free_balance(&payer), 700);
+        assert!(pallet::Invoices::<Test>::get(0).is_none());
+        assert_eq!(pallet::OpenInvoiceCount::<Test>::get(&issuer), 0);
+        assert!(System::events().iter().any(|record| matches!(
+            &record.event,
+            RuntimeEvent::OnchainTransaction(pallet::Event::InvoicePaid {
+                invoice_id: 0,
+                amount: 200,
+                paid_total: 300,
+                remaining: 0,
+                ..
+            })
+        )));
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::OnchainTransaction(pallet::Event::InvoiceSettled { invoice_id: 0 })
+        )));
+        assert_noop!(
+            OnchainTransaction::pay_invoice(RuntimeOrigin::signed(payer), 0, 1),
+            pallet::Error::<Test>::InvoiceNotFound
+        );
+    });
+}
+
+#[test]
+fn invoice_creation_rejects_invalid_terms_and_caps_open_invoices() {
+    new_test_ext().execute_with(|| {
+        let issuer = RuntimeOrigin::signed(account(1));
+        assert_noop!(
+            OnchainTransaction::create_invoice(issuer.clone(), None, 0, 10, [0u8; 32]),
+            pallet::Error::<Test>::ZeroAmount
+        );
+        assert_noop!(
+            OnchainTransaction::create_invoice(issuer.clone(), None, 5, 1, [0u8; 32]),
+            pallet::Error::<Test>::InvoiceDueInPast
+        );
+        assert_noop!(
+            OnchainTransaction::create_invoice(issuer.clone(), Some(account(1)), 5, 10, [0u8; 32]),
+            pallet::Error::<Test>::SelfTransferNotAllowed
+        );
+        assert_ok!(OnchainTransaction::create_invoice(
+            issuer.clone(),
+            None,
+            5,
+            10,
+            [0u8; 32]
+        ));
+        assert_ok!(OnchainTransaction::create_invoice(
+            issuer.clone(),
+            None,
+            5,
+            10,
+            [0u8; 32]
+        ));
+        assert_noop!(
+            OnchainTransaction::create_invoice(issuer, None, 5, 10, [0u8; 32]),
+            pallet::Error::<Test>::TooManyOpenInvoices
+        );
+        assert_noop!(
+            OnchainTransaction::pay_invoice(RuntimeOrigin::signed(account(1)), 0, 1),
+            pallet::Error::<Test>::SelfTransferNotAllowed
+        );
+    });
+}
+
+#[test]
+fn invoice_cancel_and_expiry_follow_due_block() {
+    new_test_ext().execute_with(|| {
+        let issuer = account(1);
+        assert_ok!(OnchainTransaction::create_invoice(
+            RuntimeOrigin::signed(issuer.clone()),
+            None,
+            50,
+            5,
+            [1u8; 32],
+        ));
+        assert_ok!(OnchainTransaction::create_invoice(
+            RuntimeOrigin::signed(issuer.clone()),
+            None,
+            50,
+            5,
+            [2u8; 32],
+        ));
+
+        assert_noop!(
+            OnchainTransaction::cancel_invoice(RuntimeOrigin::signed(account(2)), 0),
+            pallet::Error::<Test>::NotInvoiceIssuer
+        );
+        assert_noop!(
+            OnchainTransaction::expire_invoice(RuntimeOrigin::signed(account(2)), 1),
+            pallet::Error::<Test>::InvoiceNotDue
+        );
+        assert_ok!(OnchainTransaction::pay_invoice(
+            RuntimeOrigin::signed(account(2)),
+            0,
+            20,
+        ));
+        assert_ok!(OnchainTransaction::cancel_invoice(
+            RuntimeOrigin::signed(issuer.clone()),
+            0,
+        ));
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::OnchainTransaction(pallet::Event::InvoiceCancelled {
+                invoice_id: 0,
+                paid: 20,
+            })
+        )));
+
+        System::set_block_number(5);
+        assert_noop!(
+            OnchainTransaction::pay_invoice(RuntimeOrigin::signed(account(2)), 1, 1),
+            pallet::Error::<Test>::InvoiceExpired
+        );
+        assert_noop!(
+            OnchainTransaction::cancel_invoice(RuntimeOrigin::signed(issuer.clone()), 1),
+            pallet::Error::<Test>::InvoiceExpired
+        );
+        assert_ok!(OnchainTransaction::expire_invoice(
+            RuntimeOrigin::signed(account(2)),
+            1,
+        ));
+        assert!(pallet::Invoices::<Test>::get(1).is_none());
+        assert_eq!(pallet::OpenInvoiceCount::<Test>::get(&issuer), 0);
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::OnchainTransaction(pallet::Event::InvoiceExpired {
+                invoice_id: 1,
+                paid: 0,
+            })
+        )));
+    });
+}
+
+#[test]
+fn invoice_deposit_is_reserved_and_released_on_close() {
+    new_test_ext().execute_with(|| {
+        let issuer = account(1);
+        assert_noop!(
+            OnchainTransaction::create_invoice(
+                RuntimeOrigin::signed(issuer.clone()),
+                None,
+                5,
+                102,
+                [0u8; 32]
+            ),
+            pallet::Error::<Test>::InvoiceDurationTooLong
+        );
+        assert_noop!(
+            OnchainTransaction::create_invoice(
+                RuntimeOrigin::signed(account(3)),
+                None,
+                5,
+                10,
+                [0u8; 32]
+            ),
+            pallet::Error::<Test>::InsufficientInvoiceDeposit
+        );
+        assert_ok!(OnchainTransaction::create_invoice(
+            RuntimeOrigin::signed(issuer.clone()),
+            None,
+            5,
+            101,
+            [0u8; 32],
+        ));
+        assert_ok!(OnchainTransaction::create_invoice(
+            RuntimeOrigin::signed(issuer.clone()),
+            None,
+            5,
+            10,
+            [0u8; 32],
+        ));
+        assert_eq!(Balances::reserved_balance(&issuer), 20);
+        assert_eq!(
+            pallet::Invoices::<Test>::get(0).map(|i| i.deposit),
+            Some(10)
+        );
+
+        assert_ok!(OnchainTransaction::cancel_invoice(
+            RuntimeOrigin::signed(issuer.clone()),
+            0,
+        ));
+        assert_eq!(Balances::reserved_balance(&issuer), 10);
+        System::set_block_number(10);
+        assert_ok!(OnchainTransaction::expire_invoice(
+            RuntimeOrigin::signed(account(2)),
+            1,
+        ));
+        assert_eq!(Balances::reserved_balance(&issuer), 0);
+        assert_eq!(Balances::free_balance(&issuer), 1_000);
+    });
+}
+
+#[test]
+fn named_payer_declines_unsolicited_invoice_and_takes_deposit() {
+    new_test_ext().execute_with(|| {
+        let issuer = account(1);
+        let payer = account(2);
+        assert_ok!(OnchainTransaction::create_invoice(
+            RuntimeOrigin::signed(issuer.clone()),
+            Some(payer.clone()),
+            300,
+            10,
+            [3u8; 32],
+        ));
+        assert_ok!(OnchainTransaction::create_invoice(
+            RuntimeOrigin::signed(issuer.clone()),
+            None,
+            300,
+            10,
+            [4u8; 32],
+        ));
+
+        // 只有被指定的付款方能拒付；公开账单没有可拒付的对象。
+        assert_noop!(
+            OnchainTransaction::decline_invoice(RuntimeOrigin::signed(account(3)), 0),
+            pallet::Error::<Test>::NotInvoicePayerToDecline
+        );
+        assert_noop!(
+            OnchainTransaction::decline_invoice(RuntimeOrigin::signed(payer.clone()), 1),
+            pallet::Error::<Test>::NotInvoicePayerToDecline
+        );
+        assert_ok!(OnchainTransaction::decline_invoice(
+            RuntimeOrigin::signed(payer.clone()),
+            0,
+        ));
+
+        assert!(pallet::Invoices::<Test>::get(0).is_none());
+        assert_eq!(pallet::OpenInvoiceCount::<Test>::get(&issuer), 1);
+        assert_eq!(Balances::reserved_balance(&issuer), 10);
+        assert_eq!(Balances::free_balance(&issuer), 980);
+        assert_eq!(Balances::free_balance(&payer), 1_010);
+        assert!(System::events().iter().any(|record| matches!(
+            &record.event,
+            RuntimeEvent::OnchainTransaction(pallet::Event::InvoiceDeclined {
+                invoice_id: 0,
+                paid: 0,
+                deposit: 10,
+                ..
+            })
+        )));
+    });
+}
+
 #[test]
 fn charge_details_handles_all_fee_routes() {
     new_test_ext().execute_with(|| {
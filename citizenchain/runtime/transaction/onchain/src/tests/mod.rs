@@ -111,6 +111,9 @@ impl crate::pallet::Config for Test {
         frame_support::traits::ConstU128<{ primitives::fee_policy::ONCHAIN_MIN_FEE }>;
     type OnchainFeeRate = TestOnchainFeeRate;
     type VoteFlatFee = frame_support::traits::ConstU128<{ primitives::fee_policy::VOTE_FLAT_FEE }>;
+    type MaxOpenInvoicesPerIssuer = frame_support::traits::ConstU32<2>;
+    type InvoiceDeposit = frame_support::traits::ConstU128<10>;
+    type MaxInvoiceDuration = frame_support::traits::ConstU32<100>;
 }
 
 frame_support::parameter_types! {
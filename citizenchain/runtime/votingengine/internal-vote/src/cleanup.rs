@@ -18,8 +18,12 @@ impl<T: Config> votingengine::traits::InternalCleanupHandler for Pallet<T> {
         limit: u32,
     ) -> votingengine::traits::CleanupChunkResult {
         let result = InternalVotesByTicket::<T>::clear_prefix(proposal_id, limit, None);
-        let has_remaining = result.maybe_cursor.is_some();
-        (result.unique, has_remaining)
+        let remaining_limit = limit.saturating_sub(result.unique);
+        let signers =
+            InternalDelegatedVoteSigners::<T>::clear_prefix(proposal_id, remaining_limit, None);
+        // 代投留痕与票据同键且条数不超过票据，共用本批次删除额度。
+        let has_remaining = result.maybe_cursor.is_some() || signers.maybe_cursor.is_some();
+        (result.unique.saturating_add(signers.unique), has_remaining)
     }
 
     fn cleanup_internal_terminal(proposal_id: u64) {
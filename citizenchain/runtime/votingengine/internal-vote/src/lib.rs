@@ -11,7 +11,8 @@
 //! 仍归 [`votingengine`] 引擎核心,本 pallet 通过 `Config: votingengine::Config` 直接访问。
 //!
 //! 本 pallet 自有:
-//! - storage:`InternalVotesByTicket` / `InternalTallies` / `InternalThresholdSnapshot`
+//! - storage:`InternalVotesByTicket` / `InternalDelegatedVoteSigners` / `InternalTallies` /
+//!   `InternalThresholdSnapshot`
 //! - event:`InternalVoteCast`
 //! - error:`InvalidInternalCode` / `MissingThresholdSnapshot` / `InvalidThresholdSnapshot`
 //! - extrinsic:`cast(proposal_id, ticket_claim, approve)`
@@ -148,6 +149,20 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// 岗位投票委托代投留痕：票据 -> 实际签名的受托人钱包。
+    ///
+    /// 票值仍在 `InternalVotesByTicket`（bool 不变），本人亲投不写此表。
+    #[pallet::storage]
+    pub type InternalDelegatedVoteSigners<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,
+        Blake2_128Concat,
+        InternalVoteTicket<T::AccountId>,
+        T::AccountId,
+        OptionQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn internal_tally)]
     pub type InternalTallies<T: Config> =
@@ -194,6 +209,8 @@ pub mod pallet {
             who: T::AccountId,
             /// 个人多签为 `None`；机构投票记录实际使用的岗位码。
             voter_role_code: Option<RoleCode>,
+            /// 受托人代投时为委托人规范账户，票据记在其名下；本人亲投为 `None`。
+            on_behalf_of: Option<T::AccountId>,
             approve: bool,
         },
     }
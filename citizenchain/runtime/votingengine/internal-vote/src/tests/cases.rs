@@ -2508,3 +2508,141 @@ fn internal_vote_rejects_wrong_stage_joint_proposal() {
         );
     });
 }
+
+fn nrc_role_subject() -> RoleSubject<CidNumber, votingengine::types::RoleCode> {
+    RoleSubject {
+        cid_number: nrc_cid(),
+        role_code: test_institution_role(NRC)
+            .to_vec()
+            .try_into()
+            .expect("测试岗位码合法"),
+    }
+}
+
+#[test]
+fn delegated_vote_is_ticketed_under_delegator_and_records_signer() {
+    new_test_ext().execute_with(|| {
+        reset_internal_callback_state();
+        set_role_absent_admin(nrc_admin(2));
+        let proposal_id = create_internal_proposal_via_engine(nrc_admin(0), NRC, nrc_cid());
+        assert_ok!(VotingEngine::delegate_role_vote(
+            RuntimeOrigin::signed(nrc_admin(1)),
+            nrc_role_subject(),
+            nrc_admin(2),
+            System::block_number() + 10,
+        ));
+
+        assert_ok!(cast_internal_vote_via_extrinsic(
+            nrc_admin(2),
+            proposal_id,
+            true
+        ));
+
+        let ticket = InternalVoteTicket::Institution(votingengine::InstitutionVoteTicket {
+            role_subject: nrc_role_subject(),
+            voter_account_id: nrc_admin(1),
+        });
+        assert_eq!(
+            InternalVotesByTicket::<Test>::get(proposal_id, &ticket),
+            Some(true)
+        );
+        assert_eq!(
+            InternalDelegatedVoteSigners::<Test>::get(proposal_id, &ticket),
+            Some(nrc_admin(2))
+        );
+        assert!(System::events().into_iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::InternalVote(Event::InternalVoteCast {
+                ref who,
+                on_behalf_of: Some(ref delegator),
+                ..
+            }) if *who == nrc_admin(2) && *delegator == nrc_admin(1)
+        )));
+        // 委托人与受托人共用同一张票据。
+        assert_noop!(
+            cast_internal_vote_via_extrinsic(nrc_admin(1), proposal_id, true),
+            votingengine::Error::<Test>::AlreadyVoted
+        );
+        assert!(!VotingEngine::is_any_institution_voter_in_snapshot(
+            proposal_id,
+            &nrc_admin(2)
+        ));
+    });
+}
+
+#[test]
+fn expired_or_revoked_delegation_cannot_vote() {
+    new_test_ext().execute_with(|| {
+        reset_internal_callback_state();
+        set_role_absent_admin(nrc_admin(2));
+        let proposal_id = create_internal_proposal_via_engine(nrc_admin(0), NRC, nrc_cid());
+        let now = System::block_number();
+        assert_ok!(VotingEngine::delegate_role_vote(
+            RuntimeOrigin::signed(nrc_admin(1)),
+            nrc_role_subject(),
+            nrc_admin(2),
+            now + 1,
+        ));
+        System::set_block_number(now + 2);
+        assert_noop!(
+            cast_internal_vote_via_extrinsic(nrc_admin(2), proposal_id, true),
+            votingengine::Error::<Test>::NoPermission
+        );
+
+        assert_ok!(VotingEngine::delegate_role_vote(
+            RuntimeOrigin::signed(nrc_admin(1)),
+            nrc_role_subject(),
+            nrc_admin(2),
+            now + 10,
+        ));
+        assert_ok!(VotingEngine::revoke_role_vote_delegation(
+            RuntimeOrigin::signed(nrc_admin(1)),
+            nrc_role_subject(),
+        ));
+        assert_noop!(
+            cast_internal_vote_via_extrinsic(nrc_admin(2), proposal_id, true),
+            votingengine::Error::<Test>::NoPermission
+        );
+        assert_noop!(
+            VotingEngine::revoke_role_vote_delegation(
+                RuntimeOrigin::signed(nrc_admin(1)),
+                nrc_role_subject(),
+            ),
+            votingengine::Error::<Test>::VoteDelegationNotFound
+        );
+    });
+}
+
+#[test]
+fn delegation_requires_institution_admin_and_bounded_expiry() {
+    new_test_ext().execute_with(|| {
+        let now = System::block_number();
+        assert_noop!(
+            VotingEngine::delegate_role_vote(
+                RuntimeOrigin::signed(nrc_admin(1)),
+                nrc_role_subject(),
+                prb_admin(0),
+                now + 10,
+            ),
+            votingengine::Error::<Test>::InvalidVoteDelegation
+        );
+        assert_noop!(
+            VotingEngine::delegate_role_vote(
+                RuntimeOrigin::signed(nrc_admin(1)),
+                nrc_role_subject(),
+                nrc_admin(2),
+                now + u64::from(primitives::count_const::VOTE_DELEGATION_MAX_BLOCKS) + 1,
+            ),
+            votingengine::Error::<Test>::InvalidVoteDelegation
+        );
+        assert_noop!(
+            VotingEngine::delegate_role_vote(
+                RuntimeOrigin::signed(prb_admin(0)),
+                nrc_role_subject(),
+                nrc_admin(2),
+                now + 10,
+            ),
+            votingengine::Error::<Test>::NoPermission
+        );
+    });
+}
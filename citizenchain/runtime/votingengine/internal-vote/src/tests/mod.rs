@@ -178,6 +178,16 @@ pub fn set_rebind(canonical: AccountId32, current_wallet: AccountId32) {
     REBIND.with(|cell| *cell.borrow_mut() = Some((canonical, current_wallet)));
 }
 
+// 岗位快照缺席模拟：名单内账户仍是机构管理员，但不在任何岗位的有效任职中。
+thread_local! {
+    static ROLE_ABSENT_ADMINS: RefCell<Vec<AccountId32>> = const { RefCell::new(Vec::new()) };
+}
+
+/// 让机构管理员不出现在岗位快照中，用于模拟岗位投票委托的受托人。
+pub fn set_role_absent_admin(who: AccountId32) {
+    ROLE_ABSENT_ADMINS.with(|cell| cell.borrow_mut().push(who));
+}
+
 pub struct TestCitizenIdentityReader;
 pub struct TestJointVoteResultCallback;
 pub struct TestInternalVoteResultCallback;
@@ -431,7 +441,9 @@ impl votingengine::InstitutionRoleProvider<AccountId32> for TestInternalAdminPro
         if role_code != expected_role && !is_test_second_role {
             return Vec::new();
         }
-        Self::institution_admins(code, cid_number).unwrap_or_default()
+        let mut accounts = Self::institution_admins(code, cid_number).unwrap_or_default();
+        ROLE_ABSENT_ADMINS.with(|cell| accounts.retain(|who| !cell.borrow().contains(who)));
+        accounts
     }
 }
 
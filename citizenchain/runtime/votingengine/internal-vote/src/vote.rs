@@ -19,58 +19,77 @@ impl<T: Config> Pallet<T> {
             proposal.stage == STAGE_INTERNAL,
             votingengine::Error::<T>::InvalidProposalStage
         );
-        let (ticket, voter_role_code, eligible, eligible_total) = if let Some(actor_cid_number) =
-            proposal.actor_cid_number
-        {
-            let role_code = match ticket_claim {
-                InternalVoteTicketClaim::InstitutionRole(role_code) => role_code,
-                InternalVoteTicketClaim::Personal => {
-                    return Err(votingengine::Error::<T>::NoPermission.into())
-                }
-            };
-            let role_subject = votingengine::types::RoleSubject {
-                cid_number: actor_cid_number.clone(),
-                role_code: role_code.clone(),
-            };
-            let subject = AuthorizationSubject::Institution(role_subject.clone());
-            // 票据按【规范账户】防重：换绑前后归并为同一张票，杜绝新旧钱包各投一票。
-            // 与下面的资格校验同源，同样只解析原始 `who`（绝不二次解析规范账户）。
-            // 解析为 None 时资格校验必然失败，此处的回退值不会被采用。
-            let voter_account_id = <votingengine::Pallet<T>>::resolve_subject_voter(&subject, &who)
-                .unwrap_or_else(|| who.clone());
-            (
-                InternalVoteTicket::Institution(InstitutionVoteTicket {
-                    role_subject,
-                    voter_account_id,
-                }),
-                Some(role_code),
-                <votingengine::Pallet<T>>::is_subject_voter_in_snapshot(proposal_id, subject, &who),
-                <votingengine::Pallet<T>>::institution_ticket_count(proposal_id, actor_cid_number)
+        let (ticket, voter_role_code, delegator, eligible, eligible_total) =
+            if let Some(actor_cid_number) = proposal.actor_cid_number {
+                let role_code = match ticket_claim {
+                    InternalVoteTicketClaim::InstitutionRole(role_code) => role_code,
+                    InternalVoteTicketClaim::Personal => {
+                        return Err(votingengine::Error::<T>::NoPermission.into())
+                    }
+                };
+                let role_subject = votingengine::types::RoleSubject {
+                    cid_number: actor_cid_number.clone(),
+                    role_code: role_code.clone(),
+                };
+                let subject = AuthorizationSubject::Institution(role_subject.clone());
+                // 票据按【规范账户】防重：换绑前后归并为同一张票，杜绝新旧钱包各投一票。
+                // 受托人代投时票据记在委托人名下，委托人与受托人共用同一张票。
+                // 解析为 None 时资格校验失败，此处的回退值不会被采用。
+                let voter =
+                    <votingengine::Pallet<T>>::resolve_snapshot_voter(proposal_id, &subject, &who);
+                let eligible = voter.is_some();
+                let (voter_account_id, delegator) = match voter {
+                    Some(voter) if voter.delegate.is_some() => {
+                        (voter.ticket_holder.clone(), Some(voter.ticket_holder))
+                    }
+                    Some(voter) => (voter.ticket_holder, None),
+                    None => (who.clone(), None),
+                };
+                (
+                    InternalVoteTicket::Institution(InstitutionVoteTicket {
+                        role_subject,
+                        voter_account_id,
+                    }),
+                    Some(role_code),
+                    delegator,
+                    eligible,
+                    <votingengine::Pallet<T>>::institution_ticket_count(
+                        proposal_id,
+                        actor_cid_number,
+                    )
                     .ok_or(votingengine::Error::<T>::MissingVoterSnapshot)?,
-            )
-        } else {
-            ensure!(
-                matches!(ticket_claim, InternalVoteTicketClaim::Personal),
-                votingengine::Error::<T>::NoPermission
-            );
-            let personal_account_id = proposal
-                .execution_account_id
-                .ok_or(votingengine::Error::<T>::InvalidInstitution)?;
-            let subject = ProposalSubject::PersonalAccount(personal_account_id);
-            (
-                InternalVoteTicket::Personal(who.clone()),
-                None,
-                <votingengine::Pallet<T>>::is_admin_in_snapshot(proposal_id, subject.clone(), &who),
-                <votingengine::Pallet<T>>::snapshot_admins_len(proposal_id, subject)
-                    .ok_or(votingengine::Error::<T>::MissingAdminSnapshot)?,
-            )
-        };
+                )
+            } else {
+                ensure!(
+                    matches!(ticket_claim, InternalVoteTicketClaim::Personal),
+                    votingengine::Error::<T>::NoPermission
+                );
+                let personal_account_id = proposal
+                    .execution_account_id
+                    .ok_or(votingengine::Error::<T>::InvalidInstitution)?;
+                let subject = ProposalSubject::PersonalAccount(personal_account_id);
+                (
+                    InternalVoteTicket::Personal(who.clone()),
+                    None,
+                    None,
+                    <votingengine::Pallet<T>>::is_admin_in_snapshot(
+                        proposal_id,
+                        subject.clone(),
+                        &who,
+                    ),
+                    <votingengine::Pallet<T>>::snapshot_admins_len(proposal_id, subject)
+                        .ok_or(votingengine::Error::<T>::MissingAdminSnapshot)?,
+                )
+            };
         ensure!(eligible, votingengine::Error::<T>::NoPermission);
         ensure!(
             !InternalVotesByTicket::<T>::contains_key(proposal_id, &ticket),
             votingengine::Error::<T>::AlreadyVoted
         );
 
+        if delegator.is_some() {
+            InternalDelegatedVoteSigners::<T>::insert(proposal_id, &ticket, &who);
+        }
         InternalVotesByTicket::<T>::insert(proposal_id, ticket, approve);
         let tally = InternalTallies::<T>::mutate(proposal_id, |tally| {
             if approve {
@@ -85,6 +104,7 @@ impl<T: Config> Pallet<T> {
             proposal_id,
             who,
             voter_role_code,
+            on_behalf_of: delegator,
             approve,
         });
 
@@ -124,6 +124,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_parts(0, 67255))
 			.saturating_add(T::DbWeight::get().reads(8))
 			.saturating_add(T::DbWeight::get().writes(8))
+			// 正式 benchmark 重跑前手写叠加岗位投票委托解析：`Proposals`、`ProposalMutexBindings`、
+			// `VoteDelegators`、`VoteDelegations` 各 1 次读，代投另写 1 次 `InternalDelegatedVoteSigners`。
+			.saturating_add(Weight::from_parts(8_000_000, 30_000))
+			.saturating_add(T::DbWeight::get().reads_writes(4, 1))
 	}
 	/// Storage: `VotingEngine::Proposals` (r:1 w:1)
 	/// Proof: `VotingEngine::Proposals` (`max_values`: None, `max_size`: Some(8557), added: 11032, mode: `MaxEncodedLen`)
@@ -201,6 +205,10 @@ impl WeightInfo for () {
 			.saturating_add(Weight::from_parts(0, 67255))
 			.saturating_add(RocksDbWeight::get().reads(8))
 			.saturating_add(RocksDbWeight::get().writes(8))
+			// 正式 benchmark 重跑前手写叠加岗位投票委托解析：`Proposals`、`ProposalMutexBindings`、
+			// `VoteDelegators`、`VoteDelegations` 各 1 次读，代投另写 1 次 `InternalDelegatedVoteSigners`。
+			.saturating_add(Weight::from_parts(8_000_000, 30_000))
+			.saturating_add(RocksDbWeight::get().reads_writes(4, 1))
 	}
 	/// Storage: `VotingEngine::Proposals` (r:1 w:1)
 	/// Proof: `VotingEngine::Proposals` (`max_values`: None, `max_size`: Some(8557), added: 11032, mode: `MaxEncodedLen`)
@@ -33,8 +33,8 @@ use votingengine::{
 };
 
 use super::pallet::{
-    Config, Error, Event, JointInstitutionTallies, JointTallies, JointVotesByInstitution,
-    JointVotesByTicket, Pallet,
+    Config, Error, Event, JointDelegatedVoteSigners, JointInstitutionTallies, JointTallies,
+    JointVotesByInstitution, JointVotesByTicket, Pallet,
 };
 use super::{institution_info, is_joint_unanimous};
 
@@ -271,26 +271,26 @@ impl<T: Config> Pallet<T> {
             role_code: voter_role_code.clone(),
         };
         let subject = AuthorizationSubject::Institution(role_subject.clone());
-        ensure!(
-            <votingengine::Pallet<T>>::is_subject_voter_in_snapshot(
-                proposal_id,
-                subject.clone(),
-                &who,
-            ),
-            votingengine::Error::<T>::NoPermission
-        );
         // 票据按【规范账户】防重：换绑前后归并为同一张票，杜绝新旧钱包各投一票。
-        let voter_account_id = <votingengine::Pallet<T>>::resolve_subject_voter(&subject, &who)
-            .unwrap_or_else(|| who.clone());
+        // 受托人代投时票据记在委托人名下，委托人与受托人共用同一张票。
+        let voter = <votingengine::Pallet<T>>::resolve_snapshot_voter(proposal_id, &subject, &who)
+            .ok_or(votingengine::Error::<T>::NoPermission)?;
+        let on_behalf_of = voter
+            .delegate
+            .is_some()
+            .then(|| voter.ticket_holder.clone());
         let ticket = InstitutionVoteTicket {
             role_subject,
-            voter_account_id,
+            voter_account_id: voter.ticket_holder,
         };
         ensure!(
             !JointVotesByTicket::<T>::contains_key(proposal_id, &ticket),
             votingengine::Error::<T>::AlreadyVoted
         );
 
+        if on_behalf_of.is_some() {
+            JointDelegatedVoteSigners::<T>::insert(proposal_id, &ticket, &who);
+        }
         JointVotesByTicket::<T>::insert(proposal_id, ticket, approve);
         let tally =
             JointInstitutionTallies::<T>::mutate(proposal_id, cid_number.clone(), |tally| {
@@ -307,6 +307,7 @@ impl<T: Config> Pallet<T> {
             cid_number: cid_number.clone(),
             who,
             voter_role_code,
+            on_behalf_of,
             approve,
         });
 
@@ -115,6 +115,20 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// 岗位投票委托代投留痕：票据 -> 实际签名的受托人钱包。
+    ///
+    /// 票值仍在 `JointVotesByTicket`（bool 不变），本人亲投不写此表。
+    #[pallet::storage]
+    pub type JointDelegatedVoteSigners<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,
+        Blake2_128Concat,
+        InstitutionVoteTicket<T::AccountId>,
+        T::AccountId,
+        OptionQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn joint_institution_tally)]
     pub type JointInstitutionTallies<T: Config> = StorageDoubleMap<
@@ -165,6 +179,8 @@ pub mod pallet {
             cid_number: CidNumber,
             who: T::AccountId,
             voter_role_code: RoleCode,
+            /// 受托人代投时为委托人规范账户，票据记在其名下；本人亲投为 `None`。
+            on_behalf_of: Option<T::AccountId>,
             approve: bool,
         },
         /// 联合投票中某机构已形成最终结果(赞成/反对)。
@@ -344,7 +360,16 @@ impl<T: Config> votingengine::traits::JointCleanupHandler for Pallet<T> {
         limit: u32,
     ) -> votingengine::traits::CleanupChunkResult {
         let result = JointVotesByTicket::<T>::clear_prefix(proposal_id, limit, None);
-        (result.unique, result.maybe_cursor.is_some())
+        // 代投留痕与票据同键且条数不超过票据，共用本批次删除额度。
+        let signers = JointDelegatedVoteSigners::<T>::clear_prefix(
+            proposal_id,
+            limit.saturating_sub(result.unique),
+            None,
+        );
+        (
+            result.unique.saturating_add(signers.unique),
+            result.maybe_cursor.is_some() || signers.maybe_cursor.is_some(),
+        )
     }
     fn cleanup_joint_institution_votes_chunk(
         proposal_id: u64,
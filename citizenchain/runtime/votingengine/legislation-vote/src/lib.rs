@@ -385,29 +385,36 @@ pub mod pallet {
     }
 }
 // 业务方法
-// trait 实现(供 votingengine 核心 + 业务壳接入)
-impl<T: Config> crate::LegislationVoteEngine<T::AccountId> for Pallet<T> {
-    fn create_representative_vote(
-        who: T::AccountId,
+impl<T: Config> Pallet<T> {
+    /// 法律程序建案共用实现。`who = None` 为公民创制案:无任职发起人,也不自动投赞成票。
+    #[allow(clippy::too_many_arguments)]
+    fn do_create_legislation_vote(
+        who: Option<T::AccountId>,
         actor_cid_number: votingengine::types::CidNumber,
         vote_plan: VotePlanOf<T::AccountId>,
         route: RepresentativeRoute,
         rule: RepresentativeVoteRule,
-        subject_cid_numbers: ProposalSubjectCidNumbers,
+        procedure: LegislationProcedureConfig,
         module_tag: &[u8],
         data: sp_runtime::sp_std::vec::Vec<u8>,
+        object_data: sp_runtime::sp_std::vec::Vec<u8>,
     ) -> Result<u64, DispatchError> {
         let first_body = Self::validate_representative_route(&route)?.1;
         with_transaction(|| {
-            let id = match Self::do_create_representative_proposal(
-                who.clone(),
+            let id = match Self::create_representative_proposal(
+                who.as_ref(),
                 actor_cid_number,
                 vote_plan,
                 route,
                 rule,
-                VoteProcedure::RepresentativeOnly,
-                subject_cid_numbers,
-                None,
+                VoteProcedure::Legislation,
+                ProposalSubjectCidNumbers::new(),
+                Some(pallet::LegislationMeta {
+                    executive: procedure.executive,
+                    override_signers: procedure.override_signers,
+                    needs_guard: procedure.needs_guard,
+                    guard: procedure.guard,
+                }),
             ) {
                 Ok(id) => id,
                 Err(err) => return TransactionOutcome::Rollback(Err(err)),
@@ -418,31 +425,45 @@ impl<T: Config> crate::LegislationVoteEngine<T::AccountId> for Pallet<T> {
             {
                 return TransactionOutcome::Rollback(Err(err));
             }
-            if <votingengine::Pallet<T>>::is_subject_voter_in_snapshot(
+            if let Err(err) = <votingengine::Pallet<T>>::store_proposal_object(
                 id,
-                AuthorizationSubject::Institution(first_body.clone()),
-                &who,
+                PROPOSAL_OBJECT_KIND_LAW_TEXT,
+                object_data,
             ) {
-                match Self::do_cast_representative_vote(who, id, first_body.role_code, true) {
-                    Ok(()) => TransactionOutcome::Commit(Ok(id)),
-                    Err(err) => TransactionOutcome::Rollback(Err(err)),
+                return TransactionOutcome::Rollback(Err(err));
+            }
+            // 发起人若属表决院(国家/省两院:发起院=众议会/教委会)则自动赞成一票;
+            // 市行政区 市自治会/市教委会 委员提案时发起人不在表决院(市立法会),不自动投票。
+            match who {
+                Some(who)
+                    if <votingengine::Pallet<T>>::is_subject_voter_in_snapshot(
+                        id,
+                        AuthorizationSubject::Institution(first_body.clone()),
+                        &who,
+                    ) =>
+                {
+                    match Self::do_cast_representative_vote(who, id, first_body.role_code, true) {
+                        Ok(()) => TransactionOutcome::Commit(Ok(id)),
+                        Err(err) => TransactionOutcome::Rollback(Err(err)),
+                    }
                 }
-            } else {
-                TransactionOutcome::Commit(Ok(id))
+                _ => TransactionOutcome::Commit(Ok(id)),
             }
         })
     }
+}
 
-    fn create_legislation_vote(
+// trait 实现(供 votingengine 核心 + 业务壳接入)
+impl<T: Config> crate::LegislationVoteEngine<T::AccountId> for Pallet<T> {
+    fn create_representative_vote(
         who: T::AccountId,
         actor_cid_number: votingengine::types::CidNumber,
         vote_plan: VotePlanOf<T::AccountId>,
         route: RepresentativeRoute,
         rule: RepresentativeVoteRule,
-        procedure: LegislationProcedureConfig,
+        subject_cid_numbers: ProposalSubjectCidNumbers,
         module_tag: &[u8],
         data: sp_runtime::sp_std::vec::Vec<u8>,
-        object_data: sp_runtime::sp_std::vec::Vec<u8>,
     ) -> Result<u64, DispatchError> {
         let first_body = Self::validate_representative_route(&route)?.1;
         with_transaction(|| {
@@ -452,14 +473,9 @@ impl<T: Config> crate::LegislationVoteEngine<T::AccountId> for Pallet<T> {
                 vote_plan,
                 route,
                 rule,
-                VoteProcedure::Legislation,
-                ProposalSubjectCidNumbers::new(),
-                Some(pallet::LegislationMeta {
-                    executive: procedure.executive,
-                    override_signers: procedure.override_signers,
-                    needs_guard: procedure.needs_guard,
-                    guard: procedure.guard,
-                }),
+                VoteProcedure::RepresentativeOnly,
+                subject_cid_numbers,
+                None,
             ) {
                 Ok(id) => id,
                 Err(err) => return TransactionOutcome::Rollback(Err(err)),
@@ -470,15 +486,6 @@ impl<T: Config> crate::LegislationVoteEngine<T::AccountId> for Pallet<T> {
             {
                 return TransactionOutcome::Rollback(Err(err));
             }
-            if let Err(err) = <votingengine::Pallet<T>>::store_proposal_object(
-                id,
-                PROPOSAL_OBJECT_KIND_LAW_TEXT,
-                object_data,
-            ) {
-                return TransactionOutcome::Rollback(Err(err));
-            }
-            // 发起人若属表决院(国家/省两院:发起院=众议会/教委会)则自动赞成一票;
-            // 市行政区 市自治会/市教委会 委员提案时发起人不在表决院(市立法会),不自动投票。
             if <votingengine::Pallet<T>>::is_subject_voter_in_snapshot(
                 id,
                 AuthorizationSubject::Institution(first_body.clone()),
@@ -494,6 +501,53 @@ impl<T: Config> crate::LegislationVoteEngine<T::AccountId> for Pallet<T> {
         })
     }
 
+    fn create_legislation_vote(
+        who: T::AccountId,
+        actor_cid_number: votingengine::types::CidNumber,
+        vote_plan: VotePlanOf<T::AccountId>,
+        route: RepresentativeRoute,
+        rule: RepresentativeVoteRule,
+        procedure: LegislationProcedureConfig,
+        module_tag: &[u8],
+        data: sp_runtime::sp_std::vec::Vec<u8>,
+        object_data: sp_runtime::sp_std::vec::Vec<u8>,
+    ) -> Result<u64, DispatchError> {
+        Self::do_create_legislation_vote(
+            Some(who),
+            actor_cid_number,
+            vote_plan,
+            route,
+            rule,
+            procedure,
+            module_tag,
+            data,
+            object_data,
+        )
+    }
+
+    fn create_initiative_legislation_vote(
+        actor_cid_number: votingengine::types::CidNumber,
+        vote_plan: VotePlanOf<T::AccountId>,
+        route: RepresentativeRoute,
+        rule: RepresentativeVoteRule,
+        procedure: LegislationProcedureConfig,
+        module_tag: &[u8],
+        data: sp_runtime::sp_std::vec::Vec<u8>,
+        object_data: sp_runtime::sp_std::vec::Vec<u8>,
+    ) -> Result<u64, DispatchError> {
+        Self::do_create_legislation_vote(
+            None,
+            actor_cid_number,
+            vote_plan,
+            route,
+            rule,
+            procedure,
+            module_tag,
+            data,
+            object_data,
+        )
+    }
+
     /// 读取某立法提案的强制公投结果 `(eligible, yes, no)`。
     /// 没有提案人口快照(即非特别案)或提案不存在 → `None`。
     /// 公投计票 `LegReferendumTally` 在提案 90 天清理前一直保留,故核心修宪写入(护宪终审同块)时可读到。
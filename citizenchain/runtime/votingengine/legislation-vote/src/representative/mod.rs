@@ -120,6 +120,31 @@ impl<T: Config> Pallet<T> {
         procedure: VoteProcedure,
         additional_subjects: ProposalSubjectCidNumbers,
         legislation_meta: Option<pallet::LegislationMeta>,
+    ) -> Result<u64, DispatchError> {
+        Self::create_representative_proposal(
+            Some(&who),
+            actor_cid_number,
+            vote_plan,
+            route,
+            rule,
+            procedure,
+            additional_subjects,
+            legislation_meta,
+        )
+    }
+
+    /// `proposer = None` 仅用于公民创制案:没有任职发起人,提案主体必须是发起机构本身,
+    /// 资格由业务模块的联署门槛背书。
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn create_representative_proposal(
+        proposer: Option<&T::AccountId>,
+        actor_cid_number: votingengine::types::CidNumber,
+        vote_plan: VotePlanOf<T::AccountId>,
+        route: RepresentativeRoute,
+        rule: RepresentativeVoteRule,
+        procedure: VoteProcedure,
+        additional_subjects: ProposalSubjectCidNumbers,
+        legislation_meta: Option<pallet::LegislationMeta>,
     ) -> Result<u64, DispatchError> {
         let (first_code, _first_body) = Self::validate_representative_route(&route)?;
         let actor_code = Self::institution_code_for_cid(&actor_cid_number)?;
@@ -135,11 +160,13 @@ impl<T: Config> Pallet<T> {
         };
         ensure!(
             proposer_role.cid_number == actor_cid_number
-                && T::InstitutionRoleProvider::is_active_assignment(
-                    actor_cid_number.as_slice(),
-                    &who,
-                    proposer_role.role_code.as_slice(),
-                ),
+                && proposer.is_none_or(|who| {
+                    T::InstitutionRoleProvider::is_active_assignment(
+                        actor_cid_number.as_slice(),
+                        who,
+                        proposer_role.role_code.as_slice(),
+                    )
+                }),
             votingengine::Error::<T>::NoPermission
         );
         ensure!(
@@ -324,6 +324,48 @@ fn create_requires_actor_cid_admin_even_when_vote_route_is_valid() {
     });
 }
 
+fn create_initiative(
+    route: crate::RepresentativeRoute,
+    vote_plan: votingengine::VotePlanOf<AccountId32>,
+) -> Result<u64, sp_runtime::DispatchError> {
+    let meta =
+        legislation_meta(&route, RepresentativeVoteRule::Regular, false).expect("legislation meta");
+    <Lib as crate::LegislationVoteEngine<AccountId32>>::create_initiative_legislation_vote(
+        actor_cid_number(),
+        vote_plan,
+        route,
+        RepresentativeVoteRule::Regular,
+        crate::LegislationProcedureConfig {
+            executive: meta.executive,
+            override_signers: meta.override_signers,
+            needs_guard: meta.needs_guard,
+            guard: meta.guard,
+        },
+        b"leg-yuan",
+        vec![1],
+        vec![],
+    )
+}
+
+#[test]
+fn initiative_creates_without_proposer_and_casts_no_vote() {
+    // 公民创制案没有任职发起人:建案不查发起岗位任职,也不代投首张赞成票。
+    new_test_ext().execute_with(|| {
+        let route = crate::RepresentativeRoute::Single(house1());
+        let meta = legislation_meta(&route, RepresentativeVoteRule::Regular, false);
+        let vote_plan = test_vote_plan(&route, meta.as_ref());
+        let pid = create_initiative(route, vote_plan).expect("initiative created");
+        assert_eq!(stage(pid), STAGE_LEG_REPRESENTATIVE);
+        for i in 1u8..=7 {
+            assert_ok!(cast(member(i), pid, true));
+        }
+        for i in 8u8..=10 {
+            assert_ok!(cast(member(i), pid, false));
+        }
+        assert_eq!(stage(pid), STAGE_LEG_SIGN);
+    });
+}
+
 // ───────────────── 两院顺序 + 签署 + 三人会签 ─────────────────
 
 /// 两院全过后推进至行政签署阶段(辅助):返回处于 STAGE_LEG_SIGN 的提案。
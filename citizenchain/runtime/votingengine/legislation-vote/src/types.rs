@@ -170,6 +170,20 @@ pub trait LegislationVoteEngine<AccountId> {
         object_data: Vec<u8>,
     ) -> Result<u64, DispatchError>;
 
+    /// 创建公民创制法律提案:联署达门槛后由业务模块直接建案,没有任职发起人,
+    /// 提案主体为发起机构代表岗位本身;其余程序与 `create_legislation_vote` 相同。
+    #[allow(clippy::too_many_arguments)]
+    fn create_initiative_legislation_vote(
+        actor_cid_number: CidNumber,
+        vote_plan: VotePlanOf<AccountId>,
+        route: RepresentativeRoute,
+        rule: RepresentativeVoteRule,
+        procedure: LegislationProcedureConfig,
+        module_tag: &[u8],
+        data: Vec<u8>,
+        object_data: Vec<u8>,
+    ) -> Result<u64, DispatchError>;
+
     /// 读取特别案公民投票永久凭据。
     fn referendum_result(proposal_id: u64) -> Option<(u64, u64, u64)>;
 
@@ -205,6 +219,19 @@ impl<AccountId> LegislationVoteEngine<AccountId> for () {
         Err(DispatchError::Other("LegislationVoteEngineNotConfigured"))
     }
 
+    fn create_initiative_legislation_vote(
+        _actor_cid_number: CidNumber,
+        _vote_plan: VotePlanOf<AccountId>,
+        _route: RepresentativeRoute,
+        _rule: RepresentativeVoteRule,
+        _procedure: LegislationProcedureConfig,
+        _module_tag: &[u8],
+        _data: Vec<u8>,
+        _object_data: Vec<u8>,
+    ) -> Result<u64, DispatchError> {
+        Err(DispatchError::Other("LegislationVoteEngineNotConfigured"))
+    }
+
     fn referendum_result(_proposal_id: u64) -> Option<(u64, u64, u64)> {
         None
     }
@@ -1,9 +1,11 @@
 //! `votingengine` 核心 FRAME benchmark。
 //!
-//! mode-specific 写票由各 Track benchmark；这里测量统一超时入口、管理员恢复入口和
-//! 异步执行队列框架成本。业务执行最重的 `set_code` 另由 WeightInfo 显式叠加系统权重。
+//! mode-specific 写票由各 Track benchmark；这里测量统一超时入口、管理员恢复入口、
+//! 异步执行队列框架成本与岗位投票委托登记/撤销。业务执行最重的 `set_code` 另由
+//! WeightInfo 显式叠加系统权重。
 #![cfg(feature = "runtime-benchmarks")]
 
+use codec::Decode;
 use frame_benchmarking::v2::*;
 use frame_system::RawOrigin;
 use sp_runtime::traits::SaturatedConversion;
@@ -83,6 +85,31 @@ fn setup<T: Config>(status: u8) -> (u64, T::AccountId) {
     (proposal_id, who)
 }
 
+/// 委托用例须按机构管理员真源解析签名账户；fresh spec 中 NRC 在册管理员可直接使用。
+fn delegation_setup<T: Config>() -> (
+    crate::types::RoleSubject<crate::types::CidNumber, crate::types::RoleCode>,
+    sp_std::vec::Vec<T::AccountId>,
+) {
+    let nrc = &primitives::cid::china::china_cb::CHINA_CB[0];
+    let admins = nrc.admins[..4]
+        .iter()
+        .map(|raw| T::AccountId::decode(&mut &raw[..]).expect("NRC benchmark admin decodes"))
+        .collect();
+    let role_subject = crate::types::RoleSubject {
+        cid_number: nrc
+            .cid_number
+            .as_bytes()
+            .to_vec()
+            .try_into()
+            .expect("NRC CID fits runtime bound"),
+        role_code: b"BENCHMARK_ROLE"
+            .to_vec()
+            .try_into()
+            .expect("benchmark role fits"),
+    };
+    (role_subject, admins)
+}
+
 #[benchmarks]
 mod benchmarks {
     use super::*;
@@ -150,4 +177,61 @@ mod benchmarks {
 
         assert!(PendingProposalExecutions::<T>::contains_key(proposal_id));
     }
+
+    /// 最坏路径：覆盖本人旧委托，且受托人槽位被另一名委托人的已过期委托占用。
+    #[benchmark]
+    fn delegate_role_vote() {
+        let (role_subject, admins) = delegation_setup::<T>();
+        frame_system::Pallet::<T>::set_block_number(1u32.saturated_into());
+        Pallet::<T>::do_delegate_role_vote(
+            &admins[3],
+            role_subject.clone(),
+            admins[1].clone(),
+            2u32.saturated_into(),
+        )
+        .expect("seed expiring delegation");
+        frame_system::Pallet::<T>::set_block_number(3u32.saturated_into());
+        Pallet::<T>::do_delegate_role_vote(
+            &admins[0],
+            role_subject.clone(),
+            admins[2].clone(),
+            4u32.saturated_into(),
+        )
+        .expect("seed previous delegation");
+        let expires_at = 5u32.saturated_into();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(admins[0].clone()),
+            role_subject.clone(),
+            admins[1].clone(),
+            expires_at,
+        );
+
+        assert_eq!(
+            crate::pallet::VoteDelegators::<T>::get(&role_subject, &admins[1]),
+            Some(admins[0].clone())
+        );
+    }
+
+    #[benchmark]
+    fn revoke_role_vote_delegation() {
+        let (role_subject, admins) = delegation_setup::<T>();
+        frame_system::Pallet::<T>::set_block_number(1u32.saturated_into());
+        Pallet::<T>::do_delegate_role_vote(
+            &admins[0],
+            role_subject.clone(),
+            admins[1].clone(),
+            2u32.saturated_into(),
+        )
+        .expect("seed delegation");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(admins[0].clone()), role_subject.clone());
+
+        assert!(!crate::pallet::VoteDelegations::<T>::contains_key(
+            &role_subject,
+            &admins[0]
+        ));
+    }
 }
@@ -0,0 +1,150 @@
+//! 机构岗位投票委托。
+//!
+//! 任职人出差、住院等无法亲自签名时，可把某个岗位主体下本人的票据在限定区块内委托给
+//! 同一机构的另一名管理员代投，避免内部/联合投票因缺票超时否决或被推入公投。
+//!
+//! - 委托只改变“谁签名”，不改变票据归属：代投票据仍记在委托人规范账户名下，与本人票据
+//!   共用防重键，委托人与受托人不能各投一票；实际签名人由 sub-pallet 另行留痕。
+//! - 只在内部投票与联合投票机构阶段生效；持有 `AdminSetMutationExclusive` 互斥锁的
+//!   管理员集合变更提案一律不承认委托。
+//! - 受托人本人也在同一岗位快照中时，投票优先解析为本人票据。
+
+use frame_support::{ensure, pallet_prelude::DispatchResult};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::{SaturatedConversion, Saturating};
+
+use crate::pallet::{
+    self, Error, Event, ProposalMutexBindings, Proposals, VoteDelegations, VoteDelegators,
+};
+use crate::types::{
+    institution_code_from_cid_number, CidNumber, InstitutionCode, RoleCode, RoleSubject,
+    VoteDelegation, PROPOSAL_KIND_INTERNAL, PROPOSAL_KIND_JOINT, STAGE_INTERNAL, STAGE_JOINT,
+};
+use crate::{InternalAdminProvider, InternalProposalMutexKind};
+
+impl<T: pallet::Config> pallet::Pallet<T> {
+    pub fn do_delegate_role_vote(
+        who: &T::AccountId,
+        role_subject: RoleSubject<CidNumber, RoleCode>,
+        delegate: T::AccountId,
+        expires_at: BlockNumberFor<T>,
+    ) -> DispatchResult {
+        let (institution_code, delegator) = Self::resolve_delegation_admin(&role_subject, who)?;
+        ensure!(
+            delegate != delegator
+                && T::InternalAdminProvider::is_institution_admin(
+                    institution_code,
+                    role_subject.cid_number.as_slice(),
+                    &delegate,
+                ),
+            Error::<T>::InvalidVoteDelegation
+        );
+        let now = frame_system::Pallet::<T>::block_number();
+        let max_expires_at = now
+            .saturating_add(primitives::count_const::VOTE_DELEGATION_MAX_BLOCKS.saturated_into());
+        ensure!(
+            expires_at > now && expires_at <= max_expires_at,
+            Error::<T>::InvalidVoteDelegation
+        );
+        if let Some(other) = VoteDelegators::<T>::get(&role_subject, &delegate) {
+            ensure!(
+                other == delegator || !Self::is_delegation_active(&role_subject, &other, &delegate),
+                Error::<T>::VoteDelegateAlreadyAssigned
+            );
+        }
+        if let Some(previous) = VoteDelegations::<T>::get(&role_subject, &delegator) {
+            VoteDelegators::<T>::remove(&role_subject, &previous.delegate);
+        }
+        VoteDelegations::<T>::insert(
+            &role_subject,
+            &delegator,
+            VoteDelegation {
+                delegate: delegate.clone(),
+                expires_at,
+            },
+        );
+        VoteDelegators::<T>::insert(&role_subject, &delegate, &delegator);
+        Self::deposit_event(Event::<T>::VoteDelegationRegistered {
+            role_subject,
+            delegator,
+            delegate,
+            expires_at,
+        });
+        Ok(())
+    }
+
+    pub fn do_revoke_role_vote_delegation(
+        who: &T::AccountId,
+        role_subject: RoleSubject<CidNumber, RoleCode>,
+    ) -> DispatchResult {
+        let (_, delegator) = Self::resolve_delegation_admin(&role_subject, who)?;
+        let delegation = VoteDelegations::<T>::take(&role_subject, &delegator)
+            .ok_or(Error::<T>::VoteDelegationNotFound)?;
+        if VoteDelegators::<T>::get(&role_subject, &delegation.delegate).as_ref()
+            == Some(&delegator)
+        {
+            VoteDelegators::<T>::remove(&role_subject, &delegation.delegate);
+        }
+        Self::deposit_event(Event::<T>::VoteDelegationRevoked {
+            role_subject,
+            delegator,
+            delegate: delegation.delegate,
+        });
+        Ok(())
+    }
+
+    /// 查询受托人在指定提案、岗位主体下当前可代投的委托人。
+    ///
+    /// 只认内部投票/联合投票机构阶段、非管理员集合变更提案、且截止区块未过的委托。
+    pub fn active_vote_delegator(
+        proposal_id: u64,
+        role_subject: &RoleSubject<CidNumber, RoleCode>,
+        delegate: &T::AccountId,
+    ) -> Option<T::AccountId> {
+        let proposal = Proposals::<T>::get(proposal_id)?;
+        let delegable = matches!(
+            (proposal.kind, proposal.stage),
+            (PROPOSAL_KIND_INTERNAL, STAGE_INTERNAL) | (PROPOSAL_KIND_JOINT, STAGE_JOINT)
+        );
+        if !delegable || Self::is_admin_set_mutation_proposal(proposal_id) {
+            return None;
+        }
+        let delegator = VoteDelegators::<T>::get(role_subject, delegate)?;
+        Self::is_delegation_active(role_subject, &delegator, delegate).then_some(delegator)
+    }
+
+    /// 提案是否持有管理员集合变更独占锁。
+    pub fn is_admin_set_mutation_proposal(proposal_id: u64) -> bool {
+        ProposalMutexBindings::<T>::get(proposal_id)
+            .iter()
+            .any(|binding| binding.kind == InternalProposalMutexKind::AdminSetMutationExclusive)
+    }
+
+    fn is_delegation_active(
+        role_subject: &RoleSubject<CidNumber, RoleCode>,
+        delegator: &T::AccountId,
+        delegate: &T::AccountId,
+    ) -> bool {
+        VoteDelegations::<T>::get(role_subject, delegator).is_some_and(|delegation| {
+            delegation.delegate == *delegate
+                && frame_system::Pallet::<T>::block_number() <= delegation.expires_at
+        })
+    }
+
+    /// 把签名钱包解析为本机构当前管理员的规范账户；委托人只能操作本人名下的委托。
+    fn resolve_delegation_admin(
+        role_subject: &RoleSubject<CidNumber, RoleCode>,
+        who: &T::AccountId,
+    ) -> Result<(InstitutionCode, T::AccountId), sp_runtime::DispatchError> {
+        let cid_number = role_subject.cid_number.as_slice();
+        let institution_code =
+            institution_code_from_cid_number(core::str::from_utf8(cid_number).unwrap_or_default())
+                .ok_or(Error::<T>::InvalidInstitution)?;
+        let admin = T::InternalAdminProvider::resolve_institution_voter(cid_number, who)
+            .filter(|admin| {
+                T::InternalAdminProvider::is_institution_admin(institution_code, cid_number, admin)
+            })
+            .ok_or(Error::<T>::NoPermission)?;
+        Ok((institution_code, admin))
+    }
+}
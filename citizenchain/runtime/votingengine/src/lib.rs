@@ -34,6 +34,7 @@
 mod benchmarks;
 pub mod cleanup;
 pub mod data;
+pub mod delegation;
 mod execution;
 mod expiry;
 pub mod id;
@@ -492,6 +493,34 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 岗位投票委托：(岗位主体, 委托人规范账户) → 受托人规范账户与截止区块。
+    ///
+    /// 只在内部投票与联合投票机构阶段生效，管理员集合变更提案不承认委托。
+    #[pallet::storage]
+    #[pallet::getter(fn vote_delegation)]
+    pub type VoteDelegations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        RoleSubject<CidNumber, RoleCode>,
+        Blake2_128Concat,
+        T::AccountId,
+        VoteDelegation<T::AccountId, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// 委托反向索引：(岗位主体, 受托人规范账户) → 委托人规范账户。
+    /// 同一岗位主体下一名受托人同一时刻只代理一名任职人，投票时据此唯一确定票据归属。
+    #[pallet::storage]
+    pub type VoteDelegators<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        RoleSubject<CidNumber, RoleCode>,
+        Blake2_128Concat,
+        T::AccountId,
+        T::AccountId,
+        OptionQuery,
+    >;
+
     // ──── 双层 ID 与反向索引 ────
 
     /// 提案展示号:`proposal_id → (year, seq_in_year)`。
@@ -601,6 +630,19 @@ pub mod pallet {
         },
         /// 自动超时终结连续失败或重试桶已满，已进入 dead-letter。
         ProposalAutoFinalizeDeadLettered { proposal_id: u64, attempts: u8 },
+        /// 任职人已登记岗位投票委托。
+        VoteDelegationRegistered {
+            role_subject: RoleSubject<CidNumber, RoleCode>,
+            delegator: T::AccountId,
+            delegate: T::AccountId,
+            expires_at: BlockNumberFor<T>,
+        },
+        /// 任职人已撤销岗位投票委托。
+        VoteDelegationRevoked {
+            role_subject: RoleSubject<CidNumber, RoleCode>,
+            delegator: T::AccountId,
+            delegate: T::AccountId,
+        },
     }
 
     #[pallet::error]
@@ -671,6 +713,12 @@ pub mod pallet {
         CleanupQueueSequenceExhausted,
         /// citizen-identity 尚未把四级有效人口完整推进到当前 UTC+8 日期。
         PopulationDataNotReady,
+        /// 受托人不是本机构其他管理员，或截止区块已过/超出最长委托期限。
+        InvalidVoteDelegation,
+        /// 受托人在该岗位主体下已代理另一名任职人的有效委托。
+        VoteDelegateAlreadyAssigned,
+        /// 委托人在该岗位主体下没有登记委托。
+        VoteDelegationNotFound,
     }
 
     #[pallet::hooks]
@@ -741,7 +789,7 @@ pub mod pallet {
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        // 引擎核心仅承载生命周期 extrinsic(超时结算 / 重试 / 取消)。
+        // 引擎核心仅承载生命周期 extrinsic(超时结算 / 重试 / 取消)与跨投票模式共用的岗位投票委托登记。
         // mode-specific 投票 extrinsic 由各 sub-pallet 提供:
         //   - InternalVote::cast(20.0)
         //   - JointVote::cast_admin(21.0)
@@ -798,5 +846,31 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             Self::cancel_passed_proposal_inner(&who, proposal_id)
         }
+
+        /// 任职人登记岗位投票委托：截止区块前由受托人代投该岗位主体下本人的票据。
+        ///
+        /// `delegate` 为受托人的名册规范账户；重复登记覆盖本人原有委托。
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::delegate_role_vote())]
+        pub fn delegate_role_vote(
+            origin: OriginFor<T>,
+            role_subject: RoleSubject<CidNumber, RoleCode>,
+            delegate: T::AccountId,
+            expires_at: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_delegate_role_vote(&who, role_subject, delegate, expires_at)
+        }
+
+        /// 任职人撤销本人在某岗位主体下的投票委托，立即生效。
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::revoke_role_vote_delegation())]
+        pub fn revoke_role_vote_delegation(
+            origin: OriginFor<T>,
+            role_subject: RoleSubject<CidNumber, RoleCode>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_revoke_role_vote_delegation(&who, role_subject)
+        }
     }
 }
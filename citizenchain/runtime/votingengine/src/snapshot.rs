@@ -4,6 +4,7 @@
 //! 累加岗位票据总数；投票期间不随后续任职变化。`AdminSnapshot` 只供独立个人多签路径
 //! 使用，不得用于机构投票资格判定。
 //!
+//! - `resolve_snapshot_voter`:把投票钱包解析为岗位快照中的票据归属人（含岗位投票委托）
 //! - `is_admin_in_snapshot`:查个人多签快照判断某账户是否为冻结管理员
 //! - `snapshot_admins_len`:个人多签快照中的管理员数量
 //! - `snapshot_role_voters`:按完整岗位主体写入任职快照和 CID 岗位票据总数
@@ -13,7 +14,7 @@ use frame_support::pallet_prelude::{BoundedVec, DispatchResult};
 use crate::pallet::{
     self, AdminSnapshot, Error, InstitutionTicketCountSnapshot, ProposalVotePlans, VoterSnapshot,
 };
-use crate::types::{AuthorizationSubject, CidNumber, ProposalSubject};
+use crate::types::{AuthorizationSubject, CidNumber, ProposalSubject, SnapshotVoter};
 use crate::InternalAdminProvider;
 
 impl<T: pallet::Config> pallet::Pallet<T> {
@@ -40,20 +41,44 @@ impl<T: pallet::Config> pallet::Pallet<T> {
         }
     }
 
-    /// 查询某完整岗位主体冻结的投票人名单。
+    /// 查询某完整岗位主体冻结的投票人名单；有效岗位投票委托的受托人同样视为可投。
     pub fn is_subject_voter_in_snapshot(
         proposal_id: u64,
         subject: AuthorizationSubject<CidNumber, crate::types::RoleCode, T::AccountId>,
         who: &T::AccountId,
     ) -> bool {
-        let Some(voters) = VoterSnapshot::<T>::get(proposal_id, &subject) else {
-            return false;
-        };
+        Self::resolve_snapshot_voter(proposal_id, &subject, who).is_some()
+    }
+
+    /// 把投票钱包解析为冻结快照中的票据归属人。
+    ///
+    /// 本人规范账户在快照中时优先使用本人票据；否则按 [`crate::delegation`] 查询该钱包是否为
+    /// 快照内某任职人的有效受托人，命中时票据仍归属委托人，`delegate` 记录实际签名人。
+    pub fn resolve_snapshot_voter(
+        proposal_id: u64,
+        subject: &AuthorizationSubject<CidNumber, crate::types::RoleCode, T::AccountId>,
+        who: &T::AccountId,
+    ) -> Option<SnapshotVoter<T::AccountId>> {
+        let voters = VoterSnapshot::<T>::get(proposal_id, subject)?;
         // 解析原始钱包 → 规范账户；None = 运行期已非当前管理员（含换绑后的旧钱包）→ 拒。
-        let Some(canonical) = Self::resolve_subject_voter(&subject, who) else {
-            return false;
+        let canonical = Self::resolve_subject_voter(subject, who)?;
+        if voters.iter().any(|account| account == &canonical) {
+            return Some(SnapshotVoter {
+                ticket_holder: canonical,
+                delegate: None,
+            });
+        }
+        let AuthorizationSubject::Institution(role_subject) = subject else {
+            return None;
         };
-        voters.iter().any(|account| account == &canonical)
+        let delegator = Self::active_vote_delegator(proposal_id, role_subject, &canonical)?;
+        voters
+            .iter()
+            .any(|account| account == &delegator)
+            .then_some(SnapshotVoter {
+                ticket_holder: delegator,
+                delegate: Some(canonical),
+            })
     }
 
     /// 查询某个完整岗位主体的冻结选民人数。
@@ -70,12 +95,15 @@ impl<T: pallet::Config> pallet::Pallet<T> {
     }
 
     /// 判断账户是否持有提案任一冻结机构岗位，仅用于重试、取消等非记票权限。
+    ///
+    /// 投票委托只授权代投，不授权受托人重试或取消提案。
     pub fn is_any_institution_voter_in_snapshot(proposal_id: u64, who: &T::AccountId) -> bool {
         ProposalVotePlans::<T>::get(proposal_id)
             .map(|plan| {
                 plan.voter_subjects.iter().any(|subject| {
                     matches!(subject, AuthorizationSubject::Institution(_))
-                        && Self::is_subject_voter_in_snapshot(proposal_id, subject.clone(), who)
+                        && Self::resolve_snapshot_voter(proposal_id, subject, who)
+                            .is_some_and(|voter| voter.delegate.is_none())
                 })
             })
             .unwrap_or(false)
@@ -481,6 +481,26 @@ impl InternalProposalMutexState {
     }
 }
 
+/// 岗位投票委托：任职人把某岗位主体的票据在截止区块前交给同机构另一名管理员代投。
+#[derive(
+    Clone, Debug, PartialEq, Eq, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen,
+)]
+pub struct VoteDelegation<AccountId, BlockNumber> {
+    /// 受托人名册规范账户。
+    pub delegate: AccountId,
+    /// 委托最后有效区块（含）。
+    pub expires_at: BlockNumber,
+}
+
+/// 在冻结岗位快照中解析出的一张票据：票据归属人与实际签名的受托人。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotVoter<AccountId> {
+    /// 票据防重与计票所归属的快照任职人规范账户。
+    pub ticket_holder: AccountId,
+    /// 代投时为受托人规范账户；本人投票为 `None`。
+    pub delegate: Option<AccountId>,
+}
+
 /// proposal_id 到互斥锁的反向绑定，用于终态/阶段切换时释放锁。
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct InternalProposalMutexBinding<AccountId> {
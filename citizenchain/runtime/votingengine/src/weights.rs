@@ -105,6 +105,16 @@ pub trait WeightInfo {
 	/// Storage: `VotingEngine::ProposalOwner` (r:1 w:0)
 	/// Proof: `VotingEngine::ProposalOwner` (`max_values`: None, `max_size`: Some(57), added: 2532, mode: `MaxEncodedLen`)
 	fn process_pending_execution() -> Weight;
+	/// Storage: `VotingEngine::VoteDelegators` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegators` (`max_values`: None, `max_size`: Some(141), added: 2616, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::VoteDelegations` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegations` (`max_values`: None, `max_size`: Some(145), added: 2620, mode: `MaxEncodedLen`)
+	fn delegate_role_vote() -> Weight;
+	/// Storage: `VotingEngine::VoteDelegators` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegators` (`max_values`: None, `max_size`: Some(141), added: 2616, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::VoteDelegations` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegations` (`max_values`: None, `max_size`: Some(145), added: 2620, mode: `MaxEncodedLen`)
+	fn revoke_role_vote_delegation() -> Weight;
 }
 
 pub struct SubstrateWeight<T>(PhantomData<T>);
@@ -215,6 +225,28 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(7))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `VotingEngine::VoteDelegators` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegators` (`max_values`: None, `max_size`: Some(141), added: 2616, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::VoteDelegations` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegations` (`max_values`: None, `max_size`: Some(145), added: 2620, mode: `MaxEncodedLen`)
+	fn delegate_role_vote() -> Weight {
+		// 正式 benchmark 补齐前手写保守上界：管理员名册解析与委托人/受托人两次在册校验
+		// 按各 3 次读计，另含受托人槽位与新旧委托记录读取。
+		Weight::from_parts(60_000_000, 40_000)
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `VotingEngine::VoteDelegators` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegators` (`max_values`: None, `max_size`: Some(141), added: 2616, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::VoteDelegations` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegations` (`max_values`: None, `max_size`: Some(145), added: 2620, mode: `MaxEncodedLen`)
+	fn revoke_role_vote_delegation() -> Weight {
+		// 正式 benchmark 补齐前手写保守上界：管理员名册解析与在册校验按 6 次读计，
+		// 另含委托记录与受托人槽位读取。
+		Weight::from_parts(45_000_000, 40_000)
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 }
 
 impl WeightInfo for () {
@@ -324,4 +356,26 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(7))
 			.saturating_add(RocksDbWeight::get().writes(1))
 	}
+	/// Storage: `VotingEngine::VoteDelegators` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegators` (`max_values`: None, `max_size`: Some(141), added: 2616, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::VoteDelegations` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegations` (`max_values`: None, `max_size`: Some(145), added: 2620, mode: `MaxEncodedLen`)
+	fn delegate_role_vote() -> Weight {
+		// 正式 benchmark 补齐前手写保守上界：管理员名册解析与委托人/受托人两次在册校验
+		// 按各 3 次读计，另含受托人槽位与新旧委托记录读取。
+		Weight::from_parts(60_000_000, 40_000)
+			.saturating_add(RocksDbWeight::get().reads(12))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	/// Storage: `VotingEngine::VoteDelegators` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegators` (`max_values`: None, `max_size`: Some(141), added: 2616, mode: `MaxEncodedLen`)
+	/// Storage: `VotingEngine::VoteDelegations` (r:1 w:1)
+	/// Proof: `VotingEngine::VoteDelegations` (`max_values`: None, `max_size`: Some(145), added: 2620, mode: `MaxEncodedLen`)
+	fn revoke_role_vote_delegation() -> Weight {
+		// 正式 benchmark 补齐前手写保守上界：管理员名册解析与在册校验按 6 次读计，
+		// 另含委托记录与受托人槽位读取。
+		Weight::from_parts(45_000_000, 40_000)
+			.saturating_add(RocksDbWeight::get().reads(8))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
 }
@@ -1,24 +1,18 @@
 #!/usr/bin/env bash
-# Card 05:大市机房——每日 pg_basebackup 全量 + 持续 WAL 归档(= PITR)。
-# 全量落 NAS;WAL 由内嵌 PG 的 archive_command 持续归档到同 NAS(见 onchina 自管 postgresql.conf)。
-# 建议 cron 每日执行本脚本。
+# Card 05:大市机房——加密全量备份 + 持续加密 WAL 归档(= PITR)。
+# onchina 在内嵌 PG 运行时已按 ONCHINA_PG_BACKUP_INTERVAL_SECS 定时备份;本脚本供 cron/运维
+# 额外手动触发一次,并在备份后做整体校验(基础备份逐帧认证 + WAL 连续性)。
+# 备份与 WAL 归档均为密文,恢复必须有 ONCHINA_PG_BACKUP_KEY_FILE 指向的主密钥(请离机托管)。
 set -euo pipefail
 
+: "${ONCHINA_BIN:?需指向 onchina 可执行文件}"
 : "${ONCHINA_PG_BIN_DIR:?需指向内嵌 PG 的 bin 目录(含 pg_basebackup)}"
-: "${ONCHINA_PG_PORT:=5433}"
-: "${ONCHINA_PG_BACKUP_DIR:?需指向 NAS 全量备份根目录}"
+: "${ONCHINA_PG_DATA_DIR:?内嵌 PG 数据目录}"
+: "${ONCHINA_PG_BACKUP_DIR:?需指向 NAS 加密全量备份根目录}"
+export ONCHINA_EMBEDDED_PG=1
 
-STAMP="$(date +%Y%m%d_%H%M%S)"
-DEST="$ONCHINA_PG_BACKUP_DIR/basebackup_$STAMP"
-mkdir -p "$DEST"
+"$ONCHINA_BIN" pg-backup
+"$ONCHINA_BIN" pg-verify
 
-"$ONCHINA_PG_BIN_DIR/pg_basebackup" \
-  -h 127.0.0.1 -p "$ONCHINA_PG_PORT" -U postgres \
-  -D "$DEST" -Ft -z -Xs -P
-
-echo "[backup] 全量备份完成: $DEST"
-echo "[backup] WAL 持续归档由 postgresql.conf 的 archive_command 落 NAS(ONCHINA_PG_WAL_ARCHIVE_DIR)。"
-
-# 可选:保留最近 N 份全量,清理更旧的(默认保留 14 份)。
-KEEP="${ONCHINA_PG_BACKUP_KEEP:-14}"
-ls -1dt "$ONCHINA_PG_BACKUP_DIR"/basebackup_* 2>/dev/null | tail -n +"$((KEEP + 1))" | xargs -r rm -rf
+echo "[backup] 加密全量备份完成并已校验: $ONCHINA_PG_BACKUP_DIR(保留份数 ONCHINA_PG_BACKUP_KEEP,默认 14)"
+echo "[backup] WAL 由 archive_command 回调 onchina pg-wal-archive 加密归档到 ONCHINA_PG_WAL_ARCHIVE_DIR。"
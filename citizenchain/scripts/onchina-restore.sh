@@ -1,28 +1,29 @@
 #!/usr/bin/env bash
-# Card 05:PITR 恢复——从某次 pg_basebackup 全量 + WAL 归档恢复到目标时间点。
-# 用法(目标数据目录须为空):
-#   ONCHINA_PG_DATA_DIR=<新数据目录> \
-#   ONCHINA_PG_WAL_ARCHIVE_DIR=<NAS WAL 归档目录> \
-#   RESTORE_BASEBACKUP=<某次 basebackup_* 全量目录(含 base.tar.gz)> \
-#   [RECOVERY_TARGET_TIME='2026-06-26 12:00:00+08'] \
+# Card 05:PITR 恢复——从某次加密全量备份 + 加密 WAL 归档恢复到目标时间点。
+# 须先停止链上中国平台;原数据目录会被改名为 <数据目录>.pre-restore-<时间> 保留,不删除。
+# 用法:
+#   ONCHINA_BIN=<onchina 可执行文件> \
+#   ONCHINA_PG_BIN_DIR=<内嵌 PG bin 目录> \
+#   ONCHINA_PG_DATA_DIR=<数据目录> \
+#   ONCHINA_PG_BACKUP_DIR=<加密全量备份目录> \
+#   ONCHINA_PG_WAL_ARCHIVE_DIR=<加密 WAL 归档目录> \
+#   ONCHINA_PG_BACKUP_KEY_FILE=<备份主密钥> \
+#   [RESTORE_BASEBACKUP=<basebackup_* 编号,缺省取目标时间前最新一份>] \
+#   [RECOVERY_TARGET_TIME='2026-06-26T12:00:00+08:00'] \
 #   citizenchain/scripts/onchina-restore.sh
 set -euo pipefail
 
-: "${ONCHINA_PG_DATA_DIR:?目标数据目录(须为空,或先把旧目录移走)}"
-: "${ONCHINA_PG_WAL_ARCHIVE_DIR:?WAL 归档目录(NAS)}"
-: "${RESTORE_BASEBACKUP:?某次 pg_basebackup 全量目录(含 base.tar.gz)}"
+: "${ONCHINA_BIN:?需指向 onchina 可执行文件}"
+: "${ONCHINA_PG_BIN_DIR:?内嵌 PG bin 目录}"
+: "${ONCHINA_PG_DATA_DIR:?内嵌 PG 数据目录}"
+: "${ONCHINA_PG_BACKUP_DIR:?加密全量备份目录}"
+: "${ONCHINA_PG_WAL_ARCHIVE_DIR:?加密 WAL 归档目录}"
+export ONCHINA_EMBEDDED_PG=1
 
-mkdir -p "$ONCHINA_PG_DATA_DIR" "$ONCHINA_PG_DATA_DIR/pg_wal"
-tar -xzf "$RESTORE_BASEBACKUP/base.tar.gz" -C "$ONCHINA_PG_DATA_DIR"
-[ -f "$RESTORE_BASEBACKUP/pg_wal.tar.gz" ] && tar -xzf "$RESTORE_BASEBACKUP/pg_wal.tar.gz" -C "$ONCHINA_PG_DATA_DIR/pg_wal"
+args=(pg-restore --backup "${RESTORE_BASEBACKUP:-latest}")
+if [ -n "${RECOVERY_TARGET_TIME:-}" ]; then
+  args+=(--target-time "$RECOVERY_TARGET_TIME")
+fi
+"$ONCHINA_BIN" "${args[@]}"
 
-{
-  echo "restore_command = 'cp $ONCHINA_PG_WAL_ARCHIVE_DIR/%f %p'"
-  if [ -n "${RECOVERY_TARGET_TIME:-}" ]; then
-    echo "recovery_target_time = '$RECOVERY_TARGET_TIME'"
-    echo "recovery_target_action = 'promote'"
-  fi
-} >> "$ONCHINA_PG_DATA_DIR/postgresql.conf"
-touch "$ONCHINA_PG_DATA_DIR/recovery.signal"
-
-echo "[restore] 就绪。启动 postgres 即进入 PITR 恢复;到达目标点后自动 promote 转正常。"
+echo "[restore] 已回放到目标点并 promote;正常启动链上中国平台即使用恢复后的数据。"
@@ -10,6 +10,7 @@ class SignRequestBody implements QrBody {
     required this.signerPublicKey,
     required this.payload,
     this.alg = 1,
+    this.callDescriptor,
   });
 
   /// 业务动作码 `a`:扫码流向以 `k` 表达,业务语义统一放这里。
@@ -27,6 +28,12 @@ class SignRequestBody implements QrBody {
   /// 签名端按 action 重新计算，不能把 32 字节 signing bytes 冒充成这里的载荷。
   final String payload;
 
+  /// 通用调用描述 `c`:仅 `compose_call` 动作携带，其余动作必须缺省。
+  ///
+  /// 描述由生成端按 runtime 元数据给出，签名端只把它当作解码模板，
+  /// 展示内容仍必须从 `d` 逐字节严格解码得出。
+  final Map<String, dynamic>? callDescriptor;
+
   Uint8List get payloadBytes => _b64ToBytes(payload, 'd');
 
   Uint8List get signerPublicKeyBytes => _b64ToBytes(signerPublicKey, 'u');
@@ -41,10 +48,18 @@ class SignRequestBody implements QrBody {
         'g': alg,
         'u': signerPublicKey,
         'd': payload,
+        if (callDescriptor != null) 'c': callDescriptor,
       };
 
   static SignRequestBody fromJson(Map<String, dynamic> data) {
-    requireExactKeys(data, const {'a', 'g', 'u', 'd'}, 'sign_request.b');
+    final isComposeCall = data['a'] == QrActions.composeCall;
+    requireExactKeys(
+      data,
+      isComposeCall
+          ? const {'a', 'g', 'u', 'd', 'c'}
+          : const {'a', 'g', 'u', 'd'},
+      'sign_request.b',
+    );
     final action = data['a'];
     final alg = data['g'];
     final signerPublicKey = data['u'];
@@ -77,11 +92,16 @@ class SignRequestBody implements QrBody {
     if (_b64ToBytes(payload, 'd').isEmpty) {
       throw const FormatException('签名请求 d 不能为空载荷');
     }
+    final callDescriptor = data['c'];
+    if (isComposeCall && callDescriptor is! Map<String, dynamic>) {
+      throw const FormatException('通用调用签名请求 c 必须是调用描述对象');
+    }
     return SignRequestBody(
       action: action,
       alg: alg as int,
       signerPublicKey: signerPublicKey,
       payload: payload,
+      callDescriptor: callDescriptor as Map<String, dynamic>?,
     );
   }
 
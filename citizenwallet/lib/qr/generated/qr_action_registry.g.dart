@@ -85,6 +85,7 @@ class GeneratedQrActionRegistry {
     0x2103: 'set_address',
     0x2104: 'remove_address',
     0x2205: 'propose_set_platform_price',
+    0xff00: 'compose_call',
   };
 
   static const Map<String, int> actionCodeByKey = {
@@ -168,6 +169,7 @@ class GeneratedQrActionRegistry {
     'set_address': 0x2103,
     'remove_address': 0x2104,
     'propose_set_platform_price': 0x2205,
+    'compose_call': 0xff00,
   };
 
   static const Map<String, String> actionLabelZhByKey = {
@@ -251,6 +253,7 @@ class GeneratedQrActionRegistry {
     'set_address': '设置详细地址',
     'remove_address': '删除详细地址',
     'propose_set_platform_price': '发起平台会员调价提案',
+    'compose_call': '通用链上调用',
   };
 
   static const Map<String, String> fieldLabelZhByKey = {
@@ -285,6 +288,10 @@ class GeneratedQrActionRegistry {
     'beneficiary_account_id': '收款账户',
     'birth_date': '出生日期',
     'birth_place': '出生地',
+    'call_args': '调用参数',
+    'call_data_hex': '原始调用数据',
+    'call_name': '调用名称',
+    'call_verification': '元数据核验',
     'catalog_hash': '地址库哈希',
     'catalog_version': '地址库版本',
     'challenge_id': '挑战编号',
@@ -328,6 +335,7 @@ class GeneratedQrActionRegistry {
     'new_rate_bp': '链下费率',
     'new_threshold': '新阈值',
     'operation_fee_payer_description': '操作费付款说明',
+    'pallet_name': '链上模块',
     'peer_id': '节点标识',
     'personal_account_id': '个人多签账户',
     'proof_expires_at': '持钥证明过期区块',
@@ -388,6 +396,9 @@ class GeneratedQrActionRegistry {
     0x0c02,
   };
 
+  static const Map<String, String> pinnedCallDescriptorJsonByKey = {
+  };
+
   static String? actionKeyForCode(int actionCode) =>
       actionKeyByCode[actionCode];
 
@@ -425,4 +436,11 @@ class GeneratedQrActionRegistry {
 
   static bool isHashOnlyAction(int actionCode) =>
       hashOnlyActionCodes.contains(actionCode);
+
+  static String? pinnedCallDescriptorJson(
+    int specVersion,
+    int palletIndex,
+    int callIndex,
+  ) =>
+      pinnedCallDescriptorJsonByKey['$specVersion:$palletIndex:$callIndex'];
 }
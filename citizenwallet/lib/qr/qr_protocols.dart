@@ -202,6 +202,9 @@ class QrActions {
   static int get overrideSign => _code('override_sign');
   static int get guardVote => _code('guard_vote');
 
+  // 通用链上调用：保留动作码，真实 pallet/call 索引在 b.c 描述与 d 载荷中。
+  static int get composeCall => _code('compose_call');
+
   /// 链交易动作统一按 `(pallet_index << 8) | call_index` 生成。
   static int chain(int palletIndex, int callIndex) =>
       ((palletIndex & 0xff) << 8) | (callIndex & 0xff);
@@ -0,0 +1,491 @@
+import 'dart:convert';
+import 'dart:typed_data';
+
+import '../qr/generated/qr_action_registry.g.dart';
+import 'payload_decoder.dart';
+
+/// 通用链上调用(compose_call)签名载荷解码器。
+///
+/// 与 `citizenchain/crates/qr-protocol/src/generic_call.rs` 同口径:节点桌面端按
+/// runtime 元数据组装任意调用时，把调用描述放进 QR `b.c`;钱包据此逐字节严格
+/// 解码 `d` 中的 call_data,前两字节必须等于描述索引、参数完整消费、其后只能是
+/// 合法 SigningPayload 扩展尾。已有专用 decoder 的调用一律拒绝走通用路径。
+///
+/// 描述中的名称与参数类型由请求方自报，只有与 registry 按 spec_version 登记的可信描述
+/// 逐项一致时才展示为已核验；登记了但不一致直接拒绝；未登记时名称标注未核验，
+/// 并把原始索引与 call_data 十六进制放在确认页显著位置。
+class GenericCallDecoder {
+  GenericCallDecoder._();
+
+  /// 参数类型允许的最大嵌套深度。
+  static const int maxArgDepth = 8;
+
+  /// 单个 `Vec` 参数允许的最大元素数。
+  static const int maxVecItems = 4096;
+
+  /// 解码失败(描述非法、命中专用动作、载荷不可解码、与可信描述不符)统一返回 null。
+  ///
+  /// [pinnedDescriptorJson] 仅供测试替换可信描述表，生产路径使用生成的 registry。
+  static DecodedPayload? decode(
+    Map<String, dynamic> descriptorJson,
+    Uint8List bytes, {
+    String? Function(int specVersion, int palletIndex, int callIndex)
+        pinnedDescriptorJson =
+        GeneratedQrActionRegistry.pinnedCallDescriptorJson,
+  }) {
+    final descriptor = GenericCallDescriptor.tryParse(descriptorJson);
+    if (descriptor == null || descriptor.hasDedicatedAction) return null;
+    if (bytes.length < 2 ||
+        bytes[0] != descriptor.palletIndex ||
+        bytes[1] != descriptor.callIndex) {
+      return null;
+    }
+    final reader = _Reader(bytes, 2);
+    final lines = <String>[];
+    for (final arg in descriptor.args) {
+      final value = _decodeValue(arg.type, reader);
+      if (value == null) return null;
+      lines.add('${arg.name}: $value');
+    }
+    final callEnd = reader.offset;
+    if (!PayloadDecoder.hasValidSigningTail(bytes, callEnd)) return null;
+    final specVersion = PayloadDecoder.signingSpecVersion(bytes);
+    final verified = _isPinned(
+      descriptor,
+      pinnedDescriptorJson(
+        specVersion,
+        descriptor.palletIndex,
+        descriptor.callIndex,
+      ),
+    );
+    if (verified == null) return null;
+    final fields = <String, String>{
+      'call_verification': verified
+          ? '已核验：与 spec_version $specVersion 登记的调用描述一致'
+          : '未核验：spec_version $specVersion 未登记该调用，名称与参数类型由请求方自报，'
+              '请以索引和原始调用数据为准',
+      'pallet_name': verified
+          ? '${descriptor.palletName}(${descriptor.palletIndex})'
+          : '#${descriptor.palletIndex}(自报名称 ${descriptor.palletName},未核验)',
+      'call_name': verified
+          ? '${descriptor.callName}(${descriptor.callIndex})'
+          : '#${descriptor.callIndex}(自报名称 ${descriptor.callName},未核验)',
+      'call_args': lines.join('\n'),
+      'call_data_hex':
+          '0x${_toHex(Uint8List.sublistView(bytes, 0, callEnd))}',
+    };
+    return DecodedPayload(
+      action: 'compose_call',
+      summary: verified
+          ? '通用链上调用 ${descriptor.palletName}::${descriptor.callName}'
+          : '未核验的通用链上调用 #${descriptor.palletIndex}/#${descriptor.callIndex}'
+              '(名称由请求方自报)',
+      fields: fields,
+      reviewFields: fields,
+    );
+  }
+
+  /// true=与可信描述一致；false=未登记；null=登记了但不一致(拒绝签名)。
+  static bool? _isPinned(GenericCallDescriptor descriptor, String? pinnedJson) {
+    if (pinnedJson == null) return false;
+    final Object? raw;
+    try {
+      raw = jsonDecode(pinnedJson);
+    } on FormatException {
+      return null;
+    }
+    if (raw is! Map<String, dynamic>) return null;
+    final pinned = GenericCallDescriptor.tryParse(raw);
+    if (pinned == null) return null;
+    return pinned.canonicalJson == descriptor.canonicalJson ? true : null;
+  }
+
+  static String? _decodeValue(_ArgType type, _Reader reader) {
+    switch (type.kind) {
+      case 'bool':
+        final flag = reader.byte();
+        if (flag == 0) return 'false';
+        if (flag == 1) return 'true';
+        return null;
+      case 'u8':
+        return reader.uint(1)?.toString();
+      case 'u16':
+        return reader.uint(2)?.toString();
+      case 'u32':
+        return reader.uint(4)?.toString();
+      case 'u64':
+        return reader.uint(8)?.toString();
+      case 'u128':
+        return reader.uint(16)?.toString();
+      case 'compact':
+        return reader.compact()?.toString();
+      case 'bytes':
+        final len = reader.length(reader.remaining);
+        if (len == null) return null;
+        final raw = reader.take(len);
+        return raw == null ? null : _displayBytes(raw);
+      case 'fixed_bytes':
+        final raw = reader.take(type.fixedLen);
+        return raw == null ? null : '0x${_toHex(raw)}';
+      case 'account_id':
+        final raw = reader.take(32);
+        return raw == null ? null : '0x${_toHex(raw)}';
+      case 'option':
+        final tag = reader.byte();
+        if (tag == 0) return 'None';
+        if (tag == 1) return _decodeValue(type.inner.single, reader);
+        return null;
+      case 'vec':
+        final len = reader.length(maxVecItems);
+        if (len == null) return null;
+        final items = <String>[];
+        for (var i = 0; i < len; i++) {
+          final item = _decodeValue(type.inner.single, reader);
+          if (item == null) return null;
+          items.add(item);
+        }
+        return '[${items.join(', ')}]';
+      case 'tuple':
+        final items = <String>[];
+        for (final itemType in type.inner) {
+          final item = _decodeValue(itemType, reader);
+          if (item == null) return null;
+          items.add(item);
+        }
+        return '(${items.join(', ')})';
+      case 'composite':
+        return _decodeFields(type.fields, reader);
+      case 'variant':
+        final index = reader.byte();
+        if (index == null) return null;
+        final matches = type.variants.where((v) => v.index == index);
+        if (matches.isEmpty) return null;
+        final variant = matches.first;
+        if (variant.fields.isEmpty) return variant.name;
+        final fields = _decodeFields(variant.fields, reader);
+        return fields == null ? null : '${variant.name} $fields';
+    }
+    return null;
+  }
+
+  static String? _decodeFields(List<_ArgDescriptor> fields, _Reader reader) {
+    if (fields.length == 1 && fields.single.name.isEmpty) {
+      return _decodeValue(fields.single.type, reader);
+    }
+    final items = <String>[];
+    for (final field in fields) {
+      final value = _decodeValue(field.type, reader);
+      if (value == null) return null;
+      items.add(field.name.isEmpty ? value : '${field.name}: $value');
+    }
+    return '{${items.join(', ')}}';
+  }
+
+  /// 可打印 UTF-8 原样加引号展示，否则展示 `0x` 十六进制，防止控制字符伪造排版。
+  static String _displayBytes(Uint8List raw) {
+    if (raw.isNotEmpty) {
+      try {
+        final text = utf8.decode(raw);
+        final hasControl =
+            text.runes.any((r) => r < 0x20 || (r >= 0x7f && r <= 0x9f));
+        if (!hasControl) return '"$text"';
+      } on FormatException {
+        // 非 UTF-8 落到十六进制展示。
+      }
+    }
+    return '0x${_toHex(raw)}';
+  }
+
+  static String _toHex(List<int> bytes) =>
+      bytes.map((b) => b.toRadixString(16).padLeft(2, '0')).join();
+}
+
+/// QR `b.c` 中的调用描述，字段与 Rust `CallDescriptor` 的 serde 形态一致。
+class GenericCallDescriptor {
+  const GenericCallDescriptor._({
+    required this.palletIndex,
+    required this.palletName,
+    required this.callIndex,
+    required this.callName,
+    required this.args,
+  });
+
+  final int palletIndex;
+  final String palletName;
+  final int callIndex;
+  final String callName;
+  final List<_ArgDescriptor> args;
+
+  int get chainActionCode => (palletIndex << 8) | callIndex;
+
+  /// 按固定键序重新编码，用于与可信描述逐项比较(与请求方 JSON 的键序、空白无关)。
+  String get canonicalJson => jsonEncode({
+        'pallet_index': palletIndex,
+        'pallet_name': palletName,
+        'call_index': callIndex,
+        'call_name': callName,
+        'args': _fieldsJson(args),
+      });
+
+  static List<Object> _fieldsJson(List<_ArgDescriptor> fields) => [
+        for (final field in fields)
+          {'name': field.name, 'ty': _typeJson(field.type)},
+      ];
+
+  static Object _typeJson(_ArgType type) {
+    switch (type.kind) {
+      case 'fixed_bytes':
+        return {'fixed_bytes': type.fixedLen};
+      case 'option':
+      case 'vec':
+        return {type.kind: _typeJson(type.inner.single)};
+      case 'tuple':
+        return {'tuple': type.inner.map(_typeJson).toList()};
+      case 'composite':
+        return {'composite': _fieldsJson(type.fields)};
+      case 'variant':
+        return {
+          'variant': [
+            for (final variant in type.variants)
+              {
+                'index': variant.index,
+                'name': variant.name,
+                'fields': _fieldsJson(variant.fields),
+              },
+          ],
+        };
+    }
+    return type.kind;
+  }
+
+  /// 该索引已登记专用链交易动作(含 compose_call 自身保留码)时必须拒绝。
+  bool get hasDedicatedAction =>
+      chainActionCode >= 0x0100 &&
+      GeneratedQrActionRegistry.actionKeyForCode(chainActionCode) != null;
+
+  /// 严格解析：键集精确、索引为 u8、名称非空、参数名唯一、深度与判别字节合法。
+  static GenericCallDescriptor? tryParse(Map<String, dynamic> json) {
+    if (!_hasExactKeys(json, const {
+      'pallet_index',
+      'pallet_name',
+      'call_index',
+      'call_name',
+      'args',
+    })) {
+      return null;
+    }
+    final palletIndex = json['pallet_index'];
+    final palletName = json['pallet_name'];
+    final callIndex = json['call_index'];
+    final callName = json['call_name'];
+    if (!_isU8(palletIndex) ||
+        !_isU8(callIndex) ||
+        palletName is! String ||
+        callName is! String ||
+        palletName.trim().isEmpty ||
+        callName.trim().isEmpty) {
+      return null;
+    }
+    final args = _parseFields(json['args'], 0, requireNames: true);
+    if (args == null) return null;
+    return GenericCallDescriptor._(
+      palletIndex: palletIndex as int,
+      palletName: palletName,
+      callIndex: callIndex as int,
+      callName: callName,
+      args: args,
+    );
+  }
+
+  static List<_ArgDescriptor>? _parseFields(
+    Object? json,
+    int depth, {
+    bool requireNames = false,
+  }) {
+    if (json is! List) return null;
+    final names = <String>{};
+    final fields = <_ArgDescriptor>[];
+    for (final item in json) {
+      if (item is! Map<String, dynamic> ||
+          !_hasExactKeys(item, const {'name', 'ty'})) {
+        return null;
+      }
+      final name = item['name'];
+      if (name is! String) return null;
+      if (requireNames && name.trim().isEmpty) return null;
+      if (name.isNotEmpty && !names.add(name)) return null;
+      final type = _parseType(item['ty'], depth + 1);
+      if (type == null) return null;
+      fields.add(_ArgDescriptor(name, type));
+    }
+    return fields;
+  }
+
+  static _ArgType? _parseType(Object? json, int depth) {
+    if (depth > GenericCallDecoder.maxArgDepth) return null;
+    if (json is String) {
+      const scalars = {
+        'bool',
+        'u8',
+        'u16',
+        'u32',
+        'u64',
+        'u128',
+        'compact',
+        'bytes',
+        'account_id',
+      };
+      return scalars.contains(json) ? _ArgType(json) : null;
+    }
+    if (json is! Map<String, dynamic> || json.length != 1) return null;
+    final kind = json.keys.single;
+    final value = json[kind];
+    switch (kind) {
+      case 'fixed_bytes':
+        if (value is! int || value < 0 || value > 0xffffffff) return null;
+        return _ArgType(kind, fixedLen: value);
+      case 'option':
+      case 'vec':
+        final inner = _parseType(value, depth + 1);
+        return inner == null ? null : _ArgType(kind, inner: [inner]);
+      case 'tuple':
+        if (value is! List) return null;
+        final items = <_ArgType>[];
+        for (final item in value) {
+          final itemType = _parseType(item, depth + 1);
+          if (itemType == null) return null;
+          items.add(itemType);
+        }
+        return _ArgType(kind, inner: items);
+      case 'composite':
+        final fields = _parseFields(value, depth);
+        return fields == null ? null : _ArgType(kind, fields: fields);
+      case 'variant':
+        if (value is! List) return null;
+        final indices = <int>{};
+        final variants = <_VariantDescriptor>[];
+        for (final item in value) {
+          if (item is! Map<String, dynamic> ||
+              !_hasExactKeys(item, const {'index', 'name', 'fields'})) {
+            return null;
+          }
+          final index = item['index'];
+          final name = item['name'];
+          if (!_isU8(index) ||
+              name is! String ||
+              name.trim().isEmpty ||
+              !indices.add(index as int)) {
+            return null;
+          }
+          final fields = _parseFields(item['fields'], depth);
+          if (fields == null) return null;
+          variants.add(_VariantDescriptor(index, name, fields));
+        }
+        return _ArgType(kind, variants: variants);
+    }
+    return null;
+  }
+
+  static bool _isU8(Object? value) =>
+      value is int && value >= 0 && value <= 0xff;
+
+  static bool _hasExactKeys(Map<String, dynamic> json, Set<String> expected) =>
+      json.length == expected.length && expected.every(json.containsKey);
+}
+
+class _ArgDescriptor {
+  const _ArgDescriptor(this.name, this.type);
+
+  final String name;
+  final _ArgType type;
+}
+
+class _VariantDescriptor {
+  const _VariantDescriptor(this.index, this.name, this.fields);
+
+  final int index;
+  final String name;
+  final List<_ArgDescriptor> fields;
+}
+
+class _ArgType {
+  const _ArgType(
+    this.kind, {
+    this.fixedLen = 0,
+    this.inner = const [],
+    this.fields = const [],
+    this.variants = const [],
+  });
+
+  final String kind;
+  final int fixedLen;
+  final List<_ArgType> inner;
+  final List<_ArgDescriptor> fields;
+  final List<_VariantDescriptor> variants;
+}
+
+class _Reader {
+  _Reader(this.bytes, this.offset);
+
+  final Uint8List bytes;
+  int offset;
+
+  int get remaining => bytes.length - offset;
+
+  Uint8List? take(int len) {
+    if (len < 0 || len > remaining) return null;
+    final slice = Uint8List.sublistView(bytes, offset, offset + len);
+    offset += len;
+    return slice;
+  }
+
+  int? byte() {
+    final raw = take(1);
+    return raw?.first;
+  }
+
+  BigInt? uint(int len) {
+    final raw = take(len);
+    if (raw == null) return null;
+    var value = BigInt.zero;
+    for (var i = raw.length - 1; i >= 0; i--) {
+      value = (value << 8) | BigInt.from(raw[i]);
+    }
+    return value;
+  }
+
+  /// 只接受规范最短编码，与 `parity-scale-codec` 的 `Compact` 解码规则一致。
+  BigInt? compact() {
+    final first = byte();
+    if (first == null) return null;
+    switch (first & 0x03) {
+      case 0:
+        return BigInt.from(first >> 2);
+      case 1:
+        final second = byte();
+        if (second == null) return null;
+        final value = ((second << 8) | first) >> 2;
+        return value < (1 << 6) ? null : BigInt.from(value);
+      case 2:
+        final rest = take(3);
+        if (rest == null) return null;
+        final value =
+            ((rest[2] << 24) | (rest[1] << 16) | (rest[0] << 8) | first) >> 2;
+        return value < (1 << 14) ? null : BigInt.from(value);
+      default:
+        final len = (first >> 2) + 4;
+        if (len > 16) return null;
+        final value = uint(len);
+        if (value == null || value < BigInt.from(1 << 30)) return null;
+        final minLen = (value.bitLength + 7) ~/ 8;
+        if (len != (minLen < 4 ? 4 : minLen)) return null;
+        return value;
+    }
+  }
+
+  int? length(int max) {
+    final value = compact();
+    if (value == null || value > BigInt.from(max)) return null;
+    return value.toInt();
+  }
+}
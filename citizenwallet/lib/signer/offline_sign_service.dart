@@ -5,6 +5,7 @@ import '../isar/wallet_isar.dart';
 import '../wallet/wallet_manager.dart';
 import 'action_labels.dart';
 import 'field_labels.dart';
+import 'generic_call_decoder.dart';
 import 'payload_decoder.dart';
 import 'qr_signer.dart';
 
@@ -152,7 +153,13 @@ class OfflineSignService {
       );
     }
 
-    final decoded = PayloadDecoder.decode(body.payloadHex);
+    // 通用链上调用只能按 b.c 描述解码；缺描述视同载荷不可解码。
+    final callDescriptor = body.callDescriptor;
+    final decoded = body.action == QrActions.composeCall
+        ? (callDescriptor == null
+            ? null
+            : GenericCallDecoder.decode(callDescriptor, body.payloadBytes))
+        : PayloadDecoder.decode(body.payloadHex);
 
     if (decoded == null) {
       return OfflineSignVerification(
@@ -2179,6 +2179,7 @@ class PayloadDecoder {
   //        [actor_cid_number][proposer_role_code][executive_cid_number]
   //        [legislature_cid_number:Option<CidNumber>]
   //        [vote_type:u8][title][title_en][chapters][effective_at:u64_le]
+  //        [sunset:LawSunset{expires_at:Option<u64>,review_by:Option<u64>}]
   static DecodedPayload? _decodeProposeEnactLaw(Uint8List bytes) {
     if (bytes.length < 3) return null;
     var offset = 2;
@@ -2242,12 +2243,21 @@ class PayloadDecoder {
     final effectiveAt = _readU64Le(bytes, offset);
     offset += 8;
 
+    // sunset: 失效 / 复审时间戳，均为 Option<u64> 毫秒。
+    final expiresRead = _readOptionalU64(bytes, offset);
+    if (expiresRead == null) return null;
+    offset = expiresRead.$2;
+    final reviewRead = _readOptionalU64(bytes, offset);
+    if (reviewRead == null) return null;
+    offset = reviewRead.$2;
+    final sunsetText = _lawSunsetText(expiresRead.$1, reviewRead.$1);
+
     if (!_hasValidSigningTail(bytes, offset)) return null;
 
     return DecodedPayload(
       action: 'propose_enact_law',
       summary:
-          '发起立法「$title」（$tierLabel·$voteTypeLabel，$chapterCount 章 $articleTotal 条，时间戳 $effectiveAt 生效）',
+          '发起立法「$title」（$tierLabel·$voteTypeLabel，$chapterCount 章 $articleTotal 条，时间戳 $effectiveAt 生效$sunsetText）',
       fields: {
         'title': title,
         'tier': tierLabel,
@@ -2262,6 +2272,10 @@ class PayloadDecoder {
         'chapter_count': chapterCount.toString(),
         'article_count': articleTotal.toString(),
         'effective_at': effectiveAt.toString(),
+        if (expiresRead.$1 != null)
+          'sunset_expires_at': expiresRead.$1.toString(),
+        if (reviewRead.$1 != null)
+          'sunset_review_by': reviewRead.$1.toString(),
       },
     );
   }
@@ -2270,6 +2284,7 @@ class PayloadDecoder {
   // SCALE: [25][1][law_id:u64_le][actor_cid_number][proposer_role_code][executive_cid_number]
   //        [legislature_cid_number:Option<CidNumber>][vote_type:u8][title]
   //        [title_en][chapters][effective_at:u64_le]
+  //        [sunset:LawSunset{expires_at:Option<u64>,review_by:Option<u64>}]
   static DecodedPayload? _decodeProposeAmendLaw(Uint8List bytes) {
     if (bytes.length < 10) return null;
     var offset = 2;
@@ -2315,12 +2330,21 @@ class PayloadDecoder {
     final effectiveAt = _readU64Le(bytes, offset);
     offset += 8;
 
+    // sunset: 失效 / 复审时间戳，均为 Option<u64> 毫秒。
+    final expiresRead = _readOptionalU64(bytes, offset);
+    if (expiresRead == null) return null;
+    offset = expiresRead.$2;
+    final reviewRead = _readOptionalU64(bytes, offset);
+    if (reviewRead == null) return null;
+    offset = reviewRead.$2;
+    final sunsetText = _lawSunsetText(expiresRead.$1, reviewRead.$1);
+
     if (!_hasValidSigningTail(bytes, offset)) return null;
 
     return DecodedPayload(
       action: 'propose_amend_law',
       summary:
-          '发起修法「$title」（法律 #$lawId·$voteTypeLabel，$chapterCount 章 $articleTotal 条，时间戳 $effectiveAt 生效）',
+          '发起修法「$title」（法律 #$lawId·$voteTypeLabel，$chapterCount 章 $articleTotal 条，时间戳 $effectiveAt 生效$sunsetText）',
       fields: {
         'law_id': lawId.toString(),
         'actor_cid_number': actorRead.$1,
@@ -2333,6 +2357,10 @@ class PayloadDecoder {
         'chapter_count': chapterCount.toString(),
         'article_count': articleTotal.toString(),
         'effective_at': effectiveAt.toString(),
+        if (expiresRead.$1 != null)
+          'sunset_expires_at': expiresRead.$1.toString(),
+        if (reviewRead.$1 != null)
+          'sunset_review_by': reviewRead.$1.toString(),
       },
     );
   }
@@ -3260,21 +3288,40 @@ class PayloadDecoder {
   /// + genesis_hash(32) + birth_hash(32) + CheckMetadataHash None(1)。
   static const int _signingTailFixedLen = 73;
 
+  /// mortal era 周期上限,与 chain-signing `MAX_MORTAL_PERIOD` 一致
+  /// (不超过链上 BlockHashCount,出生块哈希必须仍可查)。
+  static const int _maxMortalPeriod = 2048;
+
   /// 校验 call_data 在 [callEnd] 处结束,其后是合法的 SigningPayload 扩展尾。
   ///
   /// QR 的 payload_hex 是完整 SigningPayload,call_data 永远不会顶到末尾;
   /// 尾部布局与节点端 build_signing_payload / CitizenApp polkadart 编码一致:
-  /// era(0x00 immortal,P-SIGN-001) + Compact<nonce> + Compact<tip=0>
+  /// era(0x00 immortal 或 2 字节 mortal) + Compact<nonce> + Compact<tip=0>
   /// + mode(0x00) + 固定 73 字节(末字节 Option::None=0x00,immortal 下
-  /// birth hash 必等于 genesis hash)。
+  /// birth hash 必等于 genesis hash,mortal 下为出生块哈希)。
+  /// 通用调用解码器与专用分支共用同一扩展尾校验口径。
+  static bool hasValidSigningTail(Uint8List bytes, int callEnd) =>
+      _hasValidSigningTail(bytes, callEnd);
+
+  /// 读取扩展尾固定段开头的 spec_version;调用方须先通过 [hasValidSigningTail]。
+  static int signingSpecVersion(Uint8List bytes) {
+    final start = bytes.length - _signingTailFixedLen;
+    return bytes[start] |
+        (bytes[start + 1] << 8) |
+        (bytes[start + 2] << 16) |
+        (bytes[start + 3] << 24);
+  }
+
   /// 所有链上 extrinsic 分支统一以此判定 call_data 边界:既接受真实 payload,
   /// 又防止在 call_data 后夹带任意字节骗签。
   static bool _hasValidSigningTail(Uint8List bytes, int callEnd) {
     if (callEnd < 2 || callEnd >= bytes.length) return false;
     var offset = callEnd;
-    // CheckEra: immortal 单字节 0x00。
-    if (bytes[offset] != 0x00) return false;
-    offset += 1;
+    // CheckEra: immortal 单字节 0x00;mortal 为 2 字节 u16 LE。
+    final eraSize = _signingEraSize(bytes, offset);
+    if (eraSize == 0) return false;
+    final immortal = eraSize == 1;
+    offset += eraSize;
     // CheckNonce: Compact<u32>
     final (nonceValue, nonceSize) = _decodeCompactBigInt(bytes, offset);
     if (nonceValue == null || nonceSize == 0) return false;
@@ -3293,6 +3340,7 @@ class PayloadDecoder {
     // 末字节 CheckMetadataHash Option::None。
     if (bytes[bytes.length - 1] != 0x00) return false;
     // immortal:CheckEra additional 的 birth hash 必等于 genesis hash。
+    if (!immortal) return true;
     final genesisStart = offset + 8;
     for (var i = 0; i < 32; i++) {
       if (bytes[genesisStart + i] != bytes[genesisStart + 32 + i]) {
@@ -3302,6 +3350,21 @@ class PayloadDecoder {
     return true;
   }
 
+  /// 返回 era 编码字节数:immortal=1,合法 mortal=2,非法=0。
+  ///
+  /// mortal 按 sp_runtime `Era::decode`:低 4 位给出 period=2<<n,
+  /// 高 12 位乘量化因子为 phase;要求 4<=period<=2048 且 phase<period。
+  static int _signingEraSize(Uint8List bytes, int offset) {
+    if (bytes[offset] == 0x00) return 1;
+    if (offset + 1 >= bytes.length) return 0;
+    final encoded = bytes[offset] | (bytes[offset + 1] << 8);
+    final period = 2 << (encoded & 0x0f);
+    final quantizeFactor = (period >> 12) > 1 ? period >> 12 : 1;
+    final phase = (encoded >> 4) * quantizeFactor;
+    if (period < 4 || period > _maxMortalPeriod || phase >= period) return 0;
+    return 2;
+  }
+
   /// OnChina 部分 QR 直接携带裸 call_data；完整 SigningPayload 仍走严格尾部校验。
   static bool _hasCallDataEnd(Uint8List bytes, int callEnd) {
     return callEnd == bytes.length || _hasValidSigningTail(bytes, callEnd);
@@ -3945,6 +4008,24 @@ class PayloadDecoder {
     return (cidRead.$1, cidRead.$2);
   }
 
+  /// 解码 `Option<u64>`（小端）；只接受规范的 0/1 判别值。
+  static (int?, int)? _readOptionalU64(Uint8List bytes, int offset) {
+    if (offset >= bytes.length) return null;
+    final tag = bytes[offset++];
+    if (tag == 0) return (null, offset);
+    if (tag != 1 || offset + 8 > bytes.length) return null;
+    return (_readU64Le(bytes, offset), offset + 8);
+  }
+
+  /// 立法/修法日落条款的摘要片段；两项皆空返回空串。
+  static String _lawSunsetText(int? expiresAt, int? reviewBy) {
+    final parts = [
+      if (expiresAt != null) '时间戳 $expiresAt 日落失效',
+      if (reviewBy != null) '时间戳 $reviewBy 复审',
+    ];
+    return parts.isEmpty ? '' : '，${parts.join('，')}';
+  }
+
   /// 激活凭证里的账户 kind 与机构码是否匹配。
   ///
   /// kind 语义对齐链端 admin-primitives::AdminAccountKind(SCALE 判别值):
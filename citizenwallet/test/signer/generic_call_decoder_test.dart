@@ -0,0 +1,149 @@
+import 'dart:convert';
+import 'dart:typed_data';
+
+import 'package:flutter_test/flutter_test.dart';
+import 'package:citizenwallet/signer/generic_call_decoder.dart';
+
+/// 与 qr-protocol `generic_call.rs` 测试同一描述：Sandbox(200)::configure(3)。
+Map<String, dynamic> _descriptor({int palletIndex = 200, int callIndex = 3}) =>
+    <String, dynamic>{
+      'pallet_index': palletIndex,
+      'pallet_name': 'Sandbox',
+      'call_index': callIndex,
+      'call_name': 'configure',
+      'args': [
+        {'name': 'who', 'ty': 'account_id'},
+        {'name': 'amount', 'ty': 'compact'},
+        {
+          'name': 'memo',
+          'ty': {'option': 'bytes'},
+        },
+        {
+          'name': 'mode',
+          'ty': {
+            'variant': [
+              {'index': 0, 'name': 'Off', 'fields': <dynamic>[]},
+              {
+                'index': 1,
+                'name': 'Limit',
+                'fields': [
+                  {'name': '', 'ty': 'u32'},
+                ],
+              },
+            ],
+          },
+        },
+      ],
+    };
+
+List<int> _callData() => [
+      200, 3,
+      ...List<int>.filled(32, 0xab),
+      0x28, // Compact(10)
+      0x01, 0x08, 0x68, 0x69, // Some("hi")
+      0x01, 7, 0, 0, 0, // Limit(7)
+    ];
+
+Uint8List _withSigningTail(List<int> callData) {
+  final genesis = List<int>.generate(32, (i) => 0x49 ^ i);
+  return Uint8List.fromList([
+    ...callData,
+    0x00, // era: immortal
+    0x04, // Compact(nonce=1)
+    0x00, // Compact(tip=0)
+    0x00, // CheckMetadataHash mode=Disabled
+    1, 0, 0, 0,
+    1, 0, 0, 0,
+    ...genesis,
+    ...genesis,
+    0x00,
+  ]);
+}
+
+void main() {
+  group('GenericCallDecoder', () {
+    test('未登记可信描述时名称标注未核验并展示原始调用数据', () {
+      final decoded =
+          GenericCallDecoder.decode(_descriptor(), _withSigningTail(_callData()));
+      expect(decoded, isNotNull);
+      expect(decoded!.action, 'compose_call');
+      expect(decoded.summary, contains('未核验'));
+      expect(decoded.reviewFields['call_verification'], startsWith('未核验'));
+      expect(decoded.reviewFields['pallet_name'], '#200(自报名称 Sandbox,未核验)');
+      expect(decoded.reviewFields['call_name'], '#3(自报名称 configure,未核验)');
+      expect(
+        decoded.reviewFields['call_args'],
+        'who: 0x${'ab' * 32}\namount: 10\nmemo: "hi"\nmode: Limit 7',
+      );
+      expect(
+        decoded.reviewFields['call_data_hex'],
+        '0x${_callData().map((b) => b.toRadixString(16).padLeft(2, '0')).join()}',
+      );
+    });
+
+    test('与 spec_version 登记的可信描述一致时展示已核验名称', () {
+      final pinned = jsonEncode(_descriptor());
+      final decoded = GenericCallDecoder.decode(
+        _descriptor(),
+        _withSigningTail(_callData()),
+        pinnedDescriptorJson: (spec, pallet, call) =>
+            spec == 1 && pallet == 200 && call == 3 ? pinned : null,
+      );
+      expect(decoded, isNotNull);
+      expect(decoded!.reviewFields['call_verification'], startsWith('已核验'));
+      expect(decoded.reviewFields['pallet_name'], 'Sandbox(200)');
+      expect(decoded.reviewFields['call_name'], 'configure(3)');
+    });
+
+    test('与可信描述不一致(改名或改参数类型)时拒绝', () {
+      final renamed = _descriptor()..['call_name'] = 'transfer_all';
+      final pinned = jsonEncode(renamed);
+      expect(
+        GenericCallDecoder.decode(
+          _descriptor(),
+          _withSigningTail(_callData()),
+          pinnedDescriptorJson: (_, __, ___) => pinned,
+        ),
+        isNull,
+      );
+    });
+
+    test('夹带字节、索引不符、非规范 Compact 一律拒绝', () {
+      expect(
+        GenericCallDecoder.decode(
+            _descriptor(), _withSigningTail([..._callData(), 0x00])),
+        isNull,
+      );
+      expect(
+        GenericCallDecoder.decode(
+            _descriptor(callIndex: 4), _withSigningTail(_callData())),
+        isNull,
+      );
+      final nonCanonical = _callData()..replaceRange(34, 35, [0x29, 0x00]);
+      expect(
+        GenericCallDecoder.decode(_descriptor(), _withSigningTail(nonCanonical)),
+        isNull,
+      );
+    });
+
+    test('已有专用动作的调用不能走通用路径', () {
+      // Balances(4)::transfer(0) 已登记 transfer 专用 decoder。
+      final call = [4, 0, ..._callData().sublist(2)];
+      expect(
+        GenericCallDecoder.decode(
+          _descriptor(palletIndex: 4, callIndex: 0),
+          _withSigningTail(call),
+        ),
+        isNull,
+      );
+    });
+
+    test('描述含未知键或重复参数名时拒绝', () {
+      final extra = _descriptor()..['extra'] = 1;
+      expect(GenericCallDescriptor.tryParse(extra), isNull);
+      final duplicated = _descriptor();
+      (duplicated['args'] as List).add({'name': 'who', 'ty': 'bool'});
+      expect(GenericCallDescriptor.tryParse(duplicated), isNull);
+    });
+  });
+}
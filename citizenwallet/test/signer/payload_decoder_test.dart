@@ -142,8 +142,8 @@ void main() {
   });
 
   // SigningPayload 扩展尾,布局与节点端 build_signing_payload / citizenapp
-  // polkadart 编码一致:era(0x00 immortal) + Compact<nonce> + Compact<tip>
-  // + mode(0x00) + spec(4) + tx(4) + genesis(32) + birth=genesis(32) + None。
+  // polkadart 编码一致:era(0x00 immortal / 2 字节 mortal) + Compact<nonce>
+  // + Compact<tip> + mode(0x00) + spec(4) + tx(4) + genesis(32) + birth=genesis(32) + None。
   // 真实 QR payload_hex = call_data + 本尾部;链上分支夹具必须带尾构造,
   // 裸 call_data 会被 decoder 的尾部校验拒绝(Reject → 红色)。
   final tailGenesis = List<int>.generate(32, (i) => 0x49 ^ i);
@@ -2584,11 +2584,28 @@ void main() {
           reason: 'tip 不属于五类交易费，冷钱包必须在签名前拒绝非零 tip');
     });
 
+    test('接受 mortal era 扩展尾(出生块哈希不必等于 genesis)', () {
+      final callData = buildNrcTransferCallData();
+      final good = withSigningTail(callData);
+      // period=64, phase=40 → u16 LE 0x0285。
+      final mortal = Uint8List.fromList([
+        ...callData,
+        0x85, 0x02,
+        ...good.sublist(callData.length + 1),
+      ]);
+      mortal[mortal.length - 2] ^= 0xff;
+      expect(PayloadDecoder.decode(hexOf(mortal))?.action, 'propose_transfer');
+
+      // period=4096 超出 BlockHashCount 可查范围。
+      final tooLong = Uint8List.fromList(mortal)..[callData.length] = 0x0b;
+      expect(PayloadDecoder.decode(hexOf(tooLong)), isNull);
+    });
+
     test('rejects 篡改的签名扩展尾', () {
       final callData = buildNrcTransferCallData();
       final good = withSigningTail(callData);
 
-      // era ≠ immortal(0x00)
+      // 非法 era:0x15 与其后 nonce 字节组成 phase >= period 的 mortal era
       final badEra = Uint8List.fromList(good);
       badEra[callData.length] = 0x15;
       expect(PayloadDecoder.decode(hexOf(badEra)), isNull);
@@ -2653,6 +2670,7 @@ void main() {
         0x00, // title_en None
         ...minimalChapters(),
         ...u64Le(5000), // effective_at: unix 毫秒
+        0x00, 0x00, // sunset: 不设失效 / 复审
       ];
       final decoded = PayloadDecoder.decode(hexOf(withSigningTail(callData)));
       expect(decoded, isNotNull);
@@ -2663,6 +2681,7 @@ void main() {
       expect(decoded.fields['chapter_count'], '1');
       expect(decoded.fields['article_count'], '1');
       expect(decoded.fields['effective_at'], '5000');
+      expect(decoded.fields.containsKey('sunset_expires_at'), isFalse);
       expect(decoded.fields['houses'], '$firstHouseCid、$secondHouseCid');
       expect(decoded.fields['actor_cid_number'], nrcActorCid);
       expect(decoded.fields['proposer_role_code'], proposerRoleCode);
@@ -2685,6 +2704,7 @@ void main() {
         0x00,
         ...minimalChapters(),
         ...u64Le(1),
+        0x00, 0x00,
       ];
       expect(PayloadDecoder.decode(hexOf(withSigningTail(callData))), isNull);
     });
@@ -2705,6 +2725,7 @@ void main() {
         0x00,
         ...minimalChapters(),
         ...u64Le(1),
+        0x00, 0x00,
       ];
       expect(PayloadDecoder.decode(hexOf(withSigningTail(callData))), isNull);
     });
@@ -2722,6 +2743,8 @@ void main() {
         0x00,
         ...minimalChapters(),
         ...u64Le(7777),
+        0x01, ...u64Le(90000), // sunset.expires_at Some
+        0x01, ...u64Le(60000), // sunset.review_by Some
       ];
       final decoded = PayloadDecoder.decode(hexOf(withSigningTail(callData)));
       expect(decoded, isNotNull);
@@ -2730,12 +2753,33 @@ void main() {
       expect(decoded.fields['title'], '修订版');
       expect(decoded.fields['vote_type'], '特别案（强制公投）');
       expect(decoded.fields['effective_at'], '7777');
+      expect(decoded.fields['sunset_expires_at'], '90000');
+      expect(decoded.fields['sunset_review_by'], '60000');
+      expect(decoded.summary, contains('时间戳 90000 日落失效'));
       expect(decoded.fields['actor_cid_number'], nrcActorCid);
       expect(decoded.fields['proposer_role_code'], proposerRoleCode);
       expect(decoded.fields['executive_cid_number'], executiveCid);
       expect(decoded.fields['legislature_cid_number'], legislatureCid);
     });
 
+    test('rejects propose_amend_law with non-canonical sunset tag', () {
+      final callData = [
+        25, 1,
+        ...u64Le(42),
+        ...compactVec(nrcActorCid),
+        ...compactVec(proposerRoleCode),
+        ...compactVec(executiveCid),
+        0x00,
+        0,
+        ...compactVec('修订版'),
+        0x00,
+        ...minimalChapters(),
+        ...u64Le(7777),
+        0x02, 0x00, // 非法 Option 判别值
+      ];
+      expect(PayloadDecoder.decode(hexOf(withSigningTail(callData))), isNull);
+    });
+
     test('decodes propose_repeal_law (25.2)', () {
       final callData = [
         25, 2,